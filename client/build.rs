@@ -1,8 +1,95 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// A session module from `smart_contracts/contracts/client` embedded in the client.
+struct SessionModule {
+    root: &'static str,
+    wasm_filename: &'static str,
+}
+
+const SESSION_MODULES: &[SessionModule] = &[
+    SessionModule {
+        root: "../smart_contracts/contracts/client/put-named-key",
+        wasm_filename: "put_named_key.wasm",
+    },
+    SessionModule {
+        root: "../smart_contracts/contracts/client/remove-named-key",
+        wasm_filename: "remove_named_key.wasm",
+    },
+];
+
+const TARGET_DIR_FOR_WASM: &str = "built-contracts";
+const ORIGINAL_WASM_DIR: &str = "wasm32-unknown-unknown/release";
+const PREBUILT_WASM_DIR: &str = "wasm";
+
+/// Builds the session module and copies the resulting Wasm file to `out_dir`, from where it is
+/// embedded by `include_bytes!`.
+fn build_session_module(module: &SessionModule, out_dir: &Path) {
+    // Watch contract source files for changes.
+    println!("cargo:rerun-if-changed={}/Cargo.toml", module.root);
+    println!("cargo:rerun-if-changed={}/src/main.rs", module.root);
+
+    // Full path to the cargo binary.
+    let cargo = env::var("CARGO").expect("env var 'CARGO' should be set");
+
+    // We can't build the contract right into the normal target dir since cargo has a lock on
+    // this while building the client.  Instead, we'll build to '$OUT_DIR/built-contracts' and
+    // then copy the resulting Wasm file from there to '$OUT_DIR'.
+    let target_dir = out_dir.join(TARGET_DIR_FOR_WASM);
+    let output = Command::new(cargo)
+        .current_dir(module.root)
+        .args(&[
+            "build",
+            "--release",
+            "--target=wasm32-unknown-unknown",
+            &format!(
+                "--target-dir={}",
+                target_dir.to_str().expect("Expected valid unicode")
+            ),
+        ])
+        .output()
+        .unwrap_or_else(|_| panic!("Expected to build {}", module.wasm_filename));
+    assert!(
+        output.status.success(),
+        "Failed to build {}:\n{:?}",
+        module.wasm_filename,
+        output
+    );
+
+    let original_wasm_file = target_dir
+        .join(ORIGINAL_WASM_DIR)
+        .join(module.wasm_filename);
+    fs::copy(original_wasm_file, out_dir.join(module.wasm_filename)).unwrap();
+}
+
+/// Copies the prebuilt Wasm file of the session module from the client's 'wasm' dir to `out_dir`.
+fn copy_prebuilt_session_module(module: &SessionModule, out_dir: &Path) {
+    let root_dir = PathBuf::from(
+        env::var("CARGO_MANIFEST_DIR").expect("env var 'CARGO_MANIFEST_DIR' should be set"),
+    );
+    let wasm_file = root_dir.join(PREBUILT_WASM_DIR).join(module.wasm_filename);
+    assert!(wasm_file.is_file(), "{} must exist", wasm_file.display());
+    fs::copy(wasm_file, out_dir.join(module.wasm_filename)).unwrap();
+}
+
 fn main() {
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("env var 'OUT_DIR' should be set"));
+    for module in SESSION_MODULES {
+        if Path::new(module.root).is_dir() {
+            // We're building from within casper-node repo - build the session module.
+            build_session_module(module, &out_dir);
+        } else {
+            // We're outside the casper-node repo - the compiled session module should exist
+            // locally.
+            copy_prebuilt_session_module(module, &out_dir);
+        }
+    }
+
     #[cfg(feature = "ffi")]
     {
-        use std::env;
-
         use cbindgen::{Builder, Language};
 
         let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
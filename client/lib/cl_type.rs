@@ -1,6 +1,6 @@
 //! Supported `CLType` and `CLValue` parsing and validation.
 
-use std::{result::Result as StdResult, str::FromStr};
+use std::{convert::TryFrom, result::Result as StdResult, str::FromStr};
 
 use casper_node::crypto::asymmetric_key::PublicKey as NodePublicKey;
 use casper_types::{
@@ -8,45 +8,85 @@ use casper_types::{
     U256, U512,
 };
 
-use crate::error::{Error, Result};
+use crate::{
+    cl_value,
+    error::{Error, Result},
+};
 
 /// Parse a `CLType` from `&str`.
+///
+/// As well as the simple types listed in `supported_cl_types()`, compound types can be given as
+/// `option<T>`, `list<T>`, `map<K, V>`, `tuple<T1>`, `tuple<T1, T2>`, `tuple<T1, T2, T3>`,
+/// `result<T, E>` and `byte_array<N>`, nested to any depth.
 pub(crate) fn parse(strval: &str) -> StdResult<CLType, ()> {
-    let supported_types = supported_cl_types();
-    let cl_type = match strval.to_lowercase() {
-        t if t == supported_types[0].0 => supported_types[0].1.clone(),
-        t if t == supported_types[1].0 => supported_types[1].1.clone(),
-        t if t == supported_types[2].0 => supported_types[2].1.clone(),
-        t if t == supported_types[3].0 => supported_types[3].1.clone(),
-        t if t == supported_types[4].0 => supported_types[4].1.clone(),
-        t if t == supported_types[5].0 => supported_types[5].1.clone(),
-        t if t == supported_types[6].0 => supported_types[6].1.clone(),
-        t if t == supported_types[7].0 => supported_types[7].1.clone(),
-        t if t == supported_types[8].0 => supported_types[8].1.clone(),
-        t if t == supported_types[9].0 => supported_types[9].1.clone(),
-        t if t == supported_types[10].0 => supported_types[10].1.clone(),
-        t if t == supported_types[11].0 => supported_types[11].1.clone(),
-        t if t == supported_types[12].0 => supported_types[12].1.clone(),
-        t if t == supported_types[13].0 => supported_types[13].1.clone(),
-        t if t == supported_types[14].0 => supported_types[14].1.clone(),
-        t if t == supported_types[15].0 => supported_types[15].1.clone(),
-        t if t == supported_types[16].0 => supported_types[16].1.clone(),
-        t if t == supported_types[17].0 => supported_types[17].1.clone(),
-        t if t == supported_types[18].0 => supported_types[18].1.clone(),
-        t if t == supported_types[19].0 => supported_types[19].1.clone(),
-        t if t == supported_types[20].0 => supported_types[20].1.clone(),
-        t if t == supported_types[21].0 => supported_types[21].1.clone(),
-        t if t == supported_types[22].0 => supported_types[22].1.clone(),
-        t if t == supported_types[23].0 => supported_types[23].1.clone(),
-        t if t == supported_types[24].0 => supported_types[24].1.clone(),
-        t if t == supported_types[25].0 => supported_types[25].1.clone(),
-        t if t == supported_types[26].0 => supported_types[26].1.clone(),
-        t if t == supported_types[27].0 => supported_types[27].1.clone(),
-        t if t == supported_types[28].0 => supported_types[28].1.clone(),
-        t if t == supported_types[29].0 => supported_types[29].1.clone(),
+    let normalized: String = strval
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase();
+    match parse_prefix(&normalized)? {
+        (cl_type, "") => Ok(cl_type),
+        _ => Err(()),
+    }
+}
+
+/// Parses the `CLType` at the start of `input`, returning it along with the unparsed remainder.
+fn parse_prefix(input: &str) -> StdResult<(CLType, &str), ()> {
+    let name_end = input
+        .find(|c: char| c == '<' || c == ',' || c == '>')
+        .unwrap_or_else(|| input.len());
+    let (name, remainder) = input.split_at(name_end);
+    if !remainder.starts_with('<') {
+        let cl_type = supported_cl_types()
+            .into_iter()
+            .find(|(supported_name, _)| *supported_name == name)
+            .map(|(_, cl_type)| cl_type)
+            .ok_or(())?;
+        return Ok((cl_type, remainder));
+    }
+
+    let mut remainder = &remainder[1..];
+    if name == "byte_array" {
+        let length_end = remainder.find('>').ok_or(())?;
+        let length = remainder[..length_end].parse::<u32>().map_err(|_| ())?;
+        let cl_type = CLType::FixedList(Box::new(CLType::U8), length);
+        return Ok((cl_type, &remainder[length_end + 1..]));
+    }
+
+    let mut params = vec![];
+    loop {
+        let (param, rest) = parse_prefix(remainder)?;
+        params.push(Box::new(param));
+        if let Some(rest) = rest.strip_prefix(',') {
+            remainder = rest;
+        } else if let Some(rest) = rest.strip_prefix('>') {
+            remainder = rest;
+            break;
+        } else {
+            return Err(());
+        }
+    }
+
+    let count = params.len();
+    let mut params = params.into_iter();
+    let mut next = || params.next().ok_or(());
+    let cl_type = match (name, count) {
+        ("option", 1) => CLType::Option(next()?),
+        ("list", 1) => CLType::List(next()?),
+        ("map", 2) => CLType::Map {
+            key: next()?,
+            value: next()?,
+        },
+        ("result", 2) => CLType::Result {
+            ok: next()?,
+            err: next()?,
+        },
+        ("tuple", 1) => CLType::Tuple1([next()?]),
+        ("tuple", 2) => CLType::Tuple2([next()?, next()?]),
+        ("tuple", 3) => CLType::Tuple3([next()?, next()?, next()?]),
         _ => return Err(()),
     };
-    Ok(cl_type)
+    Ok((cl_type, remainder))
 }
 
 pub(crate) fn supported_cl_types() -> Vec<(&'static str, CLType)> {
@@ -90,6 +130,16 @@ pub(crate) fn supported_cl_types() -> Vec<(&'static str, CLType)> {
     ]
 }
 
+/// The forms of the compound `CLType`s, whose parameters can be any supported type.
+const COMPOUND_CL_TYPES: [&str; 6] = [
+    "option<T>",
+    "list<T>",
+    "map<K, V>",
+    "tuple<T1[, T2[, T3]]>",
+    "result<T, E>",
+    "byte_array<N>",
+];
+
 /// Functions for use in help commands.
 pub mod help {
     use std::convert::TryFrom;
@@ -102,12 +152,11 @@ pub mod help {
     pub fn supported_cl_type_list() -> String {
         let mut msg = String::new();
         let supported_types = super::supported_cl_types();
-        for (index, item) in supported_types.iter().map(|(name, _)| name).enumerate() {
+        for item in supported_types.iter().map(|(name, _)| name) {
             msg.push_str(item);
-            if index < supported_types.len() - 1 {
-                msg.push_str(", ")
-            }
+            msg.push_str(", ")
         }
+        msg.push_str(&super::COMPOUND_CL_TYPES.join(", "));
         msg
     }
 
@@ -148,6 +197,25 @@ Prefix the type with "opt_" and use the term "null" without quotes to specify a
 "name_08:opt_string='a value'"  # Some("a value".to_string())
 "name_09:opt_string='null'"     # Some("null".to_string())
 "name_10:opt_string=null"       # None
+
+Compound types can be nested to any depth. Their values are written using [...] for lists,
+(...) for tuples, {{KEY: VALUE, ...}} for maps, ok(...) or err(...) for results and hex for byte
+arrays. Strings within compound values must be double-quoted, escaping any '"' or '\' with a '\':
+"name_01:list<u64>='[1, 2, 3]'"
+"name_02:option<list<string>>='[\"a\", \"b\"]'"  # Some(vec!["a", "b"])
+"name_03:option<list<string>>=null"              # None
+"name_04:list<option<u8>>='[1, null, 3]'"
+"name_05:map<string, u512>='{{\"alice\": 10, \"bob\": 20}}'"
+"name_06:tuple<u8, string, bool>='(1, \"x\", true)'"
+"name_07:result<u64, string>='ok(5)'"
+"name_08:result<u64, string>='err(\"failed\")'"
+"name_09:byte_array<4>='01020304'"
+"name_10:list<uref>='[{}]'"
+
+Alternatively, args can be given as a JSON array of objects with "name", "type" and "value"
+fields, using JSON arrays for lists and tuples, objects for maps and {{"ok": ...}} or
+{{"err": ...}} for results:
+[{{"name": "name_01", "type": "map<string, list<u8>>", "value": {{"a": [1, 2], "b": []}}}}]
 "#,
             Key::Account(AccountHash::new(array)).to_formatted_string(),
             Key::Hash(array).to_formatted_string(),
@@ -159,6 +227,7 @@ Prefix the type with "opt_" and use the term "null" without quotes to specify a
             )
             .unwrap()
             .to_hex(),
+            URef::new(array, AccessRights::READ_ADD_WRITE).to_formatted_string(),
         )
     }
 }
@@ -192,6 +261,28 @@ where
 
 /// Returns a value built from a single arg which has been split into its constituent parts.
 pub fn parts_to_cl_value(cl_type: CLType, value: &str) -> Result<CLValue> {
+    if !supported_cl_types()
+        .iter()
+        .any(|(_, supported_type)| *supported_type == cl_type)
+    {
+        // Compound values are parsed from the text within the single quotes, or `null` for `None`.
+        let text = if value == "null" {
+            value
+        } else {
+            value
+                .strip_prefix('\'')
+                .and_then(|text| text.strip_suffix('\''))
+                .ok_or_else(|| {
+                    Error::InvalidCLValue(format!(
+                        "value in arg should be surrounded by single quotes unless it's a null \
+                        optional value (value passed: {})",
+                        value
+                    ))
+                })?
+        };
+        return cl_value::from_text(cl_type, text);
+    }
+
     let (cl_type_to_parse, optional_status, trimmed_value) = match cl_type {
         CLType::Option(inner_type) => {
             if value == "null" {
@@ -339,11 +430,18 @@ pub fn parts_to_cl_value(cl_type: CLType, value: &str) -> Result<CLValue> {
         CLType::FixedList(ty, 32) => match *ty {
             CLType::U8 => {
                 let parse = || {
-                    AccountHash::from_formatted_str(trimmed_value).map_err(|error| {
-                        Error::InvalidCLValue(format!(
-                            "can't parse {} as AccountHash: {:?}",
-                            trimmed_value, error
-                        ))
+                    AccountHash::from_formatted_str(trimmed_value).or_else(|error| {
+                        // A `byte_array<32>` can also be given in hex.
+                        hex::decode(trimmed_value)
+                            .ok()
+                            .and_then(|bytes| <[u8; 32]>::try_from(bytes.as_slice()).ok())
+                            .map(AccountHash::new)
+                            .ok_or_else(|| {
+                                Error::InvalidCLValue(format!(
+                                    "can't parse {} as AccountHash: {:?}",
+                                    trimmed_value, error
+                                ))
+                            })
                     })
                 };
                 parse_to_cl_value(optional_status, parse)
@@ -0,0 +1,749 @@
+//! Parsing of `CLValue`s of any `CLType` from text or from JSON, and decoding of `CLValue`s into
+//! type-tagged JSON.
+//!
+//! Text values use the following syntax:
+//!
+//! ```text
+//! value  := scalar | string | 'null' | list | tuple | map | result
+//! scalar := a bare word such as 'true', '-5', '1000', 'uref-<hex>-007' or a hex-encoded byte array
+//! string := a double-quoted string, in which '"' and '\' are escaped by a preceding '\'
+//! list   := '[' [value {',' value}] ']'
+//! tuple  := '(' [value {',' value}] ')'
+//! map    := '{' [value ':' value {',' value ':' value}] '}'
+//! result := 'ok(' value ')' | 'err(' value ')'
+//! ```
+//!
+//! JSON values have the same structure, with arrays in place of lists and tuples, objects (or
+//! arrays of key-value pairs) in place of maps, and `{"ok": value}` or `{"err": value}` in place of
+//! results.
+//!
+//! The value is interpreted according to the expected `CLType`: `null` is `None` for an option and
+//! any other value is `Some`, the unit value is `()`, and a byte array is given in hex, or for a
+//! 32-byte array, optionally as a formatted account hash.
+//!
+//! Decoded values are JSON objects of the form `{"type": TYPE, "value": VALUE}`, where `TYPE` names
+//! the kind of `CLType` (e.g. `"U512"`, `"List"` or `"Map"`) and the `VALUE` of a compound type is
+//! built from decoded values:
+//!
+//! * an option's value is `null` or the decoded inner value
+//! * a list's, fixed list's or tuple's value is an array of decoded values
+//! * a map's value is an array of `{"key": KEY, "value": VALUE}` objects
+//! * a result's value is `{"ok": VALUE}` or `{"err": VALUE}`
+//! * a byte array's value is its hex encoding
+
+use std::{collections::BTreeSet, convert::TryFrom, str::FromStr};
+
+use serde::Serialize;
+use serde_json::{json, Value as JsonValue};
+
+use casper_node::crypto::asymmetric_key::PublicKey as NodePublicKey;
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLValue, Key, PublicKey, URef, U128, U256, U512,
+};
+
+use crate::error::{Error, Result};
+
+/// A value parsed from text or JSON, not yet interpreted according to a `CLType`.
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    Null,
+    Scalar(String),
+    List(Vec<Value>),
+    Tuple(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+    Ok(Box<Value>),
+    Err(Box<Value>),
+}
+
+impl From<&JsonValue> for Value {
+    fn from(json: &JsonValue) -> Self {
+        match json {
+            JsonValue::Null => Value::Null,
+            JsonValue::Bool(value) => Value::Scalar(value.to_string()),
+            JsonValue::Number(value) => Value::Scalar(value.to_string()),
+            JsonValue::String(value) => Value::Scalar(value.clone()),
+            JsonValue::Array(items) => Value::List(items.iter().map(Value::from).collect()),
+            JsonValue::Object(entries) => Value::Map(
+                entries
+                    .iter()
+                    .map(|(key, value)| (Value::Scalar(key.clone()), Value::from(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Parses `text` as a value of the given type.
+pub(crate) fn from_text(cl_type: CLType, text: &str) -> Result<CLValue> {
+    let value = TextParser::new(text).parse()?;
+    to_cl_value(cl_type, &value)
+}
+
+/// Interprets `json` as a value of the given type.
+pub(crate) fn from_json(cl_type: CLType, json: &JsonValue) -> Result<CLValue> {
+    to_cl_value(cl_type, &Value::from(json))
+}
+
+fn to_cl_value(cl_type: CLType, value: &Value) -> Result<CLValue> {
+    let mut bytes = vec![];
+    encode(&cl_type, value, &mut bytes)?;
+    Ok(CLValue::from_components(cl_type, bytes))
+}
+
+/// A recursive-descent parser of the text syntax described in the module docs.
+struct TextParser<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+impl<'a> TextParser<'a> {
+    fn new(text: &'a str) -> Self {
+        TextParser { text, position: 0 }
+    }
+
+    fn parse(mut self) -> Result<Value> {
+        let value = self.value()?;
+        if self.peek().is_some() {
+            return Err(self.error("unexpected trailing characters"));
+        }
+        Ok(value)
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.text[self.position..]
+    }
+
+    /// Skips any whitespace and returns the next character, without consuming it.
+    fn peek(&mut self) -> Option<char> {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+        self.rest().chars().next()
+    }
+
+    fn consume(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.position += expected.len_utf8();
+            return true;
+        }
+        false
+    }
+
+    fn expect(&mut self, expected: char) -> Result<()> {
+        if self.consume(expected) {
+            return Ok(());
+        }
+        Err(self.error(&format!("expected '{}'", expected)))
+    }
+
+    fn error(&self, message: &str) -> Error {
+        Error::InvalidCLValue(format!(
+            "{} at position {} of '{}'",
+            message, self.position, self.text
+        ))
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        if self.consume('[') {
+            return Ok(Value::List(self.sequence(']')?));
+        }
+        if self.consume('(') {
+            return Ok(Value::Tuple(self.sequence(')')?));
+        }
+        if self.consume('{') {
+            return self.map();
+        }
+        if self.consume('"') {
+            return self.string().map(Value::Scalar);
+        }
+
+        let word = self.word();
+        match word {
+            "" => Err(self.error("expected a value")),
+            "null" => Ok(Value::Null),
+            "ok" | "err" if self.consume('(') => {
+                let inner = Box::new(self.value()?);
+                self.expect(')')?;
+                if word == "ok" {
+                    Ok(Value::Ok(inner))
+                } else {
+                    Ok(Value::Err(inner))
+                }
+            }
+            _ => Ok(Value::Scalar(word.to_string())),
+        }
+    }
+
+    /// Parses comma-separated values up to and including the closing delimiter.
+    fn sequence(&mut self, close: char) -> Result<Vec<Value>> {
+        let mut values = vec![];
+        if self.consume(close) {
+            return Ok(values);
+        }
+        loop {
+            values.push(self.value()?);
+            if !self.consume(',') {
+                self.expect(close)?;
+                return Ok(values);
+            }
+        }
+    }
+
+    /// Parses comma-separated key-value pairs up to and including the closing brace.
+    fn map(&mut self) -> Result<Value> {
+        let mut entries = vec![];
+        if self.consume('}') {
+            return Ok(Value::Map(entries));
+        }
+        loop {
+            let key = self.value()?;
+            self.expect(':')?;
+            entries.push((key, self.value()?));
+            if !self.consume(',') {
+                self.expect('}')?;
+                return Ok(Value::Map(entries));
+            }
+        }
+    }
+
+    fn word(&mut self) -> &'a str {
+        self.peek();
+        let rest = self.rest();
+        let length = rest
+            .find(|c: char| !(c.is_alphanumeric() || "-_+.".contains(c)))
+            .unwrap_or_else(|| rest.len());
+        self.position += length;
+        &rest[..length]
+    }
+
+    /// Parses the remainder of a string whose opening quote has been consumed.
+    fn string(&mut self) -> Result<String> {
+        let mut string = String::new();
+        let mut chars = self.rest().char_indices();
+        loop {
+            match chars.next() {
+                Some((index, '"')) => {
+                    self.position += index + 1;
+                    return Ok(string);
+                }
+                Some((_, '\\')) => match chars.next() {
+                    Some((_, escaped)) if escaped == '"' || escaped == '\\' => string.push(escaped),
+                    _ => return Err(self.error("invalid escape sequence in string")),
+                },
+                Some((_, c)) => string.push(c),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+}
+
+fn mismatch(cl_type: &CLType, value: &Value) -> Error {
+    Error::InvalidCLValue(format!("can't parse {:?} as {:?}", value, cl_type))
+}
+
+fn append<T: ToBytes>(value: &T, bytes: &mut Vec<u8>) -> Result<()> {
+    bytes.extend(value.to_bytes()?);
+    Ok(())
+}
+
+fn append_length(length: usize, bytes: &mut Vec<u8>) -> Result<()> {
+    let length = u32::try_from(length)
+        .map_err(|_| Error::InvalidCLValue(format!("too many elements: {}", length)))?;
+    append(&length, bytes)
+}
+
+/// Serializes `value` as a value of type `cl_type`, appending the result to `bytes`.
+fn encode(cl_type: &CLType, value: &Value, bytes: &mut Vec<u8>) -> Result<()> {
+    match (cl_type, value) {
+        (CLType::Option(_), Value::Null) => bytes.push(0),
+        (CLType::Option(inner_type), _) => {
+            bytes.push(1);
+            encode(inner_type, value, bytes)?;
+        }
+        (CLType::List(item_type), Value::List(items)) => {
+            append_length(items.len(), bytes)?;
+            for item in items {
+                encode(item_type, item, bytes)?;
+            }
+        }
+        (CLType::FixedList(item_type, length), _) => {
+            encode_fixed_list(item_type, *length, value, bytes)?
+        }
+        (
+            CLType::Map {
+                key,
+                value: value_type,
+            },
+            _,
+        ) => {
+            let entries = map_entries(value).ok_or_else(|| mismatch(cl_type, value))?;
+            append_length(entries.len(), bytes)?;
+            let mut keys = BTreeSet::new();
+            for (entry_key, entry_value) in entries {
+                let mut key_bytes = vec![];
+                encode(key, entry_key, &mut key_bytes)?;
+                if !keys.insert(key_bytes.clone()) {
+                    return Err(Error::InvalidCLValue(format!(
+                        "duplicate map key {:?}",
+                        entry_key
+                    )));
+                }
+                bytes.extend(key_bytes);
+                encode(value_type, entry_value, bytes)?;
+            }
+        }
+        (CLType::Result { ok, err }, _) => match result_variant(value) {
+            Some((true, inner)) => {
+                bytes.push(1);
+                encode(ok, inner, bytes)?;
+            }
+            Some((false, inner)) => {
+                bytes.push(0);
+                encode(err, inner, bytes)?;
+            }
+            None => return Err(mismatch(cl_type, value)),
+        },
+        (CLType::Tuple1(types), _) => encode_tuple(&types[..], value, bytes)?,
+        (CLType::Tuple2(types), _) => encode_tuple(&types[..], value, bytes)?,
+        (CLType::Tuple3(types), _) => encode_tuple(&types[..], value, bytes)?,
+        (CLType::Unit, Value::Tuple(items)) | (CLType::Unit, Value::List(items))
+            if items.is_empty() => {}
+        (CLType::Unit, Value::Scalar(scalar)) if scalar.is_empty() => {}
+        (CLType::Any, _) => {
+            return Err(Error::InvalidCLValue(
+                "values of type Any are not supported".to_string(),
+            ))
+        }
+        (_, Value::Scalar(scalar)) => encode_scalar(cl_type, scalar, bytes)?,
+        _ => return Err(mismatch(cl_type, value)),
+    }
+    Ok(())
+}
+
+fn encode_scalar(cl_type: &CLType, scalar: &str, bytes: &mut Vec<u8>) -> Result<()> {
+    let invalid = |error: String| {
+        Error::InvalidCLValue(format!(
+            "can't parse {} as {:?}: {}",
+            scalar, cl_type, error
+        ))
+    };
+    match cl_type {
+        CLType::Bool => match scalar.to_lowercase().as_str() {
+            "true" | "t" => append(&true, bytes),
+            "false" | "f" => append(&false, bytes),
+            _ => Err(invalid("should be 'true' or 'false'".to_string())),
+        },
+        CLType::I32 => append(
+            &i32::from_str(scalar).map_err(|error| invalid(error.to_string()))?,
+            bytes,
+        ),
+        CLType::I64 => append(
+            &i64::from_str(scalar).map_err(|error| invalid(error.to_string()))?,
+            bytes,
+        ),
+        CLType::U8 => append(
+            &u8::from_str(scalar).map_err(|error| invalid(error.to_string()))?,
+            bytes,
+        ),
+        CLType::U32 => append(
+            &u32::from_str(scalar).map_err(|error| invalid(error.to_string()))?,
+            bytes,
+        ),
+        CLType::U64 => append(
+            &u64::from_str(scalar).map_err(|error| invalid(error.to_string()))?,
+            bytes,
+        ),
+        CLType::U128 => append(
+            &U128::from_dec_str(scalar).map_err(|error| invalid(format!("{:?}", error)))?,
+            bytes,
+        ),
+        CLType::U256 => append(
+            &U256::from_dec_str(scalar).map_err(|error| invalid(format!("{:?}", error)))?,
+            bytes,
+        ),
+        CLType::U512 => append(
+            &U512::from_dec_str(scalar).map_err(|error| invalid(format!("{:?}", error)))?,
+            bytes,
+        ),
+        CLType::String => append(&scalar.to_string(), bytes),
+        CLType::Key => append(
+            &Key::from_formatted_str(scalar).map_err(|error| invalid(format!("{:?}", error)))?,
+            bytes,
+        ),
+        CLType::URef => append(
+            &URef::from_formatted_str(scalar).map_err(|error| invalid(format!("{:?}", error)))?,
+            bytes,
+        ),
+        CLType::PublicKey => {
+            let public_key =
+                NodePublicKey::from_hex(scalar).map_err(|error| invalid(error.to_string()))?;
+            append(&PublicKey::from(public_key), bytes)
+        }
+        _ => Err(mismatch(cl_type, &Value::Scalar(scalar.to_string()))),
+    }
+}
+
+fn encode_fixed_list(
+    item_type: &CLType,
+    length: u32,
+    value: &Value,
+    bytes: &mut Vec<u8>,
+) -> Result<()> {
+    let length = length as usize;
+    if let (CLType::U8, Value::Scalar(scalar)) = (item_type, value) {
+        if length == 32 {
+            if let Ok(account_hash) = AccountHash::from_formatted_str(scalar) {
+                bytes.extend_from_slice(account_hash.as_bytes());
+                return Ok(());
+            }
+        }
+        let decoded = hex::decode(scalar).map_err(|error| {
+            Error::InvalidCLValue(format!("can't parse {} as a byte array: {}", scalar, error))
+        })?;
+        if decoded.len() != length {
+            return Err(Error::InvalidCLValue(format!(
+                "expected {} bytes, got {} in {}",
+                length,
+                decoded.len(),
+                scalar
+            )));
+        }
+        bytes.extend(decoded);
+        return Ok(());
+    }
+
+    let cl_type = CLType::FixedList(Box::new(item_type.clone()), length as u32);
+    let items = match value {
+        Value::List(items) | Value::Tuple(items) if items.len() == length => items,
+        _ => return Err(mismatch(&cl_type, value)),
+    };
+    for item in items {
+        encode(item_type, item, bytes)?;
+    }
+    Ok(())
+}
+
+fn encode_tuple(types: &[Box<CLType>], value: &Value, bytes: &mut Vec<u8>) -> Result<()> {
+    let items = match value {
+        Value::Tuple(items) | Value::List(items) if items.len() == types.len() => items,
+        _ => {
+            return Err(Error::InvalidCLValue(format!(
+                "can't parse {:?} as a tuple of {} elements",
+                value,
+                types.len()
+            )))
+        }
+    };
+    for (item_type, item) in types.iter().zip(items) {
+        encode(item_type, item, bytes)?;
+    }
+    Ok(())
+}
+
+/// Returns the entries of a map, given either as a map or as a list of key-value pairs.
+fn map_entries(value: &Value) -> Option<Vec<(&Value, &Value)>> {
+    match value {
+        Value::Map(entries) => Some(entries.iter().map(|(key, value)| (key, value)).collect()),
+        Value::List(pairs) => pairs
+            .iter()
+            .map(|pair| match pair {
+                Value::List(items) | Value::Tuple(items) if items.len() == 2 => {
+                    Some((&items[0], &items[1]))
+                }
+                _ => None,
+            })
+            .collect(),
+        _ => None,
+    }
+}
+
+/// Returns whether a result is `Ok`, along with its inner value.
+fn result_variant(value: &Value) -> Option<(bool, &Value)> {
+    match value {
+        Value::Ok(inner) => Some((true, inner.as_ref())),
+        Value::Err(inner) => Some((false, inner.as_ref())),
+        Value::Map(entries) if entries.len() == 1 => match &entries[0] {
+            (Value::Scalar(variant), inner) if variant == "ok" => Some((true, inner)),
+            (Value::Scalar(variant), inner) if variant == "err" => Some((false, inner)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Adds a `decoded` field to every `CLValue` within `json`, holding the value as type-tagged JSON.
+///
+/// `CLValue`s are recognized as JSON objects with `cl_type` and `serialized_bytes` fields.  Any
+/// which fail to decode are left unchanged.
+pub fn decode_cl_values(json: &mut JsonValue) {
+    match json {
+        JsonValue::Object(fields) => {
+            if fields.contains_key("cl_type") && fields.contains_key("serialized_bytes") {
+                let maybe_decoded =
+                    serde_json::from_value::<CLValue>(JsonValue::Object(fields.clone()))
+                        .ok()
+                        .and_then(|cl_value| decode_cl_value(&cl_value).ok());
+                if let Some(decoded) = maybe_decoded {
+                    fields.insert("decoded".to_string(), decoded);
+                }
+                return;
+            }
+            fields.values_mut().for_each(decode_cl_values);
+        }
+        JsonValue::Array(items) => items.iter_mut().for_each(decode_cl_values),
+        _ => (),
+    }
+}
+
+/// Decodes a `CLValue` into type-tagged JSON.
+pub fn decode_cl_value(cl_value: &CLValue) -> Result<JsonValue> {
+    let (decoded, remainder) = decode(cl_value.cl_type(), cl_value.inner_bytes())?;
+    if !remainder.is_empty() {
+        return Err(bytesrepr::Error::LeftOverBytes.into());
+    }
+    Ok(decoded)
+}
+
+fn tagged(tag: &str, value: JsonValue) -> JsonValue {
+    json!({ "type": tag, "value": value })
+}
+
+fn decode_as<'a, T: FromBytes + Serialize>(
+    tag: &str,
+    bytes: &'a [u8],
+) -> Result<(JsonValue, &'a [u8])> {
+    let (value, remainder) = T::from_bytes(bytes)?;
+    Ok((tagged(tag, json!(value)), remainder))
+}
+
+fn decode_sequence<'a>(
+    types: impl IntoIterator<Item = &'a CLType>,
+    mut bytes: &'a [u8],
+) -> Result<(Vec<JsonValue>, &'a [u8])> {
+    let mut values = vec![];
+    for cl_type in types {
+        let (value, remainder) = decode(cl_type, bytes)?;
+        values.push(value);
+        bytes = remainder;
+    }
+    Ok((values, bytes))
+}
+
+/// Decodes the serialized value of type `cl_type` at the start of `bytes`, returning it along with
+/// the remaining bytes.
+fn decode<'a>(cl_type: &'a CLType, bytes: &'a [u8]) -> Result<(JsonValue, &'a [u8])> {
+    match cl_type {
+        CLType::Bool => decode_as::<bool>("Bool", bytes),
+        CLType::I32 => decode_as::<i32>("I32", bytes),
+        CLType::I64 => decode_as::<i64>("I64", bytes),
+        CLType::U8 => decode_as::<u8>("U8", bytes),
+        CLType::U32 => decode_as::<u32>("U32", bytes),
+        CLType::U64 => decode_as::<u64>("U64", bytes),
+        CLType::U128 => decode_as::<U128>("U128", bytes),
+        CLType::U256 => decode_as::<U256>("U256", bytes),
+        CLType::U512 => decode_as::<U512>("U512", bytes),
+        CLType::Unit => Ok((tagged("Unit", JsonValue::Null), bytes)),
+        CLType::String => decode_as::<String>("String", bytes),
+        CLType::Key => {
+            let (key, remainder) = Key::from_bytes(bytes)?;
+            Ok((tagged("Key", json!(key.to_formatted_string())), remainder))
+        }
+        CLType::URef => {
+            let (uref, remainder) = URef::from_bytes(bytes)?;
+            Ok((tagged("URef", json!(uref.to_formatted_string())), remainder))
+        }
+        CLType::PublicKey => {
+            let (public_key, remainder) = PublicKey::from_bytes(bytes)?;
+            Ok((tagged("PublicKey", json!(public_key.to_hex())), remainder))
+        }
+        CLType::Option(inner_type) => match u8::from_bytes(bytes)? {
+            (0, remainder) => Ok((tagged("Option", JsonValue::Null), remainder)),
+            (1, remainder) => {
+                let (value, remainder) = decode(inner_type, remainder)?;
+                Ok((tagged("Option", value), remainder))
+            }
+            _ => Err(bytesrepr::Error::Formatting.into()),
+        },
+        CLType::List(item_type) => {
+            let (count, remainder) = u32::from_bytes(bytes)?;
+            let (items, remainder) =
+                decode_sequence((0..count).map(|_| item_type.as_ref()), remainder)?;
+            Ok((tagged("List", JsonValue::Array(items)), remainder))
+        }
+        CLType::FixedList(item_type, length) if **item_type == CLType::U8 => {
+            let length = *length as usize;
+            if bytes.len() < length {
+                return Err(bytesrepr::Error::EarlyEndOfStream.into());
+            }
+            let (array, remainder) = bytes.split_at(length);
+            Ok((tagged("ByteArray", json!(hex::encode(array))), remainder))
+        }
+        CLType::FixedList(item_type, length) => {
+            let (items, remainder) =
+                decode_sequence((0..*length).map(|_| item_type.as_ref()), bytes)?;
+            Ok((tagged("FixedList", JsonValue::Array(items)), remainder))
+        }
+        CLType::Result { ok, err } => match u8::from_bytes(bytes)? {
+            (0, remainder) => {
+                let (value, remainder) = decode(err, remainder)?;
+                Ok((tagged("Result", json!({ "err": value })), remainder))
+            }
+            (1, remainder) => {
+                let (value, remainder) = decode(ok, remainder)?;
+                Ok((tagged("Result", json!({ "ok": value })), remainder))
+            }
+            _ => Err(bytesrepr::Error::Formatting.into()),
+        },
+        CLType::Map { key, value } => {
+            let (count, mut remainder) = u32::from_bytes(bytes)?;
+            let mut entries = vec![];
+            for _ in 0..count {
+                let (decoded_key, rest) = decode(key, remainder)?;
+                let (decoded_value, rest) = decode(value, rest)?;
+                entries.push(json!({ "key": decoded_key, "value": decoded_value }));
+                remainder = rest;
+            }
+            Ok((tagged("Map", JsonValue::Array(entries)), remainder))
+        }
+        CLType::Tuple1(types) => decode_tuple(&types[..], bytes),
+        CLType::Tuple2(types) => decode_tuple(&types[..], bytes),
+        CLType::Tuple3(types) => decode_tuple(&types[..], bytes),
+        CLType::Any => Ok((
+            tagged("Any", json!(hex::encode(bytes))),
+            &bytes[bytes.len()..],
+        )),
+    }
+}
+
+fn decode_tuple<'a>(types: &'a [Box<CLType>], bytes: &'a [u8]) -> Result<(JsonValue, &'a [u8])> {
+    let (items, remainder) = decode_sequence(types.iter().map(|cl_type| cl_type.as_ref()), bytes)?;
+    Ok((tagged("Tuple", JsonValue::Array(items)), remainder))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use casper_types::{AccessRights, CLTyped};
+
+    use super::*;
+    use crate::cl_type;
+
+    fn check<T: CLTyped + ToBytes>(type_str: &str, text: &str, expected: T) {
+        let cl_type = cl_type::parse(type_str).expect("should parse type");
+        let expected = CLValue::from_t(expected).unwrap();
+        assert_eq!(
+            from_text(cl_type, text).expect("should parse value"),
+            expected
+        );
+    }
+
+    #[test]
+    fn should_parse_nested_options_and_lists() {
+        check("list<u64>", "[1, 2, 3]", vec![1u64, 2, 3]);
+        check("list<string>", "[]", Vec::<String>::new());
+        check(
+            "list<option<string>>",
+            r#"["a", null, "c \"quoted\""]"#,
+            vec![
+                Some("a".to_string()),
+                None,
+                Some("c \"quoted\"".to_string()),
+            ],
+        );
+        check("option<list<u8>>", "null", None::<Vec<u8>>);
+        check("option<list<u8>>", "[7]", Some(vec![7u8]));
+    }
+
+    #[test]
+    fn should_parse_maps_tuples_and_results() {
+        let mut map = BTreeMap::new();
+        map.insert("one".to_string(), 1u32);
+        map.insert("two".to_string(), 2u32);
+        check("map<string, u32>", r#"{one: 1, "two": 2}"#, map);
+        check(
+            "tuple<u8, string, bool>",
+            r#"(1, "x", true)"#,
+            (1u8, "x".to_string(), true),
+        );
+        check("result<u64, string>", "ok(5)", Ok::<u64, String>(5));
+        check(
+            "result<u64, string>",
+            r#"err("failed")"#,
+            Err::<u64, String>("failed".to_string()),
+        );
+    }
+
+    #[test]
+    fn should_parse_byte_arrays_and_urefs() {
+        check("byte_array<4>", "01020304", [1u8, 2, 3, 4]);
+        let account_hash = AccountHash::new([3; 32]);
+        check(
+            "byte_array<32>",
+            &account_hash.to_formatted_string(),
+            account_hash,
+        );
+        let uref = URef::new([2; 32], AccessRights::READ_ADD_WRITE);
+        check(
+            "list<uref>",
+            &format!("[{}]", uref.to_formatted_string()),
+            vec![uref],
+        );
+    }
+
+    #[test]
+    fn should_parse_json() {
+        let cl_type = cl_type::parse("map<u64, result<string, u8>>").unwrap();
+        let json = serde_json::json!({"1": {"ok": "yes"}, "2": {"err": 3}});
+        let mut expected = BTreeMap::new();
+        expected.insert(1u64, Ok::<String, u8>("yes".to_string()));
+        expected.insert(2u64, Err::<String, u8>(3));
+        assert_eq!(
+            from_json(cl_type, &json).unwrap(),
+            CLValue::from_t(expected).unwrap()
+        );
+    }
+
+    #[test]
+    fn should_decode_nested_values() {
+        let mut map = BTreeMap::new();
+        map.insert("a".to_string(), vec![Some(1u8), None]);
+        let cl_value = CLValue::from_t(map).unwrap();
+        let expected = json!({
+            "type": "Map",
+            "value": [{
+                "key": { "type": "String", "value": "a" },
+                "value": {
+                    "type": "List",
+                    "value": [
+                        { "type": "Option", "value": { "type": "U8", "value": 1 } },
+                        { "type": "Option", "value": null }
+                    ]
+                }
+            }]
+        });
+        assert_eq!(decode_cl_value(&cl_value).unwrap(), expected);
+    }
+
+    #[test]
+    fn should_add_decoded_field_to_cl_values() {
+        let cl_value = CLValue::from_t(Ok::<U512, String>(U512::from(9))).unwrap();
+        let mut response = json!({ "result": { "stored_value": { "CLValue": cl_value } } });
+        decode_cl_values(&mut response);
+        assert_eq!(
+            response["result"]["stored_value"]["CLValue"]["decoded"],
+            json!({ "type": "Result", "value": { "ok": { "type": "U512", "value": "9" } } })
+        );
+    }
+
+    #[test]
+    fn should_fail_on_invalid_values() {
+        let cl_type = cl_type::parse("list<u8>").unwrap();
+        assert!(from_text(cl_type.clone(), "[1, 256]").is_err());
+        assert!(from_text(cl_type.clone(), "[1, 2").is_err());
+        assert!(from_text(cl_type, "[1] 2").is_err());
+        let cl_type = cl_type::parse("map<u8, u8>").unwrap();
+        assert!(from_text(cl_type, "{1: 1, 1: 2}").is_err());
+    }
+}
@@ -0,0 +1,150 @@
+//! A typed, JSON-friendly view of a contract package read from global state.
+
+use std::collections::BTreeMap;
+
+use jsonrpc_lite::JsonRpc;
+use serde::{Deserialize, Serialize};
+
+use casper_node::{rpcs::state::GetItemResult, types::json_compatibility::StoredValue};
+use casper_types::{bytesrepr, ContractPackage};
+
+use crate::error::{Error, Result};
+
+/// A version of the contract held by a contract package.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct ContractVersionInfo {
+    /// The major protocol version with which the contract version is compatible.
+    pub protocol_version_major: u32,
+    /// The version of the contract within that major protocol version.
+    pub contract_version: u32,
+    /// The formatted hash of the contract, e.g. `"hash-0102..."`.
+    pub contract_hash: String,
+    /// Whether the version can be called, i.e. has not been disabled.
+    pub enabled: bool,
+}
+
+/// A contract package, listing its versions and user groups.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct ContractPackageInfo {
+    /// The formatted `URef` required to add or disable versions of the package.
+    pub access_key: String,
+    /// All versions of the contract, enabled and disabled, oldest first.
+    pub versions: Vec<ContractVersionInfo>,
+    /// The version called when a deploy names the package without a version, if any is enabled.
+    pub current_version: Option<u32>,
+    /// The user groups controlling access to entry points, mapped to their formatted `URef`s.
+    pub groups: BTreeMap<String, Vec<String>>,
+}
+
+impl From<&ContractPackage> for ContractPackageInfo {
+    fn from(contract_package: &ContractPackage) -> Self {
+        let versions = contract_package
+            .versions()
+            .iter()
+            .map(|(version_key, contract_hash)| ContractVersionInfo {
+                protocol_version_major: version_key.protocol_version_major(),
+                contract_version: version_key.contract_version(),
+                contract_hash: format!("hash-{}", hex::encode(contract_hash)),
+                enabled: contract_package.is_version_enabled(*version_key),
+            })
+            .collect();
+        let groups = contract_package
+            .groups()
+            .iter()
+            .map(|(group, urefs)| {
+                let urefs = urefs
+                    .iter()
+                    .map(|uref| uref.to_formatted_string())
+                    .collect();
+                (group.value().to_string(), urefs)
+            })
+            .collect();
+        ContractPackageInfo {
+            access_key: contract_package.access_key().to_formatted_string(),
+            versions,
+            current_version: contract_package
+                .current_contract_version()
+                .map(|version_key| version_key.contract_version()),
+            groups,
+        }
+    }
+}
+
+impl ContractPackageInfo {
+    /// Decodes the contract package held in a "state_get_item" response.
+    pub(crate) fn from_get_item_response(response: &JsonRpc) -> Result<Self> {
+        let result = response
+            .get_result()
+            .cloned()
+            .ok_or_else(|| Error::InvalidRpcResponse(response.clone()))?;
+        let get_item_result: GetItemResult = serde_json::from_value(result)?;
+        let hex_bytes = match get_item_result.stored_value {
+            Some(StoredValue::ContractPackage(hex_bytes)) => hex_bytes,
+            Some(_) => {
+                return Err(Error::InvalidArgument(
+                    "contract_package_hash",
+                    "global state holds something other than a contract package under this key"
+                        .to_string(),
+                ))
+            }
+            None => {
+                return Err(Error::InvalidArgument(
+                    "contract_package_hash",
+                    "no contract package under this key".to_string(),
+                ))
+            }
+        };
+        let bytes =
+            hex::decode(&hex_bytes).map_err(|_| Error::InvalidRpcResponse(response.clone()))?;
+        let contract_package: ContractPackage = bytesrepr::deserialize(bytes)
+            .map_err(|_| Error::InvalidRpcResponse(response.clone()))?;
+        Ok(ContractPackageInfo::from(&contract_package))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use casper_types::{
+        contracts::{ContractVersions, DisabledVersions, Groups},
+        AccessRights, ContractVersionKey, Group, URef,
+    };
+
+    use super::*;
+
+    #[test]
+    fn should_list_versions_and_groups() {
+        let mut versions = ContractVersions::new();
+        versions.insert(ContractVersionKey::new(1, 1), [1; 32]);
+        versions.insert(ContractVersionKey::new(1, 2), [2; 32]);
+        let mut disabled_versions = DisabledVersions::new();
+        disabled_versions.insert(ContractVersionKey::new(1, 2));
+        let group_uref = URef::new([4; 32], AccessRights::READ);
+        let mut groups = Groups::new();
+        groups.insert(
+            Group::new("admins"),
+            vec![group_uref].into_iter().collect::<BTreeSet<_>>(),
+        );
+        let contract_package = ContractPackage::new(
+            URef::new([3; 32], AccessRights::READ_ADD_WRITE),
+            versions,
+            disabled_versions,
+            groups,
+        );
+
+        let info = ContractPackageInfo::from(&contract_package);
+        assert_eq!(info.versions.len(), 2);
+        assert!(info.versions[0].enabled);
+        assert!(!info.versions[1].enabled);
+        assert_eq!(
+            info.versions[1].contract_hash,
+            format!("hash-{}", hex::encode([2; 32]))
+        );
+        assert_eq!(info.current_version, Some(1));
+        assert_eq!(
+            info.groups.get("admins"),
+            Some(&vec![group_uref.to_formatted_string()])
+        );
+    }
+}
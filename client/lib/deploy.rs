@@ -9,7 +9,12 @@ use serde::{Deserialize, Serialize};
 use casper_execution_engine::core::engine_state::ExecutableDeployItem;
 use casper_node::{
     crypto::asymmetric_key::SecretKey,
-    rpcs::{account::PutDeploy, chain::GetBlockResult, info::GetDeploy, RpcWithParams},
+    rpcs::{
+        account::{CheckDeploy, PutDeploy},
+        chain::GetBlockResult,
+        info::{EstimateDeployCost, GetDeploy},
+        RpcWithParams,
+    },
     types::{Deploy, DeployHash, TimeDiff, Timestamp},
 };
 
@@ -28,6 +33,14 @@ impl RpcClient for PutDeploy {
     const RPC_METHOD: &'static str = Self::METHOD;
 }
 
+impl RpcClient for CheckDeploy {
+    const RPC_METHOD: &'static str = Self::METHOD;
+}
+
+impl RpcClient for EstimateDeployCost {
+    const RPC_METHOD: &'static str = Self::METHOD;
+}
+
 impl RpcClient for GetDeploy {
     const RPC_METHOD: &'static str = Self::METHOD;
 }
@@ -0,0 +1,482 @@
+//! A fluent, typed builder for `Deploy`s.
+//!
+//! Unlike the string-based functions at the root of this crate, `DeployBuilder` takes runtime args
+//! as Rust values and the session and payment as typed `DeployCode`s.  A deploy can only be built
+//! once both its session and payment have been provided; omitting either is a compile-time error.
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use casper_execution_engine::core::engine_state::ExecutableDeployItem;
+use casper_node::{
+    crypto::asymmetric_key::SecretKey,
+    types::{Deploy, DeployHash, TimeDiff, Timestamp},
+};
+use casper_types::{
+    bytesrepr::ToBytes, CLTyped, CLValue, ContractHash, ContractPackageHash, Key, RuntimeArgs, U512,
+};
+
+use crate::{
+    deploy::{DeployExt, DeployParams},
+    error::{Error, Result},
+    executable_deploy_item_ext::ExecutableDeployItemExt,
+    parsing::STANDARD_PAYMENT_ARG_NAME,
+};
+
+/// The default time to live of a deploy, one hour.
+const DEFAULT_TTL_MILLIS: u64 = 60 * 60 * 1_000;
+
+/// The default gas price of a deploy.
+const DEFAULT_GAS_PRICE: u64 = 10;
+
+/// The runtime arg names of the session modules in `smart_contracts/contracts/client`.
+const ARG_CONTRACT_PACKAGE_HASH: &str = "contract_package_hash";
+const ARG_CONTRACT_HASH: &str = "contract_hash";
+const ARG_NAME: &str = "name";
+const ARG_KEY: &str = "key";
+
+/// The compiled `put_named_key.wasm`, built from `smart_contracts/contracts/client/put-named-key`.
+const PUT_NAMED_KEY_WASM: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/put_named_key.wasm"));
+
+/// The compiled `remove_named_key.wasm`, built from
+/// `smart_contracts/contracts/client/remove-named-key`.
+const REMOVE_NAMED_KEY_WASM: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/remove_named_key.wasm"));
+
+/// Marks the session or payment of a [`DeployBuilder`] as not yet provided.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Missing;
+
+/// The code to execute as the session or payment of a deploy.
+#[derive(Clone, Debug)]
+enum Target {
+    ModuleBytes(Vec<u8>),
+    StoredContractByHash {
+        hash: ContractHash,
+        entry_point: String,
+    },
+    StoredContractByName {
+        name: String,
+        entry_point: String,
+    },
+    StoredVersionedContractByHash {
+        hash: ContractPackageHash,
+        version: Option<u32>,
+        entry_point: String,
+    },
+    StoredVersionedContractByName {
+        name: String,
+        version: Option<u32>,
+        entry_point: String,
+    },
+}
+
+/// The session or payment of a deploy: the code to execute along with its runtime args.
+#[derive(Clone, Debug)]
+pub struct DeployCode {
+    target: Target,
+    args: RuntimeArgs,
+    /// The first arg which failed to convert to a `CLValue`, reported when the deploy is built.
+    invalid_arg: Option<String>,
+}
+
+impl DeployCode {
+    fn new(target: Target) -> Self {
+        DeployCode {
+            target,
+            args: RuntimeArgs::new(),
+            invalid_arg: None,
+        }
+    }
+
+    /// Code given as compiled Wasm.
+    pub fn module_bytes(module_bytes: Vec<u8>) -> Self {
+        DeployCode::new(Target::ModuleBytes(module_bytes))
+    }
+
+    /// Code given as compiled Wasm, read from the file at `path`.
+    pub fn module_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let module_bytes = fs::read(path).map_err(|error| Error::IoError {
+            context: format!("unable to read {}", path.display()),
+            error,
+        })?;
+        Ok(DeployCode::module_bytes(module_bytes))
+    }
+
+    /// The standard payment code, paying `amount` motes.
+    pub fn standard_payment<T: Into<U512>>(amount: T) -> Self {
+        DeployCode::module_bytes(vec![]).with_arg(STANDARD_PAYMENT_ARG_NAME, amount.into())
+    }
+
+    /// The entry point `entry_point` of the stored contract with the given hash.
+    pub fn stored_contract_by_hash<E: Into<String>>(hash: ContractHash, entry_point: E) -> Self {
+        DeployCode::new(Target::StoredContractByHash {
+            hash,
+            entry_point: entry_point.into(),
+        })
+    }
+
+    /// The entry point `entry_point` of the stored contract under the named key `name` in the
+    /// account's context.
+    pub fn stored_contract_by_name<N: Into<String>, E: Into<String>>(
+        name: N,
+        entry_point: E,
+    ) -> Self {
+        DeployCode::new(Target::StoredContractByName {
+            name: name.into(),
+            entry_point: entry_point.into(),
+        })
+    }
+
+    /// The entry point `entry_point` of the given version of the contract package with the given
+    /// hash.  If `version` is `None`, the highest enabled version is used.
+    pub fn stored_versioned_contract_by_hash<E: Into<String>>(
+        hash: ContractPackageHash,
+        version: Option<u32>,
+        entry_point: E,
+    ) -> Self {
+        DeployCode::new(Target::StoredVersionedContractByHash {
+            hash,
+            version,
+            entry_point: entry_point.into(),
+        })
+    }
+
+    /// The entry point `entry_point` of the given version of the contract package under the named
+    /// key `name` in the account's context.  If `version` is `None`, the highest enabled version is
+    /// used.
+    pub fn stored_versioned_contract_by_name<N: Into<String>, E: Into<String>>(
+        name: N,
+        version: Option<u32>,
+        entry_point: E,
+    ) -> Self {
+        DeployCode::new(Target::StoredVersionedContractByName {
+            name: name.into(),
+            version,
+            entry_point: entry_point.into(),
+        })
+    }
+
+    /// Session code disabling a version of a stored contract, run by
+    /// `disable_contract_version.wasm` read from `session_path`.
+    ///
+    /// The account must hold the contract package's access key in its named keys.
+    pub fn disable_contract_version<P: AsRef<Path>>(
+        session_path: P,
+        contract_package_hash: ContractPackageHash,
+        contract_hash: ContractHash,
+    ) -> Result<Self> {
+        Ok(DeployCode::module_file(session_path)?
+            .with_arg(ARG_CONTRACT_PACKAGE_HASH, contract_package_hash)
+            .with_arg(ARG_CONTRACT_HASH, contract_hash))
+    }
+
+    /// Session code adding `key` to the account's named keys under `name`, run by the
+    /// `put_named_key.wasm` embedded in this crate.
+    ///
+    /// Any key already held under `name` is replaced.  Only the named keys of the account itself
+    /// can be managed this way: a stored contract's named keys can only be changed by its own entry
+    /// points.
+    pub fn put_named_key<N: Into<String>>(name: N, key: Key) -> Self {
+        DeployCode::module_bytes(PUT_NAMED_KEY_WASM.to_vec())
+            .with_arg(ARG_NAME, name.into())
+            .with_arg(ARG_KEY, key)
+    }
+
+    /// Session code removing the key held under `name` from the account's named keys, run by the
+    /// `remove_named_key.wasm` embedded in this crate.
+    ///
+    /// Only the named keys of the account itself can be managed this way.
+    pub fn remove_named_key<N: Into<String>>(name: N) -> Self {
+        DeployCode::module_bytes(REMOVE_NAMED_KEY_WASM.to_vec()).with_arg(ARG_NAME, name.into())
+    }
+
+    /// Replaces the code with compiled Wasm read from the file at `path`, keeping the runtime
+    /// args, e.g. to run a locally built version of an embedded session module.
+    pub fn with_module_file<P: AsRef<Path>>(self, path: P) -> Result<Self> {
+        let DeployCode {
+            args, invalid_arg, ..
+        } = self;
+        let target = DeployCode::module_file(path)?.target;
+        Ok(DeployCode {
+            target,
+            args,
+            invalid_arg,
+        })
+    }
+
+    /// Adds the runtime arg `name`, replacing any existing arg of that name.
+    ///
+    /// The `CLType` of the arg is inferred from the type of `value`.
+    pub fn with_arg<N: Into<String>, T: CLTyped + ToBytes>(mut self, name: N, value: T) -> Self {
+        let name = name.into();
+        match CLValue::from_t(value) {
+            Ok(cl_value) => self.args.insert_cl_value(name, cl_value),
+            Err(error) => {
+                if self.invalid_arg.is_none() {
+                    self.invalid_arg = Some(format!("arg '{}': {}", name, error));
+                }
+            }
+        }
+        self
+    }
+
+    /// Adds all of the given runtime args, replacing any existing args of the same names.
+    pub fn with_args(mut self, args: RuntimeArgs) -> Self {
+        let args: BTreeMap<String, CLValue> = args.into();
+        for (name, cl_value) in args {
+            self.args.insert_cl_value(name, cl_value);
+        }
+        self
+    }
+
+    /// Returns the runtime args added so far.
+    pub fn args(&self) -> &RuntimeArgs {
+        &self.args
+    }
+
+    pub(crate) fn into_executable_deploy_item(self) -> Result<ExecutableDeployItem> {
+        if let Some(message) = self.invalid_arg {
+            return Err(Error::InvalidCLValue(message));
+        }
+        let args = self.args;
+        match self.target {
+            Target::ModuleBytes(module_bytes) => {
+                ExecutableDeployItem::new_module_bytes(module_bytes, args)
+            }
+            Target::StoredContractByHash { hash, entry_point } => {
+                ExecutableDeployItem::new_stored_contract_by_hash(hash, entry_point, args)
+            }
+            Target::StoredContractByName { name, entry_point } => {
+                ExecutableDeployItem::new_stored_contract_by_name(name, entry_point, args)
+            }
+            Target::StoredVersionedContractByHash {
+                hash,
+                version,
+                entry_point,
+            } => ExecutableDeployItem::new_stored_versioned_contract_by_hash(
+                hash,
+                version,
+                entry_point,
+                args,
+            ),
+            Target::StoredVersionedContractByName {
+                name,
+                version,
+                entry_point,
+            } => ExecutableDeployItem::new_stored_versioned_contract_by_name(
+                name,
+                version,
+                entry_point,
+                args,
+            ),
+        }
+    }
+}
+
+/// A fluent builder of a signed `Deploy`.
+///
+/// The type parameters track whether the session (`S`) and payment (`P`) have been provided, so
+/// that `build` is only available once both are set:
+///
+/// ```ignore
+/// let deploy = DeployBuilder::new("casper-net-1", secret_key)
+///     .with_session(
+///         DeployCode::stored_contract_by_name("faucet", "call_faucet")
+///             .with_arg("target", account_hash)
+///             .with_arg("amount", U512::from(1_000)),
+///     )
+///     .with_standard_payment(10_000_000)
+///     .with_ttl(TimeDiff::from(1_800_000))
+///     .build()?;
+/// ```
+pub struct DeployBuilder<S = Missing, P = Missing> {
+    chain_name: String,
+    secret_key: SecretKey,
+    timestamp: Option<Timestamp>,
+    ttl: TimeDiff,
+    gas_price: u64,
+    dependencies: Vec<DeployHash>,
+    session: S,
+    payment: P,
+}
+
+impl DeployBuilder {
+    /// Starts building a deploy for the network `chain_name`, to be signed with `secret_key`.
+    ///
+    /// Unless overridden, the deploy has a time to live of one hour, a gas price of 10, no
+    /// dependencies, and is timestamped when built.
+    pub fn new<C: Into<String>>(chain_name: C, secret_key: SecretKey) -> Self {
+        DeployBuilder {
+            chain_name: chain_name.into(),
+            secret_key,
+            timestamp: None,
+            ttl: TimeDiff::from(DEFAULT_TTL_MILLIS),
+            gas_price: DEFAULT_GAS_PRICE,
+            dependencies: vec![],
+            session: Missing,
+            payment: Missing,
+        }
+    }
+}
+
+impl<S, P> DeployBuilder<S, P> {
+    /// Sets the creation timestamp of the deploy.  If unset, the time at which it is built is used.
+    pub fn with_timestamp(mut self, timestamp: Timestamp) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Sets the time to live of the deploy.
+    pub fn with_ttl(mut self, ttl: TimeDiff) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Sets the gas price of the deploy.
+    pub fn with_gas_price(mut self, gas_price: u64) -> Self {
+        self.gas_price = gas_price;
+        self
+    }
+
+    /// Adds a deploy which must be executed before this one.
+    pub fn with_dependency(mut self, dependency: DeployHash) -> Self {
+        self.dependencies.push(dependency);
+        self
+    }
+}
+
+impl<P> DeployBuilder<Missing, P> {
+    /// Sets the session code of the deploy.
+    pub fn with_session(self, session: DeployCode) -> DeployBuilder<DeployCode, P> {
+        DeployBuilder {
+            chain_name: self.chain_name,
+            secret_key: self.secret_key,
+            timestamp: self.timestamp,
+            ttl: self.ttl,
+            gas_price: self.gas_price,
+            dependencies: self.dependencies,
+            session,
+            payment: self.payment,
+        }
+    }
+}
+
+impl<S> DeployBuilder<S, Missing> {
+    /// Sets the payment code of the deploy.
+    pub fn with_payment(self, payment: DeployCode) -> DeployBuilder<S, DeployCode> {
+        DeployBuilder {
+            chain_name: self.chain_name,
+            secret_key: self.secret_key,
+            timestamp: self.timestamp,
+            ttl: self.ttl,
+            gas_price: self.gas_price,
+            dependencies: self.dependencies,
+            session: self.session,
+            payment,
+        }
+    }
+
+    /// Sets the payment code of the deploy to the standard payment of `amount` motes.
+    pub fn with_standard_payment<T: Into<U512>>(self, amount: T) -> DeployBuilder<S, DeployCode> {
+        self.with_payment(DeployCode::standard_payment(amount))
+    }
+}
+
+impl DeployBuilder<DeployCode, DeployCode> {
+    /// Builds and signs the deploy.
+    ///
+    /// Fails if any runtime arg could not be converted to a `CLValue` or serialized.
+    pub fn build(self) -> Result<Deploy> {
+        let session = self.session.into_executable_deploy_item()?;
+        let payment = self.payment.into_executable_deploy_item()?;
+        let params = DeployParams {
+            secret_key: self.secret_key,
+            timestamp: self.timestamp.unwrap_or_else(Timestamp::now),
+            ttl: self.ttl,
+            gas_price: self.gas_price,
+            dependencies: self.dependencies,
+            chain_name: self.chain_name,
+        };
+        Ok(Deploy::with_payment_and_session(params, payment, session))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use casper_types::{account::AccountHash, CLType};
+
+    use super::*;
+
+    #[test]
+    fn should_infer_arg_types() {
+        let code = DeployCode::stored_contract_by_name("faucet", "call_faucet")
+            .with_arg("target", AccountHash::new([1; 32]))
+            .with_arg("amount", U512::from(1_000))
+            .with_arg("memo", Some(7u64));
+
+        let arg_type = |name: &str| code.args().get(name).unwrap().cl_type().clone();
+        assert_eq!(
+            arg_type("target"),
+            CLType::FixedList(Box::new(CLType::U8), 32)
+        );
+        assert_eq!(arg_type("amount"), CLType::U512);
+        assert_eq!(arg_type("memo"), CLType::Option(Box::new(CLType::U64)));
+    }
+
+    #[test]
+    fn named_key_code_should_have_typed_args() {
+        let key = Key::Hash([3; 32]);
+
+        let put = DeployCode::put_named_key("faucet", key);
+        assert_eq!(
+            put.args().get(ARG_NAME).unwrap().clone().into_t::<String>(),
+            Ok("faucet".to_string())
+        );
+        assert_eq!(
+            put.args().get(ARG_KEY).unwrap().clone().into_t::<Key>(),
+            Ok(key)
+        );
+
+        let remove = DeployCode::remove_named_key("faucet");
+        assert!(remove.args().get(ARG_KEY).is_none());
+    }
+
+    #[test]
+    fn named_key_code_should_embed_session_modules() {
+        // Every Wasm module starts with the magic number "\0asm".
+        for wasm in &[PUT_NAMED_KEY_WASM, REMOVE_NAMED_KEY_WASM] {
+            assert!(wasm.starts_with(b"\0asm"));
+        }
+
+        match DeployCode::put_named_key("faucet", Key::Hash([3; 32])).target {
+            Target::ModuleBytes(module_bytes) => assert_eq!(module_bytes, PUT_NAMED_KEY_WASM),
+            target => panic!("unexpected target {:?}", target),
+        }
+    }
+
+    #[test]
+    fn module_file_should_replace_code_and_keep_args() {
+        let mut session_file = tempfile::NamedTempFile::new().unwrap();
+        session_file.write_all(b"\0asm local build").unwrap();
+
+        let put = DeployCode::put_named_key("faucet", Key::Hash([3; 32]))
+            .with_module_file(session_file.path())
+            .unwrap();
+        match &put.target {
+            Target::ModuleBytes(module_bytes) => assert_eq!(module_bytes, b"\0asm local build"),
+            target => panic!("unexpected target {:?}", target),
+        }
+        assert!(put.args().get(ARG_NAME).is_some());
+        assert!(put.args().get(ARG_KEY).is_some());
+    }
+
+    #[test]
+    fn standard_payment_should_have_amount_arg() {
+        let payment = DeployCode::standard_payment(10u64);
+        let amount = payment.args().get(STANDARD_PAYMENT_ARG_NAME).unwrap();
+        assert_eq!(amount.clone().into_t::<U512>().unwrap(), U512::from(10));
+    }
+}
@@ -4,7 +4,10 @@ use humantime::{DurationError, TimestampError};
 use jsonrpc_lite::JsonRpc;
 use thiserror::Error;
 
-use casper_node::crypto::Error as CryptoError;
+use casper_node::{
+    crypto::Error as CryptoError, types::DeployHash, utils::passphrase::PassphraseError,
+    ChainspecError, SimulationError,
+};
 use casper_types::{bytesrepr::Error as ToBytesError, UIntParseError, URefFromStrError};
 
 use crate::validation::ValidateResponseError;
@@ -61,6 +64,23 @@ pub enum Error {
     #[error("rpc response is error: {0}")]
     ResponseIsError(#[from] jsonrpc_lite::Error),
 
+    /// The node is at capacity and refused the deploy.
+    #[error("node at capacity, retry after {retry_after_secs} seconds")]
+    NodeAtCapacity {
+        /// The number of seconds the node advised waiting before retrying.
+        retry_after_secs: u64,
+    },
+
+    /// The node refused to query global state older than its historical state window.
+    #[error(
+        "state root hash outside the node's historical state window, earliest queryable block \
+         height is {earliest_block_height}"
+    )]
+    StateRootOutsideWindow {
+        /// The height of the earliest block whose global state the node can query.
+        earliest_block_height: u64,
+    },
+
     /// Invalid JSON returned from the node.
     #[error("invalid json: {0}")]
     InvalidJson(#[from] serde_json::Error),
@@ -91,6 +111,18 @@ pub enum Error {
     #[error("Crypto error: {0}")]
     CryptoError(#[from] CryptoError),
 
+    /// Failed to get the passphrase of an encrypted secret key.
+    #[error("Passphrase error: {0}")]
+    PassphraseError(#[from] PassphraseError),
+
+    /// Failed to load a chainspec.
+    #[error("Chainspec error: {0}")]
+    ChainspecError(#[from] ChainspecError),
+
+    /// Failed to simulate the execution of a deploy.
+    #[error("Simulation error: {0}")]
+    SimulationError(#[from] SimulationError),
+
     /// Invalid `CLValue`.
     #[error("Invalid CLValue error {0}")]
     InvalidCLValue(String),
@@ -99,6 +131,15 @@ pub enum Error {
     #[error("Invalid argument '{0}' {1}")]
     InvalidArgument(&'static str, String),
 
+    /// The deploy didn't satisfy the criteria waited for before the timeout elapsed.
+    #[error("{deploy_hash} not {criteria} before the timeout elapsed")]
+    WaitForDeployTimedOut {
+        /// The hash of the deploy waited for.
+        deploy_hash: DeployHash,
+        /// The criteria waited for.
+        criteria: String,
+    },
+
     /// Failed to validate response.
     #[error("Invalid response {0}")]
     InvalidResponse(#[from] ValidateResponseError),
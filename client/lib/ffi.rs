@@ -53,6 +53,12 @@ pub enum casper_error_t {
     CASPER_INVALID_RESPONSE = -20,
     CASPER_FFI_SETUP_NOT_CALLED = -21,
     CASPER_FFI_PTR_NULL_BUT_REQUIRED = -22,
+    CASPER_NODE_AT_CAPACITY = -23,
+    CASPER_STATE_ROOT_OUTSIDE_WINDOW = -24,
+    CASPER_WAIT_FOR_DEPLOY_TIMED_OUT = -25,
+    CASPER_PASSPHRASE_ERROR = -26,
+    CASPER_CHAINSPEC_ERROR = -27,
+    CASPER_SIMULATION_ERROR = -28,
 }
 
 trait AsFFIError {
@@ -73,6 +79,10 @@ impl AsFFIError for Error {
             Error::FileAlreadyExists(_) => casper_error_t::CASPER_FILE_ALREADY_EXISTS,
             Error::UnsupportedAlgorithm(_) => casper_error_t::CASPER_UNSUPPORTED_ALGORITHM,
             Error::ResponseIsError(_) => casper_error_t::CASPER_REPSONSE_IS_ERROR,
+            Error::NodeAtCapacity { .. } => casper_error_t::CASPER_NODE_AT_CAPACITY,
+            Error::StateRootOutsideWindow { .. } => {
+                casper_error_t::CASPER_STATE_ROOT_OUTSIDE_WINDOW
+            }
             Error::InvalidJson(_) => casper_error_t::CASPER_INVALID_JSON,
             Error::InvalidRpcResponse(_) => casper_error_t::CASPER_INVALID_RPC_RESPONSE,
             Error::FailedSending(_) => casper_error_t::CASPER_FAILED_SENDING,
@@ -82,6 +92,10 @@ impl AsFFIError for Error {
             Error::InvalidCLValue(_) => casper_error_t::CASPER_INVALID_CL_VALUE,
             Error::InvalidArgument(_, _) => casper_error_t::CASPER_INVALID_ARGUMENT,
             Error::InvalidResponse(_) => casper_error_t::CASPER_INVALID_RESPONSE,
+            Error::WaitForDeployTimedOut { .. } => casper_error_t::CASPER_WAIT_FOR_DEPLOY_TIMED_OUT,
+            Error::PassphraseError(_) => casper_error_t::CASPER_PASSPHRASE_ERROR,
+            Error::ChainspecError(_) => casper_error_t::CASPER_CHAINSPEC_ERROR,
+            Error::SimulationError(_) => casper_error_t::CASPER_SIMULATION_ERROR,
             Error::FFISetupNotCalled => casper_error_t::CASPER_FFI_SETUP_NOT_CALLED,
             Error::FFIPtrNullButRequired(_) => casper_error_t::CASPER_FFI_PTR_NULL_BUT_REQUIRED,
         }
@@ -284,6 +298,81 @@ pub extern "C" fn casper_put_deploy(
     })
 }
 
+/// Creates a `Deploy` and sends it to the network to estimate its cost.
+///
+/// See [super::estimate_deploy_cost](super::estimate_deploy_cost) for more details.
+#[no_mangle]
+pub extern "C" fn casper_estimate_deploy_cost(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbose: bool,
+    deploy_params: *const casper_deploy_params_t,
+    session_params: *const casper_session_params_t,
+    payment_params: *const casper_payment_params_t,
+    profile_gas: bool,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let mut runtime = RUNTIME.lock().expect("should lock");
+    let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
+    let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
+    let node_address = try_unsafe_arg!(node_address);
+    let deploy_params = try_arg_into!(deploy_params);
+    let session_params = try_arg_into!(session_params);
+    let payment_params = try_arg_into!(payment_params);
+    runtime.block_on(async move {
+        let result = super::estimate_deploy_cost(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            deploy_params,
+            session_params,
+            payment_params,
+            profile_gas,
+        );
+        let response = try_unwrap_rpc!(result);
+        copy_str_to_buf(&response, response_buf, response_buf_len);
+        casper_error_t::CASPER_SUCCESS
+    })
+}
+
+/// Creates a `Deploy` and executes it locally, without a node, returning the effects and cost of
+/// its execution.
+///
+/// The result is written to `response_buf` as JSON.
+///
+/// See [super::simulate_deploy](super::simulate_deploy) for more details.
+#[no_mangle]
+pub extern "C" fn casper_simulate_deploy(
+    chainspec_path: *const c_char,
+    storage_path: *const c_char,
+    state_root_hash: *const c_char,
+    deploy_params: *const casper_deploy_params_t,
+    session_params: *const casper_session_params_t,
+    payment_params: *const casper_payment_params_t,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let chainspec_path = try_unsafe_arg!(chainspec_path);
+    let storage_path = try_unsafe_arg!(storage_path);
+    let state_root_hash = try_unsafe_arg!(state_root_hash);
+    let deploy_params = try_arg_into!(deploy_params);
+    let session_params = try_arg_into!(session_params);
+    let payment_params = try_arg_into!(payment_params);
+    let result = super::simulate_deploy(
+        chainspec_path,
+        storage_path,
+        state_root_hash,
+        deploy_params,
+        session_params,
+        payment_params,
+    );
+    let value = try_unwrap_result!(result);
+    let response = try_unwrap_result!(serde_json::to_string(&value).map_err(Into::into));
+    copy_str_to_buf(&response, response_buf, response_buf_len);
+    casper_error_t::CASPER_SUCCESS
+}
+
 /// Creates a `Deploy` and outputs it to a file or stdout.
 ///
 /// See [super::make_deploy](super::make_deploy) for more details
@@ -326,6 +415,27 @@ pub extern "C" fn casper_sign_deploy_file(
     casper_error_t::CASPER_SUCCESS
 }
 
+/// Returns the account hash of the account with the given public key.
+///
+/// The formatted account hash is written to `response_buf`.
+///
+/// See [super::account_address](super::account_address) for more details.
+#[no_mangle]
+pub extern "C" fn casper_account_address(
+    public_key: *const c_char,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let public_key = try_unsafe_arg!(public_key);
+    let account_hash = try_unwrap_result!(super::account_address(public_key));
+    copy_str_to_buf(
+        &account_hash.to_formatted_string(),
+        response_buf,
+        response_buf_len,
+    );
+    casper_error_t::CASPER_SUCCESS
+}
+
 /// Reads a previously-saved `Deploy` from a file and sends it to the network for execution.
 ///
 /// See [super::send_deploy_file](super::send_deploy_file) for more details.
@@ -335,6 +445,7 @@ pub extern "C" fn casper_send_deploy_file(
     node_address: *const c_char,
     verbose: bool,
     input_path: *const c_char,
+    maybe_idempotency_key: *const c_char,
     response_buf: *mut c_uchar,
     response_buf_len: usize,
 ) -> casper_error_t {
@@ -343,8 +454,41 @@ pub extern "C" fn casper_send_deploy_file(
     let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
     let node_address = try_unsafe_arg!(node_address);
     let input_path = try_unsafe_arg!(input_path);
+    let maybe_idempotency_key = try_unsafe_arg!(maybe_idempotency_key);
     runtime.block_on(async move {
-        let result = super::send_deploy_file(maybe_rpc_id, node_address, verbose, input_path);
+        let result = super::send_deploy_file(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            input_path,
+            maybe_idempotency_key,
+        );
+        let response = try_unwrap_rpc!(result);
+        copy_str_to_buf(&response, response_buf, response_buf_len);
+        casper_error_t::CASPER_SUCCESS
+    })
+}
+
+/// Reads a previously-saved `Deploy` from a file and has the node check it, without sending it to
+/// the network.
+///
+/// See [super::check_deploy_file](super::check_deploy_file) for more details.
+#[no_mangle]
+pub extern "C" fn casper_check_deploy_file(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbose: bool,
+    input_path: *const c_char,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let mut runtime = RUNTIME.lock().expect("should lock");
+    let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
+    let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
+    let node_address = try_unsafe_arg!(node_address);
+    let input_path = try_unsafe_arg!(input_path);
+    runtime.block_on(async move {
+        let result = super::check_deploy_file(maybe_rpc_id, node_address, verbose, input_path);
         let response = try_unwrap_rpc!(result);
         copy_str_to_buf(&response, response_buf, response_buf_len);
         casper_error_t::CASPER_SUCCESS
@@ -396,6 +540,132 @@ pub extern "C" fn casper_transfer(
     })
 }
 
+/// Creates a `Deploy` which disables a version of a stored contract, and sends it to the network
+/// for execution.
+///
+/// See [super::disable_contract_version](super::disable_contract_version) for more details.
+#[no_mangle]
+pub extern "C" fn casper_disable_contract_version(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbose: bool,
+    session_path: *const c_char,
+    contract_package_hash: *const c_char,
+    contract_hash: *const c_char,
+    deploy_params: *const casper_deploy_params_t,
+    payment_params: *const casper_payment_params_t,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let mut runtime = RUNTIME.lock().expect("should lock");
+    let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
+    let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
+    let node_address = try_unsafe_arg!(node_address);
+    let session_path = try_unsafe_arg!(session_path);
+    let contract_package_hash = try_unsafe_arg!(contract_package_hash);
+    let contract_hash = try_unsafe_arg!(contract_hash);
+    let deploy_params = try_arg_into!(deploy_params);
+    let payment_params = try_arg_into!(payment_params);
+    runtime.block_on(async move {
+        let result = super::disable_contract_version(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            session_path,
+            contract_package_hash,
+            contract_hash,
+            deploy_params,
+            payment_params,
+        );
+        let response = try_unwrap_rpc!(result);
+        copy_str_to_buf(&response, response_buf, response_buf_len);
+        casper_error_t::CASPER_SUCCESS
+    })
+}
+
+/// Creates a `Deploy` which adds a key to the named keys of the account signing it, and sends it to
+/// the network for execution.
+///
+/// See [super::put_named_key](super::put_named_key) for more details.
+#[no_mangle]
+pub extern "C" fn casper_put_named_key(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbose: bool,
+    maybe_session_path: *const c_char,
+    name: *const c_char,
+    key: *const c_char,
+    deploy_params: *const casper_deploy_params_t,
+    payment_params: *const casper_payment_params_t,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let mut runtime = RUNTIME.lock().expect("should lock");
+    let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
+    let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
+    let node_address = try_unsafe_arg!(node_address);
+    let maybe_session_path = try_unsafe_arg!(maybe_session_path);
+    let name = try_unsafe_arg!(name);
+    let key = try_unsafe_arg!(key);
+    let deploy_params = try_arg_into!(deploy_params);
+    let payment_params = try_arg_into!(payment_params);
+    runtime.block_on(async move {
+        let result = super::put_named_key(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            maybe_session_path,
+            name,
+            key,
+            deploy_params,
+            payment_params,
+        );
+        let response = try_unwrap_rpc!(result);
+        copy_str_to_buf(&response, response_buf, response_buf_len);
+        casper_error_t::CASPER_SUCCESS
+    })
+}
+
+/// Creates a `Deploy` which removes a key from the named keys of the account signing it, and sends
+/// it to the network for execution.
+///
+/// See [super::remove_named_key](super::remove_named_key) for more details.
+#[no_mangle]
+pub extern "C" fn casper_remove_named_key(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbose: bool,
+    maybe_session_path: *const c_char,
+    name: *const c_char,
+    deploy_params: *const casper_deploy_params_t,
+    payment_params: *const casper_payment_params_t,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let mut runtime = RUNTIME.lock().expect("should lock");
+    let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
+    let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
+    let node_address = try_unsafe_arg!(node_address);
+    let maybe_session_path = try_unsafe_arg!(maybe_session_path);
+    let name = try_unsafe_arg!(name);
+    let deploy_params = try_arg_into!(deploy_params);
+    let payment_params = try_arg_into!(payment_params);
+    runtime.block_on(async move {
+        let result = super::remove_named_key(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            maybe_session_path,
+            name,
+            deploy_params,
+            payment_params,
+        );
+        let response = try_unwrap_rpc!(result);
+        copy_str_to_buf(&response, response_buf, response_buf_len);
+        casper_error_t::CASPER_SUCCESS
+    })
+}
+
 /// Retrieves a `Deploy` from the network.
 ///
 /// See [super::get_deploy](super::get_deploy) for more details.
@@ -421,15 +691,19 @@ pub extern "C" fn casper_get_deploy(
     })
 }
 
-/// Retrieves a `Block` from the network.
+/// Polls the network until a `Deploy` satisfies the given criteria, returning its status.
 ///
-/// See [super::get_block](super::get_block) for more details.
+/// The result is written to `response_buf` as JSON.
+///
+/// See [super::wait_for_deploy](super::wait_for_deploy) for more details.
 #[no_mangle]
-pub extern "C" fn casper_get_block(
+pub extern "C" fn casper_wait_for_deploy(
     maybe_rpc_id: *const c_char,
     node_address: *const c_char,
     verbose: bool,
-    maybe_block_id: *const c_char,
+    deploy_hash: *const c_char,
+    criteria: *const c_char,
+    timeout: *const c_char,
     response_buf: *mut c_uchar,
     response_buf_len: usize,
 ) -> casper_error_t {
@@ -437,20 +711,30 @@ pub extern "C" fn casper_get_block(
     let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
     let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
     let node_address = try_unsafe_arg!(node_address);
-    let maybe_block_id = try_unsafe_arg!(maybe_block_id);
+    let deploy_hash = try_unsafe_arg!(deploy_hash);
+    let criteria = try_unsafe_arg!(criteria);
+    let timeout = try_unsafe_arg!(timeout);
     runtime.block_on(async move {
-        let result = super::get_block(maybe_rpc_id, node_address, verbose, maybe_block_id);
-        let response = try_unwrap_rpc!(result);
+        let result = super::wait_for_deploy(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            deploy_hash,
+            criteria,
+            timeout,
+        );
+        let value = try_unwrap_result!(result);
+        let response = try_unwrap_result!(serde_json::to_string(&value).map_err(Into::into));
         copy_str_to_buf(&response, response_buf, response_buf_len);
         casper_error_t::CASPER_SUCCESS
     })
 }
 
-/// Retrieves a state root hash at a given `Block`.
+/// Retrieves a `Block` from the network.
 ///
-/// See [super::get_state_root_hash](super::get_state_root_hash) for more details.
+/// See [super::get_block](super::get_block) for more details.
 #[no_mangle]
-pub extern "C" fn casper_get_state_root_hash(
+pub extern "C" fn casper_get_block(
     maybe_rpc_id: *const c_char,
     node_address: *const c_char,
     verbose: bool,
@@ -464,25 +748,24 @@ pub extern "C" fn casper_get_state_root_hash(
     let node_address = try_unsafe_arg!(node_address);
     let maybe_block_id = try_unsafe_arg!(maybe_block_id);
     runtime.block_on(async move {
-        let result =
-            super::get_state_root_hash(maybe_rpc_id, node_address, verbose, maybe_block_id);
+        let result = super::get_block(maybe_rpc_id, node_address, verbose, maybe_block_id);
         let response = try_unwrap_rpc!(result);
         copy_str_to_buf(&response, response_buf, response_buf_len);
         casper_error_t::CASPER_SUCCESS
     })
 }
 
-/// Retrieves a stored value from the network.
+/// Retrieves a contiguous range of `Block`s from the network.
 ///
-/// See [super::get_item](super::get_item) for more details.
+/// See [super::get_blocks](super::get_blocks) for more details.
 #[no_mangle]
-pub extern "C" fn casper_get_item(
+pub extern "C" fn casper_get_blocks(
     maybe_rpc_id: *const c_char,
     node_address: *const c_char,
     verbose: bool,
-    state_root_hash: *const c_char,
-    key: *const c_char,
-    path: *const c_char,
+    start_height: *const c_char,
+    end_height: *const c_char,
+    headers_only: bool,
     response_buf: *mut c_uchar,
     response_buf_len: usize,
 ) -> casper_error_t {
@@ -490,17 +773,16 @@ pub extern "C" fn casper_get_item(
     let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
     let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
     let node_address = try_unsafe_arg!(node_address);
-    let state_root_hash = try_unsafe_arg!(state_root_hash);
-    let key = try_unsafe_arg!(key);
-    let path = try_unsafe_arg!(path);
+    let start_height = try_unsafe_arg!(start_height);
+    let end_height = try_unsafe_arg!(end_height);
     runtime.block_on(async move {
-        let result = super::get_item(
+        let result = super::get_blocks(
             maybe_rpc_id,
             node_address,
             verbose,
-            state_root_hash,
-            key,
-            path,
+            start_height,
+            end_height,
+            headers_only,
         );
         let response = try_unwrap_rpc!(result);
         copy_str_to_buf(&response, response_buf, response_buf_len);
@@ -508,16 +790,15 @@ pub extern "C" fn casper_get_item(
     })
 }
 
-/// Retrieves a purse's balance from the network.
+/// Retrieves the `Block`s at the given height which conflict with the `Block` on the linear chain.
 ///
-/// See [super::get_balance](super::get_balance) for more details.
+/// See [super::get_alternative_blocks](super::get_alternative_blocks) for more details.
 #[no_mangle]
-pub extern "C" fn casper_get_balance(
+pub extern "C" fn casper_get_alternative_blocks(
     maybe_rpc_id: *const c_char,
     node_address: *const c_char,
     verbose: bool,
-    state_root_hash: *const c_char,
-    purse: *const c_char,
+    height: *const c_char,
     response_buf: *mut c_uchar,
     response_buf_len: usize,
 ) -> casper_error_t {
@@ -525,25 +806,25 @@ pub extern "C" fn casper_get_balance(
     let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
     let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
     let node_address = try_unsafe_arg!(node_address);
-    let state_root_hash = try_unsafe_arg!(state_root_hash);
-    let purse = try_unsafe_arg!(purse);
+    let height = try_unsafe_arg!(height);
     runtime.block_on(async move {
-        let result =
-            super::get_balance(maybe_rpc_id, node_address, verbose, state_root_hash, purse);
+        let result = super::get_alternative_blocks(maybe_rpc_id, node_address, verbose, height);
         let response = try_unwrap_rpc!(result);
         copy_str_to_buf(&response, response_buf, response_buf_len);
         casper_error_t::CASPER_SUCCESS
     })
 }
 
-/// Retrieves the bids and validators as of the most recently added `Block`.
+/// Retrieves the header of the `Block` on the linear chain including a `Deploy`, along with the
+/// `Deploy`'s index within the `Block`.
 ///
-/// See [super::get_auction_info](super::get_auction_info) for more details.
+/// See [super::get_block_by_deploy](super::get_block_by_deploy) for more details.
 #[no_mangle]
-pub extern "C" fn casper_get_auction_info(
+pub extern "C" fn casper_get_block_by_deploy(
     maybe_rpc_id: *const c_char,
     node_address: *const c_char,
     verbose: bool,
+    deploy_hash: *const c_char,
     response_buf: *mut c_uchar,
     response_buf_len: usize,
 ) -> casper_error_t {
@@ -551,36 +832,741 @@ pub extern "C" fn casper_get_auction_info(
     let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
     let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
     let node_address = try_unsafe_arg!(node_address);
+    let deploy_hash = try_unsafe_arg!(deploy_hash);
     runtime.block_on(async move {
-        let result = super::get_auction_info(maybe_rpc_id, node_address, verbose);
+        let result = super::get_block_by_deploy(maybe_rpc_id, node_address, verbose, deploy_hash);
         let response = try_unwrap_rpc!(result);
         copy_str_to_buf(&response, response_buf, response_buf_len);
         casper_error_t::CASPER_SUCCESS
     })
 }
 
-/// Container for `Deploy` construction options.
+/// Retrieves the header of the most recent `Block` on the linear chain not later than a given
+/// timestamp, along with the header of the next `Block` if known to the node.
 ///
-/// See [DeployStrParams](super::DeployStrParams) for more info.
-#[allow(non_snake_case)]
-#[repr(C)]
-#[derive(Clone)]
-pub struct casper_deploy_params_t {
-    secret_key: *const c_char,
+/// See [super::get_block_at_timestamp](super::get_block_at_timestamp) for more details.
+#[no_mangle]
+pub extern "C" fn casper_get_block_at_timestamp(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbose: bool,
     timestamp: *const c_char,
-    ttl: *const c_char,
-    gas_price: *const c_char,
-    dependencies: *const *const c_char,
-    dependencies_len: usize,
-    chain_name: *const c_char,
-}
-
-impl TryInto<super::DeployStrParams<'_>> for casper_deploy_params_t {
-    type Error = Error;
-
-    fn try_into(self) -> Result<super::DeployStrParams<'static>> {
-        let secret_key = unsafe_str_arg(self.secret_key, "casper_deploy_params_t.secret_key")?;
-        let timestamp = unsafe_str_arg(self.timestamp, "casper_deploy_params_t.timestamp")?;
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let mut runtime = RUNTIME.lock().expect("should lock");
+    let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
+    let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
+    let node_address = try_unsafe_arg!(node_address);
+    let timestamp = try_unsafe_arg!(timestamp);
+    runtime.block_on(async move {
+        let result = super::get_block_at_timestamp(maybe_rpc_id, node_address, verbose, timestamp);
+        let response = try_unwrap_rpc!(result);
+        copy_str_to_buf(&response, response_buf, response_buf_len);
+        casper_error_t::CASPER_SUCCESS
+    })
+}
+
+/// Retrieves the summary of an era which has ended, i.e. the header of its switch `Block` (holding
+/// the era's rewards and equivocators) and the era info recorded by the auction contract.
+///
+/// See [super::get_era_summary](super::get_era_summary) for more details.
+#[no_mangle]
+pub extern "C" fn casper_get_era_summary(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbose: bool,
+    maybe_era_id: *const c_char,
+    maybe_block_id: *const c_char,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let mut runtime = RUNTIME.lock().expect("should lock");
+    let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
+    let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
+    let node_address = try_unsafe_arg!(node_address);
+    let maybe_era_id = try_unsafe_arg!(maybe_era_id);
+    let maybe_block_id = try_unsafe_arg!(maybe_block_id);
+    runtime.block_on(async move {
+        let result = super::get_era_summary(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            maybe_era_id,
+            maybe_block_id,
+        );
+        let response = try_unwrap_rpc!(result);
+        copy_str_to_buf(&response, response_buf, response_buf_len);
+        casper_error_t::CASPER_SUCCESS
+    })
+}
+
+/// Retrieves a page of the rewards credited to each validator and delegator at the end of an era.
+///
+/// See [super::get_validator_rewards](super::get_validator_rewards) for more details.
+#[no_mangle]
+pub extern "C" fn casper_get_validator_rewards(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbose: bool,
+    era_id: *const c_char,
+    offset: *const c_char,
+    maybe_limit: *const c_char,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let mut runtime = RUNTIME.lock().expect("should lock");
+    let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
+    let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
+    let node_address = try_unsafe_arg!(node_address);
+    let era_id = try_unsafe_arg!(era_id);
+    let offset = try_unsafe_arg!(offset);
+    let maybe_limit = try_unsafe_arg!(maybe_limit);
+    runtime.block_on(async move {
+        let result = super::get_validator_rewards(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            era_id,
+            offset,
+            maybe_limit,
+        );
+        let response = try_unwrap_rpc!(result);
+        copy_str_to_buf(&response, response_buf, response_buf_len);
+        casper_error_t::CASPER_SUCCESS
+    })
+}
+
+/// Retrieves the total supply of motes, along with a merkle proof, and optionally the circulating
+/// supply.
+///
+/// See [super::get_supply](super::get_supply) for more details.
+#[no_mangle]
+pub extern "C" fn casper_get_supply(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbose: bool,
+    maybe_block_id: *const c_char,
+    include_circulating_supply: bool,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let mut runtime = RUNTIME.lock().expect("should lock");
+    let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
+    let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
+    let node_address = try_unsafe_arg!(node_address);
+    let maybe_block_id = try_unsafe_arg!(maybe_block_id);
+    runtime.block_on(async move {
+        let result = super::get_supply(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            maybe_block_id,
+            include_circulating_supply,
+        );
+        let response = try_unwrap_rpc!(result);
+        copy_str_to_buf(&response, response_buf, response_buf_len);
+        casper_error_t::CASPER_SUCCESS
+    })
+}
+
+/// Retrieves the status of the node signed with its validator key, and verifies the signature.
+///
+/// See [super::get_signed_status](super::get_signed_status) for more details.
+#[no_mangle]
+pub extern "C" fn casper_get_signed_status(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbose: bool,
+    maybe_nonce: *const c_char,
+    maybe_public_key: *const c_char,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let mut runtime = RUNTIME.lock().expect("should lock");
+    let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
+    let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
+    let node_address = try_unsafe_arg!(node_address);
+    let maybe_nonce = try_unsafe_arg!(maybe_nonce);
+    let maybe_public_key = try_unsafe_arg!(maybe_public_key);
+    runtime.block_on(async move {
+        let result = super::get_signed_status(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            maybe_nonce,
+            maybe_public_key,
+        );
+        let response = try_unwrap_rpc!(result);
+        copy_str_to_buf(&response, response_buf, response_buf_len);
+        casper_error_t::CASPER_SUCCESS
+    })
+}
+
+/// Retrieves the deploy limits, costs, era settings and protocol version of the active chainspec.
+///
+/// See [super::get_chainspec](super::get_chainspec) for more details.
+#[no_mangle]
+pub extern "C" fn casper_get_chainspec(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbose: bool,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let mut runtime = RUNTIME.lock().expect("should lock");
+    let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
+    let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
+    let node_address = try_unsafe_arg!(node_address);
+    runtime.block_on(async move {
+        let result = super::get_chainspec(maybe_rpc_id, node_address, verbose);
+        let response = try_unwrap_rpc!(result);
+        copy_str_to_buf(&response, response_buf, response_buf_len);
+        casper_error_t::CASPER_SUCCESS
+    })
+}
+
+/// Retrieves a state root hash at a given `Block`.
+///
+/// See [super::get_state_root_hash](super::get_state_root_hash) for more details.
+#[no_mangle]
+pub extern "C" fn casper_get_state_root_hash(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbose: bool,
+    maybe_block_id: *const c_char,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let mut runtime = RUNTIME.lock().expect("should lock");
+    let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
+    let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
+    let node_address = try_unsafe_arg!(node_address);
+    let maybe_block_id = try_unsafe_arg!(maybe_block_id);
+    runtime.block_on(async move {
+        let result =
+            super::get_state_root_hash(maybe_rpc_id, node_address, verbose, maybe_block_id);
+        let response = try_unwrap_rpc!(result);
+        copy_str_to_buf(&response, response_buf, response_buf_len);
+        casper_error_t::CASPER_SUCCESS
+    })
+}
+
+/// Retrieves a stored value from the network.
+///
+/// See [super::get_item](super::get_item) for more details.
+#[no_mangle]
+pub extern "C" fn casper_get_item(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbose: bool,
+    state_root_hash: *const c_char,
+    key: *const c_char,
+    path: *const c_char,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let mut runtime = RUNTIME.lock().expect("should lock");
+    let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
+    let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
+    let node_address = try_unsafe_arg!(node_address);
+    let state_root_hash = try_unsafe_arg!(state_root_hash);
+    let key = try_unsafe_arg!(key);
+    let path = try_unsafe_arg!(path);
+    runtime.block_on(async move {
+        let result = super::get_item(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            state_root_hash,
+            key,
+            path,
+        );
+        let response = try_unwrap_rpc!(result);
+        copy_str_to_buf(&response, response_buf, response_buf_len);
+        casper_error_t::CASPER_SUCCESS
+    })
+}
+
+/// Retrieves a stored value from the network as of a given `Block`.
+///
+/// See [super::get_item_at_block](super::get_item_at_block) for more details.
+#[no_mangle]
+pub extern "C" fn casper_get_item_at_block(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbose: bool,
+    maybe_block_id: *const c_char,
+    key: *const c_char,
+    path: *const c_char,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let mut runtime = RUNTIME.lock().expect("should lock");
+    let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
+    let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
+    let node_address = try_unsafe_arg!(node_address);
+    let maybe_block_id = try_unsafe_arg!(maybe_block_id);
+    let key = try_unsafe_arg!(key);
+    let path = try_unsafe_arg!(path);
+    runtime.block_on(async move {
+        let result = super::get_item_at_block(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            maybe_block_id,
+            key,
+            path,
+        );
+        let response = try_unwrap_rpc!(result);
+        copy_str_to_buf(&response, response_buf, response_buf_len);
+        casper_error_t::CASPER_SUCCESS
+    })
+}
+
+/// Retrieves a purse's balance from the network.
+///
+/// See [super::get_balance](super::get_balance) for more details.
+#[no_mangle]
+pub extern "C" fn casper_get_balance(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbose: bool,
+    state_root_hash: *const c_char,
+    purse: *const c_char,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let mut runtime = RUNTIME.lock().expect("should lock");
+    let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
+    let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
+    let node_address = try_unsafe_arg!(node_address);
+    let state_root_hash = try_unsafe_arg!(state_root_hash);
+    let purse = try_unsafe_arg!(purse);
+    runtime.block_on(async move {
+        let result =
+            super::get_balance(maybe_rpc_id, node_address, verbose, state_root_hash, purse);
+        let response = try_unwrap_rpc!(result);
+        copy_str_to_buf(&response, response_buf, response_buf_len);
+        casper_error_t::CASPER_SUCCESS
+    })
+}
+
+/// Retrieves a purse's balance from the network, sampled at intervals over a range of `Block`s.
+///
+/// See [super::get_balance_history](super::get_balance_history) for more details.
+#[no_mangle]
+pub extern "C" fn casper_get_balance_history(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbose: bool,
+    purse: *const c_char,
+    from_height: *const c_char,
+    to_height: *const c_char,
+    step: *const c_char,
+    include_proofs: bool,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let mut runtime = RUNTIME.lock().expect("should lock");
+    let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
+    let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
+    let node_address = try_unsafe_arg!(node_address);
+    let purse = try_unsafe_arg!(purse);
+    let from_height = try_unsafe_arg!(from_height);
+    let to_height = try_unsafe_arg!(to_height);
+    let step = try_unsafe_arg!(step);
+    runtime.block_on(async move {
+        let result = super::get_balance_history(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            purse,
+            from_height,
+            to_height,
+            step,
+            include_proofs,
+        );
+        let response = try_unwrap_rpc!(result);
+        copy_str_to_buf(&response, response_buf, response_buf_len);
+        casper_error_t::CASPER_SUCCESS
+    })
+}
+
+/// Retrieves an account, with its named keys, associated keys, action thresholds and main purse,
+/// from the network.
+///
+/// See [super::get_account_info](super::get_account_info) for more details.
+#[no_mangle]
+pub extern "C" fn casper_get_account_info(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbose: bool,
+    account_identifier: *const c_char,
+    maybe_block_id: *const c_char,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let mut runtime = RUNTIME.lock().expect("should lock");
+    let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
+    let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
+    let node_address = try_unsafe_arg!(node_address);
+    let account_identifier = try_unsafe_arg!(account_identifier);
+    let maybe_block_id = try_unsafe_arg!(maybe_block_id);
+    runtime.block_on(async move {
+        let result = super::get_account_info(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            account_identifier,
+            maybe_block_id,
+        );
+        let response = try_unwrap_rpc!(result);
+        copy_str_to_buf(&response, response_buf, response_buf_len);
+        casper_error_t::CASPER_SUCCESS
+    })
+}
+
+/// Retrieves the hashes of the `Deploy`s involving an account, most recent first.
+///
+/// See [super::get_account_deploys](super::get_account_deploys) for more details.
+#[no_mangle]
+pub extern "C" fn casper_get_account_deploys(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbose: bool,
+    account_identifier: *const c_char,
+    maybe_page: *const c_char,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let mut runtime = RUNTIME.lock().expect("should lock");
+    let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
+    let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
+    let node_address = try_unsafe_arg!(node_address);
+    let account_identifier = try_unsafe_arg!(account_identifier);
+    let maybe_page = try_unsafe_arg!(maybe_page);
+    runtime.block_on(async move {
+        let result = super::get_account_deploys(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            account_identifier,
+            maybe_page,
+        );
+        let response = try_unwrap_rpc!(result);
+        copy_str_to_buf(&response, response_buf, response_buf_len);
+        casper_error_t::CASPER_SUCCESS
+    })
+}
+
+/// Retrieves the `Deploy`s which wrote to a global state key, most recent first, along with the
+/// `Block`s in which they were executed.
+///
+/// See [super::get_key_history](super::get_key_history) for more details.
+#[no_mangle]
+pub extern "C" fn casper_get_key_history(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbose: bool,
+    key: *const c_char,
+    maybe_page: *const c_char,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let mut runtime = RUNTIME.lock().expect("should lock");
+    let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
+    let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
+    let node_address = try_unsafe_arg!(node_address);
+    let key = try_unsafe_arg!(key);
+    let maybe_page = try_unsafe_arg!(maybe_page);
+    runtime.block_on(async move {
+        let result = super::get_key_history(maybe_rpc_id, node_address, verbose, key, maybe_page);
+        let response = try_unwrap_rpc!(result);
+        copy_str_to_buf(&response, response_buf, response_buf_len);
+        casper_error_t::CASPER_SUCCESS
+    })
+}
+
+/// Retrieves a page of the global state keys whose serialized representation starts with a given
+/// prefix, along with their stored values and proofs of inclusion.
+///
+/// See [super::get_keys_with_prefix](super::get_keys_with_prefix) for more details.
+#[no_mangle]
+pub extern "C" fn casper_get_keys_with_prefix(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbose: bool,
+    state_root_hash: *const c_char,
+    prefix: *const c_char,
+    maybe_page: *const c_char,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let mut runtime = RUNTIME.lock().expect("should lock");
+    let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
+    let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
+    let node_address = try_unsafe_arg!(node_address);
+    let state_root_hash = try_unsafe_arg!(state_root_hash);
+    let prefix = try_unsafe_arg!(prefix);
+    let maybe_page = try_unsafe_arg!(maybe_page);
+    runtime.block_on(async move {
+        let result = super::get_keys_with_prefix(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            state_root_hash,
+            prefix,
+            maybe_page,
+        );
+        let response = try_unwrap_rpc!(result);
+        copy_str_to_buf(&response, response_buf, response_buf_len);
+        casper_error_t::CASPER_SUCCESS
+    })
+}
+
+/// Retrieves the entry points of a stored contract, documented by the contract's metadata if it has
+/// any.
+///
+/// See [super::get_contract_abi](super::get_contract_abi) for more details.
+#[no_mangle]
+pub extern "C" fn casper_get_contract_abi(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbose: bool,
+    state_root_hash: *const c_char,
+    contract_hash: *const c_char,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let mut runtime = RUNTIME.lock().expect("should lock");
+    let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
+    let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
+    let node_address = try_unsafe_arg!(node_address);
+    let state_root_hash = try_unsafe_arg!(state_root_hash);
+    let contract_hash = try_unsafe_arg!(contract_hash);
+    runtime.block_on(async move {
+        let result = super::get_contract_abi(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            state_root_hash,
+            contract_hash,
+        );
+        let response = try_unwrap_rpc!(result);
+        copy_str_to_buf(&response, response_buf, response_buf_len);
+        casper_error_t::CASPER_SUCCESS
+    })
+}
+
+/// Retrieves a contract package from global state, listing its versions, whether each is enabled,
+/// and its user groups.
+///
+/// The result is written to `response_buf` as JSON.
+///
+/// See [super::get_contract_package](super::get_contract_package) for more details.
+#[no_mangle]
+pub extern "C" fn casper_get_contract_package(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbose: bool,
+    state_root_hash: *const c_char,
+    contract_package_hash: *const c_char,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let mut runtime = RUNTIME.lock().expect("should lock");
+    let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
+    let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
+    let node_address = try_unsafe_arg!(node_address);
+    let state_root_hash = try_unsafe_arg!(state_root_hash);
+    let contract_package_hash = try_unsafe_arg!(contract_package_hash);
+    runtime.block_on(async move {
+        let result = super::get_contract_package(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            state_root_hash,
+            contract_package_hash,
+        );
+        let value = try_unwrap_result!(result);
+        let response = try_unwrap_result!(serde_json::to_string(&value).map_err(Into::into));
+        copy_str_to_buf(&response, response_buf, response_buf_len);
+        casper_error_t::CASPER_SUCCESS
+    })
+}
+
+/// Retrieves the successful native transfers to an account or purse, oldest first.
+///
+/// See [super::get_transfers_to](super::get_transfers_to) for more details.
+#[no_mangle]
+pub extern "C" fn casper_get_transfers_to(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbose: bool,
+    target: *const c_char,
+    maybe_since_block_id: *const c_char,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let mut runtime = RUNTIME.lock().expect("should lock");
+    let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
+    let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
+    let node_address = try_unsafe_arg!(node_address);
+    let target = try_unsafe_arg!(target);
+    let maybe_since_block_id = try_unsafe_arg!(maybe_since_block_id);
+    runtime.block_on(async move {
+        let result = super::get_transfers_to(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            target,
+            maybe_since_block_id,
+        );
+        let response = try_unwrap_rpc!(result);
+        copy_str_to_buf(&response, response_buf, response_buf_len);
+        casper_error_t::CASPER_SUCCESS
+    })
+}
+
+/// Retrieves the bids and validators as of the most recently added `Block`.
+///
+/// See [super::get_auction_info](super::get_auction_info) for more details.
+#[no_mangle]
+pub extern "C" fn casper_get_auction_info(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbose: bool,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let mut runtime = RUNTIME.lock().expect("should lock");
+    let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
+    let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
+    let node_address = try_unsafe_arg!(node_address);
+    runtime.block_on(async move {
+        let result = super::get_auction_info(maybe_rpc_id, node_address, verbose);
+        let response = try_unwrap_rpc!(result);
+        copy_str_to_buf(&response, response_buf, response_buf_len);
+        casper_error_t::CASPER_SUCCESS
+    })
+}
+
+/// Retrieves the stakes of a delegator across the bids of all validators, along with its pending
+/// undelegations.
+///
+/// See [super::get_delegations](super::get_delegations) for more details.
+#[no_mangle]
+pub extern "C" fn casper_get_delegations(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbose: bool,
+    public_key: *const c_char,
+    maybe_block_id: *const c_char,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let mut runtime = RUNTIME.lock().expect("should lock");
+    let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
+    let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
+    let node_address = try_unsafe_arg!(node_address);
+    let public_key = try_unsafe_arg!(public_key);
+    let maybe_block_id = try_unsafe_arg!(maybe_block_id);
+    runtime.block_on(async move {
+        let result = super::get_delegations(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            public_key,
+            maybe_block_id,
+        );
+        let response = try_unwrap_rpc!(result);
+        copy_str_to_buf(&response, response_buf, response_buf_len);
+        casper_error_t::CASPER_SUCCESS
+    })
+}
+
+/// Retrieves the amounts in the unbonding queue as of the most recently added `Block`, along with
+/// the estimated time at which each will be released.
+///
+/// See [super::get_unbonding_purses](super::get_unbonding_purses) for more details.
+#[no_mangle]
+pub extern "C" fn casper_get_unbonding_purses(
+    maybe_rpc_id: *const c_char,
+    node_address: *const c_char,
+    verbose: bool,
+    maybe_validator: *const c_char,
+    maybe_delegator: *const c_char,
+    response_buf: *mut c_uchar,
+    response_buf_len: usize,
+) -> casper_error_t {
+    let mut runtime = RUNTIME.lock().expect("should lock");
+    let runtime = try_unwrap_option!(&mut *runtime, or_else => Error::FFISetupNotCalled);
+    let maybe_rpc_id = try_unsafe_arg!(maybe_rpc_id);
+    let node_address = try_unsafe_arg!(node_address);
+    let maybe_validator = try_unsafe_arg!(maybe_validator);
+    let maybe_delegator = try_unsafe_arg!(maybe_delegator);
+    runtime.block_on(async move {
+        let result = super::get_unbonding_purses(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            maybe_validator,
+            maybe_delegator,
+        );
+        let response = try_unwrap_rpc!(result);
+        copy_str_to_buf(&response, response_buf, response_buf_len);
+        casper_error_t::CASPER_SUCCESS
+    })
+}
+
+/// Generates a new asymmetric key pair and writes the key files to `output_dir`.
+///
+/// If `maybe_passphrase` is null or empty, the secret key is written unencrypted.
+///
+/// See [super::keygen::generate_files](super::keygen::generate_files) for more details.
+#[no_mangle]
+pub extern "C" fn casper_keygen(
+    output_dir: *const c_char,
+    algorithm: *const c_char,
+    maybe_passphrase: *const c_char,
+    force: bool,
+) -> casper_error_t {
+    let output_dir = try_unsafe_arg!(output_dir);
+    let algorithm = try_unsafe_arg!(algorithm);
+    let maybe_passphrase = try_unsafe_arg!(maybe_passphrase);
+    let maybe_passphrase = if maybe_passphrase.is_empty() {
+        None
+    } else {
+        Some(maybe_passphrase.as_bytes())
+    };
+    let result = super::keygen::generate_files(output_dir, algorithm, maybe_passphrase, force);
+    try_unwrap_result!(result);
+    casper_error_t::CASPER_SUCCESS
+}
+
+/// Container for `Deploy` construction options.
+///
+/// See [DeployStrParams](super::DeployStrParams) for more info.
+#[allow(non_snake_case)]
+#[repr(C)]
+#[derive(Clone)]
+pub struct casper_deploy_params_t {
+    secret_key: *const c_char,
+    timestamp: *const c_char,
+    ttl: *const c_char,
+    gas_price: *const c_char,
+    dependencies: *const *const c_char,
+    dependencies_len: usize,
+    chain_name: *const c_char,
+}
+
+impl TryInto<super::DeployStrParams<'_>> for casper_deploy_params_t {
+    type Error = Error;
+
+    fn try_into(self) -> Result<super::DeployStrParams<'static>> {
+        let secret_key = unsafe_str_arg(self.secret_key, "casper_deploy_params_t.secret_key")?;
+        let timestamp = unsafe_str_arg(self.timestamp, "casper_deploy_params_t.timestamp")?;
         let ttl = unsafe_str_arg(self.ttl, "casper_deploy_params_t.ttl")?;
         let gas_price = unsafe_str_arg(self.gas_price, "casper_deploy_params_t.gas_price")?;
         let chain_name = unsafe_str_arg(self.chain_name, "casper_deploy_params_t.chain_name")?;
@@ -616,8 +1602,10 @@ pub struct casper_payment_params_t {
     payment_args_simple: *const *const c_char,
     payment_args_simple_len: usize,
     payment_args_complex: *const c_char,
+    payment_args_json: *const c_char,
     payment_version: *const c_char,
     payment_entry_point: *const c_char,
+    payment_purse_name: *const c_char,
 }
 
 impl TryInto<super::PaymentStrParams<'static>> for casper_payment_params_t {
@@ -651,6 +1639,10 @@ impl TryInto<super::PaymentStrParams<'static>> for casper_payment_params_t {
             self.payment_args_complex,
             "casper_payment_params_t.payment_args_complex",
         )?;
+        let payment_args_json = unsafe_str_arg(
+            self.payment_args_json,
+            "casper_payment_params_t.payment_args_json",
+        )?;
         let payment_version = unsafe_str_arg(
             self.payment_version,
             "casper_payment_params_t.payment_version",
@@ -659,6 +1651,10 @@ impl TryInto<super::PaymentStrParams<'static>> for casper_payment_params_t {
             self.payment_entry_point,
             "casper_payment_params_t.payment_entry_point",
         )?;
+        let payment_purse_name = unsafe_str_arg(
+            self.payment_purse_name,
+            "casper_payment_params_t.payment_purse_name",
+        )?;
         Ok(super::PaymentStrParams {
             payment_amount,
             payment_hash,
@@ -668,8 +1664,10 @@ impl TryInto<super::PaymentStrParams<'static>> for casper_payment_params_t {
             payment_path,
             payment_args_simple,
             payment_args_complex,
+            payment_args_json,
             payment_version,
             payment_entry_point,
+            payment_purse_name,
         })
     }
 }
@@ -689,6 +1687,7 @@ pub struct casper_session_params_t {
     session_args_simple: *const *const c_char,
     session_args_simple_len: usize,
     session_args_complex: *const c_char,
+    session_args_json: *const c_char,
     session_version: *const c_char,
     session_entry_point: *const c_char,
 }
@@ -720,6 +1719,10 @@ impl TryInto<super::SessionStrParams<'static>> for casper_session_params_t {
             self.session_args_complex,
             "casper_session_params_t.session_args_complex",
         )?;
+        let session_args_json = unsafe_str_arg(
+            self.session_args_json,
+            "casper_session_params_t.session_args_json",
+        )?;
         let session_version = unsafe_str_arg(
             self.session_version,
             "casper_session_params_t.session_version",
@@ -736,6 +1739,7 @@ impl TryInto<super::SessionStrParams<'static>> for casper_session_params_t {
             session_path,
             session_args_simple,
             session_args_complex,
+            session_args_json,
             session_version,
             session_entry_point,
         })
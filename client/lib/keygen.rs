@@ -28,10 +28,18 @@ pub const SECP256K1: &str = "secp256k1";
 /// and also in hex format to "public_key_hex". For the hex format, the algorithm's tag is
 /// prepended, e.g. `01` for Ed25519, `02` for secp256k1.
 ///
+/// If `maybe_passphrase` is `Some`, the secret key is written as an encrypted PKCS#8 PEM file
+/// protected by the given passphrase.
+///
 /// If `force` is true, existing files will be overwritten. If `force` is false and any of the
 /// files exist, [`Error::FileAlreadyExists`](../enum.Error.html#variant.FileAlreadyExists) is
 /// returned and no files are written.
-pub fn generate_files(output_dir: &str, algorithm: &str, force: bool) -> Result<()> {
+pub fn generate_files(
+    output_dir: &str,
+    algorithm: &str,
+    maybe_passphrase: Option<&[u8]>,
+    force: bool,
+) -> Result<()> {
     let _ = fs::create_dir_all(output_dir).map_err(|error| Error::IoError {
         context: format!("unable to create directory at '{}'", output_dir),
         error,
@@ -71,10 +79,84 @@ pub fn generate_files(output_dir: &str, algorithm: &str, force: bool) -> Result<
     })?;
 
     let secret_key_path = output_dir.join(SECRET_KEY_PEM);
-    secret_key.to_file(&secret_key_path)?;
+    match maybe_passphrase {
+        Some(passphrase) => secret_key.to_encrypted_file(&secret_key_path, passphrase)?,
+        None => secret_key.to_file(&secret_key_path)?,
+    }
 
     let public_key_path = output_dir.join(PUBLIC_KEY_PEM);
     public_key.to_file(&public_key_path)?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use casper_node::crypto::asymmetric_key;
+
+    const MESSAGE: &[u8] = b"message";
+    const PASSPHRASE: &[u8] = b"passphrase";
+
+    /// Checks the generated key files agree with each other, and that both signatures and account
+    /// hashes produced from them agree with the node's and casper-types' view.
+    fn generated_files_roundtrip(algorithm: &str, maybe_passphrase: Option<&[u8]>) {
+        let tempdir = tempfile::tempdir().unwrap();
+        let output_dir = tempdir.path().to_str().unwrap();
+        generate_files(output_dir, algorithm, maybe_passphrase, false).unwrap();
+
+        let secret_key_path = tempdir.path().join(SECRET_KEY_PEM);
+        let secret_key = match maybe_passphrase {
+            Some(passphrase) => {
+                SecretKey::from_encrypted_file(&secret_key_path, passphrase).unwrap()
+            }
+            None => SecretKey::from_file(&secret_key_path).unwrap(),
+        };
+        let public_key = PublicKey::from_file(tempdir.path().join(PUBLIC_KEY_PEM)).unwrap();
+        let public_key_hex = fs::read_to_string(tempdir.path().join(PUBLIC_KEY_HEX)).unwrap();
+        assert_eq!(PublicKey::from(&secret_key), public_key);
+        assert_eq!(PublicKey::from_hex(&public_key_hex).unwrap(), public_key);
+
+        let mut rng = casper_node::new_rng();
+        let signature = asymmetric_key::sign(MESSAGE, &secret_key, &public_key, &mut rng);
+        asymmetric_key::verify(MESSAGE, &signature, &public_key).unwrap();
+
+        let account_hash = crate::account_address(&public_key_hex).unwrap();
+        assert_eq!(account_hash, public_key.to_account_hash());
+        assert_eq!(
+            account_hash,
+            casper_types::account::AccountHash::from(casper_types::PublicKey::from(public_key))
+        );
+    }
+
+    #[test]
+    fn should_generate_ed25519_files() {
+        generated_files_roundtrip(ED25519, None);
+    }
+
+    #[test]
+    fn should_generate_secp256k1_files() {
+        generated_files_roundtrip(SECP256K1, None);
+    }
+
+    #[test]
+    fn should_generate_encrypted_ed25519_files() {
+        generated_files_roundtrip(ED25519, Some(PASSPHRASE));
+    }
+
+    #[test]
+    fn should_generate_encrypted_secp256k1_files() {
+        generated_files_roundtrip(SECP256K1, Some(PASSPHRASE));
+    }
+
+    #[test]
+    fn should_reject_unsupported_algorithm() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let output_dir = tempdir.path().to_str().unwrap();
+        assert!(matches!(
+            generate_files(output_dir, "rsa", None, false),
+            Err(Error::UnsupportedAlgorithm(_))
+        ));
+    }
+}
@@ -13,7 +13,10 @@
 )]
 
 mod cl_type;
+mod cl_value;
+mod contract_package;
 mod deploy;
+mod deploy_builder;
 mod error;
 mod executable_deploy_item_ext;
 #[cfg(feature = "ffi")]
@@ -21,24 +24,36 @@ pub mod ffi;
 pub mod keygen;
 mod parsing;
 mod rpc;
+mod state_snapshot;
 mod validation;
+mod wait;
 
 use std::convert::TryInto;
 
 use jsonrpc_lite::JsonRpc;
 
 use casper_execution_engine::core::engine_state::ExecutableDeployItem;
-use casper_node::types::Deploy;
-use casper_types::{UIntParseError, U512};
+use casper_node::{
+    crypto::hash::Digest,
+    types::{Deploy, DeployHash},
+    SimulationReport,
+};
+use casper_types::{account::AccountHash, Key, UIntParseError, U512};
 
 pub use cl_type::help;
+pub use cl_value::{decode_cl_value, decode_cl_values};
+pub use contract_package::{ContractPackageInfo, ContractVersionInfo};
 pub use deploy::ListDeploysResult;
 use deploy::{DeployExt, DeployParams};
+pub use deploy_builder::{DeployBuilder, DeployCode, Missing};
 pub use error::Error;
 use error::Result;
 use executable_deploy_item_ext::ExecutableDeployItemExt;
 use parsing::none_if_empty;
 use rpc::{RpcCall, TransferTarget};
+pub use rpc::{LATEST_SWITCH_BLOCK, SWITCH_BLOCK_PREFIX};
+pub use state_snapshot::{StateSnapshot, StateSnapshotEntry};
+pub use wait::{DeployCriteria, DeployStatus};
 
 /// Creates a `Deploy` and sends it to the network for execution.
 ///
@@ -54,6 +69,9 @@ use rpc::{RpcCall, TransferTarget};
 ///   [`SessionStrParams`](struct.SessionStrParams.html) for more details.
 /// * `payment` contains payment-related options for this `Deploy`. See
 ///   [`PaymentStrParams`](struct.PaymentStrParams.html) for more details.
+///
+/// If the node is at capacity, returns `Error::NodeAtCapacity` holding the number of seconds the
+/// node advised waiting before retrying.
 pub fn put_deploy(
     maybe_rpc_id: &str,
     node_address: &str,
@@ -70,6 +88,80 @@ pub fn put_deploy(
     RpcCall::new(maybe_rpc_id, node_address, verbose)?.put_deploy(deploy)
 }
 
+/// Creates a `Deploy` and sends it to the network to estimate its cost.
+///
+/// The `Deploy` is executed against the state of the most recently added `Block`, but is neither
+/// stored nor gossiped by the node, and the effects of its execution are never committed.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbose` is `true`, the JSON-RPC request will be printed to `stdout`.
+/// * `deploy` contains deploy-related options for this `Deploy`. See
+///   [`DeployStrParams`](struct.DeployStrParams.html) for more details.
+/// * `session` contains session-related options for this `Deploy`. See
+///   [`SessionStrParams`](struct.SessionStrParams.html) for more details.
+/// * `payment` contains payment-related options for this `Deploy`. See
+///   [`PaymentStrParams`](struct.PaymentStrParams.html) for more details.
+/// * When `profile_gas` is `true`, the response includes a breakdown of the gas consumed by host
+///   function and by opcode cost category.
+pub fn estimate_deploy_cost(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbose: bool,
+    deploy: DeployStrParams<'_>,
+    session: SessionStrParams<'_>,
+    payment: PaymentStrParams<'_>,
+    profile_gas: bool,
+) -> Result<JsonRpc> {
+    let deploy = Deploy::with_payment_and_session(
+        deploy.try_into()?,
+        payment.try_into()?,
+        session.try_into()?,
+    );
+    RpcCall::new(maybe_rpc_id, node_address, verbose)?.estimate_deploy_cost(deploy, profile_gas)
+}
+
+/// Creates a `Deploy` and executes it locally, without a node, returning the effects and cost of
+/// its execution.
+///
+/// The `Deploy` is executed by the execution engine against a fresh global state resulting from
+/// genesis as specified by a chainspec, or against an existing global state, e.g. a copy of a
+/// node's storage directory.  The effects of the execution are never committed.
+///
+/// * `chainspec_path` is the path to the chainspec from which to construct a fresh global state.
+///   Must be empty if `storage_path` is given.
+/// * `storage_path` is the path to a directory holding an existing global state.  Must be empty if
+///   `chainspec_path` is given.  It must not be in use by a running node.
+/// * `state_root_hash` is the hex-encoded state root hash to execute the `Deploy` on top of, and is
+///   required if and only if `storage_path` is given.
+/// * `deploy` contains deploy-related options for this `Deploy`. See
+///   [`DeployStrParams`](struct.DeployStrParams.html) for more details.
+/// * `session` contains session-related options for this `Deploy`. See
+///   [`SessionStrParams`](struct.SessionStrParams.html) for more details.
+/// * `payment` contains payment-related options for this `Deploy`. See
+///   [`PaymentStrParams`](struct.PaymentStrParams.html) for more details.
+pub fn simulate_deploy(
+    chainspec_path: &str,
+    storage_path: &str,
+    state_root_hash: &str,
+    deploy: DeployStrParams<'_>,
+    session: SessionStrParams<'_>,
+    payment: PaymentStrParams<'_>,
+) -> Result<SimulationReport> {
+    let state = parsing::simulation_state(chainspec_path, storage_path, state_root_hash)?;
+    let deploy = Deploy::with_payment_and_session(
+        deploy.try_into()?,
+        payment.try_into()?,
+        session.try_into()?,
+    );
+    // The deploy is executed as if included in a block created at the deploy's own timestamp.
+    let block_time = deploy.header().timestamp();
+    Ok(casper_node::simulate_deploy(state, deploy, block_time)?)
+}
+
 /// Creates a `Deploy` and outputs it to a file or stdout.
 ///
 /// As a file, the `Deploy` can subsequently be signed by other parties using
@@ -116,6 +208,16 @@ pub fn sign_deploy_file(input_path: &str, secret_key: &str, maybe_output_path: &
     Deploy::sign_deploy_file(&input_path, secret_key, maybe_output)
 }
 
+/// Returns the account hash of the account with the given public key.
+///
+/// * `public_key` is the hex-encoded public key of either the Ed25519 or secp256k1 algorithm, with
+///   the algorithm's tag prefixed, e.g. as written to "public_key_hex" by
+///   [`keygen::generate_files`](keygen/fn.generate_files.html).
+pub fn account_address(public_key: &str) -> Result<AccountHash> {
+    let public_key = parsing::public_key(public_key)?;
+    Ok(public_key.to_account_hash())
+}
+
 /// Reads a previously-saved `Deploy` from a file and sends it to the network for execution.
 ///
 /// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
@@ -125,13 +227,43 @@ pub fn sign_deploy_file(input_path: &str, secret_key: &str, maybe_output_path: &
 ///   running, e.g. `"http://127.0.0.1:7777"`.
 /// * When `verbose` is `true`, the JSON-RPC request will be printed to `stdout`.
 /// * `input_path` specifies the path to the previously-saved `Deploy` file.
+/// * `maybe_idempotency_key` is a key identifying this submission, e.g. a random UUID, or empty.
+///   Resending the `Deploy` with the same key, e.g. after a timeout, returns the original
+///   acceptance with `"replayed": true` rather than submitting the `Deploy` again.
+///
+/// If the node is at capacity, returns `Error::NodeAtCapacity` holding the number of seconds the
+/// node advised waiting before retrying.
 pub fn send_deploy_file(
     maybe_rpc_id: &str,
     node_address: &str,
     verbose: bool,
     input_path: &str,
+    maybe_idempotency_key: &str,
+) -> Result<JsonRpc> {
+    RpcCall::new(maybe_rpc_id, node_address, verbose)?
+        .send_deploy_file(input_path, maybe_idempotency_key)
+}
+
+/// Reads a previously-saved `Deploy` from a file and has the node check it, without sending it to
+/// the network.
+///
+/// The response lists every reason for which the node would currently refuse the `Deploy`, such as
+/// its size, TTL, chain name, payment or signatures.  The list is empty if it would be accepted.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbose` is `true`, the JSON-RPC request will be printed to `stdout`.
+/// * `input_path` specifies the path to the previously-saved `Deploy` file.
+pub fn check_deploy_file(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbose: bool,
+    input_path: &str,
 ) -> Result<JsonRpc> {
-    RpcCall::new(maybe_rpc_id, node_address, verbose)?.send_deploy_file(input_path)
+    RpcCall::new(maybe_rpc_id, node_address, verbose)?.check_deploy_file(input_path)
 }
 
 /// Transfers funds between purses.
@@ -186,6 +318,130 @@ pub fn transfer(
     )
 }
 
+/// Creates a `Deploy` which disables a version of a stored contract, and sends it to the network
+/// for execution.
+///
+/// The account signing the `Deploy` must hold the contract package's access key in its named keys.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbose` is `true`, the JSON-RPC request will be printed to `stdout`.
+/// * `session_path` is the path to the compiled `disable_contract_version.wasm`.
+/// * `contract_package_hash` is the hash of the contract package, either hex-encoded or formatted
+///   as a `Key::Hash`, e.g. `"hash-0102..."`.
+/// * `contract_hash` is the hash of the version to disable, given in the same forms.
+/// * `deploy` contains deploy-related options for this `Deploy`. See
+///   [`DeployStrParams`](struct.DeployStrParams.html) for more details.
+/// * `payment` contains payment-related options for this `Deploy`. See
+///   [`PaymentStrParams`](struct.PaymentStrParams.html) for more details.
+#[allow(clippy::too_many_arguments)]
+pub fn disable_contract_version(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbose: bool,
+    session_path: &str,
+    contract_package_hash: &str,
+    contract_hash: &str,
+    deploy: DeployStrParams<'_>,
+    payment: PaymentStrParams<'_>,
+) -> Result<JsonRpc> {
+    let contract_package_hash = parsing::parse_contract_hash(contract_package_hash)?;
+    let contract_hash = parsing::parse_contract_hash(contract_hash)?;
+    let session =
+        DeployCode::disable_contract_version(session_path, contract_package_hash, contract_hash)?
+            .into_executable_deploy_item()?;
+    let deploy = Deploy::with_payment_and_session(deploy.try_into()?, payment.try_into()?, session);
+    RpcCall::new(maybe_rpc_id, node_address, verbose)?.put_deploy(deploy)
+}
+
+/// Creates a `Deploy` which adds a key to the named keys of the account signing it, and sends it to
+/// the network for execution.
+///
+/// Any key already held under `name` is replaced.  The session code is the `put_named_key.wasm`
+/// embedded in this crate, unless overridden by `maybe_session_path`.
+///
+/// Only an account's own named keys can be managed this way: adding to or removing from a stored
+/// contract's named keys is not supported, as they can only be changed by the contract's own entry
+/// points.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbose` is `true`, the JSON-RPC request will be printed to `stdout`.
+/// * `maybe_session_path` is the path to a compiled `put_named_key.wasm` to use instead of the
+///   embedded one, or empty.
+/// * `name` is the name under which the key is added.
+/// * `key` must be a formatted `Key`, e.g. `"uref-0102...-007"` or `"hash-0102..."`.
+/// * `deploy` contains deploy-related options for this `Deploy`. See
+///   [`DeployStrParams`](struct.DeployStrParams.html) for more details.
+/// * `payment` contains payment-related options for this `Deploy`. See
+///   [`PaymentStrParams`](struct.PaymentStrParams.html) for more details.
+#[allow(clippy::too_many_arguments)]
+pub fn put_named_key(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbose: bool,
+    maybe_session_path: &str,
+    name: &str,
+    key: &str,
+    deploy: DeployStrParams<'_>,
+    payment: PaymentStrParams<'_>,
+) -> Result<JsonRpc> {
+    let key = Key::from_formatted_str(key).map_err(|_| Error::FailedToParseKey)?;
+    let mut session = DeployCode::put_named_key(name, key);
+    if let Some(session_path) = none_if_empty(maybe_session_path) {
+        session = session.with_module_file(session_path)?;
+    }
+    let session = session.into_executable_deploy_item()?;
+    let deploy = Deploy::with_payment_and_session(deploy.try_into()?, payment.try_into()?, session);
+    RpcCall::new(maybe_rpc_id, node_address, verbose)?.put_deploy(deploy)
+}
+
+/// Creates a `Deploy` which removes a key from the named keys of the account signing it, and sends
+/// it to the network for execution.
+///
+/// Removing a name the account doesn't hold has no effect.  The session code is the
+/// `remove_named_key.wasm` embedded in this crate, unless overridden by `maybe_session_path`.
+///
+/// Only an account's own named keys can be managed this way: removing from a stored contract's
+/// named keys is not supported.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbose` is `true`, the JSON-RPC request will be printed to `stdout`.
+/// * `maybe_session_path` is the path to a compiled `remove_named_key.wasm` to use instead of the
+///   embedded one, or empty.
+/// * `name` is the name of the key to remove.
+/// * `deploy` contains deploy-related options for this `Deploy`. See
+///   [`DeployStrParams`](struct.DeployStrParams.html) for more details.
+/// * `payment` contains payment-related options for this `Deploy`. See
+///   [`PaymentStrParams`](struct.PaymentStrParams.html) for more details.
+pub fn remove_named_key(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbose: bool,
+    maybe_session_path: &str,
+    name: &str,
+    deploy: DeployStrParams<'_>,
+    payment: PaymentStrParams<'_>,
+) -> Result<JsonRpc> {
+    let mut session = DeployCode::remove_named_key(name);
+    if let Some(session_path) = none_if_empty(maybe_session_path) {
+        session = session.with_module_file(session_path)?;
+    }
+    let session = session.into_executable_deploy_item()?;
+    let deploy = Deploy::with_payment_and_session(deploy.try_into()?, payment.try_into()?, session);
+    RpcCall::new(maybe_rpc_id, node_address, verbose)?.put_deploy(deploy)
+}
+
 /// Retrieves a `Deploy` from the network.
 ///
 /// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
@@ -204,6 +460,44 @@ pub fn get_deploy(
     RpcCall::new(maybe_rpc_id, node_address, verbose)?.get_deploy(deploy_hash)
 }
 
+/// Polls the network until a `Deploy` satisfies the given criteria, returning its status.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to each request and returned in the
+///   responses. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbose` is `true`, the JSON-RPC requests will be printed to `stdout`.
+/// * `deploy_hash` must be a hex-encoded, 32-byte hash digest.
+/// * `criteria` must be `"included"` for the `Deploy` to be included in a `Block` on the linear
+///   chain, `"executed"` for its execution result to also be known, or `"finalized"` for the
+///   `Block` to also be signed by validators holding at least one third of the weight of its era.
+/// * `timeout` is the time to wait before giving up, e.g. `"5min"`.  See [the `humantime` docs](https://docs.rs/humantime/latest/humantime/fn.parse_duration.html)
+///   for all supported formats.
+///
+/// The node is polled at intervals doubling from one second up to 16 seconds.  Errors which may be
+/// transient, e.g. the `Deploy` not being known yet or the node being unreachable, cause another
+/// poll, while responses failing validation are returned as errors.  If the `Deploy` doesn't
+/// satisfy the criteria before the timeout elapses, returns `Error::WaitForDeployTimedOut`.
+///
+/// The validators' signatures are weighed against the stakes recorded by the switch `Block` of
+/// the previous era, so `"finalized"` can't be verified for `Block`s of the genesis era.
+pub fn wait_for_deploy(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbose: bool,
+    deploy_hash: &str,
+    criteria: &str,
+    timeout: &str,
+) -> Result<DeployStatus> {
+    let deploy_hash = DeployHash::new(Digest::from_hex(deploy_hash)?);
+    let criteria = criteria.parse()?;
+    let timeout = humantime::parse_duration(timeout)
+        .map_err(|error| Error::FailedToParseTimeDiff("timeout", error))?;
+    let rpc_call = RpcCall::new(maybe_rpc_id, node_address, verbose)?;
+    wait::wait_for_deploy(rpc_call, deploy_hash, criteria, timeout)
+}
+
 /// Retrieves a `Block` from the network.
 ///
 /// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
@@ -212,8 +506,10 @@ pub fn get_deploy(
 /// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
 ///   running, e.g. `"http://127.0.0.1:7777"`.
 /// * When `verbose` is `true`, the JSON-RPC request will be printed to `stdout`.
-/// * `maybe_block_id` must be a hex-encoded, 32-byte hash digest or a `u64` representing the
-///   `Block` height or empty. If empty, the latest `Block` will be retrieved.
+/// * `maybe_block_id` must be a hex-encoded, 32-byte hash digest, a `u64` representing the `Block`
+///   height, `"latest-switch"` for the most recent switch `Block`, `"era-"` followed by an era ID
+///   for the switch `Block` of that era, an RFC 3339 timestamp for the most recent `Block` not
+///   later than it, or empty. If empty, the latest `Block` will be retrieved.
 pub fn get_block(
     maybe_rpc_id: &str,
     node_address: &str,
@@ -223,6 +519,248 @@ pub fn get_block(
     RpcCall::new(maybe_rpc_id, node_address, verbose)?.get_block(maybe_block_id)
 }
 
+/// Retrieves a contiguous range of `Block`s from the network.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbose` is `true`, the JSON-RPC request will be printed to `stdout`.
+/// * `start_height` and `end_height` are the `u64` heights of the first and last `Block` in the
+///   range, both inclusive.
+/// * When `headers_only` is `true`, only the `BlockHeader`s are returned, in the response's
+///   `headers`, rather than the full `Block`s.
+///
+/// The node limits the number of `Block`s returned per call. If the range was cut short, the
+/// response's `next_height` indicates where to resume.
+pub fn get_blocks(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbose: bool,
+    start_height: &str,
+    end_height: &str,
+    headers_only: bool,
+) -> Result<JsonRpc> {
+    let start_height = start_height
+        .parse()
+        .map_err(|error| Error::FailedToParseInt("start_height", error))?;
+    let end_height = end_height
+        .parse()
+        .map_err(|error| Error::FailedToParseInt("end_height", error))?;
+    RpcCall::new(maybe_rpc_id, node_address, verbose)?.get_blocks(
+        start_height,
+        end_height,
+        headers_only,
+    )
+}
+
+/// Retrieves the `Block`s at the given height which conflict with the `Block` on the linear chain.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbose` is `true`, the JSON-RPC request will be printed to `stdout`.
+/// * `height` is the `u64` height of the `Block`s.
+///
+/// Only `Block`s which reached the node's storage are returned, so an empty list does not imply
+/// that no other `Block` was proposed at that height.
+pub fn get_alternative_blocks(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbose: bool,
+    height: &str,
+) -> Result<JsonRpc> {
+    let height = height
+        .parse()
+        .map_err(|error| Error::FailedToParseInt("height", error))?;
+    RpcCall::new(maybe_rpc_id, node_address, verbose)?.get_alternative_blocks(height)
+}
+
+/// Retrieves the header of the `Block` on the linear chain including a `Deploy`, along with the
+/// `Deploy`'s index within the `Block`.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbose` is `true`, the JSON-RPC request will be printed to `stdout`.
+/// * `deploy_hash` must be a hex-encoded, 32-byte hash digest.
+///
+/// The response is validated: the `Block` header must hash to the returned `Block` hash and must
+/// list the `Deploy` at the returned index.
+pub fn get_block_by_deploy(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbose: bool,
+    deploy_hash: &str,
+) -> Result<JsonRpc> {
+    RpcCall::new(maybe_rpc_id, node_address, verbose)?.get_block_by_deploy(deploy_hash)
+}
+
+/// Retrieves the header of the most recent `Block` on the linear chain not later than a given
+/// timestamp, along with the header of the next `Block` if known to the node.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbose` is `true`, the JSON-RPC request will be printed to `stdout`.
+/// * `timestamp` must be an RFC 3339 timestamp, e.g. `"2020-11-30T23:59:59Z"`.
+///
+/// The response is validated: the `Block` header must hash to the returned `Block` hash and must
+/// not be later than `timestamp`, while the next `Block` header, if any, must follow it and be
+/// later than `timestamp`.  The `Block`'s state root hash can then be passed to
+/// [`get_item`](fn.get_item.html) or [`get_balance`](fn.get_balance.html) to query global state as
+/// of `timestamp`, with the results validated against their Merkle proofs.
+pub fn get_block_at_timestamp(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbose: bool,
+    timestamp: &str,
+) -> Result<JsonRpc> {
+    let timestamp = timestamp
+        .parse()
+        .map_err(|error| Error::FailedToParseTimestamp("timestamp", error))?;
+    RpcCall::new(maybe_rpc_id, node_address, verbose)?.get_block_at_timestamp(timestamp)
+}
+
+/// Retrieves the summary of an era which has ended, i.e. the header of its switch `Block`
+/// (holding the era's rewards and equivocators) and the era info recorded by the auction contract.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbose` is `true`, the JSON-RPC request will be printed to `stdout`.
+/// * `maybe_era_id` must be a `u64` representing the era ID or empty.
+/// * `maybe_block_id` must be a hex-encoded, 32-byte hash digest or a `u64` representing the height
+///   of a `Block` in the requested era, or empty.
+///
+/// At most one of `maybe_era_id` and `maybe_block_id` may be provided. If both are empty, the
+/// most recently ended era will be used. The switch `Block` header and the Merkle proof of the
+/// era info are validated.
+pub fn get_era_summary(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbose: bool,
+    maybe_era_id: &str,
+    maybe_block_id: &str,
+) -> Result<JsonRpc> {
+    RpcCall::new(maybe_rpc_id, node_address, verbose)?.get_era_summary(maybe_era_id, maybe_block_id)
+}
+
+/// Retrieves a page of the rewards credited to each validator and delegator at the end of an era.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbose` is `true`, the JSON-RPC request will be printed to `stdout`.
+/// * `era_id` must be a `u64` representing the ID of an era which has ended.
+/// * `offset` must be a `u64` representing the index of the first reward to return, or empty for
+///   the first page.
+/// * `maybe_limit` must be a `u64` representing the maximum number of rewards to return, or empty
+///   for the node's maximum page size.
+///
+/// The response holds the offset of the next page, if any.
+pub fn get_validator_rewards(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbose: bool,
+    era_id: &str,
+    offset: &str,
+    maybe_limit: &str,
+) -> Result<JsonRpc> {
+    let era_id = era_id
+        .parse()
+        .map_err(|error| Error::FailedToParseInt("era_id", error))?;
+    let offset = if offset.is_empty() {
+        0
+    } else {
+        offset
+            .parse()
+            .map_err(|error| Error::FailedToParseInt("offset", error))?
+    };
+    let limit = if maybe_limit.is_empty() {
+        None
+    } else {
+        Some(
+            maybe_limit
+                .parse()
+                .map_err(|error| Error::FailedToParseInt("limit", error))?,
+        )
+    };
+    RpcCall::new(maybe_rpc_id, node_address, verbose)?.get_validator_rewards(era_id, offset, limit)
+}
+
+/// Retrieves the total supply of motes, along with a merkle proof, and optionally the circulating
+/// supply.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbose` is `true`, the JSON-RPC request will be printed to `stdout`.
+/// * `maybe_block_id` must be a hex-encoded, 32-byte hash digest or a `u64` representing the height
+///   of the `Block` as of which the supply is read, or empty. If empty, the latest `Block` will be
+///   used.
+/// * When `include_circulating_supply` is `true`, the response also holds the total supply less the
+///   balances of the accounts which the node is configured to exclude.
+pub fn get_supply(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbose: bool,
+    maybe_block_id: &str,
+    include_circulating_supply: bool,
+) -> Result<JsonRpc> {
+    RpcCall::new(maybe_rpc_id, node_address, verbose)?
+        .get_supply(maybe_block_id, include_circulating_supply)
+}
+
+/// Retrieves the status of the node signed with its validator key, and verifies the signature.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbose` is `true`, the JSON-RPC request will be printed to `stdout`.
+/// * `maybe_nonce` is included in the signed status, binding the signature to this request, and
+///   must be at most 128 bytes long. If empty, a random nonce will be used.
+/// * `maybe_public_key` is the hex-encoded public key with which the node is expected to sign, or
+///   empty. If not empty, a status signed with any other key is rejected.
+pub fn get_signed_status(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbose: bool,
+    maybe_nonce: &str,
+    maybe_public_key: &str,
+) -> Result<JsonRpc> {
+    RpcCall::new(maybe_rpc_id, node_address, verbose)?
+        .get_signed_status(maybe_nonce, maybe_public_key)
+}
+
+/// Retrieves the deploy limits, costs, era settings and protocol version of the chainspec active
+/// as of the most recently added `Block`, along with the hash of the whole chainspec.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbose` is `true`, the JSON-RPC request will be printed to `stdout`.
+pub fn get_chainspec(maybe_rpc_id: &str, node_address: &str, verbose: bool) -> Result<JsonRpc> {
+    RpcCall::new(maybe_rpc_id, node_address, verbose)?.get_chainspec()
+}
+
 /// Retrieves a state root hash at a given `Block`.
 ///
 /// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
@@ -231,8 +769,8 @@ pub fn get_block(
 /// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
 ///   running, e.g. `"http://127.0.0.1:7777"`.
 /// * When `verbose` is `true`, the JSON-RPC request will be printed to `stdout`.
-/// * `maybe_block_id` must be a hex-encoded, 32-byte hash digest or a `u64` representing the
-///   `Block` height or empty. If empty, the latest `Block` will be used.
+/// * `maybe_block_id` identifies the `Block` in any of the forms accepted by
+///   [`get_block`](fn.get_block.html), or is empty. If empty, the latest `Block` will be used.
 pub fn get_state_root_hash(
     maybe_rpc_id: &str,
     node_address: &str,
@@ -263,6 +801,13 @@ pub fn get_state_root_hash(
 /// deploy-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20      # Key::DeployInfo
 /// ```
 /// * `path` is comprised of components starting from the `key`, separated by `/`s.
+///
+/// If the queried key is absent from global state, the response holds a Merkle proof of its
+/// absence in place of a stored value, which is validated in the same way.
+///
+/// If the node only keeps the global state of its most recent blocks queryable and
+/// `state_root_hash` is older, returns `Error::StateRootOutsideWindow` holding the height of the
+/// earliest queryable block.
 pub fn get_item(
     maybe_rpc_id: &str,
     node_address: &str,
@@ -274,6 +819,60 @@ pub fn get_item(
     RpcCall::new(maybe_rpc_id, node_address, verbose)?.get_item(state_root_hash, key, path)
 }
 
+/// Retrieves a stored value from the network as of a given `Block`.
+///
+/// The state root hash of the `Block` is retrieved first, and then used as for
+/// [`get_item`](fn.get_item.html), with the same JSON-RPC identifier.
+///
+/// * `maybe_block_id` identifies the `Block` in any of the forms accepted by
+///   [`get_block`](fn.get_block.html), or is empty. If empty, the latest `Block` will be used.
+/// * The other arguments are as for [`get_item`](fn.get_item.html).
+pub fn get_item_at_block(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbose: bool,
+    maybe_block_id: &str,
+    key: &str,
+    path: &str,
+) -> Result<JsonRpc> {
+    RpcCall::new(maybe_rpc_id, node_address, verbose)?.get_item_at_block(maybe_block_id, key, path)
+}
+
+/// Captures the values stored under the given keys as of a given `Block` into a snapshot, for
+/// loading into an in-memory engine via `casper_engine_test_support::StateSnapshot`.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the requests and returned in the
+///   responses. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbose` is `true`, the JSON-RPC requests will be printed to `stdout`.
+/// * `maybe_block_id` identifies the `Block` in any of the forms accepted by
+///   [`get_block`](fn.get_block.html), or is empty. If empty, the latest `Block` will be used.
+/// * `keys` are formatted keys or public keys, in any of the forms accepted by
+///   [`get_item`](fn.get_item.html).
+/// * When `follow_references` is `true`, the values referenced by those read are captured too: an
+///   account's main purse balance, a contract's package and Wasm, and the value under any key
+///   stored as a `CLValue`.
+///
+/// Each value is checked against its merkle proof.  Returns an error if any key is absent from
+/// global state.
+pub fn get_state_snapshot(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbose: bool,
+    maybe_block_id: &str,
+    keys: &[&str],
+    follow_references: bool,
+) -> Result<StateSnapshot> {
+    StateSnapshot::capture(
+        RpcCall::new(maybe_rpc_id, node_address, verbose)?,
+        maybe_block_id,
+        keys,
+        follow_references,
+    )
+}
+
 /// Retrieves a purse's balance from the network.
 ///
 /// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
@@ -297,6 +896,281 @@ pub fn get_balance(
     RpcCall::new(maybe_rpc_id, node_address, verbose)?.get_balance(state_root_hash, purse)
 }
 
+/// Retrieves a purse's balance from the network, sampled at intervals over a range of `Block`s.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbose` is `true`, the JSON-RPC request will be printed to `stdout`.
+/// * `purse` is a URef, formatted as e.g.
+/// ```text
+/// uref-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20-007
+/// ```
+/// * `from_height` and `to_height` are the `u64` heights of the first and last `Block` of the
+///   range, both inclusive.
+/// * `step` is the `u64` number of `Block`s between consecutive samples, at least 1.
+/// * When `include_proofs` is `true`, each sampled balance is returned along with its Merkle proof,
+///   which is validated against the state root hash of the sample's `Block`.
+///
+/// The node limits the number of samples returned per call. If the samples were cut short, the
+/// response's `next_height` indicates where to resume.
+#[allow(clippy::too_many_arguments)]
+pub fn get_balance_history(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbose: bool,
+    purse: &str,
+    from_height: &str,
+    to_height: &str,
+    step: &str,
+    include_proofs: bool,
+) -> Result<JsonRpc> {
+    let from_height = from_height
+        .parse()
+        .map_err(|error| Error::FailedToParseInt("from_height", error))?;
+    let to_height = to_height
+        .parse()
+        .map_err(|error| Error::FailedToParseInt("to_height", error))?;
+    let step = step
+        .parse()
+        .map_err(|error| Error::FailedToParseInt("step", error))?;
+    RpcCall::new(maybe_rpc_id, node_address, verbose)?.get_balance_history(
+        purse,
+        from_height,
+        to_height,
+        step,
+        include_proofs,
+    )
+}
+
+/// Retrieves an account, with its named keys, associated keys, action thresholds and main purse,
+/// from the network.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbose` is `true`, the JSON-RPC request will be printed to `stdout`.
+/// * `account_identifier` is either a hex-encoded public key or a formatted account hash:
+/// ```text
+/// 01c9e33693951aaac23c49bee44ad6f863eedcd38c084a3a8f11237716a3df9c2c           # PublicKey
+/// account-hash-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20  # AccountHash
+/// ```
+/// * `maybe_block_id` must be a hex-encoded, 32-byte hash digest or a `u64` representing the
+///   `Block` height or empty. If empty, the latest `Block` will be used.
+///
+/// The Merkle proof returned by the node is validated against the returned state root hash.
+pub fn get_account_info(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbose: bool,
+    account_identifier: &str,
+    maybe_block_id: &str,
+) -> Result<JsonRpc> {
+    RpcCall::new(maybe_rpc_id, node_address, verbose)?
+        .get_account_info(account_identifier, maybe_block_id)
+}
+
+/// Retrieves the hashes of the `Deploy`s involving an account, most recent first.
+///
+/// A `Deploy` involves the account on whose behalf it is executed and, if it is a native transfer
+/// to an account, the target account.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbose` is `true`, the JSON-RPC request will be printed to `stdout`.
+/// * `account_identifier` is either a hex-encoded public key or a formatted account hash:
+/// ```text
+/// 01c9e33693951aaac23c49bee44ad6f863eedcd38c084a3a8f11237716a3df9c2c           # PublicKey
+/// account-hash-0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20  # AccountHash
+/// ```
+/// * `maybe_page` is the `u64` page of deploy hashes to retrieve, or empty.  Page 0, the default,
+///   holds the most recent ones.
+///
+/// The response's `total_deploys` gives the number of `Deploy`s held by the node for the account.
+/// Only `Deploy`s in `Block`s stored since the node started maintaining the index are included.
+pub fn get_account_deploys(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbose: bool,
+    account_identifier: &str,
+    maybe_page: &str,
+) -> Result<JsonRpc> {
+    let page = if maybe_page.is_empty() {
+        0
+    } else {
+        maybe_page
+            .parse()
+            .map_err(|error| Error::FailedToParseInt("page", error))?
+    };
+    RpcCall::new(maybe_rpc_id, node_address, verbose)?.get_account_deploys(account_identifier, page)
+}
+
+/// Retrieves the `Deploy`s which wrote to a global state key, most recent first, along with the
+/// `Block`s in which they were executed.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbose` is `true`, the JSON-RPC request will be printed to `stdout`.
+/// * `key` must be a formatted [`PublicKey`](https://docs.rs/casper-node/latest/casper-node/crypto/asymmetric_key/enum.PublicKey.html)
+///   or [`Key`](https://docs.rs/casper-types/latest/casper-types/enum.PublicKey.html), in any of
+///   the forms accepted by [`get_item`].  The access rights of a `URef` are ignored.
+/// * `maybe_page` is the `u64` page of the history to retrieve, or empty.  Page 0, the default,
+///   holds the most recent entries.
+///
+/// The response's `total_entries` gives the number of entries held by the node for the key.  Only
+/// `Deploy`s executed since the node started maintaining the index are included.
+pub fn get_key_history(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbose: bool,
+    key: &str,
+    maybe_page: &str,
+) -> Result<JsonRpc> {
+    let page = if maybe_page.is_empty() {
+        0
+    } else {
+        maybe_page
+            .parse()
+            .map_err(|error| Error::FailedToParseInt("page", error))?
+    };
+    RpcCall::new(maybe_rpc_id, node_address, verbose)?.get_key_history(key, page)
+}
+
+/// Retrieves a page of the global state keys whose serialized representation starts with a given
+/// prefix, along with their stored values and proofs of inclusion.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbose` is `true`, the JSON-RPC request will be printed to `stdout`.
+/// * `state_root_hash` must be a hex-encoded, 32-byte hash digest.
+/// * `prefix` is the hex-encoded prefix of the serialized keys, e.g. `"03"` for all
+///   `Key::Transfer`s.  An empty prefix matches every key.
+/// * `maybe_page` is the `u64` page of the matching keys to retrieve, or empty.  Page 0, the
+///   default, holds the first keys in the lexicographic order of their serialized representation.
+///
+/// A response holding fewer keys than the page size is the last page.
+pub fn get_keys_with_prefix(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbose: bool,
+    state_root_hash: &str,
+    prefix: &str,
+    maybe_page: &str,
+) -> Result<JsonRpc> {
+    let page = if maybe_page.is_empty() {
+        0
+    } else {
+        maybe_page
+            .parse()
+            .map_err(|error| Error::FailedToParseInt("page", error))?
+    };
+    RpcCall::new(maybe_rpc_id, node_address, verbose)?.get_keys_with_prefix(
+        state_root_hash,
+        prefix,
+        page,
+    )
+}
+
+/// Retrieves the entry points of a stored contract, documented by the contract's metadata if it
+/// has any.
+///
+/// The metadata is the JSON representation of a `casper_types::ContractMetadata`, stored as a
+/// `CLValue` under the contract's named key `"contract_metadata"`.  It gives the contract's entry
+/// points and their args human-readable descriptions, so that the response is enough for a generic
+/// UI to call the contract.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbose` is `true`, the JSON-RPC request will be printed to `stdout`.
+/// * `state_root_hash` must be a hex-encoded, 32-byte hash digest.
+/// * `contract_hash` is the hash of the contract, either hex-encoded or formatted as a `Key::Hash`,
+///   e.g. `"hash-0102..."`.
+pub fn get_contract_abi(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbose: bool,
+    state_root_hash: &str,
+    contract_hash: &str,
+) -> Result<JsonRpc> {
+    RpcCall::new(maybe_rpc_id, node_address, verbose)?
+        .get_contract_abi(state_root_hash, contract_hash)
+}
+
+/// Retrieves a contract package from global state, listing its versions, whether each is enabled,
+/// and its user groups.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbose` is `true`, the JSON-RPC request will be printed to `stdout`.
+/// * `state_root_hash` must be a hex-encoded, 32-byte hash digest.
+/// * `contract_package_hash` is the hash of the contract package, either hex-encoded or formatted
+///   as a `Key::Hash`, e.g. `"hash-0102..."`.
+///
+/// The response is checked against its merkle proof before being decoded.
+pub fn get_contract_package(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbose: bool,
+    state_root_hash: &str,
+    contract_package_hash: &str,
+) -> Result<ContractPackageInfo> {
+    let key = Key::Hash(parsing::parse_contract_hash(contract_package_hash)?);
+    let response = RpcCall::new(maybe_rpc_id, node_address, verbose)?.get_item(
+        state_root_hash,
+        &key.to_formatted_string(),
+        "",
+    )?;
+    ContractPackageInfo::from_get_item_response(&response)
+}
+
+/// Retrieves the successful native transfers to an account or purse, oldest first.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbose` is `true`, the JSON-RPC request will be printed to `stdout`.
+/// * `target` must be a formatted [`PublicKey`](https://docs.rs/casper-node/latest/casper-node/crypto/asymmetric_key/enum.PublicKey.html),
+///   [`AccountHash`](https://docs.rs/casper-types/latest/casper-types/account/struct.AccountHash.html)
+///   or [`URef`](https://docs.rs/casper-types/latest/casper-types/struct.URef.html) of a purse.
+/// * `maybe_since_block_id` is the hex-encoded hash or the height of the earliest `Block` whose
+///   transfers are to be retrieved, or empty to retrieve transfers from genesis onwards.
+///
+/// At most 100 transfers are returned per request, unless more are needed to complete the
+/// transfers of the last `Block`.  If more remain, the response's `next_since_block_height` gives
+/// the height from which to resume.  Only transfers executed since the node started maintaining the
+/// index are included.
+pub fn get_transfers_to(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbose: bool,
+    target: &str,
+    maybe_since_block_id: &str,
+) -> Result<JsonRpc> {
+    RpcCall::new(maybe_rpc_id, node_address, verbose)?
+        .get_transfers_to(target, maybe_since_block_id)
+}
+
 /// Retrieves the bids and validators as of the most recently added `Block`.
 ///
 /// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
@@ -309,6 +1183,53 @@ pub fn get_auction_info(maybe_rpc_id: &str, node_address: &str, verbose: bool) -
     RpcCall::new(maybe_rpc_id, node_address, verbose)?.get_auction_info()
 }
 
+/// Retrieves the stakes of a delegator across the bids of all validators, along with its pending
+/// undelegations.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbose` is `true`, the JSON-RPC request will be printed to `stdout`.
+/// * `public_key` is the hex-encoded public key of the delegator.
+/// * `maybe_block_id` must be a hex-encoded, 32-byte hash digest or a `u64` representing the height
+///   of the `Block` as of which the auction state is read, or empty. If empty, the latest `Block`
+///   will be used.
+pub fn get_delegations(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbose: bool,
+    public_key: &str,
+    maybe_block_id: &str,
+) -> Result<JsonRpc> {
+    RpcCall::new(maybe_rpc_id, node_address, verbose)?.get_delegations(public_key, maybe_block_id)
+}
+
+/// Retrieves the amounts in the unbonding queue as of the most recently added `Block`, along with
+/// the estimated time at which each will be released.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbose` is `true`, the JSON-RPC request will be printed to `stdout`.
+/// * `maybe_validator` is the hex-encoded public key of the validator whose bid the amounts were
+///   unbonded from, or empty. If empty, amounts unbonded from all bids are returned.
+/// * `maybe_delegator` is the hex-encoded public key of the validator or delegator which unbonded
+///   the amounts, or empty. If empty, amounts unbonded by anyone are returned.
+pub fn get_unbonding_purses(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbose: bool,
+    maybe_validator: &str,
+    maybe_delegator: &str,
+) -> Result<JsonRpc> {
+    RpcCall::new(maybe_rpc_id, node_address, verbose)?
+        .get_unbonding_purses(maybe_validator, maybe_delegator)
+}
+
 /// Container for `Deploy` construction options.
 #[derive(Default)]
 pub struct DeployStrParams<'a> {
@@ -371,10 +1292,12 @@ impl<'a> TryInto<DeployParams> for DeployStrParams<'a> {
 /// For methods taking `payment_args_simple`, this parameter is the payment contract arguments, in
 /// the form `<NAME:TYPE='VALUE'>` or `<NAME:TYPE=null>`.
 ///
-/// It can only be used with the following simple `CLType`s: bool, i32, i64, u8, u32, u64, u128,
-/// u256, u512, unit, string, key, account_hash, uref, public_key and `Option` of each of these.
+/// The simple `CLType`s are bool, i32, i64, u8, u32, u64, u128, u256, u512, unit, string, key,
+/// account_hash, uref, public_key and `Option` of each of these.  Compound types are written as
+/// `option<T>`, `list<T>`, `map<K, V>`, `tuple<T1[, T2[, T3]]>`, `result<T, E>` and
+/// `byte_array<N>`, and can be nested to any depth.
 ///
-/// Example inputs are:
+/// Example inputs for the simple types are:
 ///
 /// ```text
 /// name_01:bool='false'
@@ -412,6 +1335,20 @@ impl<'a> TryInto<DeployParams> for DeployStrParams<'a> {
 /// name_10:opt_string=null       # None
 /// ```
 ///
+/// Values of compound types are written using `[...]` for lists, `(...)` for tuples,
+/// `{KEY: VALUE, ...}` for maps, `ok(...)` or `err(...)` for results and hex for byte arrays.
+/// Strings within compound values must be double-quoted, escaping any `"` or `\` with a `\`:
+///
+/// ```text
+/// name_01:list<u64>='[1, 2, 3]'
+/// name_02:option<list<string>>='["a", "b"]'   # Some(vec!["a", "b"])
+/// name_03:option<list<string>>=null           # None
+/// name_04:map<string, u512>='{"alice": 10, "bob": 20}'
+/// name_05:tuple<u8, string, bool>='(1, "x", true)'
+/// name_06:result<u64, string>='err("failed")'
+/// name_07:byte_array<4>='01020304'
+/// ```
+///
 /// To get a list of supported types, call
 /// [`supported_cl_type_list()`](help/fn.supported_cl_type_list.html). To get this list of examples
 /// for supported types, call
@@ -422,10 +1359,28 @@ impl<'a> TryInto<DeployParams> for DeployStrParams<'a> {
 /// For methods taking `payment_args_complex`, this parameter is the payment contract arguments, in
 /// the form of a `ToBytes`-encoded file.
 ///
+/// ## `payment_args_json`
+///
+/// Set via [`with_payment_args_json`](#method.with_payment_args_json), this is the payment contract
+/// arguments as a JSON array of objects with `name`, `type` and `value` fields.  The types are
+/// written as for `payment_args_simple`, and the values use JSON arrays for lists and tuples,
+/// objects for maps and `{"ok": ...}` or `{"err": ...}` for results, e.g.:
+///
+/// ```text
+/// [{"name": "amounts", "type": "map<string, u512>", "value": {"alice": "10", "bob": "20"}}]
+/// ```
+///
+/// ## `payment_purse_name`
+///
+/// Set via [`with_payment_purse_name`](#method.with_payment_purse_name), this is the name of a
+/// purse in the account's named keys to pay from instead of the account's main purse.  It is
+/// passed to the payment code as the `purse_name` arg, which the standard-payment contract honours
+/// when the purse is held with write access.  It should be empty if not required.
+///
 /// ---
 ///
 /// **Note** while multiple payment args can be specified for a single payment code instance, only
-/// one of `payment_args_simple` and `payment_args_complex` may be used.
+/// one of `payment_args_simple`, `payment_args_complex` and `payment_args_json` may be used.
 #[derive(Default)]
 pub struct PaymentStrParams<'a> {
     payment_amount: &'a str,
@@ -436,8 +1391,10 @@ pub struct PaymentStrParams<'a> {
     payment_path: &'a str,
     payment_args_simple: Vec<&'a str>,
     payment_args_complex: &'a str,
+    payment_args_json: &'a str,
     payment_version: &'a str,
     payment_entry_point: &'a str,
+    payment_purse_name: &'a str,
 }
 
 impl<'a> TryInto<ExecutableDeployItem> for PaymentStrParams<'a> {
@@ -453,8 +1410,10 @@ impl<'a> TryInto<ExecutableDeployItem> for PaymentStrParams<'a> {
             payment_path,
             payment_args_simple,
             payment_args_complex,
+            payment_args_json,
             payment_version,
             payment_entry_point,
+            payment_purse_name,
         } = self;
 
         parsing::parse_payment_info(
@@ -466,13 +1425,32 @@ impl<'a> TryInto<ExecutableDeployItem> for PaymentStrParams<'a> {
             payment_path,
             &payment_args_simple,
             payment_args_complex,
+            payment_args_json,
             payment_version,
             payment_entry_point,
+            payment_purse_name,
         )
     }
 }
 
 impl<'a> PaymentStrParams<'a> {
+    /// Sets the payment args to those given in `payment_args_json`, which should be empty unless
+    /// the `PaymentStrParams` was constructed with neither simple nor complex args.
+    ///
+    /// * See the struct docs for a description of [`payment_args_json`](#payment_args_json).
+    pub fn with_payment_args_json(mut self, payment_args_json: &'a str) -> Self {
+        self.payment_args_json = payment_args_json;
+        self
+    }
+
+    /// Sets the name of the purse in the account's named keys from which payment is to be made.
+    ///
+    /// * See the struct docs for a description of [`payment_purse_name`](#payment_purse_name).
+    pub fn with_payment_purse_name(mut self, payment_purse_name: &'a str) -> Self {
+        self.payment_purse_name = payment_purse_name;
+        self
+    }
+
     /// Constructs a `PaymentStrParams` using a payment smart contract file.
     ///
     /// * `payment_path` is the path to the compiled Wasm payment code.
@@ -613,6 +1591,7 @@ impl<'a> TryInto<ExecutableDeployItem> for SessionStrParams<'a> {
             session_path,
             session_args_simple,
             session_args_complex,
+            session_args_json,
             session_version,
             session_entry_point,
         } = self;
@@ -625,6 +1604,7 @@ impl<'a> TryInto<ExecutableDeployItem> for SessionStrParams<'a> {
             session_path,
             &session_args_simple,
             session_args_complex,
+            session_args_json,
             session_version,
             session_entry_point,
         )
@@ -647,10 +1627,19 @@ impl<'a> TryInto<ExecutableDeployItem> for SessionStrParams<'a> {
 /// For methods taking `session_args_complex`, this parameter is the session contract arguments, in
 /// the form of a `ToBytes`-encoded file.
 ///
+/// ## `session_args_json`
+///
+/// Set via [`with_session_args_json`](#method.with_session_args_json), this is the session contract
+/// arguments as a JSON array of objects with `name`, `type` and `value` fields.
+///
+/// There are further details in
+/// [the docs for the equivalent
+/// `payment_args_json`](struct.PaymentStrParams.html#payment_args_json).
+///
 /// ---
 ///
 /// **Note** while multiple payment args can be specified for a single session code instance, only
-/// one of `session_args_simple` and `session_args_complex` may be used.
+/// one of `session_args_simple`, `session_args_complex` and `session_args_json` may be used.
 #[derive(Default)]
 pub struct SessionStrParams<'a> {
     session_hash: &'a str,
@@ -660,11 +1649,21 @@ pub struct SessionStrParams<'a> {
     session_path: &'a str,
     session_args_simple: Vec<&'a str>,
     session_args_complex: &'a str,
+    session_args_json: &'a str,
     session_version: &'a str,
     session_entry_point: &'a str,
 }
 
 impl<'a> SessionStrParams<'a> {
+    /// Sets the session args to those given in `session_args_json`, which should be empty unless
+    /// the `SessionStrParams` was constructed with neither simple nor complex args.
+    ///
+    /// * See the struct docs for a description of [`session_args_json`](#session_args_json).
+    pub fn with_session_args_json(mut self, session_args_json: &'a str) -> Self {
+        self.session_args_json = session_args_json;
+        self
+    }
+
     /// Constructs a `SessionStrParams` using a session smart contract file.
     ///
     /// * `session_path` is the path to the compiled Wasm session code.
@@ -10,16 +10,19 @@ use casper_node::{
     crypto::{
         asymmetric_key::{PublicKey as NodePublicKey, SecretKey},
         hash::Digest,
+        Error as CryptoError,
     },
     types::{DeployHash, TimeDiff, Timestamp},
+    utils::{passphrase, Loadable},
+    Chainspec, SimulationState,
 };
 use casper_types::{
-    bytesrepr, CLType, CLValue, ContractHash, Key, NamedArg, RuntimeArgs, UIntParseError, URef,
-    U512,
+    bytesrepr, standard_payment::ARG_PURSE_NAME, CLType, CLValue, ContractHash, Key, NamedArg,
+    RuntimeArgs, UIntParseError, URef, U512,
 };
 
 use crate::{
-    cl_type,
+    cl_type, cl_value,
     deploy::DeployParams,
     error::{Error, Result},
     help, ExecutableDeployItemExt, TransferTarget,
@@ -209,7 +212,65 @@ mod args_complex {
     }
 }
 
-const STANDARD_PAYMENT_ARG_NAME: &str = "amount";
+/// Handles retrieval of session and payment args given as a JSON array.
+mod args_json {
+    use super::*;
+
+    /// A single named and typed arg, with its value given in JSON.
+    #[derive(Debug, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct JsonArg {
+        name: String,
+        #[serde(rename = "type")]
+        cl_type: String,
+        value: serde_json::Value,
+    }
+
+    pub(crate) mod session {
+        use super::*;
+
+        pub fn parse(json: &str) -> Result<Option<RuntimeArgs>> {
+            Ok(if json.is_empty() {
+                None
+            } else {
+                Some(get(json)?)
+            })
+        }
+    }
+
+    pub(crate) mod payment {
+        use super::*;
+
+        pub fn parse(json: &str) -> Result<Option<RuntimeArgs>> {
+            Ok(if json.is_empty() {
+                None
+            } else {
+                Some(get(json)?)
+            })
+        }
+    }
+
+    fn get(json: &str) -> Result<RuntimeArgs> {
+        let args: Vec<JsonArg> = serde_json::from_str(json).map_err(|error| {
+            Error::InvalidCLValue(format!("can't parse args as JSON: {}", error))
+        })?;
+        let mut runtime_args = RuntimeArgs::new();
+        for arg in args {
+            let cl_type = cl_type::parse(&arg.cl_type).map_err(|_| {
+                Error::InvalidCLValue(format!(
+                    "unknown variant {}, expected one of {}",
+                    arg.cl_type,
+                    help::supported_cl_type_list()
+                ))
+            })?;
+            let cl_value = cl_value::from_json(cl_type, &arg.value)?;
+            runtime_args.insert_cl_value(arg.name, cl_value);
+        }
+        Ok(runtime_args)
+    }
+}
+
+pub(crate) const STANDARD_PAYMENT_ARG_NAME: &str = "amount";
 fn standard_payment(value: &str) -> Result<RuntimeArgs> {
     let arg = U512::from_dec_str(value)
         .map_err(|err| Error::FailedToParseUint("amount", UIntParseError::FromDecStr(err)))?;
@@ -218,20 +279,30 @@ fn standard_payment(value: &str) -> Result<RuntimeArgs> {
     Ok(runtime_args)
 }
 
+/// Loads the secret key at `value`.  If the key is encrypted, the passphrase is read from the
+/// `CASPER_SECRET_KEY_PASSPHRASE` environment variable if set, or else prompted for.
 pub(crate) fn secret_key(value: &str) -> Result<SecretKey> {
     let path = PathBuf::from(value);
-    SecretKey::from_file(path).map_err(Error::CryptoError)
+    match SecretKey::from_file(&path) {
+        Err(CryptoError::SecretKeyEncrypted) => {
+            let prompt = format!("Passphrase for {}: ", path.display());
+            let passphrase = passphrase::get_passphrase(None, &prompt)?;
+            SecretKey::from_encrypted_file(path, &passphrase).map_err(Error::CryptoError)
+        }
+        result => result.map_err(Error::CryptoError),
+    }
 }
 
-fn args_from_simple_or_complex(
+fn args_from_simple_complex_or_json(
     simple: Option<RuntimeArgs>,
     complex: Option<RuntimeArgs>,
+    json: Option<RuntimeArgs>,
 ) -> RuntimeArgs {
-    // We can have exactly zero or one of the two as `Some`.
-    match (simple, complex) {
-        (Some(args), None) | (None, Some(args)) => args,
-        (None, None) => RuntimeArgs::new(),
-        (Some(_), Some(_)) => unreachable!("should not have both simple and complex args"),
+    // We can have exactly zero or one of the three as `Some`.
+    match (simple, complex, json) {
+        (Some(args), None, None) | (None, Some(args), None) | (None, None, Some(args)) => args,
+        (None, None, None) => RuntimeArgs::new(),
+        _ => unreachable!("should not have more than one of simple, complex and JSON args"),
     }
 }
 
@@ -260,6 +331,48 @@ pub(super) fn parse_deploy_params(
     })
 }
 
+pub(super) fn simulation_state(
+    chainspec_path: &str,
+    storage_path: &str,
+    state_root_hash: &str,
+) -> Result<SimulationState> {
+    match (none_if_empty(chainspec_path), none_if_empty(storage_path)) {
+        (Some(chainspec_path), None) => {
+            if !state_root_hash.is_empty() {
+                return Err(Error::InvalidArgument(
+                    "state_root_hash",
+                    format!(
+                        "cannot be used with a fresh global state created from {}",
+                        chainspec_path
+                    ),
+                ));
+            }
+            let chainspec = Chainspec::from_file(chainspec_path)?;
+            Ok(SimulationState::Genesis(Box::new(chainspec)))
+        }
+        (None, Some(storage_path)) => {
+            let state_root_hash = none_if_empty(state_root_hash).ok_or_else(|| {
+                Error::InvalidArgument(
+                    "state_root_hash",
+                    "required with an existing global state".to_string(),
+                )
+            })?;
+            Ok(SimulationState::Snapshot {
+                storage_path: PathBuf::from(storage_path),
+                state_root_hash: Digest::from_hex(state_root_hash)?,
+            })
+        }
+        (Some(_), Some(_)) => Err(Error::InvalidArgument(
+            "storage_path",
+            "cannot be used with chainspec_path".to_string(),
+        )),
+        (None, None) => Err(Error::InvalidArgument(
+            "chainspec_path",
+            "either chainspec_path or storage_path is required".to_string(),
+        )),
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub(super) fn parse_session_info(
     session_hash: &str,
@@ -269,12 +382,14 @@ pub(super) fn parse_session_info(
     session_path: &str,
     session_args: &[&str],
     session_args_complex: &str,
+    session_args_json: &str,
     session_version: &str,
     session_entry_point: &str,
 ) -> Result<ExecutableDeployItem> {
-    let session_args = args_from_simple_or_complex(
+    let session_args = args_from_simple_complex_or_json(
         arg_simple::session::parse(session_args)?,
         args_complex::session::parse(session_args_complex).ok(),
+        args_json::session::parse(session_args_json)?,
     );
     let invalid_entry_point =
         || Error::InvalidArgument("session_entry_point", session_entry_point.to_string());
@@ -330,20 +445,31 @@ pub(super) fn parse_payment_info(
     payment_path: &str,
     payment_args: &[&str],
     payment_args_complex: &str,
+    payment_args_json: &str,
     payment_version: &str,
     payment_entry_point: &str,
+    payment_purse_name: &str,
 ) -> Result<ExecutableDeployItem> {
-    if let Ok(payment_args) = standard_payment(standard_payment_amount) {
+    let purse_name = name(payment_purse_name);
+
+    if let Ok(mut payment_args) = standard_payment(standard_payment_amount) {
+        if let Some(purse_name) = purse_name {
+            payment_args.insert(ARG_PURSE_NAME, purse_name);
+        }
         return ExecutableDeployItem::new_module_bytes(vec![], payment_args);
     }
 
     let invalid_entry_point =
         || Error::InvalidArgument("payment_entry_point", payment_entry_point.to_string());
 
-    let payment_args = args_from_simple_or_complex(
+    let mut payment_args = args_from_simple_complex_or_json(
         arg_simple::payment::parse(payment_args)?,
         args_complex::payment::parse(payment_args_complex).ok(),
+        args_json::payment::parse(payment_args_json)?,
     );
+    if let Some(purse_name) = purse_name {
+        payment_args.insert(ARG_PURSE_NAME, purse_name);
+    }
 
     if let Some(payment_name) = name(payment_name) {
         return ExecutableDeployItem::new_stored_contract_by_name(
@@ -409,7 +535,7 @@ pub(crate) fn output(value: &str) -> Option<&str> {
     none_if_empty(value)
 }
 
-fn parse_contract_hash(value: &str) -> Result<ContractHash> {
+pub(crate) fn parse_contract_hash(value: &str) -> Result<ContractHash> {
     if let Ok(digest) = Digest::from_hex(value) {
         return Ok(digest.to_array());
     }
@@ -441,6 +567,15 @@ fn account(value: &str) -> Result<NodePublicKey> {
     Ok(NodePublicKey::from_hex(value)?)
 }
 
+pub(crate) fn public_key(value: &str) -> Result<NodePublicKey> {
+    NodePublicKey::from_hex(value).map_err(|error| {
+        Error::InvalidArgument(
+            "public_key",
+            format!("failed to parse '{}' as a public key: {}", value, error),
+        )
+    })
+}
+
 pub(crate) fn purse(value: &str) -> Result<URef> {
     Ok(
         URef::from_formatted_str(value)
@@ -626,6 +761,31 @@ mod tests {
         valid_simple_args_test::<Option<URef>>("x:opt_uref=null", None);
     }
 
+    #[test]
+    fn should_parse_compound_types_via_args_simple() {
+        valid_simple_args_test("x:list<u32>='[1, 2]'", vec![1_u32, 2]);
+        valid_simple_args_test(
+            r#"x:option<tuple<string, u8>>='("a", 1)'"#,
+            Some(("a".to_string(), 1_u8)),
+        );
+        valid_simple_args_test::<Option<Vec<u8>>>("x:option<list<u8>>=null", None);
+    }
+
+    #[test]
+    fn should_parse_args_json() {
+        let json = r#"[{"name": "x", "type": "list<option<u64>>", "value": [1, null]}]"#;
+        let expected = RuntimeArgs::from(vec![NamedArg::new(
+            "x".to_string(),
+            CLValue::from_t(vec![Some(1_u64), None]).unwrap(),
+        )]);
+        assert_eq!(
+            args_json::session::parse(json).expect("should parse"),
+            Some(expected)
+        );
+        assert_eq!(args_json::payment::parse("").expect("should parse"), None);
+        assert!(args_json::session::parse(r#"[{"name": "x"}]"#).is_err());
+    }
+
     #[test]
     fn should_parse_public_key_via_args_simple() {
         let hex_value = "0119bf44096984cdfe8541bac167dc3b96c85086aa30b6b6cb0c5c38ad703166e1";
@@ -634,4 +794,39 @@ mod tests {
         valid_simple_args_test(&format!("x:opt_public_key='{}'", hex_value), Some(value));
         valid_simple_args_test::<Option<PublicKey>>("x:opt_public_key=null", None);
     }
+
+    #[test]
+    fn should_pass_payment_purse_name_as_arg() {
+        let payment =
+            parse_payment_info("100", "", "", "", "", "", &[], "", "", "", "", "treasury")
+                .expect("should parse");
+        let args = payment.into_runtime_args().expect("should have args");
+        assert_eq!(
+            args.get(ARG_PURSE_NAME),
+            Some(&CLValue::from_t("treasury".to_string()).unwrap())
+        );
+
+        let payment = parse_payment_info(
+            "",
+            "",
+            "payment_contract",
+            "",
+            "",
+            "",
+            &[],
+            "",
+            "",
+            "",
+            "pay",
+            "treasury",
+        )
+        .expect("should parse");
+        let args = payment.into_runtime_args().expect("should have args");
+        assert!(args.get(ARG_PURSE_NAME).is_some());
+
+        let payment = parse_payment_info("100", "", "", "", "", "", &[], "", "", "", "", "")
+            .expect("should parse");
+        let args = payment.into_runtime_args().expect("should have args");
+        assert!(args.get(ARG_PURSE_NAME).is_none());
+    }
 }
@@ -9,21 +9,39 @@ use casper_execution_engine::core::engine_state::ExecutableDeployItem;
 use casper_node::{
     crypto::{asymmetric_key::PublicKey, hash::Digest},
     rpcs::{
-        account::{PutDeploy, PutDeployParams},
+        account::{
+            self, CheckDeploy, CheckDeployParams, GetAccountDeploys, GetAccountDeploysParams,
+            PutDeploy, PutDeployParams, NODE_AT_CAPACITY_ERROR_CODE,
+        },
         chain::{
-            BlockIdentifier, GetBlock, GetBlockParams, GetStateRootHash, GetStateRootHashParams,
+            BlockIdentifier, EraIdentifier, GetAlternativeBlocks, GetAlternativeBlocksParams,
+            GetBlock, GetBlockAtTimestamp, GetBlockAtTimestampParams, GetBlockByDeploy,
+            GetBlockByDeployParams, GetBlockParams, GetBlocks, GetBlocksParams, GetEraSummary,
+            GetEraSummaryParams, GetStateRootHash, GetStateRootHashParams,
+        },
+        info::{
+            EstimateDeployCost, EstimateDeployCostParams, GetChainspec, GetDeploy, GetDeployParams,
+            GetSignedStatus, GetSignedStatusParams, GetSupply, GetSupplyParams,
+            GetValidatorRewards, GetValidatorRewardsParams,
+        },
+        state::{
+            self, GetAccountInfo, GetAccountInfoParams, GetAuctionInfo, GetBalance,
+            GetBalanceHistory, GetBalanceHistoryParams, GetBalanceParams, GetContractAbi,
+            GetContractAbiParams, GetDelegations, GetDelegationsParams, GetItem, GetItemParams,
+            GetKeyHistory, GetKeyHistoryParams, GetKeysWithPrefix, GetKeysWithPrefixParams,
+            GetTransfersTo, GetTransfersToParams, GetUnbondingPurses, GetUnbondingPursesParams,
+            STATE_ROOT_OUTSIDE_WINDOW_ERROR_CODE,
         },
-        info::{GetDeploy, GetDeployParams},
-        state::{GetAuctionInfo, GetBalance, GetBalanceParams, GetItem, GetItemParams},
         RpcWithOptionalParams, RpcWithParams, RpcWithoutParams, RPC_API_PATH,
     },
-    types::{BlockHash, Deploy, DeployHash},
+    types::{BlockHash, Deploy, DeployHash, Timestamp},
 };
-use casper_types::{bytesrepr::ToBytes, Key, RuntimeArgs, URef, U512};
+use casper_types::{account::AccountHash, bytesrepr::ToBytes, Key, RuntimeArgs, URef, U512};
 
 use crate::{
     deploy::{DeployExt, DeployParams, SendDeploy, Transfer},
     error::{Error, Result},
+    parsing::none_if_empty,
     validation,
 };
 
@@ -35,8 +53,14 @@ pub(crate) enum TransferTarget {
     Account(PublicKey),
 }
 
+/// The block identifier denoting the most recent switch block.
+pub const LATEST_SWITCH_BLOCK: &str = "latest-switch";
+
+/// The prefix of a block identifier denoting the switch block of an era, followed by the era ID.
+pub const SWITCH_BLOCK_PREFIX: &str = "era-";
+
 /// Struct representing a single JSON-RPC call to the casper node.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub(crate) struct RpcCall {
     rpc_id: Id,
     node_address: String,
@@ -107,6 +131,23 @@ impl RpcCall {
         Ok(response)
     }
 
+    /// Queries global state as of the given block, whose state root hash is retrieved first.
+    pub(crate) fn get_item_at_block(
+        self,
+        maybe_block_identifier: &str,
+        key: &str,
+        path: &str,
+    ) -> Result<JsonRpc> {
+        let response = self.clone().get_state_root_hash(maybe_block_identifier)?;
+        let state_root_hash = response
+            .get_result()
+            .and_then(|result| result.get("state_root_hash"))
+            .and_then(Value::as_str)
+            .ok_or_else(|| Error::InvalidRpcResponse(response.clone()))?
+            .to_string();
+        self.get_item(&state_root_hash, key, path)
+    }
+
     pub(crate) fn get_state_root_hash(self, maybe_block_identifier: &str) -> Result<JsonRpc> {
         match Self::block_identifier(maybe_block_identifier)? {
             Some(block_identifier) => {
@@ -132,10 +173,185 @@ impl RpcCall {
         Ok(response)
     }
 
+    pub(crate) fn get_balance_history(
+        self,
+        purse_uref: &str,
+        from_height: u64,
+        to_height: u64,
+        step: u64,
+        include_proofs: bool,
+    ) -> Result<JsonRpc> {
+        let uref = URef::from_formatted_str(purse_uref)
+            .map_err(|error| Error::FailedToParseURef("purse_uref", error))?;
+        let key = Key::from(uref);
+
+        let params = GetBalanceHistoryParams {
+            purse_uref: purse_uref.to_string(),
+            from_height,
+            to_height,
+            step,
+            include_proofs,
+        };
+        let response = GetBalanceHistory::request_with_map_params(self, params)?;
+        validation::validate_get_balance_history_response(
+            &response,
+            &key,
+            from_height,
+            to_height,
+            step,
+        )?;
+        Ok(response)
+    }
+
+    pub(crate) fn get_account_info(
+        self,
+        account_identifier: &str,
+        maybe_block_identifier: &str,
+    ) -> Result<JsonRpc> {
+        let account_hash = if let Ok(public_key) = PublicKey::from_hex(account_identifier) {
+            public_key.to_account_hash()
+        } else if let Ok(account_hash) = AccountHash::from_formatted_str(account_identifier) {
+            account_hash
+        } else {
+            return Err(Error::FailedToParseKey);
+        };
+
+        let params = GetAccountInfoParams {
+            account_identifier: account_identifier.to_string(),
+            block_identifier: Self::block_identifier(maybe_block_identifier)?,
+        };
+        let response = GetAccountInfo::request_with_map_params(self, params)?;
+        validation::validate_get_account_info_response(&response, account_hash)?;
+        Ok(response)
+    }
+
+    pub(crate) fn get_account_deploys(
+        self,
+        account_identifier: &str,
+        page: u64,
+    ) -> Result<JsonRpc> {
+        if PublicKey::from_hex(account_identifier).is_err()
+            && AccountHash::from_formatted_str(account_identifier).is_err()
+        {
+            return Err(Error::FailedToParseKey);
+        }
+
+        let params = GetAccountDeploysParams {
+            account_identifier: account_identifier.to_string(),
+            page,
+        };
+        GetAccountDeploys::request_with_map_params(self, params)
+    }
+
+    pub(crate) fn get_key_history(self, key: &str, page: u64) -> Result<JsonRpc> {
+        let key = if let Ok(key) = Key::from_formatted_str(key) {
+            key
+        } else if let Ok(public_key) = PublicKey::from_hex(key) {
+            Key::Account(public_key.to_account_hash())
+        } else {
+            return Err(Error::FailedToParseKey);
+        };
+
+        let params = GetKeyHistoryParams {
+            key: key.to_formatted_string(),
+            page,
+        };
+        GetKeyHistory::request_with_map_params(self, params)
+    }
+
+    pub(crate) fn get_keys_with_prefix(
+        self,
+        state_root_hash: &str,
+        prefix: &str,
+        page: u64,
+    ) -> Result<JsonRpc> {
+        let state_root_hash = Digest::from_hex(state_root_hash)?;
+        if let Err(error) = hex::decode(prefix) {
+            return Err(Error::InvalidArgument(
+                "prefix",
+                format!("failed to parse as hex: {}", error),
+            ));
+        }
+
+        let params = GetKeysWithPrefixParams {
+            state_root_hash,
+            prefix: prefix.to_string(),
+            page,
+        };
+        GetKeysWithPrefix::request_with_map_params(self, params)
+    }
+
+    pub(crate) fn get_contract_abi(
+        self,
+        state_root_hash: &str,
+        contract_hash: &str,
+    ) -> Result<JsonRpc> {
+        let state_root_hash = Digest::from_hex(state_root_hash)?;
+        let params = GetContractAbiParams {
+            state_root_hash,
+            contract_hash: contract_hash.to_string(),
+        };
+        GetContractAbi::request_with_map_params(self, params)
+    }
+
+    pub(crate) fn get_transfers_to(
+        self,
+        target: &str,
+        maybe_since_block_id: &str,
+    ) -> Result<JsonRpc> {
+        let target = if let Ok(public_key) = PublicKey::from_hex(target) {
+            Key::Account(public_key.to_account_hash())
+        } else {
+            match Key::from_formatted_str(target) {
+                Ok(key @ Key::Account(_)) | Ok(key @ Key::URef(_)) => key,
+                _ => return Err(Error::FailedToParseKey),
+            }
+        };
+
+        let params = GetTransfersToParams {
+            target: target.to_formatted_string(),
+            since_block: Self::block_identifier(maybe_since_block_id)?,
+        };
+        GetTransfersTo::request_with_map_params(self, params)
+    }
+
     pub(crate) fn get_auction_info(self) -> Result<JsonRpc> {
         GetAuctionInfo::request(self)
     }
 
+    pub(crate) fn get_delegations(
+        self,
+        public_key: &str,
+        maybe_block_identifier: &str,
+    ) -> Result<JsonRpc> {
+        let public_key = PublicKey::from_hex(public_key).map_err(|_| Error::FailedToParseKey)?;
+        let params = GetDelegationsParams {
+            public_key,
+            block_identifier: Self::block_identifier(maybe_block_identifier)?,
+        };
+        GetDelegations::request_with_map_params(self, params)
+    }
+
+    pub(crate) fn get_unbonding_purses(
+        self,
+        maybe_validator: &str,
+        maybe_delegator: &str,
+    ) -> Result<JsonRpc> {
+        let parse_public_key = |maybe_public_key: &str| -> Result<Option<PublicKey>> {
+            if maybe_public_key.is_empty() {
+                return Ok(None);
+            }
+            PublicKey::from_hex(maybe_public_key)
+                .map(Some)
+                .map_err(|_| Error::FailedToParseKey)
+        };
+        let params = GetUnbondingPursesParams {
+            validator: parse_public_key(maybe_validator)?,
+            delegator: parse_public_key(maybe_delegator)?,
+        };
+        GetUnbondingPurses::request_with_map_params(self, params)
+    }
+
     pub(crate) fn transfer(
         self,
         amount: U512,
@@ -166,21 +382,51 @@ impl RpcCall {
             args: transfer_args.to_bytes()?,
         };
         let deploy = Deploy::with_payment_and_session(deploy_params, payment, session);
-        let params = PutDeployParams { deploy };
+        let params = PutDeployParams {
+            deploy,
+            receipt: false,
+            idempotency_key: None,
+        };
         Transfer::request_with_map_params(self, params)
     }
 
-    pub(crate) fn send_deploy_file(self, input_path: &str) -> Result<JsonRpc> {
+    pub(crate) fn send_deploy_file(
+        self,
+        input_path: &str,
+        maybe_idempotency_key: &str,
+    ) -> Result<JsonRpc> {
         let deploy = Deploy::read_deploy(input_path)?;
-        let params = PutDeployParams { deploy };
+        let params = PutDeployParams {
+            deploy,
+            receipt: false,
+            idempotency_key: none_if_empty(maybe_idempotency_key).map(ToString::to_string),
+        };
         SendDeploy::request_with_map_params(self, params)
     }
 
+    pub(crate) fn check_deploy_file(self, input_path: &str) -> Result<JsonRpc> {
+        let deploy = Deploy::read_deploy(input_path)?;
+        let params = CheckDeployParams { deploy };
+        CheckDeploy::request_with_map_params(self, params)
+    }
+
     pub(crate) fn put_deploy(self, deploy: Deploy) -> Result<JsonRpc> {
-        let params = PutDeployParams { deploy };
+        let params = PutDeployParams {
+            deploy,
+            receipt: false,
+            idempotency_key: None,
+        };
         PutDeploy::request_with_map_params(self, params)
     }
 
+    pub(crate) fn estimate_deploy_cost(self, deploy: Deploy, profile_gas: bool) -> Result<JsonRpc> {
+        let params = EstimateDeployCostParams {
+            deploy,
+            profile_gas,
+        };
+        EstimateDeployCost::request_with_map_params(self, params)
+    }
+
     pub(crate) fn get_block(self, maybe_block_identifier: &str) -> Result<JsonRpc> {
         let maybe_block_identifier = Self::block_identifier(maybe_block_identifier)?;
         let response = match maybe_block_identifier {
@@ -194,6 +440,138 @@ impl RpcCall {
         Ok(response)
     }
 
+    pub(crate) fn get_blocks(
+        self,
+        start_height: u64,
+        end_height: u64,
+        headers_only: bool,
+    ) -> Result<JsonRpc> {
+        let params = GetBlocksParams {
+            start_height,
+            end_height,
+            headers_only,
+        };
+        let response = GetBlocks::request_with_map_params(self, params)?;
+        if headers_only {
+            validation::validate_get_block_headers_response(&response, start_height, end_height)?;
+        } else {
+            validation::validate_get_blocks_response(&response, start_height, end_height)?;
+        }
+        Ok(response)
+    }
+
+    pub(crate) fn get_alternative_blocks(self, height: u64) -> Result<JsonRpc> {
+        let params = GetAlternativeBlocksParams { height };
+        let response = GetAlternativeBlocks::request_with_map_params(self, params)?;
+        validation::validate_get_alternative_blocks_response(&response, height)?;
+        Ok(response)
+    }
+
+    pub(crate) fn get_block_by_deploy(self, deploy_hash: &str) -> Result<JsonRpc> {
+        let deploy_hash = DeployHash::new(Digest::from_hex(deploy_hash)?);
+        let params = GetBlockByDeployParams { deploy_hash };
+        let response = GetBlockByDeploy::request_with_map_params(self, params)?;
+        validation::validate_get_block_by_deploy_response(&response, &deploy_hash)?;
+        Ok(response)
+    }
+
+    pub(crate) fn get_block_at_timestamp(self, timestamp: Timestamp) -> Result<JsonRpc> {
+        let params = GetBlockAtTimestampParams { timestamp };
+        let response = GetBlockAtTimestamp::request_with_map_params(self, params)?;
+        validation::validate_get_block_at_timestamp_response(&response, timestamp)?;
+        Ok(response)
+    }
+
+    pub(crate) fn get_era_summary(
+        self,
+        maybe_era_id: &str,
+        maybe_block_identifier: &str,
+    ) -> Result<JsonRpc> {
+        let maybe_era_identifier = match (maybe_era_id, maybe_block_identifier) {
+            ("", "") => None,
+            (era_id, "") => Some(EraIdentifier::EraId(
+                era_id
+                    .parse()
+                    .map_err(|error| Error::FailedToParseInt("era_id", error))?,
+            )),
+            ("", block_identifier) => {
+                Self::block_identifier(block_identifier)?.map(EraIdentifier::Block)
+            }
+            _ => {
+                return Err(Error::InvalidArgument(
+                    "era_id | block_identifier",
+                    "at most one of era ID or block identifier may be provided".to_string(),
+                ))
+            }
+        };
+        let response = match maybe_era_identifier {
+            Some(era_identifier) => {
+                let params = GetEraSummaryParams { era_identifier };
+                GetEraSummary::request_with_map_params(self, params)
+            }
+            None => GetEraSummary::request(self),
+        }?;
+        validation::validate_get_era_summary_response(&response)?;
+        Ok(response)
+    }
+
+    pub(crate) fn get_validator_rewards(
+        self,
+        era_id: u64,
+        offset: u64,
+        limit: Option<u64>,
+    ) -> Result<JsonRpc> {
+        let params = GetValidatorRewardsParams {
+            era_id,
+            offset,
+            limit,
+        };
+        GetValidatorRewards::request_with_map_params(self, params)
+    }
+
+    pub(crate) fn get_supply(
+        self,
+        maybe_block_identifier: &str,
+        include_circulating_supply: bool,
+    ) -> Result<JsonRpc> {
+        let block_identifier = Self::block_identifier(maybe_block_identifier)?;
+        if block_identifier.is_none() && !include_circulating_supply {
+            return GetSupply::request(self);
+        }
+        let params = GetSupplyParams {
+            block_identifier,
+            include_circulating_supply,
+        };
+        GetSupply::request_with_map_params(self, params)
+    }
+
+    pub(crate) fn get_signed_status(
+        self,
+        maybe_nonce: &str,
+        maybe_signer: &str,
+    ) -> Result<JsonRpc> {
+        let nonce = if maybe_nonce.is_empty() {
+            hex::encode(rand::thread_rng().gen::<[u8; 16]>())
+        } else {
+            maybe_nonce.to_string()
+        };
+        let maybe_signer = if maybe_signer.is_empty() {
+            None
+        } else {
+            Some(PublicKey::from_hex(maybe_signer).map_err(|_| Error::FailedToParseKey)?)
+        };
+        let params = GetSignedStatusParams {
+            nonce: Some(nonce.clone()),
+        };
+        let response = GetSignedStatus::request_with_map_params(self, params)?;
+        validation::validate_get_signed_status_response(&response, &nonce, maybe_signer.as_ref())?;
+        Ok(response)
+    }
+
+    pub(crate) fn get_chainspec(self) -> Result<JsonRpc> {
+        GetChainspec::request(self)
+    }
+
     fn block_identifier(maybe_block_identifier: &str) -> Result<Option<BlockIdentifier>> {
         if maybe_block_identifier.is_empty() {
             return Ok(None);
@@ -202,11 +580,26 @@ impl RpcCall {
         if maybe_block_identifier.len() == (Digest::LENGTH * 2) {
             let hash = Digest::from_hex(maybe_block_identifier)?;
             Ok(Some(BlockIdentifier::Hash(BlockHash::new(hash))))
-        } else {
+        } else if maybe_block_identifier == LATEST_SWITCH_BLOCK {
+            Ok(Some(BlockIdentifier::LatestSwitchBlock))
+        } else if let Some(era_id) = maybe_block_identifier.strip_prefix(SWITCH_BLOCK_PREFIX) {
+            let era_id = era_id
+                .parse()
+                .map_err(|error| Error::FailedToParseInt("block_identifier", error))?;
+            Ok(Some(BlockIdentifier::SwitchBlock(era_id)))
+        } else if maybe_block_identifier
+            .chars()
+            .all(|character| character.is_ascii_digit())
+        {
             let height = maybe_block_identifier
                 .parse()
                 .map_err(|error| Error::FailedToParseInt("block_identifier", error))?;
             Ok(Some(BlockIdentifier::Height(height)))
+        } else {
+            let timestamp = maybe_block_identifier
+                .parse::<Timestamp>()
+                .map_err(|error| Error::FailedToParseTimestamp("block_identifier", error))?;
+            Ok(Some(BlockIdentifier::Timestamp(timestamp)))
         }
     }
 
@@ -258,6 +651,20 @@ impl RpcCall {
             if self.verbose {
                 println!("Response returned an error");
             }
+            if error.code == NODE_AT_CAPACITY_ERROR_CODE {
+                if let Some(retry_after_secs) = account::retry_after_secs(error.data.as_ref()) {
+                    return Err(Error::NodeAtCapacity { retry_after_secs });
+                }
+            }
+            if error.code == STATE_ROOT_OUTSIDE_WINDOW_ERROR_CODE {
+                if let Some(earliest_block_height) =
+                    state::earliest_queryable_block_height(&error.message)
+                {
+                    return Err(Error::StateRootOutsideWindow {
+                        earliest_block_height,
+                    });
+                }
+            }
             return Err(Error::ResponseIsError(error.clone()));
         }
 
@@ -295,18 +702,90 @@ impl RpcClient for GetBlock {
     const RPC_METHOD: &'static str = Self::METHOD;
 }
 
+impl RpcClient for GetBlocks {
+    const RPC_METHOD: &'static str = Self::METHOD;
+}
+
+impl RpcClient for GetBlockByDeploy {
+    const RPC_METHOD: &'static str = Self::METHOD;
+}
+
+impl RpcClient for GetBlockAtTimestamp {
+    const RPC_METHOD: &'static str = Self::METHOD;
+}
+
+impl RpcClient for GetAlternativeBlocks {
+    const RPC_METHOD: &'static str = Self::METHOD;
+}
+
+impl RpcClient for GetEraSummary {
+    const RPC_METHOD: &'static str = Self::METHOD;
+}
+
+impl RpcClient for GetValidatorRewards {
+    const RPC_METHOD: &'static str = Self::METHOD;
+}
+
+impl RpcClient for GetSupply {
+    const RPC_METHOD: &'static str = Self::METHOD;
+}
+
+impl RpcClient for GetSignedStatus {
+    const RPC_METHOD: &'static str = Self::METHOD;
+}
+
+impl RpcClient for GetChainspec {
+    const RPC_METHOD: &'static str = Self::METHOD;
+}
+
 impl RpcClient for GetStateRootHash {
     const RPC_METHOD: &'static str = Self::METHOD;
 }
 
+impl RpcClient for GetBalanceHistory {
+    const RPC_METHOD: &'static str = Self::METHOD;
+}
+
 impl RpcClient for GetItem {
     const RPC_METHOD: &'static str = <Self as RpcWithParams>::METHOD;
 }
 
+impl RpcClient for GetAccountInfo {
+    const RPC_METHOD: &'static str = Self::METHOD;
+}
+
+impl RpcClient for GetAccountDeploys {
+    const RPC_METHOD: &'static str = Self::METHOD;
+}
+
+impl RpcClient for GetKeyHistory {
+    const RPC_METHOD: &'static str = Self::METHOD;
+}
+
+impl RpcClient for GetKeysWithPrefix {
+    const RPC_METHOD: &'static str = Self::METHOD;
+}
+
+impl RpcClient for GetContractAbi {
+    const RPC_METHOD: &'static str = Self::METHOD;
+}
+
+impl RpcClient for GetTransfersTo {
+    const RPC_METHOD: &'static str = Self::METHOD;
+}
+
 impl RpcClient for GetAuctionInfo {
     const RPC_METHOD: &'static str = Self::METHOD;
 }
 
+impl RpcClient for GetDelegations {
+    const RPC_METHOD: &'static str = Self::METHOD;
+}
+
+impl RpcClient for GetUnbondingPurses {
+    const RPC_METHOD: &'static str = Self::METHOD;
+}
+
 pub(crate) trait IntoJsonMap: Serialize {
     fn into_json_map(self) -> Map<String, Value>
     where
@@ -320,8 +799,66 @@ pub(crate) trait IntoJsonMap: Serialize {
 }
 
 impl IntoJsonMap for PutDeployParams {}
+impl IntoJsonMap for CheckDeployParams {}
+impl IntoJsonMap for GetAccountDeploysParams {}
+impl IntoJsonMap for EstimateDeployCostParams {}
 impl IntoJsonMap for GetBlockParams {}
+impl IntoJsonMap for GetBlocksParams {}
+impl IntoJsonMap for GetAlternativeBlocksParams {}
+impl IntoJsonMap for GetBlockByDeployParams {}
+impl IntoJsonMap for GetBlockAtTimestampParams {}
+impl IntoJsonMap for GetEraSummaryParams {}
+impl IntoJsonMap for GetValidatorRewardsParams {}
+impl IntoJsonMap for GetSupplyParams {}
+impl IntoJsonMap for GetSignedStatusParams {}
 impl IntoJsonMap for GetStateRootHashParams {}
 impl IntoJsonMap for GetDeployParams {}
 impl IntoJsonMap for GetBalanceParams {}
+impl IntoJsonMap for GetBalanceHistoryParams {}
 impl IntoJsonMap for GetItemParams {}
+impl IntoJsonMap for GetAccountInfoParams {}
+impl IntoJsonMap for GetKeyHistoryParams {}
+impl IntoJsonMap for GetKeysWithPrefixParams {}
+impl IntoJsonMap for GetContractAbiParams {}
+impl IntoJsonMap for GetTransfersToParams {}
+impl IntoJsonMap for GetDelegationsParams {}
+impl IntoJsonMap for GetUnbondingPursesParams {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block_identifier(value: &str) -> Option<BlockIdentifier> {
+        RpcCall::block_identifier(value).expect("should parse")
+    }
+
+    #[test]
+    fn should_parse_block_identifiers() {
+        assert!(block_identifier("").is_none());
+        assert!(matches!(
+            block_identifier(&"ab".repeat(Digest::LENGTH)),
+            Some(BlockIdentifier::Hash(_))
+        ));
+        assert!(matches!(
+            block_identifier("42"),
+            Some(BlockIdentifier::Height(42))
+        ));
+        assert!(matches!(
+            block_identifier("latest-switch"),
+            Some(BlockIdentifier::LatestSwitchBlock)
+        ));
+        assert!(matches!(
+            block_identifier("era-7"),
+            Some(BlockIdentifier::SwitchBlock(7))
+        ));
+        match block_identifier("2021-01-01T00:00:00Z") {
+            Some(BlockIdentifier::Timestamp(timestamp)) => {
+                assert_eq!(timestamp.millis(), 1_609_459_200_000)
+            }
+            other => panic!("unexpected block identifier {:?}", other),
+        }
+
+        assert!(RpcCall::block_identifier("era-latest").is_err());
+        assert!(RpcCall::block_identifier("yesterday").is_err());
+    }
+}
@@ -0,0 +1,137 @@
+//! A reduced snapshot of global state, captured from a live network as a test fixture.
+
+use std::collections::{BTreeSet, VecDeque};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use casper_execution_engine::shared::stored_value::StoredValue;
+use casper_node::crypto::asymmetric_key::PublicKey;
+use casper_types::{bytesrepr::ToBytes, CLType, Key};
+
+use crate::{
+    error::{Error, Result},
+    rpc::RpcCall,
+    validation,
+};
+
+/// A value stored in global state under a given key.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct StateSnapshotEntry {
+    /// The formatted key, e.g. `"account-hash-0102..."` or `"hash-0102..."`.
+    pub key: String,
+    /// The hex-encoded, `bytesrepr`-serialized stored value.
+    pub value: String,
+}
+
+/// Selected entries of global state, in the format loaded by
+/// `casper_engine_test_support::StateSnapshot`.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct StateSnapshot {
+    /// The hex-encoded state root hash of the global state the entries were read from.
+    pub state_root_hash: String,
+    /// The captured entries, in the order they were read.
+    pub entries: Vec<StateSnapshotEntry>,
+}
+
+impl StateSnapshot {
+    /// Reads the values stored under `keys` as of the given block, each checked against its
+    /// merkle proof.
+    ///
+    /// If `follow_references` is `true`, the values referenced by each value read are captured
+    /// too: an account's main purse balance, a contract's package and Wasm, and the value under
+    /// any key stored as a `CLValue`.
+    pub(crate) fn capture(
+        rpc_call: RpcCall,
+        maybe_block_id: &str,
+        keys: &[&str],
+        follow_references: bool,
+    ) -> Result<Self> {
+        let mut pending = keys
+            .iter()
+            .map(|key| parse_key(key))
+            .collect::<Result<VecDeque<_>>>()?;
+
+        let response = rpc_call.clone().get_state_root_hash(maybe_block_id)?;
+        let state_root_hash = response
+            .get_result()
+            .and_then(|result| result.get("state_root_hash"))
+            .and_then(Value::as_str)
+            .ok_or_else(|| Error::InvalidRpcResponse(response.clone()))?
+            .to_string();
+
+        let mut captured = BTreeSet::new();
+        let mut entries = vec![];
+        while let Some(key) = pending.pop_front() {
+            let key = key.normalize();
+            if !captured.insert(key) {
+                continue;
+            }
+            let formatted_key = key.to_formatted_string();
+            let response = rpc_call
+                .clone()
+                .get_item(&state_root_hash, &formatted_key, "")?;
+            let value = validation::query_stored_value(&response)?.ok_or_else(|| {
+                Error::InvalidArgument("key", format!("{} is absent from global state", key))
+            })?;
+            if follow_references {
+                pending.extend(references(&value));
+            }
+            entries.push(StateSnapshotEntry {
+                key: formatted_key,
+                value: hex::encode(value.to_bytes()?),
+            });
+        }
+
+        Ok(StateSnapshot {
+            state_root_hash,
+            entries,
+        })
+    }
+}
+
+fn parse_key(key: &str) -> Result<Key> {
+    if let Ok(key) = Key::from_formatted_str(key) {
+        Ok(key)
+    } else if let Ok(public_key) = PublicKey::from_hex(key) {
+        Ok(Key::Account(public_key.to_account_hash()))
+    } else {
+        Err(Error::FailedToParseKey)
+    }
+}
+
+/// Returns the keys of the values needed alongside `value` to use it in a test.
+fn references(value: &StoredValue) -> Vec<Key> {
+    match value {
+        // The purse's balance key is stored under the purse's address.
+        StoredValue::Account(account) => Key::URef(account.main_purse())
+            .uref_to_hash()
+            .into_iter()
+            .collect(),
+        StoredValue::Contract(contract) => vec![
+            Key::Hash(contract.contract_package_hash()),
+            Key::Hash(contract.contract_wasm_hash()),
+        ],
+        StoredValue::CLValue(cl_value) if *cl_value.cl_type() == CLType::Key => {
+            cl_value.clone().into_t::<Key>().into_iter().collect()
+        }
+        _ => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{AccessRights, CLValue, URef};
+
+    use super::*;
+
+    #[test]
+    fn should_follow_key_held_by_cl_value() {
+        let balance_key = Key::URef(URef::new([1; 32], AccessRights::READ_ADD_WRITE));
+        let value = StoredValue::CLValue(CLValue::from_t(balance_key).unwrap());
+        assert_eq!(references(&value), vec![balance_key]);
+
+        let value = StoredValue::CLValue(CLValue::from_t(1u64).unwrap());
+        assert!(references(&value).is_empty());
+    }
+}
@@ -4,19 +4,35 @@ use jsonrpc_lite::JsonRpc;
 use thiserror::Error;
 
 use casper_execution_engine::{
-    core, core::ValidationError, shared::stored_value::StoredValue,
-    storage::trie::merkle_proof::TrieMerkleProof,
+    core,
+    core::ValidationError,
+    shared::{account::Account, stored_value::StoredValue},
+    storage::trie::merkle_proof::{TrieMerkleProof, TrieMerkleProofOfAbsence},
 };
 use casper_node::{
-    crypto::hash::Digest,
-    rpcs::chain::BlockIdentifier,
-    types::{json_compatibility, Block, BlockValidationError},
+    crypto::{asymmetric_key::PublicKey, hash::Digest},
+    rpcs::{
+        chain::{BlockIdentifier, GetBlockAtTimestampResult, GetBlockByDeployResult},
+        info::GetSignedStatusResult,
+        state::BalanceSample,
+    },
+    types::{
+        json_compatibility, Block, BlockHash, BlockHeader, BlockValidationError, DeployHash,
+        Timestamp,
+    },
 };
-use casper_types::{bytesrepr, Key, U512};
+use casper_types::{account::AccountHash, auction::ERA_INFO_KEY, bytesrepr, Key, U512};
 
 const GET_ITEM_RESULT_BALANCE_VALUE: &str = "balance_value";
 const GET_ITEM_RESULT_STORED_VALUE: &str = "stored_value";
 const GET_ITEM_RESULT_MERKLE_PROOF: &str = "merkle_proof";
+const GET_ITEM_RESULT_MERKLE_PROOF_OF_ABSENCE: &str = "merkle_proof_of_absence";
+const GET_BALANCE_HISTORY_RESULT_SAMPLES: &str = "samples";
+const GET_ACCOUNT_INFO_RESULT_ACCOUNT: &str = "account";
+const GET_ACCOUNT_INFO_RESULT_STATE_ROOT_HASH: &str = "state_root_hash";
+const GET_ERA_SUMMARY_RESULT_ERA_SUMMARY: &str = "era_summary";
+const GET_ERA_SUMMARY_RESULT_BLOCK_HASH: &str = "block_hash";
+const GET_ERA_SUMMARY_RESULT_BLOCK_HEADER: &str = "block_header";
 
 /// Error that can be returned by when validating
 #[derive(Error, Debug)]
@@ -56,6 +72,42 @@ pub enum ValidateResponseError {
     /// Block height was not as requested
     #[error("block height was not as requested")]
     UnexpectedBlockHeight,
+
+    /// No blocks in response
+    #[error("no blocks in response")]
+    NoBlocksInResponse,
+
+    /// Block in response is not a switch block
+    #[error("block in response is not a switch block")]
+    NotASwitchBlock,
+
+    /// Block in response is from a different era than requested
+    #[error("block in response is not from the requested era")]
+    UnexpectedEra,
+
+    /// Block in response is later than the requested timestamp
+    #[error("block in response is later than the requested timestamp")]
+    UnexpectedBlockTimestamp,
+
+    /// Block in response does not include the deploy at the given index
+    #[error("block in response does not include the deploy at the given index")]
+    DeployNotInBlock,
+
+    /// Signed status in response failed verification
+    #[error("signed status failed verification: {0}")]
+    InvalidSignedStatus(String),
+
+    /// Signed status in response does not include the nonce sent
+    #[error("signed status does not include the nonce sent")]
+    UnexpectedNonce,
+
+    /// Stored value in response doesn't represent a valid value
+    #[error("invalid stored value in response: {0}")]
+    InvalidStoredValue(#[from] json_compatibility::ConversionError),
+
+    /// Signed status in response was not signed with the expected key
+    #[error("signed status was signed by {0} rather than the expected key")]
+    UnexpectedSigner(PublicKey),
 }
 
 impl From<bytesrepr::Error> for ValidateResponseError {
@@ -96,6 +148,105 @@ pub(crate) fn validate_query_response(
         bytesrepr::deserialize(proof_bytes)?
     };
 
+    // If the queried key is absent, the response holds a proof of its absence rather than a value.
+    if let Some(proof_of_absence) = object.get(GET_ITEM_RESULT_MERKLE_PROOF_OF_ABSENCE) {
+        let proof_of_absence: TrieMerkleProofOfAbsence<Key, StoredValue> = {
+            let proof_str = proof_of_absence
+                .as_str()
+                .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
+            let proof_bytes = hex::decode(proof_str)
+                .map_err(|_| ValidateResponseError::ValidateResponseFailedToParse)?;
+            bytesrepr::deserialize(proof_bytes)?
+        };
+        return core::validate_query_proof_of_absence(
+            &state_root_hash.to_owned().into(),
+            &proofs,
+            &proof_of_absence,
+            key,
+            path,
+        )
+        .map_err(Into::into);
+    }
+
+    // The JSON `stored_value` is checked directly against the value held by the proof.
+    let value = stored_value(object)?;
+
+    core::validate_query_proof(
+        &state_root_hash.to_owned().into(),
+        &proofs,
+        key,
+        path,
+        &value,
+    )
+    .map_err(Into::into)
+}
+
+/// Returns the value held by a validated query response, or `None` if it holds a proof of the
+/// queried key's absence.
+pub(crate) fn query_stored_value(
+    response: &JsonRpc,
+) -> Result<Option<StoredValue>, ValidateResponseError> {
+    let object = response
+        .get_result()
+        .and_then(|value| value.as_object())
+        .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
+    if object.contains_key(GET_ITEM_RESULT_MERKLE_PROOF_OF_ABSENCE) {
+        return Ok(None);
+    }
+    stored_value(object).map(Some)
+}
+
+/// Parses the JSON `stored_value` field of a query result into the value it represents.
+fn stored_value(
+    object: &serde_json::Map<String, serde_json::Value>,
+) -> Result<StoredValue, ValidateResponseError> {
+    let value: json_compatibility::StoredValue = {
+        let value = object
+            .get(GET_ITEM_RESULT_STORED_VALUE)
+            .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
+        serde_json::from_value(value.to_owned())?
+    };
+    StoredValue::try_from(value).map_err(Into::into)
+}
+
+pub(crate) fn validate_get_account_info_response(
+    response: &JsonRpc,
+    account_hash: AccountHash,
+) -> Result<(), ValidateResponseError> {
+    let value = response
+        .get_result()
+        .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
+
+    let object = value
+        .as_object()
+        .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
+
+    let proofs: Vec<TrieMerkleProof<Key, StoredValue>> = {
+        let proof = object
+            .get(GET_ITEM_RESULT_MERKLE_PROOF)
+            .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
+        let proof_str = proof
+            .as_str()
+            .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
+        let proof_bytes = hex::decode(proof_str)
+            .map_err(|_| ValidateResponseError::ValidateResponseFailedToParse)?;
+        bytesrepr::deserialize(proof_bytes)?
+    };
+
+    let state_root_hash: Digest = {
+        let value = object
+            .get(GET_ACCOUNT_INFO_RESULT_STATE_ROOT_HASH)
+            .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
+        serde_json::from_value(value.to_owned())?
+    };
+
+    let account: Account = {
+        let value = object
+            .get(GET_ACCOUNT_INFO_RESULT_ACCOUNT)
+            .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
+        serde_json::from_value(value.to_owned())?
+    };
+
     let proof_value: &StoredValue = {
         let last_proof = proofs
             .last()
@@ -103,29 +254,152 @@ pub(crate) fn validate_query_response(
         last_proof.value()
     };
 
-    // Here we need to validate that JSON `stored_value` is contained in the proof.
-    //
-    // Possible to deserialize that field into a `StoredValue` and pass below to
-    // `validate_query_proof` instead of using this approach?
+    // The JSON `account` must be the one contained in the proof.
+    match proof_value {
+        StoredValue::Account(proof_account) if *proof_account == account => (),
+        _ => return Err(ValidateResponseError::SerializedValueNotContainedInProof),
+    }
+
+    core::validate_query_proof(
+        &state_root_hash.into(),
+        &proofs,
+        &Key::Account(account_hash),
+        &[],
+        proof_value,
+    )
+    .map_err(Into::into)
+}
+
+pub(crate) fn validate_get_block_by_deploy_response(
+    response: &JsonRpc,
+    deploy_hash: &DeployHash,
+) -> Result<(), ValidateResponseError> {
+    let value = response
+        .get_result()
+        .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
+    let result: GetBlockByDeployResult = serde_json::from_value(value.to_owned())?;
+    if result.block_header.hash() != result.block_hash {
+        return Err(ValidateResponseError::UnexpectedBlockHash);
+    }
+    if result
+        .block_header
+        .deploy_hashes()
+        .get(result.deploy_index as usize)
+        != Some(deploy_hash)
     {
-        let value: json_compatibility::StoredValue = {
+        return Err(ValidateResponseError::DeployNotInBlock);
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_get_block_at_timestamp_response(
+    response: &JsonRpc,
+    timestamp: Timestamp,
+) -> Result<(), ValidateResponseError> {
+    let value = response
+        .get_result()
+        .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
+    let result: GetBlockAtTimestampResult = serde_json::from_value(value.to_owned())?;
+    if result.block_header.hash() != result.block_hash {
+        return Err(ValidateResponseError::UnexpectedBlockHash);
+    }
+    if result.block_header.timestamp() > timestamp {
+        return Err(ValidateResponseError::UnexpectedBlockTimestamp);
+    }
+    // The next block, if given, must follow the block and lie after the timestamp.  More is
+    // necessary here to mitigate a MITM attack omitting it.
+    if let Some(next_block_header) = &result.next_block_header {
+        if *next_block_header.parent_hash() != result.block_hash {
+            return Err(ValidateResponseError::UnexpectedBlockHash);
+        }
+        if next_block_header.timestamp() <= timestamp {
+            return Err(ValidateResponseError::UnexpectedBlockTimestamp);
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_get_signed_status_response(
+    response: &JsonRpc,
+    nonce: &str,
+    maybe_signer: Option<&PublicKey>,
+) -> Result<(), ValidateResponseError> {
+    let value = response
+        .get_result()
+        .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
+    let result: GetSignedStatusResult = serde_json::from_value(value.to_owned())?;
+    if let Some(signer) = maybe_signer {
+        if result.public_key != *signer {
+            return Err(ValidateResponseError::UnexpectedSigner(result.public_key));
+        }
+    }
+    let payload = result
+        .verify()
+        .map_err(ValidateResponseError::InvalidSignedStatus)?;
+    if payload.nonce.as_deref() != Some(nonce) {
+        return Err(ValidateResponseError::UnexpectedNonce);
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_get_era_summary_response(
+    response: &JsonRpc,
+) -> Result<(), ValidateResponseError> {
+    let value = response
+        .get_result()
+        .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
+
+    let object = value
+        .get(GET_ERA_SUMMARY_RESULT_ERA_SUMMARY)
+        .and_then(|era_summary| era_summary.as_object())
+        .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
+
+    // The switch block header must hash to the given block hash, and must hold an era end.
+    let block_header: BlockHeader = {
+        let block_hash: BlockHash = {
             let value = object
-                .get(GET_ITEM_RESULT_STORED_VALUE)
+                .get(GET_ERA_SUMMARY_RESULT_BLOCK_HASH)
                 .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
             serde_json::from_value(value.to_owned())?
         };
-        match json_compatibility::StoredValue::try_from(proof_value) {
-            Ok(json_proof_value) if json_proof_value == value => (),
-            _ => return Err(ValidateResponseError::SerializedValueNotContainedInProof),
+        let value = object
+            .get(GET_ERA_SUMMARY_RESULT_BLOCK_HEADER)
+            .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
+        let block_header: BlockHeader = serde_json::from_value(value.to_owned())?;
+        if block_header.hash() != block_hash {
+            return Err(ValidateResponseError::UnexpectedBlockHash);
         }
-    }
+        if !block_header.switch_block() {
+            return Err(ValidateResponseError::NotASwitchBlock);
+        }
+        block_header
+    };
+
+    let proofs: Vec<TrieMerkleProof<Key, StoredValue>> = {
+        let proof = object
+            .get(GET_ITEM_RESULT_MERKLE_PROOF)
+            .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
+        let proof_str = proof
+            .as_str()
+            .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
+        let proof_bytes = hex::decode(proof_str)
+            .map_err(|_| ValidateResponseError::ValidateResponseFailedToParse)?;
+        bytesrepr::deserialize(proof_bytes)?
+    };
+
+    let auction_key = proofs
+        .first()
+        .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?
+        .key();
+
+    let value = stored_value(object)?;
 
     core::validate_query_proof(
-        &state_root_hash.to_owned().into(),
+        &block_header.state_root_hash().to_owned().into(),
         &proofs,
-        key,
-        path,
-        proof_value,
+        auction_key,
+        &[ERA_INFO_KEY.to_string()],
+        &value,
     )
     .map_err(Into::into)
 }
@@ -179,6 +453,61 @@ pub(crate) fn validate_get_balance_response(
     .map_err(Into::into)
 }
 
+pub(crate) fn validate_get_balance_history_response(
+    response: &JsonRpc,
+    key: &Key,
+    from_height: u64,
+    to_height: u64,
+    step: u64,
+) -> Result<(), ValidateResponseError> {
+    let samples = response
+        .get_result()
+        .and_then(|value| value.get(GET_BALANCE_HISTORY_RESULT_SAMPLES))
+        .and_then(|samples| samples.as_array())
+        .ok_or(ValidateResponseError::ValidateResponseFailedToParse)?;
+
+    // The samples must be taken at every `step` blocks from `from_height` up to `to_height`.
+    let step = step.max(1);
+    let max_samples = if to_height < from_height {
+        0
+    } else {
+        (to_height - from_height) / step + 1
+    };
+    if samples.len() as u64 > max_samples {
+        return Err(ValidateResponseError::UnexpectedBlockHeight);
+    }
+    for (index, sample) in samples.iter().enumerate() {
+        let expected_height = from_height + index as u64 * step;
+        let sample: BalanceSample = serde_json::from_value(sample.to_owned())?;
+        if sample.block_height != expected_height {
+            return Err(ValidateResponseError::UnexpectedBlockHeight);
+        }
+
+        // Only the balances returned along with a proof can be validated.
+        let (proof_str, balance) = match (&sample.merkle_proof, sample.balance_value) {
+            (Some(proof_str), Some(balance)) => (proof_str, balance),
+            (None, _) => continue,
+            (Some(_), None) => return Err(ValidateResponseError::ValidateResponseFailedToParse),
+        };
+        let (purse_proof, balance_proof): (
+            TrieMerkleProof<Key, StoredValue>,
+            TrieMerkleProof<Key, StoredValue>,
+        ) = {
+            let proof_bytes = hex::decode(proof_str)
+                .map_err(|_| ValidateResponseError::ValidateResponseFailedToParse)?;
+            bytesrepr::deserialize(proof_bytes)?
+        };
+        core::validate_balance_proof(
+            &sample.state_root_hash.into(),
+            &purse_proof,
+            &balance_proof,
+            *key,
+            &balance,
+        )?;
+    }
+    Ok(())
+}
+
 pub(crate) fn validate_get_block_response(
     response: &JsonRpc,
     maybe_block_identifier: &Option<BlockIdentifier>,
@@ -206,6 +535,27 @@ pub(crate) fn validate_get_block_response(
                 return Err(ValidateResponseError::UnexpectedBlockHeight);
             }
         }
+        Some(BlockIdentifier::SwitchBlock(era_id)) => {
+            if !block.header().switch_block() {
+                return Err(ValidateResponseError::NotASwitchBlock);
+            }
+            if *era_id != u64::from(block.header().era_id()) {
+                return Err(ValidateResponseError::UnexpectedEra);
+            }
+        }
+        // As for the latest block, more is necessary here to show that no later switch block
+        // exists.
+        Some(BlockIdentifier::LatestSwitchBlock) => {
+            if !block.header().switch_block() {
+                return Err(ValidateResponseError::NotASwitchBlock);
+            }
+        }
+        // More is necessary here to show that the next block is later than the timestamp.
+        Some(BlockIdentifier::Timestamp(timestamp)) => {
+            if block.header().timestamp() > *timestamp {
+                return Err(ValidateResponseError::UnexpectedBlockTimestamp);
+            }
+        }
         // More is necessary here to mitigate a MITM attack. In this case we would want to validate
         // `block.proofs()` to make sure that 1/3 of the validator weight signed the block, and we
         // would have to know the latest validators through some trustworthy means
@@ -213,3 +563,73 @@ pub(crate) fn validate_get_block_response(
     }
     Ok(())
 }
+
+pub(crate) fn validate_get_blocks_response(
+    response: &JsonRpc,
+    start_height: u64,
+    end_height: u64,
+) -> Result<(), ValidateResponseError> {
+    let maybe_result = response.get_result();
+    let blocks_value = maybe_result
+        .and_then(|value| value.get("blocks"))
+        .ok_or(ValidateResponseError::NoBlocksInResponse)?;
+    let blocks: Vec<Block> = serde_json::from_value(blocks_value.to_owned())?;
+    // The blocks must form a contiguous run starting at `start_height` and not exceeding
+    // `end_height`.
+    if blocks.len() as u64 > end_height.saturating_sub(start_height).saturating_add(1) {
+        return Err(ValidateResponseError::UnexpectedBlockHeight);
+    }
+    for (expected_height, block) in (start_height..).zip(blocks.iter()) {
+        block.verify()?;
+        if block.height() != expected_height {
+            return Err(ValidateResponseError::UnexpectedBlockHeight);
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_get_block_headers_response(
+    response: &JsonRpc,
+    start_height: u64,
+    end_height: u64,
+) -> Result<(), ValidateResponseError> {
+    let maybe_result = response.get_result();
+    let headers_value = maybe_result
+        .and_then(|value| value.get("headers"))
+        .ok_or(ValidateResponseError::NoBlocksInResponse)?;
+    let headers: Vec<BlockHeader> = serde_json::from_value(headers_value.to_owned())?;
+    // The headers must form a contiguous run starting at `start_height` and not exceeding
+    // `end_height`, each being the parent of the next.
+    if headers.len() as u64 > end_height.saturating_sub(start_height).saturating_add(1) {
+        return Err(ValidateResponseError::UnexpectedBlockHeight);
+    }
+    for (expected_height, header) in (start_height..).zip(headers.iter()) {
+        if header.height() != expected_height {
+            return Err(ValidateResponseError::UnexpectedBlockHeight);
+        }
+    }
+    for pair in headers.windows(2) {
+        if *pair[1].parent_hash() != pair[0].hash() {
+            return Err(ValidateResponseError::UnexpectedBlockHash);
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_get_alternative_blocks_response(
+    response: &JsonRpc,
+    height: u64,
+) -> Result<(), ValidateResponseError> {
+    let maybe_result = response.get_result();
+    let blocks_value = maybe_result
+        .and_then(|value| value.get("blocks"))
+        .ok_or(ValidateResponseError::NoBlocksInResponse)?;
+    let blocks: Vec<Block> = serde_json::from_value(blocks_value.to_owned())?;
+    for block in &blocks {
+        block.verify()?;
+        if block.height() != height {
+            return Err(ValidateResponseError::UnexpectedBlockHeight);
+        }
+    }
+    Ok(())
+}
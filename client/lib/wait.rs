@@ -0,0 +1,323 @@
+//! Polling a node until a deploy satisfies given criteria, e.g. until its block is finalized.
+
+use std::{
+    cmp,
+    collections::BTreeMap,
+    convert::TryFrom,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+    thread,
+    time::{Duration, Instant},
+};
+
+use jsonrpc_lite::JsonRpc;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use casper_execution_engine::{
+    shared::stored_value::StoredValue, storage::trie::merkle_proof::TrieMerkleProof,
+};
+use casper_node::{
+    crypto::asymmetric_key::{self, PublicKey, Signature},
+    rpcs::{
+        chain::{GetBlockByDeployResult, GetBlockResult, GetEraSummaryResult},
+        info::GetDeployResult,
+    },
+    types::{json_compatibility::ExecutionResult, BlockHash, DeployHash},
+};
+use casper_types::{
+    auction::{SeigniorageRecipients, SeigniorageRecipientsSnapshot},
+    bytesrepr, Key, U512,
+};
+
+use crate::{
+    error::{Error, Result},
+    rpc::RpcCall,
+};
+
+/// The interval between the first two polls.
+const INITIAL_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The maximum interval between two polls, reached by doubling the interval after each poll.
+const MAX_POLL_INTERVAL: Duration = Duration::from_secs(16);
+
+/// The state a deploy must reach for [`wait_for_deploy`](fn.wait_for_deploy.html) to return.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DeployCriteria {
+    /// The deploy is included in a block on the linear chain.
+    Included,
+    /// The deploy is included in a block on the linear chain, and its execution result is known.
+    Executed,
+    /// The deploy is executed, and its block is signed by validators holding at least one third
+    /// of the weight of the block's era.
+    Finalized,
+}
+
+impl FromStr for DeployCriteria {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "included" => Ok(DeployCriteria::Included),
+            "executed" => Ok(DeployCriteria::Executed),
+            "finalized" => Ok(DeployCriteria::Finalized),
+            _ => Err(Error::InvalidArgument(
+                "criteria",
+                format!(
+                    "'{}' must be one of 'included', 'executed' or 'finalized'",
+                    value
+                ),
+            )),
+        }
+    }
+}
+
+impl Display for DeployCriteria {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            DeployCriteria::Included => write!(formatter, "included"),
+            DeployCriteria::Executed => write!(formatter, "executed"),
+            DeployCriteria::Finalized => write!(formatter, "finalized"),
+        }
+    }
+}
+
+/// The state of a deploy once it satisfied the criteria waited for.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct DeployStatus {
+    /// The deploy hash.
+    pub deploy_hash: DeployHash,
+    /// The hash of the block on the linear chain including the deploy.
+    pub block_hash: BlockHash,
+    /// The height of the block.
+    pub block_height: u64,
+    /// The result of executing the deploy, if the criteria required it.
+    pub execution_result: Option<ExecutionResult>,
+    /// The weight of the validators whose signatures of the block were verified, if the criteria
+    /// required it.
+    pub signed_weight: Option<U512>,
+    /// The total weight of the validators of the block's era, if the criteria required it.
+    pub total_weight: Option<U512>,
+}
+
+/// Polls the node until the deploy satisfies `criteria`, backing off exponentially between polls.
+///
+/// Errors which may be transient, e.g. the node not knowing the deploy yet or being unreachable,
+/// cause another poll.  Responses failing validation are returned as errors straight away.
+pub(crate) fn wait_for_deploy(
+    rpc_call: RpcCall,
+    deploy_hash: DeployHash,
+    criteria: DeployCriteria,
+    timeout: Duration,
+) -> Result<DeployStatus> {
+    let deadline = Instant::now() + timeout;
+    let mut interval = INITIAL_POLL_INTERVAL;
+    loop {
+        match poll(rpc_call.clone(), deploy_hash, criteria) {
+            Ok(Some(status)) => return Ok(status),
+            Ok(None) => (),
+            Err(error) if is_transient(&error) => (),
+            Err(error) => return Err(error),
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return Err(Error::WaitForDeployTimedOut {
+                deploy_hash,
+                criteria: criteria.to_string(),
+            });
+        }
+        thread::sleep(cmp::min(interval, deadline - now));
+        interval = cmp::min(interval * 2, MAX_POLL_INTERVAL);
+    }
+}
+
+/// Returns whether a later poll might succeed after `error`.
+fn is_transient(error: &Error) -> bool {
+    matches!(
+        error,
+        Error::ResponseIsError(_)
+            | Error::FailedToGetResponse(_)
+            | Error::FailedSending(_)
+            | Error::NodeAtCapacity { .. }
+    )
+}
+
+/// Queries the node once, returning the deploy's status if it satisfies `criteria`.
+fn poll(
+    rpc_call: RpcCall,
+    deploy_hash: DeployHash,
+    criteria: DeployCriteria,
+) -> Result<Option<DeployStatus>> {
+    let hex_deploy_hash = hex::encode(deploy_hash.inner());
+    let included: GetBlockByDeployResult =
+        parse_result(&rpc_call.clone().get_block_by_deploy(&hex_deploy_hash)?)?;
+    let mut status = DeployStatus {
+        deploy_hash,
+        block_hash: included.block_hash,
+        block_height: included.block_header.height(),
+        execution_result: None,
+        signed_weight: None,
+        total_weight: None,
+    };
+    if criteria == DeployCriteria::Included {
+        return Ok(Some(status));
+    }
+
+    let deploy: GetDeployResult = parse_result(&rpc_call.clone().get_deploy(&hex_deploy_hash)?)?;
+    status.execution_result = deploy
+        .execution_results
+        .into_iter()
+        .find(|result| result.block_hash == status.block_hash)
+        .map(|result| result.result);
+    if status.execution_result.is_none() {
+        return Ok(None);
+    }
+    if criteria == DeployCriteria::Executed {
+        return Ok(Some(status));
+    }
+
+    // The validators of an era are recorded by the switch block of the previous era, so the
+    // signatures of blocks in the genesis era can't be weighed.
+    let era_id = u64::from(included.block_header.era_id());
+    if era_id == 0 {
+        return Err(Error::InvalidArgument(
+            "criteria",
+            "finality can't be verified for blocks of the genesis era".to_string(),
+        ));
+    }
+    let block: GetBlockResult = parse_result(
+        &rpc_call
+            .clone()
+            .get_block(&hex::encode(status.block_hash.inner()))?,
+    )?;
+    let block = match block.block {
+        Some(block) => block,
+        None => return Ok(None),
+    };
+    let era_summary: GetEraSummaryResult =
+        parse_result(&rpc_call.get_era_summary(&(era_id - 1).to_string(), "")?)?;
+    let weights = validator_weights(&era_summary, era_id)?;
+    let signed_weight = signed_weight(&status.block_hash, block.proofs(), &weights);
+    let total_weight = weights
+        .values()
+        .fold(U512::zero(), |sum, weight| sum + *weight);
+    status.signed_weight = Some(signed_weight);
+    status.total_weight = Some(total_weight);
+    if signed_weight * 3 < total_weight {
+        return Ok(None);
+    }
+    Ok(Some(status))
+}
+
+/// Parses the result of a successful response.
+fn parse_result<T: DeserializeOwned>(response: &JsonRpc) -> Result<T> {
+    let value = response
+        .get_result()
+        .ok_or_else(|| Error::InvalidRpcResponse(response.clone()))?;
+    Ok(serde_json::from_value(value.to_owned())?)
+}
+
+/// Returns the weights of the validators of era `era_id`, keyed by the key they sign with, as
+/// recorded by the switch block of the previous era.
+///
+/// The era summary must have been validated against its merkle proof.
+fn validator_weights(
+    era_summary: &GetEraSummaryResult,
+    era_id: u64,
+) -> Result<BTreeMap<PublicKey, U512>> {
+    let proof_bytes = hex::decode(&era_summary.era_summary.merkle_proof).map_err(|error| {
+        Error::InvalidArgument("merkle_proof", format!("invalid hex: {}", error))
+    })?;
+    let proofs: Vec<TrieMerkleProof<Key, StoredValue>> = bytesrepr::deserialize(proof_bytes)?;
+    let snapshot: SeigniorageRecipientsSnapshot = proofs
+        .last()
+        .and_then(|proof| proof.value().as_cl_value())
+        .ok_or_else(|| {
+            Error::InvalidCLValue("era summary doesn't hold a recipients snapshot".to_string())
+        })?
+        .clone()
+        .into_t()
+        .map_err(|error| Error::InvalidCLValue(format!("{:?}", error)))?;
+    let recipients: &SeigniorageRecipients = snapshot.get(&era_id).ok_or_else(|| {
+        Error::InvalidCLValue(format!("no validators recorded for era {}", era_id))
+    })?;
+    let mut weights = BTreeMap::new();
+    for (public_key, recipient) in recipients {
+        let signing_key = recipient.signing_key().unwrap_or(public_key);
+        let signing_key = PublicKey::try_from(*signing_key)?;
+        weights.insert(signing_key, recipient.total_stake());
+    }
+    Ok(weights)
+}
+
+/// Returns the total weight of the validators with a valid signature of `block_hash` among
+/// `proofs`.
+fn signed_weight(
+    block_hash: &BlockHash,
+    proofs: &[Signature],
+    weights: &BTreeMap<PublicKey, U512>,
+) -> U512 {
+    weights
+        .iter()
+        .filter(|(public_key, _)| {
+            proofs.iter().any(|signature| {
+                asymmetric_key::verify(block_hash.inner(), signature, public_key).is_ok()
+            })
+        })
+        .fold(U512::zero(), |sum, (_, weight)| sum + *weight)
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_node::{crypto::hash::Digest, NodeRng};
+
+    use super::*;
+
+    #[test]
+    fn should_parse_criteria() {
+        assert_eq!(
+            "included".parse::<DeployCriteria>().unwrap(),
+            DeployCriteria::Included
+        );
+        assert_eq!(
+            "Executed".parse::<DeployCriteria>().unwrap(),
+            DeployCriteria::Executed
+        );
+        assert_eq!(
+            "finalized".parse::<DeployCriteria>().unwrap(),
+            DeployCriteria::Finalized
+        );
+        assert!("proposed".parse::<DeployCriteria>().is_err());
+    }
+
+    #[test]
+    fn should_weigh_only_valid_signatures() {
+        let mut rng = casper_node::new_rng();
+        let block_hash = BlockHash::new(Digest::from([1; Digest::LENGTH]));
+        let other_block_hash = BlockHash::new(Digest::from([2; Digest::LENGTH]));
+        let validators: Vec<_> = (0..3)
+            .map(|_| asymmetric_key::generate_ed25519_keypair())
+            .collect();
+        let weights: BTreeMap<_, _> = validators
+            .iter()
+            .zip(&[40u64, 30, 30])
+            .map(|((_, public_key), weight)| (*public_key, U512::from(*weight)))
+            .collect();
+
+        let sign = |hash: &BlockHash, index: usize, rng: &mut NodeRng| {
+            let (secret_key, public_key) = &validators[index];
+            asymmetric_key::sign(hash.inner(), secret_key, public_key, rng)
+        };
+        let proofs = vec![
+            sign(&block_hash, 0, &mut rng),
+            // A signature of another block doesn't count.
+            sign(&other_block_hash, 1, &mut rng),
+            sign(&block_hash, 2, &mut rng),
+            // Nor does a duplicate.
+            sign(&block_hash, 2, &mut rng),
+        ];
+        assert_eq!(
+            signed_weight(&block_hash, &proofs, &weights),
+            U512::from(70)
+        );
+    }
+}
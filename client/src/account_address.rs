@@ -0,0 +1,77 @@
+use std::{fs, str};
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use casper_node::crypto::asymmetric_key::PublicKey;
+
+use crate::command::ClientCommand;
+
+/// This struct defines the order in which the args are shown for this subcommand's help message.
+enum DisplayOrder {
+    PublicKey,
+}
+
+/// Handles providing the arg for and retrieval of the public key.
+mod public_key {
+    use super::*;
+
+    const ARG_NAME: &str = "public-key";
+    const ARG_SHORT: &str = "p";
+    const ARG_VALUE_NAME: &str = "FORMATTED STRING or PATH";
+    const ARG_HELP: &str =
+        "The public key of the account, of either the Ed25519 or secp256k1 algorithm. This must be \
+        a properly formatted public key, i.e. \"<HEX STRING>\" with the hex-encoded algorithm tag \
+        prefixed. The public key may instead be read in from a file, in which case enter the path \
+        to the file as the --public-key argument. The file should be one of the two public key \
+        files generated via the `keygen` subcommand; \"public_key_hex\" or \"public_key.pem\"";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(true)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::PublicKey as usize)
+    }
+
+    pub(super) fn get(matches: &ArgMatches) -> String {
+        let value = matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME));
+
+        // Try to read as a PublicKey PEM file first.
+        if let Ok(public_key) = PublicKey::from_file(value) {
+            return public_key.to_hex();
+        }
+
+        // Try to read as a hex-encoded PublicKey file next.
+        if let Ok(contents) = fs::read_to_string(value) {
+            return contents.trim().to_string();
+        }
+
+        // Just return the value.
+        value.to_string()
+    }
+}
+
+pub struct AccountAddress {}
+
+impl<'a, 'b> ClientCommand<'a, 'b> for AccountAddress {
+    const NAME: &'static str = "account-address";
+    const ABOUT: &'static str = "Generates an account hash from a given public key";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(public_key::arg())
+    }
+
+    fn run(matches: &ArgMatches<'_>) {
+        let public_key = public_key::get(matches);
+        let account_hash = casper_client::account_address(&public_key)
+            .unwrap_or_else(|error| panic!("error: {}", error));
+        println!("{}", account_hash.to_formatted_string());
+    }
+}
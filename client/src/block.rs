@@ -1 +1,5 @@
 mod get;
+mod get_alternatives;
+mod get_at_timestamp;
+mod get_by_deploy;
+mod get_range;
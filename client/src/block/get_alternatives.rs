@@ -0,0 +1,74 @@
+use std::str;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use casper_node::rpcs::chain::GetAlternativeBlocks;
+
+use crate::{command::ClientCommand, common};
+
+/// This struct defines the order in which the args are shown for this subcommand.
+enum DisplayOrder {
+    Verbose,
+    NodeAddress,
+    RpcId,
+    Height,
+}
+
+/// Handles providing the arg for and retrieval of the height of the blocks.
+mod height {
+    use super::*;
+
+    const ARG_NAME: &str = "height";
+    const ARG_SHORT: &str = "H";
+    const ARG_VALUE_NAME: &str = common::ARG_INTEGER;
+    const ARG_HELP: &str = "Height of the blocks";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(true)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Height as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+impl<'a, 'b> ClientCommand<'a, 'b> for GetAlternativeBlocks {
+    const NAME: &'static str = "get-alternative-blocks";
+    const ABOUT: &'static str =
+        "Retrieves the blocks held by the node which conflict with the linear chain at a given height";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(common::verbose::arg(DisplayOrder::Verbose as usize))
+            .arg(common::node_address::arg(
+                DisplayOrder::NodeAddress as usize,
+            ))
+            .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
+            .arg(height::arg())
+    }
+
+    fn run(matches: &ArgMatches<'_>) {
+        let maybe_rpc_id = common::rpc_id::get(matches);
+        let node_address = common::node_address::get(matches);
+        let verbose = common::verbose::get(matches);
+        let height = height::get(matches);
+
+        let response =
+            casper_client::get_alternative_blocks(maybe_rpc_id, node_address, verbose, height)
+                .unwrap_or_else(|error| panic!("response error: {}", error));
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&response).expect("should encode to JSON")
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use std::str;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use serde_json::json;
+
+use casper_node::rpcs::chain::GetBlockAtTimestamp;
+
+use crate::{command::ClientCommand, common};
+
+/// This struct defines the order in which the args are shown for this subcommand.
+enum DisplayOrder {
+    Verbose,
+    NodeAddress,
+    RpcId,
+    Timestamp,
+    Key,
+    Path,
+    PurseURef,
+}
+
+/// Handles providing the arg for and retrieval of the timestamp.
+mod timestamp {
+    use super::*;
+
+    const ARG_NAME: &str = "timestamp";
+    const ARG_VALUE_NAME: &str = "TIMESTAMP";
+    const ARG_HELP: &str = "RFC 3339 timestamp, e.g. 2020-11-30T23:59:59Z";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .required(true)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Timestamp as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+/// Handles providing the arg for and retrieval of the key to query as of the block.
+mod key {
+    use super::*;
+
+    pub(super) const ARG_NAME: &str = "key";
+    const ARG_SHORT: &str = "k";
+    const ARG_VALUE_NAME: &str = "FORMATTED STRING";
+    const ARG_HELP: &str =
+        "A public key or formatted key, as for query-state, to query as of the block. The query \
+        result is validated against its Merkle proof";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Key as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> Option<&'a str> {
+        matches.value_of(ARG_NAME)
+    }
+}
+
+/// Handles providing the arg for and retrieval of the query path.
+mod path {
+    use super::*;
+
+    const ARG_NAME: &str = "query-path";
+    const ARG_SHORT: &str = "q";
+    const ARG_VALUE_NAME: &str = "PATH/FROM/KEY";
+    const ARG_HELP: &str = "The path from the key of the query";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(false)
+            .requires(super::key::ARG_NAME)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Path as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
+/// Handles providing the arg for and retrieval of the purse whose balance to query.
+mod purse_uref {
+    use super::*;
+
+    const ARG_NAME: &str = "purse-uref";
+    const ARG_SHORT: &str = "p";
+    const ARG_VALUE_NAME: &str = "FORMATTED STRING";
+    const ARG_HELP: &str =
+        "The URef of a purse whose balance to query as of the block, formatted as \
+        \"uref-<HEX STRING>-<THREE DIGIT INTEGER>\". The balance is validated against its Merkle \
+        proof";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::PurseURef as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> Option<&'a str> {
+        matches.value_of(ARG_NAME)
+    }
+}
+
+impl<'a, 'b> ClientCommand<'a, 'b> for GetBlockAtTimestamp {
+    const NAME: &'static str = "get-block-at-timestamp";
+    const ABOUT: &'static str =
+        "Retrieves the header of the last block not later than a given timestamp, optionally \
+        querying global state as of that block";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(common::verbose::arg(DisplayOrder::Verbose as usize))
+            .arg(common::node_address::arg(
+                DisplayOrder::NodeAddress as usize,
+            ))
+            .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
+            .arg(timestamp::arg())
+            .arg(key::arg())
+            .arg(path::arg())
+            .arg(purse_uref::arg())
+    }
+
+    fn run(matches: &ArgMatches<'_>) {
+        let maybe_rpc_id = common::rpc_id::get(matches);
+        let node_address = common::node_address::get(matches);
+        let verbose = common::verbose::get(matches);
+        let timestamp = timestamp::get(matches);
+
+        let response =
+            casper_client::get_block_at_timestamp(maybe_rpc_id, node_address, verbose, timestamp)
+                .unwrap_or_else(|error| panic!("response error: {}", error));
+
+        let maybe_key = key::get(matches);
+        let maybe_purse_uref = purse_uref::get(matches);
+        if maybe_key.is_none() && maybe_purse_uref.is_none() {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&response).expect("should encode to JSON")
+            );
+            return;
+        }
+
+        // Follow-up queries are made against the state root hash of the validated block header.
+        let state_root_hash = response
+            .get_result()
+            .and_then(|result| result.get("block_header"))
+            .and_then(|block_header| block_header.get("state_root_hash"))
+            .and_then(|state_root_hash| state_root_hash.as_str())
+            .unwrap_or_else(|| panic!("response should contain a state root hash"))
+            .to_string();
+
+        let mut output = json!({ "block_response": response });
+        if let Some(key) = maybe_key {
+            let query_response = casper_client::get_item(
+                maybe_rpc_id,
+                node_address,
+                verbose,
+                &state_root_hash,
+                key,
+                path::get(matches),
+            )
+            .unwrap_or_else(|error| panic!("query response error: {}", error));
+            let mut query_output =
+                serde_json::to_value(&query_response).expect("should encode to JSON");
+            casper_client::decode_cl_values(&mut query_output);
+            output["query_response"] = query_output;
+        }
+        if let Some(purse_uref) = maybe_purse_uref {
+            let balance_response = casper_client::get_balance(
+                maybe_rpc_id,
+                node_address,
+                verbose,
+                &state_root_hash,
+                purse_uref,
+            )
+            .unwrap_or_else(|error| panic!("balance response error: {}", error));
+            output["balance_response"] =
+                serde_json::to_value(&balance_response).expect("should encode to JSON");
+        }
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&output).expect("should encode to JSON")
+        );
+    }
+}
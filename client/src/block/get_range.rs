@@ -0,0 +1,134 @@
+use std::str;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use casper_node::rpcs::chain::GetBlocks;
+
+use crate::{command::ClientCommand, common};
+
+/// This struct defines the order in which the args are shown for this subcommand.
+enum DisplayOrder {
+    Verbose,
+    NodeAddress,
+    RpcId,
+    StartHeight,
+    EndHeight,
+    HeadersOnly,
+}
+
+/// Handles providing the arg for and retrieval of the height of the first block in the range.
+mod start_height {
+    use super::*;
+
+    const ARG_NAME: &str = "start-height";
+    const ARG_SHORT: &str = "s";
+    const ARG_VALUE_NAME: &str = common::ARG_INTEGER;
+    const ARG_HELP: &str = "Height of the first block in the range";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(true)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::StartHeight as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+/// Handles providing the arg for and retrieval of the height of the last block in the range.
+mod end_height {
+    use super::*;
+
+    const ARG_NAME: &str = "end-height";
+    const ARG_SHORT: &str = "e";
+    const ARG_VALUE_NAME: &str = common::ARG_INTEGER;
+    const ARG_HELP: &str =
+        "Height of the last block in the range (inclusive). The node may return fewer blocks than \
+        requested, in which case the response's \"next_height\" indicates where to resume";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(true)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::EndHeight as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+/// Handles providing the arg for and retrieval of the headers-only flag.
+mod headers_only {
+    use super::*;
+
+    const ARG_NAME: &str = "headers-only";
+    const ARG_HELP: &str =
+        "If passed, only the headers of the blocks are retrieved, rather than the full blocks";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::HeadersOnly as usize)
+    }
+
+    pub(super) fn get(matches: &ArgMatches) -> bool {
+        matches.is_present(ARG_NAME)
+    }
+}
+
+impl<'a, 'b> ClientCommand<'a, 'b> for GetBlocks {
+    const NAME: &'static str = "get-blocks";
+    const ABOUT: &'static str = "Retrieves a contiguous range of blocks from the network";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(common::verbose::arg(DisplayOrder::Verbose as usize))
+            .arg(common::node_address::arg(
+                DisplayOrder::NodeAddress as usize,
+            ))
+            .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
+            .arg(start_height::arg())
+            .arg(end_height::arg())
+            .arg(headers_only::arg())
+    }
+
+    fn run(matches: &ArgMatches<'_>) {
+        let maybe_rpc_id = common::rpc_id::get(matches);
+        let node_address = common::node_address::get(matches);
+        let verbose = common::verbose::get(matches);
+        let start_height = start_height::get(matches);
+        let end_height = end_height::get(matches);
+        let headers_only = headers_only::get(matches);
+
+        let response = casper_client::get_blocks(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            start_height,
+            end_height,
+            headers_only,
+        )
+        .unwrap_or_else(|error| panic!("response error: {}", error));
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&response).expect("should encode to JSON")
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use clap::{Arg, ArgMatches};
 
+use crate::profile;
+
 pub const ARG_PATH: &str = "PATH";
 pub const ARG_HEX_STRING: &str = "HEX STRING";
 pub const ARG_STRING: &str = "STRING";
@@ -34,7 +36,9 @@ pub mod node_address {
     const ARG_SHORT: &str = "n";
     const ARG_VALUE_NAME: &str = "HOST:PORT";
     const ARG_DEFAULT: &str = "http://localhost:50101";
-    const ARG_HELP: &str = "Hostname or IP and port of node on which HTTP service is running";
+    const ARG_HELP: &str =
+        "Hostname or IP and port of node on which HTTP service is running. If not provided, the \
+        node address of the selected profile is used, if any";
 
     pub fn arg(order: usize) -> Arg<'static, 'static> {
         Arg::with_name(ARG_NAME)
@@ -48,6 +52,11 @@ pub mod node_address {
     }
 
     pub fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        if matches.occurrences_of(ARG_NAME) == 0 {
+            if let Some(node_address) = profile::get().node_address() {
+                return node_address;
+            }
+        }
         matches
             .value_of(ARG_NAME)
             .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
@@ -85,13 +94,15 @@ pub mod secret_key {
     const ARG_NAME: &str = "secret-key";
     const ARG_SHORT: &str = "k";
     const ARG_VALUE_NAME: &str = super::ARG_PATH;
-    const ARG_HELP: &str = "Path to secret key file";
+    const ARG_HELP: &str =
+        "Path to secret key file. If not provided, the secret key in the keys directory of the \
+        selected profile is used";
 
     pub fn arg(order: usize) -> Arg<'static, 'static> {
         Arg::with_name(ARG_NAME)
             .long(ARG_NAME)
             .short(ARG_SHORT)
-            .required(true)
+            .required(false)
             .value_name(ARG_VALUE_NAME)
             .help(ARG_HELP)
             .display_order(order)
@@ -100,7 +111,13 @@ pub mod secret_key {
     pub fn get<'a>(matches: &'a ArgMatches) -> &'a str {
         matches
             .value_of(ARG_NAME)
-            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+            .or_else(|| profile::get().secret_key())
+            .unwrap_or_else(|| {
+                panic!(
+                    "should have {} arg, or a profile with a keys directory",
+                    ARG_NAME
+                )
+            })
     }
 }
 
@@ -138,7 +155,7 @@ pub mod force {
 pub mod state_root_hash {
     use super::*;
 
-    const ARG_NAME: &str = "state-root-hash";
+    pub(crate) const ARG_NAME: &str = "state-root-hash";
     const ARG_SHORT: &str = "s";
     const ARG_VALUE_NAME: &str = super::ARG_HEX_STRING;
     const ARG_HELP: &str = "Hex-encoded hash of the state root";
@@ -164,12 +181,14 @@ pub mod state_root_hash {
 pub mod block_identifier {
     use super::*;
 
-    const ARG_NAME: &str = "block-identifier";
+    pub(crate) const ARG_NAME: &str = "block-identifier";
     const ARG_SHORT: &str = "b";
     const ARG_VALUE_NAME: &str = "HEX STRING OR INTEGER";
     const ARG_HELP: &str =
-        "Hex-encoded block hash or height of the block. If not given, the last block added to the \
-        chain as known at the given node will be used";
+        "Hex-encoded block hash or height of the block. Also accepted are \"latest-switch\" for the \
+        most recent switch block, \"era-<INTEGER>\" for the switch block of the given era and an \
+        RFC 3339 timestamp for the most recent block not later than it. If not given, the last \
+        block added to the chain as known at the given node will be used";
 
     pub(crate) fn arg(order: usize) -> Arg<'static, 'static> {
         Arg::with_name(ARG_NAME)
@@ -185,3 +204,91 @@ pub mod block_identifier {
         matches.value_of(ARG_NAME).unwrap_or_default()
     }
 }
+
+/// Handles providing the args for and retrieval of a block selected by its height, its era or a
+/// point in time, as alternatives to the block identifier.
+pub mod block_selector {
+    use clap::ArgGroup;
+
+    use casper_client::{LATEST_SWITCH_BLOCK, SWITCH_BLOCK_PREFIX};
+
+    use super::*;
+
+    pub(crate) const GROUP_NAME: &str = "block";
+
+    const HEIGHT_ARG_NAME: &str = "block-height";
+    const HEIGHT_ARG_HELP: &str = "Height of the block";
+
+    const ERA_ID_ARG_NAME: &str = "era-id";
+    const ERA_ID_ARG_VALUE_NAME: &str = "INTEGER OR \"latest-switch\"";
+    const ERA_ID_ARG_HELP: &str =
+        "ID of the era whose switch block is used, or \"latest-switch\" for the most recent switch \
+        block";
+
+    const TIMESTAMP_ARG_NAME: &str = "timestamp";
+    const TIMESTAMP_ARG_VALUE_NAME: &str = "TIMESTAMP";
+    const TIMESTAMP_ARG_HELP: &str =
+        "RFC 3339 timestamp, e.g. 2020-11-17T00:39:24Z. The most recent block not later than it is \
+        used, as found by the node";
+
+    /// Returns the block identifier arg and its alternatives, all shown at `order`.
+    pub(crate) fn args(order: usize) -> Vec<Arg<'static, 'static>> {
+        vec![
+            block_identifier::arg(order),
+            Arg::with_name(HEIGHT_ARG_NAME)
+                .long(HEIGHT_ARG_NAME)
+                .required(false)
+                .value_name(super::ARG_INTEGER)
+                .help(HEIGHT_ARG_HELP)
+                .display_order(order),
+            Arg::with_name(ERA_ID_ARG_NAME)
+                .long(ERA_ID_ARG_NAME)
+                .required(false)
+                .value_name(ERA_ID_ARG_VALUE_NAME)
+                .help(ERA_ID_ARG_HELP)
+                .display_order(order),
+            Arg::with_name(TIMESTAMP_ARG_NAME)
+                .long(TIMESTAMP_ARG_NAME)
+                .required(false)
+                .value_name(TIMESTAMP_ARG_VALUE_NAME)
+                .help(TIMESTAMP_ARG_HELP)
+                .display_order(order),
+        ]
+    }
+
+    /// Returns the group allowing at most one of the args to be provided.
+    pub(crate) fn group() -> ArgGroup<'static> {
+        ArgGroup::with_name(GROUP_NAME)
+            .args(&[
+                block_identifier::ARG_NAME,
+                HEIGHT_ARG_NAME,
+                ERA_ID_ARG_NAME,
+                TIMESTAMP_ARG_NAME,
+            ])
+            .multiple(false)
+    }
+
+    /// Returns the provided arg in the form of a block identifier, or an empty string if none was
+    /// provided.
+    pub(crate) fn get(matches: &ArgMatches) -> String {
+        if let Some(height) = matches.value_of(HEIGHT_ARG_NAME) {
+            let height: u64 = height
+                .parse()
+                .unwrap_or_else(|error| panic!("invalid --{}: {}", HEIGHT_ARG_NAME, error));
+            return height.to_string();
+        }
+        if let Some(era_id) = matches.value_of(ERA_ID_ARG_NAME) {
+            if era_id == LATEST_SWITCH_BLOCK {
+                return era_id.to_string();
+            }
+            let era_id: u64 = era_id
+                .parse()
+                .unwrap_or_else(|error| panic!("invalid --{}: {}", ERA_ID_ARG_NAME, error));
+            return format!("{}{}", SWITCH_BLOCK_PREFIX, era_id);
+        }
+        if let Some(timestamp) = matches.value_of(TIMESTAMP_ARG_NAME) {
+            return timestamp.to_string();
+        }
+        block_identifier::get(matches).to_string()
+    }
+}
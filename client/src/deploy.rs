@@ -1,15 +1,26 @@
+mod check;
 mod creation_common;
+mod disable_version;
+mod estimate;
 mod get;
 mod list;
 mod make;
+mod named_key;
 mod put;
 mod send;
 mod sign;
+mod simulate;
 mod transfer;
 
 pub use transfer::Transfer;
 
+pub use check::CheckDeploy;
+
+pub use disable_version::DisableContractVersion;
+
 pub use list::ListDeploys;
 pub use make::MakeDeploy;
+pub use named_key::{PutNamedKey, RemoveNamedKey};
 pub use send::SendDeploy;
 pub use sign::SignDeploy;
+pub use simulate::SimulateDeploy;
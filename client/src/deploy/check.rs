@@ -0,0 +1,40 @@
+use clap::{App, ArgMatches, SubCommand};
+
+use super::creation_common::{self, DisplayOrder};
+use crate::{command::ClientCommand, common};
+
+pub struct CheckDeploy;
+
+impl<'a, 'b> ClientCommand<'a, 'b> for CheckDeploy {
+    const NAME: &'static str = "check-deploy";
+    const ABOUT: &'static str =
+        "Reads a previously-saved deploy from a file and lists every reason the node would refuse \
+        it, without sending it";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(common::verbose::arg(DisplayOrder::Verbose as usize))
+            .arg(common::node_address::arg(
+                DisplayOrder::NodeAddress as usize,
+            ))
+            .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
+            .arg(creation_common::input::arg())
+    }
+
+    fn run(matches: &ArgMatches<'_>) {
+        let maybe_rpc_id = common::rpc_id::get(matches);
+        let node_address = common::node_address::get(matches);
+        let verbose = common::verbose::get(matches);
+        let input_path = creation_common::input::get(matches);
+
+        let response =
+            casper_client::check_deploy_file(maybe_rpc_id, node_address, verbose, &input_path)
+                .unwrap_or_else(|error| panic!("response error: {}", error));
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&response).expect("should encode to JSON")
+        );
+    }
+}
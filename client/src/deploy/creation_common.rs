@@ -8,7 +8,7 @@ use lazy_static::lazy_static;
 
 use casper_client::{help, PaymentStrParams, SessionStrParams};
 
-use crate::common;
+use crate::{common, profile};
 
 /// This struct defines the order in which the args are shown for this subcommand's help message.
 pub(super) enum DisplayOrder {
@@ -18,11 +18,20 @@ pub(super) enum DisplayOrder {
     RpcId,
     SecretKey,
     Input,
+    IdempotencyKey,
     Output,
+    Chainspec,
+    StoragePath,
+    StateRootHash,
+    ProfileGas,
     TransferAmount,
     TransferSourcePurse,
     TransferTargetAccount,
     TransferTargetPurse,
+    DisableContractPackageHash,
+    DisableContractHash,
+    NamedKeyName,
+    NamedKeyKey,
     Timestamp,
     Ttl,
     GasPrice,
@@ -31,6 +40,7 @@ pub(super) enum DisplayOrder {
     SessionCode,
     SessionArgSimple,
     SessionArgsComplex,
+    SessionArgsJson,
     SessionHash,
     SessionName,
     SessionPackageHash,
@@ -41,12 +51,14 @@ pub(super) enum DisplayOrder {
     PaymentCode,
     PaymentArgSimple,
     PaymentArgsComplex,
+    PaymentArgsJson,
     PaymentHash,
     PaymentName,
     PaymentPackageHash,
     PaymentPackageName,
     PaymentEntryPoint,
     PaymentVersion,
+    PaymentPurseName,
 }
 
 /// Handles providing the arg for and executing the show-arg-examples option.
@@ -73,7 +85,10 @@ pub(super) mod show_arg_examples {
             return false;
         }
 
-        println!("Examples for passing values via --session-arg or --payment-arg:");
+        println!(
+            "Examples for passing values via --session-arg, --payment-arg, --session-args-json or \
+            --payment-args-json:"
+        );
         println!("{}", help::supported_cl_type_examples());
 
         true
@@ -81,6 +96,10 @@ pub(super) mod show_arg_examples {
 }
 
 pub(super) fn session_str_params<'a>(matches: &'a ArgMatches) -> SessionStrParams<'a> {
+    session_code_str_params(matches).with_session_args_json(args_json::session::get(matches))
+}
+
+fn session_code_str_params<'a>(matches: &'a ArgMatches) -> SessionStrParams<'a> {
     let session_args_simple = arg_simple::session::get(matches);
     let session_args_complex = args_complex::session::get(matches);
     if let Some(session_path) = session_path::get(matches) {
@@ -130,6 +149,12 @@ pub(super) fn session_str_params<'a>(matches: &'a ArgMatches) -> SessionStrParam
 }
 
 pub(super) fn payment_str_params<'a>(matches: &'a ArgMatches) -> PaymentStrParams<'a> {
+    payment_code_str_params(matches)
+        .with_payment_args_json(args_json::payment::get(matches))
+        .with_payment_purse_name(payment_purse_name::get(matches))
+}
+
+fn payment_code_str_params<'a>(matches: &'a ArgMatches) -> PaymentStrParams<'a> {
     if let Some(payment_amount) = standard_payment_amount::get(matches) {
         return PaymentStrParams::with_amount(payment_amount);
     }
@@ -178,7 +203,11 @@ pub(super) fn payment_str_params<'a>(matches: &'a ArgMatches) -> PaymentStrParam
             payment_args_complex,
         );
     }
-    unreachable!("clap arg groups and parsing should prevent this")
+    // No payment option was given, so fall back to the standard payment of the selected profile.
+    match profile::get().payment_amount() {
+        Some(payment_amount) => PaymentStrParams::with_amount(payment_amount),
+        None => panic!("should have a payment option, or a profile with a payment amount"),
+    }
 }
 
 /// Handles providing the arg for and retrieval of the timestamp.
@@ -298,12 +327,12 @@ pub(super) mod chain_name {
     const ARG_VALUE_NAME: &str = "NAME";
     const ARG_HELP: &str =
         "Name of the chain, to avoid the deploy from being accidentally or maliciously included in \
-        a different chain";
+        a different chain. If not provided, the chain name of the selected profile is used";
 
     pub(in crate::deploy) fn arg() -> Arg<'static, 'static> {
         Arg::with_name(ARG_NAME)
             .long(ARG_NAME)
-            .required_unless(show_arg_examples::ARG_NAME)
+            .required(false)
             .value_name(ARG_VALUE_NAME)
             .help(ARG_HELP)
             .display_order(DisplayOrder::ChainName as usize)
@@ -312,7 +341,13 @@ pub(super) mod chain_name {
     pub(in crate::deploy) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
         matches
             .value_of(ARG_NAME)
-            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+            .or_else(|| profile::get().chain_name())
+            .unwrap_or_else(|| {
+                panic!(
+                    "should have {} arg, or a profile with a chain name",
+                    ARG_NAME
+                )
+            })
     }
 }
 
@@ -340,6 +375,36 @@ pub(super) mod session_path {
     }
 }
 
+/// Handles providing the arg for and retrieval of the path to one of the session modules bundled
+/// in `smart_contracts/contracts/client`, for subcommands which build the session args themselves.
+///
+/// The arg is optional for session modules embedded in the client, overriding the embedded one.
+pub(super) mod bundled_session_path {
+    use super::*;
+
+    const ARG_NAME: &str = session_path::ARG_NAME;
+    const ARG_SHORT: &str = "s";
+    const ARG_VALUE_NAME: &str = common::ARG_PATH;
+
+    pub(in crate::deploy) fn arg(help: &'static str, required: bool) -> Arg<'static, 'static> {
+        let arg = Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .value_name(ARG_VALUE_NAME)
+            .help(help)
+            .display_order(DisplayOrder::SessionCode as usize);
+        if required {
+            arg.required_unless(show_arg_examples::ARG_NAME)
+        } else {
+            arg.required(false)
+        }
+    }
+
+    pub(in crate::deploy) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
 /// Handles providing the arg for and retrieval of simple session and payment args.
 pub(super) mod arg_simple {
     use super::*;
@@ -348,9 +413,9 @@ pub(super) mod arg_simple {
 
     lazy_static! {
         static ref ARG_HELP: String = format!(
-            "For simple CLTypes, a named and typed arg which is passed to the Wasm code. To see \
-            an example for each type, run '--{}'. This arg can be repeated to pass multiple named, \
-            typed args, but can only be used for the following types: {}",
+            "A named and typed arg which is passed to the Wasm code. To see an example for each \
+            type, run '--{}'. This arg can be repeated to pass multiple named, typed args, and can \
+            be used for the following types, where compound types can be nested: {}",
             super::show_arg_examples::ARG_NAME,
             help::supported_cl_type_list()
         );
@@ -459,6 +524,62 @@ pub(super) mod args_complex {
     }
 }
 
+/// Handles providing the arg for and retrieval of session and payment args given as JSON.
+pub(super) mod args_json {
+    use super::*;
+
+    const ARG_VALUE_NAME: &str = "JSON";
+
+    lazy_static! {
+        static ref ARG_HELP: String = format!(
+            "A JSON array of named and typed args which are passed to the Wasm code, e.g. \
+            '[{{\"name\": \"amounts\", \"type\": \"list<u512>\", \"value\": [10, 20]}}]'. \
+            Supports all of the types supported by --{}. To see examples, run '--{}'",
+            super::arg_simple::session::ARG_NAME,
+            super::show_arg_examples::ARG_NAME,
+        );
+    }
+
+    pub(in crate::deploy) mod session {
+        use super::*;
+
+        pub const ARG_NAME: &str = "session-args-json";
+
+        pub fn arg() -> Arg<'static, 'static> {
+            super::arg(ARG_NAME, DisplayOrder::SessionArgsJson as usize)
+                .requires(super::session::ARG_NAME)
+        }
+
+        pub fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+            matches.value_of(ARG_NAME).unwrap_or_default()
+        }
+    }
+
+    pub(in crate::deploy) mod payment {
+        use super::*;
+
+        pub const ARG_NAME: &str = "payment-args-json";
+
+        pub fn arg() -> Arg<'static, 'static> {
+            super::arg(ARG_NAME, DisplayOrder::PaymentArgsJson as usize)
+                .requires(super::payment::ARG_NAME)
+        }
+
+        pub fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+            matches.value_of(ARG_NAME).unwrap_or_default()
+        }
+    }
+
+    fn arg(name: &'static str, order: usize) -> Arg<'static, 'static> {
+        Arg::with_name(name)
+            .long(name)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(&*ARG_HELP)
+            .display_order(order)
+    }
+}
+
 /// Handles providing the arg for and retrieval of the payment code bytes.
 pub(super) mod payment_path {
     use super::*;
@@ -524,10 +645,7 @@ pub(super) fn apply_common_creation_options<'a, 'b>(
     }
 
     subcommand = subcommand
-        .arg(
-            common::secret_key::arg(DisplayOrder::SecretKey as usize)
-                .required_unless(show_arg_examples::ARG_NAME),
-        )
+        .arg(common::secret_key::arg(DisplayOrder::SecretKey as usize))
         .arg(timestamp::arg())
         .arg(ttl::arg())
         .arg(gas_price::arg())
@@ -545,11 +663,13 @@ pub(super) fn apply_common_session_options<'a, 'b>(subcommand: App<'a, 'b>) -> A
         .arg(session_name::arg())
         .arg(arg_simple::session::arg())
         .arg(args_complex::session::arg())
+        .arg(args_json::session::arg())
         // Group the session-arg args so only one style is used to ensure consistent ordering.
         .group(
             ArgGroup::with_name("session-args")
                 .arg(arg_simple::session::ARG_NAME)
                 .arg(args_complex::session::ARG_NAME)
+                .arg(args_json::session::ARG_NAME)
                 .required(false),
         )
         .arg(session_entry_point::arg())
@@ -578,15 +698,18 @@ pub(crate) fn apply_common_payment_options(
         .arg(payment_name::arg())
         .arg(arg_simple::payment::arg())
         .arg(args_complex::payment::arg())
+        .arg(args_json::payment::arg())
         // Group the payment-arg args so only one style is used to ensure consistent ordering.
         .group(
             ArgGroup::with_name("payment-args")
                 .arg(arg_simple::payment::ARG_NAME)
                 .arg(args_complex::payment::ARG_NAME)
+                .arg(args_json::payment::ARG_NAME)
                 .required(false),
         )
         .arg(payment_entry_point::arg())
         .arg(payment_version::arg())
+        .arg(payment_purse_name::arg())
         .group(
             ArgGroup::with_name("payment")
                 .arg(standard_payment_amount::ARG_NAME)
@@ -596,7 +719,7 @@ pub(crate) fn apply_common_payment_options(
                 .arg(payment_hash::ARG_NAME)
                 .arg(payment_name::ARG_NAME)
                 .arg(show_arg_examples::ARG_NAME)
-                .required(true),
+                .required(false),
         )
 }
 
@@ -915,3 +1038,27 @@ pub(super) mod payment_version {
         matches.value_of(ARG_NAME).unwrap_or_default()
     }
 }
+
+pub(super) mod payment_purse_name {
+    use super::*;
+
+    pub const ARG_NAME: &str = "payment-purse-name";
+    const ARG_VALUE_NAME: &str = "NAME";
+    const ARG_HELP: &str =
+        "Name of a purse in the account's named keys to pay from instead of the main purse. The \
+        account must hold the purse with write access. Passed to the payment code as the \
+        'purse_name' arg";
+
+    pub fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .required(false)
+            .display_order(DisplayOrder::PaymentPurseName as usize)
+    }
+
+    pub fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
@@ -0,0 +1,130 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use casper_client::DeployStrParams;
+
+use super::creation_common::{self, DisplayOrder};
+use crate::{command::ClientCommand, common};
+
+/// Handles providing the arg for and retrieval of the contract package hash.
+mod contract_package_hash {
+    use super::*;
+
+    const ARG_NAME: &str = "contract-package-hash";
+    const ARG_VALUE_NAME: &str = "FORMATTED STRING or HEX STRING";
+    const ARG_HELP: &str =
+        "The hash of the contract package, either hex-encoded or formatted as a hash key, e.g. \
+        hash-0102...";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required_unless(creation_common::show_arg_examples::ARG_NAME)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::DisableContractPackageHash as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+/// Handles providing the arg for and retrieval of the hash of the contract version to disable.
+mod contract_hash {
+    use super::*;
+
+    const ARG_NAME: &str = "contract-hash";
+    const ARG_VALUE_NAME: &str = "FORMATTED STRING or HEX STRING";
+    const ARG_HELP: &str =
+        "The hash of the contract version to disable, either hex-encoded or formatted as a hash \
+        key. The hashes of a package's versions are listed by get-contract-package";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required_unless(creation_common::show_arg_examples::ARG_NAME)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::DisableContractHash as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+const SESSION_PATH_HELP: &str = "Path to the compiled disable_contract_version.wasm, built from \
+    smart_contracts/contracts/client/disable-contract-version";
+
+pub struct DisableContractVersion {}
+
+impl<'a, 'b> ClientCommand<'a, 'b> for DisableContractVersion {
+    const NAME: &'static str = "disable-contract-version";
+    const ABOUT: &'static str =
+        "Disables a version of a stored contract so that it can no longer be called. The account \
+        must hold the contract package's access key";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        let subcommand = SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(common::verbose::arg(DisplayOrder::Verbose as usize))
+            .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
+            .arg(creation_common::bundled_session_path::arg(
+                SESSION_PATH_HELP,
+                true,
+            ))
+            .arg(contract_package_hash::arg())
+            .arg(contract_hash::arg());
+        let subcommand = creation_common::apply_common_payment_options(subcommand);
+        creation_common::apply_common_creation_options(subcommand, true)
+    }
+
+    fn run(matches: &ArgMatches<'_>) {
+        creation_common::show_arg_examples_and_exit_if_required(matches);
+
+        let session_path = creation_common::bundled_session_path::get(matches);
+        let contract_package_hash = contract_package_hash::get(matches);
+        let contract_hash = contract_hash::get(matches);
+
+        let maybe_rpc_id = common::rpc_id::get(matches);
+        let node_address = common::node_address::get(matches);
+        let verbose = common::verbose::get(matches);
+
+        let secret_key = common::secret_key::get(matches);
+        let timestamp = creation_common::timestamp::get(matches);
+        let ttl = creation_common::ttl::get(matches);
+        let gas_price = creation_common::gas_price::get(matches);
+        let dependencies = creation_common::dependencies::get(matches);
+        let chain_name = creation_common::chain_name::get(matches);
+
+        let payment_str_params = creation_common::payment_str_params(matches);
+
+        let response = casper_client::disable_contract_version(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            session_path,
+            contract_package_hash,
+            contract_hash,
+            DeployStrParams {
+                secret_key,
+                timestamp,
+                ttl,
+                dependencies,
+                gas_price,
+                chain_name,
+            },
+            payment_str_params,
+        )
+        .unwrap_or_else(|err| panic!("unable to put deploy {:?}", err));
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&response).expect("should encode to JSON")
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use casper_client::DeployStrParams;
+use casper_node::rpcs::info::EstimateDeployCost;
+
+use super::creation_common::{self, DisplayOrder};
+use crate::{command::ClientCommand, common};
+
+/// Handles providing the arg for and retrieval of the gas profiling flag.
+mod profile_gas {
+    use super::*;
+
+    const ARG_NAME: &str = "profile-gas";
+    const ARG_HELP: &str =
+        "Breaks the gas consumed by the deploy down by host function and by opcode cost category";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::ProfileGas as usize)
+    }
+
+    pub(super) fn get(matches: &ArgMatches) -> bool {
+        matches.is_present(ARG_NAME)
+    }
+}
+
+impl<'a, 'b> ClientCommand<'a, 'b> for EstimateDeployCost {
+    const NAME: &'static str = "estimate";
+    const ABOUT: &'static str =
+        "Creates a deploy and sends it to the network to estimate its cost. The deploy is executed \
+        against the state of the latest block, but is not stored and its effects are not committed";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        let subcommand = SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(common::verbose::arg(DisplayOrder::Verbose as usize))
+            .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
+            .arg(profile_gas::arg());
+        let subcommand = creation_common::apply_common_session_options(subcommand);
+        let subcommand = creation_common::apply_common_payment_options(subcommand);
+        creation_common::apply_common_creation_options(subcommand, true)
+    }
+
+    fn run(matches: &ArgMatches<'_>) {
+        creation_common::show_arg_examples_and_exit_if_required(matches);
+
+        let maybe_rpc_id = common::rpc_id::get(matches);
+        let node_address = common::node_address::get(matches);
+        let verbose = common::verbose::get(matches);
+        let profile_gas = profile_gas::get(matches);
+
+        let secret_key = common::secret_key::get(matches);
+        let timestamp = creation_common::timestamp::get(matches);
+        let ttl = creation_common::ttl::get(matches);
+        let gas_price = creation_common::gas_price::get(matches);
+        let dependencies = creation_common::dependencies::get(matches);
+        let chain_name = creation_common::chain_name::get(matches);
+
+        let session_str_params = creation_common::session_str_params(matches);
+        let payment_str_params = creation_common::payment_str_params(matches);
+
+        let response = casper_client::estimate_deploy_cost(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            DeployStrParams {
+                secret_key,
+                timestamp,
+                ttl,
+                dependencies,
+                gas_price,
+                chain_name,
+            },
+            session_str_params,
+            payment_str_params,
+            profile_gas,
+        )
+        .unwrap_or_else(|err| panic!("unable to estimate deploy cost {:?}", err));
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&response).expect("should encode to JSON")
+        );
+    }
+}
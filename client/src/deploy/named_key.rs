@@ -0,0 +1,168 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use casper_client::DeployStrParams;
+
+use super::creation_common::{self, DisplayOrder};
+use crate::{command::ClientCommand, common};
+
+/// Handles providing the arg for and retrieval of the name of the named key.
+mod name {
+    use super::*;
+
+    const ARG_NAME: &str = "name";
+    const ARG_VALUE_NAME: &str = "STRING";
+    const ARG_HELP: &str = "The name of the key in the account's named keys";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required_unless(creation_common::show_arg_examples::ARG_NAME)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::NamedKeyName as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+/// Handles providing the arg for and retrieval of the key to add.
+mod key {
+    use super::*;
+
+    const ARG_NAME: &str = "key";
+    const ARG_VALUE_NAME: &str = "FORMATTED STRING";
+    const ARG_HELP: &str =
+        "The key to add, formatted as e.g. uref-0102...-007, hash-0102... or account-hash-0102...";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required_unless(creation_common::show_arg_examples::ARG_NAME)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::NamedKeyKey as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+fn deploy_str_params<'a>(matches: &'a ArgMatches) -> DeployStrParams<'a> {
+    DeployStrParams {
+        secret_key: common::secret_key::get(matches),
+        timestamp: creation_common::timestamp::get(matches),
+        ttl: creation_common::ttl::get(matches),
+        dependencies: creation_common::dependencies::get(matches),
+        gas_price: creation_common::gas_price::get(matches),
+        chain_name: creation_common::chain_name::get(matches),
+    }
+}
+
+pub struct PutNamedKey {}
+
+impl<'a, 'b> ClientCommand<'a, 'b> for PutNamedKey {
+    const NAME: &'static str = "put-named-key";
+    const ABOUT: &'static str =
+        "Adds a key to the named keys of the account, replacing any key already held under the \
+        given name. Changing the named keys of a stored contract is not supported";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        let subcommand = SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(common::verbose::arg(DisplayOrder::Verbose as usize))
+            .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
+            .arg(creation_common::bundled_session_path::arg(
+                "Path to a compiled put_named_key.wasm to use instead of the one embedded in the \
+                client, built from smart_contracts/contracts/client/put-named-key",
+                false,
+            ))
+            .arg(name::arg())
+            .arg(key::arg());
+        let subcommand = creation_common::apply_common_payment_options(subcommand);
+        creation_common::apply_common_creation_options(subcommand, true)
+    }
+
+    fn run(matches: &ArgMatches<'_>) {
+        creation_common::show_arg_examples_and_exit_if_required(matches);
+
+        let maybe_rpc_id = common::rpc_id::get(matches);
+        let node_address = common::node_address::get(matches);
+        let verbose = common::verbose::get(matches);
+        let maybe_session_path = creation_common::bundled_session_path::get(matches);
+        let name = name::get(matches);
+        let key = key::get(matches);
+
+        let response = casper_client::put_named_key(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            maybe_session_path,
+            name,
+            key,
+            deploy_str_params(matches),
+            creation_common::payment_str_params(matches),
+        )
+        .unwrap_or_else(|err| panic!("unable to put deploy {:?}", err));
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&response).expect("should encode to JSON")
+        );
+    }
+}
+
+pub struct RemoveNamedKey {}
+
+impl<'a, 'b> ClientCommand<'a, 'b> for RemoveNamedKey {
+    const NAME: &'static str = "remove-named-key";
+    const ABOUT: &'static str = "Removes a key from the named keys of the account. Changing the \
+        named keys of a stored contract is not supported";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        let subcommand = SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(common::verbose::arg(DisplayOrder::Verbose as usize))
+            .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
+            .arg(creation_common::bundled_session_path::arg(
+                "Path to a compiled remove_named_key.wasm to use instead of the one embedded in \
+                the client, built from smart_contracts/contracts/client/remove-named-key",
+                false,
+            ))
+            .arg(name::arg());
+        let subcommand = creation_common::apply_common_payment_options(subcommand);
+        creation_common::apply_common_creation_options(subcommand, true)
+    }
+
+    fn run(matches: &ArgMatches<'_>) {
+        creation_common::show_arg_examples_and_exit_if_required(matches);
+
+        let maybe_rpc_id = common::rpc_id::get(matches);
+        let node_address = common::node_address::get(matches);
+        let verbose = common::verbose::get(matches);
+        let maybe_session_path = creation_common::bundled_session_path::get(matches);
+        let name = name::get(matches);
+
+        let response = casper_client::remove_named_key(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            maybe_session_path,
+            name,
+            deploy_str_params(matches),
+            creation_common::payment_str_params(matches),
+        )
+        .unwrap_or_else(|err| panic!("unable to put deploy {:?}", err));
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&response).expect("should encode to JSON")
+        );
+    }
+}
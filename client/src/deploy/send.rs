@@ -1,8 +1,33 @@
-use clap::{App, ArgMatches, SubCommand};
+use clap::{App, Arg, ArgMatches, SubCommand};
 
 use super::creation_common::{self, DisplayOrder};
 use crate::{command::ClientCommand, common};
 
+/// Handles providing the arg for and retrieval of the idempotency key.
+mod idempotency_key {
+    use super::*;
+
+    const ARG_NAME: &str = "idempotency-key";
+    const ARG_VALUE_NAME: &str = "STRING";
+    const ARG_HELP: &str =
+        "A key identifying this submission, e.g. a random UUID. If the deploy was already accepted \
+        with the same key, e.g. by an earlier attempt which timed out, the node returns the \
+        original acceptance with \"replayed\": true rather than submitting the deploy again";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::IdempotencyKey as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
 pub struct SendDeploy;
 
 impl<'a, 'b> ClientCommand<'a, 'b> for SendDeploy {
@@ -20,6 +45,7 @@ impl<'a, 'b> ClientCommand<'a, 'b> for SendDeploy {
             ))
             .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
             .arg(creation_common::input::arg())
+            .arg(idempotency_key::arg())
     }
 
     fn run(matches: &ArgMatches<'_>) {
@@ -27,10 +53,16 @@ impl<'a, 'b> ClientCommand<'a, 'b> for SendDeploy {
         let node_address = common::node_address::get(matches);
         let verbose = common::verbose::get(matches);
         let input_path = creation_common::input::get(matches);
+        let maybe_idempotency_key = idempotency_key::get(matches);
 
-        let response =
-            casper_client::send_deploy_file(maybe_rpc_id, node_address, verbose, &input_path)
-                .unwrap_or_else(|error| panic!("response error: {}", error));
+        let response = casper_client::send_deploy_file(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            &input_path,
+            maybe_idempotency_key,
+        )
+        .unwrap_or_else(|error| panic!("response error: {}", error));
         println!(
             "{}",
             serde_json::to_string_pretty(&response).expect("should encode to JSON")
@@ -0,0 +1,148 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use casper_client::DeployStrParams;
+
+use super::creation_common::{self, DisplayOrder};
+use crate::{command::ClientCommand, common};
+
+pub struct SimulateDeploy;
+
+/// Handles providing the arg for and retrieval of the chainspec path.
+mod chainspec {
+    use super::*;
+
+    pub(super) const ARG_NAME: &str = "chainspec";
+    const ARG_VALUE_NAME: &str = common::ARG_PATH;
+    const ARG_HELP: &str =
+        "Path to the chainspec from which to create a fresh global state to execute the deploy \
+        against. Conflicts with --storage-path";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required_unless_one(&[
+                storage_path::ARG_NAME,
+                creation_common::show_arg_examples::ARG_NAME,
+            ])
+            .conflicts_with(storage_path::ARG_NAME)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Chainspec as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
+/// Handles providing the arg for and retrieval of the path to an existing global state.
+mod storage_path {
+    use super::*;
+
+    pub(super) const ARG_NAME: &str = "storage-path";
+    const ARG_VALUE_NAME: &str = common::ARG_PATH;
+    const ARG_HELP: &str =
+        "Path to a directory holding an existing global state, e.g. a copy of a node's storage \
+        directory, to execute the deploy against. Must not be in use by a running node. Requires \
+        --state-root-hash";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .requires(state_root_hash::ARG_NAME)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::StoragePath as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
+/// Handles providing the arg for and retrieval of the state root hash within the existing global
+/// state.
+///
+/// Unlike `common::state_root_hash`, this has no short name, as `-s` is taken by the session code
+/// path.
+mod state_root_hash {
+    use super::*;
+
+    pub(super) const ARG_NAME: &str = "state-root-hash";
+    const ARG_VALUE_NAME: &str = common::ARG_HEX_STRING;
+    const ARG_HELP: &str =
+        "Hex-encoded hash of the state root within the existing global state to execute the \
+        deploy on top of. Requires --storage-path";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .requires(storage_path::ARG_NAME)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::StateRootHash as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
+impl<'a, 'b> ClientCommand<'a, 'b> for SimulateDeploy {
+    const NAME: &'static str = "simulate";
+    const ABOUT: &'static str =
+        "Creates a deploy and executes it locally, without a node, against a fresh global state \
+        created from a chainspec or against an existing global state. The effects of the \
+        execution are not committed";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        let subcommand = SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(chainspec::arg())
+            .arg(storage_path::arg())
+            .arg(state_root_hash::arg());
+        let subcommand = creation_common::apply_common_session_options(subcommand);
+        let subcommand = creation_common::apply_common_payment_options(subcommand);
+        creation_common::apply_common_creation_options(subcommand, false)
+    }
+
+    fn run(matches: &ArgMatches<'_>) {
+        creation_common::show_arg_examples_and_exit_if_required(matches);
+
+        let chainspec_path = chainspec::get(matches);
+        let storage_path = storage_path::get(matches);
+        let state_root_hash = state_root_hash::get(matches);
+
+        let secret_key = common::secret_key::get(matches);
+        let timestamp = creation_common::timestamp::get(matches);
+        let ttl = creation_common::ttl::get(matches);
+        let gas_price = creation_common::gas_price::get(matches);
+        let dependencies = creation_common::dependencies::get(matches);
+        let chain_name = creation_common::chain_name::get(matches);
+
+        let session_str_params = creation_common::session_str_params(matches);
+        let payment_str_params = creation_common::payment_str_params(matches);
+
+        let report = casper_client::simulate_deploy(
+            chainspec_path,
+            storage_path,
+            state_root_hash,
+            DeployStrParams {
+                secret_key,
+                timestamp,
+                ttl,
+                dependencies,
+                gas_price,
+                chain_name,
+            },
+            session_str_params,
+            payment_str_params,
+        )
+        .unwrap_or_else(|err| panic!("unable to simulate deploy {:?}", err));
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).expect("should encode to JSON")
+        );
+    }
+}
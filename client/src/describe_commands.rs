@@ -0,0 +1,183 @@
+//! A machine-readable description of the client's commands and their args, so that wrappers and
+//! GUIs can be generated from, or checked against, the client's actual surface.
+
+use clap::{App, Arg, ArgMatches, ArgSettings};
+use serde::Serialize;
+
+const ARG_NAME: &str = "describe-commands-json";
+const ARG_HELP: &str =
+    "Prints a JSON description of every subcommand and its args to stdout, then exits";
+
+/// The description of a command or subcommand.
+#[derive(Serialize)]
+struct CommandDescription {
+    name: String,
+    about: Option<String>,
+    args: Vec<ArgDescription>,
+    subcommands: Vec<CommandDescription>,
+}
+
+/// The description of a single arg of a command.
+#[derive(Serialize)]
+struct ArgDescription {
+    name: String,
+    long: Option<String>,
+    short: Option<char>,
+    help: Option<String>,
+    /// Whether the arg takes a value, as opposed to being a flag.
+    takes_value: bool,
+    /// The names of the value(s), if the arg takes a value.
+    value_names: Vec<String>,
+    /// The position of the arg, if it is positional.
+    index: Option<u64>,
+    required: bool,
+    multiple: bool,
+    global: bool,
+    default_value: Option<String>,
+    possible_values: Vec<String>,
+}
+
+impl CommandDescription {
+    // The arg definitions are only exposed via clap's hidden `Parser` internals, which are also
+    // what clap uses to generate the shell completion scripts.
+    fn new(app: &App) -> Self {
+        let parser = &app.p;
+        let flags = parser.flags.iter().map(|flag| ArgDescription {
+            name: flag.b.name.to_string(),
+            long: flag.s.long.map(str::to_string),
+            short: flag.s.short,
+            help: flag.b.help.map(str::to_string),
+            takes_value: false,
+            value_names: vec![],
+            index: None,
+            required: flag.b.is_set(ArgSettings::Required),
+            multiple: flag.b.is_set(ArgSettings::Multiple),
+            global: flag.b.is_set(ArgSettings::Global),
+            default_value: None,
+            possible_values: vec![],
+        });
+        let opts = parser.opts.iter().map(|opt| ArgDescription {
+            name: opt.b.name.to_string(),
+            long: opt.s.long.map(str::to_string),
+            short: opt.s.short,
+            help: opt.b.help.map(str::to_string),
+            takes_value: true,
+            value_names: opt
+                .v
+                .val_names
+                .iter()
+                .flat_map(|names| names.values().map(|name| name.to_string()))
+                .collect(),
+            index: None,
+            required: opt.b.is_set(ArgSettings::Required),
+            multiple: opt.b.is_set(ArgSettings::Multiple),
+            global: opt.b.is_set(ArgSettings::Global),
+            default_value: opt
+                .v
+                .default_val
+                .map(|value| value.to_string_lossy().into_owned()),
+            possible_values: opt
+                .v
+                .possible_vals
+                .iter()
+                .flatten()
+                .map(|value| value.to_string())
+                .collect(),
+        });
+        let positionals = parser.positionals.values().map(|pos| ArgDescription {
+            name: pos.b.name.to_string(),
+            long: None,
+            short: None,
+            help: pos.b.help.map(str::to_string),
+            takes_value: true,
+            value_names: pos
+                .v
+                .val_names
+                .iter()
+                .flat_map(|names| names.values().map(|name| name.to_string()))
+                .collect(),
+            index: Some(pos.index),
+            required: pos.b.is_set(ArgSettings::Required),
+            multiple: pos.b.is_set(ArgSettings::Multiple),
+            global: pos.b.is_set(ArgSettings::Global),
+            default_value: pos
+                .v
+                .default_val
+                .map(|value| value.to_string_lossy().into_owned()),
+            possible_values: pos
+                .v
+                .possible_vals
+                .iter()
+                .flatten()
+                .map(|value| value.to_string())
+                .collect(),
+        });
+        let args = flags
+            .chain(opts)
+            .chain(positionals)
+            .filter(|arg| arg.name != ARG_NAME)
+            .collect();
+        let subcommands = parser
+            .subcommands
+            .iter()
+            .map(CommandDescription::new)
+            .collect();
+        CommandDescription {
+            name: parser.meta.name.clone(),
+            about: parser.meta.about.map(str::to_string),
+            args,
+            subcommands,
+        }
+    }
+}
+
+pub fn arg() -> Arg<'static, 'static> {
+    Arg::with_name(ARG_NAME)
+        .long(ARG_NAME)
+        .required(false)
+        .help(ARG_HELP)
+}
+
+pub fn get(matches: &ArgMatches) -> bool {
+    matches.is_present(ARG_NAME)
+}
+
+/// Prints the description of `app` and all its subcommands as pretty-printed JSON to stdout.
+pub fn print(app: &App) {
+    let description = CommandDescription::new(app);
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&description)
+            .unwrap_or_else(|error| panic!("should serialize command description: {}", error))
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_describe_every_subcommand() {
+        let app = crate::cli();
+        let description = CommandDescription::new(&app);
+        assert_eq!(description.subcommands.len(), app.p.subcommands.len());
+        assert!(description.args.iter().all(|arg| arg.name != ARG_NAME));
+
+        let put_deploy = description
+            .subcommands
+            .iter()
+            .find(|subcommand| subcommand.name == "put-deploy")
+            .expect("should describe put-deploy");
+        let node_address = put_deploy
+            .args
+            .iter()
+            .find(|arg| arg.name == "node-address")
+            .expect("should describe node-address");
+        assert_eq!(node_address.short, Some('n'));
+        assert!(node_address.takes_value);
+        assert_eq!(
+            node_address.default_value.as_deref(),
+            Some("http://localhost:50101")
+        );
+    }
+}
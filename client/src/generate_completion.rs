@@ -21,11 +21,18 @@ mod output_file {
     const ARG_VALUE_NAME: &str = common::ARG_PATH;
     const ARG_HELP: &str =
         "Path to output file. If the path's parent folder doesn't exist, the command will fail. \
-        Default path normally requires running the command with sudo";
+        Defaults to the system-wide completions directory for bash, zsh and fish, which normally \
+        requires running the command with sudo, and is required for other shells";
 
     lazy_static! {
-        static ref ARG_DEFAULT: String =
+        static ref BASH_DEFAULT: String =
             format!("/usr/share/bash-completion/completions/{}", crate_name!());
+        static ref ZSH_DEFAULT: String =
+            format!("/usr/share/zsh/vendor-completions/_{}", crate_name!());
+        static ref FISH_DEFAULT: String = format!(
+            "/usr/share/fish/vendor_completions.d/{}.fish",
+            crate_name!()
+        );
     }
 
     pub(super) fn arg() -> Arg<'static, 'static> {
@@ -33,17 +40,25 @@ mod output_file {
             .long(ARG_NAME)
             .short(ARG_NAME_SHORT)
             .required(false)
-            .default_value(&*ARG_DEFAULT)
             .value_name(ARG_VALUE_NAME)
             .help(ARG_HELP)
             .display_order(DisplayOrder::OutputFile as usize)
     }
 
-    pub(super) fn get(matches: &ArgMatches) -> PathBuf {
-        matches
-            .value_of(ARG_NAME)
-            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
-            .into()
+    pub(super) fn get(matches: &ArgMatches, shell: Shell) -> PathBuf {
+        if let Some(path) = matches.value_of(ARG_NAME) {
+            return path.into();
+        }
+        let default = match shell {
+            Shell::Bash => &*BASH_DEFAULT,
+            Shell::Zsh => &*ZSH_DEFAULT,
+            Shell::Fish => &*FISH_DEFAULT,
+            _ => {
+                eprintln!("--{} is required for {}", ARG_NAME, shell);
+                process::exit(1);
+            }
+        };
+        default.into()
     }
 }
 
@@ -93,9 +108,9 @@ impl<'a, 'b> ClientCommand<'a, 'b> for GenerateCompletion {
     }
 
     fn run(matches: &ArgMatches<'_>) {
-        let output_path = output_file::get(matches);
-        let force = common::force::get(matches);
         let shell = shell::get(matches);
+        let output_path = output_file::get(matches, shell);
+        let force = common::force::get(matches);
 
         if !force && output_path.exists() {
             eprintln!(
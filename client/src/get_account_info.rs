@@ -0,0 +1,110 @@
+use std::{fs, str};
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use casper_node::{crypto::asymmetric_key::PublicKey, rpcs::state::GetAccountInfo};
+
+use crate::{command::ClientCommand, common};
+
+/// This struct defines the order in which the args are shown for this subcommand's help message.
+enum DisplayOrder {
+    Verbose,
+    NodeAddress,
+    RpcId,
+    AccountIdentifier,
+    BlockIdentifier,
+}
+
+/// Handles providing the arg for and retrieval of the account identifier.
+mod account_identifier {
+    use super::*;
+
+    const ARG_NAME: &str = "account-identifier";
+    const ARG_SHORT: &str = "a";
+    const ARG_VALUE_NAME: &str = "FORMATTED STRING or PATH";
+    const ARG_HELP: &str =
+        "The account to retrieve. This must be a properly formatted public key or account hash. \
+        The format for each respectively is \"<HEX STRING>\" and \"account-hash-<HEX STRING>\". \
+        The public key may instead be read in from a file, in which case enter the path to the \
+        file as the --account-identifier argument. The file should be one of the two public key \
+        files generated via the `keygen` subcommand; \"public_key_hex\" or \"public_key.pem\"";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(true)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::AccountIdentifier as usize)
+    }
+
+    pub(super) fn get(matches: &ArgMatches) -> String {
+        let value = matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME));
+
+        // Try to read as a PublicKey PEM file first.
+        if let Ok(public_key) = PublicKey::from_file(value) {
+            return public_key.to_hex();
+        }
+
+        // Try to read as a hex-encoded PublicKey file next.
+        if let Ok(contents) = fs::read_to_string(value) {
+            let contents = contents.trim();
+            PublicKey::from_hex(contents.as_bytes()).unwrap_or_else(|error| {
+                panic!(
+                    "failed to parse '{}' as a hex-encoded public key file: {}",
+                    value, error
+                )
+            });
+            return contents.to_string();
+        }
+
+        // Just return the value.
+        value.to_string()
+    }
+}
+
+impl<'a, 'b> ClientCommand<'a, 'b> for GetAccountInfo {
+    const NAME: &'static str = "get-account-info";
+    const ABOUT: &'static str =
+        "Retrieves an account's named keys, associated keys, action thresholds and main purse from \
+        the network";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(common::verbose::arg(DisplayOrder::Verbose as usize))
+            .arg(common::node_address::arg(
+                DisplayOrder::NodeAddress as usize,
+            ))
+            .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
+            .arg(account_identifier::arg())
+            .arg(common::block_identifier::arg(
+                DisplayOrder::BlockIdentifier as usize,
+            ))
+    }
+
+    fn run(matches: &ArgMatches<'_>) {
+        let maybe_rpc_id = common::rpc_id::get(matches);
+        let node_address = common::node_address::get(matches);
+        let verbose = common::verbose::get(matches);
+        let account_identifier = account_identifier::get(matches);
+        let maybe_block_id = common::block_identifier::get(matches);
+
+        let response = casper_client::get_account_info(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            &account_identifier,
+            maybe_block_id,
+        )
+        .unwrap_or_else(|error| panic!("response error: {}", error));
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&response).expect("should encode to JSON")
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use casper_node::rpcs::state::GetBalanceHistory;
+
+use crate::{command::ClientCommand, common};
+
+/// This struct defines the order in which the args are shown for this subcommand's help message.
+enum DisplayOrder {
+    Verbose,
+    NodeAddress,
+    RpcId,
+    PurseURef,
+    FromHeight,
+    ToHeight,
+    Step,
+    IncludeProofs,
+}
+
+/// Handles providing the arg for and retrieval of the purse URef.
+mod purse_uref {
+    use super::*;
+
+    const ARG_NAME: &str = "purse-uref";
+    const ARG_SHORT: &str = "p";
+    const ARG_VALUE_NAME: &str = "FORMATTED STRING";
+    const ARG_HELP: &str =
+        "The URef under which the purse is stored. This must be a properly formatted URef \
+        \"uref-<HEX STRING>-<THREE DIGIT INTEGER>\"";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(true)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::PurseURef as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+/// Handles providing the arg for and retrieval of the height of the first block sampled.
+mod from_height {
+    use super::*;
+
+    const ARG_NAME: &str = "from-height";
+    const ARG_SHORT: &str = "f";
+    const ARG_VALUE_NAME: &str = common::ARG_INTEGER;
+    const ARG_HELP: &str = "Height of the first block at which to sample the balance";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(true)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::FromHeight as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+/// Handles providing the arg for and retrieval of the height of the last block of the range.
+mod to_height {
+    use super::*;
+
+    const ARG_NAME: &str = "to-height";
+    const ARG_SHORT: &str = "t";
+    const ARG_VALUE_NAME: &str = common::ARG_INTEGER;
+    const ARG_HELP: &str =
+        "Height of the last block of the range (inclusive). The node may return fewer samples \
+        than requested, in which case the response's \"next_height\" indicates where to resume";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(true)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::ToHeight as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+/// Handles providing the arg for and retrieval of the number of blocks between samples.
+mod step {
+    use super::*;
+
+    const ARG_NAME: &str = "step";
+    const ARG_SHORT: &str = "s";
+    const ARG_VALUE_NAME: &str = common::ARG_INTEGER;
+    const ARG_DEFAULT: &str = "1";
+    const ARG_HELP: &str = "The number of blocks between consecutive samples";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(false)
+            .default_value(ARG_DEFAULT)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Step as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+/// Handles providing the arg for and retrieval of the include-proofs flag.
+mod include_proofs {
+    use super::*;
+
+    const ARG_NAME: &str = "include-proofs";
+    const ARG_HELP: &str =
+        "If passed, each sampled balance is returned along with its Merkle proof, which is \
+        validated against the state root hash of the sampled block";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::IncludeProofs as usize)
+    }
+
+    pub(super) fn get(matches: &ArgMatches) -> bool {
+        matches.is_present(ARG_NAME)
+    }
+}
+
+impl<'a, 'b> ClientCommand<'a, 'b> for GetBalanceHistory {
+    const NAME: &'static str = "get-balance-history";
+    const ABOUT: &'static str =
+        "Retrieves a purse's balance from the network, sampled at intervals over a range of blocks";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(common::verbose::arg(DisplayOrder::Verbose as usize))
+            .arg(common::node_address::arg(
+                DisplayOrder::NodeAddress as usize,
+            ))
+            .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
+            .arg(purse_uref::arg())
+            .arg(from_height::arg())
+            .arg(to_height::arg())
+            .arg(step::arg())
+            .arg(include_proofs::arg())
+    }
+
+    fn run(matches: &ArgMatches<'_>) {
+        let maybe_rpc_id = common::rpc_id::get(matches);
+        let node_address = common::node_address::get(matches);
+        let verbose = common::verbose::get(matches);
+        let purse_uref = purse_uref::get(matches);
+        let from_height = from_height::get(matches);
+        let to_height = to_height::get(matches);
+        let step = step::get(matches);
+        let include_proofs = include_proofs::get(matches);
+
+        let response = casper_client::get_balance_history(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            purse_uref,
+            from_height,
+            to_height,
+            step,
+            include_proofs,
+        )
+        .unwrap_or_else(|error| panic!("response error: {}", error));
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&response).expect("should encode to JSON")
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use casper_node::rpcs::state::GetContractAbi;
+
+use crate::{command::ClientCommand, common};
+
+/// This struct defines the order in which the args are shown for this subcommand's help message.
+enum DisplayOrder {
+    Verbose,
+    NodeAddress,
+    RpcId,
+    StateRootHash,
+    ContractHash,
+}
+
+/// Handles providing the arg for and retrieval of the contract hash.
+mod contract_hash {
+    use super::*;
+
+    const ARG_NAME: &str = "contract-hash";
+    const ARG_SHORT: &str = "c";
+    const ARG_VALUE_NAME: &str = "FORMATTED STRING or HEX STRING";
+    const ARG_HELP: &str =
+        "The hash of the stored contract, either hex-encoded or formatted as a hash key, e.g. \
+        hash-0102...";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(true)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::ContractHash as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+impl<'a, 'b> ClientCommand<'a, 'b> for GetContractAbi {
+    const NAME: &'static str = "get-contract-abi";
+    const ABOUT: &'static str =
+        "Retrieves the entry points of a stored contract along with their arg names and types, \
+        documented by the contract's metadata if it has any";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(common::verbose::arg(DisplayOrder::Verbose as usize))
+            .arg(common::node_address::arg(
+                DisplayOrder::NodeAddress as usize,
+            ))
+            .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
+            .arg(common::state_root_hash::arg(
+                DisplayOrder::StateRootHash as usize,
+            ))
+            .arg(contract_hash::arg())
+    }
+
+    fn run(matches: &ArgMatches<'_>) {
+        let maybe_rpc_id = common::rpc_id::get(matches);
+        let node_address = common::node_address::get(matches);
+        let verbose = common::verbose::get(matches);
+        let state_root_hash = common::state_root_hash::get(matches);
+        let contract_hash = contract_hash::get(matches);
+
+        let response = casper_client::get_contract_abi(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            state_root_hash,
+            contract_hash,
+        )
+        .unwrap_or_else(|error| panic!("response error: {}", error));
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&response).expect("should encode to JSON")
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use casper_node::rpcs::chain::GetEraSummary;
+
+use crate::{command::ClientCommand, common};
+
+/// This struct defines the order in which the args are shown for this subcommand's help message.
+enum DisplayOrder {
+    Verbose,
+    NodeAddress,
+    RpcId,
+    EraId,
+    BlockIdentifier,
+}
+
+/// Handles providing the arg for and retrieval of the era ID.
+mod era_id {
+    use super::*;
+
+    const ARG_NAME: &str = "era-id";
+    const ARG_SHORT: &str = "e";
+    const ARG_VALUE_NAME: &str = "INTEGER";
+    const ARG_HELP: &str =
+        "The ID of the era to summarize. Must not be provided alongside --block-identifier. If \
+        neither is provided, the most recently ended era will be used";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::EraId as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
+impl<'a, 'b> ClientCommand<'a, 'b> for GetEraSummary {
+    const NAME: &'static str = "get-era-summary";
+    const ABOUT: &'static str =
+        "Retrieves the era-end report and era info of an era which has ended";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(common::verbose::arg(DisplayOrder::Verbose as usize))
+            .arg(common::node_address::arg(
+                DisplayOrder::NodeAddress as usize,
+            ))
+            .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
+            .arg(era_id::arg())
+            .arg(common::block_identifier::arg(
+                DisplayOrder::BlockIdentifier as usize,
+            ))
+    }
+
+    fn run(matches: &ArgMatches<'_>) {
+        let maybe_rpc_id = common::rpc_id::get(matches);
+        let node_address = common::node_address::get(matches);
+        let verbose = common::verbose::get(matches);
+        let maybe_era_id = era_id::get(matches);
+        let maybe_block_id = common::block_identifier::get(matches);
+
+        let response = casper_client::get_era_summary(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            maybe_era_id,
+            maybe_block_id,
+        )
+        .unwrap_or_else(|error| panic!("response error: {}", error));
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&response).expect("should encode to JSON")
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use std::{fs, str};
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use casper_node::{crypto::asymmetric_key::PublicKey, rpcs::state::GetKeyHistory};
+
+use crate::{command::ClientCommand, common};
+
+/// This struct defines the order in which the args are shown for this subcommand's help message.
+enum DisplayOrder {
+    Verbose,
+    NodeAddress,
+    RpcId,
+    Key,
+    Page,
+}
+
+/// Handles providing the arg for and retrieval of the key.
+mod key {
+    use super::*;
+
+    const ARG_NAME: &str = "key";
+    const ARG_SHORT: &str = "k";
+    const ARG_VALUE_NAME: &str = "FORMATTED STRING or PATH";
+    const ARG_HELP: &str =
+        "The key whose history to retrieve. This must be a properly formatted public key, account \
+        hash, contract address hash, URef, transfer hash or deploy-info hash. The format for each \
+        respectively is \"<HEX STRING>\", \"account-hash-<HEX STRING>\", \"hash-<HEX STRING>\", \
+        \"uref-<HEX STRING>-<THREE DIGIT INTEGER>\", \"transfer-<HEX-STRING>\" and \
+        \"deploy-<HEX-STRING>\". The public key may instead be read in from a file, in which case \
+        enter the path to the file as the --key argument. The file should be one of the two public \
+        key files generated via the `keygen` subcommand; \"public_key_hex\" or \"public_key.pem\"";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(true)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Key as usize)
+    }
+
+    pub(super) fn get(matches: &ArgMatches) -> String {
+        let value = matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME));
+
+        // Try to read as a PublicKey PEM file first.
+        if let Ok(public_key) = PublicKey::from_file(value) {
+            return public_key.to_hex();
+        }
+
+        // Try to read as a hex-encoded PublicKey file next.
+        if let Ok(contents) = fs::read_to_string(value) {
+            let contents = contents.trim();
+            PublicKey::from_hex(contents.as_bytes()).unwrap_or_else(|error| {
+                panic!(
+                    "failed to parse '{}' as a hex-encoded public key file: {}",
+                    value, error
+                )
+            });
+            return contents.to_string();
+        }
+
+        // Just return the value.
+        value.to_string()
+    }
+}
+
+/// Handles providing the arg for and retrieval of the page.
+mod page {
+    use super::*;
+
+    const ARG_NAME: &str = "page";
+    const ARG_SHORT: &str = "p";
+    const ARG_VALUE_NAME: &str = "INTEGER";
+    const ARG_HELP: &str =
+        "The page of the history to retrieve. Page 0, the default, holds the most recent entries";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Page as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
+impl<'a, 'b> ClientCommand<'a, 'b> for GetKeyHistory {
+    const NAME: &'static str = "get-key-history";
+    const ABOUT: &'static str =
+        "Retrieves the deploys which wrote to a global state key, most recent first";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(common::verbose::arg(DisplayOrder::Verbose as usize))
+            .arg(common::node_address::arg(
+                DisplayOrder::NodeAddress as usize,
+            ))
+            .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
+            .arg(key::arg())
+            .arg(page::arg())
+    }
+
+    fn run(matches: &ArgMatches<'_>) {
+        let maybe_rpc_id = common::rpc_id::get(matches);
+        let node_address = common::node_address::get(matches);
+        let verbose = common::verbose::get(matches);
+        let key = key::get(matches);
+        let maybe_page = page::get(matches);
+
+        let response =
+            casper_client::get_key_history(maybe_rpc_id, node_address, verbose, &key, maybe_page)
+                .unwrap_or_else(|error| panic!("response error: {}", error));
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&response).expect("should encode to JSON")
+        );
+    }
+}
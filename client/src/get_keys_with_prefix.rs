@@ -0,0 +1,112 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use casper_node::rpcs::state::GetKeysWithPrefix;
+
+use crate::{command::ClientCommand, common};
+
+/// This struct defines the order in which the args are shown for this subcommand's help message.
+enum DisplayOrder {
+    Verbose,
+    NodeAddress,
+    RpcId,
+    StateRootHash,
+    Prefix,
+    Page,
+}
+
+/// Handles providing the arg for and retrieval of the key prefix.
+mod prefix {
+    use super::*;
+
+    const ARG_NAME: &str = "prefix";
+    const ARG_VALUE_NAME: &str = common::ARG_HEX_STRING;
+    const ARG_HELP: &str =
+        "The hex-encoded prefix of the serialized keys to retrieve. The first byte of a serialized \
+        key identifies its variant: \"00\" for accounts, \"01\" for hashes, \"02\" for URefs, \"03\" \
+        for transfers and \"04\" for deploy infos. If not given, all keys are matched";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Prefix as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
+/// Handles providing the arg for and retrieval of the page.
+mod page {
+    use super::*;
+
+    const ARG_NAME: &str = "page";
+    const ARG_SHORT: &str = "p";
+    const ARG_VALUE_NAME: &str = "INTEGER";
+    const ARG_HELP: &str =
+        "The page of the matching keys to retrieve. Page 0, the default, holds the first keys in \
+        the order of their serialized representation";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Page as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
+impl<'a, 'b> ClientCommand<'a, 'b> for GetKeysWithPrefix {
+    const NAME: &'static str = "get-keys-with-prefix";
+    const ABOUT: &'static str =
+        "Retrieves a page of the global state keys with a given prefix, along with their values \
+        and proofs";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(common::verbose::arg(DisplayOrder::Verbose as usize))
+            .arg(common::node_address::arg(
+                DisplayOrder::NodeAddress as usize,
+            ))
+            .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
+            .arg(common::state_root_hash::arg(
+                DisplayOrder::StateRootHash as usize,
+            ))
+            .arg(prefix::arg())
+            .arg(page::arg())
+    }
+
+    fn run(matches: &ArgMatches<'_>) {
+        let maybe_rpc_id = common::rpc_id::get(matches);
+        let node_address = common::node_address::get(matches);
+        let verbose = common::verbose::get(matches);
+        let state_root_hash = common::state_root_hash::get(matches);
+        let prefix = prefix::get(matches);
+        let maybe_page = page::get(matches);
+
+        let response = casper_client::get_keys_with_prefix(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            state_root_hash,
+            prefix,
+            maybe_page,
+        )
+        .unwrap_or_else(|error| panic!("response error: {}", error));
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&response).expect("should encode to JSON")
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use std::{fs, str};
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use casper_node::{crypto::asymmetric_key::PublicKey, rpcs::info::GetSignedStatus};
+
+use crate::{command::ClientCommand, common};
+
+/// This struct defines the order in which the args are shown for this subcommand's help message.
+enum DisplayOrder {
+    Verbose,
+    NodeAddress,
+    RpcId,
+    Nonce,
+    PublicKey,
+}
+
+/// Handles providing the arg for and retrieval of the nonce.
+mod nonce {
+    use super::*;
+
+    const ARG_NAME: &str = "nonce";
+    const ARG_VALUE_NAME: &str = "STRING";
+    const ARG_HELP: &str =
+        "The nonce to be included in the signed status, of at most 128 bytes. If not provided, a \
+        random nonce is used";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Nonce as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
+/// Handles providing the arg for and retrieval of the node's expected public key.
+mod public_key {
+    use super::*;
+
+    const ARG_NAME: &str = "public-key";
+    const ARG_SHORT: &str = "p";
+    const ARG_VALUE_NAME: &str = "FORMATTED STRING or PATH";
+    const ARG_HELP: &str =
+        "The public key with which the node is expected to sign, as \"<HEX STRING>\". If provided, \
+        a status signed with any other key is rejected. The public key may instead be read in from \
+        a file, in which case enter the path to the file as the --public-key argument. The file \
+        should be one of the two public key files generated via the `keygen` subcommand; \
+        \"public_key_hex\" or \"public_key.pem\"";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::PublicKey as usize)
+    }
+
+    pub(super) fn get(matches: &ArgMatches) -> String {
+        let value = match matches.value_of(ARG_NAME) {
+            Some(value) => value,
+            None => return String::new(),
+        };
+
+        // Try to read as a PublicKey PEM file first.
+        if let Ok(public_key) = PublicKey::from_file(value) {
+            return public_key.to_hex();
+        }
+
+        // Try to read as a hex-encoded PublicKey file next.
+        if let Ok(contents) = fs::read_to_string(value) {
+            let contents = contents.trim();
+            PublicKey::from_hex(contents.as_bytes()).unwrap_or_else(|error| {
+                panic!(
+                    "failed to parse '{}' as a hex-encoded public key file: {}",
+                    value, error
+                )
+            });
+            return contents.to_string();
+        }
+
+        // Just return the value.
+        value.to_string()
+    }
+}
+
+impl<'a, 'b> ClientCommand<'a, 'b> for GetSignedStatus {
+    const NAME: &'static str = "get-signed-status";
+    const ABOUT: &'static str =
+        "Retrieves the status of the node signed with its validator key, and verifies the signature";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(common::verbose::arg(DisplayOrder::Verbose as usize))
+            .arg(common::node_address::arg(
+                DisplayOrder::NodeAddress as usize,
+            ))
+            .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
+            .arg(nonce::arg())
+            .arg(public_key::arg())
+    }
+
+    fn run(matches: &ArgMatches<'_>) {
+        let maybe_rpc_id = common::rpc_id::get(matches);
+        let node_address = common::node_address::get(matches);
+        let verbose = common::verbose::get(matches);
+        let maybe_nonce = nonce::get(matches);
+        let maybe_public_key = public_key::get(matches);
+
+        let response = casper_client::get_signed_status(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            maybe_nonce,
+            &maybe_public_key,
+        )
+        .unwrap_or_else(|error| panic!("response error: {}", error));
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&response).expect("should encode to JSON")
+        );
+    }
+}
@@ -27,20 +27,25 @@ impl<'a, 'b> ClientCommand<'a, 'b> for GetStateRootHash {
                 DisplayOrder::NodeAddress as usize,
             ))
             .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
-            .arg(common::block_identifier::arg(
+            .args(&common::block_selector::args(
                 DisplayOrder::BlockHash as usize,
             ))
+            .group(common::block_selector::group())
     }
 
     fn run(matches: &ArgMatches<'_>) {
         let maybe_rpc_id = common::rpc_id::get(matches);
         let node_address = common::node_address::get(matches);
         let verbose = common::verbose::get(matches);
-        let maybe_block_id = common::block_identifier::get(matches);
-
-        let response =
-            casper_client::get_state_root_hash(maybe_rpc_id, node_address, verbose, maybe_block_id)
-                .unwrap_or_else(|error| panic!("response error: {}", error));
+        let maybe_block_id = common::block_selector::get(matches);
+
+        let response = casper_client::get_state_root_hash(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            &maybe_block_id,
+        )
+        .unwrap_or_else(|error| panic!("response error: {}", error));
         println!(
             "{}",
             serde_json::to_string_pretty(&response).expect("should encode to JSON")
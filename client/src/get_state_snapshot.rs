@@ -0,0 +1,168 @@
+use std::{fs, path::Path, process};
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use crate::{command::ClientCommand, common};
+
+/// This struct defines the order in which the args are shown for this subcommand's help message.
+enum DisplayOrder {
+    Verbose,
+    NodeAddress,
+    RpcId,
+    BlockIdentifier,
+    Key,
+    FollowReferences,
+    Output,
+    Force,
+}
+
+/// Handles providing the arg for and retrieval of the keys to capture.
+mod key {
+    use super::*;
+
+    const ARG_NAME: &str = "key";
+    const ARG_SHORT: &str = "k";
+    const ARG_VALUE_NAME: &str = "FORMATTED STRING";
+    const ARG_HELP: &str =
+        "A key whose value is captured. This must be a properly formatted public key, account \
+        hash, contract address hash, URef, transfer hash or deploy-info hash, as for \
+        query-state. May be given more than once";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(true)
+            .multiple(true)
+            .number_of_values(1)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Key as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> Vec<&'a str> {
+        matches
+            .values_of(ARG_NAME)
+            .iter()
+            .cloned()
+            .flatten()
+            .collect()
+    }
+}
+
+/// Handles providing the arg for and retrieval of whether to capture referenced values.
+mod follow_references {
+    use super::*;
+
+    const ARG_NAME: &str = "follow-references";
+    const ARG_HELP: &str =
+        "If this flag is passed, the values referenced by those captured are captured too: an \
+        account's main purse balance, a contract's package and Wasm, and the value under any key \
+        stored as a CLValue";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::FollowReferences as usize)
+    }
+
+    pub(super) fn get(matches: &ArgMatches) -> bool {
+        matches.is_present(ARG_NAME)
+    }
+}
+
+/// Handles providing the arg for and retrieval of the output file path.
+mod output {
+    use super::*;
+
+    const ARG_NAME: &str = "output";
+    const ARG_SHORT: &str = "o";
+    const ARG_VALUE_NAME: &str = common::ARG_PATH;
+    const ARG_HELP: &str = "Path to the fixture file to write. If omitted, defaults to stdout";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Output as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> Option<&'a str> {
+        matches.value_of(ARG_NAME)
+    }
+}
+
+pub struct GetStateSnapshot {}
+
+impl<'a, 'b> ClientCommand<'a, 'b> for GetStateSnapshot {
+    const NAME: &'static str = "get-state-snapshot";
+    const ABOUT: &'static str =
+        "Captures the values stored under the given keys into a JSON fixture file, which can be \
+        loaded into an in-memory engine with casper-engine-test-support's StateSnapshot";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(common::verbose::arg(DisplayOrder::Verbose as usize))
+            .arg(common::node_address::arg(
+                DisplayOrder::NodeAddress as usize,
+            ))
+            .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
+            .args(&common::block_selector::args(
+                DisplayOrder::BlockIdentifier as usize,
+            ))
+            .group(common::block_selector::group())
+            .arg(key::arg())
+            .arg(follow_references::arg())
+            .arg(output::arg())
+            .arg(common::force::arg(DisplayOrder::Force as usize, true))
+    }
+
+    fn run(matches: &ArgMatches<'_>) {
+        let maybe_rpc_id = common::rpc_id::get(matches);
+        let node_address = common::node_address::get(matches);
+        let verbose = common::verbose::get(matches);
+        let keys = key::get(matches);
+        let follow_references = follow_references::get(matches);
+        let maybe_output_path = output::get(matches);
+        let force = common::force::get(matches);
+
+        if let Some(output_path) = maybe_output_path {
+            if !force && Path::new(output_path).exists() {
+                eprintln!("{} exists. To overwrite, rerun with -f", output_path);
+                process::exit(1);
+            }
+        }
+
+        let snapshot = casper_client::get_state_snapshot(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            &common::block_selector::get(matches),
+            &keys,
+            follow_references,
+        )
+        .unwrap_or_else(|error| panic!("response error: {}", error));
+        let json = serde_json::to_string_pretty(&snapshot).expect("should encode to JSON");
+
+        match maybe_output_path {
+            Some(output_path) => {
+                fs::write(output_path, json)
+                    .unwrap_or_else(|error| panic!("failed to write {}: {}", output_path, error));
+                println!(
+                    "Wrote {} entries under state root hash {} to {}",
+                    snapshot.entries.len(),
+                    snapshot.state_root_hash,
+                    output_path
+                );
+            }
+            None => println!("{}", json),
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use std::str;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use casper_node::rpcs::info::GetSupply;
+
+use crate::{command::ClientCommand, common};
+
+/// This struct defines the order in which the args are shown for this subcommand's help message.
+enum DisplayOrder {
+    Verbose,
+    NodeAddress,
+    RpcId,
+    BlockIdentifier,
+    Circulating,
+}
+
+/// Handles the arg for whether the circulating supply is required or not.
+mod circulating {
+    use super::*;
+
+    const ARG_NAME: &str = "circulating";
+    const ARG_SHORT: &str = "c";
+    const ARG_HELP: &str =
+        "Also retrieves the circulating supply, i.e. the total supply less the balances of the \
+        accounts which the node is configured to exclude";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(false)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Circulating as usize)
+    }
+
+    pub(super) fn get(matches: &ArgMatches) -> bool {
+        matches.is_present(ARG_NAME)
+    }
+}
+
+impl<'a, 'b> ClientCommand<'a, 'b> for GetSupply {
+    const NAME: &'static str = "get-supply";
+    const ABOUT: &'static str =
+        "Retrieves the total supply of motes with a merkle proof, and optionally the circulating \
+        supply";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(common::verbose::arg(DisplayOrder::Verbose as usize))
+            .arg(common::node_address::arg(
+                DisplayOrder::NodeAddress as usize,
+            ))
+            .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
+            .arg(common::block_identifier::arg(
+                DisplayOrder::BlockIdentifier as usize,
+            ))
+            .arg(circulating::arg())
+    }
+
+    fn run(matches: &ArgMatches<'_>) {
+        let maybe_rpc_id = common::rpc_id::get(matches);
+        let node_address = common::node_address::get(matches);
+        let verbose = common::verbose::get(matches);
+        let maybe_block_id = common::block_identifier::get(matches);
+        let include_circulating_supply = circulating::get(matches);
+
+        let response = casper_client::get_supply(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            maybe_block_id,
+            include_circulating_supply,
+        )
+        .unwrap_or_else(|error| panic!("response error: {}", error));
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&response).expect("should encode to JSON")
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use std::{fs, str};
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use casper_node::{crypto::asymmetric_key::PublicKey, rpcs::state::GetUnbondingPurses};
+
+use crate::{command::ClientCommand, common};
+
+/// This struct defines the order in which the args are shown for this subcommand's help message.
+enum DisplayOrder {
+    Verbose,
+    NodeAddress,
+    RpcId,
+    Validator,
+    Delegator,
+}
+
+/// Reads a public key given either as a hex string or as the path to a public key file.
+fn parse_public_key(arg_name: &str, value: &str) -> String {
+    // Try to read as a PublicKey PEM file first.
+    if let Ok(public_key) = PublicKey::from_file(value) {
+        return public_key.to_hex();
+    }
+
+    // Try to read as a hex-encoded PublicKey file next.
+    if let Ok(contents) = fs::read_to_string(value) {
+        let contents = contents.trim();
+        PublicKey::from_hex(contents.as_bytes()).unwrap_or_else(|error| {
+            panic!(
+                "failed to parse '{}' given as --{} as a hex-encoded public key file: {}",
+                value, arg_name, error
+            )
+        });
+        return contents.to_string();
+    }
+
+    // Just return the value.
+    value.to_string()
+}
+
+/// Handles providing the arg for and retrieval of the validator's public key.
+mod validator {
+    use super::*;
+
+    const ARG_NAME: &str = "validator";
+    const ARG_VALUE_NAME: &str = "FORMATTED STRING or PATH";
+    const ARG_HELP: &str =
+        "If given, only the amounts unbonded from this validator's bid are returned. The public \
+        key may be given as \"<HEX STRING>\" or as the path to one of the two public key files \
+        generated via the `keygen` subcommand; \"public_key_hex\" or \"public_key.pem\"";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Validator as usize)
+    }
+
+    pub(super) fn get(matches: &ArgMatches) -> String {
+        matches
+            .value_of(ARG_NAME)
+            .map(|value| parse_public_key(ARG_NAME, value))
+            .unwrap_or_default()
+    }
+}
+
+/// Handles providing the arg for and retrieval of the unbonder's public key.
+mod delegator {
+    use super::*;
+
+    const ARG_NAME: &str = "delegator";
+    const ARG_SHORT: &str = "d";
+    const ARG_VALUE_NAME: &str = "FORMATTED STRING or PATH";
+    const ARG_HELP: &str =
+        "If given, only the amounts unbonded by this validator or delegator are returned. The \
+        public key may be given as \"<HEX STRING>\" or as the path to one of the two public key \
+        files generated via the `keygen` subcommand; \"public_key_hex\" or \"public_key.pem\"";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::Delegator as usize)
+    }
+
+    pub(super) fn get(matches: &ArgMatches) -> String {
+        matches
+            .value_of(ARG_NAME)
+            .map(|value| parse_public_key(ARG_NAME, value))
+            .unwrap_or_default()
+    }
+}
+
+impl<'a, 'b> ClientCommand<'a, 'b> for GetUnbondingPurses {
+    const NAME: &'static str = "get-unbonding-purses";
+    const ABOUT: &'static str =
+        "Retrieves the amounts in the unbonding queue, along with their estimated release times";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(common::verbose::arg(DisplayOrder::Verbose as usize))
+            .arg(common::node_address::arg(
+                DisplayOrder::NodeAddress as usize,
+            ))
+            .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
+            .arg(validator::arg())
+            .arg(delegator::arg())
+    }
+
+    fn run(matches: &ArgMatches<'_>) {
+        let maybe_rpc_id = common::rpc_id::get(matches);
+        let node_address = common::node_address::get(matches);
+        let verbose = common::verbose::get(matches);
+        let maybe_validator = validator::get(matches);
+        let maybe_delegator = delegator::get(matches);
+
+        let response = casper_client::get_unbonding_purses(
+            maybe_rpc_id,
+            node_address,
+            verbose,
+            &maybe_validator,
+            &maybe_delegator,
+        )
+        .unwrap_or_else(|error| panic!("response error: {}", error));
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&response).expect("should encode to JSON")
+        );
+    }
+}
@@ -7,6 +7,7 @@ use casper_client::{
     keygen::{self, FILES, PUBLIC_KEY_HEX},
     Error,
 };
+use casper_node::utils::passphrase::{self, PASSPHRASE_ENV_VAR};
 
 use crate::{command::ClientCommand, common};
 
@@ -25,6 +26,7 @@ enum DisplayOrder {
     OutputDir,
     Force,
     Algorithm,
+    Encrypt,
 }
 
 /// Handles providing the arg for and retrieval of the output directory.
@@ -79,6 +81,58 @@ mod algorithm {
     }
 }
 
+/// Handles providing the arg for and retrieval of the passphrase used to encrypt the secret key.
+mod encrypt {
+    use super::*;
+
+    const ARG_NAME: &str = "encrypt";
+    const ARG_SHORT: &str = "e";
+
+    lazy_static! {
+        static ref ARG_HELP: String = format!(
+            "If this flag is passed, the secret key file will be encrypted with a passphrase. The \
+            passphrase is read from the {} environment variable if set, otherwise it is prompted \
+            for",
+            PASSPHRASE_ENV_VAR
+        );
+    }
+
+    pub fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .short(ARG_SHORT)
+            .required(false)
+            .help(ARG_HELP.as_str())
+            .display_order(DisplayOrder::Encrypt as usize)
+    }
+
+    pub fn get(matches: &ArgMatches) -> Option<Vec<u8>> {
+        if !matches.is_present(ARG_NAME) {
+            return None;
+        }
+        if std::env::var_os(PASSPHRASE_ENV_VAR).is_some() {
+            return Some(
+                passphrase::get_passphrase(None, "")
+                    .unwrap_or_else(|error| panic!("should get passphrase: {}", error)),
+            );
+        }
+        let prompt = |prompt| {
+            passphrase::prompt_passphrase(prompt)
+                .unwrap_or_else(|error| panic!("should prompt for passphrase: {}", error))
+        };
+        let passphrase = prompt("Enter passphrase: ");
+        if passphrase.is_empty() {
+            eprintln!("Passphrase must not be empty");
+            process::exit(1);
+        }
+        if prompt("Confirm passphrase: ") != passphrase {
+            eprintln!("Passphrases do not match");
+            process::exit(1);
+        }
+        Some(passphrase.into_bytes())
+    }
+}
+
 pub struct Keygen {}
 
 impl<'a, 'b> ClientCommand<'a, 'b> for Keygen {
@@ -93,14 +147,16 @@ impl<'a, 'b> ClientCommand<'a, 'b> for Keygen {
             .arg(output_dir::arg())
             .arg(common::force::arg(DisplayOrder::Force as usize, false))
             .arg(algorithm::arg())
+            .arg(encrypt::arg())
     }
 
     fn run(matches: &ArgMatches<'_>) {
         let output_dir = output_dir::get(matches);
         let algorithm = algorithm::get(matches);
         let force = common::force::get(matches);
+        let maybe_passphrase = encrypt::get(matches);
 
-        match keygen::generate_files(&output_dir, algorithm, force) {
+        match keygen::generate_files(&output_dir, algorithm, maybe_passphrase.as_deref(), force) {
             Err(Error::FileAlreadyExists(existing)) => {
                 eprintln!(
                     "{} exists. To overwrite, rerun with --{}",
@@ -1,28 +1,60 @@
+mod account_address;
 mod block;
 mod command;
 mod common;
 mod deploy;
+mod describe_commands;
 mod generate_completion;
+mod get_account_deploys;
+mod get_account_info;
 mod get_auction_info;
 mod get_balance;
+mod get_balance_history;
+mod get_chainspec;
+mod get_contract_abi;
+mod get_contract_package;
+mod get_delegations;
+mod get_era_summary;
+mod get_key_history;
+mod get_keys_with_prefix;
+mod get_signed_status;
 mod get_state_hash;
+mod get_state_snapshot;
+mod get_supply;
+mod get_transfers_to;
+mod get_unbonding_purses;
+mod get_validator_rewards;
 mod keygen;
+mod profile;
 mod query_state;
 
 use clap::{crate_description, crate_version, App};
 
 use casper_node::rpcs::{
-    account::PutDeploy,
-    chain::{GetBlock, GetStateRootHash},
-    info::GetDeploy,
-    state::{GetAuctionInfo, GetBalance, GetItem as QueryState},
+    account::{GetAccountDeploys, PutDeploy},
+    chain::{
+        GetAlternativeBlocks, GetBlock, GetBlockAtTimestamp, GetBlockByDeploy, GetBlocks,
+        GetEraSummary, GetStateRootHash,
+    },
+    info::{
+        EstimateDeployCost, GetChainspec, GetDeploy, GetSignedStatus, GetSupply,
+        GetValidatorRewards,
+    },
+    state::{
+        GetAccountInfo, GetAuctionInfo, GetBalance, GetBalanceHistory, GetContractAbi,
+        GetDelegations, GetItem as QueryState, GetKeyHistory, GetKeysWithPrefix, GetTransfersTo,
+        GetUnbondingPurses,
+    },
 };
 
-use deploy::{ListDeploys, MakeDeploy, SendDeploy, SignDeploy};
+use deploy::{CheckDeploy, ListDeploys, MakeDeploy, SendDeploy, SignDeploy, SimulateDeploy};
 
+use account_address::AccountAddress;
 use command::ClientCommand;
-use deploy::Transfer;
+use deploy::{DisableContractVersion, PutNamedKey, RemoveNamedKey, Transfer};
 use generate_completion::GenerateCompletion;
+use get_contract_package::GetContractPackage;
+use get_state_snapshot::GetStateSnapshot;
 use keygen::Keygen;
 
 const APP_NAME: &str = "Casper client";
@@ -33,15 +65,42 @@ enum DisplayOrder {
     MakeDeploy,
     SignDeploy,
     SendDeploy,
+    CheckDeploy,
     Transfer,
+    EstimateDeployCost,
+    SimulateDeploy,
+    DisableContractVersion,
+    PutNamedKey,
+    RemoveNamedKey,
     GetDeploy,
     GetBlock,
+    GetBlocks,
+    GetAlternativeBlocks,
+    GetBlockByDeploy,
+    GetBlockAtTimestamp,
     ListDeploys,
     GetStateRootHash,
     QueryState,
     GetBalance,
+    GetBalanceHistory,
+    GetAccountInfo,
+    GetAccountDeploys,
+    GetKeyHistory,
+    GetKeysWithPrefix,
+    GetContractAbi,
+    GetContractPackage,
+    GetStateSnapshot,
+    GetTransfersTo,
     GetAuctionInfo,
+    GetDelegations,
+    GetUnbondingPurses,
+    GetEraSummary,
+    GetValidatorRewards,
+    GetSupply,
+    GetSignedStatus,
+    GetChainspec,
     Keygen,
+    AccountAddress,
     GenerateCompletion,
 }
 
@@ -49,21 +108,76 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
     App::new(APP_NAME)
         .version(crate_version!())
         .about(crate_description!())
+        .arg(profile::arg())
+        .arg(describe_commands::arg())
         .subcommand(PutDeploy::build(DisplayOrder::PutDeploy as usize))
         .subcommand(MakeDeploy::build(DisplayOrder::MakeDeploy as usize))
         .subcommand(SignDeploy::build(DisplayOrder::SignDeploy as usize))
         .subcommand(SendDeploy::build(DisplayOrder::SendDeploy as usize))
+        .subcommand(CheckDeploy::build(DisplayOrder::CheckDeploy as usize))
         .subcommand(Transfer::build(DisplayOrder::Transfer as usize))
+        .subcommand(EstimateDeployCost::build(
+            DisplayOrder::EstimateDeployCost as usize,
+        ))
+        .subcommand(SimulateDeploy::build(DisplayOrder::SimulateDeploy as usize))
+        .subcommand(DisableContractVersion::build(
+            DisplayOrder::DisableContractVersion as usize,
+        ))
+        .subcommand(PutNamedKey::build(DisplayOrder::PutNamedKey as usize))
+        .subcommand(RemoveNamedKey::build(DisplayOrder::RemoveNamedKey as usize))
         .subcommand(GetDeploy::build(DisplayOrder::GetDeploy as usize))
         .subcommand(GetBlock::build(DisplayOrder::GetBlock as usize))
+        .subcommand(GetBlocks::build(DisplayOrder::GetBlocks as usize))
+        .subcommand(GetAlternativeBlocks::build(
+            DisplayOrder::GetAlternativeBlocks as usize,
+        ))
+        .subcommand(GetBlockByDeploy::build(
+            DisplayOrder::GetBlockByDeploy as usize,
+        ))
+        .subcommand(GetBlockAtTimestamp::build(
+            DisplayOrder::GetBlockAtTimestamp as usize,
+        ))
         .subcommand(ListDeploys::build(DisplayOrder::ListDeploys as usize))
         .subcommand(GetBalance::build(DisplayOrder::GetBalance as usize))
+        .subcommand(GetBalanceHistory::build(
+            DisplayOrder::GetBalanceHistory as usize,
+        ))
         .subcommand(GetStateRootHash::build(
             DisplayOrder::GetStateRootHash as usize,
         ))
         .subcommand(QueryState::build(DisplayOrder::QueryState as usize))
+        .subcommand(GetAccountInfo::build(DisplayOrder::GetAccountInfo as usize))
+        .subcommand(GetAccountDeploys::build(
+            DisplayOrder::GetAccountDeploys as usize,
+        ))
+        .subcommand(GetKeyHistory::build(DisplayOrder::GetKeyHistory as usize))
+        .subcommand(GetKeysWithPrefix::build(
+            DisplayOrder::GetKeysWithPrefix as usize,
+        ))
+        .subcommand(GetContractAbi::build(DisplayOrder::GetContractAbi as usize))
+        .subcommand(GetContractPackage::build(
+            DisplayOrder::GetContractPackage as usize,
+        ))
+        .subcommand(GetStateSnapshot::build(
+            DisplayOrder::GetStateSnapshot as usize,
+        ))
+        .subcommand(GetTransfersTo::build(DisplayOrder::GetTransfersTo as usize))
         .subcommand(GetAuctionInfo::build(DisplayOrder::GetAuctionInfo as usize))
+        .subcommand(GetDelegations::build(DisplayOrder::GetDelegations as usize))
+        .subcommand(GetUnbondingPurses::build(
+            DisplayOrder::GetUnbondingPurses as usize,
+        ))
+        .subcommand(GetEraSummary::build(DisplayOrder::GetEraSummary as usize))
+        .subcommand(GetValidatorRewards::build(
+            DisplayOrder::GetValidatorRewards as usize,
+        ))
+        .subcommand(GetSupply::build(DisplayOrder::GetSupply as usize))
+        .subcommand(GetSignedStatus::build(
+            DisplayOrder::GetSignedStatus as usize,
+        ))
+        .subcommand(GetChainspec::build(DisplayOrder::GetChainspec as usize))
         .subcommand(Keygen::build(DisplayOrder::Keygen as usize))
+        .subcommand(AccountAddress::build(DisplayOrder::AccountAddress as usize))
         .subcommand(GenerateCompletion::build(
             DisplayOrder::GenerateCompletion as usize,
         ))
@@ -72,20 +186,52 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
 #[tokio::main]
 async fn main() {
     let arg_matches = cli().get_matches();
+    if describe_commands::get(&arg_matches) {
+        describe_commands::print(&cli());
+        return;
+    }
+    profile::init(&arg_matches);
     match arg_matches.subcommand() {
         (PutDeploy::NAME, Some(matches)) => PutDeploy::run(matches),
         (MakeDeploy::NAME, Some(matches)) => MakeDeploy::run(matches),
         (SignDeploy::NAME, Some(matches)) => SignDeploy::run(matches),
         (SendDeploy::NAME, Some(matches)) => SendDeploy::run(matches),
+        (CheckDeploy::NAME, Some(matches)) => CheckDeploy::run(matches),
         (Transfer::NAME, Some(matches)) => Transfer::run(matches),
+        (EstimateDeployCost::NAME, Some(matches)) => EstimateDeployCost::run(matches),
+        (SimulateDeploy::NAME, Some(matches)) => SimulateDeploy::run(matches),
+        (DisableContractVersion::NAME, Some(matches)) => DisableContractVersion::run(matches),
+        (PutNamedKey::NAME, Some(matches)) => PutNamedKey::run(matches),
+        (RemoveNamedKey::NAME, Some(matches)) => RemoveNamedKey::run(matches),
         (GetDeploy::NAME, Some(matches)) => GetDeploy::run(matches),
         (GetBlock::NAME, Some(matches)) => GetBlock::run(matches),
+        (GetBlocks::NAME, Some(matches)) => GetBlocks::run(matches),
+        (GetAlternativeBlocks::NAME, Some(matches)) => GetAlternativeBlocks::run(matches),
+        (GetBlockByDeploy::NAME, Some(matches)) => GetBlockByDeploy::run(matches),
+        (GetBlockAtTimestamp::NAME, Some(matches)) => GetBlockAtTimestamp::run(matches),
         (ListDeploys::NAME, Some(matches)) => ListDeploys::run(matches),
         (GetBalance::NAME, Some(matches)) => GetBalance::run(matches),
+        (GetBalanceHistory::NAME, Some(matches)) => GetBalanceHistory::run(matches),
         (GetStateRootHash::NAME, Some(matches)) => GetStateRootHash::run(matches),
         (QueryState::NAME, Some(matches)) => QueryState::run(matches),
+        (GetAccountInfo::NAME, Some(matches)) => GetAccountInfo::run(matches),
+        (GetAccountDeploys::NAME, Some(matches)) => GetAccountDeploys::run(matches),
+        (GetKeyHistory::NAME, Some(matches)) => GetKeyHistory::run(matches),
+        (GetKeysWithPrefix::NAME, Some(matches)) => GetKeysWithPrefix::run(matches),
+        (GetContractAbi::NAME, Some(matches)) => GetContractAbi::run(matches),
+        (GetContractPackage::NAME, Some(matches)) => GetContractPackage::run(matches),
+        (GetStateSnapshot::NAME, Some(matches)) => GetStateSnapshot::run(matches),
+        (GetTransfersTo::NAME, Some(matches)) => GetTransfersTo::run(matches),
         (GetAuctionInfo::NAME, Some(matches)) => GetAuctionInfo::run(matches),
+        (GetDelegations::NAME, Some(matches)) => GetDelegations::run(matches),
+        (GetUnbondingPurses::NAME, Some(matches)) => GetUnbondingPurses::run(matches),
+        (GetEraSummary::NAME, Some(matches)) => GetEraSummary::run(matches),
+        (GetValidatorRewards::NAME, Some(matches)) => GetValidatorRewards::run(matches),
+        (GetSupply::NAME, Some(matches)) => GetSupply::run(matches),
+        (GetSignedStatus::NAME, Some(matches)) => GetSignedStatus::run(matches),
+        (GetChainspec::NAME, Some(matches)) => GetChainspec::run(matches),
         (Keygen::NAME, Some(matches)) => Keygen::run(matches),
+        (AccountAddress::NAME, Some(matches)) => AccountAddress::run(matches),
         (GenerateCompletion::NAME, Some(matches)) => GenerateCompletion::run(matches),
         _ => {
             let _ = cli().print_long_help();
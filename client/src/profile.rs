@@ -0,0 +1,220 @@
+//! Named network profiles read from the client config file.
+//!
+//! A profile supplies defaults for the node address, chain name, standard payment amount and
+//! secret key, so that switching between e.g. testnet and mainnet takes a single `--profile` arg
+//! rather than several flags which must all be changed together.  Args given on the command line
+//! always take precedence over the profile.
+
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use clap::{Arg, ArgMatches};
+use once_cell::sync::OnceCell;
+use serde::Deserialize;
+
+use casper_client::keygen::SECRET_KEY_PEM;
+
+const ARG_NAME: &str = "profile";
+const ARG_VALUE_NAME: &str = "NAME";
+const ARG_HELP: &str =
+    "Name of the network profile in the client config file supplying defaults for the node \
+    address, chain name, payment amount and secret key. If not given, the config file's default \
+    profile is used, if any. The config file is read from ~/.casper/config.toml, or from the path \
+    held by the CASPER_CLIENT_CONFIG environment variable";
+
+/// The environment variable holding the path of the config file, overriding the default path.
+const CONFIG_PATH_ENV_VAR: &str = "CASPER_CLIENT_CONFIG";
+/// The directory holding the config file, relative to the user's home directory.
+const CONFIG_DIR: &str = ".casper";
+/// The name of the config file.
+const CONFIG_FILE: &str = "config.toml";
+
+static PROFILE: OnceCell<Profile> = OnceCell::new();
+
+/// The contents of the client config file.
+#[derive(Default, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    /// The profile used when none is named via `--profile`.
+    #[serde(default)]
+    default_profile: Option<String>,
+    /// The profiles, keyed by name.
+    #[serde(default)]
+    profiles: BTreeMap<String, Profile>,
+}
+
+/// A named set of defaults for a single network.
+#[derive(Clone, Default, Deserialize, PartialEq, Eq, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    /// Hostname or IP and port of the node on which the HTTP service is running.
+    node_address: Option<String>,
+    /// Name of the chain.
+    chain_name: Option<String>,
+    /// The 'amount' arg of the standard-payment contract, used if no payment option is given.
+    payment_amount: Option<String>,
+    /// Directory holding the "secret_key.pem" file used to sign deploys.  Relative paths are
+    /// resolved from the config file's directory.
+    keys_directory: Option<PathBuf>,
+    /// The path of the secret key file, derived from `keys_directory`.
+    #[serde(skip)]
+    secret_key: Option<String>,
+}
+
+impl Profile {
+    pub fn node_address(&self) -> Option<&str> {
+        self.node_address.as_deref()
+    }
+
+    pub fn chain_name(&self) -> Option<&str> {
+        self.chain_name.as_deref()
+    }
+
+    pub fn payment_amount(&self) -> Option<&str> {
+        self.payment_amount.as_deref()
+    }
+
+    pub fn secret_key(&self) -> Option<&str> {
+        self.secret_key.as_deref()
+    }
+
+    /// Resolves the secret key path from the keys directory.
+    fn resolve(mut self, config_dir: &Path) -> Self {
+        self.secret_key = self.keys_directory.as_ref().map(|keys_directory| {
+            let keys_directory = match keys_directory.strip_prefix("~") {
+                Ok(relative) => dirs::home_dir()
+                    .map(|home_dir| home_dir.join(relative))
+                    .unwrap_or_else(|| keys_directory.clone()),
+                Err(_) => config_dir.join(keys_directory),
+            };
+            keys_directory.join(SECRET_KEY_PEM).display().to_string()
+        });
+        self
+    }
+}
+
+pub fn arg() -> Arg<'static, 'static> {
+    Arg::with_name(ARG_NAME)
+        .long(ARG_NAME)
+        .global(true)
+        .required(false)
+        .value_name(ARG_VALUE_NAME)
+        .help(ARG_HELP)
+}
+
+/// Loads the profile named by the `--profile` arg, or else the config file's default profile.
+///
+/// Must be called once the args are parsed and before any arg falling back to the profile is
+/// retrieved.  Exits with an error if a profile is named but can't be loaded.
+pub fn init(matches: &ArgMatches) {
+    let maybe_name = matches.value_of(ARG_NAME).or_else(|| {
+        matches
+            .subcommand()
+            .1
+            .and_then(|sub_matches| sub_matches.value_of(ARG_NAME))
+    });
+    let profile = load(maybe_name).unwrap_or_else(|error| panic!("{}", error));
+    let _ = PROFILE.set(profile);
+}
+
+/// Returns the loaded profile, or an empty one if none was loaded.
+pub fn get() -> &'static Profile {
+    PROFILE.get_or_init(Profile::default)
+}
+
+fn config_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os(CONFIG_PATH_ENV_VAR) {
+        return Some(PathBuf::from(path));
+    }
+    dirs::home_dir().map(|home_dir| home_dir.join(CONFIG_DIR).join(CONFIG_FILE))
+}
+
+fn load(maybe_name: Option<&str>) -> Result<Profile, String> {
+    let path = match config_path() {
+        Some(path) if path.exists() => path,
+        _ if maybe_name.is_none() => return Ok(Profile::default()),
+        Some(path) => return Err(format!("no client config file at {}", path.display())),
+        None => return Err("can't locate the client config file".to_string()),
+    };
+    let contents = fs::read_to_string(&path)
+        .map_err(|error| format!("failed to read {}: {}", path.display(), error))?;
+    let config_dir = path.parent().unwrap_or_else(|| Path::new("."));
+    select(&contents, maybe_name, config_dir)
+        .map_err(|error| format!("invalid client config file {}: {}", path.display(), error))
+}
+
+/// Parses the config file `contents` and returns the profile named `maybe_name`, or else the
+/// default profile, or else an empty profile.
+fn select(contents: &str, maybe_name: Option<&str>, config_dir: &Path) -> Result<Profile, String> {
+    let mut config: ConfigFile = toml::from_str(contents).map_err(|error| error.to_string())?;
+    let name = match maybe_name.or_else(|| config.default_profile.as_deref()) {
+        Some(name) => name.to_string(),
+        None => return Ok(Profile::default()),
+    };
+    let available = config.profiles.keys().cloned().collect::<Vec<_>>();
+    config
+        .profiles
+        .remove(&name)
+        .map(|profile| profile.resolve(config_dir))
+        .ok_or_else(|| {
+            format!(
+                "no profile named '{}', available profiles: [{}]",
+                name,
+                available.join(", ")
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+        default_profile = "testnet"
+
+        [profiles.testnet]
+        node_address = "http://testnet.example.com:7777"
+        chain_name = "casper-test"
+        payment_amount = "100000000"
+        keys_directory = "keys/testnet"
+
+        [profiles.mainnet]
+        node_address = "http://mainnet.example.com:7777"
+        chain_name = "casper"
+    "#;
+
+    #[test]
+    fn should_select_named_or_default_profile() {
+        let config_dir = Path::new("/etc/casper");
+
+        let testnet = select(CONFIG, None, config_dir).unwrap();
+        assert_eq!(
+            testnet.node_address(),
+            Some("http://testnet.example.com:7777")
+        );
+        assert_eq!(testnet.chain_name(), Some("casper-test"));
+        assert_eq!(testnet.payment_amount(), Some("100000000"));
+        assert_eq!(
+            testnet.secret_key(),
+            Some("/etc/casper/keys/testnet/secret_key.pem")
+        );
+
+        let mainnet = select(CONFIG, Some("mainnet"), config_dir).unwrap();
+        assert_eq!(mainnet.chain_name(), Some("casper"));
+        assert_eq!(mainnet.payment_amount(), None);
+        assert_eq!(mainnet.secret_key(), None);
+
+        let error = select(CONFIG, Some("devnet"), config_dir).unwrap_err();
+        assert!(error.contains("mainnet, testnet"), "{}", error);
+    }
+
+    #[test]
+    fn should_use_empty_profile_without_default() {
+        let profile = select("", None, Path::new(".")).unwrap();
+        assert_eq!(profile, Profile::default());
+        assert!(select("[profiles.x]\nunknown = 1\n", Some("x"), Path::new(".")).is_err());
+    }
+}
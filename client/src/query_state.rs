@@ -12,6 +12,7 @@ enum DisplayOrder {
     NodeAddress,
     RpcId,
     StateRootHash,
+    BlockIdentifier,
     Key,
     Path,
 }
@@ -107,9 +108,11 @@ impl<'a, 'b> ClientCommand<'a, 'b> for GetItem {
                 DisplayOrder::NodeAddress as usize,
             ))
             .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
-            .arg(common::state_root_hash::arg(
-                DisplayOrder::StateRootHash as usize,
+            .arg(common::state_root_hash::arg(DisplayOrder::StateRootHash as usize).required(false))
+            .args(&common::block_selector::args(
+                DisplayOrder::BlockIdentifier as usize,
             ))
+            .group(common::block_selector::group().arg(common::state_root_hash::ARG_NAME))
             .arg(key::arg())
             .arg(path::arg())
     }
@@ -118,22 +121,34 @@ impl<'a, 'b> ClientCommand<'a, 'b> for GetItem {
         let maybe_rpc_id = common::rpc_id::get(matches);
         let node_address = common::node_address::get(matches);
         let verbose = common::verbose::get(matches);
-        let state_root_hash = common::state_root_hash::get(matches);
         let key = key::get(matches);
         let path = path::get(matches);
 
-        let response = casper_client::get_item(
-            maybe_rpc_id,
-            node_address,
-            verbose,
-            state_root_hash,
-            &key,
-            path,
-        )
+        // Without a state root hash, the state as of the selected block is queried.
+        let response = match matches.value_of(common::state_root_hash::ARG_NAME) {
+            Some(state_root_hash) => casper_client::get_item(
+                maybe_rpc_id,
+                node_address,
+                verbose,
+                state_root_hash,
+                &key,
+                path,
+            ),
+            None => casper_client::get_item_at_block(
+                maybe_rpc_id,
+                node_address,
+                verbose,
+                &common::block_selector::get(matches),
+                &key,
+                path,
+            ),
+        }
         .unwrap_or_else(|error| panic!("response error: {}", error));
+        let mut output = serde_json::to_value(&response).expect("should encode to JSON");
+        casper_client::decode_cl_values(&mut output);
         println!(
             "{}",
-            serde_json::to_string_pretty(&response).expect("should encode to JSON")
+            serde_json::to_string_pretty(&output).expect("should encode to JSON")
         );
     }
 }
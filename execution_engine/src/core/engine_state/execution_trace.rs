@@ -0,0 +1,131 @@
+use std::{cell::RefCell, mem, rc::Rc};
+
+use casper_types::Phase;
+
+use crate::shared::gas::Gas;
+
+/// A single call to a host function made by Wasm code during execution.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HostFunctionCall {
+    /// The name under which the host function is imported, e.g. `casper_write`.
+    pub name: &'static str,
+    /// The raw arguments of the call, mostly pointers into and sizes of Wasm memory.
+    pub args: Vec<i64>,
+    /// The phase during which the call was made.
+    pub phase: Phase,
+    /// The number of contract calls on the stack, i.e. `0` for the session or payment code itself.
+    pub depth: usize,
+    /// The gas spent in the current phase immediately before the call.
+    pub gas_before: Gas,
+    /// The gas spent in the current phase once the call returned, including the cost of any nested
+    /// contract calls.
+    pub gas_after: Gas,
+    /// The trap raised by the call, if any.
+    pub error: Option<String>,
+}
+
+/// The host function calls made during execution of a deploy, in the order they were made.
+///
+/// Calls to the `gas` host function used for metering are omitted.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExecutionTrace {
+    calls: Vec<HostFunctionCall>,
+}
+
+impl ExecutionTrace {
+    pub fn new() -> Self {
+        ExecutionTrace::default()
+    }
+
+    pub fn calls(&self) -> &[HostFunctionCall] {
+        &self.calls
+    }
+
+    pub fn into_calls(self) -> Vec<HostFunctionCall> {
+        self.calls
+    }
+}
+
+/// Records host function calls into an `ExecutionTrace` shared by all the runtimes of a deploy.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Tracer {
+    trace: Rc<RefCell<ExecutionTrace>>,
+    depth: usize,
+}
+
+impl Tracer {
+    /// Returns a tracer recording into the same trace, for a contract called by the current one.
+    pub(crate) fn nested(&self) -> Self {
+        Tracer {
+            trace: Rc::clone(&self.trace),
+            depth: self.depth + 1,
+        }
+    }
+
+    /// Records the start of a call, returning the index to pass to `finish`.
+    ///
+    /// Calls are recorded when started so that they precede any calls nested within them.
+    pub(crate) fn start(
+        &self,
+        name: &'static str,
+        args: Vec<i64>,
+        phase: Phase,
+        gas_before: Gas,
+    ) -> usize {
+        let mut trace = self.trace.borrow_mut();
+        trace.calls.push(HostFunctionCall {
+            name,
+            args,
+            phase,
+            depth: self.depth,
+            gas_before,
+            gas_after: gas_before,
+            error: None,
+        });
+        trace.calls.len() - 1
+    }
+
+    /// Records the outcome of the call at `index`.
+    pub(crate) fn finish(&self, index: usize, gas_after: Gas, error: Option<String>) {
+        if let Some(call) = self.trace.borrow_mut().calls.get_mut(index) {
+            call.gas_after = gas_after;
+            call.error = error;
+        }
+    }
+
+    /// Returns the calls recorded so far, leaving the trace empty.
+    pub(crate) fn take(&self) -> ExecutionTrace {
+        mem::take(&mut *self.trace.borrow_mut())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_record_nested_calls_in_order() {
+        let tracer = Tracer::default();
+        let outer = tracer.start(
+            "casper_call_contract",
+            vec![1, 2],
+            Phase::Session,
+            Gas::new(10.into()),
+        );
+        let nested = tracer.nested();
+        let inner = nested.start("casper_write", vec![3], Phase::Session, Gas::new(20.into()));
+        nested.finish(inner, Gas::new(30.into()), None);
+        tracer.finish(outer, Gas::new(40.into()), Some("revert".to_string()));
+
+        let calls = tracer.take().into_calls();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].name, "casper_call_contract");
+        assert_eq!(calls[0].depth, 0);
+        assert_eq!(calls[0].gas_after, Gas::new(40.into()));
+        assert_eq!(calls[0].error.as_deref(), Some("revert"));
+        assert_eq!(calls[1].name, "casper_write");
+        assert_eq!(calls[1].depth, 1);
+        assert_eq!(calls[1].gas_before, Gas::new(20.into()));
+        assert!(tracer.take().calls().is_empty());
+    }
+}
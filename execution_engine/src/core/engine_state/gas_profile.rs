@@ -0,0 +1,327 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    rc::Rc,
+};
+
+use parity_wasm::elements::{External, ImportCountType, Instruction, Module};
+use pwasm_utils::rules::InstructionType;
+
+use crate::shared::{gas::Gas, opcode_costs::OpcodeCosts};
+
+/// The gas spent during execution of a deploy, broken down by where it was spent.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GasProfile {
+    /// The gas charged by each host function, excluding the gas spent by any contracts it called.
+    pub host_functions: BTreeMap<&'static str, Gas>,
+    /// The gas charged for executing Wasm instructions, by the name of the opcode cost category as
+    /// configured in the chainspec.
+    pub opcodes: BTreeMap<&'static str, Gas>,
+}
+
+impl GasProfile {
+    pub fn new() -> Self {
+        GasProfile::default()
+    }
+
+    /// Returns the total gas accounted for by this profile.
+    pub fn total(&self) -> Gas {
+        self.host_functions
+            .values()
+            .chain(self.opcodes.values())
+            .copied()
+            .sum()
+    }
+}
+
+fn add_gas(gas_by_name: &mut BTreeMap<&'static str, Gas>, name: &'static str, amount: Gas) {
+    *gas_by_name.entry(name).or_default() += amount;
+}
+
+#[derive(Debug, Default)]
+struct ProfilerState {
+    profile: GasProfile,
+    /// The gas attributed so far, used to exclude the gas of nested calls from host functions.
+    attributed: Gas,
+}
+
+/// Builds a `GasProfile` shared by all the runtimes of a deploy.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct GasProfiler {
+    state: Rc<RefCell<ProfilerState>>,
+    blocks: Rc<BlockCosts>,
+}
+
+impl GasProfiler {
+    /// Returns a profiler recording into the same profile, for a runtime executing `module`.
+    pub(crate) fn for_module(&self, module: &Module, opcode_costs: &OpcodeCosts) -> Self {
+        GasProfiler {
+            state: Rc::clone(&self.state),
+            blocks: Rc::new(BlockCosts::new(module, opcode_costs)),
+        }
+    }
+
+    /// Returns the value to pass to `host_function_finished` once the host function returns.
+    pub(crate) fn host_function_started(&self) -> Gas {
+        self.state.borrow().attributed
+    }
+
+    /// Attributes the gas spent by a host function call to it, excluding any gas already
+    /// attributed to Wasm instructions or host functions executed by contracts it called.
+    pub(crate) fn host_function_finished(
+        &self,
+        name: &'static str,
+        attributed_before: Gas,
+        gas_spent: Gas,
+    ) {
+        let mut state = self.state.borrow_mut();
+        let nested = state
+            .attributed
+            .value()
+            .saturating_sub(attributed_before.value());
+        let exclusive = Gas::new(gas_spent.value().saturating_sub(nested));
+        add_gas(&mut state.profile.host_functions, name, exclusive);
+        state.attributed += exclusive;
+    }
+
+    /// Attributes the gas charged by the metering code injected into the Wasm module.
+    pub(crate) fn opcodes_charged(&self, amount: u32) {
+        let mut state = self.state.borrow_mut();
+        match self.blocks.0.get(&amount) {
+            Some(categories) => {
+                for (category, gas) in categories {
+                    add_gas(
+                        &mut state.profile.opcodes,
+                        category,
+                        Gas::new((*gas).into()),
+                    );
+                }
+            }
+            // Only the injected memory growth counter charges amounts computed at runtime.
+            None => add_gas(
+                &mut state.profile.opcodes,
+                "grow_memory",
+                Gas::new(amount.into()),
+            ),
+        }
+        state.attributed += Gas::new(amount.into());
+    }
+
+    /// Returns the profile built so far, leaving it empty.
+    pub(crate) fn take(&self) -> GasProfile {
+        let mut state = self.state.borrow_mut();
+        state.attributed = Gas::default();
+        std::mem::take(&mut state.profile)
+    }
+}
+
+/// The composition of the gas charged when entering each metered block of a module, by opcode cost
+/// category, keyed by the amount charged.
+///
+/// The metering code only passes the amount to charge, so blocks charging the same amount can't be
+/// told apart; the composition of the first such block is used for all of them.  Blocks which
+/// contain instructions added by instrumentation applied after metering, e.g. the stack height
+/// limiter, have their composition scaled to the amount charged.
+#[derive(Debug, Default)]
+struct BlockCosts(BTreeMap<u32, BTreeMap<&'static str, u64>>);
+
+impl BlockCosts {
+    fn new(module: &Module, opcode_costs: &OpcodeCosts) -> Self {
+        let mut block_costs = BlockCosts::default();
+
+        let gas_function = match module.import_section().and_then(|import_section| {
+            import_section
+                .entries()
+                .iter()
+                .filter(|entry| matches!(entry.external(), External::Function(_)))
+                .position(|entry| entry.field() == "gas")
+        }) {
+            Some(index) => index as u32,
+            None => return block_costs,
+        };
+        let bodies = match module.code_section() {
+            Some(code_section) => code_section.bodies(),
+            None => return block_costs,
+        };
+
+        // The memory growth counter injected along with the metering code replaces every
+        // `grow_memory` instruction, and is the only function still containing one.
+        let imported_functions = module.import_count(ImportCountType::Function);
+        let grow_counters: BTreeSet<u32> = bodies
+            .iter()
+            .enumerate()
+            .filter(|(_, body)| {
+                body.code()
+                    .elements()
+                    .iter()
+                    .any(|instruction| matches!(instruction, Instruction::GrowMemory(_)))
+            })
+            .map(|(index, _)| (imported_functions + index) as u32)
+            .collect();
+
+        for body in bodies {
+            let instructions = body.code().elements();
+            // The open blocks, each with the amount charged on entering it and its composition.
+            let mut open_blocks = vec![(None, BTreeMap::new())];
+            let mut cursor = 0;
+            while cursor < instructions.len() {
+                let instruction = &instructions[cursor];
+                if let (Instruction::I32Const(amount), Some(Instruction::Call(index))) =
+                    (instruction, instructions.get(cursor + 1))
+                {
+                    if *index == gas_function {
+                        if let Some((charged, _)) = open_blocks.last_mut() {
+                            *charged = Some(*amount as u32);
+                        }
+                        cursor += 2;
+                        continue;
+                    }
+                }
+
+                let instruction_type = match instruction {
+                    Instruction::Call(index) if grow_counters.contains(index) => {
+                        InstructionType::GrowMemory
+                    }
+                    _ => InstructionType::op(instruction),
+                };
+                let (category, cost) = opcode_costs.category_and_cost(instruction_type);
+                let tally = |open_blocks: &mut Vec<(Option<u32>, BTreeMap<_, _>)>| {
+                    if let Some((_, composition)) = open_blocks.last_mut() {
+                        *composition.entry(category).or_insert(0) += u64::from(cost);
+                    }
+                };
+                match instruction {
+                    Instruction::Block(_) | Instruction::If(_) | Instruction::Loop(_) => {
+                        tally(&mut open_blocks);
+                        open_blocks.push((None, BTreeMap::new()));
+                    }
+                    Instruction::End => {
+                        tally(&mut open_blocks);
+                        block_costs.close(open_blocks.pop());
+                    }
+                    Instruction::Else => {
+                        block_costs.close(open_blocks.pop());
+                        open_blocks.push((None, BTreeMap::new()));
+                    }
+                    _ => tally(&mut open_blocks),
+                }
+                cursor += 1;
+            }
+        }
+
+        block_costs
+    }
+
+    fn close(&mut self, maybe_block: Option<(Option<u32>, BTreeMap<&'static str, u64>)>) {
+        if let Some((Some(charged), composition)) = maybe_block {
+            self.0
+                .entry(charged)
+                .or_insert_with(|| scale(composition, charged));
+        }
+    }
+}
+
+/// Scales the costs in `composition` so that they add up to `total`.
+fn scale(mut composition: BTreeMap<&'static str, u64>, total: u32) -> BTreeMap<&'static str, u64> {
+    let total = u64::from(total);
+    let sum: u64 = composition.values().sum();
+    if sum == total {
+        return composition;
+    }
+    if sum == 0 {
+        let mut composition = BTreeMap::new();
+        composition.insert("regular", total);
+        return composition;
+    }
+
+    for cost in composition.values_mut() {
+        *cost = (u128::from(*cost) * u128::from(total) / u128::from(sum)) as u64;
+    }
+    // Assign the remainder lost to rounding down to the largest category.
+    let remainder = total - composition.values().sum::<u64>();
+    if let Some(largest) = composition.values_mut().max() {
+        *largest += remainder;
+    }
+    composition
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::{builder, elements::Instructions};
+
+    use super::*;
+    use crate::shared::{wasm_config::WasmConfig, wasm_prep::Preprocessor};
+
+    #[test]
+    fn should_attribute_block_cost_to_categories() {
+        let module = builder::module()
+            .function()
+            .signature()
+            .build()
+            .body()
+            .with_instructions(Instructions::new(vec![
+                Instruction::I32Const(1),
+                Instruction::I32Const(2),
+                Instruction::I32Add,
+                Instruction::Drop,
+                Instruction::End,
+            ]))
+            .build()
+            .build()
+            .export()
+            .field("call")
+            .build()
+            .memory()
+            .build()
+            .build();
+        let module_bytes = parity_wasm::serialize(module).expect("should serialize");
+        let wasm_config = WasmConfig::default();
+        let module = Preprocessor::new(wasm_config)
+            .preprocess(&module_bytes)
+            .expect("should preprocess");
+
+        let opcode_costs = wasm_config.opcode_costs();
+        let profiler = GasProfiler::default().for_module(&module, &opcode_costs);
+        // The function has a single metered block, charged for all of its instructions.
+        assert_eq!(profiler.blocks.0.len(), 1);
+        let block_total = *profiler.blocks.0.keys().next().unwrap();
+        profiler.opcodes_charged(block_total);
+
+        let profile = profiler.take();
+        assert_eq!(profile.total(), Gas::new(block_total.into()));
+        assert_eq!(
+            profile.opcodes.get("const"),
+            Some(&Gas::new((2 * opcode_costs.op_const).into()))
+        );
+        assert_eq!(
+            profile.opcodes.get("add"),
+            Some(&Gas::new(opcode_costs.add.into()))
+        );
+        assert!(profile.host_functions.is_empty());
+    }
+
+    #[test]
+    fn should_exclude_nested_gas_from_host_functions() {
+        let profiler = GasProfiler::default();
+        let outer = profiler.host_function_started();
+        profiler.opcodes_charged(10);
+        let inner = profiler.host_function_started();
+        profiler.host_function_finished("casper_write", inner, Gas::new(5.into()));
+        profiler.host_function_finished("casper_call_contract", outer, Gas::new(100.into()));
+
+        let profile = profiler.take();
+        assert_eq!(
+            profile.host_functions.get("casper_call_contract"),
+            Some(&Gas::new(85.into()))
+        );
+        assert_eq!(
+            profile.host_functions.get("casper_write"),
+            Some(&Gas::new(5.into()))
+        );
+        assert_eq!(
+            profile.opcodes.get("grow_memory"),
+            Some(&Gas::new(10.into()))
+        );
+        assert_eq!(profile.total(), Gas::new(100.into()));
+    }
+}
@@ -19,7 +19,10 @@ use casper_types::{
 use super::SYSTEM_ACCOUNT_ADDR;
 use crate::{
     core::engine_state::execution_effect::ExecutionEffect,
-    shared::{motes::Motes, newtypes::Blake2bHash, wasm_config::WasmConfig, TypeMismatch},
+    shared::{
+        motes::Motes, newtypes::Blake2bHash, system_config::SystemConfig, wasm_config::WasmConfig,
+        TypeMismatch,
+    },
     storage::global_state::CommitResult,
 };
 
@@ -184,6 +187,79 @@ impl FromBytes for GenesisAccount {
     }
 }
 
+/// A stake delegated at genesis by `delegator` to the genesis validator `validator`.
+#[derive(DataSize, Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenesisDelegator {
+    delegator: PublicKey,
+    validator: PublicKey,
+    delegated_amount: Motes,
+}
+
+impl GenesisDelegator {
+    pub fn new(delegator: PublicKey, validator: PublicKey, delegated_amount: Motes) -> Self {
+        GenesisDelegator {
+            delegator,
+            validator,
+            delegated_amount,
+        }
+    }
+
+    pub fn delegator(&self) -> PublicKey {
+        self.delegator
+    }
+
+    pub fn validator(&self) -> PublicKey {
+        self.validator
+    }
+
+    pub fn delegated_amount(&self) -> Motes {
+        self.delegated_amount
+    }
+}
+
+impl Distribution<GenesisDelegator> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> GenesisDelegator {
+        let delegator = PublicKey::Ed25519(rng.gen());
+        let validator = PublicKey::Ed25519(rng.gen());
+
+        let mut u512_array = [0u8; 64];
+        rng.fill_bytes(u512_array.as_mut());
+        let delegated_amount = Motes::new(U512::from(u512_array));
+
+        GenesisDelegator::new(delegator, validator, delegated_amount)
+    }
+}
+
+impl ToBytes for GenesisDelegator {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        buffer.extend(self.delegator.to_bytes()?);
+        buffer.extend(self.validator.to_bytes()?);
+        buffer.extend(self.delegated_amount.value().to_bytes()?);
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.delegator.serialized_length()
+            + self.validator.serialized_length()
+            + self.delegated_amount.value().serialized_length()
+    }
+}
+
+impl FromBytes for GenesisDelegator {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (delegator, remainder) = PublicKey::from_bytes(bytes)?;
+        let (validator, remainder) = PublicKey::from_bytes(remainder)?;
+        let (delegated_amount_value, remainder) = U512::from_bytes(remainder)?;
+        let genesis_delegator = GenesisDelegator {
+            delegator,
+            validator,
+            delegated_amount: Motes::new(delegated_amount_value),
+        };
+        Ok((genesis_delegator, remainder))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct GenesisConfig {
     name: String,
@@ -262,7 +338,9 @@ pub struct ExecConfig {
     standard_payment_installer_bytes: Vec<u8>,
     auction_installer_bytes: Vec<u8>,
     accounts: Vec<GenesisAccount>,
+    delegators: Vec<GenesisDelegator>,
     wasm_config: WasmConfig,
+    system_config: SystemConfig,
     validator_slots: u32,
     auction_delay: u64,
     locked_funds_period: EraId,
@@ -289,7 +367,9 @@ impl ExecConfig {
             standard_payment_installer_bytes,
             auction_installer_bytes,
             accounts,
+            delegators: Vec::new(),
             wasm_config,
+            system_config: SystemConfig::default(),
             validator_slots,
             auction_delay,
             locked_funds_period,
@@ -297,6 +377,18 @@ impl ExecConfig {
         }
     }
 
+    /// Sets the stakes delegated to genesis validators.
+    pub fn with_delegators(mut self, delegators: Vec<GenesisDelegator>) -> Self {
+        self.delegators = delegators;
+        self
+    }
+
+    /// Sets the costs of calling the system contracts' entry points.
+    pub fn with_system_config(mut self, system_config: SystemConfig) -> Self {
+        self.system_config = system_config;
+        self
+    }
+
     pub fn mint_installer_bytes(&self) -> &[u8] {
         self.mint_installer_bytes.as_slice()
     }
@@ -317,6 +409,10 @@ impl ExecConfig {
         &self.wasm_config
     }
 
+    pub fn system_config(&self) -> &SystemConfig {
+        &self.system_config
+    }
+
     pub fn get_bonded_validators(&self) -> impl Iterator<Item = &GenesisAccount> {
         self.accounts
             .iter()
@@ -331,6 +427,14 @@ impl ExecConfig {
         self.accounts.push(account)
     }
 
+    pub fn delegators(&self) -> &[GenesisDelegator] {
+        self.delegators.as_slice()
+    }
+
+    pub fn push_delegator(&mut self, delegator: GenesisDelegator) {
+        self.delegators.push(delegator)
+    }
+
     pub fn validator_slots(&self) -> u32 {
         self.validator_slots
     }
@@ -366,8 +470,13 @@ impl Distribution<ExecConfig> for Standard {
         count = rng.gen_range(1, 10);
         let accounts = iter::repeat(()).map(|_| rng.gen()).take(count).collect();
 
+        count = rng.gen_range(0, 10);
+        let delegators = iter::repeat(()).map(|_| rng.gen()).take(count).collect();
+
         let wasm_config = rng.gen();
 
+        let system_config = rng.gen();
+
         let validator_slots = rng.gen();
 
         let auction_delay = rng.gen();
@@ -385,7 +494,9 @@ impl Distribution<ExecConfig> for Standard {
             standard_payment_installer_bytes,
             auction_installer_bytes,
             accounts,
+            delegators,
             wasm_config,
+            system_config,
             validator_slots,
             auction_delay,
             locked_funds_period,
@@ -404,4 +515,11 @@ mod tests {
         let genesis_account: GenesisAccount = rng.gen();
         bytesrepr::test_serialization_roundtrip(&genesis_account);
     }
+
+    #[test]
+    fn genesis_delegator_bytesrepr_roundtrip() {
+        let mut rng = rand::thread_rng();
+        let genesis_delegator: GenesisDelegator = rng.gen();
+        bytesrepr::test_serialization_roundtrip(&genesis_delegator);
+    }
 }
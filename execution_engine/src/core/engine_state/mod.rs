@@ -7,6 +7,8 @@ pub mod executable_deploy_item;
 pub mod execute_request;
 pub mod execution_effect;
 pub mod execution_result;
+pub mod execution_trace;
+pub mod gas_profile;
 pub mod genesis;
 pub mod op;
 pub mod query;
@@ -19,7 +21,6 @@ pub mod upgrade;
 use std::{
     cell::RefCell,
     collections::{BTreeMap, BTreeSet},
-    iter::FromIterator,
     rc::Rc,
 };
 
@@ -27,14 +28,16 @@ use lazy_static::lazy_static;
 use num_rational::Ratio;
 use num_traits::Zero;
 use parity_wasm::elements::Module;
-use tracing::{debug, error, warn};
+use tracing::{debug, debug_span, error, warn};
 
 use casper_types::{
     account::AccountHash,
     auction::{
-        EraValidators, ARG_AUCTION_DELAY, ARG_GENESIS_VALIDATORS, ARG_LOCKED_FUNDS_PERIOD,
+        self, EraValidators, SeigniorageRecipientsSnapshot, ARG_AUCTION_DELAY,
+        ARG_GENESIS_DELEGATORS, ARG_GENESIS_VALIDATORS, ARG_LOCKED_FUNDS_PERIOD,
         ARG_MINT_CONTRACT_PACKAGE_HASH, ARG_REWARD_FACTORS, ARG_VALIDATOR_PUBLIC_KEYS,
-        ARG_VALIDATOR_SLOTS, AUCTION_DELAY_KEY, LOCKED_FUNDS_PERIOD_KEY, VALIDATOR_SLOTS_KEY,
+        ARG_VALIDATOR_SLOTS, AUCTION_DELAY_KEY, LOCKED_FUNDS_PERIOD_KEY,
+        SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY, VALIDATOR_SLOTS_KEY,
     },
     bytesrepr::{self, ToBytes},
     contracts::{NamedKeys, ENTRY_POINT_NAME_INSTALL, UPGRADE_ENTRY_POINT_NAME},
@@ -55,8 +58,13 @@ pub use self::{
     executable_deploy_item::ExecutableDeployItem,
     execute_request::ExecuteRequest,
     execution_result::{ExecutionResult, ExecutionResults, ForcedTransferResult},
+    execution_trace::{ExecutionTrace, HostFunctionCall},
+    gas_profile::GasProfile,
     genesis::{ExecConfig, GenesisAccount, GenesisResult, POS_PAYMENT_PURSE},
-    query::{QueryRequest, QueryResult},
+    query::{
+        KeysWithPrefixRequest, KeysWithPrefixResult, QueryBatchResult, QueryRequest,
+        QueryRequestBatch, QueryResult,
+    },
     system_contract_cache::SystemContractCache,
     transfer::{TransferRuntimeArgsBuilder, TransferTargetMode},
     upgrade::{UpgradeConfig, UpgradeResult},
@@ -76,6 +84,7 @@ use crate::{
         account::Account,
         additive_map::AdditiveMap,
         gas::Gas,
+        metrics,
         motes::Motes,
         newtypes::{Blake2bHash, CorrelationId},
         stored_value::StoredValue,
@@ -84,7 +93,10 @@ use crate::{
         wasm_prep::{self, Preprocessor},
     },
     storage::{
-        global_state::{CommitResult, StateProvider},
+        global_state::{
+            caching::CachingStateReader, lmdb::LmdbGlobalState, scratch::ScratchGlobalState,
+            CommitResult, StateProvider, StateReader,
+        },
         protocol_data::ProtocolData,
     },
 };
@@ -136,6 +148,20 @@ impl GetModuleResult {
     }
 }
 
+impl EngineState<LmdbGlobalState> {
+    /// Returns an engine executing against a scratch layer over this engine's global state.
+    ///
+    /// Everything the returned engine commits is held in memory and dropped along with it, so it
+    /// suits speculative execution which must never reach LMDB.
+    pub fn scratch(&self) -> EngineState<ScratchGlobalState> {
+        EngineState {
+            config: self.config,
+            system_contract_cache: self.system_contract_cache.clone(),
+            state: self.state.scratch(),
+        }
+    }
+}
+
 impl<S> EngineState<S>
 where
     S: StateProvider,
@@ -182,6 +208,10 @@ where
         protocol_version: ProtocolVersion,
         ee_config: &ExecConfig,
     ) -> Result<GenesisResult, Error> {
+        let span = debug_span!("genesis", %protocol_version);
+        let _entered = span.enter();
+        let _timer = metrics::GENESIS.start_timer();
+
         // Preliminaries
         let executor = Executor::new(self.config);
         let blocktime = BlockTime::new(GENESIS_INITIAL_BLOCKTIME);
@@ -345,6 +375,7 @@ where
         // specification.
         let protocol_data = ProtocolData::partial_without_standard_payment(
             *wasm_config,
+            *ee_config.system_config(),
             mint_hash,
             proof_of_stake_hash,
         );
@@ -411,6 +442,20 @@ where
                 })
                 .collect();
 
+            // Delegated stakes keyed by validator, then by delegator.
+            let mut genesis_delegators: BTreeMap<
+                casper_types::PublicKey,
+                BTreeMap<casper_types::PublicKey, U512>,
+            > = BTreeMap::new();
+            for genesis_delegator in ee_config.delegators() {
+                let delegated_amount = genesis_delegators
+                    .entry(genesis_delegator.validator())
+                    .or_default()
+                    .entry(genesis_delegator.delegator())
+                    .or_default();
+                *delegated_amount += genesis_delegator.delegated_amount().value();
+            }
+
             let auction_installer_bytes = {
                 // NOTE: Before integration node wasn't updated to pass the bytes, so we were
                 // bundling it. This debug_assert can be removed once integration with genesis
@@ -429,6 +474,7 @@ where
             let args = runtime_args! {
                 ARG_MINT_CONTRACT_PACKAGE_HASH => mint_package_hash,
                 ARG_GENESIS_VALIDATORS => bonded_validators,
+                ARG_GENESIS_DELEGATORS => genesis_delegators,
                 ARG_VALIDATOR_SLOTS => validator_slots,
                 ARG_AUCTION_DELAY => auction_delay,
                 ARG_LOCKED_FUNDS_PERIOD => locked_funds_period,
@@ -465,6 +511,7 @@ where
         // Spec #2: Associate given CostTable with given ProtocolVersion.
         let protocol_data = ProtocolData::new(
             *wasm_config,
+            *ee_config.system_config(),
             mint_hash,
             proof_of_stake_hash,
             standard_payment_hash,
@@ -650,9 +697,16 @@ where
             None => current_protocol_data.wasm_config(),
         };
 
+        // resolve system contract costs for new protocol version
+        let new_system_config = match upgrade_config.system_config() {
+            Some(new_system_config) => new_system_config,
+            None => current_protocol_data.system_config(),
+        };
+
         // 3.1.2.2 persist wasm CostTable
         let mut new_protocol_data = ProtocolData::new(
             *new_wasm_config,
+            *new_system_config,
             current_protocol_data.mint(),
             current_protocol_data.proof_of_stake(),
             current_protocol_data.standard_payment(),
@@ -875,21 +929,137 @@ where
             .into())
     }
 
+    /// Runs all queries of the batch against the same checkout of the global state, reading each
+    /// key traversed by any of them from the store only once.
+    pub fn run_query_batch(
+        &self,
+        correlation_id: CorrelationId,
+        batch: QueryRequestBatch,
+    ) -> Result<QueryBatchResult, Error> {
+        let reader = match self
+            .state
+            .checkout(batch.state_hash())
+            .map_err(Into::into)?
+        {
+            Some(reader) => reader,
+            None => return Ok(QueryBatchResult::RootNotFound),
+        };
+        let tracking_copy = TrackingCopy::new(CachingStateReader::new(reader));
+
+        let mut results = Vec::with_capacity(batch.queries().len());
+        for (key, path) in batch.queries() {
+            let result = tracking_copy
+                .query(correlation_id, *key, path)
+                .map_err(|err| Error::Exec(err.into()))?;
+            results.push(result.into());
+        }
+        Ok(QueryBatchResult::Success(results))
+    }
+
+    /// Returns a page of the keys matching the requested prefix, each with a proof of its inclusion
+    /// in the global state.
+    pub fn get_keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        request: KeysWithPrefixRequest,
+    ) -> Result<KeysWithPrefixResult, Error> {
+        let reader = match self
+            .state
+            .checkout(request.state_hash())
+            .map_err(Into::into)?
+        {
+            Some(reader) => reader,
+            None => return Ok(KeysWithPrefixResult::RootNotFound),
+        };
+
+        let keys = reader
+            .keys_with_prefix(
+                correlation_id,
+                request.prefix(),
+                request.offset(),
+                request.count(),
+            )
+            .map_err(Into::into)?;
+
+        let mut proofs = Vec::with_capacity(keys.len());
+        for key in keys {
+            match reader
+                .read_with_proof(correlation_id, &key)
+                .map_err(Into::into)?
+            {
+                Some(proof) => proofs.push(proof),
+                None => return Err(Error::Exec(execution::Error::KeyNotFound(key))),
+            }
+        }
+
+        Ok(KeysWithPrefixResult::Success { proofs })
+    }
+
     pub fn run_execute(
         &self,
         correlation_id: CorrelationId,
-        mut exec_request: ExecuteRequest,
+        exec_request: ExecuteRequest,
     ) -> Result<ExecutionResults, RootNotFound> {
+        Ok(self
+            .execute_deploys(correlation_id, exec_request, false, false)?
+            .into_iter()
+            .map(|(result, _trace, _gas_profile)| result)
+            .collect())
+    }
+
+    /// Executes the deploys like `run_execute`, additionally recording the host function calls
+    /// made by each of them.
+    ///
+    /// Tracing slows down execution, so this is intended for speculative execution of deploys, not
+    /// for executing blocks.
+    pub fn run_execute_with_trace(
+        &self,
+        correlation_id: CorrelationId,
+        exec_request: ExecuteRequest,
+    ) -> Result<Vec<(ExecutionResult, ExecutionTrace)>, RootNotFound> {
+        Ok(self
+            .execute_deploys(correlation_id, exec_request, true, false)?
+            .into_iter()
+            .map(|(result, trace, _gas_profile)| (result, trace))
+            .collect())
+    }
+
+    /// Executes the deploys like `run_execute`, additionally breaking down the gas spent by each
+    /// of them by host function and opcode category.
+    ///
+    /// Like tracing, profiling slows down execution, so this is intended for speculative execution
+    /// of deploys.
+    pub fn run_execute_with_gas_profile(
+        &self,
+        correlation_id: CorrelationId,
+        exec_request: ExecuteRequest,
+    ) -> Result<Vec<(ExecutionResult, GasProfile)>, RootNotFound> {
+        Ok(self
+            .execute_deploys(correlation_id, exec_request, false, true)?
+            .into_iter()
+            .map(|(result, _trace, gas_profile)| (result, gas_profile))
+            .collect())
+    }
+
+    fn execute_deploys(
+        &self,
+        correlation_id: CorrelationId,
+        mut exec_request: ExecuteRequest,
+        trace: bool,
+        profile_gas: bool,
+    ) -> Result<Vec<(ExecutionResult, ExecutionTrace, GasProfile)>, RootNotFound> {
         // TODO: do not unwrap
         let wasm_config = self
             .wasm_config(exec_request.protocol_version)
             .unwrap()
             .unwrap();
-        let executor = Executor::new(self.config);
+        let executor = Executor::new(self.config)
+            .with_trace(trace)
+            .with_gas_profile(profile_gas);
         let preprocessor = Preprocessor::new(wasm_config);
 
         let deploys = exec_request.take_deploys();
-        let mut results = ExecutionResults::with_capacity(deploys.len());
+        let mut results = Vec::with_capacity(deploys.len());
 
         for deploy_item in deploys {
             let result = match deploy_item {
@@ -917,7 +1087,9 @@ where
                 },
             };
             match result {
-                Ok(result) => results.push_back(result),
+                Ok(result) => {
+                    results.push((result, executor.take_trace(), executor.take_gas_profile()))
+                }
                 Err(error) => {
                     return Err(error);
                 }
@@ -1994,6 +2166,9 @@ where
     }
 
     /// Obtains validator weights for given era.
+    ///
+    /// The weights are computed directly from the seigniorage recipients snapshot stored by the
+    /// auction contract under the requested state root, without executing the contract.
     pub fn get_era_validators(
         &self,
         correlation_id: CorrelationId,
@@ -2001,8 +2176,8 @@ where
     ) -> Result<EraValidators, GetEraValidatorsError> {
         let protocol_version = get_era_validators_request.protocol_version();
 
-        let tracking_copy = match self.tracking_copy(get_era_validators_request.state_hash())? {
-            Some(tracking_copy) => Rc::new(RefCell::new(tracking_copy)),
+        let mut tracking_copy = match self.tracking_copy(get_era_validators_request.state_hash())? {
+            Some(tracking_copy) => tracking_copy,
             None => return Err(GetEraValidatorsError::RootNotFound),
         };
 
@@ -2011,81 +2186,29 @@ where
             None => return Err(Error::InvalidProtocolVersion(protocol_version).into()),
         };
 
-        let wasm_config = protocol_data.wasm_config();
-
-        let preprocessor = Preprocessor::new(*wasm_config);
-
         let auction_contract: Contract = tracking_copy
-            .borrow_mut()
             .get_contract(correlation_id, protocol_data.auction())
             .map_err(Error::from)?;
 
-        let auction_module = {
-            let contract_wasm_hash = auction_contract.contract_wasm_hash();
-            let use_system_contracts = self.config.use_system_contracts();
-            tracking_copy
-                .borrow_mut()
-                .get_system_module(
-                    correlation_id,
-                    contract_wasm_hash,
-                    use_system_contracts,
-                    &preprocessor,
-                )
-                .map_err(Error::from)?
+        let snapshot_key = match auction_contract
+            .named_keys()
+            .get(SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY)
+        {
+            Some(key) => *key,
+            None => return Err(GetEraValidatorsError::EraValidatorsMissing),
         };
 
-        let executor = Executor::new(self.config);
-
-        let mut named_keys = auction_contract.named_keys().to_owned();
-        let base_key = Key::from(protocol_data.auction());
-        let gas_limit = Gas::new(U512::from(std::u64::MAX));
-        let virtual_system_account = {
-            let named_keys = NamedKeys::new();
-            let purse = URef::new(Default::default(), AccessRights::READ_ADD_WRITE);
-            Account::create(SYSTEM_ACCOUNT_ADDR, named_keys, purse)
-        };
-        let authorization_keys = BTreeSet::from_iter(vec![SYSTEM_ACCOUNT_ADDR]);
-        let blocktime = BlockTime::default();
-        let deploy_hash = {
-            // seeds address generator w/ protocol version
-            let bytes: Vec<u8> = get_era_validators_request
-                .protocol_version()
-                .value()
-                .into_bytes()
-                .map_err(Error::from)?
-                .to_vec();
-            Blake2bHash::new(&bytes).value()
+        let snapshot: SeigniorageRecipientsSnapshot = match tracking_copy
+            .read(correlation_id, &snapshot_key)
+            .map_err(|error| Error::Exec(error.into()))?
+        {
+            Some(StoredValue::CLValue(cl_value)) => cl_value
+                .into_t()
+                .map_err(|error| Error::Exec(error.into()))?,
+            _ => return Err(GetEraValidatorsError::EraValidatorsMissing),
         };
 
-        let (era_validators, execution_result): (Option<EraValidators>, ExecutionResult) = executor
-            .exec_system_contract(
-                DirectSystemContractCall::GetEraValidators,
-                auction_module,
-                runtime_args! {},
-                &mut named_keys,
-                Default::default(),
-                base_key,
-                &virtual_system_account,
-                authorization_keys,
-                blocktime,
-                deploy_hash,
-                gas_limit,
-                protocol_version,
-                correlation_id,
-                Rc::clone(&tracking_copy),
-                Phase::Session,
-                protocol_data,
-                SystemContractCache::clone(&self.system_contract_cache),
-            );
-
-        if let Some(error) = execution_result.take_error() {
-            return Err(error.into());
-        }
-
-        match era_validators {
-            None => Err(GetEraValidatorsError::EraValidatorsMissing),
-            Some(era_validators) => Ok(era_validators),
-        }
+        Ok(auction::era_validators_from_snapshot(snapshot))
     }
 
     pub fn commit_step(
@@ -3,13 +3,20 @@ use casper_types::Key;
 use crate::{
     core::tracking_copy::TrackingCopyQueryResult,
     shared::{newtypes::Blake2bHash, stored_value::StoredValue},
-    storage::trie::merkle_proof::TrieMerkleProof,
+    storage::trie::merkle_proof::{TrieMerkleProof, TrieMerkleProofOfAbsence},
 };
 
 #[derive(Debug)]
 pub enum QueryResult {
     RootNotFound,
     ValueNotFound(String),
+    /// The query reached a key which is absent from the global state.
+    Absent {
+        /// The proofs of the values traversed before reaching the absent key.
+        proofs: Vec<TrieMerkleProof<Key, StoredValue>>,
+        /// The proof of the absence of the key reached.
+        proof_of_absence: Box<TrieMerkleProofOfAbsence<Key, StoredValue>>,
+    },
     CircularReference(String),
     Success {
         value: Box<StoredValue>,
@@ -46,10 +53,99 @@ impl QueryRequest {
     }
 }
 
+/// A request for several queries against the same state root, run in a single session sharing the
+/// reads of the global state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryRequestBatch {
+    state_hash: Blake2bHash,
+    queries: Vec<(Key, Vec<String>)>,
+}
+
+impl QueryRequestBatch {
+    /// Creates a batch of queries, each given as a base key and the path components starting from
+    /// it.
+    pub fn new(state_hash: Blake2bHash, queries: Vec<(Key, Vec<String>)>) -> Self {
+        QueryRequestBatch {
+            state_hash,
+            queries,
+        }
+    }
+
+    pub fn state_hash(&self) -> Blake2bHash {
+        self.state_hash
+    }
+
+    pub fn queries(&self) -> &[(Key, Vec<String>)] {
+        &self.queries
+    }
+}
+
+#[derive(Debug)]
+pub enum QueryBatchResult {
+    RootNotFound,
+    /// The result of each query, in the order of the queries in the batch.  None of them is
+    /// `QueryResult::RootNotFound`.
+    Success(Vec<QueryResult>),
+}
+
+/// A request for a page of the keys whose serialized representation starts with a given prefix,
+/// e.g. `[3]` for all `Key::Transfer`s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeysWithPrefixRequest {
+    state_hash: Blake2bHash,
+    prefix: Vec<u8>,
+    offset: usize,
+    count: usize,
+}
+
+impl KeysWithPrefixRequest {
+    pub fn new(state_hash: Blake2bHash, prefix: Vec<u8>, offset: usize, count: usize) -> Self {
+        KeysWithPrefixRequest {
+            state_hash,
+            prefix,
+            offset,
+            count,
+        }
+    }
+
+    pub fn state_hash(&self) -> Blake2bHash {
+        self.state_hash
+    }
+
+    pub fn prefix(&self) -> &[u8] {
+        &self.prefix
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+#[derive(Debug)]
+pub enum KeysWithPrefixResult {
+    RootNotFound,
+    /// The proofs of inclusion of the matching keys, each holding the key and its value, in the
+    /// lexicographic order of the serialized keys.
+    Success {
+        proofs: Vec<TrieMerkleProof<Key, StoredValue>>,
+    },
+}
+
 impl From<TrackingCopyQueryResult> for QueryResult {
     fn from(tracking_copy_query_result: TrackingCopyQueryResult) -> Self {
         match tracking_copy_query_result {
             TrackingCopyQueryResult::ValueNotFound(message) => QueryResult::ValueNotFound(message),
+            TrackingCopyQueryResult::Absent {
+                proofs,
+                proof_of_absence,
+            } => QueryResult::Absent {
+                proofs,
+                proof_of_absence: Box::new(proof_of_absence),
+            },
             TrackingCopyQueryResult::CircularReference(message) => {
                 QueryResult::CircularReference(message)
             }
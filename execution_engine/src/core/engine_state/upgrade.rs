@@ -6,7 +6,9 @@ use casper_types::{auction::EraId, bytesrepr, Key, ProtocolVersion};
 
 use crate::{
     core::engine_state::execution_effect::ExecutionEffect,
-    shared::{newtypes::Blake2bHash, wasm_config::WasmConfig, TypeMismatch},
+    shared::{
+        newtypes::Blake2bHash, system_config::SystemConfig, wasm_config::WasmConfig, TypeMismatch,
+    },
     storage::global_state::CommitResult,
 };
 
@@ -64,6 +66,7 @@ pub struct UpgradeConfig {
     upgrade_installer_args: Option<Vec<u8>>,
     upgrade_installer_bytes: Option<Vec<u8>>,
     wasm_config: Option<WasmConfig>,
+    system_config: Option<SystemConfig>,
     activation_point: Option<ActivationPoint>,
     new_validator_slots: Option<u32>,
     new_auction_delay: Option<u64>,
@@ -80,6 +83,7 @@ impl UpgradeConfig {
         upgrade_installer_args: Option<Vec<u8>>,
         upgrade_installer_bytes: Option<Vec<u8>>,
         wasm_config: Option<WasmConfig>,
+        system_config: Option<SystemConfig>,
         activation_point: Option<ActivationPoint>,
         new_validator_slots: Option<u32>,
         new_auction_delay: Option<u64>,
@@ -93,6 +97,7 @@ impl UpgradeConfig {
             upgrade_installer_args,
             upgrade_installer_bytes,
             wasm_config,
+            system_config,
             activation_point,
             new_validator_slots,
             new_auction_delay,
@@ -127,6 +132,10 @@ impl UpgradeConfig {
         self.wasm_config.as_ref()
     }
 
+    pub fn system_config(&self) -> Option<&SystemConfig> {
+        self.system_config.as_ref()
+    }
+
     pub fn activation_point(&self) -> Option<u64> {
         self.activation_point
     }
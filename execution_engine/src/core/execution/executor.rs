@@ -17,8 +17,12 @@ use casper_types::{
 use crate::{
     core::{
         engine_state::{
-            execution_effect::ExecutionEffect, execution_result::ExecutionResult,
-            system_contract_cache::SystemContractCache, EngineConfig,
+            execution_effect::ExecutionEffect,
+            execution_result::ExecutionResult,
+            execution_trace::{ExecutionTrace, Tracer},
+            gas_profile::{GasProfile, GasProfiler},
+            system_contract_cache::SystemContractCache,
+            EngineConfig,
         },
         execution::{address_generator::AddressGenerator, Error},
         runtime::{
@@ -78,18 +82,60 @@ macro_rules! on_fail_charge {
 
 pub struct Executor {
     config: EngineConfig,
+    tracer: Option<Tracer>,
+    gas_profiler: Option<GasProfiler>,
 }
 
 #[allow(clippy::too_many_arguments)]
 impl Executor {
     pub fn new(config: EngineConfig) -> Self {
-        Executor { config }
+        Executor {
+            config,
+            tracer: None,
+            gas_profiler: None,
+        }
+    }
+
+    /// Enables or disables recording of the host function calls made by the Wasm code executed by
+    /// this executor.
+    pub fn with_trace(mut self, enabled: bool) -> Self {
+        self.tracer = if enabled {
+            Some(Tracer::default())
+        } else {
+            None
+        };
+        self
+    }
+
+    /// Enables or disables profiling of the gas spent by the Wasm code executed by this executor.
+    pub fn with_gas_profile(mut self, enabled: bool) -> Self {
+        self.gas_profiler = if enabled {
+            Some(GasProfiler::default())
+        } else {
+            None
+        };
+        self
     }
 
     pub fn config(&self) -> EngineConfig {
         self.config
     }
 
+    /// Returns the host function calls recorded since the last call, or an empty trace if tracing
+    /// is disabled.
+    pub fn take_trace(&self) -> ExecutionTrace {
+        self.tracer.as_ref().map(Tracer::take).unwrap_or_default()
+    }
+
+    /// Returns the gas profile built since the last call, or an empty profile if profiling is
+    /// disabled.
+    pub fn take_gas_profile(&self) -> GasProfile {
+        self.gas_profiler
+            .as_ref()
+            .map(GasProfiler::take)
+            .unwrap_or_default()
+    }
+
     pub fn exec<R>(
         &self,
         module: Module,
@@ -168,7 +214,9 @@ impl Executor {
             transfers,
         );
 
-        let mut runtime = Runtime::new(self.config, system_contract_cache, memory, module, context);
+        let mut runtime = Runtime::new(self.config, system_contract_cache, memory, module, context)
+            .with_tracer(self.tracer.clone())
+            .with_gas_profiler(self.gas_profiler.as_ref());
 
         let accounts_access_rights = {
             let keys: Vec<Key> = account.named_keys().values().cloned().collect();
@@ -624,7 +672,9 @@ impl Executor {
             memory,
             module,
             runtime_context,
-        );
+        )
+        .with_tracer(self.tracer.clone())
+        .with_gas_profiler(self.gas_profiler.as_ref());
 
         Ok((instance, runtime))
     }
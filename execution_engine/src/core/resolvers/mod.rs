@@ -3,13 +3,21 @@ pub mod memory_resolver;
 pub mod v1_function_index;
 mod v1_resolver;
 
+use std::convert::TryFrom;
+
+use parity_wasm::elements::{External, Module};
 use wasmi::ModuleImportResolver;
 
 use casper_types::ProtocolVersion;
 
-use self::error::ResolverError;
+use self::{error::ResolverError, v1_function_index::FunctionIndex};
 use crate::core::resolvers::memory_resolver::MemoryResolver;
 
+/// The name of the module from which Wasm modules import the host functions and memory.
+const HOST_MODULE_NAME: &str = "env";
+/// The name under which Wasm modules import the memory.
+const MEMORY_FIELD_NAME: &str = "memory";
+
 /// Creates a module resolver for given protocol version.
 ///
 /// * `protocol_version` Version of the protocol. Can't be lower than 1.
@@ -23,6 +31,36 @@ pub fn create_module_resolver(
     Err(ResolverError::UnknownProtocolVersion(protocol_version))
 }
 
+/// Returns the module and field names of the first import of `module` which can't be resolved when
+/// it is executed, or `None` if all can be.
+///
+/// Only the host functions and the memory can be imported.
+pub fn find_unresolvable_import(module: &Module) -> Option<(&str, &str)> {
+    let import_section = module.import_section()?;
+    import_section
+        .entries()
+        .iter()
+        .find(|import_entry| {
+            let field = import_entry.field();
+            let resolvable = import_entry.module() == HOST_MODULE_NAME
+                && match import_entry.external() {
+                    External::Function(_) => is_host_function(field),
+                    External::Memory(_) => field == MEMORY_FIELD_NAME,
+                    External::Global(_) | External::Table(_) => false,
+                };
+            !resolvable
+        })
+        .map(|import_entry| (import_entry.module(), import_entry.field()))
+}
+
+fn is_host_function(name: &str) -> bool {
+    (0..)
+        .map(FunctionIndex::try_from)
+        .take_while(Result::is_ok)
+        .flatten()
+        .any(|function_index| function_index.name() == name)
+}
+
 #[test]
 fn resolve_invalid_module() {
     assert!(create_module_resolver(ProtocolVersion::default()).is_err());
@@ -32,3 +70,51 @@ fn resolve_invalid_module() {
 fn protocol_version_1_always_resolves() {
     assert!(create_module_resolver(ProtocolVersion::V1_0_0).is_ok());
 }
+
+#[test]
+fn should_find_unresolvable_imports() {
+    use parity_wasm::{builder, elements::ValueType};
+
+    let import_function = |module: &str, field: &str| {
+        builder::module()
+            .function()
+            .signature()
+            .with_params(vec![ValueType::I32])
+            .build()
+            .body()
+            .build()
+            .build()
+            .import()
+            .module(module)
+            .field(field)
+            .external()
+            .func(0)
+            .build()
+            .build()
+    };
+
+    let host_function = import_function(HOST_MODULE_NAME, "casper_revert");
+    assert_eq!(find_unresolvable_import(&host_function), None);
+
+    let unknown_function = import_function(HOST_MODULE_NAME, "casper_unknown");
+    assert_eq!(
+        find_unresolvable_import(&unknown_function),
+        Some((HOST_MODULE_NAME, "casper_unknown"))
+    );
+
+    let other_module = import_function("wasi", "casper_revert");
+    assert_eq!(
+        find_unresolvable_import(&other_module),
+        Some(("wasi", "casper_revert"))
+    );
+
+    let memory = builder::module()
+        .import()
+        .module(HOST_MODULE_NAME)
+        .field(MEMORY_FIELD_NAME)
+        .external()
+        .memory(1, None)
+        .build()
+        .build();
+    assert_eq!(find_unresolvable_import(&memory), None);
+}
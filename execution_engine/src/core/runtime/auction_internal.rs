@@ -1,10 +1,14 @@
+use std::convert::TryFrom;
+
+use signature::Verifier as _;
+
 use casper_types::{
     account,
     account::AccountHash,
     auction::{Auction, MintProvider, RuntimeProvider, StorageProvider, SystemProvider},
     bytesrepr::{FromBytes, ToBytes},
     system_contract_errors::auction::Error,
-    ApiError, CLTyped, CLValue, Key, TransferredTo, URef, BLAKE2B_DIGEST_LENGTH, U512,
+    ApiError, CLTyped, CLValue, Key, PublicKey, TransferredTo, URef, BLAKE2B_DIGEST_LENGTH, U512,
 };
 
 use super::Runtime;
@@ -84,6 +88,33 @@ where
     fn blake2b<T: AsRef<[u8]>>(&self, data: T) -> [u8; BLAKE2B_DIGEST_LENGTH] {
         account::blake2b(data)
     }
+
+    fn verify_signature(&self, message: &[u8], signature: &[u8], public_key: PublicKey) -> bool {
+        match public_key {
+            PublicKey::Ed25519(public_key_bytes) => {
+                let public_key = match ed25519_dalek::PublicKey::from_bytes(&public_key_bytes) {
+                    Ok(public_key) => public_key,
+                    Err(_) => return false,
+                };
+                match ed25519_dalek::Signature::try_from(signature) {
+                    Ok(signature) => public_key.verify_strict(message, &signature).is_ok(),
+                    Err(_) => false,
+                }
+            }
+            PublicKey::Secp256k1(public_key_bytes) => {
+                let verifier = match k256::PublicKey::from_bytes(public_key_bytes.as_ref())
+                    .and_then(|public_key| k256::ecdsa::Verifier::new(&public_key).ok())
+                {
+                    Some(verifier) => verifier,
+                    None => return false,
+                };
+                match k256::ecdsa::Signature::try_from(signature) {
+                    Ok(signature) => verifier.verify(message, &signature).is_ok(),
+                    Err(_) => false,
+                }
+            }
+        }
+    }
 }
 
 impl<'a, R> MintProvider for Runtime<'a, R>
@@ -29,6 +29,66 @@ where
         args: RuntimeArgs,
     ) -> Result<Option<RuntimeValue>, Trap> {
         let func = FunctionIndex::try_from(index).expect("unknown function index");
+
+        // Metering calls are made for every basic block and only clutter the trace; the gas they
+        // charge is reflected in the gas counter of the next traced call.  They are profiled by
+        // opcode category rather than as a host function.
+        if func == FunctionIndex::GasFuncIndex
+            || (self.tracer.is_none() && self.gas_profiler.is_none())
+        {
+            return self.call_host_function(func, args);
+        }
+
+        let gas_before = self.context.gas_counter();
+        let maybe_traced_call = self.tracer.clone().map(|tracer| {
+            let arg_values = args.as_ref().iter().map(arg_value).collect();
+            let call_index =
+                tracer.start(func.name(), arg_values, self.context.phase(), gas_before);
+            (tracer, call_index)
+        });
+        let maybe_profiled_call = self
+            .gas_profiler
+            .clone()
+            .map(|profiler| (profiler.host_function_started(), profiler));
+
+        let result = self.call_host_function(func, args);
+
+        let gas_after = self.context.gas_counter();
+        if let Some((tracer, call_index)) = maybe_traced_call {
+            let maybe_error = result
+                .as_ref()
+                .err()
+                .map(|error| format!("{:?}", error.kind()));
+            tracer.finish(call_index, gas_after, maybe_error);
+        }
+        if let Some((attributed_before, profiler)) = maybe_profiled_call {
+            let gas_spent = Gas::new(gas_after.value().saturating_sub(gas_before.value()));
+            profiler.host_function_finished(func.name(), attributed_before, gas_spent);
+        }
+        result
+    }
+}
+
+/// Returns the raw value of a host function argument.
+fn arg_value(value: &RuntimeValue) -> i64 {
+    match value {
+        RuntimeValue::I32(value) => i64::from(*value),
+        RuntimeValue::I64(value) => *value,
+        RuntimeValue::F32(value) => i64::from(value.to_bits()),
+        RuntimeValue::F64(value) => value.to_bits() as i64,
+    }
+}
+
+impl<'a, R> Runtime<'a, R>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<Error>,
+{
+    fn call_host_function(
+        &mut self,
+        func: FunctionIndex,
+        args: RuntimeArgs,
+    ) -> Result<Option<RuntimeValue>, Trap> {
         let mut scoped_instrumenter = ScopedInstrumenter::new(func);
 
         let host_function_costs = self
@@ -233,6 +293,9 @@ where
                 // Gas is special cased internal host function and for accounting purposes it isn't
                 // represented in protocol data.
                 self.gas(Gas::new(gas_arg.into()))?;
+                if let Some(gas_profiler) = &self.gas_profiler {
+                    gas_profiler.opcodes_charged(gas_arg);
+                }
                 Ok(None)
             }
 
@@ -15,6 +15,7 @@ use std::{
 
 use itertools::Itertools;
 use parity_wasm::elements::Module;
+use tracing::debug_span;
 use wasmi::{ImportsBuilder, MemoryRef, ModuleInstance, ModuleRef, Trap, TrapKind};
 
 use casper_types::{
@@ -37,7 +38,10 @@ use casper_types::{
 
 use crate::{
     core::{
-        engine_state::{system_contract_cache::SystemContractCache, EngineConfig},
+        engine_state::{
+            execution_trace::Tracer, gas_profile::GasProfiler,
+            system_contract_cache::SystemContractCache, EngineConfig,
+        },
         execution::Error,
         resolvers::{create_module_resolver, memory_resolver::MemoryResolver},
         runtime_context::{self, RuntimeContext},
@@ -47,6 +51,7 @@ use crate::{
         account::Account,
         gas::Gas,
         host_function_costs::{Cost, HostFunction},
+        metrics,
         stored_value::StoredValue,
     },
     storage::{global_state::StateReader, protocol_data::ProtocolData},
@@ -60,6 +65,8 @@ pub struct Runtime<'a, R> {
     module: Module,
     host_buffer: Option<CLValue>,
     context: RuntimeContext<'a, R>,
+    tracer: Option<Tracer>,
+    gas_profiler: Option<GasProfiler>,
 }
 
 /// Rename function called `name` in the `module` to `call`.
@@ -82,6 +89,10 @@ pub fn instance_and_memory(
     parity_module: Module,
     protocol_version: ProtocolVersion,
 ) -> Result<(ModuleRef, MemoryRef), Error> {
+    let span = debug_span!("wasm_instantiation");
+    let _entered = span.enter();
+    let _timer = metrics::WASM_INSTANTIATION.start_timer();
+
     let module = wasmi::Module::from_parity_wasm_module(parity_module)?;
     let resolver = create_module_resolver(protocol_version)?;
     let mut imports = ImportsBuilder::new();
@@ -1383,9 +1394,27 @@ where
             module,
             host_buffer: None,
             context,
+            tracer: None,
+            gas_profiler: None,
         }
     }
 
+    /// Records the host function calls made by this runtime and any contracts it calls with the
+    /// given tracer.
+    pub(crate) fn with_tracer(mut self, tracer: Option<Tracer>) -> Self {
+        self.tracer = tracer;
+        self
+    }
+
+    /// Attributes the gas spent by this runtime and any contracts it calls to the profile built by
+    /// the given profiler.
+    pub(crate) fn with_gas_profiler(mut self, gas_profiler: Option<&GasProfiler>) -> Self {
+        let opcode_costs = self.protocol_data().wasm_config().opcode_costs();
+        self.gas_profiler =
+            gas_profiler.map(|gas_profiler| gas_profiler.for_module(&self.module, &opcode_costs));
+        self
+    }
+
     pub fn memory(&self) -> &MemoryRef {
         &self.memory
     }
@@ -1727,9 +1756,12 @@ where
             mint_context,
         );
 
+        let mint_costs = *self.protocol_data().system_config().mint_costs();
+
         let ret: CLValue = match entry_point_name {
             // Type: `fn mint(amount: U512) -> Result<URef, Error>`
             mint::METHOD_MINT => {
+                self.charge_system_contract_call(mint_costs.mint)?;
                 let amount: U512 = Self::get_named_argument(&runtime_args, mint::ARG_AMOUNT)?;
                 let result: Result<URef, system_contract_errors::mint::Error> =
                     mint_runtime.mint(amount);
@@ -1737,11 +1769,13 @@ where
             }
             // Type: `fn create() -> URef`
             mint::METHOD_CREATE => {
+                self.charge_system_contract_call(mint_costs.create)?;
                 let uref = mint_runtime.mint(U512::zero()).map_err(Self::reverter)?;
                 CLValue::from_t(uref).map_err(Self::reverter)?
             }
             // Type: `fn balance(purse: URef) -> Option<U512>`
             mint::METHOD_BALANCE => {
+                self.charge_system_contract_call(mint_costs.balance)?;
                 let uref: URef = Self::get_named_argument(&runtime_args, mint::ARG_PURSE)?;
                 let maybe_balance: Option<U512> =
                     mint_runtime.balance(uref).map_err(Self::reverter)?;
@@ -1749,6 +1783,7 @@ where
             }
             // Type: `fn transfer(source: URef, target: URef, amount: U512) -> Result<(), Error>`
             mint::METHOD_TRANSFER => {
+                self.charge_system_contract_call(mint_costs.transfer)?;
                 let source: URef = Self::get_named_argument(&runtime_args, mint::ARG_SOURCE)?;
                 let target: URef = Self::get_named_argument(&runtime_args, mint::ARG_TARGET)?;
                 let amount: U512 = Self::get_named_argument(&runtime_args, mint::ARG_AMOUNT)?;
@@ -1758,6 +1793,7 @@ where
             }
             // Type: `fn read_base_round_reward() -> Result<U512, Error>`
             mint::METHOD_READ_BASE_ROUND_REWARD => {
+                self.charge_system_contract_call(mint_costs.read_base_round_reward)?;
                 let result: U512 = mint_runtime
                     .read_base_round_reward()
                     .map_err(Self::reverter)?;
@@ -1836,23 +1872,29 @@ where
             runtime_context,
         );
 
+        let proof_of_stake_costs = *self.protocol_data().system_config().proof_of_stake_costs();
+
         let ret: CLValue = match entry_point_name {
             proof_of_stake::METHOD_GET_PAYMENT_PURSE => {
+                self.charge_system_contract_call(proof_of_stake_costs.get_payment_purse)?;
                 let rights_controlled_purse =
                     runtime.get_payment_purse().map_err(Self::reverter)?;
                 CLValue::from_t(rights_controlled_purse).map_err(Self::reverter)?
             }
             proof_of_stake::METHOD_SET_REFUND_PURSE => {
+                self.charge_system_contract_call(proof_of_stake_costs.set_refund_purse)?;
                 let purse: URef =
                     Self::get_named_argument(&runtime_args, proof_of_stake::ARG_PURSE)?;
                 runtime.set_refund_purse(purse).map_err(Self::reverter)?;
                 CLValue::from_t(()).map_err(Self::reverter)?
             }
             proof_of_stake::METHOD_GET_REFUND_PURSE => {
+                self.charge_system_contract_call(proof_of_stake_costs.get_refund_purse)?;
                 let maybe_purse = runtime.get_refund_purse().map_err(Self::reverter)?;
                 CLValue::from_t(maybe_purse).map_err(Self::reverter)?
             }
             proof_of_stake::METHOD_FINALIZE_PAYMENT => {
+                self.charge_system_contract_call(proof_of_stake_costs.finalize_payment)?;
                 let amount_spent: U512 =
                     Self::get_named_argument(&runtime_args, proof_of_stake::ARG_AMOUNT)?;
                 let account: AccountHash =
@@ -1877,8 +1919,26 @@ where
     }
 
     pub fn call_host_standard_payment(&mut self) -> Result<(), Error> {
+        let standard_payment_costs = *self
+            .protocol_data()
+            .system_config()
+            .standard_payment_costs();
+        self.charge_system_contract_call(standard_payment_costs.pay)?;
+
         let amount: U512 =
             Self::get_named_argument(&self.context.args(), standard_payment::ARG_AMOUNT)?;
+        if self
+            .context
+            .args()
+            .get(standard_payment::ARG_PURSE_NAME)
+            .is_some()
+        {
+            let purse_name: String =
+                Self::get_named_argument(&self.context.args(), standard_payment::ARG_PURSE_NAME)?;
+            return self
+                .pay_from_named_purse(&purse_name, amount)
+                .map_err(Self::reverter);
+        }
         self.pay(amount).map_err(Self::reverter)
     }
 
@@ -1943,14 +2003,18 @@ where
             runtime_context,
         );
 
+        let auction_costs = *self.protocol_data().system_config().auction_costs();
+
         let ret: CLValue = match entry_point_name {
             auction::METHOD_GET_ERA_VALIDATORS => {
+                self.charge_system_contract_call(auction_costs.get_era_validators)?;
                 let result = runtime.get_era_validators().map_err(Self::reverter)?;
 
                 CLValue::from_t(result).map_err(Self::reverter)?
             }
 
             auction::METHOD_READ_SEIGNIORAGE_RECIPIENTS => {
+                self.charge_system_contract_call(auction_costs.read_seigniorage_recipients)?;
                 let result = runtime
                     .read_seigniorage_recipients()
                     .map_err(Self::reverter)?;
@@ -1959,6 +2023,7 @@ where
             }
 
             auction::METHOD_ADD_BID => {
+                self.charge_system_contract_call(auction_costs.add_bid)?;
                 let account_hash =
                     Self::get_named_argument(&runtime_args, auction::ARG_PUBLIC_KEY)?;
                 let source_purse =
@@ -1975,6 +2040,7 @@ where
             }
 
             auction::METHOD_WITHDRAW_BID => {
+                self.charge_system_contract_call(auction_costs.withdraw_bid)?;
                 let account_hash =
                     Self::get_named_argument(&runtime_args, auction::ARG_PUBLIC_KEY)?;
                 let amount = Self::get_named_argument(&runtime_args, auction::ARG_AMOUNT)?;
@@ -1987,7 +2053,21 @@ where
                 CLValue::from_t(result).map_err(Self::reverter)?
             }
 
+            auction::METHOD_ROTATE_SIGNING_KEY => {
+                self.charge_system_contract_call(auction_costs.rotate_signing_key)?;
+                let public_key = Self::get_named_argument(&runtime_args, auction::ARG_PUBLIC_KEY)?;
+                let new_signing_key =
+                    Self::get_named_argument(&runtime_args, auction::ARG_NEW_SIGNING_KEY)?;
+                let signature = Self::get_named_argument(&runtime_args, auction::ARG_SIGNATURE)?;
+
+                runtime
+                    .rotate_signing_key(public_key, new_signing_key, signature)
+                    .map_err(Self::reverter)?;
+                CLValue::from_t(()).map_err(Self::reverter)?
+            }
+
             auction::METHOD_DELEGATE => {
+                self.charge_system_contract_call(auction_costs.delegate)?;
                 let delegator = Self::get_named_argument(&runtime_args, auction::ARG_DELEGATOR)?;
                 let source_purse =
                     Self::get_named_argument(&runtime_args, auction::ARG_SOURCE_PURSE)?;
@@ -2002,6 +2082,7 @@ where
             }
 
             auction::METHOD_UNDELEGATE => {
+                self.charge_system_contract_call(auction_costs.undelegate)?;
                 let delegator = Self::get_named_argument(&runtime_args, auction::ARG_DELEGATOR)?;
                 let validator = Self::get_named_argument(&runtime_args, auction::ARG_VALIDATOR)?;
                 let amount = Self::get_named_argument(&runtime_args, auction::ARG_AMOUNT)?;
@@ -2016,12 +2097,14 @@ where
             }
 
             auction::METHOD_RUN_AUCTION => {
+                self.charge_system_contract_call(auction_costs.run_auction)?;
                 runtime.run_auction().map_err(Self::reverter)?;
                 CLValue::from_t(()).map_err(Self::reverter)?
             }
 
             // Type: `fn slash(validator_account_hashes: &[AccountHash]) -> Result<(), Error>`
             auction::METHOD_SLASH => {
+                self.charge_system_contract_call(auction_costs.slash)?;
                 let validator_public_keys =
                     Self::get_named_argument(&runtime_args, auction::ARG_VALIDATOR_PUBLIC_KEYS)?;
                 runtime
@@ -2031,6 +2114,7 @@ where
             }
             // Type: `fn distribute(reward_factors: BTreeMap<PublicKey, u64>) -> Result<(), Error>`
             auction::METHOD_DISTRIBUTE => {
+                self.charge_system_contract_call(auction_costs.distribute)?;
                 let reward_factors: BTreeMap<PublicKey, u64> =
                     Self::get_named_argument(&runtime_args, auction::ARG_REWARD_FACTORS)?;
                 runtime.distribute(reward_factors).map_err(Self::reverter)?;
@@ -2039,6 +2123,7 @@ where
             // Type: `fn withdraw_delegator_reward(validator_public_key: PublicKey,
             // delegator_public_key: PublicKey, target_purse: URef) -> Result<(), Error>`
             auction::METHOD_WITHDRAW_DELEGATOR_REWARD => {
+                self.charge_system_contract_call(auction_costs.withdraw_delegator_reward)?;
                 let validator_public_key: PublicKey =
                     Self::get_named_argument(&runtime_args, auction::ARG_VALIDATOR_PUBLIC_KEY)?;
                 let delegator_public_key: PublicKey =
@@ -2057,6 +2142,7 @@ where
             // Type: `fn withdraw_delegator_reward(validator_public_key: PublicKey, target_purse:
             // URef) -> Result<(), Error>`
             auction::METHOD_WITHDRAW_VALIDATOR_REWARD => {
+                self.charge_system_contract_call(auction_costs.withdraw_validator_reward)?;
                 let validator_public_key: PublicKey =
                     Self::get_named_argument(&runtime_args, auction::ARG_VALIDATOR_PUBLIC_KEY)?;
                 let target_purse: URef =
@@ -2068,6 +2154,7 @@ where
             }
             // Type: `fn read_era_id() -> Result<EraId, Error>`
             auction::METHOD_READ_ERA_ID => {
+                self.charge_system_contract_call(auction_costs.read_era_id)?;
                 let result = runtime.read_era_id().map_err(Self::reverter)?;
                 CLValue::from_t(result).map_err(Self::reverter)?
             }
@@ -2342,6 +2429,14 @@ where
 
         let host_buffer = None;
 
+        let tracer = self.tracer.as_ref().map(Tracer::nested);
+
+        let opcode_costs = self.protocol_data().wasm_config().opcode_costs();
+        let gas_profiler = self
+            .gas_profiler
+            .as_ref()
+            .map(|gas_profiler| gas_profiler.for_module(&module, &opcode_costs));
+
         let context = RuntimeContext::new(
             self.context.state(),
             entry_point.entry_point_type(),
@@ -2372,6 +2467,8 @@ where
             module,
             host_buffer,
             context,
+            tracer,
+            gas_profiler,
         };
 
         let result = instance.invoke_export(entry_point_name, &[], &mut runtime);
@@ -3617,6 +3714,11 @@ where
         Ok(Ok(()))
     }
 
+    /// Charges the gas cost of calling a system contract's entry point natively.
+    fn charge_system_contract_call(&mut self, amount: u32) -> Result<(), Error> {
+        self.gas(Gas::new(U512::from(amount)))
+    }
+
     /// Calculate gas cost for a host function
     fn charge_host_function_call<T>(
         &mut self,
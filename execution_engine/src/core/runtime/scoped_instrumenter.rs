@@ -4,8 +4,11 @@ use std::{
     time::{Duration, Instant},
 };
 
+use tracing::trace;
+
 use crate::{
-    core::resolvers::v1_function_index::FunctionIndex, shared::logging::log_host_function_metrics,
+    core::resolvers::v1_function_index::FunctionIndex,
+    shared::{logging::log_host_function_metrics, metrics},
 };
 
 enum PauseState {
@@ -143,6 +146,9 @@ impl Drop for ScopedInstrumenter {
             FunctionIndex::RecordTransfer => "host_record_transfer",
         };
 
+        metrics::host_function(host_function).observe(duration.as_secs_f64());
+        trace!(host_function, ?duration, "host function call");
+
         let mut properties = mem::take(&mut self.properties);
         properties.insert(
             "duration_in_seconds",
@@ -21,6 +21,18 @@ where
             .get_main_purse()
             .map_err(|_| ApiError::InvalidPurse)
     }
+
+    fn get_named_purse(&self, name: &str) -> Result<URef, ApiError> {
+        let purse = match self.context.named_keys_get(name) {
+            Some(Key::URef(uref)) => *uref,
+            Some(_) => return Err(ApiError::InvalidPurse),
+            None => return Err(ApiError::InvalidPurseName),
+        };
+        if !purse.is_writeable() || self.context.validate_uref(&purse).is_err() {
+            return Err(ApiError::InvalidPurse);
+        }
+        Ok(purse)
+    }
 }
 
 impl<'a, R> MintProvider for Runtime<'a, R>
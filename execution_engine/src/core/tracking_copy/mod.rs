@@ -26,7 +26,10 @@ use crate::{
         transform::{self, Transform},
         TypeMismatch,
     },
-    storage::{global_state::StateReader, trie::merkle_proof::TrieMerkleProof},
+    storage::{
+        global_state::StateReader,
+        trie::merkle_proof::{TrieMerkleProof, TrieMerkleProofOfAbsence},
+    },
 };
 
 #[derive(Debug)]
@@ -36,6 +39,13 @@ pub enum TrackingCopyQueryResult {
         proofs: Vec<TrieMerkleProof<Key, StoredValue>>,
     },
     ValueNotFound(String),
+    /// The query reached a key which is absent from the global state.
+    Absent {
+        /// The proofs of the values traversed before reaching the absent key.
+        proofs: Vec<TrieMerkleProof<Key, StoredValue>>,
+        /// The proof of the absence of the key reached.
+        proof_of_absence: TrieMerkleProofOfAbsence<Key, StoredValue>,
+    },
     CircularReference(String),
 }
 
@@ -360,7 +370,16 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
                 .read_with_proof(correlation_id, &query.current_key)?
             {
                 None => {
-                    return Ok(query.into_not_found_result("Failed to find base key"));
+                    return match self
+                        .reader
+                        .read_proof_of_absence(correlation_id, &query.current_key)?
+                    {
+                        Some(proof_of_absence) => Ok(TrackingCopyQueryResult::Absent {
+                            proofs,
+                            proof_of_absence,
+                        }),
+                        None => Ok(query.into_not_found_result("Failed to find base key")),
+                    };
                 }
                 Some(stored_value) => stored_value,
             };
@@ -459,6 +478,25 @@ impl<R: StateReader<Key, StoredValue>> StateReader<Key, StoredValue> for &Tracki
     ) -> Result<Option<TrieMerkleProof<Key, StoredValue>>, Self::Error> {
         self.reader.read_with_proof(correlation_id, key)
     }
+
+    fn read_proof_of_absence(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProofOfAbsence<Key, StoredValue>>, Self::Error> {
+        self.reader.read_proof_of_absence(correlation_id, key)
+    }
+
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+        offset: usize,
+        count: usize,
+    ) -> Result<Vec<Key>, Self::Error> {
+        self.reader
+            .keys_with_prefix(correlation_id, prefix, offset, count)
+    }
 }
 
 #[derive(Error, Debug, PartialEq, Eq)]
@@ -478,6 +516,12 @@ pub enum ValidationError {
     #[error("The path went cold.")]
     PathCold,
 
+    #[error("There are more proofs than path components.")]
+    ProofsExceedPath,
+
+    #[error("The proof does not show the absence of the key.")]
+    NotAProofOfAbsence,
+
     #[error("Serialization error: {0}")]
     BytesRepr(bytesrepr::Error),
 
@@ -559,6 +603,60 @@ pub fn validate_query_proof(
     Ok(())
 }
 
+/// Validates that the query for `path` starting from `expected_first_key` reaches a key which is
+/// absent from the global state with the given hash.
+///
+/// `proofs` are the proofs of the values traversed before reaching the absent key, so they are
+/// empty if `expected_first_key` itself is absent.
+pub fn validate_query_proof_of_absence(
+    hash: &Blake2bHash,
+    proofs: &[TrieMerkleProof<Key, StoredValue>],
+    proof_of_absence: &TrieMerkleProofOfAbsence<Key, StoredValue>,
+    expected_first_key: &Key,
+    path: &[String],
+) -> Result<(), ValidationError> {
+    if proofs.len() > path.len() {
+        return Err(ValidationError::ProofsExceedPath);
+    }
+
+    let mut expected_key = expected_first_key.normalize();
+
+    for (proof, path_component) in proofs.iter().zip(path.iter()) {
+        if proof.key() != &expected_key {
+            return Err(ValidationError::UnexpectedKey);
+        }
+
+        if hash != &proof.compute_state_hash()? {
+            return Err(ValidationError::InvalidProofHash);
+        }
+
+        let named_keys = match proof.value() {
+            StoredValue::Account(account) => account.named_keys(),
+            StoredValue::Contract(contract) => contract.named_keys(),
+            _ => return Err(ValidationError::PathCold),
+        };
+
+        expected_key = match named_keys.get(path_component) {
+            Some(key) => key.normalize(),
+            None => return Err(ValidationError::PathCold),
+        };
+    }
+
+    if proof_of_absence.key() != &expected_key {
+        return Err(ValidationError::UnexpectedKey);
+    }
+
+    if hash != &proof_of_absence.compute_state_hash()? {
+        return Err(ValidationError::InvalidProofHash);
+    }
+
+    if !proof_of_absence.proves_absence()? {
+        return Err(ValidationError::NotAProofOfAbsence);
+    }
+
+    Ok(())
+}
+
 #[allow(unused)]
 pub fn validate_balance_proof(
     hash: &Blake2bHash,
@@ -23,7 +23,7 @@ use crate::{
     },
     storage::{
         global_state::{in_memory::InMemoryGlobalState, StateProvider, StateReader},
-        trie::merkle_proof::TrieMerkleProof,
+        trie::merkle_proof::{TrieMerkleProof, TrieMerkleProofOfAbsence},
     },
 };
 
@@ -71,6 +71,24 @@ impl StateReader<Key, StoredValue> for CountingDb {
     ) -> Result<Option<TrieMerkleProof<Key, StoredValue>>, Self::Error> {
         Ok(None)
     }
+
+    fn read_proof_of_absence(
+        &self,
+        _correlation_id: CorrelationId,
+        _key: &Key,
+    ) -> Result<Option<TrieMerkleProofOfAbsence<Key, StoredValue>>, Self::Error> {
+        Ok(None)
+    }
+
+    fn keys_with_prefix(
+        &self,
+        _correlation_id: CorrelationId,
+        _prefix: &[u8],
+        _offset: usize,
+        _count: usize,
+    ) -> Result<Vec<Key>, Self::Error> {
+        Ok(Vec::new())
+    }
 }
 
 #[test]
@@ -313,8 +331,30 @@ proptest! {
         }
 
         if missing_key != k {
-            let result = tc.query(correlation_id, missing_key, &empty_path);
-            assert_matches!(result, Ok(TrackingCopyQueryResult::ValueNotFound(_)));
+            match tc.query(correlation_id, missing_key, &empty_path) {
+                Ok(TrackingCopyQueryResult::Absent { proofs, proof_of_absence }) => {
+                    assert!(proofs.is_empty());
+                    assert!(crate::core::validate_query_proof_of_absence(
+                        &root_hash,
+                        &proofs,
+                        &proof_of_absence,
+                        &missing_key,
+                        &empty_path,
+                    )
+                    .is_ok());
+                    assert_eq!(
+                        crate::core::validate_query_proof_of_absence(
+                            &root_hash,
+                            &proofs,
+                            &proof_of_absence,
+                            &k,
+                            &empty_path,
+                        ),
+                        Err(ValidationError::UnexpectedKey)
+                    );
+                }
+                _ => panic!("Query should have returned a proof of absence!"),
+            }
         }
     }
 
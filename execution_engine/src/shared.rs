@@ -6,12 +6,14 @@ pub mod gas;
 pub mod account;
 pub mod host_function_costs;
 pub mod logging;
+pub mod metrics;
 pub mod motes;
 pub mod newtypes;
 pub mod opcode_costs;
 pub mod socket;
 pub mod storage_costs;
 pub mod stored_value;
+pub mod system_config;
 pub mod test_utils;
 pub mod transform;
 mod type_mismatch;
@@ -0,0 +1,187 @@
+//! Timing metrics of the execution engine.
+//!
+//! The metrics are process-wide, so they can be recorded at any depth of the execution engine
+//! without threading a handle through each layer.  They are only exported once added to a
+//! registry via [`register`], e.g. by the node as part of its metrics endpoint.
+
+use lazy_static::lazy_static;
+use prometheus::{self, Histogram, HistogramOpts, HistogramVec, IntCounter, Registry};
+
+/// Upper bound of the first bucket of the histograms of short operations, i.e. 1 µs.
+const SHORT_BUCKET_START: f64 = 0.000_001;
+/// Multiplier of the previous upper bound for the next bound of short operations.
+const SHORT_BUCKET_FACTOR: f64 = 4.0;
+/// Bucket count of short operations, with the last one going to +Inf.
+const SHORT_BUCKET_COUNT: usize = 10;
+
+/// Upper bound of the first bucket of the histograms of long operations, i.e. 1 ms.
+const LONG_BUCKET_START: f64 = 0.001;
+/// Multiplier of the previous upper bound for the next bound of long operations.
+const LONG_BUCKET_FACTOR: f64 = 2.0;
+/// Bucket count of long operations, with the last one going to +Inf.
+const LONG_BUCKET_COUNT: usize = 14;
+
+/// Upper bound of the first bucket of the histogram of commit batch sizes.
+const BATCH_SIZE_BUCKET_START: f64 = 1.0;
+/// Multiplier of the previous upper bound for the next bound of commit batch sizes.
+const BATCH_SIZE_BUCKET_FACTOR: f64 = 4.0;
+/// Bucket count of commit batch sizes, with the last one going to +Inf.
+const BATCH_SIZE_BUCKET_COUNT: usize = 9;
+
+/// The label of the host function metric holding the name of the host function.
+const HOST_FUNCTION_LABEL: &str = "function";
+
+lazy_static! {
+    /// Time taken to read a value from a trie.
+    pub(crate) static ref TRIE_READ: Histogram = short_histogram(
+        "execution_engine_trie_read",
+        "time in seconds to read a value from the global state trie",
+    );
+    /// Number of tries read from a trie store's cache rather than from the store itself.
+    pub(crate) static ref TRIE_CACHE_HITS: IntCounter = IntCounter::new(
+        "execution_engine_trie_cache_hits",
+        "number of tries read from the trie cache",
+    )
+    .expect("trie cache hits metric should be valid");
+    /// Number of tries missing from a trie store's cache, and so read from the store itself.
+    pub(crate) static ref TRIE_CACHE_MISSES: IntCounter = IntCounter::new(
+        "execution_engine_trie_cache_misses",
+        "number of tries missing from the trie cache",
+    )
+    .expect("trie cache misses metric should be valid");
+    /// Time taken to write a value to a trie.
+    pub(crate) static ref TRIE_WRITE: Histogram = short_histogram(
+        "execution_engine_trie_write",
+        "time in seconds to write a value to the global state trie",
+    );
+    /// Time taken by each call of a host function, excluding nested contract executions.
+    pub(crate) static ref HOST_FUNCTION: HistogramVec = HistogramVec::new(
+        HistogramOpts::new(
+            "execution_engine_host_function",
+            "time in seconds spent in a host function, by host function",
+        )
+        .buckets(buckets(SHORT_BUCKET_START, SHORT_BUCKET_FACTOR, SHORT_BUCKET_COUNT)),
+        &[HOST_FUNCTION_LABEL],
+    )
+    .expect("host function metric should be valid");
+    /// Time taken to instantiate a Wasm module.
+    pub(crate) static ref WASM_INSTANTIATION: Histogram = long_histogram(
+        "execution_engine_wasm_instantiation",
+        "time in seconds to instantiate a wasm module",
+    );
+    /// Time taken to commit a batch of effects to global state.
+    pub(crate) static ref COMMIT: Histogram = long_histogram(
+        "execution_engine_commit",
+        "time in seconds to commit a batch of effects to global state",
+    );
+    /// Number of effects in each batch committed to global state.
+    pub(crate) static ref COMMIT_BATCH_SIZE: Histogram = Histogram::with_opts(
+        HistogramOpts::new(
+            "execution_engine_commit_batch_size",
+            "number of effects in a batch committed to global state",
+        )
+        .buckets(buckets(
+            BATCH_SIZE_BUCKET_START,
+            BATCH_SIZE_BUCKET_FACTOR,
+            BATCH_SIZE_BUCKET_COUNT,
+        )),
+    )
+    .expect("commit batch size metric should be valid");
+    /// Number of trie nodes written to global state by each commit.
+    pub(crate) static ref COMMIT_TRIE_NODES_WRITTEN: Histogram = Histogram::with_opts(
+        HistogramOpts::new(
+            "execution_engine_commit_trie_nodes_written",
+            "number of trie nodes written to global state by a commit",
+        )
+        .buckets(buckets(
+            BATCH_SIZE_BUCKET_START,
+            BATCH_SIZE_BUCKET_FACTOR,
+            BATCH_SIZE_BUCKET_COUNT,
+        )),
+    )
+    .expect("commit trie nodes written metric should be valid");
+    /// Number of intermediate trie nodes created by each commit but unreachable from its new state
+    /// root, and so never written.
+    pub(crate) static ref COMMIT_TRIE_NODES_DISCARDED: Histogram = Histogram::with_opts(
+        HistogramOpts::new(
+            "execution_engine_commit_trie_nodes_discarded",
+            "number of intermediate trie nodes discarded rather than written by a commit",
+        )
+        .buckets(buckets(
+            BATCH_SIZE_BUCKET_START,
+            BATCH_SIZE_BUCKET_FACTOR,
+            BATCH_SIZE_BUCKET_COUNT,
+        )),
+    )
+    .expect("commit trie nodes discarded metric should be valid");
+    /// Time taken to run genesis.
+    pub(crate) static ref GENESIS: Histogram = long_histogram(
+        "execution_engine_genesis",
+        "time in seconds to run genesis and commit its effects",
+    );
+}
+
+/// Registers all the execution engine metrics with `registry`.
+pub fn register(registry: &Registry) -> Result<(), prometheus::Error> {
+    registry.register(Box::new(TRIE_READ.clone()))?;
+    registry.register(Box::new(TRIE_CACHE_HITS.clone()))?;
+    registry.register(Box::new(TRIE_CACHE_MISSES.clone()))?;
+    registry.register(Box::new(TRIE_WRITE.clone()))?;
+    registry.register(Box::new(HOST_FUNCTION.clone()))?;
+    registry.register(Box::new(WASM_INSTANTIATION.clone()))?;
+    registry.register(Box::new(COMMIT.clone()))?;
+    registry.register(Box::new(COMMIT_BATCH_SIZE.clone()))?;
+    registry.register(Box::new(COMMIT_TRIE_NODES_WRITTEN.clone()))?;
+    registry.register(Box::new(COMMIT_TRIE_NODES_DISCARDED.clone()))?;
+    registry.register(Box::new(GENESIS.clone()))?;
+    Ok(())
+}
+
+/// Returns the histogram recording the time taken by `host_function`.
+pub(crate) fn host_function(host_function: &str) -> Histogram {
+    HOST_FUNCTION.with_label_values(&[host_function])
+}
+
+fn buckets(start: f64, factor: f64, count: usize) -> Vec<f64> {
+    prometheus::exponential_buckets(start, factor, count).expect("buckets should be valid")
+}
+
+fn short_histogram(name: &str, help: &str) -> Histogram {
+    Histogram::with_opts(HistogramOpts::new(name, help).buckets(buckets(
+        SHORT_BUCKET_START,
+        SHORT_BUCKET_FACTOR,
+        SHORT_BUCKET_COUNT,
+    )))
+    .expect("short histogram metric should be valid")
+}
+
+fn long_histogram(name: &str, help: &str) -> Histogram {
+    Histogram::with_opts(HistogramOpts::new(name, help).buckets(buckets(
+        LONG_BUCKET_START,
+        LONG_BUCKET_FACTOR,
+        LONG_BUCKET_COUNT,
+    )))
+    .expect("long histogram metric should be valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_export_registered_metrics() {
+        let registry = Registry::new();
+        register(&registry).unwrap();
+
+        TRIE_READ.observe(0.001);
+        host_function("host_function_write").observe(0.001);
+
+        let names: Vec<_> = registry
+            .gather()
+            .iter()
+            .map(|family| family.get_name().to_string())
+            .collect();
+        assert!(names.contains(&"execution_engine_trie_read".to_string()));
+        assert!(names.contains(&"execution_engine_host_function".to_string()));
+    }
+}
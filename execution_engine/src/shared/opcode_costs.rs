@@ -69,6 +69,33 @@ pub struct OpcodeCosts {
 }
 
 impl OpcodeCosts {
+    /// Returns the name of the field holding the cost of the given type of instruction, along with
+    /// that cost.
+    pub(crate) fn category_and_cost(
+        &self,
+        instruction_type: InstructionType,
+    ) -> (&'static str, u32) {
+        match instruction_type {
+            InstructionType::Bit => ("bit", self.bit),
+            InstructionType::Add => ("add", self.add),
+            InstructionType::Mul => ("mul", self.mul),
+            InstructionType::Div => ("div", self.div),
+            InstructionType::Load => ("load", self.load),
+            InstructionType::Store => ("store", self.store),
+            InstructionType::Const => ("const", self.op_const),
+            InstructionType::Local => ("local", self.local),
+            InstructionType::Global => ("global", self.global),
+            InstructionType::ControlFlow => ("control_flow", self.control_flow),
+            InstructionType::IntegerComparsion => ("integer_comparsion", self.integer_comparsion),
+            InstructionType::Conversion => ("conversion", self.conversion),
+            InstructionType::Unreachable => ("unreachable", self.unreachable),
+            InstructionType::Nop => ("nop", self.nop),
+            InstructionType::CurrentMemory => ("current_memory", self.current_memory),
+            InstructionType::GrowMemory => ("grow_memory", self.grow_memory),
+            _ => ("regular", self.regular),
+        }
+    }
+
     pub(crate) fn to_set(&self) -> Set {
         let meterings = {
             let mut tmp = BTreeMap::new();
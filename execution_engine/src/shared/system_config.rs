@@ -0,0 +1,156 @@
+pub mod auction_costs;
+pub mod mint_costs;
+pub mod proof_of_stake_costs;
+pub mod standard_payment_costs;
+
+use datasize::DataSize;
+use rand::{distributions::Standard, prelude::*, Rng};
+use serde::{Deserialize, Serialize};
+
+use casper_types::bytesrepr::{self, FromBytes, ToBytes};
+
+use self::{
+    auction_costs::AuctionCosts, mint_costs::MintCosts, proof_of_stake_costs::ProofOfStakeCosts,
+    standard_payment_costs::StandardPaymentCosts,
+};
+
+/// Costs of calling the entry points of the system contracts when they are executed natively by
+/// the host.
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug, DataSize, Default)]
+pub struct SystemConfig {
+    /// Mint costs.
+    mint_costs: MintCosts,
+    /// Proof of stake costs.
+    proof_of_stake_costs: ProofOfStakeCosts,
+    /// Standard payment costs.
+    standard_payment_costs: StandardPaymentCosts,
+    /// Auction costs.
+    auction_costs: AuctionCosts,
+}
+
+impl SystemConfig {
+    pub const fn new(
+        mint_costs: MintCosts,
+        proof_of_stake_costs: ProofOfStakeCosts,
+        standard_payment_costs: StandardPaymentCosts,
+        auction_costs: AuctionCosts,
+    ) -> Self {
+        Self {
+            mint_costs,
+            proof_of_stake_costs,
+            standard_payment_costs,
+            auction_costs,
+        }
+    }
+
+    pub fn mint_costs(&self) -> &MintCosts {
+        &self.mint_costs
+    }
+
+    pub fn proof_of_stake_costs(&self) -> &ProofOfStakeCosts {
+        &self.proof_of_stake_costs
+    }
+
+    pub fn standard_payment_costs(&self) -> &StandardPaymentCosts {
+        &self.standard_payment_costs
+    }
+
+    pub fn auction_costs(&self) -> &AuctionCosts {
+        &self.auction_costs
+    }
+}
+
+impl ToBytes for SystemConfig {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = bytesrepr::unchecked_allocate_buffer(self);
+
+        ret.append(&mut self.mint_costs.to_bytes()?);
+        ret.append(&mut self.proof_of_stake_costs.to_bytes()?);
+        ret.append(&mut self.standard_payment_costs.to_bytes()?);
+        ret.append(&mut self.auction_costs.to_bytes()?);
+
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.mint_costs.serialized_length()
+            + self.proof_of_stake_costs.serialized_length()
+            + self.standard_payment_costs.serialized_length()
+            + self.auction_costs.serialized_length()
+    }
+}
+
+impl FromBytes for SystemConfig {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (mint_costs, rem) = FromBytes::from_bytes(bytes)?;
+        let (proof_of_stake_costs, rem) = FromBytes::from_bytes(rem)?;
+        let (standard_payment_costs, rem) = FromBytes::from_bytes(rem)?;
+        let (auction_costs, rem) = FromBytes::from_bytes(rem)?;
+
+        Ok((
+            SystemConfig {
+                mint_costs,
+                proof_of_stake_costs,
+                standard_payment_costs,
+                auction_costs,
+            },
+            rem,
+        ))
+    }
+}
+
+impl Distribution<SystemConfig> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> SystemConfig {
+        SystemConfig {
+            mint_costs: rng.gen(),
+            proof_of_stake_costs: rng.gen(),
+            standard_payment_costs: rng.gen(),
+            auction_costs: rng.gen(),
+        }
+    }
+}
+
+#[cfg(any(feature = "gens", test))]
+pub mod gens {
+    use proptest::prop_compose;
+
+    use super::{
+        auction_costs::gens::auction_costs_arb, mint_costs::gens::mint_costs_arb,
+        proof_of_stake_costs::gens::proof_of_stake_costs_arb,
+        standard_payment_costs::gens::standard_payment_costs_arb, SystemConfig,
+    };
+
+    prop_compose! {
+        pub fn system_config_arb()(
+            mint_costs in mint_costs_arb(),
+            proof_of_stake_costs in proof_of_stake_costs_arb(),
+            standard_payment_costs in standard_payment_costs_arb(),
+            auction_costs in auction_costs_arb(),
+        ) -> SystemConfig {
+            SystemConfig {
+                mint_costs,
+                proof_of_stake_costs,
+                standard_payment_costs,
+                auction_costs,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use casper_types::bytesrepr;
+
+    use super::gens;
+
+    proptest! {
+        #[test]
+        fn should_serialize_and_deserialize_with_arbitrary_values(
+            system_config in gens::system_config_arb()
+        ) {
+            bytesrepr::test_serialization_roundtrip(&system_config);
+        }
+    }
+}
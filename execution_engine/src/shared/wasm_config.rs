@@ -24,6 +24,10 @@ pub struct WasmConfig {
     storage_costs: StorageCosts,
     /// Host function costs table
     host_function_costs: HostFunctionCosts,
+    /// Whether to reject Wasm modules using floating-point values in any way, since their
+    /// behavior can differ between platforms.
+    #[serde(default)]
+    pub strict_determinism: bool,
 }
 
 impl WasmConfig {
@@ -40,9 +44,15 @@ impl WasmConfig {
             opcode_costs,
             storage_costs,
             host_function_costs,
+            strict_determinism: false,
         }
     }
 
+    pub fn with_strict_determinism(mut self, strict_determinism: bool) -> Self {
+        self.strict_determinism = strict_determinism;
+        self
+    }
+
     pub fn opcode_costs(&self) -> OpcodeCosts {
         self.opcode_costs
     }
@@ -64,6 +74,7 @@ impl Default for WasmConfig {
             opcode_costs: OpcodeCosts::default(),
             storage_costs: StorageCosts::default(),
             host_function_costs: HostFunctionCosts::default(),
+            strict_determinism: false,
         }
     }
 }
@@ -77,6 +88,7 @@ impl ToBytes for WasmConfig {
         ret.append(&mut self.opcode_costs.to_bytes()?);
         ret.append(&mut self.storage_costs.to_bytes()?);
         ret.append(&mut self.host_function_costs.to_bytes()?);
+        ret.append(&mut self.strict_determinism.to_bytes()?);
 
         Ok(ret)
     }
@@ -87,6 +99,7 @@ impl ToBytes for WasmConfig {
             + self.opcode_costs.serialized_length()
             + self.storage_costs.serialized_length()
             + self.host_function_costs.serialized_length()
+            + self.strict_determinism.serialized_length()
     }
 }
 
@@ -97,6 +110,7 @@ impl FromBytes for WasmConfig {
         let (opcode_costs, rem) = FromBytes::from_bytes(rem)?;
         let (storage_costs, rem) = FromBytes::from_bytes(rem)?;
         let (host_function_costs, rem) = FromBytes::from_bytes(rem)?;
+        let (strict_determinism, rem) = FromBytes::from_bytes(rem)?;
 
         Ok((
             WasmConfig {
@@ -105,6 +119,7 @@ impl FromBytes for WasmConfig {
                 opcode_costs,
                 storage_costs,
                 host_function_costs,
+                strict_determinism,
             },
             rem,
         ))
@@ -119,13 +134,14 @@ impl Distribution<WasmConfig> for Standard {
             opcode_costs: rng.gen(),
             storage_costs: rng.gen(),
             host_function_costs: rng.gen(),
+            strict_determinism: rng.gen(),
         }
     }
 }
 
 #[cfg(any(feature = "gens", test))]
 pub mod gens {
-    use proptest::{num, prop_compose};
+    use proptest::{bool, num, prop_compose};
 
     use super::WasmConfig;
     use crate::shared::{
@@ -140,6 +156,7 @@ pub mod gens {
             opcode_costs in opcode_costs_arb(),
             storage_costs in storage_costs_arb(),
             host_function_costs in host_function_costs_arb(),
+            strict_determinism in bool::ANY,
         ) -> WasmConfig {
             WasmConfig {
                 initial_memory,
@@ -147,6 +164,7 @@ pub mod gens {
                 opcode_costs,
                 storage_costs,
                 host_function_costs,
+                strict_determinism,
             }
         }
     }
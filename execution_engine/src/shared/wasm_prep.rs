@@ -1,7 +1,7 @@
 use std::fmt::{self, Display, Formatter};
 
-use parity_wasm::elements::{self, Module};
-use pwasm_utils::{self, stack_height};
+use parity_wasm::elements::{self, External, Instruction, Module, Type, ValueType};
+use pwasm_utils::{self, rules::InstructionType, stack_height};
 use thiserror::Error;
 
 use super::wasm_config::WasmConfig;
@@ -11,6 +11,7 @@ pub enum PreprocessingError {
     Deserialize(String),
     OperationForbiddenByGasRules,
     StackLimiter,
+    Nondeterministic(String),
 }
 
 impl From<elements::Error> for PreprocessingError {
@@ -25,6 +26,9 @@ impl Display for PreprocessingError {
             PreprocessingError::Deserialize(error) => write!(f, "Deserialization error: {}", error),
             PreprocessingError::OperationForbiddenByGasRules => write!(f, "Encountered operation forbidden by gas rules. Consult instruction -> metering config map"),
             PreprocessingError::StackLimiter => write!(f, "Stack limiter error"),
+            PreprocessingError::Nondeterministic(what) => {
+                write!(f, "Nondeterministic Wasm: module uses {}", what)
+            }
         }
     }
 }
@@ -40,6 +44,9 @@ impl Preprocessor {
 
     pub fn preprocess(&self, module_bytes: &[u8]) -> Result<Module, PreprocessingError> {
         let module = deserialize(module_bytes)?;
+        if self.wasm_config.strict_determinism {
+            ensure_deterministic(&module)?;
+        }
         let module = pwasm_utils::externalize_mem(module, None, self.wasm_config.initial_memory);
         let module =
             pwasm_utils::inject_gas_counter(module, &self.wasm_config.opcode_costs().to_set())
@@ -54,3 +61,146 @@ impl Preprocessor {
 pub fn deserialize(module_bytes: &[u8]) -> Result<Module, PreprocessingError> {
     parity_wasm::deserialize_buffer::<Module>(module_bytes).map_err(Into::into)
 }
+
+fn is_float(value_type: &ValueType) -> bool {
+    matches!(value_type, ValueType::F32 | ValueType::F64)
+}
+
+/// Returns an error if the module uses floating-point values in any way.
+///
+/// Float arithmetic is already forbidden by the gas rules, but float values can still be declared
+/// as parameters, results, globals and locals, and be loaded from and stored to memory, where the
+/// handling of NaN payloads differs between platforms.
+pub fn ensure_deterministic(module: &Module) -> Result<(), PreprocessingError> {
+    let nondeterministic = |what: &str| Err(PreprocessingError::Nondeterministic(what.to_string()));
+
+    if let Some(type_section) = module.type_section() {
+        for Type::Function(function_type) in type_section.types() {
+            if function_type.params().iter().any(is_float)
+                || function_type.return_type().as_ref().map_or(false, is_float)
+            {
+                return nondeterministic("a function type with float values");
+            }
+        }
+    }
+
+    if let Some(import_section) = module.import_section() {
+        for import_entry in import_section.entries() {
+            if let External::Global(global_type) = import_entry.external() {
+                if is_float(&global_type.content_type()) {
+                    return nondeterministic("an imported float global");
+                }
+            }
+        }
+    }
+
+    if let Some(global_section) = module.global_section() {
+        for global_entry in global_section.entries() {
+            if is_float(&global_entry.global_type().content_type()) {
+                return nondeterministic("a float global");
+            }
+        }
+    }
+
+    if let Some(code_section) = module.code_section() {
+        for func_body in code_section.bodies() {
+            if func_body
+                .locals()
+                .iter()
+                .any(|local| is_float(&local.value_type()))
+            {
+                return nondeterministic("a float local");
+            }
+            for instruction in func_body.code().elements() {
+                let is_float_instruction = match instruction {
+                    Instruction::F32Load(..)
+                    | Instruction::F64Load(..)
+                    | Instruction::F32Store(..)
+                    | Instruction::F64Store(..) => true,
+                    _ => matches!(
+                        InstructionType::op(instruction),
+                        InstructionType::Float
+                            | InstructionType::FloatConst
+                            | InstructionType::FloatComparsion
+                            | InstructionType::FloatConversion
+                    ),
+                };
+                if is_float_instruction {
+                    return nondeterministic("a float instruction");
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::{builder, elements::Instructions};
+
+    use super::*;
+
+    fn module_with(params: Vec<ValueType>, instructions: Vec<Instruction>) -> Vec<u8> {
+        let module = builder::module()
+            .function()
+            .signature()
+            .with_params(params)
+            .build()
+            .body()
+            .with_instructions(Instructions::new(instructions))
+            .build()
+            .build()
+            .export()
+            .field("call")
+            .build()
+            .memory()
+            .build()
+            .build();
+        parity_wasm::serialize(module).expect("should serialize")
+    }
+
+    fn strict_preprocessor() -> Preprocessor {
+        Preprocessor::new(WasmConfig::default().with_strict_determinism(true))
+    }
+
+    #[test]
+    fn should_accept_integer_only_module() {
+        let module_bytes = module_with(
+            vec![ValueType::I32],
+            vec![
+                Instruction::GetLocal(0),
+                Instruction::Drop,
+                Instruction::End,
+            ],
+        );
+        strict_preprocessor()
+            .preprocess(&module_bytes)
+            .expect("should preprocess");
+    }
+
+    #[test]
+    fn should_reject_float_params_and_memory_accesses() {
+        let float_param = module_with(vec![ValueType::F64], vec![Instruction::End]);
+        let float_load = module_with(
+            vec![],
+            vec![
+                Instruction::I32Const(0),
+                Instruction::F32Load(2, 0),
+                Instruction::Drop,
+                Instruction::End,
+            ],
+        );
+
+        for module_bytes in &[float_param, float_load] {
+            assert!(matches!(
+                strict_preprocessor().preprocess(module_bytes),
+                Err(PreprocessingError::Nondeterministic(_))
+            ));
+            // Without strict determinism, neither is rejected by the gas rules.
+            Preprocessor::new(WasmConfig::default())
+                .preprocess(module_bytes)
+                .expect("should preprocess");
+        }
+    }
+}
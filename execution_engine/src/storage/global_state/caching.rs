@@ -0,0 +1,186 @@
+//! A state reader remembering the values and proofs read through it, so that a session of several
+//! queries against the same state root reads each key from the underlying store only once.
+
+use std::{cell::RefCell, collections::HashMap, hash::Hash};
+
+use crate::{
+    shared::newtypes::CorrelationId,
+    storage::{
+        global_state::StateReader,
+        trie::merkle_proof::{TrieMerkleProof, TrieMerkleProofOfAbsence},
+    },
+};
+
+/// Wraps a `StateReader`, caching the results of `read_with_proof` and `read_proof_of_absence`.
+///
+/// Intended to be short-lived: nothing is ever evicted from the cache.
+pub struct CachingStateReader<K, V, R> {
+    reader: R,
+    proofs: RefCell<HashMap<K, Option<TrieMerkleProof<K, V>>>>,
+    proofs_of_absence: RefCell<HashMap<K, Option<TrieMerkleProofOfAbsence<K, V>>>>,
+}
+
+impl<K, V, R> CachingStateReader<K, V, R>
+where
+    K: Eq + Hash,
+{
+    pub fn new(reader: R) -> Self {
+        CachingStateReader {
+            reader,
+            proofs: RefCell::new(HashMap::new()),
+            proofs_of_absence: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+impl<K, V, R> StateReader<K, V> for CachingStateReader<K, V, R>
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+    R: StateReader<K, V>,
+{
+    type Error = R::Error;
+
+    fn read(&self, correlation_id: CorrelationId, key: &K) -> Result<Option<V>, Self::Error> {
+        Ok(self
+            .read_with_proof(correlation_id, key)?
+            .map(|proof| proof.value().clone()))
+    }
+
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &K,
+    ) -> Result<Option<TrieMerkleProof<K, V>>, Self::Error> {
+        if let Some(maybe_proof) = self.proofs.borrow().get(key) {
+            return Ok(maybe_proof.clone());
+        }
+        let maybe_proof = self.reader.read_with_proof(correlation_id, key)?;
+        self.proofs
+            .borrow_mut()
+            .insert(key.clone(), maybe_proof.clone());
+        Ok(maybe_proof)
+    }
+
+    fn read_proof_of_absence(
+        &self,
+        correlation_id: CorrelationId,
+        key: &K,
+    ) -> Result<Option<TrieMerkleProofOfAbsence<K, V>>, Self::Error> {
+        if let Some(maybe_proof_of_absence) = self.proofs_of_absence.borrow().get(key) {
+            return Ok(maybe_proof_of_absence.clone());
+        }
+        let maybe_proof_of_absence = self.reader.read_proof_of_absence(correlation_id, key)?;
+        self.proofs_of_absence
+            .borrow_mut()
+            .insert(key.clone(), maybe_proof_of_absence.clone());
+        Ok(maybe_proof_of_absence)
+    }
+
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+        offset: usize,
+        count: usize,
+    ) -> Result<Vec<K>, Self::Error> {
+        self.reader
+            .keys_with_prefix(correlation_id, prefix, offset, count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use casper_types::{account::AccountHash, CLValue, Key};
+
+    use super::*;
+    use crate::{
+        shared::stored_value::StoredValue,
+        storage::global_state::{in_memory::InMemoryGlobalState, StateProvider},
+    };
+
+    /// Counts the proofs read through it.
+    struct CountingReader<R> {
+        reader: R,
+        reads: Cell<usize>,
+    }
+
+    impl<R: StateReader<Key, StoredValue>> StateReader<Key, StoredValue> for CountingReader<R> {
+        type Error = R::Error;
+
+        fn read(
+            &self,
+            correlation_id: CorrelationId,
+            key: &Key,
+        ) -> Result<Option<StoredValue>, Self::Error> {
+            self.reader.read(correlation_id, key)
+        }
+
+        fn read_with_proof(
+            &self,
+            correlation_id: CorrelationId,
+            key: &Key,
+        ) -> Result<Option<TrieMerkleProof<Key, StoredValue>>, Self::Error> {
+            self.reads.set(self.reads.get() + 1);
+            self.reader.read_with_proof(correlation_id, key)
+        }
+
+        fn read_proof_of_absence(
+            &self,
+            correlation_id: CorrelationId,
+            key: &Key,
+        ) -> Result<Option<TrieMerkleProofOfAbsence<Key, StoredValue>>, Self::Error> {
+            self.reads.set(self.reads.get() + 1);
+            self.reader.read_proof_of_absence(correlation_id, key)
+        }
+
+        fn keys_with_prefix(
+            &self,
+            correlation_id: CorrelationId,
+            prefix: &[u8],
+            offset: usize,
+            count: usize,
+        ) -> Result<Vec<Key>, Self::Error> {
+            self.reader
+                .keys_with_prefix(correlation_id, prefix, offset, count)
+        }
+    }
+
+    #[test]
+    fn should_read_each_key_once() {
+        let correlation_id = CorrelationId::new();
+        let present = Key::Account(AccountHash::new([1; 32]));
+        let absent = Key::Account(AccountHash::new([2; 32]));
+        let value = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+        let (state, root_hash) =
+            InMemoryGlobalState::from_pairs(correlation_id, &[(present, value.clone())]).unwrap();
+        let reader = CountingReader {
+            reader: state.checkout(root_hash).unwrap().unwrap(),
+            reads: Cell::new(0),
+        };
+        let caching_reader = CachingStateReader::new(reader);
+
+        for _ in 0..3 {
+            let proof = caching_reader
+                .read_with_proof(correlation_id, &present)
+                .unwrap()
+                .unwrap();
+            assert_eq!(proof.value(), &value);
+            assert_eq!(
+                caching_reader.read(correlation_id, &present).unwrap(),
+                Some(value.clone())
+            );
+            assert!(caching_reader
+                .read_with_proof(correlation_id, &absent)
+                .unwrap()
+                .is_none());
+            assert!(caching_reader
+                .read_proof_of_absence(correlation_id, &absent)
+                .unwrap()
+                .is_some());
+        }
+        assert_eq!(caching_reader.reader.reads.get(), 3);
+    }
+}
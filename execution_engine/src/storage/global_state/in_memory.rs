@@ -10,7 +10,7 @@ use casper_types::{Key, ProtocolVersion};
 
 use crate::storage::{
     error::{self, in_memory},
-    global_state::{commit, CommitResult, StateProvider, StateReader},
+    global_state::{collect_keys_page, commit, CommitResult, StateProvider, StateReader},
     protocol_data::ProtocolData,
     protocol_data_store::in_memory::InMemoryProtocolDataStore,
     store::Store,
@@ -18,10 +18,17 @@ use crate::storage::{
         in_memory::{InMemoryEnvironment, InMemoryReadTransaction},
         Transaction, TransactionSource,
     },
-    trie::{merkle_proof::TrieMerkleProof, operations::create_hashed_empty_trie, Trie},
+    trie::{
+        merkle_proof::{TrieMerkleProof, TrieMerkleProofOfAbsence},
+        operations::create_hashed_empty_trie,
+        Trie,
+    },
     trie_store::{
         in_memory::InMemoryTrieStore,
-        operations::{self, read, read_with_proof, ReadResult, WriteResult},
+        operations::{
+            self, keys_with_prefix, read, read_with_proof, read_with_proof_of_absence,
+            ProvenReadResult, ReadResult, WriteResult,
+        },
     },
 };
 
@@ -164,6 +171,53 @@ impl StateReader<Key, StoredValue> for InMemoryGlobalStateView {
         txn.commit()?;
         Ok(ret)
     }
+
+    fn read_proof_of_absence(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProofOfAbsence<Key, StoredValue>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match read_with_proof_of_absence::<
+            Key,
+            StoredValue,
+            InMemoryReadTransaction,
+            InMemoryTrieStore,
+            Self::Error,
+        >(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            key,
+        )? {
+            ProvenReadResult::NotFound(proof_of_absence) => Some(proof_of_absence),
+            ProvenReadResult::Found(_) => None,
+            ProvenReadResult::RootNotFound => panic!("InMemoryGlobalState has invalid root"),
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+        offset: usize,
+        count: usize,
+    ) -> Result<Vec<Key>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let keys = keys_with_prefix::<Key, StoredValue, InMemoryReadTransaction, InMemoryTrieStore>(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            prefix,
+        );
+        let page = collect_keys_page::<_, Self::Error, _, _>(keys, offset, count)?;
+        txn.commit()?;
+        Ok(page)
+    }
 }
 
 impl StateProvider for InMemoryGlobalState {
@@ -228,8 +282,14 @@ impl StateProvider for InMemoryGlobalState {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
+
     use crate::shared::newtypes::Blake2bHash;
-    use casper_types::{account::AccountHash, CLValue};
+    use casper_types::{
+        account::AccountHash,
+        bytesrepr::{self, ToBytes},
+        CLValue,
+    };
 
     use super::*;
 
@@ -291,6 +351,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn keys_with_prefix_from_a_checkout_are_paged() {
+        let correlation_id = CorrelationId::new();
+        let mut pairs = create_test_pairs().to_vec();
+        pairs.push(TestPair {
+            key: Key::Hash([1_u8; 32]),
+            value: StoredValue::CLValue(CLValue::from_t(3_i32).unwrap()),
+        });
+        let (state, root_hash) = InMemoryGlobalState::from_pairs(
+            correlation_id,
+            &pairs
+                .into_iter()
+                .map(|TestPair { key, value }| (key, value))
+                .collect::<Vec<(Key, StoredValue)>>(),
+        )
+        .unwrap();
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+
+        // Only the two accounts have the account tag as their first byte.
+        let accounts = checkout
+            .keys_with_prefix(correlation_id, &[0], 0, 10)
+            .unwrap();
+        let expected: Vec<Key> = create_test_pairs().iter().map(|pair| pair.key).collect();
+        assert_eq!(accounts, expected);
+
+        let second_page = checkout
+            .keys_with_prefix(correlation_id, &[0], 1, 1)
+            .unwrap();
+        assert_eq!(second_page, vec![expected[1]]);
+
+        let past_the_end = checkout
+            .keys_with_prefix(correlation_id, &[0], 2, 1)
+            .unwrap();
+        assert!(past_the_end.is_empty());
+    }
+
     #[test]
     fn checkout_fails_if_unknown_hash_is_given() {
         let (state, _) = create_test_state();
@@ -381,4 +477,44 @@ mod tests {
         let (_, root_hash) = InMemoryGlobalState::from_pairs(correlation_id, &[]).unwrap();
         assert_eq!(expected_bytes, root_hash.to_vec())
     }
+
+    #[test]
+    fn commit_only_writes_tries_reachable_from_new_root() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let tries_before = state.environment.data(None).unwrap().unwrap();
+
+        let effects: AdditiveMap<Key, Transform> = create_test_pairs_updated()
+            .iter()
+            .cloned()
+            .map(|TestPair { key, value }| (key, Transform::Write(value)))
+            .collect();
+        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+        let tries_after = state.environment.data(None).unwrap().unwrap();
+
+        let mut reachable = HashSet::new();
+        let mut pending = vec![updated_hash];
+        while let Some(hash) = pending.pop() {
+            let trie_key = hash.to_bytes().unwrap();
+            let trie: Trie<Key, StoredValue> =
+                bytesrepr::deserialize(tries_after[&trie_key].clone()).unwrap();
+            reachable.insert(trie_key);
+            match trie {
+                Trie::Leaf { .. } => (),
+                Trie::Node { pointer_block } => pending.extend(
+                    pointer_block
+                        .to_indexed_pointers()
+                        .map(|(_, pointer)| *pointer.hash()),
+                ),
+                Trie::Extension { pointer, .. } => pending.push(*pointer.hash()),
+            }
+        }
+
+        for trie_key in tries_after.keys() {
+            assert!(tries_before.contains_key(trie_key) || reachable.contains(trie_key));
+        }
+    }
 }
@@ -10,15 +10,22 @@ use casper_types::{Key, ProtocolVersion};
 
 use crate::storage::{
     error,
-    global_state::{commit, CommitResult, StateProvider, StateReader},
+    global_state::{collect_keys_page, commit, CommitResult, StateProvider, StateReader},
     protocol_data::ProtocolData,
     protocol_data_store::lmdb::LmdbProtocolDataStore,
     store::Store,
     transaction_source::{lmdb::LmdbEnvironment, Transaction, TransactionSource},
-    trie::{merkle_proof::TrieMerkleProof, operations::create_hashed_empty_trie, Trie},
+    trie::{
+        merkle_proof::{TrieMerkleProof, TrieMerkleProofOfAbsence},
+        operations::create_hashed_empty_trie,
+        Trie,
+    },
     trie_store::{
         lmdb::LmdbTrieStore,
-        operations::{read, read_with_proof, ReadResult},
+        operations::{
+            keys_with_prefix, read, read_with_proof, read_with_proof_of_absence, ProvenReadResult,
+            ReadResult,
+        },
     },
 };
 
@@ -125,6 +132,53 @@ impl StateReader<Key, StoredValue> for LmdbGlobalStateView {
         txn.commit()?;
         Ok(ret)
     }
+
+    fn read_proof_of_absence(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProofOfAbsence<Key, StoredValue>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match read_with_proof_of_absence::<
+            Key,
+            StoredValue,
+            lmdb::RoTransaction,
+            LmdbTrieStore,
+            Self::Error,
+        >(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            key,
+        )? {
+            ProvenReadResult::NotFound(proof_of_absence) => Some(proof_of_absence),
+            ProvenReadResult::Found(_) => None,
+            ProvenReadResult::RootNotFound => panic!("LmdbGlobalState has invalid root"),
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+        offset: usize,
+        count: usize,
+    ) -> Result<Vec<Key>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let keys = keys_with_prefix::<Key, StoredValue, lmdb::RoTransaction, LmdbTrieStore>(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            prefix,
+        );
+        let page = collect_keys_page::<_, Self::Error, _, _>(keys, offset, count)?;
+        txn.commit()?;
+        Ok(page)
+    }
 }
 
 impl StateProvider for LmdbGlobalState {
@@ -1,21 +1,32 @@
+pub mod caching;
 pub mod in_memory;
 pub mod lmdb;
+pub mod scratch;
 
-use std::{fmt, hash::BuildHasher};
+use std::{collections::HashSet, fmt, hash::BuildHasher};
+
+use tracing::debug_span;
 
 use crate::shared::{
     additive_map::AdditiveMap,
+    metrics,
     newtypes::{Blake2bHash, CorrelationId},
     stored_value::StoredValue,
     transform::{self, Transform},
     TypeMismatch,
 };
-use casper_types::{bytesrepr, Key, ProtocolVersion};
+use casper_types::{
+    bytesrepr::{self, ToBytes},
+    Key, ProtocolVersion,
+};
 
 use crate::storage::{
     protocol_data::ProtocolData,
-    transaction_source::{Transaction, TransactionSource},
-    trie::{merkle_proof::TrieMerkleProof, Trie},
+    transaction_source::{buffered::BufferedTransaction, Transaction, TransactionSource},
+    trie::{
+        merkle_proof::{TrieMerkleProof, TrieMerkleProofOfAbsence},
+        Pointer, Trie,
+    },
     trie_store::{
         operations::{read, write, ReadResult, WriteResult},
         TrieStore,
@@ -36,6 +47,42 @@ pub trait StateReader<K, V> {
         correlation_id: CorrelationId,
         key: &K,
     ) -> Result<Option<TrieMerkleProof<K, V>>, Self::Error>;
+
+    /// Returns the merkle proof that no state value is stored under the corresponding key, or
+    /// `None` if one is.
+    fn read_proof_of_absence(
+        &self,
+        correlation_id: CorrelationId,
+        key: &K,
+    ) -> Result<Option<TrieMerkleProofOfAbsence<K, V>>, Self::Error>;
+
+    /// Returns up to `count` of the keys whose serialized representation starts with `prefix`,
+    /// skipping the first `offset` of them, in the lexicographic order of their serialized
+    /// representation.
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+        offset: usize,
+        count: usize,
+    ) -> Result<Vec<K>, Self::Error>;
+}
+
+/// Collects `count` of the given keys following the first `offset` of them, failing on the first
+/// error encountered, including any among the skipped keys.
+fn collect_keys_page<K, E, F, I>(keys: I, offset: usize, count: usize) -> Result<Vec<K>, E>
+where
+    E: From<F>,
+    I: Iterator<Item = Result<K, F>>,
+{
+    let mut page = Vec::new();
+    for (index, maybe_key) in keys.enumerate().take(offset.saturating_add(count)) {
+        let key = maybe_key?;
+        if index >= offset {
+            page.push(key);
+        }
+    }
+    Ok(page)
 }
 
 #[derive(Debug)]
@@ -114,11 +161,19 @@ pub fn commit<'a, R, S, H, E>(
 where
     R: TransactionSource<'a, Handle = S::Handle>,
     S: TrieStore<Key, StoredValue>,
+    S::Handle: Clone + PartialEq,
     S::Error: From<R::Error>,
     E: From<R::Error> + From<S::Error> + From<bytesrepr::Error>,
     H: BuildHasher,
 {
-    let mut txn = environment.create_read_write_txn()?;
+    let span = debug_span!("commit", effects = effects.len());
+    let _entered = span.enter();
+    let _timer = metrics::COMMIT.start_timer();
+    metrics::COMMIT_BATCH_SIZE.observe(effects.len() as f64);
+
+    // The trie nodes are only written once all the effects are applied, so that those created for
+    // intermediate state roots can be discarded, and the rest written in a single sorted pass.
+    let mut txn = BufferedTransaction::new(environment.create_read_write_txn()?);
     let mut state_root = prestate_hash;
 
     let maybe_root: Option<Trie<Key, StoredValue>> = store.get(&txn, &state_root)?;
@@ -154,7 +209,55 @@ where
         }
     }
 
+    let handle = store.handle();
+    let created = txn.buffered_count(&handle);
+    let reachable = reachable_buffered_tries(&txn, &handle, state_root)?;
+    txn.retain(&handle, |trie_key| reachable.contains(trie_key));
+    metrics::COMMIT_TRIE_NODES_WRITTEN.observe(reachable.len() as f64);
+    metrics::COMMIT_TRIE_NODES_DISCARDED.observe((created - reachable.len()) as f64);
+
     txn.commit()?;
 
     Ok(CommitResult::Success { state_root })
 }
+
+/// Returns the serialized hashes of the trie nodes buffered by `txn` which are reachable from
+/// `state_root`.
+///
+/// The descendants of a node which isn't buffered are already stored, so aren't visited.
+fn reachable_buffered_tries<T>(
+    txn: &BufferedTransaction<T>,
+    handle: &T::Handle,
+    state_root: Blake2bHash,
+) -> Result<HashSet<Vec<u8>>, bytesrepr::Error>
+where
+    T: Transaction,
+    T::Handle: Clone + PartialEq,
+{
+    let mut reachable = HashSet::new();
+    let mut pending = vec![Pointer::NodePointer(state_root)];
+    while let Some(pointer) = pending.pop() {
+        let trie_key = pointer.hash().to_bytes()?;
+        let bytes = match txn.buffered(handle, &trie_key) {
+            Some(bytes) => bytes,
+            None => continue,
+        };
+        if !reachable.insert(trie_key) {
+            continue;
+        }
+        // Leaves have no descendants, so needn't be deserialized.
+        if let Pointer::LeafPointer(_) = pointer {
+            continue;
+        }
+        match bytesrepr::deserialize::<Trie<Key, StoredValue>>(bytes.to_vec())? {
+            Trie::Leaf { .. } => (),
+            Trie::Node { pointer_block } => pending.extend(
+                pointer_block
+                    .to_indexed_pointers()
+                    .map(|(_, pointer)| pointer),
+            ),
+            Trie::Extension { pointer, .. } => pending.push(pointer),
+        }
+    }
+    Ok(reachable)
+}
@@ -0,0 +1,274 @@
+use std::{ops::Deref, sync::Arc};
+
+use crate::shared::{
+    additive_map::AdditiveMap,
+    newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
+    transform::Transform,
+};
+use casper_types::{Key, ProtocolVersion};
+
+use crate::storage::{
+    error,
+    global_state::{
+        collect_keys_page, commit, lmdb::LmdbGlobalState, CommitResult, StateProvider, StateReader,
+    },
+    protocol_data::ProtocolData,
+    protocol_data_store::lmdb::LmdbProtocolDataStore,
+    store::Store,
+    transaction_source::{
+        scratch::{ScratchEnvironment, ScratchReadTransaction},
+        Transaction, TransactionSource,
+    },
+    trie::{
+        merkle_proof::{TrieMerkleProof, TrieMerkleProofOfAbsence},
+        Trie,
+    },
+    trie_store::{
+        lmdb::LmdbTrieStore,
+        operations::{
+            keys_with_prefix, read, read_with_proof, read_with_proof_of_absence, ProvenReadResult,
+            ReadResult,
+        },
+    },
+};
+
+/// Global state layered over an [`LmdbGlobalState`], committing to memory only.
+///
+/// Used for speculative execution: everything committed is visible through this instance alone,
+/// and is dropped along with it.
+pub struct ScratchGlobalState {
+    pub environment: Arc<ScratchEnvironment>,
+    pub trie_store: Arc<LmdbTrieStore>,
+    pub protocol_data_store: Arc<LmdbProtocolDataStore>,
+    pub empty_root_hash: Blake2bHash,
+}
+
+/// Represents a "view" of scratch global state at a particular root hash.
+pub struct ScratchGlobalStateView {
+    pub environment: Arc<ScratchEnvironment>,
+    pub store: Arc<LmdbTrieStore>,
+    pub root_hash: Blake2bHash,
+}
+
+impl LmdbGlobalState {
+    /// Creates a scratch global state on top of this one, which has nothing committed to it yet.
+    pub fn scratch(&self) -> ScratchGlobalState {
+        ScratchGlobalState {
+            environment: Arc::new(ScratchEnvironment::new(Arc::clone(&self.environment))),
+            trie_store: Arc::clone(&self.trie_store),
+            protocol_data_store: Arc::clone(&self.protocol_data_store),
+            empty_root_hash: self.empty_root_hash,
+        }
+    }
+}
+
+impl StateReader<Key, StoredValue> for ScratchGlobalStateView {
+    type Error = error::Error;
+
+    fn read(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<StoredValue>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match read::<Key, StoredValue, ScratchReadTransaction, LmdbTrieStore, Self::Error>(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            key,
+        )? {
+            ReadResult::Found(value) => Some(value),
+            ReadResult::NotFound => None,
+            ReadResult::RootNotFound => panic!("ScratchGlobalState has invalid root"),
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, StoredValue>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match read_with_proof::<
+            Key,
+            StoredValue,
+            ScratchReadTransaction,
+            LmdbTrieStore,
+            Self::Error,
+        >(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            key,
+        )? {
+            ReadResult::Found(value) => Some(value),
+            ReadResult::NotFound => None,
+            ReadResult::RootNotFound => panic!("ScratchGlobalState has invalid root"),
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+
+    fn read_proof_of_absence(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProofOfAbsence<Key, StoredValue>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match read_with_proof_of_absence::<
+            Key,
+            StoredValue,
+            ScratchReadTransaction,
+            LmdbTrieStore,
+            Self::Error,
+        >(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            key,
+        )? {
+            ProvenReadResult::NotFound(proof_of_absence) => Some(proof_of_absence),
+            ProvenReadResult::Found(_) => None,
+            ProvenReadResult::RootNotFound => panic!("ScratchGlobalState has invalid root"),
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+        offset: usize,
+        count: usize,
+    ) -> Result<Vec<Key>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let keys = keys_with_prefix::<Key, StoredValue, ScratchReadTransaction, LmdbTrieStore>(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            prefix,
+        );
+        let page = collect_keys_page::<_, Self::Error, _, _>(keys, offset, count)?;
+        txn.commit()?;
+        Ok(page)
+    }
+}
+
+impl StateProvider for ScratchGlobalState {
+    type Error = error::Error;
+
+    type Reader = ScratchGlobalStateView;
+
+    fn checkout(&self, state_hash: Blake2bHash) -> Result<Option<Self::Reader>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let maybe_root: Option<Trie<Key, StoredValue>> = self.trie_store.get(&txn, &state_hash)?;
+        let maybe_state = maybe_root.map(|_| ScratchGlobalStateView {
+            environment: Arc::clone(&self.environment),
+            store: Arc::clone(&self.trie_store),
+            root_hash: state_hash,
+        });
+        txn.commit()?;
+        Ok(maybe_state)
+    }
+
+    fn commit(
+        &self,
+        correlation_id: CorrelationId,
+        prestate_hash: Blake2bHash,
+        effects: AdditiveMap<Key, Transform>,
+    ) -> Result<CommitResult, Self::Error> {
+        let commit_result = commit::<ScratchEnvironment, LmdbTrieStore, _, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            prestate_hash,
+            effects,
+        )?;
+        Ok(commit_result)
+    }
+
+    fn put_protocol_data(
+        &self,
+        protocol_version: ProtocolVersion,
+        protocol_data: &ProtocolData,
+    ) -> Result<(), Self::Error> {
+        let mut txn = self.environment.create_read_write_txn()?;
+        self.protocol_data_store
+            .put(&mut txn, &protocol_version, protocol_data)?;
+        txn.commit().map_err(Into::into)
+    }
+
+    fn get_protocol_data(
+        &self,
+        protocol_version: ProtocolVersion,
+    ) -> Result<Option<ProtocolData>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let result = self.protocol_data_store.get(&txn, &protocol_version)?;
+        txn.commit()?;
+        Ok(result)
+    }
+
+    fn empty_root(&self) -> Blake2bHash {
+        self.empty_root_hash
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lmdb::DatabaseFlags;
+    use tempfile::tempdir;
+
+    use casper_types::{account::AccountHash, CLValue};
+
+    use super::*;
+    use crate::storage::{
+        transaction_source::lmdb::LmdbEnvironment, DEFAULT_TEST_MAX_DB_SIZE,
+        DEFAULT_TEST_MAX_READERS,
+    };
+
+    #[test]
+    fn commit_is_visible_through_scratch_state_only() {
+        let correlation_id = CorrelationId::new();
+        let temp_dir = tempdir().unwrap();
+        let environment = Arc::new(
+            LmdbEnvironment::new(
+                &temp_dir.path().to_path_buf(),
+                DEFAULT_TEST_MAX_DB_SIZE,
+                DEFAULT_TEST_MAX_READERS,
+            )
+            .unwrap(),
+        );
+        let trie_store =
+            Arc::new(LmdbTrieStore::new(&environment, None, DatabaseFlags::empty()).unwrap());
+        let protocol_data_store = Arc::new(
+            LmdbProtocolDataStore::new(&environment, None, DatabaseFlags::empty()).unwrap(),
+        );
+        let state = LmdbGlobalState::empty(environment, trie_store, protocol_data_store).unwrap();
+        let scratch = state.scratch();
+
+        let key = Key::Account(AccountHash::new([1; 32]));
+        let value = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+        let mut effects = AdditiveMap::new();
+        effects.insert(key, Transform::Write(value.clone()));
+        let root_hash = match scratch
+            .commit(correlation_id, state.empty_root(), effects)
+            .unwrap()
+        {
+            CommitResult::Success { state_root } => state_root,
+            commit_result => panic!("commit failed: {:?}", commit_result),
+        };
+
+        let checkout = scratch.checkout(root_hash).unwrap().unwrap();
+        assert_eq!(checkout.read(correlation_id, &key).unwrap(), Some(value));
+        assert!(scratch.environment.written_count().unwrap() > 0);
+        assert!(state.checkout(root_hash).unwrap().is_none());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::shared::wasm_config::WasmConfig;
+use crate::shared::{system_config::SystemConfig, wasm_config::WasmConfig};
 use casper_types::{
     bytesrepr::{self, FromBytes, ToBytes},
     ContractHash, HashAddr,
@@ -11,6 +11,7 @@ const DEFAULT_ADDRESS: [u8; 32] = [0; 32];
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct ProtocolData {
     wasm_config: WasmConfig,
+    system_config: SystemConfig,
     mint: ContractHash,
     proof_of_stake: ContractHash,
     standard_payment: ContractHash,
@@ -25,6 +26,7 @@ impl Default for ProtocolData {
     fn default() -> ProtocolData {
         ProtocolData {
             wasm_config: WasmConfig::default(),
+            system_config: SystemConfig::default(),
             mint: DEFAULT_ADDRESS,
             proof_of_stake: DEFAULT_ADDRESS,
             standard_payment: DEFAULT_ADDRESS,
@@ -34,9 +36,11 @@ impl Default for ProtocolData {
 }
 
 impl ProtocolData {
-    /// Creates a new [`ProtocolData`] value from a given [`WasmCosts`] value.
+    /// Creates a new [`ProtocolData`] value from given [`WasmConfig`] and [`SystemConfig`]
+    /// values.
     pub fn new(
         wasm_config: WasmConfig,
+        system_config: SystemConfig,
         mint: ContractHash,
         proof_of_stake: ContractHash,
         standard_payment: ContractHash,
@@ -44,6 +48,7 @@ impl ProtocolData {
     ) -> Self {
         ProtocolData {
             wasm_config,
+            system_config,
             mint,
             proof_of_stake,
             standard_payment,
@@ -67,11 +72,13 @@ impl ProtocolData {
     /// Used during `commit_genesis` before all system contracts' URefs are known.
     pub fn partial_without_standard_payment(
         wasm_config: WasmConfig,
+        system_config: SystemConfig,
         mint: ContractHash,
         proof_of_stake: ContractHash,
     ) -> Self {
         ProtocolData {
             wasm_config,
+            system_config,
             mint,
             proof_of_stake,
             ..Default::default()
@@ -83,6 +90,11 @@ impl ProtocolData {
         &self.wasm_config
     }
 
+    /// Gets the [`SystemConfig`] value from a given [`ProtocolData`] value.
+    pub fn system_config(&self) -> &SystemConfig {
+        &self.system_config
+    }
+
     pub fn mint(&self) -> ContractHash {
         self.mint
     }
@@ -139,6 +151,7 @@ impl ToBytes for ProtocolData {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         let mut ret = bytesrepr::unchecked_allocate_buffer(self);
         ret.append(&mut self.wasm_config.to_bytes()?);
+        ret.append(&mut self.system_config.to_bytes()?);
         ret.append(&mut self.mint.to_bytes()?);
         ret.append(&mut self.proof_of_stake.to_bytes()?);
         ret.append(&mut self.standard_payment.to_bytes()?);
@@ -148,6 +161,7 @@ impl ToBytes for ProtocolData {
 
     fn serialized_length(&self) -> usize {
         self.wasm_config.serialized_length()
+            + self.system_config.serialized_length()
             + self.mint.serialized_length()
             + self.proof_of_stake.serialized_length()
             + self.standard_payment.serialized_length()
@@ -158,6 +172,7 @@ impl ToBytes for ProtocolData {
 impl FromBytes for ProtocolData {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
         let (wasm_config, rem) = WasmConfig::from_bytes(bytes)?;
+        let (system_config, rem) = SystemConfig::from_bytes(rem)?;
         let (mint, rem) = HashAddr::from_bytes(rem)?;
         let (proof_of_stake, rem) = HashAddr::from_bytes(rem)?;
         let (standard_payment, rem) = HashAddr::from_bytes(rem)?;
@@ -166,6 +181,7 @@ impl FromBytes for ProtocolData {
         Ok((
             ProtocolData {
                 wasm_config,
+                system_config,
                 mint,
                 proof_of_stake,
                 standard_payment,
@@ -180,7 +196,9 @@ impl FromBytes for ProtocolData {
 pub(crate) mod gens {
     use proptest::prop_compose;
 
-    use crate::shared::wasm_config::gens::wasm_config_arb;
+    use crate::shared::{
+        system_config::gens::system_config_arb, wasm_config::gens::wasm_config_arb,
+    };
     use casper_types::gens;
 
     use super::ProtocolData;
@@ -188,6 +206,7 @@ pub(crate) mod gens {
     prop_compose! {
         pub fn protocol_data_arb()(
             wasm_config in wasm_config_arb(),
+            system_config in system_config_arb(),
             mint in gens::u8_slice_32(),
             proof_of_stake in gens::u8_slice_32(),
             standard_payment in gens::u8_slice_32(),
@@ -195,6 +214,7 @@ pub(crate) mod gens {
         ) -> ProtocolData {
             ProtocolData {
                 wasm_config,
+                system_config,
                 mint,
                 proof_of_stake,
                 standard_payment,
@@ -208,7 +228,7 @@ pub(crate) mod gens {
 mod tests {
     use proptest::proptest;
 
-    use crate::shared::wasm_config::WasmConfig;
+    use crate::shared::{system_config::SystemConfig, wasm_config::WasmConfig};
     use casper_types::{bytesrepr, ContractHash};
 
     use super::{gens, ProtocolData};
@@ -223,6 +243,7 @@ mod tests {
             let wasm_config = WasmConfig::default();
             ProtocolData::new(
                 wasm_config,
+                SystemConfig::default(),
                 mint_reference,
                 proof_of_stake_reference,
                 standard_payment_reference,
@@ -256,6 +277,7 @@ mod tests {
             let wasm_config = WasmConfig::default();
             ProtocolData::new(
                 wasm_config,
+                SystemConfig::default(),
                 mint_reference,
                 proof_of_stake_reference,
                 standard_payment_reference,
@@ -0,0 +1,142 @@
+//! A read-write transaction holding its writes in memory until committed.
+
+use std::{collections::BTreeMap, mem};
+
+use crate::storage::transaction_source::{Readable, Transaction, Writable};
+
+/// Wraps a read-write transaction, buffering the values written through it.
+///
+/// Reads see the buffered values.  On commit, the buffered values are written to the wrapped
+/// transaction in one pass, sorted by key and with each key written once, before it is committed
+/// itself.  Buffered values can be discarded beforehand via [`retain`](Self::retain).
+pub struct BufferedTransaction<T: Transaction> {
+    txn: T,
+    writes: Vec<(T::Handle, BTreeMap<Vec<u8>, Vec<u8>>)>,
+}
+
+impl<T: Transaction> BufferedTransaction<T>
+where
+    T::Handle: Clone + PartialEq,
+{
+    /// Wraps `txn`.
+    pub fn new(txn: T) -> Self {
+        BufferedTransaction {
+            txn,
+            writes: Vec::new(),
+        }
+    }
+
+    /// Returns the value buffered under `key` in `handle`, if any.
+    pub fn buffered(&self, handle: &T::Handle, key: &[u8]) -> Option<&[u8]> {
+        self.writes(handle)
+            .and_then(|writes| writes.get(key))
+            .map(Vec::as_slice)
+    }
+
+    /// Returns the number of values buffered in `handle`.
+    pub fn buffered_count(&self, handle: &T::Handle) -> usize {
+        self.writes(handle).map_or(0, BTreeMap::len)
+    }
+
+    /// Discards the values buffered in `handle` for which `keep` returns `false`.
+    pub fn retain<F: FnMut(&[u8]) -> bool>(&mut self, handle: &T::Handle, mut keep: F) {
+        if let Some((_, writes)) = self
+            .writes
+            .iter_mut()
+            .find(|(write_handle, _)| write_handle == handle)
+        {
+            *writes = mem::take(writes)
+                .into_iter()
+                .filter(|(key, _)| keep(key))
+                .collect();
+        }
+    }
+
+    fn writes(&self, handle: &T::Handle) -> Option<&BTreeMap<Vec<u8>, Vec<u8>>> {
+        self.writes
+            .iter()
+            .find(|(write_handle, _)| write_handle == handle)
+            .map(|(_, writes)| writes)
+    }
+}
+
+impl<T> Transaction for BufferedTransaction<T>
+where
+    T: Writable,
+    T::Handle: Clone,
+{
+    type Error = T::Error;
+
+    type Handle = T::Handle;
+
+    fn commit(mut self) -> Result<(), Self::Error> {
+        for (handle, writes) in self.writes {
+            for (key, value) in writes {
+                self.txn.write(handle.clone(), &key, &value)?;
+            }
+        }
+        self.txn.commit()
+    }
+}
+
+impl<T> Readable for BufferedTransaction<T>
+where
+    T: Readable + Writable,
+    T::Handle: Clone + PartialEq,
+{
+    fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+        match self.buffered(&handle, key) {
+            Some(value) => Ok(Some(value.to_vec())),
+            None => self.txn.read(handle, key),
+        }
+    }
+}
+
+impl<T> Writable for BufferedTransaction<T>
+where
+    T: Writable,
+    T::Handle: Clone + PartialEq,
+{
+    fn write(&mut self, handle: Self::Handle, key: &[u8], value: &[u8]) -> Result<(), Self::Error> {
+        let index = match self
+            .writes
+            .iter()
+            .position(|(write_handle, _)| *write_handle == handle)
+        {
+            Some(index) => index,
+            None => {
+                self.writes.push((handle, BTreeMap::new()));
+                self.writes.len() - 1
+            }
+        };
+        let _ = self.writes[index].1.insert(key.to_vec(), value.to_vec());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::transaction_source::{in_memory::InMemoryEnvironment, TransactionSource};
+
+    #[test]
+    fn should_read_buffered_writes_and_commit_retained_ones() {
+        let environment = InMemoryEnvironment::new();
+        let handle: Option<String> = None;
+
+        let mut txn = BufferedTransaction::new(environment.create_read_write_txn().unwrap());
+        txn.write(handle.clone(), b"a", b"1").unwrap();
+        txn.write(handle.clone(), b"b", b"2").unwrap();
+        txn.write(handle.clone(), b"a", b"3").unwrap();
+        assert_eq!(txn.read(handle.clone(), b"a").unwrap(), Some(b"3".to_vec()));
+        assert_eq!(txn.buffered_count(&handle), 2);
+
+        txn.retain(&handle, |key| key == b"a");
+        assert_eq!(txn.read(handle.clone(), b"b").unwrap(), None);
+        txn.commit().unwrap();
+
+        let txn = environment.create_read_txn().unwrap();
+        assert_eq!(txn.read(handle.clone(), b"a").unwrap(), Some(b"3".to_vec()));
+        assert_eq!(txn.read(handle, b"b").unwrap(), None);
+    }
+}
@@ -1,5 +1,7 @@
+pub mod buffered;
 pub mod in_memory;
 pub mod lmdb;
+pub mod scratch;
 
 /// A transaction which can be committed or aborted.
 pub trait Transaction: Sized {
@@ -0,0 +1,158 @@
+//! A source of transactions reading from an LMDB environment, but writing to memory only.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use lmdb::{Database, RoTransaction};
+
+use crate::storage::{
+    error,
+    transaction_source::{
+        buffered::BufferedTransaction, lmdb::LmdbEnvironment, Readable, Transaction,
+        TransactionSource, Writable,
+    },
+};
+
+/// The values written to each database, held in memory.
+type Overlay = Vec<(Database, HashMap<Vec<u8>, Vec<u8>>)>;
+
+fn read_overlay(overlay: &Overlay, handle: Database, key: &[u8]) -> Option<Vec<u8>> {
+    overlay
+        .iter()
+        .find(|(overlay_handle, _)| *overlay_handle == handle)
+        .and_then(|(_, values)| values.get(key))
+        .cloned()
+}
+
+/// Wraps an [`LmdbEnvironment`], holding the values written through its transactions in memory
+/// rather than writing them to LMDB.
+///
+/// Reads see the values written to memory, falling back to those stored in LMDB.  The values
+/// written are dropped along with the environment, leaving LMDB untouched.
+pub struct ScratchEnvironment {
+    environment: Arc<LmdbEnvironment>,
+    overlay: RwLock<Overlay>,
+}
+
+impl ScratchEnvironment {
+    /// Wraps `environment`, with nothing written to memory yet.
+    pub fn new(environment: Arc<LmdbEnvironment>) -> Self {
+        ScratchEnvironment {
+            environment,
+            overlay: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Returns the number of values written to memory.
+    pub fn written_count(&self) -> Result<usize, error::Error> {
+        Ok(self
+            .overlay
+            .read()?
+            .iter()
+            .map(|(_, values)| values.len())
+            .sum())
+    }
+}
+
+/// A read transaction of a [`ScratchEnvironment`].
+pub struct ScratchReadTransaction<'a> {
+    txn: RoTransaction<'a>,
+    overlay: RwLockReadGuard<'a, Overlay>,
+}
+
+impl<'a> Transaction for ScratchReadTransaction<'a> {
+    type Error = error::Error;
+
+    type Handle = Database;
+
+    fn commit(self) -> Result<(), Self::Error> {
+        Transaction::commit(self.txn).map_err(Into::into)
+    }
+}
+
+impl<'a> Readable for ScratchReadTransaction<'a> {
+    fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+        match read_overlay(&self.overlay, handle, key) {
+            Some(value) => Ok(Some(value)),
+            None => self.txn.read(handle, key).map_err(Into::into),
+        }
+    }
+}
+
+/// A transaction writing straight to the memory of a [`ScratchEnvironment`].
+///
+/// It holds the environment's overlay exclusively, so that only one read-write transaction is live
+/// at a time, as for LMDB.
+pub struct ScratchOverlayTransaction<'a> {
+    txn: RoTransaction<'a>,
+    overlay: RwLockWriteGuard<'a, Overlay>,
+}
+
+impl<'a> Transaction for ScratchOverlayTransaction<'a> {
+    type Error = error::Error;
+
+    type Handle = Database;
+
+    fn commit(self) -> Result<(), Self::Error> {
+        Transaction::commit(self.txn).map_err(Into::into)
+    }
+}
+
+impl<'a> Readable for ScratchOverlayTransaction<'a> {
+    fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+        match read_overlay(&self.overlay, handle, key) {
+            Some(value) => Ok(Some(value)),
+            None => self.txn.read(handle, key).map_err(Into::into),
+        }
+    }
+}
+
+impl<'a> Writable for ScratchOverlayTransaction<'a> {
+    fn write(&mut self, handle: Self::Handle, key: &[u8], value: &[u8]) -> Result<(), Self::Error> {
+        let index = match self
+            .overlay
+            .iter()
+            .position(|(overlay_handle, _)| *overlay_handle == handle)
+        {
+            Some(index) => index,
+            None => {
+                self.overlay.push((handle, HashMap::new()));
+                self.overlay.len() - 1
+            }
+        };
+        let _ = self.overlay[index].1.insert(key.to_vec(), value.to_vec());
+        Ok(())
+    }
+}
+
+/// A read-write transaction of a [`ScratchEnvironment`].
+///
+/// Its writes only reach the environment's memory once committed.
+pub type ScratchReadWriteTransaction<'a> = BufferedTransaction<ScratchOverlayTransaction<'a>>;
+
+impl<'a> TransactionSource<'a> for ScratchEnvironment {
+    type Error = error::Error;
+
+    type Handle = Database;
+
+    type ReadTransaction = ScratchReadTransaction<'a>;
+
+    type ReadWriteTransaction = ScratchReadWriteTransaction<'a>;
+
+    fn create_read_txn(&'a self) -> Result<ScratchReadTransaction<'a>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let overlay = self.overlay.read()?;
+        Ok(ScratchReadTransaction { txn, overlay })
+    }
+
+    fn create_read_write_txn(&'a self) -> Result<ScratchReadWriteTransaction<'a>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let overlay = self.overlay.write()?;
+        Ok(BufferedTransaction::new(ScratchOverlayTransaction {
+            txn,
+            overlay,
+        }))
+    }
+}
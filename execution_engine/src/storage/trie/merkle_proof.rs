@@ -155,36 +155,46 @@ where
     ///
     /// The steps in this function reflect `operations::rehash`.
     pub fn compute_state_hash(&self) -> Result<Blake2bHash, bytesrepr::Error> {
-        let mut hash = {
-            let leaf_bytes = Trie::leaf(self.key, self.value.to_owned()).to_bytes()?;
-            Blake2bHash::new(&leaf_bytes)
-        };
+        let leaf = Trie::leaf(self.key, self.value.to_owned());
+        compute_state_hash(&leaf, &self.proof_steps)
+    }
+}
 
-        for (proof_step_index, proof_step) in self.proof_steps.iter().enumerate() {
-            let pointer = if proof_step_index == 0 {
-                Pointer::LeafPointer(hash)
-            } else {
-                Pointer::NodePointer(hash)
-            };
-            let proof_step_bytes = match proof_step {
-                TrieMerkleProofStep::Node {
-                    hole_index,
-                    indexed_pointers_with_hole,
-                } => {
-                    let hole_index = *hole_index;
-                    assert!(hole_index as usize <= RADIX, "hole_index exceeded RADIX");
-                    let mut indexed_pointers = indexed_pointers_with_hole.to_owned();
-                    indexed_pointers.push((hole_index, pointer));
-                    Trie::<K, V>::node(&indexed_pointers).to_bytes()?
-                }
-                TrieMerkleProofStep::Extension { affix } => {
-                    Trie::<K, V>::extension(affix.to_owned(), pointer).to_bytes()?
-                }
-            };
-            hash = Blake2bHash::new(&proof_step_bytes);
-        }
-        Ok(hash)
+/// Recomputes a state root hash from the given trie element and the steps leading from it up to
+/// the root.
+fn compute_state_hash<K, V>(
+    trie: &Trie<K, V>,
+    proof_steps: &VecDeque<TrieMerkleProofStep>,
+) -> Result<Blake2bHash, bytesrepr::Error>
+where
+    K: ToBytes,
+    V: ToBytes,
+{
+    let mut hash = Blake2bHash::new(&trie.to_bytes()?);
+
+    for (proof_step_index, proof_step) in proof_steps.iter().enumerate() {
+        let pointer = match trie {
+            Trie::Leaf { .. } if proof_step_index == 0 => Pointer::LeafPointer(hash),
+            _ => Pointer::NodePointer(hash),
+        };
+        let proof_step_bytes = match proof_step {
+            TrieMerkleProofStep::Node {
+                hole_index,
+                indexed_pointers_with_hole,
+            } => {
+                let hole_index = *hole_index;
+                assert!(hole_index as usize <= RADIX, "hole_index exceeded RADIX");
+                let mut indexed_pointers = indexed_pointers_with_hole.to_owned();
+                indexed_pointers.push((hole_index, pointer));
+                Trie::<K, V>::node(&indexed_pointers).to_bytes()?
+            }
+            TrieMerkleProofStep::Extension { affix } => {
+                Trie::<K, V>::extension(affix.to_owned(), pointer).to_bytes()?
+            }
+        };
+        hash = Blake2bHash::new(&proof_step_bytes);
     }
+    Ok(hash)
 }
 
 impl<K, V> ToBytes for TrieMerkleProof<K, V>
@@ -228,6 +238,142 @@ where
     }
 }
 
+/// A proof that no node with a specified `key` is present in the Merkle trie.
+///
+/// The proof holds the element of the trie at which a lookup of `key` ends without finding it,
+/// along with the steps leading from that element up to the root.  The element is either a node
+/// with no pointer at the index given by the next byte of the key, an extension whose affix
+/// diverges from the key, or a leaf holding a different key.
+///
+/// Given a state hash `x`, one can validate a proof `p` by checking both
+/// `x == p.compute_state_hash()` and `p.proves_absence()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrieMerkleProofOfAbsence<K, V> {
+    key: K,
+    terminal: Trie<K, V>,
+    proof_steps: VecDeque<TrieMerkleProofStep>,
+}
+
+impl<K, V> TrieMerkleProofOfAbsence<K, V> {
+    /// Constructor for [`TrieMerkleProofOfAbsence`]
+    pub fn new(key: K, terminal: Trie<K, V>, proof_steps: VecDeque<TrieMerkleProofStep>) -> Self {
+        TrieMerkleProofOfAbsence {
+            key,
+            terminal,
+            proof_steps,
+        }
+    }
+
+    /// Getter for the key in [`TrieMerkleProofOfAbsence`]
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Getter for the trie element at which the lookup of the key ends
+    pub fn terminal(&self) -> &Trie<K, V> {
+        &self.terminal
+    }
+
+    /// Getter for the proof steps in [`TrieMerkleProofOfAbsence`]
+    pub fn proof_steps(&self) -> &VecDeque<TrieMerkleProofStep> {
+        &self.proof_steps
+    }
+}
+
+impl<K, V> TrieMerkleProofOfAbsence<K, V>
+where
+    K: ToBytes + Eq,
+    V: ToBytes,
+{
+    /// Recomputes a state root hash from a [`TrieMerkleProofOfAbsence`], in the same way as
+    /// [`TrieMerkleProof::compute_state_hash`] but starting from the terminal element rather than
+    /// from a leaf holding the key.
+    pub fn compute_state_hash(&self) -> Result<Blake2bHash, bytesrepr::Error> {
+        compute_state_hash(&self.terminal, &self.proof_steps)
+    }
+
+    /// Returns whether the proof steps follow the path given by the key, and the terminal element
+    /// shows that the key is absent from the end of that path.
+    ///
+    /// This alone doesn't show that the key is absent from the trie; the proof must also hash to
+    /// the expected state hash.
+    pub fn proves_absence(&self) -> Result<bool, bytesrepr::Error> {
+        let path = self.key.to_bytes()?;
+
+        // The steps are ordered from the terminal element up to the root.
+        let mut depth = 0;
+        for proof_step in self.proof_steps.iter().rev() {
+            match proof_step {
+                TrieMerkleProofStep::Node { hole_index, .. } => {
+                    if path.get(depth) != Some(hole_index) {
+                        return Ok(false);
+                    }
+                    depth += 1;
+                }
+                TrieMerkleProofStep::Extension { affix } => {
+                    if path.get(depth..depth + affix.len()) != Some(affix.as_slice()) {
+                        return Ok(false);
+                    }
+                    depth += affix.len();
+                }
+            }
+        }
+
+        let absent = match &self.terminal {
+            Trie::Leaf { key, .. } => *key != self.key,
+            Trie::Node { pointer_block } => match path.get(depth) {
+                Some(index) => pointer_block[*index as usize].is_none(),
+                None => false,
+            },
+            Trie::Extension { affix, .. } => {
+                path.get(depth..depth + affix.len()) != Some(affix.as_slice())
+            }
+        };
+        Ok(absent)
+    }
+}
+
+impl<K, V> ToBytes for TrieMerkleProofOfAbsence<K, V>
+where
+    K: ToBytes,
+    V: ToBytes,
+{
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret: Vec<u8> = bytesrepr::allocate_buffer(self)?;
+        ret.extend(self.key.to_bytes()?);
+        ret.extend(self.terminal.to_bytes()?);
+        ret.extend(self.proof_steps.to_bytes()?);
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.key.serialized_length()
+            + self.terminal.serialized_length()
+            + self.proof_steps.serialized_length()
+    }
+}
+
+impl<K, V> FromBytes for TrieMerkleProofOfAbsence<K, V>
+where
+    K: FromBytes,
+    V: FromBytes,
+{
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (key, rem): (K, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (terminal, rem): (Trie<K, V>, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (proof_steps, rem): (VecDeque<TrieMerkleProofStep>, &[u8]) =
+            FromBytes::from_bytes(rem)?;
+        Ok((
+            TrieMerkleProofOfAbsence {
+                key,
+                terminal,
+                proof_steps,
+            },
+            rem,
+        ))
+    }
+}
+
 #[cfg(test)]
 mod gens {
     use proptest::{collection::vec, prelude::*};
@@ -237,8 +383,8 @@ mod gens {
     use crate::{
         shared::stored_value::{gens::stored_value_arb, StoredValue},
         storage::trie::{
-            gens::trie_pointer_arb,
-            merkle_proof::{TrieMerkleProof, TrieMerkleProofStep},
+            gens::{trie_arb, trie_pointer_arb},
+            merkle_proof::{TrieMerkleProof, TrieMerkleProofOfAbsence, TrieMerkleProofStep},
             RADIX,
         },
     };
@@ -274,6 +420,18 @@ mod gens {
                 TrieMerkleProof::new(key, value, proof_steps.into())
             })
     }
+
+    pub fn trie_merkle_proof_of_absence_arb(
+    ) -> impl Strategy<Value = TrieMerkleProofOfAbsence<Key, StoredValue>> {
+        (
+            key_arb(),
+            trie_arb(),
+            vec(trie_merkle_proof_step_arb(), STEPS_SIZE),
+        )
+            .prop_map(|(key, terminal, proof_steps)| {
+                TrieMerkleProofOfAbsence::new(key, terminal, proof_steps.into())
+            })
+    }
 }
 
 #[cfg(test)]
@@ -298,5 +456,12 @@ mod tests {
         ) {
             bytesrepr::test_serialization_roundtrip(&proof)
         }
+
+        #[test]
+        fn trie_merkle_proof_of_absence_serialization_is_correct(
+            proof in gens::trie_merkle_proof_of_absence_arb()
+        ) {
+            bytesrepr::test_serialization_roundtrip(&proof)
+        }
     }
 }
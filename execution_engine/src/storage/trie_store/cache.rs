@@ -0,0 +1,128 @@
+//! A bounded cache of serialized tries, shared by all readers of a trie store.
+//!
+//! Tries are stored at their hashes, so a cached trie never goes stale.  The cache only needs
+//! bounding, never invalidating, and may be filled ahead of time by reading the tries a later
+//! execution is expected to read.
+
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::Mutex,
+};
+
+use linked_hash_map::LinkedHashMap;
+
+use crate::shared::{metrics, newtypes::Blake2bHash};
+
+/// A cache of serialized tries by hash, evicting the least recently used ones once their total
+/// size exceeds a maximum.
+pub struct TrieCache {
+    max_size: usize,
+    inner: Mutex<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    tries: LinkedHashMap<Blake2bHash, Vec<u8>>,
+    size: usize,
+}
+
+impl TrieCache {
+    /// Creates a cache holding at most `max_size` bytes of serialized tries.
+    pub fn new(max_size: usize) -> Self {
+        TrieCache {
+            max_size,
+            inner: Mutex::new(Inner::default()),
+        }
+    }
+
+    /// Returns a copy of the serialized trie under `hash`, if cached.
+    pub fn get(&self, hash: &Blake2bHash) -> Option<Vec<u8>> {
+        let mut inner = self
+            .inner
+            .lock()
+            .expect("trie cache lock should not be poisoned");
+        let maybe_trie_bytes = inner.tries.get_refresh(hash).cloned();
+        if maybe_trie_bytes.is_some() {
+            metrics::TRIE_CACHE_HITS.inc();
+        } else {
+            metrics::TRIE_CACHE_MISSES.inc();
+        }
+        maybe_trie_bytes
+    }
+
+    /// Caches the serialized trie under `hash`, evicting the least recently used tries if the
+    /// cache grows too large.
+    pub fn insert(&self, hash: Blake2bHash, trie_bytes: Vec<u8>) {
+        if trie_bytes.len() > self.max_size {
+            return;
+        }
+        let mut inner = self
+            .inner
+            .lock()
+            .expect("trie cache lock should not be poisoned");
+        inner.size += trie_bytes.len();
+        if let Some(previous) = inner.tries.insert(hash, trie_bytes) {
+            inner.size -= previous.len();
+        }
+        while inner.size > self.max_size {
+            match inner.tries.pop_front() {
+                Some((_, evicted)) => inner.size -= evicted.len(),
+                None => break,
+            }
+        }
+    }
+
+    /// Returns the total size in bytes of the cached tries.
+    pub fn size(&self) -> usize {
+        self.inner
+            .lock()
+            .expect("trie cache lock should not be poisoned")
+            .size
+    }
+}
+
+impl Debug for TrieCache {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrieCache")
+            .field("max_size", &self.max_size)
+            .field("size", &self.size())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(index: u8) -> Blake2bHash {
+        Blake2bHash::new(&[index])
+    }
+
+    #[test]
+    fn should_evict_least_recently_used_tries() {
+        let cache = TrieCache::new(30);
+        cache.insert(hash(1), vec![1; 10]);
+        cache.insert(hash(2), vec![2; 10]);
+        cache.insert(hash(3), vec![3; 10]);
+        assert_eq!(cache.size(), 30);
+
+        // Reading the first trie makes the second the least recently used.
+        assert_eq!(cache.get(&hash(1)), Some(vec![1; 10]));
+        cache.insert(hash(4), vec![4; 10]);
+        assert_eq!(cache.size(), 30);
+        assert_eq!(cache.get(&hash(2)), None);
+        assert_eq!(cache.get(&hash(1)), Some(vec![1; 10]));
+        assert_eq!(cache.get(&hash(3)), Some(vec![3; 10]));
+        assert_eq!(cache.get(&hash(4)), Some(vec![4; 10]));
+    }
+
+    #[test]
+    fn should_not_cache_tries_larger_than_the_cache() {
+        let cache = TrieCache::new(10);
+        cache.insert(hash(1), vec![1; 5]);
+        cache.insert(hash(2), vec![2; 11]);
+        assert_eq!(cache.size(), 5);
+        assert_eq!(cache.get(&hash(1)), Some(vec![1; 5]));
+        assert_eq!(cache.get(&hash(2)), None);
+    }
+}
@@ -104,16 +104,20 @@
 //! tmp_dir.close().unwrap();
 //! ```
 
+use std::sync::Arc;
+
 use lmdb::{Database, DatabaseFlags};
 
+use casper_types::bytesrepr::{self, FromBytes, ToBytes};
+
 use crate::shared::newtypes::Blake2bHash;
 
 use crate::storage::{
     error,
     store::Store,
-    transaction_source::lmdb::LmdbEnvironment,
+    transaction_source::{lmdb::LmdbEnvironment, Readable},
     trie::Trie,
-    trie_store::{self, TrieStore},
+    trie_store::{self, cache::TrieCache, TrieStore},
 };
 
 /// An LMDB-backed trie store.
@@ -122,6 +126,7 @@ use crate::storage::{
 #[derive(Debug, Clone)]
 pub struct LmdbTrieStore {
     db: Database,
+    cache: Option<Arc<TrieCache>>,
 }
 
 impl LmdbTrieStore {
@@ -132,13 +137,19 @@ impl LmdbTrieStore {
     ) -> Result<Self, error::Error> {
         let name = Self::name(maybe_name);
         let db = env.env().create_db(Some(&name), flags)?;
-        Ok(LmdbTrieStore { db })
+        Ok(LmdbTrieStore { db, cache: None })
     }
 
     pub fn open(env: &LmdbEnvironment, maybe_name: Option<&str>) -> Result<Self, error::Error> {
         let name = Self::name(maybe_name);
         let db = env.env().open_db(Some(&name))?;
-        Ok(LmdbTrieStore { db })
+        Ok(LmdbTrieStore { db, cache: None })
+    }
+
+    /// Sets a cache of the tries read from the store, consulted before reading from the store.
+    pub fn with_cache(mut self, cache: Arc<TrieCache>) -> Self {
+        self.cache = Some(cache);
+        self
     }
 
     fn name(maybe_name: Option<&str>) -> String {
@@ -156,6 +167,27 @@ impl<K, V> Store<Blake2bHash, Trie<K, V>> for LmdbTrieStore {
     fn handle(&self) -> Self::Handle {
         self.db
     }
+
+    fn get<T>(&self, txn: &T, key: &Blake2bHash) -> Result<Option<Trie<K, V>>, Self::Error>
+    where
+        T: Readable<Handle = Self::Handle>,
+        Blake2bHash: ToBytes,
+        Trie<K, V>: FromBytes,
+        Self::Error: From<T::Error>,
+    {
+        if let Some(trie_bytes) = self.cache.as_ref().and_then(|cache| cache.get(key)) {
+            return Ok(Some(bytesrepr::deserialize(trie_bytes)?));
+        }
+        match txn.read(self.db, &key.to_bytes()?)? {
+            None => Ok(None),
+            Some(trie_bytes) => {
+                if let Some(cache) = &self.cache {
+                    cache.insert(*key, trie_bytes.clone());
+                }
+                Ok(Some(bytesrepr::deserialize(trie_bytes)?))
+            }
+        }
+    }
 }
 
 impl<K, V> TrieStore<K, V> for LmdbTrieStore {}
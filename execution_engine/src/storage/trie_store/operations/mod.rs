@@ -3,14 +3,19 @@ mod tests;
 
 use std::{cmp, collections::VecDeque, convert::TryInto, mem};
 
+use tracing::trace_span;
+
 use casper_types::bytesrepr::{self, FromBytes, ToBytes};
 
 use crate::{
-    shared::newtypes::{Blake2bHash, CorrelationId},
+    shared::{
+        metrics,
+        newtypes::{Blake2bHash, CorrelationId},
+    },
     storage::{
         transaction_source::{Readable, Writable},
         trie::{
-            merkle_proof::{TrieMerkleProof, TrieMerkleProofStep},
+            merkle_proof::{TrieMerkleProof, TrieMerkleProofOfAbsence, TrieMerkleProofStep},
             Parents, Pointer, Trie, RADIX, USIZE_EXCEEDS_U8,
         },
         trie_store::TrieStore,
@@ -40,6 +45,10 @@ where
     S::Error: From<T::Error>,
     E: From<S::Error> + From<bytesrepr::Error>,
 {
+    let span = trace_span!("trie_read");
+    let _entered = span.enter();
+    let _timer = metrics::TRIE_READ.start_timer();
+
     let path: Vec<u8> = key.to_bytes()?;
 
     let mut depth: usize = 0;
@@ -117,29 +126,66 @@ where
 
 /// Same as [`read`], except that a [`TrieMerkleProof`] is generated and returned along with the key
 /// and the value given the root and store.
-#[allow(unused)] // TODO: Use this
 pub fn read_with_proof<K, V, T, S, E>(
-    _correlation_id: CorrelationId,
+    correlation_id: CorrelationId,
     txn: &T,
     store: &S,
     root: &Blake2bHash,
     key: &K,
 ) -> Result<ReadResult<TrieMerkleProof<K, V>>, E>
 where
-    K: ToBytes + FromBytes + Eq + std::fmt::Debug,
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<bytesrepr::Error>,
+{
+    let read_result =
+        match read_with_proof_of_absence::<K, V, T, S, E>(correlation_id, txn, store, root, key)? {
+            ProvenReadResult::Found(proof) => ReadResult::Found(proof),
+            ProvenReadResult::NotFound(_) => ReadResult::NotFound,
+            ProvenReadResult::RootNotFound => ReadResult::RootNotFound,
+        };
+    Ok(read_result)
+}
+
+/// The result of a read which proves either the presence or the absence of the key.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ProvenReadResult<K, V> {
+    Found(TrieMerkleProof<K, V>),
+    NotFound(TrieMerkleProofOfAbsence<K, V>),
+    RootNotFound,
+}
+
+/// Same as [`read_with_proof`], except that if the key is not found, a
+/// [`TrieMerkleProofOfAbsence`] is generated and returned instead.
+pub fn read_with_proof_of_absence<K, V, T, S, E>(
+    _correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    root: &Blake2bHash,
+    key: &K,
+) -> Result<ProvenReadResult<K, V>, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
     V: ToBytes + FromBytes,
     T: Readable<Handle = S::Handle>,
     S: TrieStore<K, V>,
     S::Error: From<T::Error>,
     E: From<S::Error> + From<bytesrepr::Error>,
 {
+    let span = trace_span!("trie_read_with_proof");
+    let _entered = span.enter();
+    let _timer = metrics::TRIE_READ.start_timer();
+
     let mut proof_steps = VecDeque::new();
     let path: Vec<u8> = key.to_bytes()?;
 
     let mut depth: usize = 0;
     let mut current: Trie<K, V> = match store.get(txn, root)? {
         Some(root) => root,
-        None => return Ok(ReadResult::RootNotFound),
+        None => return Ok(ProvenReadResult::RootNotFound),
     };
     loop {
         match current {
@@ -148,10 +194,15 @@ where
                 value,
             } => {
                 if *key != leaf_key {
-                    return Ok(ReadResult::NotFound);
+                    let terminal = Trie::leaf(leaf_key, value);
+                    return Ok(ProvenReadResult::NotFound(TrieMerkleProofOfAbsence::new(
+                        key.clone(),
+                        terminal,
+                        proof_steps,
+                    )));
                 }
                 let key = leaf_key;
-                return Ok(ReadResult::Found(TrieMerkleProof::new(
+                return Ok(ProvenReadResult::Found(TrieMerkleProof::new(
                     key,
                     value,
                     proof_steps,
@@ -164,9 +215,17 @@ where
                 };
                 let pointer: Pointer = {
                     assert!(hole_index < RADIX, "key length must be < {}", RADIX);
-                    match pointer_block[hole_index] {
+                    let maybe_pointer = pointer_block[hole_index];
+                    match maybe_pointer {
                         Some(pointer) => pointer,
-                        None => return Ok(ReadResult::NotFound),
+                        None => {
+                            let terminal = Trie::Node { pointer_block };
+                            return Ok(ProvenReadResult::NotFound(TrieMerkleProofOfAbsence::new(
+                                key.clone(),
+                                terminal,
+                                proof_steps,
+                            )));
+                        }
                     }
                 };
                 let indexed_pointers_with_hole = pointer_block
@@ -192,9 +251,13 @@ where
                 ));
             }
             Trie::Extension { affix, pointer } => {
-                let sub_path = &path[depth..depth + affix.len()];
-                if sub_path != affix.as_slice() {
-                    return Ok(ReadResult::NotFound);
+                if path.get(depth..depth + affix.len()) != Some(affix.as_slice()) {
+                    let terminal = Trie::extension(affix, pointer);
+                    return Ok(ProvenReadResult::NotFound(TrieMerkleProofOfAbsence::new(
+                        key.clone(),
+                        terminal,
+                        proof_steps,
+                    )));
                 };
 
                 let next = match store.get(txn, pointer.hash())? {
@@ -578,6 +641,10 @@ where
     S::Error: From<T::Error>,
     E: From<S::Error> + From<bytesrepr::Error>,
 {
+    let span = trace_span!("trie_write");
+    let _entered = span.enter();
+    let _timer = metrics::TRIE_WRITE.start_timer();
+
     match store.get(txn, root)? {
         None => Ok(WriteResult::RootNotFound),
         Some(current_root) => {
@@ -656,7 +723,6 @@ enum KeysIteratorState<K, V, S: TrieStore<K, V>> {
     /// Iterate normally
     Ok,
     /// Return the error and stop iterating
-    #[allow(dead_code)] // Return variant alone is used in testing.
     ReturnError(S::Error),
     /// Already failed, only return None
     Failed,
@@ -818,8 +884,8 @@ where
 
 /// Returns the iterator over the keys in the subtrie matching `prefix`.
 ///
-/// The root should be the apex of the trie.
-#[cfg(test)]
+/// The root should be the apex of the trie.  Keys are returned in the lexicographic order of their
+/// serialized representation.
 pub fn keys_with_prefix<'a, 'b, K, V, T, S>(
     _correlation_id: CorrelationId,
     txn: &'b T,
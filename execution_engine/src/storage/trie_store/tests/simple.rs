@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use lmdb::DatabaseFlags;
 use tempfile::tempdir;
 
@@ -6,12 +8,12 @@ use casper_types::bytesrepr::{self, FromBytes, ToBytes};
 use super::TestData;
 use crate::storage::{
     error::{self, in_memory},
-    store::StoreExt,
+    store::{Store, StoreExt},
     transaction_source::{
         in_memory::InMemoryEnvironment, lmdb::LmdbEnvironment, Transaction, TransactionSource,
     },
     trie::Trie,
-    trie_store::{in_memory::InMemoryTrieStore, lmdb::LmdbTrieStore, TrieStore},
+    trie_store::{cache::TrieCache, in_memory::InMemoryTrieStore, lmdb::LmdbTrieStore, TrieStore},
     DEFAULT_TEST_MAX_DB_SIZE, DEFAULT_TEST_MAX_READERS,
 };
 
@@ -129,6 +131,45 @@ fn lmdb_put_get_succeeds() {
     tmp_dir.close().unwrap();
 }
 
+#[test]
+fn lmdb_get_reads_through_cache() {
+    let tmp_dir = tempdir().unwrap();
+    let env = LmdbEnvironment::new(
+        &tmp_dir.path().to_path_buf(),
+        DEFAULT_TEST_MAX_DB_SIZE,
+        DEFAULT_TEST_MAX_READERS,
+    )
+    .unwrap();
+    let cache = Arc::new(TrieCache::new(1024 * 1024));
+    let store = LmdbTrieStore::new(&env, None, DatabaseFlags::empty())
+        .unwrap()
+        .with_cache(Arc::clone(&cache));
+    let TestData(hash, trie) = super::create_data().remove(0);
+
+    let mut txn = env.create_read_write_txn().unwrap();
+    store.put(&mut txn, &hash, &trie).unwrap();
+    txn.commit().unwrap();
+    assert_eq!(cache.size(), 0);
+
+    let txn = env.create_read_txn().unwrap();
+    let maybe_trie: Option<Trie<Vec<u8>, Vec<u8>>> = store.get(&txn, &hash).unwrap();
+    txn.commit().unwrap();
+    assert_eq!(maybe_trie.as_ref(), Some(&trie));
+    assert_eq!(cache.size(), trie.to_bytes().unwrap().len());
+
+    // A store sharing the cache reads the trie from the cache, though it was never put in its own
+    // database.
+    let other_store = LmdbTrieStore::new(&env, Some("other"), DatabaseFlags::empty())
+        .unwrap()
+        .with_cache(cache);
+    let txn = env.create_read_txn().unwrap();
+    let maybe_trie: Option<Trie<Vec<u8>, Vec<u8>>> = other_store.get(&txn, &hash).unwrap();
+    txn.commit().unwrap();
+    assert_eq!(maybe_trie, Some(trie));
+
+    tmp_dir.close().unwrap();
+}
+
 #[test]
 fn in_memory_put_get_many_succeeds() {
     let env = InMemoryEnvironment::new();
@@ -87,6 +87,8 @@ impl TryFrom<UpgradeRequest> for UpgradeConfig {
             upgrade_installer_args,
             upgrade_installer_bytes,
             wasm_config,
+            // System contract costs aren't part of the IPC upgrade point; keep the current ones.
+            None,
             activation_point,
             new_validator_slots,
             new_auction_delay,
@@ -13,6 +13,7 @@ impl From<WasmConfig> for ipc::ChainSpec_WasmConfig {
         pb_wasmconfig.set_max_stack_height(wasm_config.max_stack_height);
         pb_wasmconfig.set_opcode_costs(wasm_config.opcode_costs().into());
         pb_wasmconfig.set_storage_costs(wasm_config.storage_costs().into());
+        pb_wasmconfig.set_strict_determinism(wasm_config.strict_determinism);
         pb_wasmconfig.set_host_function_costs(wasm_config.take_host_function_costs().into());
 
         pb_wasmconfig
@@ -29,7 +30,8 @@ impl TryFrom<ipc::ChainSpec_WasmConfig> for WasmConfig {
             pb_wasm_config.take_opcode_costs().into(),
             pb_wasm_config.take_storage_costs().into(),
             pb_wasm_config.take_host_function_costs().try_into()?,
-        ))
+        )
+        .with_strict_determinism(pb_wasm_config.strict_determinism))
     }
 }
 
@@ -118,6 +118,13 @@ where
                 result.set_failure(msg);
                 result
             }
+            Ok(QueryResult::Absent { .. }) => {
+                let log_message = "Value not found";
+                info!("{}", log_message);
+                let mut result = ipc::QueryResponse::new();
+                result.set_failure(log_message.to_string());
+                result
+            }
             Ok(QueryResult::RootNotFound) => {
                 let log_message = "Root not found";
                 info!("{}", log_message);
@@ -11,7 +11,9 @@ use casper_execution_engine::{
         runtime::{self, Runtime},
         runtime_context::RuntimeContext,
     },
-    shared::{gas::Gas, newtypes::CorrelationId, wasm_prep::Preprocessor},
+    shared::{
+        gas::Gas, newtypes::CorrelationId, system_config::SystemConfig, wasm_prep::Preprocessor,
+    },
     storage::{global_state::StateProvider, protocol_data::ProtocolData},
 };
 use casper_types::{
@@ -91,7 +93,14 @@ where
         let pos = builder.get_mint_contract_hash();
         let standard_payment = builder.get_standard_payment_contract_hash();
         let auction = builder.get_auction_contract_hash();
-        ProtocolData::new(*DEFAULT_WASM_CONFIG, mint, pos, standard_payment, auction)
+        ProtocolData::new(
+            *DEFAULT_WASM_CONFIG,
+            SystemConfig::default(),
+            mint,
+            pos,
+            standard_payment,
+            auction,
+        )
     };
 
     let transfers = Vec::default();
@@ -59,8 +59,9 @@ use casper_types::{
     Key, RuntimeArgs, Transfer, TransferAddr, URef, U512,
 };
 
-use crate::internal::{
-    utils, ExecuteRequestBuilder, DEFAULT_PROPOSER_ADDR, DEFAULT_PROTOCOL_VERSION,
+use crate::{
+    internal::{utils, ExecuteRequestBuilder, DEFAULT_PROPOSER_ADDR, DEFAULT_PROTOCOL_VERSION},
+    StateSnapshot,
 };
 
 /// LMDB initial map size is calculated based on DEFAULT_LMDB_PAGES and systems page size.
@@ -186,6 +187,21 @@ impl InMemoryWasmTestBuilder {
             ..Default::default()
         }
     }
+
+    /// Creates a builder whose global state holds only the entries of `snapshot`.
+    ///
+    /// Genesis is not run, so only the captured accounts and contracts are present.  To execute
+    /// deploys against them, run genesis on a default builder and then call
+    /// [`commit_state_snapshot`](WasmTestBuilder::commit_state_snapshot) instead.
+    pub fn from_state_snapshot(snapshot: &StateSnapshot) -> Self {
+        let global_state = InMemoryGlobalState::empty().expect("should create global state");
+        let empty_root_hash = global_state.empty_root_hash.to_vec();
+        let engine_config =
+            EngineConfig::new().with_use_system_contracts(cfg!(feature = "use-system-contracts"));
+        let mut builder = Self::new(global_state, engine_config, empty_root_hash);
+        builder.commit_state_snapshot(snapshot);
+        builder
+    }
 }
 
 impl LmdbWasmTestBuilder {
@@ -531,6 +547,14 @@ where
         self
     }
 
+    /// Writes the entries of `snapshot` over the current post state, replacing any values already
+    /// stored under their keys.
+    pub fn commit_state_snapshot(&mut self, snapshot: &StateSnapshot) -> &mut Self {
+        let effects = snapshot.effects().expect("should decode state snapshot");
+        let prestate_hash = self.get_post_state_hash();
+        self.commit_effects(prestate_hash, effects)
+    }
+
     pub fn upgrade_with_upgrade_request(
         &mut self,
         upgrade_request: &mut UpgradeRequest,
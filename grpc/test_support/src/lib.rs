@@ -71,6 +71,7 @@ mod error;
 #[doc(hidden)]
 pub mod internal;
 mod session;
+mod state_snapshot;
 mod test_context;
 mod value;
 
@@ -79,6 +80,7 @@ pub use casper_types::account::AccountHash;
 pub use code::Code;
 pub use error::{Error, Result};
 pub use session::{Session, SessionBuilder, SessionTransferInfo};
+pub use state_snapshot::{StateSnapshot, StateSnapshotEntry};
 pub use test_context::{TestContext, TestContextBuilder};
 pub use value::Value;
 
@@ -0,0 +1,111 @@
+use std::{fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use casper_execution_engine::shared::{
+    additive_map::AdditiveMap, stored_value::StoredValue, transform::Transform,
+};
+use casper_types::{bytesrepr, Key};
+
+use crate::{Error, Result};
+
+/// A value stored in global state under a given key, as held by a [`StateSnapshot`].
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct StateSnapshotEntry {
+    /// The formatted key, e.g. `"account-hash-0102..."` or `"hash-0102..."`.
+    pub key: String,
+    /// The hex-encoded, `bytesrepr`-serialized stored value.
+    pub value: String,
+}
+
+/// A reduced snapshot of global state, holding selected entries read from a live network.
+///
+/// Snapshots are captured with the client's `get-state-snapshot` subcommand, which writes them as
+/// JSON.  Once loaded into a test builder, contracts can be exercised against the real shapes of
+/// the captured accounts and contracts.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct StateSnapshot {
+    /// The hex-encoded state root hash of the global state the entries were read from.
+    pub state_root_hash: String,
+    /// The captured entries.
+    pub entries: Vec<StateSnapshotEntry>,
+}
+
+impl StateSnapshot {
+    /// Reads a snapshot from the JSON file at `path`.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let json = fs::read_to_string(path).map_err(|error| {
+            Error::from(format!("failed to read {}: {}", path.display(), error))
+        })?;
+        Self::from_json(&json)
+    }
+
+    /// Parses a snapshot from JSON.
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json)
+            .map_err(|error| Error::from(format!("failed to parse state snapshot: {}", error)))
+    }
+
+    /// Returns the decoded keys and stored values of the snapshot's entries.
+    pub fn values(&self) -> Result<Vec<(Key, StoredValue)>> {
+        self.entries
+            .iter()
+            .map(|entry| {
+                let key = Key::from_formatted_str(&entry.key).map_err(|error| {
+                    Error::from(format!("invalid key {}: {:?}", entry.key, error))
+                })?;
+                let bytes = hex::decode(&entry.value).map_err(|error| {
+                    Error::from(format!("invalid value of {}: {}", entry.key, error))
+                })?;
+                let value: StoredValue = bytesrepr::deserialize(bytes).map_err(|error| {
+                    Error::from(format!("invalid value of {}: {}", entry.key, error))
+                })?;
+                Ok((key.normalize(), value))
+            })
+            .collect()
+    }
+
+    /// Returns the effects writing each of the snapshot's entries to global state.
+    pub fn effects(&self) -> Result<AdditiveMap<Key, Transform>> {
+        let mut effects = AdditiveMap::new();
+        for (key, value) in self.values()? {
+            effects.insert(key, Transform::Write(value));
+        }
+        Ok(effects)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{bytesrepr::ToBytes, AccessRights, CLValue, URef, U512};
+
+    use super::*;
+
+    #[test]
+    fn should_decode_entries_into_writes() {
+        let uref = URef::new([1; 32], AccessRights::READ_ADD_WRITE);
+        let value = StoredValue::CLValue(CLValue::from_t(U512::from(7)).unwrap());
+        let json = format!(
+            r#"{{"state_root_hash":"00","entries":[{{"key":"{}","value":"{}"}}]}}"#,
+            uref.to_formatted_string(),
+            hex::encode(value.to_bytes().unwrap())
+        );
+
+        let snapshot = StateSnapshot::from_json(&json).unwrap();
+        let effects = snapshot.effects().unwrap();
+
+        assert_eq!(effects.len(), 1);
+        assert_eq!(
+            effects.get(&Key::URef(uref.remove_access_rights())),
+            Some(&Transform::Write(value))
+        );
+    }
+
+    #[test]
+    fn should_reject_invalid_values() {
+        let json = r#"{"state_root_hash":"00","entries":[{"key":"hash-0000000000000000000000000000000000000000000000000000000000000000","value":"ff"}]}"#;
+        let snapshot = StateSnapshot::from_json(json).unwrap();
+        assert!(snapshot.effects().is_err());
+    }
+}
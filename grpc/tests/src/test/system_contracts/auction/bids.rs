@@ -1,5 +1,6 @@
 use std::{collections::BTreeSet, iter::FromIterator};
 
+use ed25519_dalek::ExpandedSecretKey;
 use lazy_static::lazy_static;
 
 use casper_engine_test_support::{
@@ -14,10 +15,12 @@ use casper_types::{
     self,
     account::AccountHash,
     auction::{
-        Bids, DelegationRate, EraId, EraValidators, SeigniorageRecipients, UnbondingPurses,
-        ValidatorWeights, ARG_AMOUNT, ARG_DELEGATION_RATE, ARG_DELEGATOR, ARG_PUBLIC_KEY,
+        self, Bids, DelegationRate, EraId, EraValidators, SeigniorageRecipients,
+        SeigniorageRecipientsSnapshot, UnbondingPurses, ValidatorWeights, ARG_AMOUNT,
+        ARG_DELEGATION_RATE, ARG_DELEGATOR, ARG_NEW_SIGNING_KEY, ARG_PUBLIC_KEY, ARG_SIGNATURE,
         ARG_UNBOND_PURSE, ARG_VALIDATOR, BIDS_KEY, DEFAULT_UNBONDING_DELAY, ERA_ID_KEY,
-        INITIAL_ERA_ID, METHOD_RUN_AUCTION, UNBONDING_PURSES_KEY,
+        INITIAL_ERA_ID, METHOD_RUN_AUCTION, SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY,
+        UNBONDING_PURSES_KEY,
     },
     runtime_args, PublicKey, RuntimeArgs, URef, U512,
 };
@@ -31,6 +34,7 @@ const CONTRACT_WITHDRAW_BID: &str = "withdraw_bid.wasm";
 const CONTRACT_DELEGATE: &str = "delegate.wasm";
 const CONTRACT_UNDELEGATE: &str = "undelegate.wasm";
 const CONTRACT_CREATE_PURSE_01: &str = "create_purse_01.wasm";
+const CONTRACT_ROTATE_SIGNING_KEY: &str = "rotate_signing_key.wasm";
 
 const TRANSFER_AMOUNT: u64 = MINIMUM_ACCOUNT_CREATION_BALANCE + 1000;
 const SYSTEM_ADDR: AccountHash = AccountHash::new([0u8; 32]);
@@ -70,6 +74,8 @@ const BID_ACCOUNT_2_PK: PublicKey = PublicKey::Ed25519([206; 32]);
 const BID_ACCOUNT_2_BALANCE: u64 = MINIMUM_ACCOUNT_CREATION_BALANCE;
 const BID_ACCOUNT_2_BOND: u64 = 0;
 
+const NEW_SIGNING_SECRET_KEY: [u8; 32] = [208; 32];
+
 lazy_static! {
     static ref NON_FOUNDER_VALIDATOR_1_ADDR: AccountHash = NON_FOUNDER_VALIDATOR_1_PK.into();
     static ref NON_FOUNDER_VALIDATOR_2_ADDR: AccountHash = NON_FOUNDER_VALIDATOR_2_PK.into();
@@ -77,6 +83,21 @@ lazy_static! {
     static ref ACCOUNT_2_ADDR: AccountHash = ACCOUNT_2_PK.into();
     static ref BID_ACCOUNT_1_ADDR: AccountHash = BID_ACCOUNT_1_PK.into();
     static ref BID_ACCOUNT_2_ADDR: AccountHash = BID_ACCOUNT_2_PK.into();
+    static ref NEW_SIGNING_KEY_PK: PublicKey = {
+        let secret_key = ed25519_dalek::SecretKey::from_bytes(&NEW_SIGNING_SECRET_KEY).unwrap();
+        PublicKey::Ed25519(ed25519_dalek::PublicKey::from(&secret_key).to_bytes())
+    };
+}
+
+/// Signs the rotation of the signing key of the bid of `public_key` to `NEW_SIGNING_KEY_PK` in
+/// era `era_id`.
+fn sign_rotation(public_key: PublicKey, era_id: EraId) -> Vec<u8> {
+    let secret_key = ed25519_dalek::SecretKey::from_bytes(&NEW_SIGNING_SECRET_KEY).unwrap();
+    let message = auction::rotate_signing_key_message(public_key, era_id);
+    ExpandedSecretKey::from(&secret_key)
+        .sign(&message, &ed25519_dalek::PublicKey::from(&secret_key))
+        .to_bytes()
+        .to_vec()
 }
 
 const UNBONDING_PURSE_NAME_1: &str = "unbonding_purse_1";
@@ -1393,3 +1414,158 @@ fn fully_undelegated_funds_should_be_released() {
         U512::from(DELEGATE_AMOUNT_1)
     )
 }
+
+#[ignore]
+#[test]
+fn should_rotate_signing_key() {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        let account_1 = GenesisAccount::new(
+            ACCOUNT_1_PK,
+            *ACCOUNT_1_ADDR,
+            Motes::new(ACCOUNT_1_BALANCE.into()),
+            Motes::new(ACCOUNT_1_BOND.into()),
+        );
+        let account_2 = GenesisAccount::new(
+            BID_ACCOUNT_1_PK,
+            *BID_ACCOUNT_1_ADDR,
+            Motes::new(BID_ACCOUNT_1_BALANCE.into()),
+            Motes::new(BID_ACCOUNT_1_BOND.into()),
+        );
+        tmp.push(account_1);
+        tmp.push(account_2);
+        tmp
+    };
+
+    let run_genesis_request = utils::create_run_genesis_request(accounts);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&run_genesis_request);
+
+    let transfer_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            "target" => SYSTEM_ADDR,
+            ARG_AMOUNT => U512::from(TRANSFER_AMOUNT)
+        },
+    )
+    .build();
+    builder.exec(transfer_request).commit().expect_success();
+
+    let add_bid_request = ExecuteRequestBuilder::standard(
+        *BID_ACCOUNT_1_ADDR,
+        CONTRACT_ADD_BID,
+        runtime_args! {
+            ARG_PUBLIC_KEY => BID_ACCOUNT_1_PK,
+            ARG_AMOUNT => U512::from(ADD_BID_AMOUNT_1),
+            ARG_DELEGATION_RATE => ADD_BID_DELEGATION_RATE_1,
+        },
+    )
+    .build();
+    builder.exec(add_bid_request).commit().expect_success();
+
+    let auction_hash = builder.get_auction_contract_hash();
+    let era_id: EraId = builder.get_value(auction_hash, ERA_ID_KEY);
+
+    // The new key has to sign the rotation for the current era.
+    let replayed_rotate_request = ExecuteRequestBuilder::standard(
+        *BID_ACCOUNT_1_ADDR,
+        CONTRACT_ROTATE_SIGNING_KEY,
+        runtime_args! {
+            ARG_PUBLIC_KEY => BID_ACCOUNT_1_PK,
+            ARG_NEW_SIGNING_KEY => *NEW_SIGNING_KEY_PK,
+            ARG_SIGNATURE => sign_rotation(BID_ACCOUNT_1_PK, era_id + 1),
+        },
+    )
+    .build();
+    assert!(builder.exec(replayed_rotate_request).is_error());
+
+    let rotate_request = ExecuteRequestBuilder::standard(
+        *BID_ACCOUNT_1_ADDR,
+        CONTRACT_ROTATE_SIGNING_KEY,
+        runtime_args! {
+            ARG_PUBLIC_KEY => BID_ACCOUNT_1_PK,
+            ARG_NEW_SIGNING_KEY => *NEW_SIGNING_KEY_PK,
+            ARG_SIGNATURE => sign_rotation(BID_ACCOUNT_1_PK, era_id),
+        },
+    )
+    .build();
+    builder.exec(rotate_request).commit().expect_success();
+
+    // The bid keeps its stake and gains the new signing key.
+    let bids: Bids = builder.get_value(auction_hash, BIDS_KEY);
+    let bid = bids.get(&BID_ACCOUNT_1_PK).expect("should have bid");
+    assert_eq!(bid.signing_key(), Some(&*NEW_SIGNING_KEY_PK));
+    assert_eq!(
+        builder.get_purse_balance(*bid.bonding_purse()),
+        U512::from(ADD_BID_AMOUNT_1)
+    );
+
+    // Another validator can't take over the same signing key, even with its signature.
+    let conflicting_rotate_request = ExecuteRequestBuilder::standard(
+        *ACCOUNT_1_ADDR,
+        CONTRACT_ROTATE_SIGNING_KEY,
+        runtime_args! {
+            ARG_PUBLIC_KEY => ACCOUNT_1_PK,
+            ARG_NEW_SIGNING_KEY => *NEW_SIGNING_KEY_PK,
+            ARG_SIGNATURE => sign_rotation(ACCOUNT_1_PK, era_id),
+        },
+    )
+    .build();
+    assert!(builder.exec(conflicting_rotate_request).is_error());
+
+    let run_auction_request = ExecuteRequestBuilder::standard(
+        SYSTEM_ADDR,
+        CONTRACT_AUCTION_BIDS,
+        runtime_args! {
+            ARG_ENTRY_POINT => ARG_RUN_AUCTION,
+        },
+    )
+    .build();
+    builder.exec(run_auction_request).commit().expect_success();
+
+    // The era selected by the auction is validated with the new signing key.
+    let post_era_id: EraId = builder.get_value(auction_hash, ERA_ID_KEY);
+    let validator_weights = builder
+        .get_validator_weights(post_era_id + DEFAULT_AUCTION_DELAY)
+        .expect("should have validator weights");
+    assert_eq!(
+        validator_weights.get(&*NEW_SIGNING_KEY_PK),
+        Some(&U512::from(ADD_BID_AMOUNT_1))
+    );
+    assert!(!validator_weights.contains_key(&BID_ACCOUNT_1_PK));
+}
+
+#[ignore]
+#[test]
+fn should_compute_era_validators_from_seigniorage_recipients_snapshot() {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        tmp.push(GenesisAccount::new(
+            ACCOUNT_1_PK,
+            *ACCOUNT_1_ADDR,
+            Motes::new(ACCOUNT_1_BALANCE.into()),
+            Motes::new(ACCOUNT_1_BOND.into()),
+        ));
+        tmp
+    };
+
+    let run_genesis_request = utils::create_run_genesis_request(accounts);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&run_genesis_request);
+
+    let auction_hash = builder.get_auction_contract_hash();
+    let snapshot: SeigniorageRecipientsSnapshot =
+        builder.get_value(auction_hash, SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY);
+
+    let era_validators: EraValidators = builder.get_era_validators();
+    assert_eq!(era_validators.len(), snapshot.len());
+    assert_eq!(
+        era_validators,
+        casper_types::auction::era_validators_from_snapshot(snapshot)
+    );
+}
@@ -12,9 +12,9 @@ use casper_execution_engine::core::engine_state::EngineConfig;
 use casper_types::{
     account::AccountHash,
     auction::{
-        ARG_AUCTION_DELAY, ARG_GENESIS_VALIDATORS, ARG_LOCKED_FUNDS_PERIOD,
+        ARG_AUCTION_DELAY, ARG_GENESIS_DELEGATORS, ARG_GENESIS_VALIDATORS, ARG_LOCKED_FUNDS_PERIOD,
         ARG_MINT_CONTRACT_PACKAGE_HASH, ARG_VALIDATOR_SLOTS, AUCTION_DELAY_KEY, BIDS_KEY,
-        DELEGATOR_REWARD_PURSE_KEY, ERA_ID_KEY, LOCKED_FUNDS_PERIOD_KEY,
+        DELEGATOR_REWARD_PURSE_KEY, ERA_ID_KEY, ERA_INFO_KEY, LOCKED_FUNDS_PERIOD_KEY,
         SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY, UNBONDING_PURSES_KEY, VALIDATOR_REWARD_PURSE_KEY,
     },
     runtime_args, ContractHash, RuntimeArgs, U512,
@@ -27,7 +27,7 @@ const DEPLOY_HASH_2: [u8; 32] = [2u8; 32];
 
 // one named_key for each validator and three for the purses, one for validator slots, one for
 // auction_delay, one for locked_funds_period.
-const EXPECTED_KNOWN_KEYS_LEN: usize = 9;
+const EXPECTED_KNOWN_KEYS_LEN: usize = 10;
 
 #[ignore]
 #[test]
@@ -68,6 +68,10 @@ fn should_run_auction_install_contract() {
     let _auction_hash = auction.contract_package_hash();
 
     let genesis_validators: BTreeMap<casper_types::PublicKey, U512> = BTreeMap::new();
+    let genesis_delegators: BTreeMap<
+        casper_types::PublicKey,
+        BTreeMap<casper_types::PublicKey, U512>,
+    > = BTreeMap::new();
 
     let res = exec_with_return::exec(
         engine_config,
@@ -80,6 +84,7 @@ fn should_run_auction_install_contract() {
         runtime_args! {
             ARG_MINT_CONTRACT_PACKAGE_HASH => mint.contract_package_hash(),
             ARG_GENESIS_VALIDATORS => genesis_validators,
+            ARG_GENESIS_DELEGATORS => genesis_delegators,
             ARG_VALIDATOR_SLOTS => DEFAULT_VALIDATOR_SLOTS,
             ARG_AUCTION_DELAY => DEFAULT_AUCTION_DELAY,
             ARG_LOCKED_FUNDS_PERIOD => DEFAULT_LOCKED_FUNDS_PERIOD,
@@ -104,6 +109,7 @@ fn should_run_auction_install_contract() {
     assert!(named_keys.contains_key(AUCTION_DELAY_KEY));
     assert!(named_keys.contains_key(LOCKED_FUNDS_PERIOD_KEY));
     assert!(named_keys.contains_key(ERA_ID_KEY));
+    assert!(named_keys.contains_key(ERA_INFO_KEY));
     assert!(named_keys.contains_key(SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY));
     assert!(named_keys.contains_key(UNBONDING_PURSES_KEY));
     assert!(named_keys.contains_key(DELEGATOR_REWARD_PURSE_KEY));
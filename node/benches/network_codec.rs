@@ -0,0 +1,59 @@
+//! Throughput of sending a large `GetResponse` to a number of peers and decoding it on receipt, in
+//! each wire version.
+//!
+//! A message is encoded once per wire version and the frame shared by all peers speaking it.  Under
+//! `WireVersion::V1`, the serialized item is encoded as a sequence of integers, and collected into
+//! a new buffer on receipt.  Under `WireVersion::V2`, it is encoded as a single binary blob, and
+//! the decoded message refers to it within the received frame rather than copying it.
+
+use bytes::Bytes;
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+use casper_node::{
+    protocol::{Message, Payload, WireVersion},
+    types::Tag,
+};
+
+/// The size of the serialized item, e.g. a large trie chunk or deploy.
+const ITEM_SIZE: usize = 4 * 1024 * 1024;
+
+/// The number of peers a message is sent to.
+const PEER_COUNT: usize = 10;
+
+fn network_codec_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("get_response");
+    group.throughput(Throughput::Bytes((ITEM_SIZE * PEER_COUNT) as u64));
+    group.sample_size(10);
+
+    let msg = Message::GetResponse {
+        tag: Tag::Deploy,
+        serialized_item: (0..ITEM_SIZE).map(|index| index as u8).collect(),
+    };
+    for &(name, wire_version) in &[("v1", WireVersion::V1), ("v2", WireVersion::V2)] {
+        group.bench_function(format!("encode_{}", name), |b| {
+            b.iter(|| {
+                let frame = black_box(&msg).encode(wire_version).unwrap().unwrap();
+                (0..PEER_COUNT).map(|_| frame.clone()).collect::<Vec<_>>()
+            })
+        });
+
+        // Each peer receives its own copy of the frame.
+        let frame = msg.encode(wire_version).unwrap().unwrap();
+        let frames: Vec<Bytes> = (0..PEER_COUNT)
+            .map(|_| Bytes::copy_from_slice(&frame))
+            .collect();
+        group.bench_function(format!("decode_{}", name), |b| {
+            b.iter(|| {
+                frames
+                    .iter()
+                    .map(|frame| Message::decode(black_box(frame.clone())).unwrap())
+                    .collect::<Vec<_>>()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, network_codec_bench);
+criterion_main!(benches);
@@ -3,8 +3,16 @@
 //! Most configuration is done via config files (see [`config`](../config/index.html) for details).
 
 pub mod arglang;
+mod bench;
+mod localnet;
 
-use std::{env, fs, path::PathBuf, str::FromStr};
+use std::{
+    collections::BTreeSet,
+    env, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::atomic::Ordering,
+};
 
 use anyhow::{self, bail, Context};
 use regex::Regex;
@@ -14,10 +22,13 @@ use tracing::{info, trace};
 
 use crate::config;
 use casper_node::{
+    crypto::hash::Digest,
     logging,
     reactor::{initializer, joiner, validator, Runner},
-    setup_signal_hooks,
+    rebuild_storage_indexes, replay_block, setup_signal_hooks,
+    types::BlockHash,
     utils::WithDir,
+    NodeRng, ReplayReport, StorageIndex,
 };
 use prometheus::Registry;
 
@@ -45,8 +56,82 @@ pub enum Cli {
         /// <SECTION>.<KEY>=<VALUE>.  For example, '-C=node.chainspec_config_path=chainspec.toml'
         config_ext: Vec<ConfigExt>,
     },
+    /// Rebuild the secondary indexes of the node's storage.
+    ///
+    /// Reconstructs the indexes of deploys by account, of native transfers by target and of the
+    /// history of global state keys from the blocks, deploys and execution results held in
+    /// storage, so that data stored by versions of the node lacking an index is covered by it.
+    /// The node must not be running.
+    RebuildIndexes {
+        /// Path to configuration file.
+        config: PathBuf,
+
+        #[structopt(
+            short = "C",
+            long,
+            env = "NODE_CONFIG",
+            use_delimiter(true),
+            value_delimiter(";")
+        )]
+        /// Overrides and extensions for configuration file entries in the form
+        /// <SECTION>.<KEY>=<VALUE>.  For example, '-C=storage.path=/var/lib/casper/db'
+        config_ext: Vec<ConfigExt>,
+
+        #[structopt(short, long)]
+        /// Index to rebuild, one of 'account-deploys', 'deploy-blocks', 'key-history' or
+        /// 'transfers'.  May be given multiple times.  If not given, all indexes are rebuilt.
+        index: Vec<StorageIndex>,
+    },
+    /// Replay a stored block.
+    ///
+    /// Re-executes the block on top of its parent's state root hash in an engine instance separate
+    /// from any running node, and compares the resulting state root hash and execution results
+    /// with those held in storage.  Exits with an error if they differ.  The node must not be
+    /// running.
+    ReplayBlock {
+        /// Path to configuration file.
+        config: PathBuf,
+
+        #[structopt(
+            short = "C",
+            long,
+            env = "NODE_CONFIG",
+            use_delimiter(true),
+            value_delimiter(";")
+        )]
+        /// Overrides and extensions for configuration file entries in the form
+        /// <SECTION>.<KEY>=<VALUE>.  For example, '-C=storage.path=/var/lib/casper/db'
+        config_ext: Vec<ConfigExt>,
+
+        #[structopt(short, long, parse(try_from_str = parse_digest))]
+        /// Hex-encoded hash of the block to replay.
+        block_hash: Digest,
+
+        #[structopt(short, long, parse(try_from_str = parse_digest))]
+        /// Hex-encoded state root hash to execute the block on top of.  Required if the parent of
+        /// the block is not in storage, e.g. for the first block of the chain.
+        pre_state_root_hash: Option<Digest>,
+    },
+    /// Run a local network of validators for development.
+    ///
+    /// Generates the keys of the given number of validators and of funded accounts, and a
+    /// chainspec and configuration files for the network with genesis shortly in the future.  Then
+    /// runs each validator as a child process of this one, printing their endpoints, until
+    /// interrupted.  All files, including the validators' storage and logs, are kept in the given
+    /// directory.
+    Localnet(localnet::Options),
+    /// Benchmark a local network of validators.
+    ///
+    /// Generates a local network as 'localnet' does, but runs its validators within this process.
+    /// Once the first block is added, measures deploy throughput, block latency, RPC queries per
+    /// second and the time a new node takes to sync the chain, then stops the network and prints
+    /// the results as JSON, suitable for tracking regressions across releases.
+    Bench(bench::Options),
 }
 
+/// The number of blocks between progress reports while rebuilding indexes.
+const REBUILD_PROGRESS_INTERVAL: u64 = 1000;
+
 #[derive(Debug)]
 /// Command line extension to be applied to TOML-based config file values.
 pub struct ConfigExt {
@@ -116,84 +201,226 @@ impl Cli {
                 // Setup UNIX signal hooks.
                 setup_signal_hooks();
 
-                // Determine the parent directory of the configuration file, if any.
-                // Otherwise, we default to `/`.
-                let root = config
-                    .parent()
-                    .map(|path| path.to_owned())
-                    .unwrap_or_else(|| "/".into());
-
-                // The app supports running without a config file, using default values.
-                let config_raw: String = fs::read_to_string(&config)
-                    .context("could not read configuration file")
-                    .with_context(|| config.display().to_string())?;
-
-                // Get the TOML table version of the config indicated from CLI args, or from a new
-                // defaulted config instance if one is not provided.
-                let mut config_table: Value = toml::from_str(&config_raw)?;
-
-                // If any command line overrides to the config values are passed, apply them.
-                for item in config_ext {
-                    item.update_toml_table(&mut config_table)?;
-                }
-
-                // Create validator config, including any overridden values.
-                let validator_config: validator::Config = config_table.try_into()?;
+                let (root, validator_config) = load_config(&config, config_ext)?;
                 logging::init_with_config(&validator_config.logging)?;
                 info!(version = %env!("CARGO_PKG_VERSION"), "node starting up");
                 trace!("{}", config::to_string(&validator_config)?);
 
-                // We use a `ChaCha20Rng` for the production node. For one, we want to completely
-                // eliminate any chance of runtime failures, regardless of how small (these
-                // exist with `OsRng`). Additionally, we want to limit the number of syscalls for
-                // performance reasons.
-                let mut rng = casper_node::new_rng();
-
-                // The metrics are shared across all reactors.
-                let registry = Registry::new();
-
-                let mut initializer_runner = Runner::<initializer::Reactor>::with_metrics(
-                    WithDir::new(root.clone(), validator_config),
-                    &mut rng,
-                    &registry,
-                )
-                .await?;
-
-                // let mut initializer2_runner = Runner::<initializer2::Initializer>::with_metrics(
-                //     WithDir::new(root.clone(), validator_config),
-                //     &mut rng,
-                //     &registry,
-                // )
-                // .await?;
-                // initializer2_runner.run(&mut rng).await;
-
-                initializer_runner.run(&mut rng).await;
-
-                info!("finished initialization");
-
-                let initializer = initializer_runner.into_inner();
-                if !initializer.stopped_successfully() {
-                    bail!("failed to initialize successfully");
-                }
-
-                let mut joiner_runner = Runner::<joiner::Reactor>::with_metrics(
-                    WithDir::new(root, initializer),
-                    &mut rng,
-                    &registry,
-                )
-                .await?;
-                joiner_runner.run(&mut rng).await;
+                run_node(root, validator_config).await?;
+            }
+            Cli::RebuildIndexes {
+                config,
+                config_ext,
+                index,
+            } => {
+                let (root, validator_config) = load_config(&config, config_ext)?;
+                logging::init_with_config(&validator_config.logging)?;
 
-                info!("finished joining");
+                let indexes: BTreeSet<StorageIndex> = if index.is_empty() {
+                    StorageIndex::ALL.iter().copied().collect()
+                } else {
+                    index.into_iter().collect()
+                };
+                for index in &indexes {
+                    info!(%index, "rebuilding index");
+                }
 
-                let config = joiner_runner.into_inner().into_validator_config().await;
+                rebuild_storage_indexes(
+                    &WithDir::new(root, validator_config.storage),
+                    &indexes,
+                    |processed, total| {
+                        if processed % REBUILD_PROGRESS_INTERVAL == 0 || processed == total {
+                            info!(processed, total, "processed blocks");
+                        }
+                    },
+                )?;
+                info!("finished rebuilding indexes");
+            }
+            Cli::ReplayBlock {
+                config,
+                config_ext,
+                block_hash,
+                pre_state_root_hash,
+            } => {
+                let (root, validator_config) = load_config(&config, config_ext)?;
+                logging::init_with_config(&validator_config.logging)?;
 
-                let mut validator_runner =
-                    Runner::<validator::Reactor>::with_metrics(config, &mut rng, &registry).await?;
-                validator_runner.run(&mut rng).await;
+                let report = replay_block(
+                    WithDir::new(root, validator_config.storage),
+                    &validator_config.contract_runtime,
+                    BlockHash::new(block_hash),
+                    pre_state_root_hash,
+                )?;
+                print_replay_report(&report)?;
+                if !report.is_match() {
+                    bail!(
+                        "replay of block {} diverged from storage",
+                        report.block_hash
+                    );
+                }
             }
+            Cli::Localnet(options) => localnet::run(options).await?,
+            Cli::Bench(options) => bench::run(options).await?,
         }
 
         Ok(())
     }
 }
+
+/// Runs a node with the given configuration, whose paths are resolved relative to `root`, until it
+/// stops.
+async fn run_node(root: PathBuf, validator_config: validator::Config) -> anyhow::Result<()> {
+    // We use a `ChaCha20Rng` for the production node. For one, we want to completely
+    // eliminate any chance of runtime failures, regardless of how small (these
+    // exist with `OsRng`). Additionally, we want to limit the number of syscalls for
+    // performance reasons.
+    let mut rng = casper_node::new_rng();
+
+    let sidecar = validator_config.node.sidecar;
+
+    // The metrics are shared across all reactors.
+    let registry = Registry::new();
+
+    let joiner_runner = match join(root, validator_config, &mut rng, &registry).await? {
+        Some(joiner_runner) => joiner_runner,
+        None => return Ok(()),
+    };
+
+    if sidecar {
+        // A sidecar keeps following the linear chain until termination is requested.
+        joiner_runner.into_inner().shut_down_sidecar().await;
+        info!("sidecar stopped");
+        return Ok(());
+    }
+
+    info!("finished joining");
+
+    let config = joiner_runner.into_inner().into_validator_config().await;
+    if termination_requested() {
+        info!("termination requested during joining, exiting");
+        return Ok(());
+    }
+
+    let mut validator_runner =
+        Runner::<validator::Reactor>::with_metrics(config, &mut rng, &registry).await?;
+    validator_runner.run(&mut rng).await;
+
+    info!("node stopped");
+    Ok(())
+}
+
+/// Initializes a node with the given configuration and runs its joiner until it has synced the
+/// linear chain, or returns `None` if termination was requested during initialization.
+async fn join(
+    root: PathBuf,
+    validator_config: validator::Config,
+    rng: &mut NodeRng,
+    registry: &Registry,
+) -> anyhow::Result<Option<Runner<joiner::Reactor>>> {
+    let mut initializer_runner = Runner::<initializer::Reactor>::with_metrics(
+        WithDir::new(root.clone(), validator_config),
+        rng,
+        registry,
+    )
+    .await?;
+
+    // let mut initializer2_runner = Runner::<initializer2::Initializer>::with_metrics(
+    //     WithDir::new(root.clone(), validator_config),
+    //     &mut rng,
+    //     &registry,
+    // )
+    // .await?;
+    // initializer2_runner.run(&mut rng).await;
+
+    initializer_runner.run(rng).await;
+
+    info!("finished initialization");
+
+    let initializer = initializer_runner.into_inner();
+    if termination_requested() {
+        info!("termination requested during initialization, exiting");
+        return Ok(None);
+    }
+    if !initializer.stopped_successfully() {
+        bail!("failed to initialize successfully");
+    }
+
+    let mut joiner_runner =
+        Runner::<joiner::Reactor>::with_metrics(WithDir::new(root, initializer), rng, registry)
+            .await?;
+    joiner_runner.run(rng).await;
+    Ok(Some(joiner_runner))
+}
+
+/// Parses a hex-encoded hash given on the command line.
+fn parse_digest(input: &str) -> anyhow::Result<Digest> {
+    Digest::from_hex(input).context("could not parse hex-encoded hash")
+}
+
+/// Prints the outcome of replaying a block, including both versions of any diverging execution
+/// result.
+fn print_replay_report(report: &ReplayReport) -> anyhow::Result<()> {
+    println!("block:                    {}", report.block_hash);
+    println!("height:                   {}", report.height);
+    println!("pre-state root hash:      {}", report.pre_state_root_hash);
+    println!(
+        "stored state root hash:   {}",
+        report.stored_state_root_hash
+    );
+    println!(
+        "replayed state root hash: {}",
+        report.replayed_state_root_hash
+    );
+    for mismatch in &report.execution_result_mismatches {
+        println!();
+        println!(
+            "execution result of deploy {} differs",
+            mismatch.deploy_hash
+        );
+        println!(
+            "stored:\n{}",
+            serde_json::to_string_pretty(&mismatch.stored)?
+        );
+        println!(
+            "replayed:\n{}",
+            serde_json::to_string_pretty(&mismatch.replayed)?
+        );
+    }
+    Ok(())
+}
+
+/// Returns whether termination of the node has been requested, e.g. by a SIGTERM.
+fn termination_requested() -> bool {
+    casper_node::TERMINATION_REQUESTED.load(Ordering::SeqCst)
+}
+
+/// Loads the validator config from the given file, applying any command line overrides, and
+/// returns it along with the directory relative to which the paths it holds are resolved.
+fn load_config(
+    config: &Path,
+    config_ext: Vec<ConfigExt>,
+) -> anyhow::Result<(PathBuf, validator::Config)> {
+    // Determine the parent directory of the configuration file, if any.
+    // Otherwise, we default to `/`.
+    let root = config
+        .parent()
+        .map(|path| path.to_owned())
+        .unwrap_or_else(|| "/".into());
+
+    // The app supports running without a config file, using default values.
+    let config_raw: String = fs::read_to_string(config)
+        .context("could not read configuration file")
+        .with_context(|| config.display().to_string())?;
+
+    // Get the TOML table version of the config indicated from CLI args, or from a new
+    // defaulted config instance if one is not provided.
+    let mut config_table: Value = toml::from_str(&config_raw)?;
+
+    // If any command line overrides to the config values are passed, apply them.
+    for item in config_ext {
+        item.update_toml_table(&mut config_table)?;
+    }
+
+    // Create validator config, including any overridden values.
+    Ok((root, config_table.try_into()?))
+}
@@ -0,0 +1,501 @@
+//! Benchmarking of a local network run within this process.
+//!
+//! The validators of a network generated as by `localnet` are run within this process, and a
+//! series of workloads is run against their JSON-RPC servers once the first block is added.  The
+//! results are printed as JSON, so that they can be compared across releases.
+
+use std::{
+    fs,
+    path::PathBuf,
+    sync::atomic::Ordering,
+    time::{Duration, Instant},
+};
+
+use anyhow::{self, bail, Context};
+use futures::future::{self, Either};
+use prometheus::Registry;
+use reqwest::{header::CONTENT_TYPE, Client};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+use structopt::StructOpt;
+use tokio::time;
+
+use super::{join, load_config, localnet, run_node};
+use casper_execution_engine::core::engine_state::executable_deploy_item::ExecutableDeployItem;
+use casper_node::{
+    crypto::asymmetric_key::{PublicKey, SecretKey},
+    rpcs::{
+        account::{PutDeployParams, PutDeployResult},
+        chain::GetBlockResult,
+        info::{GetDeployParams, GetDeployResult},
+    },
+    setup_signal_hooks,
+    types::{Block, Deploy, DeployHash, GetStatusResult, TimeDiff, Timestamp},
+    NodeRng,
+};
+use casper_types::{bytesrepr::ToBytes, mint, standard_payment, RuntimeArgs, U512};
+
+/// The interval at which the nodes are polled for new blocks and executed deploys.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// The time to wait for the network to add its first block after genesis.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(300);
+/// The time to wait for all submitted deploys to be executed.
+const EXECUTION_TIMEOUT: Duration = Duration::from_secs(600);
+/// The amount of motes paid for each transfer.
+const PAYMENT_AMOUNT: u64 = 1_000_000_000;
+/// The amount of motes transferred by each transfer.
+const TRANSFER_AMOUNT: u64 = 1_000_000_000;
+/// The time to live of the submitted deploys.
+const DEPLOY_TTL: Duration = Duration::from_secs(3600);
+
+#[derive(Debug, StructOpt)]
+pub struct Options {
+    #[structopt(flatten)]
+    network: localnet::Options,
+
+    #[structopt(long, default_value = "100")]
+    /// Number of native transfers to submit when measuring deploy throughput.
+    deploys: usize,
+
+    #[structopt(long, default_value = "20")]
+    /// Number of blocks to observe when measuring block latency.
+    blocks: usize,
+
+    #[structopt(long, default_value = "10")]
+    /// Number of seconds to send RPC queries for when measuring RPC queries per second.
+    rpc_duration: u64,
+
+    #[structopt(long, default_value = "16")]
+    /// Number of RPC queries to keep in flight at a time.
+    rpc_concurrency: usize,
+
+    #[structopt(long)]
+    /// Skip measuring how long a node joining the network takes to sync the linear chain.
+    no_sync: bool,
+
+    #[structopt(short, long)]
+    /// File to write the results to.  If not given, they are printed to stdout.
+    output: Option<PathBuf>,
+}
+
+/// The results of a benchmark run.
+#[derive(Debug, Serialize)]
+struct Report {
+    /// The version of the node benchmarked.
+    version: String,
+    /// The number of validators.
+    validators: usize,
+    /// Seconds from genesis until the first block was added.
+    startup_secs: f64,
+    deploy_throughput: ThroughputReport,
+    block_latency: LatencyReport,
+    rpc: RpcReport,
+    sync: Option<SyncReport>,
+}
+
+/// Throughput of native transfers, from submitting the first until the last was executed.
+#[derive(Debug, Serialize)]
+struct ThroughputReport {
+    deploys: usize,
+    secs: f64,
+    deploys_per_sec: f64,
+}
+
+/// Time from a block's timestamp until it was first returned by the RPC server.
+#[derive(Debug, Default, Serialize)]
+struct LatencyReport {
+    blocks: usize,
+    min_ms: u64,
+    mean_ms: u64,
+    p50_ms: u64,
+    p90_ms: u64,
+    max_ms: u64,
+}
+
+/// Rate of `info_get_status` queries answered by a single node.
+#[derive(Debug, Serialize)]
+struct RpcReport {
+    concurrency: usize,
+    secs: f64,
+    queries: u64,
+    errors: u64,
+    queries_per_sec: f64,
+}
+
+/// Time taken by a node joining the network to sync the linear chain.
+#[derive(Debug, Serialize)]
+struct SyncReport {
+    /// The height of the trusted block the node synced from.
+    trusted_height: u64,
+    secs: f64,
+    blocks_per_sec: f64,
+}
+
+/// A JSON-RPC response.
+#[derive(Deserialize)]
+struct Response<T> {
+    result: Option<T>,
+    error: Option<Value>,
+}
+
+/// Generates the local network described by `options`, runs the benchmark against it and writes
+/// the results.
+pub async fn run(options: Options) -> anyhow::Result<()> {
+    setup_signal_hooks();
+
+    let network = localnet::generate(&options.network)?;
+    let configs = network
+        .nodes
+        .iter()
+        .map(|node| load_config(&node.config_path(), vec![]))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let nodes = future::try_join_all(
+        configs
+            .into_iter()
+            .map(|(root, config)| run_node(root, config)),
+    );
+    let benchmark = benchmark(&options, &network);
+    futures::pin_mut!(nodes, benchmark);
+
+    let report = match future::select(benchmark, nodes).await {
+        Either::Left((report, nodes)) => {
+            eprintln!("stopping local network");
+            casper_node::TERMINATION_REQUESTED.store(true, Ordering::SeqCst);
+            nodes.await?;
+            report?
+        }
+        Either::Right((result, _)) => {
+            result?;
+            bail!("local network stopped before the benchmark finished");
+        }
+    };
+
+    let json = serde_json::to_string_pretty(&report)?;
+    match &options.output {
+        Some(path) => {
+            fs::write(path, json).with_context(|| format!("could not write {}", path.display()))?
+        }
+        None => println!("{}", json),
+    }
+    Ok(())
+}
+
+/// Runs all workloads against the running network.
+async fn benchmark(options: &Options, network: &localnet::LocalNetwork) -> anyhow::Result<Report> {
+    let (account_dir, _) = network
+        .accounts
+        .first()
+        .context("at least one funded account is required")?;
+    let secret_key = SecretKey::from_file(account_dir.join("secret_key.pem"))?;
+    let client = Client::new();
+    let urls = network
+        .nodes
+        .iter()
+        .map(|node| format!("http://127.0.0.1:{}/rpc", node.rpc_port()))
+        .collect::<Vec<_>>();
+    let mut rng = casper_node::new_rng();
+
+    eprintln!("waiting for the first block");
+    wait_for_first_block(&client, &urls[0]).await?;
+    let startup_secs = Timestamp::now()
+        .millis()
+        .saturating_sub(network.genesis_timestamp.millis()) as f64
+        / 1000.0;
+    let status: GetStatusResult = rpc(&client, &urls[0], "info_get_status", None::<()>).await?;
+
+    eprintln!("submitting {} transfers", options.deploys);
+    let deploy_throughput = measure_throughput(
+        &client,
+        &urls,
+        options.deploys,
+        &status.chainspec_name,
+        &secret_key,
+        &network
+            .nodes
+            .iter()
+            .map(|node| &node.public_key)
+            .collect::<Vec<_>>(),
+        &mut rng,
+    )
+    .await?;
+
+    eprintln!("observing {} blocks", options.blocks);
+    let block_latency = measure_latency(&client, &urls[0], options.blocks).await?;
+
+    eprintln!("querying status for {}s", options.rpc_duration);
+    let rpc_report = measure_rpc(
+        &client,
+        &urls[0],
+        options.rpc_concurrency,
+        Duration::from_secs(options.rpc_duration),
+    )
+    .await;
+
+    let sync = if options.no_sync {
+        None
+    } else {
+        eprintln!("syncing a joining node");
+        Some(measure_sync(&client, &urls[0], network, &mut rng).await?)
+    };
+
+    Ok(Report {
+        version: casper_node::VERSION_STRING.clone(),
+        validators: network.nodes.len(),
+        startup_secs,
+        deploy_throughput,
+        block_latency,
+        rpc: rpc_report,
+        sync,
+    })
+}
+
+/// Submits `count` native transfers to the given targets, round-robin to the nodes, and waits until
+/// all are executed.
+async fn measure_throughput(
+    client: &Client,
+    urls: &[String],
+    count: usize,
+    chain_name: &str,
+    secret_key: &SecretKey,
+    targets: &[&PublicKey],
+    rng: &mut NodeRng,
+) -> anyhow::Result<ThroughputReport> {
+    let start = Instant::now();
+    let mut pending = Vec::with_capacity(count);
+    for index in 0..count {
+        let target = targets[index % targets.len()];
+        let deploy = transfer(chain_name, secret_key, target, rng);
+        let url = &urls[index % urls.len()];
+        let params = PutDeployParams {
+            deploy,
+            receipt: false,
+            idempotency_key: None,
+        };
+        let result: PutDeployResult = rpc(client, url, "account_put_deploy", Some(params)).await?;
+        pending.push((url, result.deploy_hash));
+    }
+
+    while !pending.is_empty() {
+        if start.elapsed() > EXECUTION_TIMEOUT {
+            bail!("{} deploys were not executed in time", pending.len());
+        }
+        let mut still_pending = vec![];
+        for (url, deploy_hash) in pending {
+            if !is_executed(client, url, deploy_hash).await? {
+                still_pending.push((url, deploy_hash));
+            }
+        }
+        pending = still_pending;
+        if !pending.is_empty() {
+            time::delay_for(POLL_INTERVAL).await;
+        }
+    }
+
+    let secs = start.elapsed().as_secs_f64();
+    Ok(ThroughputReport {
+        deploys: count,
+        secs,
+        deploys_per_sec: count as f64 / secs,
+    })
+}
+
+/// Creates a native transfer from the account of `secret_key` to that of `target`.
+fn transfer(
+    chain_name: &str,
+    secret_key: &SecretKey,
+    target: &PublicKey,
+    rng: &mut NodeRng,
+) -> Deploy {
+    let mut payment_args = RuntimeArgs::new();
+    payment_args.insert(standard_payment::ARG_AMOUNT, U512::from(PAYMENT_AMOUNT));
+    let payment = ExecutableDeployItem::ModuleBytes {
+        module_bytes: vec![],
+        args: payment_args
+            .to_bytes()
+            .expect("should serialize payment args"),
+    };
+
+    let mut session_args = RuntimeArgs::new();
+    session_args.insert(mint::ARG_TARGET, target.to_account_hash());
+    session_args.insert(mint::ARG_AMOUNT, U512::from(TRANSFER_AMOUNT));
+    let session = ExecutableDeployItem::Transfer {
+        args: session_args
+            .to_bytes()
+            .expect("should serialize transfer args"),
+    };
+
+    Deploy::new(
+        Timestamp::now(),
+        TimeDiff::from(DEPLOY_TTL),
+        1,
+        vec![],
+        chain_name.to_string(),
+        payment,
+        session,
+        secret_key,
+        rng,
+    )
+}
+
+/// Returns whether the given deploy has been executed in a block known to the node.
+async fn is_executed(client: &Client, url: &str, deploy_hash: DeployHash) -> anyhow::Result<bool> {
+    let params = GetDeployParams { deploy_hash };
+    let result: GetDeployResult = rpc(client, url, "info_get_deploy", Some(params)).await?;
+    Ok(!result.execution_results.is_empty())
+}
+
+/// Observes the next `count` blocks, recording how long after their timestamp they were returned.
+async fn measure_latency(
+    client: &Client,
+    url: &str,
+    count: usize,
+) -> anyhow::Result<LatencyReport> {
+    let mut height = latest_block(client, url)
+        .await?
+        .map_or(0, |block| block.height());
+    let mut latencies = Vec::with_capacity(count);
+    while latencies.len() < count {
+        match latest_block(client, url).await? {
+            Some(block) if block.height() > height => {
+                height = block.height();
+                latencies.push(
+                    Timestamp::now()
+                        .millis()
+                        .saturating_sub(block.timestamp().millis()),
+                );
+            }
+            _ => time::delay_for(POLL_INTERVAL).await,
+        }
+    }
+
+    if latencies.is_empty() {
+        return Ok(LatencyReport::default());
+    }
+    latencies.sort_unstable();
+    let percentile = |percent: usize| latencies[(latencies.len() - 1) * percent / 100];
+    Ok(LatencyReport {
+        blocks: latencies.len(),
+        min_ms: latencies[0],
+        mean_ms: latencies.iter().sum::<u64>() / latencies.len() as u64,
+        p50_ms: percentile(50),
+        p90_ms: percentile(90),
+        max_ms: latencies[latencies.len() - 1],
+    })
+}
+
+/// Sends `info_get_status` queries for `duration`, keeping `concurrency` of them in flight.
+async fn measure_rpc(
+    client: &Client,
+    url: &str,
+    concurrency: usize,
+    duration: Duration,
+) -> RpcReport {
+    let start = Instant::now();
+    let workers = (0..concurrency.max(1)).map(|_| async move {
+        let (mut queries, mut errors) = (0, 0);
+        while start.elapsed() < duration {
+            match rpc::<(), GetStatusResult>(client, url, "info_get_status", None).await {
+                Ok(_) => queries += 1,
+                Err(_) => errors += 1,
+            }
+        }
+        (queries, errors)
+    });
+    let (queries, errors) = future::join_all(workers).await.into_iter().fold(
+        (0, 0),
+        |(queries, errors), (worker_queries, worker_errors)| {
+            (queries + worker_queries, errors + worker_errors)
+        },
+    );
+
+    let secs = start.elapsed().as_secs_f64();
+    RpcReport {
+        concurrency,
+        secs,
+        queries,
+        errors,
+        queries_per_sec: queries as f64 / secs,
+    }
+}
+
+/// Starts a node joining the network, trusting its latest block, and measures how long it takes
+/// to sync the linear chain.
+async fn measure_sync(
+    client: &Client,
+    url: &str,
+    network: &localnet::LocalNetwork,
+    rng: &mut NodeRng,
+) -> anyhow::Result<SyncReport> {
+    let trusted_block = latest_block(client, url)
+        .await?
+        .context("network has no blocks to sync")?;
+    let node = network.generate_joining_node()?;
+    let (root, mut config) = load_config(&node.config_path(), vec![])?;
+    config.node.trusted_hash = Some(*trusted_block.hash());
+
+    let registry = Registry::new();
+    let start = Instant::now();
+    let joiner_runner = join(root, config, rng, &registry)
+        .await?
+        .context("termination requested while syncing")?;
+    let secs = start.elapsed().as_secs_f64();
+    // Shut down the joiner's components cleanly before dropping them.
+    drop(joiner_runner.into_inner().into_validator_config().await);
+
+    Ok(SyncReport {
+        trusted_height: trusted_block.height(),
+        secs,
+        blocks_per_sec: trusted_block.height() as f64 / secs,
+    })
+}
+
+/// Waits until the node has added its first block, returning it.
+async fn wait_for_first_block(client: &Client, url: &str) -> anyhow::Result<Block> {
+    let start = Instant::now();
+    loop {
+        // The RPC server isn't listening until the node has joined.
+        if let Ok(Some(block)) = latest_block(client, url).await {
+            return Ok(block);
+        }
+        if start.elapsed() > STARTUP_TIMEOUT {
+            bail!("no block added in time");
+        }
+        time::delay_for(POLL_INTERVAL).await;
+    }
+}
+
+/// Returns the latest block added by the node, if any.
+async fn latest_block(client: &Client, url: &str) -> anyhow::Result<Option<Block>> {
+    let result: GetBlockResult = rpc(client, url, "chain_get_block", None::<()>).await?;
+    Ok(result.block)
+}
+
+/// Sends a JSON-RPC request to the node at `url`, returning its result.
+async fn rpc<P: Serialize, R: DeserializeOwned>(
+    client: &Client,
+    url: &str,
+    method: &str,
+    params: Option<P>,
+) -> anyhow::Result<R> {
+    let mut request = json!({ "jsonrpc": "2.0", "id": 1, "method": method });
+    if let Some(params) = params {
+        request["params"] = serde_json::to_value(params)?;
+    }
+    let body = client
+        .post(url)
+        .header(CONTENT_TYPE, "application/json")
+        .body(serde_json::to_vec(&request)?)
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+    let response: Response<R> = serde_json::from_slice(&body)
+        .with_context(|| format!("could not parse response to {}", method))?;
+    match (response.result, response.error) {
+        (Some(result), _) => Ok(result),
+        (None, Some(error)) => bail!("{} failed: {}", method, error),
+        (None, None) => bail!("{} returned neither a result nor an error", method),
+    }
+}
@@ -0,0 +1,493 @@
+//! Local network launcher.
+//!
+//! Generates everything needed to run a network of validators on the local machine, then runs each
+//! validator as a supervised child process of the launcher.
+
+use std::{
+    fs::{self, File},
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    time::Duration,
+};
+
+use anyhow::{self, bail, Context};
+use structopt::StructOpt;
+use tokio::time;
+use toml::{value::Table, Value};
+
+use super::termination_requested;
+use casper_node::{
+    crypto::asymmetric_key::{PublicKey, SecretKey},
+    setup_signal_hooks,
+    types::{TimeDiff, Timestamp},
+};
+use casper_types::U512;
+
+/// Maximum number of validators of a local network.
+const MAX_NODES: u16 = 100;
+
+/// Port of the first node's networking component, the nodes' ports are consecutive.
+const BASE_NETWORK_PORT: u16 = 34553;
+
+/// Port of the first node's JSON-RPC server.
+const BASE_RPC_PORT: u16 = 7777;
+
+/// Port of the first node's REST server.
+const BASE_REST_PORT: u16 = 8888;
+
+/// Port of the first node's event stream server.
+const BASE_EVENT_STREAM_PORT: u16 = 9999;
+
+/// Chainspec entries holding paths to files, which may be relative to the chainspec.
+const CHAINSPEC_PATH_KEYS: [&str; 4] = [
+    "mint_installer_path",
+    "pos_installer_path",
+    "standard_payment_installer_path",
+    "auction_installer_path",
+];
+
+/// Interval at which the child processes are checked.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Time given to the child processes to shut down gracefully before they are killed.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, StructOpt)]
+pub struct Options {
+    /// Path to the chainspec to base the network on, e.g. one generated by
+    /// `generate-chainspec.sh`.  Its genesis timestamps and accounts are replaced.
+    chainspec: PathBuf,
+
+    /// Path to the configuration file to base the nodes' configuration on, e.g.
+    /// 'resources/local/config.toml'.  Keys, storage paths and addresses are replaced.
+    config: PathBuf,
+
+    #[structopt(short, long, default_value = "localnet")]
+    /// Directory in which to generate the network's keys, chainspec, configuration and storage.
+    dir: PathBuf,
+
+    #[structopt(short, long)]
+    /// Remove the directory given by '--dir' first if it already exists.
+    force: bool,
+
+    #[structopt(short, long, default_value = "5")]
+    /// Number of validators to run.
+    nodes: u16,
+
+    #[structopt(short, long, default_value = "1")]
+    /// Number of funded accounts to generate in addition to the validators' accounts.
+    accounts: u16,
+
+    #[structopt(long, default_value = "1000000000000000000", parse(try_from_str = parse_motes))]
+    /// Initial balance in motes of each validator's and funded account.
+    balance: U512,
+
+    #[structopt(long, default_value = "500000000000000", parse(try_from_str = parse_motes))]
+    /// Initial bond in motes of each validator.
+    bond: U512,
+
+    #[structopt(long, default_value = "10")]
+    /// Number of seconds from now until genesis.
+    genesis_delay: u64,
+}
+
+/// A validator of the local network.
+pub(super) struct Node {
+    /// One-based index of the node.
+    pub(super) index: u16,
+    /// The node's directory.
+    pub(super) dir: PathBuf,
+    /// The validator's public key.
+    pub(super) public_key: PublicKey,
+}
+
+impl Node {
+    fn network_port(&self) -> u16 {
+        BASE_NETWORK_PORT + self.index - 1
+    }
+
+    pub(super) fn rpc_port(&self) -> u16 {
+        BASE_RPC_PORT + self.index - 1
+    }
+
+    fn rest_port(&self) -> u16 {
+        BASE_REST_PORT + self.index - 1
+    }
+
+    fn event_stream_port(&self) -> u16 {
+        BASE_EVENT_STREAM_PORT + self.index - 1
+    }
+
+    pub(super) fn config_path(&self) -> PathBuf {
+        self.dir.join("config.toml")
+    }
+}
+
+/// The generated files of a local network.
+pub(super) struct LocalNetwork {
+    /// The network's directory.
+    dir: PathBuf,
+    /// The validators.
+    pub(super) nodes: Vec<Node>,
+    /// The directories and public keys of the funded accounts.
+    pub(super) accounts: Vec<(PathBuf, PublicKey)>,
+    /// The genesis timestamp.
+    pub(super) genesis_timestamp: Timestamp,
+    /// The path of the chainspec.
+    chainspec_path: PathBuf,
+    /// The path of the configuration file the nodes' configuration is based on.
+    base_config: PathBuf,
+}
+
+impl LocalNetwork {
+    /// Generates the key and configuration of a node joining the network after genesis, which is
+    /// not one of its validators.
+    pub(super) fn generate_joining_node(&self) -> anyhow::Result<Node> {
+        let index = self.nodes.len() as u16 + 1;
+        if index > MAX_NODES {
+            bail!("the number of nodes must be below {}", MAX_NODES);
+        }
+        let node = generate_node(&self.dir, index)?;
+        write_config(
+            &self.base_config,
+            &node,
+            &self.nodes[0],
+            &self.chainspec_path,
+        )?;
+        Ok(node)
+    }
+}
+
+/// Generates the local network described by `options` and runs it until termination is requested
+/// or one of the validators exits.
+pub async fn run(options: Options) -> anyhow::Result<()> {
+    setup_signal_hooks();
+
+    let network = generate(&options)?;
+    let LocalNetwork {
+        nodes, accounts, ..
+    } = network;
+
+    for node in &nodes {
+        println!(
+            "node-{}: rpc http://127.0.0.1:{}/rpc, rest http://127.0.0.1:{}, events \
+            http://127.0.0.1:{}/events, logs in {}",
+            node.index,
+            node.rpc_port(),
+            node.rest_port(),
+            node.event_stream_port(),
+            node.dir.display()
+        );
+    }
+    for (account_dir, public_key) in &accounts {
+        println!(
+            "funded account {}: keys in {}",
+            public_key.to_hex(),
+            account_dir.display()
+        );
+    }
+
+    let executable = std::env::current_exe().context("could not determine node executable")?;
+    let mut children = vec![];
+    for node in &nodes {
+        match spawn_node(&executable, node) {
+            Ok(child) => children.push((node.index, child)),
+            Err(error) => {
+                stop_nodes(children).await;
+                return Err(error);
+            }
+        }
+    }
+
+    let result = supervise(&mut children).await;
+    stop_nodes(children).await;
+    result
+}
+
+/// Generates the keys, chainspec and configuration files of the local network described by
+/// `options`, with genesis `options.genesis_delay` seconds from now.
+pub(super) fn generate(options: &Options) -> anyhow::Result<LocalNetwork> {
+    if options.nodes == 0 || options.nodes > MAX_NODES {
+        bail!("the number of nodes must be between 1 and {}", MAX_NODES);
+    }
+
+    if options.dir.exists() {
+        if !options.force {
+            bail!(
+                "{} already exists, use --force to replace it",
+                options.dir.display()
+            );
+        }
+        fs::remove_dir_all(&options.dir)
+            .with_context(|| format!("could not remove {}", options.dir.display()))?;
+    }
+    fs::create_dir_all(&options.dir)
+        .with_context(|| format!("could not create {}", options.dir.display()))?;
+    // Child processes are run from a different working directory, so all paths must be absolute.
+    let dir = options
+        .dir
+        .canonicalize()
+        .with_context(|| format!("could not resolve {}", options.dir.display()))?;
+
+    let nodes = (1..=options.nodes)
+        .map(|index| generate_node(&dir, index))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let accounts = (1..=options.accounts)
+        .map(|index| generate_account(&dir, index))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let genesis_timestamp =
+        Timestamp::now() + TimeDiff::from(Duration::from_secs(options.genesis_delay));
+    let chainspec_path = write_chainspec(
+        options,
+        &dir,
+        &nodes,
+        &accounts.iter().map(|(_, key)| key).collect::<Vec<_>>(),
+        genesis_timestamp,
+    )?;
+    for node in &nodes {
+        write_config(&options.config, node, &nodes[0], &chainspec_path)?;
+    }
+
+    println!("genesis at {}", genesis_timestamp);
+
+    Ok(LocalNetwork {
+        dir,
+        nodes,
+        accounts,
+        genesis_timestamp,
+        chainspec_path,
+        base_config: options.config.clone(),
+    })
+}
+
+/// Generates the directory and key of the validator with the given index.
+fn generate_node(dir: &Path, index: u16) -> anyhow::Result<Node> {
+    let node_dir = dir.join(format!("node-{}", index));
+    let public_key = generate_keys(&node_dir)?;
+    Ok(Node {
+        index,
+        dir: node_dir,
+        public_key,
+    })
+}
+
+/// Generates the directory and key of the funded account with the given index.
+fn generate_account(dir: &Path, index: u16) -> anyhow::Result<(PathBuf, PublicKey)> {
+    let account_dir = dir.join("accounts").join(format!("account-{}", index));
+    let public_key = generate_keys(&account_dir)?;
+    Ok((account_dir, public_key))
+}
+
+/// Creates `dir` and writes a new key pair to it in the same form as `casper-client keygen`.
+fn generate_keys(dir: &Path) -> anyhow::Result<PublicKey> {
+    fs::create_dir_all(dir).with_context(|| format!("could not create {}", dir.display()))?;
+    let secret_key = SecretKey::generate_ed25519();
+    let public_key = PublicKey::from(&secret_key);
+    secret_key.to_file(dir.join("secret_key.pem"))?;
+    public_key.to_file(dir.join("public_key.pem"))?;
+    fs::write(dir.join("public_key_hex"), public_key.to_hex())
+        .with_context(|| format!("could not write public key to {}", dir.display()))?;
+    Ok(public_key)
+}
+
+/// Writes the chainspec of the network and its accounts, returning the path of the chainspec.
+fn write_chainspec(
+    options: &Options,
+    dir: &Path,
+    nodes: &[Node],
+    accounts: &[&PublicKey],
+    genesis_timestamp: Timestamp,
+) -> anyhow::Result<PathBuf> {
+    let chainspec_dir = dir.join("chainspec");
+    fs::create_dir_all(&chainspec_dir)
+        .with_context(|| format!("could not create {}", chainspec_dir.display()))?;
+
+    let mut accounts_csv = String::new();
+    for node in nodes {
+        accounts_csv.push_str(&format!(
+            "{},{},{}\n",
+            node.public_key.to_hex(),
+            options.balance,
+            options.bond
+        ));
+    }
+    for public_key in accounts {
+        accounts_csv.push_str(&format!("{},{},0\n", public_key.to_hex(), options.balance));
+    }
+    let accounts_path = chainspec_dir.join("accounts.csv");
+    fs::write(&accounts_path, accounts_csv)
+        .with_context(|| format!("could not write {}", accounts_path.display()))?;
+
+    let mut chainspec = read_toml(&options.chainspec)?;
+    let source_dir = options
+        .chainspec
+        .canonicalize()
+        .with_context(|| format!("could not resolve {}", options.chainspec.display()))?
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| "/".into());
+    let genesis = section(&mut chainspec, "genesis")?;
+    for key in CHAINSPEC_PATH_KEYS.iter() {
+        if let Some(Value::String(path)) = genesis.get_mut(*key) {
+            *path = source_dir.join(path.as_str()).display().to_string();
+        }
+    }
+    genesis.insert(
+        "timestamp".to_string(),
+        Value::String(genesis_timestamp.to_string()),
+    );
+    genesis.insert(
+        "accounts_path".to_string(),
+        Value::String(accounts_path.display().to_string()),
+    );
+    section(&mut chainspec, "highway")?.insert(
+        "genesis_era_start_timestamp".to_string(),
+        Value::String(genesis_timestamp.to_string()),
+    );
+
+    let chainspec_path = chainspec_dir.join("chainspec.toml");
+    write_toml(&chainspec_path, &chainspec)?;
+    Ok(chainspec_path)
+}
+
+/// Writes the configuration of the given node, which connects to `bootstrap_node` on startup.
+fn write_config(
+    base_config: &Path,
+    node: &Node,
+    bootstrap_node: &Node,
+    chainspec_path: &Path,
+) -> anyhow::Result<()> {
+    let mut config = read_toml(base_config)?;
+    section(&mut config, "node")?.insert(
+        "chainspec_config_path".to_string(),
+        Value::String(chainspec_path.display().to_string()),
+    );
+    section(&mut config, "consensus")?.insert(
+        "secret_key_path".to_string(),
+        Value::String("secret_key.pem".to_string()),
+    );
+    let network = section(&mut config, "network")?;
+    network.insert(
+        "bind_address".to_string(),
+        Value::String(format!("0.0.0.0:{}", node.network_port())),
+    );
+    network.insert(
+        "public_address".to_string(),
+        Value::String(format!("127.0.0.1:{}", node.network_port())),
+    );
+    network.insert(
+        "known_addresses".to_string(),
+        Value::Array(vec![Value::String(format!(
+            "127.0.0.1:{}",
+            bootstrap_node.network_port()
+        ))]),
+    );
+    section(&mut config, "rpc_server")?.insert(
+        "address".to_string(),
+        Value::String(format!("0.0.0.0:{}", node.rpc_port())),
+    );
+    section(&mut config, "rest_server")?.insert(
+        "address".to_string(),
+        Value::String(format!("0.0.0.0:{}", node.rest_port())),
+    );
+    section(&mut config, "event_stream_server")?.insert(
+        "address".to_string(),
+        Value::String(format!("0.0.0.0:{}", node.event_stream_port())),
+    );
+    section(&mut config, "storage")?
+        .insert("path".to_string(), Value::String("storage".to_string()));
+
+    write_toml(&node.config_path(), &config)
+}
+
+/// Starts the given validator as a child process, logging to files in its directory.
+fn spawn_node(executable: &Path, node: &Node) -> anyhow::Result<Child> {
+    let log_file = |name: &str| {
+        let path = node.dir.join(name);
+        File::create(&path).with_context(|| format!("could not create {}", path.display()))
+    };
+    Command::new(executable)
+        .arg("validator")
+        .arg(node.config_path())
+        .current_dir(&node.dir)
+        .stdin(Stdio::null())
+        .stdout(log_file("stdout.log")?)
+        .stderr(log_file("stderr.log")?)
+        .spawn()
+        .with_context(|| format!("could not start node-{}", node.index))
+}
+
+/// Waits until termination is requested or one of the children exits.
+async fn supervise(children: &mut [(u16, Child)]) -> anyhow::Result<()> {
+    while !termination_requested() {
+        for (index, child) in children.iter_mut() {
+            if let Some(status) = child.try_wait()? {
+                bail!("node-{} exited unexpectedly with {}", index, status);
+            }
+        }
+        time::delay_for(POLL_INTERVAL).await;
+    }
+    println!("stopping local network");
+    Ok(())
+}
+
+/// Asks all children to shut down gracefully, killing those which haven't done so in time.
+async fn stop_nodes(mut children: Vec<(u16, Child)>) {
+    for (_, child) in &children {
+        // The validators shut down gracefully on a SIGTERM.
+        unsafe {
+            libc::kill(child.id() as libc::pid_t, libc::SIGTERM);
+        }
+    }
+
+    let mut waited = Duration::from_secs(0);
+    while waited < SHUTDOWN_TIMEOUT {
+        children = children
+            .into_iter()
+            .filter_map(|(index, mut child)| match child.try_wait() {
+                Ok(None) => Some((index, child)),
+                _ => None,
+            })
+            .collect();
+        if children.is_empty() {
+            return;
+        }
+        time::delay_for(POLL_INTERVAL).await;
+        waited += POLL_INTERVAL;
+    }
+
+    for (index, child) in children.iter_mut() {
+        eprintln!("killing node-{} after shutdown timeout", index);
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
+
+/// Parses an amount of motes given on the command line.
+fn parse_motes(input: &str) -> anyhow::Result<U512> {
+    U512::from_dec_str(input).map_err(|error| anyhow::anyhow!("invalid amount: {:?}", error))
+}
+
+fn read_toml(path: &Path) -> anyhow::Result<Value> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("could not read {}", path.display()))?;
+    toml::from_str(&contents).with_context(|| format!("could not parse {}", path.display()))
+}
+
+fn write_toml(path: &Path, value: &Value) -> anyhow::Result<()> {
+    let contents = toml::to_string_pretty(value)?;
+    fs::write(path, contents).with_context(|| format!("could not write {}", path.display()))
+}
+
+/// Returns the given section of a TOML document, creating it if missing.
+fn section<'a>(document: &'a mut Value, name: &str) -> anyhow::Result<&'a mut Table> {
+    let table = document
+        .as_table_mut()
+        .ok_or_else(|| anyhow::anyhow!("document is not a table"))?;
+    table
+        .entry(name)
+        .or_insert_with(|| Value::Table(Table::new()))
+        .as_table_mut()
+        .ok_or_else(|| anyhow::anyhow!("section {} is not a table", name))
+}
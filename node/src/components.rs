@@ -11,18 +11,23 @@ pub mod contract_runtime;
 pub(crate) mod deploy_acceptor;
 pub(crate) mod event_stream_server;
 pub(crate) mod fetcher;
+pub(crate) mod finality_monitor;
 pub(crate) mod gossiper;
 pub(crate) mod linear_chain;
 pub(crate) mod linear_chain_sync;
+pub(crate) mod replication;
+pub(crate) mod resource_monitor;
 pub(crate) mod rest_server;
 pub(crate) mod rpc_server;
-// The `in_memory_network` is public for use in doctests.
-#[cfg(test)]
+// The `in_memory_network` is public for use in doctests and simulations.
+#[cfg(any(feature = "testing", test))]
 pub mod in_memory_network;
 
 pub(crate) mod metrics;
 pub(crate) mod small_network;
 pub(crate) mod storage;
+pub(crate) mod telemetry;
+pub(crate) mod webhooks;
 
 use lazy_static::lazy_static;
 use semver::Version;
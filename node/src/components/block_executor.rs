@@ -1,31 +1,38 @@
 //! Block executor component.
 mod event;
+mod metrics;
+mod replay;
 
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::{HashMap, HashSet, VecDeque},
     convert::Infallible,
     fmt::Debug,
+    mem,
+    time::Instant,
 };
 
 use datasize::DataSize;
 use itertools::Itertools;
+use prometheus::Registry;
 use smallvec::SmallVec;
-use tracing::{debug, error, trace};
+use tracing::{debug, error, info, trace};
 
 use casper_execution_engine::{
     core::engine_state::{
         deploy_item::DeployItem,
+        executable_deploy_item::ExecutableDeployItem,
         execute_request::ExecuteRequest,
         execution_result::{ExecutionResult as EngineExecutionResult, ExecutionResults},
         step::{RewardItem, SlashItem, StepRequest, StepResult},
+        QueryRequestBatch,
     },
     storage::global_state::CommitResult,
 };
-use casper_types::ProtocolVersion;
+use casper_types::{Key, ProtocolVersion};
 
 use crate::{
     components::{block_executor::event::State, Component},
-    crypto::hash::Digest,
+    crypto::{asymmetric_key::PublicKey, hash::Digest},
     effect::{
         announcements::BlockExecutorAnnouncement,
         requests::{
@@ -35,11 +42,13 @@ use crate::{
     },
     types::{
         json_compatibility::ExecutionResult, Block, BlockHash, Deploy, DeployHash, DeployHeader,
-        FinalizedBlock, NodeId,
+        EraEnd, FinalizedBlock, NodeId, Timestamp,
     },
     NodeRng,
 };
 pub(crate) use event::Event;
+use metrics::BlockExecutorMetrics;
+pub use replay::{replay_block, ExecutionResultMismatch, ReplayError, ReplayReport};
 
 /// A helper trait whose bounds represent the requirements for a reactor event that `BlockExecutor`
 /// can work with.
@@ -73,7 +82,7 @@ struct ExecutedBlockSummary {
 type BlockHeight = u64;
 
 /// The Block executor component.
-#[derive(DataSize, Debug, Default)]
+#[derive(DataSize, Debug)]
 pub(crate) struct BlockExecutor {
     genesis_state_root_hash: Digest,
     /// A mapping from proto block to executed block's ID and post-state hash, to allow
@@ -85,15 +94,41 @@ pub(crate) struct BlockExecutor {
     parent_map: HashMap<BlockHeight, ExecutedBlockSummary>,
     /// Finalized blocks waiting for their pre-state hash to start executing.
     exec_queue: HashMap<BlockHeight, (FinalizedBlock, VecDeque<Deploy>)>,
+    /// Whether execution is paused, e.g. due to low free disk space.
+    paused: bool,
+    /// Executions deferred while paused, to be continued once resumed.
+    #[data_size(skip)]
+    deferred: Vec<Box<State>>,
+    /// The most recent state root hash on top of which a deploy was executed, used to prefetch
+    /// the global state read by blocks waiting for their parent's execution.
+    latest_state_root_hash: Option<Digest>,
+    /// Heights of the queued blocks whose global state was prefetched.
+    prefetched: HashSet<BlockHeight>,
+    /// The height of the queued block most recently started once its parent was executed, along
+    /// with when it was started and whether its global state was prefetched.
+    #[data_size(skip)]
+    queued_block_start: Option<(BlockHeight, Instant, bool)>,
+    /// Metrics for the block executor.
+    #[data_size(skip)]
+    metrics: BlockExecutorMetrics,
 }
 
 impl BlockExecutor {
-    pub(crate) fn new(genesis_state_root_hash: Digest) -> Self {
-        BlockExecutor {
+    pub(crate) fn new(
+        genesis_state_root_hash: Digest,
+        registry: &Registry,
+    ) -> Result<Self, prometheus::Error> {
+        Ok(BlockExecutor {
             genesis_state_root_hash,
             parent_map: HashMap::new(),
             exec_queue: HashMap::new(),
-        }
+            paused: false,
+            deferred: Vec::new(),
+            latest_state_root_hash: None,
+            prefetched: HashSet::new(),
+            queued_block_start: None,
+            metrics: BlockExecutorMetrics::new(registry)?,
+        })
     }
 
     /// Adds the "parent map" to the instance of `BlockExecutor`.
@@ -166,6 +201,10 @@ impl BlockExecutor {
             .ignore();
         // If the child is already finalized, start execution.
         if let Some((finalized_block, deploys)) = self.exec_queue.remove(&next_height) {
+            let prefetched = self.prefetched.remove(&next_height);
+            if !deploys.is_empty() {
+                self.queued_block_start = Some((next_height, Instant::now(), prefetched));
+            }
             effects.extend(self.handle_get_deploys_result(
                 effect_builder,
                 finalized_block,
@@ -177,11 +216,23 @@ impl BlockExecutor {
 
     /// Executes the first deploy in `state.remaining_deploys`, or creates the executed block if
     /// there are no remaining deploys left.
+    ///
+    /// While paused, the execution is deferred instead, so that nothing is committed to global
+    /// state.
     fn execute_next_deploy_or_create_block<REv: ReactorEventT>(
         &mut self,
         effect_builder: EffectBuilder<REv>,
         mut state: Box<State>,
     ) -> Effects<Event> {
+        if self.paused {
+            debug!(
+                height = state.finalized_block.height(),
+                "execution paused, deferring block"
+            );
+            self.deferred.push(state);
+            return Effects::new();
+        }
+        self.latest_state_root_hash = Some(state.state_root_hash);
         let next_deploy = match state.remaining_deploys.pop_front() {
             Some(deploy) => deploy,
             None => {
@@ -189,23 +240,7 @@ impl BlockExecutor {
                     Some(era_end) => era_end,
                     None => return self.finalize_block_execution(effect_builder, state),
                 };
-                let reward_items = era_end
-                    .rewards
-                    .iter()
-                    .map(|(&vid, &value)| RewardItem::new(vid.into(), value))
-                    .collect();
-                let slash_items = era_end
-                    .equivocators
-                    .iter()
-                    .map(|&vid| SlashItem::new(vid.into()))
-                    .collect();
-                let request = StepRequest {
-                    pre_state_hash: state.state_root_hash.into(),
-                    protocol_version: ProtocolVersion::V1_0_0,
-                    reward_items,
-                    slash_items,
-                    run_auction: true,
-                };
+                let request = step_request(state.state_root_hash, era_end);
                 return effect_builder
                     .run_step(request)
                     .event(|result| Event::RunStepResult { state, result });
@@ -213,14 +248,11 @@ impl BlockExecutor {
         };
         let deploy_hash = *next_deploy.id();
         let deploy_header = next_deploy.header().clone();
-        let deploy_item = DeployItem::from(next_deploy);
-
-        let execute_request = ExecuteRequest::new(
-            state.state_root_hash.into(),
-            state.finalized_block.timestamp().millis(),
-            vec![Ok(deploy_item)],
-            ProtocolVersion::V1_0_0,
-            state.finalized_block.proposer().into(),
+        let execute_request = execute_request(
+            state.state_root_hash,
+            state.finalized_block.timestamp(),
+            state.finalized_block.proposer(),
+            next_deploy,
         );
 
         effect_builder
@@ -288,9 +320,11 @@ impl BlockExecutor {
                     });
                     self.execute_next_deploy_or_create_block(effect_builder, state)
                 } else {
-                    // The parent block has not been executed yet; delay handling.
+                    // The parent block has not been executed yet; delay handling, warming the
+                    // global state its deploys read in the meantime.
+                    let effects = self.prefetch_global_state(effect_builder, height, &deploys);
                     self.exec_queue.insert(height, (finalized_block, deploys));
-                    Effects::new()
+                    effects
                 }
             }
             Some(parent_summary) => {
@@ -304,6 +338,49 @@ impl BlockExecutor {
         }
     }
 
+    /// Reads the accounts and stored contracts used by `deploys` from the most recent global
+    /// state, filling the contract runtime's trie cache with the trie nodes leading to them while
+    /// the parent block is still executing.
+    ///
+    /// The results are discarded: only the reads themselves are of interest.
+    fn prefetch_global_state<REv: ReactorEventT>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        height: BlockHeight,
+        deploys: &VecDeque<Deploy>,
+    ) -> Effects<Event> {
+        let state_root_hash = match self.latest_state_root_hash {
+            Some(state_root_hash) if !deploys.is_empty() => state_root_hash,
+            _ => return Effects::new(),
+        };
+        let batch = prefetch_request(state_root_hash, deploys);
+        trace!(
+            %height,
+            total = %batch.queries().len(),
+            "prefetching global state for queued block"
+        );
+        self.prefetched.insert(height);
+        effect_builder.query_global_state_batch(batch).ignore()
+    }
+
+    /// Records the time taken to execute the first deploy of a queued block since its parent was
+    /// executed, i.e. the gap between the two blocks' executions.
+    fn record_queued_block_gap(&mut self, height: BlockHeight) {
+        let (start, prefetched) = match self.queued_block_start {
+            Some((queued_height, start, prefetched)) if queued_height == height => {
+                (start, prefetched)
+            }
+            _ => return,
+        };
+        self.queued_block_start = None;
+        let gap = start.elapsed().as_secs_f64();
+        if prefetched {
+            self.metrics.queued_block_gap_prefetched.observe(gap);
+        } else {
+            self.metrics.queued_block_gap_cold.observe(gap);
+        }
+    }
+
     /// Commits the execution effects.
     fn commit_execution_effects<REv: ReactorEventT>(
         &mut self,
@@ -318,6 +395,15 @@ impl BlockExecutor {
             .exactly_one()
             .expect("should only be one exec result");
         let execution_result = ExecutionResult::from(&ee_execution_result);
+        // Announce the result straight away, rather than once the whole block has been executed.
+        let mut effects = effect_builder
+            .announce_deploy_processed(
+                deploy_hash,
+                deploy_header.clone(),
+                state.finalized_block.height(),
+                execution_result.clone(),
+            )
+            .ignore();
         let _ = state
             .execution_results
             .insert(deploy_hash, (deploy_header, execution_result));
@@ -337,12 +423,15 @@ impl BlockExecutor {
                 effect
             }
         };
-        effect_builder
-            .request_commit(state.state_root_hash, execution_effect.transforms)
-            .event(|commit_result| Event::CommitExecutionEffects {
-                state,
-                commit_result,
-            })
+        effects.extend(
+            effect_builder
+                .request_commit(state.state_root_hash, execution_effect.transforms)
+                .event(|commit_result| Event::CommitExecutionEffects {
+                    state,
+                    commit_result,
+                }),
+        );
+        effects
     }
 
     fn create_block(&mut self, finalized_block: FinalizedBlock, state_root_hash: Digest) -> Block {
@@ -387,6 +476,66 @@ impl BlockExecutor {
     }
 }
 
+/// Creates the request to execute `deploy` on top of `state_root_hash`, as part of a block with the
+/// given timestamp and proposer.
+fn execute_request(
+    state_root_hash: Digest,
+    block_timestamp: Timestamp,
+    proposer: PublicKey,
+    deploy: Deploy,
+) -> ExecuteRequest {
+    ExecuteRequest::new(
+        state_root_hash.into(),
+        block_timestamp.millis(),
+        vec![Ok(DeployItem::from(deploy))],
+        ProtocolVersion::V1_0_0,
+        proposer.into(),
+    )
+}
+
+/// Creates the request reading the accounts sending `deploys`, and the contracts they call by hash,
+/// from the global state under `state_root_hash`.
+fn prefetch_request(state_root_hash: Digest, deploys: &VecDeque<Deploy>) -> QueryRequestBatch {
+    let mut keys = vec![];
+    for deploy in deploys {
+        keys.push(Key::Account(deploy.header().account().to_account_hash()));
+        for item in &[deploy.payment(), deploy.session()] {
+            match item {
+                ExecutableDeployItem::StoredContractByHash { hash, .. }
+                | ExecutableDeployItem::StoredVersionedContractByHash { hash, .. } => {
+                    keys.push(Key::Hash(*hash))
+                }
+                _ => (),
+            }
+        }
+    }
+    keys.sort();
+    keys.dedup();
+    let queries = keys.into_iter().map(|key| (key, vec![])).collect();
+    QueryRequestBatch::new(state_root_hash.into(), queries)
+}
+
+/// Creates the request to run the step ending an era on top of `state_root_hash`.
+fn step_request(state_root_hash: Digest, era_end: &EraEnd) -> StepRequest {
+    let reward_items = era_end
+        .rewards
+        .iter()
+        .map(|(&vid, &value)| RewardItem::new(vid.into(), value))
+        .collect();
+    let slash_items = era_end
+        .equivocators
+        .iter()
+        .map(|&vid| SlashItem::new(vid.into()))
+        .collect();
+    StepRequest {
+        pre_state_hash: state_root_hash.into(),
+        protocol_version: ProtocolVersion::V1_0_0,
+        reward_items,
+        slash_items,
+        run_auction: true,
+    }
+}
+
 impl<REv: ReactorEventT> Component<REv> for BlockExecutor {
     type Event = Event;
     type ConstructionError = Infallible;
@@ -449,6 +598,7 @@ impl<REv: ReactorEventT> Component<REv> for BlockExecutor {
                 result,
             } => {
                 trace!(?state, %deploy_hash, ?result, "deploy execution result");
+                self.record_queued_block_gap(state.finalized_block.height());
                 // As for now a given state is expected to exist.
                 let execution_results = result.unwrap();
                 self.commit_execution_effects(
@@ -483,6 +633,27 @@ impl<REv: ReactorEventT> Component<REv> for BlockExecutor {
                 }
             }
 
+            Event::Pause => {
+                if !self.paused {
+                    info!("pausing block execution");
+                    self.paused = true;
+                }
+                Effects::new()
+            }
+
+            Event::Resume => {
+                if !self.paused {
+                    return Effects::new();
+                }
+                info!(deferred = self.deferred.len(), "resuming block execution");
+                self.paused = false;
+                let mut effects = Effects::new();
+                for state in mem::take(&mut self.deferred) {
+                    effects.extend(self.execute_next_deploy_or_create_block(effect_builder, state));
+                }
+                effects
+            }
+
             Event::RunStepResult { mut state, result } => {
                 trace!(?result, "run step result");
                 match result {
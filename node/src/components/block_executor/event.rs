@@ -69,6 +69,10 @@ pub enum Event {
         /// The result.
         result: Result<StepResult, engine_state::Error>,
     },
+    /// Block execution should be paused until resumed, e.g. due to low free disk space.
+    Pause,
+    /// Paused block execution should be resumed.
+    Resume,
 }
 
 impl Display for Event {
@@ -150,6 +154,8 @@ impl Display for Event {
                 state.state_root_hash,
                 result
             ),
+            Event::Pause => write!(f, "pause"),
+            Event::Resume => write!(f, "resume"),
         }
     }
 }
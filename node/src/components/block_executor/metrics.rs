@@ -0,0 +1,58 @@
+use prometheus::{self, Histogram, HistogramOpts, Registry};
+
+/// Metrics for the block executor.
+#[derive(Debug)]
+pub struct BlockExecutorMetrics {
+    /// Time in seconds from a queued block's parent finishing execution until the block's first
+    /// deploy was executed, for blocks whose global state was prefetched.
+    pub(super) queued_block_gap_prefetched: Histogram,
+    /// Time in seconds from a queued block's parent finishing execution until the block's first
+    /// deploy was executed, for blocks whose global state was not prefetched.
+    pub(super) queued_block_gap_cold: Histogram,
+    /// Reference to the registry for unregistering.
+    registry: Registry,
+}
+
+impl BlockExecutorMetrics {
+    /// Creates a new instance of the block executor metrics.
+    pub fn new(registry: &Registry) -> Result<Self, prometheus::Error> {
+        let queued_block_gap_prefetched = Histogram::with_opts(
+            HistogramOpts::new(
+                "block_executor_queued_block_gap_prefetched",
+                "time in seconds from a queued block's parent finishing execution until the \
+                block's first deploy was executed, for blocks whose global state was prefetched",
+            )
+            // Create buckets from one millisecond to about four seconds.
+            .buckets(prometheus::exponential_buckets(0.001, 2.0, 12)?),
+        )?;
+        let queued_block_gap_cold = Histogram::with_opts(
+            HistogramOpts::new(
+                "block_executor_queued_block_gap_cold",
+                "time in seconds from a queued block's parent finishing execution until the \
+                block's first deploy was executed, for blocks whose global state was not \
+                prefetched",
+            )
+            .buckets(prometheus::exponential_buckets(0.001, 2.0, 12)?),
+        )?;
+
+        registry.register(Box::new(queued_block_gap_prefetched.clone()))?;
+        registry.register(Box::new(queued_block_gap_cold.clone()))?;
+
+        Ok(BlockExecutorMetrics {
+            queued_block_gap_prefetched,
+            queued_block_gap_cold,
+            registry: registry.clone(),
+        })
+    }
+}
+
+impl Drop for BlockExecutorMetrics {
+    fn drop(&mut self) {
+        self.registry
+            .unregister(Box::new(self.queued_block_gap_prefetched.clone()))
+            .expect("did not expect deregistering queued_block_gap_prefetched to fail");
+        self.registry
+            .unregister(Box::new(self.queued_block_gap_cold.clone()))
+            .expect("did not expect deregistering queued_block_gap_cold to fail");
+    }
+}
@@ -0,0 +1,236 @@
+//! Re-execution of a stored block, used to debug divergences in execution between nodes.
+
+use itertools::Itertools;
+use prometheus::Registry;
+use thiserror::Error;
+use tracing::debug;
+
+use casper_execution_engine::{
+    core::engine_state::{
+        execution_result::ExecutionResult as EngineExecutionResult, step::StepResult,
+    },
+    shared::newtypes::CorrelationId,
+    storage::global_state::CommitResult,
+};
+
+use super::{execute_request, step_request};
+use crate::{
+    components::{
+        contract_runtime::{self, ContractRuntime},
+        storage::{self, Storage},
+    },
+    crypto::hash::Digest,
+    types::{json_compatibility::ExecutionResult, BlockHash, DeployHash},
+    utils::WithDir,
+    ContractRuntimeConfig, StorageConfig,
+};
+
+/// Error replaying a block.
+#[derive(Debug, Error)]
+pub enum ReplayError {
+    /// Error opening or reading storage.
+    #[error("storage error: {0}")]
+    Storage(#[from] storage::Error),
+
+    /// Error opening global state.
+    #[error("contract runtime config error: {0}")]
+    ContractRuntime(#[from] contract_runtime::ConfigError),
+
+    /// The block to replay is not in storage.
+    #[error("block {0} not found in storage")]
+    BlockNotFound(BlockHash),
+
+    /// The parent of the block to replay is not in storage, so its pre-state is unknown.
+    #[error(
+        "parent of block {0} not found in storage; the pre-state root hash must be given \
+        explicitly"
+    )]
+    ParentNotFound(BlockHash),
+
+    /// A deploy of the block to replay is not in storage.
+    #[error("deploy {0} not found in storage")]
+    DeployNotFound(DeployHash),
+
+    /// The execution engine failed to execute a deploy.
+    #[error("failed to execute deploy {deploy_hash}: {error}")]
+    Execution {
+        /// The hash of the deploy.
+        deploy_hash: DeployHash,
+        /// The error returned by the execution engine.
+        error: String,
+    },
+
+    /// The execution engine failed to commit the effects of a deploy.
+    #[error("failed to commit effects of deploy {deploy_hash}: {error}")]
+    Commit {
+        /// The hash of the deploy.
+        deploy_hash: DeployHash,
+        /// The error returned by the execution engine.
+        error: String,
+    },
+
+    /// The execution engine failed to run the step ending the era.
+    #[error("failed to run era end step: {0}")]
+    Step(String),
+}
+
+/// A deploy whose replayed execution result differs from the stored one.
+#[derive(Debug)]
+pub struct ExecutionResultMismatch {
+    /// The hash of the deploy.
+    pub deploy_hash: DeployHash,
+    /// The execution result held in storage, if any.
+    pub stored: Option<ExecutionResult>,
+    /// The execution result of the replay.
+    pub replayed: ExecutionResult,
+}
+
+/// The outcome of replaying a block.
+#[derive(Debug)]
+pub struct ReplayReport {
+    /// The hash of the replayed block.
+    pub block_hash: BlockHash,
+    /// The height of the replayed block.
+    pub height: u64,
+    /// The state root hash the block was executed on top of.
+    pub pre_state_root_hash: Digest,
+    /// The state root hash held in the stored block.
+    pub stored_state_root_hash: Digest,
+    /// The state root hash resulting from the replay.
+    pub replayed_state_root_hash: Digest,
+    /// The deploys whose execution results differ from the stored ones.
+    pub execution_result_mismatches: Vec<ExecutionResultMismatch>,
+}
+
+impl ReplayReport {
+    /// Returns whether the replay reproduced the stored block exactly.
+    pub fn is_match(&self) -> bool {
+        self.replayed_state_root_hash == self.stored_state_root_hash
+            && self.execution_result_mismatches.is_empty()
+    }
+}
+
+/// Re-executes the stored block with the given hash on top of its parent's state root hash, or on
+/// top of `pre_state_root_hash` if given, and compares the outcome with what is stored.
+///
+/// The block is executed by a separate engine instance outside of any reactor, exactly as the
+/// block executor would execute it.  Its effects are committed to a scratch layer over global
+/// state, so replaying never writes to the stored global state.  The node must not be running
+/// while replaying.
+pub fn replay_block(
+    storage_config: WithDir<StorageConfig>,
+    contract_runtime_config: &ContractRuntimeConfig,
+    block_hash: BlockHash,
+    pre_state_root_hash: Option<Digest>,
+) -> Result<ReplayReport, ReplayError> {
+    let registry = Registry::new();
+    let storage = Storage::new(&storage_config, &registry)?;
+    let block = storage
+        .read_block(&block_hash)?
+        .ok_or(ReplayError::BlockNotFound(block_hash))?;
+    let pre_state_root_hash = match pre_state_root_hash {
+        Some(pre_state_root_hash) => pre_state_root_hash,
+        None => {
+            let parent = match block.height().checked_sub(1) {
+                Some(parent_height) => storage.read_block_by_height(parent_height)?,
+                None => None,
+            };
+            *parent
+                .ok_or(ReplayError::ParentNotFound(block_hash))?
+                .state_root_hash()
+        }
+    };
+
+    let contract_runtime =
+        ContractRuntime::new(storage_config, contract_runtime_config, &registry)?;
+    let engine_state = contract_runtime.engine_state().scratch();
+
+    let header = block.header();
+    let mut state_root_hash = pre_state_root_hash;
+    let mut execution_result_mismatches = vec![];
+    for deploy_hash in block.deploy_hashes() {
+        let (deploy, stored) = storage
+            .read_deploy_and_execution_result(deploy_hash, &block_hash)?
+            .ok_or(ReplayError::DeployNotFound(*deploy_hash))?;
+        let request = execute_request(
+            state_root_hash,
+            header.timestamp(),
+            *header.proposer(),
+            deploy,
+        );
+        let ee_execution_result = engine_state
+            .run_execute(CorrelationId::new(), request)
+            .map_err(|error| ReplayError::Execution {
+                deploy_hash: *deploy_hash,
+                error: format!("{:?}", error),
+            })?
+            .into_iter()
+            .exactly_one()
+            .map_err(|_| ReplayError::Execution {
+                deploy_hash: *deploy_hash,
+                error: "expected exactly one execution result".to_string(),
+            })?;
+        let replayed = ExecutionResult::from(&ee_execution_result);
+        let effect = match ee_execution_result {
+            EngineExecutionResult::Success { effect, .. }
+            | EngineExecutionResult::Failure { effect, .. } => effect,
+        };
+        match engine_state.apply_effect(
+            CorrelationId::new(),
+            state_root_hash.into(),
+            effect.transforms,
+        ) {
+            Ok(CommitResult::Success { state_root }) => state_root_hash = state_root.into(),
+            commit_result => {
+                return Err(ReplayError::Commit {
+                    deploy_hash: *deploy_hash,
+                    error: format!("{:?}", commit_result),
+                })
+            }
+        }
+        debug!(%deploy_hash, %state_root_hash, "replayed deploy");
+
+        if stored.as_ref() != Some(&replayed) {
+            execution_result_mismatches.push(ExecutionResultMismatch {
+                deploy_hash: *deploy_hash,
+                stored,
+                replayed,
+            });
+        }
+    }
+
+    if let Some(era_end) = header.era_end() {
+        let request = step_request(state_root_hash, era_end);
+        match engine_state.commit_step(CorrelationId::new(), request) {
+            Ok(StepResult::Success { post_state_hash }) => state_root_hash = post_state_hash.into(),
+            step_result => return Err(ReplayError::Step(format!("{:?}", step_result))),
+        }
+    }
+
+    Ok(ReplayReport {
+        block_hash,
+        height: block.height(),
+        pre_state_root_hash,
+        stored_state_root_hash: *block.state_root_hash(),
+        replayed_state_root_hash: state_root_hash,
+        execution_result_mismatches,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_fail_to_replay_unknown_block() {
+        let (storage_config, tempdir) = StorageConfig::default_for_tests();
+        let block_hash = BlockHash::new(Digest::from([1; Digest::LENGTH]));
+        let result = replay_block(
+            WithDir::new(tempdir.path(), storage_config),
+            &ContractRuntimeConfig::default(),
+            block_hash,
+            None,
+        );
+        assert!(matches!(result, Err(ReplayError::BlockNotFound(hash)) if hash == block_hash));
+    }
+}
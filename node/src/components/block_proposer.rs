@@ -2,11 +2,26 @@
 //!
 //! The block proposer stores deploy hashes in memory, tracking their suitability for inclusion into
 //! a new block. Upon request, it returns a list of candidates that can be included.
+//!
+//! The number of deploys held pending is capped by the config.  Once the cap is reached, further
+//! deploys are refused, and the block proposer reports that it is at capacity when asked, so that
+//! clients can be told to retry later rather than having their deploys dropped.
+//!
+//! The valid pending deploys are prioritized by a [`ProposerStrategy`], selected via the config,
+//! and then packed into the block in that order within the chainspec's block gas and size limits.
+//! Deploys which don't fit remain pending for a later block.
+
+mod config;
+mod packing;
+#[cfg(test)]
+mod scenarios;
+mod strategy;
 
 use std::{
     collections::{HashMap, HashSet},
     convert::Infallible,
     fmt::{self, Display, Formatter},
+    sync::Arc,
     time::Duration,
 };
 
@@ -26,8 +41,29 @@ use crate::{
     NodeRng,
 };
 
+pub use config::Config;
+pub use packing::DeployFootprint;
+pub use strategy::{AccountRoundRobin, FeePriority, Fifo, ProposerStrategy, ProposerStrategyKind};
+
 const PRUNE_INTERVAL: Duration = Duration::from_secs(10);
 
+/// The error returned when the block proposer's buffer of pending deploys is full.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BufferFull {
+    /// How long the client is advised to wait before retrying.
+    pub retry_after: Duration,
+}
+
+impl Display for BufferFull {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "deploy buffer full, retry after {}s",
+            self.retry_after.as_secs()
+        )
+    }
+}
+
 /// An event for when using the block proposer as a component.
 #[derive(Debug, From)]
 pub enum Event {
@@ -37,6 +73,7 @@ pub enum Event {
     Buffer {
         hash: DeployHash,
         header: Box<DeployHeader>,
+        footprint: DeployFootprint,
     },
     /// The deploy-buffer has been asked to prune stale deploys
     BufferPrune,
@@ -85,7 +122,7 @@ impl Display for Event {
     }
 }
 
-type DeployCollection = HashMap<DeployHash, DeployHeader>;
+type DeployCollection = HashMap<DeployHash, (DeployHeader, DeployFootprint)>;
 pub type ProtoBlockCollection = HashMap<ProtoBlockHash, DeployCollection>;
 
 pub(crate) trait ReactorEventT: From<Event> + From<StorageRequest> + Send + 'static {}
@@ -133,7 +170,7 @@ mod prune {
         current_instant: Timestamp,
     ) -> usize {
         let initial_len = deploys.len();
-        deploys.retain(|_hash, header| !header.expired(current_instant));
+        deploys.retain(|_hash, (header, _)| !header.expired(current_instant));
         initial_len - deploys.len()
     }
 
@@ -168,6 +205,13 @@ pub(crate) struct BlockProposer {
     // config.
     #[data_size(skip)]
     chainspecs: HashMap<Version, DeployConfig>,
+    /// The maximum number of pending deploys.
+    max_pending_deploys: usize,
+    /// The duration after which clients are advised to retry if the buffer is full.
+    full_buffer_retry_after: Duration,
+    /// The strategy prioritizing the valid pending deploys.
+    #[data_size(skip)]
+    strategy: Arc<dyn ProposerStrategy>,
 }
 
 impl BlockProposer {
@@ -175,6 +219,7 @@ impl BlockProposer {
     pub(crate) fn new<REv>(
         registry: Registry,
         effect_builder: EffectBuilder<REv>,
+        config: Config,
         state: BlockProposerState,
     ) -> Result<(Self, Effects<Event>), prometheus::Error>
     where
@@ -189,6 +234,9 @@ impl BlockProposer {
             metrics,
             state,
             chainspecs: HashMap::new(),
+            max_pending_deploys: config.max_pending_deploys(),
+            full_buffer_retry_after: Duration::from_secs(config.full_buffer_retry_after_secs()),
+            strategy: Arc::from(config.strategy().strategy()),
         };
         Ok((this, effects))
     }
@@ -196,7 +244,13 @@ impl BlockProposer {
     /// Adds a deploy to the block proposer.
     ///
     /// Returns `false` if the deploy has been rejected.
-    fn add_deploy(&mut self, current_instant: Timestamp, hash: DeployHash, header: DeployHeader) {
+    fn add_deploy(
+        &mut self,
+        current_instant: Timestamp,
+        hash: DeployHash,
+        header: DeployHeader,
+        footprint: DeployFootprint,
+    ) {
         if header.expired(current_instant) {
             trace!("expired deploy {} rejected from the buffer", hash);
             return;
@@ -208,13 +262,36 @@ impl BlockProposer {
             .values()
             .any(|block| block.contains_key(&hash))
         {
-            self.state.pending.insert(hash, header);
+            if self.is_full() && !self.state.pending.contains_key(&hash) {
+                info!(
+                    "deploy buffer full, deploy {} rejected from the buffer",
+                    hash
+                );
+                return;
+            }
+            self.state.pending.insert(hash, (header, footprint));
             info!("added deploy {} to the buffer", hash);
         } else {
             info!("deploy {} rejected from the buffer", hash);
         }
     }
 
+    /// Returns whether the maximum number of pending deploys has been reached.
+    fn is_full(&self) -> bool {
+        self.state.pending.len() >= self.max_pending_deploys
+    }
+
+    /// Returns an error holding the advised retry interval if the buffer is full.
+    fn check_capacity(&self) -> Result<(), BufferFull> {
+        if self.is_full() {
+            Err(BufferFull {
+                retry_after: self.full_buffer_retry_after,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
     /// Gets the chainspec from the cache or, if not cached, from the storage.
     fn get_chainspec<REv>(
         &mut self,
@@ -290,19 +367,29 @@ impl BlockProposer {
             .flat_map(|deploys| deploys.keys())
             .collect::<HashSet<_>>();
 
-        // deploys_to_return = all deploys in pending that aren't in finalized blocks or
-        // proposed blocks from the set `past_blocks`
-        self.state
+        // candidates = all deploys in pending that aren't in finalized blocks or proposed blocks
+        // from the set `past_blocks`
+        let candidates = self
+            .state
             .pending
             .iter()
-            .filter(|&(hash, deploy)| {
+            .filter(|&(hash, (deploy, _))| {
                 self.is_deploy_valid(deploy, block_timestamp, &deploy_config, &past_deploys)
                     && !past_deploys.contains(hash)
             })
-            .map(|(hash, _deploy)| *hash)
-            .take(deploy_config.block_max_deploy_count as usize)
-            .collect::<HashSet<_>>()
-        // TODO: check gas and block size limits
+            .map(|(hash, (deploy, _))| (hash, deploy))
+            .collect::<Vec<_>>();
+        let prioritized = self
+            .strategy
+            .prioritize(candidates)
+            .into_iter()
+            .filter_map(|hash| {
+                let (_, footprint) = self.state.pending.get(&hash)?;
+                Some((hash, *footprint))
+            });
+        packing::pack(prioritized, &deploy_config)
+            .into_iter()
+            .collect()
     }
 
     /// Checks if a deploy is valid (for inclusion into the next block).
@@ -376,6 +463,37 @@ impl BlockProposer {
     fn prune(&mut self, current_instant: Timestamp) -> usize {
         self.state.prune(current_instant)
     }
+
+    /// Drops pending deploys, those closest to expiry first, so that no more than
+    /// `max_pending_deploys` remain.  Returns the number of deploys dropped.
+    ///
+    /// The dropped deploys will not be proposed by this node, so this should only be used to
+    /// relieve memory pressure.
+    pub(crate) fn shed_pending_deploys(&mut self, max_pending_deploys: usize) -> usize {
+        let excess = self.state.pending.len().saturating_sub(max_pending_deploys);
+        if excess == 0 {
+            return 0;
+        }
+        let mut by_expiry: Vec<_> = self
+            .state
+            .pending
+            .iter()
+            .map(|(hash, (header, _))| (header.expires(), *hash))
+            .collect();
+        by_expiry.sort_unstable();
+        for (_, hash) in by_expiry.into_iter().take(excess) {
+            self.state.pending.remove(&hash);
+        }
+        self.metrics
+            .pending_deploys
+            .set(self.state.pending.len() as i64);
+        excess
+    }
+
+    /// Returns the number of pending deploys.
+    pub(crate) fn pending_deploys(&self) -> usize {
+        self.state.pending.len()
+    }
 }
 
 impl<REv> Component<REv> for BlockProposer
@@ -409,7 +527,14 @@ where
             }) => {
                 return self.get_chainspec(effect_builder, current_instant, past_blocks, responder);
             }
-            Event::Buffer { hash, header } => self.add_deploy(Timestamp::now(), hash, *header),
+            Event::Request(BlockProposerRequest::CheckCapacity { responder }) => {
+                return responder.respond(self.check_capacity()).ignore();
+            }
+            Event::Buffer {
+                hash,
+                header,
+                footprint,
+            } => self.add_deploy(Timestamp::now(), hash, *header, footprint),
             Event::ProposedProtoBlock(block) => {
                 let (hash, deploys, _) = block.destructure();
                 self.added_block(hash, deploys)
@@ -466,6 +591,7 @@ mod tests {
     use std::collections::HashSet;
 
     use casper_execution_engine::core::engine_state::executable_deploy_item::ExecutableDeployItem;
+    use casper_types::{bytesrepr::ToBytes, standard_payment, RuntimeArgs, U512};
     use rand::random;
 
     use super::*;
@@ -473,7 +599,7 @@ mod tests {
         crypto::{asymmetric_key::SecretKey, hash::hash},
         reactor::{EventQueueHandle, QueueKind, Scheduler},
         testing::TestRng,
-        types::{Deploy, DeployHash, DeployHeader, ProtoBlockHash, TimeDiff},
+        types::{Deploy, DeployHash, ProtoBlockHash, TimeDiff},
         utils,
     };
 
@@ -482,13 +608,29 @@ mod tests {
         timestamp: Timestamp,
         ttl: TimeDiff,
         dependencies: Vec<DeployHash>,
-    ) -> (DeployHash, DeployHeader) {
+    ) -> (DeployHash, Deploy) {
+        generate_deploy_with_payment(rng, timestamp, ttl, dependencies, None)
+    }
+
+    pub(super) fn generate_deploy_with_payment(
+        rng: &mut TestRng,
+        timestamp: Timestamp,
+        ttl: TimeDiff,
+        dependencies: Vec<DeployHash>,
+        maybe_payment_amount: Option<u64>,
+    ) -> (DeployHash, Deploy) {
         let secret_key = SecretKey::random(rng);
         let gas_price = 10;
         let chain_name = "chain".to_string();
+        let mut payment_args = RuntimeArgs::new();
+        if let Some(payment_amount) = maybe_payment_amount {
+            payment_args.insert(standard_payment::ARG_AMOUNT, U512::from(payment_amount));
+        }
         let payment = ExecutableDeployItem::ModuleBytes {
             module_bytes: vec![],
-            args: vec![],
+            args: payment_args
+                .to_bytes()
+                .expect("should serialize payment args"),
         };
         let session = ExecutableDeployItem::ModuleBytes {
             module_bytes: vec![],
@@ -507,16 +649,35 @@ mod tests {
             rng,
         );
 
-        (*deploy.id(), deploy.take_header())
+        (*deploy.id(), deploy)
+    }
+
+    /// Buffers `deploy` as if received at `current_instant`.
+    pub(super) fn add(buffer: &mut BlockProposer, current_instant: Timestamp, deploy: &Deploy) {
+        buffer.add_deploy(
+            current_instant,
+            *deploy.id(),
+            deploy.header().clone(),
+            DeployFootprint::new(deploy),
+        );
+    }
+
+    pub(super) fn create_test_buffer() -> (BlockProposer, Effects<Event>) {
+        create_test_buffer_with_config(Config::default())
     }
 
-    fn create_test_buffer() -> (BlockProposer, Effects<Event>) {
+    fn create_test_buffer_with_config(config: Config) -> (BlockProposer, Effects<Event>) {
         let registry = Registry::new();
         let scheduler = utils::leak(Scheduler::<Event>::new(QueueKind::weights()));
         let event_queue = EventQueueHandle::new(&scheduler);
         let effect_builder = EffectBuilder::new(event_queue);
-        BlockProposer::new(registry, effect_builder, BlockProposerState::default())
-            .expect("Failure to create a new Block Proposer")
+        BlockProposer::new(
+            registry,
+            effect_builder,
+            config,
+            BlockProposerState::default(),
+        )
+        .expect("Failure to create a new Block Proposer")
     }
 
     impl From<StorageRequest> for Event {
@@ -548,8 +709,8 @@ mod tests {
             .is_empty());
 
         // add two deploys
-        buffer.add_deploy(block_time2, hash1, deploy1);
-        buffer.add_deploy(block_time2, hash2, deploy2.clone());
+        add(&mut buffer, block_time2, &deploy1);
+        add(&mut buffer, block_time2, &deploy2);
 
         // if we try to create a block with a timestamp that is too early, we shouldn't get any
         // deploys
@@ -593,7 +754,7 @@ mod tests {
             .is_empty());
 
         // try adding the same deploy again
-        buffer.add_deploy(block_time2, hash2, deploy2.clone());
+        add(&mut buffer, block_time2, &deploy2);
 
         // it shouldn't be returned if we include block 1 in the past blocks
         assert!(buffer
@@ -608,14 +769,14 @@ mod tests {
         );
 
         // the previous check removed the deploy from the buffer, let's re-add it
-        buffer.add_deploy(block_time2, hash2, deploy2);
+        add(&mut buffer, block_time2, &deploy2);
 
         // finalize the block
         buffer.finalized_block(block_hash1);
 
         // add more deploys
-        buffer.add_deploy(block_time2, hash3, deploy3);
-        buffer.add_deploy(block_time2, hash4, deploy4);
+        add(&mut buffer, block_time2, &deploy3);
+        add(&mut buffer, block_time2, &deploy4);
 
         let deploys = buffer.remaining_deploys(DeployConfig::default(), block_time2, no_blocks);
 
@@ -635,8 +796,8 @@ mod tests {
         let mut rng = crate::new_rng();
         let (hash1, deploy1) = generate_deploy(&mut rng, creation_time, ttl, vec![]);
         let (hash2, deploy2) = generate_deploy(&mut rng, creation_time, ttl, vec![]);
-        let (hash3, deploy3) = generate_deploy(&mut rng, creation_time, ttl, vec![]);
-        let (hash4, deploy4) = generate_deploy(
+        let (_, deploy3) = generate_deploy(&mut rng, creation_time, ttl, vec![]);
+        let (_, deploy4) = generate_deploy(
             &mut rng,
             creation_time + Duration::from_secs(20).into(),
             ttl,
@@ -645,10 +806,10 @@ mod tests {
         let (mut buffer, _effects) = create_test_buffer();
 
         // pending
-        buffer.add_deploy(creation_time, hash1, deploy1);
-        buffer.add_deploy(creation_time, hash2, deploy2);
-        buffer.add_deploy(creation_time, hash3, deploy3);
-        buffer.add_deploy(creation_time, hash4, deploy4);
+        add(&mut buffer, creation_time, &deploy1);
+        add(&mut buffer, creation_time, &deploy2);
+        add(&mut buffer, creation_time, &deploy3);
+        add(&mut buffer, creation_time, &deploy4);
 
         // pending => proposed
         let block_hash1 = ProtoBlockHash::new(hash(random::<[u8; 16]>()));
@@ -697,7 +858,7 @@ mod tests {
         let (mut buffer, _effects) = create_test_buffer();
 
         // add deploy2
-        buffer.add_deploy(creation_time, hash2, deploy2);
+        add(&mut buffer, creation_time, &deploy2);
 
         // deploy2 has an unsatisfied dependency
         assert!(buffer
@@ -705,7 +866,7 @@ mod tests {
             .is_empty());
 
         // add deploy1
-        buffer.add_deploy(creation_time, hash1, deploy1);
+        add(&mut buffer, creation_time, &deploy1);
 
         let deploys = buffer.remaining_deploys(DeployConfig::default(), block_time, blocks.clone());
         // only deploy1 should be returned, as it has no dependencies
@@ -722,4 +883,99 @@ mod tests {
         assert_eq!(deploys2.len(), 1);
         assert!(deploys2.contains(&hash2));
     }
+
+    #[test]
+    fn should_refuse_deploys_when_full() {
+        let creation_time = Timestamp::from(100);
+        let ttl = TimeDiff::from(100);
+        let config = Config::new(2, 30);
+
+        let mut rng = crate::new_rng();
+        let (hash1, deploy1) = generate_deploy(&mut rng, creation_time, ttl, vec![]);
+        let (_, deploy2) = generate_deploy(&mut rng, creation_time, ttl, vec![]);
+        let (hash3, deploy3) = generate_deploy(&mut rng, creation_time, ttl, vec![]);
+        let (mut buffer, _effects) = create_test_buffer_with_config(config);
+
+        add(&mut buffer, creation_time, &deploy1);
+        assert_eq!(buffer.check_capacity(), Ok(()));
+
+        // the second deploy fills the buffer
+        add(&mut buffer, creation_time, &deploy2);
+        let expected_error = BufferFull {
+            retry_after: Duration::from_secs(30),
+        };
+        assert_eq!(buffer.check_capacity(), Err(expected_error));
+
+        // a new deploy is refused, but re-adding a pending one is harmless
+        add(&mut buffer, creation_time, &deploy3);
+        add(&mut buffer, creation_time, &deploy2);
+        assert_eq!(buffer.state.pending.len(), 2);
+        assert!(!buffer.state.pending.contains_key(&hash3));
+
+        // proposing a block drains the buffer
+        let block_hash = ProtoBlockHash::new(hash(random::<[u8; 16]>()));
+        buffer.added_block(block_hash, vec![hash1]);
+        assert_eq!(buffer.check_capacity(), Ok(()));
+    }
+
+    #[test]
+    fn should_shed_deploys_closest_to_expiry() {
+        let creation_time = Timestamp::from(100);
+
+        let mut rng = crate::new_rng();
+        let (short_hash, short_lived) =
+            generate_deploy(&mut rng, creation_time, TimeDiff::from(100), vec![]);
+        let (medium_hash, medium_lived) =
+            generate_deploy(&mut rng, creation_time, TimeDiff::from(200), vec![]);
+        let (long_hash, long_lived) =
+            generate_deploy(&mut rng, creation_time, TimeDiff::from(300), vec![]);
+        let (mut buffer, _effects) = create_test_buffer();
+
+        add(&mut buffer, creation_time, &long_lived);
+        add(&mut buffer, creation_time, &short_lived);
+        add(&mut buffer, creation_time, &medium_lived);
+        assert_eq!(buffer.shed_pending_deploys(3), 0);
+
+        assert_eq!(buffer.shed_pending_deploys(1), 2);
+        assert_eq!(buffer.pending_deploys(), 1);
+        assert!(buffer.state.pending.contains_key(&long_hash));
+        assert!(!buffer.state.pending.contains_key(&short_hash));
+        assert!(!buffer.state.pending.contains_key(&medium_hash));
+    }
+
+    #[test]
+    fn should_carry_over_deploys_exceeding_block_gas_limit() {
+        let creation_time = Timestamp::from(100);
+        let ttl = TimeDiff::from(100);
+        let block_time = Timestamp::from(120);
+        let deploy_config = DeployConfig {
+            block_gas_limit: 100,
+            ..DeployConfig::default()
+        };
+
+        let mut rng = crate::new_rng();
+        let (hash1, deploy1) =
+            generate_deploy_with_payment(&mut rng, creation_time, ttl, vec![], Some(60));
+        let (hash2, deploy2) =
+            generate_deploy_with_payment(&mut rng, creation_time, ttl, vec![], Some(60));
+        let (mut buffer, _effects) = create_test_buffer();
+        add(&mut buffer, creation_time, &deploy1);
+        add(&mut buffer, creation_time, &deploy2);
+
+        // only one of the deploys fits in the block
+        let deploys = buffer.remaining_deploys(deploy_config, block_time, HashSet::new());
+        assert_eq!(deploys.len(), 1);
+        let block_hash1 = ProtoBlockHash::new(hash(random::<[u8; 16]>()));
+        buffer.added_block(block_hash1, deploys.clone());
+
+        // the other one remains pending and is proposed in the next block
+        let mut blocks = HashSet::new();
+        blocks.insert(block_hash1);
+        let next_deploys = buffer.remaining_deploys(deploy_config, block_time, blocks);
+        assert_eq!(next_deploys.len(), 1);
+        assert!(deploys
+            .union(&next_deploys)
+            .all(|hash| *hash == hash1 || *hash == hash2));
+        assert_ne!(deploys, next_deploys);
+    }
 }
@@ -0,0 +1,56 @@
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+
+use super::ProposerStrategyKind;
+
+const DEFAULT_MAX_PENDING_DEPLOYS: usize = 10_000;
+const DEFAULT_FULL_BUFFER_RETRY_AFTER_SECS: u64 = 60;
+
+/// Configuration options for the block proposer.
+#[derive(Copy, Clone, DataSize, Debug, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// The maximum number of deploys held in the buffer awaiting inclusion in a block.  Once this
+    /// is reached, further deploys are refused until the buffer has drained.
+    max_pending_deploys: usize,
+    /// The duration in seconds which clients are advised to wait before retrying a deploy refused
+    /// because the buffer is full.
+    full_buffer_retry_after_secs: u64,
+    /// The strategy prioritizing the valid pending deploys for inclusion in a block.
+    #[serde(default)]
+    strategy: ProposerStrategyKind,
+}
+
+impl Config {
+    #[cfg(test)]
+    pub(crate) fn new(max_pending_deploys: usize, full_buffer_retry_after_secs: u64) -> Self {
+        Config {
+            max_pending_deploys,
+            full_buffer_retry_after_secs,
+            strategy: ProposerStrategyKind::default(),
+        }
+    }
+
+    pub(crate) fn max_pending_deploys(&self) -> usize {
+        self.max_pending_deploys
+    }
+
+    pub(crate) fn full_buffer_retry_after_secs(&self) -> u64 {
+        self.full_buffer_retry_after_secs
+    }
+
+    pub(crate) fn strategy(&self) -> ProposerStrategyKind {
+        self.strategy
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            max_pending_deploys: DEFAULT_MAX_PENDING_DEPLOYS,
+            full_buffer_retry_after_secs: DEFAULT_FULL_BUFFER_RETRY_AFTER_SECS,
+            strategy: ProposerStrategyKind::default(),
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! Packing of prioritized deploys into a block within the chainspec's limits.
+//!
+//! Deploys are taken in priority order and each one is included if it still fits in the block's
+//! remaining gas and size budget.  A deploy which doesn't fit is skipped rather than ending the
+//! block, so that smaller deploys further down the order can use the space left; skipped deploys
+//! stay pending and are considered again for the next block.
+
+use datasize::DataSize;
+
+use casper_execution_engine::{
+    core::engine_state::CONV_RATE,
+    shared::{gas::Gas, motes::Motes},
+};
+use casper_types::{bytesrepr::ToBytes, U512};
+
+use crate::{
+    components::chainspec_loader::DeployConfig,
+    types::{Deploy, DeployHash},
+};
+
+/// The share of a block's limits taken up by a deploy.
+#[derive(Clone, Copy, DataSize, Debug, PartialEq, Eq)]
+pub struct DeployFootprint {
+    /// The size of the serialized deploy in bytes.
+    size: usize,
+    /// The amount given to the deploy's payment code, if known.
+    payment_amount: Option<Motes>,
+}
+
+impl DeployFootprint {
+    /// Returns the footprint of `deploy`.
+    pub(crate) fn new(deploy: &Deploy) -> Self {
+        DeployFootprint {
+            size: deploy.serialized_length(),
+            payment_amount: deploy.payment_amount().map(Motes::new),
+        }
+    }
+
+    /// Returns the most gas the deploy can use.
+    ///
+    /// If the payment amount is unknown, the deploy is assumed to use as much as the payment code
+    /// is allowed to.
+    fn gas_limit(&self, deploy_config: &DeployConfig) -> Gas {
+        let motes = self
+            .payment_amount
+            .unwrap_or(deploy_config.max_payment_cost);
+        Gas::from_motes(motes, CONV_RATE).unwrap_or_default()
+    }
+}
+
+/// Returns the deploys to include in the block, taken from `prioritized` in order, highest
+/// priority first, while staying within the block's deploy count, gas and size limits.
+pub(super) fn pack<I>(prioritized: I, deploy_config: &DeployConfig) -> Vec<DeployHash>
+where
+    I: IntoIterator<Item = (DeployHash, DeployFootprint)>,
+{
+    let max_count = deploy_config.block_max_deploy_count as usize;
+    let mut gas_left = Gas::new(U512::from(deploy_config.block_gas_limit));
+    let mut size_left = deploy_config.max_block_size as usize;
+
+    let mut packed = vec![];
+    for (hash, footprint) in prioritized {
+        if packed.len() >= max_count {
+            break;
+        }
+        let gas_limit = footprint.gas_limit(deploy_config);
+        if gas_limit > gas_left || footprint.size > size_left {
+            continue;
+        }
+        gas_left = gas_left - gas_limit;
+        size_left -= footprint.size;
+        packed.push(hash);
+    }
+    packed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestRng;
+
+    impl DeployFootprint {
+        fn with(size: usize, payment_amount: u64) -> Self {
+            DeployFootprint {
+                size,
+                payment_amount: Some(Motes::new(U512::from(payment_amount))),
+            }
+        }
+    }
+
+    fn deploy_config(
+        block_max_deploy_count: u32,
+        block_gas_limit: u64,
+        max_block_size: u32,
+    ) -> DeployConfig {
+        DeployConfig {
+            block_max_deploy_count,
+            block_gas_limit,
+            max_block_size,
+            ..DeployConfig::default()
+        }
+    }
+
+    fn candidates(
+        rng: &mut TestRng,
+        footprints: &[DeployFootprint],
+    ) -> Vec<(DeployHash, DeployFootprint)> {
+        footprints
+            .iter()
+            .map(|footprint| (DeployHash::random(rng), *footprint))
+            .collect()
+    }
+
+    fn hashes(candidates: &[(DeployHash, DeployFootprint)], indices: &[usize]) -> Vec<DeployHash> {
+        indices.iter().map(|index| candidates[*index].0).collect()
+    }
+
+    #[test]
+    fn should_fill_limits_exactly() {
+        let mut rng = crate::new_rng();
+        let candidates = candidates(
+            &mut rng,
+            &[
+                DeployFootprint::with(40, 60),
+                DeployFootprint::with(60, 40),
+                DeployFootprint::with(1, 1),
+            ],
+        );
+
+        let packed = pack(candidates.clone(), &deploy_config(10, 100, 100));
+        assert_eq!(packed, hashes(&candidates, &[0, 1]));
+    }
+
+    #[test]
+    fn should_skip_deploys_which_dont_fit() {
+        let mut rng = crate::new_rng();
+        let candidates = candidates(
+            &mut rng,
+            &[
+                DeployFootprint::with(10, 70),
+                // exceeds the gas left
+                DeployFootprint::with(10, 31),
+                // exceeds the size left
+                DeployFootprint::with(91, 1),
+                DeployFootprint::with(90, 30),
+            ],
+        );
+
+        let packed = pack(candidates.clone(), &deploy_config(10, 100, 100));
+        assert_eq!(packed, hashes(&candidates, &[0, 3]));
+    }
+
+    #[test]
+    fn should_skip_deploys_exceeding_block_limits() {
+        let mut rng = crate::new_rng();
+        let candidates = candidates(
+            &mut rng,
+            &[
+                DeployFootprint::with(10, 101),
+                DeployFootprint::with(101, 10),
+                DeployFootprint::with(100, 100),
+            ],
+        );
+
+        let packed = pack(candidates.clone(), &deploy_config(10, 100, 100));
+        assert_eq!(packed, hashes(&candidates, &[2]));
+    }
+
+    #[test]
+    fn should_respect_max_deploy_count() {
+        let mut rng = crate::new_rng();
+        let candidates = candidates(&mut rng, &[DeployFootprint::with(1, 1); 3]);
+
+        let packed = pack(candidates.clone(), &deploy_config(2, 100, 100));
+        assert_eq!(packed, hashes(&candidates, &[0, 1]));
+        assert!(pack(candidates, &deploy_config(0, 100, 100)).is_empty());
+    }
+
+    #[test]
+    fn should_assume_max_payment_cost_if_payment_amount_unknown() {
+        let mut rng = crate::new_rng();
+        let unknown = DeployFootprint {
+            size: 1,
+            payment_amount: None,
+        };
+        let candidates = candidates(&mut rng, &[unknown, DeployFootprint::with(1, 50)]);
+
+        let config = DeployConfig {
+            max_payment_cost: Motes::new(U512::from(60)),
+            ..deploy_config(10, 100, 100)
+        };
+        let packed = pack(candidates.clone(), &config);
+        assert_eq!(packed, hashes(&candidates, &[0]));
+    }
+}
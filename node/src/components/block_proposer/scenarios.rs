@@ -0,0 +1,389 @@
+//! Property tests driving the block proposer through generated scenarios.
+//!
+//! A scenario is a set of deploys and a sequence of steps: deploys arriving, possibly more than
+//! once and in any order, time passing up to and across their expiry, blocks being proposed,
+//! finalized or orphaned, the buffer being pruned, and eras ending with new chainspec limits.
+//! After each proposal, the deploys returned by the block proposer are checked against the
+//! scenario's history.  Proptest shrinks failing scenarios to a minimal sequence of steps.
+
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+};
+
+use proptest::{
+    collection::vec,
+    prelude::*,
+    sample::Index,
+    test_runner::{Config as ProptestConfig, TestCaseError, TestRunner},
+};
+
+use casper_execution_engine::shared::motes::Motes;
+use casper_types::{bytesrepr::ToBytes, U512};
+
+use super::{
+    tests::{self, create_test_buffer, generate_deploy_with_payment},
+    BlockProposer, DeployConfig,
+};
+use crate::{
+    crypto::hash::hash,
+    testing::TestRng,
+    types::{Deploy, DeployHash, ProtoBlockHash, TimeDiff, Timestamp},
+};
+
+/// The number of scenarios generated per test run.
+const CASES: u32 = 64;
+/// Upper bound for deploy timestamps and TTLs, in milliseconds.
+const MAX_TIME: u64 = 1_000;
+
+/// A deploy in a scenario.
+#[derive(Clone, Debug)]
+struct DeploySpec {
+    /// The deploy's timestamp, in milliseconds after the start of the scenario.
+    timestamp: u64,
+    /// The deploy's time to live, in milliseconds.
+    ttl: u64,
+    /// The deploy's dependencies, among the deploys preceding it in the scenario.
+    dependencies: Vec<Index>,
+    /// The amount given to the payment code, if specified.
+    payment_amount: Option<u64>,
+}
+
+/// A step in a scenario.
+#[derive(Clone, Debug)]
+enum Step {
+    /// A deploy arrives at the block proposer.
+    Arrive(Index),
+    /// Time passes.
+    Wait(u64),
+    /// Time passes until just before, exactly at, or just after a deploy's expiry.
+    WaitForExpiry { deploy: Index, offset: i8 },
+    /// A block is proposed on top of the current chain.
+    Propose,
+    /// The oldest block of the chain that is not finalized yet is finalized.
+    Finalize,
+    /// The newest block of the chain that is not finalized yet is orphaned.
+    Orphan,
+    /// The expired deploys are pruned.
+    Prune,
+    /// The era ends: All blocks are finalized, and the next era has new limits.
+    EndEra(DeployConfig),
+}
+
+#[derive(Clone, Debug)]
+struct Scenario {
+    deploy_config: DeployConfig,
+    deploys: Vec<DeploySpec>,
+    steps: Vec<Step>,
+}
+
+fn deploy_config() -> impl Strategy<Value = DeployConfig> {
+    (
+        0..=100u64,
+        0..=MAX_TIME,
+        0..=3u8,
+        0..=2_000u32,
+        0..=5u32,
+        0..=300u64,
+    )
+        .prop_map(
+            |(
+                max_payment_cost,
+                max_ttl,
+                max_dependencies,
+                max_block_size,
+                block_max_deploy_count,
+                block_gas_limit,
+            )| DeployConfig {
+                max_payment_cost: Motes::new(U512::from(max_payment_cost)),
+                max_ttl: TimeDiff::from(max_ttl),
+                max_dependencies,
+                max_block_size,
+                block_max_deploy_count,
+                block_gas_limit,
+            },
+        )
+}
+
+fn deploy_spec() -> impl Strategy<Value = DeploySpec> {
+    (
+        0..MAX_TIME,
+        0..=MAX_TIME,
+        vec(any::<Index>(), 0..=3),
+        proptest::option::of(1..=100u64),
+    )
+        .prop_map(
+            |(timestamp, ttl, dependencies, payment_amount)| DeploySpec {
+                timestamp,
+                ttl,
+                dependencies,
+                payment_amount,
+            },
+        )
+}
+
+fn step() -> impl Strategy<Value = Step> {
+    prop_oneof![
+        4 => any::<Index>().prop_map(Step::Arrive),
+        2 => (0..MAX_TIME / 4).prop_map(Step::Wait),
+        1 => (any::<Index>(), -1..=1i8)
+            .prop_map(|(deploy, offset)| Step::WaitForExpiry { deploy, offset }),
+        4 => Just(Step::Propose),
+        2 => Just(Step::Finalize),
+        1 => Just(Step::Orphan),
+        1 => Just(Step::Prune),
+        1 => deploy_config().prop_map(Step::EndEra),
+    ]
+}
+
+fn scenario() -> impl Strategy<Value = Scenario> {
+    (
+        deploy_config(),
+        vec(deploy_spec(), 1..12),
+        vec(step(), 1..40),
+    )
+        .prop_map(|(deploy_config, deploys, steps)| Scenario {
+            deploy_config,
+            deploys,
+            steps,
+        })
+}
+
+/// The state of a scenario being run against a block proposer.
+struct ScenarioRun {
+    buffer: BlockProposer,
+    deploy_config: DeployConfig,
+    deploys: Vec<Deploy>,
+    deploys_by_hash: HashMap<DeployHash, Deploy>,
+    now: Timestamp,
+    /// The deploys in finalized blocks.
+    finalized: HashSet<DeployHash>,
+    /// The blocks that are proposed but not finalized yet, oldest first, with their deploys.
+    chain: Vec<(ProtoBlockHash, Vec<DeployHash>)>,
+    /// The deploys that were accepted by the block proposer.
+    accepted: HashSet<DeployHash>,
+    /// The deploys that were rejected or pruned because they expired.
+    reported_expired: HashSet<DeployHash>,
+    /// The number of blocks proposed so far.
+    block_count: u64,
+}
+
+impl ScenarioRun {
+    fn new(rng: &mut TestRng, scenario: &Scenario) -> Self {
+        let mut deploys: Vec<Deploy> = vec![];
+        for spec in &scenario.deploys {
+            let dependencies = if deploys.is_empty() {
+                vec![]
+            } else {
+                spec.dependencies
+                    .iter()
+                    .map(|index| *index.get(&deploys).id())
+                    .collect()
+            };
+            let (_, deploy) = generate_deploy_with_payment(
+                rng,
+                Timestamp::zero() + TimeDiff::from(spec.timestamp),
+                TimeDiff::from(spec.ttl),
+                dependencies,
+                spec.payment_amount,
+            );
+            deploys.push(deploy);
+        }
+        let deploys_by_hash = deploys
+            .iter()
+            .map(|deploy| (*deploy.id(), deploy.clone()))
+            .collect();
+        let (buffer, _effects) = create_test_buffer();
+        ScenarioRun {
+            buffer,
+            deploy_config: scenario.deploy_config,
+            deploys,
+            deploys_by_hash,
+            now: Timestamp::zero(),
+            finalized: HashSet::new(),
+            chain: vec![],
+            accepted: HashSet::new(),
+            reported_expired: HashSet::new(),
+            block_count: 0,
+        }
+    }
+
+    fn apply(&mut self, step: &Step) -> Result<(), TestCaseError> {
+        match step {
+            Step::Arrive(index) => {
+                let deploy = index.get(&self.deploys).clone();
+                let hash = *deploy.id();
+                let was_pending = self.buffer.state.pending.contains_key(&hash);
+                tests::add(&mut self.buffer, self.now, &deploy);
+                if deploy.header().expired(self.now) {
+                    self.reported_expired.insert(hash);
+                } else if !was_pending && self.buffer.state.pending.contains_key(&hash) {
+                    self.accepted.insert(hash);
+                }
+            }
+            Step::Wait(millis) => self.now = self.now + TimeDiff::from(*millis),
+            Step::WaitForExpiry { deploy, offset } => {
+                let expires = deploy.get(&self.deploys).header().expires().millis();
+                let target = Timestamp::from((expires as i64 + i64::from(*offset)).max(0) as u64);
+                self.now = self.now.max(target);
+            }
+            Step::Propose => self.propose()?,
+            Step::Finalize => {
+                if !self.chain.is_empty() {
+                    self.finalize_oldest();
+                }
+            }
+            Step::Orphan => {
+                if let Some((block_hash, _)) = self.chain.pop() {
+                    self.buffer.orphaned_block(block_hash);
+                }
+            }
+            Step::Prune => {
+                let expired: Vec<DeployHash> = self
+                    .buffer
+                    .state
+                    .pending
+                    .iter()
+                    .filter(|(_, (header, _))| header.expired(self.now))
+                    .map(|(hash, _)| *hash)
+                    .collect();
+                self.buffer.prune(self.now);
+                for hash in expired {
+                    prop_assert!(
+                        !self.buffer.state.pending.contains_key(&hash),
+                        "expired deploy {} was not pruned",
+                        hash
+                    );
+                    self.reported_expired.insert(hash);
+                }
+            }
+            Step::EndEra(deploy_config) => {
+                while !self.chain.is_empty() {
+                    self.finalize_oldest();
+                }
+                self.deploy_config = *deploy_config;
+            }
+        }
+        Ok(())
+    }
+
+    fn finalize_oldest(&mut self) {
+        let (block_hash, deploys) = self.chain.remove(0);
+        self.buffer.finalized_block(block_hash);
+        self.finalized.extend(deploys);
+    }
+
+    fn propose(&mut self) -> Result<(), TestCaseError> {
+        let past_blocks = self
+            .chain
+            .iter()
+            .map(|(block_hash, _)| *block_hash)
+            .collect();
+        let deploys: Vec<DeployHash> = self
+            .buffer
+            .remaining_deploys(self.deploy_config, self.now, past_blocks)
+            .into_iter()
+            .collect();
+        self.check_proposal(&deploys)?;
+
+        self.block_count += 1;
+        let block_hash = ProtoBlockHash::new(hash(self.block_count.to_le_bytes()));
+        self.buffer.added_block(block_hash, deploys.clone());
+        self.chain.push((block_hash, deploys));
+        Ok(())
+    }
+
+    /// Checks that the deploys proposed for a block at the current time are valid on top of the
+    /// current chain, and that the block stays within the current chainspec limits.
+    fn check_proposal(&self, proposed: &[DeployHash]) -> Result<(), TestCaseError> {
+        let config = &self.deploy_config;
+        let in_chain: HashSet<&DeployHash> = self
+            .chain
+            .iter()
+            .flat_map(|(_, deploys)| deploys)
+            .chain(&self.finalized)
+            .collect();
+
+        prop_assert!(
+            proposed.len() <= config.block_max_deploy_count as usize,
+            "proposed {} deploys, exceeding the limit of {}",
+            proposed.len(),
+            config.block_max_deploy_count
+        );
+        let mut total_gas = U512::zero();
+        let mut total_size = 0;
+        for hash in proposed {
+            prop_assert!(
+                self.accepted.contains(hash),
+                "proposed unknown deploy {}",
+                hash
+            );
+            prop_assert!(
+                !self.reported_expired.contains(hash),
+                "proposed deploy {} which was reported expired",
+                hash
+            );
+            prop_assert!(!in_chain.contains(hash), "proposed deploy {} twice", hash);
+            let deploy = &self.deploys_by_hash[hash];
+            let header = deploy.header();
+            prop_assert!(
+                header.timestamp() <= self.now && !header.expired(self.now),
+                "proposed deploy {} outside its lifespan",
+                hash
+            );
+            prop_assert!(
+                header.ttl() <= config.max_ttl,
+                "proposed deploy {} with TTL too long",
+                hash
+            );
+            prop_assert!(
+                header.dependencies().len() <= config.max_dependencies as usize,
+                "proposed deploy {} with too many dependencies",
+                hash
+            );
+            for dependency in header.dependencies() {
+                prop_assert!(
+                    in_chain.contains(dependency),
+                    "proposed deploy {} before its dependency {}",
+                    hash,
+                    dependency
+                );
+            }
+            total_gas += deploy
+                .payment_amount()
+                .unwrap_or_else(|| config.max_payment_cost.value());
+            total_size += deploy.serialized_length();
+        }
+        prop_assert!(
+            total_gas <= U512::from(config.block_gas_limit),
+            "proposed deploys use {} gas, exceeding the limit of {}",
+            total_gas,
+            config.block_gas_limit
+        );
+        prop_assert!(
+            total_size <= config.max_block_size as usize,
+            "proposed deploys have {} bytes, exceeding the limit of {}",
+            total_size,
+            config.max_block_size
+        );
+        Ok(())
+    }
+}
+
+#[test]
+fn proposals_should_be_valid_and_within_limits() {
+    // Only one test RNG is allowed per thread, so it is shared by all cases.  It only determines
+    // the deploys' keys and signatures.
+    let rng = RefCell::new(crate::new_rng());
+    let mut runner = TestRunner::new(ProptestConfig::with_cases(CASES));
+    let result = runner.run(&scenario(), |scenario| {
+        let mut run = ScenarioRun::new(&mut rng.borrow_mut(), &scenario);
+        for step in &scenario.steps {
+            run.apply(step)?;
+        }
+        Ok(())
+    });
+    if let Err(error) = result {
+        panic!("{}", error);
+    }
+}
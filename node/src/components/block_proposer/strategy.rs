@@ -0,0 +1,207 @@
+//! Strategies selecting which pending deploys are proposed for inclusion in a block.
+//!
+//! The block proposer filters its pending deploys down to those valid for the block being proposed,
+//! then leaves their prioritization to a `ProposerStrategy`.  The prioritized deploys are included
+//! in order for as long as they fit within the block's limits.
+
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, VecDeque},
+    fmt::Debug,
+};
+
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    crypto::asymmetric_key::PublicKey,
+    types::{DeployHash, DeployHeader},
+};
+
+/// A policy deciding which of the valid pending deploys are preferred for inclusion in a block.
+pub trait ProposerStrategy: Debug + Send + Sync {
+    /// Orders `candidates`, all of which are valid for inclusion in the block, highest priority
+    /// first.
+    ///
+    /// Every candidate must be returned exactly once.
+    fn prioritize(&self, candidates: Vec<(&DeployHash, &DeployHeader)>) -> Vec<DeployHash>;
+}
+
+/// The built-in strategies, selectable via the config.
+#[derive(Clone, Copy, DataSize, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposerStrategyKind {
+    /// Oldest deploys first.
+    Fifo,
+    /// Deploys with the highest gas price first, the oldest first among equal gas prices.
+    FeePriority,
+    /// One deploy per account in turn, so that no single account can fill a block while others
+    /// are waiting.
+    AccountRoundRobin,
+}
+
+impl Default for ProposerStrategyKind {
+    fn default() -> Self {
+        ProposerStrategyKind::Fifo
+    }
+}
+
+impl ProposerStrategyKind {
+    /// Returns the strategy of this kind.
+    pub(crate) fn strategy(self) -> Box<dyn ProposerStrategy> {
+        match self {
+            ProposerStrategyKind::Fifo => Box::new(Fifo),
+            ProposerStrategyKind::FeePriority => Box::new(FeePriority),
+            ProposerStrategyKind::AccountRoundRobin => Box::new(AccountRoundRobin),
+        }
+    }
+}
+
+/// Sorts the candidates oldest first, breaking ties by hash so that the order is deterministic.
+fn sort_by_age(candidates: &mut Vec<(&DeployHash, &DeployHeader)>) {
+    candidates.sort_by_key(|(hash, header)| (header.timestamp(), **hash));
+}
+
+/// Prefers the oldest deploys.
+#[derive(Debug)]
+pub struct Fifo;
+
+impl ProposerStrategy for Fifo {
+    fn prioritize(&self, mut candidates: Vec<(&DeployHash, &DeployHeader)>) -> Vec<DeployHash> {
+        sort_by_age(&mut candidates);
+        candidates.into_iter().map(|(hash, _)| *hash).collect()
+    }
+}
+
+/// Prefers the deploys with the highest gas price, the oldest among equal gas prices.
+#[derive(Debug)]
+pub struct FeePriority;
+
+impl ProposerStrategy for FeePriority {
+    fn prioritize(&self, mut candidates: Vec<(&DeployHash, &DeployHeader)>) -> Vec<DeployHash> {
+        candidates.sort_by_key(|(hash, header)| {
+            (Reverse(header.gas_price()), header.timestamp(), **hash)
+        });
+        candidates.into_iter().map(|(hash, _)| *hash).collect()
+    }
+}
+
+/// Takes one deploy per account in turn, oldest first within each account.  Accounts take turns in
+/// the order of their oldest candidate.
+#[derive(Debug)]
+pub struct AccountRoundRobin;
+
+impl ProposerStrategy for AccountRoundRobin {
+    fn prioritize(&self, mut candidates: Vec<(&DeployHash, &DeployHeader)>) -> Vec<DeployHash> {
+        sort_by_age(&mut candidates);
+
+        // Queue each account's deploys, and order the accounts by their oldest deploy.
+        let mut queues: BTreeMap<&PublicKey, VecDeque<DeployHash>> = BTreeMap::new();
+        let mut accounts = vec![];
+        for (hash, header) in candidates {
+            let queue = queues.entry(header.account()).or_insert_with(|| {
+                accounts.push(header.account());
+                VecDeque::new()
+            });
+            queue.push_back(*hash);
+        }
+
+        let mut prioritized = vec![];
+        while !accounts.is_empty() {
+            accounts.retain(|account| {
+                match queues.get_mut(account).and_then(VecDeque::pop_front) {
+                    Some(hash) => {
+                        prioritized.push(hash);
+                        true
+                    }
+                    None => false,
+                }
+            });
+        }
+        prioritized
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_execution_engine::core::engine_state::executable_deploy_item::ExecutableDeployItem;
+
+    use super::*;
+    use crate::{
+        crypto::asymmetric_key::SecretKey,
+        testing::TestRng,
+        types::{Deploy, TimeDiff, Timestamp},
+    };
+
+    fn deploy(
+        rng: &mut TestRng,
+        secret_key: &SecretKey,
+        timestamp: u64,
+        gas_price: u64,
+    ) -> (DeployHash, DeployHeader) {
+        let module_bytes = || ExecutableDeployItem::ModuleBytes {
+            module_bytes: vec![],
+            args: vec![],
+        };
+        let deploy = Deploy::new(
+            Timestamp::from(timestamp),
+            TimeDiff::from(1_000),
+            gas_price,
+            vec![],
+            "chain".to_string(),
+            module_bytes(),
+            module_bytes(),
+            secret_key,
+            rng,
+        );
+        (*deploy.id(), deploy.take_header())
+    }
+
+    fn prioritize(
+        strategy: &dyn ProposerStrategy,
+        deploys: &[(DeployHash, DeployHeader)],
+    ) -> Vec<DeployHash> {
+        let candidates = deploys
+            .iter()
+            .map(|(hash, header)| (hash, header))
+            .collect();
+        strategy.prioritize(candidates)
+    }
+
+    #[test]
+    fn fifo_should_prefer_oldest() {
+        let mut rng = crate::new_rng();
+        let secret_key = SecretKey::random(&mut rng);
+        let newer = deploy(&mut rng, &secret_key, 200, 10);
+        let older = deploy(&mut rng, &secret_key, 100, 1);
+
+        let prioritized = prioritize(&Fifo, &[newer.clone(), older.clone()]);
+        assert_eq!(prioritized, vec![older.0, newer.0]);
+    }
+
+    #[test]
+    fn fee_priority_should_prefer_highest_gas_price() {
+        let mut rng = crate::new_rng();
+        let secret_key = SecretKey::random(&mut rng);
+        let cheap = deploy(&mut rng, &secret_key, 100, 1);
+        let pricey = deploy(&mut rng, &secret_key, 200, 10);
+
+        let prioritized = prioritize(&FeePriority, &[cheap.clone(), pricey.clone()]);
+        assert_eq!(prioritized, vec![pricey.0, cheap.0]);
+    }
+
+    #[test]
+    fn account_round_robin_should_alternate_accounts() {
+        let mut rng = crate::new_rng();
+        let busy_key = SecretKey::random(&mut rng);
+        let quiet_key = SecretKey::random(&mut rng);
+        let busy1 = deploy(&mut rng, &busy_key, 100, 1);
+        let busy2 = deploy(&mut rng, &busy_key, 101, 1);
+        let busy3 = deploy(&mut rng, &busy_key, 102, 1);
+        let quiet = deploy(&mut rng, &quiet_key, 150, 1);
+        let deploys = [busy1.clone(), busy2.clone(), busy3.clone(), quiet.clone()];
+
+        let prioritized = prioritize(&AccountRoundRobin, &deploys);
+        assert_eq!(prioritized, vec![busy1.0, quiet.0, busy2.0, busy3.0]);
+    }
+}
@@ -16,8 +16,8 @@ use serde::{Deserialize, Serialize};
 use tracing::warn;
 
 use casper_execution_engine::{
-    core::engine_state::genesis::{ExecConfig, GenesisAccount},
-    shared::{motes::Motes, wasm_config::WasmConfig},
+    core::engine_state::genesis::{ExecConfig, GenesisAccount, GenesisDelegator},
+    shared::{motes::Motes, system_config::SystemConfig, wasm_config::WasmConfig},
 };
 use casper_types::{auction::EraId, U512};
 
@@ -25,7 +25,10 @@ use super::{config, error::GenesisLoadError, Error};
 #[cfg(test)]
 use crate::testing::TestRng;
 use crate::{
-    crypto::asymmetric_key::PublicKey,
+    crypto::{
+        asymmetric_key::PublicKey,
+        hash::{self, Digest},
+    },
     types::{TimeDiff, Timestamp},
     utils::Loadable,
 };
@@ -187,6 +190,32 @@ impl Loadable for Vec<GenesisAccount> {
     }
 }
 
+impl Loadable for Vec<GenesisDelegator> {
+    type Error = GenesisLoadError;
+
+    fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Self::Error> {
+        #[derive(Debug, Deserialize)]
+        struct ParsedDelegator {
+            delegator_public_key: PublicKey,
+            validator_public_key: PublicKey,
+            delegated_amount: U512,
+        }
+
+        let mut reader = ReaderBuilder::new().has_headers(false).from_path(path)?;
+        let mut delegators = vec![];
+        for result in reader.deserialize() {
+            let parsed: ParsedDelegator = result?;
+            let delegator = GenesisDelegator::new(
+                casper_types::PublicKey::from(parsed.delegator_public_key),
+                casper_types::PublicKey::from(parsed.validator_public_key),
+                Motes::new(parsed.delegated_amount),
+            );
+            delegators.push(delegator);
+        }
+        Ok(delegators)
+    }
+}
+
 #[derive(Clone, DataSize, PartialEq, Eq, Serialize, Deserialize)]
 // Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
 #[serde(deny_unknown_fields)]
@@ -213,13 +242,16 @@ pub struct GenesisConfig {
     pub(crate) standard_payment_installer_bytes: Vec<u8>,
     pub(crate) auction_installer_bytes: Vec<u8>,
     pub(crate) accounts: Vec<GenesisAccount>,
+    pub(crate) delegators: Vec<GenesisDelegator>,
     pub(crate) wasm_config: WasmConfig,
+    pub(crate) system_config: SystemConfig,
     pub(crate) deploy_config: DeployConfig,
     pub(crate) highway_config: HighwayConfig,
 }
 
 impl GenesisConfig {
-    /// Returns a vector of Genesis validators' public key and their stake.
+    /// Returns a vector of Genesis validators' public key and their stake, including the stakes
+    /// delegated to them at genesis.
     pub fn genesis_validator_stakes(&self) -> Vec<(PublicKey, Motes)> {
         self.accounts
             .iter()
@@ -229,11 +261,22 @@ impl GenesisConfig {
                         .public_key()
                         .expect("should have genesis public key");
 
+                    let delegated_amount = self
+                        .delegators
+                        .iter()
+                        .filter(|genesis_delegator| genesis_delegator.validator() == public_key)
+                        .fold(Motes::zero(), |total, genesis_delegator| {
+                            total + genesis_delegator.delegated_amount()
+                        });
+
                     let crypto_public_key = public_key
                         .try_into()
                         .expect("should have valid genesis public key");
 
-                    Some((crypto_public_key, genesis_account.bonded_amount()))
+                    Some((
+                        crypto_public_key,
+                        genesis_account.bonded_amount() + delegated_amount,
+                    ))
                 } else {
                     None
                 }
@@ -271,7 +314,9 @@ impl Debug for GenesisConfig {
                 &format_args!("[{} bytes]", self.standard_payment_installer_bytes.len()),
             )
             .field("accounts", &self.accounts)
+            .field("delegators", &self.delegators)
             .field("costs", &self.wasm_config)
+            .field("system_config", &self.system_config)
             .field("deploy_config", &self.deploy_config)
             .field("highway_config", &self.highway_config)
             .finish()
@@ -301,7 +346,9 @@ impl GenesisConfig {
         let standard_payment_installer_bytes = vec![rng.gen()];
         let auction_installer_bytes = vec![rng.gen()];
         let accounts = vec![rng.gen(), rng.gen(), rng.gen(), rng.gen(), rng.gen()];
+        let delegators = vec![rng.gen(), rng.gen()];
         let costs = rng.gen();
+        let system_config = rng.gen();
         let deploy_config = DeployConfig::random(rng);
         let highway_config = HighwayConfig::random(rng);
 
@@ -318,7 +365,9 @@ impl GenesisConfig {
             standard_payment_installer_bytes,
             auction_installer_bytes,
             accounts,
+            delegators,
             wasm_config: costs,
+            system_config,
             deploy_config,
             highway_config,
         }
@@ -338,6 +387,7 @@ pub(crate) struct UpgradePoint {
     pub(crate) upgrade_installer_bytes: Option<Vec<u8>>,
     pub(crate) upgrade_installer_args: Option<Vec<u8>>,
     pub(crate) new_wasm_config: Option<WasmConfig>,
+    pub(crate) new_system_config: Option<SystemConfig>,
     pub(crate) new_deploy_config: Option<DeployConfig>,
     pub(crate) new_validator_slots: Option<u32>,
 }
@@ -365,6 +415,7 @@ impl UpgradePoint {
             None
         };
         let new_costs = if rng.gen() { Some(rng.gen()) } else { None };
+        let new_system_config = if rng.gen() { Some(rng.gen()) } else { None };
         let new_deploy_config = if rng.gen() {
             Some(DeployConfig::random(rng))
         } else {
@@ -378,6 +429,7 @@ impl UpgradePoint {
             upgrade_installer_bytes,
             upgrade_installer_args,
             new_wasm_config: new_costs,
+            new_system_config,
             new_deploy_config,
             new_validator_slots,
         }
@@ -405,6 +457,24 @@ impl Chainspec {
     pub fn validate_config(&self) {
         self.genesis.validate_config();
     }
+
+    /// Returns the hash of the chainspec, covering every setting including the installer bytes.
+    pub fn hash(&self) -> Digest {
+        let serialized = bincode::serialize(self).expect("should serialize chainspec");
+        hash::hash(&serialized)
+    }
+
+    /// Returns the protocol version active at the given block height, i.e. that of the last
+    /// upgrade activated at or below it, or that of genesis if there is no such upgrade.
+    pub(crate) fn protocol_version_at(&self, block_height: u64) -> &Version {
+        self.upgrades
+            .iter()
+            .take_while(|upgrade| upgrade.activation_point.height <= block_height)
+            .last()
+            .map_or(&self.genesis.protocol_version, |upgrade| {
+                &upgrade.protocol_version
+            })
+    }
 }
 
 #[cfg(test)]
@@ -431,6 +501,8 @@ impl Into<ExecConfig> for Chainspec {
             self.genesis.locked_funds_period,
             self.genesis.round_seigniorage_rate,
         )
+        .with_delegators(self.genesis.delegators)
+        .with_system_config(self.genesis.system_config)
     }
 }
 
@@ -496,7 +568,8 @@ mod tests {
             EXPECTED_GENESIS_COSTS,
             EXPECTED_GENESIS_STORAGE_COSTS,
             *EXPECTED_GENESIS_HOST_FUNCTION_COSTS,
-        );
+        )
+        .with_strict_determinism(true);
     }
     const EXPECTED_GENESIS_STORAGE_COSTS: StorageCosts = StorageCosts::new(101);
 
@@ -568,6 +641,22 @@ mod tests {
             );
         }
 
+        assert_eq!(spec.genesis.delegators.len(), 2);
+        for (index, delegator) in spec.genesis.delegators.iter().enumerate() {
+            assert_eq!(
+                delegator.validator(),
+                spec.genesis.accounts[index].public_key().unwrap()
+            );
+            assert_eq!(
+                delegator.delegated_amount(),
+                Motes::new(U512::from((index as u64 + 1) * 5))
+            );
+        }
+        assert_eq!(
+            spec.genesis.genesis_validator_stakes()[0].1,
+            Motes::new(U512::from(15))
+        );
+
         assert_eq!(
             spec.genesis
                 .highway_config
@@ -611,6 +700,12 @@ mod tests {
 
         assert_eq!(spec.genesis.wasm_config, *EXPECTED_GENESIS_WASM_CONFIG);
 
+        let system_config = spec.genesis.system_config;
+        assert_eq!(system_config.mint_costs().mint, 200);
+        assert_eq!(system_config.proof_of_stake_costs().finalize_payment, 208);
+        assert_eq!(system_config.standard_payment_costs().pay, 209);
+        assert_eq!(system_config.auction_costs().rotate_signing_key, 222);
+
         assert_eq!(spec.upgrades.len(), 2);
 
         let upgrade0 = &spec.upgrades[0];
@@ -634,6 +729,7 @@ mod tests {
 
         assert_eq!(new_wasm_config.initial_memory, 17);
         assert_eq!(new_wasm_config.max_stack_height, 19);
+        assert!(!new_wasm_config.strict_determinism);
 
         assert_eq!(
             upgrade0.new_deploy_config.unwrap().max_payment_cost,
@@ -651,12 +747,19 @@ mod tests {
         );
         assert_eq!(upgrade0.new_deploy_config.unwrap().block_gas_limit, 38);
 
+        let new_system_config = upgrade0
+            .new_system_config
+            .expect("should have new system config");
+        assert_eq!(new_system_config.mint_costs().mint, 300);
+        assert_eq!(new_system_config.auction_costs().rotate_signing_key, 322);
+
         let upgrade1 = &spec.upgrades[1];
         assert_eq!(upgrade1.activation_point, ActivationPoint { height: 39 });
         assert_eq!(upgrade1.protocol_version, Version::from((0, 3, 0)));
         assert!(upgrade1.upgrade_installer_bytes.is_none());
         assert!(upgrade1.upgrade_installer_args.is_none());
         assert!(upgrade1.new_wasm_config.is_none());
+        assert!(upgrade1.new_system_config.is_none());
         assert!(upgrade1.new_deploy_config.is_none());
     }
 
@@ -666,6 +769,20 @@ mod tests {
         check_spec(spec);
     }
 
+    #[test]
+    fn should_get_protocol_version_at_height() {
+        let spec = Chainspec::from_resources("test/valid/chainspec.toml");
+        assert_eq!(*spec.protocol_version_at(0), Version::from((0, 1, 0)));
+        assert_eq!(*spec.protocol_version_at(22), Version::from((0, 1, 0)));
+        assert_eq!(*spec.protocol_version_at(23), Version::from((0, 2, 0)));
+        assert_eq!(*spec.protocol_version_at(38), Version::from((0, 2, 0)));
+        assert_eq!(*spec.protocol_version_at(39), Version::from((0, 3, 0)));
+        assert_eq!(
+            *spec.protocol_version_at(u64::MAX),
+            Version::from((0, 3, 0))
+        );
+    }
+
     #[test]
     fn bincode_roundtrip() {
         let mut rng = crate::new_rng();
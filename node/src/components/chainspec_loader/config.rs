@@ -1,13 +1,14 @@
 //! Helper structs used to parse chainspec configuration files into their respective domain objects.
 
-use std::path::Path;
+use std::{convert::TryInto, path::Path};
 
 use num_rational::Ratio;
 use semver::Version;
 use serde::{Deserialize, Serialize};
 
 use casper_execution_engine::{
-    core::engine_state::genesis::GenesisAccount, shared::wasm_config::WasmConfig,
+    core::engine_state::genesis::{GenesisAccount, GenesisDelegator},
+    shared::{system_config::SystemConfig, wasm_config::WasmConfig},
 };
 use casper_types::auction::EraId;
 
@@ -23,6 +24,7 @@ const DEFAULT_POS_INSTALLER_PATH: &str = "pos_install.wasm";
 const DEFAULT_STANDARD_PAYMENT_INSTALLER_PATH: &str = "standard_payment_install.wasm";
 const DEFAULT_AUCTION_INSTALLER_PATH: &str = "auction_install.wasm";
 const DEFAULT_ACCOUNTS_CSV_PATH: &str = "accounts.csv";
+const DEFAULT_DELEGATORS_CSV_PATH: &str = "delegators.csv";
 const DEFAULT_UPGRADE_INSTALLER_PATH: &str = "upgrade_install.wasm";
 const DEFAULT_VALIDATOR_SLOTS: u32 = 5;
 const DEFAULT_AUCTION_DELAY: u64 = 3;
@@ -50,6 +52,8 @@ struct Genesis {
     standard_payment_installer_path: External<Vec<u8>>,
     auction_installer_path: External<Vec<u8>>,
     accounts_path: External<Vec<GenesisAccount>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    delegators_path: Option<External<Vec<GenesisDelegator>>>,
 }
 
 impl Default for Genesis {
@@ -69,6 +73,7 @@ impl Default for Genesis {
             ),
             auction_installer_path: External::path(DEFAULT_AUCTION_INSTALLER_PATH),
             accounts_path: External::path(DEFAULT_ACCOUNTS_CSV_PATH),
+            delegators_path: None,
         }
     }
 }
@@ -80,6 +85,7 @@ struct UpgradePoint {
     upgrade_installer_path: Option<External<Vec<u8>>>,
     activation_point: chainspec::ActivationPoint,
     new_wasm_config: Option<WasmConfig>,
+    new_system_config: Option<SystemConfig>,
     new_deploy_config: Option<DeployConfig>,
     new_validator_slots: Option<u32>,
 }
@@ -91,6 +97,7 @@ impl From<&chainspec::UpgradePoint> for UpgradePoint {
             upgrade_installer_path: Some(External::path(DEFAULT_UPGRADE_INSTALLER_PATH)),
             activation_point: upgrade_point.activation_point,
             new_wasm_config: upgrade_point.new_wasm_config,
+            new_system_config: upgrade_point.new_system_config,
             new_deploy_config: upgrade_point.new_deploy_config,
             new_validator_slots: upgrade_point.new_validator_slots,
         }
@@ -116,6 +123,7 @@ impl UpgradePoint {
             upgrade_installer_bytes,
             upgrade_installer_args,
             new_wasm_config: self.new_wasm_config,
+            new_system_config: self.new_system_config,
             new_deploy_config: self.new_deploy_config,
             new_validator_slots: self.new_validator_slots,
         })
@@ -131,6 +139,7 @@ pub(super) struct ChainspecConfig {
     deploys: DeployConfig,
     upgrade: Option<Vec<UpgradePoint>>,
     wasm_config: WasmConfig,
+    system_config: SystemConfig,
 }
 
 impl From<&chainspec::Chainspec> for ChainspecConfig {
@@ -150,11 +159,17 @@ impl From<&chainspec::Chainspec> for ChainspecConfig {
             ),
             auction_installer_path: External::path(DEFAULT_AUCTION_INSTALLER_PATH),
             accounts_path: External::path(DEFAULT_ACCOUNTS_CSV_PATH),
+            delegators_path: if chainspec.genesis.delegators.is_empty() {
+                None
+            } else {
+                Some(External::path(DEFAULT_DELEGATORS_CSV_PATH))
+            },
         };
 
         let highway = chainspec.genesis.highway_config;
         let deploys = chainspec.genesis.deploy_config;
         let wasm_config = chainspec.genesis.wasm_config;
+        let system_config = chainspec.genesis.system_config;
 
         let upgrades = chainspec
             .upgrades
@@ -173,6 +188,7 @@ impl From<&chainspec::Chainspec> for ChainspecConfig {
             deploys,
             upgrade,
             wasm_config,
+            system_config,
         }
     }
 }
@@ -216,6 +232,29 @@ pub(super) fn parse_toml<P: AsRef<Path>>(chainspec_path: P) -> Result<chainspec:
         .load(root)
         .map_err(Error::LoadGenesisAccounts)?;
 
+    let delegators: Vec<GenesisDelegator> = chainspec
+        .genesis
+        .delegators_path
+        .map(|ext_vec| ext_vec.load(root))
+        .transpose()
+        .map_err(Error::LoadGenesisDelegators)?
+        .unwrap_or_default();
+
+    for genesis_delegator in &delegators {
+        let is_genesis_validator = accounts.iter().any(|genesis_account| {
+            genesis_account.is_genesis_validator()
+                && genesis_account.public_key() == Some(genesis_delegator.validator())
+        });
+        if !is_genesis_validator {
+            return Err(Error::NotAGenesisValidator(
+                genesis_delegator
+                    .validator()
+                    .try_into()
+                    .expect("should have valid genesis public key"),
+            ));
+        }
+    }
+
     let genesis = chainspec::GenesisConfig {
         name: chainspec.genesis.name,
         timestamp: chainspec.genesis.timestamp,
@@ -229,7 +268,9 @@ pub(super) fn parse_toml<P: AsRef<Path>>(chainspec_path: P) -> Result<chainspec:
         standard_payment_installer_bytes,
         auction_installer_bytes,
         accounts,
+        delegators,
         wasm_config: chainspec.wasm_config,
+        system_config: chainspec.system_config,
         deploy_config: chainspec.deploys,
         highway_config: chainspec.highway,
     };
@@ -3,7 +3,10 @@ use uint::FromDecStrErr;
 
 use casper_types::account::ACCOUNT_HASH_LENGTH;
 
-use crate::utils::{LoadError, ReadFileError};
+use crate::{
+    crypto::asymmetric_key::PublicKey,
+    utils::{LoadError, ReadFileError},
+};
 
 /// Error while encoding or decoding the chainspec.
 #[derive(Debug, Error)]
@@ -62,6 +65,14 @@ pub enum Error {
     /// Error loading the genesis accounts.
     #[error("could not load genesis accounts: {0}")]
     LoadGenesisAccounts(LoadError<GenesisLoadError>),
+
+    /// Error loading the genesis delegators.
+    #[error("could not load genesis delegators: {0}")]
+    LoadGenesisDelegators(LoadError<GenesisLoadError>),
+
+    /// A genesis delegator delegates to a key which is not a genesis validator.
+    #[error("genesis delegation to {0}, which is not a genesis validator")]
+    NotAGenesisValidator(PublicKey),
 }
 
 /// Error loading genesis accounts file.
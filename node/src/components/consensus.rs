@@ -8,6 +8,7 @@ mod era_supervisor;
 mod highway_core;
 mod metrics;
 mod protocols;
+mod stub;
 #[cfg(test)]
 mod tests;
 mod traits;
@@ -28,7 +29,10 @@ use casper_types::auction::ValidatorWeights;
 
 use crate::{
     components::Component,
-    crypto::{asymmetric_key::PublicKey, hash::Digest},
+    crypto::{
+        asymmetric_key::{PublicKey, Signature},
+        hash::Digest,
+    },
     effect::{
         announcements::ConsensusAnnouncement,
         requests::{
@@ -43,9 +47,11 @@ use crate::{
 };
 
 pub use config::Config;
+pub(crate) use config::UnitLogConfig;
 pub(crate) use consensus_protocol::{BlockContext, EraEnd};
 pub(crate) use era_supervisor::{EraId, EraSupervisor};
 pub(crate) use protocols::highway::HighwayProtocol;
+pub(crate) use stub::ConsensusStub;
 use traits::NodeIdT;
 
 #[derive(DataSize, Clone, Serialize, Deserialize)]
@@ -92,6 +98,20 @@ pub enum Event<I> {
     },
     /// An event instructing us to shutdown if the latest era received no votes
     Shutdown,
+    /// Our signature of a hash, requested by the consensus protocol instance of the given era.
+    Signature {
+        era_id: EraId,
+        hash: Digest,
+        /// The signature, or `None` if signing failed.
+        signature: Option<Signature>,
+    },
+    /// The drift of our clock from the network's has crossed the configured bound.
+    ClockDrift {
+        /// The median estimated offset in milliseconds of our clock from peers' clocks.
+        median_offset: i64,
+        /// Whether the drift now exceeds the configured bound.
+        exceeds_bound: bool,
+    },
 }
 
 impl Debug for ConsensusMessage {
@@ -172,6 +192,19 @@ impl<I: Debug> Display for Event<I> {
                 booking_block_hash, key_block_seed, get_validators_result
             ),
             Event::Shutdown => write!(f, "Shutdown if current era is inactive"),
+            Event::Signature {
+                era_id,
+                hash,
+                signature,
+            } => write!(f, "signature of {} for {}: {:?}", hash, era_id, signature),
+            Event::ClockDrift {
+                median_offset,
+                exceeds_bound,
+            } => write!(
+                f,
+                "clock drift of {} ms, exceeds bound: {}",
+                median_offset, exceeds_bound
+            ),
         }
     }
 }
@@ -231,6 +264,14 @@ where
                 block_header,
                 responder,
             )) => handling_es.handle_linear_chain_block(*block_header, responder),
+            Event::ConsensusRequest(requests::ConsensusRequest::SignStatus(payload, responder)) => {
+                handling_es.handle_sign_status(payload, responder)
+            }
+            Event::ConsensusRequest(requests::ConsensusRequest::SignDeployReceipt(
+                deploy_hash,
+                timestamp,
+                responder,
+            )) => handling_es.handle_sign_deploy_receipt(deploy_hash, timestamp, responder),
             Event::ResolveValidity {
                 era_id,
                 sender,
@@ -279,6 +320,15 @@ where
                 )
             }
             Event::Shutdown => handling_es.shutdown_if_necessary(),
+            Event::Signature {
+                era_id,
+                hash,
+                signature,
+            } => handling_es.handle_signature(era_id, hash, signature),
+            Event::ClockDrift {
+                median_offset,
+                exceeds_bound,
+            } => handling_es.handle_clock_drift(median_offset, exceeds_bound),
         }
     }
 }
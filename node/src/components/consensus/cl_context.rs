@@ -1,5 +1,3 @@
-use std::rc::Rc;
-
 use tracing::info;
 
 use crate::{
@@ -8,32 +6,25 @@ use crate::{
         traits::{Context, ValidatorSecret},
     },
     crypto::{
-        asymmetric_key::{self, PublicKey, SecretKey, Signature},
+        asymmetric_key::{self, PublicKey, Signature},
         hash::{self, Digest},
     },
     NodeRng,
 };
 
-pub(crate) struct Keypair {
-    secret_key: Rc<SecretKey>,
-    public_key: PublicKey,
-}
-
-impl Keypair {
-    pub(crate) fn new(secret_key: Rc<SecretKey>, public_key: PublicKey) -> Self {
-        Self {
-            secret_key,
-            public_key,
-        }
-    }
-}
+/// Our validator key, which never signs right away.
+///
+/// Signatures are requested via `ProtocolOutcome::RequestSignature` instead, and created by the
+/// era supervisor's `Signer` without blocking the reactor, as the key may be held by a remote
+/// signer service.
+pub(crate) struct DeferredSecret;
 
-impl ValidatorSecret for Keypair {
+impl ValidatorSecret for DeferredSecret {
     type Hash = Digest;
     type Signature = Signature;
 
-    fn sign(&self, hash: &Digest, rng: &mut NodeRng) -> Signature {
-        asymmetric_key::sign(hash, self.secret_key.as_ref(), &self.public_key, rng)
+    fn sign(&self, _hash: &Digest, _rng: &mut NodeRng) -> Option<Signature> {
+        None
     }
 }
 
@@ -44,7 +35,7 @@ pub(crate) struct ClContext;
 impl Context for ClContext {
     type ConsensusValue = CandidateBlock;
     type ValidatorId = PublicKey;
-    type ValidatorSecret = Keypair;
+    type ValidatorSecret = DeferredSecret;
     type Signature = Signature;
     type Hash = Digest;
     type InstanceId = Digest;
@@ -1,13 +1,227 @@
+use std::{
+    path::{Path, PathBuf},
+    rc::Rc,
+    time::Duration,
+};
+
+use anyhow::{bail, Context};
 use datasize::DataSize;
+use reqwest::Url;
 use serde::{Deserialize, Serialize};
 
-use crate::{crypto::asymmetric_key::SecretKey, utils::External};
+use crate::{
+    crypto::{
+        self,
+        asymmetric_key::{PublicKey, SecretKey},
+        signer::{LocalSigner, RemoteSigner, RemoteSignerCredentials, Signer},
+    },
+    utils::{self, passphrase, External, LoadError},
+};
+
+/// Default number of finalized blocks between two compactions of the unit log.
+const DEFAULT_UNIT_LOG_COMPACTION_INTERVAL: u64 = 10;
 
 /// Consensus configuration.
-#[derive(DataSize, Debug, Deserialize, Serialize, Default, Clone)]
+#[derive(DataSize, Debug, Deserialize, Serialize, Clone)]
 // Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
 #[serde(deny_unknown_fields)]
 pub struct Config {
-    /// Path to secret key file.
+    /// Path to secret key file.  Must not be set if `remote_signer` is.
+    #[serde(default)]
     pub secret_key_path: External<SecretKey>,
+    /// Paths to secret key files which were rotated out via the auction, and are still used to
+    /// sign in eras whose validators were selected before the rotation took effect.
+    #[serde(default)]
+    pub previous_secret_key_paths: Vec<External<SecretKey>>,
+    /// Path to a file holding the passphrase of encrypted secret key files.  If not given, the
+    /// passphrase is read from the `CASPER_SECRET_KEY_PASSPHRASE` environment variable, or
+    /// prompted for on the terminal.
+    #[serde(default)]
+    pub secret_key_passphrase_path: Option<PathBuf>,
+    /// External signer service holding the validator's keys, used instead of local key files.
+    #[serde(default)]
+    pub remote_signer: Option<RemoteSignerConfig>,
+    /// Whether to refuse to start validating in a new era while the drift of our clock from the
+    /// network's exceeds the network's `max_clock_drift`.
+    #[serde(default)]
+    pub refuse_validating_on_clock_drift: bool,
+    /// Directory in which each era's finalized units are logged, so that the payloads of finalized
+    /// blocks can be dropped from memory.  If not set, all units are kept in memory for the whole
+    /// era.
+    #[serde(default)]
+    pub unit_log_dir: Option<PathBuf>,
+    /// Number of finalized blocks after which the payloads of finalized blocks are moved from
+    /// memory into the unit log.
+    #[serde(default = "default_unit_log_compaction_interval")]
+    pub unit_log_compaction_interval: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            secret_key_path: Default::default(),
+            previous_secret_key_paths: Default::default(),
+            secret_key_passphrase_path: None,
+            remote_signer: None,
+            refuse_validating_on_clock_drift: false,
+            unit_log_dir: None,
+            unit_log_compaction_interval: DEFAULT_UNIT_LOG_COMPACTION_INTERVAL,
+        }
+    }
+}
+
+fn default_unit_log_compaction_interval() -> u64 {
+    DEFAULT_UNIT_LOG_COMPACTION_INTERVAL
+}
+
+/// Where a single era's consensus protocol instance logs its units, and how often.
+#[derive(Clone, Debug)]
+pub(crate) struct UnitLogConfig {
+    /// The path of the era's log file.
+    pub(crate) path: PathBuf,
+    /// Number of finalized blocks after which the protocol state is compacted.
+    pub(crate) compaction_interval: u64,
+}
+
+/// Configuration of an external signer service, e.g. a KMS or HSM proxy.
+#[derive(DataSize, Debug, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct RemoteSignerConfig {
+    /// URL to which signing requests are posted.  Must use `https`, unless the service runs on a
+    /// loopback address.
+    pub address: String,
+    /// Path to a file holding the token the service requires in the `Authorization` header.
+    pub auth_token_path: PathBuf,
+    /// Path to a PEM file holding a root certificate to trust for the service's TLS certificate,
+    /// in addition to the system's root certificates.
+    #[serde(default)]
+    pub ca_certificate_path: Option<PathBuf>,
+    /// Path to a PKCS #12 archive without password holding the client certificate and key to
+    /// present to the service, if it requires mutual TLS.
+    #[serde(default)]
+    pub client_identity_path: Option<PathBuf>,
+    /// Path to the public key file of the key the service signs with.
+    pub public_key_path: External<PublicKey>,
+    /// Paths to the public key files of keys which were rotated out via the auction, and which
+    /// the service still signs with in eras whose validators were selected before the rotation.
+    #[serde(default)]
+    pub previous_public_key_paths: Vec<External<PublicKey>>,
+    /// Timeout in milliseconds of a single signing request.
+    #[serde(with = "crate::utils::milliseconds")]
+    pub timeout: Duration,
+}
+
+impl Config {
+    /// Creates the signers for the current and previous keys, resolving relative paths from
+    /// `root`.
+    ///
+    /// If a remote signer is configured, all signatures are requested from it and no secret key
+    /// is loaded.  Otherwise, the passphrase is only retrieved, and at most once, if any of the
+    /// secret keys is encrypted.
+    pub(crate) fn load_signers(
+        self,
+        root: &Path,
+    ) -> anyhow::Result<(Rc<dyn Signer>, Vec<Rc<dyn Signer>>)> {
+        match self.remote_signer {
+            Some(remote_signer) => {
+                if !matches!(self.secret_key_path, External::Missing)
+                    || !self.previous_secret_key_paths.is_empty()
+                {
+                    bail!("secret key paths must not be set when a remote signer is configured");
+                }
+                remote_signer.load_signers(root)
+            }
+            None => {
+                let (secret_key, previous_secret_keys) = load_secret_keys(
+                    self.secret_key_path,
+                    self.previous_secret_key_paths,
+                    self.secret_key_passphrase_path,
+                    root,
+                )?;
+                let to_signer =
+                    |secret_key| -> Rc<dyn Signer> { Rc::new(LocalSigner::new(secret_key)) };
+                Ok((
+                    to_signer(secret_key),
+                    previous_secret_keys.into_iter().map(to_signer).collect(),
+                ))
+            }
+        }
+    }
+}
+
+impl RemoteSignerConfig {
+    fn load_signers(self, root: &Path) -> anyhow::Result<(Rc<dyn Signer>, Vec<Rc<dyn Signer>>)> {
+        let address = Url::parse(&self.address)
+            .with_context(|| format!("invalid remote signer address {}", self.address))?;
+        let auth_token = utils::read_file(root.join(&self.auth_token_path))?;
+        let auth_token = String::from_utf8(auth_token).with_context(|| {
+            format!(
+                "invalid auth token in {}",
+                root.join(&self.auth_token_path).display()
+            )
+        })?;
+        let credentials = RemoteSignerCredentials {
+            auth_token,
+            ca_certificate_pem: self
+                .ca_certificate_path
+                .map(|path| utils::read_file(root.join(path)))
+                .transpose()?,
+            client_identity_pkcs12: self
+                .client_identity_path
+                .map(|path| utils::read_file(root.join(path)))
+                .transpose()?,
+        };
+        let timeout = self.timeout;
+        let to_signer = |public_key_path: External<PublicKey>| -> anyhow::Result<Rc<dyn Signer>> {
+            let public_key = public_key_path.load(root)?;
+            let signer =
+                RemoteSigner::new(address.clone(), credentials.clone(), public_key, timeout)?;
+            Ok(Rc::new(signer))
+        };
+        let signer = to_signer(self.public_key_path)?;
+        let previous_signers = self
+            .previous_public_key_paths
+            .into_iter()
+            .map(to_signer)
+            .collect::<anyhow::Result<_>>()?;
+        Ok((signer, previous_signers))
+    }
+}
+
+/// Loads the current and previous secret keys, resolving relative paths from `root`.
+fn load_secret_keys(
+    secret_key_path: External<SecretKey>,
+    previous_secret_key_paths: Vec<External<SecretKey>>,
+    secret_key_passphrase_path: Option<PathBuf>,
+    root: &Path,
+) -> anyhow::Result<(SecretKey, Vec<SecretKey>)> {
+    let passphrase_path = secret_key_passphrase_path.map(|path| root.join(path));
+    let mut maybe_passphrase = None;
+    let mut load = |external: External<SecretKey>| -> anyhow::Result<SecretKey> {
+        match external.load(root) {
+            Err(LoadError::Failed {
+                path,
+                error: crypto::Error::SecretKeyEncrypted,
+            }) => {
+                if maybe_passphrase.is_none() {
+                    let passphrase = passphrase::get_passphrase(
+                        passphrase_path.as_deref(),
+                        "Secret key passphrase: ",
+                    )?;
+                    maybe_passphrase = Some(passphrase);
+                }
+                let passphrase = maybe_passphrase.as_ref().expect("should be set");
+                SecretKey::from_encrypted_file(&path, passphrase)
+                    .with_context(|| format!("could not decrypt secret key {}", path.display()))
+            }
+            result => Ok(result?),
+        }
+    };
+
+    let secret_key = load(secret_key_path)?;
+    let previous_secret_keys = previous_secret_key_paths
+        .into_iter()
+        .map(&mut load)
+        .collect::<anyhow::Result<_>>()?;
+    Ok((secret_key, previous_secret_keys))
 }
@@ -2,6 +2,7 @@ use std::{any::Any, collections::BTreeMap, fmt::Debug};
 
 use anyhow::Error;
 use datasize::DataSize;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{components::consensus::traits::Context, types::Timestamp, NodeRng};
@@ -26,7 +27,9 @@ impl BlockContext {
 }
 
 /// Equivocation and reward information to be included in the terminal finalized block.
-#[derive(Clone, DataSize, Debug, PartialOrd, Ord, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(
+    Clone, DataSize, Debug, PartialOrd, Ord, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema,
+)]
 #[serde(bound(
     serialize = "VID: Ord + Serialize",
     deserialize = "VID: Ord + Deserialize<'de>",
@@ -85,6 +88,9 @@ pub(crate) enum ProtocolOutcome<I, C: Context> {
     NewEvidence(C::ValidatorId),
     /// Send evidence about the validator from an earlier era to the peer.
     SendEvidence(I, C::ValidatorId),
+    /// Request our signature of the given hash, and pass it to
+    /// `ConsensusProtocol::handle_signature`.
+    RequestSignature(C::Hash),
 }
 
 /// An API for a single instance of the consensus.
@@ -127,6 +133,15 @@ pub(crate) trait ConsensusProtocol<I, C: Context> {
         rng: &mut NodeRng,
     ) -> Vec<ProtocolOutcome<I, C>>;
 
+    /// Handles our signature of `hash`, requested via `ProtocolOutcome::RequestSignature`, or
+    /// `None` if it could not be signed.
+    fn handle_signature(
+        &mut self,
+        hash: C::Hash,
+        opt_signature: Option<C::Signature>,
+        rng: &mut NodeRng,
+    ) -> Vec<ProtocolOutcome<I, C>>;
+
     /// Turns this instance into an active validator, that participates in the consensus protocol.
     fn activate_validator(
         &mut self,
@@ -9,6 +9,8 @@ use std::{
     collections::{HashMap, HashSet},
     convert::TryInto,
     fmt::{self, Debug, Formatter},
+    iter,
+    path::{Path, PathBuf},
     rc::Rc,
 };
 
@@ -34,23 +36,27 @@ use crate::{
         chainspec_loader::Chainspec,
         consensus::{
             candidate_block::CandidateBlock,
-            cl_context::{ClContext, Keypair},
+            cl_context::{ClContext, DeferredSecret},
             consensus_protocol::{
                 BlockContext, ConsensusProtocol, EraEnd, FinalizedBlock as CpFinalizedBlock,
                 ProtocolOutcome,
             },
             metrics::ConsensusMetrics,
             traits::NodeIdT,
-            Config, ConsensusMessage, Event, ReactorEventT,
+            Config, ConsensusMessage, Event, ReactorEventT, UnitLogConfig,
         },
     },
     crypto::{
-        asymmetric_key::{self, PublicKey, SecretKey, Signature},
+        asymmetric_key::{PublicKey, Signature},
         hash::Digest,
+        signer::Signer,
     },
     effect::{EffectBuilder, EffectExt, Effects, Responder},
     fatal,
-    types::{BlockHash, BlockHeader, FinalizedBlock, ProtoBlock, Timestamp},
+    types::{
+        signed_status_digest, BlockHash, BlockHeader, DeployHash, DeployReceipt, FinalizedBlock,
+        ProtoBlock, Timestamp,
+    },
     utils::WithDir,
     NodeRng,
 };
@@ -70,6 +76,7 @@ type ConsensusConstructor<I> = dyn Fn(
     Option<&dyn ConsensusProtocol<I, ClContext>>, // previous era's consensus instance
     Timestamp,                                    // start time for this era
     u64,                                          // random seed
+    Option<UnitLogConfig>,                        // where to log this era's units, if anywhere
 ) -> Box<dyn ConsensusProtocol<I, ClContext>>;
 
 #[derive(DataSize)]
@@ -80,8 +87,12 @@ pub struct EraSupervisor<I> {
     /// This map always contains exactly `2 * bonded_eras + 1` entries, with the last one being the
     /// current one.
     active_eras: HashMap<EraId, Era<I>>,
-    pub(super) secret_signing_key: Rc<SecretKey>,
+    #[data_size(skip)]
+    pub(super) signer: Rc<dyn Signer>,
     pub(super) public_signing_key: PublicKey,
+    /// Keys rotated out via the auction, still used in eras which have them as validators.
+    #[data_size(skip)]
+    previous_signers: HashMap<PublicKey, Rc<dyn Signer>>,
     current_era: EraId,
     chainspec: Chainspec,
     #[data_size(skip)] // Negligible for most closures, zero for functions.
@@ -95,6 +106,15 @@ pub struct EraSupervisor<I> {
     bonded_eras: u64,
     #[data_size(skip)]
     metrics: ConsensusMetrics,
+    /// Whether to refuse to start validating in a new era while our clock drifts too far from the
+    /// network's.
+    refuse_validating_on_clock_drift: bool,
+    /// Whether the drift of our clock from the network's was last announced to exceed the bound.
+    clock_drift_exceeded: bool,
+    /// Directory in which each era's finalized units are logged, if any.
+    unit_log_dir: Option<PathBuf>,
+    /// Number of finalized blocks after which an era's protocol state is compacted.
+    unit_log_compaction_interval: u64,
 }
 
 impl<I> Debug for EraSupervisor<I> {
@@ -122,22 +142,34 @@ where
         mut rng: &mut NodeRng,
     ) -> Result<(Self, Effects<Event<I>>), Error> {
         let (root, config) = config.into_parts();
-        let secret_signing_key = Rc::new(config.secret_key_path.load(root)?);
-        let public_signing_key = PublicKey::from(secret_signing_key.as_ref());
+        let refuse_validating_on_clock_drift = config.refuse_validating_on_clock_drift;
+        let unit_log_dir = config.unit_log_dir.as_ref().map(|dir| root.join(dir));
+        let unit_log_compaction_interval = config.unit_log_compaction_interval;
+        let (signer, previous_signers) = config.load_signers(&root)?;
+        let public_signing_key = *signer.public_key();
+        let previous_signers = previous_signers
+            .into_iter()
+            .map(|signer| (*signer.public_key(), signer))
+            .collect();
         let bonded_eras: u64 = DEFAULT_UNBONDING_DELAY - chainspec.genesis.auction_delay;
         let metrics = ConsensusMetrics::new(registry)
             .expect("failure to setup and register ConsensusMetrics");
 
         let mut era_supervisor = Self {
             active_eras: Default::default(),
-            secret_signing_key,
+            signer,
             public_signing_key,
+            previous_signers,
             current_era: EraId(0),
             chainspec: chainspec.clone(),
             new_consensus,
             node_start_time: Timestamp::now(),
             bonded_eras,
             metrics,
+            refuse_validating_on_clock_drift,
+            clock_drift_exceeded: false,
+            unit_log_dir,
+            unit_log_compaction_interval,
         };
 
         let results = era_supervisor.new_era(
@@ -171,6 +203,18 @@ where
         }
     }
 
+    /// Returns the first of our signing keys, starting with the current one, for which
+    /// `is_validator` returns true.
+    fn our_signing_key<F>(&self, is_validator: F) -> Option<(PublicKey, Rc<dyn Signer>)>
+    where
+        F: Fn(&PublicKey) -> bool,
+    {
+        iter::once((&self.public_signing_key, &self.signer))
+            .chain(self.previous_signers.iter())
+            .find(|(public_key, _)| is_validator(*public_key))
+            .map(|(public_key, signer)| (*public_key, Rc::clone(signer)))
+    }
+
     fn booking_block_height(&self, era_id: EraId) -> u64 {
         // The booking block for era N is the last block of era N - AUCTION_DELAY - 1
         // To find it, we get the start height of era N - AUCTION_DELAY and subtract 1
@@ -241,17 +285,25 @@ where
             .collect();
 
         // Activate the era if this node was already running when the era began, it is still
-        // ongoing based on its minimum duration, and we are one of the validators.
-        let our_id = self.public_signing_key;
+        // ongoing based on its minimum duration, and one of our keys is among the validators.
+        let our_signing_key =
+            self.our_signing_key(|key| validator_stakes.iter().any(|(v, _)| v == key));
         let should_activate = if self.node_start_time >= start_time {
             info!(
                 era = era_id.0,
                 %self.node_start_time, "not voting; node was not started before the era began",
             );
             false
-        } else if !validator_stakes.iter().any(|(v, _)| *v == our_id) {
+        } else if our_signing_key.is_none() {
+            let our_id = self.public_signing_key;
             info!(era = era_id.0, %our_id, "not voting; not a validator");
             false
+        } else if self.refuse_validating_on_clock_drift && self.clock_drift_exceeded {
+            warn!(
+                era = era_id.0,
+                "not voting; local clock drifts from the network's by more than the maximum",
+            );
+            false
         } else {
             info!(era = era_id.0, "start voting");
             true
@@ -261,6 +313,11 @@ where
             .checked_sub(1)
             .and_then(|last_era_id| self.active_eras.get(&last_era_id));
 
+        let unit_log_config = self.unit_log_dir.as_ref().map(|dir| UnitLogConfig {
+            path: dir.join(format!("era-{}.units", era_id.0)),
+            compaction_interval: self.unit_log_compaction_interval,
+        });
+
         let mut consensus = (self.new_consensus)(
             instance_id(&self.chainspec, state_root_hash, start_height),
             validator_stakes,
@@ -269,16 +326,21 @@ where
             prev_era.map(|era| &*era.consensus),
             start_time,
             seed,
+            unit_log_config,
         );
 
-        let results = if should_activate {
-            let secret = Keypair::new(Rc::clone(&self.secret_signing_key), our_id);
-            consensus.activate_validator(our_id, secret, timestamp)
-        } else {
-            Vec::new()
+        let results = match &our_signing_key {
+            Some((our_id, _)) if should_activate => {
+                if *our_id != self.public_signing_key {
+                    info!(era = era_id.0, %our_id, "voting with a previous signing key");
+                }
+                consensus.activate_validator(*our_id, DeferredSecret, timestamp)
+            }
+            _ => Vec::new(),
         };
 
-        let era = Era::new(consensus, start_height, newly_slashed, slashed);
+        let our_id = our_signing_key.map(|(our_id, _)| our_id);
+        let era = Era::new(consensus, start_height, newly_slashed, slashed, our_id);
         let _ = self.active_eras.insert(era_id, era);
 
         // Remove the era that has become obsolete now. We keep 2 * bonded_eras past eras because
@@ -303,6 +365,11 @@ where
         era_id.0 + self.bonded_eras >= self.current_era.0 && era_id <= self.current_era
     }
 
+    /// Returns the folder the finalized units are logged to, if configured.
+    pub(crate) fn unit_log_dir(&self) -> Option<&Path> {
+        self.unit_log_dir.as_deref()
+    }
+
     /// Inspect the active eras.
     #[cfg(test)]
     pub(crate) fn active_eras(&self) -> &HashMap<EraId, Era<I>> {
@@ -418,16 +485,35 @@ where
     pub(super) fn handle_linear_chain_block(
         &mut self,
         block_header: BlockHeader,
-        responder: Responder<Signature>,
+        responder: Responder<Option<(PublicKey, Signature)>>,
     ) -> Effects<Event<I>> {
         // TODO - we should only sign if we're a validator for the given era ID.
-        let signature = asymmetric_key::sign(
-            block_header.hash().inner(),
-            &self.era_supervisor.secret_signing_key,
-            &self.era_supervisor.public_signing_key,
-            self.rng,
-        );
-        let mut effects = responder.respond(signature).ignore();
+        // Sign with the key we use in the block's era, which differs from the current one if it
+        // has been rotated since.
+        let signer = self
+            .era_supervisor
+            .active_eras
+            .get(&block_header.era_id())
+            .and_then(|era| era.our_signing_key)
+            .and_then(|our_id| self.era_supervisor.our_signing_key(|key| *key == our_id))
+            .map_or_else(
+                || Rc::clone(&self.era_supervisor.signer),
+                |(_, signer)| signer,
+            );
+        let public_key = *signer.public_key();
+        let block_hash = *block_header.hash();
+        let signing = signer.sign(block_hash.inner().as_ref().to_vec(), self.rng);
+        let mut effects = async move {
+            let maybe_signature = match signing.await {
+                Ok(signature) => Some((public_key, signature)),
+                Err(error) => {
+                    error!(%error, %block_hash, "failed to sign block");
+                    None
+                }
+            };
+            responder.respond(maybe_signature).await
+        }
+        .ignore();
         if block_header.era_id() < self.era_supervisor.current_era {
             trace!(era_id = %block_header.era_id(), "executed block in old era");
             return effects;
@@ -472,6 +558,77 @@ where
         effects
     }
 
+    pub(super) fn handle_sign_status(
+        &mut self,
+        payload: Vec<u8>,
+        responder: Responder<Option<(PublicKey, Signature)>>,
+    ) -> Effects<Event<I>> {
+        let signer = &self.era_supervisor.signer;
+        let public_key = *signer.public_key();
+        let digest = signed_status_digest(&payload);
+        let signing = signer.sign(digest.as_ref().to_vec(), self.rng);
+        async move {
+            let maybe_signature = match signing.await {
+                Ok(signature) => Some((public_key, signature)),
+                Err(error) => {
+                    error!(%error, "failed to sign status");
+                    None
+                }
+            };
+            responder.respond(maybe_signature).await
+        }
+        .ignore()
+    }
+
+    pub(super) fn handle_sign_deploy_receipt(
+        &mut self,
+        deploy_hash: DeployHash,
+        timestamp: Timestamp,
+        responder: Responder<Option<DeployReceipt>>,
+    ) -> Effects<Event<I>> {
+        let signer = &*self.era_supervisor.signer;
+        let signing = DeployReceipt::sign(deploy_hash, timestamp, signer, self.rng);
+        async move {
+            let maybe_receipt = match signing.await {
+                Ok(receipt) => Some(receipt),
+                Err(error) => {
+                    error!(%error, %deploy_hash, "failed to sign deploy receipt");
+                    None
+                }
+            };
+            responder.respond(maybe_receipt).await
+        }
+        .ignore()
+    }
+
+    /// Passes our signature of `hash` to the consensus protocol instance of the era that
+    /// requested it.
+    pub(super) fn handle_signature(
+        &mut self,
+        era_id: EraId,
+        hash: Digest,
+        signature: Option<Signature>,
+    ) -> Effects<Event<I>> {
+        self.delegate_to_era(era_id, move |consensus, rng| {
+            consensus.handle_signature(hash, signature, rng)
+        })
+    }
+
+    pub(super) fn handle_clock_drift(
+        &mut self,
+        median_offset: i64,
+        exceeds_bound: bool,
+    ) -> Effects<Event<I>> {
+        self.era_supervisor.clock_drift_exceeded = exceeds_bound;
+        if exceeds_bound && self.era_supervisor.refuse_validating_on_clock_drift {
+            warn!(
+                median_offset,
+                "clock drift exceeds the maximum; won't vote in new eras until it is corrected"
+            );
+        }
+        Effects::new()
+    }
+
     pub(super) fn handle_create_new_era(
         &mut self,
         block_header: BlockHeader,
@@ -710,6 +867,35 @@ where
                 }
                 effects
             }
+            ProtocolOutcome::RequestSignature(hash) => {
+                // Sign with the key we use in this era, which differs from the current one if it
+                // has been rotated since.
+                let opt_signer = self
+                    .era(era_id)
+                    .our_signing_key
+                    .and_then(|our_id| self.era_supervisor.our_signing_key(|key| *key == our_id));
+                let signer = match opt_signer {
+                    Some((_, signer)) => signer,
+                    None => {
+                        error!(era = era_id.0, %hash, "no signing key to sign with");
+                        return self.delegate_to_era(era_id, move |consensus, rng| {
+                            consensus.handle_signature(hash, None, rng)
+                        });
+                    }
+                };
+                signer
+                    .sign(hash.as_ref().to_vec(), self.rng)
+                    .event(move |result| {
+                        let signature = result
+                            .map_err(|error| error!(%error, %hash, "failed to sign"))
+                            .ok();
+                        Event::Signature {
+                            era_id,
+                            hash,
+                            signature,
+                        }
+                    })
+            }
             ProtocolOutcome::SendEvidence(sender, pub_key) => era_id
                 .iter_other(self.era_supervisor.bonded_eras)
                 .flat_map(|e_id| {
@@ -6,6 +6,7 @@ use std::{
 use casper_types::bytesrepr::{self, FromBytes, ToBytes};
 use datasize::DataSize;
 use itertools::Itertools;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use tracing::warn;
 
@@ -20,7 +21,18 @@ use crate::{
 };
 
 #[derive(
-    DataSize, Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+    DataSize,
+    Debug,
+    Clone,
+    Copy,
+    Hash,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    JsonSchema,
 )]
 pub struct EraId(pub(crate) u64);
 
@@ -123,6 +135,8 @@ pub struct Era<I> {
     pub(crate) slashed: HashSet<PublicKey>,
     /// Accusations collected in this era so far.
     accusations: HashSet<PublicKey>,
+    /// The key we sign with in this era, if we are one of its validators.
+    pub(crate) our_signing_key: Option<PublicKey>,
 }
 
 impl<I> Era<I> {
@@ -131,6 +145,7 @@ impl<I> Era<I> {
         start_height: u64,
         newly_slashed: Vec<PublicKey>,
         slashed: HashSet<PublicKey>,
+        our_signing_key: Option<PublicKey>,
     ) -> Self {
         Era {
             consensus,
@@ -139,6 +154,7 @@ impl<I> Era<I> {
             newly_slashed,
             slashed,
             accusations: HashSet::new(),
+            our_signing_key,
         }
     }
 
@@ -242,6 +258,7 @@ where
             newly_slashed,
             slashed,
             accusations,
+            our_signing_key,
         } = self;
 
         // `DataSize` cannot be made object safe due its use of associated constants. We implement
@@ -267,6 +284,7 @@ where
             + newly_slashed.estimate_heap_size()
             + slashed.estimate_heap_size()
             + accusations.estimate_heap_size()
+            + our_signing_key.estimate_heap_size()
     }
 }
 
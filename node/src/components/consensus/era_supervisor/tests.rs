@@ -181,6 +181,11 @@ async fn propose_and_finalize() -> Result<(), Error> {
     let chainspec = new_test_chainspec(vec![(alice_pk, 10), (bob_pk, 100)]);
     let config = Config {
         secret_key_path: External::Loaded(alice_sk),
+        previous_secret_key_paths: vec![],
+        secret_key_passphrase_path: None,
+        remote_signer: None,
+        refuse_validating_on_clock_drift: false,
+        ..Default::default()
     };
 
     let registry = Registry::new();
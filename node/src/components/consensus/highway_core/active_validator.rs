@@ -34,6 +34,9 @@ pub(crate) enum Effect<C: Context> {
     ///
     /// When this is returned, the validator automatically deactivates.
     WeEquivocated(Evidence<C>),
+    /// Our signature of the given hash is needed to publish a new vertex: `on_signature` needs to
+    /// be called with it.
+    RequestSignature(C::Hash),
 }
 
 /// A validator that actively participates in consensus by creating new vertices.
@@ -61,6 +64,10 @@ pub(crate) struct ActiveValidator<C: Context> {
     next_timer: Timestamp,
     /// Panorama and timestamp for a block we are about to propose when we get a consensus value.
     next_proposal: Option<(Timestamp, Panorama<C>)>,
+    /// Our latest unit, if it is still waiting for its signature.
+    unsigned_unit: Option<WireUnit<C>>,
+    /// Our endorsements that are still waiting for their signatures.
+    unsigned_endorsements: Vec<Endorsement<C>>,
 }
 
 impl<C: Context> Debug for ActiveValidator<C> {
@@ -87,6 +94,8 @@ impl<C: Context> ActiveValidator<C> {
             next_round_exp: state.params().init_round_exp(),
             next_timer: Timestamp::zero(),
             next_proposal: None,
+            unsigned_unit: None,
+            unsigned_endorsements: Vec::new(),
         };
         let effects = av.schedule_timer(start_time, state);
         (av, effects)
@@ -123,9 +132,7 @@ impl<C: Context> ActiveValidator<C> {
         } else if timestamp == r_id + self.witness_offset(r_len) {
             let panorama = state.citable_panorama().cutoff(state, timestamp);
             if panorama.has_correct() {
-                let witness_unit =
-                    self.new_unit(panorama, timestamp, None, state, instance_id, rng);
-                effects.push(Effect::NewVertex(ValidVertex(Vertex::Unit(witness_unit))))
+                effects.extend(self.new_unit(panorama, timestamp, None, state, instance_id, rng))
             }
         }
         effects
@@ -147,14 +154,11 @@ impl<C: Context> ActiveValidator<C> {
         if self.should_send_confirmation(vhash, now, state) {
             let panorama = state.confirmation_panorama(self.vidx, vhash);
             if panorama.has_correct() {
-                let confirmation_unit = self.new_unit(panorama, now, None, state, instance_id, rng);
-                let vv = ValidVertex(Vertex::Unit(confirmation_unit));
-                effects.extend(vec![Effect::NewVertex(vv)])
+                effects.extend(self.new_unit(panorama, now, None, state, instance_id, rng))
             }
         };
         if self.should_endorse(vhash, state) {
-            let endorsement = self.endorse(vhash, rng);
-            effects.extend(vec![Effect::NewVertex(ValidVertex(endorsement))]);
+            effects.push(self.endorse(vhash, rng));
         }
         effects
     }
@@ -176,15 +180,71 @@ impl<C: Context> ActiveValidator<C> {
                 unit.new_hash_obs(state, vidx)
             })
             .map(|v| self.endorse(v, rng))
-            .map(|endorsement| Effect::NewVertex(ValidVertex(endorsement)))
             .collect()
     }
 
+    /// Returns actions a validator needs to take upon receiving the signature of `hash` requested
+    /// via `Effect::RequestSignature`, or `None` if it could not be signed.
+    ///
+    /// The signed vertex is published, unless a newer unit by us has been added in the meantime.
+    pub(crate) fn on_signature(
+        &mut self,
+        hash: &C::Hash,
+        opt_signature: Option<C::Signature>,
+        state: &State<C>,
+    ) -> Vec<Effect<C>> {
+        if self
+            .unsigned_unit
+            .as_ref()
+            .map_or(false, |wunit| wunit.hash() == *hash)
+        {
+            let wire_unit = self
+                .unsigned_unit
+                .take()
+                .expect("unsigned unit should exist");
+            let signature = match opt_signature {
+                Some(signature) => signature,
+                None => {
+                    error!(%hash, "dropping unit: it could not be signed");
+                    return vec![];
+                }
+            };
+            if wire_unit.panorama[self.vidx] != state.panorama()[self.vidx] {
+                error!(%hash, "dropping signed unit to avoid equivocation");
+                return vec![];
+            }
+            let swunit = SignedWireUnit {
+                wire_unit,
+                signature,
+            };
+            return vec![Effect::NewVertex(ValidVertex(Vertex::Unit(swunit)))];
+        }
+        let opt_position = self
+            .unsigned_endorsements
+            .iter()
+            .position(|endorsement| endorsement.hash() == *hash);
+        match (opt_position, opt_signature) {
+            (Some(position), Some(signature)) => {
+                let endorsement = self.unsigned_endorsements.swap_remove(position);
+                vec![Self::endorsement_effect(endorsement, signature)]
+            }
+            (Some(position), None) => {
+                self.unsigned_endorsements.swap_remove(position);
+                error!(%hash, "dropping endorsement: it could not be signed");
+                vec![]
+            }
+            (None, _) => {
+                warn!(%hash, "received signature of unknown vertex");
+                vec![]
+            }
+        }
+    }
+
     /// Returns an effect to request a consensus value for a block to propose.
     ///
     /// If we are already waiting for a consensus value, `None` is returned instead.
     /// If the new value would come after a terminal block, the proposal is made immediately, and
-    /// without a value.
+    /// without a value; if that proposal can't be created, `None` is returned.
     pub(crate) fn request_new_block(
         &mut self,
         state: &State<C>,
@@ -202,8 +262,7 @@ impl<C: Context> ActiveValidator<C> {
         let panorama = state.citable_panorama().cutoff(state, timestamp);
         let opt_parent_hash = state.fork_choice(&panorama);
         if opt_parent_hash.map_or(false, |hash| state.is_terminal_block(hash)) {
-            let proposal_unit = self.new_unit(panorama, timestamp, None, state, instance_id, rng);
-            return Some(Effect::NewVertex(ValidVertex(Vertex::Unit(proposal_unit))));
+            return self.new_unit(panorama, timestamp, None, state, instance_id, rng);
         }
         let opt_parent = opt_parent_hash.map(|bh| state.block(bh));
         let height = opt_parent.map_or(0, |block| block.height);
@@ -243,9 +302,9 @@ impl<C: Context> ActiveValidator<C> {
             warn!("unexpected proposal value");
             return vec![];
         };
-        let proposal_unit =
-            self.new_unit(panorama, timestamp, Some(value), state, instance_id, rng);
-        vec![Effect::NewVertex(ValidVertex(Vertex::Unit(proposal_unit)))]
+        self.new_unit(panorama, timestamp, Some(value), state, instance_id, rng)
+            .into_iter()
+            .collect()
     }
 
     /// Returns whether the incoming message is a proposal that we need to send a confirmation for.
@@ -292,7 +351,11 @@ impl<C: Context> ActiveValidator<C> {
         true
     }
 
-    /// Returns a new unit with the given data, and the correct sequence number.
+    /// Returns an effect publishing a new unit with the given data, and the correct sequence
+    /// number, or requesting its signature if it can't be signed right away.
+    ///
+    /// Returns `None` if our previous unit is still waiting for its signature: the new unit would
+    /// have to cite it.
     fn new_unit(
         &mut self,
         mut panorama: Panorama<C>,
@@ -301,7 +364,14 @@ impl<C: Context> ActiveValidator<C> {
         state: &State<C>,
         instance_id: C::InstanceId,
         rng: &mut NodeRng,
-    ) -> SignedWireUnit<C> {
+    ) -> Option<Effect<C>> {
+        if self.unsigned_unit.is_some() {
+            warn!(
+                %timestamp,
+                "skipping unit; the previous one is still waiting for its signature"
+            );
+            return None;
+        }
         if let Some((prop_time, _)) = self.next_proposal.take() {
             warn!(
                 ?timestamp,
@@ -324,7 +394,20 @@ impl<C: Context> ActiveValidator<C> {
             round_exp: self.round_exp(state, timestamp),
             endorsed,
         };
-        SignedWireUnit::new(wunit, &self.secret, rng)
+        let hash = wunit.hash();
+        match self.secret.sign(&hash, rng) {
+            Some(signature) => {
+                let swunit = SignedWireUnit {
+                    wire_unit: wunit,
+                    signature,
+                };
+                Some(Effect::NewVertex(ValidVertex(Vertex::Unit(swunit))))
+            }
+            None => {
+                self.unsigned_unit = Some(wunit);
+                Some(Effect::RequestSignature(hash))
+            }
+        }
     }
 
     /// Returns a `ScheduleTimer` effect for the next time we need to be called.
@@ -412,14 +495,25 @@ impl<C: Context> ActiveValidator<C> {
                 .any(|(vidx, _)| state.is_faulty(vidx) && unit.new_hash_obs(state, vidx))
     }
 
-    /// Creates endorsement of the `vhash`.
-    fn endorse(&self, vhash: &C::Hash, rng: &mut NodeRng) -> Vertex<C> {
+    /// Returns an effect publishing an endorsement of the `vhash`, or requesting its signature if
+    /// it can't be signed right away.
+    fn endorse(&mut self, vhash: &C::Hash, rng: &mut NodeRng) -> Effect<C> {
         let endorsement = Endorsement::new(*vhash, self.vidx);
-        let signature = self.secret.sign(&endorsement.hash(), rng);
-        Vertex::Endorsements(Endorsements::new(vec![SignedEndorsement::new(
-            endorsement,
-            signature,
-        )]))
+        let hash = endorsement.hash();
+        match self.secret.sign(&hash, rng) {
+            Some(signature) => Self::endorsement_effect(endorsement, signature),
+            None => {
+                self.unsigned_endorsements.push(endorsement);
+                Effect::RequestSignature(hash)
+            }
+        }
+    }
+
+    /// Returns an effect publishing the signed endorsement.
+    fn endorsement_effect(endorsement: Endorsement<C>, signature: C::Signature) -> Effect<C> {
+        Effect::NewVertex(ValidVertex(Vertex::Endorsements(Endorsements::new(vec![
+            SignedEndorsement::new(endorsement, signature),
+        ]))))
     }
 }
 
@@ -525,4 +619,34 @@ mod tests {
         assert_eq!(Some(&prop_hash), fd.next_finalized(&state));
         Ok(())
     }
+
+    #[test]
+    fn unit_awaiting_signature() {
+        let state = State::new_test(&[Weight(3), Weight(4)], 0);
+        let mut rng = crate::new_rng();
+        let instance_id = 1u64;
+        let (mut bob_av, _) = ActiveValidator::new(BOB, TestSecret(1), 410.into(), &state);
+
+        // Bob creates his witness unit, but it is still waiting for its signature.
+        let mut effects = bob_av
+            .handle_timer(426.into(), &state, instance_id, &mut rng)
+            .into_iter();
+        assert_eq!(Some(Eff::ScheduleTimer(432.into())), effects.next());
+        let swunit = effects.next().unwrap().unwrap_unit();
+        bob_av.unsigned_unit = Some(swunit.wire_unit.clone());
+
+        // He can't create another unit before, as it would have to cite the unsigned one.
+        let panorama = state.panorama().clone();
+        let opt_effect = bob_av.new_unit(panorama, 427.into(), None, &state, instance_id, &mut rng);
+        assert_eq!(None, opt_effect);
+
+        // A signature of anything else is ignored.
+        let other_hash = swunit.hash().wrapping_add(1);
+        assert!(bob_av.on_signature(&other_hash, Some(0), &state).is_empty());
+
+        // Once the signature arrives, the unit is published.
+        let effects = bob_av.on_signature(&swunit.hash(), Some(swunit.signature), &state);
+        assert_eq!(swunit, unwrap_single(effects).unwrap_unit());
+        assert!(bob_av.unsigned_unit.is_none());
+    }
 }
@@ -44,6 +44,11 @@ impl<C: Context> FinalityDetector<C> {
         }
     }
 
+    /// Returns the most recent known finalized block.
+    pub(crate) fn last_finalized(&self) -> Option<&C::Hash> {
+        self.last_finalized.as_ref()
+    }
+
     /// Returns all blocks that have been finalized since the last call.
     // TODO: Verify the consensus instance ID?
     pub(crate) fn run<'a>(
@@ -71,7 +76,10 @@ impl<C: Context> FinalityDetector<C> {
             let faulty_iter = unit.panorama.enumerate().filter(|(_, obs)| obs.is_faulty());
 
             Some(FinalizedBlock {
-                value: block.value.clone(),
+                value: block
+                    .value
+                    .clone()
+                    .expect("payloads are only dropped after finalization"),
                 timestamp: unit.timestamp,
                 height: block.height,
                 rewards,
@@ -306,6 +306,28 @@ impl<C: Context> Highway<C> {
         }
     }
 
+    /// Returns the unit with the given hash as it was sent over the wire, unless it is unknown or
+    /// it is a block whose payload was dropped from memory.
+    pub(crate) fn wire_unit(&self, hash: &C::Hash) -> Option<SignedWireUnit<C>> {
+        self.state.wire_unit(hash, self.instance_id)
+    }
+
+    /// Drops the payload of the block with the given hash from memory.
+    ///
+    /// This must only be called for finalized blocks, and the unit should be kept elsewhere, so
+    /// that it can be restored if needed as a dependency or as evidence.
+    pub(crate) fn drop_block_value(&mut self, hash: &C::Hash) {
+        self.state.drop_block_value(hash);
+    }
+
+    /// Restores the payload of a block whose payload was dropped from memory, from its unit.
+    pub(crate) fn restore_block_value(&mut self, swunit: SignedWireUnit<C>) {
+        let hash = swunit.hash();
+        if let Some(value) = swunit.wire_unit.value {
+            self.state.restore_block_value(&hash, value);
+        }
+    }
+
     /// Returns a vertex that satisfies the dependency, if available.
     ///
     /// If we send a vertex to a peer who is missing a dependency, they will ask us for it. In that
@@ -399,6 +421,26 @@ impl<C: Context> Highway<C> {
         })
     }
 
+    /// Publishes the vertex whose signature was requested via `Effect::RequestSignature`, or drops
+    /// it if `opt_signature` is `None`, i.e. if it could not be signed.
+    pub(crate) fn on_signature(
+        &mut self,
+        hash: &C::Hash,
+        opt_signature: Option<C::Signature>,
+        timestamp: Timestamp,
+        rng: &mut NodeRng,
+    ) -> Vec<Effect<C>> {
+        self.map_active_validator(
+            |av, state, _| av.on_signature(hash, opt_signature, state),
+            timestamp,
+            rng,
+        )
+        .unwrap_or_else(|| {
+            debug!(%hash, "ignoring signature: validator has been deactivated");
+            vec![]
+        })
+    }
+
     pub(crate) fn validators(&self) -> &Validators<C::ValidatorId> {
         &self.validators
     }
@@ -475,7 +517,9 @@ impl<C: Context> Highway<C> {
                     result.extend(self.add_valid_vertex(vv.clone(), rng, timestamp))
                 }
                 Effect::WeEquivocated(_) => self.deactivate_validator(),
-                Effect::ScheduleTimer(_) | Effect::RequestNewBlock(_) => (),
+                Effect::ScheduleTimer(_)
+                | Effect::RequestNewBlock(_)
+                | Effect::RequestSignature(_) => (),
             }
         }
         result.extend(effects);
@@ -641,7 +685,7 @@ pub(crate) mod tests {
 
         // TODO: Also test the `missing_dependency` and `validate_vertex` steps.
 
-        let valid_signature = CAROL_SEC.sign(&wunit.hash(), &mut rng);
+        let valid_signature = CAROL_SEC.sign(&wunit.hash(), &mut rng).unwrap();
         let correct_signature_unit = SignedWireUnit {
             wire_unit: wunit,
             signature: valid_signature,
@@ -669,8 +713,8 @@ pub(crate) mod tests {
                             signer0: &TestSecret,
                             wunit1: &WireUnit<TestContext>,
                             signer1: &TestSecret| {
-            let swunit0 = SignedWireUnit::new(wunit0.clone(), signer0, &mut rng);
-            let swunit1 = SignedWireUnit::new(wunit1.clone(), signer1, &mut rng);
+            let swunit0 = SignedWireUnit::new(wunit0.clone(), signer0, &mut rng).unwrap();
+            let swunit1 = SignedWireUnit::new(wunit1.clone(), signer1, &mut rng).unwrap();
             let evidence = Evidence::Equivocation(swunit0, swunit1);
             let vertex = Vertex::Evidence(evidence);
             highway
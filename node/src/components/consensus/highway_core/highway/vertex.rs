@@ -115,16 +115,17 @@ pub(crate) struct SignedWireUnit<C: Context> {
 }
 
 impl<C: Context> SignedWireUnit<C> {
+    /// Signs the unit, or returns `None` if the signature has to be requested asynchronously.
     pub(crate) fn new(
         wire_unit: WireUnit<C>,
         secret_key: &C::ValidatorSecret,
         rng: &mut NodeRng,
-    ) -> Self {
-        let signature = secret_key.sign(&wire_unit.hash(), rng);
-        SignedWireUnit {
+    ) -> Option<Self> {
+        let signature = secret_key.sign(&wire_unit.hash(), rng)?;
+        Some(SignedWireUnit {
             wire_unit,
             signature,
-        }
+        })
     }
 
     pub(crate) fn hash(&self) -> C::Hash {
@@ -72,18 +72,20 @@ impl Debug for HighwayMessage {
 }
 
 impl HighwayMessage {
-    fn into_targeted(self, creator: ValidatorId) -> TargetedMessage<HighwayMessage> {
-        let create_msg = |hwm: HighwayMessage| Message::new(creator, hwm);
-
+    /// Returns the recipients of the message created by `creator`.
+    ///
+    /// Broadcast messages are addressed to each recipient separately, so that the delivery
+    /// strategy can schedule them independently.
+    fn recipients(&self, creator: ValidatorId, validator_ids: &[ValidatorId]) -> Vec<ValidatorId> {
         match self {
-            HighwayMessage::NewVertex(_) => {
-                TargetedMessage::new(create_msg(self), Target::AllExcept(creator))
-            }
+            HighwayMessage::NewVertex(_) => validator_ids
+                .iter()
+                .filter(|id| **id != creator)
+                .cloned()
+                .collect(),
             HighwayMessage::Timer(_)
             | HighwayMessage::RequestBlock(_)
-            | HighwayMessage::WeEquivocated(_) => {
-                TargetedMessage::new(create_msg(self), Target::SingleValidator(creator))
-            }
+            | HighwayMessage::WeEquivocated(_) => vec![creator],
         }
     }
 
@@ -101,6 +103,12 @@ impl From<Effect<TestContext>> for HighwayMessage {
             Effect::ScheduleTimer(t) => HighwayMessage::Timer(t),
             Effect::RequestNewBlock(block_context) => HighwayMessage::RequestBlock(block_context),
             Effect::WeEquivocated(evidence) => HighwayMessage::WeEquivocated(Box::new(evidence)),
+            Effect::RequestSignature(hash) => {
+                panic!(
+                    "test validators sign right away; unexpected request to sign {}",
+                    hash
+                )
+            }
         }
     }
 }
@@ -278,7 +286,8 @@ impl HighwayValidator {
                                 let mut wunit = swunit.wire_unit.clone();
                                 wunit.timestamp += 1.into();
                                 let secret = TestSecret(wunit.creator.0.into());
-                                let swunit2 = SignedWireUnit::new(wunit, &secret, rng);
+                                let swunit2 = SignedWireUnit::new(wunit, &secret, rng)
+                                    .expect("should sign unit");
                                 vec![
                                     msg,
                                     HighwayMessage::NewVertex(Box::new(Vertex::Unit(swunit2))),
@@ -350,9 +359,15 @@ where
 
         let messages = self.process_message(rng, recipient, message, delivery_time)?;
 
+        let validator_ids: Vec<ValidatorId> = self.virtual_net.validators_ids().cloned().collect();
         let targeted_messages = messages
             .into_iter()
-            .filter_map(|hwm| {
+            .flat_map(|hwm| {
+                hwm.recipients(recipient, &validator_ids)
+                    .into_iter()
+                    .map(move |target| (hwm.clone(), target))
+            })
+            .filter_map(|(hwm, target)| {
                 let delivery = self.delivery_time_strategy.gen_delay(
                     rng,
                     &hwm,
@@ -365,8 +380,10 @@ where
                         None
                     }
                     DeliverySchedule::AtInstant(timestamp) => {
-                        trace!("{:?} scheduled for {:?}", hwm, timestamp);
-                        let targeted = hwm.into_targeted(recipient);
+                        trace!("{:?} to {} scheduled for {:?}", hwm, target, timestamp);
+                        let message = Message::new(recipient, hwm);
+                        let targeted =
+                            TargetedMessage::new(message, Target::SingleValidator(target));
                         Some((targeted, timestamp))
                     }
                 }
@@ -634,6 +651,44 @@ where
         }
     }
 
+    /// Asserts the safety invariants that must hold in every schedule, as long as the faulty
+    /// validators' weight is below the fault tolerance threshold:
+    ///
+    /// * No two honest validators finalized conflicting values, i.e. each one's sequence of
+    ///   finalized values is a prefix of the other's.
+    /// * No honest validator has evidence against another honest validator.
+    fn assert_safety(&self) {
+        let honest = self
+            .virtual_net
+            .validators()
+            .filter(|node| node.validator().fault.is_none())
+            .collect_vec();
+        for (node0, node1) in honest.iter().tuple_combinations() {
+            let finalized0 = node0.finalized_values().collect_vec();
+            let finalized1 = node1.finalized_values().collect_vec();
+            let common = finalized0.len().min(finalized1.len());
+            assert_eq!(
+                finalized0[..common],
+                finalized1[..common],
+                "validators {} and {} finalized conflicting values",
+                node0.id,
+                node1.id
+            );
+        }
+        for node in &honest {
+            for accused in node.validator().highway().validators_with_evidence() {
+                assert!(
+                    self.virtual_net
+                        .validator(accused)
+                        .map_or(false, |accused| accused.validator().fault.is_some()),
+                    "validator {} has evidence against honest validator {}",
+                    node.id,
+                    accused
+                );
+            }
+        }
+    }
+
     /// Returns a `MutableHandle` on the `HighwayTestHarness` object
     /// that allows for manipulating internal state of the test state.
     fn mutable_handle(&mut self) -> MutableHandle<DS> {
@@ -728,6 +783,54 @@ impl DeliveryStrategy for InstantDeliveryNoDropping {
     }
 }
 
+/// An adversarial strategy for message delivery: Every vertex is delayed by a random amount of
+/// time, independently for each recipient, so that different validators see them in different
+/// orders. Some vertices are dropped entirely.
+///
+/// Timers and block requests are local to a validator, so they are always delivered on time.
+struct AdversarialDelivery {
+    /// The maximum delay of a vertex, in milliseconds.
+    max_delay: u64,
+    /// The percentage of vertices that are dropped.
+    drop_percent: u64,
+}
+
+impl AdversarialDelivery {
+    fn new(max_delay: u64, drop_percent: u64) -> Self {
+        assert!(max_delay > 0, "maximum delay must be positive");
+        assert!(drop_percent < 100, "cannot drop all vertices");
+        AdversarialDelivery {
+            max_delay,
+            drop_percent,
+        }
+    }
+}
+
+impl DeliveryStrategy for AdversarialDelivery {
+    fn gen_delay(
+        &mut self,
+        rng: &mut NodeRng,
+        message: &HighwayMessage,
+        distributon: &Distribution,
+        base_delivery_timestamp: Timestamp,
+    ) -> DeliverySchedule {
+        match message {
+            HighwayMessage::RequestBlock(bc) => DeliverySchedule::AtInstant(bc.timestamp()),
+            HighwayMessage::Timer(t) => DeliverySchedule::AtInstant(*t),
+            HighwayMessage::NewVertex(_) if rng.gen_range(0, 100) < self.drop_percent => {
+                DeliverySchedule::Drop
+            }
+            HighwayMessage::NewVertex(_) => {
+                let delay = distributon.gen_range_vec(rng, 1, self.max_delay + 1, 1)[0];
+                DeliverySchedule::AtInstant(base_delivery_timestamp + delay.into())
+            }
+            HighwayMessage::WeEquivocated(_) => {
+                DeliverySchedule::AtInstant(base_delivery_timestamp + 1.into())
+            }
+        }
+    }
+}
+
 impl HighwayTestHarnessBuilder<InstantDeliveryNoDropping> {
     fn new() -> Self {
         HighwayTestHarnessBuilder {
@@ -779,6 +882,31 @@ impl<DS: DeliveryStrategy> HighwayTestHarnessBuilder<DS> {
         self
     }
 
+    /// Sets the strategy for delaying and dropping messages.
+    fn delivery_strategy<DS2: DeliveryStrategy>(
+        self,
+        delivery_strategy: DS2,
+    ) -> HighwayTestHarnessBuilder<DS2> {
+        HighwayTestHarnessBuilder {
+            max_faulty_validators: self.max_faulty_validators,
+            faulty_percent: self.faulty_percent,
+            fault_type: self.fault_type,
+            ftt: self.ftt,
+            consensus_values_count: self.consensus_values_count,
+            delivery_distribution: self.delivery_distribution,
+            delivery_strategy,
+            weight_limits: self.weight_limits,
+            start_time: self.start_time,
+            weight_distribution: self.weight_distribution,
+            seed: self.seed,
+        }
+    }
+
+    fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
     fn build(self, rng: &mut NodeRng) -> Result<HighwayTestHarness<DS>, BuilderError> {
         let consensus_values = (0..self.consensus_values_count as u32)
             .map(|el| vec![el])
@@ -990,8 +1118,8 @@ impl ValidatorSecret for TestSecret {
     type Hash = HashWrapper;
     type Signature = SignatureWrapper;
 
-    fn sign(&self, data: &Self::Hash, _rng: &mut NodeRng) -> Self::Signature {
-        SignatureWrapper(data.0 + self.0)
+    fn sign(&self, data: &Self::Hash, _rng: &mut NodeRng) -> Option<Self::Signature> {
+        Some(SignatureWrapper(data.0 + self.0))
     }
 }
 
@@ -1022,9 +1150,11 @@ impl Context for TestContext {
 mod test_harness {
     use std::{collections::HashSet, fmt::Debug};
 
+    use rand::Rng;
+
     use super::{
-        crank_until, ConsensusValue, HighwayTestHarness, HighwayTestHarnessBuilder,
-        InstantDeliveryNoDropping, TestRunError,
+        crank_until, AdversarialDelivery, ConsensusValue, HighwayTestHarness,
+        HighwayTestHarnessBuilder, InstantDeliveryNoDropping, TestRunError, TEST_MIN_ROUND_EXP,
     };
     use crate::{
         components::consensus::tests::consensus_des_testing::{Fault, ValidatorId},
@@ -1221,4 +1351,57 @@ mod test_harness {
             "Nodes saw different set of equivocators.",
         );
     }
+
+    #[test]
+    fn safety_test_adversarial_schedules() {
+        let _ = logging::init_with_config(&LoggingConfig::new(LoggingFormat::Text, true, true));
+
+        // Each run uses a different network, fault type and schedule. They are all derived from
+        // the test RNG, which prints its seed if the test fails, so failures can be reproduced.
+        const RUNS: usize = 8;
+        const MAX_CRANKS: usize = 50_000;
+
+        let mut rng = crate::new_rng();
+        let cv_count = 5;
+
+        for _ in 0..RUNS {
+            // Delay vertices by up to three rounds of the minimum length, and drop up to 20%.
+            let max_delay = rng.gen_range(1, 3 << TEST_MIN_ROUND_EXP);
+            let drop_percent = rng.gen_range(0, 20);
+            let mut builder = HighwayTestHarnessBuilder::new()
+                .max_faulty_validators(3)
+                .consensus_values_count(cv_count)
+                .weight_limits(100, 150)
+                .seed(rng.gen());
+            builder = match rng.gen_range(0, 3) {
+                0 => builder,
+                1 => builder
+                    .faulty_weight_perc(rng.gen_range(1, 30))
+                    .fault_type(Fault::Mute),
+                _ => builder
+                    .faulty_weight_perc(rng.gen_range(1, 20))
+                    .fault_type(Fault::Equivocate),
+            };
+            let mut highway_test_harness = builder
+                .delivery_strategy(AdversarialDelivery::new(max_delay, drop_percent))
+                .build(&mut rng)
+                .ok()
+                .expect("Construction was successful");
+
+            for _ in 0..MAX_CRANKS {
+                if highway_test_harness
+                    .virtual_net
+                    .validators()
+                    .all(|v| v.finalized_count() >= cv_count as usize)
+                {
+                    break;
+                }
+                highway_test_harness
+                    .crank(&mut rng)
+                    .expect("test run should not fail");
+            }
+
+            highway_test_harness.assert_safety();
+        }
+    }
 }
@@ -12,7 +12,7 @@ pub(crate) use params::Params;
 use quanta::Clock;
 pub(crate) use weight::Weight;
 
-pub(super) use panorama::{Observation, Panorama};
+pub(crate) use panorama::{Observation, Panorama};
 pub(super) use unit::Unit;
 
 use std::{
@@ -487,14 +487,18 @@ impl<C: Context> State<C> {
         }
     }
 
+    /// Returns the unit with the given hash as it was sent over the wire, or `None` if it is
+    /// unknown or it is a block whose payload was dropped from memory.
     pub(crate) fn wire_unit(
         &self,
         hash: &C::Hash,
         instance_id: C::InstanceId,
     ) -> Option<SignedWireUnit<C>> {
         let unit = self.opt_unit(hash)?.clone();
-        let opt_block = self.opt_block(hash);
-        let value = opt_block.map(|block| block.value.clone());
+        let value = match self.opt_block(hash) {
+            None => None,
+            Some(block) => Some(block.value.clone()?),
+        };
         let endorsed = unit.claims_endorsed().cloned().collect();
         let wunit = WireUnit {
             panorama: unit.panorama.clone(),
@@ -512,6 +516,33 @@ impl<C: Context> State<C> {
         })
     }
 
+    /// Drops the payload of the block with the given hash from memory. The block's unit can't be
+    /// returned by `wire_unit` anymore until the payload is restored.
+    pub(crate) fn drop_block_value(&mut self, hash: &C::Hash) {
+        if let Some(block) = self.blocks.get_mut(hash) {
+            block.value = None;
+        }
+    }
+
+    /// Restores the payload of a block that was dropped from memory.
+    pub(crate) fn restore_block_value(&mut self, hash: &C::Hash, value: C::ConsensusValue) {
+        if let Some(block) = self.blocks.get_mut(hash) {
+            block.value = Some(value);
+        }
+    }
+
+    /// Returns the hash of the known unit that `wunit` equivocates with, if its creator is not
+    /// known to be faulty yet. Panics unless all dependencies of `wunit` are present.
+    pub(crate) fn equivocated_unit(&self, wunit: &WireUnit<C>) -> Option<&C::Hash> {
+        let obs0 = self.panoramas.get(wunit.creator);
+        match obs0 {
+            Observation::Correct(hash0) if obs0 != wunit.panorama.get(wunit.creator) => {
+                self.find_in_swimlane(hash0, wunit.seq_number)
+            }
+            _ => None,
+        }
+    }
+
     /// Returns the fork choice from `pan`'s view, or `None` if there are no blocks yet.
     ///
     /// The correct validators' latest units count as votes for the block they point to, as well as
@@ -12,7 +12,9 @@ where
     /// The total number of ancestors, i.e. the height in the blockchain.
     pub(crate) height: u64,
     /// The payload, e.g. a list of transactions.
-    pub(crate) value: C::ConsensusValue,
+    ///
+    /// This is `None` if the payload was moved out of memory after the block was finalized.
+    pub(crate) value: Option<C::ConsensusValue>,
     /// A skip list index of the block's ancestors.
     ///
     /// For every `p = 1 << i` that divides `height`, this contains an `i`-th entry pointing to the
@@ -38,7 +40,7 @@ impl<C: Context> Block<C> {
         }
         Block {
             height,
-            value,
+            value: Some(value),
             skip_idx,
         }
     }
@@ -51,7 +53,7 @@ impl<C: Context> Block<C> {
     fn initial(value: C::ConsensusValue) -> Block<C> {
         Block {
             height: 0,
-            value,
+            value: Some(value),
             skip_idx: vec![],
         }
     }
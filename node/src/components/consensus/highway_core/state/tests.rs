@@ -40,8 +40,8 @@ impl ValidatorSecret for TestSecret {
     type Hash = u64;
     type Signature = u64;
 
-    fn sign(&self, data: &Self::Hash, _rng: &mut NodeRng) -> Self::Signature {
-        data + u64::from(self.0)
+    fn sign(&self, data: &Self::Hash, _rng: &mut NodeRng) -> Option<Self::Signature> {
+        Some(data + u64::from(self.0))
     }
 }
 
@@ -167,12 +167,12 @@ fn add_unit() -> Result<(), AddUnitError<TestContext>> {
         round_exp: 4u8,
         endorsed: BTreeSet::new(),
     };
-    let unit = SignedWireUnit::new(wunit.clone(), &BOB_SEC, &mut rng);
+    let unit = SignedWireUnit::new(wunit.clone(), &BOB_SEC, &mut rng).unwrap();
     let opt_err = state.add_unit(unit).err().map(unit_err);
     assert_eq!(Some(UnitError::SequenceNumber), opt_err);
     // Still not valid: This would be the third unit in the first round.
     wunit.seq_number = 2;
-    let unit = SignedWireUnit::new(wunit, &BOB_SEC, &mut rng);
+    let unit = SignedWireUnit::new(wunit, &BOB_SEC, &mut rng).unwrap();
     let opt_err = state.add_unit(unit).err().map(unit_err);
     assert_eq!(Some(UnitError::ThreeUnitsInRound), opt_err);
 
@@ -70,7 +70,8 @@ macro_rules! add_unit {
             endorsed: vec![$($ends),*].into_iter().collect(),
         };
         let hash = wunit.hash();
-        let swunit = SignedWireUnit::new(wunit, &TestSecret(($creator).0), &mut $rng);
+        let swunit = SignedWireUnit::new(wunit, &TestSecret(($creator).0), &mut $rng)
+            .expect("should sign unit");
         $state.add_unit(swunit).map(|()| hash)
     }};
     ($state: ident, $rng: ident, $creator: expr, $time: expr, $round_exp: expr, $val: expr; $($obs:expr),*) => {{
@@ -96,7 +97,8 @@ macro_rules! add_unit {
             endorsed: $($ends.into()),*
         };
         let hash = wunit.hash();
-        let swunit = SignedWireUnit::new(wunit, &TestSecret(($creator).0), &mut $rng);
+        let swunit = SignedWireUnit::new(wunit, &TestSecret(($creator).0), &mut $rng)
+            .expect("should sign unit");
         $state.add_unit(swunit).map(|()| hash)
     }};
 }
@@ -111,7 +113,9 @@ macro_rules! endorse {
     };
     ($state: ident, $rng: ident, $creator: expr, $vote: expr) => {
         let endorsement: Endorsement<TestContext> = Endorsement::new($vote, ($creator));
-        let signature = TestSecret(($creator).0).sign(&endorsement.hash(), &mut $rng);
+        let signature = TestSecret(($creator).0)
+            .sign(&endorsement.hash(), &mut $rng)
+            .expect("should sign endorsement");
         let signed_endorsement = SignedEndorsement::new(endorsement, signature);
         let endorsements: Endorsements<TestContext> =
             Endorsements::new(vec![signed_endorsement].into_iter());
@@ -1,11 +1,13 @@
 mod round_success_meter;
 #[cfg(test)]
 mod tests;
+mod unit_log;
 
 use std::{
     any::Any,
     collections::{BTreeMap, HashMap, HashSet},
     fmt::Debug,
+    iter,
 };
 
 use casper_execution_engine::shared::motes::Motes;
@@ -15,7 +17,7 @@ use num_traits::AsPrimitive;
 use serde::{Deserialize, Serialize};
 use tracing::{info, trace, warn};
 
-use self::round_success_meter::RoundSuccessMeter;
+use self::{round_success_meter::RoundSuccessMeter, unit_log::UnitLog};
 use casper_types::{auction::BLOCK_REWARD, U512};
 
 use crate::{
@@ -27,12 +29,13 @@ use crate::{
                 active_validator::Effect as AvEffect,
                 finality_detector::FinalityDetector,
                 highway::{
-                    Dependency, GetDepOutcome, Highway, Params, PreValidatedVertex, ValidVertex,
-                    Vertex,
+                    Dependency, GetDepOutcome, Highway, Params, PreValidatedVertex, SignedWireUnit,
+                    ValidVertex, Vertex,
                 },
                 validators::Validators,
             },
             traits::{Context, NodeIdT},
+            UnitLogConfig,
         },
     },
     types::Timestamp,
@@ -55,6 +58,9 @@ where
     vertices_to_be_added_later: BTreeMap<Timestamp, Vec<(I, PreValidatedVertex<C>)>>,
     /// A tracker for whether we are keeping up with the current round exponent or not.
     round_success_meter: RoundSuccessMeter<C>,
+    /// The log of finalized units whose payloads were dropped from memory, if enabled.
+    #[data_size(skip)]
+    unit_log: Option<UnitLog<C>>,
 }
 
 impl<I: NodeIdT, C: Context + 'static> HighwayProtocol<I, C> {
@@ -67,6 +73,7 @@ impl<I: NodeIdT, C: Context + 'static> HighwayProtocol<I, C> {
         prev_cp: Option<&dyn ConsensusProtocol<I, C>>,
         start_time: Timestamp,
         seed: u64,
+        unit_log_config: Option<UnitLogConfig>,
     ) -> Box<dyn ConsensusProtocol<I, C>> {
         let sum_stakes: Motes = validator_stakes.iter().map(|(_, stake)| *stake).sum();
         assert!(
@@ -115,6 +122,16 @@ impl<I: NodeIdT, C: Context + 'static> HighwayProtocol<I, C> {
             start_time + highway_config.era_duration,
         );
 
+        let unit_log = unit_log_config.and_then(|config| {
+            match UnitLog::create(config.path.clone(), config.compaction_interval) {
+                Ok(unit_log) => Some(unit_log),
+                Err(error) => {
+                    warn!(%error, path = %config.path.display(), "failed to create unit log");
+                    None
+                }
+            }
+        });
+
         let min_round_exp = params.min_round_exp();
         let max_round_exp = params.max_round_exp();
         let round_exp = params.init_round_exp();
@@ -131,6 +148,7 @@ impl<I: NodeIdT, C: Context + 'static> HighwayProtocol<I, C> {
                 max_round_exp,
                 start_timestamp,
             ),
+            unit_log,
         })
     }
 
@@ -157,6 +175,7 @@ impl<I: NodeIdT, C: Context + 'static> HighwayProtocol<I, C> {
             AvEffect::WeEquivocated(evidence) => {
                 panic!("this validator equivocated: {:?}", evidence);
             }
+            AvEffect::RequestSignature(hash) => vec![ProtocolOutcome::RequestSignature(hash)],
         }
     }
 
@@ -179,11 +198,60 @@ impl<I: NodeIdT, C: Context + 'static> HighwayProtocol<I, C> {
         results
     }
 
-    fn detect_finality(&mut self) -> impl Iterator<Item = ProtocolOutcome<I, C>> + '_ {
-        self.finality_detector
+    fn detect_finality(&mut self) -> Vec<ProtocolOutcome<I, C>> {
+        let outcomes: Vec<_> = self
+            .finality_detector
             .run(&self.highway)
             .expect("too many faulty validators")
             .map(ProtocolOutcome::FinalizedBlock)
+            .collect();
+        self.compact(outcomes.len() as u64);
+        outcomes
+    }
+
+    /// Moves the finalized blocks' units into the unit log and drops their payloads from memory,
+    /// if `finalized_count` more finalized blocks make that due.
+    fn compact(&mut self, finalized_count: u64) {
+        let unit_log = match self.unit_log.as_mut() {
+            Some(unit_log) => unit_log,
+            None => return,
+        };
+        if !unit_log.should_compact(finalized_count) {
+            return;
+        }
+        let last_finalized = match self.finality_detector.last_finalized() {
+            Some(last_finalized) => *last_finalized,
+            None => return,
+        };
+        let state = self.highway.state();
+        // Ancestors of blocks that were already logged have been logged, too.
+        let hashes: Vec<C::Hash> = iter::once(&last_finalized)
+            .chain(state.ancestor_hashes(&last_finalized))
+            .take_while(|hash| !unit_log.contains(hash))
+            .cloned()
+            .collect();
+        for hash in hashes {
+            let swunit = match self.highway.wire_unit(&hash) {
+                Some(swunit) => swunit,
+                None => continue,
+            };
+            if let Err(error) = unit_log.append(&swunit) {
+                warn!(%error, ?unit_log, "failed to log unit; not compacting");
+                return;
+            }
+            self.highway.drop_block_value(&hash);
+        }
+    }
+
+    /// Reads the unit with the given hash from the unit log, if it was logged.
+    fn read_logged_unit(&self, hash: &C::Hash) -> Option<SignedWireUnit<C>> {
+        match self.unit_log.as_ref()?.read(hash) {
+            Ok(swunit) => swunit,
+            Err(error) => {
+                warn!(%error, ?hash, "failed to read unit from unit log");
+                None
+            }
+        }
     }
 
     /// Store a (pre-validated) vertex which will be added later.  This creates a timer to be sent
@@ -333,6 +401,17 @@ impl<I: NodeIdT, C: Context + 'static> HighwayProtocol<I, C> {
         // round has finished, we now have all the vertices from that round in the state, and no
         // newer ones.
         self.calculate_round_exponent(&vv);
+        // If the vertex is an equivocation, the conflicting unit is needed as evidence: Restore
+        // its payload if it was dropped.
+        if let Some(swunit) = vv.inner().signed_wire_unit() {
+            let state = self.highway.state();
+            let opt_hash = state.equivocated_unit(&swunit.wire_unit).cloned();
+            if let Some(hash) = opt_hash.filter(|hash| self.highway.wire_unit(hash).is_none()) {
+                if let Some(logged) = self.read_logged_unit(&hash) {
+                    self.highway.restore_block_value(logged);
+                }
+            }
+        }
         let av_effects = self.highway.add_valid_vertex(vv.clone(), rng, now);
         let mut results = self.process_av_effects(av_effects);
         let msg = HighwayMessage::NewVertex(vv.into());
@@ -487,8 +566,25 @@ where
             }
             Ok(HighwayMessage::RequestDependency(dep)) => match self.highway.get_dependency(&dep) {
                 GetDepOutcome::None => {
-                    info!(?dep, ?sender, "requested dependency doesn't exist");
-                    vec![]
+                    let logged = match &dep {
+                        Dependency::Unit(hash) => self.read_logged_unit(hash),
+                        _ => None,
+                    };
+                    match logged {
+                        Some(swunit) => {
+                            let msg = HighwayMessage::NewVertex(Vertex::Unit(swunit));
+                            let serialized_msg =
+                                bincode::serialize(&msg).expect("should serialize message");
+                            vec![ProtocolOutcome::CreatedTargetedMessage(
+                                serialized_msg,
+                                sender,
+                            )]
+                        }
+                        None => {
+                            info!(?dep, ?sender, "requested dependency doesn't exist");
+                            vec![]
+                        }
+                    }
                 }
                 GetDepOutcome::Evidence(vid) => vec![ProtocolOutcome::SendEvidence(sender, vid)],
                 GetDepOutcome::Vertex(vv) => {
@@ -569,6 +665,18 @@ where
         }
     }
 
+    fn handle_signature(
+        &mut self,
+        hash: C::Hash,
+        opt_signature: Option<C::Signature>,
+        rng: &mut NodeRng,
+    ) -> Vec<ProtocolOutcome<I, C>> {
+        let effects = self
+            .highway
+            .on_signature(&hash, opt_signature, Timestamp::now(), rng);
+        self.process_av_effects(effects)
+    }
+
     fn activate_validator(
         &mut self,
         our_id: C::ValidatorId,
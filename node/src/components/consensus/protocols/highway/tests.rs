@@ -57,6 +57,7 @@ fn test() {
             None,
             0.into(),
             0,
+            None,
         )
     };
 
@@ -0,0 +1,196 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug, Formatter},
+    fs::{self, File, OpenOptions},
+    io::{self, Read, Seek, SeekFrom, Write},
+    path::PathBuf,
+};
+
+use tracing::warn;
+
+use crate::components::consensus::{highway_core::highway::SignedWireUnit, traits::Context};
+
+/// Length of the prefix holding the size of each record.
+const RECORD_LEN_SIZE: u64 = 4;
+
+/// An append-only file of Highway units, each stored as its little-endian `u32` length followed by
+/// the bincode-encoded `SignedWireUnit`.
+///
+/// Once blocks are finalized, their units are written to the log and their payloads are dropped
+/// from the in-memory protocol state. They are read back from the log when a peer requests them,
+/// or when they are needed as evidence against an equivocator.
+///
+/// The file only lives as long as the protocol instance, and is removed when the log is dropped.
+pub(crate) struct UnitLog<C: Context> {
+    path: PathBuf,
+    file: File,
+    /// The offset of each logged unit's record, by hash.
+    offsets: HashMap<C::Hash, u64>,
+    /// The length of the file, i.e. the offset of the next record.
+    len: u64,
+    /// The number of blocks to be finalized between two compactions.
+    compaction_interval: u64,
+    /// The number of blocks finalized since the last compaction.
+    finalized_since_compaction: u64,
+}
+
+impl<C: Context> UnitLog<C> {
+    /// Creates an empty log at `path`, replacing any existing file.
+    pub(crate) fn create(path: PathBuf, compaction_interval: u64) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)?;
+        Ok(UnitLog {
+            path,
+            file,
+            offsets: HashMap::new(),
+            len: 0,
+            compaction_interval,
+            finalized_since_compaction: 0,
+        })
+    }
+
+    /// Returns whether the unit with the given hash is in the log.
+    pub(crate) fn contains(&self, hash: &C::Hash) -> bool {
+        self.offsets.contains_key(hash)
+    }
+
+    /// Appends the unit to the log, unless it is already present.
+    pub(crate) fn append(&mut self, swunit: &SignedWireUnit<C>) -> io::Result<()> {
+        let hash = swunit.hash();
+        if self.contains(&hash) {
+            return Ok(());
+        }
+        let bytes = bincode::serialize(swunit)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        let record_len = bytes.len() as u32;
+        let mut file = &self.file;
+        file.seek(SeekFrom::Start(self.len))?;
+        file.write_all(&record_len.to_le_bytes())?;
+        file.write_all(&bytes)?;
+        self.offsets.insert(hash, self.len);
+        self.len += RECORD_LEN_SIZE + u64::from(record_len);
+        Ok(())
+    }
+
+    /// Reads the unit with the given hash from the log, or returns `None` if it isn't present.
+    pub(crate) fn read(&self, hash: &C::Hash) -> io::Result<Option<SignedWireUnit<C>>> {
+        let offset = match self.offsets.get(hash) {
+            Some(offset) => *offset,
+            None => return Ok(None),
+        };
+        let mut file = &self.file;
+        file.seek(SeekFrom::Start(offset))?;
+        let mut len_bytes = [0u8; RECORD_LEN_SIZE as usize];
+        file.read_exact(&mut len_bytes)?;
+        let mut bytes = vec![0u8; u32::from_le_bytes(len_bytes) as usize];
+        file.read_exact(&mut bytes)?;
+        let swunit = bincode::deserialize(&bytes)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        Ok(Some(swunit))
+    }
+
+    /// Records that `count` more blocks were finalized, and returns whether the protocol state
+    /// should be compacted now.
+    pub(crate) fn should_compact(&mut self, count: u64) -> bool {
+        self.finalized_since_compaction += count;
+        if count == 0 || self.finalized_since_compaction < self.compaction_interval {
+            return false;
+        }
+        self.finalized_since_compaction = 0;
+        true
+    }
+}
+
+impl<C: Context> Debug for UnitLog<C> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("UnitLog")
+            .field("path", &self.path)
+            .field("units", &self.offsets.len())
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl<C: Context> Drop for UnitLog<C> {
+    fn drop(&mut self) {
+        if let Err(error) = fs::remove_file(&self.path) {
+            warn!(%error, path = %self.path.display(), "failed to remove unit log");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+    use crate::{
+        components::consensus::highway_core::{
+            highway::WireUnit,
+            state::{tests::*, Panorama},
+        },
+        types::Timestamp,
+    };
+
+    fn signed_wire_unit(value: Option<u32>, seq_number: u64) -> SignedWireUnit<TestContext> {
+        let wire_unit = WireUnit {
+            panorama: Panorama::new(WEIGHTS.len()),
+            creator: ALICE,
+            instance_id: 1u64,
+            value,
+            seq_number,
+            timestamp: Timestamp::zero(),
+            round_exp: 4,
+            endorsed: BTreeSet::new(),
+        };
+        let signature = wire_unit.hash();
+        SignedWireUnit {
+            wire_unit,
+            signature,
+        }
+    }
+
+    #[test]
+    fn should_read_appended_units() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("units").join("era-1.units");
+        let mut unit_log = UnitLog::<TestContext>::create(path.clone(), 2).unwrap();
+
+        let swunit0 = signed_wire_unit(Some(7), 0);
+        let swunit1 = signed_wire_unit(None, 1);
+        unit_log.append(&swunit0).unwrap();
+        unit_log.append(&swunit1).unwrap();
+        unit_log.append(&swunit0).unwrap();
+        assert!(unit_log.contains(&swunit0.hash()));
+        assert_eq!(unit_log.offsets.len(), 2);
+
+        assert_eq!(unit_log.read(&swunit1.hash()).unwrap(), Some(swunit1));
+        assert_eq!(unit_log.read(&swunit0.hash()).unwrap(), Some(swunit0));
+        let unknown = signed_wire_unit(Some(8), 0);
+        assert_eq!(unit_log.read(&unknown.hash()).unwrap(), None);
+
+        drop(unit_log);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn should_compact_at_interval() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("era-1.units");
+        let mut unit_log = UnitLog::<TestContext>::create(path, 3).unwrap();
+
+        assert!(!unit_log.should_compact(2));
+        assert!(!unit_log.should_compact(0));
+        assert!(unit_log.should_compact(1));
+        assert!(!unit_log.should_compact(1));
+        assert!(unit_log.should_compact(5));
+    }
+}
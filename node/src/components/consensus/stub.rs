@@ -0,0 +1,83 @@
+//! A stand-in for the consensus component, used by nodes running in sidecar mode.
+//!
+//! A sidecar node follows the linear chain and serves queries, but never participates in
+//! consensus, so it has no need for the eras, protocol state or signing keys of the
+//! `EraSupervisor`.  The stub merely acknowledges each executed linear chain block, so that
+//! synchronization can proceed, and declines to sign anything.
+
+use std::{convert::Infallible, marker::PhantomData};
+
+use datasize::DataSize;
+use tracing::trace;
+
+use super::Event;
+use crate::{
+    components::Component,
+    effect::{
+        announcements::ConsensusAnnouncement, requests::ConsensusRequest, EffectBuilder, EffectExt,
+        Effects,
+    },
+    NodeRng,
+};
+
+/// A consensus component which doesn't participate in consensus.
+#[derive(DataSize, Debug)]
+pub(crate) struct ConsensusStub<I> {
+    #[data_size(skip)]
+    _node_id: PhantomData<I>,
+}
+
+impl<I> ConsensusStub<I> {
+    pub(crate) fn new() -> Self {
+        ConsensusStub {
+            _node_id: PhantomData,
+        }
+    }
+}
+
+impl<I, REv> Component<REv> for ConsensusStub<I>
+where
+    I: Send + 'static,
+    REv: From<ConsensusAnnouncement> + Send,
+{
+    type Event = Event<I>;
+    type ConstructionError = Infallible;
+
+    fn handle_event(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        _rng: &mut NodeRng,
+        event: Self::Event,
+    ) -> Effects<Self::Event> {
+        match event {
+            Event::ConsensusRequest(ConsensusRequest::HandleLinearBlock(
+                block_header,
+                responder,
+            )) => {
+                let mut effects = responder.respond(None).ignore();
+                effects.extend(
+                    effect_builder
+                        .announce_block_handled(*block_header)
+                        .ignore(),
+                );
+                effects
+            }
+            Event::ConsensusRequest(ConsensusRequest::SignStatus(_, responder)) => {
+                responder.respond(None).ignore()
+            }
+            Event::ConsensusRequest(ConsensusRequest::SignDeployReceipt(_, _, responder)) => {
+                responder.respond(None).ignore()
+            }
+            Event::MessageReceived { .. }
+            | Event::Timer { .. }
+            | Event::NewProtoBlock { .. }
+            | Event::ResolveValidity { .. }
+            | Event::CreateNewEra { .. }
+            | Event::Shutdown
+            | Event::ClockDrift { .. } => {
+                trace!("consensus stub ignoring event");
+                Effects::new()
+            }
+        }
+    }
+}
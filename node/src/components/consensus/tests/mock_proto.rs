@@ -16,7 +16,7 @@ use crate::{
                 BlockContext, ConsensusProtocol, FinalizedBlock, ProtocolOutcome,
             },
             traits::Context,
-            ConsensusMessage, EraId, Event,
+            ConsensusMessage, EraId, Event, UnitLogConfig,
         },
     },
     types::Timestamp,
@@ -112,6 +112,7 @@ where
         _prev_cp: Option<&dyn ConsensusProtocol<NodeId, C>>,
         _start_time: Timestamp,
         _seed: u64,
+        _unit_log_config: Option<UnitLogConfig>,
     ) -> Box<dyn ConsensusProtocol<NodeId, C>> {
         Box::new(MockProto {
             instance_id,
@@ -235,6 +236,15 @@ where
         vec![]
     }
 
+    fn handle_signature(
+        &mut self,
+        _hash: C::Hash,
+        _opt_signature: Option<C::Signature>,
+        _rng: &mut NodeRng,
+    ) -> Vec<ProtocolOutcome<NodeId, C>> {
+        todo!("implement handle_signature")
+    }
+
     fn activate_validator(
         &mut self,
         our_id: C::ValidatorId,
@@ -38,7 +38,9 @@ pub(crate) trait ValidatorSecret {
 
     type Signature: Eq + PartialEq + Clone + Debug + Hash + Serialize + DeserializeOwned;
 
-    fn sign(&self, hash: &Self::Hash, rng: &mut NodeRng) -> Self::Signature;
+    /// Signs the hash, or returns `None` if the key is not at hand and the signature has to be
+    /// requested asynchronously instead, via `active_validator::Effect::RequestSignature`.
+    fn sign(&self, hash: &Self::Hash, rng: &mut NodeRng) -> Option<Self::Signature>;
 }
 
 /// The collection of types the user can choose for cryptography, IDs, transactions, etc.
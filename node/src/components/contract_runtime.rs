@@ -1,11 +1,14 @@
 //! Contract Runtime component.
 mod config;
+mod simulation;
 mod types;
 
 pub use config::Config;
+pub use simulation::{simulate_deploy, SimulationError, SimulationReport, SimulationState};
 pub use types::{EraValidatorsRequest, ValidatorWeightsByEraIdRequest};
 
 use std::{
+    collections::VecDeque,
     fmt::{self, Debug, Display, Formatter},
     sync::Arc,
     time::Instant,
@@ -13,6 +16,7 @@ use std::{
 
 use datasize::DataSize;
 use derive_more::From;
+use itertools::Itertools;
 use lmdb::DatabaseFlags;
 use prometheus::{self, Histogram, HistogramOpts, Registry};
 use serde::Serialize;
@@ -22,21 +26,30 @@ use tracing::trace;
 
 use casper_execution_engine::{
     core::engine_state::{
-        genesis::GenesisResult, EngineConfig, EngineState, Error, GetEraValidatorsError,
+        deploy_item::DeployItem, execute_request::ExecuteRequest, genesis::GenesisResult,
+        EngineConfig, EngineState, Error, GetEraValidatorsError,
+    },
+    shared::{
+        newtypes::{Blake2bHash, CorrelationId},
+        stored_value::StoredValue,
     },
-    shared::newtypes::CorrelationId,
     storage::{
-        error::lmdb::Error as StorageLmdbError, global_state::lmdb::LmdbGlobalState,
+        error::lmdb::Error as StorageLmdbError,
+        global_state::lmdb::LmdbGlobalState,
         protocol_data_store::lmdb::LmdbProtocolDataStore,
-        transaction_source::lmdb::LmdbEnvironment, trie_store::lmdb::LmdbTrieStore,
+        store::Store,
+        transaction_source::{lmdb::LmdbEnvironment, Readable, Transaction, TransactionSource},
+        trie::Trie,
+        trie_store::{cache::TrieCache, lmdb::LmdbTrieStore},
     },
 };
-use casper_types::{auction::ValidatorWeights, ProtocolVersion};
+use casper_types::{auction::ValidatorWeights, bytesrepr::ToBytes, Key, ProtocolVersion};
 
 use crate::{
-    components::Component,
-    crypto::hash,
+    components::{consensus::EraId, Component},
+    crypto::hash::{self, Digest},
     effect::{requests::ContractRuntimeRequest, EffectBuilder, EffectExt, Effects},
+    types::json_compatibility::{ExecutionResult, GasProfile},
     utils::WithDir,
     Chainspec, NodeRng, StorageConfig,
 };
@@ -46,6 +59,10 @@ use crate::{
 pub struct ContractRuntime {
     engine_state: Arc<EngineState<LmdbGlobalState>>,
     metrics: Arc<ContractRuntimeMetrics>,
+    #[data_size(skip)]
+    environment: Arc<LmdbEnvironment>,
+    #[data_size(skip)]
+    trie_store: Arc<LmdbTrieStore>,
 }
 
 impl Debug for ContractRuntime {
@@ -81,6 +98,9 @@ pub struct ContractRuntimeMetrics {
     get_validator_weights: Histogram,
 }
 
+/// Maximum number of tries visited when verifying that a state root hash resolves in global state.
+const MAX_TRIES_TO_VERIFY: usize = 1_000;
+
 /// Value of upper bound of histogram.
 const EXPONENTIAL_BUCKET_START: f64 = 0.01;
 /// Multiplier of previous upper bound for next bound.
@@ -144,6 +164,22 @@ impl ContractRuntimeMetrics {
     }
 }
 
+/// Reads the weights of the validators of the requested era directly from the global state.
+///
+/// Returns `None` if the era's validators are not recorded there.
+fn get_validator_weights(
+    engine_state: &EngineState<LmdbGlobalState>,
+    correlation_id: CorrelationId,
+    request: ValidatorWeightsByEraIdRequest,
+) -> Result<Option<ValidatorWeights>, GetEraValidatorsError> {
+    let era_id = request.era_id().into();
+    match engine_state.get_era_validators(correlation_id, request.into()) {
+        Ok(mut era_validators) => Ok(era_validators.remove(&era_id)),
+        Err(GetEraValidatorsError::EraValidatorsMissing) => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
 impl<REv> Component<REv> for ContractRuntime
 where
     REv: From<Event> + Send,
@@ -272,6 +308,36 @@ where
                 }
                 .ignore()
             }
+            Event::Request(ContractRuntimeRequest::QueryBatch { batch, responder }) => {
+                trace!(?batch, "query batch");
+                let engine_state = Arc::clone(&self.engine_state);
+                async move {
+                    let correlation_id = CorrelationId::new();
+                    let result = task::spawn_blocking(move || {
+                        engine_state.run_query_batch(correlation_id, batch)
+                    })
+                    .await
+                    .expect("should run");
+                    trace!(?result, "query batch result");
+                    responder.respond(result).await
+                }
+                .ignore()
+            }
+            Event::Request(ContractRuntimeRequest::GetKeysWithPrefix { request, responder }) => {
+                trace!(?request, "get keys with prefix");
+                let engine_state = Arc::clone(&self.engine_state);
+                async move {
+                    let correlation_id = CorrelationId::new();
+                    let result = task::spawn_blocking(move || {
+                        engine_state.get_keys_with_prefix(correlation_id, request)
+                    })
+                    .await
+                    .expect("should run");
+                    trace!(?result, "get keys with prefix result");
+                    responder.respond(result).await
+                }
+                .ignore()
+            }
             Event::Request(ContractRuntimeRequest::GetBalance {
                 balance_request,
                 responder,
@@ -308,7 +374,9 @@ where
                         let start = Instant::now();
                         let era_validators =
                             engine_state.get_era_validators(correlation_id, request.into());
-                        metrics.get_balance.observe(start.elapsed().as_secs_f64());
+                        metrics
+                            .get_validator_weights
+                            .observe(start.elapsed().as_secs_f64());
                         era_validators
                     })
                     .await
@@ -329,20 +397,11 @@ where
                     let correlation_id = CorrelationId::new();
                     let result = task::spawn_blocking(move || {
                         let start = Instant::now();
-                        let era_id = request.era_id().into();
-                        let era_validators =
-                            engine_state.get_era_validators(correlation_id, request.into());
-                        let ret: Result<Option<ValidatorWeights>, GetEraValidatorsError> =
-                            match era_validators {
-                                Ok(era_validators) => {
-                                    let validator_weights = era_validators.get(&era_id).cloned();
-                                    Ok(validator_weights)
-                                }
-                                Err(GetEraValidatorsError::EraValidatorsMissing) => Ok(None),
-                                Err(error) => Err(error),
-                            };
-                        metrics.get_balance.observe(start.elapsed().as_secs_f64());
-                        ret
+                        let result = get_validator_weights(&engine_state, correlation_id, request);
+                        metrics
+                            .get_validator_weights
+                            .observe(start.elapsed().as_secs_f64());
+                        result
                     })
                     .await
                     .expect("should run");
@@ -351,6 +410,61 @@ where
                 }
                 .ignore()
             }
+            Event::Request(ContractRuntimeRequest::EstimateDeployCost {
+                state_root_hash,
+                block_time,
+                deploy,
+                profile_gas,
+                responder,
+            }) => {
+                trace!(%state_root_hash, ?deploy, profile_gas, "estimate deploy cost");
+                let engine_state = Arc::clone(&self.engine_state);
+                async move {
+                    let correlation_id = CorrelationId::new();
+                    let proposer = (*deploy.header().account()).into();
+                    let execute_request = ExecuteRequest::new(
+                        state_root_hash.into(),
+                        block_time.millis(),
+                        vec![Ok(DeployItem::from(*deploy))],
+                        ProtocolVersion::V1_0_0,
+                        proposer,
+                    );
+                    // The deploy is executed against a scratch layer, so even were its effects
+                    // committed, they would never reach global state.
+                    let result = task::spawn_blocking(move || {
+                        let engine_state = engine_state.scratch();
+                        if profile_gas {
+                            engine_state
+                                .run_execute_with_gas_profile(correlation_id, execute_request)
+                                .map(|execution_results| {
+                                    let (ee_execution_result, ee_gas_profile) = execution_results
+                                        .into_iter()
+                                        .exactly_one()
+                                        .expect("should only be one exec result");
+                                    (
+                                        ExecutionResult::from(&ee_execution_result),
+                                        Some(GasProfile::from(&ee_gas_profile)),
+                                    )
+                                })
+                        } else {
+                            engine_state
+                                .run_execute(correlation_id, execute_request)
+                                .map(|execution_results| {
+                                    let ee_execution_result = execution_results
+                                        .into_iter()
+                                        .exactly_one()
+                                        .expect("should only be one exec result");
+                                    (ExecutionResult::from(&ee_execution_result), None)
+                                })
+                        }
+                    })
+                    .await
+                    .expect("should run");
+                    trace!(?result, "estimate deploy cost result");
+                    responder.respond(result).await
+                }
+                .ignore()
+            }
             Event::Request(ContractRuntimeRequest::Step {
                 step_request,
                 responder,
@@ -373,10 +487,99 @@ where
                 }
                 .ignore()
             }
+            Event::Request(ContractRuntimeRequest::ReadTries {
+                trie_keys,
+                responder,
+            }) => {
+                trace!(count = trie_keys.len(), "read tries request");
+                let environment = Arc::clone(&self.environment);
+                let trie_store = Arc::clone(&self.trie_store);
+                async move {
+                    let result = task::spawn_blocking(move || {
+                        read_tries(&environment, &trie_store, &trie_keys)
+                    })
+                    .await
+                    .expect("should run");
+                    responder.respond(result).await
+                }
+                .ignore()
+            }
+            Event::Request(ContractRuntimeRequest::FindMissingTries {
+                trie_keys,
+                responder,
+            }) => {
+                trace!(count = trie_keys.len(), "find missing tries request");
+                let environment = Arc::clone(&self.environment);
+                let trie_store = Arc::clone(&self.trie_store);
+                async move {
+                    let result = task::spawn_blocking(move || {
+                        read_tries(&environment, &trie_store, &trie_keys).map(|tries| {
+                            trie_keys
+                                .into_iter()
+                                .zip(tries)
+                                .filter(|(_, maybe_trie)| maybe_trie.is_none())
+                                .map(|(trie_key, _)| trie_key)
+                                .collect()
+                        })
+                    })
+                    .await
+                    .expect("should run");
+                    responder.respond(result).await
+                }
+                .ignore()
+            }
+            Event::Request(ContractRuntimeRequest::WriteTries { tries, responder }) => {
+                trace!(count = tries.len(), "write tries request");
+                let environment = Arc::clone(&self.environment);
+                let trie_store = Arc::clone(&self.trie_store);
+                async move {
+                    let result = task::spawn_blocking(move || {
+                        write_tries(&environment, &trie_store, &tries)
+                    })
+                    .await
+                    .expect("should run");
+                    trace!(?result, "write tries response");
+                    responder.respond(result).await
+                }
+                .ignore()
+            }
         }
     }
 }
 
+/// Reads the serialized tries under `trie_keys`, `None` for each trie absent from global state.
+fn read_tries(
+    environment: &LmdbEnvironment,
+    trie_store: &LmdbTrieStore,
+    trie_keys: &[Digest],
+) -> Result<Vec<Option<Vec<u8>>>, StorageLmdbError> {
+    let handle = <LmdbTrieStore as Store<Blake2bHash, Trie<Key, StoredValue>>>::handle(trie_store);
+    let txn = environment.create_read_txn()?;
+    let mut tries = Vec::with_capacity(trie_keys.len());
+    for trie_key in trie_keys {
+        let key_bytes = Blake2bHash::from(*trie_key).to_bytes()?;
+        tries.push(txn.read(handle, &key_bytes)?);
+    }
+    txn.commit()?;
+    Ok(tries)
+}
+
+/// Writes `tries` to global state in a single transaction.
+///
+/// The caller is responsible for having checked that each trie hashes to its key.
+fn write_tries(
+    environment: &LmdbEnvironment,
+    trie_store: &LmdbTrieStore,
+    tries: &[(Digest, Trie<Key, StoredValue>)],
+) -> Result<(), StorageLmdbError> {
+    let mut txn = environment.create_read_write_txn()?;
+    for (trie_key, trie) in tries {
+        trie_store.put(&mut txn, &Blake2bHash::from(*trie_key), trie)?;
+    }
+    txn.commit()?;
+    Ok(())
+}
+
 /// Error returned from mis-configuring the contract runtime component.
 #[derive(Debug, Error)]
 pub enum ConfigError {
@@ -401,11 +604,12 @@ impl ContractRuntime {
             contract_runtime_config.max_readers(),
         )?);
 
-        let trie_store = Arc::new(LmdbTrieStore::new(
-            &environment,
-            None,
-            DatabaseFlags::empty(),
-        )?);
+        let mut trie_store = LmdbTrieStore::new(&environment, None, DatabaseFlags::empty())?;
+        let max_trie_cache_size = contract_runtime_config.max_trie_cache_size();
+        if max_trie_cache_size > 0 {
+            trie_store = trie_store.with_cache(Arc::new(TrieCache::new(max_trie_cache_size)));
+        }
+        let trie_store = Arc::new(trie_store);
 
         let protocol_data_store = Arc::new(LmdbProtocolDataStore::new(
             &environment,
@@ -413,19 +617,92 @@ impl ContractRuntime {
             DatabaseFlags::empty(),
         )?);
 
-        let global_state = LmdbGlobalState::empty(environment, trie_store, protocol_data_store)?;
+        let global_state = LmdbGlobalState::empty(
+            Arc::clone(&environment),
+            Arc::clone(&trie_store),
+            protocol_data_store,
+        )?;
         let engine_config = EngineConfig::new()
             .with_use_system_contracts(contract_runtime_config.use_system_contracts());
 
         let engine_state = Arc::new(EngineState::new(global_state, engine_config));
 
         let metrics = Arc::new(ContractRuntimeMetrics::new(registry)?);
+        casper_execution_engine::shared::metrics::register(registry)?;
         Ok(ContractRuntime {
             engine_state,
             metrics,
+            environment,
+            trie_store,
         })
     }
 
+    /// Returns the LMDB environment holding global state.
+    pub(crate) fn lmdb_environment(&self) -> Arc<LmdbEnvironment> {
+        Arc::clone(&self.environment)
+    }
+
+    /// Returns the execution engine, for use outside of a reactor.
+    pub(crate) fn engine_state(&self) -> &EngineState<LmdbGlobalState> {
+        &self.engine_state
+    }
+
+    /// Reads the weights of the validators of `era_id` as recorded in the global state under
+    /// `state_root_hash`, bypassing the event queue.
+    ///
+    /// Returns `None` if the era's validators are not recorded there.
+    pub(crate) fn read_validator_weights(
+        &self,
+        state_root_hash: Digest,
+        era_id: EraId,
+    ) -> Result<Option<ValidatorWeights>, GetEraValidatorsError> {
+        let request = ValidatorWeightsByEraIdRequest::new(
+            state_root_hash.into(),
+            era_id,
+            ProtocolVersion::V1_0_0,
+        );
+        get_validator_weights(&self.engine_state, CorrelationId::new(), request)
+    }
+
+    /// Checks that the trie under `state_root_hash` is present in global state.
+    ///
+    /// The trie is walked breadth-first from its root, visiting at most `MAX_TRIES_TO_VERIFY`
+    /// tries, so the root and the upper levels of the trie are always checked without having to
+    /// read the whole of global state.  Returns the hash of the first trie found to be missing.
+    pub(crate) fn find_missing_trie(
+        &self,
+        state_root_hash: Digest,
+    ) -> Result<Option<Digest>, StorageLmdbError> {
+        let txn = self.environment.create_read_txn()?;
+        let mut pending = VecDeque::new();
+        pending.push_back(Blake2bHash::from(state_root_hash));
+        let mut visited = 0;
+        let mut missing = None;
+        while let Some(trie_hash) = pending.pop_front() {
+            if visited == MAX_TRIES_TO_VERIFY {
+                break;
+            }
+            visited += 1;
+            let maybe_trie: Option<Trie<Key, StoredValue>> =
+                self.trie_store.get(&txn, &trie_hash)?;
+            match maybe_trie {
+                None => {
+                    missing = Some(trie_hash.into());
+                    break;
+                }
+                Some(Trie::Leaf { .. }) => (),
+                Some(Trie::Node { pointer_block }) => pending.extend(
+                    pointer_block
+                        .to_indexed_pointers()
+                        .map(|(_, pointer)| *pointer.hash()),
+                ),
+                Some(Trie::Extension { pointer, .. }) => pending.push_back(*pointer.hash()),
+            }
+        }
+        txn.commit()?;
+        Ok(missing)
+    }
+
     /// Commits a genesis using a chainspec
     fn commit_genesis(&self, chainspec: Box<Chainspec>) -> Result<GenesisResult, Error> {
         let correlation_id = CorrelationId::new();
@@ -447,3 +724,28 @@ impl ContractRuntime {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn should_find_missing_trie_of_unknown_state_root_hash() {
+        let (storage_config, tempdir) = StorageConfig::default_for_tests();
+        fs::create_dir_all(&storage_config.path).unwrap();
+        let contract_runtime = ContractRuntime::new(
+            WithDir::new(tempdir.path(), storage_config),
+            &Config::default(),
+            &Registry::new(),
+        )
+        .unwrap();
+
+        let state_root_hash = Digest::from([1; Digest::LENGTH]);
+        assert_eq!(
+            contract_runtime.find_missing_trie(state_root_hash).unwrap(),
+            Some(state_root_hash)
+        );
+    }
+}
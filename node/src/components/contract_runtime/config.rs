@@ -6,6 +6,7 @@ use casper_execution_engine::shared::utils;
 const DEFAULT_USE_SYSTEM_CONTRACTS: bool = false;
 const DEFAULT_MAX_GLOBAL_STATE_SIZE: usize = 805_306_368_000; // 750 GiB
 const DEFAULT_MAX_READERS: u32 = 512;
+const DEFAULT_MAX_TRIE_CACHE_SIZE: usize = 134_217_728; // 128 MiB
 
 /// Contract runtime configuration.
 #[derive(Clone, Copy, DataSize, Debug, Deserialize, Serialize)]
@@ -24,6 +25,11 @@ pub struct Config {
     ///
     /// Defaults to 512.
     max_readers: Option<u32>,
+    /// The maximum total size in bytes of the tries held in the in-memory trie cache, which is
+    /// shared by block execution and global state prefetching.  A value of 0 disables the cache.
+    ///
+    /// Defaults to 134,217,728 == 128 MiB.
+    max_trie_cache_size: Option<usize>,
 }
 
 impl Config {
@@ -43,6 +49,11 @@ impl Config {
     pub(crate) fn max_readers(&self) -> u32 {
         self.max_readers.unwrap_or(DEFAULT_MAX_READERS)
     }
+
+    pub(crate) fn max_trie_cache_size(&self) -> usize {
+        self.max_trie_cache_size
+            .unwrap_or(DEFAULT_MAX_TRIE_CACHE_SIZE)
+    }
 }
 
 impl Default for Config {
@@ -51,6 +62,7 @@ impl Default for Config {
             use_system_contracts: Some(DEFAULT_USE_SYSTEM_CONTRACTS),
             max_global_state_size: Some(DEFAULT_MAX_GLOBAL_STATE_SIZE),
             max_readers: Some(DEFAULT_MAX_READERS),
+            max_trie_cache_size: Some(DEFAULT_MAX_TRIE_CACHE_SIZE),
         }
     }
 }
@@ -0,0 +1,166 @@
+//! Execution of a deploy outside of any node, used to iterate quickly on contracts.
+
+use std::path::PathBuf;
+
+use itertools::Itertools;
+use prometheus::Registry;
+use serde::Serialize;
+use thiserror::Error;
+
+use casper_execution_engine::{
+    core::engine_state::{
+        deploy_item::DeployItem, execute_request::ExecuteRequest, genesis::GenesisResult,
+    },
+    shared::newtypes::CorrelationId,
+};
+use casper_types::ProtocolVersion;
+
+use super::{Config, ConfigError, ContractRuntime};
+use crate::{
+    crypto::hash::Digest,
+    types::{
+        json_compatibility::{ExecutionResult, ExecutionTrace},
+        Deploy, Timestamp,
+    },
+    utils::WithDir,
+    Chainspec, StorageConfig,
+};
+
+/// Error simulating the execution of a deploy.
+#[derive(Debug, Error)]
+pub enum SimulationError {
+    /// Error creating a temporary directory for a fresh global state.
+    #[error("could not create temporary directory: {0}")]
+    TempDir(#[from] std::io::Error),
+
+    /// Error opening global state.
+    #[error("contract runtime config error: {0}")]
+    ContractRuntime(#[from] ConfigError),
+
+    /// The execution engine failed to commit genesis.
+    #[error("failed to commit genesis: {0}")]
+    Genesis(String),
+
+    /// The execution engine failed to execute the deploy.
+    #[error("failed to execute deploy: {0}")]
+    Execution(String),
+}
+
+/// The global state against which a deploy is simulated.
+#[derive(Debug)]
+pub enum SimulationState {
+    /// A fresh global state, resulting from committing genesis as specified by the chainspec.
+    Genesis(Box<Chainspec>),
+    /// An existing global state, e.g. a copy of a node's storage directory.
+    Snapshot {
+        /// The directory holding the global state.
+        storage_path: PathBuf,
+        /// The state root hash to execute the deploy on top of.
+        state_root_hash: Digest,
+    },
+}
+
+/// The outcome of simulating the execution of a deploy, traced down to its host function calls.
+#[derive(Debug, Serialize)]
+pub struct SimulationReport {
+    /// The state root hash the deploy was executed on top of.
+    pub pre_state_root_hash: Digest,
+    /// The result of the execution, including its effects and cost.
+    pub execution_result: ExecutionResult,
+    /// The host function calls made during the execution.
+    pub trace: ExecutionTrace,
+}
+
+/// Executes the deploy against the given global state, as if it were included in a block with the
+/// given timestamp, and returns the result.
+///
+/// The deploy is executed by an engine instance outside of any reactor, exactly as a node would
+/// execute it, but against a scratch layer over the global state, so nothing is written to it.  A
+/// snapshot of a node's global state must not be in use by a running node.
+pub fn simulate_deploy(
+    state: SimulationState,
+    deploy: Deploy,
+    block_time: Timestamp,
+) -> Result<SimulationReport, SimulationError> {
+    let registry = Registry::new();
+    // A fresh global state lives in a temporary directory, which is removed once dropped.
+    let (contract_runtime, pre_state_root_hash, _maybe_tempdir) = match state {
+        SimulationState::Genesis(chainspec) => {
+            let tempdir = tempfile::tempdir()?;
+            let storage_config = StorageConfig::with_path(tempdir.path().to_path_buf());
+            let contract_runtime = ContractRuntime::new(
+                WithDir::new(tempdir.path(), storage_config),
+                &Config::default(),
+                &registry,
+            )?;
+            let post_state_hash = match contract_runtime.commit_genesis(chainspec) {
+                Ok(GenesisResult::Success {
+                    post_state_hash, ..
+                }) => post_state_hash,
+                Ok(genesis_result) => {
+                    return Err(SimulationError::Genesis(genesis_result.to_string()))
+                }
+                Err(error) => return Err(SimulationError::Genesis(format!("{:?}", error))),
+            };
+            (contract_runtime, post_state_hash.into(), Some(tempdir))
+        }
+        SimulationState::Snapshot {
+            storage_path,
+            state_root_hash,
+        } => {
+            let storage_config = StorageConfig::with_path(storage_path);
+            let contract_runtime = ContractRuntime::new(
+                WithDir::new(".", storage_config),
+                &Config::default(),
+                &registry,
+            )?;
+            (contract_runtime, state_root_hash, None)
+        }
+    };
+
+    let proposer = (*deploy.header().account()).into();
+    let execute_request = ExecuteRequest::new(
+        pre_state_root_hash.into(),
+        block_time.millis(),
+        vec![Ok(DeployItem::from(deploy))],
+        ProtocolVersion::V1_0_0,
+        proposer,
+    );
+    let (ee_execution_result, ee_trace) = contract_runtime
+        .engine_state()
+        .scratch()
+        .run_execute_with_trace(CorrelationId::new(), execute_request)
+        .map_err(|error| SimulationError::Execution(format!("{:?}", error)))?
+        .into_iter()
+        .exactly_one()
+        .map_err(|_| {
+            SimulationError::Execution("expected exactly one execution result".to_string())
+        })?;
+
+    Ok(SimulationReport {
+        pre_state_root_hash,
+        execution_result: ExecutionResult::from(&ee_execution_result),
+        trace: ExecutionTrace::from(&ee_trace),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::testing::TestRng;
+
+    #[test]
+    fn should_not_succeed_on_unknown_state_root_hash() {
+        let mut rng = TestRng::new();
+        let (storage_config, _tempdir) = StorageConfig::default_for_tests();
+        fs::create_dir_all(&storage_config.path).unwrap();
+        let state = SimulationState::Snapshot {
+            storage_path: storage_config.path,
+            state_root_hash: Digest::from([1; Digest::LENGTH]),
+        };
+        let result = simulate_deploy(state, Deploy::random(&mut rng), Timestamp::now());
+        assert!(!matches!(result, Ok(report) if report.execution_result.is_success()));
+    }
+}
@@ -1,23 +1,35 @@
+mod era_gate;
 mod event;
+mod precheck;
+mod wasm_check;
 // mod tests;
 
 use std::{collections::HashMap, convert::Infallible, fmt::Debug};
 
 use semver::Version;
-use tracing::{debug, error, warn};
+use thiserror::Error;
+use tracing::{debug, error, info, warn};
+
+use casper_execution_engine::shared::wasm_config::WasmConfig;
 
 use crate::{
-    components::{chainspec_loader::Chainspec, Component},
+    components::{block_proposer::BufferFull, chainspec_loader::Chainspec, Component},
     effect::{
         announcements::DeployAcceptorAnnouncement, requests::StorageRequest, EffectBuilder,
         EffectExt, Effects,
     },
-    types::{Deploy, NodeId},
-    utils::Source,
+    types::{Deploy, DeployHash, NodeId},
+    utils::{Source, VerificationPool},
     NodeRng,
 };
 
+pub use era_gate::EraGateError;
+pub(crate) use era_gate::{check as check_era_gate, EraContext};
 pub use event::Event;
+pub(crate) use precheck::check as precheck;
+pub use precheck::DeployViolation;
+pub(crate) use wasm_check::check as check_wasm;
+pub use wasm_check::WasmCheckError;
 
 use super::chainspec_loader::DeployConfig;
 
@@ -32,10 +44,54 @@ impl<REv> ReactorEventT for REv where
 {
 }
 
+/// The reason a deploy submitted by a client was refused.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum SubmitDeployError {
+    /// The block proposer's buffer of pending deploys is full.
+    #[error("{0}")]
+    BufferFull(BufferFull),
+    /// The deploy is unlikely to be executed as intended due to its timing relative to the current
+    /// era.
+    #[error(transparent)]
+    EraGate(#[from] EraGateError),
+    /// The deploy could never be executed successfully due to its Wasm module bytes.
+    #[error(transparent)]
+    Wasm(#[from] WasmCheckError),
+    /// Deploy acceptance is paused due to low free disk space.
+    #[error("node paused due to low disk space, not accepting deploys")]
+    Paused,
+    /// The node runs in sidecar mode, serving queries only.
+    #[error("node runs in sidecar mode, not accepting deploys")]
+    Sidecar,
+    /// The submission's idempotency key was already used to submit a different deploy.
+    #[error("idempotency key already used to submit {deploy_hash}")]
+    IdempotencyKeyReused {
+        /// The hash of the deploy submitted with the key.
+        deploy_hash: DeployHash,
+    },
+}
+
+/// How a deploy submitted by a client was accepted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeployAcceptance {
+    /// The deploy was accepted by this submission.
+    New,
+    /// The deploy had already been accepted by an earlier submission with the same idempotency
+    /// key, e.g. one whose response the client didn't receive before timing out.
+    Replayed,
+}
+
+impl From<BufferFull> for SubmitDeployError {
+    fn from(buffer_full: BufferFull) -> Self {
+        SubmitDeployError::BufferFull(buffer_full)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct DeployAcceptorConfig {
     chain_name: String,
     deploy_config: DeployConfig,
+    wasm_config: WasmConfig,
 }
 
 impl From<Chainspec> for DeployAcceptorConfig {
@@ -43,6 +99,7 @@ impl From<Chainspec> for DeployAcceptorConfig {
         DeployAcceptorConfig {
             chain_name: c.genesis.name,
             deploy_config: c.genesis.deploy_config,
+            wasm_config: c.genesis.wasm_config,
         }
     }
 }
@@ -51,16 +108,23 @@ impl From<Chainspec> for DeployAcceptorConfig {
 /// received by this node, regardless of whether they were provided by a peer or a client.
 ///
 /// It validates a new `Deploy` as far as possible, stores it if valid, then announces the newly-
-/// accepted `Deploy`.
+/// accepted `Deploy`.  While paused, e.g. due to low free disk space, new `Deploy`s are dropped.
+///
+/// Validation, which includes verifying the signatures of the `Deploy`'s approvals, runs on the
+/// signature verification pool rather than on the reactor's threads.
 #[derive(Debug)]
 pub(crate) struct DeployAcceptor {
     cached_deploy_configs: HashMap<Version, DeployAcceptorConfig>,
+    paused: bool,
+    verification_pool: VerificationPool,
 }
 
 impl DeployAcceptor {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(verification_pool: VerificationPool) -> Self {
         DeployAcceptor {
             cached_deploy_configs: HashMap::new(),
+            paused: false,
+            verification_pool,
         }
     }
 
@@ -71,6 +135,10 @@ impl DeployAcceptor {
         deploy: Box<Deploy>,
         source: Source<NodeId>,
     ) -> Effects<Event> {
+        if self.paused {
+            debug!(deploy_hash = %deploy.id(), %source, "deploy acceptance paused, dropping deploy");
+            return Effects::new();
+        }
         // TODO - where to get version from?
         let chainspec_version = Version::new(1, 0, 0);
         let cached_config = self.cached_deploy_configs.get(&chainspec_version).cloned();
@@ -96,17 +164,34 @@ impl DeployAcceptor {
         }
     }
 
-    fn validate<REv: ReactorEventT>(
+    fn validate(
         &mut self,
-        effect_builder: EffectBuilder<REv>,
         deploy: Box<Deploy>,
         source: Source<NodeId>,
         deploy_config: DeployAcceptorConfig,
     ) -> Effects<Event> {
-        let mut cloned_deploy = deploy.clone();
-        if is_valid(&mut cloned_deploy, deploy_config) {
+        self.verification_pool
+            .run(move || {
+                let is_valid = is_valid(&deploy, deploy_config);
+                (deploy, is_valid)
+            })
+            .event(move |(deploy, is_valid)| Event::ValidationResult {
+                deploy,
+                source,
+                is_valid,
+            })
+    }
+
+    fn handle_validation_result<REv: ReactorEventT>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        deploy: Box<Deploy>,
+        source: Source<NodeId>,
+        is_valid: bool,
+    ) -> Effects<Event> {
+        if is_valid {
             effect_builder
-                .put_deploy_to_storage(cloned_deploy)
+                .put_deploy_to_storage(deploy.clone())
                 .event(move |is_new| Event::PutToStorageResult {
                     deploy,
                     source,
@@ -168,51 +253,48 @@ impl<REv: ReactorEventT> Component<REv> for DeployAcceptor {
                     // Update chainspec cache.
                     self.cached_deploy_configs
                         .insert(chainspec_version, deploy_config.clone());
-                    self.validate(effect_builder, deploy, source, deploy_config)
+                    self.validate(deploy, source, deploy_config)
                 }
                 None => self.failed_to_get_chainspec(deploy, source, chainspec_version),
             },
+            Event::ValidationResult {
+                deploy,
+                source,
+                is_valid,
+            } => self.handle_validation_result(effect_builder, deploy, source, is_valid),
             Event::PutToStorageResult {
                 deploy,
                 source,
                 is_new,
             } => self.handle_put_to_storage(effect_builder, deploy, source, is_new),
+            Event::Pause => {
+                info!("pausing deploy acceptance");
+                self.paused = true;
+                Effects::new()
+            }
+            Event::Resume => {
+                info!("resuming deploy acceptance");
+                self.paused = false;
+                Effects::new()
+            }
         }
     }
 }
 
-fn is_valid(deploy: &mut Deploy, config: DeployAcceptorConfig) -> bool {
-    if deploy.header().chain_name() != config.chain_name {
+fn is_valid(deploy: &Deploy, config: DeployAcceptorConfig) -> bool {
+    let violations = precheck(
+        deploy,
+        &config.chain_name,
+        &config.deploy_config,
+        &config.wasm_config,
+    );
+    for violation in &violations {
         warn!(
             deploy_hash = %deploy.id(),
             deploy_header = %deploy.header(),
-            chain_name = %config.chain_name,
-            "invalid chain identifier"
+            %violation,
+            "invalid deploy"
         );
-        return false;
     }
-
-    if deploy.header().dependencies().len() > config.deploy_config.max_dependencies as usize {
-        warn!(
-            deploy_hash = %deploy.id(),
-            deploy_header = %deploy.header(),
-            max_dependencies = %config.deploy_config.max_dependencies,
-            "deploy dependency ceiling exceeded"
-        );
-        return false;
-    }
-
-    if deploy.header().ttl() > config.deploy_config.max_ttl {
-        warn!(
-            deploy_hash = %deploy.id(),
-            deploy_header = %deploy.header(),
-            max_ttl = %config.deploy_config.max_ttl,
-            "deploy ttl excessive"
-        );
-        return false;
-    }
-
-    // TODO - check if there is more that can be validated here.
-
-    deploy.is_valid()
+    violations.is_empty()
 }
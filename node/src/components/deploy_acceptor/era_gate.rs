@@ -0,0 +1,248 @@
+//! Gating of deploys submitted by clients which would be accepted, but are unlikely to be executed
+//! as intended because of their timing relative to the current era.
+//!
+//! Two kinds of deploys are refused:
+//!   * deploys which expire before they could be included in a block, even if proposed right away
+//!   * calls to the auction entry points whose effect depends on the era in which they are
+//!     executed, if submitted so close to the expected end of the era that they are likely to be
+//!     executed only after the era transition
+
+use thiserror::Error;
+
+use casper_execution_engine::core::engine_state::executable_deploy_item::ExecutableDeployItem;
+use casper_types::{
+    auction::{METHOD_ADD_BID, METHOD_DELEGATE, METHOD_UNDELEGATE, METHOD_WITHDRAW_BID},
+    ContractHash,
+};
+
+use crate::{
+    components::chainspec_loader::Chainspec,
+    types::{Block, Deploy, TimeDiff, Timestamp},
+};
+
+/// The auction entry points whose effect depends on the era in which they are executed.
+const ERA_BOUNDARY_ENTRY_POINTS: [&str; 4] = [
+    METHOD_ADD_BID,
+    METHOD_WITHDRAW_BID,
+    METHOD_DELEGATE,
+    METHOD_UNDELEGATE,
+];
+
+/// The number of rounds of minimum length before the expected end of an era within which calls
+/// to the auction's era boundary entry points are refused.
+const ERA_END_MARGIN_ROUNDS: u64 = 4;
+
+/// The reason a deploy was refused due to its timing relative to the current era.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum EraGateError {
+    /// The deploy expires before it can be executed.
+    #[error(
+        "deploy expires at {expires}, before it can be executed at {earliest_execution} at the \
+         earliest"
+    )]
+    ExpiresTooSoon {
+        /// The time at which the deploy expires.
+        expires: Timestamp,
+        /// The earliest time at which the deploy can be executed.
+        earliest_execution: Timestamp,
+    },
+    /// The deploy calls an auction entry point taking effect at era boundaries too close to the
+    /// expected end of the current era.
+    #[error(
+        "deploy calls the auction entry point '{entry_point}' too close to the end of the current \
+         era, expected at {era_end}; resubmit it after the era transition"
+    )]
+    BidNearEraEnd {
+        /// The auction entry point called.
+        entry_point: String,
+        /// The expected end of the current era.
+        era_end: Timestamp,
+    },
+}
+
+/// What is known about the current era when a deploy is submitted.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct EraContext {
+    /// The expected end of the current era, or `None` if eras are defined by height only.
+    pub(crate) era_end: Option<Timestamp>,
+    /// The length of the shortest round, i.e. the least time it takes to include a deploy in a
+    /// block.
+    pub(crate) min_round_length: TimeDiff,
+    /// The hash of the auction contract, if known.
+    pub(crate) auction_contract_hash: Option<ContractHash>,
+}
+
+impl EraContext {
+    /// Returns the context of the era following `maybe_highest_switch_block`, or of the first era
+    /// if there is no switch block yet.
+    pub(crate) fn new(
+        chainspec: &Chainspec,
+        maybe_highest_switch_block: Option<&Block>,
+        auction_contract_hash: Option<ContractHash>,
+    ) -> Self {
+        let highway_config = &chainspec.genesis.highway_config;
+        let era_start = maybe_highest_switch_block
+            .map(|switch_block| switch_block.header().timestamp())
+            .unwrap_or(highway_config.genesis_era_start_timestamp);
+        let era_end = if highway_config.era_duration.millis() > 0 {
+            Some(era_start + highway_config.era_duration)
+        } else {
+            None
+        };
+        EraContext {
+            era_end,
+            min_round_length: TimeDiff::from(1u64 << highway_config.minimum_round_exponent),
+            auction_contract_hash,
+        }
+    }
+
+    /// Returns whether `session` calls one of the auction's era boundary entry points, and if so
+    /// which one.
+    fn era_boundary_entry_point<'a>(&self, session: &'a ExecutableDeployItem) -> Option<&'a str> {
+        let auction_contract_hash = self.auction_contract_hash?;
+        match session {
+            ExecutableDeployItem::StoredContractByHash {
+                hash, entry_point, ..
+            } if *hash == auction_contract_hash
+                && ERA_BOUNDARY_ENTRY_POINTS.contains(&entry_point.as_str()) =>
+            {
+                Some(entry_point)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Checks whether `deploy`, submitted at `now`, is likely to be executed as intended.
+pub(crate) fn check(
+    deploy: &Deploy,
+    now: Timestamp,
+    context: &EraContext,
+) -> Result<(), EraGateError> {
+    let earliest_execution = deploy.header().timestamp().max(now) + context.min_round_length;
+    let expires = deploy.header().expires();
+    if expires < earliest_execution {
+        return Err(EraGateError::ExpiresTooSoon {
+            expires,
+            earliest_execution,
+        });
+    }
+
+    if let (Some(era_end), Some(entry_point)) = (
+        context.era_end,
+        context.era_boundary_entry_point(deploy.session()),
+    ) {
+        let margin = context.min_round_length * ERA_END_MARGIN_ROUNDS;
+        if earliest_execution + margin > era_end {
+            return Err(EraGateError::BidNearEraEnd {
+                entry_point: entry_point.to_string(),
+                era_end,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{bytesrepr::ToBytes, RuntimeArgs};
+
+    use super::*;
+    use crate::{crypto::asymmetric_key::SecretKey, testing::TestRng};
+
+    const AUCTION_CONTRACT_HASH: ContractHash = [7; 32];
+
+    fn deploy(
+        rng: &mut TestRng,
+        timestamp: u64,
+        ttl: u64,
+        session: ExecutableDeployItem,
+    ) -> Deploy {
+        let secret_key = SecretKey::random(rng);
+        let payment = ExecutableDeployItem::ModuleBytes {
+            module_bytes: vec![],
+            args: RuntimeArgs::new().to_bytes().unwrap(),
+        };
+        Deploy::new(
+            Timestamp::from(timestamp),
+            TimeDiff::from(ttl),
+            1,
+            vec![],
+            "chain".to_string(),
+            payment,
+            session,
+            &secret_key,
+            rng,
+        )
+    }
+
+    fn add_bid() -> ExecutableDeployItem {
+        ExecutableDeployItem::StoredContractByHash {
+            hash: AUCTION_CONTRACT_HASH,
+            entry_point: METHOD_ADD_BID.to_string(),
+            args: RuntimeArgs::new().to_bytes().unwrap(),
+        }
+    }
+
+    fn context(era_end: u64) -> EraContext {
+        EraContext {
+            era_end: Some(Timestamp::from(era_end)),
+            min_round_length: TimeDiff::from(100),
+            auction_contract_hash: Some(AUCTION_CONTRACT_HASH),
+        }
+    }
+
+    #[test]
+    fn should_refuse_deploy_expiring_before_execution() {
+        let mut rng = crate::new_rng();
+        let short_lived = deploy(&mut rng, 1_000, 50, add_bid());
+
+        let error = check(&short_lived, Timestamp::from(1_000), &context(100_000)).unwrap_err();
+        assert_eq!(
+            error,
+            EraGateError::ExpiresTooSoon {
+                expires: Timestamp::from(1_050),
+                earliest_execution: Timestamp::from(1_100),
+            }
+        );
+
+        // a deploy living for exactly one round can still be executed, unless submitted late
+        let one_round = deploy(&mut rng, 1_000, 100, add_bid());
+        assert!(check(&one_round, Timestamp::from(1_000), &context(100_000)).is_ok());
+        assert!(check(&one_round, Timestamp::from(1_001), &context(100_000)).is_err());
+    }
+
+    #[test]
+    fn should_refuse_bids_near_era_end() {
+        let mut rng = crate::new_rng();
+        let bid = deploy(&mut rng, 1_000, 10_000, add_bid());
+
+        // the earliest execution is at 1_100, followed by a margin of four rounds
+        assert!(check(&bid, Timestamp::from(1_000), &context(1_500)).is_ok());
+        let error = check(&bid, Timestamp::from(1_000), &context(1_499)).unwrap_err();
+        assert_eq!(
+            error,
+            EraGateError::BidNearEraEnd {
+                entry_point: METHOD_ADD_BID.to_string(),
+                era_end: Timestamp::from(1_499),
+            }
+        );
+
+        // calls to other contracts are unaffected
+        let session = ExecutableDeployItem::StoredContractByHash {
+            hash: [8; 32],
+            entry_point: METHOD_ADD_BID.to_string(),
+            args: RuntimeArgs::new().to_bytes().unwrap(),
+        };
+        let other = deploy(&mut rng, 1_000, 10_000, session);
+        assert!(check(&other, Timestamp::from(1_000), &context(1_499)).is_ok());
+
+        // as are bids if eras are defined by height only
+        let height_only = EraContext {
+            era_end: None,
+            ..context(1_499)
+        };
+        assert!(check(&bid, Timestamp::from(1_000), &height_only).is_ok());
+    }
+}
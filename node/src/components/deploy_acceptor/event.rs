@@ -20,12 +20,22 @@ pub enum Event {
         chainspec_version: Version,
         maybe_deploy_config: Box<Option<DeployAcceptorConfig>>,
     },
+    /// The result of validating a `Deploy` on the signature verification pool.
+    ValidationResult {
+        deploy: Box<Deploy>,
+        source: Source<NodeId>,
+        is_valid: bool,
+    },
     /// The result of the `DeployAcceptor` putting a `Deploy` to the storage component.
     PutToStorageResult {
         deploy: Box<Deploy>,
         source: Source<NodeId>,
         is_new: bool,
     },
+    /// Deploy acceptance should be paused until resumed, e.g. due to low free disk space.
+    Pause,
+    /// Paused deploy acceptance should be resumed.
+    Resume,
 }
 
 impl Display for Event {
@@ -49,6 +59,15 @@ impl Display for Event {
                     )
                 }
             }
+            Event::ValidationResult {
+                deploy, is_valid, ..
+            } => {
+                if *is_valid {
+                    write!(formatter, "validated {}", deploy.id())
+                } else {
+                    write!(formatter, "found {} invalid", deploy.id())
+                }
+            }
             Event::PutToStorageResult { deploy, is_new, .. } => {
                 if *is_new {
                     write!(formatter, "put new {} to storage", deploy.id())
@@ -56,6 +75,8 @@ impl Display for Event {
                     write!(formatter, "had already stored {}", deploy.id())
                 }
             }
+            Event::Pause => write!(formatter, "pause"),
+            Event::Resume => write!(formatter, "resume"),
         }
     }
 }
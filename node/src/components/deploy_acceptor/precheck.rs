@@ -0,0 +1,202 @@
+//! The complete set of checks the deploy acceptor applies to a deploy, reporting every violation
+//! rather than stopping at the first, so that clients can validate a deploy before it is signed or
+//! submitted.
+//!
+//! A deploy is refused if
+//!   * its chain name differs from the node's
+//!   * it has more dependencies or a longer TTL than the chainspec allows
+//!   * it uses the standard payment without giving an `"amount"` argument
+//!   * it carries no approvals, or its hashes or approvals are invalid
+//!   * it fails the static checks of its size and Wasm module bytes
+//!
+//! The era gate is not checked here, as it depends on the time and the current era.
+
+use thiserror::Error;
+
+use casper_execution_engine::{
+    core::engine_state::executable_deploy_item::ExecutableDeployItem,
+    shared::wasm_config::WasmConfig,
+};
+
+use super::{era_gate::EraGateError, wasm_check, WasmCheckError};
+use crate::{
+    components::chainspec_loader::DeployConfig,
+    types::{Deploy, TimeDiff},
+};
+
+/// A reason the deploy acceptor refuses a deploy.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum DeployViolation {
+    /// The deploy is for a different chain.
+    #[error("chain name '{got}' doesn't match the node's chain name '{expected}'")]
+    InvalidChainName {
+        /// The node's chain name.
+        expected: String,
+        /// The deploy's chain name.
+        got: String,
+    },
+    /// The deploy has too many dependencies.
+    #[error("{got} dependencies exceed the maximum of {max_dependencies}")]
+    ExcessiveDependencies {
+        /// The maximum number of dependencies.
+        max_dependencies: u8,
+        /// The deploy's number of dependencies.
+        got: usize,
+    },
+    /// The deploy's TTL is too long.
+    #[error("TTL of {got} exceeds the maximum of {max_ttl}")]
+    ExcessiveTtl {
+        /// The maximum TTL.
+        max_ttl: TimeDiff,
+        /// The deploy's TTL.
+        got: TimeDiff,
+    },
+    /// The deploy uses the standard payment, but doesn't say how much to pay.
+    #[error("standard payment is missing the 'amount' argument")]
+    MissingPaymentAmount,
+    /// The deploy has not been signed.
+    #[error("deploy has no approvals")]
+    MissingApprovals,
+    /// The deploy's hashes or approvals are invalid.
+    #[error("{0}")]
+    Invalid(String),
+    /// The deploy could never be executed successfully due to its size or Wasm module bytes.
+    #[error(transparent)]
+    Wasm(#[from] WasmCheckError),
+    /// The deploy is unlikely to be executed as intended due to its timing relative to the current
+    /// era.
+    #[error(transparent)]
+    EraGate(#[from] EraGateError),
+}
+
+impl DeployViolation {
+    /// Returns a short, stable name for the kind of violation.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            DeployViolation::InvalidChainName { .. } => "chain_name",
+            DeployViolation::ExcessiveDependencies { .. } => "dependencies",
+            DeployViolation::ExcessiveTtl { .. } => "ttl",
+            DeployViolation::MissingPaymentAmount => "payment",
+            DeployViolation::MissingApprovals | DeployViolation::Invalid(_) => "signatures",
+            DeployViolation::Wasm(WasmCheckError::DeployTooLarge { .. }) => "size",
+            DeployViolation::Wasm(_) => "wasm",
+            DeployViolation::EraGate(_) => "era_gate",
+        }
+    }
+}
+
+/// Returns every violation of the deploy acceptor's checks by `deploy`, or an empty vector if it
+/// would be accepted.
+pub(crate) fn check(
+    deploy: &Deploy,
+    chain_name: &str,
+    deploy_config: &DeployConfig,
+    wasm_config: &WasmConfig,
+) -> Vec<DeployViolation> {
+    let mut violations = vec![];
+    let header = deploy.header();
+
+    if header.chain_name() != chain_name {
+        violations.push(DeployViolation::InvalidChainName {
+            expected: chain_name.to_string(),
+            got: header.chain_name().to_string(),
+        });
+    }
+
+    if header.dependencies().len() > deploy_config.max_dependencies as usize {
+        violations.push(DeployViolation::ExcessiveDependencies {
+            max_dependencies: deploy_config.max_dependencies,
+            got: header.dependencies().len(),
+        });
+    }
+
+    if header.ttl() > deploy_config.max_ttl {
+        violations.push(DeployViolation::ExcessiveTtl {
+            max_ttl: deploy_config.max_ttl,
+            got: header.ttl(),
+        });
+    }
+
+    let is_standard_payment = matches!(
+        deploy.payment(),
+        ExecutableDeployItem::ModuleBytes { module_bytes, .. } if module_bytes.is_empty()
+    );
+    if is_standard_payment && deploy.payment_amount().is_none() {
+        violations.push(DeployViolation::MissingPaymentAmount);
+    }
+
+    if deploy.approvals().is_empty() {
+        violations.push(DeployViolation::MissingApprovals);
+    }
+
+    if let Err(error) = deploy.validate() {
+        violations.push(DeployViolation::Invalid(error.to_string()));
+    }
+
+    if let Err(error) = wasm_check::check(deploy, deploy_config, wasm_config) {
+        violations.push(DeployViolation::Wasm(error));
+    }
+
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{bytesrepr::ToBytes, standard_payment, RuntimeArgs, U512};
+
+    use super::*;
+    use crate::{crypto::asymmetric_key::SecretKey, testing::TestRng, types::Timestamp};
+
+    fn deploy(rng: &mut TestRng, payment_args: RuntimeArgs, ttl: TimeDiff) -> Deploy {
+        let secret_key = SecretKey::random(rng);
+        Deploy::new(
+            Timestamp::from(1_000),
+            ttl,
+            1,
+            vec![],
+            "chain".to_string(),
+            ExecutableDeployItem::ModuleBytes {
+                module_bytes: vec![],
+                args: payment_args.to_bytes().unwrap(),
+            },
+            ExecutableDeployItem::Transfer {
+                args: RuntimeArgs::new().to_bytes().unwrap(),
+            },
+            &secret_key,
+            rng,
+        )
+    }
+
+    #[test]
+    fn should_accept_valid_deploy() {
+        let mut rng = crate::new_rng();
+        let mut payment_args = RuntimeArgs::new();
+        payment_args.insert(standard_payment::ARG_AMOUNT, U512::from(1_000));
+        let deploy = deploy(&mut rng, payment_args, TimeDiff::from(10_000));
+
+        let violations = check(
+            &deploy,
+            "chain",
+            &DeployConfig::default(),
+            &WasmConfig::default(),
+        );
+        assert!(violations.is_empty(), "{:?}", violations);
+    }
+
+    #[test]
+    fn should_report_every_violation() {
+        let mut rng = crate::new_rng();
+        let deploy_config = DeployConfig::default();
+        let ttl = TimeDiff::from(deploy_config.max_ttl.millis() + 1);
+        let deploy = deploy(&mut rng, RuntimeArgs::new(), ttl);
+
+        let violations = check(
+            &deploy,
+            "other-chain",
+            &deploy_config,
+            &WasmConfig::default(),
+        );
+        let kinds: Vec<_> = violations.iter().map(DeployViolation::kind).collect();
+        assert_eq!(kinds, vec!["chain_name", "ttl", "payment"]);
+    }
+}
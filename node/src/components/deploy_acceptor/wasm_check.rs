@@ -0,0 +1,258 @@
+//! Cheap static checks of the Wasm module bytes of deploys, refusing deploys which could never be
+//! executed successfully before they are gossiped or take up space in a block.
+//!
+//! The module bytes of the payment and session code are checked to
+//!   * start with a Wasm header and parse as a Wasm module
+//!   * only import the host functions and the memory
+//!   * not use floating-point values, if the chainspec requires strict determinism
+//!
+//! Empty module bytes are not checked, as they denote the standard payment.  The deploy as a whole
+//! must also fit within a block.  None of the checks instrument or instantiate the modules.
+
+use thiserror::Error;
+
+use casper_execution_engine::{
+    core::{engine_state::executable_deploy_item::ExecutableDeployItem, resolvers},
+    shared::{wasm_config::WasmConfig, wasm_prep},
+};
+use casper_types::bytesrepr::ToBytes;
+
+use crate::{components::chainspec_loader::DeployConfig, types::Deploy};
+
+/// The magic number and version with which every Wasm module starts.
+const WASM_HEADER: [u8; 8] = [0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+
+/// The reason a deploy was refused due to its Wasm module bytes.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum WasmCheckError {
+    /// The deploy can never be included in a block.
+    #[error("deploy of {size} bytes exceeds the maximum block size of {max_block_size} bytes")]
+    DeployTooLarge {
+        /// The size of the serialized deploy.
+        size: usize,
+        /// The maximum size of a block.
+        max_block_size: u32,
+    },
+    /// The module bytes don't start with a Wasm header.
+    #[error("{code} module bytes don't start with a Wasm header")]
+    InvalidHeader {
+        /// Either "payment" or "session".
+        code: &'static str,
+    },
+    /// The module bytes don't parse as a Wasm module.
+    #[error("{code} module bytes are not a valid Wasm module: {error}")]
+    Malformed {
+        /// Either "payment" or "session".
+        code: &'static str,
+        /// The parsing error.
+        error: String,
+    },
+    /// The module imports something other than the host functions and the memory.
+    #[error("{code} module imports '{field}' from '{module}', which is not provided")]
+    UnresolvableImport {
+        /// Either "payment" or "session".
+        code: &'static str,
+        /// The name of the module imported from.
+        module: String,
+        /// The name of the import.
+        field: String,
+    },
+    /// The module uses floating-point values while the chainspec requires strict determinism.
+    #[error("{code} module is nondeterministic: {error}")]
+    Nondeterministic {
+        /// Either "payment" or "session".
+        code: &'static str,
+        /// What makes the module nondeterministic.
+        error: String,
+    },
+}
+
+/// Checks the size of `deploy` and the module bytes of its payment and session code.
+pub(crate) fn check(
+    deploy: &Deploy,
+    deploy_config: &DeployConfig,
+    wasm_config: &WasmConfig,
+) -> Result<(), WasmCheckError> {
+    let size = deploy.serialized_length();
+    if size > deploy_config.max_block_size as usize {
+        return Err(WasmCheckError::DeployTooLarge {
+            size,
+            max_block_size: deploy_config.max_block_size,
+        });
+    }
+
+    check_item("payment", deploy.payment(), wasm_config)?;
+    check_item("session", deploy.session(), wasm_config)
+}
+
+fn check_item(
+    code: &'static str,
+    item: &ExecutableDeployItem,
+    wasm_config: &WasmConfig,
+) -> Result<(), WasmCheckError> {
+    let module_bytes = match item {
+        ExecutableDeployItem::ModuleBytes { module_bytes, .. } if !module_bytes.is_empty() => {
+            module_bytes
+        }
+        _ => return Ok(()),
+    };
+
+    if !module_bytes.starts_with(&WASM_HEADER) {
+        return Err(WasmCheckError::InvalidHeader { code });
+    }
+
+    let module =
+        wasm_prep::deserialize(module_bytes).map_err(|error| WasmCheckError::Malformed {
+            code,
+            error: error.to_string(),
+        })?;
+
+    if let Some((module, field)) = resolvers::find_unresolvable_import(&module) {
+        return Err(WasmCheckError::UnresolvableImport {
+            code,
+            module: module.to_string(),
+            field: field.to_string(),
+        });
+    }
+
+    if wasm_config.strict_determinism {
+        wasm_prep::ensure_deterministic(&module).map_err(|error| {
+            WasmCheckError::Nondeterministic {
+                code,
+                error: error.to_string(),
+            }
+        })?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::{
+        builder,
+        elements::{Instruction, Instructions, ValueType},
+    };
+
+    use casper_types::RuntimeArgs;
+
+    use super::*;
+    use crate::{
+        crypto::asymmetric_key::SecretKey,
+        testing::TestRng,
+        types::{TimeDiff, Timestamp},
+    };
+
+    fn module_bytes(import_field: &str, param: ValueType) -> Vec<u8> {
+        let module = builder::module()
+            .function()
+            .signature()
+            .with_params(vec![param])
+            .build()
+            .body()
+            .with_instructions(Instructions::new(vec![Instruction::End]))
+            .build()
+            .build()
+            .import()
+            .module("env")
+            .field(import_field)
+            .external()
+            .func(0)
+            .build()
+            .build();
+        parity_wasm::serialize(module).expect("should serialize")
+    }
+
+    fn deploy(rng: &mut TestRng, session_bytes: Vec<u8>) -> Deploy {
+        let secret_key = SecretKey::random(rng);
+        let module_bytes = |module_bytes| ExecutableDeployItem::ModuleBytes {
+            module_bytes,
+            args: RuntimeArgs::new().to_bytes().unwrap(),
+        };
+        Deploy::new(
+            Timestamp::from(1_000),
+            TimeDiff::from(10_000),
+            1,
+            vec![],
+            "chain".to_string(),
+            module_bytes(vec![]),
+            module_bytes(session_bytes),
+            &secret_key,
+            rng,
+        )
+    }
+
+    fn check_session(rng: &mut TestRng, session_bytes: Vec<u8>) -> Result<(), WasmCheckError> {
+        let strict = WasmConfig::default().with_strict_determinism(true);
+        check(
+            &deploy(rng, session_bytes),
+            &DeployConfig::default(),
+            &strict,
+        )
+    }
+
+    #[test]
+    fn should_accept_valid_module() {
+        let mut rng = crate::new_rng();
+        let valid = module_bytes("casper_revert", ValueType::I32);
+        assert_eq!(check_session(&mut rng, valid), Ok(()));
+    }
+
+    #[test]
+    fn should_refuse_invalid_modules() {
+        let mut rng = crate::new_rng();
+
+        let mut no_header = module_bytes("casper_revert", ValueType::I32);
+        no_header[0] = 0xff;
+        assert_eq!(
+            check_session(&mut rng, no_header),
+            Err(WasmCheckError::InvalidHeader { code: "session" })
+        );
+
+        let mut truncated = module_bytes("casper_revert", ValueType::I32);
+        truncated.truncate(WASM_HEADER.len() + 1);
+        assert!(matches!(
+            check_session(&mut rng, truncated),
+            Err(WasmCheckError::Malformed {
+                code: "session", ..
+            })
+        ));
+
+        let unknown_import = module_bytes("fd_write", ValueType::I32);
+        assert_eq!(
+            check_session(&mut rng, unknown_import),
+            Err(WasmCheckError::UnresolvableImport {
+                code: "session",
+                module: "env".to_string(),
+                field: "fd_write".to_string(),
+            })
+        );
+
+        let float_param = module_bytes("casper_revert", ValueType::F64);
+        assert!(matches!(
+            check_session(&mut rng, float_param),
+            Err(WasmCheckError::Nondeterministic {
+                code: "session", ..
+            })
+        ));
+    }
+
+    #[test]
+    fn should_refuse_deploy_exceeding_block_size() {
+        let mut rng = crate::new_rng();
+        let deploy = deploy(&mut rng, module_bytes("casper_revert", ValueType::I32));
+        let size = deploy.serialized_length();
+        let deploy_config = DeployConfig {
+            max_block_size: size as u32 - 1,
+            ..DeployConfig::default()
+        };
+
+        assert_eq!(
+            check(&deploy, &deploy_config, &WasmConfig::default()),
+            Err(WasmCheckError::DeployTooLarge {
+                size,
+                max_block_size: size as u32 - 1,
+            })
+        );
+    }
+}
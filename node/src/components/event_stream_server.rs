@@ -25,13 +25,27 @@ mod event;
 mod http_server;
 mod sse_server;
 
-use std::{convert::Infallible, fmt::Debug};
+use std::{
+    convert::{Infallible, TryFrom},
+    fmt::Debug,
+};
 
 use datasize::DataSize;
-use tokio::sync::mpsc::{self, UnboundedSender};
+use futures::{future::BoxFuture, FutureExt};
+use tokio::{
+    sync::{
+        mpsc::{self, UnboundedSender},
+        oneshot,
+    },
+    task::JoinHandle,
+};
+use tracing::{debug, error, warn};
+
+use casper_types::auction::{UnbondingPurse, UnbondingPurses};
 
 use super::Component;
 use crate::{
+    crypto::asymmetric_key::PublicKey,
     effect::{EffectBuilder, Effects},
     NodeRng,
 };
@@ -52,6 +66,11 @@ pub(crate) struct EventStreamServer {
     // TODO - this should not be skipped.  Awaiting support for `UnboundedSender` in datasize crate.
     #[data_size(skip)]
     sse_data_sender: UnboundedSender<SseData>,
+    /// When the message is sent, it signals the server loop to send the final `Shutdown` event to
+    /// all clients and exit cleanly.
+    shutdown_sender: Option<oneshot::Sender<()>>,
+    /// The task handle which will only join once the server loop has exited.
+    server_join_handle: Option<JoinHandle<()>>,
 }
 
 impl EventStreamServer {
@@ -60,9 +79,42 @@ impl EventStreamServer {
         REv: ReactorEventT,
     {
         let (sse_data_sender, sse_data_receiver) = mpsc::unbounded_channel();
-        tokio::spawn(http_server::run(config, sse_data_receiver));
+        let (shutdown_sender, shutdown_receiver) = oneshot::channel::<()>();
+        let server_join_handle = tokio::spawn(http_server::run(
+            config,
+            sse_data_receiver,
+            shutdown_receiver,
+        ));
+
+        EventStreamServer {
+            sse_data_sender,
+            shutdown_sender: Some(shutdown_sender),
+            server_join_handle: Some(server_join_handle),
+        }
+    }
 
-        EventStreamServer { sse_data_sender }
+    /// Signals the server to send the final `Shutdown` event to all clients and to exit once they
+    /// have disconnected.
+    ///
+    /// The returned future completes once the server has exited.
+    pub(crate) fn shut_down(&mut self) -> BoxFuture<'static, ()> {
+        if let Some(shutdown_sender) = self.shutdown_sender.take() {
+            let _ = shutdown_sender.send(());
+        }
+
+        // Wait for the server to exit cleanly.
+        let maybe_join_handle = self.server_join_handle.take();
+        async move {
+            if let Some(join_handle) = maybe_join_handle {
+                match join_handle.await {
+                    Ok(_) => debug!("event stream server exited cleanly"),
+                    Err(error) => error!(%error, "could not join event stream server task cleanly"),
+                }
+            } else {
+                warn!("event stream server shutdown while already shut down")
+            }
+        }
+        .boxed()
     }
 
     /// Broadcasts the SSE data to all clients connected to the event stream.
@@ -99,7 +151,7 @@ where
             Event::DeployProcessed {
                 deploy_hash,
                 deploy_header,
-                block_hash,
+                block_height,
                 execution_result,
             } => self.broadcast(SseData::DeployProcessed {
                 deploy_hash,
@@ -107,9 +159,123 @@ where
                 timestamp: deploy_header.timestamp(),
                 ttl: deploy_header.ttl(),
                 dependencies: deploy_header.dependencies().clone(),
-                block_hash,
+                block_height,
                 execution_result,
             }),
+            Event::FinalizationLagExceeded {
+                block_height,
+                block_timestamp,
+                lag,
+            } => self.broadcast(SseData::FinalizationLagExceeded {
+                block_height,
+                block_timestamp,
+                lag,
+            }),
+            Event::EraTransition { era_id, duration } => {
+                self.broadcast(SseData::EraTransition { era_id, duration })
+            }
+            Event::EraEnd {
+                block_hash,
+                block_header,
+                next_era_validator_weights,
+            } => {
+                let era_id = block_header.era_id();
+                let (era_end, validator_weights) =
+                    match (block_header.era_end(), next_era_validator_weights) {
+                        (Some(era_end), Some(validator_weights)) => (era_end, validator_weights),
+                        _ => {
+                            warn!(
+                                %block_hash,
+                                "validator weights of the era following {} unknown, not \
+                                 announcing its end",
+                                era_id
+                            );
+                            return Effects::new();
+                        }
+                    };
+                let next_era_validator_weights = validator_weights
+                    .into_iter()
+                    .filter_map(|(public_key, weight)| {
+                        Some((PublicKey::try_from(public_key).ok()?, weight))
+                    })
+                    .collect();
+                self.broadcast(SseData::EraEnd {
+                    era_id,
+                    block_hash,
+                    rewards: era_end.rewards.clone(),
+                    equivocators: era_end.equivocators.clone(),
+                    next_era_validator_weights,
+                })
+            }
+            Event::UnbondsProcessed {
+                block_hash,
+                era_id,
+                unbonding_purses_before,
+                unbonding_purses_after,
+            } => {
+                let (before, after) = match (unbonding_purses_before, unbonding_purses_after) {
+                    (Some(before), Some(after)) => (before, after),
+                    _ => {
+                        warn!(
+                            %block_hash,
+                            "unbonding purses around the end of {} unknown, not announcing \
+                             processed unbonds",
+                            era_id
+                        );
+                        return Effects::new();
+                    }
+                };
+                let mut effects = Effects::new();
+                for purse in processed_unbonding_purses(before, &after, era_id.0) {
+                    let public_key = match PublicKey::try_from(purse.public_key) {
+                        Ok(public_key) => public_key,
+                        Err(error) => {
+                            warn!(%error, "failed to convert public key of unbonding purse");
+                            continue;
+                        }
+                    };
+                    effects.extend(self.broadcast(SseData::UnbondProcessed {
+                        era_id,
+                        block_hash,
+                        public_key,
+                        bonding_purse: purse.bonding_purse,
+                        unbonding_purse: purse.unbonding_purse,
+                        amount: purse.amount,
+                    }));
+                }
+                effects
+            }
+            Event::BlockReproposed(proto_block_hash) => {
+                self.broadcast(SseData::BlockReproposed { proto_block_hash })
+            }
+            Event::ResourceThresholdCrossed {
+                resource,
+                percent,
+                threshold_percent,
+            } => self.broadcast(SseData::ResourceThresholdCrossed {
+                resource,
+                percent,
+                threshold_percent,
+            }),
         }
     }
 }
+
+/// Returns the unbonding purses which were paid out at the end of the era `era_id`: those due by
+/// then which were in the queue before the switch block but not after it.
+fn processed_unbonding_purses(
+    before: UnbondingPurses,
+    after: &UnbondingPurses,
+    era_id: u64,
+) -> Vec<UnbondingPurse> {
+    before
+        .into_iter()
+        .flat_map(|(public_key, purses)| {
+            let remaining = after.get(&public_key);
+            purses.into_iter().filter(move |purse| {
+                purse.era_of_withdrawal <= era_id
+                    && !remaining.map_or(false, |remaining| remaining.contains(purse))
+            })
+        })
+        .collect()
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
@@ -9,6 +11,9 @@ const DEFAULT_ADDRESS: &str = "0.0.0.0:9999";
 /// Default number of SSEs to buffer.
 const DEFAULT_EVENT_STREAM_BUFFER_LENGTH: u32 = 100;
 
+/// Default time allowed for clients to receive the final events when shutting down.
+const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// SSE HTTP server configuration.
 #[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
 // Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
@@ -19,6 +24,18 @@ pub struct Config {
 
     /// Number of SSEs to buffer.
     pub event_stream_buffer_length: u32,
+
+    /// Time in milliseconds allowed for clients to receive the final events when shutting down,
+    /// after which any remaining connections are dropped.
+    #[serde(
+        default = "default_shutdown_timeout",
+        with = "crate::utils::milliseconds"
+    )]
+    pub shutdown_timeout: Duration,
+}
+
+fn default_shutdown_timeout() -> Duration {
+    DEFAULT_SHUTDOWN_TIMEOUT
 }
 
 impl Config {
@@ -27,6 +44,7 @@ impl Config {
         Config {
             address: DEFAULT_ADDRESS.to_string(),
             event_stream_buffer_length: DEFAULT_EVENT_STREAM_BUFFER_LENGTH,
+            shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
         }
     }
 }
@@ -1,8 +1,13 @@
 use std::fmt::{self, Display, Formatter};
 
-use crate::types::{
-    json_compatibility::ExecutionResult, BlockHash, BlockHeader, DeployHash, DeployHeader,
-    FinalizedBlock,
+use casper_types::auction::{UnbondingPurses, ValidatorWeights};
+
+use crate::{
+    components::consensus::EraId,
+    types::{
+        json_compatibility::ExecutionResult, BlockHash, BlockHeader, DeployHash, DeployHeader,
+        FinalizedBlock, ProtoBlockHash, TimeDiff, Timestamp,
+    },
 };
 
 #[derive(Debug)]
@@ -15,9 +20,38 @@ pub enum Event {
     DeployProcessed {
         deploy_hash: DeployHash,
         deploy_header: Box<DeployHeader>,
-        block_hash: BlockHash,
+        block_height: u64,
         execution_result: Box<ExecutionResult>,
     },
+    FinalizationLagExceeded {
+        block_height: u64,
+        block_timestamp: Timestamp,
+        lag: TimeDiff,
+    },
+    EraTransition {
+        era_id: EraId,
+        duration: TimeDiff,
+    },
+    EraEnd {
+        block_hash: BlockHash,
+        block_header: Box<BlockHeader>,
+        /// `None` if the weights could not be read from the switch block's global state.
+        next_era_validator_weights: Option<ValidatorWeights>,
+    },
+    UnbondsProcessed {
+        block_hash: BlockHash,
+        era_id: EraId,
+        /// The unbonding queue as of the block before the switch block, `None` if unknown.
+        unbonding_purses_before: Option<UnbondingPurses>,
+        /// The unbonding queue as of the switch block, `None` if unknown.
+        unbonding_purses_after: Option<UnbondingPurses>,
+    },
+    BlockReproposed(ProtoBlockHash),
+    ResourceThresholdCrossed {
+        resource: String,
+        percent: u8,
+        threshold_percent: u8,
+    },
 }
 
 impl Display for Event {
@@ -32,6 +66,22 @@ impl Display for Event {
             Event::DeployProcessed { deploy_hash, .. } => {
                 write!(formatter, "deploy processed {}", deploy_hash)
             }
+            Event::FinalizationLagExceeded { lag, .. } => {
+                write!(formatter, "finalization lag exceeded {}", lag)
+            }
+            Event::EraTransition { era_id, .. } => write!(formatter, "era transition {}", era_id),
+            Event::EraEnd { block_header, .. } => {
+                write!(formatter, "era end {}", block_header.era_id())
+            }
+            Event::UnbondsProcessed { era_id, .. } => {
+                write!(formatter, "unbonds processed at end of {}", era_id)
+            }
+            Event::BlockReproposed(proto_block_hash) => {
+                write!(formatter, "block reproposed {}", proto_block_hash)
+            }
+            Event::ResourceThresholdCrossed { resource, .. } => {
+                write!(formatter, "resource threshold crossed for {}", resource)
+            }
         }
     }
 }
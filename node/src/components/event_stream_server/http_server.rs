@@ -1,7 +1,7 @@
 use std::convert::Infallible;
 
 use futures::{
-    future::{self, select},
+    future::{self, select, Either},
     FutureExt,
 };
 use hyper::Server;
@@ -21,8 +21,14 @@ use crate::utils;
 /// Run the HTTP server.
 ///
 /// `data_receiver` will provide the server with local events which should then be sent to all
-/// subscribed clients.
-pub(super) async fn run(config: Config, mut data_receiver: mpsc::UnboundedReceiver<SseData>) {
+/// subscribed clients.  Once `shutdown_receiver` fires (or `data_receiver` is closed), a final
+/// `Shutdown` event is sent to all subscribed clients and their streams are closed.  Clients still
+/// connected after `config.shutdown_timeout` are then dropped.
+pub(super) async fn run(
+    config: Config,
+    mut data_receiver: mpsc::UnboundedReceiver<SseData>,
+    mut shutdown_receiver: oneshot::Receiver<()>,
+) {
     // Event stream channels and filter.
     let (broadcaster, mut new_subscriber_info_receiver, sse_filter) =
         sse_server::create_channels_and_filter();
@@ -59,16 +65,14 @@ pub(super) async fn run(config: Config, mut data_receiver: mpsc::UnboundedReceiv
     // Start the server, passing a oneshot receiver to allow the server to be shut down gracefully.
     let make_svc =
         hyper::service::make_service_fn(move |_| future::ok::<_, Infallible>(service.clone()));
-    let (shutdown_sender, shutdown_receiver) = oneshot::channel::<()>();
+    let (server_shutdown_sender, server_shutdown_receiver) = oneshot::channel::<()>();
+    let (shutdown_signal, drain_deadline) =
+        utils::graceful_shutdown_with_deadline(server_shutdown_receiver, config.shutdown_timeout);
 
     let server = builder.serve(make_svc);
     info!(address = %server.local_addr(), "started HTTP server");
 
-    let server_with_shutdown = server.with_graceful_shutdown(async {
-        shutdown_receiver.await.ok();
-    });
-
-    let server_joiner = tokio::spawn(server_with_shutdown);
+    let mut server_joiner = tokio::spawn(server.with_graceful_shutdown(shutdown_signal));
 
     // Initialize the index and buffer for the SSEs.
     let mut event_index = 0_u32;
@@ -121,17 +125,41 @@ pub(super) async fn run(config: Config, mut data_receiver: mpsc::UnboundedReceiv
                         }
                     }
                 }
+
+                _ = &mut shutdown_receiver => {
+                    info!("shutting down HTTP server");
+                    break;
+                }
             }
         }
+
+        // Let the clients know the stream is ending before closing it.
+        let event = ServerSentEvent {
+            id: Some(event_index),
+            data: SseData::Shutdown,
+        };
+        let _ = broadcaster.send(BroadcastChannelMessage::ServerSentEvent(event));
     };
 
-    // Wait for the event stream future to exit, which will only happen if the last `data_sender`
-    // paired with `data_receiver` is dropped.  `server_joiner` will never return here.
-    let _ = select(server_joiner, event_stream_fut.boxed()).await;
+    // Wait for the event stream future to exit, which will only happen if shutdown is requested or
+    // the last `data_sender` paired with `data_receiver` is dropped.  `server_joiner` will never
+    // return here unless the server fails.
+    if let Either::Left(_) = select(&mut server_joiner, event_stream_fut.boxed()).await {
+        trace!("Event stream server stopped");
+        return;
+    }
 
-    // Kill the event-stream handlers, and shut down the server.
+    // Kill the event-stream handlers, and shut down the server, allowing the clients until the
+    // deadline to disconnect.
     let _ = broadcaster.send(BroadcastChannelMessage::Shutdown);
-    let _ = shutdown_sender.send(());
+    let _ = server_shutdown_sender.send(());
+
+    select! {
+        _ = server_joiner => (),
+        _ = drain_deadline => {
+            warn!("event stream clients still connected after shutdown timeout, dropping connections")
+        }
+    }
 
     trace!("Event stream server stopped");
 }
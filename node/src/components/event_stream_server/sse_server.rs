@@ -1,12 +1,16 @@
 //! Types and functions used by the http server to manage the event-stream.
 
+use std::collections::BTreeMap;
+
 use datasize::DataSize;
-use futures::{Stream, StreamExt};
+use futures::{future, Stream, StreamExt};
 use lazy_static::lazy_static;
 use semver::Version;
 use serde::{Deserialize, Serialize};
 use tokio::sync::{broadcast, mpsc};
 use tracing::{error, trace};
+
+use casper_types::{URef, U512};
 use warp::{
     filters::BoxedFilter,
     sse::{self, ServerSentEvent as WarpServerSentEvent},
@@ -14,11 +18,11 @@ use warp::{
 };
 
 use crate::{
-    components::CLIENT_API_VERSION,
+    components::{consensus::EraId, CLIENT_API_VERSION},
     crypto::asymmetric_key::PublicKey,
     types::{
         json_compatibility::ExecutionResult, BlockHash, BlockHeader, DeployHash, FinalizedBlock,
-        TimeDiff, Timestamp,
+        ProtoBlockHash, TimeDiff, Timestamp,
     },
 };
 
@@ -54,16 +58,68 @@ pub enum SseData {
         block_hash: BlockHash,
         block_header: BlockHeader,
     },
-    /// The given deploy has been executed, committed and forms part of the given block.
+    /// The given deploy has been executed as part of the finalized block at the given height.
+    ///
+    /// This is emitted as soon as the deploy has been executed, before the rest of the block's
+    /// deploys, so the block's hash is only known from the subsequent `BlockAdded` event.
     DeployProcessed {
         deploy_hash: DeployHash,
         account: PublicKey,
         timestamp: Timestamp,
         ttl: TimeDiff,
         dependencies: Vec<DeployHash>,
-        block_hash: BlockHash,
+        block_height: u64,
         execution_result: Box<ExecutionResult>,
     },
+    /// The time elapsed since the timestamp of the most recently finalized block exceeded the
+    /// configured alert threshold.
+    FinalizationLagExceeded {
+        block_height: u64,
+        block_timestamp: Timestamp,
+        lag: TimeDiff,
+    },
+    /// The first block of the given era has been finalized, the given duration after the switch
+    /// block of the previous era.
+    EraTransition { era_id: EraId, duration: TimeDiff },
+    /// The given era has ended with the given switch block, which has been added to the linear
+    /// chain.
+    EraEnd {
+        era_id: EraId,
+        block_hash: BlockHash,
+        /// The rewards for the era's validators, as recorded in the switch block.
+        rewards: BTreeMap<PublicKey, u64>,
+        /// The validators found to have equivocated in the era.
+        equivocators: Vec<PublicKey>,
+        /// The weights of the validators of the next era.
+        #[data_size(skip)]
+        next_era_validator_weights: BTreeMap<PublicKey, U512>,
+    },
+    /// An unbonded amount has been paid out at the end of the given era.
+    #[data_size(skip)] // `URef` is not supported by datasize.
+    UnbondProcessed {
+        era_id: EraId,
+        /// The hash of the era's switch block.
+        block_hash: BlockHash,
+        /// The validator or delegator who unbonded the amount.
+        public_key: PublicKey,
+        /// The purse the amount was staked from.
+        bonding_purse: URef,
+        /// The purse the amount was paid out to.
+        unbonding_purse: URef,
+        amount: U512,
+    },
+    /// The given proto block has been proposed again.
+    BlockReproposed { proto_block_hash: ProtoBlockHash },
+    /// The usage of the given resource crossed the configured alert threshold.  For disk space,
+    /// `percent` is the free space; for the other resources, it is the share in use.
+    ResourceThresholdCrossed {
+        resource: String,
+        percent: u8,
+        threshold_percent: u8,
+    },
+    /// The node is shutting down.  This will always be the last event sent to a client before the
+    /// stream is closed.
+    Shutdown,
 }
 
 /// The components of a single SSE.
@@ -160,6 +216,9 @@ pub(super) fn create_channels_and_filter() -> (
 /// either the client disconnects, or the server shuts down (indicated by sending a `Shutdown`
 /// variant via the channel).  This channel will receive all SSEs created from the moment the client
 /// subscribed to the server's event stream.
+///
+/// The stream ends cleanly on `Shutdown` rather than with an error, as an error aborts the
+/// connection, possibly before the final `SseData::Shutdown` event has been flushed to the client.
 fn stream_to_client(
     initial_events: mpsc::UnboundedReceiver<ServerSentEvent>,
     ongoing_events: broadcast::Receiver<BroadcastChannelMessage>,
@@ -167,6 +226,9 @@ fn stream_to_client(
     initial_events
         .map(|event| Ok(BroadcastChannelMessage::ServerSentEvent(event)))
         .chain(ongoing_events)
+        .take_while(|result| {
+            future::ready(!matches!(result, Ok(BroadcastChannelMessage::Shutdown)))
+        })
         .map(|result| {
             trace!(?result);
             match result? {
@@ -174,12 +236,19 @@ fn stream_to_client(
                     (None, &SseData::ApiVersion { .. }) => Ok(sse::json(event.data).boxed()),
                     (Some(id), &SseData::BlockFinalized { .. })
                     | (Some(id), &SseData::BlockAdded { .. })
-                    | (Some(id), &SseData::DeployProcessed { .. }) => {
+                    | (Some(id), &SseData::DeployProcessed { .. })
+                    | (Some(id), &SseData::FinalizationLagExceeded { .. })
+                    | (Some(id), &SseData::EraTransition { .. })
+                    | (Some(id), &SseData::EraEnd { .. })
+                    | (Some(id), &SseData::UnbondProcessed { .. })
+                    | (Some(id), &SseData::BlockReproposed { .. })
+                    | (Some(id), &SseData::ResourceThresholdCrossed { .. })
+                    | (Some(id), &SseData::Shutdown) => {
                         Ok((sse::id(id), sse::json(event.data)).boxed())
                     }
                     _ => unreachable!("only ApiVersion may have no event ID"),
                 },
-                BroadcastChannelMessage::Shutdown => Err(broadcast::RecvError::Closed),
+                BroadcastChannelMessage::Shutdown => unreachable!("the stream ends on Shutdown"),
             }
         })
 }
@@ -1,3 +1,4 @@
+mod cache;
 mod config;
 mod event;
 mod tests;
@@ -20,6 +21,7 @@ use crate::{
     NodeRng,
 };
 
+use cache::FetchCache;
 pub use config::Config;
 pub use event::{Event, FetchResult};
 
@@ -54,11 +56,17 @@ where
 pub trait ItemFetcher<T: Item + 'static> {
     fn responders(&mut self) -> &mut HashMap<T::Id, HashMap<NodeId, Vec<FetchResponder<T>>>>;
 
+    /// The peer each item currently being fetched is requested from.
+    fn in_flight(&mut self) -> &mut HashMap<T::Id, NodeId>;
+
+    fn cache(&mut self) -> &mut FetchCache<T>;
+
     fn peer_timeout(&self) -> Duration;
 
-    /// We've been asked to fetch the item by another component of this node.  We'll try to get it
-    /// from our own storage component first, and if that fails, we'll send a request to `peer` for
-    /// the item.
+    /// We've been asked to fetch the item by another component of this node.  If it was fetched
+    /// recently, we'll return the cached result.  If it's already being fetched, we'll return the
+    /// result of that.  Otherwise we'll try to get it from our own storage component first, and if
+    /// that fails, we'll send a request to `peer` for the item.
     fn fetch<REv: ReactorEventT<T>>(
         &mut self,
         effect_builder: EffectBuilder<REv>,
@@ -66,6 +74,11 @@ pub trait ItemFetcher<T: Item + 'static> {
         peer: NodeId,
         responder: FetchResponder<T>,
     ) -> Effects<Event<T>> {
+        if let Some(result) = self.cache().get(&id) {
+            debug!(%id, "fetched item from cache");
+            return responder.respond(Some(result)).ignore();
+        }
+
         // Capture responder for later signalling.
         let responders = self.responders();
         responders
@@ -75,6 +88,14 @@ pub trait ItemFetcher<T: Item + 'static> {
             .or_default()
             .push(responder);
 
+        // Coalesce with the request already in flight, if any.  Should that fail, the item is
+        // requested from `peer` next.
+        if self.in_flight().contains_key(&id) {
+            debug!(%id, %peer, "item already being fetched");
+            return Effects::new();
+        }
+        let _ = self.in_flight().insert(id, peer.clone());
+
         // Get the item from the storage component.
         self.get_from_storage(effect_builder, id, peer)
     }
@@ -119,11 +140,37 @@ pub trait ItemFetcher<T: Item + 'static> {
             }
             Err(error) => {
                 error!("failed to construct get request: {}", error);
-                self.signal(id, None, peer)
+                self.fetch_failed(effect_builder, id, peer)
             }
         }
     }
 
+    /// Handles failing to get the item from `peer`.  Signals the responders waiting for it from
+    /// `peer`, then requests it from one of the other peers it's been requested from, if any.
+    fn fetch_failed<REv: ReactorEventT<T>>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        id: T::Id,
+        peer: NodeId,
+    ) -> Effects<Event<T>> {
+        let mut effects = self.signal(id, None, peer);
+        if self.in_flight().contains_key(&id) {
+            return effects;
+        }
+        let next_peer = match self
+            .responders()
+            .get(&id)
+            .and_then(|responders| responders.keys().next().cloned())
+        {
+            Some(next_peer) => next_peer,
+            None => return effects,
+        };
+        let _ = self.in_flight().insert(id, next_peer.clone());
+        // Storage was already checked when the item was first requested.
+        effects.extend(self.failed_to_get_from_storage(effect_builder, id, next_peer));
+        effects
+    }
+
     /// Handles signalling responders with the item or `None`.
     fn signal(
         &mut self,
@@ -135,6 +182,10 @@ pub trait ItemFetcher<T: Item + 'static> {
         let mut all_responders = self.responders().remove(&id).unwrap_or_default();
         match result {
             Some(ret) => {
+                let _ = self.in_flight().remove(&id);
+                if !all_responders.is_empty() {
+                    self.cache().insert(id, ret.clone());
+                }
                 // signal all responders waiting for this item
                 for (_, responders) in all_responders {
                     for responder in responders {
@@ -143,6 +194,9 @@ pub trait ItemFetcher<T: Item + 'static> {
                 }
             }
             None => {
+                if self.in_flight().get(&id) == Some(&peer) {
+                    let _ = self.in_flight().remove(&id);
+                }
                 // remove only the peer specific responders for this id
                 if let Some(responders) = all_responders.remove(&peer) {
                     for responder in responders {
@@ -166,6 +220,9 @@ where
 {
     get_from_peer_timeout: Duration,
     responders: HashMap<T::Id, HashMap<NodeId, Vec<FetchResponder<T>>>>,
+    in_flight: HashMap<T::Id, NodeId>,
+    #[data_size(skip)] // Not supported by datasize.
+    cache: FetchCache<T>,
 }
 
 impl<T: Item> Fetcher<T> {
@@ -173,6 +230,8 @@ impl<T: Item> Fetcher<T> {
         Fetcher {
             get_from_peer_timeout: Duration::from_secs(config.get_from_peer_timeout()),
             responders: HashMap::new(),
+            in_flight: HashMap::new(),
+            cache: FetchCache::new(config.cache_size()),
         }
     }
 }
@@ -184,6 +243,14 @@ impl ItemFetcher<Deploy> for Fetcher<Deploy> {
         &mut self.responders
     }
 
+    fn in_flight(&mut self) -> &mut HashMap<DeployHash, NodeId> {
+        &mut self.in_flight
+    }
+
+    fn cache(&mut self) -> &mut FetchCache<Deploy> {
+        &mut self.cache
+    }
+
     fn peer_timeout(&self) -> Duration {
         self.get_from_peer_timeout
     }
@@ -212,6 +279,14 @@ impl ItemFetcher<Block> for Fetcher<Block> {
         &mut self.responders
     }
 
+    fn in_flight(&mut self) -> &mut HashMap<BlockHash, NodeId> {
+        &mut self.in_flight
+    }
+
+    fn cache(&mut self) -> &mut FetchCache<Block> {
+        &mut self.cache
+    }
+
     fn peer_timeout(&self) -> Duration {
         self.get_from_peer_timeout
     }
@@ -239,6 +314,14 @@ impl ItemFetcher<BlockByHeight> for Fetcher<BlockByHeight> {
         &mut self.responders
     }
 
+    fn in_flight(&mut self) -> &mut HashMap<u64, NodeId> {
+        &mut self.in_flight
+    }
+
+    fn cache(&mut self) -> &mut FetchCache<BlockByHeight> {
+        &mut self.cache
+    }
+
     fn peer_timeout(&self) -> Duration {
         self.get_from_peer_timeout
     }
@@ -302,8 +385,12 @@ where
                     }
                 }
             }
-            Event::AbsentRemotely { id, peer } => self.signal(id, None, peer),
-            Event::TimeoutPeer { id, peer } => self.signal(id, None, peer),
+            Event::AbsentRemotely { id, peer } => self.fetch_failed(effect_builder, id, peer),
+            Event::TimeoutPeer { id, peer } => self.fetch_failed(effect_builder, id, peer),
+            Event::SetPeerTimeout(timeout) => {
+                self.get_from_peer_timeout = timeout;
+                Effects::new()
+            }
         }
     }
 }
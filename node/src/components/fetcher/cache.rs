@@ -0,0 +1,77 @@
+use linked_hash_map::LinkedHashMap;
+
+use super::FetchResult;
+use crate::types::Item;
+
+/// A bounded cache of recently fetched items, evicting the least recently used ones first.
+#[derive(Debug)]
+pub struct FetchCache<T: Item> {
+    results: LinkedHashMap<T::Id, FetchResult<T>>,
+    capacity: usize,
+}
+
+impl<T: Item> FetchCache<T> {
+    /// Creates a cache holding up to `capacity` items.  A capacity of 0 disables it.
+    pub(crate) fn new(capacity: usize) -> Self {
+        FetchCache {
+            results: LinkedHashMap::new(),
+            capacity,
+        }
+    }
+
+    /// Returns the cached result of fetching the given item, if any, marking it as most recently
+    /// used.
+    pub(crate) fn get(&mut self, id: &T::Id) -> Option<FetchResult<T>> {
+        self.results.get_refresh(id).map(|result| result.clone())
+    }
+
+    /// Caches the result of fetching the given item, evicting the least recently used items if
+    /// the cache is full.
+    pub(crate) fn insert(&mut self, id: T::Id, result: FetchResult<T>) {
+        if self.capacity == 0 {
+            return;
+        }
+        let _ = self.results.insert(id, result);
+        while self.results.len() > self.capacity {
+            let _ = self.results.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Deploy;
+
+    #[test]
+    fn should_evict_least_recently_used() {
+        let mut rng = crate::new_rng();
+        let deploys: Vec<Deploy> = (0..3).map(|_| Deploy::random(&mut rng)).collect();
+        let result = |deploy: &Deploy| FetchResult::FromStorage(Box::new(deploy.clone()));
+
+        let mut cache = FetchCache::new(2);
+        cache.insert(*deploys[0].id(), result(&deploys[0]));
+        cache.insert(*deploys[1].id(), result(&deploys[1]));
+
+        // Using the first deploy makes the second one the least recently used.
+        assert_eq!(cache.get(deploys[0].id()), Some(result(&deploys[0])));
+        cache.insert(*deploys[2].id(), result(&deploys[2]));
+
+        assert_eq!(cache.get(deploys[0].id()), Some(result(&deploys[0])));
+        assert_eq!(cache.get(deploys[1].id()), None);
+        assert_eq!(cache.get(deploys[2].id()), Some(result(&deploys[2])));
+    }
+
+    #[test]
+    fn should_not_cache_if_disabled() {
+        let mut rng = crate::new_rng();
+        let deploy = Deploy::random(&mut rng);
+
+        let mut cache = FetchCache::new(0);
+        cache.insert(
+            *deploy.id(),
+            FetchResult::FromStorage(Box::new(deploy.clone())),
+        );
+        assert_eq!(cache.get(deploy.id()), None);
+    }
+}
@@ -2,23 +2,37 @@ use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
 const DEFAULT_GET_FROM_PEER_TIMEOUT_SECS: u64 = 3;
+const DEFAULT_CACHE_SIZE: usize = 100;
 
 /// Configuration options for fetching.
 #[derive(Copy, Clone, DataSize, Debug, Deserialize, Serialize)]
 pub struct Config {
     get_from_peer_timeout: u64,
+    /// The number of recently fetched items to keep, so that they are returned without fetching
+    /// them again if requested once more.  0 disables the cache.
+    #[serde(default = "default_cache_size")]
+    cache_size: usize,
+}
+
+fn default_cache_size() -> usize {
+    DEFAULT_CACHE_SIZE
 }
 
 impl Config {
     pub(crate) fn get_from_peer_timeout(&self) -> u64 {
         self.get_from_peer_timeout
     }
+
+    pub(crate) fn cache_size(&self) -> usize {
+        self.cache_size
+    }
 }
 
 impl Default for Config {
     fn default() -> Self {
         Config {
             get_from_peer_timeout: DEFAULT_GET_FROM_PEER_TIMEOUT_SECS,
+            cache_size: DEFAULT_CACHE_SIZE,
         }
     }
 }
@@ -1,4 +1,7 @@
-use std::fmt::{self, Display, Formatter};
+use std::{
+    fmt::{self, Display, Formatter},
+    time::Duration,
+};
 
 use super::Item;
 use crate::{
@@ -41,6 +44,8 @@ pub enum Event<T: Item> {
     AbsentRemotely { id: T::Id, peer: NodeId },
     /// The timeout has elapsed and we should clean up state.
     TimeoutPeer { id: T::Id, peer: NodeId },
+    /// The node operator requested a new timeout for getting an item from a peer.
+    SetPeerTimeout(Duration),
 }
 
 impl<T: Item> From<FetcherRequest<NodeId, T>> for Event<T> {
@@ -81,6 +86,9 @@ impl<T: Item> Display for Event<T> {
             Event::AbsentRemotely { id, peer } => {
                 write!(formatter, "Item {} was not available on {}", id, peer)
             }
+            Event::SetPeerTimeout(timeout) => {
+                write!(formatter, "set get from peer timeout to {:?}", timeout)
+            }
         }
     }
 }
@@ -5,7 +5,7 @@ use std::{
 };
 
 use derive_more::From;
-use futures::FutureExt;
+use futures::{channel::oneshot, FutureExt};
 use prometheus::Registry;
 use serde::Serialize;
 use tempfile::TempDir;
@@ -23,15 +23,16 @@ use crate::{
     effect::{
         announcements::{DeployAcceptorAnnouncement, NetworkAnnouncement, RpcServerAnnouncement},
         requests::FetcherRequest,
+        Responder,
     },
     protocol::Message,
-    reactor::{self, EventQueueHandle, Runner},
+    reactor::{self, EventQueueHandle, QueueKind, Runner, Scheduler},
     testing::{
         network::{Network, NetworkedReactor},
         ConditionCheckReactor, TestRng,
     },
     types::{Deploy, DeployHash, NodeId, Tag},
-    utils::{Loadable, WithDir},
+    utils::{self, Loadable, VerificationPool, WithDir},
     FetcherConfig, NodeRng,
 };
 
@@ -115,16 +116,20 @@ impl reactor::Reactor for Reactor {
 
     fn new(
         config: Self::Config,
-        _registry: &Registry,
+        registry: &Registry,
         event_queue: EventQueueHandle<Self::Event>,
         rng: &mut NodeRng,
     ) -> Result<(Self, Effects<Self::Event>), Self::Error> {
         let network = NetworkController::create_node(event_queue, rng);
 
         let (storage_config, _storage_tempdir) = storage::Config::default_for_tests();
-        let storage = Storage::new(&WithDir::new(_storage_tempdir.path(), storage_config)).unwrap();
+        let storage = Storage::new(
+            &WithDir::new(_storage_tempdir.path(), storage_config),
+            registry,
+        )
+        .unwrap();
 
-        let deploy_acceptor = DeployAcceptor::new();
+        let deploy_acceptor = DeployAcceptor::new(VerificationPool::new(1, registry).unwrap());
         let deploy_fetcher = Fetcher::<Deploy>::new(config);
 
         let reactor = Reactor {
@@ -256,6 +261,9 @@ impl reactor::Reactor for Reactor {
                 };
                 self.dispatch_event(effect_builder, rng, Event::DeployAcceptor(event))
             }
+            Event::RpcServerAnnouncement(RpcServerAnnouncement::AdminCommand(_)) => {
+                unreachable!("should not receive announcements of type AdminCommand");
+            }
             Event::DeployAcceptorAnnouncement(DeployAcceptorAnnouncement::AcceptedNewDeploy {
                 deploy,
                 source,
@@ -547,3 +555,167 @@ async fn should_timeout_fetch_from_peer() {
 
     NetworkController::<Message>::remove_active();
 }
+
+/// A fetcher driven directly by the test, whose effects are run without a reactor, so that the
+/// storage and network requests it makes can be inspected.
+struct FetcherHarness {
+    fetcher: Fetcher<Deploy>,
+    scheduler: &'static Scheduler<Event>,
+    effect_builder: EffectBuilder<Event>,
+    rng: TestRng,
+}
+
+impl FetcherHarness {
+    fn new() -> Self {
+        let scheduler = utils::leak(Scheduler::<Event>::new(QueueKind::weights()));
+        let effect_builder = EffectBuilder::new(EventQueueHandle::new(scheduler));
+        FetcherHarness {
+            fetcher: Fetcher::new(FetcherConfig::default()),
+            scheduler,
+            effect_builder,
+            rng: crate::new_rng(),
+        }
+    }
+
+    /// Handles `event`, then runs the resulting effects and returns the requests they made.  The
+    /// requests must be kept alive while the effects await their responses.
+    async fn handle_event(&mut self, event: super::Event<Deploy>) -> Vec<Event> {
+        let effects = self
+            .fetcher
+            .handle_event(self.effect_builder, &mut self.rng, event);
+        for effect in effects {
+            tokio::spawn(effect);
+        }
+        // Give the spawned effects the chance to schedule their requests.
+        time::delay_for(Duration::from_millis(10)).await;
+        let mut requests = Vec::new();
+        while self.scheduler.item_count() > 0 {
+            requests.push(self.scheduler.pop().await.0);
+        }
+        requests
+    }
+
+    /// Asks the fetcher for `id` from `peer`, returning the requests made and the receiver of the
+    /// fetch's result.
+    async fn fetch(
+        &mut self,
+        id: DeployHash,
+        peer: &NodeId,
+    ) -> (Vec<Event>, oneshot::Receiver<Option<FetchResult<Deploy>>>) {
+        let (sender, receiver) = oneshot::channel();
+        let event = super::Event::Fetch {
+            id,
+            peer: peer.clone(),
+            responder: Responder::create(sender),
+        };
+        (self.handle_event(event).await, receiver)
+    }
+}
+
+fn is_storage_request(event: &Event) -> bool {
+    matches!(event, Event::Storage(storage::Event::StorageRequest(_)))
+}
+
+fn is_get_request_to(event: &Event, peer: &NodeId) -> bool {
+    matches!(
+        event,
+        Event::NetworkRequest(NetworkRequest::SendMessage {
+            dest,
+            payload: Message::GetRequest { .. },
+            ..
+        }) if dest == peer
+    )
+}
+
+#[tokio::test]
+async fn should_coalesce_concurrent_fetches_of_an_item() {
+    let mut harness = FetcherHarness::new();
+    let deploy = Deploy::random(&mut harness.rng);
+    let deploy_hash = *deploy.id();
+    let peer_a = NodeId::random(&mut harness.rng);
+    let peer_b = NodeId::random(&mut harness.rng);
+
+    // Only the first fetch checks storage; the others join it.
+    let (requests, receiver_1) = harness.fetch(deploy_hash, &peer_a).await;
+    assert_eq!(requests.len(), 1);
+    assert!(is_storage_request(&requests[0]));
+    let (requests, receiver_2) = harness.fetch(deploy_hash, &peer_b).await;
+    assert!(requests.is_empty());
+    let (requests, receiver_3) = harness.fetch(deploy_hash, &peer_a).await;
+    assert!(requests.is_empty());
+
+    // The item is missing from storage, so it's requested from the first peer only.
+    let requests = harness
+        .handle_event(super::Event::GetFromStorageResult {
+            id: deploy_hash,
+            peer: peer_a.clone(),
+            maybe_item: Box::new(None),
+        })
+        .await;
+    assert_eq!(requests.len(), 1);
+    assert!(is_get_request_to(&requests[0], &peer_a));
+
+    // The single response answers every responder.
+    let requests = harness
+        .handle_event(super::Event::GotRemotely {
+            item: Box::new(deploy.clone()),
+            source: Source::Peer(peer_a.clone()),
+        })
+        .await;
+    assert!(requests.is_empty());
+    let expected = Some(FetchResult::FromPeer(Box::new(deploy), peer_a.clone()));
+    for receiver in vec![receiver_1, receiver_2, receiver_3] {
+        assert_eq!(receiver.await.unwrap(), expected);
+    }
+
+    // A later fetch is answered from the cache without any request.
+    let (requests, receiver_4) = harness.fetch(deploy_hash, &peer_b).await;
+    assert!(requests.is_empty());
+    assert_eq!(receiver_4.await.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn should_fall_back_to_next_peer_on_absence_or_timeout() {
+    let mut harness = FetcherHarness::new();
+    let deploy_hash = *Deploy::random(&mut harness.rng).id();
+    let peer_a = NodeId::random(&mut harness.rng);
+    let peer_b = NodeId::random(&mut harness.rng);
+
+    let (_storage_requests, mut receiver_a) = harness.fetch(deploy_hash, &peer_a).await;
+    let (_, mut receiver_b) = harness.fetch(deploy_hash, &peer_b).await;
+    let requests = harness
+        .handle_event(super::Event::GetFromStorageResult {
+            id: deploy_hash,
+            peer: peer_a.clone(),
+            maybe_item: Box::new(None),
+        })
+        .await;
+    assert_eq!(requests.len(), 1);
+    assert!(is_get_request_to(&requests[0], &peer_a));
+
+    // The first peer doesn't have the item: its responder gets `None`, and the item is requested
+    // from the next peer without checking storage again.
+    let requests = harness
+        .handle_event(super::Event::AbsentRemotely {
+            id: deploy_hash,
+            peer: peer_a.clone(),
+        })
+        .await;
+    assert_eq!(requests.len(), 1);
+    assert!(is_get_request_to(&requests[0], &peer_b));
+    assert_eq!(receiver_a.try_recv().unwrap(), Some(None));
+    assert_eq!(receiver_b.try_recv().unwrap(), None);
+    assert_eq!(harness.fetcher.in_flight.get(&deploy_hash), Some(&peer_b));
+
+    // The next peer times out: with no peers left, the fetch is abandoned.
+    let requests = harness
+        .handle_event(super::Event::TimeoutPeer {
+            id: deploy_hash,
+            peer: peer_b.clone(),
+        })
+        .await;
+    assert!(requests.is_empty());
+    assert_eq!(receiver_b.try_recv().unwrap(), Some(None));
+    assert!(harness.fetcher.in_flight.is_empty());
+    assert!(harness.fetcher.responders.is_empty());
+}
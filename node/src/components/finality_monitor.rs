@@ -0,0 +1,446 @@
+//! Finality monitor.
+//!
+//! The finality monitor tracks how far finalization lags behind the current time, how long
+//! transitions between eras take, and whether proto blocks are proposed more than once.  Its
+//! observations are exposed as metrics, and announced so that they can be pushed to event stream
+//! clients.
+//!
+//! The finalization lag is the time elapsed since the timestamp of the most recently finalized
+//! block.  When it exceeds the configured threshold, an alert is raised: it is announced, and the
+//! configured alert command is run and the configured alert webhook is sent the details of the
+//! alert.  No further alert is raised until a block has been finalized within the threshold again.
+
+mod config;
+mod event;
+mod metrics;
+
+use std::{
+    collections::{HashSet, VecDeque},
+    convert::Infallible,
+    process::Command,
+    time::Duration,
+};
+
+use datasize::DataSize;
+use prometheus::Registry;
+use reqwest::{blocking::Client, header::CONTENT_TYPE};
+use serde::Serialize;
+use tokio::task;
+use tracing::{info, warn};
+
+use crate::{
+    components::{consensus::EraId, Component},
+    effect::{announcements::FinalityMonitorAnnouncement, EffectBuilder, EffectExt, Effects},
+    types::{BlockHeader, FinalizedBlock, ProtoBlock, ProtoBlockHash, TimeDiff, Timestamp},
+    NodeRng,
+};
+
+pub use config::Config;
+pub(crate) use event::Event;
+use metrics::FinalityMonitorMetrics;
+
+/// The number of proposed proto blocks remembered in order to detect re-proposals.
+const MAX_TRACKED_PROPOSALS: usize = 1_000;
+
+/// The time allowed for the alert webhook to respond.
+const ALERT_WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A helper trait whose bounds represent the requirements for a reactor event that the finality
+/// monitor can work with.
+pub(crate) trait ReactorEventT:
+    From<Event> + From<FinalityMonitorAnnouncement> + Send + 'static
+{
+}
+
+impl<REv> ReactorEventT for REv where
+    REv: From<Event> + From<FinalityMonitorAnnouncement> + Send + 'static
+{
+}
+
+/// The details of a finalization lag alert, as passed to the alert command and webhook.
+#[derive(Clone, Debug, Serialize)]
+struct FinalizationLagAlert {
+    /// The height of the most recently finalized block.
+    block_height: u64,
+    /// The timestamp of the most recently finalized block.
+    block_timestamp: Timestamp,
+    /// The time in milliseconds elapsed since the block's timestamp.
+    lag_millis: u64,
+}
+
+#[derive(DataSize, Debug)]
+pub(crate) struct FinalityMonitor {
+    #[data_size(skip)]
+    metrics: FinalityMonitorMetrics,
+    config: Config,
+    /// The height and timestamp of the most recently finalized block.
+    last_finalized: Option<(u64, Timestamp)>,
+    /// The era and timestamp of the most recently finalized switch block, until the first block of
+    /// the following era is finalized.
+    last_switch_block: Option<(EraId, Timestamp)>,
+    /// The hashes of the most recently proposed non-empty proto blocks.
+    proposed: HashSet<ProtoBlockHash>,
+    /// The hashes in `proposed`, oldest first.
+    proposal_order: VecDeque<ProtoBlockHash>,
+    /// Whether an alert has been raised for the current episode of excessive lag.
+    alert_raised: bool,
+}
+
+impl FinalityMonitor {
+    /// Creates a new finality monitor, taking the header of the highest block already stored, if
+    /// any, as the most recently finalized block.
+    pub(crate) fn new<REv>(
+        config: Config,
+        registry: &Registry,
+        effect_builder: EffectBuilder<REv>,
+        maybe_highest_block: Option<&BlockHeader>,
+    ) -> Result<(Self, Effects<Event>), prometheus::Error>
+    where
+        REv: ReactorEventT,
+    {
+        let metrics = FinalityMonitorMetrics::new(registry)?;
+        let effects = effect_builder
+            .set_timeout(config.check_interval)
+            .event(|_| Event::CheckLag);
+        let finality_monitor = FinalityMonitor {
+            metrics,
+            config,
+            last_finalized: maybe_highest_block.map(|header| (header.height(), header.timestamp())),
+            last_switch_block: None,
+            proposed: HashSet::new(),
+            proposal_order: VecDeque::new(),
+            alert_raised: false,
+        };
+        Ok((finality_monitor, effects))
+    }
+
+    /// Records a proposed proto block, returning an announcement if it was proposed before.
+    ///
+    /// Empty proto blocks are ignored, as all of them with the same random bit share a hash.
+    fn observe_proposal(
+        &mut self,
+        proto_block: &ProtoBlock,
+    ) -> Option<FinalityMonitorAnnouncement> {
+        if proto_block.deploys().is_empty() {
+            return None;
+        }
+        let proto_block_hash = *proto_block.hash();
+        if !self.proposed.insert(proto_block_hash) {
+            self.metrics.block_reproposals.inc();
+            info!(%proto_block_hash, "proto block reproposed");
+            return Some(FinalityMonitorAnnouncement::BlockReproposed(
+                proto_block_hash,
+            ));
+        }
+        self.proposal_order.push_back(proto_block_hash);
+        if self.proposal_order.len() > MAX_TRACKED_PROPOSALS {
+            if let Some(oldest) = self.proposal_order.pop_front() {
+                self.proposed.remove(&oldest);
+            }
+        }
+        None
+    }
+
+    /// Records a finalized block, returning an announcement if it is the first block of a new era.
+    fn observe_finalized(
+        &mut self,
+        finalized_block: &FinalizedBlock,
+        now: Timestamp,
+    ) -> Option<FinalityMonitorAnnouncement> {
+        let era_id = finalized_block.era_id();
+        let timestamp = finalized_block.timestamp();
+
+        let mut maybe_announcement = None;
+        if let Some((switch_block_era_id, switch_block_timestamp)) = self.last_switch_block {
+            if era_id > switch_block_era_id {
+                let duration = timestamp.saturating_sub(switch_block_timestamp);
+                self.metrics
+                    .era_transition_duration
+                    .set(duration.millis() as i64);
+                self.last_switch_block = None;
+                maybe_announcement =
+                    Some(FinalityMonitorAnnouncement::EraTransition { era_id, duration });
+            }
+        }
+        if finalized_block.era_end().is_some() {
+            self.last_switch_block = Some((era_id, timestamp));
+        }
+
+        self.last_finalized = Some((finalized_block.height(), timestamp));
+        let lag = now.saturating_sub(timestamp);
+        self.metrics.finalization_lag.set(lag.millis() as i64);
+        if self.alert_raised && lag <= self.lag_alert_threshold() {
+            info!(%lag, "finalization lag back within alert threshold");
+            self.alert_raised = false;
+        }
+
+        maybe_announcement
+    }
+
+    /// Updates the finalization lag, returning an alert if it newly exceeded the threshold.
+    fn check_lag(&mut self, now: Timestamp) -> Option<FinalizationLagAlert> {
+        let (block_height, block_timestamp) = self.last_finalized?;
+        let lag = now.saturating_sub(block_timestamp);
+        self.metrics.finalization_lag.set(lag.millis() as i64);
+        if self.alert_raised || lag <= self.lag_alert_threshold() {
+            return None;
+        }
+
+        warn!(%lag, %block_height, "finalization lag exceeded alert threshold");
+        self.metrics.finalization_lag_alerts.inc();
+        self.alert_raised = true;
+        Some(FinalizationLagAlert {
+            block_height,
+            block_timestamp,
+            lag_millis: lag.millis(),
+        })
+    }
+
+    fn lag_alert_threshold(&self) -> TimeDiff {
+        TimeDiff::from(self.config.lag_alert_threshold)
+    }
+
+    /// Runs the configured alert command and posts to the configured alert webhook.
+    fn run_alert_hooks(&self, alert: FinalizationLagAlert) -> Effects<Event> {
+        let maybe_command = self.config.alert_command.clone();
+        let maybe_webhook = self.config.alert_webhook.clone();
+        if maybe_command.is_none() && maybe_webhook.is_none() {
+            return Effects::new();
+        }
+
+        async move {
+            let result = task::spawn_blocking(move || {
+                if let Some(command) = maybe_command {
+                    match Command::new(&command)
+                        .env("CASPER_ALERT", "finalization_lag")
+                        .env("CASPER_BLOCK_HEIGHT", alert.block_height.to_string())
+                        .env("CASPER_BLOCK_TIMESTAMP", alert.block_timestamp.to_string())
+                        .env("CASPER_FINALIZATION_LAG_MS", alert.lag_millis.to_string())
+                        .status()
+                    {
+                        Ok(status) if status.success() => (),
+                        Ok(status) => warn!(%command, %status, "alert command failed"),
+                        Err(error) => warn!(%command, %error, "failed to run alert command"),
+                    }
+                }
+
+                if let Some(webhook) = maybe_webhook {
+                    let body = match serde_json::to_vec(&alert) {
+                        Ok(body) => body,
+                        Err(error) => {
+                            warn!(%error, "failed to encode alert");
+                            return;
+                        }
+                    };
+                    let result = Client::builder()
+                        .timeout(ALERT_WEBHOOK_TIMEOUT)
+                        .build()
+                        .and_then(|client| {
+                            client
+                                .post(&webhook)
+                                .header(CONTENT_TYPE, "application/json")
+                                .body(body)
+                                .send()
+                        })
+                        .and_then(|response| response.error_for_status());
+                    if let Err(error) = result {
+                        warn!(%webhook, %error, "failed to post alert to webhook");
+                    }
+                }
+            })
+            .await;
+            if let Err(error) = result {
+                warn!(%error, "alert hook task failed");
+            }
+        }
+        .ignore()
+    }
+}
+
+impl<REv> Component<REv> for FinalityMonitor
+where
+    REv: ReactorEventT,
+{
+    type Event = Event;
+    type ConstructionError = Infallible;
+
+    fn handle_event(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        _rng: &mut NodeRng,
+        event: Self::Event,
+    ) -> Effects<Self::Event> {
+        let maybe_announcement = match event {
+            Event::ProtoBlockProposed(proto_block) => self.observe_proposal(&proto_block),
+            Event::BlockFinalized(finalized_block) => {
+                self.observe_finalized(&finalized_block, Timestamp::now())
+            }
+            Event::CheckLag => {
+                let mut effects = effect_builder
+                    .set_timeout(self.config.check_interval)
+                    .event(|_| Event::CheckLag);
+                if let Some(alert) = self.check_lag(Timestamp::now()) {
+                    effects.extend(
+                        effect_builder
+                            .announce_finality_observation(
+                                FinalityMonitorAnnouncement::FinalizationLagExceeded {
+                                    block_height: alert.block_height,
+                                    block_timestamp: alert.block_timestamp,
+                                    lag: TimeDiff::from(alert.lag_millis),
+                                },
+                            )
+                            .ignore(),
+                    );
+                    effects.extend(self.run_alert_hooks(alert));
+                }
+                return effects;
+            }
+        };
+
+        match maybe_announcement {
+            Some(announcement) => effect_builder
+                .announce_finality_observation(announcement)
+                .ignore(),
+            None => Effects::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        crypto::{asymmetric_key::PublicKey, hash::Digest},
+        reactor::{EventQueueHandle, QueueKind, Scheduler},
+        testing::TestRng,
+        types::{DeployHash, EraEnd},
+        utils,
+    };
+
+    #[derive(Debug)]
+    enum TestEvent {
+        FinalityMonitor,
+        Announcement,
+    }
+
+    impl From<Event> for TestEvent {
+        fn from(_: Event) -> Self {
+            TestEvent::FinalityMonitor
+        }
+    }
+
+    impl From<FinalityMonitorAnnouncement> for TestEvent {
+        fn from(_: FinalityMonitorAnnouncement) -> Self {
+            TestEvent::Announcement
+        }
+    }
+
+    fn new_finality_monitor(lag_alert_threshold: Duration) -> FinalityMonitor {
+        let scheduler = utils::leak(Scheduler::<TestEvent>::new(QueueKind::weights()));
+        let effect_builder = EffectBuilder::new(EventQueueHandle::new(scheduler));
+        let config = Config {
+            lag_alert_threshold,
+            ..Config::default()
+        };
+        let (finality_monitor, _effects) =
+            FinalityMonitor::new(config, &Registry::new(), effect_builder, None)
+                .expect("should create finality monitor");
+        finality_monitor
+    }
+
+    fn finalized_block(
+        rng: &mut TestRng,
+        era_id: u64,
+        height: u64,
+        timestamp: u64,
+        switch_block: bool,
+    ) -> FinalizedBlock {
+        let era_end = if switch_block {
+            Some(EraEnd {
+                equivocators: vec![],
+                rewards: Default::default(),
+            })
+        } else {
+            None
+        };
+        FinalizedBlock::new(
+            ProtoBlock::new(vec![], false),
+            Timestamp::from(timestamp),
+            era_end,
+            EraId(era_id),
+            height,
+            PublicKey::random(rng),
+        )
+    }
+
+    #[test]
+    fn should_detect_reproposal() {
+        let mut rng = TestRng::new();
+        let mut finality_monitor = new_finality_monitor(Duration::from_secs(60));
+        let proto_block = ProtoBlock::new(vec![DeployHash::new(Digest::random(&mut rng))], false);
+
+        assert!(finality_monitor.observe_proposal(&proto_block).is_none());
+        assert!(matches!(
+            finality_monitor.observe_proposal(&proto_block),
+            Some(FinalityMonitorAnnouncement::BlockReproposed(hash)) if hash == *proto_block.hash()
+        ));
+
+        // Empty proto blocks are never reported.
+        let empty_proto_block = ProtoBlock::new(vec![], false);
+        assert!(finality_monitor
+            .observe_proposal(&empty_proto_block)
+            .is_none());
+        assert!(finality_monitor
+            .observe_proposal(&empty_proto_block)
+            .is_none());
+    }
+
+    #[test]
+    fn should_measure_era_transition() {
+        let mut rng = TestRng::new();
+        let mut finality_monitor = new_finality_monitor(Duration::from_secs(60));
+        let now = Timestamp::from(10_000);
+
+        let switch_block = finalized_block(&mut rng, 1, 10, 1_000, true);
+        assert!(finality_monitor
+            .observe_finalized(&switch_block, now)
+            .is_none());
+
+        let first_block = finalized_block(&mut rng, 2, 11, 1_500, false);
+        assert!(matches!(
+            finality_monitor.observe_finalized(&first_block, now),
+            Some(FinalityMonitorAnnouncement::EraTransition { era_id, duration })
+                if era_id == EraId(2) && duration == TimeDiff::from(500)
+        ));
+
+        let second_block = finalized_block(&mut rng, 2, 12, 2_000, false);
+        assert!(finality_monitor
+            .observe_finalized(&second_block, now)
+            .is_none());
+    }
+
+    #[test]
+    fn should_alert_once_per_episode_of_excessive_lag() {
+        let mut rng = TestRng::new();
+        let mut finality_monitor = new_finality_monitor(Duration::from_millis(1_000));
+
+        // No alert before any block is finalized.
+        assert!(finality_monitor.check_lag(Timestamp::from(5_000)).is_none());
+
+        let block = finalized_block(&mut rng, 0, 1, 1_000, false);
+        finality_monitor.observe_finalized(&block, Timestamp::from(1_500));
+        assert!(finality_monitor.check_lag(Timestamp::from(1_800)).is_none());
+
+        let alert = finality_monitor
+            .check_lag(Timestamp::from(2_500))
+            .expect("should raise alert");
+        assert_eq!(alert.block_height, 1);
+        assert_eq!(alert.lag_millis, 1_500);
+        assert!(finality_monitor.check_lag(Timestamp::from(3_000)).is_none());
+
+        // Once a block is finalized within the threshold, a new alert can be raised.
+        let block = finalized_block(&mut rng, 0, 2, 3_000, false);
+        finality_monitor.observe_finalized(&block, Timestamp::from(3_100));
+        assert!(finality_monitor.check_lag(Timestamp::from(4_500)).is_some());
+    }
+}
@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+
+/// Default interval between checks of the finalization lag.
+const DEFAULT_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Default finalization lag beyond which an alert is raised.
+const DEFAULT_LAG_ALERT_THRESHOLD: Duration = Duration::from_secs(120);
+
+/// Configuration options for the finality monitor.
+#[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Interval in milliseconds between checks of the finalization lag.
+    #[serde(with = "crate::utils::milliseconds")]
+    pub check_interval: Duration,
+
+    /// Finalization lag in milliseconds beyond which an alert is raised.  The lag is the time
+    /// elapsed since the timestamp of the most recently finalized block.
+    #[serde(with = "crate::utils::milliseconds")]
+    pub lag_alert_threshold: Duration,
+
+    /// Path to an executable run when an alert is raised.  The details of the alert are passed in
+    /// environment variables.
+    #[serde(default)]
+    pub alert_command: Option<String>,
+
+    /// URL to which the details of an alert are posted as a JSON object when it is raised.
+    #[serde(default)]
+    pub alert_webhook: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            check_interval: DEFAULT_CHECK_INTERVAL,
+            lag_alert_threshold: DEFAULT_LAG_ALERT_THRESHOLD,
+            alert_command: None,
+            alert_webhook: None,
+        }
+    }
+}
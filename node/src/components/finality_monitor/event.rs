@@ -0,0 +1,28 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::types::{FinalizedBlock, ProtoBlock};
+
+/// An event for the finality monitor.
+#[derive(Debug)]
+pub enum Event {
+    /// A proto block has been proposed.
+    ProtoBlockProposed(ProtoBlock),
+    /// A block has been finalized.
+    BlockFinalized(Box<FinalizedBlock>),
+    /// The finalization lag should be checked.
+    CheckLag,
+}
+
+impl Display for Event {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Event::ProtoBlockProposed(proto_block) => {
+                write!(formatter, "proto block proposed {}", proto_block)
+            }
+            Event::BlockFinalized(finalized_block) => {
+                write!(formatter, "block finalized {}", finalized_block)
+            }
+            Event::CheckLag => write!(formatter, "check finalization lag"),
+        }
+    }
+}
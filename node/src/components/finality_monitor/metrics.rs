@@ -0,0 +1,70 @@
+use prometheus::{IntCounter, IntGauge, Registry};
+
+/// Metrics for the finality monitor.
+#[derive(Debug)]
+pub struct FinalityMonitorMetrics {
+    /// Time in milliseconds elapsed since the timestamp of the most recently finalized block.
+    pub(super) finalization_lag: IntGauge,
+    /// Time in milliseconds between the most recent switch block and the first block of the
+    /// following era.
+    pub(super) era_transition_duration: IntGauge,
+    /// Number of proto blocks proposed again after having been proposed before.
+    pub(super) block_reproposals: IntCounter,
+    /// Number of alerts raised due to excessive finalization lag.
+    pub(super) finalization_lag_alerts: IntCounter,
+    /// Reference to the registry for unregistering.
+    registry: Registry,
+}
+
+impl FinalityMonitorMetrics {
+    /// Creates a new instance of the finality monitor metrics.
+    pub fn new(registry: &Registry) -> Result<Self, prometheus::Error> {
+        let finalization_lag = IntGauge::new(
+            "finalization_lag",
+            "time in milliseconds elapsed since the timestamp of the most recently finalized block",
+        )?;
+        let era_transition_duration = IntGauge::new(
+            "era_transition_duration",
+            "time in milliseconds between the most recent switch block and the first block of the \
+            following era",
+        )?;
+        let block_reproposals = IntCounter::new(
+            "block_reproposals",
+            "number of proto blocks proposed again after having been proposed before",
+        )?;
+        let finalization_lag_alerts = IntCounter::new(
+            "finalization_lag_alerts",
+            "number of alerts raised due to excessive finalization lag",
+        )?;
+
+        registry.register(Box::new(finalization_lag.clone()))?;
+        registry.register(Box::new(era_transition_duration.clone()))?;
+        registry.register(Box::new(block_reproposals.clone()))?;
+        registry.register(Box::new(finalization_lag_alerts.clone()))?;
+
+        Ok(FinalityMonitorMetrics {
+            finalization_lag,
+            era_transition_duration,
+            block_reproposals,
+            finalization_lag_alerts,
+            registry: registry.clone(),
+        })
+    }
+}
+
+impl Drop for FinalityMonitorMetrics {
+    fn drop(&mut self) {
+        self.registry
+            .unregister(Box::new(self.finalization_lag.clone()))
+            .expect("did not expect deregistering finalization_lag to fail");
+        self.registry
+            .unregister(Box::new(self.era_transition_duration.clone()))
+            .expect("did not expect deregistering era_transition_duration to fail");
+        self.registry
+            .unregister(Box::new(self.block_reproposals.clone()))
+            .expect("did not expect deregistering block_reproposals to fail");
+        self.registry
+            .unregister(Box::new(self.finalization_lag_alerts.clone()))
+            .expect("did not expect deregistering finalization_lag_alerts to fail");
+    }
+}
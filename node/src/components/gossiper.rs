@@ -107,6 +107,11 @@ where
     #[data_size(skip)] // Not well supported by datasize.
     get_from_holder:
         Box<dyn Fn(EffectBuilder<REv>, T::Id, NodeId) -> Effects<Event<T>> + Send + 'static>,
+    /// See `Config::max_current_entries`.
+    max_current_entries: usize,
+    /// Whether the gossiper exceeds its memory limit, in which case peers gossiping new items are
+    /// answered as busy.
+    under_memory_pressure: bool,
     #[data_size(skip)]
     metrics: GossiperMetrics,
 }
@@ -141,6 +146,8 @@ impl<T: Item + 'static, REv: ReactorEventT<T>> Gossiper<T, REv> {
             gossip_timeout: Duration::from_secs(config.gossip_request_timeout_secs()),
             get_from_peer_timeout: Duration::from_secs(config.get_remainder_timeout_secs()),
             get_from_holder: Box::new(get_from_holder),
+            max_current_entries: config.max_current_entries(),
+            under_memory_pressure: false,
             metrics: GossiperMetrics::new(name, registry)?,
         })
     }
@@ -166,6 +173,8 @@ impl<T: Item + 'static, REv: ReactorEventT<T>> Gossiper<T, REv> {
             get_from_holder: Box::new(|_, item, _| {
                 panic!("gossiper should never try to get {}", item)
             }),
+            max_current_entries: config.max_current_entries(),
+            under_memory_pressure: false,
             metrics: GossiperMetrics::new(name, registry)?,
         })
     }
@@ -179,6 +188,12 @@ impl<T: Item + 'static, REv: ReactorEventT<T>> Gossiper<T, REv> {
     ) -> Effects<Event<T>> {
         self.metrics.items_received.inc();
 
+        // Items received from clients or fetched from peers are gossiped even if there's no room
+        // to be made for them.
+        if !self.table.contains(&item_id) {
+            let _ = self.make_room();
+        }
+
         if let Some(should_gossip) = self.table.new_complete_data(&item_id, source.node_id()) {
             self.metrics.items_gossiped_onwards.inc();
             self.gossip(
@@ -307,12 +322,24 @@ impl<T: Item + 'static, REv: ReactorEventT<T>> Gossiper<T, REv> {
     }
 
     /// Handles an incoming gossip request from a peer on the network.
+    ///
+    /// If too busy to take on a new item, the peer is answered as busy.  Peers speaking the original
+    /// wire version can't decode that response, so the network doesn't send it to them; they gossip
+    /// the item to a different peer once their gossip timeout elapses instead.
     fn handle_gossip(
         &mut self,
         effect_builder: EffectBuilder<REv>,
         item_id: T::Id,
         sender: NodeId,
     ) -> Effects<Event<T>> {
+        if !self.table.contains(&item_id) && !self.make_room() {
+            self.metrics.busy_responses.inc();
+            debug!(%item_id, %sender, "too busy to gossip new item");
+            return effect_builder
+                .send_message(sender, Message::Busy(item_id))
+                .ignore();
+        }
+
         let action = if T::ID_IS_COMPLETE_ITEM {
             self.table
                 .new_complete_data(&item_id, Some(sender.clone()))
@@ -417,6 +444,32 @@ impl<T: Item + 'static, REv: ReactorEventT<T>> Gossiper<T, REv> {
         effects
     }
 
+    /// Handles an incoming busy response from a peer on the network, gossiping the item to a
+    /// different peer instead.
+    fn handle_busy(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        item_id: T::Id,
+        sender: NodeId,
+    ) -> Effects<Event<T>> {
+        debug!(%item_id, %sender, "peer too busy to gossip item");
+        self.check_gossip_timeout(effect_builder, item_id, sender)
+    }
+
+    /// Returns whether there is room to gossip a new item, stopping gossiping the oldest item held
+    /// in full if necessary.  There is none while under memory pressure.
+    fn make_room(&mut self) -> bool {
+        if self.under_memory_pressure {
+            return false;
+        }
+        if self.max_current_entries == 0 || self.table.items_current() < self.max_current_entries {
+            return true;
+        }
+        let shed = self.table.shed_current(self.max_current_entries - 1);
+        self.metrics.items_shed.inc_by(shed as i64);
+        shed > 0
+    }
+
     /// Handles the `Ok` case for a `Result` of attempting to get the item from the component
     /// responsible for holding it, in order to send it to the requester.
     fn got_from_holder(
@@ -446,7 +499,14 @@ impl<T: Item + 'static, REv: ReactorEventT<T>> Gossiper<T, REv> {
     }
 
     /// Updates the gossiper metrics from the state of the gossip table.
-    fn update_gossip_table_metrics(&self) {
+    fn update_gossip_table_metrics(&mut self) {
+        let evictions = self.table.take_evictions();
+        self.metrics
+            .table_items_expired
+            .inc_by(evictions.expired as i64);
+        self.metrics
+            .table_items_evicted
+            .inc_by(evictions.over_capacity as i64);
         self.metrics
             .table_items_current
             .set(self.table.items_current() as i64);
@@ -457,6 +517,28 @@ impl<T: Item + 'static, REv: ReactorEventT<T>> Gossiper<T, REv> {
             .table_items_paused
             .set(self.table.items_paused() as i64);
     }
+
+    /// Sheds load as the gossiper's estimated memory `usage` exceeds its `limit`, returning how
+    /// many items were dropped.
+    ///
+    /// Forgets the items which have finished gossiping, then stops gossiping the oldest items held
+    /// in full, assuming the usage is proportional to the number of items.  Until
+    /// `relieve_memory_pressure` is called, peers gossiping new items are answered as busy.
+    pub(crate) fn shed_load(&mut self, usage: u64, limit: u64) -> usize {
+        self.under_memory_pressure = true;
+        let items = self.table.items_current() + self.table.items_finished();
+        let max_items = (items as u128 * u128::from(limit) / u128::from(usage.max(1))) as usize;
+        let mut shed = self.table.shed_finished();
+        shed += self.table.shed_current(max_items);
+        self.metrics.items_shed.inc_by(shed as i64);
+        self.update_gossip_table_metrics();
+        shed
+    }
+
+    /// Resumes taking on new items from peers once the gossiper is back within its memory limit.
+    pub(crate) fn relieve_memory_pressure(&mut self) {
+        self.under_memory_pressure = false;
+    }
 }
 
 impl<T, REv> Component<REv> for Gossiper<T, REv>
@@ -492,6 +574,7 @@ where
                     item_id,
                     is_already_held,
                 } => self.handle_gossip_response(effect_builder, item_id, is_already_held, sender),
+                Message::Busy(item_id) => self.handle_busy(effect_builder, item_id, sender),
             },
             Event::GetFromHolderResult {
                 item_id,
@@ -501,6 +584,18 @@ where
                 Ok(item) => self.got_from_holder(effect_builder, item, requester),
                 Err(error) => self.failed_to_get_from_holder(item_id, error),
             },
+            Event::SetTimeouts {
+                gossip_timeout,
+                get_from_peer_timeout,
+            } => {
+                if let Some(gossip_timeout) = gossip_timeout {
+                    self.gossip_timeout = gossip_timeout;
+                }
+                if let Some(get_from_peer_timeout) = get_from_peer_timeout {
+                    self.get_from_peer_timeout = get_from_peer_timeout;
+                }
+                Effects::new()
+            }
         };
         self.update_gossip_table_metrics();
         effects
@@ -14,6 +14,10 @@ pub(super) const MAX_SATURATION_LIMIT_PERCENT: u8 = 99;
 pub(super) const DEFAULT_FINISHED_ENTRY_DURATION_SECS: u64 = 3_600;
 const DEFAULT_GOSSIP_REQUEST_TIMEOUT_SECS: u64 = 10;
 const DEFAULT_GET_REMAINDER_TIMEOUT_SECS: u64 = 60;
+const DEFAULT_MAX_CURRENT_ENTRIES: usize = 10_000;
+const DEFAULT_CURRENT_ENTRY_DURATION_SECS: u64 = 600;
+const DEFAULT_MAX_FINISHED_ENTRIES: usize = 100_000;
+const DEFAULT_MAX_PAUSED_ENTRIES: usize = 10_000;
 
 /// Configuration options for gossiping.
 #[derive(Copy, Clone, DataSize, Debug, Deserialize, Serialize)]
@@ -40,6 +44,39 @@ pub struct Config {
     /// The timeout duration in seconds for retrieving the remaining part(s) of newly-discovered
     /// data from a peer which gossiped information about that data to this node.
     get_remainder_timeout_secs: u64,
+    /// The maximum number of entries being gossiped at a time.  Once reached, the oldest entries
+    /// held in full stop being gossiped to make room for new ones, and if there are none, peers
+    /// gossiping new data are told this node is busy.  0 means unlimited.
+    #[serde(default = "default_max_current_entries")]
+    max_current_entries: usize,
+    /// The maximum duration in seconds for which to gossip an entry.  Entries which haven't
+    /// finished gossiping by then are treated as finished.  0 means unlimited.
+    #[serde(default = "default_current_entry_duration_secs")]
+    current_entry_duration_secs: u64,
+    /// The maximum number of finished entries to keep.  Once exceeded, the oldest ones are
+    /// forgotten before they time out.  0 means unlimited.
+    #[serde(default = "default_max_finished_entries")]
+    max_finished_entries: usize,
+    /// The maximum number of paused entries to keep.  Once exceeded, the oldest ones are forgotten
+    /// before they time out.  0 means unlimited.
+    #[serde(default = "default_max_paused_entries")]
+    max_paused_entries: usize,
+}
+
+fn default_max_current_entries() -> usize {
+    DEFAULT_MAX_CURRENT_ENTRIES
+}
+
+fn default_current_entry_duration_secs() -> u64 {
+    DEFAULT_CURRENT_ENTRY_DURATION_SECS
+}
+
+fn default_max_finished_entries() -> usize {
+    DEFAULT_MAX_FINISHED_ENTRIES
+}
+
+fn default_max_paused_entries() -> usize {
+    DEFAULT_MAX_PAUSED_ENTRIES
 }
 
 impl Config {
@@ -60,6 +97,10 @@ impl Config {
             finished_entry_duration_secs,
             gossip_request_timeout_secs,
             get_remainder_timeout_secs,
+            max_current_entries: DEFAULT_MAX_CURRENT_ENTRIES,
+            current_entry_duration_secs: DEFAULT_CURRENT_ENTRY_DURATION_SECS,
+            max_finished_entries: DEFAULT_MAX_FINISHED_ENTRIES,
+            max_paused_entries: DEFAULT_MAX_PAUSED_ENTRIES,
         })
     }
 
@@ -82,6 +123,36 @@ impl Config {
     pub(crate) fn get_remainder_timeout_secs(&self) -> u64 {
         self.get_remainder_timeout_secs
     }
+
+    pub(crate) fn max_current_entries(&self) -> usize {
+        self.max_current_entries
+    }
+
+    pub(crate) fn current_entry_duration_secs(&self) -> u64 {
+        self.current_entry_duration_secs
+    }
+
+    pub(crate) fn max_finished_entries(&self) -> usize {
+        self.max_finished_entries
+    }
+
+    pub(crate) fn max_paused_entries(&self) -> usize {
+        self.max_paused_entries
+    }
+
+    /// Returns a copy of this config with the given bounds on the gossip table's entries.
+    #[cfg(test)]
+    pub(crate) fn with_entry_bounds(
+        mut self,
+        current_entry_duration_secs: u64,
+        max_finished_entries: usize,
+        max_paused_entries: usize,
+    ) -> Self {
+        self.current_entry_duration_secs = current_entry_duration_secs;
+        self.max_finished_entries = max_finished_entries;
+        self.max_paused_entries = max_paused_entries;
+        self
+    }
 }
 
 impl Default for Config {
@@ -92,6 +163,10 @@ impl Default for Config {
             finished_entry_duration_secs: DEFAULT_FINISHED_ENTRY_DURATION_SECS,
             gossip_request_timeout_secs: DEFAULT_GOSSIP_REQUEST_TIMEOUT_SECS,
             get_remainder_timeout_secs: DEFAULT_GET_REMAINDER_TIMEOUT_SECS,
+            max_current_entries: DEFAULT_MAX_CURRENT_ENTRIES,
+            current_entry_duration_secs: DEFAULT_CURRENT_ENTRY_DURATION_SECS,
+            max_finished_entries: DEFAULT_MAX_FINISHED_ENTRIES,
+            max_paused_entries: DEFAULT_MAX_PAUSED_ENTRIES,
         }
     }
 }
@@ -129,6 +204,10 @@ mod tests {
             finished_entry_duration_secs: DEFAULT_FINISHED_ENTRY_DURATION_SECS,
             gossip_request_timeout_secs: DEFAULT_GOSSIP_REQUEST_TIMEOUT_SECS,
             get_remainder_timeout_secs: DEFAULT_GET_REMAINDER_TIMEOUT_SECS,
+            max_current_entries: DEFAULT_MAX_CURRENT_ENTRIES,
+            current_entry_duration_secs: DEFAULT_CURRENT_ENTRY_DURATION_SECS,
+            max_finished_entries: DEFAULT_MAX_FINISHED_ENTRIES,
+            max_paused_entries: DEFAULT_MAX_PAUSED_ENTRIES,
         };
 
         // Parsing should fail.
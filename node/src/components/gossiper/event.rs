@@ -1,6 +1,7 @@
 use std::{
     collections::HashSet,
     fmt::{self, Display, Formatter},
+    time::Duration,
 };
 
 use serde::Serialize;
@@ -39,6 +40,11 @@ pub enum Event<T: Item> {
         requester: NodeId,
         result: Box<Result<T, String>>,
     },
+    /// The node operator requested new timeouts.  Timeouts which are `None` are left unchanged.
+    SetTimeouts {
+        gossip_timeout: Option<Duration>,
+        get_from_peer_timeout: Option<Duration>,
+    },
 }
 
 impl<T: Item> Display for Event<T> {
@@ -75,6 +81,14 @@ impl<T: Item> Display for Event<T> {
                     write!(formatter, "failed to get {} from holder component", item_id)
                 }
             }
+            Event::SetTimeouts {
+                gossip_timeout,
+                get_from_peer_timeout,
+            } => write!(
+                formatter,
+                "set gossip timeout to {:?} and get from peer timeout to {:?}",
+                gossip_timeout, get_from_peer_timeout
+            ),
         }
     }
 }
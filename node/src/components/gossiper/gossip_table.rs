@@ -4,13 +4,14 @@ use std::{
     collections::{hash_map::Entry, HashMap, HashSet},
     fmt::Display,
     hash::Hash,
+    mem,
     time::Duration,
 };
 
 use datasize::DataSize;
 #[cfg(test)]
 use fake_instant::FakeClock as Instant;
-use tracing::warn;
+use tracing::{debug, warn};
 
 use super::Config;
 #[cfg(test)]
@@ -56,9 +57,19 @@ pub(crate) struct State {
     infected_by_us: HashSet<NodeId>,
     /// The count of in-flight gossip messages sent by us for this data.
     in_flight_count: usize,
+    /// The order in which the entry was added, used to shed the oldest entries first.
+    sequence: u64,
 }
 
 impl State {
+    /// Returns a new entry with the given sequence number.
+    fn new(sequence: u64) -> Self {
+        State {
+            sequence,
+            ..State::default()
+        }
+    }
+
     /// Returns whether we should finish gossiping this data.
     fn is_finished(&self, infection_target: usize, holders_limit: usize) -> bool {
         self.infected_by_us.len() >= infection_target || self.holders.len() >= holders_limit
@@ -133,6 +144,23 @@ impl<T> Timeouts<T> {
             .drain(..split_index)
             .map(|(_timeout, data_id)| data_id)
     }
+
+    /// Drains and returns up to `count` of the IDs with the earliest timeouts.
+    fn drain_oldest(&mut self, count: usize) -> impl Iterator<Item = T> + '_ {
+        let count = count.min(self.values.len());
+        self.values
+            .drain(..count)
+            .map(|(_timeout, data_id)| data_id)
+    }
+}
+
+/// The numbers of entries evicted from a `GossipTable` since they were last taken.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct Evictions {
+    /// Current entries which outlived `Config::current_entry_duration_secs`.
+    pub(crate) expired: usize,
+    /// Finished or paused entries forgotten before timing out, as there were too many.
+    pub(crate) over_capacity: usize,
 }
 
 #[derive(DataSize, Debug)]
@@ -158,6 +186,21 @@ pub(crate) struct GossipTable<T> {
     holders_limit: usize,
     /// See `Config::finished_entry_duration`.
     finished_entry_duration: Duration,
+    /// Timeouts for treating entries in `current` as finished, along with their sequence numbers
+    /// so that an entry added again after being removed isn't affected.
+    #[data_size(skip)]
+    current_timeouts: Timeouts<(T, u64)>,
+    /// See `Config::current_entry_duration_secs`.
+    current_entry_duration: Option<Duration>,
+    /// See `Config::max_finished_entries`.
+    max_finished: Option<usize>,
+    /// See `Config::max_paused_entries`.
+    max_paused: Option<usize>,
+    /// The sequence number of the next entry added to `current`.
+    next_sequence: u64,
+    /// The entries evicted since last taken.
+    #[data_size(skip)]
+    evictions: Evictions,
 }
 
 impl<T> GossipTable<T> {
@@ -191,9 +234,30 @@ impl<T: Copy + Eq + Hash + Display> GossipTable<T> {
             infection_target: usize::from(config.infection_target()),
             holders_limit,
             finished_entry_duration: Duration::from_secs(config.finished_entry_duration_secs()),
+            current_timeouts: Timeouts::new(),
+            current_entry_duration: Some(config.current_entry_duration_secs())
+                .filter(|secs| *secs > 0)
+                .map(Duration::from_secs),
+            max_finished: Some(config.max_finished_entries()).filter(|max| *max > 0),
+            max_paused: Some(config.max_paused_entries()).filter(|max| *max > 0),
+            next_sequence: 0,
+            evictions: Evictions::default(),
         }
     }
 
+    /// Returns the numbers of entries evicted since this was last called.
+    pub(crate) fn take_evictions(&mut self) -> Evictions {
+        mem::take(&mut self.evictions)
+    }
+
+    /// Returns whether the data with the given ID is known, i.e. being gossiped, paused or
+    /// finished.
+    pub(crate) fn contains(&self, data_id: &T) -> bool {
+        self.current.contains_key(data_id)
+            || self.paused.contains_key(data_id)
+            || self.finished.contains(data_id)
+    }
+
     /// We received knowledge about potentially new data with given ID from the given peer.  This
     /// should only be called where we don't already hold everything locally we need to be able to
     /// gossip it onwards.  If we are able to gossip the data already, call `new_data` instead.
@@ -223,7 +287,12 @@ impl<T: Copy + Eq + Hash + Display> GossipTable<T> {
             }
             Entry::Vacant(entry) => {
                 let is_new = true;
-                let state = entry.insert(State::default());
+                let state = entry.insert(State::new(self.next_sequence));
+                if let Some(duration) = self.current_entry_duration {
+                    self.current_timeouts
+                        .push(Instant::now() + duration, (*data_id, self.next_sequence));
+                }
+                self.next_sequence += 1;
                 let _ = state.holders.insert(holder);
                 state.action(self.infection_target, self.holders_limit, is_new)
             }
@@ -267,7 +336,12 @@ impl<T: Copy + Eq + Hash + Display> GossipTable<T> {
                 state.action(self.infection_target, self.holders_limit, is_new)
             }
             Entry::Vacant(entry) => {
-                let state = entry.insert(State::default());
+                let state = entry.insert(State::new(self.next_sequence));
+                if let Some(duration) = self.current_entry_duration {
+                    self.current_timeouts
+                        .push(Instant::now() + duration, (*data_id, self.next_sequence));
+                }
+                self.next_sequence += 1;
                 update(state);
                 let is_new = true;
                 state.action(self.infection_target, self.holders_limit, is_new)
@@ -445,10 +519,75 @@ impl<T: Copy + Eq + Hash + Display> GossipTable<T> {
         Ok(action)
     }
 
-    /// Retains only those finished entries which still haven't timed out.
+    /// Forgets all entries which have finished gossiping, returning how many were dropped.
+    ///
+    /// Data forgotten this way is gossiped again if received anew, so this should only be used to
+    /// relieve memory pressure.
+    pub(crate) fn shed_finished(&mut self) -> usize {
+        let shed = self.finished.len();
+        self.finished.clear();
+        self.finished_timeouts.values.clear();
+        shed
+    }
+
+    /// Stops gossiping the oldest entries we hold in full until at most `max_current` entries are
+    /// being gossiped, returning how many were shed.
+    ///
+    /// Shed entries are treated as finished, so that they aren't gossiped again if received anew.
+    /// Entries whose remainder we're still awaiting are kept, as they're likely new to the network.
+    pub(crate) fn shed_current(&mut self, max_current: usize) -> usize {
+        let excess = self.current.len().saturating_sub(max_current);
+        if excess == 0 {
+            return 0;
+        }
+
+        let mut candidates: Vec<(u64, T)> = self
+            .current
+            .iter()
+            .filter(|(_, state)| state.held_by_us)
+            .map(|(data_id, state)| (state.sequence, *data_id))
+            .collect();
+        candidates.sort_unstable_by_key(|(sequence, _)| *sequence);
+
+        let mut shed = 0;
+        for (_, data_id) in candidates.into_iter().take(excess) {
+            self.finish_current(data_id);
+            shed += 1;
+        }
+        shed
+    }
+
+    /// Moves the given entry from `current` to `finished`.
+    fn finish_current(&mut self, data_id: T) {
+        let _ = self.current.remove(&data_id);
+        let timeout = Instant::now() + self.finished_entry_duration;
+        let _ = self.finished.insert(data_id);
+        self.finished_timeouts.push(timeout, data_id);
+    }
+
+    /// Retains only those finished and paused entries which still haven't timed out, forgetting
+    /// the oldest ones beyond their maximum numbers, and treats current entries which outlived
+    /// their maximum duration as finished.
     fn purge_finished(&mut self) {
         let now = Instant::now();
 
+        let current = &self.current;
+        let expired_current: Vec<T> = self
+            .current_timeouts
+            .purge(&now)
+            .filter(|(data_id, sequence)| {
+                current
+                    .get(data_id)
+                    .map_or(false, |state| state.sequence == *sequence)
+            })
+            .map(|(data_id, _sequence)| data_id)
+            .collect();
+        for data_id in expired_current {
+            debug!(%data_id, "gossiping timed out");
+            self.finish_current(data_id);
+            self.evictions.expired += 1;
+        }
+
         for expired_finished in self.finished_timeouts.purge(&now) {
             let _ = self.finished.remove(&expired_finished);
         }
@@ -456,6 +595,28 @@ impl<T: Copy + Eq + Hash + Display> GossipTable<T> {
         for expired_paused in self.paused_timeouts.purge(&now) {
             let _ = self.paused.remove(&expired_paused);
         }
+
+        if let Some(max_finished) = self.max_finished {
+            while self.finished.len() > max_finished && !self.finished_timeouts.values.is_empty() {
+                let excess = self.finished.len() - max_finished;
+                for data_id in self.finished_timeouts.drain_oldest(excess) {
+                    if self.finished.remove(&data_id) {
+                        self.evictions.over_capacity += 1;
+                    }
+                }
+            }
+        }
+
+        if let Some(max_paused) = self.max_paused {
+            while self.paused.len() > max_paused && !self.paused_timeouts.values.is_empty() {
+                let excess = self.paused.len() - max_paused;
+                for data_id in self.paused_timeouts.drain_oldest(excess) {
+                    if self.paused.remove(&data_id).is_some() {
+                        self.evictions.over_capacity += 1;
+                    }
+                }
+            }
+        }
     }
 }
 
@@ -931,6 +1092,174 @@ mod tests {
         assert!(!gossip_table.paused.contains_key(&data_id));
     }
 
+    #[test]
+    fn should_shed_finished() {
+        let mut rng = crate::new_rng();
+        let node_ids = random_node_ids(&mut rng);
+        let finished_id: u64 = rng.gen();
+        let current_id: u64 = rng.gen();
+
+        let mut gossip_table = GossipTable::new(Config::default());
+
+        // Finish gossiping one item, and start gossiping another.
+        let _ = gossip_table.new_complete_data(&finished_id, None);
+        for node_id in &node_ids[0..EXPECTED_DEFAULT_INFECTION_TARGET] {
+            let _ = gossip_table.we_infected(&finished_id, node_id.clone());
+        }
+        let _ = gossip_table.new_complete_data(&current_id, None);
+
+        assert_eq!(gossip_table.shed_finished(), 1);
+        assert_eq!(gossip_table.items_finished(), 0);
+        assert!(gossip_table.finished_timeouts.values.is_empty());
+        assert_eq!(gossip_table.items_current(), 1);
+
+        // The shed item is treated as new when received again.
+        let action = gossip_table.new_complete_data(&finished_id, None);
+        assert!(action.is_some());
+    }
+
+    #[test]
+    fn should_shed_oldest_current_held_by_us() {
+        let mut rng = crate::new_rng();
+        let node_ids = random_node_ids(&mut rng);
+
+        let mut gossip_table = GossipTable::new(Config::default());
+
+        // Start gossiping three items we hold, oldest first, and learn of one we don't hold yet.
+        let held_ids: Vec<u64> = iter::repeat_with(|| rng.gen()).take(3).collect();
+        for data_id in &held_ids {
+            let _ = gossip_table.new_complete_data(data_id, None);
+        }
+        let partial_id: u64 = rng.gen();
+        let _ = gossip_table.new_partial_data(&partial_id, node_ids[0].clone());
+        assert_eq!(gossip_table.items_current(), 4);
+
+        // Nothing is shed while within the limit.
+        assert_eq!(gossip_table.shed_current(4), 0);
+
+        // The two oldest held items are shed, while the partial one is kept.
+        assert_eq!(gossip_table.shed_current(2), 2);
+        assert_eq!(gossip_table.items_current(), 2);
+        assert!(gossip_table.current.contains_key(&held_ids[2]));
+        assert!(gossip_table.current.contains_key(&partial_id));
+
+        // Shed items are finished, so not gossiped again if received anew.
+        assert!(gossip_table.contains(&held_ids[0]));
+        assert!(gossip_table.new_complete_data(&held_ids[0], None).is_none());
+
+        // Only items held by us can be shed.
+        assert_eq!(gossip_table.shed_current(0), 1);
+        assert_eq!(gossip_table.items_current(), 1);
+        assert!(gossip_table.current.contains_key(&partial_id));
+    }
+
+    #[test]
+    fn should_expire_current_entries() {
+        let mut rng = crate::new_rng();
+        let node_ids = random_node_ids(&mut rng);
+        let complete_id: u64 = rng.gen();
+        let partial_id: u64 = rng.gen();
+        let new_id: u64 = rng.gen();
+
+        let config = Config::default().with_entry_bounds(60, 0, 0);
+        let mut gossip_table = GossipTable::new(config);
+
+        // Start gossiping one item we hold and learn of one we don't, neither of which finish.
+        let _ = gossip_table.new_complete_data(&complete_id, None);
+        let _ = gossip_table.new_partial_data(&partial_id, node_ids[0].clone());
+        assert_eq!(gossip_table.items_current(), 2);
+
+        // Within their lifetime, both are still gossiped.
+        Instant::advance_time(59 * 1_000);
+        gossip_table.purge_finished();
+        assert_eq!(gossip_table.items_current(), 2);
+        assert_eq!(gossip_table.take_evictions(), Evictions::default());
+
+        // Add another item, then let the first two outlive their lifetime.
+        let _ = gossip_table.new_complete_data(&new_id, None);
+        Instant::advance_time(1_000 + 1);
+        gossip_table.purge_finished();
+        assert_eq!(gossip_table.items_current(), 1);
+        assert!(gossip_table.current.contains_key(&new_id));
+        assert!(gossip_table.finished.contains(&complete_id));
+        assert!(gossip_table.finished.contains(&partial_id));
+        assert_eq!(
+            gossip_table.take_evictions(),
+            Evictions {
+                expired: 2,
+                over_capacity: 0
+            }
+        );
+        assert_eq!(gossip_table.take_evictions(), Evictions::default());
+    }
+
+    #[test]
+    fn should_not_expire_entry_added_again() {
+        let mut rng = crate::new_rng();
+        let node_ids = random_node_ids(&mut rng);
+        let data_id: u64 = rng.gen();
+
+        let config =
+            Config::default().with_entry_bounds(DEFAULT_FINISHED_ENTRY_DURATION_SECS * 2, 0, 0);
+        let mut gossip_table = GossipTable::new(config);
+
+        // Finish gossiping the item and let the finished entry time out.
+        let _ = gossip_table.new_complete_data(&data_id, None);
+        for node_id in &node_ids[0..EXPECTED_DEFAULT_INFECTION_TARGET] {
+            let _ = gossip_table.we_infected(&data_id, node_id.clone());
+        }
+        Instant::advance_time(DEFAULT_FINISHED_ENTRY_DURATION_SECS * 1_000 + 1);
+
+        // Gossip it anew, then pass the lifetime of the original entry but not the new one.
+        assert!(gossip_table.new_complete_data(&data_id, None).is_some());
+        Instant::advance_time(DEFAULT_FINISHED_ENTRY_DURATION_SECS * 1_000);
+        gossip_table.purge_finished();
+        assert!(gossip_table.current.contains_key(&data_id));
+        assert_eq!(gossip_table.take_evictions(), Evictions::default());
+    }
+
+    #[test]
+    fn should_evict_oldest_entries_over_capacity() {
+        let mut rng = crate::new_rng();
+        let node_ids = random_node_ids(&mut rng);
+        let finished_ids: Vec<u64> = iter::repeat_with(|| rng.gen()).take(5).collect();
+        let paused_ids: Vec<u64> = iter::repeat_with(|| rng.gen()).take(4).collect();
+
+        let config = Config::default().with_entry_bounds(0, 3, 2);
+        let mut gossip_table = GossipTable::new(config);
+
+        for data_id in &finished_ids {
+            let _ = gossip_table.new_complete_data(data_id, None);
+            for node_id in &node_ids[0..EXPECTED_DEFAULT_INFECTION_TARGET] {
+                let _ = gossip_table.we_infected(data_id, node_id.clone());
+            }
+            Instant::advance_time(1);
+        }
+        for data_id in &paused_ids {
+            let _ = gossip_table.new_complete_data(data_id, None);
+            gossip_table.pause(data_id);
+            Instant::advance_time(1);
+        }
+
+        // Only the newest finished and paused entries are kept.
+        gossip_table.purge_finished();
+        assert_eq!(gossip_table.items_finished(), 3);
+        assert!(finished_ids[2..]
+            .iter()
+            .all(|data_id| gossip_table.finished.contains(data_id)));
+        assert_eq!(gossip_table.items_paused(), 2);
+        assert!(paused_ids[2..]
+            .iter()
+            .all(|data_id| gossip_table.paused.contains_key(data_id)));
+        assert_eq!(
+            gossip_table.take_evictions(),
+            Evictions {
+                expired: 0,
+                over_capacity: 4
+            }
+        );
+    }
+
     #[bench]
     fn benchmark_purging(bencher: &mut Bencher) {
         const ENTRY_COUNT: usize = 10_000;
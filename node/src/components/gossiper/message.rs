@@ -15,6 +15,11 @@ pub enum Message<T: Item> {
         item_id: T::Id,
         is_already_held: bool,
     },
+    /// Response to a `Gossip` message from a peer too busy to take on new items.  The item should
+    /// be gossiped to a different peer instead.
+    ///
+    /// Only sent to peers speaking wire version 2 or later, as older ones can't decode it.
+    Busy(T::Id),
 }
 
 impl<T: Item> Display for Message<T> {
@@ -29,6 +34,7 @@ impl<T: Item> Display for Message<T> {
                 "gossip-response({}, {})",
                 item_id, is_already_held
             ),
+            Message::Busy(item_id) => write!(formatter, "busy({})", item_id),
         }
     }
 }
@@ -15,6 +15,15 @@ pub struct GossiperMetrics {
     pub(super) table_items_current: IntGauge,
     /// Number of items in the gossip table that are finished.
     pub(super) table_items_finished: IntGauge,
+    /// Total number of items which stopped being gossiped to relieve memory or queue pressure.
+    pub(super) items_shed: IntCounter,
+    /// Total number of gossip messages for new items answered with a busy response.
+    pub(super) busy_responses: IntCounter,
+    /// Total number of items in the gossip table treated as finished after gossiping for too long.
+    pub(super) table_items_expired: IntCounter,
+    /// Total number of finished or paused items in the gossip table forgotten before timing out,
+    /// as there were too many.
+    pub(super) table_items_evicted: IntCounter,
     /// Reference to the registry for unregistering.
     registry: Registry,
 }
@@ -64,6 +73,37 @@ impl GossiperMetrics {
                 name
             ),
         )?;
+        let items_shed = IntCounter::new(
+            format!("{}_items_shed", name),
+            format!(
+                "number of items the {} gossiper stopped gossiping to relieve memory or queue \
+                pressure",
+                name
+            ),
+        )?;
+        let busy_responses = IntCounter::new(
+            format!("{}_busy_responses", name),
+            format!(
+                "number of gossip messages for new items the {} gossiper answered as busy",
+                name
+            ),
+        )?;
+        let table_items_expired = IntCounter::new(
+            format!("{}_table_items_expired", name),
+            format!(
+                "number of items in the gossip table of {} which stopped being gossiped after \
+                their maximum duration",
+                name
+            ),
+        )?;
+        let table_items_evicted = IntCounter::new(
+            format!("{}_table_items_evicted", name),
+            format!(
+                "number of finished or paused items in the gossip table of {} forgotten as there \
+                were too many",
+                name
+            ),
+        )?;
 
         registry.register(Box::new(items_received.clone()))?;
         registry.register(Box::new(items_gossiped_onwards.clone()))?;
@@ -71,6 +111,10 @@ impl GossiperMetrics {
         registry.register(Box::new(table_items_paused.clone()))?;
         registry.register(Box::new(table_items_current.clone()))?;
         registry.register(Box::new(table_items_finished.clone()))?;
+        registry.register(Box::new(items_shed.clone()))?;
+        registry.register(Box::new(busy_responses.clone()))?;
+        registry.register(Box::new(table_items_expired.clone()))?;
+        registry.register(Box::new(table_items_evicted.clone()))?;
 
         Ok(GossiperMetrics {
             items_received,
@@ -79,6 +123,10 @@ impl GossiperMetrics {
             table_items_paused,
             table_items_current,
             table_items_finished,
+            items_shed,
+            busy_responses,
+            table_items_expired,
+            table_items_evicted,
             registry: registry.clone(),
         })
     }
@@ -104,5 +152,17 @@ impl Drop for GossiperMetrics {
         self.registry
             .unregister(Box::new(self.table_items_finished.clone()))
             .expect("did not expect deregistering table_items_finished to fail");
+        self.registry
+            .unregister(Box::new(self.items_shed.clone()))
+            .expect("did not expect deregistering items_shed to fail");
+        self.registry
+            .unregister(Box::new(self.busy_responses.clone()))
+            .expect("did not expect deregistering busy_responses to fail");
+        self.registry
+            .unregister(Box::new(self.table_items_expired.clone()))
+            .expect("did not expect deregistering table_items_expired to fail");
+        self.registry
+            .unregister(Box::new(self.table_items_evicted.clone()))
+            .expect("did not expect deregistering table_items_evicted to fail");
     }
 }
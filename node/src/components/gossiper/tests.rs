@@ -34,7 +34,7 @@ use crate::{
         ConditionCheckReactor, TestRng,
     },
     types::{Deploy, NodeId, Tag},
-    utils::{Loadable, WithDir},
+    utils::{Loadable, VerificationPool, WithDir},
     NodeRng,
 };
 
@@ -128,9 +128,13 @@ impl reactor::Reactor for Reactor {
         let network = NetworkController::create_node(event_queue, rng);
 
         let (storage_config, storage_tempdir) = storage::Config::default_for_tests();
-        let storage = Storage::new(&WithDir::new(storage_tempdir.path(), storage_config)).unwrap();
+        let storage = Storage::new(
+            &WithDir::new(storage_tempdir.path(), storage_config),
+            registry,
+        )
+        .unwrap();
 
-        let deploy_acceptor = DeployAcceptor::new();
+        let deploy_acceptor = DeployAcceptor::new(VerificationPool::new(1, registry).unwrap());
         let deploy_gossiper = Gossiper::new_for_partial_items(
             "deploy_gossiper",
             config,
@@ -261,6 +265,10 @@ impl reactor::Reactor for Reactor {
                 // We do not care about new peers in the gossiper test.
                 Effects::new()
             }
+            Event::NetworkAnnouncement(NetworkAnnouncement::ClockDrift { .. }) => {
+                // We do not care about clock drift in the gossiper test.
+                Effects::new()
+            }
             Event::RpcServerAnnouncement(RpcServerAnnouncement::DeployReceived { deploy }) => {
                 let event = deploy_acceptor::Event::Accept {
                     deploy,
@@ -268,6 +276,9 @@ impl reactor::Reactor for Reactor {
                 };
                 self.dispatch_event(effect_builder, rng, Event::DeployAcceptor(event))
             }
+            Event::RpcServerAnnouncement(RpcServerAnnouncement::AdminCommand(_)) => {
+                unreachable!("should not receive announcements of type AdminCommand");
+            }
             Event::DeployAcceptorAnnouncement(DeployAcceptorAnnouncement::AcceptedNewDeploy {
                 deploy,
                 source,
@@ -506,3 +517,61 @@ async fn should_timeout_gossip_response() {
 
     NetworkController::<NodeMessage>::remove_active();
 }
+
+#[tokio::test]
+async fn should_gossip_with_latency_but_not_across_partition() {
+    const NETWORK_SIZE: usize = 3;
+    const LATENCY: Duration = Duration::from_millis(500);
+
+    NetworkController::<NodeMessage>::create_active();
+    let mut network = Network::<Reactor>::new();
+    let mut rng = crate::new_rng();
+
+    // Add `NETWORK_SIZE` nodes, delay all messages and cut off node 2 from the rest.
+    let node_ids = network.add_nodes(&mut rng, NETWORK_SIZE).await;
+    NetworkController::<NodeMessage>::set_latency(LATENCY);
+    NetworkController::<NodeMessage>::isolate(&node_ids[2]);
+
+    // Give a random deploy to node 0 to be gossiped.
+    let deploy = Box::new(Deploy::random(&mut rng));
+    let deploy_id = *deploy.id();
+    network
+        .process_injected_effect_on(&node_ids[0], announce_deploy_received(deploy))
+        .await;
+
+    let deploy_held = |nodes: &HashMap<NodeId, Runner<ConditionCheckReactor<Reactor>>>,
+                       node_id: &NodeId| {
+        nodes
+            .get(node_id)
+            .unwrap()
+            .reactor()
+            .inner()
+            .storage
+            .get_deploy_by_hash(deploy_id)
+            .is_some()
+    };
+
+    // Node 1 can't have received the deploy before the gossip message has even arrived.
+    network.advance_time(&mut rng, LATENCY / 2).await;
+    assert!(deploy_held(network.nodes(), &node_ids[0]));
+    assert!(!deploy_held(network.nodes(), &node_ids[1]));
+
+    // The gossip message, the request for the deploy and the response each take `LATENCY`.
+    network
+        .run_until(
+            &mut rng,
+            |nodes| deploy_held(nodes, &node_ids[1]),
+            4 * LATENCY,
+        )
+        .await;
+
+    // Node 2 never receives the deploy, even after all gossip timeouts have elapsed.
+    let secs_to_advance = Config::default().gossip_request_timeout_secs()
+        + Config::default().get_remainder_timeout_secs();
+    network
+        .advance_time(&mut rng, Duration::from_secs(secs_to_advance + 1))
+        .await;
+    assert!(!deploy_held(network.nodes(), &node_ids[2]));
+
+    NetworkController::<NodeMessage>::remove_active();
+}
@@ -3,6 +3,17 @@
 //! The `InMemoryNetwork` represents a full virtual network with flawless connectivity and delivery
 //! by default.
 //!
+//! # Link conditions
+//!
+//! The `NetworkController` of the active network can degrade the links between nodes to simulate
+//! real-world conditions: messages can be delayed by a latency (`set_latency`,
+//! `set_link_latency`), and links can be severed entirely, either individually (`sever_link`) or by
+//! partitioning the network (`partition`, `isolate`), until the network is healed again (`heal`).
+//! Messages sent over a severed link are silently dropped.
+//!
+//! Latencies are timed using tokio's clock, so pausing and advancing the clock, as done by
+//! `Network::advance_time`, allows simulating a network in virtual time.
+//!
 //! # Setup
 //!
 //! The network itself is managed by a `NetworkController` that can be used to create networking
@@ -286,10 +297,14 @@ use std::{
     convert::Infallible,
     fmt::Display,
     sync::{Arc, RwLock},
+    time::Duration,
 };
 
 use rand::seq::IteratorRandom;
-use tokio::sync::mpsc::{self, error::SendError};
+use tokio::{
+    sync::mpsc::{self, error::SendError},
+    time,
+};
 use tracing::{debug, error, info, warn};
 
 use crate::{
@@ -307,6 +322,38 @@ use crate::{
 
 type Network<P> = Arc<RwLock<HashMap<NodeId, mpsc::UnboundedSender<(NodeId, P)>>>>;
 
+/// Conditions of the links between the nodes of a network, shared by all of its nodes.
+type Links = Arc<RwLock<LinkConditions>>;
+
+/// Conditions of the links between the nodes of a network.
+///
+/// Links are directed, keyed by sender and recipient.
+#[derive(Debug, Default)]
+struct LinkConditions {
+    /// Latency of all links without a specific latency.
+    default_latency: Duration,
+    /// Latencies of specific links.
+    latencies: HashMap<(NodeId, NodeId), Duration>,
+    /// Links over which all messages are dropped.
+    severed: HashSet<(NodeId, NodeId)>,
+}
+
+impl LinkConditions {
+    /// Returns the latency of the link, or `None` if it is severed.
+    fn latency(&self, sender: &NodeId, recipient: &NodeId) -> Option<Duration> {
+        let link = (sender.clone(), recipient.clone());
+        if self.severed.contains(&link) {
+            return None;
+        }
+        Some(
+            self.latencies
+                .get(&link)
+                .copied()
+                .unwrap_or(self.default_latency),
+        )
+    }
+}
+
 thread_local! {
     /// The currently active network as a thread local.
     ///
@@ -319,6 +366,8 @@ thread_local! {
 pub struct NetworkController<P> {
     /// Channels for network communication.
     nodes: Network<P>,
+    /// Conditions of the links between nodes.
+    links: Links,
 }
 
 impl<P> NetworkController<P>
@@ -330,6 +379,7 @@ where
         let _ = logging::init();
         NetworkController {
             nodes: Default::default(),
+            links: Default::default(),
         }
     }
 
@@ -419,7 +469,121 @@ where
     where
         REv: From<NetworkAnnouncement<NodeId, P>> + Send,
     {
-        InMemoryNetwork::new(event_queue, NodeId::random(rng), self.nodes.clone())
+        InMemoryNetwork::new(
+            event_queue,
+            NodeId::random(rng),
+            self.nodes.clone(),
+            self.links.clone(),
+        )
+    }
+
+    /// Sets the latency of all links of the active network without a latency set via
+    /// `set_link_latency`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal lock has been poisoned, there is no active network or the active
+    /// network is not of the correct message type.
+    pub fn set_latency(latency: Duration) {
+        Self::with_active_links(|links| links.default_latency = latency)
+    }
+
+    /// Sets the latency of messages sent from `sender` to `recipient` on the active network.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal lock has been poisoned, there is no active network or the active
+    /// network is not of the correct message type.
+    pub fn set_link_latency(sender: &NodeId, recipient: &NodeId, latency: Duration) {
+        Self::with_active_links(|links| {
+            let _ = links
+                .latencies
+                .insert((sender.clone(), recipient.clone()), latency);
+        })
+    }
+
+    /// Severs the link from `sender` to `recipient` on the active network, dropping all messages
+    /// sent over it until the network is healed.
+    ///
+    /// The link in the opposite direction is not affected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal lock has been poisoned, there is no active network or the active
+    /// network is not of the correct message type.
+    pub fn sever_link(sender: &NodeId, recipient: &NodeId) {
+        Self::with_active_links(|links| {
+            let _ = links.severed.insert((sender.clone(), recipient.clone()));
+        })
+    }
+
+    /// Partitions the active network into the given nodes and all other nodes, severing all links
+    /// between the two sides in both directions.
+    ///
+    /// Only nodes currently part of the network are partitioned off, nodes added later are fully
+    /// connected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal lock has been poisoned, there is no active network or the active
+    /// network is not of the correct message type.
+    pub fn partition(side: &[NodeId]) {
+        Self::with_active(|controller| {
+            let side: HashSet<_> = side.iter().collect();
+            let nodes = controller.nodes.read().expect("poisoned lock");
+            let mut links = controller.links.write().expect("poisoned lock");
+            for inside in nodes.keys().filter(|node_id| side.contains(node_id)) {
+                for outside in nodes.keys().filter(|node_id| !side.contains(node_id)) {
+                    let _ = links.severed.insert((inside.clone(), outside.clone()));
+                    let _ = links.severed.insert((outside.clone(), inside.clone()));
+                }
+            }
+        })
+    }
+
+    /// Isolates the given node from all other nodes of the active network.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal lock has been poisoned, there is no active network or the active
+    /// network is not of the correct message type.
+    pub fn isolate(node_id: &NodeId) {
+        Self::partition(&[node_id.clone()])
+    }
+
+    /// Restores all severed links of the active network.
+    ///
+    /// Latencies are left unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal lock has been poisoned, there is no active network or the active
+    /// network is not of the correct message type.
+    pub fn heal() {
+        Self::with_active_links(|links| links.severed.clear())
+    }
+
+    /// Calls `f` on the active network controller.
+    fn with_active<F, T>(f: F) -> T
+    where
+        F: FnOnce(&Self) -> T,
+    {
+        ACTIVE_NETWORK.with(|active_network| {
+            f(active_network
+                .borrow()
+                .as_ref()
+                .expect("no active network set")
+                .downcast_ref::<Self>()
+                .expect("active network has wrong message type"))
+        })
+    }
+
+    /// Calls `f` on the link conditions of the active network.
+    fn with_active_links<F, T>(f: F) -> T
+    where
+        F: FnOnce(&mut LinkConditions) -> T,
+    {
+        Self::with_active(|controller| f(&mut controller.links.write().expect("poisoned lock")))
     }
 }
 
@@ -431,13 +595,21 @@ pub struct InMemoryNetwork<P> {
 
     /// The nodes map, contains the incoming channel for each virtual node.
     nodes: Network<P>,
+
+    /// Conditions of the links between nodes.
+    links: Links,
 }
 
 impl<P> InMemoryNetwork<P>
 where
     P: 'static + Send,
 {
-    fn new<REv>(event_queue: EventQueueHandle<REv>, node_id: NodeId, nodes: Network<P>) -> Self
+    fn new<REv>(
+        event_queue: EventQueueHandle<REv>,
+        node_id: NodeId,
+        nodes: Network<P>,
+        links: Links,
+    ) -> Self
     where
         REv: From<NetworkAnnouncement<NodeId, P>> + Send,
     {
@@ -452,7 +624,11 @@ where
 
         tokio::spawn(receiver_task(event_queue, receiver));
 
-        InMemoryNetwork { node_id, nodes }
+        InMemoryNetwork {
+            node_id,
+            nodes,
+            links,
+        }
     }
 
     /// Returns this node's ID.
@@ -464,7 +640,7 @@ where
 
 impl<P> InMemoryNetwork<P>
 where
-    P: Display,
+    P: 'static + Send + Display,
 {
     /// Internal helper, sends a payload to a node, ignoring but logging all errors.
     fn send(
@@ -477,12 +653,38 @@ where
             panic!("can't send message to self");
         }
 
+        let latency = match self.links.read() {
+            Ok(links) => links.latency(&self.node_id, &dest),
+            Err(_) => {
+                error!("link conditions lock has been poisoned");
+                return;
+            }
+        };
+        let latency = match latency {
+            Some(latency) => latency,
+            None => {
+                debug!(%dest, %payload, "dropping message sent over severed link");
+                return;
+            }
+        };
+
         match nodes.get(&dest) {
             Some(sender) => {
-                if let Err(SendError((_, msg))) = sender.send((self.node_id.clone(), payload)) {
-                    warn!(%dest, %msg, "could not send message (send error)");
+                let message = (self.node_id.clone(), payload);
+                if latency == Duration::from_secs(0) {
+                    if let Err(SendError((_, msg))) = sender.send(message) {
+                        warn!(%dest, %msg, "could not send message (send error)");
 
-                    // We do nothing else, the message is just dropped.
+                        // We do nothing else, the message is just dropped.
+                    }
+                } else {
+                    let sender = sender.clone();
+                    tokio::spawn(async move {
+                        time::delay_for(latency).await;
+                        if let Err(SendError((_, msg))) = sender.send(message) {
+                            warn!(%dest, %msg, "could not send delayed message (send error)");
+                        }
+                    });
                 }
             }
             None => info!(%dest, %payload, "dropping message to non-existent recipient"),
@@ -492,7 +694,7 @@ where
 
 impl<P, REv> Component<REv> for InMemoryNetwork<P>
 where
-    P: Display + Clone,
+    P: 'static + Send + Display + Clone,
 {
     type Event = NetworkRequest<NodeId, P>;
     type ConstructionError = Infallible;
@@ -1,8 +1,19 @@
+//! The linear chain component.
+//!
+//! Stores blocks added to the linear chain along with the execution results of their deploys, has
+//! them signed by consensus, and serves them to peers.  Finality signatures are verified on the
+//! signature verification pool, then collected in a `SignatureBuffer` before being appended to the
+//! stored blocks.
+
+mod config;
+mod signature_buffer;
+
 use std::{
     collections::HashMap,
     convert::Infallible,
     fmt::{self, Display, Formatter},
     marker::PhantomData,
+    time::Duration,
 };
 
 use datasize::DataSize;
@@ -12,7 +23,7 @@ use tracing::{debug, error, info, warn};
 
 use super::Component;
 use crate::{
-    crypto::asymmetric_key::Signature,
+    crypto::asymmetric_key::{PublicKey, Signature},
     effect::{
         announcements::LinearChainAnnouncement,
         requests::{ConsensusRequest, LinearChainRequest, NetworkRequest, StorageRequest},
@@ -20,8 +31,11 @@ use crate::{
     },
     protocol::Message,
     types::{json_compatibility::ExecutionResult, Block, BlockByHeight, BlockHash, DeployHash},
+    utils::VerificationPool,
     NodeRng,
 };
+pub use config::Config;
+use signature_buffer::{AddOutcome, SignatureBuffer};
 
 #[derive(Debug, From)]
 pub enum Event<I> {
@@ -42,13 +56,35 @@ pub enum Event<I> {
     /// A continuation for `BlockAtHeightLocal` scenario.
     GetBlockByHeightResultLocal(u64, Option<Box<Block>>, Responder<Option<Block>>),
     /// New finality signature.
-    NewFinalitySignature(BlockHash, Signature),
-    /// The result of putting a block to storage.
+    NewFinalitySignature {
+        /// The hash of the signed block.
+        block_hash: BlockHash,
+        /// The height of the signed block.
+        height: u64,
+        /// The signer's public key and the signature, or `None` if consensus failed to sign the
+        /// block.
+        maybe_signature: Option<(PublicKey, Signature)>,
+    },
+    /// The result of verifying a finality signature on the signature verification pool.
+    FinalitySignatureVerified {
+        /// The hash of the signed block.
+        block_hash: BlockHash,
+        /// The height of the signed block.
+        height: u64,
+        /// The signer's public key.
+        public_key: PublicKey,
+        /// The signature.
+        signature: Signature,
+        /// Whether the signature is valid for the block hash and the signer's key.
+        is_valid: bool,
+    },
+    /// The delay for collecting finality signatures for a block has passed, and the signatures
+    /// collected so far are to be stored.
+    FlushFinalitySignatures(BlockHash),
+    /// The result of putting a block and its deploys' execution results to storage.
     PutBlockResult {
         /// The block.
         block: Box<Block>,
-        /// The deploys' execution results.
-        execution_results: HashMap<DeployHash, ExecutionResult>,
     },
 }
 
@@ -66,11 +102,25 @@ impl<I: Display> Display for Event<I> {
                 peer,
                 maybe_block.is_some()
             ),
-            Event::NewFinalitySignature(block_hash, _) => write!(
+            Event::NewFinalitySignature { block_hash, .. } => write!(
                 f,
                 "linear-chain new finality signature for block: {}",
                 block_hash
             ),
+            Event::FinalitySignatureVerified {
+                block_hash,
+                is_valid,
+                ..
+            } => write!(
+                f,
+                "linear-chain verified finality signature for block: {}, valid: {}",
+                block_hash, is_valid
+            ),
+            Event::FlushFinalitySignatures(block_hash) => write!(
+                f,
+                "linear-chain flush finality signatures for block: {}",
+                block_hash
+            ),
             Event::PutBlockResult { .. } => write!(f, "linear-chain put-block result"),
             Event::GetBlockByHeightResult(height, result, peer) => write!(
                 f,
@@ -94,13 +144,23 @@ pub(crate) struct LinearChain<I> {
     /// A temporary workaround.
     // TODO: Refactor to proper LRU cache.
     linear_chain: Vec<Block>,
+    /// Finality signatures not yet written to storage, deduplicated per block and validator.
+    signature_buffer: SignatureBuffer,
+    /// The delay for which finality signatures for a block are collected before being stored.
+    signature_flush_delay: Duration,
+    /// The pool on which finality signatures are verified.
+    #[data_size(skip)]
+    verification_pool: VerificationPool,
     _marker: PhantomData<I>,
 }
 
 impl<I> LinearChain<I> {
-    pub fn new() -> Self {
+    pub fn new(config: Config, verification_pool: VerificationPool) -> Self {
         LinearChain {
             linear_chain: Vec::new(),
+            signature_buffer: SignatureBuffer::new(config.max_signature_age),
+            signature_flush_delay: config.signature_flush_delay,
+            verification_pool,
             _marker: PhantomData,
         }
     }
@@ -132,22 +192,28 @@ where
         match event {
             Event::Request(LinearChainRequest::BlockRequest(block_hash, sender)) => effect_builder
                 .get_block_from_storage(block_hash)
-                .event(move |maybe_block| Event::GetBlockResult(block_hash, maybe_block.map(Box::new), sender)),
+                .event(move |maybe_block| {
+                    Event::GetBlockResult(block_hash, maybe_block.map(Box::new), sender)
+                }),
             Event::Request(LinearChainRequest::BlockAtHeightLocal(height, responder)) => {
                 effect_builder
                     .get_block_at_height(height)
-                    .event(move |block| Event::GetBlockByHeightResultLocal(height, block.map(Box::new), responder))
+                    .event(move |block| {
+                        Event::GetBlockByHeightResultLocal(height, block.map(Box::new), responder)
+                    })
             }
             Event::Request(LinearChainRequest::BlockAtHeight(height, sender)) => {
                 // Treat `linear_chain` as a cache of least-recently asked for blocks.
                 // match self.linear_chain.get(height as usize).cloned() {
                 //     Some(block) => effect_builder
                 //         .immediately()
-                //         .event(move |_| Event::GetBlockByHeightResult(height, Some(block), sender)),
-                //     None =>
+                //         .event(move |_| Event::GetBlockByHeightResult(height, Some(block),
+                // sender)),     None =>
                 effect_builder
                     .get_block_at_height(height)
-                    .event(move |maybe_block| Event::GetBlockByHeightResult(height, maybe_block.map(Box::new), sender))
+                    .event(move |maybe_block| {
+                        Event::GetBlockByHeightResult(height, maybe_block.map(Box::new), sender)
+                    })
             }
             Event::GetBlockByHeightResultLocal(_height, block, responder) => {
                 responder.respond(block.map(|boxed| *boxed)).ignore()
@@ -157,7 +223,7 @@ where
                     None => {
                         debug!("failed to get {} for {}", block_height, sender);
                         BlockByHeight::Absent(block_height)
-                    },
+                    }
                     Some(block) => BlockByHeight::new(*block),
                 };
                 match Message::new_get_response(&block_at_height) {
@@ -168,27 +234,26 @@ where
                     }
                 }
             }
-            Event::GetBlockResult(block_hash, maybe_block, sender) => {
-                match maybe_block {
-                    None => {
-                        debug!("failed to get {} for {}", block_hash, sender);
+            Event::GetBlockResult(block_hash, maybe_block, sender) => match maybe_block {
+                None => {
+                    debug!("failed to get {} for {}", block_hash, sender);
+                    Effects::new()
+                }
+                Some(block) => match Message::new_get_response(&*block) {
+                    Ok(message) => effect_builder.send_message(sender, message).ignore(),
+                    Err(error) => {
+                        error!("failed to create get-response {}", error);
                         Effects::new()
-                    },
-                    Some(block) => match Message::new_get_response(&*block) {
-                        Ok(message) => effect_builder.send_message(sender, message).ignore(),
-                        Err(error) => {
-                            error!("failed to create get-response {}", error);
-                            Effects::new()
-                        }
                     }
-                }
-            }
-            Event::LinearChainBlock { block, execution_results } => {
-                effect_builder
-                .put_block_to_storage(block.clone())
-                .event(move |_| Event::PutBlockResult{ block, execution_results })
+                },
             },
-            Event::PutBlockResult { block, execution_results } => {
+            Event::LinearChainBlock {
+                block,
+                execution_results,
+            } => effect_builder
+                .put_block_and_execution_results_to_storage(block.clone(), execution_results)
+                .event(move |_| Event::PutBlockResult { block }),
+            Event::PutBlockResult { block } => {
                 // TODO: Remove once we can return all linear chain blocks from persistent storage.
                 self.linear_chain.push(*block.clone());
 
@@ -197,28 +262,92 @@ where
                 let era_id = block_header.era_id();
                 let height = block_header.height();
                 info!(?block_hash, ?era_id, ?height, "Linear chain block stored.");
-                let mut effects = effect_builder.put_execution_results_to_storage(block_hash, execution_results).ignore();
+                let dropped = self.signature_buffer.block_stored(height);
+                if dropped > 0 {
+                    debug!(
+                        dropped,
+                        "dropped pending finality signatures for old blocks"
+                    );
+                }
+                let mut effects = effect_builder
+                    .handle_linear_chain_block(block_header.clone())
+                    .event(move |maybe_signature| Event::NewFinalitySignature {
+                        block_hash,
+                        height,
+                        maybe_signature,
+                    });
                 effects.extend(
-                    effect_builder.handle_linear_chain_block(block_header.clone())
-                    .event(move |signature| Event::NewFinalitySignature(block_hash, signature)));
-                effects.extend(effect_builder.announce_block_added(block_hash, block_header).ignore());
+                    effect_builder
+                        .announce_block_added(block_hash, block_header)
+                        .ignore(),
+                );
                 effects
+            }
+            // Consensus has already logged the failure to sign.
+            Event::NewFinalitySignature {
+                maybe_signature: None,
+                ..
+            } => Effects::new(),
+            Event::NewFinalitySignature {
+                block_hash,
+                height,
+                maybe_signature: Some((public_key, signature)),
+            } => self
+                .verification_pool
+                .run(move || signature_buffer::verify(&block_hash, &public_key, &signature))
+                .event(move |is_valid| Event::FinalitySignatureVerified {
+                    block_hash,
+                    height,
+                    public_key,
+                    signature,
+                    is_valid,
+                }),
+            Event::FinalitySignatureVerified {
+                block_hash,
+                public_key,
+                is_valid: false,
+                ..
+            } => {
+                warn!(%block_hash, %public_key, "ignoring invalid finality signature");
+                Effects::new()
+            }
+            Event::FinalitySignatureVerified {
+                block_hash,
+                height,
+                public_key,
+                signature,
+                is_valid: true,
+            } => match self
+                .signature_buffer
+                .add(block_hash, height, public_key, signature)
+            {
+                AddOutcome::FirstPending => effect_builder
+                    .set_timeout(self.signature_flush_delay)
+                    .event(move |_| Event::FlushFinalitySignatures(block_hash)),
+                AddOutcome::Pending => Effects::new(),
+                AddOutcome::Duplicate => {
+                    debug!(%block_hash, %public_key, "ignoring duplicate finality signature");
+                    Effects::new()
+                }
+                AddOutcome::TooOld => {
+                    debug!(%block_hash, height, "ignoring finality signature for old block");
+                    Effects::new()
+                }
             },
-            Event::NewFinalitySignature(block_hash, signature) => {
+            Event::FlushFinalitySignatures(block_hash) => {
+                let proofs = self.signature_buffer.take_pending(&block_hash);
+                if proofs.is_empty() {
+                    return Effects::new();
+                }
                 effect_builder
-                    .get_block_from_storage(block_hash)
-                    .then(move |maybe_block| match maybe_block {
-                        Some(mut block) => {
-                            block.append_proof(signature);
-                            effect_builder.put_block_to_storage(Box::new(block))
-                        }
-                        None => {
-                            warn!("Received a signature for {} but block was not found in the Linear chain storage", block_hash);
-                            panic!("Unhandled")
+                    .append_block_proofs_to_storage(block_hash, proofs)
+                    .map(move |block_found| {
+                        if !block_found {
+                            warn!(%block_hash, "received finality signatures for a block not found in storage");
                         }
                     })
                     .ignore()
-            },
+            }
         }
     }
 }
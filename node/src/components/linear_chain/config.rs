@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+
+/// Default delay for which finality signatures for a block are collected before being stored.
+const DEFAULT_SIGNATURE_FLUSH_DELAY: Duration = Duration::from_secs(1);
+
+/// Default number of blocks below the highest stored block beyond which signatures are dropped.
+const DEFAULT_MAX_SIGNATURE_AGE: u64 = 100;
+
+/// Configuration options for the linear chain component.
+#[derive(Clone, Copy, DataSize, Debug, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Delay in milliseconds for which finality signatures for a block are collected before they
+    /// are written to storage together.
+    #[serde(with = "crate::utils::milliseconds")]
+    pub signature_flush_delay: Duration,
+
+    /// Number of blocks below the highest stored block beyond which finality signatures for a
+    /// block are dropped rather than stored.
+    pub max_signature_age: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            signature_flush_delay: DEFAULT_SIGNATURE_FLUSH_DELAY,
+            max_signature_age: DEFAULT_MAX_SIGNATURE_AGE,
+        }
+    }
+}
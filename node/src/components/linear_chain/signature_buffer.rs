@@ -0,0 +1,238 @@
+//! Buffering of finality signatures before they are written to storage.
+//!
+//! A signature is kept only if no signature of the same validator for the same block has been seen
+//! before.  Signatures are to be verified, via [`verify`], before being added.  The signatures for
+//! a block are collected for a short while and then appended to the stored block in a single write,
+//! rather than with one write per signature.  Signatures for blocks lying more than a configured
+//! number of blocks below the highest stored block are dropped, along with the record of which
+//! validators signed them.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    mem,
+};
+
+use datasize::DataSize;
+
+use crate::{
+    crypto::asymmetric_key::{self, PublicKey, Signature},
+    types::BlockHash,
+};
+
+/// The outcome of adding a finality signature to the buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum AddOutcome {
+    /// The signature was buffered, and is the first one pending for its block.
+    FirstPending,
+    /// The signature was buffered alongside others already pending for its block.
+    Pending,
+    /// A signature by the same validator for the same block was added before.
+    Duplicate,
+    /// The block is too old for its signatures to be kept.
+    TooOld,
+}
+
+/// Returns whether `signature` is valid for the block hash and the validator's key.
+pub(super) fn verify(
+    block_hash: &BlockHash,
+    public_key: &PublicKey,
+    signature: &Signature,
+) -> bool {
+    asymmetric_key::verify(block_hash.inner(), signature, public_key).is_ok()
+}
+
+/// The signatures for a single block.
+#[derive(DataSize, Debug)]
+struct BlockSignatures {
+    /// The height of the block.
+    height: u64,
+    /// The signatures not yet written to storage, by validator.
+    pending: BTreeMap<PublicKey, Signature>,
+    /// The validators whose signatures have already been written to storage.
+    flushed: HashSet<PublicKey>,
+}
+
+/// A buffer deduplicating finality signatures per block and validator, and collecting them so that
+/// they can be written to storage together.
+#[derive(DataSize, Debug)]
+pub(super) struct SignatureBuffer {
+    /// The number of blocks below the highest stored block beyond which signatures are dropped.
+    max_age: u64,
+    /// The height of the highest stored block.
+    highest_height: u64,
+    /// The signatures of the blocks which are not too old.
+    blocks: BTreeMap<BlockHash, BlockSignatures>,
+}
+
+impl SignatureBuffer {
+    /// Creates an empty buffer dropping signatures for blocks more than `max_age` blocks below the
+    /// highest stored block.
+    pub(super) fn new(max_age: u64) -> Self {
+        SignatureBuffer {
+            max_age,
+            highest_height: 0,
+            blocks: BTreeMap::new(),
+        }
+    }
+
+    fn is_too_old(&self, height: u64) -> bool {
+        height.saturating_add(self.max_age) < self.highest_height
+    }
+
+    /// Adds the signature by `public_key` for the block with the given hash and height, which must
+    /// have been verified already.
+    pub(super) fn add(
+        &mut self,
+        block_hash: BlockHash,
+        height: u64,
+        public_key: PublicKey,
+        signature: Signature,
+    ) -> AddOutcome {
+        if self.is_too_old(height) {
+            return AddOutcome::TooOld;
+        }
+        if let Some(block) = self.blocks.get(&block_hash) {
+            if block.pending.contains_key(&public_key) || block.flushed.contains(&public_key) {
+                return AddOutcome::Duplicate;
+            }
+        }
+
+        let block = self
+            .blocks
+            .entry(block_hash)
+            .or_insert_with(|| BlockSignatures {
+                height,
+                pending: BTreeMap::new(),
+                flushed: HashSet::new(),
+            });
+        block.pending.insert(public_key, signature);
+        if block.pending.len() == 1 {
+            AddOutcome::FirstPending
+        } else {
+            AddOutcome::Pending
+        }
+    }
+
+    /// Takes the signatures pending for the given block, to be written to storage.
+    ///
+    /// Their validators are remembered, so that signatures by them for the same block are still
+    /// recognized as duplicates.
+    pub(super) fn take_pending(&mut self, block_hash: &BlockHash) -> Vec<Signature> {
+        let block = match self.blocks.get_mut(block_hash) {
+            Some(block) => block,
+            None => return vec![],
+        };
+        let flushed = &mut block.flushed;
+        mem::take(&mut block.pending)
+            .into_iter()
+            .map(|(public_key, signature)| {
+                flushed.insert(public_key);
+                signature
+            })
+            .collect()
+    }
+
+    /// Records that a block at `height` has been stored, dropping the signatures for blocks which
+    /// have become too old.
+    ///
+    /// Returns the number of dropped signatures which were still pending.
+    pub(super) fn block_stored(&mut self, height: u64) -> usize {
+        if height <= self.highest_height {
+            return 0;
+        }
+        self.highest_height = height;
+
+        let too_old: Vec<BlockHash> = self
+            .blocks
+            .iter()
+            .filter(|(_, block)| self.is_too_old(block.height))
+            .map(|(block_hash, _)| *block_hash)
+            .collect();
+        too_old
+            .iter()
+            .filter_map(|block_hash| self.blocks.remove(block_hash))
+            .map(|block| block.pending.len())
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{crypto::asymmetric_key::SecretKey, testing::TestRng};
+
+    fn sign(rng: &mut TestRng, block_hash: &BlockHash) -> (PublicKey, Signature) {
+        let secret_key = SecretKey::random(rng);
+        let public_key = PublicKey::from(&secret_key);
+        let signature = asymmetric_key::sign(block_hash.inner(), &secret_key, &public_key, rng);
+        (public_key, signature)
+    }
+
+    #[test]
+    fn should_deduplicate_per_block_and_validator() {
+        let mut rng = crate::new_rng();
+        let mut buffer = SignatureBuffer::new(10);
+        let block_hash = BlockHash::random(&mut rng);
+        let (public_key, signature) = sign(&mut rng, &block_hash);
+        let (other_public_key, other_signature) = sign(&mut rng, &block_hash);
+
+        assert_eq!(
+            buffer.add(block_hash, 5, public_key, signature),
+            AddOutcome::FirstPending
+        );
+        assert_eq!(
+            buffer.add(block_hash, 5, public_key, signature),
+            AddOutcome::Duplicate
+        );
+        assert_eq!(
+            buffer.add(block_hash, 5, other_public_key, other_signature),
+            AddOutcome::Pending
+        );
+
+        let mut flushed = buffer.take_pending(&block_hash);
+        flushed.sort();
+        let mut expected = vec![signature, other_signature];
+        expected.sort();
+        assert_eq!(flushed, expected);
+        assert!(buffer.take_pending(&block_hash).is_empty());
+
+        // validators whose signatures were already flushed are still recognized
+        assert_eq!(
+            buffer.add(block_hash, 5, public_key, signature),
+            AddOutcome::Duplicate
+        );
+    }
+
+    #[test]
+    fn should_reject_invalid_signature() {
+        let mut rng = crate::new_rng();
+        let block_hash = BlockHash::random(&mut rng);
+        let other_block_hash = BlockHash::random(&mut rng);
+        let (public_key, signature) = sign(&mut rng, &other_block_hash);
+
+        assert!(!verify(&block_hash, &public_key, &signature));
+        assert!(verify(&other_block_hash, &public_key, &signature));
+    }
+
+    #[test]
+    fn should_drop_signatures_for_old_blocks() {
+        let mut rng = crate::new_rng();
+        let mut buffer = SignatureBuffer::new(10);
+        let old_block_hash = BlockHash::random(&mut rng);
+        let (public_key, signature) = sign(&mut rng, &old_block_hash);
+
+        assert_eq!(
+            buffer.add(old_block_hash, 5, public_key, signature),
+            AddOutcome::FirstPending
+        );
+        assert_eq!(buffer.block_stored(15), 0);
+        assert_eq!(buffer.block_stored(16), 1);
+        assert!(buffer.take_pending(&old_block_hash).is_empty());
+
+        let (other_public_key, other_signature) = sign(&mut rng, &old_block_hash);
+        assert_eq!(
+            buffer.add(old_block_hash, 5, other_public_key, other_signature),
+            AddOutcome::TooOld
+        );
+    }
+}
@@ -22,10 +22,20 @@
 //! execution is interleaved. If we had downloaded the whole chain, and then deploys, and then
 //! execute (as we do in the first, SynchronizeTrustedHash, phase) it would have taken more time and
 //! we might miss more eras.
+//!
+//! Synchronization resumes where a previous run left off: executed blocks are persisted in storage,
+//! so on restart step 1 stops at the child of the highest stored block instead of at Genesis, and
+//! if the trusted block itself has already been executed, the synchronizer moves straight on to
+//! syncing its descendants from the highest stored block.  Global state is not synchronized
+//! separately but recreated by executing the blocks, so there is no trie download to resume.
+//!
+//! In follow mode, used by nodes running in sidecar mode, synchronization never finishes: once
+//! none of the peers has the child of the highest block, it is requested again periodically, so
+//! that the node keeps executing blocks as they are added to the linear chain.
 
 mod event;
 
-use std::{convert::Infallible, fmt::Display, mem};
+use std::{convert::Infallible, fmt::Display, mem, time::Duration};
 
 use datasize::DataSize;
 use rand::{seq::SliceRandom, Rng};
@@ -37,12 +47,18 @@ use crate::{
         requests::{BlockExecutorRequest, BlockValidationRequest, FetcherRequest, StorageRequest},
         EffectBuilder, EffectExt, EffectOptionExt, Effects,
     },
-    types::{Block, BlockByHeight, BlockHash, BlockHeader, FinalizedBlock},
+    types::{
+        Block, BlockByHeight, BlockHash, BlockHeader, FinalizedBlock, SyncProgress, SyncState,
+        Timestamp,
+    },
     NodeRng,
 };
 use event::BlockByHeightResult;
 pub use event::Event;
 
+/// The interval at which the child of the highest block is requested again in follow mode.
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_secs(4);
+
 pub trait ReactorEventT<I>:
     From<StorageRequest>
     + From<FetcherRequest<I, Block>>
@@ -158,18 +174,51 @@ pub(crate) struct LinearChainSync<I> {
     // NOTE: Maybe use a bitmask to decide which peers were tried?.
     peers_to_try: Vec<I>,
     state: State,
+    /// When synchronization started.
+    started: Timestamp,
+    /// The number of blocks downloaded from peers so far.
+    blocks_downloaded: u64,
+    /// The heights and timestamps of the lowest and highest blocks downloaded from peers so far.
+    downloaded_range: Option<((u64, Timestamp), (u64, Timestamp))>,
+    /// The highest block executed by a previous run, from which synchronization resumes.
+    resumed_from: Option<BlockHeader>,
+    /// Whether to keep synchronizing descendants as they are added to the linear chain, rather
+    /// than finishing once none of the peers has the next one.
+    follow: bool,
 }
 
 impl<I: Clone + PartialEq + 'static> LinearChainSync<I> {
-    pub fn new(init_hash: Option<BlockHash>) -> Self {
+    /// Creates a synchronizer for the linear chain up to and beyond `init_hash`, resuming after
+    /// `highest_stored`, the highest block already executed and persisted, if any.
+    pub fn new(init_hash: Option<BlockHash>, highest_stored: Option<BlockHeader>) -> Self {
         let state = init_hash.map_or(State::None, State::sync_trusted_hash);
+        let resumed_from = init_hash.and(highest_stored);
+        if let Some(header) = &resumed_from {
+            info!(
+                height = header.height(),
+                block_hash = %header.hash(),
+                "resuming linear chain synchronization after highest stored block"
+            );
+        }
         LinearChainSync {
             peers: Vec::new(),
             peers_to_try: Vec::new(),
             state,
+            started: Timestamp::now(),
+            blocks_downloaded: 0,
+            downloaded_range: None,
+            resumed_from,
+            follow: false,
         }
     }
 
+    /// Sets the synchronizer to follow the linear chain indefinitely rather than finishing once
+    /// it has caught up.
+    pub(crate) fn with_follow_mode(mut self) -> Self {
+        self.follow = true;
+        self
+    }
+
     /// Resets `peers_to_try` back to all `peers` we know of.
     fn reset_peers<R: Rng + ?Sized>(&mut self, rng: &mut R) {
         self.peers_to_try = self.peers.clone();
@@ -208,6 +257,61 @@ impl<I: Clone + PartialEq + 'static> LinearChainSync<I> {
         matches!(self.state, State::None | State::Done)
     }
 
+    /// Returns the progress of synchronizing the linear chain.
+    pub(crate) fn progress(&self) -> SyncProgress {
+        if self.is_synced() {
+            return SyncProgress::new(SyncState::Joining);
+        }
+
+        let now = Timestamp::now();
+        let elapsed_millis = now.saturating_sub(self.started).millis();
+        let download_rate = if elapsed_millis > 0 {
+            Some(self.blocks_downloaded as f64 * 1000.0 / elapsed_millis as f64)
+        } else {
+            None
+        };
+        let executed_height = self.latest_block().map(BlockHeader::height);
+        SyncProgress {
+            state: SyncState::Syncing,
+            highest_peer_block_height: self.downloaded_range.map(|(_, (height, _))| height),
+            estimated_blocks_remaining: self.downloaded_range.and_then(|(lowest, highest)| {
+                estimate_blocks_remaining(executed_height, lowest, highest, now)
+            }),
+            download_rate,
+            resumed_from_height: self.resumed_from.as_ref().map(BlockHeader::height),
+        }
+    }
+
+    /// Returns whether `block_header` is the child of the highest block executed by a previous run,
+    /// i.e. the lowest block still to be executed.
+    fn is_resume_point(&self, block_header: &BlockHeader) -> bool {
+        self.resumed_from.as_ref().map_or(false, |resumed_from| {
+            *block_header.parent_hash() == resumed_from.hash()
+        })
+    }
+
+    /// Returns the highest block executed by a previous run if it is at or above `block_header`,
+    /// in which case the block has already been executed.
+    fn resumed_at_or_above(&self, block_header: &BlockHeader) -> Option<BlockHeader> {
+        self.resumed_from
+            .as_ref()
+            .filter(|resumed_from| resumed_from.height() >= block_header.height())
+            .cloned()
+    }
+
+    /// Records the download of a block for the progress statistics.
+    fn record_download(&mut self, block_header: &BlockHeader) {
+        self.blocks_downloaded += 1;
+        let block = (block_header.height(), block_header.timestamp());
+        self.downloaded_range = Some(match self.downloaded_range {
+            None => (block, block),
+            Some((lowest, highest)) => (
+                if block.0 < lowest.0 { block } else { lowest },
+                if block.0 > highest.0 { block } else { highest },
+            ),
+        });
+    }
+
     fn block_downloaded<REv>(
         &mut self,
         rng: &mut NodeRng,
@@ -219,12 +323,28 @@ impl<I: Clone + PartialEq + 'static> LinearChainSync<I> {
         REv: ReactorEventT<I>,
     {
         self.reset_peers(rng);
+        self.record_download(block_header);
         self.state.block_downloaded(block_header);
         self.add_block(block_header.clone());
         match &self.state {
             State::None | State::Done => panic!("Downloaded block when in {} state.", self.state),
-            State::SyncingTrustedHash { .. } => {
-                if block_header.is_genesis_child() {
+            State::SyncingTrustedHash { trusted_hash, .. } => {
+                let trusted_hash = *trusted_hash;
+                if block_header.hash() == trusted_hash {
+                    if let Some(resumed_from) = self.resumed_at_or_above(block_header) {
+                        // The trusted block was executed by a previous run: continue with its
+                        // descendants after the highest stored block.
+                        info!(
+                            height = resumed_from.height(),
+                            "Trusted block already executed. Synchronizing descendants."
+                        );
+                        let next_height = resumed_from.height() + 1;
+                        self.state = State::sync_descendants(trusted_hash, resumed_from);
+                        let peer = self.random_peer_unsafe();
+                        return fetch_block_at_height(effect_builder, peer, next_height);
+                    }
+                }
+                if block_header.is_genesis_child() || self.is_resume_point(block_header) {
                     info!("Linear chain downloaded. Start downloading deploys.");
                     effect_builder
                         .immediately()
@@ -412,6 +532,14 @@ where
             }
             Event::GetBlockHeightResult(block_height, fetch_result) => match fetch_result {
                 BlockByHeightResult::Absent => match self.random_peer() {
+                    None if self.follow => {
+                        // We have caught up with the peers: try again once a block might have
+                        // been added.
+                        trace!(%block_height, "Next linear chain block not available yet.");
+                        effect_builder
+                            .set_timeout(FOLLOW_POLL_INTERVAL)
+                            .event(move |_| Event::PollNextBlock(block_height))
+                    }
                     None => {
                         // `block_height` not found on any of the peers.
                         // We have synchronized all, currently existing, descendants of trusted
@@ -533,6 +661,15 @@ where
                 trace!(?block_height, ?block_hash, "Block handled.");
                 self.block_handled(rng, effect_builder, *header)
             }
+            Event::PollNextBlock(block_height) => {
+                self.reset_peers(rng);
+                match self.random_peer() {
+                    None => effect_builder
+                        .set_timeout(FOLLOW_POLL_INTERVAL)
+                        .event(move |_| Event::PollNextBlock(block_height)),
+                    Some(peer) => fetch_block_at_height(effect_builder, peer, block_height),
+                }
+            }
         }
     }
 }
@@ -611,3 +748,83 @@ where
             move || Event::GetBlockHeightResult(block_height, BlockByHeightResult::Absent),
         )
 }
+
+/// Estimates the number of blocks still to be synchronized: those up to the highest block
+/// downloaded which haven't been executed yet, plus those expected to have been added to the chain
+/// since the highest block's timestamp, at the average rate between the lowest and highest blocks
+/// downloaded.
+///
+/// Returns `None` if the rate can't be determined yet.
+fn estimate_blocks_remaining(
+    executed_height: Option<u64>,
+    lowest: (u64, Timestamp),
+    highest: (u64, Timestamp),
+    now: Timestamp,
+) -> Option<u64> {
+    let (lowest_height, lowest_timestamp) = lowest;
+    let (highest_height, highest_timestamp) = highest;
+    let heights = highest_height.saturating_sub(lowest_height);
+    let duration = highest_timestamp.saturating_sub(lowest_timestamp).millis();
+    if heights == 0 || duration == 0 {
+        return None;
+    }
+
+    let not_executed = match executed_height {
+        Some(height) => highest_height.saturating_sub(height),
+        None => highest_height + 1,
+    };
+    let since_highest = now.saturating_sub(highest_timestamp).millis();
+    let expected = u128::from(since_highest) * u128::from(heights) / u128::from(duration);
+    Some(not_executed + expected as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestRng;
+
+    #[test]
+    fn should_estimate_blocks_remaining() {
+        let lowest = (10, Timestamp::from(10_000));
+        let highest = (20, Timestamp::from(20_000));
+
+        // Ten blocks in ten seconds; five seconds later five more blocks are expected.
+        let now = Timestamp::from(25_000);
+        assert_eq!(
+            estimate_blocks_remaining(Some(15), lowest, highest, now),
+            Some(10)
+        );
+        assert_eq!(
+            estimate_blocks_remaining(None, lowest, highest, now),
+            Some(26)
+        );
+
+        // No rate can be determined from a single block.
+        assert_eq!(estimate_blocks_remaining(None, highest, highest, now), None);
+    }
+
+    #[test]
+    fn should_resume_after_highest_stored_block() {
+        let mut rng = crate::new_rng();
+        let block_at_height = |rng: &mut TestRng, height: u64| {
+            let mut block = Block::random(rng);
+            block.set_height(height);
+            block.take_header()
+        };
+        let highest_stored = block_at_height(&mut rng, 10);
+        let trusted = block_at_height(&mut rng, 5);
+        let sync: LinearChainSync<u64> =
+            LinearChainSync::new(Some(trusted.hash()), Some(highest_stored.clone()));
+
+        assert_eq!(sync.progress().resumed_from_height, Some(10));
+        assert_eq!(sync.resumed_at_or_above(&trusted), Some(highest_stored));
+        assert!(sync
+            .resumed_at_or_above(&block_at_height(&mut rng, 11))
+            .is_none());
+
+        // Without a trusted hash there is nothing to synchronize, let alone resume.
+        let sync: LinearChainSync<u64> =
+            LinearChainSync::new(None, Some(block_at_height(&mut rng, 10)));
+        assert_eq!(sync.progress().resumed_from_height, None);
+    }
+}
@@ -16,6 +16,8 @@ pub enum Event<I> {
     StartDownloadingDeploys,
     NewPeerConnected(I),
     BlockHandled(Box<BlockHeader>),
+    /// The block at the given height should be requested again, as none of the peers had it.
+    PollNextBlock(u64),
 }
 
 #[derive(Debug)]
@@ -53,6 +55,7 @@ where
             Event::GetBlockHeightResult(height, res) => {
                 write!(f, "Get block result for height {}: {:?}", height, res)
             }
+            Event::PollNextBlock(height) => write!(f, "Poll for block at height {}", height),
         }
     }
 }
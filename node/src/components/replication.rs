@@ -0,0 +1,487 @@
+//! Read replica state streaming.
+//!
+//! A fleet of nodes serving the APIs need not each execute every deploy: a single primary node
+//! executes the blocks, and its read replicas follow it by copying the result.
+//!
+//! The primary runs a replication server, which serves each linear chain block together with its
+//! deploys and their execution results, and the tries of global state by their hashes.  A request
+//! for a block the primary doesn't have yet is held until the block is stored or the long-poll
+//! timeout elapses, so replicas learn of new blocks as soon as they are added.
+//!
+//! A replica is a node running in sidecar mode with a primary configured.  Instead of
+//! synchronizing with and executing the blocks of its peers, its replication client requests each
+//! block from the primary in turn, starting after its highest stored block.  It then walks the
+//! global state under the block's state root hash, requesting only the tries it doesn't hold
+//! already, which for consecutive blocks amounts to the tries committed by the block.  Each trie
+//! is checked against its hash, and the tries are written children first, so that the global
+//! state is never left referring to missing tries.  Finally the block is announced as executed,
+//! so that it is stored and reported to the event stream just as a block executed locally.
+//!
+//! The channel is authenticated with a secret shared by the primary and its replicas, see the
+//! [`protocol`] module.
+
+mod config;
+mod event;
+mod protocol;
+mod server;
+
+use std::{
+    collections::{HashMap, HashSet},
+    convert::Infallible,
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+    time::Duration,
+};
+
+use datasize::DataSize;
+use futures::{future::BoxFuture, FutureExt};
+use reqwest::{Client, Method, Url};
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+use tokio::{sync::oneshot, task::JoinHandle};
+use tracing::{debug, error, info, trace, warn};
+
+use casper_execution_engine::{
+    shared::{newtypes::Blake2bHash, stored_value::StoredValue},
+    storage::trie::Trie,
+};
+use casper_types::{bytesrepr, Key};
+
+use crate::{
+    components::Component,
+    crypto::hash::Digest,
+    effect::{
+        announcements::BlockExecutorAnnouncement,
+        requests::{ContractRuntimeRequest, StorageRequest},
+        EffectBuilder, EffectExt, Effects,
+    },
+    types::Timestamp,
+    NodeRng,
+};
+
+pub use config::Config;
+pub(crate) use event::Event;
+use protocol::{ReplicatedBlock, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+
+/// The interval at which the replication server checks whether a block requested by a replica
+/// has been stored.
+const BLOCK_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// A helper trait whose bounds represent the requirements for a reactor event that the
+/// replication client can work with.
+pub(crate) trait ReactorEventT:
+    From<Event>
+    + From<StorageRequest>
+    + From<ContractRuntimeRequest>
+    + From<BlockExecutorAnnouncement>
+    + Send
+    + 'static
+{
+}
+
+impl<REv> ReactorEventT for REv where
+    REv: From<Event>
+        + From<StorageRequest>
+        + From<ContractRuntimeRequest>
+        + From<BlockExecutorAnnouncement>
+        + Send
+        + 'static
+{
+}
+
+/// An error constructing the replication server or client.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// No secret is configured to authenticate the replication channel.
+    #[error("replication requires a secret shared by the primary and its replicas")]
+    MissingSecret,
+
+    /// The HTTP client could not be built.
+    #[error("failed to build replication HTTP client: {0}")]
+    Client(#[from] reqwest::Error),
+
+    /// The configured URL of the primary is invalid.
+    #[error("invalid replication primary URL {url}: {message}")]
+    InvalidUrl { url: String, message: String },
+}
+
+/// Returns the configured secret, which is required to replicate in either direction.
+fn secret(config: &Config) -> Result<Arc<str>, Error> {
+    match &config.secret {
+        Some(secret) if !secret.is_empty() => Ok(Arc::from(secret.as_str())),
+        _ => Err(Error::MissingSecret),
+    }
+}
+
+/// The server streaming the linear chain and global state of a primary node to its replicas.
+#[derive(Debug)]
+pub(crate) struct ReplicationServer {
+    /// When the message is sent, it signals the server loop to exit cleanly.
+    shutdown_sender: Option<oneshot::Sender<()>>,
+    /// The task handle which will only join once the server loop has exited.
+    server_join_handle: Option<JoinHandle<()>>,
+}
+
+impl ReplicationServer {
+    /// Starts the replication server if a listen address is configured.
+    pub(crate) fn new<REv>(
+        config: Config,
+        effect_builder: EffectBuilder<REv>,
+    ) -> Result<Option<Self>, Error>
+    where
+        REv: From<StorageRequest> + From<ContractRuntimeRequest> + Send + 'static,
+    {
+        if config.listen_address.is_none() {
+            return Ok(None);
+        }
+        let secret = secret(&config)?;
+        let (shutdown_sender, shutdown_receiver) = oneshot::channel::<()>();
+        let server_join_handle = tokio::spawn(server::run(
+            config,
+            secret,
+            effect_builder,
+            shutdown_receiver,
+        ));
+        Ok(Some(ReplicationServer {
+            shutdown_sender: Some(shutdown_sender),
+            server_join_handle: Some(server_join_handle),
+        }))
+    }
+
+    /// Signals the server to stop accepting new connections and to exit once the in-flight
+    /// requests have completed.
+    ///
+    /// The returned future completes once the server has exited.
+    pub(crate) fn shut_down(&mut self) -> BoxFuture<'static, ()> {
+        if let Some(shutdown_sender) = self.shutdown_sender.take() {
+            let _ = shutdown_sender.send(());
+        }
+        let maybe_join_handle = self.server_join_handle.take();
+        async move {
+            if let Some(join_handle) = maybe_join_handle {
+                match join_handle.await {
+                    Ok(_) => debug!("replication server exited cleanly"),
+                    Err(error) => error!(%error, "could not join replication server task cleanly"),
+                }
+            } else {
+                warn!("replication server shutdown while already shut down")
+            }
+        }
+        .boxed()
+    }
+}
+
+/// The connection of a replica to its primary.
+#[derive(Clone)]
+struct Primary {
+    client: Client,
+    url: Url,
+    secret: Arc<str>,
+    max_tries_per_request: usize,
+}
+
+impl Primary {
+    /// Sends a signed request to the primary and decodes its response, once it is verified to be
+    /// signed by the primary.
+    async fn request<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Vec<u8>,
+    ) -> Result<T, String> {
+        let url = self
+            .url
+            .join(path)
+            .map_err(|error| format!("invalid URL path {}: {}", path, error))?;
+        let timestamp = Timestamp::now();
+        let signature =
+            protocol::sign_request(&self.secret, timestamp, method.as_str(), url.path(), &body)
+                .map_err(|error| format!("failed to sign request: {}", error))?;
+        let response = self
+            .client
+            .request(method, url)
+            .header(TIMESTAMP_HEADER, timestamp.millis().to_string())
+            .header(SIGNATURE_HEADER, signature)
+            .body(body)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|error| error.to_string())?;
+        let response_signature = response
+            .headers()
+            .get(SIGNATURE_HEADER)
+            .and_then(|header_value| header_value.to_str().ok())
+            .map(str::to_string)
+            .ok_or_else(|| "unsigned response".to_string())?;
+        let response_body = response.bytes().await.map_err(|error| error.to_string())?;
+        if !protocol::verify_response(&self.secret, timestamp, &response_body, &response_signature)
+        {
+            return Err("invalid response signature".to_string());
+        }
+        bincode::deserialize(&response_body)
+            .map_err(|error| format!("failed to decode response: {}", error))
+    }
+
+    /// Requests the block at `height`, returning `None` if the primary doesn't have it yet.
+    async fn block(&self, height: u64) -> Result<Option<ReplicatedBlock>, String> {
+        let path = format!("{}/{}", protocol::BLOCK_PATH, height);
+        self.request(Method::GET, &path, vec![]).await
+    }
+
+    /// Requests the serialized tries under `trie_keys`.
+    async fn tries(&self, trie_keys: &[Digest]) -> Result<Vec<Option<Vec<u8>>>, String> {
+        let body = bincode::serialize(trie_keys).map_err(|error| error.to_string())?;
+        self.request(Method::POST, protocol::TRIES_PATH, body).await
+    }
+}
+
+/// The client replicating the linear chain and global state of a primary node.
+#[derive(DataSize)]
+pub(crate) struct ReplicationClient {
+    #[data_size(skip)]
+    primary: Primary,
+    retry_interval: Duration,
+    /// The height of the next block to replicate.
+    next_height: u64,
+}
+
+impl ReplicationClient {
+    /// Creates a client replicating the blocks following the one at `highest_stored_height`, or
+    /// all blocks if there is none.
+    pub(crate) fn new<REv: ReactorEventT>(
+        config: &Config,
+        effect_builder: EffectBuilder<REv>,
+        highest_stored_height: Option<u64>,
+    ) -> Result<(Self, Effects<Event>), Error> {
+        let primary_url = config.primary_url.clone().unwrap_or_default();
+        let mut url = Url::parse(&primary_url).map_err(|error| Error::InvalidUrl {
+            url: primary_url.clone(),
+            message: error.to_string(),
+        })?;
+        // Ensure the endpoint paths are joined onto the URL's path rather than replacing its last
+        // segment.
+        if !url.path().ends_with('/') {
+            url.set_path(&format!("{}/", url.path()));
+        }
+        let primary = Primary {
+            client: Client::builder().timeout(config.request_timeout).build()?,
+            url,
+            secret: secret(config)?,
+            max_tries_per_request: config.max_tries_per_request.max(1) as usize,
+        };
+        let next_height = highest_stored_height.map_or(0, |height| height + 1);
+        info!(%primary_url, %next_height, "replicating from primary");
+        let client = ReplicationClient {
+            primary,
+            retry_interval: config.retry_interval,
+            next_height,
+        };
+        let effects = effect_builder.immediately().event(|_| Event::Poll);
+        Ok((client, effects))
+    }
+}
+
+impl Debug for ReplicationClient {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("ReplicationClient")
+            .field("primary_url", &self.primary.url.as_str())
+            .field("next_height", &self.next_height)
+            .finish()
+    }
+}
+
+impl<REv> Component<REv> for ReplicationClient
+where
+    REv: ReactorEventT,
+{
+    type Event = Event;
+    type ConstructionError = Infallible;
+
+    fn handle_event(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        _rng: &mut NodeRng,
+        event: Self::Event,
+    ) -> Effects<Self::Event> {
+        match event {
+            Event::Poll => {
+                let height = self.next_height;
+                let primary = self.primary.clone();
+                replicate_block(effect_builder, primary, height)
+                    .event(move |result| Event::Replicated { height, result })
+            }
+            // The block has been announced: wait for the linear chain to handle it.
+            Event::Replicated {
+                result: Ok(true), ..
+            } => Effects::new(),
+            // The long poll timed out: ask again.
+            Event::Replicated {
+                result: Ok(false), ..
+            } => effect_builder.immediately().event(|_| Event::Poll),
+            Event::Replicated {
+                height,
+                result: Err(error),
+            } => {
+                warn!(%height, %error, "failed to replicate block from primary");
+                effect_builder
+                    .set_timeout(self.retry_interval)
+                    .event(|_| Event::Poll)
+            }
+            Event::BlockHandled(block_header) => {
+                if block_header.height() != self.next_height {
+                    trace!(height = block_header.height(), "ignoring handled block");
+                    return Effects::new();
+                }
+                debug!(height = self.next_height, "replicated block");
+                self.next_height += 1;
+                effect_builder.immediately().event(|_| Event::Poll)
+            }
+        }
+    }
+}
+
+/// Replicates the block at `height` from `primary`, together with its global state.
+///
+/// Returns `false` if the primary doesn't have the block yet.
+async fn replicate_block<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    primary: Primary,
+    height: u64,
+) -> Result<bool, String> {
+    let ReplicatedBlock {
+        block,
+        deploys,
+        execution_results,
+    } = match primary.block(height).await? {
+        Some(replicated_block) => replicated_block,
+        None => return Ok(false),
+    };
+
+    if block.height() != height {
+        return Err(format!(
+            "requested block {}, got {}",
+            height,
+            block.height()
+        ));
+    }
+    block.verify().map_err(|error| error.to_string())?;
+    if height > 0 {
+        let parent_hash = effect_builder
+            .get_block_at_height(height - 1)
+            .await
+            .map(|parent| *parent.hash());
+        if parent_hash.as_ref() != Some(block.header().parent_hash()) {
+            return Err(format!(
+                "block {} doesn't descend from the stored block at height {}",
+                block.hash(),
+                height - 1
+            ));
+        }
+    }
+    let deploy_hashes: Vec<_> = deploys.iter().map(|deploy| *deploy.id()).collect();
+    if &deploy_hashes != block.deploy_hashes() {
+        return Err(format!(
+            "deploys don't match those of block {}",
+            block.hash()
+        ));
+    }
+
+    replicate_global_state(effect_builder, &primary, *block.state_root_hash()).await?;
+
+    let mut deploy_headers = HashMap::new();
+    for deploy in deploys {
+        let _ = deploy_headers.insert(*deploy.id(), deploy.header().clone());
+        let _ = effect_builder.put_deploy_to_storage(Box::new(deploy)).await;
+    }
+    let execution_results: HashMap<_, _> = execution_results
+        .into_iter()
+        .filter_map(|(deploy_hash, execution_result)| {
+            let deploy_header = deploy_headers.remove(&deploy_hash)?;
+            Some((deploy_hash, (deploy_header, execution_result)))
+        })
+        .collect();
+
+    // The deploys were executed by the primary, so their results are announced along with the
+    // block rather than as each finishes executing.
+    for (deploy_hash, (deploy_header, execution_result)) in &execution_results {
+        effect_builder
+            .announce_deploy_processed(
+                *deploy_hash,
+                deploy_header.clone(),
+                block.height(),
+                execution_result.clone(),
+            )
+            .await;
+    }
+
+    effect_builder
+        .announce_linear_chain_block(block, execution_results)
+        .await;
+    Ok(true)
+}
+
+/// Copies the global state under `state_root_hash` from `primary`, skipping the tries already
+/// held.
+async fn replicate_global_state<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    primary: &Primary,
+    state_root_hash: Digest,
+) -> Result<(), String> {
+    let mut pending = vec![state_root_hash];
+    let mut requested = HashSet::new();
+    // The tries in the order they were received, each after its parent.
+    let mut received = vec![];
+    while !pending.is_empty() {
+        let batch_len = pending.len().min(primary.max_tries_per_request);
+        let batch: Vec<_> = pending.drain(..batch_len).collect();
+        let missing = effect_builder
+            .find_missing_tries(batch)
+            .await
+            .map_err(|error| format!("failed to read global state: {}", error))?;
+        if missing.is_empty() {
+            continue;
+        }
+        let tries = primary.tries(&missing).await?;
+        if tries.len() != missing.len() {
+            return Err(format!(
+                "requested {} tries, got {}",
+                missing.len(),
+                tries.len()
+            ));
+        }
+        for (trie_key, maybe_trie_bytes) in missing.into_iter().zip(tries) {
+            let trie_bytes =
+                maybe_trie_bytes.ok_or_else(|| format!("primary is missing trie {}", trie_key))?;
+            if Digest::from(Blake2bHash::new(&trie_bytes)) != trie_key {
+                return Err(format!("trie {} doesn't match its hash", trie_key));
+            }
+            let trie: Trie<Key, StoredValue> = bytesrepr::deserialize(trie_bytes)
+                .map_err(|error| format!("failed to decode trie {}: {}", trie_key, error))?;
+            let children: Vec<Digest> = match &trie {
+                Trie::Leaf { .. } => vec![],
+                Trie::Node { pointer_block } => pointer_block
+                    .to_indexed_pointers()
+                    .map(|(_, pointer)| (*pointer.hash()).into())
+                    .collect(),
+                Trie::Extension { pointer, .. } => vec![(*pointer.hash()).into()],
+            };
+            pending.extend(
+                children
+                    .into_iter()
+                    .filter(|child| requested.insert(*child)),
+            );
+            received.push((trie_key, trie));
+        }
+    }
+
+    // Write the children before their parents.
+    received.reverse();
+    let count = received.len();
+    effect_builder
+        .write_tries(received)
+        .await
+        .map_err(|error| format!("failed to write global state: {}", error))?;
+    trace!(%state_root_hash, count, "replicated global state");
+    Ok(())
+}
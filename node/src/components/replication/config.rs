@@ -0,0 +1,96 @@
+use std::{
+    fmt::{self, Debug, Formatter},
+    time::Duration,
+};
+
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+
+/// Default time allowed for the primary to respond to a request.  Must exceed the long-poll
+/// timeout.
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default time for which the primary holds a request for a block it doesn't have yet.
+const DEFAULT_LONG_POLL_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default delay before a replica retries after a failed request.
+const DEFAULT_RETRY_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Default maximum number of tries requested at once.
+const DEFAULT_MAX_TRIES_PER_REQUEST: u32 = 1_000;
+
+/// Default time allowed for in-flight requests to complete when shutting down.
+const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Replication configuration.
+#[derive(Clone, DataSize, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Address to bind the replication server to, from which read replicas stream the linear
+    /// chain and global state.  Not served if unset.
+    #[serde(default)]
+    pub listen_address: Option<String>,
+
+    /// URL of the replication server of the primary node to follow.  If set, a node running in
+    /// sidecar mode replicates the primary's blocks and global state instead of synchronizing
+    /// with and executing the blocks of its peers.
+    #[serde(default)]
+    pub primary_url: Option<String>,
+
+    /// Secret shared by the primary and its replicas, used to sign each request and response with
+    /// HMAC-SHA256.  Required if either `listen_address` or `primary_url` is set.
+    #[serde(default)]
+    pub secret: Option<String>,
+
+    /// Time in milliseconds allowed for the primary to respond to a request.
+    #[serde(with = "crate::utils::milliseconds")]
+    pub request_timeout: Duration,
+
+    /// Time in milliseconds for which the primary holds a request for a block it doesn't have yet.
+    #[serde(with = "crate::utils::milliseconds")]
+    pub long_poll_timeout: Duration,
+
+    /// Delay in milliseconds before a replica retries after a failed request.
+    #[serde(with = "crate::utils::milliseconds")]
+    pub retry_interval: Duration,
+
+    /// Maximum number of tries requested from, or served by, the primary in a single request.
+    pub max_tries_per_request: u32,
+
+    /// Time in milliseconds allowed for in-flight requests to complete when shutting down the
+    /// replication server, after which any remaining connections are dropped.
+    #[serde(with = "crate::utils::milliseconds")]
+    pub shutdown_timeout: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            listen_address: None,
+            primary_url: None,
+            secret: None,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            long_poll_timeout: DEFAULT_LONG_POLL_TIMEOUT,
+            retry_interval: DEFAULT_RETRY_INTERVAL,
+            max_tries_per_request: DEFAULT_MAX_TRIES_PER_REQUEST,
+            shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+        }
+    }
+}
+
+// Manually implemented to keep the secret out of the logs.
+impl Debug for Config {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("Config")
+            .field("listen_address", &self.listen_address)
+            .field("primary_url", &self.primary_url)
+            .field("request_timeout", &self.request_timeout)
+            .field("long_poll_timeout", &self.long_poll_timeout)
+            .field("retry_interval", &self.retry_interval)
+            .field("max_tries_per_request", &self.max_tries_per_request)
+            .field("shutdown_timeout", &self.shutdown_timeout)
+            .finish()
+    }
+}
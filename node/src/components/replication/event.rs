@@ -0,0 +1,37 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::types::BlockHeader;
+
+#[derive(Debug)]
+pub enum Event {
+    /// The block at the next height should be replicated from the primary.
+    Poll,
+    /// An attempt to replicate the block at the given height has completed.  On success, holds
+    /// whether the primary had the block; if so, it has been announced to the linear chain.
+    Replicated {
+        height: u64,
+        result: Result<bool, String>,
+    },
+    /// A block has been stored and handled by the linear chain.
+    BlockHandled(Box<BlockHeader>),
+}
+
+impl Display for Event {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Event::Poll => write!(formatter, "poll"),
+            Event::Replicated { height, result } => write!(
+                formatter,
+                "replication of block at height {} completed: {:?}",
+                height, result
+            ),
+            Event::BlockHandled(block_header) => {
+                write!(
+                    formatter,
+                    "block handled at height {}",
+                    block_header.height()
+                )
+            }
+        }
+    }
+}
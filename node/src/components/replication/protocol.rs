@@ -0,0 +1,171 @@
+//! The wire protocol between a primary and its read replicas.
+//!
+//! Request and response bodies are bincode-encoded.  Each request carries the time at which it was
+//! sent, in milliseconds since the Unix epoch, and the hex-encoded HMAC-SHA256 keyed by the shared
+//! secret of that timestamp, the request's method, path and body.  The primary refuses requests
+//! whose signature doesn't verify or whose timestamp is too far from its own clock, and signs its
+//! response body together with the request's timestamp, so that a replica only accepts a response
+//! to a request it sent.
+
+use std::time::Duration;
+
+use openssl::{error::ErrorStack, hash::MessageDigest, memcmp, pkey::PKey, sign::Signer};
+use serde::{Deserialize, Serialize};
+
+use crate::types::{json_compatibility::ExecutionResult, Block, Deploy, DeployHash, Timestamp};
+
+/// The header holding the time at which a request was sent.
+pub(super) const TIMESTAMP_HEADER: &str = "X-Casper-Replication-Timestamp";
+
+/// The header holding the signature of a request or response.
+pub(super) const SIGNATURE_HEADER: &str = "X-Casper-Replication-Signature";
+
+/// The URL path of the block endpoint, followed by the block's height.
+pub(super) const BLOCK_PATH: &str = "replication/block";
+
+/// The URL path of the tries endpoint.
+pub(super) const TRIES_PATH: &str = "replication/tries";
+
+/// The maximum difference between the timestamp of a request and the primary's clock.
+const MAX_CLOCK_SKEW: Duration = Duration::from_secs(60);
+
+/// A linear chain block together with its deploys and their execution results, as sent to a
+/// replica.  The global state under the block's state root hash is sent separately.
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct ReplicatedBlock {
+    pub(super) block: Block,
+    /// The block's deploys, in the same order as their hashes in the block.
+    pub(super) deploys: Vec<Deploy>,
+    pub(super) execution_results: Vec<(DeployHash, ExecutionResult)>,
+}
+
+/// Returns the signature of a request.
+pub(super) fn sign_request(
+    secret: &str,
+    timestamp: Timestamp,
+    method: &str,
+    path: &str,
+    body: &[u8],
+) -> Result<String, ErrorStack> {
+    sign(secret, &request_message(timestamp, method, path, body))
+}
+
+/// Returns whether `signature` is the valid signature of a request sent recently.
+pub(super) fn verify_request(
+    secret: &str,
+    timestamp: Timestamp,
+    method: &str,
+    path: &str,
+    body: &[u8],
+    signature: &str,
+) -> bool {
+    let now = Timestamp::now();
+    let skew = if timestamp > now {
+        timestamp.saturating_sub(now)
+    } else {
+        now.saturating_sub(timestamp)
+    };
+    Duration::from(skew) <= MAX_CLOCK_SKEW
+        && verify(
+            secret,
+            &request_message(timestamp, method, path, body),
+            signature,
+        )
+}
+
+/// Returns the signature of the response to a request sent at `timestamp`.
+pub(super) fn sign_response(
+    secret: &str,
+    timestamp: Timestamp,
+    body: &[u8],
+) -> Result<String, ErrorStack> {
+    sign(secret, &response_message(timestamp, body))
+}
+
+/// Returns whether `signature` is the valid signature of the response to a request sent at
+/// `timestamp`.
+pub(super) fn verify_response(
+    secret: &str,
+    timestamp: Timestamp,
+    body: &[u8],
+    signature: &str,
+) -> bool {
+    verify(secret, &response_message(timestamp, body), signature)
+}
+
+fn request_message(timestamp: Timestamp, method: &str, path: &str, body: &[u8]) -> Vec<u8> {
+    let mut message = format!("{}\n{}\n{}\n", timestamp.millis(), method, path).into_bytes();
+    message.extend_from_slice(body);
+    message
+}
+
+fn response_message(timestamp: Timestamp, body: &[u8]) -> Vec<u8> {
+    let mut message = format!("{}\n", timestamp.millis()).into_bytes();
+    message.extend_from_slice(body);
+    message
+}
+
+/// Returns the hex-encoded HMAC-SHA256 of `message` keyed by `secret`.
+fn sign(secret: &str, message: &[u8]) -> Result<String, ErrorStack> {
+    let key = PKey::hmac(secret.as_bytes())?;
+    let mut signer = Signer::new(MessageDigest::sha256(), &key)?;
+    signer.update(message)?;
+    Ok(hex::encode(signer.sign_to_vec()?))
+}
+
+/// Returns whether `signature` is the hex-encoded HMAC-SHA256 of `message` keyed by `secret`,
+/// comparing them in constant time.
+fn verify(secret: &str, message: &[u8], signature: &str) -> bool {
+    match sign(secret, message) {
+        Ok(expected) => {
+            expected.len() == signature.len()
+                && memcmp::eq(expected.as_bytes(), signature.as_bytes())
+        }
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "shared secret";
+
+    #[test]
+    fn should_verify_only_genuine_recent_requests() {
+        let now = Timestamp::now();
+        let path = "/replication/block/7";
+        let signature = sign_request(SECRET, now, "GET", path, b"").unwrap();
+        assert!(verify_request(SECRET, now, "GET", path, b"", &signature));
+
+        assert!(!verify_request("other", now, "GET", path, b"", &signature));
+        assert!(!verify_request(
+            SECRET,
+            now,
+            "GET",
+            "/replication/block/8",
+            b"",
+            &signature
+        ));
+        assert!(!verify_request(SECRET, now, "POST", path, b"", &signature));
+        assert!(!verify_request(SECRET, now, "GET", path, b"x", &signature));
+
+        let stale = Timestamp::from(now.millis() - 2 * MAX_CLOCK_SKEW.as_millis() as u64);
+        let signature = sign_request(SECRET, stale, "GET", path, b"").unwrap();
+        assert!(!verify_request(SECRET, stale, "GET", path, b"", &signature));
+    }
+
+    #[test]
+    fn should_bind_response_to_request_timestamp() {
+        let timestamp = Timestamp::from(1_000);
+        let signature = sign_response(SECRET, timestamp, b"body").unwrap();
+        assert!(verify_response(SECRET, timestamp, b"body", &signature));
+        assert!(!verify_response(
+            SECRET,
+            Timestamp::from(1_001),
+            b"body",
+            &signature
+        ));
+        assert!(!verify_response(SECRET, timestamp, b"other", &signature));
+    }
+}
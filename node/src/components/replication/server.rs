@@ -0,0 +1,288 @@
+use std::{
+    convert::Infallible,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use futures::future;
+use http::{Method, Response, StatusCode};
+use hyper::{
+    body::{Body, Bytes},
+    Server,
+};
+use serde::Serialize;
+use tokio::{select, sync::oneshot};
+use tracing::{debug, info, warn};
+use warp::{filters::path::FullPath, Filter};
+
+use super::{
+    protocol::{self, ReplicatedBlock, SIGNATURE_HEADER, TIMESTAMP_HEADER},
+    Config, BLOCK_POLL_INTERVAL,
+};
+use crate::{
+    crypto::hash::Digest,
+    effect::{
+        requests::{ContractRuntimeRequest, StorageRequest},
+        EffectBuilder,
+    },
+    types::{TimeDiff, Timestamp},
+    utils,
+};
+
+/// An authenticated request: the time at which it was sent, and its body.
+struct Request {
+    timestamp: Timestamp,
+    body: Bytes,
+}
+
+/// Run the replication HTTP server.
+///
+/// A message received on `shutdown_receiver` will cause the server to stop accepting new
+/// connections and exit once the in-flight requests have completed, or the shutdown timeout has
+/// elapsed.
+pub(super) async fn run<REv>(
+    config: Config,
+    secret: Arc<str>,
+    effect_builder: EffectBuilder<REv>,
+    shutdown_receiver: oneshot::Receiver<()>,
+) where
+    REv: From<StorageRequest> + From<ContractRuntimeRequest> + Send + 'static,
+{
+    let listen_address = match &config.listen_address {
+        Some(listen_address) => listen_address.clone(),
+        None => return,
+    };
+
+    // Yields the request if it is signed with the shared secret, `None` otherwise.
+    let authenticated = {
+        let secret = Arc::clone(&secret);
+        warp::method()
+            .and(warp::path::full())
+            .and(warp::header::optional::<u64>(TIMESTAMP_HEADER))
+            .and(warp::header::optional::<String>(SIGNATURE_HEADER))
+            .and(warp::body::bytes())
+            .map(
+                move |method: Method,
+                      path: FullPath,
+                      maybe_timestamp: Option<u64>,
+                      maybe_signature: Option<String>,
+                      body: Bytes| {
+                    let timestamp =
+                        Timestamp::zero() + TimeDiff::from(Duration::from_millis(maybe_timestamp?));
+                    if protocol::verify_request(
+                        &secret,
+                        timestamp,
+                        method.as_str(),
+                        path.as_str(),
+                        &body,
+                        &maybe_signature?,
+                    ) {
+                        Some(Request { timestamp, body })
+                    } else {
+                        None
+                    }
+                },
+            )
+    };
+
+    let block = {
+        let secret = Arc::clone(&secret);
+        let long_poll_timeout = config.long_poll_timeout;
+        warp::get()
+            .and(warp::path!("replication" / "block" / u64))
+            .and(authenticated.clone())
+            .and_then(move |height: u64, maybe_request: Option<Request>| {
+                let secret = Arc::clone(&secret);
+                async move {
+                    let request = match maybe_request {
+                        Some(request) => request,
+                        None => return Ok::<_, Infallible>(unauthorized()),
+                    };
+                    let response = match read_block(effect_builder, height, long_poll_timeout).await
+                    {
+                        Ok(maybe_block) => {
+                            signed_response(&secret, request.timestamp, &maybe_block)
+                        }
+                        Err(error) => {
+                            warn!(%height, %error, "failed to read block for replica");
+                            status_response(StatusCode::INTERNAL_SERVER_ERROR)
+                        }
+                    };
+                    Ok(response)
+                }
+            })
+    };
+
+    let tries = {
+        let secret = Arc::clone(&secret);
+        let max_tries_per_request = config.max_tries_per_request as usize;
+        warp::post()
+            .and(warp::path!("replication" / "tries"))
+            .and(authenticated)
+            .and_then(move |maybe_request: Option<Request>| {
+                let secret = Arc::clone(&secret);
+                async move {
+                    let request = match maybe_request {
+                        Some(request) => request,
+                        None => return Ok::<_, Infallible>(unauthorized()),
+                    };
+                    let trie_keys: Vec<Digest> = match bincode::deserialize(&request.body) {
+                        Ok(trie_keys) => trie_keys,
+                        Err(error) => {
+                            debug!(%error, "failed to decode tries request");
+                            return Ok(status_response(StatusCode::BAD_REQUEST));
+                        }
+                    };
+                    if trie_keys.len() > max_tries_per_request {
+                        return Ok(status_response(StatusCode::PAYLOAD_TOO_LARGE));
+                    }
+                    let response = match effect_builder.read_tries(trie_keys).await {
+                        Ok(tries) => signed_response(&secret, request.timestamp, &tries),
+                        Err(error) => {
+                            warn!(%error, "failed to read tries for replica");
+                            status_response(StatusCode::INTERNAL_SERVER_ERROR)
+                        }
+                    };
+                    Ok(response)
+                }
+            })
+    };
+
+    let service = warp::service(block.or(tries).unify());
+
+    let mut server_address = match utils::resolve_address(&listen_address) {
+        Ok(address) => address,
+        Err(error) => {
+            warn!(%error, "failed to start replication server, cannot parse address");
+            return;
+        }
+    };
+
+    // Try to bind to the user's chosen port, or if that fails, try once to bind to any port then
+    // error out if that fails too.
+    let builder = loop {
+        match Server::try_bind(&server_address) {
+            Ok(builder) => {
+                break builder;
+            }
+            Err(error) => {
+                if server_address.port() == 0 {
+                    warn!(%error, "failed to start replication server");
+                    return;
+                } else {
+                    server_address.set_port(0);
+                    debug!(%error, "failed to start replication server. retrying on random port");
+                }
+            }
+        }
+    };
+
+    // Start the server.
+    let make_svc =
+        hyper::service::make_service_fn(move |_| future::ok::<_, Infallible>(service.clone()));
+
+    let server = builder.serve(make_svc);
+    info!(address = %server.local_addr(), "started replication server");
+
+    // Shut down the server gracefully, dropping any connections still open after the timeout.
+    let (shutdown_signal, drain_deadline) =
+        utils::graceful_shutdown_with_deadline(shutdown_receiver, config.shutdown_timeout);
+    select! {
+        result = server.with_graceful_shutdown(shutdown_signal) => {
+            if let Err(error) = result {
+                warn!(%error, "error running replication server");
+            }
+        }
+        _ = drain_deadline => {
+            warn!("replication requests still in flight after shutdown timeout, dropping \
+                connections");
+        }
+    }
+}
+
+/// Reads the block at `height` with its deploys and their execution results from storage,
+/// waiting for up to `long_poll_timeout` for the block to be stored.
+///
+/// Returns `None` if the block isn't stored by then.
+async fn read_block<REv>(
+    effect_builder: EffectBuilder<REv>,
+    height: u64,
+    long_poll_timeout: Duration,
+) -> Result<Option<ReplicatedBlock>, String>
+where
+    REv: From<StorageRequest> + Send,
+{
+    let start = Instant::now();
+    let block = loop {
+        if let Some(block) = effect_builder.get_block_at_height(height).await {
+            break block;
+        }
+        if start.elapsed() >= long_poll_timeout {
+            return Ok(None);
+        }
+        effect_builder.set_timeout(BLOCK_POLL_INTERVAL).await;
+    };
+
+    let block_hash = *block.hash();
+    let mut deploys = Vec::with_capacity(block.deploy_hashes().len());
+    let mut execution_results = Vec::with_capacity(block.deploy_hashes().len());
+    for deploy_hash in block.deploy_hashes() {
+        let (deploy, mut metadata) = effect_builder
+            .get_deploy_and_metadata_from_storage(*deploy_hash)
+            .await
+            .ok_or_else(|| format!("deploy {} not stored", deploy_hash))?;
+        let execution_result = metadata
+            .execution_results
+            .remove(&block_hash)
+            .ok_or_else(|| format!("no execution result of deploy {} stored", deploy_hash))?;
+        deploys.push(deploy);
+        execution_results.push((*deploy_hash, execution_result));
+    }
+    Ok(Some(ReplicatedBlock {
+        block,
+        deploys,
+        execution_results,
+    }))
+}
+
+/// Returns a response holding the bincode-encoded `value`, signed for a request sent at
+/// `timestamp`.
+fn signed_response<T: Serialize>(secret: &str, timestamp: Timestamp, value: &T) -> Response<Body> {
+    let body = match bincode::serialize(value) {
+        Ok(body) => body,
+        Err(error) => {
+            warn!(%error, "failed to encode replication response");
+            return status_response(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    let signature = match protocol::sign_response(secret, timestamp, &body) {
+        Ok(signature) => signature,
+        Err(error) => {
+            warn!(%error, "failed to sign replication response");
+            return status_response(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    let mut response = Response::new(Body::from(body));
+    match signature.parse() {
+        Ok(header_value) => {
+            let _ = response
+                .headers_mut()
+                .insert(SIGNATURE_HEADER, header_value);
+        }
+        Err(error) => {
+            warn!(%error, "invalid replication response signature header");
+            return status_response(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    }
+    response
+}
+
+fn unauthorized() -> Response<Body> {
+    status_response(StatusCode::UNAUTHORIZED)
+}
+
+fn status_response(status: StatusCode) -> Response<Body> {
+    let mut response = Response::new(Body::empty());
+    *response.status_mut() = status;
+    response
+}
@@ -0,0 +1,552 @@
+//! Resource monitor.
+//!
+//! The resource monitor periodically samples the node process' CPU time, resident memory and open
+//! file descriptors, the free space of the disks holding the node's data directories, and how much
+//! of the map size of its LMDB databases is in use.  The samples are exposed as metrics.
+//!
+//! When the usage of a resource crosses the configured threshold, e.g. the free space of a disk
+//! drops below 10%, an alert is raised: a warning is logged and the alert is announced, so that it
+//! can be pushed to event stream clients.  No further alert is raised for that resource until its
+//! usage has been back within the threshold.
+//!
+//! If the free space of the disk holding the storage directory drops below the configured pause
+//! threshold, the resource monitor announces that writes are paused: block execution and deploy
+//! acceptance stop, so that the databases are not corrupted by running out of space during a
+//! commit, while consensus messages are still relayed.  Once enough space has been freed to reach
+//! the resume threshold, it announces that writes are resumed.
+
+mod config;
+mod event;
+mod metrics;
+mod sample;
+
+use std::{
+    collections::HashSet,
+    convert::Infallible,
+    fmt::{self, Debug, Display, Formatter},
+    path::PathBuf,
+    sync::Arc,
+    time::Instant,
+};
+
+use datasize::DataSize;
+use libc::{c_long, sysconf, _SC_CLK_TCK};
+use lmdb::Environment;
+use prometheus::Registry;
+use tokio::task;
+use tracing::{info, warn};
+
+use casper_execution_engine::storage::transaction_source::lmdb::LmdbEnvironment;
+
+use crate::{
+    components::Component,
+    effect::{announcements::ResourceMonitorAnnouncement, EffectBuilder, EffectExt, Effects},
+    NodeRng,
+};
+
+pub use config::Config;
+pub(crate) use event::Event;
+use metrics::ResourceMonitorMetrics;
+use sample::Sample;
+
+/// A helper trait whose bounds represent the requirements for a reactor event that the resource
+/// monitor can work with.
+pub(crate) trait ReactorEventT:
+    From<Event> + From<ResourceMonitorAnnouncement> + Send + 'static
+{
+}
+
+impl<REv> ReactorEventT for REv where
+    REv: From<Event> + From<ResourceMonitorAnnouncement> + Send + 'static
+{
+}
+
+/// An LMDB environment whose map usage is monitored.
+pub(crate) trait MonitoredEnvironment: Debug + Send + Sync {
+    /// Returns the underlying LMDB environment.
+    fn environment(&self) -> &Environment;
+}
+
+impl MonitoredEnvironment for Environment {
+    fn environment(&self) -> &Environment {
+        self
+    }
+}
+
+impl MonitoredEnvironment for LmdbEnvironment {
+    fn environment(&self) -> &Environment {
+        self.env()
+    }
+}
+
+/// A resource whose usage is checked against a threshold.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Resource {
+    /// The free space of the disk holding the given data directory.
+    DiskSpace(PathBuf),
+    /// The map size of the given LMDB database.
+    LmdbMap(&'static str),
+    /// The file descriptors the node process may open.
+    FileDescriptors,
+    /// The system's memory.
+    Memory,
+}
+
+impl Display for Resource {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Resource::DiskSpace(path) => write!(formatter, "free disk space at {}", path.display()),
+            Resource::LmdbMap(name) => write!(formatter, "map size of the {} database", name),
+            Resource::FileDescriptors => write!(formatter, "open file descriptors"),
+            Resource::Memory => write!(formatter, "resident memory"),
+        }
+    }
+}
+
+/// Returns `part` in percent of `whole`, or `None` if `whole` is zero.
+fn percent(part: u64, whole: u64) -> Option<u8> {
+    if whole == 0 {
+        return None;
+    }
+    Some((u128::from(part) * 100 / u128::from(whole)).min(100) as u8)
+}
+
+#[derive(DataSize, Debug)]
+pub(crate) struct ResourceMonitor {
+    #[data_size(skip)]
+    metrics: ResourceMonitorMetrics,
+    config: Config,
+    /// The directories whose disks' free space is monitored, starting with the storage directory.
+    #[data_size(skip)]
+    data_dirs: Vec<PathBuf>,
+    /// The LMDB environments whose map usage is monitored, with their names.
+    #[data_size(skip)]
+    environments: Vec<(&'static str, Arc<dyn MonitoredEnvironment>)>,
+    /// The number of clock ticks per second, used to convert CPU time.
+    ticks_per_second: u64,
+    /// The CPU time used by the process as of the previous sample, and when it was taken.
+    #[data_size(skip)]
+    previous_cpu_ticks: Option<(u64, Instant)>,
+    /// The resources for which an alert has been raised and whose usage hasn't been back within
+    /// the threshold since.
+    #[data_size(skip)]
+    alerts_raised: HashSet<Resource>,
+    /// Whether writes are paused due to low free space on the disk holding the storage directory.
+    writes_paused: bool,
+}
+
+impl ResourceMonitor {
+    /// Creates a new resource monitor for the disks holding `storage_dir` and the other
+    /// `data_dirs`, and the named LMDB `environments`.
+    pub(crate) fn new<REv>(
+        config: Config,
+        registry: &Registry,
+        effect_builder: EffectBuilder<REv>,
+        storage_dir: PathBuf,
+        other_data_dirs: Vec<PathBuf>,
+        environments: Vec<(&'static str, Arc<dyn MonitoredEnvironment>)>,
+    ) -> Result<(Self, Effects<Event>), prometheus::Error>
+    where
+        REv: ReactorEventT,
+    {
+        let metrics = ResourceMonitorMetrics::new(registry)?;
+        let effects = effect_builder
+            .set_timeout(config.sample_interval)
+            .event(|_| Event::TakeSample);
+        // Safe, as `sysconf` has no preconditions.
+        let ticks_per_second: c_long = unsafe { sysconf(_SC_CLK_TCK) };
+        let mut data_dirs = vec![storage_dir];
+        data_dirs.extend(other_data_dirs);
+        let resource_monitor = ResourceMonitor {
+            metrics,
+            config,
+            data_dirs,
+            environments,
+            ticks_per_second: ticks_per_second.max(0) as u64,
+            previous_cpu_ticks: None,
+            alerts_raised: HashSet::new(),
+            writes_paused: false,
+        };
+        Ok((resource_monitor, effects))
+    }
+
+    /// Samples the resource usage in a blocking task.
+    fn take_sample(&self) -> Effects<Event> {
+        let data_dirs = self.data_dirs.clone();
+        let environments = self.environments.clone();
+        async move {
+            task::spawn_blocking(move || Sample::take(&data_dirs, &environments))
+                .await
+                .unwrap_or_else(|error| {
+                    warn!(%error, "resource sampling task failed");
+                    Sample::default()
+                })
+        }
+        .event(|sample| Event::SampleTaken(Box::new(sample)))
+    }
+
+    /// Updates the metrics from a sample taken at `now`.
+    fn record_sample(&mut self, sample: &Sample, now: Instant) {
+        if let Some(cpu_ticks) = sample.cpu_ticks {
+            if let Some((previous_ticks, previous_time)) = self.previous_cpu_ticks {
+                let elapsed = now.saturating_duration_since(previous_time).as_secs_f64();
+                if elapsed > 0.0 && self.ticks_per_second > 0 {
+                    let seconds = cpu_ticks.saturating_sub(previous_ticks) as f64
+                        / self.ticks_per_second as f64;
+                    self.metrics
+                        .cpu_usage_percent
+                        .set((seconds * 100.0 / elapsed).round() as i64);
+                }
+            }
+            self.previous_cpu_ticks = Some((cpu_ticks, now));
+        }
+        if let Some(resident_memory) = sample.resident_memory {
+            self.metrics
+                .resident_memory_bytes
+                .set(resident_memory as i64);
+        }
+        if let Some(open_fds) = sample.open_fds {
+            self.metrics.open_fds.set(open_fds as i64);
+        }
+        if let Some(max_fds) = sample.max_fds {
+            self.metrics.max_fds.set(max_fds as i64);
+        }
+        if let Some(disk_free) = sample.disks.iter().map(|disk| disk.free).min() {
+            self.metrics.disk_free_bytes.set(disk_free as i64);
+        }
+        if let Some(disk_free_percent) = sample
+            .disks
+            .iter()
+            .filter_map(|disk| percent(disk.free, disk.total))
+            .min()
+        {
+            self.metrics
+                .disk_free_percent
+                .set(i64::from(disk_free_percent));
+        }
+        if let Some(lmdb_map_usage_percent) = sample
+            .lmdb
+            .iter()
+            .filter_map(|lmdb| percent(lmdb.used, lmdb.map_size))
+            .max()
+        {
+            self.metrics
+                .lmdb_map_usage_percent
+                .set(i64::from(lmdb_map_usage_percent));
+        }
+    }
+
+    /// Checks the sample against the thresholds, returning an announcement for each resource
+    /// which newly crossed its threshold.
+    fn check_thresholds(&mut self, sample: &Sample) -> Vec<ResourceMonitorAnnouncement> {
+        let config = &self.config;
+        let mut observations = vec![];
+        for disk in &sample.disks {
+            if let Some(free_percent) = percent(disk.free, disk.total) {
+                observations.push((
+                    Resource::DiskSpace(disk.path.clone()),
+                    free_percent,
+                    config.min_disk_free_percent,
+                    free_percent < config.min_disk_free_percent,
+                ));
+            }
+        }
+        for lmdb in &sample.lmdb {
+            if let Some(usage_percent) = percent(lmdb.used, lmdb.map_size) {
+                observations.push((
+                    Resource::LmdbMap(lmdb.name),
+                    usage_percent,
+                    config.max_lmdb_map_usage_percent,
+                    usage_percent > config.max_lmdb_map_usage_percent,
+                ));
+            }
+        }
+        if let Some(usage_percent) = sample
+            .open_fds
+            .zip(sample.max_fds)
+            .and_then(|(open_fds, max_fds)| percent(open_fds, max_fds))
+        {
+            observations.push((
+                Resource::FileDescriptors,
+                usage_percent,
+                config.max_open_fds_percent,
+                usage_percent > config.max_open_fds_percent,
+            ));
+        }
+        if let Some(usage_percent) = sample
+            .resident_memory
+            .zip(sample.total_memory)
+            .and_then(|(resident_memory, total_memory)| percent(resident_memory, total_memory))
+        {
+            observations.push((
+                Resource::Memory,
+                usage_percent,
+                config.max_memory_percent,
+                usage_percent > config.max_memory_percent,
+            ));
+        }
+
+        let mut announcements = vec![];
+        for (resource, percent, threshold_percent, crossed) in observations {
+            if !crossed {
+                if self.alerts_raised.remove(&resource) {
+                    info!(%resource, %percent, "resource usage back within threshold");
+                }
+                continue;
+            }
+            if self.alerts_raised.contains(&resource) {
+                continue;
+            }
+            warn!(
+                %resource,
+                %percent,
+                %threshold_percent,
+                "resource usage crossed alert threshold"
+            );
+            self.metrics.resource_alerts.inc();
+            self.alerts_raised.insert(resource.clone());
+            announcements.push(ResourceMonitorAnnouncement::ThresholdCrossed {
+                resource,
+                percent,
+                threshold_percent,
+            });
+        }
+        announcements
+    }
+
+    /// Checks the free space of the disk holding the storage directory against the pause and
+    /// resume thresholds, returning an announcement if writes are to be paused or resumed.
+    fn check_storage_space(&mut self, sample: &Sample) -> Option<ResourceMonitorAnnouncement> {
+        if self.config.pause_disk_free_percent == 0 {
+            return None;
+        }
+        let storage_dir = self.data_dirs.first()?;
+        let free_percent = sample
+            .disks
+            .iter()
+            .find(|disk| disk.path == *storage_dir)
+            .and_then(|disk| percent(disk.free, disk.total))?;
+
+        if !self.writes_paused && free_percent < self.config.pause_disk_free_percent {
+            warn!(
+                %free_percent,
+                storage_dir = %storage_dir.display(),
+                "free disk space below pause threshold, pausing block execution and deploy \
+                 acceptance"
+            );
+            self.writes_paused = true;
+            self.metrics.writes_paused.set(1);
+            return Some(ResourceMonitorAnnouncement::WritesPaused { free_percent });
+        }
+        if self.writes_paused && free_percent >= self.config.resume_disk_free_percent {
+            info!(
+                %free_percent,
+                storage_dir = %storage_dir.display(),
+                "free disk space back above resume threshold, resuming block execution and \
+                 deploy acceptance"
+            );
+            self.writes_paused = false;
+            self.metrics.writes_paused.set(0);
+            return Some(ResourceMonitorAnnouncement::WritesResumed { free_percent });
+        }
+        None
+    }
+}
+
+impl<REv> Component<REv> for ResourceMonitor
+where
+    REv: ReactorEventT,
+{
+    type Event = Event;
+    type ConstructionError = Infallible;
+
+    fn handle_event(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        _rng: &mut NodeRng,
+        event: Self::Event,
+    ) -> Effects<Self::Event> {
+        match event {
+            Event::TakeSample => self.take_sample(),
+            Event::SampleTaken(sample) => {
+                self.record_sample(&sample, Instant::now());
+                let mut effects = effect_builder
+                    .set_timeout(self.config.sample_interval)
+                    .event(|_| Event::TakeSample);
+                let announcements = self
+                    .check_thresholds(&sample)
+                    .into_iter()
+                    .chain(self.check_storage_space(&sample));
+                for announcement in announcements {
+                    effects.extend(
+                        effect_builder
+                            .announce_resource_observation(announcement)
+                            .ignore(),
+                    );
+                }
+                effects
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        sample::{DiskSample, LmdbSample},
+        *,
+    };
+    use crate::{
+        reactor::{EventQueueHandle, QueueKind, Scheduler},
+        utils,
+    };
+
+    #[derive(Debug)]
+    enum TestEvent {
+        ResourceMonitor,
+        Announcement,
+    }
+
+    impl From<Event> for TestEvent {
+        fn from(_: Event) -> Self {
+            TestEvent::ResourceMonitor
+        }
+    }
+
+    impl From<ResourceMonitorAnnouncement> for TestEvent {
+        fn from(_: ResourceMonitorAnnouncement) -> Self {
+            TestEvent::Announcement
+        }
+    }
+
+    fn new_resource_monitor() -> ResourceMonitor {
+        let scheduler = utils::leak(Scheduler::<TestEvent>::new(QueueKind::weights()));
+        let effect_builder = EffectBuilder::new(EventQueueHandle::new(scheduler));
+        let (resource_monitor, _effects) = ResourceMonitor::new(
+            Config::default(),
+            &Registry::new(),
+            effect_builder,
+            PathBuf::from("/data"),
+            vec![],
+            vec![],
+        )
+        .expect("should create resource monitor");
+        resource_monitor
+    }
+
+    fn sample(disk_free: u64, lmdb_used: u64, open_fds: u64) -> Sample {
+        Sample {
+            cpu_ticks: None,
+            resident_memory: Some(1),
+            total_memory: Some(100),
+            open_fds: Some(open_fds),
+            max_fds: Some(100),
+            disks: vec![DiskSample {
+                path: PathBuf::from("/data"),
+                free: disk_free,
+                total: 100,
+            }],
+            lmdb: vec![LmdbSample {
+                name: "storage",
+                used: lmdb_used,
+                map_size: 100,
+            }],
+        }
+    }
+
+    fn crossed(announcements: &[ResourceMonitorAnnouncement]) -> Vec<&Resource> {
+        announcements
+            .iter()
+            .filter_map(|announcement| match announcement {
+                ResourceMonitorAnnouncement::ThresholdCrossed { resource, .. } => Some(resource),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_raise_alert_once_per_crossing() {
+        let mut resource_monitor = new_resource_monitor();
+        let disk = Resource::DiskSpace(PathBuf::from("/data"));
+
+        assert!(resource_monitor
+            .check_thresholds(&sample(50, 10, 10))
+            .is_empty());
+
+        // 9% free disk space is below the default threshold of 10%.
+        let announcements = resource_monitor.check_thresholds(&sample(9, 10, 10));
+        assert_eq!(crossed(&announcements), vec![&disk]);
+        assert!(matches!(
+            announcements[0],
+            ResourceMonitorAnnouncement::ThresholdCrossed {
+                percent: 9,
+                threshold_percent: 10,
+                ..
+            }
+        ));
+        assert!(resource_monitor
+            .check_thresholds(&sample(5, 10, 10))
+            .is_empty());
+
+        // Once back within the threshold, crossing it again raises another alert.
+        assert!(resource_monitor
+            .check_thresholds(&sample(20, 10, 10))
+            .is_empty());
+        let announcements = resource_monitor.check_thresholds(&sample(1, 10, 10));
+        assert_eq!(crossed(&announcements), vec![&disk]);
+        assert_eq!(resource_monitor.metrics.resource_alerts.get(), 2);
+    }
+
+    #[test]
+    fn should_raise_alerts_for_lmdb_and_file_descriptors() {
+        let mut resource_monitor = new_resource_monitor();
+        let announcements = resource_monitor.check_thresholds(&sample(50, 95, 91));
+        assert_eq!(
+            crossed(&announcements),
+            vec![&Resource::LmdbMap("storage"), &Resource::FileDescriptors]
+        );
+        // Exactly at the threshold is still within it.
+        let mut resource_monitor = new_resource_monitor();
+        assert!(resource_monitor
+            .check_thresholds(&sample(10, 90, 90))
+            .is_empty());
+    }
+
+    #[test]
+    fn should_pause_and_resume_writes() {
+        let mut resource_monitor = new_resource_monitor();
+        let pause = resource_monitor.config.pause_disk_free_percent;
+        let resume = resource_monitor.config.resume_disk_free_percent;
+
+        assert!(resource_monitor
+            .check_storage_space(&sample(u64::from(pause), 10, 10))
+            .is_none());
+        assert!(matches!(
+            resource_monitor.check_storage_space(&sample(u64::from(pause) - 1, 10, 10)),
+            Some(ResourceMonitorAnnouncement::WritesPaused { .. })
+        ));
+        assert!(resource_monitor
+            .check_storage_space(&sample(0, 10, 10))
+            .is_none());
+
+        // Writes stay paused until the free space reaches the resume threshold.
+        assert!(resource_monitor
+            .check_storage_space(&sample(u64::from(resume) - 1, 10, 10))
+            .is_none());
+        assert!(matches!(
+            resource_monitor.check_storage_space(&sample(u64::from(resume), 10, 10)),
+            Some(ResourceMonitorAnnouncement::WritesResumed { .. })
+        ));
+        assert!(resource_monitor
+            .check_storage_space(&sample(50, 10, 10))
+            .is_none());
+    }
+
+    #[test]
+    fn should_compute_percent() {
+        assert_eq!(percent(1, 0), None);
+        assert_eq!(percent(0, 7), Some(0));
+        assert_eq!(percent(7, 7), Some(100));
+        assert_eq!(percent(u64::max_value(), u64::max_value() / 2), Some(100));
+    }
+}
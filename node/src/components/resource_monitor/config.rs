@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+
+/// Default interval between samples of the node's resource usage.
+const DEFAULT_SAMPLE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Default percentage of free disk space below which an alert is raised.
+const DEFAULT_MIN_DISK_FREE_PERCENT: u8 = 10;
+
+/// Default percentage of free disk space below which writes are paused.
+const DEFAULT_PAUSE_DISK_FREE_PERCENT: u8 = 2;
+
+/// Default percentage of free disk space at which paused writes are resumed.
+const DEFAULT_RESUME_DISK_FREE_PERCENT: u8 = 5;
+
+/// Default percentage of the LMDB map size in use beyond which an alert is raised.
+const DEFAULT_MAX_LMDB_MAP_USAGE_PERCENT: u8 = 90;
+
+/// Default percentage of the open file descriptor limit in use beyond which an alert is raised.
+const DEFAULT_MAX_OPEN_FDS_PERCENT: u8 = 90;
+
+/// Default percentage of the system's memory resident in the node beyond which an alert is raised.
+const DEFAULT_MAX_MEMORY_PERCENT: u8 = 90;
+
+/// Configuration options for the resource monitor.
+#[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Interval in milliseconds between samples of the node's resource usage.
+    #[serde(with = "crate::utils::milliseconds")]
+    pub sample_interval: Duration,
+
+    /// Percentage of free space on the disk holding a data directory below which an alert is
+    /// raised.
+    pub min_disk_free_percent: u8,
+
+    /// Percentage of free space on the disk holding the storage directory below which block
+    /// execution and deploy acceptance are paused.  0 disables pausing.
+    pub pause_disk_free_percent: u8,
+
+    /// Percentage of free space on the disk holding the storage directory at which block execution
+    /// and deploy acceptance are resumed after having been paused.  Should be greater than
+    /// `pause_disk_free_percent`.
+    pub resume_disk_free_percent: u8,
+
+    /// Percentage of the map size of an LMDB database in use beyond which an alert is raised.
+    pub max_lmdb_map_usage_percent: u8,
+
+    /// Percentage of the limit on open file descriptors in use beyond which an alert is raised.
+    pub max_open_fds_percent: u8,
+
+    /// Percentage of the system's memory resident in the node process beyond which an alert is
+    /// raised.
+    pub max_memory_percent: u8,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            sample_interval: DEFAULT_SAMPLE_INTERVAL,
+            min_disk_free_percent: DEFAULT_MIN_DISK_FREE_PERCENT,
+            pause_disk_free_percent: DEFAULT_PAUSE_DISK_FREE_PERCENT,
+            resume_disk_free_percent: DEFAULT_RESUME_DISK_FREE_PERCENT,
+            max_lmdb_map_usage_percent: DEFAULT_MAX_LMDB_MAP_USAGE_PERCENT,
+            max_open_fds_percent: DEFAULT_MAX_OPEN_FDS_PERCENT,
+            max_memory_percent: DEFAULT_MAX_MEMORY_PERCENT,
+        }
+    }
+}
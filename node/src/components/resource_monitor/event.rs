@@ -0,0 +1,21 @@
+use std::fmt::{self, Display, Formatter};
+
+use super::sample::Sample;
+
+/// An event for the resource monitor.
+#[derive(Debug)]
+pub enum Event {
+    /// The node's resource usage should be sampled.
+    TakeSample,
+    /// The node's resource usage has been sampled.
+    SampleTaken(Box<Sample>),
+}
+
+impl Display for Event {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Event::TakeSample => write!(formatter, "take resource usage sample"),
+            Event::SampleTaken(_) => write!(formatter, "resource usage sample taken"),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use prometheus::{IntCounter, IntGauge, Registry};
+
+/// Metrics for the resource monitor.
+#[derive(Debug)]
+pub struct ResourceMonitorMetrics {
+    /// CPU time used by the node process since the previous sample, in percent of one core.
+    pub(super) cpu_usage_percent: IntGauge,
+    /// Resident set size of the node process in bytes.
+    pub(super) resident_memory_bytes: IntGauge,
+    /// Number of file descriptors the node process has open.
+    pub(super) open_fds: IntGauge,
+    /// Soft limit on the number of file descriptors the node process may open.
+    pub(super) max_fds: IntGauge,
+    /// Smallest free space in bytes of the disks holding the data directories.
+    pub(super) disk_free_bytes: IntGauge,
+    /// Smallest free space in percent of the disks holding the data directories.
+    pub(super) disk_free_percent: IntGauge,
+    /// Largest usage in percent of the map size of the LMDB databases.
+    pub(super) lmdb_map_usage_percent: IntGauge,
+    /// Number of alerts raised due to resource usage crossing a threshold.
+    pub(super) resource_alerts: IntCounter,
+    /// Whether writes are paused due to low free disk space, 1 if so, 0 otherwise.
+    pub(super) writes_paused: IntGauge,
+    /// Reference to the registry for unregistering.
+    registry: Registry,
+}
+
+impl ResourceMonitorMetrics {
+    /// Creates a new instance of the resource monitor metrics.
+    pub fn new(registry: &Registry) -> Result<Self, prometheus::Error> {
+        let cpu_usage_percent = IntGauge::new(
+            "resource_cpu_usage_percent",
+            "CPU time used by the node process since the previous sample, in percent of one core",
+        )?;
+        let resident_memory_bytes = IntGauge::new(
+            "resource_resident_memory_bytes",
+            "resident set size of the node process in bytes",
+        )?;
+        let open_fds = IntGauge::new(
+            "resource_open_fds",
+            "number of file descriptors the node process has open",
+        )?;
+        let max_fds = IntGauge::new(
+            "resource_max_fds",
+            "soft limit on the number of file descriptors the node process may open",
+        )?;
+        let disk_free_bytes = IntGauge::new(
+            "resource_disk_free_bytes",
+            "smallest free space in bytes of the disks holding the data directories",
+        )?;
+        let disk_free_percent = IntGauge::new(
+            "resource_disk_free_percent",
+            "smallest free space in percent of the disks holding the data directories",
+        )?;
+        let lmdb_map_usage_percent = IntGauge::new(
+            "resource_lmdb_map_usage_percent",
+            "largest usage in percent of the map size of the LMDB databases",
+        )?;
+        let resource_alerts = IntCounter::new(
+            "resource_alerts",
+            "number of alerts raised due to resource usage crossing a threshold",
+        )?;
+        let writes_paused = IntGauge::new(
+            "resource_writes_paused",
+            "whether writes are paused due to low free disk space, 1 if so, 0 otherwise",
+        )?;
+
+        registry.register(Box::new(cpu_usage_percent.clone()))?;
+        registry.register(Box::new(resident_memory_bytes.clone()))?;
+        registry.register(Box::new(open_fds.clone()))?;
+        registry.register(Box::new(max_fds.clone()))?;
+        registry.register(Box::new(disk_free_bytes.clone()))?;
+        registry.register(Box::new(disk_free_percent.clone()))?;
+        registry.register(Box::new(lmdb_map_usage_percent.clone()))?;
+        registry.register(Box::new(resource_alerts.clone()))?;
+        registry.register(Box::new(writes_paused.clone()))?;
+
+        Ok(ResourceMonitorMetrics {
+            cpu_usage_percent,
+            resident_memory_bytes,
+            open_fds,
+            max_fds,
+            disk_free_bytes,
+            disk_free_percent,
+            lmdb_map_usage_percent,
+            resource_alerts,
+            writes_paused,
+            registry: registry.clone(),
+        })
+    }
+}
+
+impl Drop for ResourceMonitorMetrics {
+    fn drop(&mut self) {
+        self.registry
+            .unregister(Box::new(self.cpu_usage_percent.clone()))
+            .expect("did not expect deregistering cpu_usage_percent to fail");
+        self.registry
+            .unregister(Box::new(self.resident_memory_bytes.clone()))
+            .expect("did not expect deregistering resident_memory_bytes to fail");
+        self.registry
+            .unregister(Box::new(self.open_fds.clone()))
+            .expect("did not expect deregistering open_fds to fail");
+        self.registry
+            .unregister(Box::new(self.max_fds.clone()))
+            .expect("did not expect deregistering max_fds to fail");
+        self.registry
+            .unregister(Box::new(self.disk_free_bytes.clone()))
+            .expect("did not expect deregistering disk_free_bytes to fail");
+        self.registry
+            .unregister(Box::new(self.disk_free_percent.clone()))
+            .expect("did not expect deregistering disk_free_percent to fail");
+        self.registry
+            .unregister(Box::new(self.lmdb_map_usage_percent.clone()))
+            .expect("did not expect deregistering lmdb_map_usage_percent to fail");
+        self.registry
+            .unregister(Box::new(self.resource_alerts.clone()))
+            .expect("did not expect deregistering resource_alerts to fail");
+        self.registry
+            .unregister(Box::new(self.writes_paused.clone()))
+            .expect("did not expect deregistering writes_paused to fail");
+    }
+}
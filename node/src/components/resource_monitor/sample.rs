@@ -0,0 +1,234 @@
+//! Sampling of the node process' resource usage.
+//!
+//! Process statistics are read from `/proc`, so they are only available on Linux.  Any value which
+//! can't be read is left out of the sample rather than failing it as a whole.
+
+use std::{
+    ffi::CString,
+    fs, io,
+    mem::MaybeUninit,
+    os::unix::ffi::OsStrExt,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use lmdb::Environment;
+use tracing::debug;
+
+use super::MonitoredEnvironment;
+use crate::utils::OS_PAGE_SIZE;
+
+/// The free and total space of the disk holding a data directory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct DiskSample {
+    /// The data directory.
+    pub(super) path: PathBuf,
+    /// The space available to the node, in bytes.
+    pub(super) free: u64,
+    /// The size of the file system, in bytes.
+    pub(super) total: u64,
+}
+
+/// The space used by an LMDB environment, compared to its map size.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct LmdbSample {
+    /// The name of the environment.
+    pub(super) name: &'static str,
+    /// The space used by the environment's pages, in bytes.
+    pub(super) used: u64,
+    /// The environment's map size, in bytes.
+    pub(super) map_size: u64,
+}
+
+/// A sample of the node's resource usage.
+#[derive(Clone, Debug, Default)]
+pub struct Sample {
+    /// The CPU time used by the process so far, in clock ticks.
+    pub(super) cpu_ticks: Option<u64>,
+    /// The process' resident set size, in bytes.
+    pub(super) resident_memory: Option<u64>,
+    /// The system's total memory, in bytes.
+    pub(super) total_memory: Option<u64>,
+    /// The number of file descriptors the process has open.
+    pub(super) open_fds: Option<u64>,
+    /// The soft limit on the number of file descriptors the process may open.
+    pub(super) max_fds: Option<u64>,
+    /// The disks holding the data directories.
+    pub(super) disks: Vec<DiskSample>,
+    /// The LMDB environments.
+    pub(super) lmdb: Vec<LmdbSample>,
+}
+
+impl Sample {
+    /// Samples the process' resource usage, the disks holding `data_dirs` and the LMDB
+    /// `environments`.
+    ///
+    /// This performs blocking I/O.
+    pub(super) fn take(
+        data_dirs: &[PathBuf],
+        environments: &[(&'static str, Arc<dyn MonitoredEnvironment>)],
+    ) -> Self {
+        let mut sample = Sample::default();
+        match fs::read_to_string("/proc/self/stat") {
+            Ok(stat) => sample.cpu_ticks = parse_cpu_ticks(&stat),
+            Err(error) => debug!(%error, "failed to read process stat"),
+        }
+        match fs::read_to_string("/proc/self/statm") {
+            Ok(statm) => {
+                sample.resident_memory =
+                    parse_resident_pages(&statm).map(|pages| pages * *OS_PAGE_SIZE as u64)
+            }
+            Err(error) => debug!(%error, "failed to read process statm"),
+        }
+        match fs::read_to_string("/proc/meminfo") {
+            Ok(meminfo) => sample.total_memory = parse_total_memory(&meminfo),
+            Err(error) => debug!(%error, "failed to read meminfo"),
+        }
+        match fs::read_dir("/proc/self/fd") {
+            Ok(entries) => sample.open_fds = Some(entries.count() as u64),
+            Err(error) => debug!(%error, "failed to list open file descriptors"),
+        }
+        sample.max_fds = max_fds();
+        for path in data_dirs {
+            match disk_space(path) {
+                Ok((free, total)) => sample.disks.push(DiskSample {
+                    path: path.clone(),
+                    free,
+                    total,
+                }),
+                Err(error) => debug!(%error, path = %path.display(), "failed to get disk space"),
+            }
+        }
+        for (name, environment) in environments {
+            match lmdb_usage(environment.environment()) {
+                Ok((used, map_size)) => sample.lmdb.push(LmdbSample {
+                    name: *name,
+                    used,
+                    map_size,
+                }),
+                Err(error) => debug!(%error, %name, "failed to get LMDB usage"),
+            }
+        }
+        sample
+    }
+}
+
+/// Parses the user and system CPU time from the contents of `/proc/<pid>/stat`.
+fn parse_cpu_ticks(stat: &str) -> Option<u64> {
+    // The command name in the second field is parenthesized and may contain spaces.
+    let fields: Vec<&str> = stat
+        .get(stat.rfind(')')? + 1..)?
+        .split_whitespace()
+        .collect();
+    // `utime` and `stime` are the 14th and 15th fields, counting the pid and command name.
+    let utime: u64 = fields.get(11)?.parse().ok()?;
+    let stime: u64 = fields.get(12)?.parse().ok()?;
+    Some(utime + stime)
+}
+
+/// Parses the resident set size in pages from the contents of `/proc/<pid>/statm`.
+fn parse_resident_pages(statm: &str) -> Option<u64> {
+    statm.split_whitespace().nth(1)?.parse().ok()
+}
+
+/// Parses the total memory in bytes from the contents of `/proc/meminfo`.
+fn parse_total_memory(meminfo: &str) -> Option<u64> {
+    let line = meminfo.lines().find(|line| line.starts_with("MemTotal:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+/// Returns the soft limit on the number of file descriptors the process may open.
+fn max_fds() -> Option<u64> {
+    let mut limit = MaybeUninit::<libc::rlimit>::uninit();
+    // Safe, as `limit` is valid for writes and initialized by a successful call.
+    let limit = unsafe {
+        if libc::getrlimit(libc::RLIMIT_NOFILE, limit.as_mut_ptr()) != 0 {
+            return None;
+        }
+        limit.assume_init()
+    };
+    if limit.rlim_cur == libc::RLIM_INFINITY {
+        return None;
+    }
+    Some(u64::from(limit.rlim_cur))
+}
+
+/// Returns the space available to unprivileged users and the total size of the file system
+/// holding `path`, in bytes.
+fn disk_space(path: &Path) -> io::Result<(u64, u64)> {
+    let c_path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+    let mut stat = MaybeUninit::<libc::statvfs>::uninit();
+    // Safe, as `c_path` is a valid NUL-terminated string, and `stat` is valid for writes and
+    // initialized by a successful call.
+    let stat = unsafe {
+        if libc::statvfs(c_path.as_ptr(), stat.as_mut_ptr()) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        stat.assume_init()
+    };
+    let fragment_size = u64::from(stat.f_frsize);
+    Ok((
+        u64::from(stat.f_bavail) * fragment_size,
+        u64::from(stat.f_blocks) * fragment_size,
+    ))
+}
+
+/// Returns the space used by the pages of `env` and its map size, in bytes.
+fn lmdb_usage(env: &Environment) -> Result<(u64, u64), lmdb::Error> {
+    let mut info = MaybeUninit::<lmdb_sys::MDB_envinfo>::uninit();
+    let mut stat = MaybeUninit::<lmdb_sys::MDB_stat>::uninit();
+    // Safe, as `env` is an open environment for the duration of the calls, and `info` and `stat`
+    // are valid for writes and initialized by successful calls.
+    let (info, stat) = unsafe {
+        let result = lmdb_sys::mdb_env_info(env.env(), info.as_mut_ptr());
+        if result != 0 {
+            return Err(lmdb::Error::from_err_code(result));
+        }
+        let result = lmdb_sys::mdb_env_stat(env.env(), stat.as_mut_ptr());
+        if result != 0 {
+            return Err(lmdb::Error::from_err_code(result));
+        }
+        (info.assume_init(), stat.assume_init())
+    };
+    // Page numbers start at 0, so the number of pages in use is one more than the last one.
+    let used = (info.me_last_pgno as u64 + 1) * u64::from(stat.ms_psize);
+    Ok((used, info.me_mapsize as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_cpu_ticks() {
+        let stat = "4242 (casper (node)) S 1 4242 4242 0 -1 4194560 30573 0 0 0 1234 567 0 0 20 \
+                    0 42 0 1049 1862455296 30126 18446744073709551615";
+        assert_eq!(parse_cpu_ticks(stat), Some(1234 + 567));
+        assert_eq!(parse_cpu_ticks("4242 (casper-node) S 1"), None);
+    }
+
+    #[test]
+    fn should_parse_resident_pages_and_total_memory() {
+        assert_eq!(
+            parse_resident_pages("454832 30126 6104 9062 0 71405 0\n"),
+            Some(30126)
+        );
+        let meminfo = "MemTotal:       16318412 kB\nMemFree:         1187456 kB\n";
+        assert_eq!(parse_total_memory(meminfo), Some(16_318_412 * 1024));
+        assert_eq!(parse_total_memory("MemFree: 1187456 kB\n"), None);
+    }
+
+    #[test]
+    fn should_sample_current_process() {
+        let tempdir = tempfile::tempdir().expect("should create tempdir");
+        let sample = Sample::take(&[tempdir.path().to_path_buf()], &[]);
+        assert_eq!(sample.disks.len(), 1);
+        assert!(sample.disks[0].free <= sample.disks[0].total);
+        if cfg!(target_os = "linux") {
+            assert!(sample.resident_memory.unwrap_or_default() > 0);
+            assert!(sample.open_fds.unwrap_or_default() > 0);
+        }
+    }
+}
@@ -17,11 +17,16 @@
 //!     example: curl -X GET 'http://<ip>:8888/status'
 //! /metrics : time series data collected from the internals of the node being queried.
 //!     example: curl -X GET 'http://<ip>:8888/metrics'
+//!
+//! The metrics endpoint can instead be served by a separate server bound to its own address,
+//! optionally with TLS and client certificate authentication, and can require HTTP basic
+//! authentication on either server.
 
 mod config;
 mod event;
 mod filters;
 mod http_server;
+mod metrics_server;
 
 use std::{convert::Infallible, fmt::Debug};
 
@@ -33,7 +38,10 @@ use tracing::{debug, error, warn};
 use super::Component;
 use crate::{
     effect::{
-        requests::{ChainspecLoaderRequest, MetricsRequest, NetworkInfoRequest, StorageRequest},
+        requests::{
+            ChainspecLoaderRequest, MetricsRequest, NetworkInfoRequest, StorageRequest,
+            SyncProgressRequest,
+        },
         EffectBuilder, EffectExt, Effects,
     },
     reactor::Finalize,
@@ -42,7 +50,7 @@ use crate::{
 };
 
 use crate::effect::requests::RestRequest;
-pub use config::Config;
+pub use config::{BasicAuthConfig, Config, MetricsTlsConfig};
 pub(crate) use event::Event;
 
 /// A helper trait capturing all of this components Request type dependencies.
@@ -51,6 +59,7 @@ pub trait ReactorEventT:
     + From<RestRequest<NodeId>>
     + From<NetworkInfoRequest<NodeId>>
     + From<StorageRequest>
+    + From<SyncProgressRequest>
     + From<ChainspecLoaderRequest>
     + From<MetricsRequest>
     + Send
@@ -62,6 +71,7 @@ impl<REv> ReactorEventT for REv where
         + From<RestRequest<NodeId>>
         + From<NetworkInfoRequest<NodeId>>
         + From<StorageRequest>
+        + From<SyncProgressRequest>
         + From<ChainspecLoaderRequest>
         + From<MetricsRequest>
         + Send
@@ -72,9 +82,13 @@ impl<REv> ReactorEventT for REv where
 #[derive(DataSize, Debug)]
 pub(crate) struct RestServer {
     /// When the message is sent, it signals the server loop to exit cleanly.
-    shutdown_sender: oneshot::Sender<()>,
+    shutdown_sender: Option<oneshot::Sender<()>>,
     /// The task handle which will only join once the server loop has exited.
     server_join_handle: Option<JoinHandle<()>>,
+    /// When the message is sent, it signals the separate metrics server loop to exit cleanly.
+    metrics_shutdown_sender: Option<oneshot::Sender<()>>,
+    /// The task handle which will only join once the separate metrics server loop has exited.
+    metrics_server_join_handle: Option<JoinHandle<()>>,
 }
 
 impl RestServer {
@@ -82,15 +96,68 @@ impl RestServer {
     where
         REv: ReactorEventT,
     {
+        if config.metrics_tls.is_some() && config.metrics_address.is_none() {
+            warn!("metrics TLS is configured without a metrics address, not serving metrics");
+        }
+
+        let (metrics_shutdown_sender, metrics_server_join_handle) =
+            if config.metrics_address.is_some() {
+                let (shutdown_sender, shutdown_receiver) = oneshot::channel::<()>();
+                let join_handle = tokio::spawn(metrics_server::run(
+                    config.clone(),
+                    effect_builder,
+                    shutdown_receiver,
+                ));
+                (Some(shutdown_sender), Some(join_handle))
+            } else {
+                (None, None)
+            };
+
         let (shutdown_sender, shutdown_receiver) = oneshot::channel::<()>();
 
         let server_join_handle =
             tokio::spawn(http_server::run(config, effect_builder, shutdown_receiver));
 
         RestServer {
-            shutdown_sender,
+            shutdown_sender: Some(shutdown_sender),
             server_join_handle: Some(server_join_handle),
+            metrics_shutdown_sender,
+            metrics_server_join_handle,
+        }
+    }
+
+    /// Signals the server to stop accepting new connections and to exit once the in-flight
+    /// requests have completed.
+    ///
+    /// The returned future completes once the server has exited.
+    pub(crate) fn shut_down(&mut self) -> BoxFuture<'static, ()> {
+        if let Some(shutdown_sender) = self.shutdown_sender.take() {
+            let _ = shutdown_sender.send(());
+        }
+        if let Some(shutdown_sender) = self.metrics_shutdown_sender.take() {
+            let _ = shutdown_sender.send(());
+        }
+
+        // Wait for the servers to exit cleanly.
+        let maybe_join_handle = self.server_join_handle.take();
+        let maybe_metrics_join_handle = self.metrics_server_join_handle.take();
+        async move {
+            if let Some(join_handle) = maybe_metrics_join_handle {
+                match join_handle.await {
+                    Ok(_) => debug!("metrics server exited cleanly"),
+                    Err(error) => error!(%error, "could not join metrics server task cleanly"),
+                }
+            }
+            if let Some(join_handle) = maybe_join_handle {
+                match join_handle.await {
+                    Ok(_) => debug!("rest server exited cleanly"),
+                    Err(error) => error!(%error, "could not join rest server task cleanly"),
+                }
+            } else {
+                warn!("rest server shutdown while already shut down")
+            }
         }
+        .boxed()
     }
 }
 
@@ -109,12 +176,14 @@ where
     ) -> Effects<Self::Event> {
         match event {
             Event::RestRequest(RestRequest::GetStatus { responder }) => async move {
-                let (last_added_block, peers, chainspec_info) = join!(
+                let (last_added_block, peers, chainspec_info, sync_progress) = join!(
                     effect_builder.get_highest_block(),
                     effect_builder.network_peers(),
-                    effect_builder.get_chainspec_info()
+                    effect_builder.get_chainspec_info(),
+                    effect_builder.get_sync_progress()
                 );
-                let status_feed = StatusFeed::new(last_added_block, peers, chainspec_info);
+                let status_feed =
+                    StatusFeed::new(last_added_block, peers, chainspec_info, sync_progress);
                 responder.respond(status_feed).await;
             }
             .ignore(),
@@ -134,19 +203,6 @@ where
 
 impl Finalize for RestServer {
     fn finalize(mut self) -> BoxFuture<'static, ()> {
-        async {
-            let _ = self.shutdown_sender.send(());
-
-            // Wait for the server to exit cleanly.
-            if let Some(join_handle) = self.server_join_handle.take() {
-                match join_handle.await {
-                    Ok(_) => debug!("rest server exited cleanly"),
-                    Err(error) => error!(%error, "could not join rest server task cleanly"),
-                }
-            } else {
-                warn!("rest server shutdown while already shut down")
-            }
-        }
-        .boxed()
+        self.shut_down()
     }
 }
@@ -1,3 +1,9 @@
+use std::{
+    fmt::{self, Debug, Formatter},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
@@ -6,6 +12,9 @@ use serde::{Deserialize, Serialize};
 /// Uses a fixed port per node, but binds on any interface.
 const DEFAULT_ADDRESS: &str = "0.0.0.0:8888";
 
+/// Default time allowed for in-flight requests to complete when shutting down.
+const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// REST HTTP server configuration.
 #[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
 // Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
@@ -13,6 +22,28 @@ const DEFAULT_ADDRESS: &str = "0.0.0.0:8888";
 pub struct Config {
     /// Address to bind REST HTTP server to.
     pub address: String,
+    /// Time in milliseconds allowed for in-flight requests to complete when shutting down, after
+    /// which any remaining connections are dropped.
+    #[serde(
+        default = "default_shutdown_timeout",
+        with = "crate::utils::milliseconds"
+    )]
+    pub shutdown_timeout: Duration,
+    /// Address to bind a separate HTTP server for the metrics endpoint to.  If set, metrics are
+    /// only served on this address, not on `address`.
+    #[serde(default)]
+    pub metrics_address: Option<String>,
+    /// Credentials which requests for the metrics endpoint must provide via HTTP basic
+    /// authentication.  If `None`, no credentials are required.
+    #[serde(default)]
+    pub metrics_basic_auth: Option<BasicAuthConfig>,
+    /// TLS configuration of the separate metrics server.  Requires `metrics_address` to be set.
+    #[serde(default)]
+    pub metrics_tls: Option<MetricsTlsConfig>,
+}
+
+fn default_shutdown_timeout() -> Duration {
+    DEFAULT_SHUTDOWN_TIMEOUT
 }
 
 impl Config {
@@ -20,6 +51,10 @@ impl Config {
     pub fn new() -> Self {
         Config {
             address: DEFAULT_ADDRESS.to_string(),
+            shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+            metrics_address: None,
+            metrics_basic_auth: None,
+            metrics_tls: None,
         }
     }
 }
@@ -29,3 +64,51 @@ impl Default for Config {
         Config::new()
     }
 }
+
+/// Credentials for HTTP basic authentication.
+#[derive(Clone, DataSize, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct BasicAuthConfig {
+    /// The user name.
+    pub username: String,
+    /// The password.
+    pub password: String,
+}
+
+impl Debug for BasicAuthConfig {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("BasicAuthConfig")
+            .field("username", &self.username)
+            .field("password", &"<redacted>")
+            .finish()
+    }
+}
+
+/// TLS configuration of the metrics server.
+#[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct MetricsTlsConfig {
+    /// Path to the PEM-encoded certificate chain the server presents to clients.
+    pub cert_path: PathBuf,
+    /// Path to the PEM-encoded secret key of the server's certificate.
+    pub secret_key_path: PathBuf,
+    /// Path to the PEM-encoded certificates of the authorities which must have signed the
+    /// certificates clients present.  If set, clients without a valid certificate are refused
+    /// (mutual TLS).  If `None`, clients are not asked for a certificate.
+    #[serde(default)]
+    pub client_ca_path: Option<PathBuf>,
+}
+
+impl MetricsTlsConfig {
+    /// Resolves the relative paths against `root`.
+    pub(crate) fn with_root(self, root: &Path) -> Self {
+        MetricsTlsConfig {
+            cert_path: root.join(self.cert_path),
+            secret_key_path: root.join(self.secret_key_path),
+            client_ca_path: self.client_ca_path.map(|path| root.join(path)),
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use futures::FutureExt;
+use futures::{future, FutureExt};
 use http::Response;
 use hyper::Body;
 use tracing::warn;
@@ -10,7 +10,7 @@ use warp::{
     Filter,
 };
 
-use super::ReactorEventT;
+use super::{BasicAuthConfig, ReactorEventT};
 use crate::{
     components::CLIENT_API_VERSION,
     effect::{requests::RestRequest, EffectBuilder},
@@ -24,6 +24,9 @@ pub const STATUS_API_PATH: &str = "status";
 /// The metrics URL path.
 pub const METRICS_API_PATH: &str = "metrics";
 
+/// The prefix of the "Authorization" header's value for HTTP basic authentication.
+const BASIC_PREFIX: &str = "Basic ";
+
 pub(super) fn create_status_filter<REv: ReactorEventT>(
     effect_builder: EffectBuilder<REv>,
 ) -> BoxedFilter<(Response<Body>,)> {
@@ -44,12 +47,30 @@ pub(super) fn create_status_filter<REv: ReactorEventT>(
         .boxed()
 }
 
+/// Creates the filter for the metrics endpoint.  If `maybe_basic_auth` is `Some`, requests are
+/// refused unless they provide the given credentials.
 pub(super) fn create_metrics_filter<REv: ReactorEventT>(
     effect_builder: EffectBuilder<REv>,
+    maybe_basic_auth: Option<BasicAuthConfig>,
 ) -> BoxedFilter<(Response<Body>,)> {
+    let maybe_expected_credentials = maybe_basic_auth.map(|basic_auth| {
+        base64::encode(format!("{}:{}", basic_auth.username, basic_auth.password))
+    });
     warp::get()
         .and(warp::path(METRICS_API_PATH))
-        .and_then(move || {
+        .and(warp::header::optional::<String>("authorization"))
+        .and_then(move |maybe_authorization: Option<String>| {
+            if !is_authorized(
+                maybe_expected_credentials.as_deref(),
+                maybe_authorization.as_deref(),
+            ) {
+                let response = reply::with_header(
+                    reply::with_status("unauthorized", StatusCode::UNAUTHORIZED),
+                    "www-authenticate",
+                    "Basic realm=\"metrics\"",
+                );
+                return future::ok::<_, Rejection>(response.into_response()).left_future();
+            }
             effect_builder
                 .make_request(
                     |responder| RestRequest::GetMetrics { responder },
@@ -68,6 +89,52 @@ pub(super) fn create_metrics_filter<REv: ReactorEventT>(
                         .into_response())
                     }
                 })
+                .right_future()
         })
         .boxed()
 }
+
+/// Returns whether the value of the "Authorization" header holds the expected base64-encoded
+/// basic authentication credentials.  Always returns true if no credentials are expected.
+///
+/// The comparison takes the same time wherever the first mismatching byte lies, so as not to leak
+/// the credentials to a timing attack.
+fn is_authorized(
+    maybe_expected_credentials: Option<&str>,
+    maybe_authorization: Option<&str>,
+) -> bool {
+    let expected_credentials = match maybe_expected_credentials {
+        Some(expected_credentials) => expected_credentials,
+        None => return true,
+    };
+    let provided_credentials = match maybe_authorization
+        .and_then(|authorization| authorization.strip_prefix(BASIC_PREFIX))
+    {
+        Some(provided_credentials) => provided_credentials,
+        None => return false,
+    };
+    provided_credentials.len() == expected_credentials.len()
+        && provided_credentials
+            .bytes()
+            .zip(expected_credentials.bytes())
+            .fold(0, |difference, (lhs, rhs)| difference | (lhs ^ rhs))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_authorize_only_matching_basic_credentials() {
+        // "user:secret"
+        let credentials = Some("dXNlcjpzZWNyZXQ=");
+        assert!(is_authorized(credentials, Some("Basic dXNlcjpzZWNyZXQ=")));
+        assert!(!is_authorized(credentials, None));
+        assert!(!is_authorized(credentials, Some("dXNlcjpzZWNyZXQ=")));
+        assert!(!is_authorized(credentials, Some("Basic dXNlcjpzZWNyZXQ")));
+        assert!(!is_authorized(credentials, Some("Bearer dXNlcjpzZWNyZXQ=")));
+        assert!(is_authorized(None, None));
+        assert!(is_authorized(None, Some("Basic dXNlcjpzZWNyZXQ=")));
+    }
+}
@@ -1,8 +1,8 @@
 use std::convert::Infallible;
 
-use futures::{future, TryFutureExt};
+use futures::future;
 use hyper::Server;
-use tokio::sync::oneshot;
+use tokio::{select, sync::oneshot};
 use tracing::{debug, info, warn};
 use warp::Filter;
 
@@ -11,17 +11,26 @@ use crate::{effect::EffectBuilder, utils};
 
 /// Run the REST HTTP server.
 ///
-/// A message received on `shutdown_receiver` will cause the server to exit cleanly.
+/// A message received on `shutdown_receiver` will cause the server to stop accepting new
+/// connections and exit once the in-flight requests have completed, or the shutdown timeout has
+/// elapsed.
 pub(super) async fn run<REv: ReactorEventT>(
     config: Config,
     effect_builder: EffectBuilder<REv>,
     shutdown_receiver: oneshot::Receiver<()>,
 ) {
-    // REST filters.
+    // REST filters.  The metrics are only served here if there is no separate metrics server, and
+    // never without the TLS configured for them.
     let rest_status = filters::create_status_filter(effect_builder);
-    let rest_metrics = filters::create_metrics_filter(effect_builder);
+    let routes = if config.metrics_address.is_none() && config.metrics_tls.is_none() {
+        let rest_metrics =
+            filters::create_metrics_filter(effect_builder, config.metrics_basic_auth.clone());
+        rest_status.or(rest_metrics).unify().boxed()
+    } else {
+        rest_status
+    };
 
-    let service = warp_json_rpc::service(rest_status.or(rest_metrics));
+    let service = warp_json_rpc::service(routes);
 
     let mut server_address = match utils::resolve_address(&config.address) {
         Ok(address) => address,
@@ -50,20 +59,24 @@ pub(super) async fn run<REv: ReactorEventT>(
         }
     };
 
-    // Start the server, passing a oneshot receiver to allow the server to be shut down gracefully.
+    // Start the server.
     let make_svc =
         hyper::service::make_service_fn(move |_| future::ok::<_, Infallible>(service.clone()));
 
     let server = builder.serve(make_svc);
     info!(address = %server.local_addr(), "started REST server");
 
-    // Shutdown the server gracefully.
-    let _ = server
-        .with_graceful_shutdown(async {
-            shutdown_receiver.await.ok();
-        })
-        .map_err(|error| {
-            warn!(%error, "error running REST server");
-        })
-        .await;
+    // Shut down the server gracefully, dropping any connections still open after the timeout.
+    let (shutdown_signal, drain_deadline) =
+        utils::graceful_shutdown_with_deadline(shutdown_receiver, config.shutdown_timeout);
+    select! {
+        result = server.with_graceful_shutdown(shutdown_signal) => {
+            if let Err(error) = result {
+                warn!(%error, "error running REST server");
+            }
+        }
+        _ = drain_deadline => {
+            warn!("REST requests still in flight after shutdown timeout, dropping connections");
+        }
+    }
 }
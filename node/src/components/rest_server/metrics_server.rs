@@ -0,0 +1,152 @@
+use std::{convert::Infallible, future::Future, io, sync::Arc};
+
+use futures::{future, StreamExt};
+use hyper::{server::accept, Server};
+use openssl::{
+    error::ErrorStack,
+    ssl::{SslAcceptor, SslFiletype, SslMethod, SslVerifyMode},
+};
+use tokio::{net::TcpListener, select, sync::oneshot};
+use tracing::{debug, info, warn};
+
+use super::{filters, Config, MetricsTlsConfig, ReactorEventT};
+use crate::{effect::EffectBuilder, utils};
+
+/// The maximum number of TLS handshakes with clients of the metrics server performed concurrently.
+const MAX_CONCURRENT_HANDSHAKES: usize = 16;
+
+/// Run the separate HTTP server for the metrics endpoint, bound to `config.metrics_address`.
+///
+/// A message received on `shutdown_receiver` will cause the server to stop accepting new
+/// connections and exit once the in-flight requests have completed, or the shutdown timeout has
+/// elapsed.
+pub(super) async fn run<REv: ReactorEventT>(
+    config: Config,
+    effect_builder: EffectBuilder<REv>,
+    shutdown_receiver: oneshot::Receiver<()>,
+) {
+    let metrics_address = match config.metrics_address.as_ref() {
+        Some(metrics_address) => metrics_address,
+        None => return,
+    };
+    let server_address = match utils::resolve_address(metrics_address) {
+        Ok(address) => address,
+        Err(error) => {
+            warn!(%error, "failed to start metrics server, cannot parse address");
+            return;
+        }
+    };
+
+    let rest_metrics =
+        filters::create_metrics_filter(effect_builder, config.metrics_basic_auth.clone());
+    let service = warp_json_rpc::service(rest_metrics);
+
+    let (shutdown_signal, drain_deadline) =
+        utils::graceful_shutdown_with_deadline(shutdown_receiver, config.shutdown_timeout);
+
+    // Unlike the REST server, don't fall back to a random port: Operators firewall or scrape the
+    // configured one.
+    match config.metrics_tls.as_ref() {
+        None => {
+            let builder = match Server::try_bind(&server_address) {
+                Ok(builder) => builder,
+                Err(error) => {
+                    warn!(%error, "failed to start metrics server");
+                    return;
+                }
+            };
+            let make_svc = hyper::service::make_service_fn(move |_| {
+                future::ok::<_, Infallible>(service.clone())
+            });
+            let server = builder.serve(make_svc);
+            info!(address = %server.local_addr(), "started metrics server");
+            run_until_drained(
+                server.with_graceful_shutdown(shutdown_signal),
+                drain_deadline,
+            )
+            .await
+        }
+        Some(tls_config) => {
+            let acceptor = match create_tls_acceptor(tls_config) {
+                Ok(acceptor) => Arc::new(acceptor),
+                Err(error) => {
+                    warn!(%error, "failed to start metrics server, invalid TLS configuration");
+                    return;
+                }
+            };
+            let mut listener = match TcpListener::bind(server_address).await {
+                Ok(listener) => listener,
+                Err(error) => {
+                    warn!(%error, "failed to start metrics server");
+                    return;
+                }
+            };
+            let local_address = listener.local_addr().unwrap_or(server_address);
+            let incoming = listener
+                .incoming()
+                .map(move |result| {
+                    let acceptor = Arc::clone(&acceptor);
+                    async move {
+                        let stream = result?;
+                        tokio_openssl::accept(&acceptor, stream)
+                            .await
+                            .map_err(|error| {
+                                io::Error::new(io::ErrorKind::Other, error.to_string())
+                            })
+                    }
+                })
+                .buffer_unordered(MAX_CONCURRENT_HANDSHAKES)
+                .filter_map(|result| async move {
+                    match result {
+                        Ok(tls_stream) => Some(Ok::<_, io::Error>(tls_stream)),
+                        Err(error) => {
+                            debug!(%error, "failed to accept metrics client connection");
+                            None
+                        }
+                    }
+                });
+            let make_svc = hyper::service::make_service_fn(move |_| {
+                future::ok::<_, Infallible>(service.clone())
+            });
+            let server = Server::builder(accept::from_stream(incoming)).serve(make_svc);
+            info!(address = %local_address, "started metrics server with TLS");
+            run_until_drained(
+                server.with_graceful_shutdown(shutdown_signal),
+                drain_deadline,
+            )
+            .await
+        }
+    }
+}
+
+/// Runs the server until it has shut down gracefully, or until the deadline for draining the
+/// in-flight requests has passed.
+async fn run_until_drained(
+    server: impl Future<Output = hyper::Result<()>>,
+    drain_deadline: impl Future<Output = ()>,
+) {
+    select! {
+        result = server => {
+            if let Err(error) = result {
+                warn!(%error, "error running metrics server");
+            }
+        }
+        _ = drain_deadline => {
+            warn!("metrics requests still in flight after shutdown timeout, dropping connections");
+        }
+    }
+}
+
+/// Creates the TLS acceptor of the metrics server.  If a client CA is configured, clients must
+/// present a certificate signed by it.
+fn create_tls_acceptor(tls_config: &MetricsTlsConfig) -> Result<SslAcceptor, ErrorStack> {
+    let mut builder = SslAcceptor::mozilla_intermediate_v5(SslMethod::tls_server())?;
+    builder.set_certificate_chain_file(&tls_config.cert_path)?;
+    builder.set_private_key_file(&tls_config.secret_key_path, SslFiletype::PEM)?;
+    builder.check_private_key()?;
+    if let Some(client_ca_path) = tls_config.client_ca_path.as_ref() {
+        builder.set_ca_file(client_ca_path)?;
+        builder.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
+    }
+    Ok(builder.build())
+}
@@ -13,20 +13,32 @@
 //!
 //! For the list of supported RPC methods, see:
 //! https://github.com/CasperLabs/ceps/blob/master/text/0009-client-api.md#rpcs
+//!
+//! An OpenRPC document describing the supported RPC methods, generated from their Rust types, is
+//! served in response to a GET request for `/rpc/schema`.
 
+mod api_keys;
 mod config;
 mod event;
 mod http_server;
 pub mod rpcs;
 
-use std::{convert::Infallible, fmt::Debug};
+use std::{
+    collections::{HashMap, VecDeque},
+    convert::{Infallible, TryFrom},
+    fmt::Debug,
+};
 
 use datasize::DataSize;
-use futures::join;
+use futures::{future::BoxFuture, join, FutureExt};
+use semver::Version;
+use tokio::{sync::oneshot, task::JoinHandle};
+use tracing::{debug, error, info, warn};
 
 use casper_execution_engine::{
     core::engine_state::{
-        self, BalanceRequest, BalanceResult, GetEraValidatorsError, QueryRequest, QueryResult,
+        self, BalanceRequest, BalanceResult, GetEraValidatorsError, KeysWithPrefixRequest,
+        QueryRequest, QueryRequestBatch, QueryResult,
     },
     storage::protocol_data::ProtocolData,
 };
@@ -36,17 +48,26 @@ use self::rpcs::chain::BlockIdentifier;
 
 use super::Component;
 use crate::{
-    components::contract_runtime::EraValidatorsRequest,
-    crypto::hash::Digest,
+    components::{
+        chainspec_loader::Chainspec,
+        consensus::EraId,
+        contract_runtime::EraValidatorsRequest,
+        deploy_acceptor::{self, DeployAcceptance, DeployViolation, EraContext, SubmitDeployError},
+    },
+    crypto::{asymmetric_key::PublicKey, hash::Digest},
     effect::{
         announcements::RpcServerAnnouncement,
         requests::{
-            ChainspecLoaderRequest, ContractRuntimeRequest, LinearChainRequest, MetricsRequest,
-            NetworkInfoRequest, RpcRequest, StorageRequest,
+            BlockProposerRequest, ChainspecLoaderRequest, ConsensusRequest, ContractRuntimeRequest,
+            LinearChainRequest, MetricsRequest, NetworkInfoRequest, RpcRequest, StorageRequest,
+            SyncProgressRequest,
         },
         EffectBuilder, EffectExt, Effects, Responder,
     },
-    types::{NodeId, StatusFeed},
+    types::{
+        Block, Deploy, DeployHash, DeployReceipt, HistoricalStateWindow, NodeId, StatusFeed,
+        Timestamp,
+    },
     NodeRng,
 };
 
@@ -57,13 +78,16 @@ pub(crate) use event::Event;
 pub trait ReactorEventT:
     From<Event>
     + From<RpcRequest<NodeId>>
+    + From<ConsensusRequest>
     + From<RpcServerAnnouncement>
+    + From<BlockProposerRequest>
     + From<ChainspecLoaderRequest>
     + From<ContractRuntimeRequest>
     + From<LinearChainRequest<NodeId>>
     + From<MetricsRequest>
     + From<NetworkInfoRequest<NodeId>>
     + From<StorageRequest>
+    + From<SyncProgressRequest>
     + Send
 {
 }
@@ -71,29 +95,240 @@ pub trait ReactorEventT:
 impl<REv> ReactorEventT for REv where
     REv: From<Event>
         + From<RpcRequest<NodeId>>
+        + From<ConsensusRequest>
         + From<RpcServerAnnouncement>
+        + From<BlockProposerRequest>
         + From<ChainspecLoaderRequest>
         + From<ContractRuntimeRequest>
         + From<LinearChainRequest<NodeId>>
         + From<MetricsRequest>
         + From<NetworkInfoRequest<NodeId>>
         + From<StorageRequest>
+        + From<SyncProgressRequest>
         + Send
         + 'static
 {
 }
 
 #[derive(DataSize, Debug)]
-pub(crate) struct RpcServer {}
+pub(crate) struct RpcServer {
+    /// When the message is sent, it signals the server loop to exit cleanly.
+    shutdown_sender: Option<oneshot::Sender<()>>,
+    /// The task handle which will only join once the server loop has exited.
+    server_join_handle: Option<JoinHandle<()>>,
+    /// The number of most recent blocks whose global state can be queried, or `None` if
+    /// unrestricted.
+    historical_state_window: Option<u64>,
+    /// The accounts whose balances are excluded from the circulating supply.
+    circulating_supply_excluded_accounts: Vec<PublicKey>,
+    /// Whether deploys submitted by clients are refused, e.g. due to low free disk space.
+    deploys_paused: bool,
+    /// Whether the node runs in sidecar mode, in which deploys submitted by clients are always
+    /// refused.
+    sidecar: bool,
+    /// Whether the node is an archive node, as reported in its status.
+    archive: bool,
+    /// The most recent receipts issued for deploys accepted from clients.
+    deploy_receipts: HashMap<DeployHash, DeployReceipt>,
+    /// The hashes of the deploys in `deploy_receipts`, oldest first.
+    deploy_receipt_order: VecDeque<DeployHash>,
+    /// The maximum number of receipts retained.
+    deploy_receipt_capacity: usize,
+    /// The deploys most recently accepted from clients with an idempotency key, keyed by the key.
+    idempotency_keys: HashMap<String, DeployHash>,
+    /// The keys in `idempotency_keys`, oldest first.
+    idempotency_key_order: VecDeque<String>,
+    /// The maximum number of idempotency keys retained.
+    idempotency_key_capacity: usize,
+    /// The deploys being submitted with an idempotency key, keyed by the key, along with the
+    /// responders of any resubmissions awaiting the outcome.
+    #[data_size(skip)]
+    pending_idempotent_submissions: HashMap<String, (DeployHash, Vec<SubmitDeployResponder>)>,
+}
+
+type SubmitDeployResponder = Responder<Result<DeployAcceptance, SubmitDeployError>>;
 
 impl RpcServer {
     pub(crate) fn new<REv>(config: Config, effect_builder: EffectBuilder<REv>) -> Self
     where
         REv: ReactorEventT,
     {
-        tokio::spawn(http_server::run(config, effect_builder));
+        let historical_state_window = config.historical_state_window;
+        let circulating_supply_excluded_accounts =
+            config.circulating_supply_excluded_accounts.clone();
+        let deploy_receipt_capacity = config.deploy_receipt_capacity;
+        let idempotency_key_capacity = config.idempotency_key_capacity;
+        let (shutdown_sender, shutdown_receiver) = oneshot::channel::<()>();
+
+        let server_join_handle =
+            tokio::spawn(http_server::run(config, effect_builder, shutdown_receiver));
+
+        RpcServer {
+            shutdown_sender: Some(shutdown_sender),
+            server_join_handle: Some(server_join_handle),
+            historical_state_window,
+            circulating_supply_excluded_accounts,
+            deploys_paused: false,
+            sidecar: false,
+            archive: false,
+            deploy_receipts: HashMap::new(),
+            deploy_receipt_order: VecDeque::new(),
+            deploy_receipt_capacity,
+            idempotency_keys: HashMap::new(),
+            idempotency_key_order: VecDeque::new(),
+            idempotency_key_capacity,
+            pending_idempotent_submissions: HashMap::new(),
+        }
+    }
+
+    /// Sets the server to refuse all deploys submitted by clients, as the node runs in sidecar
+    /// mode.
+    pub(crate) fn with_sidecar_mode(mut self) -> Self {
+        self.sidecar = true;
+        self
+    }
 
-        RpcServer {}
+    /// Sets the server to report the node as an archive node in its status.
+    pub(crate) fn with_archive_mode(mut self) -> Self {
+        self.archive = true;
+        self
+    }
+
+    /// Signals the server to stop accepting new connections and to exit once the in-flight
+    /// requests have completed.
+    ///
+    /// The returned future completes once the server has exited.
+    pub(crate) fn shut_down(&mut self) -> BoxFuture<'static, ()> {
+        if let Some(shutdown_sender) = self.shutdown_sender.take() {
+            let _ = shutdown_sender.send(());
+        }
+
+        // Wait for the server to exit cleanly.
+        let maybe_join_handle = self.server_join_handle.take();
+        async move {
+            if let Some(join_handle) = maybe_join_handle {
+                match join_handle.await {
+                    Ok(_) => debug!("JSON-RPC server exited cleanly"),
+                    Err(error) => error!(%error, "could not join JSON-RPC server task cleanly"),
+                }
+            } else {
+                warn!("JSON-RPC server shutdown while already shut down")
+            }
+        }
+        .boxed()
+    }
+
+    /// Retains `receipt`, dropping the oldest receipt if at capacity.
+    fn retain_deploy_receipt(&mut self, deploy_hash: DeployHash, receipt: DeployReceipt) {
+        if self.deploy_receipt_capacity == 0 {
+            return;
+        }
+        if self.deploy_receipts.insert(deploy_hash, receipt).is_none() {
+            self.deploy_receipt_order.push_back(deploy_hash);
+        }
+        while self.deploy_receipt_order.len() > self.deploy_receipt_capacity {
+            if let Some(oldest) = self.deploy_receipt_order.pop_front() {
+                self.deploy_receipts.remove(&oldest);
+            }
+        }
+    }
+
+    /// Submits a deploy received from a client, unless one was already accepted or is being
+    /// submitted with the same idempotency key.
+    fn submit_deploy<REv: ReactorEventT>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        deploy: Box<Deploy>,
+        idempotency_key: Option<String>,
+        responder: SubmitDeployResponder,
+    ) -> Effects<Event> {
+        let deploy_hash = *deploy.id();
+        // Keys are ignored if none are retained.
+        let idempotency_key = idempotency_key.filter(|_| self.idempotency_key_capacity > 0);
+        if let Some(key) = idempotency_key.as_ref() {
+            if let Some(accepted_hash) = self.idempotency_keys.get(key) {
+                let result = if *accepted_hash == deploy_hash {
+                    Ok(DeployAcceptance::Replayed)
+                } else {
+                    Err(SubmitDeployError::IdempotencyKeyReused {
+                        deploy_hash: *accepted_hash,
+                    })
+                };
+                return responder.respond(result).ignore();
+            }
+            if let Some((pending_hash, waiting)) = self.pending_idempotent_submissions.get_mut(key)
+            {
+                if *pending_hash != deploy_hash {
+                    let error = SubmitDeployError::IdempotencyKeyReused {
+                        deploy_hash: *pending_hash,
+                    };
+                    return responder.respond(Err(error)).ignore();
+                }
+                waiting.push(responder);
+                return Effects::new();
+            }
+            self.pending_idempotent_submissions
+                .insert(key.clone(), (deploy_hash, vec![]));
+        }
+
+        async move {
+            let result = check_submitted_deploy(effect_builder, &deploy).await;
+            if result.is_ok() {
+                effect_builder.announce_deploy_received(deploy).await;
+            }
+            result
+        }
+        .event(move |result| Event::SubmitDeployResult {
+            deploy_hash,
+            idempotency_key,
+            result,
+            main_responder: responder,
+        })
+    }
+
+    /// Responds to a submission and any resubmissions awaiting its outcome, retaining its
+    /// idempotency key if the deploy was accepted.
+    fn handle_submit_deploy_result(
+        &mut self,
+        deploy_hash: DeployHash,
+        idempotency_key: Option<String>,
+        result: Result<(), SubmitDeployError>,
+        main_responder: SubmitDeployResponder,
+    ) -> Effects<Event> {
+        let mut effects = Effects::new();
+        if let Some(key) = idempotency_key {
+            if let Some((_, waiting)) = self.pending_idempotent_submissions.remove(&key) {
+                for responder in waiting {
+                    let result = result.clone().map(|()| DeployAcceptance::Replayed);
+                    effects.extend(responder.respond(result).ignore());
+                }
+            }
+            if result.is_ok() {
+                self.retain_idempotency_key(key, deploy_hash);
+            }
+        }
+        effects.extend(
+            main_responder
+                .respond(result.map(|()| DeployAcceptance::New))
+                .ignore(),
+        );
+        effects
+    }
+
+    /// Retains the idempotency key of an accepted deploy, dropping the oldest key if at capacity.
+    fn retain_idempotency_key(&mut self, key: String, deploy_hash: DeployHash) {
+        if self
+            .idempotency_keys
+            .insert(key.clone(), deploy_hash)
+            .is_none()
+        {
+            self.idempotency_key_order.push_back(key);
+        }
+        while self.idempotency_key_order.len() > self.idempotency_key_capacity {
+            if let Some(oldest) = self.idempotency_key_order.pop_front() {
+                self.idempotency_keys.remove(&oldest);
+            }
+        }
     }
 }
 
@@ -162,6 +397,135 @@ impl RpcServer {
     }
 }
 
+/// Checks whether a deploy submitted by a client should be announced: the block proposer must
+/// have room for it, its Wasm must pass the static checks, and it must be likely to be executed as
+/// intended given the current era.
+async fn check_submitted_deploy<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    deploy: &Deploy,
+) -> Result<(), SubmitDeployError> {
+    effect_builder.check_deploy_buffer_capacity().await?;
+
+    // TODO - where to get version from?
+    let chainspec = match effect_builder.get_chainspec(Version::new(1, 0, 0)).await {
+        Some(chainspec) => chainspec,
+        None => {
+            warn!(
+                deploy_hash = %deploy.id(),
+                "failed to get chainspec, not checking deploy Wasm and timing"
+            );
+            return Ok(());
+        }
+    };
+    deploy_acceptor::check_wasm(
+        deploy,
+        &chainspec.genesis.deploy_config,
+        &chainspec.genesis.wasm_config,
+    )?;
+    let era_context = current_era_context(effect_builder, &chainspec).await;
+    deploy_acceptor::check_era_gate(deploy, Timestamp::now(), &era_context)?;
+    Ok(())
+}
+
+/// Returns every reason for which `deploy` would be refused by the deploy acceptor or the era gate
+/// if submitted now, or `None` if the chainspec is not available.
+async fn precheck_deploy<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    deploy: &Deploy,
+) -> Option<Vec<DeployViolation>> {
+    // TODO - where to get version from?
+    let chainspec = effect_builder.get_chainspec(Version::new(1, 0, 0)).await?;
+    let mut violations = deploy_acceptor::precheck(
+        deploy,
+        &chainspec.genesis.name,
+        &chainspec.genesis.deploy_config,
+        &chainspec.genesis.wasm_config,
+    );
+    let era_context = current_era_context(effect_builder, &chainspec).await;
+    if let Err(error) = deploy_acceptor::check_era_gate(deploy, Timestamp::now(), &era_context) {
+        violations.push(error.into());
+    }
+    Some(violations)
+}
+
+/// Returns the context of the current era in which deploys are submitted.
+async fn current_era_context<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    chainspec: &Chainspec,
+) -> EraContext {
+    let maybe_highest_switch_block = effect_builder.get_highest_switch_block().await;
+    let auction_contract_hash = effect_builder
+        .get_protocol_data(ProtocolVersion::V1_0_0)
+        .await
+        .ok()
+        .flatten()
+        .map(|protocol_data| protocol_data.auction());
+    EraContext::new(
+        chainspec,
+        maybe_highest_switch_block.as_ref(),
+        auction_contract_hash,
+    )
+}
+
+/// Returns the historical state window of `size` blocks ending at the highest block.
+async fn current_historical_state_window<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    size: u64,
+) -> HistoricalStateWindow {
+    let maybe_highest_block_height = effect_builder
+        .get_highest_block()
+        .await
+        .map(|block| block.height());
+    HistoricalStateWindow::new(size, maybe_highest_block_height)
+}
+
+/// Checks whether `state_root_hash` is the state root hash of a block within the historical state
+/// window of `size` blocks.
+async fn check_historical_state<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    size: u64,
+    state_root_hash: Digest,
+) -> Result<(), HistoricalStateWindow> {
+    let window = current_historical_state_window(effect_builder, size).await;
+    match effect_builder
+        .get_block_height_by_state_root_hash(state_root_hash)
+        .await
+    {
+        Some(block_height) if window.contains(block_height) => Ok(()),
+        _ => Err(window),
+    }
+}
+
+/// Returns the most recent block whose timestamp is not later than `timestamp`.
+///
+/// Block timestamps increase with height, so the block is found by a binary search over the
+/// heights up to the highest block.  Heights below the earliest block held in storage are treated
+/// as lying before `timestamp`.
+async fn get_block_at_timestamp<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    timestamp: Timestamp,
+) -> Option<Block> {
+    let highest_block = effect_builder.get_highest_block().await?;
+    if highest_block.header().timestamp() <= timestamp {
+        return Some(highest_block);
+    }
+
+    let mut maybe_found = None;
+    let (mut low, mut high) = (0, highest_block.height());
+    while low < high {
+        let middle = low + (high - low) / 2;
+        match effect_builder.get_block_at_height(middle).await {
+            Some(block) if block.header().timestamp() <= timestamp => {
+                low = middle + 1;
+                maybe_found = Some(block);
+            }
+            Some(_) => high = middle,
+            None => low = middle + 1,
+        }
+    }
+    maybe_found
+}
+
 impl<REv> Component<REv> for RpcServer
 where
     REv: ReactorEventT,
@@ -176,8 +540,26 @@ where
         event: Self::Event,
     ) -> Effects<Self::Event> {
         match event {
-            Event::RpcRequest(RpcRequest::SubmitDeploy { deploy, responder }) => {
-                let mut effects = effect_builder.announce_deploy_received(deploy).ignore();
+            Event::RpcRequest(RpcRequest::SubmitDeploy { responder, .. }) if self.sidecar => {
+                responder.respond(Err(SubmitDeployError::Sidecar)).ignore()
+            }
+            Event::RpcRequest(RpcRequest::SubmitDeploy { responder, .. })
+                if self.deploys_paused =>
+            {
+                responder.respond(Err(SubmitDeployError::Paused)).ignore()
+            }
+            Event::RpcRequest(RpcRequest::SubmitDeploy {
+                deploy,
+                idempotency_key,
+                responder,
+            }) => self.submit_deploy(effect_builder, deploy, idempotency_key, responder),
+            Event::RpcRequest(RpcRequest::CheckDeploy { deploy, responder }) => async move {
+                let maybe_violations = precheck_deploy(effect_builder, &deploy).await;
+                responder.respond(maybe_violations).await
+            }
+            .ignore(),
+            Event::RpcRequest(RpcRequest::AdminCommand { command, responder }) => {
+                let mut effects = effect_builder.announce_admin_command(command).ignore();
                 effects.extend(responder.respond(()).ignore());
                 effects
             }
@@ -201,6 +583,36 @@ where
                     result: Box::new(result),
                     main_responder: responder,
                 }),
+            Event::RpcRequest(RpcRequest::GetBlock {
+                maybe_id: Some(BlockIdentifier::SwitchBlock(era_id)),
+                responder,
+            }) => effect_builder
+                .get_switch_block_at_era_id(EraId(era_id))
+                .event(move |result| Event::GetBlockResult {
+                    maybe_id: Some(BlockIdentifier::SwitchBlock(era_id)),
+                    result: Box::new(result),
+                    main_responder: responder,
+                }),
+            Event::RpcRequest(RpcRequest::GetBlock {
+                maybe_id: Some(BlockIdentifier::LatestSwitchBlock),
+                responder,
+            }) => effect_builder
+                .get_highest_switch_block()
+                .event(move |result| Event::GetBlockResult {
+                    maybe_id: Some(BlockIdentifier::LatestSwitchBlock),
+                    result: Box::new(result),
+                    main_responder: responder,
+                }),
+            Event::RpcRequest(RpcRequest::GetBlock {
+                maybe_id: Some(BlockIdentifier::Timestamp(timestamp)),
+                responder,
+            }) => get_block_at_timestamp(effect_builder, timestamp).event(move |result| {
+                Event::GetBlockResult {
+                    maybe_id: Some(BlockIdentifier::Timestamp(timestamp)),
+                    result: Box::new(result),
+                    main_responder: responder,
+                }
+            }),
             Event::RpcRequest(RpcRequest::GetBlock {
                 maybe_id: None,
                 responder,
@@ -211,6 +623,56 @@ where
                     result: Box::new(result),
                     main_responder: responder,
                 }),
+            Event::RpcRequest(RpcRequest::GetSwitchBlock {
+                maybe_era_id: Some(era_id),
+                responder,
+            }) => effect_builder
+                .get_switch_block_at_era_id(era_id)
+                .event(move |result| Event::GetSwitchBlockResult {
+                    maybe_era_id: Some(era_id),
+                    result: Box::new(result),
+                    main_responder: responder,
+                }),
+            Event::RpcRequest(RpcRequest::GetSwitchBlock {
+                maybe_era_id: None,
+                responder,
+            }) => effect_builder
+                .get_highest_switch_block()
+                .event(move |result| Event::GetSwitchBlockResult {
+                    maybe_era_id: None,
+                    result: Box::new(result),
+                    main_responder: responder,
+                }),
+            Event::RpcRequest(RpcRequest::GetBlocks {
+                start_height,
+                max_count,
+                responder,
+            }) => effect_builder
+                .get_blocks_in_height_range(start_height, max_count)
+                .event(move |result| Event::GetBlocksResult {
+                    start_height,
+                    result,
+                    main_responder: responder,
+                }),
+            Event::RpcRequest(RpcRequest::GetBlockByDeploy {
+                deploy_hash,
+                responder,
+            }) => async move {
+                let maybe_block = effect_builder
+                    .get_block_by_deploy_hash_from_storage(deploy_hash)
+                    .await;
+                responder.respond(maybe_block).await
+            }
+            .ignore(),
+            Event::RpcRequest(RpcRequest::GetAlternativeBlocks { height, responder }) => {
+                async move {
+                    let blocks = effect_builder
+                        .get_alternative_blocks_at_height(height)
+                        .await;
+                    responder.respond(blocks).await;
+                }
+                .ignore()
+            }
             Event::RpcRequest(RpcRequest::QueryProtocolData {
                 protocol_version,
                 responder,
@@ -221,6 +683,53 @@ where
                 path,
                 responder,
             }) => self.handle_query(effect_builder, state_root_hash, base_key, path, responder),
+            Event::RpcRequest(RpcRequest::QueryGlobalStateBatch {
+                state_root_hash,
+                queries,
+                responder,
+            }) => {
+                let batch = QueryRequestBatch::new(state_root_hash.into(), queries);
+                async move {
+                    let result = effect_builder.query_global_state_batch(batch).await;
+                    responder.respond(result).await
+                }
+                .ignore()
+            }
+            Event::RpcRequest(RpcRequest::CheckHistoricalState {
+                state_root_hash,
+                responder,
+            }) => {
+                let maybe_size = self.historical_state_window;
+                async move {
+                    let result = match maybe_size {
+                        Some(size) => {
+                            check_historical_state(effect_builder, size, state_root_hash).await
+                        }
+                        None => Ok(()),
+                    };
+                    responder.respond(result).await
+                }
+                .ignore()
+            }
+            Event::RpcRequest(RpcRequest::GetKeysWithPrefix {
+                state_root_hash,
+                prefix,
+                offset,
+                count,
+                responder,
+            }) => {
+                let request = KeysWithPrefixRequest::new(
+                    state_root_hash.into(),
+                    prefix,
+                    usize::try_from(offset).unwrap_or(usize::MAX),
+                    usize::try_from(count).unwrap_or(usize::MAX),
+                );
+                async move {
+                    let result = effect_builder.get_keys_with_prefix(request).await;
+                    responder.respond(result).await
+                }
+                .ignore()
+            }
             Event::RpcRequest(RpcRequest::QueryEraValidators {
                 state_root_hash,
                 protocol_version,
@@ -243,33 +752,146 @@ where
                     result: Box::new(result),
                     main_responder: responder,
                 }),
+            Event::RpcRequest(RpcRequest::GetKeyHistory {
+                key,
+                offset,
+                count,
+                responder,
+            }) => effect_builder
+                .get_key_history_from_storage(key, offset, count)
+                .event(move |result| Event::GetKeyHistoryResult {
+                    key,
+                    result,
+                    main_responder: responder,
+                }),
+            Event::RpcRequest(RpcRequest::GetAccountDeploys {
+                account_hash,
+                offset,
+                count,
+                responder,
+            }) => effect_builder
+                .get_account_deploys_from_storage(account_hash, offset, count)
+                .event(move |result| Event::GetAccountDeploysResult {
+                    account_hash,
+                    result,
+                    main_responder: responder,
+                }),
+            Event::RpcRequest(RpcRequest::GetTransfersTo {
+                target,
+                since_block_height,
+                max_count,
+                responder,
+            }) => effect_builder
+                .get_transfers_to_from_storage(target, since_block_height, max_count)
+                .event(move |result| Event::GetTransfersToResult {
+                    target,
+                    result,
+                    main_responder: responder,
+                }),
             Event::RpcRequest(RpcRequest::GetPeers { responder }) => effect_builder
                 .network_peers()
                 .event(move |peers| Event::GetPeersResult {
                     peers,
                     main_responder: responder,
                 }),
-            Event::RpcRequest(RpcRequest::GetStatus { responder }) => async move {
-                let (last_added_block, peers, chainspec_info) = join!(
-                    effect_builder.get_highest_block(),
-                    effect_builder.network_peers(),
-                    effect_builder.get_chainspec_info()
-                );
-                let status_feed = StatusFeed::new(last_added_block, peers, chainspec_info);
-                responder.respond(status_feed).await;
+            Event::RpcRequest(RpcRequest::EstimateDeployCost {
+                deploy,
+                profile_gas,
+                responder,
+            }) => async move {
+                let result = match effect_builder.get_highest_block().await {
+                    Some(block) => {
+                        let execution_result = effect_builder
+                            .estimate_deploy_cost(
+                                *block.state_root_hash(),
+                                Timestamp::now(),
+                                deploy,
+                                profile_gas,
+                            )
+                            .await;
+                        Some((*block.hash(), execution_result))
+                    }
+                    None => None,
+                };
+                responder.respond(result).await;
             }
             .ignore(),
+            Event::RpcRequest(RpcRequest::GetStatus { responder }) => {
+                let maybe_window_size = self.historical_state_window;
+                let archive = self.archive;
+                async move {
+                    let (last_added_block, peers, chainspec_info, sync_progress) = join!(
+                        effect_builder.get_highest_block(),
+                        effect_builder.network_peers(),
+                        effect_builder.get_chainspec_info(),
+                        effect_builder.get_sync_progress()
+                    );
+                    let historical_state_window = maybe_window_size.map(|size| {
+                        let maybe_highest_block_height =
+                            last_added_block.as_ref().map(|block| block.height());
+                        HistoricalStateWindow::new(size, maybe_highest_block_height)
+                    });
+                    let mut status_feed =
+                        StatusFeed::new(last_added_block, peers, chainspec_info, sync_progress);
+                    status_feed.historical_state_window = historical_state_window;
+                    status_feed.archive = archive;
+                    responder.respond(status_feed).await;
+                }
+                .ignore()
+            }
             Event::RpcRequest(RpcRequest::GetMetrics { responder }) => effect_builder
                 .get_metrics()
                 .event(move |text| Event::GetMetricsResult {
                     text,
                     main_responder: responder,
                 }),
+            Event::RpcRequest(RpcRequest::GetChainspec { responder }) => async move {
+                // TODO - where to get version from?
+                let maybe_chainspec = effect_builder.get_chainspec(Version::new(1, 0, 0)).await;
+                responder.respond(maybe_chainspec).await
+            }
+            .ignore(),
+            Event::RpcRequest(RpcRequest::GetCirculatingSupplyExcludedAccounts { responder }) => {
+                responder
+                    .respond(self.circulating_supply_excluded_accounts.clone())
+                    .ignore()
+            }
+            Event::RpcRequest(RpcRequest::SignStatus { payload, responder }) => async move {
+                let maybe_signature = effect_builder.sign_status(payload).await;
+                responder.respond(maybe_signature).await
+            }
+            .ignore(),
+            Event::RpcRequest(RpcRequest::SignDeployReceipt {
+                deploy_hash,
+                responder,
+            }) => effect_builder
+                .sign_deploy_receipt(deploy_hash, Timestamp::now())
+                .event(move |result| Event::SignDeployReceiptResult {
+                    deploy_hash,
+                    result: result.map(Box::new),
+                    main_responder: responder,
+                }),
+            Event::RpcRequest(RpcRequest::GetDeployReceipt {
+                deploy_hash,
+                responder,
+            }) => responder
+                .respond(self.deploy_receipts.get(&deploy_hash).cloned())
+                .ignore(),
             Event::GetBlockResult {
                 maybe_id: _,
                 result,
                 main_responder,
             } => main_responder.respond(*result).ignore(),
+            Event::GetSwitchBlockResult {
+                maybe_era_id: _,
+                result,
+                main_responder,
+            } => main_responder.respond(*result).ignore(),
+            Event::GetBlocksResult {
+                start_height: _,
+                result,
+                main_responder,
+            } => main_responder.respond(result).ignore(),
             Event::QueryProtocolDataResult {
                 result,
                 main_responder,
@@ -291,6 +913,21 @@ where
                 result,
                 main_responder,
             } => main_responder.respond(*result).ignore(),
+            Event::GetKeyHistoryResult {
+                key: _,
+                result,
+                main_responder,
+            } => main_responder.respond(result).ignore(),
+            Event::GetAccountDeploysResult {
+                account_hash: _,
+                result,
+                main_responder,
+            } => main_responder.respond(result).ignore(),
+            Event::GetTransfersToResult {
+                target: _,
+                result,
+                main_responder,
+            } => main_responder.respond(result).ignore(),
             Event::GetPeersResult {
                 peers,
                 main_responder,
@@ -299,6 +936,37 @@ where
                 text,
                 main_responder,
             } => main_responder.respond(text).ignore(),
+            Event::SignDeployReceiptResult {
+                deploy_hash,
+                result,
+                main_responder,
+            } => {
+                let maybe_receipt = result.map(|receipt| *receipt);
+                if let Some(receipt) = maybe_receipt.as_ref() {
+                    info!(%deploy_hash, "issued deploy receipt");
+                    self.retain_deploy_receipt(deploy_hash, receipt.clone());
+                }
+                main_responder.respond(maybe_receipt).ignore()
+            }
+            Event::SubmitDeployResult {
+                deploy_hash,
+                idempotency_key,
+                result,
+                main_responder,
+            } => self.handle_submit_deploy_result(
+                deploy_hash,
+                idempotency_key,
+                result,
+                main_responder,
+            ),
+            Event::PauseDeploys => {
+                self.deploys_paused = true;
+                Effects::new()
+            }
+            Event::ResumeDeploys => {
+                self.deploys_paused = false;
+                Effects::new()
+            }
         }
     }
 }
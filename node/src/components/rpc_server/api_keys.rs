@@ -0,0 +1,442 @@
+//! API keys scoping access to the JSON-RPC server, for node operators running public RPC gateways.
+//!
+//! If an API keys file is configured, every JSON-RPC request must carry one of the listed keys in
+//! its "X-API-Key" header.  Each key can be limited to a number of requests per day (UTC) and a
+//! number of requests handled concurrently.  Requests are refused with HTTP status 401 if the key
+//! is missing or unknown, and 429 if one of its quotas is exhausted.  Requests authorized with the
+//! admin API token are exempt.
+//!
+//! The keys file is TOML, listing the keys as an array of tables:
+//!
+//! ```toml
+//! [[keys]]
+//! key = 'c3a9f1...'
+//! name = 'explorer'
+//! max_requests_per_day = 100000
+//! max_concurrent_requests = 8
+//! ```
+//!
+//! It can be reloaded at runtime via the "admin_reload_api_keys" RPC.  The usage counters of keys
+//! still listed after a reload are kept.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs, io, mem,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use http::{Response, StatusCode};
+use hyper::Body;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::types::Timestamp;
+
+/// The header holding the API key of a request.
+pub(super) const API_KEY_HEADER: &str = "x-api-key";
+
+const MILLIS_PER_DAY: u64 = 24 * 60 * 60 * 1_000;
+
+/// An entry of the API keys file.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+// Disallow unknown fields to ensure the keys file contains valid keys.
+#[serde(deny_unknown_fields)]
+struct ApiKeyConfig {
+    /// The secret key, provided by clients in the "X-API-Key" header.
+    key: String,
+    /// The name identifying the key in the usage report, e.g. the name of the client.
+    name: String,
+    /// The maximum number of requests per day (UTC).  Unlimited if not given.
+    #[serde(default)]
+    max_requests_per_day: Option<u64>,
+    /// The maximum number of requests handled concurrently.  Unlimited if not given.
+    #[serde(default)]
+    max_concurrent_requests: Option<u32>,
+}
+
+/// The contents of the API keys file.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct ApiKeysFile {
+    #[serde(default)]
+    keys: Vec<ApiKeyConfig>,
+}
+
+/// An error loading the API keys file.
+#[derive(Debug, Error)]
+pub enum ApiKeysError {
+    /// The file could not be read.
+    #[error("failed to read API keys file {}: {error}", path.display())]
+    Read {
+        /// The path of the file.
+        path: PathBuf,
+        /// The underlying error.
+        error: io::Error,
+    },
+    /// The file could not be parsed.
+    #[error("failed to parse API keys file {}: {error}", path.display())]
+    Parse {
+        /// The path of the file.
+        path: PathBuf,
+        /// The underlying error.
+        error: toml::de::Error,
+    },
+    /// The same key is listed more than once.
+    #[error("the key named '{name}' is listed more than once in API keys file {}", path.display())]
+    DuplicateKey {
+        /// The path of the file.
+        path: PathBuf,
+        /// The name of the duplicated key.
+        name: String,
+    },
+}
+
+/// The reason a request was refused.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub(super) enum Rejection {
+    /// The request carries no API key.
+    #[error("missing API key")]
+    MissingKey,
+    /// The request carries an API key not listed in the keys file.
+    #[error("unknown API key")]
+    UnknownKey,
+    /// The key's requests for the day are used up.
+    #[error("daily request quota of API key '{0}' exhausted")]
+    DailyQuotaExhausted(String),
+    /// The key already has the maximum number of requests in flight.
+    #[error("too many concurrent requests for API key '{0}'")]
+    TooManyConcurrentRequests(String),
+}
+
+impl Rejection {
+    /// Returns the HTTP response refusing the request.
+    pub(super) fn into_response(self) -> Response<Body> {
+        let status = match self {
+            Rejection::MissingKey | Rejection::UnknownKey => StatusCode::UNAUTHORIZED,
+            Rejection::DailyQuotaExhausted(_) | Rejection::TooManyConcurrentRequests(_) => {
+                StatusCode::TOO_MANY_REQUESTS
+            }
+        };
+        Response::builder()
+            .status(status)
+            .body(Body::from(self.to_string()))
+            .expect("should build response")
+    }
+}
+
+/// The usage of an API key.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ApiKeyUsage {
+    /// The name of the key.
+    pub name: String,
+    /// The number of requests admitted so far today (UTC).
+    pub requests_today: u64,
+    /// The maximum number of requests per day, if limited.
+    pub max_requests_per_day: Option<u64>,
+    /// The number of requests currently in flight.
+    pub concurrent_requests: u32,
+    /// The maximum number of concurrent requests, if limited.
+    pub max_concurrent_requests: Option<u32>,
+    /// The number of requests admitted since the node started.
+    pub total_requests: u64,
+    /// The number of requests refused due to exhausted quotas since the node started.
+    pub rejected_requests: u64,
+}
+
+/// The quotas and usage counters of a single key.
+#[derive(Debug)]
+struct KeyState {
+    config: ApiKeyConfig,
+    /// The day (UTC, counted from the Unix epoch) to which `requests_today` refers.
+    day: u64,
+    requests_today: u64,
+    concurrent_requests: u32,
+    total_requests: u64,
+    rejected_requests: u64,
+}
+
+impl KeyState {
+    fn new(config: ApiKeyConfig) -> Self {
+        KeyState {
+            config,
+            day: 0,
+            requests_today: 0,
+            concurrent_requests: 0,
+            total_requests: 0,
+            rejected_requests: 0,
+        }
+    }
+
+    fn requests_on(&self, day: u64) -> u64 {
+        if self.day == day {
+            self.requests_today
+        } else {
+            0
+        }
+    }
+}
+
+/// The API keys listed in the keys file, along with their usage.
+#[derive(Debug)]
+pub struct ApiKeys {
+    path: PathBuf,
+    keys: Mutex<HashMap<String, KeyState>>,
+}
+
+impl ApiKeys {
+    /// Loads the API keys from the file at `path`.
+    pub(super) fn load(path: PathBuf) -> Result<Self, ApiKeysError> {
+        let configs = read_keys_file(&path)?;
+        let keys = configs
+            .into_iter()
+            .map(|config| (config.key.clone(), KeyState::new(config)))
+            .collect();
+        Ok(ApiKeys {
+            path,
+            keys: Mutex::new(keys),
+        })
+    }
+
+    /// Reloads the keys file, keeping the usage counters of keys still listed.  Returns the number
+    /// of keys.
+    ///
+    /// The current keys are kept if the file can't be loaded.
+    pub(super) fn reload(&self) -> Result<usize, ApiKeysError> {
+        let configs = read_keys_file(&self.path)?;
+        let mut keys = self.keys.lock().expect("should lock API keys");
+        let mut old_keys = mem::take(&mut *keys);
+        for config in configs {
+            let state = match old_keys.remove(&config.key) {
+                Some(mut state) => {
+                    state.config = config.clone();
+                    state
+                }
+                None => KeyState::new(config.clone()),
+            };
+            keys.insert(config.key, state);
+        }
+        Ok(keys.len())
+    }
+
+    /// Admits a request carrying `maybe_key` at time `now` if the key is known and its quotas
+    /// allow it.
+    ///
+    /// The request counts as in flight until the returned permit is dropped.
+    pub(super) fn admit(
+        self: &Arc<Self>,
+        maybe_key: Option<&str>,
+        now: Timestamp,
+    ) -> Result<Permit, Rejection> {
+        let key = maybe_key.ok_or(Rejection::MissingKey)?;
+        let mut keys = self.keys.lock().expect("should lock API keys");
+        let state = keys.get_mut(key).ok_or(Rejection::UnknownKey)?;
+
+        let day = now.millis() / MILLIS_PER_DAY;
+        if state.day != day {
+            state.day = day;
+            state.requests_today = 0;
+        }
+        if let Some(max) = state.config.max_requests_per_day {
+            if state.requests_today >= max {
+                state.rejected_requests += 1;
+                return Err(Rejection::DailyQuotaExhausted(state.config.name.clone()));
+            }
+        }
+        if let Some(max) = state.config.max_concurrent_requests {
+            if state.concurrent_requests >= max {
+                state.rejected_requests += 1;
+                return Err(Rejection::TooManyConcurrentRequests(
+                    state.config.name.clone(),
+                ));
+            }
+        }
+
+        state.requests_today += 1;
+        state.total_requests += 1;
+        state.concurrent_requests += 1;
+        Ok(Permit {
+            api_keys: Arc::clone(self),
+            key: key.to_string(),
+        })
+    }
+
+    /// Returns the usage of all keys at time `now`, ordered by name.
+    pub(super) fn usage(&self, now: Timestamp) -> Vec<ApiKeyUsage> {
+        let day = now.millis() / MILLIS_PER_DAY;
+        let keys = self.keys.lock().expect("should lock API keys");
+        let mut usage: Vec<_> = keys
+            .values()
+            .map(|state| ApiKeyUsage {
+                name: state.config.name.clone(),
+                requests_today: state.requests_on(day),
+                max_requests_per_day: state.config.max_requests_per_day,
+                concurrent_requests: state.concurrent_requests,
+                max_concurrent_requests: state.config.max_concurrent_requests,
+                total_requests: state.total_requests,
+                rejected_requests: state.rejected_requests,
+            })
+            .collect();
+        usage.sort_by(|lhs, rhs| lhs.name.cmp(&rhs.name));
+        usage
+    }
+}
+
+/// A request admitted under an API key, counted as in flight until dropped.
+#[derive(Debug)]
+pub(super) struct Permit {
+    api_keys: Arc<ApiKeys>,
+    key: String,
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        let mut keys = self.api_keys.keys.lock().expect("should lock API keys");
+        // The key may have been removed by a reload in the meantime.
+        if let Some(state) = keys.get_mut(&self.key) {
+            state.concurrent_requests = state.concurrent_requests.saturating_sub(1);
+        }
+    }
+}
+
+fn read_keys_file(path: &Path) -> Result<Vec<ApiKeyConfig>, ApiKeysError> {
+    let contents = fs::read_to_string(path).map_err(|error| ApiKeysError::Read {
+        path: path.to_path_buf(),
+        error,
+    })?;
+    let file: ApiKeysFile = toml::from_str(&contents).map_err(|error| ApiKeysError::Parse {
+        path: path.to_path_buf(),
+        error,
+    })?;
+    let mut seen = HashSet::new();
+    for config in &file.keys {
+        if !seen.insert(&config.key) {
+            return Err(ApiKeysError::DuplicateKey {
+                path: path.to_path_buf(),
+                name: config.name.clone(),
+            });
+        }
+    }
+    Ok(file.keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEYS: &str = r#"
+        [[keys]]
+        key = 'secret1'
+        name = 'explorer'
+        max_requests_per_day = 2
+
+        [[keys]]
+        key = 'secret2'
+        name = 'wallet'
+        max_concurrent_requests = 1
+    "#;
+
+    fn load(contents: &str) -> (tempfile::TempDir, Arc<ApiKeys>) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("api_keys.toml");
+        fs::write(&path, contents).unwrap();
+        let api_keys = Arc::new(ApiKeys::load(path).unwrap());
+        (dir, api_keys)
+    }
+
+    fn usage_of(api_keys: &ApiKeys, name: &str, now: Timestamp) -> ApiKeyUsage {
+        api_keys
+            .usage(now)
+            .into_iter()
+            .find(|usage| usage.name == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn should_refuse_missing_and_unknown_keys() {
+        let (_dir, api_keys) = load(KEYS);
+        let now = Timestamp::from(1_000);
+        assert_eq!(
+            api_keys.admit(None, now).unwrap_err(),
+            Rejection::MissingKey
+        );
+        assert_eq!(
+            api_keys.admit(Some("guess"), now).unwrap_err(),
+            Rejection::UnknownKey
+        );
+    }
+
+    #[test]
+    fn should_enforce_daily_quota() {
+        let (_dir, api_keys) = load(KEYS);
+        let now = Timestamp::from(MILLIS_PER_DAY - 2);
+
+        drop(api_keys.admit(Some("secret1"), now).unwrap());
+        drop(api_keys.admit(Some("secret1"), now).unwrap());
+        assert_eq!(
+            api_keys.admit(Some("secret1"), now).unwrap_err(),
+            Rejection::DailyQuotaExhausted("explorer".to_string())
+        );
+        let usage = usage_of(&api_keys, "explorer", now);
+        assert_eq!(usage.requests_today, 2);
+        assert_eq!(usage.rejected_requests, 1);
+
+        // the quota is replenished on the next day
+        let tomorrow = Timestamp::from(MILLIS_PER_DAY);
+        assert_eq!(usage_of(&api_keys, "explorer", tomorrow).requests_today, 0);
+        assert!(api_keys.admit(Some("secret1"), tomorrow).is_ok());
+        assert_eq!(usage_of(&api_keys, "explorer", tomorrow).total_requests, 3);
+    }
+
+    #[test]
+    fn should_enforce_concurrency_limit() {
+        let (_dir, api_keys) = load(KEYS);
+        let now = Timestamp::from(1_000);
+
+        let permit = api_keys.admit(Some("secret2"), now).unwrap();
+        assert_eq!(usage_of(&api_keys, "wallet", now).concurrent_requests, 1);
+        assert_eq!(
+            api_keys.admit(Some("secret2"), now).unwrap_err(),
+            Rejection::TooManyConcurrentRequests("wallet".to_string())
+        );
+
+        drop(permit);
+        assert_eq!(usage_of(&api_keys, "wallet", now).concurrent_requests, 0);
+        assert!(api_keys.admit(Some("secret2"), now).is_ok());
+    }
+
+    #[test]
+    fn should_keep_usage_across_reloads() {
+        let (dir, api_keys) = load(KEYS);
+        let now = Timestamp::from(1_000);
+        drop(api_keys.admit(Some("secret1"), now).unwrap());
+
+        // drop the wallet key and raise the explorer's quota
+        let reloaded = r#"
+            [[keys]]
+            key = 'secret1'
+            name = 'explorer'
+            max_requests_per_day = 10
+        "#;
+        fs::write(dir.path().join("api_keys.toml"), reloaded).unwrap();
+        assert_eq!(api_keys.reload().unwrap(), 1);
+
+        let usage = api_keys.usage(now);
+        assert_eq!(usage.len(), 1);
+        assert_eq!(usage[0].requests_today, 1);
+        assert_eq!(usage[0].max_requests_per_day, Some(10));
+        assert_eq!(
+            api_keys.admit(Some("secret2"), now).unwrap_err(),
+            Rejection::UnknownKey
+        );
+
+        // an invalid file leaves the keys unchanged
+        fs::write(dir.path().join("api_keys.toml"), KEYS.repeat(2)).unwrap();
+        assert!(matches!(
+            api_keys.reload(),
+            Err(ApiKeysError::DuplicateKey { .. })
+        ));
+        assert_eq!(api_keys.usage(now).len(), 1);
+    }
+}
@@ -1,11 +1,24 @@
+use std::{path::PathBuf, time::Duration};
+
 use datasize::DataSize;
-use serde::{Deserialize, Serialize};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
+
+use crate::crypto::asymmetric_key::PublicKey;
 
 /// Default binding address for the JSON-RPC HTTP server.
 ///
 /// Uses a fixed port per node, but binds on any interface.
 const DEFAULT_ADDRESS: &str = "0.0.0.0:7777";
 
+/// Default time allowed for in-flight requests to complete when shutting down.
+const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Default number of most recent deploy receipts retained.
+const DEFAULT_DEPLOY_RECEIPT_CAPACITY: usize = 10_000;
+
+/// Default number of most recent idempotency keys of accepted deploys retained.
+const DEFAULT_IDEMPOTENCY_KEY_CAPACITY: usize = 10_000;
+
 /// JSON-RPC HTTP server configuration.
 #[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
 // Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
@@ -13,6 +26,70 @@ const DEFAULT_ADDRESS: &str = "0.0.0.0:7777";
 pub struct Config {
     /// Address to bind JSON-RPC HTTP server to.
     pub address: String,
+    /// Token which must be provided as a bearer token in the "Authorization" header of requests
+    /// for the node operator admin RPCs.  If `None`, all requests for the admin RPCs are
+    /// rejected.  An empty or whitespace-only token is refused when the config is loaded.
+    #[serde(default, deserialize_with = "deserialize_admin_api_token")]
+    pub admin_api_token: Option<String>,
+    /// Time in milliseconds allowed for in-flight requests to complete when shutting down, after
+    /// which any remaining connections are dropped.
+    #[serde(
+        default = "default_shutdown_timeout",
+        with = "crate::utils::milliseconds"
+    )]
+    pub shutdown_timeout: Duration,
+    /// The number of most recent blocks whose global state can be queried via "state_get_item".
+    /// Queries against the state root hash of an older block are rejected.  If `None`, the
+    /// global state of all blocks can be queried.
+    #[serde(default)]
+    pub historical_state_window: Option<u64>,
+    /// Path to a file listing the API keys, one of which every request must carry in its
+    /// "X-API-Key" header, along with their quotas.  Relative paths are resolved from the config
+    /// file's directory.  If `None`, requests need no API key.
+    #[serde(default)]
+    pub api_keys_file: Option<PathBuf>,
+    /// The accounts whose main purse balances are not considered to be circulating, e.g. those
+    /// holding locked or foundation-administered tokens.  They are subtracted from the total
+    /// supply to give the circulating supply reported by "info_get_supply".
+    #[serde(default)]
+    pub circulating_supply_excluded_accounts: Vec<PublicKey>,
+    /// The number of most recent receipts for deploys accepted via "account_put_deploy" which are
+    /// retained, so they can be retrieved via "info_get_deploy_receipt".  The oldest receipt is
+    /// dropped once this is exceeded.
+    #[serde(default = "default_deploy_receipt_capacity")]
+    pub deploy_receipt_capacity: usize,
+    /// The number of most recent idempotency keys of deploys accepted via "account_put_deploy"
+    /// which are retained.  Resubmitting a deploy with a retained key returns the original
+    /// acceptance.  The oldest key is dropped once this is exceeded, and keys are ignored if this
+    /// is 0.
+    #[serde(default = "default_idempotency_key_capacity")]
+    pub idempotency_key_capacity: usize,
+}
+
+fn deserialize_admin_api_token<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    let maybe_token = Option::<String>::deserialize(deserializer)?;
+    if let Some(token) = maybe_token.as_ref() {
+        if token.trim().is_empty() {
+            return Err(D::Error::custom(
+                "admin_api_token must not be empty or whitespace",
+            ));
+        }
+    }
+    Ok(maybe_token)
+}
+
+fn default_deploy_receipt_capacity() -> usize {
+    DEFAULT_DEPLOY_RECEIPT_CAPACITY
+}
+
+fn default_shutdown_timeout() -> Duration {
+    DEFAULT_SHUTDOWN_TIMEOUT
+}
+
+fn default_idempotency_key_capacity() -> usize {
+    DEFAULT_IDEMPOTENCY_KEY_CAPACITY
 }
 
 impl Config {
@@ -20,6 +97,13 @@ impl Config {
     pub fn new() -> Self {
         Config {
             address: DEFAULT_ADDRESS.to_string(),
+            admin_api_token: None,
+            shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+            historical_state_window: None,
+            api_keys_file: None,
+            circulating_supply_excluded_accounts: vec![],
+            deploy_receipt_capacity: DEFAULT_DEPLOY_RECEIPT_CAPACITY,
+            idempotency_key_capacity: DEFAULT_IDEMPOTENCY_KEY_CAPACITY,
         }
     }
 }
@@ -29,3 +113,20 @@ impl Default for Config {
         Config::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_reject_blank_admin_api_token() {
+        let config =
+            |token: &str| format!("address = '0.0.0.0:7777'\nadmin_api_token = '{}'", token);
+
+        let parsed: Config = toml::from_str(&config("secret")).expect("should parse config");
+        assert_eq!(parsed.admin_api_token.as_deref(), Some("secret"));
+
+        assert!(toml::from_str::<Config>(&config("")).is_err());
+        assert!(toml::from_str::<Config>(&config(" \t ")).is_err());
+    }
+}
@@ -10,12 +10,19 @@ use casper_execution_engine::{
     core::engine_state::{self, BalanceResult, GetEraValidatorsError, QueryResult},
     storage::protocol_data::ProtocolData,
 };
-use casper_types::auction::EraValidators;
+use casper_types::{account::AccountHash, auction::EraValidators, Key};
 
 use crate::{
+    components::{
+        consensus::EraId,
+        deploy_acceptor::{DeployAcceptance, SubmitDeployError},
+    },
     effect::{requests::RpcRequest, Responder},
     rpcs::chain::BlockIdentifier,
-    types::{Block, Deploy, DeployHash, DeployMetadata, NodeId},
+    types::{
+        Block, Deploy, DeployHash, DeployMetadata, DeployReceipt, KeyHistoryEntry, NodeId,
+        TransferEntry,
+    },
 };
 
 #[derive(Debug, From)]
@@ -27,6 +34,16 @@ pub enum Event {
         result: Box<Option<Block>>,
         main_responder: Responder<Option<Block>>,
     },
+    GetSwitchBlockResult {
+        maybe_era_id: Option<EraId>,
+        result: Box<Option<Block>>,
+        main_responder: Responder<Option<Block>>,
+    },
+    GetBlocksResult {
+        start_height: u64,
+        result: Vec<Block>,
+        main_responder: Responder<Vec<Block>>,
+    },
     QueryProtocolDataResult {
         result: Result<Option<Box<ProtocolData>>, engine_state::Error>,
         main_responder: Responder<Result<Option<Box<ProtocolData>>, engine_state::Error>>,
@@ -44,6 +61,21 @@ pub enum Event {
         result: Box<Option<(Deploy, DeployMetadata)>>,
         main_responder: Responder<Option<(Deploy, DeployMetadata)>>,
     },
+    GetKeyHistoryResult {
+        key: Key,
+        result: (u64, Vec<KeyHistoryEntry>),
+        main_responder: Responder<(u64, Vec<KeyHistoryEntry>)>,
+    },
+    GetAccountDeploysResult {
+        account_hash: AccountHash,
+        result: (u64, Vec<DeployHash>),
+        main_responder: Responder<(u64, Vec<DeployHash>)>,
+    },
+    GetTransfersToResult {
+        target: Key,
+        result: (Vec<TransferEntry>, Option<u64>),
+        main_responder: Responder<(Vec<TransferEntry>, Option<u64>)>,
+    },
     GetPeersResult {
         peers: HashMap<NodeId, SocketAddr>,
         main_responder: Responder<HashMap<NodeId, SocketAddr>>,
@@ -56,6 +88,22 @@ pub enum Event {
         result: Result<BalanceResult, engine_state::Error>,
         main_responder: Responder<Result<BalanceResult, engine_state::Error>>,
     },
+    SignDeployReceiptResult {
+        deploy_hash: DeployHash,
+        result: Option<Box<DeployReceipt>>,
+        main_responder: Responder<Option<DeployReceipt>>,
+    },
+    SubmitDeployResult {
+        deploy_hash: DeployHash,
+        idempotency_key: Option<String>,
+        result: Result<(), SubmitDeployError>,
+        main_responder: Responder<Result<DeployAcceptance, SubmitDeployError>>,
+    },
+    /// Deploys submitted by clients should be refused until resumed, e.g. due to low free disk
+    /// space.
+    PauseDeploys,
+    /// Deploys submitted by clients should be accepted again.
+    ResumeDeploys,
 }
 
 impl Display for Event {
@@ -72,11 +120,44 @@ impl Display for Event {
                 result,
                 ..
             } => write!(formatter, "get block result for {}: {:?}", height, result),
+            Event::GetBlockResult {
+                maybe_id: Some(block_identifier),
+                result,
+                ..
+            } => write!(
+                formatter,
+                "get block result for {}: {:?}",
+                block_identifier, result
+            ),
             Event::GetBlockResult {
                 maybe_id: None,
                 result,
                 ..
             } => write!(formatter, "get latest block result: {:?}", result),
+            Event::GetSwitchBlockResult {
+                maybe_era_id: Some(era_id),
+                result,
+                ..
+            } => write!(
+                formatter,
+                "get switch block result for {}: {:?}",
+                era_id, result
+            ),
+            Event::GetSwitchBlockResult {
+                maybe_era_id: None,
+                result,
+                ..
+            } => write!(formatter, "get latest switch block result: {:?}", result),
+            Event::GetBlocksResult {
+                start_height,
+                result,
+                ..
+            } => write!(
+                formatter,
+                "get blocks result from height {}: {} blocks",
+                start_height,
+                result.len()
+            ),
             Event::QueryProtocolDataResult { result, .. } => {
                 write!(formatter, "query protocol data result: {:?}", result)
             }
@@ -92,11 +173,56 @@ impl Display for Event {
             Event::GetDeployResult { hash, result, .. } => {
                 write!(formatter, "get deploy result for {}: {:?}", hash, result)
             }
+            Event::GetKeyHistoryResult { key, result, .. } => write!(
+                formatter,
+                "get key history result for {}: {} of {} entries",
+                key.to_formatted_string(),
+                result.1.len(),
+                result.0
+            ),
+            Event::GetAccountDeploysResult {
+                account_hash,
+                result,
+                ..
+            } => write!(
+                formatter,
+                "get account deploys result for {}: {} of {} deploys",
+                account_hash,
+                result.1.len(),
+                result.0
+            ),
+            Event::GetTransfersToResult { target, result, .. } => write!(
+                formatter,
+                "get transfers to result for {}: {} transfers",
+                target.to_formatted_string(),
+                result.0.len()
+            ),
             Event::GetPeersResult { peers, .. } => write!(formatter, "get peers: {}", peers.len()),
             Event::GetMetricsResult { text, .. } => match text {
                 Some(txt) => write!(formatter, "get metrics ({} bytes)", txt.len()),
                 None => write!(formatter, "get metrics (failed)"),
             },
+            Event::SignDeployReceiptResult {
+                deploy_hash,
+                result,
+                ..
+            } => write!(
+                formatter,
+                "sign receipt result for {}: {}",
+                deploy_hash,
+                if result.is_some() { "signed" } else { "failed" }
+            ),
+            Event::SubmitDeployResult {
+                deploy_hash,
+                result,
+                ..
+            } => write!(
+                formatter,
+                "submit deploy result for {}: {:?}",
+                deploy_hash, result
+            ),
+            Event::PauseDeploys => write!(formatter, "pause deploys"),
+            Event::ResumeDeploys => write!(formatter, "resume deploys"),
         }
     }
 }
@@ -1,40 +1,137 @@
-use std::convert::Infallible;
+use std::{convert::Infallible, sync::Arc};
 
-use futures::future::{self};
-use hyper::Server;
-use tokio::sync::oneshot;
+use futures::{
+    future::{self, Either},
+    FutureExt,
+};
+use http::{Method, Request};
+use hyper::{service::Service, Body, Server};
+use tokio::{select, sync::oneshot};
 use tracing::{debug, info, trace, warn};
 use warp::Filter;
 
 use super::{
-    rpcs::{self, RpcWithOptionalParamsExt, RpcWithParamsExt, RpcWithoutParamsExt},
+    api_keys::{ApiKeys, API_KEY_HEADER},
+    rpcs::{
+        self,
+        admin::{self, AdminApiKeysRpcExt, AdminRpcExt},
+        RpcWithOptionalParamsExt, RpcWithParamsExt, RpcWithoutParamsExt,
+    },
     Config, ReactorEventT,
 };
-use crate::{effect::EffectBuilder, utils};
+use crate::{effect::EffectBuilder, types::Timestamp, utils};
 
 /// Run the JSON-RPC server.
-pub(super) async fn run<REv: ReactorEventT>(config: Config, effect_builder: EffectBuilder<REv>) {
+///
+/// A message received on `shutdown_receiver` will cause the server to stop accepting new
+/// connections and exit once the in-flight requests have completed, or the shutdown timeout has
+/// elapsed.
+pub(super) async fn run<REv: ReactorEventT>(
+    config: Config,
+    effect_builder: EffectBuilder<REv>,
+    shutdown_receiver: oneshot::Receiver<()>,
+) {
+    // Refuse to start without the API keys if they are configured, rather than serve requests
+    // without quotas.
+    let maybe_api_keys = match config.api_keys_file.clone() {
+        Some(path) => match ApiKeys::load(path) {
+            Ok(api_keys) => Some(Arc::new(api_keys)),
+            Err(error) => {
+                warn!(%error, "failed to start JSON-RPC server");
+                return;
+            }
+        },
+        None => None,
+    };
+
     // RPC filters.
     let rpc_put_deploy = rpcs::account::PutDeploy::create_filter(effect_builder);
+    let rpc_check_deploy = rpcs::account::CheckDeploy::create_filter(effect_builder);
+    let rpc_get_account_deploys = rpcs::account::GetAccountDeploys::create_filter(effect_builder);
     let rpc_get_block = rpcs::chain::GetBlock::create_filter(effect_builder);
+    let rpc_get_blocks = rpcs::chain::GetBlocks::create_filter(effect_builder);
+    let rpc_get_alternative_blocks =
+        rpcs::chain::GetAlternativeBlocks::create_filter(effect_builder);
+    let rpc_get_block_by_deploy = rpcs::chain::GetBlockByDeploy::create_filter(effect_builder);
+    let rpc_get_block_at_timestamp =
+        rpcs::chain::GetBlockAtTimestamp::create_filter(effect_builder);
+    let rpc_get_era_summary = rpcs::chain::GetEraSummary::create_filter(effect_builder);
     let rpc_get_state_root_hash = rpcs::chain::GetStateRootHash::create_filter(effect_builder);
     let rpc_get_item = rpcs::state::GetItem::create_filter(effect_builder);
+    let rpc_get_items = rpcs::state::GetItems::create_filter(effect_builder);
     let rpc_get_balance = rpcs::state::GetBalance::create_filter(effect_builder);
+    let rpc_get_balance_history = rpcs::state::GetBalanceHistory::create_filter(effect_builder);
+    let rpc_get_account_info = rpcs::state::GetAccountInfo::create_filter(effect_builder);
+    let rpc_get_key_history = rpcs::state::GetKeyHistory::create_filter(effect_builder);
+    let rpc_get_keys_with_prefix = rpcs::state::GetKeysWithPrefix::create_filter(effect_builder);
+    let rpc_get_contract_abi = rpcs::state::GetContractAbi::create_filter(effect_builder);
+    let rpc_get_transfers_to = rpcs::state::GetTransfersTo::create_filter(effect_builder);
     let rpc_get_deploy = rpcs::info::GetDeploy::create_filter(effect_builder);
+    let rpc_get_deploy_receipt = rpcs::info::GetDeployReceipt::create_filter(effect_builder);
     let rpc_get_peers = rpcs::info::GetPeers::create_filter(effect_builder);
     let rpc_get_status = rpcs::info::GetStatus::create_filter(effect_builder);
+    let rpc_get_signed_status = rpcs::info::GetSignedStatus::create_filter(effect_builder);
+    let rpc_get_chainspec = rpcs::info::GetChainspec::create_filter(effect_builder);
+    let rpc_estimate_deploy_cost = rpcs::info::EstimateDeployCost::create_filter(effect_builder);
+    let rpc_get_validator_rewards = rpcs::info::GetValidatorRewards::create_filter(effect_builder);
+    let rpc_get_supply = rpcs::info::GetSupply::create_filter(effect_builder);
     let rpc_get_auction_info = rpcs::state::GetAuctionInfo::create_filter(effect_builder);
+    let rpc_get_delegations = rpcs::state::GetDelegations::create_filter(effect_builder);
+    let rpc_get_unbonding_purses = rpcs::state::GetUnbondingPurses::create_filter(effect_builder);
+
+    // Admin RPC filters.
+    let admin_api_token = config.admin_api_token.clone();
+    let rpc_admin_connect_peer =
+        rpcs::admin::ConnectPeer::create_filter(effect_builder, admin_api_token.clone());
+    let rpc_admin_disconnect_peer =
+        rpcs::admin::DisconnectPeer::create_filter(effect_builder, admin_api_token.clone());
+    let rpc_admin_ban_peer =
+        rpcs::admin::BanPeer::create_filter(effect_builder, admin_api_token.clone());
+    let rpc_admin_set_network_timeouts =
+        rpcs::admin::SetNetworkTimeouts::create_filter(effect_builder, admin_api_token.clone());
+    let rpc_admin_get_api_key_usage =
+        rpcs::admin::GetApiKeyUsage::create_filter(maybe_api_keys.clone(), admin_api_token.clone());
+    let rpc_admin_reload_api_keys =
+        rpcs::admin::ReloadApiKeys::create_filter(maybe_api_keys.clone(), admin_api_token.clone());
 
     let service = warp_json_rpc::service(
         rpc_put_deploy
+            .or(rpc_check_deploy)
+            .or(rpc_get_account_deploys)
             .or(rpc_get_block)
+            .or(rpc_get_blocks)
+            .or(rpc_get_alternative_blocks)
+            .or(rpc_get_block_by_deploy)
+            .or(rpc_get_block_at_timestamp)
+            .or(rpc_get_era_summary)
             .or(rpc_get_state_root_hash)
             .or(rpc_get_item)
+            .or(rpc_get_items)
             .or(rpc_get_balance)
+            .or(rpc_get_balance_history)
+            .or(rpc_get_account_info)
+            .or(rpc_get_key_history)
+            .or(rpc_get_keys_with_prefix)
+            .or(rpc_get_contract_abi)
+            .or(rpc_get_transfers_to)
             .or(rpc_get_deploy)
+            .or(rpc_get_deploy_receipt)
+            .or(rpc_estimate_deploy_cost)
             .or(rpc_get_peers)
             .or(rpc_get_status)
-            .or(rpc_get_auction_info),
+            .or(rpc_get_signed_status)
+            .or(rpc_get_chainspec)
+            .or(rpc_get_validator_rewards)
+            .or(rpc_get_supply)
+            .or(rpc_get_auction_info)
+            .or(rpc_get_delegations)
+            .or(rpc_get_unbonding_purses)
+            .or(rpc_admin_connect_peer)
+            .or(rpc_admin_disconnect_peer)
+            .or(rpc_admin_ban_peer)
+            .or(rpc_admin_set_network_timeouts)
+            .or(rpc_admin_get_api_key_usage)
+            .or(rpc_admin_reload_api_keys),
     );
 
     let mut server_address = match utils::resolve_address(&config.address) {
@@ -64,24 +161,77 @@ pub(super) async fn run<REv: ReactorEventT>(config: Config, effect_builder: Effe
         }
     };
 
-    // Start the server, passing a oneshot receiver to allow the server to be shut down gracefully.
-    let make_svc =
-        hyper::service::make_service_fn(move |_| future::ok::<_, Infallible>(service.clone()));
-    let (shutdown_sender, shutdown_receiver) = oneshot::channel::<()>();
+    // Start the server.  GET requests for the OpenRPC document and its per-type schemas are served
+    // directly, while all other requests are handled as JSON-RPCs, once admitted under their API
+    // key if keys are configured.
+    let make_svc = hyper::service::make_service_fn(move |_| {
+        let mut service = service.clone();
+        let maybe_api_keys = maybe_api_keys.clone();
+        let admin_api_token = admin_api_token.clone();
+        future::ok::<_, Infallible>(hyper::service::service_fn(move |request: Request<Body>| {
+            if request.method() == Method::GET && request.uri().path() == rpcs::docs::SCHEMA_PATH {
+                return Either::Left(future::ready(Ok(rpcs::docs::schema_response())));
+            }
+            if request.method() == Method::GET {
+                if let Some(type_name) = request
+                    .uri()
+                    .path()
+                    .strip_prefix(rpcs::docs::TYPE_SCHEMA_PATH_PREFIX)
+                {
+                    return Either::Left(future::ready(Ok(rpcs::docs::type_schema_response(
+                        type_name,
+                    ))));
+                }
+            }
 
-    let server = builder.serve(make_svc);
-    info!(address = %server.local_addr(), "started JSON-RPC server");
+            let header = |name| {
+                request
+                    .headers()
+                    .get(name)
+                    .and_then(|value| value.to_str().ok())
+            };
+            let maybe_permit = match &maybe_api_keys {
+                Some(api_keys)
+                    if !admin::is_authorized(
+                        admin_api_token.as_deref(),
+                        header(admin::AUTHORIZATION_HEADER),
+                    ) =>
+                {
+                    match api_keys.admit(header(API_KEY_HEADER), Timestamp::now()) {
+                        Ok(permit) => Some(permit),
+                        Err(rejection) => {
+                            debug!(%rejection, "refused JSON-RPC request");
+                            return Either::Left(future::ready(Ok(rejection.into_response())));
+                        }
+                    }
+                }
+                _ => None,
+            };
 
-    let server_with_shutdown = server.with_graceful_shutdown(async {
-        shutdown_receiver.await.ok();
+            // Count the request as in flight until its response is ready.
+            Either::Right(service.call(request).map(move |result| {
+                drop(maybe_permit);
+                result
+            }))
+        }))
     });
 
-    let server_joiner = tokio::spawn(server_with_shutdown);
-
-    let _ = server_joiner.await;
+    let server = builder.serve(make_svc);
+    info!(address = %server.local_addr(), "started JSON-RPC server");
 
-    // Shut down the server.
-    let _ = shutdown_sender.send(());
+    // Shut down the server gracefully, dropping any connections still open after the timeout.
+    let (shutdown_signal, drain_deadline) =
+        utils::graceful_shutdown_with_deadline(shutdown_receiver, config.shutdown_timeout);
+    select! {
+        result = server.with_graceful_shutdown(shutdown_signal) => {
+            if let Err(error) = result {
+                warn!(%error, "error running JSON-RPC server");
+            }
+        }
+        _ = drain_deadline => {
+            warn!("JSON-RPC requests still in flight after shutdown timeout, dropping connections");
+        }
+    }
 
     trace!("JSON-RPC server stopped");
 }
@@ -3,7 +3,10 @@
 //! See https://github.com/CasperLabs/ceps/blob/master/text/0009-client-api.md#rpcs for info.
 
 pub mod account;
+pub mod admin;
 pub mod chain;
+mod compat;
+pub mod docs;
 pub mod info;
 pub mod state;
 
@@ -12,6 +15,7 @@ use std::str;
 use futures::{future::BoxFuture, TryFutureExt};
 use http::Response;
 use hyper::Body;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use warp::{
     filters::BoxedFilter,
@@ -22,6 +26,7 @@ use warp_json_rpc::{filters, Builder};
 
 use super::{ReactorEventT, RpcRequest};
 use crate::effect::EffectBuilder;
+use compat::{ApiVersion, V1};
 
 /// The URL path.
 pub const RPC_API_PATH: &str = "rpc";
@@ -39,10 +44,32 @@ enum ErrorCode {
     ParseGetBalanceURef = 32005,
     GetBalanceFailed = 32006,
     GetBalanceFailedToExecute = 32007,
+    InvalidBlockRange = 32008,
+    ParseAccountIdentifier = 32009,
+    NoSuchAccount = 32010,
+    NoSuchEraSummary = 32011,
+    InvalidDeploy = 32012,
+    EstimateDeployCostFailed = 32013,
+    Unauthorized = 32014,
+    InvalidAdminCommand = 32015,
+    NodeAtCapacity = 32016,
+    ParseKeyPrefix = 32017,
+    NoSuchContract = 32018,
+    DeployExpiresTooSoon = 32019,
+    BidNearEraEnd = 32020,
+    StateRootOutsideWindow = 32021,
+    QueryBatchTooLarge = 32022,
+    InvalidWasm = 32023,
+    InvalidNonce = 32024,
+    NodePaused = 32025,
+    NoSuchDeployReceipt = 32026,
+    NodeIsSidecar = 32027,
+    IdempotencyKeyReused = 32028,
+    InvalidIdempotencyKey = 32029,
 }
 
 #[derive(Debug)]
-pub(super) struct Error(String);
+pub(crate) struct Error(String);
 
 impl Reject for Error {}
 
@@ -58,17 +85,32 @@ pub trait RpcWithParams {
     const METHOD: &'static str;
 
     /// The JSON-RPC request's "params" type.
-    type RequestParams: Serialize + for<'de> Deserialize<'de> + Send + 'static;
+    type RequestParams: Serialize + for<'de> Deserialize<'de> + JsonSchema + Send + 'static;
 
     /// The JSON-RPC response's "result" type.
-    type ResponseResult: Serialize + for<'de> Deserialize<'de> + Send + 'static;
+    type ResponseResult: Serialize + for<'de> Deserialize<'de> + JsonSchema + Send + 'static;
 }
 
 /// A trait for creating a JSON-RPC filter where the request is required to have "params".
 pub(super) trait RpcWithParamsExt: RpcWithParams {
-    /// Creates the warp filter for this particular RPC.
+    /// Creates the warp filter for this particular RPC, covering the latest and all older versions
+    /// which included it.
     fn create_filter<REv: ReactorEventT>(
         effect_builder: EffectBuilder<REv>,
+    ) -> BoxedFilter<(Response<Body>,)> {
+        let latest = Self::create_latest_filter(effect_builder);
+        if !compat::is_served::<V1>(Self::METHOD) {
+            return latest;
+        }
+        latest
+            .or(Self::create_versioned_filter::<REv, V1>(effect_builder))
+            .unify()
+            .boxed()
+    }
+
+    /// Creates the warp filter for the latest version of this particular RPC.
+    fn create_latest_filter<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
     ) -> BoxedFilter<(Response<Body>,)> {
         warp::path(RPC_API_PATH)
             .and(filters::json_rpc())
@@ -83,6 +125,26 @@ pub(super) trait RpcWithParamsExt: RpcWithParams {
             .boxed()
     }
 
+    /// Creates the warp filter for version `V` of this particular RPC.
+    fn create_versioned_filter<REv: ReactorEventT, V: ApiVersion>(
+        effect_builder: EffectBuilder<REv>,
+    ) -> BoxedFilter<(Response<Body>,)> {
+        warp::path(RPC_API_PATH)
+            .and(filters::json_rpc())
+            .and(filters::method(compat::method_name::<V>(Self::METHOD)))
+            .and(filters::params::<Self::RequestParams>())
+            .and_then(
+                move |response_builder: Builder, params: Self::RequestParams| {
+                    Self::handle_request(effect_builder, response_builder, params)
+                        .and_then(|response| {
+                            compat::translate_response::<V>(Self::METHOD, response)
+                        })
+                        .map_err(reject::custom)
+                },
+            )
+            .boxed()
+    }
+
     /// Handles the incoming RPC request.
     fn handle_request<REv: ReactorEventT>(
         effect_builder: EffectBuilder<REv>,
@@ -97,14 +159,29 @@ pub trait RpcWithoutParams {
     const METHOD: &'static str;
 
     /// The JSON-RPC response's "result" type.
-    type ResponseResult: Serialize + for<'de> Deserialize<'de> + Send + 'static;
+    type ResponseResult: Serialize + for<'de> Deserialize<'de> + JsonSchema + Send + 'static;
 }
 
 /// A trait for creating a JSON-RPC filter where the request is not required to have "params".
 pub(super) trait RpcWithoutParamsExt: RpcWithoutParams {
-    /// Creates the warp filter for this particular RPC.
+    /// Creates the warp filter for this particular RPC, covering the latest and all older versions
+    /// which included it.
     fn create_filter<REv: ReactorEventT>(
         effect_builder: EffectBuilder<REv>,
+    ) -> BoxedFilter<(Response<Body>,)> {
+        let latest = Self::create_latest_filter(effect_builder);
+        if !compat::is_served::<V1>(Self::METHOD) {
+            return latest;
+        }
+        latest
+            .or(Self::create_versioned_filter::<REv, V1>(effect_builder))
+            .unify()
+            .boxed()
+    }
+
+    /// Creates the warp filter for the latest version of this particular RPC.
+    fn create_latest_filter<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
     ) -> BoxedFilter<(Response<Body>,)> {
         warp::path(RPC_API_PATH)
             .and(filters::json_rpc())
@@ -115,6 +192,21 @@ pub(super) trait RpcWithoutParamsExt: RpcWithoutParams {
             .boxed()
     }
 
+    /// Creates the warp filter for version `V` of this particular RPC.
+    fn create_versioned_filter<REv: ReactorEventT, V: ApiVersion>(
+        effect_builder: EffectBuilder<REv>,
+    ) -> BoxedFilter<(Response<Body>,)> {
+        warp::path(RPC_API_PATH)
+            .and(filters::json_rpc())
+            .and(filters::method(compat::method_name::<V>(Self::METHOD)))
+            .and_then(move |response_builder: Builder| {
+                Self::handle_request(effect_builder, response_builder)
+                    .and_then(|response| compat::translate_response::<V>(Self::METHOD, response))
+                    .map_err(reject::custom)
+            })
+            .boxed()
+    }
+
     /// Handles the incoming RPC request.
     fn handle_request<REv: ReactorEventT>(
         effect_builder: EffectBuilder<REv>,
@@ -129,17 +221,32 @@ pub trait RpcWithOptionalParams {
 
     /// The JSON-RPC request's "params" type.  This will be passed to the handler wrapped in an
     /// `Option`.
-    type OptionalRequestParams: Serialize + for<'de> Deserialize<'de> + Send + 'static;
+    type OptionalRequestParams: Serialize + for<'de> Deserialize<'de> + JsonSchema + Send + 'static;
 
     /// The JSON-RPC response's "result" type.
-    type ResponseResult: Serialize + for<'de> Deserialize<'de> + Send + 'static;
+    type ResponseResult: Serialize + for<'de> Deserialize<'de> + JsonSchema + Send + 'static;
 }
 
 /// A trait for creating a JSON-RPC filter where the request may optionally have "params".
 pub(super) trait RpcWithOptionalParamsExt: RpcWithOptionalParams {
-    /// Creates the warp filter for this particular RPC.
+    /// Creates the warp filter for this particular RPC, covering the latest and all older versions
+    /// which included it.
     fn create_filter<REv: ReactorEventT>(
         effect_builder: EffectBuilder<REv>,
+    ) -> BoxedFilter<(Response<Body>,)> {
+        let latest = Self::create_latest_filter(effect_builder);
+        if !compat::is_served::<V1>(Self::METHOD) {
+            return latest;
+        }
+        latest
+            .or(Self::create_versioned_filter::<REv, V1>(effect_builder))
+            .unify()
+            .boxed()
+    }
+
+    /// Creates the warp filter for the latest version of this particular RPC.
+    fn create_latest_filter<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
     ) -> BoxedFilter<(Response<Body>,)> {
         let with_params = warp::path(RPC_API_PATH)
             .and(filters::json_rpc())
@@ -160,6 +267,35 @@ pub(super) trait RpcWithOptionalParamsExt: RpcWithOptionalParams {
         with_params.or(without_params).unify().boxed()
     }
 
+    /// Creates the warp filter for version `V` of this particular RPC.
+    fn create_versioned_filter<REv: ReactorEventT, V: ApiVersion>(
+        effect_builder: EffectBuilder<REv>,
+    ) -> BoxedFilter<(Response<Body>,)> {
+        let method = compat::method_name::<V>(Self::METHOD);
+        let with_params = warp::path(RPC_API_PATH)
+            .and(filters::json_rpc())
+            .and(filters::method(method))
+            .and(filters::params::<Self::OptionalRequestParams>())
+            .and_then(
+                move |response_builder: Builder, params: Self::OptionalRequestParams| {
+                    Self::handle_request(effect_builder, response_builder, Some(params))
+                        .and_then(|response| {
+                            compat::translate_response::<V>(Self::METHOD, response)
+                        })
+                        .map_err(reject::custom)
+                },
+            );
+        let without_params = warp::path(RPC_API_PATH)
+            .and(filters::json_rpc())
+            .and(filters::method(method))
+            .and_then(move |response_builder: Builder| {
+                Self::handle_request(effect_builder, response_builder, None)
+                    .and_then(|response| compat::translate_response::<V>(Self::METHOD, response))
+                    .map_err(reject::custom)
+            });
+        with_params.or(without_params).unify().boxed()
+    }
+
     /// Handles the incoming RPC request.
     fn handle_request<REv: ReactorEventT>(
         effect_builder: EffectBuilder<REv>,
@@ -1,36 +1,109 @@
 //! RPCs related to accounts.
 
-use std::str;
+use std::{ops::Not, str};
 
 use futures::{future::BoxFuture, FutureExt};
 use http::Response;
 use hyper::Body;
+use schemars::JsonSchema;
 use semver::Version;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::info;
 use warp_json_rpc::Builder;
 
-use super::{Error, ReactorEventT, RpcRequest, RpcWithParams, RpcWithParamsExt};
+use super::{state, Error, ErrorCode, ReactorEventT, RpcRequest, RpcWithParams, RpcWithParamsExt};
 use crate::{
-    components::CLIENT_API_VERSION,
+    components::{
+        deploy_acceptor::{DeployAcceptance, DeployViolation, EraGateError, SubmitDeployError},
+        CLIENT_API_VERSION,
+    },
     effect::EffectBuilder,
     reactor::QueueKind,
-    types::{Deploy, DeployHash},
+    types::{Deploy, DeployHash, DeployReceipt},
 };
 
+/// Error code returned by "account_put_deploy" if the node is at capacity and can't currently
+/// accept any more deploys.  The error's `data` holds the advised retry interval as
+/// `NodeAtCapacityData`, which can be extracted via `retry_after_secs()`.
+pub const NODE_AT_CAPACITY_ERROR_CODE: i64 = ErrorCode::NodeAtCapacity as i64;
+
+/// Error code returned by "account_put_deploy" if the deploy expires before it could be executed.
+pub const DEPLOY_EXPIRES_TOO_SOON_ERROR_CODE: i64 = ErrorCode::DeployExpiresTooSoon as i64;
+
+/// Error code returned by "account_put_deploy" if the deploy calls an auction entry point taking
+/// effect at era boundaries too close to the expected end of the current era.  The deploy should
+/// be resubmitted after the era transition.
+pub const BID_NEAR_ERA_END_ERROR_CODE: i64 = ErrorCode::BidNearEraEnd as i64;
+
+/// Error code returned by "account_put_deploy" if the deploy could never be executed successfully
+/// due to its Wasm module bytes, or could never be included in a block due to its size.
+pub const INVALID_WASM_ERROR_CODE: i64 = ErrorCode::InvalidWasm as i64;
+
+/// Error code returned by "account_put_deploy" if the node has paused accepting deploys due to low
+/// free disk space.  The deploy should be resubmitted later or to another node.
+pub const NODE_PAUSED_ERROR_CODE: i64 = ErrorCode::NodePaused as i64;
+
+/// Error code returned by "account_put_deploy" if the node runs in sidecar mode, serving queries
+/// only.  The deploy should be submitted to a node participating in the network.
+pub const NODE_IS_SIDECAR_ERROR_CODE: i64 = ErrorCode::NodeIsSidecar as i64;
+
+/// Error code returned by "account_put_deploy" if the idempotency key was already used to submit a
+/// different deploy.
+pub const IDEMPOTENCY_KEY_REUSED_ERROR_CODE: i64 = ErrorCode::IdempotencyKeyReused as i64;
+
+/// Error code returned by "account_put_deploy" if the idempotency key is empty or longer than
+/// `MAX_IDEMPOTENCY_KEY_LENGTH` bytes.
+pub const INVALID_IDEMPOTENCY_KEY_ERROR_CODE: i64 = ErrorCode::InvalidIdempotencyKey as i64;
+
+/// The maximum length in bytes of an idempotency key.
+pub const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 128;
+
+/// The `data` of a "node at capacity" error returned by "account_put_deploy".
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+pub struct NodeAtCapacityData {
+    /// The advised number of seconds to wait before resubmitting the deploy.
+    pub retry_after_secs: u64,
+}
+
+/// Returns the advised retry interval in seconds held in the `data` of a "node at capacity" error
+/// returned by "account_put_deploy", or `None` if the data isn't of that form.
+pub fn retry_after_secs(maybe_error_data: Option<&Value>) -> Option<u64> {
+    NodeAtCapacityData::deserialize(maybe_error_data?)
+        .ok()
+        .map(|data| data.retry_after_secs)
+}
+
 /// Params for "account_put_deploy" RPC request.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub struct PutDeployParams {
     /// The `Deploy`.
     pub deploy: Deploy,
+    /// Whether to return a receipt signed by the node, attesting that it accepted the deploy.
+    #[serde(default)]
+    pub receipt: bool,
+    /// A client-generated key identifying this submission, e.g. a random UUID.  If the deploy was
+    /// already accepted with the same key, e.g. by a request whose response was lost to a timeout,
+    /// the original acceptance is returned rather than the deploy being submitted again.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
 }
 
 /// Result for "account_put_deploy" RPC response.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub struct PutDeployResult {
     /// The RPC API version.
+    #[schemars(with = "String")]
     pub api_version: Version,
     /// The deploy hash.
     pub deploy_hash: DeployHash,
+    /// The receipt signed by the node, if requested and the node could sign it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receipt: Option<DeployReceipt>,
+    /// Whether the deploy had already been accepted with the same idempotency key, in which case
+    /// this is the original acceptance and the deploy was not submitted again.
+    #[serde(default, skip_serializing_if = "Not::not")]
+    pub replayed: bool,
 }
 
 /// "account_put_deploy" RPC
@@ -50,25 +123,308 @@ impl RpcWithParamsExt for PutDeploy {
     ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
         async move {
             let deploy_hash = *params.deploy.id();
+            let want_receipt = params.receipt;
+
+            if let Some(idempotency_key) = params.idempotency_key.as_ref() {
+                if idempotency_key.is_empty() || idempotency_key.len() > MAX_IDEMPOTENCY_KEY_LENGTH
+                {
+                    let error_msg = format!(
+                        "idempotency key must be between 1 and {} bytes long",
+                        MAX_IDEMPOTENCY_KEY_LENGTH
+                    );
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        INVALID_IDEMPOTENCY_KEY_ERROR_CODE,
+                        error_msg,
+                    ))?);
+                }
+            }
 
             // Submit the new deploy to be announced.
-            effect_builder
+            let submit_result = effect_builder
                 .make_request(
                     |responder| RpcRequest::SubmitDeploy {
                         deploy: Box::new(params.deploy),
+                        idempotency_key: params.idempotency_key,
                         responder,
                     },
                     QueueKind::Api,
                 )
                 .await;
 
+            let acceptance = match submit_result {
+                Ok(acceptance) => acceptance,
+                Err(error) => {
+                    info!("refused {}: {}", deploy_hash, error);
+                    let (error_code, error_msg) = match error {
+                        SubmitDeployError::BufferFull(buffer_full) => {
+                            let data = NodeAtCapacityData {
+                                retry_after_secs: buffer_full.retry_after.as_secs(),
+                            };
+                            let error_msg = format!(
+                                "node at capacity, retry after {} seconds",
+                                data.retry_after_secs
+                            );
+                            let json_error = warp_json_rpc::Error::custom(
+                                NODE_AT_CAPACITY_ERROR_CODE,
+                                error_msg,
+                            )
+                            .with_data(data);
+                            return Ok(response_builder.error(json_error)?);
+                        }
+                        SubmitDeployError::EraGate(error @ EraGateError::ExpiresTooSoon { .. }) => {
+                            (DEPLOY_EXPIRES_TOO_SOON_ERROR_CODE, error.to_string())
+                        }
+                        SubmitDeployError::EraGate(error @ EraGateError::BidNearEraEnd { .. }) => {
+                            (BID_NEAR_ERA_END_ERROR_CODE, error.to_string())
+                        }
+                        SubmitDeployError::Wasm(error) => {
+                            (INVALID_WASM_ERROR_CODE, error.to_string())
+                        }
+                        SubmitDeployError::Paused => (
+                            NODE_PAUSED_ERROR_CODE,
+                            SubmitDeployError::Paused.to_string(),
+                        ),
+                        SubmitDeployError::Sidecar => (
+                            NODE_IS_SIDECAR_ERROR_CODE,
+                            SubmitDeployError::Sidecar.to_string(),
+                        ),
+                        error @ SubmitDeployError::IdempotencyKeyReused { .. } => {
+                            (IDEMPOTENCY_KEY_REUSED_ERROR_CODE, error.to_string())
+                        }
+                    };
+                    return Ok(response_builder
+                        .error(warp_json_rpc::Error::custom(error_code, error_msg))?);
+                }
+            };
+            let replayed = acceptance == DeployAcceptance::Replayed;
+            if replayed {
+                info!("replayed acceptance of {}", deploy_hash);
+            }
+
+            // Return the original receipt of a replayed acceptance if one was issued.
+            let mut receipt = None;
+            if want_receipt && replayed {
+                receipt = effect_builder
+                    .make_request(
+                        |responder| RpcRequest::GetDeployReceipt {
+                            deploy_hash,
+                            responder,
+                        },
+                        QueueKind::Api,
+                    )
+                    .await;
+            }
+
+            // Sign a receipt if requested.  Failing to sign doesn't fail the request, as the deploy
+            // has already been accepted.
+            if want_receipt && receipt.is_none() {
+                let maybe_receipt = effect_builder
+                    .make_request(
+                        |responder| RpcRequest::SignDeployReceipt {
+                            deploy_hash,
+                            responder,
+                        },
+                        QueueKind::Api,
+                    )
+                    .await;
+                if maybe_receipt.is_none() {
+                    info!("failed to sign receipt for {}", deploy_hash);
+                }
+                receipt = maybe_receipt;
+            }
+
             // Return the result.
             let result = Self::ResponseResult {
                 api_version: CLIENT_API_VERSION.clone(),
                 deploy_hash,
+                receipt,
+                replayed,
             };
             Ok(response_builder.success(result)?)
         }
         .boxed()
     }
 }
+
+/// Params for "account_check_deploy" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct CheckDeployParams {
+    /// The `Deploy`.
+    pub deploy: Deploy,
+}
+
+/// A reason for which the node would refuse a deploy.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+pub struct Violation {
+    /// The check failed: one of "chain_name", "dependencies", "ttl", "payment", "signatures",
+    /// "size", "wasm" or "era_gate".
+    pub kind: String,
+    /// A description of the violation.
+    pub message: String,
+}
+
+impl From<DeployViolation> for Violation {
+    fn from(violation: DeployViolation) -> Self {
+        Violation {
+            kind: violation.kind().to_string(),
+            message: violation.to_string(),
+        }
+    }
+}
+
+/// Result for "account_check_deploy" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct CheckDeployResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: Version,
+    /// The deploy hash.
+    pub deploy_hash: DeployHash,
+    /// Every reason for which the deploy would currently be refused by "account_put_deploy".  The
+    /// deploy would be accepted if this is empty, unless the node is at capacity.
+    pub violations: Vec<Violation>,
+}
+
+/// "account_check_deploy" RPC
+///
+/// Applies the checks of "account_put_deploy" to a deploy without submitting it.
+pub struct CheckDeploy {}
+
+impl RpcWithParams for CheckDeploy {
+    const METHOD: &'static str = "account_check_deploy";
+    type RequestParams = CheckDeployParams;
+    type ResponseResult = CheckDeployResult;
+}
+
+impl RpcWithParamsExt for CheckDeploy {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let deploy_hash = *params.deploy.id();
+
+            let maybe_violations = effect_builder
+                .make_request(
+                    |responder| RpcRequest::CheckDeploy {
+                        deploy: Box::new(params.deploy),
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
+            let violations = match maybe_violations {
+                Some(violations) => violations,
+                None => {
+                    info!("failed to get chainspec to check {}", deploy_hash);
+                    return Ok(response_builder.error(warp_json_rpc::Error::INTERNAL_ERROR)?);
+                }
+            };
+
+            let result = Self::ResponseResult {
+                api_version: CLIENT_API_VERSION.clone(),
+                deploy_hash,
+                violations: violations.into_iter().map(Violation::from).collect(),
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+/// The number of deploy hashes returned by a single "account_get_deploys" request.
+pub const ACCOUNT_DEPLOYS_PAGE_SIZE: u64 = 100;
+
+/// Params for "account_get_deploys" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetAccountDeploysParams {
+    /// The public key or account hash of the account.
+    pub account_identifier: String,
+    /// The page of deploys to retrieve, where page 0 holds the most recent ones.
+    #[serde(default)]
+    pub page: u64,
+}
+
+/// Result for "account_get_deploys" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetAccountDeploysResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: Version,
+    /// The total number of deploys recorded as involving the account.
+    pub total_deploys: u64,
+    /// The hashes of the deploys involving the account, most recent first.
+    pub deploy_hashes: Vec<DeployHash>,
+}
+
+/// "account_get_deploys" RPC
+pub struct GetAccountDeploys {}
+
+impl RpcWithParams for GetAccountDeploys {
+    const METHOD: &'static str = "account_get_deploys";
+    type RequestParams = GetAccountDeploysParams;
+    type ResponseResult = GetAccountDeploysResult;
+}
+
+impl RpcWithParamsExt for GetAccountDeploys {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            // Try to parse the account hash from the params.
+            let account_hash = match state::parse_account_identifier(&params.account_identifier) {
+                Ok(account_hash) => account_hash,
+                Err(error_msg) => {
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::ParseAccountIdentifier as i64,
+                        error_msg,
+                    ))?);
+                }
+            };
+
+            let (total_deploys, deploy_hashes) = effect_builder
+                .make_request(
+                    |responder| RpcRequest::GetAccountDeploys {
+                        account_hash,
+                        offset: params.page.saturating_mul(ACCOUNT_DEPLOYS_PAGE_SIZE),
+                        count: ACCOUNT_DEPLOYS_PAGE_SIZE,
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
+            let result = Self::ResponseResult {
+                api_version: CLIENT_API_VERSION.clone(),
+                total_deploys,
+                deploy_hashes,
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_extract_retry_after_secs() {
+        let data = serde_json::to_value(NodeAtCapacityData {
+            retry_after_secs: 42,
+        })
+        .unwrap();
+        assert_eq!(retry_after_secs(Some(&data)), Some(42));
+        assert_eq!(retry_after_secs(None), None);
+        assert_eq!(
+            retry_after_secs(Some(&Value::String("retry after 42 seconds".to_string()))),
+            None
+        );
+    }
+}
@@ -0,0 +1,498 @@
+//! RPCs allowing the node operator to adjust the node's networking at runtime, and to manage the
+//! API keys of the JSON-RPC server.
+//!
+//! Every request must carry the admin API token set in the config in its "Authorization" header as
+//! "Bearer <token>".  If no token is set, all admin requests are rejected.
+//!
+//! These RPCs form no part of the client API, so they are neither versioned nor described by the
+//! OpenRPC document.
+
+use std::{
+    fmt::{self, Display, Formatter},
+    net::SocketAddr,
+    sync::Arc,
+    time::Duration,
+};
+
+use futures::{
+    future::{self, BoxFuture},
+    FutureExt, TryFutureExt,
+};
+use http::Response;
+use hyper::Body;
+use schemars::JsonSchema;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use warp::{filters::BoxedFilter, reject, Filter};
+use warp_json_rpc::{filters, Builder};
+
+use super::{
+    super::api_keys::{ApiKeyUsage, ApiKeys},
+    Error, ErrorCode, ReactorEventT, RpcRequest, RpcWithParams, RpcWithoutParams, RPC_API_PATH,
+};
+use crate::{
+    components::CLIENT_API_VERSION, effect::EffectBuilder, reactor::QueueKind, types::Timestamp,
+};
+
+pub(in crate::components::rpc_server) const AUTHORIZATION_HEADER: &str = "authorization";
+const BEARER_PREFIX: &str = "Bearer ";
+
+/// A command from the node operator to adjust the node's networking.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AdminCommand {
+    /// Connect to the peer listening on the given address, removing it from the blocklist if
+    /// required.
+    ConnectPeer(SocketAddr),
+    /// Drop any connections to the peer listening on the given address.
+    DisconnectPeer(SocketAddr),
+    /// Drop any connections to the peer listening on the given address and never reconnect to it.
+    BanPeer(SocketAddr),
+    /// Replace the given gossip and fetch timeouts.
+    SetNetworkTimeouts(NetworkTimeouts),
+}
+
+impl Display for AdminCommand {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AdminCommand::ConnectPeer(address) => write!(formatter, "connect to {}", address),
+            AdminCommand::DisconnectPeer(address) => {
+                write!(formatter, "disconnect from {}", address)
+            }
+            AdminCommand::BanPeer(address) => write!(formatter, "ban {}", address),
+            AdminCommand::SetNetworkTimeouts(timeouts) => {
+                write!(formatter, "set network timeouts {:?}", timeouts)
+            }
+        }
+    }
+}
+
+/// New values for the gossip and fetch timeouts.  Timeouts which are `None` are left unchanged.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NetworkTimeouts {
+    /// The interval between each round of gossiping our own public listening address.
+    pub address_gossip_interval: Option<Duration>,
+    /// How long the gossipers wait for a peer to respond to a gossip request.
+    pub gossip_request_timeout: Option<Duration>,
+    /// How long the gossipers wait for a peer to provide the remainder of a partial item.
+    pub get_remainder_timeout: Option<Duration>,
+    /// How long the fetchers wait for a peer to provide a requested item.
+    pub fetch_timeout: Option<Duration>,
+}
+
+/// Params for "admin_connect_peer" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct ConnectPeerParams {
+    /// The public listening address of the peer, e.g. "1.2.3.4:34553".
+    pub address: String,
+}
+
+/// Params for "admin_disconnect_peer" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct DisconnectPeerParams {
+    /// The public listening address of the peer, e.g. "1.2.3.4:34553".
+    pub address: String,
+}
+
+/// Params for "admin_ban_peer" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct BanPeerParams {
+    /// The public listening address of the peer, e.g. "1.2.3.4:34553".
+    pub address: String,
+}
+
+/// Params for "admin_set_network_timeouts" RPC request.
+#[derive(Serialize, Deserialize, Debug, Default, JsonSchema)]
+pub struct SetNetworkTimeoutsParams {
+    /// The interval in seconds between each round of gossiping our own public listening address.
+    #[serde(default)]
+    pub address_gossip_interval_secs: Option<u64>,
+    /// How long in seconds to wait for a peer to respond to a gossip request.
+    #[serde(default)]
+    pub gossip_request_timeout_secs: Option<u64>,
+    /// How long in seconds to wait for a peer to provide the remainder of a partial gossiped item.
+    #[serde(default)]
+    pub get_remainder_timeout_secs: Option<u64>,
+    /// How long in seconds to wait for a peer to provide a fetched item.
+    #[serde(default)]
+    pub fetch_timeout_secs: Option<u64>,
+}
+
+/// Result for all admin RPC responses.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct AdminResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: Version,
+}
+
+/// Result for "admin_get_api_key_usage" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetApiKeyUsageResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: Version,
+    /// The usage of each API key, ordered by name.
+    pub keys: Vec<ApiKeyUsage>,
+}
+
+/// Result for "admin_reload_api_keys" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct ReloadApiKeysResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: Version,
+    /// The number of API keys listed in the reloaded file.
+    pub key_count: usize,
+}
+
+/// A trait for creating the JSON-RPC filter of an admin RPC, which requires the request to be
+/// authorized with the admin API token.
+pub(crate) trait AdminRpcExt: RpcWithParams<ResponseResult = AdminResult> {
+    /// Converts the request params into the command to be executed, or returns an error message if
+    /// the params are invalid.
+    fn command(params: Self::RequestParams) -> Result<AdminCommand, String>;
+
+    /// Creates the warp filter for this particular RPC.
+    fn create_filter<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        maybe_admin_api_token: Option<String>,
+    ) -> BoxedFilter<(Response<Body>,)> {
+        warp::path(RPC_API_PATH)
+            .and(filters::json_rpc())
+            .and(filters::method(Self::METHOD))
+            .and(filters::params::<Self::RequestParams>())
+            .and(warp::header::optional::<String>(AUTHORIZATION_HEADER))
+            .and_then(
+                move |response_builder: Builder,
+                      params: Self::RequestParams,
+                      authorization: Option<String>| {
+                    let is_authorized =
+                        is_authorized(maybe_admin_api_token.as_deref(), authorization.as_deref());
+                    Self::handle_request(effect_builder, response_builder, params, is_authorized)
+                        .map_err(reject::custom)
+                },
+            )
+            .boxed()
+    }
+
+    /// Handles the incoming RPC request.
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+        is_authorized: bool,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            if !is_authorized {
+                info!("unauthorized {} request", Self::METHOD);
+                return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                    ErrorCode::Unauthorized as i64,
+                    "missing or invalid admin API token",
+                ))?);
+            }
+
+            let command = match Self::command(params) {
+                Ok(command) => command,
+                Err(error_msg) => {
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::InvalidAdminCommand as i64,
+                        error_msg,
+                    ))?);
+                }
+            };
+
+            // Submit the command to be announced.
+            info!(%command, "received admin command");
+            effect_builder
+                .make_request(
+                    |responder| RpcRequest::AdminCommand { command, responder },
+                    QueueKind::Api,
+                )
+                .await;
+
+            // Return the result.
+            let result = Self::ResponseResult {
+                api_version: CLIENT_API_VERSION.clone(),
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+/// "admin_connect_peer" RPC.
+pub struct ConnectPeer {}
+
+impl RpcWithParams for ConnectPeer {
+    const METHOD: &'static str = "admin_connect_peer";
+    type RequestParams = ConnectPeerParams;
+    type ResponseResult = AdminResult;
+}
+
+impl AdminRpcExt for ConnectPeer {
+    fn command(params: Self::RequestParams) -> Result<AdminCommand, String> {
+        parse_address(&params.address).map(AdminCommand::ConnectPeer)
+    }
+}
+
+/// "admin_disconnect_peer" RPC.
+pub struct DisconnectPeer {}
+
+impl RpcWithParams for DisconnectPeer {
+    const METHOD: &'static str = "admin_disconnect_peer";
+    type RequestParams = DisconnectPeerParams;
+    type ResponseResult = AdminResult;
+}
+
+impl AdminRpcExt for DisconnectPeer {
+    fn command(params: Self::RequestParams) -> Result<AdminCommand, String> {
+        parse_address(&params.address).map(AdminCommand::DisconnectPeer)
+    }
+}
+
+/// "admin_ban_peer" RPC.
+pub struct BanPeer {}
+
+impl RpcWithParams for BanPeer {
+    const METHOD: &'static str = "admin_ban_peer";
+    type RequestParams = BanPeerParams;
+    type ResponseResult = AdminResult;
+}
+
+impl AdminRpcExt for BanPeer {
+    fn command(params: Self::RequestParams) -> Result<AdminCommand, String> {
+        parse_address(&params.address).map(AdminCommand::BanPeer)
+    }
+}
+
+/// "admin_set_network_timeouts" RPC.
+pub struct SetNetworkTimeouts {}
+
+impl RpcWithParams for SetNetworkTimeouts {
+    const METHOD: &'static str = "admin_set_network_timeouts";
+    type RequestParams = SetNetworkTimeoutsParams;
+    type ResponseResult = AdminResult;
+}
+
+impl AdminRpcExt for SetNetworkTimeouts {
+    fn command(params: Self::RequestParams) -> Result<AdminCommand, String> {
+        let timeouts = NetworkTimeouts {
+            address_gossip_interval: to_duration(
+                "address_gossip_interval_secs",
+                params.address_gossip_interval_secs,
+            )?,
+            gossip_request_timeout: to_duration(
+                "gossip_request_timeout_secs",
+                params.gossip_request_timeout_secs,
+            )?,
+            get_remainder_timeout: to_duration(
+                "get_remainder_timeout_secs",
+                params.get_remainder_timeout_secs,
+            )?,
+            fetch_timeout: to_duration("fetch_timeout_secs", params.fetch_timeout_secs)?,
+        };
+        if timeouts == NetworkTimeouts::default() {
+            return Err("at least one timeout must be provided".to_string());
+        }
+        Ok(AdminCommand::SetNetworkTimeouts(timeouts))
+    }
+}
+
+/// A trait for creating the JSON-RPC filter of an admin RPC inspecting or managing the API keys,
+/// which requires the request to be authorized with the admin API token.
+///
+/// These are handled by the JSON-RPC server itself, without involving the reactor.
+pub(crate) trait AdminApiKeysRpcExt: RpcWithoutParams {
+    /// Returns the result of the request, or an error message.
+    fn result(api_keys: &ApiKeys) -> Result<Self::ResponseResult, String>;
+
+    /// Creates the warp filter for this particular RPC.
+    fn create_filter(
+        maybe_api_keys: Option<Arc<ApiKeys>>,
+        maybe_admin_api_token: Option<String>,
+    ) -> BoxedFilter<(Response<Body>,)> {
+        warp::path(RPC_API_PATH)
+            .and(filters::json_rpc())
+            .and(filters::method(Self::METHOD))
+            .and(warp::header::optional::<String>(AUTHORIZATION_HEADER))
+            .and_then(
+                move |response_builder: Builder, authorization: Option<String>| {
+                    let is_authorized =
+                        is_authorized(maybe_admin_api_token.as_deref(), authorization.as_deref());
+                    let response = Self::handle_request(
+                        response_builder,
+                        maybe_api_keys.as_deref(),
+                        is_authorized,
+                    );
+                    future::ready(response.map_err(reject::custom))
+                },
+            )
+            .boxed()
+    }
+
+    /// Handles the incoming RPC request.
+    fn handle_request(
+        response_builder: Builder,
+        maybe_api_keys: Option<&ApiKeys>,
+        is_authorized: bool,
+    ) -> Result<Response<Body>, Error> {
+        if !is_authorized {
+            info!("unauthorized {} request", Self::METHOD);
+            return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                ErrorCode::Unauthorized as i64,
+                "missing or invalid admin API token",
+            ))?);
+        }
+
+        let result = match maybe_api_keys {
+            Some(api_keys) => Self::result(api_keys),
+            None => Err("no API keys file is configured".to_string()),
+        };
+        match result {
+            Ok(result) => Ok(response_builder.success(result)?),
+            Err(error_msg) => {
+                info!("{}", error_msg);
+                Ok(response_builder.error(warp_json_rpc::Error::custom(
+                    ErrorCode::InvalidAdminCommand as i64,
+                    error_msg,
+                ))?)
+            }
+        }
+    }
+}
+
+/// "admin_get_api_key_usage" RPC.
+pub struct GetApiKeyUsage {}
+
+impl RpcWithoutParams for GetApiKeyUsage {
+    const METHOD: &'static str = "admin_get_api_key_usage";
+    type ResponseResult = GetApiKeyUsageResult;
+}
+
+impl AdminApiKeysRpcExt for GetApiKeyUsage {
+    fn result(api_keys: &ApiKeys) -> Result<Self::ResponseResult, String> {
+        Ok(GetApiKeyUsageResult {
+            api_version: CLIENT_API_VERSION.clone(),
+            keys: api_keys.usage(Timestamp::now()),
+        })
+    }
+}
+
+/// "admin_reload_api_keys" RPC.
+pub struct ReloadApiKeys {}
+
+impl RpcWithoutParams for ReloadApiKeys {
+    const METHOD: &'static str = "admin_reload_api_keys";
+    type ResponseResult = ReloadApiKeysResult;
+}
+
+impl AdminApiKeysRpcExt for ReloadApiKeys {
+    fn result(api_keys: &ApiKeys) -> Result<Self::ResponseResult, String> {
+        let key_count = api_keys.reload().map_err(|error| error.to_string())?;
+        info!(%key_count, "reloaded API keys");
+        Ok(ReloadApiKeysResult {
+            api_version: CLIENT_API_VERSION.clone(),
+            key_count,
+        })
+    }
+}
+
+fn parse_address(address: &str) -> Result<SocketAddr, String> {
+    address
+        .parse()
+        .map_err(|error| format!("failed to parse peer address {}: {}", address, error))
+}
+
+fn to_duration(name: &str, maybe_secs: Option<u64>) -> Result<Option<Duration>, String> {
+    match maybe_secs {
+        Some(0) => Err(format!("{} must be greater than 0", name)),
+        Some(secs) => Ok(Some(Duration::from_secs(secs))),
+        None => Ok(None),
+    }
+}
+
+/// Returns whether the value of the "Authorization" header holds the admin API token.  Always
+/// returns false if no admin API token, or a blank one, is configured.
+///
+/// The comparison takes the same time wherever the first mismatching byte lies, so as not to leak
+/// the token to a timing attack.
+pub(in crate::components::rpc_server) fn is_authorized(
+    maybe_admin_api_token: Option<&str>,
+    maybe_authorization: Option<&str>,
+) -> bool {
+    let (admin_api_token, provided_token) = match (
+        maybe_admin_api_token,
+        maybe_authorization.and_then(|authorization| authorization.strip_prefix(BEARER_PREFIX)),
+    ) {
+        (Some(admin_api_token), Some(provided_token)) if !admin_api_token.trim().is_empty() => {
+            (admin_api_token, provided_token)
+        }
+        _ => return false,
+    };
+    provided_token.len() == admin_api_token.len()
+        && provided_token
+            .bytes()
+            .zip(admin_api_token.bytes())
+            .fold(0, |difference, (lhs, rhs)| difference | (lhs ^ rhs))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_authorize_only_matching_bearer_token() {
+        let token = Some("secret");
+        assert!(is_authorized(token, Some("Bearer secret")));
+        assert!(!is_authorized(token, None));
+        assert!(!is_authorized(token, Some("secret")));
+        assert!(!is_authorized(token, Some("Bearer secreT")));
+        assert!(!is_authorized(token, Some("Bearer secret2")));
+        assert!(!is_authorized(token, Some("Basic secret")));
+        assert!(!is_authorized(None, Some("Bearer secret")));
+    }
+
+    #[test]
+    fn should_not_authorize_with_blank_token() {
+        assert!(!is_authorized(Some(""), Some("Bearer ")));
+        assert!(!is_authorized(Some(" "), Some("Bearer  ")));
+    }
+
+    #[test]
+    fn should_convert_params_to_commands() {
+        let address: SocketAddr = "1.2.3.4:34553".parse().unwrap();
+        let params = BanPeerParams {
+            address: address.to_string(),
+        };
+        assert_eq!(BanPeer::command(params), Ok(AdminCommand::BanPeer(address)));
+
+        let params = ConnectPeerParams {
+            address: "not an address".to_string(),
+        };
+        assert!(ConnectPeer::command(params).is_err());
+
+        let params = SetNetworkTimeoutsParams {
+            fetch_timeout_secs: Some(5),
+            ..Default::default()
+        };
+        let expected = NetworkTimeouts {
+            fetch_timeout: Some(Duration::from_secs(5)),
+            ..Default::default()
+        };
+        assert_eq!(
+            SetNetworkTimeouts::command(params),
+            Ok(AdminCommand::SetNetworkTimeouts(expected))
+        );
+
+        assert!(SetNetworkTimeouts::command(SetNetworkTimeoutsParams::default()).is_err());
+
+        let params = SetNetworkTimeoutsParams {
+            gossip_request_timeout_secs: Some(0),
+            ..Default::default()
+        };
+        assert!(SetNetworkTimeouts::command(params).is_err());
+    }
+}
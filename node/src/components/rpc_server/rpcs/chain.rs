@@ -1,46 +1,86 @@
 //! RPCs related to the block chain.
 
-use std::str;
+use std::{
+    convert::TryFrom,
+    fmt::{self, Display, Formatter},
+    str,
+};
 
 use futures::{future::BoxFuture, FutureExt};
 use http::Response;
 use hyper::Body;
+use schemars::JsonSchema;
 use semver::Version;
 use serde::{Deserialize, Serialize};
 use tracing::info;
 use warp_json_rpc::Builder;
 
+use casper_execution_engine::{
+    core::engine_state::QueryResult, storage::protocol_data::ProtocolData,
+};
+use casper_types::{auction::ERA_INFO_KEY, bytesrepr::ToBytes, ProtocolVersion};
+
 use super::{
     Error, ErrorCode, ReactorEventT, RpcRequest, RpcWithOptionalParams, RpcWithOptionalParamsExt,
+    RpcWithParams, RpcWithParamsExt,
 };
 use crate::{
-    components::CLIENT_API_VERSION,
+    components::{consensus::EraId, CLIENT_API_VERSION},
     crypto::hash::Digest,
     effect::EffectBuilder,
     reactor::QueueKind,
-    types::{Block, BlockHash},
+    types::{
+        json_compatibility::StoredValue, Block, BlockHash, BlockHeader, DeployHash, Timestamp,
+    },
 };
 
+/// The maximum number of blocks returned by a single "chain_get_blocks" request.
+pub const MAX_BLOCKS_PER_PAGE: u64 = 100;
+
 /// Identifier for possible ways to retrieve a block.
-#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, JsonSchema)]
 pub enum BlockIdentifier {
     /// Identify and retrieve the block with its hash.
     Hash(BlockHash),
     /// Identify and retrieve the block with its height.
     Height(u64),
+    /// Identify and retrieve the switch block of the era with the given ID.
+    SwitchBlock(u64),
+    /// Identify and retrieve the most recent switch block.
+    LatestSwitchBlock,
+    /// Identify and retrieve the most recent block whose timestamp is not later than the given
+    /// one.
+    Timestamp(Timestamp),
+}
+
+impl Display for BlockIdentifier {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            BlockIdentifier::Hash(hash) => write!(formatter, "{}", hash),
+            BlockIdentifier::Height(height) => write!(formatter, "block at height {}", height),
+            BlockIdentifier::SwitchBlock(era_id) => {
+                write!(formatter, "switch block of era {}", era_id)
+            }
+            BlockIdentifier::LatestSwitchBlock => write!(formatter, "latest switch block"),
+            BlockIdentifier::Timestamp(timestamp) => {
+                write!(formatter, "latest block at or before {}", timestamp)
+            }
+        }
+    }
 }
 
 /// Params for "chain_get_block" RPC request.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub struct GetBlockParams {
     /// The block hash.
     pub block_identifier: BlockIdentifier,
 }
 
 /// Result for "chain_get_block" RPC response.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub struct GetBlockResult {
     /// The RPC API version.
+    #[schemars(with = "String")]
     pub api_version: Version,
     /// The block, if found.
     pub block: Option<Block>,
@@ -80,17 +120,541 @@ impl RpcWithOptionalParamsExt for GetBlock {
     }
 }
 
+/// Params for "chain_get_blocks" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetBlocksParams {
+    /// The height of the first block in the range.
+    pub start_height: u64,
+    /// The height of the last block in the range (inclusive).
+    pub end_height: u64,
+    /// Whether to return only the headers of the blocks rather than the full blocks.
+    #[serde(default)]
+    pub headers_only: bool,
+}
+
+/// Result for "chain_get_blocks" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetBlocksResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: Version,
+    /// The blocks, in ascending order of height, or empty if only headers were requested.
+    ///
+    /// This is the contiguous run of blocks starting at the requested start height, cut short by
+    /// the page size or by the first block not yet known to the node.
+    pub blocks: Vec<Block>,
+    /// The headers of that same run of blocks, if only headers were requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub headers: Option<Vec<BlockHeader>>,
+    /// The height at which to continue if the range was cut short by the page size.
+    pub next_height: Option<u64>,
+}
+
+/// "chain_get_blocks" RPC.
+pub struct GetBlocks {}
+
+impl RpcWithParams for GetBlocks {
+    const METHOD: &'static str = "chain_get_blocks";
+    type RequestParams = GetBlocksParams;
+    type ResponseResult = GetBlocksResult;
+}
+
+impl RpcWithParamsExt for GetBlocks {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let GetBlocksParams {
+                start_height,
+                end_height,
+                headers_only,
+            } = params;
+
+            if end_height < start_height {
+                let error_msg = format!(
+                    "invalid block range: end height {} is lower than start height {}",
+                    end_height, start_height
+                );
+                info!("{}", error_msg);
+                return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                    ErrorCode::InvalidBlockRange as i64,
+                    error_msg,
+                ))?);
+            }
+
+            // Clamp the requested range to the page size.
+            let requested_count = (end_height - start_height).saturating_add(1);
+            let max_count = requested_count.min(MAX_BLOCKS_PER_PAGE);
+
+            let blocks = effect_builder
+                .make_request(
+                    |responder| RpcRequest::GetBlocks {
+                        start_height,
+                        max_count,
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
+            let next_height = if (blocks.len() as u64) < requested_count
+                && blocks.len() as u64 == MAX_BLOCKS_PER_PAGE
+            {
+                Some(start_height + MAX_BLOCKS_PER_PAGE)
+            } else {
+                None
+            };
+
+            let (blocks, headers) = if headers_only {
+                let headers = blocks.into_iter().map(Block::take_header).collect();
+                (vec![], Some(headers))
+            } else {
+                (blocks, None)
+            };
+
+            // Return the result.
+            let result = Self::ResponseResult {
+                api_version: CLIENT_API_VERSION.clone(),
+                blocks,
+                headers,
+                next_height,
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+/// Params for "chain_get_alternative_blocks" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetAlternativeBlocksParams {
+    /// The height of the blocks.
+    pub height: u64,
+}
+
+/// Result for "chain_get_alternative_blocks" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetAlternativeBlocksResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: Version,
+    /// The blocks at the requested height which conflict with the block on the linear chain, in
+    /// order of their hashes.
+    pub blocks: Vec<Block>,
+}
+
+/// "chain_get_alternative_blocks" RPC.
+pub struct GetAlternativeBlocks {}
+
+impl RpcWithParams for GetAlternativeBlocks {
+    const METHOD: &'static str = "chain_get_alternative_blocks";
+    type RequestParams = GetAlternativeBlocksParams;
+    type ResponseResult = GetAlternativeBlocksResult;
+}
+
+impl RpcWithParamsExt for GetAlternativeBlocks {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let height = params.height;
+            let blocks = effect_builder
+                .make_request(
+                    |responder| RpcRequest::GetAlternativeBlocks { height, responder },
+                    QueueKind::Api,
+                )
+                .await;
+
+            // Return the result.
+            let result = Self::ResponseResult {
+                api_version: CLIENT_API_VERSION.clone(),
+                blocks,
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+/// Params for "chain_get_block_by_deploy" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetBlockByDeployParams {
+    /// The deploy hash.
+    pub deploy_hash: DeployHash,
+}
+
+/// Result for "chain_get_block_by_deploy" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetBlockByDeployResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: Version,
+    /// The hash of the block on the linear chain including the deploy.
+    pub block_hash: BlockHash,
+    /// The header of the block, committed to by `block_hash`.
+    pub block_header: BlockHeader,
+    /// The index of the deploy within the block's deploy hashes.
+    pub deploy_index: u32,
+}
+
+/// "chain_get_block_by_deploy" RPC.
+pub struct GetBlockByDeploy {}
+
+impl RpcWithParams for GetBlockByDeploy {
+    const METHOD: &'static str = "chain_get_block_by_deploy";
+    type RequestParams = GetBlockByDeployParams;
+    type ResponseResult = GetBlockByDeployResult;
+}
+
+impl RpcWithParamsExt for GetBlockByDeploy {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let deploy_hash = params.deploy_hash;
+            let maybe_block = effect_builder
+                .make_request(
+                    |responder| RpcRequest::GetBlockByDeploy {
+                        deploy_hash,
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
+            let maybe_found = maybe_block.and_then(|block| {
+                let index = block
+                    .deploy_hashes()
+                    .iter()
+                    .position(|hash| *hash == deploy_hash)?;
+                Some((block, index))
+            });
+            let (block, deploy_index) = match maybe_found {
+                Some(found) => found,
+                None => {
+                    info!("failed to get block including {} from storage", deploy_hash);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::NoSuchBlock as i64,
+                        "no block including the deploy is known",
+                    ))?);
+                }
+            };
+
+            let result = Self::ResponseResult {
+                api_version: CLIENT_API_VERSION.clone(),
+                block_hash: *block.hash(),
+                block_header: block.header().clone(),
+                deploy_index: deploy_index as u32,
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+/// Params for "chain_get_block_at_timestamp" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetBlockAtTimestampParams {
+    /// The timestamp.
+    pub timestamp: Timestamp,
+}
+
+/// Result for "chain_get_block_at_timestamp" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetBlockAtTimestampResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: Version,
+    /// The hash of the most recent block on the linear chain whose timestamp is not later than the
+    /// requested one.
+    pub block_hash: BlockHash,
+    /// The header of the block, committed to by `block_hash`.
+    pub block_header: BlockHeader,
+    /// The header of the next block, if known.
+    ///
+    /// Its parent hash is `block_hash` and its timestamp is later than the requested one, showing
+    /// that `block_hash` identifies the last block at or before the requested timestamp.
+    pub next_block_header: Option<BlockHeader>,
+}
+
+/// "chain_get_block_at_timestamp" RPC.
+pub struct GetBlockAtTimestamp {}
+
+impl RpcWithParams for GetBlockAtTimestamp {
+    const METHOD: &'static str = "chain_get_block_at_timestamp";
+    type RequestParams = GetBlockAtTimestampParams;
+    type ResponseResult = GetBlockAtTimestampResult;
+}
+
+impl RpcWithParamsExt for GetBlockAtTimestamp {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let block_identifier = BlockIdentifier::Timestamp(params.timestamp);
+            let block = match get_block(Some(block_identifier), effect_builder).await {
+                Ok(Some(block)) => block,
+                Ok(None) => {
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::NoSuchBlock as i64,
+                        "block not known",
+                    ))?)
+                }
+                Err(error) => return Ok(response_builder.error(error)?),
+            };
+
+            let next_height = block.height() + 1;
+            let maybe_next_block = effect_builder
+                .make_request(
+                    |responder| RpcRequest::GetBlock {
+                        maybe_id: Some(BlockIdentifier::Height(next_height)),
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
+            let result = Self::ResponseResult {
+                api_version: CLIENT_API_VERSION.clone(),
+                block_hash: *block.hash(),
+                block_header: block.header().clone(),
+                next_block_header: maybe_next_block.map(|next_block| next_block.header().clone()),
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+/// Identifier for possible ways to retrieve an era summary.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, JsonSchema)]
+pub enum EraIdentifier {
+    /// Identify the era by its ID.
+    EraId(u64),
+    /// Identify the era as the one containing the given block.
+    Block(BlockIdentifier),
+}
+
+/// Params for "chain_get_era_summary" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetEraSummaryParams {
+    /// The era identifier.
+    pub era_identifier: EraIdentifier,
+}
+
+/// The summary of an era which has ended.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct EraSummary {
+    /// The era ID.
+    pub era_id: u64,
+    /// The hash of the era's switch block.
+    pub block_hash: BlockHash,
+    /// The header of the era's switch block.
+    ///
+    /// Its era end holds the rewards and the equivocators of the era, and it is committed to by
+    /// `block_hash`.
+    pub block_header: BlockHeader,
+    /// The era info recorded by the auction contract as of the switch block, i.e. the seigniorage
+    /// allocated to each validator and delegator for the era.
+    pub stored_value: StoredValue,
+    /// The merkle proof of `stored_value` against the switch block's state root hash.
+    pub merkle_proof: String,
+}
+
+/// Result for "chain_get_era_summary" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetEraSummaryResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: Version,
+    /// The era summary.
+    pub era_summary: EraSummary,
+}
+
+/// "chain_get_era_summary" RPC.
+pub struct GetEraSummary {}
+
+impl RpcWithOptionalParams for GetEraSummary {
+    const METHOD: &'static str = "chain_get_era_summary";
+    type OptionalRequestParams = GetEraSummaryParams;
+    type ResponseResult = GetEraSummaryResult;
+}
+
+impl RpcWithOptionalParamsExt for GetEraSummary {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        maybe_params: Option<Self::OptionalRequestParams>,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            // Work out which era is requested.  `None` means the most recently ended one.
+            let maybe_era_id = match maybe_params.map(|params| params.era_identifier) {
+                None => None,
+                Some(EraIdentifier::EraId(era_id)) => Some(EraId(era_id)),
+                Some(EraIdentifier::Block(block_identifier)) => {
+                    match get_block(Some(block_identifier), effect_builder).await {
+                        Ok(Some(block)) => Some(block.header().era_id()),
+                        Ok(None) => {
+                            return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                                ErrorCode::NoSuchBlock as i64,
+                                "block not known",
+                            ))?)
+                        }
+                        Err(error) => return Ok(response_builder.error(error)?),
+                    }
+                }
+            };
+
+            // Get the era's switch block.
+            let maybe_switch_block = effect_builder
+                .make_request(
+                    |responder| RpcRequest::GetSwitchBlock {
+                        maybe_era_id,
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
+            let switch_block = match maybe_switch_block {
+                Some(switch_block) => switch_block,
+                None => {
+                    let error_msg = match maybe_era_id {
+                        Some(era_id) => format!("{} has not ended or is not known", era_id),
+                        None => "no era has ended yet".to_string(),
+                    };
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::NoSuchEraSummary as i64,
+                        error_msg,
+                    ))?);
+                }
+            };
+
+            // The auction contract is looked up under the protocol version active at the switch
+            // block.
+            let maybe_chainspec = effect_builder
+                .make_request(
+                    |responder| RpcRequest::GetChainspec { responder },
+                    QueueKind::Api,
+                )
+                .await;
+            let protocol_version = match maybe_chainspec {
+                Some(chainspec) => {
+                    let version = chainspec.protocol_version_at(switch_block.height());
+                    ProtocolVersion::from_parts(
+                        version.major as u32,
+                        version.minor as u32,
+                        version.patch as u32,
+                    )
+                }
+                None => {
+                    info!("failed to get chainspec");
+                    return Ok(response_builder.error(warp_json_rpc::Error::INTERNAL_ERROR)?);
+                }
+            };
+            let protocol_data = match effect_builder
+                .make_request(
+                    |responder| RpcRequest::QueryProtocolData {
+                        protocol_version,
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await
+            {
+                Ok(Some(protocol_data)) => protocol_data,
+                _ => Box::new(ProtocolData::default()),
+            };
+
+            // Read the era info stored by the auction contract when executing the switch block.
+            let state_root_hash = *switch_block.header().state_root_hash();
+            let query_result = effect_builder
+                .make_request(
+                    |responder| RpcRequest::QueryGlobalState {
+                        state_root_hash,
+                        base_key: protocol_data.auction().into(),
+                        path: vec![ERA_INFO_KEY.to_string()],
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
+            let (value, proofs) = match query_result {
+                Ok(QueryResult::Success { value, proofs }) => (value, proofs),
+                Ok(query_result) => {
+                    let error_msg = format!("state query failed: {:?}", query_result);
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::QueryFailed as i64,
+                        error_msg,
+                    ))?);
+                }
+                Err(error) => {
+                    let error_msg = format!("state query failed to execute: {:?}", error);
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::QueryFailedToExecute as i64,
+                        error_msg,
+                    ))?);
+                }
+            };
+
+            let stored_value = match StoredValue::try_from(&*value) {
+                Ok(stored_value) => stored_value,
+                Err(error) => {
+                    info!("failed to encode stored value: {}", error);
+                    return Ok(response_builder.error(warp_json_rpc::Error::INTERNAL_ERROR)?);
+                }
+            };
+
+            let proof_bytes = match proofs.to_bytes() {
+                Ok(proof_bytes) => proof_bytes,
+                Err(error) => {
+                    info!("failed to encode stored value: {}", error);
+                    return Ok(response_builder.error(warp_json_rpc::Error::INTERNAL_ERROR)?);
+                }
+            };
+
+            let block_hash = *switch_block.hash();
+            let block_header = switch_block.take_header();
+            let result = Self::ResponseResult {
+                api_version: CLIENT_API_VERSION.clone(),
+                era_summary: EraSummary {
+                    era_id: block_header.era_id().0,
+                    block_hash,
+                    block_header,
+                    stored_value,
+                    merkle_proof: hex::encode(proof_bytes),
+                },
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
 /// Params for "chain_get_state_root_hash" RPC request.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub struct GetStateRootHashParams {
     /// The block hash.
     pub block_identifier: BlockIdentifier,
 }
 
 /// Result for "chain_get_state_root_hash" RPC response.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub struct GetStateRootHashResult {
     /// The RPC API version.
+    #[schemars(with = "String")]
     pub api_version: Version,
     /// Hex-encoded hash of the state root.
     pub state_root_hash: Option<Digest>,
@@ -130,7 +694,7 @@ impl RpcWithOptionalParamsExt for GetStateRootHash {
     }
 }
 
-async fn get_block<REv: ReactorEventT>(
+pub(super) async fn get_block<REv: ReactorEventT>(
     maybe_id: Option<BlockIdentifier>,
     effect_builder: EffectBuilder<REv>,
 ) -> Result<Option<Block>, warp_json_rpc::Error> {
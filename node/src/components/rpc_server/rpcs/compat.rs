@@ -0,0 +1,341 @@
+//! Compatibility layer serving older versions of the client API.
+//!
+//! Every RPC is served under its plain method name using the latest shape of its response, and
+//! additionally under a versioned method name for each older version of the API which included
+//! it, e.g. "v1_chain_get_block".  Requests made under a versioned name are handled by the latest
+//! handler, and the "result" field of the response is then translated down to the shape of that
+//! version.
+//!
+//! Clients which need a stable response shape across node upgrades should use the versioned
+//! names.  RPCs added after a version was frozen are not served under its names.  When a change
+//! is made to the shape of a response, the translation back to the older shape must be added to
+//! each older version's `translate_result()`, and the golden responses in this module's tests
+//! must continue to pass unmodified.
+
+use http::{header, Response};
+use hyper::Body;
+use serde_json::{json, Value};
+
+use super::{Error, ErrorCode};
+
+/// An error into which an older version of the client API translates a result, as that version
+/// reported the same outcome as an error.
+#[derive(Debug, PartialEq)]
+pub(crate) struct TranslatedError {
+    code: i64,
+    message: String,
+}
+
+/// An older version of the client API.
+pub(crate) trait ApiVersion: Send + Sync + 'static {
+    /// The prefix applied to the names of the RPC methods of this version.
+    const METHOD_PREFIX: &'static str;
+
+    /// The RPC methods which this version included, and under whose names it is served.
+    const METHODS: &'static [&'static str];
+
+    /// Translates the result of the latest version of the given RPC method into this version's
+    /// shape.
+    fn translate_result(method: &str, result: Value) -> Result<Value, TranslatedError>;
+}
+
+/// Version 1 of the client API.
+pub(crate) struct V1;
+
+impl ApiVersion for V1 {
+    const METHOD_PREFIX: &'static str = "v1_";
+
+    const METHODS: &'static [&'static str] = &[
+        "account_put_deploy",
+        "chain_get_block",
+        "chain_get_blocks",
+        "chain_get_era_summary",
+        "chain_get_state_root_hash",
+        "info_estimate_deploy_cost",
+        "info_get_deploy",
+        "info_get_peers",
+        "info_get_status",
+        "state_get_account_info",
+        "state_get_auction_info",
+        "state_get_balance",
+        "state_get_item",
+    ];
+
+    fn translate_result(method: &str, mut result: Value) -> Result<Value, TranslatedError> {
+        match method {
+            "account_put_deploy" => remove_fields(&mut result, &["receipt", "replayed"]),
+            "info_estimate_deploy_cost" => remove_fields(&mut result, &["gas_profile"]),
+            "info_get_status" => remove_fields(
+                &mut result,
+                &[
+                    "last_finalized_era_id",
+                    "sync_state",
+                    "highest_peer_block_height",
+                    "estimated_blocks_remaining",
+                    "download_rate",
+                    "resumed_from_height",
+                    "historical_state_window",
+                    "archive",
+                ],
+            ),
+            "state_get_auction_info" => {
+                if let Some(Value::Object(bids)) = result.pointer_mut("/auction_state/bids") {
+                    for bid in bids.values_mut() {
+                        remove_fields(bid, &["signing_key"]);
+                    }
+                }
+            }
+            "state_get_item" => {
+                // Version 1 had no proofs of absence, and reported a missing key as a failed query.
+                if result.get("stored_value").map_or(true, Value::is_null) {
+                    return Err(TranslatedError {
+                        code: ErrorCode::QueryFailed as i64,
+                        message: "state query failed: value not found".to_string(),
+                    });
+                }
+                remove_fields(&mut result, &["merkle_proof_of_absence"]);
+            }
+            _ => (),
+        }
+        Ok(result)
+    }
+}
+
+/// Returns whether the given RPC method is served under version `V`.
+pub(super) fn is_served<V: ApiVersion>(method: &str) -> bool {
+    V::METHODS.contains(&method)
+}
+
+/// Removes the given fields from `value` if it is a JSON object.
+fn remove_fields(value: &mut Value, fields: &[&str]) {
+    if let Value::Object(map) = value {
+        for field in fields {
+            map.remove(*field);
+        }
+    }
+}
+
+/// Returns the name under which the given RPC method is served for version `V`.
+///
+/// The name is leaked, as the JSON-RPC filters require a static method name.  This is only called
+/// once per RPC and version while constructing the filters on startup.
+pub(super) fn method_name<V: ApiVersion>(method: &str) -> &'static str {
+    Box::leak(format!("{}{}", V::METHOD_PREFIX, method).into_boxed_str())
+}
+
+/// Translates the "result" field of a response from the latest version of the given RPC method
+/// into the shape of version `V`, replacing it with an "error" field if version `V` reported the
+/// outcome as an error.  Error responses are returned unchanged.
+pub(super) async fn translate_response<V: ApiVersion>(
+    method: &'static str,
+    response: Response<Body>,
+) -> Result<Response<Body>, Error> {
+    let (mut parts, body) = response.into_parts();
+    let bytes = hyper::body::to_bytes(body)
+        .await
+        .map_err(|error| Error(format!("failed to read {} response: {}", method, error)))?;
+    let mut json: Value = serde_json::from_slice(&bytes)
+        .map_err(|error| Error(format!("failed to parse {} response: {}", method, error)))?;
+
+    if let Value::Object(response) = &mut json {
+        if let Some(latest) = response.remove("result") {
+            match V::translate_result(method, latest) {
+                Ok(result) => {
+                    response.insert("result".to_string(), result);
+                }
+                Err(TranslatedError { code, message }) => {
+                    response.insert(
+                        "error".to_string(),
+                        json!({ "code": code, "message": message }),
+                    );
+                }
+            }
+        }
+    }
+
+    let bytes = serde_json::to_vec(&json)
+        .map_err(|error| Error(format!("failed to encode {} response: {}", method, error)))?;
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Ok(Response::from_parts(parts, Body::from(bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, net::SocketAddr};
+
+    use serde::Serialize;
+    use serde_json::json;
+
+    use casper_types::U512;
+
+    use super::*;
+    use crate::{
+        components::{
+            rpc_server::rpcs::{
+                account::{PutDeploy, PutDeployResult},
+                chain::{GetBlocks, GetBlocksResult, GetStateRootHash, GetStateRootHashResult},
+                info::{GetPeers, GetPeersResult},
+                state::{GetAuctionInfo, GetBalance, GetBalanceResult, GetItem, GetItems},
+                RpcWithOptionalParams, RpcWithParams, RpcWithoutParams,
+            },
+            CLIENT_API_VERSION,
+        },
+        crypto::hash::Digest,
+        types::DeployHash,
+    };
+
+    /// Asserts that the latest `result` of `method`, once translated to version `V`, matches the
+    /// golden response pinned for that version.
+    fn assert_golden<V: ApiVersion, T: Serialize>(method: &str, result: T, golden: Value) {
+        let latest = serde_json::to_value(result).unwrap();
+        assert_eq!(
+            V::translate_result(method, latest),
+            Ok(golden),
+            "{}",
+            method
+        );
+    }
+
+    #[test]
+    fn should_prefix_method_names() {
+        assert_eq!(
+            method_name::<V1>(GetStateRootHash::METHOD),
+            "v1_chain_get_state_root_hash"
+        );
+    }
+
+    #[test]
+    fn should_serve_only_v1_methods_under_v1() {
+        assert!(is_served::<V1>(GetStateRootHash::METHOD));
+        assert!(is_served::<V1>(PutDeploy::METHOD));
+        assert!(!is_served::<V1>(GetItems::METHOD));
+    }
+
+    #[test]
+    fn should_translate_changed_results_to_v1() {
+        let latest = json!({
+            "api_version": "1.0.0",
+            "deploy_hash": "0101010101010101010101010101010101010101010101010101010101010101",
+            "replayed": true
+        });
+        let golden = json!({
+            "api_version": "1.0.0",
+            "deploy_hash": "0101010101010101010101010101010101010101010101010101010101010101"
+        });
+        assert_eq!(V1::translate_result(PutDeploy::METHOD, latest), Ok(golden));
+
+        let latest = json!({
+            "api_version": "1.0.0",
+            "auction_state": { "bids": { "01aa": { "reward": "1", "signing_key": null } } }
+        });
+        let golden = json!({
+            "api_version": "1.0.0",
+            "auction_state": { "bids": { "01aa": { "reward": "1" } } }
+        });
+        assert_eq!(
+            V1::translate_result(GetAuctionInfo::METHOD, latest),
+            Ok(golden)
+        );
+
+        let absent = json!({
+            "api_version": "1.0.0",
+            "merkle_proof": "01020304",
+            "merkle_proof_of_absence": "05060708"
+        });
+        let translated = V1::translate_result(GetItem::METHOD, absent).unwrap_err();
+        assert_eq!(translated.code, ErrorCode::QueryFailed as i64);
+    }
+
+    #[test]
+    fn should_match_v1_golden_responses() {
+        assert_golden::<V1, _>(
+            PutDeploy::METHOD,
+            PutDeployResult {
+                api_version: CLIENT_API_VERSION.clone(),
+                deploy_hash: DeployHash::new(Digest::from([1; Digest::LENGTH])),
+                receipt: None,
+                replayed: false,
+            },
+            json!({
+                "api_version": "1.0.0",
+                "deploy_hash": "0101010101010101010101010101010101010101010101010101010101010101"
+            }),
+        );
+
+        assert_golden::<V1, _>(
+            GetBlocks::METHOD,
+            GetBlocksResult {
+                api_version: CLIENT_API_VERSION.clone(),
+                blocks: vec![],
+                headers: None,
+                next_height: Some(5),
+            },
+            json!({
+                "api_version": "1.0.0",
+                "blocks": [],
+                "next_height": 5
+            }),
+        );
+
+        assert_golden::<V1, _>(
+            GetStateRootHash::METHOD,
+            GetStateRootHashResult {
+                api_version: CLIENT_API_VERSION.clone(),
+                state_root_hash: Some(Digest::from([2; Digest::LENGTH])),
+            },
+            json!({
+                "api_version": "1.0.0",
+                "state_root_hash":
+                    "0202020202020202020202020202020202020202020202020202020202020202"
+            }),
+        );
+
+        let mut peers = BTreeMap::new();
+        let address: SocketAddr = "127.0.0.1:34553".parse().unwrap();
+        peers.insert("tls:0102..0304".to_string(), address);
+        assert_golden::<V1, _>(
+            GetPeers::METHOD,
+            GetPeersResult {
+                api_version: CLIENT_API_VERSION.clone(),
+                peers,
+            },
+            json!({
+                "api_version": "1.0.0",
+                "peers": { "tls:0102..0304": "127.0.0.1:34553" }
+            }),
+        );
+
+        assert_golden::<V1, _>(
+            GetBalance::METHOD,
+            GetBalanceResult {
+                api_version: CLIENT_API_VERSION.clone(),
+                balance_value: U512::from(123_456),
+                merkle_proof: "01020304".to_string(),
+            },
+            json!({
+                "api_version": "1.0.0",
+                "balance_value": "123456",
+                "merkle_proof": "01020304"
+            }),
+        );
+    }
+
+    #[tokio::test]
+    async fn should_translate_result_and_leave_error_unchanged() {
+        let success = json!({ "jsonrpc": "2.0", "id": 1, "result": { "a": 1 } });
+        let response = Response::new(Body::from(serde_json::to_vec(&success).unwrap()));
+        let translated = translate_response::<V1>(GetStateRootHash::METHOD, response)
+            .await
+            .unwrap();
+        let bytes = hyper::body::to_bytes(translated.into_body()).await.unwrap();
+        assert_eq!(serde_json::from_slice::<Value>(&bytes).unwrap(), success);
+
+        let error = json!({ "jsonrpc": "2.0", "id": 1, "error": { "code": -32601 } });
+        let response = Response::new(Body::from(serde_json::to_vec(&error).unwrap()));
+        let translated = translate_response::<V1>(GetStateRootHash::METHOD, response)
+            .await
+            .unwrap();
+        let bytes = hyper::body::to_bytes(translated.into_body()).await.unwrap();
+        assert_eq!(serde_json::from_slice::<Value>(&bytes).unwrap(), error);
+    }
+}
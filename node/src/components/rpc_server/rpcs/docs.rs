@@ -0,0 +1,493 @@
+//! An OpenRPC document describing the set of JSON-RPCs which the API server handles.
+//!
+//! The schemas of the params and results of each RPC are derived from their Rust types, so the
+//! document can't drift from the node's actual API.  See https://spec.open-rpc.org for details of
+//! the format.
+//!
+//! Each type defined under the document's "components" is also served on its own as a standalone
+//! JSON Schema under `/rpc/schema/<type name>`, for use with generators of typed clients which
+//! don't understand OpenRPC.
+//!
+//! The document is pinned by the golden file `resources/test/rpc_schema.json`, so that any change
+//! to the API's types shows up in review.  After an intentional change, regenerate it with:
+//!
+//! ```text
+//! CASPER_UPDATE_RPC_SCHEMA=1 cargo test -p casper-node should_match_golden_schema
+//! ```
+
+use http::{header, Response, StatusCode};
+use hyper::Body;
+use lazy_static::lazy_static;
+use schemars::{
+    gen::{SchemaGenerator, SchemaSettings},
+    schema::{ObjectValidation, Schema},
+    JsonSchema, Map,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::{
+    account::{CheckDeploy, GetAccountDeploys, PutDeploy},
+    chain::{
+        GetAlternativeBlocks, GetBlock, GetBlockAtTimestamp, GetBlockByDeploy, GetBlocks,
+        GetEraSummary, GetStateRootHash,
+    },
+    info::{
+        EstimateDeployCost, GetChainspec, GetDeploy, GetDeployReceipt, GetPeers, GetSignedStatus,
+        GetStatus, GetSupply, GetValidatorRewards,
+    },
+    state::{
+        GetAccountInfo, GetAuctionInfo, GetBalance, GetBalanceHistory, GetContractAbi,
+        GetDelegations, GetItem, GetItems, GetKeyHistory, GetKeysWithPrefix, GetTransfersTo,
+        GetUnbondingPurses,
+    },
+    RpcWithOptionalParams, RpcWithParams, RpcWithoutParams,
+};
+use crate::components::CLIENT_API_VERSION;
+
+/// The URL path under which the OpenRPC document is served.
+pub const SCHEMA_PATH: &str = "/rpc/schema";
+/// The URL path prefix under which the JSON Schema of each individual type is served.
+pub const TYPE_SCHEMA_PATH_PREFIX: &str = "/rpc/schema/";
+
+const OPEN_RPC_VERSION: &str = "1.2.6";
+const TITLE: &str = "Client API of Casper Node";
+const DEFINITIONS_PATH: &str = "#/components/schemas/";
+const JSON_SCHEMA_VERSION: &str = "http://json-schema.org/draft-07/schema#";
+
+lazy_static! {
+    /// The OpenRPC document, generated once on first use.
+    pub static ref OPEN_RPC_SCHEMA: OpenRpcSchema = {
+        let mut builder = OpenRpcSchemaBuilder::new();
+        builder.push_with_params::<PutDeploy>("receives a Deploy to be executed by the network");
+        builder.push_with_params::<CheckDeploy>(
+            "returns every reason for which a Deploy would be refused, without submitting it",
+        );
+        builder.push_with_params::<GetAccountDeploys>(
+            "returns the hashes of the Deploys involving an account, most recent first",
+        );
+        builder.push_with_params::<EstimateDeployCost>(
+            "executes a Deploy against the latest state without committing its effects, returning \
+            the gas consumed and the resulting execution effects",
+        );
+        builder.push_with_params::<GetDeploy>("returns a Deploy from the network");
+        builder.push_with_params::<GetDeployReceipt>(
+            "returns the receipt signed by the node on accepting a Deploy, if still retained",
+        );
+        builder.push_without_params::<GetPeers>("returns a list of peers connected to the node");
+        builder.push_without_params::<GetStatus>("returns the current status of the node");
+        builder.push_with_optional_params::<GetSignedStatus>(
+            "returns the current status of the node, signed with the node's validator key",
+        );
+        builder.push_without_params::<GetChainspec>(
+            "returns the limits, costs, era settings and protocol version of the active chainspec, \
+            along with the hash of the whole chainspec",
+        );
+        builder.push_with_params::<GetValidatorRewards>(
+            "returns a page of the rewards credited to each validator and delegator at the end of \
+            an era",
+        );
+        builder.push_with_optional_params::<GetSupply>(
+            "returns the total supply of motes with a merkle proof, and optionally the circulating \
+            supply",
+        );
+        builder.push_with_optional_params::<GetBlock>("returns a Block from the network");
+        builder.push_with_params::<GetBlocks>(
+            "returns a contiguous range of Blocks from the network",
+        );
+        builder.push_with_params::<GetAlternativeBlocks>(
+            "returns the Blocks retained at a height which conflict with the linear chain",
+        );
+        builder.push_with_params::<GetBlockByDeploy>(
+            "returns the header of the Block on the linear chain including a Deploy, along with \
+            the Deploy's index within the Block",
+        );
+        builder.push_with_params::<GetBlockAtTimestamp>(
+            "returns the header of the most recent Block on the linear chain not later than a \
+            given timestamp, along with the header of the next Block if known",
+        );
+        builder.push_with_optional_params::<GetEraSummary>(
+            "returns the era-end report and era info of an era which has ended",
+        );
+        builder.push_with_optional_params::<GetStateRootHash>(
+            "returns a state root hash at a given Block",
+        );
+        builder.push_with_params::<GetItem>("returns a stored value from the network");
+        builder.push_with_params::<GetItems>(
+            "returns the stored values of several queries against the same state root hash",
+        );
+        builder.push_with_params::<GetBalance>("returns a purse's balance from the network");
+        builder.push_with_params::<GetBalanceHistory>(
+            "returns a purse's balance sampled at intervals over a range of Blocks",
+        );
+        builder.push_with_params::<GetAccountInfo>("returns an Account from the network");
+        builder.push_with_params::<GetKeyHistory>(
+            "returns the Deploys which wrote to a global state key, most recent first",
+        );
+        builder.push_with_params::<GetKeysWithPrefix>(
+            "returns a page of the global state keys with a given prefix, along with their stored \
+            values and proofs of inclusion",
+        );
+        builder.push_with_params::<GetContractAbi>(
+            "returns the entry points of a stored Contract, documented by the Contract's metadata \
+            if it has any",
+        );
+        builder.push_with_params::<GetTransfersTo>(
+            "returns the successful native transfers to an account or purse, oldest first",
+        );
+        builder.push_with_params::<GetDelegations>(
+            "returns the stakes of a delegator across all validators' bids, along with its \
+            pending undelegations",
+        );
+        builder.push_with_params::<GetUnbondingPurses>(
+            "returns the amounts awaiting release from the auction's unbonding queue, with their \
+            estimated release times",
+        );
+        builder.push_without_params::<GetAuctionInfo>(
+            "returns the bids and validators as of the most recently added Block",
+        );
+        builder.build()
+    };
+}
+
+/// The OpenRPC document.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct OpenRpcSchema {
+    openrpc: String,
+    info: OpenRpcInfoField,
+    methods: Vec<Method>,
+    components: Components,
+}
+
+impl OpenRpcSchema {
+    /// Returns the names of all the RPC methods described by the document.
+    pub fn method_names(&self) -> impl Iterator<Item = &str> {
+        self.methods.iter().map(|method| method.name.as_str())
+    }
+
+    /// Returns a standalone JSON Schema for the type named `type_name`, or `None` if no such type
+    /// is defined under "components".
+    ///
+    /// The schema refers to the type and its dependencies via the same "#/components/schemas/"
+    /// paths as the OpenRPC document, so it carries all the document's definitions along.
+    pub fn type_schema(&self, type_name: &str) -> Option<Value> {
+        if !self.components.schemas.contains_key(type_name) {
+            return None;
+        }
+        Some(json!({
+            "$schema": JSON_SCHEMA_VERSION,
+            "$ref": format!("{}{}", DEFINITIONS_PATH, type_name),
+            "components": self.components,
+        }))
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+struct OpenRpcInfoField {
+    version: String,
+    title: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Method {
+    name: String,
+    summary: String,
+    param_structure: String,
+    params: Vec<ContentDescriptor>,
+    result: ContentDescriptor,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+struct ContentDescriptor {
+    name: String,
+    required: bool,
+    schema: Schema,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+struct Components {
+    schemas: Map<String, Schema>,
+}
+
+/// Accumulates the methods of the OpenRPC document, sharing a single schema generator so that each
+/// type is only defined once under "components".
+struct OpenRpcSchemaBuilder {
+    generator: SchemaGenerator,
+    methods: Vec<Method>,
+}
+
+impl OpenRpcSchemaBuilder {
+    fn new() -> Self {
+        let settings = SchemaSettings::default().with(|settings| {
+            settings.definitions_path = DEFINITIONS_PATH.to_string();
+        });
+        OpenRpcSchemaBuilder {
+            generator: settings.into_generator(),
+            methods: vec![],
+        }
+    }
+
+    fn push_with_params<T: RpcWithParams>(&mut self, summary: &str) {
+        let params = self.params::<T::RequestParams>(true);
+        let result = self.result::<T::ResponseResult>(T::METHOD);
+        self.push(T::METHOD, summary, params, result);
+    }
+
+    fn push_without_params<T: RpcWithoutParams>(&mut self, summary: &str) {
+        let result = self.result::<T::ResponseResult>(T::METHOD);
+        self.push(T::METHOD, summary, vec![], result);
+    }
+
+    fn push_with_optional_params<T: RpcWithOptionalParams>(&mut self, summary: &str) {
+        let params = self.params::<T::OptionalRequestParams>(false);
+        let result = self.result::<T::ResponseResult>(T::METHOD);
+        self.push(T::METHOD, summary, params, result);
+    }
+
+    fn push(
+        &mut self,
+        name: &str,
+        summary: &str,
+        params: Vec<ContentDescriptor>,
+        result: ContentDescriptor,
+    ) {
+        self.methods.push(Method {
+            name: name.to_string(),
+            summary: summary.to_string(),
+            param_structure: "by-name".to_string(),
+            params,
+            result,
+        });
+    }
+
+    /// Splits the params type into one content descriptor per field.  If `params_required` is
+    /// false, the params as a whole may be omitted, so none of the individual fields are required.
+    fn params<T: JsonSchema>(&mut self, params_required: bool) -> Vec<ContentDescriptor> {
+        let schema_object = self.generator.root_schema_for::<T>().schema;
+        let ObjectValidation {
+            properties,
+            required,
+            ..
+        } = *schema_object
+            .object
+            .expect("params of every RPC should be a JSON object");
+        properties
+            .into_iter()
+            .map(|(name, schema)| ContentDescriptor {
+                required: params_required && required.contains(&name),
+                name,
+                schema,
+            })
+            .collect()
+    }
+
+    fn result<T: JsonSchema>(&mut self, method_name: &str) -> ContentDescriptor {
+        ContentDescriptor {
+            name: format!("{}_result", method_name),
+            required: true,
+            schema: self.generator.subschema_for::<T>(),
+        }
+    }
+
+    fn build(mut self) -> OpenRpcSchema {
+        OpenRpcSchema {
+            openrpc: OPEN_RPC_VERSION.to_string(),
+            info: OpenRpcInfoField {
+                version: CLIENT_API_VERSION.to_string(),
+                title: TITLE.to_string(),
+            },
+            methods: self.methods,
+            components: Components {
+                schemas: self.generator.take_definitions(),
+            },
+        }
+    }
+}
+
+/// Returns the HTTP response holding the OpenRPC document.
+pub(crate) fn schema_response() -> Response<Body> {
+    let body = serde_json::to_vec(&*OPEN_RPC_SCHEMA).expect("should encode OpenRPC schema");
+    Response::builder()
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body))
+        .expect("should build OpenRPC schema response")
+}
+
+/// Returns the HTTP response holding the JSON Schema of the type named `type_name`, or a 404 if no
+/// such type is defined.
+pub(crate) fn type_schema_response(type_name: &str) -> Response<Body> {
+    match OPEN_RPC_SCHEMA.type_schema(type_name) {
+        Some(schema) => {
+            let body = serde_json::to_vec(&schema).expect("should encode JSON schema");
+            Response::builder()
+                .header(header::CONTENT_TYPE, "application/schema+json")
+                .body(Body::from(body))
+                .expect("should build JSON schema response")
+        }
+        None => Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::from(format!("no schema for type '{}'", type_name)))
+            .expect("should build JSON schema response"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::PathBuf};
+
+    use super::*;
+
+    /// The golden file pinning the OpenRPC document, relative to the node crate's root.
+    const GOLDEN_SCHEMA_PATH: &str = "../resources/test/rpc_schema.json";
+    /// If set, the golden file is overwritten with the current document rather than checked.
+    const UPDATE_GOLDEN_ENV_VAR: &str = "CASPER_UPDATE_RPC_SCHEMA";
+
+    #[test]
+    fn should_describe_every_rpc() {
+        let method_names: Vec<_> = OPEN_RPC_SCHEMA.method_names().collect();
+        for method_name in &[
+            PutDeploy::METHOD,
+            CheckDeploy::METHOD,
+            GetAccountDeploys::METHOD,
+            EstimateDeployCost::METHOD,
+            GetDeploy::METHOD,
+            GetDeployReceipt::METHOD,
+            GetPeers::METHOD,
+            GetStatus::METHOD,
+            GetSignedStatus::METHOD,
+            GetChainspec::METHOD,
+            GetValidatorRewards::METHOD,
+            GetSupply::METHOD,
+            GetBlock::METHOD,
+            GetBlocks::METHOD,
+            GetAlternativeBlocks::METHOD,
+            GetBlockByDeploy::METHOD,
+            GetBlockAtTimestamp::METHOD,
+            GetEraSummary::METHOD,
+            GetStateRootHash::METHOD,
+            GetItem::METHOD,
+            GetItems::METHOD,
+            GetBalance::METHOD,
+            GetBalanceHistory::METHOD,
+            GetAccountInfo::METHOD,
+            GetKeyHistory::METHOD,
+            GetKeysWithPrefix::METHOD,
+            GetContractAbi::METHOD,
+            GetTransfersTo::METHOD,
+            GetAuctionInfo::METHOD,
+            GetDelegations::METHOD,
+            GetUnbondingPurses::METHOD,
+        ] {
+            assert!(
+                method_names.contains(method_name),
+                "{} missing",
+                method_name
+            );
+        }
+    }
+
+    #[test]
+    fn should_resolve_every_schema_reference() {
+        let json = serde_json::to_string(&*OPEN_RPC_SCHEMA).unwrap();
+        let document: Value = serde_json::from_str(&json).unwrap();
+        let definitions = document["components"]["schemas"].as_object().unwrap();
+        for reference in json.split("\"$ref\":\"").skip(1) {
+            let reference = &reference[..reference.find('"').unwrap()];
+            let name = reference.trim_start_matches(DEFINITIONS_PATH);
+            assert!(definitions.contains_key(name), "{} not defined", reference);
+        }
+    }
+
+    #[test]
+    fn should_match_golden_schema() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(GOLDEN_SCHEMA_PATH);
+        let actual = serde_json::to_value(&*OPEN_RPC_SCHEMA).unwrap();
+        if env::var_os(UPDATE_GOLDEN_ENV_VAR).is_some() {
+            let pretty = serde_json::to_string_pretty(&actual).unwrap();
+            fs::write(&path, pretty + "\n").unwrap();
+            return;
+        }
+
+        let golden_bytes = fs::read(&path).unwrap_or_else(|error| {
+            panic!(
+                "failed to read {}: {}; generate it by running the test with {} set",
+                GOLDEN_SCHEMA_PATH, error, UPDATE_GOLDEN_ENV_VAR
+            )
+        });
+        let golden: Value = serde_json::from_slice(&golden_bytes).unwrap();
+        let regenerate = format!(
+            "if the change is intentional, regenerate {} by running the test with {} set",
+            GOLDEN_SCHEMA_PATH, UPDATE_GOLDEN_ENV_VAR
+        );
+        let by_name = |document: &Value| -> Map<String, Value> {
+            document["methods"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|method| (method["name"].as_str().unwrap().to_string(), method.clone()))
+                .collect()
+        };
+        let (actual_methods, golden_methods) = (by_name(&actual), by_name(&golden));
+        for (name, method) in &actual_methods {
+            assert_eq!(
+                Some(method),
+                golden_methods.get(name),
+                "RPC {} changed; {}",
+                name,
+                regenerate
+            );
+        }
+        for name in golden_methods.keys() {
+            assert!(
+                actual_methods.contains_key(name),
+                "RPC {} removed; {}",
+                name,
+                regenerate
+            );
+        }
+
+        let actual_types = actual["components"]["schemas"].as_object().unwrap();
+        let golden_types = golden["components"]["schemas"].as_object().unwrap();
+        for (name, schema) in actual_types {
+            assert_eq!(
+                Some(schema),
+                golden_types.get(name),
+                "schema of {} changed; {}",
+                name,
+                regenerate
+            );
+        }
+        for name in golden_types.keys() {
+            assert!(
+                actual_types.contains_key(name),
+                "schema of {} removed; {}",
+                name,
+                regenerate
+            );
+        }
+        assert_eq!(actual, golden, "{}", regenerate);
+    }
+
+    #[test]
+    fn should_serve_schema_of_each_type() {
+        for type_name in &[
+            "Deploy",
+            "Block",
+            "ExecutionResult",
+            "StoredValue",
+            "AuctionState",
+            "DeployInfo",
+        ] {
+            let schema = OPEN_RPC_SCHEMA
+                .type_schema(type_name)
+                .unwrap_or_else(|| panic!("{} should be defined", type_name));
+            assert_eq!(
+                schema["$ref"],
+                Value::String(format!("{}{}", DEFINITIONS_PATH, type_name))
+            );
+            assert!(schema["components"]["schemas"][*type_name].is_object());
+        }
+        assert!(OPEN_RPC_SCHEMA.type_schema("NotAType").is_none());
+    }
+}
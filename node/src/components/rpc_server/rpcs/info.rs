@@ -1,38 +1,60 @@
 //! RPCs returning ancillary information.
 
-use std::{collections::BTreeMap, net::SocketAddr, str};
+use std::{collections::BTreeMap, convert::TryFrom, net::SocketAddr, str};
 
 use futures::{future::BoxFuture, FutureExt};
 use http::Response;
 use hyper::Body;
+use schemars::JsonSchema;
 use semver::Version;
 use serde::{Deserialize, Serialize};
 use tracing::info;
 use warp_json_rpc::Builder;
 
+use casper_execution_engine::{
+    core::engine_state::{BalanceResult, QueryResult},
+    shared::{
+        stored_value::StoredValue as ExecutionEngineStoredValue, system_config::SystemConfig,
+        wasm_config::WasmConfig,
+    },
+    storage::protocol_data::ProtocolData,
+};
+use casper_types::{
+    auction::{EraInfo, SeigniorageAllocation, ERA_INFO_KEY},
+    bytesrepr::ToBytes,
+    mint::TOTAL_SUPPLY_KEY,
+    Key, ProtocolVersion, U512,
+};
+
 use super::{
-    Error, ErrorCode, ReactorEventT, RpcRequest, RpcWithParams, RpcWithParamsExt, RpcWithoutParams,
-    RpcWithoutParamsExt,
+    chain::{self, BlockIdentifier},
+    Error, ErrorCode, ReactorEventT, RpcRequest, RpcWithOptionalParams, RpcWithOptionalParamsExt,
+    RpcWithParams, RpcWithParamsExt, RpcWithoutParams, RpcWithoutParamsExt,
 };
 use crate::{
-    components::CLIENT_API_VERSION,
+    components::{chainspec_loader::Chainspec, consensus::EraId, CLIENT_API_VERSION},
+    crypto::{
+        asymmetric_key::{self, PublicKey, Signature},
+        hash::Digest,
+    },
     effect::EffectBuilder,
     reactor::QueueKind,
     types::{
-        json_compatibility::ExecutionResult, BlockHash, Deploy, DeployHash, GetStatusResult,
-        PeersMap,
+        json_compatibility::{ExecutionResult, GasProfile},
+        signed_status_digest, BlockHash, Deploy, DeployHash, DeployReceipt, GetStatusResult,
+        PeersMap, SignedStatusPayload, TimeDiff, Timestamp,
     },
 };
 
 /// Params for "info_get_deploy" RPC request.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub struct GetDeployParams {
     /// The deploy hash.
     pub deploy_hash: DeployHash,
 }
 
 /// The execution result of a single deploy.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub struct JsonExecutionResult {
     /// The block hash.
     pub block_hash: BlockHash,
@@ -41,9 +63,10 @@ pub struct JsonExecutionResult {
 }
 
 /// Result for "info_get_deploy" RPC response.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub struct GetDeployResult {
     /// The RPC API version.
+    #[schemars(with = "String")]
     pub api_version: Version,
     /// The deploy.
     pub deploy: Deploy,
@@ -110,12 +133,627 @@ impl RpcWithParamsExt for GetDeploy {
     }
 }
 
+/// Params for "info_estimate_deploy_cost" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct EstimateDeployCostParams {
+    /// The `Deploy`.
+    pub deploy: Deploy,
+    /// Whether to break the gas consumed down by host function and opcode category.
+    #[serde(default)]
+    pub profile_gas: bool,
+}
+
+/// Result for "info_estimate_deploy_cost" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct EstimateDeployCostResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: Version,
+    /// The hash of the block against whose state the deploy was executed.
+    pub block_hash: BlockHash,
+    /// The gas consumed by the execution.
+    #[schemars(with = "String")]
+    pub gas_consumed: U512,
+    /// The execution result, previewing the effects which would be applied by the deploy.
+    pub execution_result: ExecutionResult,
+    /// The breakdown of the gas consumed, if requested.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub gas_profile: Option<GasProfile>,
+}
+
+/// "info_estimate_deploy_cost" RPC.
+///
+/// Executes the deploy against the state of the latest block.  Unlike "account_put_deploy", the
+/// deploy is neither stored nor gossiped, and the effects of the execution are never committed.
+pub struct EstimateDeployCost {}
+
+impl RpcWithParams for EstimateDeployCost {
+    const METHOD: &'static str = "info_estimate_deploy_cost";
+    type RequestParams = EstimateDeployCostParams;
+    type ResponseResult = EstimateDeployCostResult;
+}
+
+impl RpcWithParamsExt for EstimateDeployCost {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let profile_gas = params.profile_gas;
+            let mut deploy = params.deploy;
+            if !deploy.is_valid() {
+                info!("invalid {}", deploy.id());
+                return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                    ErrorCode::InvalidDeploy as i64,
+                    "deploy is not valid",
+                ))?);
+            }
+
+            // Execute the deploy against the latest state.
+            let maybe_result = effect_builder
+                .make_request(
+                    |responder| RpcRequest::EstimateDeployCost {
+                        deploy: Box::new(deploy),
+                        profile_gas,
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
+            let (block_hash, execution_result, gas_profile) = match maybe_result {
+                Some((block_hash, Ok((execution_result, gas_profile)))) => {
+                    (block_hash, execution_result, gas_profile)
+                }
+                Some((_, Err(error))) => {
+                    let error_msg = format!("failed to execute deploy: {:?}", error);
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::EstimateDeployCostFailed as i64,
+                        error_msg,
+                    ))?);
+                }
+                None => {
+                    info!("failed to get latest block from storage");
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::NoSuchBlock as i64,
+                        "no block has been added yet",
+                    ))?);
+                }
+            };
+
+            // Return the result.
+            let result = Self::ResponseResult {
+                api_version: CLIENT_API_VERSION.clone(),
+                block_hash,
+                gas_consumed: execution_result.cost(),
+                execution_result,
+                gas_profile,
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+/// The maximum number of rewards returned by a single "info_get_validator_rewards" request.
+pub const MAX_REWARDS_PER_PAGE: u64 = 1_000;
+
+/// Params for "info_get_validator_rewards" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetValidatorRewardsParams {
+    /// The ID of the era which ended.
+    pub era_id: u64,
+    /// The index of the first reward to return.
+    #[serde(default)]
+    pub offset: u64,
+    /// The maximum number of rewards to return, capped at `MAX_REWARDS_PER_PAGE`.
+    #[serde(default)]
+    pub limit: Option<u64>,
+}
+
+/// A reward credited to a validator or delegator at the end of an era.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+pub struct ValidatorReward {
+    /// The validator whose bid the reward was earned through.
+    pub validator_public_key: PublicKey,
+    /// The delegator credited, or `None` if the reward was credited to the validator itself.
+    pub delegator_public_key: Option<PublicKey>,
+    /// The amount credited.
+    #[schemars(with = "String")]
+    pub amount: U512,
+}
+
+impl ValidatorReward {
+    fn from_allocation(allocation: SeigniorageAllocation) -> Option<Self> {
+        let reward = match allocation {
+            SeigniorageAllocation::Validator {
+                validator_public_key,
+                amount,
+            } => ValidatorReward {
+                validator_public_key: PublicKey::try_from(validator_public_key).ok()?,
+                delegator_public_key: None,
+                amount,
+            },
+            SeigniorageAllocation::Delegator {
+                delegator_public_key,
+                validator_public_key,
+                amount,
+            } => ValidatorReward {
+                validator_public_key: PublicKey::try_from(validator_public_key).ok()?,
+                delegator_public_key: Some(PublicKey::try_from(delegator_public_key).ok()?),
+                amount,
+            },
+        };
+        Some(reward)
+    }
+}
+
+/// Result for "info_get_validator_rewards" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetValidatorRewardsResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: Version,
+    /// The ID of the era.
+    pub era_id: u64,
+    /// The hash of the era's switch block, as of which the rewards were read.
+    pub block_hash: BlockHash,
+    /// The total number of rewards credited at the end of the era.
+    pub total: u64,
+    /// The requested page of rewards, in the order they were credited.
+    pub rewards: Vec<ValidatorReward>,
+    /// The offset of the next page, or `None` if this is the last one.
+    pub next_offset: Option<u64>,
+}
+
+/// "info_get_validator_rewards" RPC.
+///
+/// Reads the seigniorage allocations which the auction contract recorded when distributing the
+/// rewards at the end of the given era, flattened to one entry per validator and delegator.
+pub struct GetValidatorRewards {}
+
+impl RpcWithParams for GetValidatorRewards {
+    const METHOD: &'static str = "info_get_validator_rewards";
+    type RequestParams = GetValidatorRewardsParams;
+    type ResponseResult = GetValidatorRewardsResult;
+}
+
+impl RpcWithParamsExt for GetValidatorRewards {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            // The rewards of an era are distributed when executing its switch block.
+            let maybe_switch_block = effect_builder
+                .make_request(
+                    |responder| RpcRequest::GetSwitchBlock {
+                        maybe_era_id: Some(EraId(params.era_id)),
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
+            let switch_block = match maybe_switch_block {
+                Some(switch_block) => switch_block,
+                None => {
+                    let error_msg =
+                        format!("{} has not ended or is not known", EraId(params.era_id));
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::NoSuchEraSummary as i64,
+                        error_msg,
+                    ))?);
+                }
+            };
+
+            let protocol_version = ProtocolVersion::V1_0_0;
+            let protocol_data = match effect_builder
+                .make_request(
+                    |responder| RpcRequest::QueryProtocolData {
+                        protocol_version,
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await
+            {
+                Ok(Some(protocol_data)) => protocol_data,
+                _ => Box::new(ProtocolData::default()),
+            };
+
+            // Read the era info recorded by the auction contract as of the switch block.
+            let state_root_hash = *switch_block.header().state_root_hash();
+            let query_result = effect_builder
+                .make_request(
+                    |responder| RpcRequest::QueryGlobalState {
+                        state_root_hash,
+                        base_key: protocol_data.auction().into(),
+                        path: vec![ERA_INFO_KEY.to_string()],
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
+            let value = match query_result {
+                Ok(QueryResult::Success { value, .. }) => value,
+                Ok(query_result) => {
+                    let error_msg = format!("state query failed: {:?}", query_result);
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::QueryFailed as i64,
+                        error_msg,
+                    ))?);
+                }
+                Err(error) => {
+                    let error_msg = format!("state query failed to execute: {:?}", error);
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::QueryFailedToExecute as i64,
+                        error_msg,
+                    ))?);
+                }
+            };
+
+            let maybe_era_info = value
+                .as_cl_value()
+                .and_then(|cl_value| cl_value.clone().into_t::<EraInfo>().ok());
+            let era_info = match maybe_era_info {
+                Some(era_info) => era_info,
+                None => {
+                    info!("failed to parse era info: {:?}", value);
+                    return Ok(response_builder.error(warp_json_rpc::Error::INTERNAL_ERROR)?);
+                }
+            };
+
+            // Flatten the allocations and return the requested page.
+            let rewards: Vec<ValidatorReward> = match era_info
+                .seigniorage_allocations()
+                .iter()
+                .cloned()
+                .map(ValidatorReward::from_allocation)
+                .collect()
+            {
+                Some(rewards) => rewards,
+                None => {
+                    info!("failed to convert public keys of era info");
+                    return Ok(response_builder.error(warp_json_rpc::Error::INTERNAL_ERROR)?);
+                }
+            };
+
+            let total = rewards.len() as u64;
+            let limit = params
+                .limit
+                .unwrap_or(MAX_REWARDS_PER_PAGE)
+                .min(MAX_REWARDS_PER_PAGE);
+            let start = params.offset.min(total);
+            let end = start.saturating_add(limit).min(total);
+            let next_offset = if end < total { Some(end) } else { None };
+
+            let result = Self::ResponseResult {
+                api_version: CLIENT_API_VERSION.clone(),
+                era_id: params.era_id,
+                block_hash: *switch_block.hash(),
+                total,
+                rewards: rewards[start as usize..end as usize].to_vec(),
+                next_offset,
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+/// Params for "info_get_supply" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetSupplyParams {
+    /// The block as of which the supply is read.  If `None`, the latest block is used.
+    #[serde(default)]
+    pub block_identifier: Option<BlockIdentifier>,
+    /// Whether to also calculate the circulating supply.
+    #[serde(default)]
+    pub include_circulating_supply: bool,
+}
+
+/// The balance of an account excluded from the circulating supply.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+pub struct ExcludedAccount {
+    /// The public key of the account.
+    pub public_key: PublicKey,
+    /// The balance of the account's main purse, or zero if the account doesn't exist.
+    #[schemars(with = "String")]
+    pub balance: U512,
+}
+
+/// The circulating supply, along with the balances which were subtracted from the total supply.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+pub struct CirculatingSupply {
+    /// The total supply less the balances of the excluded accounts.
+    #[schemars(with = "String")]
+    pub amount: U512,
+    /// The accounts configured on this node as holding non-circulating tokens.
+    pub excluded_accounts: Vec<ExcludedAccount>,
+}
+
+/// Result for "info_get_supply" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetSupplyResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: Version,
+    /// The hash of the block as of which the supply was read.
+    pub block_hash: BlockHash,
+    /// The state root hash against which the merkle proof is given.
+    pub state_root_hash: Digest,
+    /// The total supply of motes, as recorded by the mint.
+    #[schemars(with = "String")]
+    pub total_supply: U512,
+    /// The merkle proof of the total supply.
+    pub merkle_proof: String,
+    /// The circulating supply, if requested.
+    pub circulating_supply: Option<CirculatingSupply>,
+}
+
+/// "info_get_supply" RPC.
+///
+/// The circulating supply depends on this node's configuration of excluded accounts, and unlike
+/// the total supply, is not accompanied by a proof.
+pub struct GetSupply {}
+
+impl RpcWithOptionalParams for GetSupply {
+    const METHOD: &'static str = "info_get_supply";
+    type OptionalRequestParams = GetSupplyParams;
+    type ResponseResult = GetSupplyResult;
+}
+
+impl RpcWithOptionalParamsExt for GetSupply {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        maybe_params: Option<Self::OptionalRequestParams>,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let (maybe_block_id, include_circulating_supply) = match maybe_params {
+                Some(params) => (params.block_identifier, params.include_circulating_supply),
+                None => (None, false),
+            };
+
+            // Get the block whose state root hash will be queried.
+            let block = match chain::get_block(maybe_block_id, effect_builder).await {
+                Ok(Some(block)) => block,
+                Ok(None) => {
+                    let error_msg = "get-supply failed to get last added block".to_string();
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::NoSuchBlock as i64,
+                        error_msg,
+                    ))?);
+                }
+                Err(error) => return Ok(response_builder.error(error)?),
+            };
+            let state_root_hash = *block.header().state_root_hash();
+
+            let protocol_version = ProtocolVersion::V1_0_0;
+            let protocol_data = match effect_builder
+                .make_request(
+                    |responder| RpcRequest::QueryProtocolData {
+                        protocol_version,
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await
+            {
+                Ok(Some(protocol_data)) => protocol_data,
+                _ => Box::new(ProtocolData::default()),
+            };
+
+            // Read the total supply recorded by the mint.
+            let query_result = effect_builder
+                .make_request(
+                    |responder| RpcRequest::QueryGlobalState {
+                        state_root_hash,
+                        base_key: protocol_data.mint().into(),
+                        path: vec![TOTAL_SUPPLY_KEY.to_string()],
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
+            let (value, proofs) = match query_result {
+                Ok(QueryResult::Success { value, proofs }) => (value, proofs),
+                Ok(query_result) => {
+                    let error_msg = format!("state query failed: {:?}", query_result);
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::QueryFailed as i64,
+                        error_msg,
+                    ))?);
+                }
+                Err(error) => {
+                    let error_msg = format!("state query failed to execute: {:?}", error);
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::QueryFailedToExecute as i64,
+                        error_msg,
+                    ))?);
+                }
+            };
+
+            let maybe_total_supply = value
+                .as_cl_value()
+                .and_then(|cl_value| cl_value.clone().into_t::<U512>().ok());
+            let total_supply = match maybe_total_supply {
+                Some(total_supply) => total_supply,
+                None => {
+                    info!("failed to parse total supply: {:?}", value);
+                    return Ok(response_builder.error(warp_json_rpc::Error::INTERNAL_ERROR)?);
+                }
+            };
+
+            let proof_bytes = match proofs.to_bytes() {
+                Ok(proof_bytes) => proof_bytes,
+                Err(error) => {
+                    info!("failed to encode stored value: {}", error);
+                    return Ok(response_builder.error(warp_json_rpc::Error::INTERNAL_ERROR)?);
+                }
+            };
+
+            let circulating_supply = if include_circulating_supply {
+                let public_keys = effect_builder
+                    .make_request(
+                        |responder| RpcRequest::GetCirculatingSupplyExcludedAccounts { responder },
+                        QueueKind::Api,
+                    )
+                    .await;
+
+                let mut excluded_accounts = Vec::with_capacity(public_keys.len());
+                for public_key in public_keys {
+                    let balance = match main_purse_balance(
+                        effect_builder,
+                        state_root_hash,
+                        &public_key,
+                    )
+                    .await
+                    {
+                        Ok(balance) => balance,
+                        Err(error) => return Ok(response_builder.error(error)?),
+                    };
+                    excluded_accounts.push(ExcludedAccount {
+                        public_key,
+                        balance,
+                    });
+                }
+
+                let excluded_total = excluded_accounts
+                    .iter()
+                    .fold(U512::zero(), |total, account| {
+                        total.saturating_add(account.balance)
+                    });
+                Some(CirculatingSupply {
+                    amount: total_supply.saturating_sub(excluded_total),
+                    excluded_accounts,
+                })
+            } else {
+                None
+            };
+
+            let result = Self::ResponseResult {
+                api_version: CLIENT_API_VERSION.clone(),
+                block_hash: *block.hash(),
+                state_root_hash,
+                total_supply,
+                merkle_proof: hex::encode(proof_bytes),
+                circulating_supply,
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+/// Returns the balance of the main purse of the account with the given public key, or zero if
+/// there is no such account.
+async fn main_purse_balance<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    state_root_hash: Digest,
+    public_key: &PublicKey,
+) -> Result<U512, warp_json_rpc::Error> {
+    let account_hash = public_key.to_account_hash();
+    let query_result = effect_builder
+        .make_request(
+            |responder| RpcRequest::QueryGlobalState {
+                state_root_hash,
+                base_key: Key::Account(account_hash),
+                path: vec![],
+                responder,
+            },
+            QueueKind::Api,
+        )
+        .await;
+
+    let main_purse = match query_result {
+        Ok(QueryResult::Success { value, .. }) => match *value {
+            ExecutionEngineStoredValue::Account(account) => account.main_purse(),
+            other => {
+                let error_msg = format!(
+                    "value under {} is not an account: {:?}",
+                    account_hash, other
+                );
+                info!("{}", error_msg);
+                return Err(warp_json_rpc::Error::custom(
+                    ErrorCode::NoSuchAccount as i64,
+                    error_msg,
+                ));
+            }
+        },
+        Ok(QueryResult::ValueNotFound(_)) | Ok(QueryResult::Absent { .. }) => {
+            return Ok(U512::zero())
+        }
+        Ok(query_result) => {
+            let error_msg = format!("state query failed: {:?}", query_result);
+            info!("{}", error_msg);
+            return Err(warp_json_rpc::Error::custom(
+                ErrorCode::QueryFailed as i64,
+                error_msg,
+            ));
+        }
+        Err(error) => {
+            let error_msg = format!("state query failed to execute: {:?}", error);
+            info!("{}", error_msg);
+            return Err(warp_json_rpc::Error::custom(
+                ErrorCode::QueryFailedToExecute as i64,
+                error_msg,
+            ));
+        }
+    };
+
+    let balance_result = effect_builder
+        .make_request(
+            |responder| RpcRequest::GetBalance {
+                state_root_hash,
+                purse_uref: main_purse,
+                responder,
+            },
+            QueueKind::Api,
+        )
+        .await;
+
+    match balance_result {
+        Ok(BalanceResult::Success { motes, .. }) => Ok(motes),
+        Ok(balance_result) => {
+            let error_msg = format!("get-balance failed: {:?}", balance_result);
+            info!("{}", error_msg);
+            Err(warp_json_rpc::Error::custom(
+                ErrorCode::GetBalanceFailed as i64,
+                error_msg,
+            ))
+        }
+        Err(error) => {
+            let error_msg = format!("get-balance failed to execute: {}", error);
+            info!("{}", error_msg);
+            Err(warp_json_rpc::Error::custom(
+                ErrorCode::GetBalanceFailedToExecute as i64,
+                error_msg,
+            ))
+        }
+    }
+}
+
 /// Result for "info_get_peers" RPC response.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub struct GetPeersResult {
     /// The RPC API version.
+    #[schemars(with = "String")]
     pub api_version: Version,
     /// The node ID and network address of each connected peer.
+    #[schemars(with = "BTreeMap<String, String>")]
     pub peers: BTreeMap<String, SocketAddr>,
 }
 
@@ -181,3 +819,386 @@ impl RpcWithoutParamsExt for GetStatus {
         .boxed()
     }
 }
+
+/// The maximum length in bytes of the nonce given to "info_get_signed_status".
+const MAX_NONCE_LENGTH: usize = 128;
+
+/// Params for "info_get_signed_status" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetSignedStatusParams {
+    /// A value chosen by the client to be included in the signed payload, so that a signature
+    /// can't be replayed by another host.
+    #[serde(default)]
+    pub nonce: Option<String>,
+}
+
+/// Result for "info_get_signed_status" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetSignedStatusResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: Version,
+    /// The JSON-encoded `SignedStatusPayload`, exactly as signed.
+    pub payload: String,
+    /// The public key of the key with which the payload was signed.
+    pub public_key: PublicKey,
+    /// The signature of the payload.
+    pub signature: Signature,
+}
+
+impl GetSignedStatusResult {
+    /// Verifies the signature of the payload against `public_key`, and returns the parsed payload
+    /// if it is valid.
+    ///
+    /// Callers should also check that `public_key` is the key they expect the node to use, and that
+    /// the payload's nonce is the one they sent.
+    pub fn verify(&self) -> Result<SignedStatusPayload, String> {
+        asymmetric_key::verify(
+            signed_status_digest(self.payload.as_bytes()),
+            &self.signature,
+            &self.public_key,
+        )
+        .map_err(|error| format!("invalid signature: {}", error))?;
+        serde_json::from_str(&self.payload).map_err(|error| format!("invalid payload: {}", error))
+    }
+}
+
+/// "info_get_signed_status" RPC.
+///
+/// The status is signed with the node's current validator key, letting monitoring systems and
+/// delegators confirm they are talking to the operator's node.
+pub struct GetSignedStatus {}
+
+impl RpcWithOptionalParams for GetSignedStatus {
+    const METHOD: &'static str = "info_get_signed_status";
+    type OptionalRequestParams = GetSignedStatusParams;
+    type ResponseResult = GetSignedStatusResult;
+}
+
+impl RpcWithOptionalParamsExt for GetSignedStatus {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        maybe_params: Option<Self::OptionalRequestParams>,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let nonce = maybe_params.and_then(|params| params.nonce);
+            if let Some(nonce) = nonce.as_ref() {
+                if nonce.len() > MAX_NONCE_LENGTH {
+                    let error_msg = format!(
+                        "nonce of {} bytes exceeds the maximum of {}",
+                        nonce.len(),
+                        MAX_NONCE_LENGTH
+                    );
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::InvalidNonce as i64,
+                        error_msg,
+                    ))?);
+                }
+            }
+
+            // Get the status.
+            let status_feed = effect_builder
+                .make_request(
+                    |responder| RpcRequest::GetStatus { responder },
+                    QueueKind::Api,
+                )
+                .await;
+            let mut status = GetStatusResult::from(status_feed);
+            status.set_api_version(CLIENT_API_VERSION.clone());
+
+            // Serialize the payload once, so that exactly the signed bytes are returned.
+            let signed_status = SignedStatusPayload {
+                status,
+                nonce,
+                timestamp: Timestamp::now(),
+            };
+            let payload = match serde_json::to_string(&signed_status) {
+                Ok(payload) => payload,
+                Err(error) => {
+                    info!("failed to serialize status payload: {}", error);
+                    return Ok(response_builder.error(warp_json_rpc::Error::INTERNAL_ERROR)?);
+                }
+            };
+
+            let maybe_signature = effect_builder
+                .make_request(
+                    |responder| RpcRequest::SignStatus {
+                        payload: payload.clone().into_bytes(),
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+            let (public_key, signature) = match maybe_signature {
+                Some(public_key_and_signature) => public_key_and_signature,
+                None => {
+                    info!("failed to sign status payload");
+                    return Ok(response_builder.error(warp_json_rpc::Error::INTERNAL_ERROR)?);
+                }
+            };
+
+            let result = Self::ResponseResult {
+                api_version: CLIENT_API_VERSION.clone(),
+                payload,
+                public_key,
+                signature,
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+/// Params for "info_get_deploy_receipt" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetDeployReceiptParams {
+    /// The deploy hash.
+    pub deploy_hash: DeployHash,
+}
+
+/// Result for "info_get_deploy_receipt" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetDeployReceiptResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: Version,
+    /// The receipt signed by the node on accepting the deploy.
+    pub receipt: DeployReceipt,
+}
+
+/// "info_get_deploy_receipt" RPC.
+///
+/// Only the most recent receipts issued by "account_put_deploy" are retained, and they are lost
+/// when the node restarts.
+pub struct GetDeployReceipt {}
+
+impl RpcWithParams for GetDeployReceipt {
+    const METHOD: &'static str = "info_get_deploy_receipt";
+    type RequestParams = GetDeployReceiptParams;
+    type ResponseResult = GetDeployReceiptResult;
+}
+
+impl RpcWithParamsExt for GetDeployReceipt {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let maybe_receipt = effect_builder
+                .make_request(
+                    |responder| RpcRequest::GetDeployReceipt {
+                        deploy_hash: params.deploy_hash,
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
+            let receipt = match maybe_receipt {
+                Some(receipt) => receipt,
+                None => {
+                    info!("no receipt retained for {}", params.deploy_hash);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::NoSuchDeployReceipt as i64,
+                        "deploy receipt not known",
+                    ))?);
+                }
+            };
+
+            let result = Self::ResponseResult {
+                api_version: CLIENT_API_VERSION.clone(),
+                receipt,
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+/// The limits applied to deploys and blocks by the active chainspec.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct ChainspecDeployLimits {
+    /// The maximum payment amount, in motes.
+    #[schemars(with = "String")]
+    pub max_payment_cost: U512,
+    /// The maximum time-to-live of a deploy.
+    pub max_ttl: TimeDiff,
+    /// The maximum number of dependencies of a deploy.
+    pub max_dependencies: u8,
+    /// The maximum size of a block, in bytes.
+    pub max_block_size: u32,
+    /// The maximum number of deploys in a block.
+    pub block_max_deploy_count: u32,
+    /// The maximum amount of gas consumed by the deploys of a block.
+    pub block_gas_limit: u64,
+}
+
+/// The settings governing the eras and the auction.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct ChainspecEraSettings {
+    /// The start of the first era.
+    pub genesis_era_start_timestamp: Timestamp,
+    /// The minimum duration of an era.
+    pub era_duration: TimeDiff,
+    /// The minimum number of blocks in an era.
+    pub minimum_era_height: u64,
+    /// The duration between the booking block and the start of the era it books.
+    pub booking_duration: TimeDiff,
+    /// The duration over which the seed of an era is accumulated.
+    pub entropy_duration: TimeDiff,
+    /// The number of eras before an auction defines the set of validators.
+    pub auction_delay: u64,
+    /// The number of eras before unbonded funds are released.
+    pub locked_funds_period: u64,
+    /// The maximum number of validators.
+    pub validator_slots: u32,
+}
+
+/// A scheduled protocol upgrade.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct ChainspecUpgrade {
+    /// The height of the first block produced under the upgraded protocol.
+    pub activation_height: u64,
+    /// The protocol version after the upgrade.
+    #[schemars(with = "String")]
+    pub protocol_version: Version,
+}
+
+/// Result for "info_get_chainspec" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetChainspecResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: Version,
+    /// The hash of the whole chainspec, including the installer bytes not described here.
+    pub chainspec_hash: Digest,
+    /// The name of the chain.
+    pub name: String,
+    /// The genesis timestamp.
+    pub genesis_timestamp: Timestamp,
+    /// The protocol version active as of the highest block, along with the settings below.
+    #[schemars(with = "String")]
+    pub protocol_version: Version,
+    /// The deploy and block limits.
+    pub deploy_limits: ChainspecDeployLimits,
+    /// The era and auction settings.
+    pub era_settings: ChainspecEraSettings,
+    /// The costs of Wasm opcodes, host functions and storage.
+    #[schemars(with = "serde_json::Value")]
+    pub wasm_costs: WasmConfig,
+    /// The costs of calling the system contracts.
+    #[schemars(with = "serde_json::Value")]
+    pub system_costs: SystemConfig,
+    /// Every upgrade in the chainspec, whether already activated or not.
+    pub upgrades: Vec<ChainspecUpgrade>,
+}
+
+impl GetChainspecResult {
+    /// Describes the chainspec as of the given block height, i.e. with the settings of every
+    /// upgrade activated at or below that height applied.
+    fn new(chainspec: &Chainspec, block_height: u64) -> Self {
+        let genesis = &chainspec.genesis;
+        let mut protocol_version = genesis.protocol_version.clone();
+        let mut deploy_config = genesis.deploy_config;
+        let mut wasm_config = genesis.wasm_config;
+        let mut system_config = genesis.system_config;
+        let mut validator_slots = genesis.validator_slots;
+        for upgrade in chainspec
+            .upgrades
+            .iter()
+            .take_while(|upgrade| upgrade.activation_point.height <= block_height)
+        {
+            protocol_version = upgrade.protocol_version.clone();
+            deploy_config = upgrade.new_deploy_config.unwrap_or(deploy_config);
+            wasm_config = upgrade.new_wasm_config.unwrap_or(wasm_config);
+            system_config = upgrade.new_system_config.unwrap_or(system_config);
+            validator_slots = upgrade.new_validator_slots.unwrap_or(validator_slots);
+        }
+
+        let highway_config = &genesis.highway_config;
+        GetChainspecResult {
+            api_version: CLIENT_API_VERSION.clone(),
+            chainspec_hash: chainspec.hash(),
+            name: genesis.name.clone(),
+            genesis_timestamp: genesis.timestamp,
+            protocol_version,
+            deploy_limits: ChainspecDeployLimits {
+                max_payment_cost: deploy_config.max_payment_cost.value(),
+                max_ttl: deploy_config.max_ttl,
+                max_dependencies: deploy_config.max_dependencies,
+                max_block_size: deploy_config.max_block_size,
+                block_max_deploy_count: deploy_config.block_max_deploy_count,
+                block_gas_limit: deploy_config.block_gas_limit,
+            },
+            era_settings: ChainspecEraSettings {
+                genesis_era_start_timestamp: highway_config.genesis_era_start_timestamp,
+                era_duration: highway_config.era_duration,
+                minimum_era_height: highway_config.minimum_era_height,
+                booking_duration: highway_config.booking_duration,
+                entropy_duration: highway_config.entropy_duration,
+                auction_delay: genesis.auction_delay,
+                locked_funds_period: genesis.locked_funds_period,
+                validator_slots,
+            },
+            wasm_costs: wasm_config,
+            system_costs: system_config,
+            upgrades: chainspec
+                .upgrades
+                .iter()
+                .map(|upgrade| ChainspecUpgrade {
+                    activation_height: upgrade.activation_point.height,
+                    protocol_version: upgrade.protocol_version.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// "info_get_chainspec" RPC.
+pub struct GetChainspec {}
+
+impl RpcWithoutParams for GetChainspec {
+    const METHOD: &'static str = "info_get_chainspec";
+    type ResponseResult = GetChainspecResult;
+}
+
+impl RpcWithoutParamsExt for GetChainspec {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let maybe_chainspec = effect_builder
+                .make_request(
+                    |responder| RpcRequest::GetChainspec { responder },
+                    QueueKind::Api,
+                )
+                .await;
+            let chainspec = match maybe_chainspec {
+                Some(chainspec) => chainspec,
+                None => {
+                    info!("failed to get chainspec");
+                    return Ok(response_builder.error(warp_json_rpc::Error::INTERNAL_ERROR)?);
+                }
+            };
+
+            let maybe_highest_block = effect_builder
+                .make_request(
+                    |responder| RpcRequest::GetBlock {
+                        maybe_id: None,
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+            let block_height = maybe_highest_block.map_or(0, |block| block.height());
+
+            let result = GetChainspecResult::new(&chainspec, block_height);
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
@@ -5,32 +5,66 @@ use std::{convert::TryFrom, str};
 use futures::{future::BoxFuture, FutureExt};
 use http::Response;
 use hyper::Body;
+use schemars::JsonSchema;
 use semver::Version;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info};
 use warp_json_rpc::Builder;
 
 use casper_execution_engine::{
-    core::engine_state::{BalanceResult, QueryResult},
+    core::{
+        engine_state::{self, BalanceResult, KeysWithPrefixResult, QueryBatchResult, QueryResult},
+        execution,
+    },
+    shared::{account::Account, stored_value::StoredValue as ExecutionEngineStoredValue},
     storage::protocol_data::ProtocolData,
 };
-use casper_types::{bytesrepr::ToBytes, Key, ProtocolVersion, URef, U512};
+use casper_types::{
+    account::AccountHash,
+    auction::{
+        Bids, DelegationRate, EraId, UnbondingPurse, UnbondingPurses, BIDS_KEY,
+        UNBONDING_PURSES_KEY,
+    },
+    bytesrepr::{FromBytes, ToBytes},
+    CLTyped, ContractMetadata, Key, ProtocolVersion, URef, CONTRACT_METADATA_KEY, U512,
+};
 
-use super::{Error, ErrorCode, ReactorEventT, RpcRequest, RpcWithParams, RpcWithParamsExt};
+use super::{
+    chain::{self, BlockIdentifier},
+    Error, ErrorCode, ReactorEventT, RpcRequest, RpcWithParams, RpcWithParamsExt,
+};
 use crate::{
     components::CLIENT_API_VERSION,
-    crypto::hash::Digest,
+    crypto::{asymmetric_key::PublicKey, hash::Digest},
     effect::EffectBuilder,
     reactor::QueueKind,
     rpcs::{RpcWithoutParams, RpcWithoutParamsExt},
     types::{
-        json_compatibility::{AuctionState, StoredValue},
-        Block,
+        json_compatibility::{AuctionState, ContractAbi, StoredValue},
+        Block, BlockHash, KeyHistoryEntry, TimeDiff, Timestamp, TransferEntry,
     },
 };
 
+/// Error code returned by "state_get_item" if the state root hash isn't that of a block within the
+/// node's historical state window.  The error message holds the height of the earliest block whose
+/// global state can be queried, which can be extracted via `earliest_queryable_block_height()`.
+pub const STATE_ROOT_OUTSIDE_WINDOW_ERROR_CODE: i64 = ErrorCode::StateRootOutsideWindow as i64;
+
+const STATE_ROOT_OUTSIDE_WINDOW_PREFIX: &str =
+    "state root hash outside the historical state window, earliest queryable block height ";
+
+/// Returns the height of the earliest queryable block held in the message of a "state root hash
+/// outside the historical state window" error returned by "state_get_item", or `None` if the
+/// message isn't of that form.
+pub fn earliest_queryable_block_height(error_message: &str) -> Option<u64> {
+    error_message
+        .strip_prefix(STATE_ROOT_OUTSIDE_WINDOW_PREFIX)?
+        .parse()
+        .ok()
+}
+
 /// Params for "state_get_item" RPC request.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub struct GetItemParams {
     /// Hash of the state root.
     pub state_root_hash: Digest,
@@ -41,14 +75,20 @@ pub struct GetItemParams {
 }
 
 /// Result for "state_get_item" RPC response.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub struct GetItemResult {
     /// The RPC API version.
+    #[schemars(with = "String")]
     pub api_version: Version,
-    /// The stored value.
-    pub stored_value: StoredValue,
-    /// The merkle proof.
+    /// The stored value.  `None` if the queried key is absent from global state.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub stored_value: Option<StoredValue>,
+    /// The merkle proof.  If the queried key is absent, this holds the proofs of the values
+    /// traversed by the path before reaching it.
     pub merkle_proof: String,
+    /// The merkle proof of the absence of the queried key from global state.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub merkle_proof_of_absence: Option<String>,
 }
 
 /// "state_get_item" RPC.
@@ -81,11 +121,34 @@ impl RpcWithParamsExt for GetItem {
                 }
             };
 
+            // Refuse to query state roots outside the historical state window.
+            let state_root_hash = params.state_root_hash;
+            if let Err(window) = effect_builder
+                .make_request(
+                    |responder| RpcRequest::CheckHistoricalState {
+                        state_root_hash,
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await
+            {
+                let error_msg = format!(
+                    "{}{}",
+                    STATE_ROOT_OUTSIDE_WINDOW_PREFIX, window.earliest_block_height
+                );
+                info!("{}", error_msg);
+                return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                    STATE_ROOT_OUTSIDE_WINDOW_ERROR_CODE,
+                    error_msg,
+                ))?);
+            }
+
             // Run the query.
             let query_result = effect_builder
                 .make_request(
                     |responder| RpcRequest::QueryGlobalState {
-                        state_root_hash: params.state_root_hash,
+                        state_root_hash,
                         base_key,
                         path: params.path,
                         responder,
@@ -95,9 +158,13 @@ impl RpcWithParamsExt for GetItem {
                 .await;
 
             // Extract the EE `(StoredValue, Vec<TrieMerkleProof<Key, StoredValue>>)` from the
-            // result.
-            let (value, proof) = match query_result {
-                Ok(QueryResult::Success { value, proofs }) => (value, proofs),
+            // result, or the proof of absence if the queried key doesn't exist.
+            let (maybe_value, proof, maybe_proof_of_absence) = match query_result {
+                Ok(QueryResult::Success { value, proofs }) => (Some(value), proofs, None),
+                Ok(QueryResult::Absent {
+                    proofs,
+                    proof_of_absence,
+                }) => (None, proofs, Some(proof_of_absence)),
                 Ok(query_result) => {
                     let error_msg = format!("state query failed: {:?}", query_result);
                     info!("{}", error_msg);
@@ -116,7 +183,10 @@ impl RpcWithParamsExt for GetItem {
                 }
             };
 
-            let value_compat = match StoredValue::try_from(&*value) {
+            let value_compat = match maybe_value
+                .map(|value| StoredValue::try_from(&*value))
+                .transpose()
+            {
                 Ok(value_compat) => value_compat,
                 Err(error) => {
                     info!("failed to encode stored value: {}", error);
@@ -132,12 +202,238 @@ impl RpcWithParamsExt for GetItem {
                 }
             };
 
+            let proof_of_absence_bytes = match maybe_proof_of_absence
+                .map(|proof_of_absence| proof_of_absence.to_bytes())
+                .transpose()
+            {
+                Ok(proof_of_absence_bytes) => proof_of_absence_bytes,
+                Err(error) => {
+                    info!("failed to encode proof of absence: {}", error);
+                    return Ok(response_builder.error(warp_json_rpc::Error::INTERNAL_ERROR)?);
+                }
+            };
+
             let result = Self::ResponseResult {
                 api_version: CLIENT_API_VERSION.clone(),
                 stored_value: value_compat,
                 merkle_proof: hex::encode(proof_bytes),
+                merkle_proof_of_absence: proof_of_absence_bytes.map(hex::encode),
+            };
+
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+// items
+
+/// The maximum number of queries in a single "state_get_items" request.
+pub const MAX_ITEM_QUERIES: usize = 100;
+
+/// A single query of a "state_get_items" request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct ItemQuery {
+    /// `casper_types::Key` as formatted string.
+    pub key: String,
+    /// The path components starting from the key as base.
+    #[serde(default)]
+    pub path: Vec<String>,
+}
+
+/// Params for "state_get_items" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetItemsParams {
+    /// Hash of the state root.
+    pub state_root_hash: Digest,
+    /// The queries, at most 100.
+    pub queries: Vec<ItemQuery>,
+}
+
+/// The result of a single query of a "state_get_items" request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct ItemQueryResult {
+    /// The stored value.  `None` if the queried key is absent from global state or the query
+    /// failed.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub stored_value: Option<StoredValue>,
+    /// The merkle proof, as for "state_get_item".  `None` if the query failed.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub merkle_proof: Option<String>,
+    /// The merkle proof of the absence of the queried key from global state.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub merkle_proof_of_absence: Option<String>,
+    /// The reason the query failed, e.g. a name in the path which doesn't exist.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub error: Option<String>,
+}
+
+impl ItemQueryResult {
+    /// Encodes the result of a query, or returns why it couldn't be encoded.
+    fn new(query_result: QueryResult) -> Result<Self, String> {
+        let (maybe_value, proof, maybe_proof_of_absence) = match query_result {
+            QueryResult::Success { value, proofs } => (Some(value), proofs, None),
+            QueryResult::Absent {
+                proofs,
+                proof_of_absence,
+            } => (None, proofs, Some(proof_of_absence)),
+            query_result => {
+                return Ok(ItemQueryResult {
+                    stored_value: None,
+                    merkle_proof: None,
+                    merkle_proof_of_absence: None,
+                    error: Some(format!("state query failed: {:?}", query_result)),
+                })
+            }
+        };
+
+        let stored_value = maybe_value
+            .map(|value| StoredValue::try_from(&*value))
+            .transpose()
+            .map_err(|error| format!("failed to encode stored value: {}", error))?;
+        let proof_bytes = proof
+            .to_bytes()
+            .map_err(|error| format!("failed to encode merkle proof: {}", error))?;
+        let proof_of_absence_bytes = maybe_proof_of_absence
+            .map(|proof_of_absence| proof_of_absence.to_bytes())
+            .transpose()
+            .map_err(|error| format!("failed to encode proof of absence: {}", error))?;
+
+        Ok(ItemQueryResult {
+            stored_value,
+            merkle_proof: Some(hex::encode(proof_bytes)),
+            merkle_proof_of_absence: proof_of_absence_bytes.map(hex::encode),
+            error: None,
+        })
+    }
+}
+
+/// Result for "state_get_items" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetItemsResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: Version,
+    /// The result of each query, in the order of the queries in the request.
+    pub items: Vec<ItemQueryResult>,
+}
+
+/// "state_get_items" RPC.
+pub struct GetItems {}
+
+impl RpcWithParams for GetItems {
+    const METHOD: &'static str = "state_get_items";
+    type RequestParams = GetItemsParams;
+    type ResponseResult = GetItemsResult;
+}
+
+impl RpcWithParamsExt for GetItems {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            if params.queries.len() > MAX_ITEM_QUERIES {
+                let error_msg = format!(
+                    "too many queries: {}, the maximum is {}",
+                    params.queries.len(),
+                    MAX_ITEM_QUERIES
+                );
+                info!("{}", error_msg);
+                return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                    ErrorCode::QueryBatchTooLarge as i64,
+                    error_msg,
+                ))?);
+            }
+
+            // Try to parse a `casper_types::Key` from each query.
+            let mut queries = Vec::with_capacity(params.queries.len());
+            for (index, query) in params.queries.into_iter().enumerate() {
+                match Key::from_formatted_str(&query.key) {
+                    Ok(key) => queries.push((key, query.path)),
+                    Err(error) => {
+                        let error_msg =
+                            format!("failed to parse key of query {}: {:?}", index, error);
+                        info!("{}", error_msg);
+                        return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                            ErrorCode::ParseQueryKey as i64,
+                            error_msg,
+                        ))?);
+                    }
+                }
+            }
+
+            // Refuse to query state roots outside the historical state window.
+            let state_root_hash = params.state_root_hash;
+            if let Err(window) = effect_builder
+                .make_request(
+                    |responder| RpcRequest::CheckHistoricalState {
+                        state_root_hash,
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await
+            {
+                let error_msg = format!(
+                    "{}{}",
+                    STATE_ROOT_OUTSIDE_WINDOW_PREFIX, window.earliest_block_height
+                );
+                info!("{}", error_msg);
+                return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                    STATE_ROOT_OUTSIDE_WINDOW_ERROR_CODE,
+                    error_msg,
+                ))?);
+            }
+
+            // Run the queries.
+            let batch_result = effect_builder
+                .make_request(
+                    |responder| RpcRequest::QueryGlobalStateBatch {
+                        state_root_hash,
+                        queries,
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
+            let query_results = match batch_result {
+                Ok(QueryBatchResult::Success(query_results)) => query_results,
+                Ok(QueryBatchResult::RootNotFound) => {
+                    let error_msg = "state query failed: root not found".to_string();
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::QueryFailed as i64,
+                        error_msg,
+                    ))?);
+                }
+                Err(error) => {
+                    let error_msg = format!("state query failed to execute: {:?}", error);
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::QueryFailedToExecute as i64,
+                        error_msg,
+                    ))?);
+                }
             };
 
+            let mut items = Vec::with_capacity(query_results.len());
+            for query_result in query_results {
+                match ItemQueryResult::new(query_result) {
+                    Ok(item) => items.push(item),
+                    Err(error_msg) => {
+                        info!("{}", error_msg);
+                        return Ok(response_builder.error(warp_json_rpc::Error::INTERNAL_ERROR)?);
+                    }
+                }
+            }
+
+            let result = Self::ResponseResult {
+                api_version: CLIENT_API_VERSION.clone(),
+                items,
+            };
             Ok(response_builder.success(result)?)
         }
         .boxed()
@@ -145,7 +441,7 @@ impl RpcWithParamsExt for GetItem {
 }
 
 /// Params for "state_get_balance" RPC request.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub struct GetBalanceParams {
     /// The hash of state root.
     pub state_root_hash: Digest,
@@ -154,11 +450,13 @@ pub struct GetBalanceParams {
 }
 
 /// Result for "state_get_balance" RPC response.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub struct GetBalanceResult {
     /// The RPC API version.
+    #[schemars(with = "String")]
     pub api_version: Version,
     /// The balance value.
+    #[schemars(with = "String")]
     pub balance_value: U512,
     /// The merkle proof.
     pub merkle_proof: String,
@@ -252,122 +550,1489 @@ impl RpcWithParamsExt for GetBalance {
     }
 }
 
-// auction info
+/// Params for "state_get_account_info" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetAccountInfoParams {
+    /// The account, given either as a hex-encoded public key or as a formatted account hash.
+    pub account_identifier: String,
+    /// The block at whose state root the account is read.  If `None`, the latest block is used.
+    pub block_identifier: Option<BlockIdentifier>,
+}
 
-/// Result for "state_get_auction_info" RPC response.
-#[derive(Serialize, Deserialize, Debug)]
-pub struct GetAuctionInfoResult {
+/// Result for "state_get_account_info" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetAccountInfoResult {
     /// The RPC API version.
+    #[schemars(with = "String")]
     pub api_version: Version,
-    /// The auction state.
-    pub auction_state: AuctionState,
+    /// The hash of the block at which the account was read.
+    pub block_hash: BlockHash,
+    /// The state root hash against which the merkle proof is given.
+    pub state_root_hash: Digest,
+    /// The account.
+    #[schemars(with = "serde_json::Value")]
+    pub account: Account,
+    /// The merkle proof.
+    pub merkle_proof: String,
 }
 
-/// "state_get_auction_info" RPC.
-pub struct GetAuctionInfo {}
+/// "state_get_account_info" RPC.
+pub struct GetAccountInfo {}
 
-impl RpcWithoutParams for GetAuctionInfo {
-    const METHOD: &'static str = "state_get_auction_info";
-    type ResponseResult = GetAuctionInfoResult;
+impl RpcWithParams for GetAccountInfo {
+    const METHOD: &'static str = "state_get_account_info";
+    type RequestParams = GetAccountInfoParams;
+    type ResponseResult = GetAccountInfoResult;
 }
 
-impl RpcWithoutParamsExt for GetAuctionInfo {
+impl RpcWithParamsExt for GetAccountInfo {
     fn handle_request<REv: ReactorEventT>(
         effect_builder: EffectBuilder<REv>,
         response_builder: Builder,
+        params: Self::RequestParams,
     ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
         async move {
-            let block: Block = {
-                let maybe_block = effect_builder
-                    .make_request(
-                        |responder| RpcRequest::GetBlock {
-                            maybe_id: None,
-                            responder,
-                        },
-                        QueueKind::Api,
-                    )
-                    .await;
-
-                match maybe_block {
-                    None => {
-                        let error_msg =
-                            "get-auction-info failed to get last added block".to_string();
-                        info!("{}", error_msg);
-                        return Ok(response_builder.error(warp_json_rpc::Error::custom(
-                            ErrorCode::NoSuchBlock as i64,
-                            error_msg,
-                        ))?);
-                    }
-                    Some(block) => block,
+            // Try to parse the account hash from the params.
+            let account_hash = match parse_account_identifier(&params.account_identifier) {
+                Ok(account_hash) => account_hash,
+                Err(error_msg) => {
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::ParseAccountIdentifier as i64,
+                        error_msg,
+                    ))?);
                 }
             };
 
-            let protocol_version = ProtocolVersion::V1_0_0;
-            let protocol_version_result = effect_builder
-                .make_request(
-                    |responder| RpcRequest::QueryProtocolData {
-                        protocol_version,
-                        responder,
-                    },
-                    QueueKind::Api,
-                )
-                .await;
-
-            let protocol_data = {
-                if let Ok(Some(protocol_data)) = protocol_version_result {
-                    protocol_data
-                } else {
-                    Box::new(ProtocolData::default())
+            // Get the block whose state root hash will be queried.
+            let block = match chain::get_block(params.block_identifier, effect_builder).await {
+                Ok(Some(block)) => block,
+                Ok(None) => {
+                    let error_msg = "get-account-info failed to get last added block".to_string();
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::NoSuchBlock as i64,
+                        error_msg,
+                    ))?);
                 }
+                Err(error) => return Ok(response_builder.error(error)?),
             };
-
-            // auction contract key
-            let base_key = protocol_data.auction().into();
-            // bids named key in auction contract
-            let path = vec![casper_types::auction::BIDS_KEY.to_string()];
-            // the global state hash of the last block
             let state_root_hash = *block.header().state_root_hash();
-            // the block height of the last added block
-            let block_height = block.header().height();
 
+            // Run the query.
             let query_result = effect_builder
                 .make_request(
                     |responder| RpcRequest::QueryGlobalState {
                         state_root_hash,
-                        base_key,
-                        path,
+                        base_key: Key::Account(account_hash),
+                        path: vec![],
                         responder,
                     },
                     QueueKind::Api,
                 )
                 .await;
 
-            let bids = if let Ok(QueryResult::Success { value, .. }) = query_result {
-                value
-                    .as_cl_value()
-                    .and_then(|cl_value| cl_value.to_owned().into_t().ok())
-            } else {
-                None
+            let (value, proofs) = match query_result {
+                Ok(QueryResult::Success { value, proofs }) => (value, proofs),
+                Ok(QueryResult::ValueNotFound(_))
+                | Ok(QueryResult::Absent { .. })
+                | Ok(QueryResult::RootNotFound) => {
+                    let error_msg = format!(
+                        "account {} not known at state root {}",
+                        account_hash, state_root_hash
+                    );
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::NoSuchAccount as i64,
+                        error_msg,
+                    ))?);
+                }
+                Ok(query_result) => {
+                    let error_msg = format!("state query failed: {:?}", query_result);
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::QueryFailed as i64,
+                        error_msg,
+                    ))?);
+                }
+                Err(error) => {
+                    let error_msg = format!("state query failed to execute: {:?}", error);
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::QueryFailedToExecute as i64,
+                        error_msg,
+                    ))?);
+                }
             };
 
-            let era_validators_result = effect_builder
-                .make_request(
-                    |responder| RpcRequest::QueryEraValidators {
-                        state_root_hash,
-                        protocol_version,
-                        responder,
-                    },
-                    QueueKind::Api,
-                )
-                .await;
+            let account = match *value {
+                ExecutionEngineStoredValue::Account(account) => account,
+                other => {
+                    let error_msg = format!(
+                        "value under {} is not an account: {:?}",
+                        account_hash, other
+                    );
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::NoSuchAccount as i64,
+                        error_msg,
+                    ))?);
+                }
+            };
 
-            let era_validators = era_validators_result.ok();
+            let proof_bytes = match proofs.to_bytes() {
+                Ok(proof_bytes) => proof_bytes,
+                Err(error) => {
+                    info!("failed to encode stored value: {}", error);
+                    return Ok(response_builder.error(warp_json_rpc::Error::INTERNAL_ERROR)?);
+                }
+            };
 
-            let auction_state =
-                AuctionState::new(state_root_hash, block_height, era_validators, bids);
-            debug!("responding to client with: {:?}", auction_state);
-            Ok(response_builder.success(auction_state)?)
+            let result = Self::ResponseResult {
+                api_version: CLIENT_API_VERSION.clone(),
+                block_hash: *block.hash(),
+                state_root_hash,
+                account,
+                merkle_proof: hex::encode(proof_bytes),
+            };
+            Ok(response_builder.success(result)?)
         }
         .boxed()
     }
 }
+
+/// Parses an account identifier, given either as a hex-encoded public key or as a formatted
+/// account hash.
+pub(super) fn parse_account_identifier(account_identifier: &str) -> Result<AccountHash, String> {
+    if let Ok(public_key) = PublicKey::from_hex(account_identifier) {
+        return Ok(public_key.to_account_hash());
+    }
+    AccountHash::from_formatted_str(account_identifier).map_err(|error| {
+        format!(
+            "failed to parse account identifier as a public key or account hash: {:?}",
+            error
+        )
+    })
+}
+
+// key history
+
+/// The number of entries returned by a single "state_get_key_history" request.
+pub const KEY_HISTORY_PAGE_SIZE: u64 = 100;
+
+/// Params for "state_get_key_history" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetKeyHistoryParams {
+    /// `casper_types::Key` as formatted string.
+    pub key: String,
+    /// The page of the history to retrieve, where page 0 holds the most recent entries.
+    #[serde(default)]
+    pub page: u64,
+}
+
+/// Result for "state_get_key_history" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetKeyHistoryResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: Version,
+    /// The total number of deploys recorded as having written to the key.
+    pub total_entries: u64,
+    /// The deploys which wrote to the key, along with the blocks in which they were executed, most
+    /// recent first.
+    pub entries: Vec<KeyHistoryEntry>,
+}
+
+/// "state_get_key_history" RPC.
+pub struct GetKeyHistory {}
+
+impl RpcWithParams for GetKeyHistory {
+    const METHOD: &'static str = "state_get_key_history";
+    type RequestParams = GetKeyHistoryParams;
+    type ResponseResult = GetKeyHistoryResult;
+}
+
+impl RpcWithParamsExt for GetKeyHistory {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            // Try to parse a `casper_types::Key` from the params.
+            let key = match Key::from_formatted_str(&params.key)
+                .map_err(|error| format!("failed to parse key: {:?}", error))
+            {
+                Ok(key) => key.normalize(),
+                Err(error_msg) => {
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::ParseQueryKey as i64,
+                        error_msg,
+                    ))?);
+                }
+            };
+
+            let (total_entries, entries) = effect_builder
+                .make_request(
+                    |responder| RpcRequest::GetKeyHistory {
+                        key,
+                        offset: params.page.saturating_mul(KEY_HISTORY_PAGE_SIZE),
+                        count: KEY_HISTORY_PAGE_SIZE,
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
+            let result = Self::ResponseResult {
+                api_version: CLIENT_API_VERSION.clone(),
+                total_entries,
+                entries,
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+// keys with prefix
+
+/// The number of keys returned by a single "state_get_keys_with_prefix" request.
+pub const KEYS_WITH_PREFIX_PAGE_SIZE: u64 = 100;
+
+/// Params for "state_get_keys_with_prefix" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetKeysWithPrefixParams {
+    /// Hash of the state root.
+    pub state_root_hash: Digest,
+    /// The hex-encoded prefix of the serialized keys to return, e.g. "03" for all transfers.  An
+    /// empty prefix matches every key.
+    pub prefix: String,
+    /// The page of the matching keys to retrieve, where page 0 holds the first ones in the
+    /// lexicographic order of their serialized representation.
+    #[serde(default)]
+    pub page: u64,
+}
+
+/// A key matching the prefix, along with its stored value.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct KeyWithProof {
+    /// `casper_types::Key` as formatted string.
+    pub key: String,
+    /// The stored value.
+    pub stored_value: StoredValue,
+    /// The merkle proof of the inclusion of the key and value in the state.
+    pub merkle_proof: String,
+}
+
+/// Result for "state_get_keys_with_prefix" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetKeysWithPrefixResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: Version,
+    /// The matching keys of the requested page.  A page holding fewer than the page size of 100
+    /// keys is the last one.
+    pub keys: Vec<KeyWithProof>,
+}
+
+/// "state_get_keys_with_prefix" RPC.
+pub struct GetKeysWithPrefix {}
+
+impl RpcWithParams for GetKeysWithPrefix {
+    const METHOD: &'static str = "state_get_keys_with_prefix";
+    type RequestParams = GetKeysWithPrefixParams;
+    type ResponseResult = GetKeysWithPrefixResult;
+}
+
+impl RpcWithParamsExt for GetKeysWithPrefix {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            // Try to parse the prefix from the params.
+            let prefix = match hex::decode(&params.prefix)
+                .map_err(|error| format!("failed to parse prefix: {}", error))
+            {
+                Ok(prefix) => prefix,
+                Err(error_msg) => {
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::ParseKeyPrefix as i64,
+                        error_msg,
+                    ))?);
+                }
+            };
+
+            let keys_result = effect_builder
+                .make_request(
+                    |responder| RpcRequest::GetKeysWithPrefix {
+                        state_root_hash: params.state_root_hash,
+                        prefix,
+                        offset: params.page.saturating_mul(KEYS_WITH_PREFIX_PAGE_SIZE),
+                        count: KEYS_WITH_PREFIX_PAGE_SIZE,
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
+            let proofs = match keys_result {
+                Ok(KeysWithPrefixResult::Success { proofs }) => proofs,
+                Ok(KeysWithPrefixResult::RootNotFound) => {
+                    let error_msg = "state query failed: root not found".to_string();
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::QueryFailed as i64,
+                        error_msg,
+                    ))?);
+                }
+                Err(error) => {
+                    let error_msg = format!("state query failed to execute: {:?}", error);
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::QueryFailedToExecute as i64,
+                        error_msg,
+                    ))?);
+                }
+            };
+
+            let mut keys = Vec::with_capacity(proofs.len());
+            for proof in proofs {
+                let stored_value = match StoredValue::try_from(proof.value()) {
+                    Ok(stored_value) => stored_value,
+                    Err(error) => {
+                        info!("failed to encode stored value: {}", error);
+                        return Ok(response_builder.error(warp_json_rpc::Error::INTERNAL_ERROR)?);
+                    }
+                };
+                let proof_bytes = match proof.to_bytes() {
+                    Ok(proof_bytes) => proof_bytes,
+                    Err(error) => {
+                        info!("failed to encode merkle proof: {}", error);
+                        return Ok(response_builder.error(warp_json_rpc::Error::INTERNAL_ERROR)?);
+                    }
+                };
+                keys.push(KeyWithProof {
+                    key: proof.key().to_formatted_string(),
+                    stored_value,
+                    merkle_proof: hex::encode(proof_bytes),
+                });
+            }
+
+            let result = Self::ResponseResult {
+                api_version: CLIENT_API_VERSION.clone(),
+                keys,
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+// balance history
+
+/// The maximum number of samples returned by a single "state_get_balance_history" request.
+pub const MAX_BALANCE_HISTORY_SAMPLES: u64 = 100;
+
+/// Params for "state_get_balance_history" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetBalanceHistoryParams {
+    /// Formatted URef.
+    pub purse_uref: String,
+    /// The height of the first block at which to sample the balance.
+    pub from_height: u64,
+    /// The height beyond which no samples are taken (inclusive).
+    pub to_height: u64,
+    /// The number of blocks between consecutive samples.  Must be at least 1.
+    pub step: u64,
+    /// Whether to include the merkle proof of each sampled balance.
+    #[serde(default)]
+    pub include_proofs: bool,
+}
+
+/// A purse's balance as of a given block.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct BalanceSample {
+    /// The height of the block.
+    pub block_height: u64,
+    /// The hash of the block.
+    pub block_hash: BlockHash,
+    /// The state root hash of the block, against which the merkle proof is given.
+    pub state_root_hash: Digest,
+    /// The balance, or `None` if the purse didn't exist as of the block.
+    #[schemars(with = "Option<String>")]
+    pub balance_value: Option<U512>,
+    /// Whether the node retains the global state as of the block.  If not, no balance is given.
+    pub state_retained: bool,
+    /// The merkle proof of the balance, if requested and a balance is given.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub merkle_proof: Option<String>,
+}
+
+/// Result for "state_get_balance_history" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetBalanceHistoryResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: Version,
+    /// The samples, in ascending order of block height.
+    ///
+    /// The samples stop short of the requested range at the first block not yet known to the
+    /// node.
+    pub samples: Vec<BalanceSample>,
+    /// The height at which to continue if the samples were cut short by the page size.
+    pub next_height: Option<u64>,
+}
+
+/// "state_get_balance_history" RPC.
+pub struct GetBalanceHistory {}
+
+impl RpcWithParams for GetBalanceHistory {
+    const METHOD: &'static str = "state_get_balance_history";
+    type RequestParams = GetBalanceHistoryParams;
+    type ResponseResult = GetBalanceHistoryResult;
+}
+
+impl RpcWithParamsExt for GetBalanceHistory {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            // Try to parse the purse's URef from the params.
+            let purse_uref = match URef::from_formatted_str(&params.purse_uref)
+                .map_err(|error| format!("failed to parse purse_uref: {:?}", error))
+            {
+                Ok(uref) => uref,
+                Err(error_msg) => {
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::ParseGetBalanceURef as i64,
+                        error_msg,
+                    ))?);
+                }
+            };
+
+            if params.to_height < params.from_height || params.step == 0 {
+                let error_msg = format!(
+                    "invalid block range: heights {} to {} with step {}",
+                    params.from_height, params.to_height, params.step
+                );
+                info!("{}", error_msg);
+                return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                    ErrorCode::InvalidBlockRange as i64,
+                    error_msg,
+                ))?);
+            }
+
+            let mut samples = Vec::new();
+            let mut maybe_height = Some(params.from_height);
+            while let Some(height) = maybe_height {
+                if height > params.to_height || samples.len() as u64 == MAX_BALANCE_HISTORY_SAMPLES
+                {
+                    break;
+                }
+
+                let maybe_block = effect_builder
+                    .make_request(
+                        |responder| RpcRequest::GetBlock {
+                            maybe_id: Some(BlockIdentifier::Height(height)),
+                            responder,
+                        },
+                        QueueKind::Api,
+                    )
+                    .await;
+                let block = match maybe_block {
+                    Some(block) => block,
+                    None => {
+                        maybe_height = None;
+                        break;
+                    }
+                };
+                let state_root_hash = *block.state_root_hash();
+
+                let balance_result = effect_builder
+                    .make_request(
+                        |responder| RpcRequest::GetBalance {
+                            state_root_hash,
+                            purse_uref,
+                            responder,
+                        },
+                        QueueKind::Api,
+                    )
+                    .await;
+
+                let mut sample = BalanceSample {
+                    block_height: height,
+                    block_hash: *block.hash(),
+                    state_root_hash,
+                    balance_value: None,
+                    state_retained: true,
+                    merkle_proof: None,
+                };
+                match balance_result {
+                    Ok(BalanceResult::Success {
+                        motes,
+                        purse_proof,
+                        balance_proof,
+                    }) => {
+                        sample.balance_value = Some(motes);
+                        if params.include_proofs {
+                            match (*purse_proof, *balance_proof).to_bytes() {
+                                Ok(proof_bytes) => {
+                                    sample.merkle_proof = Some(hex::encode(proof_bytes))
+                                }
+                                Err(error) => {
+                                    info!("failed to encode stored value: {}", error);
+                                    return Ok(response_builder
+                                        .error(warp_json_rpc::Error::INTERNAL_ERROR)?);
+                                }
+                            }
+                        }
+                    }
+                    Ok(BalanceResult::RootNotFound) => sample.state_retained = false,
+                    Err(engine_state::Error::Exec(execution::Error::KeyNotFound(_))) => (),
+                    Err(error) => {
+                        let error_msg = format!("get-balance failed to execute: {}", error);
+                        info!("{}", error_msg);
+                        return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                            ErrorCode::GetBalanceFailedToExecute as i64,
+                            error_msg,
+                        ))?);
+                    }
+                }
+                samples.push(sample);
+
+                maybe_height = height.checked_add(params.step);
+            }
+
+            let next_height = maybe_height.filter(|height| *height <= params.to_height);
+
+            let result = Self::ResponseResult {
+                api_version: CLIENT_API_VERSION.clone(),
+                samples,
+                next_height,
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+// transfers to
+
+/// The maximum number of transfers returned by a single "state_get_transfers_to" request, exceeded
+/// only to complete the transfers of the last block returned.
+pub const MAX_TRANSFERS_PER_REQUEST: u64 = 100;
+
+/// Params for "state_get_transfers_to" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetTransfersToParams {
+    /// The target of the transfers, given as a hex-encoded public key, or as a formatted account
+    /// hash or purse `URef`.
+    pub target: String,
+    /// The earliest block whose transfers are to be returned.  If not provided, transfers are
+    /// returned from genesis onwards.
+    #[serde(default)]
+    pub since_block: Option<BlockIdentifier>,
+}
+
+/// Result for "state_get_transfers_to" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetTransfersToResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: Version,
+    /// The successful native transfers to the target, oldest first.
+    pub transfers: Vec<TransferEntry>,
+    /// If more transfers remain, the height of the block from which to resume.
+    pub next_since_block_height: Option<u64>,
+}
+
+/// "state_get_transfers_to" RPC.
+pub struct GetTransfersTo {}
+
+impl RpcWithParams for GetTransfersTo {
+    const METHOD: &'static str = "state_get_transfers_to";
+    type RequestParams = GetTransfersToParams;
+    type ResponseResult = GetTransfersToResult;
+}
+
+impl RpcWithParamsExt for GetTransfersTo {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            // Try to parse the target account or purse from the params.
+            let target = match parse_transfer_target(&params.target) {
+                Ok(target) => target,
+                Err(error_msg) => {
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::ParseQueryKey as i64,
+                        error_msg,
+                    ))?);
+                }
+            };
+
+            // Get the height of the earliest block whose transfers are requested.
+            let since_block_height = match params.since_block {
+                None => 0,
+                Some(BlockIdentifier::Height(height)) => height,
+                Some(block_identifier) => {
+                    match chain::get_block(Some(block_identifier), effect_builder).await {
+                        Ok(Some(block)) => block.height(),
+                        Ok(None) => {
+                            let error_msg = format!("block {:?} not known", block_identifier);
+                            info!("{}", error_msg);
+                            return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                                ErrorCode::NoSuchBlock as i64,
+                                error_msg,
+                            ))?);
+                        }
+                        Err(error) => return Ok(response_builder.error(error)?),
+                    }
+                }
+            };
+
+            let (transfers, next_since_block_height) = effect_builder
+                .make_request(
+                    |responder| RpcRequest::GetTransfersTo {
+                        target,
+                        since_block_height,
+                        max_count: MAX_TRANSFERS_PER_REQUEST,
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
+            let result = Self::ResponseResult {
+                api_version: CLIENT_API_VERSION.clone(),
+                transfers,
+                next_since_block_height,
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+/// Parses the target of a transfer, given either as a hex-encoded public key or as a formatted
+/// account hash or purse `URef`.
+fn parse_transfer_target(target: &str) -> Result<Key, String> {
+    if let Ok(public_key) = PublicKey::from_hex(target) {
+        return Ok(Key::Account(public_key.to_account_hash()));
+    }
+    match Key::from_formatted_str(target) {
+        Ok(key @ Key::Account(_)) | Ok(key @ Key::URef(_)) => Ok(key.normalize()),
+        Ok(key) => Err(format!(
+            "transfer target must be an account or purse, not {}",
+            key.to_formatted_string()
+        )),
+        Err(error) => Err(format!(
+            "failed to parse transfer target as a public key, account hash or purse: {:?}",
+            error
+        )),
+    }
+}
+
+// contract abi
+
+/// Params for "state_get_contract_abi" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetContractAbiParams {
+    /// Hash of the state root.
+    pub state_root_hash: Digest,
+    /// The contract hash, given either hex-encoded or as a formatted `Key::Hash`.
+    pub contract_hash: String,
+}
+
+/// Result for "state_get_contract_abi" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetContractAbiResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: Version,
+    /// The description of the contract's interface.
+    pub contract_abi: ContractAbi,
+    /// The merkle proof of the contract.
+    pub merkle_proof: String,
+}
+
+/// "state_get_contract_abi" RPC.
+pub struct GetContractAbi {}
+
+impl RpcWithParams for GetContractAbi {
+    const METHOD: &'static str = "state_get_contract_abi";
+    type RequestParams = GetContractAbiParams;
+    type ResponseResult = GetContractAbiResult;
+}
+
+impl RpcWithParamsExt for GetContractAbi {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            // Try to parse the contract hash from the params.
+            let base_key = match parse_contract_hash(&params.contract_hash) {
+                Ok(base_key) => base_key,
+                Err(error_msg) => {
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::ParseQueryKey as i64,
+                        error_msg,
+                    ))?);
+                }
+            };
+            let state_root_hash = params.state_root_hash;
+
+            // Run the query for the contract.
+            let query_result = effect_builder
+                .make_request(
+                    |responder| RpcRequest::QueryGlobalState {
+                        state_root_hash,
+                        base_key,
+                        path: vec![],
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
+            let (value, proofs) = match query_result {
+                Ok(QueryResult::Success { value, proofs }) => (value, proofs),
+                Ok(QueryResult::ValueNotFound(_)) | Ok(QueryResult::Absent { .. }) => {
+                    let error_msg = format!(
+                        "contract {} not known at state root {}",
+                        params.contract_hash, state_root_hash
+                    );
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::NoSuchContract as i64,
+                        error_msg,
+                    ))?);
+                }
+                Ok(query_result) => {
+                    let error_msg = format!("state query failed: {:?}", query_result);
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::QueryFailed as i64,
+                        error_msg,
+                    ))?);
+                }
+                Err(error) => {
+                    let error_msg = format!("state query failed to execute: {:?}", error);
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::QueryFailedToExecute as i64,
+                        error_msg,
+                    ))?);
+                }
+            };
+
+            let contract = match *value {
+                ExecutionEngineStoredValue::Contract(contract) => contract,
+                other => {
+                    let error_msg = format!(
+                        "value under {} is not a contract: {:?}",
+                        params.contract_hash, other
+                    );
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::NoSuchContract as i64,
+                        error_msg,
+                    ))?);
+                }
+            };
+
+            // The metadata is optional: if the contract publishes none, or it is invalid, the ABI
+            // is given without documentation.
+            let maybe_metadata = if contract.named_keys().contains_key(CONTRACT_METADATA_KEY) {
+                let metadata_result = effect_builder
+                    .make_request(
+                        |responder| RpcRequest::QueryGlobalState {
+                            state_root_hash,
+                            base_key,
+                            path: vec![CONTRACT_METADATA_KEY.to_string()],
+                            responder,
+                        },
+                        QueueKind::Api,
+                    )
+                    .await;
+                match metadata_result {
+                    Ok(QueryResult::Success { value, .. }) => match *value {
+                        ExecutionEngineStoredValue::CLValue(cl_value) => {
+                            ContractMetadata::from_cl_value(cl_value)
+                                .map_err(|error| {
+                                    info!(
+                                        "invalid metadata of contract {}: {}",
+                                        params.contract_hash, error
+                                    )
+                                })
+                                .ok()
+                        }
+                        _ => None,
+                    },
+                    _ => None,
+                }
+            } else {
+                None
+            };
+
+            let proof_bytes = match proofs.to_bytes() {
+                Ok(proof_bytes) => proof_bytes,
+                Err(error) => {
+                    info!("failed to encode stored value: {}", error);
+                    return Ok(response_builder.error(warp_json_rpc::Error::INTERNAL_ERROR)?);
+                }
+            };
+
+            let result = Self::ResponseResult {
+                api_version: CLIENT_API_VERSION.clone(),
+                contract_abi: ContractAbi::new(&contract, maybe_metadata.as_ref()),
+                merkle_proof: hex::encode(proof_bytes),
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+/// Parses a contract hash, given either hex-encoded or as a formatted `Key::Hash`.
+fn parse_contract_hash(contract_hash: &str) -> Result<Key, String> {
+    if let Ok(Key::Hash(hash)) = Key::from_formatted_str(contract_hash) {
+        return Ok(Key::Hash(hash));
+    }
+    let bytes = hex::decode(contract_hash)
+        .map_err(|error| format!("failed to parse contract hash: {}", error))?;
+    let hash = <[u8; 32]>::try_from(bytes.as_slice())
+        .map_err(|_| format!("contract hash must be 32 bytes, not {} bytes", bytes.len()))?;
+    Ok(Key::Hash(hash))
+}
+
+// auction info
+
+/// Result for "state_get_auction_info" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetAuctionInfoResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: Version,
+    /// The auction state.
+    pub auction_state: AuctionState,
+}
+
+/// "state_get_auction_info" RPC.
+pub struct GetAuctionInfo {}
+
+impl RpcWithoutParams for GetAuctionInfo {
+    const METHOD: &'static str = "state_get_auction_info";
+    type ResponseResult = GetAuctionInfoResult;
+}
+
+impl RpcWithoutParamsExt for GetAuctionInfo {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let block: Block = {
+                let maybe_block = effect_builder
+                    .make_request(
+                        |responder| RpcRequest::GetBlock {
+                            maybe_id: None,
+                            responder,
+                        },
+                        QueueKind::Api,
+                    )
+                    .await;
+
+                match maybe_block {
+                    None => {
+                        let error_msg =
+                            "get-auction-info failed to get last added block".to_string();
+                        info!("{}", error_msg);
+                        return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                            ErrorCode::NoSuchBlock as i64,
+                            error_msg,
+                        ))?);
+                    }
+                    Some(block) => block,
+                }
+            };
+
+            let protocol_version = ProtocolVersion::V1_0_0;
+            let protocol_version_result = effect_builder
+                .make_request(
+                    |responder| RpcRequest::QueryProtocolData {
+                        protocol_version,
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
+            let protocol_data = {
+                if let Ok(Some(protocol_data)) = protocol_version_result {
+                    protocol_data
+                } else {
+                    Box::new(ProtocolData::default())
+                }
+            };
+
+            // auction contract key
+            let base_key = protocol_data.auction().into();
+            // bids named key in auction contract
+            let path = vec![BIDS_KEY.to_string()];
+            // the global state hash of the last block
+            let state_root_hash = *block.header().state_root_hash();
+            // the block height of the last added block
+            let block_height = block.header().height();
+
+            let query_result = effect_builder
+                .make_request(
+                    |responder| RpcRequest::QueryGlobalState {
+                        state_root_hash,
+                        base_key,
+                        path,
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
+            let bids = if let Ok(QueryResult::Success { value, .. }) = query_result {
+                value
+                    .as_cl_value()
+                    .and_then(|cl_value| cl_value.to_owned().into_t().ok())
+            } else {
+                None
+            };
+
+            let era_validators_result = effect_builder
+                .make_request(
+                    |responder| RpcRequest::QueryEraValidators {
+                        state_root_hash,
+                        protocol_version,
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
+            let era_validators = era_validators_result.ok();
+
+            let auction_state =
+                AuctionState::new(state_root_hash, block_height, era_validators, bids);
+            debug!("responding to client with: {:?}", auction_state);
+            Ok(response_builder.success(auction_state)?)
+        }
+        .boxed()
+    }
+}
+
+// delegations
+
+/// Params for "state_get_delegations" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetDelegationsParams {
+    /// The public key of the delegator.
+    pub public_key: PublicKey,
+    /// The block at whose state root the auction state is read.  If `None`, the latest block is
+    /// used.
+    pub block_identifier: Option<BlockIdentifier>,
+}
+
+/// A delegator's stake in the bid of a single validator.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+pub struct Delegation {
+    /// The validator delegated to.
+    pub validator_public_key: PublicKey,
+    /// The validator's delegation rate.
+    pub delegation_rate: DelegationRate,
+    /// The amount staked by the delegator.
+    #[schemars(with = "String")]
+    pub staked_amount: U512,
+    /// The seigniorage reward accumulated by the delegator and not yet withdrawn.
+    #[schemars(with = "String")]
+    pub reward: U512,
+    /// The purse the stake was delegated from.
+    #[schemars(with = "String")]
+    pub bonding_purse: URef,
+}
+
+/// An amount undelegated or unbonded, which is paid out once its unbonding delay has passed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+pub struct PendingUnbonding {
+    /// The purse the amount was staked from.
+    #[schemars(with = "String")]
+    pub bonding_purse: URef,
+    /// The purse the amount will be paid out to.
+    #[schemars(with = "String")]
+    pub unbonding_purse: URef,
+    /// The amount.
+    #[schemars(with = "String")]
+    pub amount: U512,
+    /// The era at the end of which the amount is paid out.
+    pub era_of_withdrawal: EraId,
+}
+
+/// Result for "state_get_delegations" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetDelegationsResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: Version,
+    /// The hash of the block as of which the auction state was read.
+    pub block_hash: BlockHash,
+    /// The delegator's stakes, by validator.
+    pub delegations: Vec<Delegation>,
+    /// The delegator's undelegations which have not been paid out yet.
+    pub pending_unbondings: Vec<PendingUnbonding>,
+    /// The total amount staked by the delegator.
+    #[schemars(with = "String")]
+    pub total_staked_amount: U512,
+    /// The total seigniorage reward accumulated by the delegator and not yet withdrawn.
+    #[schemars(with = "String")]
+    pub total_reward: U512,
+}
+
+/// "state_get_delegations" RPC.
+///
+/// Collects the stakes of a delegator across the bids of all validators, along with its pending
+/// undelegations, from the auction contract's state.
+pub struct GetDelegations {}
+
+impl RpcWithParams for GetDelegations {
+    const METHOD: &'static str = "state_get_delegations";
+    type RequestParams = GetDelegationsParams;
+    type ResponseResult = GetDelegationsResult;
+}
+
+impl RpcWithParamsExt for GetDelegations {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            // Get the block whose state root hash will be queried.
+            let block = match chain::get_block(params.block_identifier, effect_builder).await {
+                Ok(Some(block)) => block,
+                Ok(None) => {
+                    let error_msg = "get-delegations failed to get last added block".to_string();
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::NoSuchBlock as i64,
+                        error_msg,
+                    ))?);
+                }
+                Err(error) => return Ok(response_builder.error(error)?),
+            };
+            let state_root_hash = *block.header().state_root_hash();
+
+            let bids: Bids =
+                match read_auction_value(effect_builder, state_root_hash, BIDS_KEY).await {
+                    Ok(bids) => bids,
+                    Err(error) => return Ok(response_builder.error(error)?),
+                };
+            let mut unbonding_purses: UnbondingPurses =
+                match read_auction_value(effect_builder, state_root_hash, UNBONDING_PURSES_KEY)
+                    .await
+                {
+                    Ok(unbonding_purses) => unbonding_purses,
+                    Err(error) => return Ok(response_builder.error(error)?),
+                };
+
+            let delegator_public_key = params.public_key.into();
+            let mut delegations = vec![];
+            for (validator_public_key, bid) in bids {
+                let delegator = match bid.delegators().get(&delegator_public_key) {
+                    Some(delegator) => delegator,
+                    None => continue,
+                };
+                let validator_public_key = match PublicKey::try_from(validator_public_key) {
+                    Ok(public_key) => public_key,
+                    Err(error) => {
+                        info!("failed to convert validator public key: {}", error);
+                        return Ok(response_builder.error(warp_json_rpc::Error::INTERNAL_ERROR)?);
+                    }
+                };
+                delegations.push(Delegation {
+                    validator_public_key,
+                    delegation_rate: *bid.delegation_rate(),
+                    staked_amount: *delegator.staked_amount(),
+                    reward: *delegator.reward(),
+                    bonding_purse: *delegator.bonding_purse(),
+                });
+            }
+
+            let pending_unbondings = unbonding_purses
+                .remove(&delegator_public_key)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|unbonding_purse| PendingUnbonding {
+                    bonding_purse: unbonding_purse.bonding_purse,
+                    unbonding_purse: unbonding_purse.unbonding_purse,
+                    amount: unbonding_purse.amount,
+                    era_of_withdrawal: unbonding_purse.era_of_withdrawal,
+                })
+                .collect();
+
+            let total_staked_amount = delegations.iter().fold(U512::zero(), |sum, delegation| {
+                sum + delegation.staked_amount
+            });
+            let total_reward = delegations
+                .iter()
+                .fold(U512::zero(), |sum, delegation| sum + delegation.reward);
+
+            let result = Self::ResponseResult {
+                api_version: CLIENT_API_VERSION.clone(),
+                block_hash: *block.hash(),
+                delegations,
+                pending_unbondings,
+                total_staked_amount,
+                total_reward,
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+// unbonding purses
+
+/// Params for "state_get_unbonding_purses" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetUnbondingPursesParams {
+    /// If `Some`, only the amounts unbonded from this validator's bid are returned.
+    pub validator: Option<PublicKey>,
+    /// If `Some`, only the amounts unbonded by this validator or delegator are returned.
+    pub delegator: Option<PublicKey>,
+}
+
+/// An amount in the unbonding queue, along with the estimated time of its release.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+pub struct UnbondingPurseEntry {
+    /// The validator or delegator who unbonded the amount.
+    pub public_key: PublicKey,
+    /// The validator whose bid the amount was unbonded from, or `None` if it can't be determined
+    /// from the current bids, e.g. as the delegator has withdrawn all of its stake.
+    pub validator_public_key: Option<PublicKey>,
+    /// The purse the amount was staked from.
+    #[schemars(with = "String")]
+    pub bonding_purse: URef,
+    /// The purse the amount will be paid out to.
+    #[schemars(with = "String")]
+    pub unbonding_purse: URef,
+    /// The amount.
+    #[schemars(with = "String")]
+    pub amount: U512,
+    /// The era at the end of which the amount is paid out.
+    pub era_of_withdrawal: EraId,
+    /// The estimated time the amount is paid out at, or `None` if eras are not bounded by
+    /// duration.
+    pub estimated_release_timestamp: Option<Timestamp>,
+}
+
+/// Result for "state_get_unbonding_purses" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetUnbondingPursesResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: Version,
+    /// The hash of the block as of which the unbonding queue was read.
+    pub block_hash: BlockHash,
+    /// The ID of the current era.
+    pub era_id: u64,
+    /// The amounts in the unbonding queue matching the filters, soonest released first.
+    pub unbonding_purses: Vec<UnbondingPurseEntry>,
+}
+
+/// "state_get_unbonding_purses" RPC.
+///
+/// Returns the auction contract's queue of unbonded amounts as of the latest block.  The amounts
+/// are paid out at the end of their era of withdrawal.
+pub struct GetUnbondingPurses {}
+
+impl RpcWithParams for GetUnbondingPurses {
+    const METHOD: &'static str = "state_get_unbonding_purses";
+    type RequestParams = GetUnbondingPursesParams;
+    type ResponseResult = GetUnbondingPursesResult;
+}
+
+impl RpcWithParamsExt for GetUnbondingPurses {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let block = match chain::get_block(None, effect_builder).await {
+                Ok(Some(block)) => block,
+                Ok(None) => {
+                    let error_msg =
+                        "get-unbonding-purses failed to get last added block".to_string();
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::NoSuchBlock as i64,
+                        error_msg,
+                    ))?);
+                }
+                Err(error) => return Ok(response_builder.error(error)?),
+            };
+            let state_root_hash = *block.header().state_root_hash();
+
+            let bids: Bids =
+                match read_auction_value(effect_builder, state_root_hash, BIDS_KEY).await {
+                    Ok(bids) => bids,
+                    Err(error) => return Ok(response_builder.error(error)?),
+                };
+            let unbonding_purses: UnbondingPurses =
+                match read_auction_value(effect_builder, state_root_hash, UNBONDING_PURSES_KEY)
+                    .await
+                {
+                    Ok(unbonding_purses) => unbonding_purses,
+                    Err(error) => return Ok(response_builder.error(error)?),
+                };
+
+            // The current era starts with the most recent switch block, or at genesis.
+            let maybe_switch_block = effect_builder
+                .make_request(
+                    |responder| RpcRequest::GetSwitchBlock {
+                        maybe_era_id: None,
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+            let maybe_chainspec = effect_builder
+                .make_request(
+                    |responder| RpcRequest::GetChainspec { responder },
+                    QueueKind::Api,
+                )
+                .await;
+            let highway_config = match maybe_chainspec {
+                Some(chainspec) => chainspec.genesis.highway_config,
+                None => {
+                    info!("failed to get chainspec");
+                    return Ok(response_builder.error(warp_json_rpc::Error::INTERNAL_ERROR)?);
+                }
+            };
+            let (era_id, era_start) = match maybe_switch_block {
+                Some(switch_block) => (
+                    switch_block.header().era_id().successor().0,
+                    switch_block.header().timestamp(),
+                ),
+                None => (0, highway_config.genesis_era_start_timestamp),
+            };
+
+            let validator_filter = params.validator.map(casper_types::PublicKey::from);
+            let delegator_filter = params.delegator.map(casper_types::PublicKey::from);
+            let mut entries = vec![];
+            for (public_key, purses) in unbonding_purses {
+                if delegator_filter.map_or(false, |delegator| delegator != public_key) {
+                    continue;
+                }
+                for purse in purses {
+                    let maybe_validator = unbonding_validator(&bids, &purse);
+                    if validator_filter.is_some() && validator_filter != maybe_validator {
+                        continue;
+                    }
+                    let entry = match unbonding_purse_entry(
+                        purse,
+                        maybe_validator,
+                        era_id,
+                        era_start,
+                        highway_config.era_duration,
+                    ) {
+                        Some(entry) => entry,
+                        None => {
+                            info!("failed to convert public keys of unbonding purse");
+                            return Ok(
+                                response_builder.error(warp_json_rpc::Error::INTERNAL_ERROR)?
+                            );
+                        }
+                    };
+                    entries.push(entry);
+                }
+            }
+            entries.sort_by_key(|entry| entry.era_of_withdrawal);
+
+            let result = Self::ResponseResult {
+                api_version: CLIENT_API_VERSION.clone(),
+                block_hash: *block.hash(),
+                era_id,
+                unbonding_purses: entries,
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+/// Returns the validator whose bid `purse` was unbonded from: the unbonder itself if the purse
+/// was bonded as its bid, else the validator to which the unbonder delegates from the purse.
+fn unbonding_validator(bids: &Bids, purse: &UnbondingPurse) -> Option<casper_types::PublicKey> {
+    if let Some(bid) = bids.get(&purse.public_key) {
+        if *bid.bonding_purse() == purse.bonding_purse {
+            return Some(purse.public_key);
+        }
+    }
+    let mut validators = bids.iter().filter(|(_, bid)| {
+        bid.delegators()
+            .get(&purse.public_key)
+            .map_or(false, |delegator| {
+                *delegator.bonding_purse() == purse.bonding_purse
+            })
+    });
+    match (validators.next(), validators.next()) {
+        (Some((validator_public_key, _)), None) => Some(*validator_public_key),
+        _ => None,
+    }
+}
+
+/// Returns the estimated end of the era `era_of_withdrawal`, at which unbonded amounts are paid
+/// out, given the start of the current era, or `None` if eras are not bounded by duration.
+fn estimated_release_timestamp(
+    era_of_withdrawal: EraId,
+    current_era_id: EraId,
+    current_era_start: Timestamp,
+    era_duration: TimeDiff,
+) -> Option<Timestamp> {
+    if era_duration.millis() == 0 {
+        return None;
+    }
+    let remaining_eras = era_of_withdrawal.saturating_sub(current_era_id) + 1;
+    Some(current_era_start + era_duration * remaining_eras)
+}
+
+fn unbonding_purse_entry(
+    purse: UnbondingPurse,
+    maybe_validator: Option<casper_types::PublicKey>,
+    current_era_id: EraId,
+    current_era_start: Timestamp,
+    era_duration: TimeDiff,
+) -> Option<UnbondingPurseEntry> {
+    let validator_public_key = match maybe_validator {
+        Some(validator) => Some(PublicKey::try_from(validator).ok()?),
+        None => None,
+    };
+    Some(UnbondingPurseEntry {
+        public_key: PublicKey::try_from(purse.public_key).ok()?,
+        validator_public_key,
+        bonding_purse: purse.bonding_purse,
+        unbonding_purse: purse.unbonding_purse,
+        amount: purse.amount,
+        era_of_withdrawal: purse.era_of_withdrawal,
+        estimated_release_timestamp: estimated_release_timestamp(
+            purse.era_of_withdrawal,
+            current_era_id,
+            current_era_start,
+            era_duration,
+        ),
+    })
+}
+
+/// Reads the value stored under the given named key of the auction contract.
+async fn read_auction_value<REv: ReactorEventT, T: FromBytes + CLTyped>(
+    effect_builder: EffectBuilder<REv>,
+    state_root_hash: Digest,
+    name: &str,
+) -> Result<T, warp_json_rpc::Error> {
+    let protocol_version = ProtocolVersion::V1_0_0;
+    let protocol_data = match effect_builder
+        .make_request(
+            |responder| RpcRequest::QueryProtocolData {
+                protocol_version,
+                responder,
+            },
+            QueueKind::Api,
+        )
+        .await
+    {
+        Ok(Some(protocol_data)) => protocol_data,
+        _ => Box::new(ProtocolData::default()),
+    };
+
+    let query_result = effect_builder
+        .make_request(
+            |responder| RpcRequest::QueryGlobalState {
+                state_root_hash,
+                base_key: protocol_data.auction().into(),
+                path: vec![name.to_string()],
+                responder,
+            },
+            QueueKind::Api,
+        )
+        .await;
+
+    let value = match query_result {
+        Ok(QueryResult::Success { value, .. }) => value,
+        Ok(query_result) => {
+            let error_msg = format!("state query failed: {:?}", query_result);
+            info!("{}", error_msg);
+            return Err(warp_json_rpc::Error::custom(
+                ErrorCode::QueryFailed as i64,
+                error_msg,
+            ));
+        }
+        Err(error) => {
+            let error_msg = format!("state query failed to execute: {:?}", error);
+            info!("{}", error_msg);
+            return Err(warp_json_rpc::Error::custom(
+                ErrorCode::QueryFailedToExecute as i64,
+                error_msg,
+            ));
+        }
+    };
+
+    value
+        .as_cl_value()
+        .and_then(|cl_value| cl_value.clone().into_t().ok())
+        .ok_or_else(|| {
+            info!("failed to parse auction value '{}': {:?}", name, value);
+            warp_json_rpc::Error::INTERNAL_ERROR
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_extract_earliest_queryable_block_height() {
+        let message = format!("{}{}", STATE_ROOT_OUTSIDE_WINDOW_PREFIX, 42);
+        assert_eq!(earliest_queryable_block_height(&message), Some(42));
+        assert_eq!(earliest_queryable_block_height("state query failed"), None);
+    }
+
+    #[test]
+    fn should_estimate_release_at_end_of_era_of_withdrawal() {
+        let era_start = Timestamp::from(1_000);
+        let era_duration = TimeDiff::from(100);
+        assert_eq!(
+            estimated_release_timestamp(7, 5, era_start, era_duration),
+            Some(Timestamp::from(1_300))
+        );
+        // amounts due are paid out at the end of the current era
+        assert_eq!(
+            estimated_release_timestamp(3, 5, era_start, era_duration),
+            Some(Timestamp::from(1_100))
+        );
+        assert_eq!(
+            estimated_release_timestamp(7, 5, era_start, TimeDiff::from(0)),
+            None
+        );
+    }
+}
@@ -16,6 +16,10 @@
 //!
 //! The network itself is best-effort, during regular operation, no messages should be lost.
 //!
+//! Messages are sent in length-delimited frames.  A message sent to several peers is encoded only
+//! once per wire version they speak, with the resulting frame shared between their connections.
+//! Payloads which peers speaking an older wire version can't decode are not sent to them.
+//!
 //! # Connection
 //!
 //! Every node has an ID and a public listening address. The objective of each node is to constantly
@@ -31,20 +35,33 @@
 //! On losing an incoming or outgoing connection for a given peer, the other connection is closed.
 //! No explicit reconnect is attempted. Instead, if the peer is still online, the normal gossiping
 //! process will cause both peers to connect again.
-
+//!
+//! # Clock drift
+//!
+//! On connections speaking wire version 2 or later, as negotiated during the TLS handshake, the
+//! first frame sent on an outgoing connection is a handshake carrying the sender's clock reading,
+//! from which the receiver estimates the offset of its clock from the sender's.  Peers speaking
+//! the original wire version send no handshake and are left out of the estimate.  If the median
+//! offset across peers exceeds the configured maximum, a warning is logged and announced.
+
+mod clock_offsets;
+mod codec;
 mod config;
 mod error;
 mod event;
 mod gossiped_address;
 mod message;
+mod metrics;
+mod payload;
 #[cfg(test)]
 mod tests;
 
 use std::{
     collections::{HashMap, HashSet},
     convert::Infallible,
-    fmt::{self, Debug, Display, Formatter},
+    fmt::{self, Debug, Formatter},
     io,
+    marker::PhantomData,
     net::{SocketAddr, TcpListener},
     sync::{
         atomic::{AtomicBool, Ordering},
@@ -54,6 +71,7 @@ use std::{
 };
 
 use anyhow::Context;
+use bytes::Bytes;
 use datasize::DataSize;
 use futures::{
     future::{select, BoxFuture, Either},
@@ -62,23 +80,33 @@ use futures::{
 };
 use openssl::pkey;
 use pkey::{PKey, Private};
+use prometheus::Registry;
 use rand::seq::IteratorRandom;
-use serde::{de::DeserializeOwned, Serialize};
 use tokio::{
     net::TcpStream,
     sync::{
         mpsc::{self, UnboundedReceiver, UnboundedSender},
-        watch,
+        oneshot, watch,
     },
     task::JoinHandle,
 };
 use tokio_openssl::SslStream;
-use tokio_serde::{formats::SymmetricalMessagePack, SymmetricallyFramed};
-use tokio_util::codec::{Framed, LengthDelimitedCodec};
 use tracing::{debug, error, info, trace, warn};
 
-use self::error::Result;
-pub(crate) use self::{event::Event, gossiped_address::GossipedAddress, message::Message};
+use self::{
+    clock_offsets::ClockOffsets,
+    codec::{framed, FramedTransport, ALPN_PROTOCOLS},
+    error::Result,
+    message::Handshake,
+    metrics::NetworkMetrics,
+};
+pub use self::{codec::WireVersion, payload::Payload};
+pub(crate) use self::{
+    codec::{decode_payload, encode_payload},
+    event::Event,
+    gossiped_address::GossipedAddress,
+    message::Message,
+};
 use crate::{
     components::Component,
     effect::{
@@ -89,7 +117,7 @@ use crate::{
     fatal,
     reactor::{EventQueueHandle, Finalize, QueueKind},
     tls::{self, TlsCert},
-    types::NodeId,
+    types::{NodeId, Timestamp},
     utils, NodeRng,
 };
 pub use config::Config;
@@ -98,10 +126,12 @@ pub use error::Error;
 const MAX_ASYMMETRIC_CONNECTION_SEEN: u16 = 3;
 
 #[derive(DataSize, Debug)]
-pub(crate) struct OutgoingConnection<P> {
+pub(crate) struct OutgoingConnection {
     #[data_size(skip)] // Unfortunately, there is no way to inspect an `UnboundedSender`.
-    sender: UnboundedSender<Message<P>>,
+    sender: UnboundedSender<Bytes>,
     peer_address: SocketAddr,
+    /// The wire version negotiated with the peer.
+    wire_version: WireVersion,
 
     // for keeping track of connection asymmetry, tracking the number of times we've seen this
     // connection be asymmetric.
@@ -111,6 +141,9 @@ pub(crate) struct OutgoingConnection<P> {
 #[derive(DataSize, Debug)]
 pub(crate) struct IncomingConnection {
     peer_address: SocketAddr,
+    /// Dropping this closes the connection by stopping its message reader.
+    #[data_size(skip)]
+    _close_sender: oneshot::Sender<()>,
 
     // for keeping track of connection asymmetry, tracking the number of times we've seen this
     // connection be asymmetric.
@@ -136,7 +169,7 @@ where
     /// Incoming network connection addresses.
     incoming: HashMap<NodeId, IncomingConnection>,
     /// Outgoing network connections' messages.
-    outgoing: HashMap<NodeId, OutgoingConnection<P>>,
+    outgoing: HashMap<NodeId, OutgoingConnection>,
 
     /// List of addresses which this node will avoid connecting to.
     blocklist: HashSet<SocketAddr>,
@@ -161,11 +194,23 @@ where
     is_stopped: Arc<AtomicBool>,
     /// Join handle for the server thread.
     server_join_handle: Option<JoinHandle<()>>,
+    /// Estimated offsets of our clock from connected peers' clocks.
+    ///
+    /// Our clock's drift from the network's is only reassessed on receiving a new estimate.
+    clock_offsets: ClockOffsets,
+    /// Maximum drift of our clock from the median of peers' clocks before warning.
+    max_clock_drift: Duration,
+    /// Whether our clock's drift was last found to exceed `max_clock_drift`.
+    clock_drift_exceeded: bool,
+    /// Metrics for the small network.
+    #[data_size(skip)]
+    metrics: NetworkMetrics,
+    _payload: PhantomData<P>,
 }
 
 impl<REv, P> SmallNetwork<REv, P>
 where
-    P: Serialize + DeserializeOwned + Clone + Debug + Display + Send + 'static,
+    P: Payload,
     REv: Send + From<Event<P>> + From<NetworkAnnouncement<NodeId, P>>,
 {
     /// Creates a new small network component instance.
@@ -176,6 +221,7 @@ where
     pub(crate) fn new(
         event_queue: EventQueueHandle<REv>,
         cfg: Config,
+        registry: &Registry,
         notify: bool,
     ) -> Result<(SmallNetwork<REv, P>, Effects<Event<P>>)> {
         // First, we generate the TLS keys.
@@ -242,6 +288,11 @@ where
             shutdown_receiver,
             server_join_handle: Some(server_join_handle),
             is_stopped: Arc::new(AtomicBool::new(false)),
+            clock_offsets: ClockOffsets::default(),
+            max_clock_drift: cfg.max_clock_drift,
+            clock_drift_exceeded: false,
+            metrics: NetworkMetrics::new(registry)?,
+            _payload: PhantomData,
         };
 
         // Bootstrap process.
@@ -296,9 +347,7 @@ where
 
     /// Queues a message to be sent to all nodes.
     fn broadcast_message(&self, msg: Message<P>) {
-        for peer_id in self.outgoing.keys() {
-            self.send_message(peer_id.clone(), msg.clone());
-        }
+        self.send_to_peers(self.outgoing.keys(), &msg);
     }
 
     /// Queues a message to `count` random nodes on the network.
@@ -327,24 +376,68 @@ where
             );
         }
 
-        for &peer_id in &peer_ids {
-            self.send_message(peer_id.clone(), msg.clone());
-        }
+        self.send_to_peers(peer_ids.iter().cloned(), &msg);
 
         peer_ids.into_iter().cloned().collect()
     }
 
     /// Queues a message to be sent to a specific node.
     fn send_message(&self, dest: NodeId, msg: Message<P>) {
-        // Try to send the message.
-        if let Some(connection) = self.outgoing.get(&dest) {
-            if let Err(msg) = connection.sender.send(msg) {
+        self.send_to_peers(Some(&dest), &msg);
+    }
+
+    /// Queues a message to be sent to each of the given nodes.
+    ///
+    /// The message is encoded at most once per wire version, and the same frame queued on every
+    /// connection speaking that version.
+    fn send_to_peers<'a, I>(&self, dests: I, msg: &Message<P>)
+    where
+        I: IntoIterator<Item = &'a NodeId>,
+    {
+        let mut frames: HashMap<WireVersion, Option<Bytes>> = HashMap::new();
+        for dest in dests {
+            let connection = match self.outgoing.get(dest) {
+                Some(connection) => connection,
+                None => {
+                    // We are not connected, so the reconnection is likely already in progress.
+                    debug!(%dest, ?msg, "{}: dropped outgoing message, no connection", self.our_id);
+                    continue;
+                }
+            };
+            let wire_version = connection.wire_version;
+            let frame = match frames
+                .entry(wire_version)
+                .or_insert_with(|| self.encode(msg, wire_version))
+            {
+                Some(frame) => frame.clone(),
+                None => continue,
+            };
+            if connection.sender.send(frame).is_err() {
                 // We lost the connection, but that fact has not reached us yet.
                 warn!(%dest, ?msg, "{}: dropped outgoing message, lost connection", self.our_id);
             }
-        } else {
-            // We are not connected, so the reconnection is likely already in progress.
-            debug!(%dest, ?msg, "{}: dropped outgoing message, no connection", self.our_id);
+        }
+    }
+
+    /// Encodes a message into a frame to be sent to peers speaking `wire_version`.
+    ///
+    /// Returns `None`, logging why, if the message can't be encoded or can't be sent to such peers.
+    fn encode(&self, msg: &Message<P>, wire_version: WireVersion) -> Option<Bytes> {
+        match msg.0.encode(wire_version) {
+            Ok(Some(frame)) => Some(frame),
+            Ok(None) => {
+                debug!(
+                    ?msg,
+                    ?wire_version,
+                    "{}: dropped outgoing message, not supported by wire version",
+                    self.our_id
+                );
+                None
+            }
+            Err(error) => {
+                error!(%error, ?msg, "{}: failed to encode outgoing message", self.our_id);
+                None
+            }
         }
     }
 
@@ -379,14 +472,23 @@ where
                     return Effects::new();
                 }
 
-                debug!(%peer_id, %peer_address, "{}: established incoming connection", self.our_id);
+                let wire_version = WireVersion::negotiated(transport.ssl());
+                debug!(
+                    %peer_id,
+                    %peer_address,
+                    ?wire_version,
+                    "{}: established incoming connection",
+                    self.our_id
+                );
                 // The sink is never used, as we only read data from incoming connections.
-                let (_sink, stream) = framed::<P>(transport).split();
+                let (_sink, stream) = framed(transport).split();
+                let (close_sender, close_receiver) = oneshot::channel();
 
                 let _ = self.incoming.insert(
                     peer_id.clone(),
                     IncomingConnection {
                         peer_address,
+                        _close_sender: close_sender,
                         times_seen_asymmetric: 0,
                     },
                 );
@@ -399,6 +501,8 @@ where
                         self.event_queue,
                         stream,
                         self.shutdown_receiver.clone(),
+                        close_receiver,
+                        wire_version,
                         self.our_id.clone(),
                         peer_id.clone(),
                     )
@@ -451,13 +555,21 @@ where
             return Effects::new();
         }
 
-        let (sink, _stream) = framed::<P>(transport).split();
-        debug!(%peer_id, %peer_address, "{}: established outgoing connection", self.our_id);
+        let wire_version = WireVersion::negotiated(transport.ssl());
+        let (sink, _stream) = framed(transport).split();
+        debug!(
+            %peer_id,
+            %peer_address,
+            ?wire_version,
+            "{}: established outgoing connection",
+            self.our_id
+        );
 
         let (sender, receiver) = mpsc::unbounded_channel();
         let connection = OutgoingConnection {
             peer_address,
             sender,
+            wire_version,
             times_seen_asymmetric: 0,
         };
         if self.outgoing.insert(peer_id.clone(), connection).is_some() {
@@ -470,10 +582,12 @@ where
         let mut effects = self.check_connection_complete(effect_builder, peer_id.clone());
 
         effects.extend(
-            message_sender(receiver, sink).event(move |result| Event::OutgoingFailed {
-                peer_id: Some(peer_id),
-                peer_address,
-                error: result.err().map(Into::into),
+            message_sender(receiver, sink, wire_version).event(move |result| {
+                Event::OutgoingFailed {
+                    peer_id: Some(peer_id),
+                    peer_address,
+                    error: result.err().map(Into::into),
+                }
             }),
         );
 
@@ -513,6 +627,28 @@ where
             let _ = self.pending.remove(&incoming.peer_address);
         }
         let _ = self.outgoing.remove(&peer_id);
+        self.clock_offsets.remove(peer_id);
+    }
+
+    /// Removes all connections to or from the peer listening on the given address.
+    ///
+    /// An incoming connection's address is the peer's ephemeral source address rather than its
+    /// listening address, so the peer is identified via our outgoing connection to it, and both
+    /// its connections are then removed by node ID.
+    fn disconnect(&mut self, peer_address: SocketAddr) {
+        let peer_ids: Vec<NodeId> = self
+            .outgoing
+            .iter()
+            .filter(|(_peer_id, connection)| connection.peer_address == peer_address)
+            .map(|(peer_id, _connection)| peer_id.clone())
+            .collect();
+        if peer_ids.is_empty() {
+            info!(%peer_address, "{}: not connected to peer", self.our_id);
+        }
+        for peer_id in peer_ids {
+            info!(%peer_id, %peer_address, "{}: disconnecting from peer", self.our_id);
+            self.remove(&peer_id);
+        }
     }
 
     /// Gossips our public listening address, and schedules the next such gossip round.
@@ -586,6 +722,58 @@ where
             .ignore()
     }
 
+    /// Records the estimated offset of our clock from a peer's, and reassesses our clock's drift
+    /// from the network's.
+    ///
+    /// Returns an announcement if the drift has crossed `max_clock_drift` in either direction.
+    fn handle_clock_offset(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        peer_id: NodeId,
+        offset: i64,
+    ) -> Effects<Event<P>> {
+        let max_clock_drift = self.max_clock_drift.as_millis() as i64;
+        if offset.abs() > max_clock_drift {
+            info!(
+                %peer_id,
+                offset,
+                max_clock_drift,
+                "{}: peer's clock is offset from ours by more than the maximum drift",
+                self.our_id
+            );
+        }
+        self.clock_offsets.insert(peer_id, offset);
+
+        let median_offset = match self.clock_offsets.median() {
+            Some(median_offset) => median_offset,
+            None => return Effects::new(),
+        };
+        self.metrics.clock_drift.set(median_offset);
+
+        let exceeds_bound = median_offset.abs() > max_clock_drift;
+        if exceeds_bound == self.clock_drift_exceeded {
+            return Effects::new();
+        }
+        self.clock_drift_exceeded = exceeds_bound;
+        if exceeds_bound {
+            warn!(
+                median_offset,
+                max_clock_drift,
+                "{}: local clock drifts from the network's by more than the maximum, check that \
+                the system clock is synchronized",
+                self.our_id
+            );
+        } else {
+            info!(
+                median_offset,
+                max_clock_drift, "{}: local clock drift is back within the maximum", self.our_id
+            );
+        }
+        effect_builder
+            .announce_clock_drift::<NodeId, P>(median_offset, exceeds_bound)
+            .ignore()
+    }
+
     fn connect_to_peer_if_required(&mut self, peer_address: SocketAddr) -> Effects<Event<P>> {
         if self.pending.contains(&peer_address)
             || self.blocklist.contains(&peer_address)
@@ -657,7 +845,7 @@ where
 
     /// Returns the node id of this network node.
     /// - Used in validator test.
-    #[cfg(test)]
+    #[cfg(any(feature = "testing", test))]
     pub(crate) fn node_id(&self) -> NodeId {
         self.our_id.clone()
     }
@@ -694,7 +882,7 @@ where
 impl<REv, P> Component<REv> for SmallNetwork<REv, P>
 where
     REv: Send + From<Event<P>> + From<NetworkAnnouncement<NodeId, P>>,
-    P: Serialize + DeserializeOwned + Clone + Debug + Display + Send + 'static,
+    P: Payload,
 {
     type Event = Event<P>;
     type ConstructionError = Infallible;
@@ -750,6 +938,9 @@ where
             Event::IncomingMessage { peer_id, msg } => {
                 self.handle_message(effect_builder, peer_id, msg)
             }
+            Event::ClockOffsetEstimated { peer_id, offset } => {
+                self.handle_clock_offset(effect_builder, peer_id, offset)
+            }
             Event::IncomingClosed {
                 result,
                 peer_id,
@@ -815,6 +1006,27 @@ where
             Event::PeerAddressReceived(gossiped_address) => {
                 self.connect_to_peer_if_required(gossiped_address.into())
             }
+            Event::ConnectPeer(peer_address) => {
+                if self.blocklist.remove(&peer_address) {
+                    info!(%peer_address, "{}: removed peer from blocklist", self.our_id);
+                }
+                self.connect_to_peer_if_required(peer_address)
+            }
+            Event::DisconnectPeer(peer_address) => {
+                self.disconnect(peer_address);
+                Effects::new()
+            }
+            Event::BanPeer(peer_address) => {
+                info!(%peer_address, "{}: adding peer to blocklist", self.our_id);
+                let _ = self.blocklist.insert(peer_address);
+                self.disconnect(peer_address);
+                Effects::new()
+            }
+            Event::SetGossipInterval(interval) => {
+                info!(?interval, "{}: setting gossip interval", self.our_id);
+                self.gossip_interval = interval;
+                Effects::new()
+            }
         }
     }
 }
@@ -888,8 +1100,12 @@ async fn setup_tls(
     secret_key: Arc<PKey<Private>>,
 ) -> Result<(NodeId, Transport)> {
     let tls_stream = tokio_openssl::accept(
-        &tls::create_tls_acceptor(&cert.as_x509().as_ref(), &secret_key.as_ref())
-            .map_err(Error::AcceptorCreation)?,
+        &tls::create_tls_acceptor(
+            &cert.as_x509().as_ref(),
+            &secret_key.as_ref(),
+            ALPN_PROTOCOLS,
+        )
+        .map_err(Error::AcceptorCreation)?,
         stream,
     )
     .await?;
@@ -908,23 +1124,55 @@ async fn setup_tls(
 
 /// Network message reader.
 ///
-/// Schedules all received messages until the stream is closed or an error occurs.
+/// Schedules all received messages until the stream is closed or an error occurs.  From peers
+/// speaking `WireVersion::V2`, a handshake is expected ahead of any messages.
 async fn message_reader<REv, P>(
     event_queue: EventQueueHandle<REv>,
-    mut stream: SplitStream<FramedTransport<P>>,
+    mut stream: SplitStream<FramedTransport>,
     mut shutdown_receiver: watch::Receiver<()>,
+    close_receiver: oneshot::Receiver<()>,
+    wire_version: WireVersion,
     our_id: NodeId,
     peer_id: NodeId,
 ) -> io::Result<()>
 where
-    P: DeserializeOwned + Send + Display,
+    P: Payload,
     REv: From<Event<P>>,
 {
     let our_id_ref = &our_id;
     let peer_id_cloned = peer_id.clone();
     let read_messages = async move {
-        while let Some(msg_result) = stream.next().await {
-            match msg_result {
+        if wire_version == WireVersion::V2 {
+            let handshake_result = match stream.next().await {
+                Some(frame_result) => {
+                    frame_result.and_then(|frame| codec::decode::<Handshake>(&frame))
+                }
+                None => return Ok(()),
+            };
+            match handshake_result {
+                Ok(Handshake { timestamp }) => {
+                    // Estimate the clock offset on receipt, before any queueing delay.
+                    let offset = clock_offsets::estimate_offset(Timestamp::now(), timestamp);
+                    debug!(offset, peer_id=%peer_id_cloned, "{}: handshake received", our_id_ref);
+                    event_queue
+                        .schedule(
+                            Event::ClockOffsetEstimated {
+                                peer_id: peer_id_cloned.clone(),
+                                offset,
+                            },
+                            QueueKind::NetworkIncoming,
+                        )
+                        .await;
+                }
+                Err(err) => {
+                    warn!(%err, peer_id=%peer_id_cloned, "{}: receiving handshake failed, closing connection", our_id_ref);
+                    return Err(err);
+                }
+            }
+        }
+
+        while let Some(frame_result) = stream.next().await {
+            match frame_result.and_then(|frame| P::decode(frame.freeze()).map(Message)) {
                 Ok(msg) => {
                     debug!(%msg, peer_id=%peer_id_cloned, "{}: message received", our_id_ref);
                     // We've received a message, push it to the reactor.
@@ -948,10 +1196,11 @@ where
     };
 
     let shutdown_messages = async move { while shutdown_receiver.recv().await.is_some() {} };
+    let stop = select(Box::pin(shutdown_messages), close_receiver);
 
-    // Now we can wait for either the `shutdown` channel's remote end to do be dropped or the
-    // while loop to terminate.
-    match select(Box::pin(shutdown_messages), Box::pin(read_messages)).await {
+    // Now we can wait for either the `shutdown` channel's remote end to do be dropped, the
+    // connection to be closed by dropping its entry in `incoming`, or the while loop to terminate.
+    match select(stop, Box::pin(read_messages)).await {
         Either::Left(_) => info!(
             %peer_id,
             "{}: shutting down incoming connection message reader",
@@ -965,17 +1214,26 @@ where
 
 /// Network message sender.
 ///
-/// Reads from a channel and sends all messages, until the stream is closed or an error occurs.
-async fn message_sender<P>(
-    mut queue: UnboundedReceiver<Message<P>>,
-    mut sink: SplitSink<FramedTransport<P>, Message<P>>,
-) -> Result<()>
-where
-    P: Serialize + Send,
-{
-    while let Some(payload) = queue.recv().await {
+/// Reads from a channel and sends all messages, until the stream is closed or an error occurs.  To
+/// peers speaking `WireVersion::V2`, a handshake is sent ahead of any messages.
+async fn message_sender(
+    mut queue: UnboundedReceiver<Bytes>,
+    mut sink: SplitSink<FramedTransport, Bytes>,
+    wire_version: WireVersion,
+) -> Result<()> {
+    if wire_version == WireVersion::V2 {
+        // Send our clock reading first, letting the peer estimate the offset of its clock from
+        // ours.
+        let handshake = codec::encode(&Handshake {
+            timestamp: Timestamp::now(),
+        })
+        .expect("should encode handshake");
+        sink.send(handshake).await.map_err(Error::MessageNotSent)?;
+    }
+
+    while let Some(frame) = queue.recv().await {
         // We simply error-out if the sink fails, it means that our connection broke.
-        sink.send(payload).await.map_err(Error::MessageNotSent)?;
+        sink.send(frame).await.map_err(Error::MessageNotSent)?;
     }
 
     Ok(())
@@ -984,22 +1242,6 @@ where
 /// Transport type alias for base encrypted connections.
 type Transport = SslStream<TcpStream>;
 
-/// A framed transport for `Message`s.
-type FramedTransport<P> = SymmetricallyFramed<
-    Framed<Transport, LengthDelimitedCodec>,
-    Message<P>,
-    SymmetricalMessagePack<Message<P>>,
->;
-
-/// Constructs a new framed transport on a stream.
-fn framed<P>(stream: Transport) -> FramedTransport<P> {
-    let length_delimited = Framed::new(stream, LengthDelimitedCodec::new());
-    SymmetricallyFramed::new(
-        length_delimited,
-        SymmetricalMessagePack::<Message<P>>::default(),
-    )
-}
-
 /// Initiates a TLS connection to a remote address.
 async fn connect_outgoing(
     peer_address: SocketAddr,
@@ -1007,10 +1249,11 @@ async fn connect_outgoing(
     secret_key: Arc<PKey<Private>>,
     server_is_stopped: Arc<AtomicBool>,
 ) -> Result<(NodeId, Transport)> {
-    let mut config = tls::create_tls_connector(&our_certificate.as_x509(), &secret_key)
-        .context("could not create TLS connector")?
-        .configure()
-        .map_err(Error::ConnectorConfiguration)?;
+    let mut config =
+        tls::create_tls_connector(&our_certificate.as_x509(), &secret_key, ALPN_PROTOCOLS)
+            .context("could not create TLS connector")?
+            .configure()
+            .map_err(Error::ConnectorConfiguration)?;
     config.set_verify_hostname(false);
 
     let stream = TcpStream::connect(peer_address)
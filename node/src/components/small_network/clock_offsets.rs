@@ -0,0 +1,88 @@
+//! Estimates of the offsets of peers' clocks from our own.
+//!
+//! Every outgoing connection to a peer speaking `WireVersion::V2` starts with a handshake frame
+//! carrying the sender's clock reading.  On receipt, the difference between our clock and that
+//! reading is recorded as the peer's offset. As there is no round trip, the estimate includes the
+//! one-way network latency, so it overstates how far our clock is ahead of the peer's by a few
+//! milliseconds.
+
+use std::collections::HashMap;
+
+use datasize::DataSize;
+
+use crate::types::{NodeId, Timestamp};
+
+/// Returns the estimated offset in milliseconds of our clock from that of a peer which sent
+/// `remote` when our clock read `local`.
+///
+/// The offset is positive if our clock is ahead of the peer's.
+pub(super) fn estimate_offset(local: Timestamp, remote: Timestamp) -> i64 {
+    local.millis() as i64 - remote.millis() as i64
+}
+
+/// The estimated offsets of connected peers' clocks from our own, in milliseconds.
+#[derive(DataSize, Debug, Default)]
+pub(super) struct ClockOffsets {
+    offsets: HashMap<NodeId, i64>,
+}
+
+impl ClockOffsets {
+    /// Records the latest estimate of the offset of the given peer's clock.
+    pub(super) fn insert(&mut self, peer_id: NodeId, offset: i64) {
+        let _ = self.offsets.insert(peer_id, offset);
+    }
+
+    /// Forgets the offset of a peer which disconnected.
+    pub(super) fn remove(&mut self, peer_id: &NodeId) {
+        let _ = self.offsets.remove(peer_id);
+    }
+
+    /// Returns the median offset of our clock from the peers' clocks, i.e. our clock's drift from
+    /// the network's, or `None` if no offsets are known.
+    pub(super) fn median(&self) -> Option<i64> {
+        let mut offsets: Vec<i64> = self.offsets.values().copied().collect();
+        if offsets.is_empty() {
+            return None;
+        }
+        offsets.sort_unstable();
+        let middle = offsets.len() / 2;
+        if offsets.len() % 2 == 0 {
+            Some((offsets[middle - 1] + offsets[middle]) / 2)
+        } else {
+            Some(offsets[middle])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestRng;
+
+    #[test]
+    fn should_estimate_signed_offset() {
+        let earlier = Timestamp::from(1_000);
+        let later = Timestamp::from(1_250);
+        assert_eq!(estimate_offset(later, earlier), 250);
+        assert_eq!(estimate_offset(earlier, later), -250);
+    }
+
+    #[test]
+    fn should_take_median_of_offsets() {
+        let mut rng = TestRng::new();
+        let mut clock_offsets = ClockOffsets::default();
+        assert_eq!(clock_offsets.median(), None);
+
+        let peers: Vec<NodeId> = (0..4).map(|_| NodeId::random(&mut rng)).collect();
+        clock_offsets.insert(peers[0].clone(), 40);
+        clock_offsets.insert(peers[1].clone(), -3_000);
+        clock_offsets.insert(peers[2].clone(), 10);
+        assert_eq!(clock_offsets.median(), Some(10));
+
+        clock_offsets.insert(peers[3].clone(), 20);
+        assert_eq!(clock_offsets.median(), Some(15));
+
+        clock_offsets.remove(&peers[1]);
+        assert_eq!(clock_offsets.median(), Some(20));
+    }
+}
@@ -0,0 +1,136 @@
+//! Framing and encoding of the messages sent between nodes.
+//!
+//! Each message is MessagePack-encoded into a length-delimited frame.  Outgoing messages are
+//! encoded once into a [`Bytes`] buffer, which is shared rather than copied between the connections
+//! it is sent on.  Incoming messages are decoded straight from the buffer their frame was read
+//! into, and their payloads may refer to parts of it rather than copying them.
+//!
+//! # Wire versions
+//!
+//! The version of the wire format spoken on a connection is negotiated via ALPN during the TLS
+//! handshake.  Nodes predating the negotiation neither offer nor select a protocol, and so are
+//! spoken to in `WireVersion::V1`, the original format.
+
+use std::io;
+
+use bytes::Bytes;
+use datasize::DataSize;
+use openssl::ssl::SslRef;
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::{Framed, LengthDelimitedCodec};
+
+use super::{Message, Transport};
+
+/// The ALPN protocol identifying `WireVersion::V2`.
+const ALPN_PROTOCOL_V2: &[u8] = b"casper/2";
+
+/// The ALPN protocols we support, in ALPN wire format and order of preference.
+pub(super) const ALPN_PROTOCOLS: &[u8] = b"\x08casper/2";
+
+/// The version of the wire format spoken on a connection.
+#[derive(Clone, Copy, DataSize, Debug, Eq, Hash, PartialEq)]
+pub enum WireVersion {
+    /// The original wire format: every frame holds a `Message`.
+    V1,
+    /// As `V1`, but an outgoing connection's first frame holds a `Handshake`, and payloads may be
+    /// encoded differently, see `Payload::encode`.
+    V2,
+}
+
+impl WireVersion {
+    /// Returns the version negotiated during the TLS handshake on the given connection.
+    pub(super) fn negotiated(ssl: &SslRef) -> Self {
+        if ssl.selected_alpn_protocol() == Some(ALPN_PROTOCOL_V2) {
+            WireVersion::V2
+        } else {
+            WireVersion::V1
+        }
+    }
+}
+
+/// A transport framed into length-delimited frames, each holding an encoded `Message` or
+/// `Handshake`.
+pub(super) type FramedTransport = Framed<Transport, LengthDelimitedCodec>;
+
+/// Constructs a new framed transport on a stream.
+pub(super) fn framed(stream: Transport) -> FramedTransport {
+    Framed::new(stream, LengthDelimitedCodec::new())
+}
+
+/// Encodes a message into a frame, ready to be sent on any number of connections.
+pub(super) fn encode<T: Serialize>(msg: &T) -> Result<Bytes, rmp_serde::encode::Error> {
+    rmp_serde::to_vec(msg).map(Bytes::from)
+}
+
+/// Decodes a message from a received frame.
+pub(super) fn decode<'de, T: Deserialize<'de>>(frame: &'de [u8]) -> io::Result<T> {
+    rmp_serde::from_read_ref(frame)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+/// Encodes a `Message` holding the given payload into a frame.
+///
+/// The payload may be given in any representation which serializes alike, e.g. one borrowing its
+/// fields.
+pub(crate) fn encode_payload<T: Serialize + ?Sized>(
+    payload: &T,
+) -> Result<Bytes, rmp_serde::encode::Error> {
+    encode(&Message(payload))
+}
+
+/// Decodes the payload of a `Message` from a received frame.
+///
+/// The payload may be decoded into any representation which deserializes alike, e.g. one borrowing
+/// from the frame.
+pub(crate) fn decode_payload<'de, T: Deserialize<'de>>(frame: &'de [u8]) -> io::Result<T> {
+    decode::<Message<T>>(frame).map(|msg| msg.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        components::small_network::{message::Handshake, Message},
+        types::Timestamp,
+    };
+
+    /// `Message` as encoded by nodes predating wire version negotiation.
+    #[derive(Serialize)]
+    struct BaselineMessage<P>(P);
+
+    #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+    enum TestPayload {
+        Ping { nonce: u64 },
+        Data(Vec<u8>),
+    }
+
+    #[test]
+    fn should_roundtrip_messages() {
+        let timestamp = Timestamp::now();
+        let frame = encode(&Handshake { timestamp }).unwrap();
+        assert_eq!(decode::<Handshake>(&frame).unwrap().timestamp, timestamp);
+
+        let frame = encode(&Message(vec![7u8; 1024])).unwrap();
+        assert_eq!(
+            decode::<Message<Vec<u8>>>(&frame).unwrap().0,
+            vec![7u8; 1024]
+        );
+
+        assert!(decode::<Message<Vec<u8>>>(&frame[..frame.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn should_decode_baseline_frames() {
+        for payload in vec![
+            TestPayload::Ping { nonce: 42 },
+            TestPayload::Data(vec![1, 2, 3]),
+        ] {
+            let baseline_frame = rmp_serde::to_vec(&BaselineMessage(payload.clone())).unwrap();
+            assert_eq!(
+                decode::<Message<TestPayload>>(&baseline_frame).unwrap().0,
+                payload
+            );
+            assert_eq!(encode(&Message(payload)).unwrap(), baseline_frame);
+        }
+    }
+}
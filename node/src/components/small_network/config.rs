@@ -18,6 +18,9 @@ const DEFAULT_PUBLIC_ADDRESS: &str = "127.0.0.1:0";
 /// Default interval for gossiping network addresses.
 const DEFAULT_GOSSIP_INTERVAL: Duration = Duration::from_secs(30);
 
+/// Default maximum drift of our clock from the network's before warning.
+const DEFAULT_MAX_CLOCK_DRIFT: Duration = Duration::from_secs(5);
+
 // Default values for networking configuration:
 impl Default for Config {
     fn default() -> Self {
@@ -27,10 +30,15 @@ impl Default for Config {
             known_addresses: Vec::new(),
             gossip_interval: DEFAULT_GOSSIP_INTERVAL,
             systemd_support: false,
+            max_clock_drift: DEFAULT_MAX_CLOCK_DRIFT,
         }
     }
 }
 
+fn default_max_clock_drift() -> Duration {
+    DEFAULT_MAX_CLOCK_DRIFT
+}
+
 /// Small network configuration.
 #[derive(DataSize, Debug, Clone, Deserialize, Serialize)]
 // Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
@@ -49,6 +57,13 @@ pub struct Config {
     pub gossip_interval: Duration,
     /// Enable systemd startup notification.
     pub systemd_support: bool,
+    /// Maximum drift in milliseconds of our clock from the median of peers' clocks, estimated
+    /// during connection handshakes, before a warning is raised.
+    #[serde(
+        default = "default_max_clock_drift",
+        with = "crate::utils::milliseconds"
+    )]
+    pub max_clock_drift: Duration,
 }
 
 #[cfg(test)]
@@ -70,6 +85,7 @@ impl Config {
             known_addresses: Vec::new(),
             gossip_interval: DEFAULT_TEST_GOSSIP_INTERVAL,
             systemd_support: false,
+            max_clock_drift: DEFAULT_MAX_CLOCK_DRIFT,
         }
     }
 
@@ -88,6 +104,7 @@ impl Config {
             ],
             gossip_interval: DEFAULT_TEST_GOSSIP_INTERVAL,
             systemd_support: false,
+            max_clock_drift: DEFAULT_MAX_CLOCK_DRIFT,
         }
     }
 }
@@ -115,4 +115,11 @@ pub enum Error {
     /// Server has stopped.
     #[error("failed to create outgoing connection as server has stopped")]
     ServerStopped,
+    /// Failed to register metrics.
+    #[error("failed to register metrics: {0}")]
+    Metrics(
+        #[serde(skip_serializing)]
+        #[from]
+        prometheus::Error,
+    ),
 }
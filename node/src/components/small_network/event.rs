@@ -2,6 +2,7 @@ use std::{
     fmt::{self, Debug, Display, Formatter},
     io,
     net::SocketAddr,
+    time::Duration,
 };
 
 use derive_more::From;
@@ -32,6 +33,8 @@ pub enum Event<P> {
     },
     /// Received network message.
     IncomingMessage { peer_id: NodeId, msg: Message<P> },
+    /// Estimated the offset in milliseconds of our clock from a peer's from its handshake.
+    ClockOffsetEstimated { peer_id: NodeId, offset: i64 },
     /// Incoming connection closed.
     IncomingClosed {
         #[serde(skip_serializing)]
@@ -71,6 +74,16 @@ pub enum Event<P> {
     GossipOurAddress,
     /// We received a peer's public listening address via gossip.
     PeerAddressReceived(GossipedAddress),
+    /// The node operator requested that we connect to the peer listening on the given address.
+    ConnectPeer(SocketAddr),
+    /// The node operator requested that we disconnect from the peer listening on the given
+    /// address.
+    DisconnectPeer(SocketAddr),
+    /// The node operator requested that we disconnect from the peer listening on the given address
+    /// and never reconnect to it.
+    BanPeer(SocketAddr),
+    /// The node operator requested a new interval between rounds of gossiping our own address.
+    SetGossipInterval(Duration),
 }
 
 impl<P: Display> Display for Event<P> {
@@ -100,6 +113,9 @@ impl<P: Display> Display for Event<P> {
                 peer_id: node_id,
                 msg,
             } => write!(f, "msg from {}: {}", node_id, msg),
+            Event::ClockOffsetEstimated { peer_id, offset } => {
+                write!(f, "clock offset of {} ms from {}", offset, peer_id)
+            }
             Event::IncomingClosed { peer_address, .. } => {
                 write!(f, "closed connection from {}", peer_address)
             }
@@ -133,6 +149,12 @@ impl<P: Display> Display for Event<P> {
             Event::PeerAddressReceived(gossiped_address) => {
                 write!(f, "received gossiped peer address {}", gossiped_address)
             }
+            Event::ConnectPeer(peer_address) => write!(f, "connect to {}", peer_address),
+            Event::DisconnectPeer(peer_address) => write!(f, "disconnect from {}", peer_address),
+            Event::BanPeer(peer_address) => write!(f, "ban {}", peer_address),
+            Event::SetGossipInterval(interval) => {
+                write!(f, "set gossip interval to {:?}", interval)
+            }
         }
     }
 }
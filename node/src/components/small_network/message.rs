@@ -2,6 +2,8 @@ use std::fmt::{self, Debug, Display, Formatter};
 
 use serde::{Deserialize, Serialize};
 
+use crate::types::Timestamp;
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Message<P>(pub(super) P);
 
@@ -10,3 +12,10 @@ impl<P: Display> Display for Message<P> {
         write!(f, "payload: {}", self.0)
     }
 }
+
+/// The first frame sent on an outgoing connection speaking `WireVersion::V2`, ahead of any
+/// messages, carrying the sender's clock reading.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(super) struct Handshake {
+    pub(super) timestamp: Timestamp,
+}
@@ -0,0 +1,36 @@
+use prometheus::{IntGauge, Registry};
+
+/// Metrics for the small network.
+#[derive(Debug)]
+pub(super) struct NetworkMetrics {
+    /// Median estimated offset in milliseconds of our clock from connected peers' clocks.
+    pub(super) clock_drift: IntGauge,
+    /// Reference to the registry for unregistering.
+    registry: Registry,
+}
+
+impl NetworkMetrics {
+    /// Creates a new instance of the small network metrics.
+    pub(super) fn new(registry: &Registry) -> Result<Self, prometheus::Error> {
+        let clock_drift = IntGauge::new(
+            "clock_drift",
+            "median estimated offset in milliseconds of our clock from connected peers' clocks, \
+            positive if ours is ahead",
+        )?;
+
+        registry.register(Box::new(clock_drift.clone()))?;
+
+        Ok(NetworkMetrics {
+            clock_drift,
+            registry: registry.clone(),
+        })
+    }
+}
+
+impl Drop for NetworkMetrics {
+    fn drop(&mut self) {
+        self.registry
+            .unregister(Box::new(self.clock_drift.clone()))
+            .expect("did not expect deregistering clock_drift to fail");
+    }
+}
@@ -0,0 +1,34 @@
+//! The payloads carried in messages between nodes.
+
+use std::{
+    fmt::{Debug, Display},
+    io,
+};
+
+use bytes::Bytes;
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::codec::{self, WireVersion};
+
+/// A payload carried in messages between nodes.
+///
+/// By default, a payload is encoded alike for every wire version.  Payloads whose encoding differs
+/// between wire versions, or which can't be sent to peers speaking some of them, override
+/// `encode`.  Payloads which can refer to parts of a received frame rather than copying them
+/// override `decode`.
+pub trait Payload: Serialize + DeserializeOwned + Clone + Debug + Display + Send + 'static {
+    /// Encodes a message holding the payload into a frame for peers speaking `wire_version`.
+    ///
+    /// Returns `Ok(None)` if the payload can't be sent to such peers.
+    fn encode(
+        &self,
+        _wire_version: WireVersion,
+    ) -> Result<Option<Bytes>, rmp_serde::encode::Error> {
+        codec::encode_payload(self).map(Some)
+    }
+
+    /// Decodes a payload from a received frame holding a message.
+    fn decode(frame: Bytes) -> io::Result<Self> {
+        codec::decode_payload(&frame)
+    }
+}
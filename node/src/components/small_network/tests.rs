@@ -16,7 +16,7 @@ use prometheus::Registry;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info};
 
-use super::{Config, Event as SmallNetworkEvent, GossipedAddress, SmallNetwork};
+use super::{Config, Event as SmallNetworkEvent, GossipedAddress, Payload, SmallNetwork};
 use crate::{
     components::{
         gossiper::{self, Gossiper},
@@ -90,6 +90,8 @@ impl Display for Message {
     }
 }
 
+impl Payload for Message {}
+
 /// Test reactor.
 ///
 /// Runs a single small network.
@@ -110,7 +112,7 @@ impl Reactor for TestReactor {
         event_queue: EventQueueHandle<Self::Event>,
         _rng: &mut NodeRng,
     ) -> anyhow::Result<(Self, Effects<Self::Event>)> {
-        let (net, effects) = SmallNetwork::new(event_queue, cfg, false)?;
+        let (net, effects) = SmallNetwork::new(event_queue, cfg, registry, false)?;
         let gossiper_config = gossiper::Config::default();
         let address_gossiper =
             Gossiper::new_for_complete_items("address_gossiper", gossiper_config, registry)?;
@@ -167,6 +169,10 @@ impl Reactor for TestReactor {
                 // We do not care about the announcement of new peers in this test.
                 Effects::new()
             }
+            Event::NetworkAnnouncement(NetworkAnnouncement::ClockDrift { .. }) => {
+                // We do not care about clock drift in this test.
+                Effects::new()
+            }
             Event::AddressGossiperAnnouncement(ann) => {
                 let GossiperAnnouncement::NewCompleteItem(gossiped_address) = ann;
                 let reactor_event =
@@ -3,11 +3,18 @@
 //! The central storage component is in charge of persisting data to disk. Its core functionalities
 //! are
 //!
-//! * storing and loading blocks,
+//! * storing and loading blocks, and appending finality signatures to stored blocks,
 //! * storing and loading deploys,
 //! * [temporary until refactored] holding `DeployMetadata` for each deploy,
 //! * holding a read-only copy of the chainspec,
-//! * keeping an index of blocks by height and
+//! * keeping an index of blocks by height,
+//! * keeping an index of switch blocks by era,
+//! * keeping an index of the deploys which wrote to each global state key,
+//! * keeping an index of the deploys involving each account,
+//! * keeping an index of the block on the linear chain including each deploy,
+//! * keeping an index of successful native transfers by target,
+//! * retaining blocks which conflict with the linear chain as alternative blocks,
+//! * optionally taking periodic backups of its database (see the `backup` module) and
 //! * [unimplemented] managing disk usage by pruning blocks and deploys from storage.
 //!
 //! Any I/O performed by the component is done on the event handling thread, this is on purpose as
@@ -21,59 +28,97 @@
 //! * [temporary until refactored] Storing an execution result for a deploy in the context of a
 //!   block is guaranteed to be idempotent: Storing the same result twice is a no-op, whilst
 //!   attempting to store a differing one will cause a fatal error.
-//! * Only one block can ever be associated with a specific block height. Attempting to store a
-//!   block with a different block already existing at the same height causes a fatal error.
+//! * Only one block can ever be associated with a specific block height on the linear chain. A
+//!   block stored with a different block already existing at the same height is retained as an
+//!   alternative block instead, which is never part of the linear chain and has no execution
+//!   results or index entries stored for it.
 //! * Storing a deploy or block that already exists (same hash) is fine and will silently be
 //!   accepted.
+//! * A block stored together with its execution results is committed in a single transaction, along
+//!   with all the resulting index updates. As LMDB only makes a transaction visible once it has
+//!   been fully written to disk, an interrupted commit leaves no trace of the block, and it is
+//!   simply stored again once the node has restarted and re-executed it.
 //!
 //! ## Indices
 //!
 //! The current implementation keeps only in-memory indices, which are not persisted, based upon the
 //! estimate that they are reasonably quick to rebuild on start-up and do not take up much memory.
 //!
+//! The exceptions are the key history index, which maps each global state key to the deploys which
+//! wrote to it, and the transfer index, which maps each account or purse to the successful native
+//! transfers to it, both in order of their execution results being stored, as well as the account
+//! activity index, which maps each account to the deploys involving it, in order of their blocks
+//! being stored, and the deploy block index, which maps each deploy to the block on the linear
+//! chain including it. They are maintained whenever a new execution result or block is stored
+//! respectively, and persisted in their own databases, as rebuilding them would require scanning
+//! every execution result or block. Data stored before an index existed is not covered by it.
+//!
+//! A deploy involves the account on whose behalf it is executed and, if it is a native transfer to
+//! an account, the target account. Deploys which are not yet stored when their block is stored are
+//! not indexed. Likewise, transfers are only indexed if their block is already stored when their
+//! execution results are stored, as is the case for blocks added to the linear chain.
+//!
+//! All four indices can be rebuilt from the blocks, deploys and execution results held in storage
+//! using [`rebuild_indexes`], e.g. after upgrading from a version of the node lacking them. A
+//! rebuilt index holds the entries of each block in order of height. The blocks in which a deploy
+//! was executed are recorded alongside its execution results in its metadata, which is primary
+//! data and is never rebuilt.
+//!
 //! ## Errors
 //!
 //! The storage component itself is panic free and in general reports three classes of errors:
 //! Corruption, temporary resource exhaustion and potential bugs.
 
+mod backup;
 mod lmdb_ext;
+mod metrics;
 #[cfg(test)]
 mod tests;
 
+#[cfg(test)]
+use std::convert::TryFrom;
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet, HashMap},
     fmt::{self, Display, Formatter},
     fs, io,
-    path::PathBuf,
+    path::{Path, PathBuf},
+    str::FromStr,
     sync::Arc,
 };
-#[cfg(test)]
-use std::{collections::BTreeSet, convert::TryFrom};
 
 use datasize::DataSize;
 use derive_more::From;
-use lmdb::{Cursor, Database, DatabaseFlags, Environment, EnvironmentFlags, Transaction};
-use serde::{Deserialize, Serialize};
+use lmdb::{
+    Cursor, Database, DatabaseFlags, Environment, EnvironmentFlags, RwTransaction, Transaction,
+};
+use prometheus::Registry;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 #[cfg(test)]
 use tempfile::TempDir;
 use thiserror::Error;
-use tracing::info;
+use tracing::{info, warn};
+
+use casper_types::Key;
 
 use super::Component;
-#[cfg(test)]
-use crate::crypto::hash::Digest;
 use crate::{
+    components::consensus::EraId,
+    crypto::{asymmetric_key::Signature, hash::Digest},
     effect::{requests::StorageRequest, EffectBuilder, EffectExt, Effects},
     fatal,
-    types::{Block, BlockHash, Deploy, DeployHash, DeployMetadata},
-    utils::WithDir,
+    types::{
+        json_compatibility::ExecutionResult, Block, BlockHash, Deploy, DeployHash, DeployMetadata,
+        KeyHistoryEntry, TransferEntry,
+    },
+    utils::{DisplayIter, WithDir},
     Chainspec, NodeRng,
 };
 use lmdb_ext::{LmdbExtError, TransactionExt, WriteTransactionExt};
+use metrics::Metrics;
 
 /// We can set this very low, as there is only a single reader/writer accessing the component at any
-/// one time.
-const MAX_TRANSACTIONS: u32 = 1;
+/// one time, plus the reader taking a backup if backups are enabled.
+const MAX_TRANSACTIONS: u32 = 2;
 
 /// One Gibibyte.
 const GIB: usize = 1024 * 1024 * 1024;
@@ -84,6 +129,22 @@ const DEFAULT_MAX_BLOCK_STORE_SIZE: usize = 450 * GIB;
 const DEFAULT_MAX_DEPLOY_STORE_SIZE: usize = 300 * GIB;
 /// Default max deploy metadata store size.
 const DEFAULT_MAX_DEPLOY_METADATA_STORE_SIZE: usize = 300 * GIB;
+/// Default max key history store size.
+const DEFAULT_MAX_KEY_HISTORY_STORE_SIZE: usize = 100 * GIB;
+/// Default max account deploys store size.
+const DEFAULT_MAX_ACCOUNT_DEPLOYS_STORE_SIZE: usize = 50 * GIB;
+/// Default max deploy block store size.
+const DEFAULT_MAX_DEPLOY_BLOCK_STORE_SIZE: usize = 50 * GIB;
+/// Default max transfer store size.
+const DEFAULT_MAX_TRANSFER_STORE_SIZE: usize = 50 * GIB;
+/// Default max alternative block store size.
+const DEFAULT_MAX_ALTERNATIVE_BLOCK_STORE_SIZE: usize = 10 * GIB;
+
+/// Separates the list key from the entry index in the keys of the entries of append-only lists.
+///
+/// List keys are either formatted global state keys, which never contain a NUL byte, or of fixed
+/// length, so entry keys can't collide with the list keys under which the list lengths are stored.
+const LIST_ENTRY_SEPARATOR: u8 = 0;
 
 #[derive(Debug, From)]
 pub enum Event {
@@ -119,6 +180,9 @@ pub enum Error {
     /// LMDB error while operating.
     #[error("internal database error: {0}")]
     InternalStorage(#[from] LmdbExtError),
+    /// Failure to register the metrics.
+    #[error("failed to register storage metrics: {0}")]
+    Metrics(#[from] prometheus::Error),
 }
 
 // We wholesale wrap lmdb errors and treat them as internal errors here.
@@ -128,13 +192,69 @@ impl From<lmdb::Error> for Error {
     }
 }
 
+/// A secondary index persisted by the storage component.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Index {
+    /// The index of the deploys involving each account.
+    AccountDeploys,
+    /// The index of the block on the linear chain including each deploy.
+    DeployBlocks,
+    /// The index of the deploys which wrote to each global state key.
+    KeyHistory,
+    /// The index of successful native transfers by target.
+    Transfers,
+}
+
+impl Index {
+    /// All the secondary indices.
+    pub const ALL: [Index; 4] = [
+        Index::AccountDeploys,
+        Index::DeployBlocks,
+        Index::KeyHistory,
+        Index::Transfers,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Index::AccountDeploys => "account-deploys",
+            Index::DeployBlocks => "deploy-blocks",
+            Index::KeyHistory => "key-history",
+            Index::Transfers => "transfers",
+        }
+    }
+}
+
+impl Display for Index {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.name())
+    }
+}
+
+impl FromStr for Index {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Index::ALL
+            .iter()
+            .copied()
+            .find(|index| index.name() == name)
+            .ok_or_else(|| {
+                format!(
+                    "unknown index '{}', expected one of {}",
+                    name,
+                    DisplayIter::new(Index::ALL.iter())
+                )
+            })
+    }
+}
+
 #[derive(DataSize, Debug)]
 pub struct Storage {
     /// Storage location.
     root: PathBuf,
     /// Environment holding LMDB databases.
     #[data_size(skip)]
-    env: Environment,
+    env: Arc<Environment>,
     /// The block database.
     #[data_size(skip)]
     block_db: Database,
@@ -144,10 +264,49 @@ pub struct Storage {
     /// The deploy metadata database.
     #[data_size(skip)]
     deploy_metadata_db: Database,
+    /// The key history database.
+    #[data_size(skip)]
+    key_history_db: Database,
+    /// The account deploys database.
+    #[data_size(skip)]
+    account_deploys_db: Database,
+    /// The deploy block database, mapping each deploy to the block on the linear chain including
+    /// it.
+    #[data_size(skip)]
+    deploy_block_db: Database,
+    /// The transfer database.
+    #[data_size(skip)]
+    transfer_db: Database,
+    /// The database of blocks conflicting with the linear chain.
+    #[data_size(skip)]
+    alternative_block_db: Database,
     /// Block height index.
     block_height_index: BTreeMap<u64, BlockHash>,
+    /// Alternative block height index.
+    alternative_block_height_index: BTreeMap<u64, Vec<BlockHash>>,
+    /// Switch block index, keyed by the era each switch block concludes.
+    switch_block_era_index: BTreeMap<EraId, BlockHash>,
+    /// State root index, mapping each state root hash to the height of the highest block with it.
+    state_root_height_index: HashMap<Digest, u64>,
     /// Chainspec cache.
     chainspec_cache: Option<Arc<Chainspec>>,
+    /// Backup configuration, with the backup folder resolved.
+    backup_config: backup::Config,
+    /// Metrics.
+    #[data_size(skip)]
+    metrics: Metrics,
+}
+
+/// The outcome of writing a block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PutBlockOutcome {
+    /// The block was added to the linear chain.
+    Added,
+    /// The block was stored previously.
+    AlreadyStored,
+    /// A different block is on the linear chain at the same height, so the block was retained as
+    /// an alternative block instead.
+    Alternative,
 }
 
 impl<REv> Component<REv> for Storage {
@@ -176,7 +335,7 @@ impl<REv> Component<REv> for Storage {
 
 impl Storage {
     /// Creates a new storage component.
-    pub(crate) fn new(cfg: &WithDir<Config>) -> Result<Self, Error> {
+    pub(crate) fn new(cfg: &WithDir<Config>, registry: &Registry) -> Result<Self, Error> {
         let config = cfg.value();
 
         // Create the database directory.
@@ -190,7 +349,12 @@ impl Storage {
         let total_size = config
             .max_block_store_size
             .saturating_add(config.max_deploy_store_size)
-            .saturating_add(config.max_deploy_metadata_store_size);
+            .saturating_add(config.max_deploy_metadata_store_size)
+            .saturating_add(config.max_key_history_store_size)
+            .saturating_add(config.max_account_deploys_store_size)
+            .saturating_add(config.max_deploy_block_store_size)
+            .saturating_add(config.max_transfer_store_size)
+            .saturating_add(config.max_alternative_block_store_size);
 
         // Creates the environment and databases.
         let env = Environment::new()
@@ -203,17 +367,25 @@ impl Storage {
                     | EnvironmentFlags::WRITE_MAP,
             )
             .set_max_readers(MAX_TRANSACTIONS)
-            .set_max_dbs(3)
+            .set_max_dbs(8)
             .set_map_size(total_size)
             .open(&root.join("storage.lmdb"))?;
 
         let block_db = env.create_db(Some("blocks"), DatabaseFlags::empty())?;
         let deploy_db = env.create_db(Some("deploys"), DatabaseFlags::empty())?;
         let deploy_metadata_db = env.create_db(Some("deploy_metadata"), DatabaseFlags::empty())?;
+        let key_history_db = env.create_db(Some("key_history"), DatabaseFlags::empty())?;
+        let account_deploys_db = env.create_db(Some("account_deploys"), DatabaseFlags::empty())?;
+        let deploy_block_db = env.create_db(Some("deploy_blocks"), DatabaseFlags::empty())?;
+        let transfer_db = env.create_db(Some("transfers"), DatabaseFlags::empty())?;
+        let alternative_block_db =
+            env.create_db(Some("alternative_blocks"), DatabaseFlags::empty())?;
 
         // We now need to restore the block-height index. Log messages allow timing here.
         info!("reindexing block store");
         let mut block_height_index = BTreeMap::new();
+        let mut switch_block_era_index = BTreeMap::new();
+        let mut state_root_height_index = HashMap::new();
         let block_txn = env.begin_ro_txn()?;
         let mut cursor = block_txn.open_ro_cursor(block_db)?;
 
@@ -236,22 +408,112 @@ impl Storage {
                     second: duplicate,
                 });
             }
+            if header.switch_block() {
+                switch_block_era_index.insert(header.era_id(), *block.hash());
+            }
+            insert_into_state_root_height_index(
+                &mut state_root_height_index,
+                *header.state_root_hash(),
+                header.height(),
+            );
+        }
+        drop(cursor);
+
+        let mut alternative_block_height_index: BTreeMap<u64, Vec<BlockHash>> = BTreeMap::new();
+        let mut cursor = block_txn.open_ro_cursor(alternative_block_db)?;
+        for (_, raw_val) in cursor.iter() {
+            let block: Block = lmdb_ext::deserialize(raw_val)?;
+            alternative_block_height_index
+                .entry(block.height())
+                .or_default()
+                .push(*block.hash());
         }
         info!("block store reindexing complete");
         drop(cursor);
         drop(block_txn);
 
+        let metrics = Metrics::new(registry)?;
+        metrics.alternative_blocks.set(
+            alternative_block_height_index
+                .values()
+                .map(Vec::len)
+                .sum::<usize>() as i64,
+        );
+
+        let mut backup_config = config.backup.clone();
+        backup_config.path = backup_config.path.map(|path| cfg.with_dir(path));
+
         Ok(Storage {
             root,
-            env,
+            env: Arc::new(env),
             block_db,
             deploy_db,
             deploy_metadata_db,
+            key_history_db,
+            account_deploys_db,
+            deploy_block_db,
+            transfer_db,
+            alternative_block_db,
             block_height_index,
+            alternative_block_height_index,
+            switch_block_era_index,
+            state_root_height_index,
             chainspec_cache: None,
+            backup_config,
+            metrics,
         })
     }
 
+    /// Starts taking periodic backups in the background, if enabled in the config.
+    pub(crate) fn start_backups(&self, registry: &Registry) -> Result<(), prometheus::Error> {
+        backup::spawn(&self.env, &self.backup_config, registry)
+    }
+
+    /// Returns the folder holding the storage's files.
+    pub(crate) fn root_path(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns the LMDB environment holding the storage's databases.
+    pub(crate) fn lmdb_environment(&self) -> Arc<Environment> {
+        Arc::clone(&self.env)
+    }
+
+    /// Reads the block with the greatest height, bypassing the request queue.
+    ///
+    /// Used on startup to check that global state is consistent with the stored blocks.
+    pub(crate) fn read_highest_block(&self) -> Result<Option<Block>, Error> {
+        Ok(self.get_highest_block(&mut self.env.begin_ro_txn()?)?)
+    }
+
+    /// Reads the block with the given hash, bypassing the request queue.
+    pub(crate) fn read_block(&self, block_hash: &BlockHash) -> Result<Option<Block>, Error> {
+        Ok(self.get_single_block(&mut self.env.begin_ro_txn()?, block_hash)?)
+    }
+
+    /// Reads the block at the given height, bypassing the request queue.
+    pub(crate) fn read_block_by_height(&self, height: u64) -> Result<Option<Block>, Error> {
+        Ok(self.get_block_by_height(&mut self.env.begin_ro_txn()?, height)?)
+    }
+
+    /// Reads a deploy along with the result of its execution in the given block, if any, bypassing
+    /// the request queue.
+    pub(crate) fn read_deploy_and_execution_result(
+        &self,
+        deploy_hash: &DeployHash,
+        block_hash: &BlockHash,
+    ) -> Result<Option<(Deploy, Option<ExecutionResult>)>, Error> {
+        let mut txn = self.env.begin_ro_txn()?;
+        let deploy: Deploy = match txn.get_value(self.deploy_db, deploy_hash)? {
+            Some(deploy) => deploy,
+            None => return Ok(None),
+        };
+        let execution_result = self
+            .get_deploy_metadata(&mut txn, deploy_hash)?
+            .and_then(|mut metadata| metadata.execution_results.remove(block_hash));
+        Ok(Some((deploy, execution_result)))
+    }
+
     /// Handles a storage request.
     fn handle_storage_request<REv>(
         &mut self,
@@ -267,26 +529,45 @@ impl Storage {
         Ok(match req {
             StorageRequest::PutBlock { block, responder } => {
                 let mut txn = self.env.begin_rw_txn()?;
-                let outcome = txn.put_value(self.block_db, block.hash(), &block, false)?;
+                let outcome = self.put_block(&mut txn, &block)?;
                 txn.commit()?;
 
-                if outcome {
-                    // If we are attempting to insert a duplicate block, return with error.
-                    if let Some(first) = self.block_height_index.get(&block.height()) {
-                        if first != block.hash() {
-                            return Err(Error::DuplicateBlockIndex {
-                                height: block.height(),
-                                first: *first,
-                                second: *block.hash(),
-                            });
-                        }
-                    }
+                responder
+                    .respond(self.record_put_block(&block, outcome))
+                    .ignore()
+            }
+            StorageRequest::PutBlockAndExecutionResults {
+                block,
+                execution_results,
+                responder,
+            } => {
+                // Everything is written in a single transaction, so either all of it or none of it
+                // is persisted, even if the node is interrupted midway.
+                let mut txn = self.env.begin_rw_txn()?;
+                let outcome = self.put_block(&mut txn, &block)?;
+                if outcome != PutBlockOutcome::Alternative {
+                    self.put_execution_results(
+                        &mut txn,
+                        *block.hash(),
+                        Some(block.height()),
+                        execution_results,
+                    )?;
                 }
+                txn.commit()?;
 
-                self.block_height_index
-                    .insert(block.height(), *block.hash());
-
-                responder.respond(outcome).ignore()
+                responder
+                    .respond(self.record_put_block(&block, outcome))
+                    .ignore()
+            }
+            StorageRequest::AppendBlockProofs {
+                block_hash,
+                proofs,
+                responder,
+            } => {
+                let mut txn = self.env.begin_rw_txn()?;
+                let found = self.append_block_proofs(&mut txn, &block_hash, proofs)?;
+                txn.commit()?;
+                responder.respond(found).ignore()
             }
             StorageRequest::GetBlock {
                 block_hash,
@@ -297,20 +578,49 @@ impl Storage {
             StorageRequest::GetBlockAtHeight { height, responder } => responder
                 .respond(self.get_block_by_height(&mut self.env.begin_ro_txn()?, height)?)
                 .ignore(),
-            StorageRequest::GetHighestBlock { responder } => {
+            StorageRequest::GetBlocksInHeightRange {
+                start_height,
+                max_count,
+                responder,
+            } => responder
+                .respond(self.get_blocks_in_height_range(
+                    &mut self.env.begin_ro_txn()?,
+                    start_height,
+                    max_count,
+                )?)
+                .ignore(),
+            StorageRequest::GetAlternativeBlocksAtHeight { height, responder } => responder
+                .respond(
+                    self.get_alternative_blocks_at_height(&mut self.env.begin_ro_txn()?, height)?,
+                )
+                .ignore(),
+            StorageRequest::GetHighestBlock { responder } => responder
+                .respond(self.get_highest_block(&mut self.env.begin_ro_txn()?)?)
+                .ignore(),
+            StorageRequest::GetSwitchBlockAtEraId { era_id, responder } => responder
+                .respond(self.get_switch_block_by_era_id(&mut self.env.begin_ro_txn()?, era_id)?)
+                .ignore(),
+            StorageRequest::GetHighestSwitchBlock { responder } => {
                 let mut txn = self.env.begin_ro_txn()?;
                 responder
                     .respond(
-                        self.block_height_index
+                        self.switch_block_era_index
                             .keys()
                             .last()
-                            .and_then(|&height| {
-                                self.get_block_by_height(&mut txn, height).transpose()
+                            .and_then(|&era_id| {
+                                self.get_switch_block_by_era_id(&mut txn, era_id)
+                                    .transpose()
                             })
                             .transpose()?,
                     )
                     .ignore()
             }
+            StorageRequest::GetBlockHeightByStateRootHash {
+                state_root_hash,
+                responder,
+            } => responder
+                .respond(self.state_root_height_index.get(&state_root_hash).copied())
+                .ignore(),
             StorageRequest::GetBlockHeader {
                 block_hash,
                 responder,
@@ -352,34 +662,15 @@ impl Storage {
                 responder,
             } => {
                 let mut txn = self.env.begin_rw_txn()?;
-
-                for (deploy_hash, execution_result) in execution_results {
-                    let mut metadata = self
-                        .get_deploy_metadata(&mut txn, &deploy_hash)?
-                        .unwrap_or_default();
-
-                    // If we have a previous execution result, we enforce that it is the same.
-                    if let Some(prev) = metadata.execution_results.get(&block_hash) {
-                        if prev != &execution_result {
-                            return Err(Error::DuplicateExecutionResult {
-                                deploy_hash,
-                                block_hash,
-                            });
-                        }
-
-                        // We can now skip adding, as the result is the same.
-                        continue;
-                    }
-
-                    // Update metadata and write back to db.
-                    metadata
-                        .execution_results
-                        .insert(block_hash, execution_result);
-                    let was_written =
-                        txn.put_value(self.deploy_metadata_db, &deploy_hash, &metadata, true)?;
-                    assert!(was_written);
-                }
-
+                let maybe_block_height = self
+                    .get_single_block(&mut txn, &block_hash)?
+                    .map(|block| block.height());
+                self.put_execution_results(
+                    &mut txn,
+                    block_hash,
+                    maybe_block_height,
+                    execution_results,
+                )?;
                 txn.commit()?;
                 responder.respond(()).ignore()
             }
@@ -403,6 +694,60 @@ impl Storage {
                     .unwrap_or_default();
                 responder.respond(Some((deploy, metadata))).ignore()
             }
+            StorageRequest::GetKeyHistory {
+                key,
+                offset,
+                count,
+                responder,
+            } => responder
+                .respond(get_list_page(
+                    &mut self.env.begin_ro_txn()?,
+                    self.key_history_db,
+                    key.normalize().to_formatted_string().as_bytes(),
+                    offset,
+                    count,
+                )?)
+                .ignore(),
+            StorageRequest::GetAccountDeploys {
+                account_hash,
+                offset,
+                count,
+                responder,
+            } => responder
+                .respond(get_list_page(
+                    &mut self.env.begin_ro_txn()?,
+                    self.account_deploys_db,
+                    account_hash.as_bytes(),
+                    offset,
+                    count,
+                )?)
+                .ignore(),
+            StorageRequest::GetBlockByDeployHash {
+                deploy_hash,
+                responder,
+            } => {
+                let mut txn = self.env.begin_ro_txn()?;
+                let maybe_block_hash: Option<BlockHash> =
+                    txn.get_value(self.deploy_block_db, &deploy_hash)?;
+                let maybe_block = match maybe_block_hash {
+                    Some(block_hash) => self.get_single_block(&mut txn, &block_hash)?,
+                    None => None,
+                };
+                responder.respond(maybe_block).ignore()
+            }
+            StorageRequest::GetTransfersTo {
+                target,
+                since_block_height,
+                max_count,
+                responder,
+            } => responder
+                .respond(self.get_transfers_to(
+                    &mut self.env.begin_ro_txn()?,
+                    target,
+                    since_block_height,
+                    max_count,
+                )?)
+                .ignore(),
             StorageRequest::PutChainspec {
                 chainspec,
                 responder,
@@ -430,6 +775,54 @@ impl Storage {
             .transpose()
     }
 
+    /// Retrieves the block with the greatest height.
+    fn get_highest_block<Tx: Transaction>(
+        &self,
+        tx: &mut Tx,
+    ) -> Result<Option<Block>, LmdbExtError> {
+        self.block_height_index
+            .keys()
+            .last()
+            .and_then(|&height| self.get_block_by_height(tx, height).transpose())
+            .transpose()
+    }
+
+    /// Retrieves the switch block of an era by looking it up in the index and returning it.
+    fn get_switch_block_by_era_id<Tx: Transaction>(
+        &self,
+        tx: &mut Tx,
+        era_id: EraId,
+    ) -> Result<Option<Block>, LmdbExtError> {
+        self.switch_block_era_index
+            .get(&era_id)
+            .and_then(|block_hash| self.get_single_block(tx, block_hash).transpose())
+            .transpose()
+    }
+
+    /// Retrieves the contiguous run of at most `max_count` blocks starting at `start_height`.
+    ///
+    /// Stops at the first height missing from the index, so the result never contains gaps.
+    fn get_blocks_in_height_range<Tx: Transaction>(
+        &self,
+        tx: &mut Tx,
+        start_height: u64,
+        max_count: u64,
+    ) -> Result<Vec<Block>, LmdbExtError> {
+        let mut blocks = Vec::new();
+        let mut expected_height = start_height;
+        for (&height, block_hash) in self.block_height_index.range(start_height..) {
+            if height != expected_height || blocks.len() as u64 >= max_count {
+                break;
+            }
+            match self.get_single_block(tx, block_hash)? {
+                Some(block) => blocks.push(block),
+                None => break,
+            }
+            expected_height += 1;
+        }
+        Ok(blocks)
+    }
+
     /// Retrieves a single block in a separate transaction from storage.
     fn get_single_block<Tx: Transaction>(
         &self,
@@ -462,6 +855,372 @@ impl Storage {
     ) -> Result<Option<DeployMetadata>, Error> {
         Ok(tx.get_value(self.deploy_metadata_db, deploy_hash)?)
     }
+
+    /// Writes the given block, indexing its deploys if it was not previously stored.
+    ///
+    /// If a different block is already on the linear chain at the same height, the block is
+    /// written to the alternative block database instead, without indexing its deploys.
+    fn put_block(&self, txn: &mut RwTransaction, block: &Block) -> Result<PutBlockOutcome, Error> {
+        if let Some(first) = self.block_height_index.get(&block.height()) {
+            if first != block.hash() {
+                let was_new =
+                    txn.put_value(self.alternative_block_db, block.hash(), block, false)?;
+                if !was_new {
+                    return Ok(PutBlockOutcome::AlreadyStored);
+                }
+                warn!(
+                    height = block.height(),
+                    block_hash = %block.hash(),
+                    linear_chain_block_hash = %first,
+                    "retaining block conflicting with the linear chain as an alternative block"
+                );
+                return Ok(PutBlockOutcome::Alternative);
+            }
+        }
+
+        if !txn.put_value(self.block_db, block.hash(), block, false)? {
+            return Ok(PutBlockOutcome::AlreadyStored);
+        }
+        self.index_account_deploys(txn, block)?;
+        self.index_deploy_blocks(txn, block)?;
+        Ok(PutBlockOutcome::Added)
+    }
+
+    /// Appends the given proofs to a block on the linear chain, skipping those it already holds.
+    ///
+    /// Returns false if the block is not stored.
+    fn append_block_proofs(
+        &self,
+        txn: &mut RwTransaction,
+        block_hash: &BlockHash,
+        proofs: Vec<Signature>,
+    ) -> Result<bool, Error> {
+        let mut block: Block = match txn.get_value(self.block_db, block_hash)? {
+            Some(block) => block,
+            None => return Ok(false),
+        };
+        let mut appended = false;
+        for proof in proofs {
+            if !block.proofs().contains(&proof) {
+                block.append_proof(proof);
+                appended = true;
+            }
+        }
+        if appended {
+            txn.put_value(self.block_db, block_hash, &block, true)?;
+        }
+        Ok(true)
+    }
+
+    /// Updates the in-memory indices and the metrics once a block has been committed.
+    ///
+    /// Returns true if the block was added to the linear chain on this attempt.
+    fn record_put_block(&mut self, block: &Block, outcome: PutBlockOutcome) -> bool {
+        match outcome {
+            PutBlockOutcome::Added => {
+                self.insert_into_block_indices(block);
+                self.metrics.linear_chain_blocks_added.inc();
+                true
+            }
+            PutBlockOutcome::AlreadyStored => false,
+            PutBlockOutcome::Alternative => {
+                let block_hashes = self
+                    .alternative_block_height_index
+                    .entry(block.height())
+                    .or_default();
+                if !block_hashes.contains(block.hash()) {
+                    block_hashes.push(*block.hash());
+                }
+                self.metrics.alternative_blocks_added.inc();
+                self.metrics.alternative_blocks.inc();
+                false
+            }
+        }
+    }
+
+    /// Adds a committed block to the in-memory indices.
+    fn insert_into_block_indices(&mut self, block: &Block) {
+        self.block_height_index
+            .insert(block.height(), *block.hash());
+        if block.header().switch_block() {
+            self.switch_block_era_index
+                .insert(block.header().era_id(), *block.hash());
+        }
+        insert_into_state_root_height_index(
+            &mut self.state_root_height_index,
+            *block.header().state_root_hash(),
+            block.height(),
+        );
+    }
+
+    /// Retrieves the alternative blocks at the given height.
+    fn get_alternative_blocks_at_height<Tx: Transaction>(
+        &self,
+        tx: &mut Tx,
+        height: u64,
+    ) -> Result<Vec<Block>, LmdbExtError> {
+        let mut blocks = vec![];
+        for block_hash in self
+            .alternative_block_height_index
+            .get(&height)
+            .into_iter()
+            .flatten()
+        {
+            if let Some(block) = tx.get_value(self.alternative_block_db, block_hash)? {
+                blocks.push(block);
+            }
+        }
+        Ok(blocks)
+    }
+
+    /// Writes the execution results of the deploys in the given block, updating the secondary
+    /// indices.
+    ///
+    /// Transfers are only indexed if the height of the block is given.
+    fn put_execution_results(
+        &self,
+        txn: &mut RwTransaction,
+        block_hash: BlockHash,
+        maybe_block_height: Option<u64>,
+        execution_results: HashMap<DeployHash, ExecutionResult>,
+    ) -> Result<(), Error> {
+        for (deploy_hash, execution_result) in execution_results {
+            let mut metadata = self
+                .get_deploy_metadata(txn, &deploy_hash)?
+                .unwrap_or_default();
+
+            // If we have a previous execution result, we enforce that it is the same.
+            if let Some(prev) = metadata.execution_results.get(&block_hash) {
+                if prev != &execution_result {
+                    return Err(Error::DuplicateExecutionResult {
+                        deploy_hash,
+                        block_hash,
+                    });
+                }
+
+                // We can now skip adding, as the result is the same.
+                continue;
+            }
+
+            self.index_key_history(txn, block_hash, deploy_hash, &execution_result)?;
+            if let Some(block_height) = maybe_block_height.filter(|_| execution_result.is_success())
+            {
+                self.index_transfer(txn, block_hash, block_height, deploy_hash)?;
+            }
+
+            // Update metadata and write back to db.
+            metadata
+                .execution_results
+                .insert(block_hash, execution_result);
+            let was_written =
+                txn.put_value(self.deploy_metadata_db, &deploy_hash, &metadata, true)?;
+            assert!(was_written);
+        }
+        Ok(())
+    }
+
+    /// Records each of the block's deploys against the accounts involved in it.
+    fn index_account_deploys(
+        &self,
+        txn: &mut RwTransaction,
+        block: &Block,
+    ) -> Result<(), LmdbExtError> {
+        for deploy_hash in block.deploy_hashes() {
+            let maybe_deploy: Option<Deploy> = txn.get_value(self.deploy_db, deploy_hash)?;
+            if let Some(deploy) = maybe_deploy {
+                for account_hash in deploy.accounts() {
+                    append_to_list(
+                        txn,
+                        self.account_deploys_db,
+                        account_hash.as_bytes(),
+                        deploy_hash,
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Records the block as the one including each of its deploys.
+    fn index_deploy_blocks(
+        &self,
+        txn: &mut RwTransaction,
+        block: &Block,
+    ) -> Result<(), LmdbExtError> {
+        for deploy_hash in block.deploy_hashes() {
+            txn.put_value(self.deploy_block_db, deploy_hash, block.hash(), true)?;
+        }
+        Ok(())
+    }
+
+    /// Records the given deploy against every key its execution wrote to.
+    fn index_key_history(
+        &self,
+        txn: &mut RwTransaction,
+        block_hash: BlockHash,
+        deploy_hash: DeployHash,
+        execution_result: &ExecutionResult,
+    ) -> Result<(), LmdbExtError> {
+        let entry = KeyHistoryEntry {
+            block_hash,
+            deploy_hash,
+        };
+        let written_keys: BTreeSet<String> = execution_result.written_keys().collect();
+        for key in &written_keys {
+            append_to_list(txn, self.key_history_db, key.as_bytes(), &entry)?;
+        }
+        Ok(())
+    }
+
+    /// Records the given deploy in the transfer index if it is a native transfer.
+    fn index_transfer(
+        &self,
+        txn: &mut RwTransaction,
+        block_hash: BlockHash,
+        block_height: u64,
+        deploy_hash: DeployHash,
+    ) -> Result<(), LmdbExtError> {
+        let deploy: Deploy = match txn.get_value(self.deploy_db, &deploy_hash)? {
+            Some(deploy) => deploy,
+            None => return Ok(()),
+        };
+        let (target, amount) = match (deploy.transfer_target(), deploy.transfer_amount()) {
+            (Some(target), Some(amount)) => (target, amount),
+            _ => return Ok(()),
+        };
+        let entry = TransferEntry {
+            block_hash,
+            block_height,
+            deploy_hash,
+            from: deploy.header().account().to_account_hash(),
+            amount,
+        };
+        append_to_list(
+            txn,
+            self.transfer_db,
+            target.to_formatted_string().as_bytes(),
+            &entry,
+        )
+    }
+
+    /// Retrieves the transfers to `target` executed in blocks at or above `since_block_height`,
+    /// oldest first.
+    ///
+    /// At most `max_count` transfers are retrieved, unless more are needed to complete the
+    /// transfers of the last block. If transfers remain, the height of the block from which to
+    /// resume is returned alongside.
+    fn get_transfers_to<Tx: Transaction>(
+        &self,
+        tx: &mut Tx,
+        target: Key,
+        since_block_height: u64,
+        max_count: u64,
+    ) -> Result<(Vec<TransferEntry>, Option<u64>), LmdbExtError> {
+        let formatted_target = target.normalize().to_formatted_string();
+        let list_key = formatted_target.as_bytes();
+        let len: u64 = tx
+            .get_value(self.transfer_db, &list_key)?
+            .unwrap_or_default();
+
+        // Transfers are appended in order of block height, so we can search for the first one at
+        // or above `since_block_height`.
+        let (mut low, mut high) = (0, len);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let entry: TransferEntry = get_list_entry(tx, self.transfer_db, list_key, mid)?;
+            if entry.block_height < since_block_height {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        let mut transfers: Vec<TransferEntry> = Vec::new();
+        for index in low..len {
+            let entry: TransferEntry = get_list_entry(tx, self.transfer_db, list_key, index)?;
+            let completes_last_block =
+                transfers.last().map(|last| last.block_height) == Some(entry.block_height);
+            if transfers.len() as u64 >= max_count && !completes_last_block {
+                return Ok((transfers, Some(entry.block_height)));
+            }
+            transfers.push(entry);
+        }
+        Ok((transfers, None))
+    }
+}
+
+/// Records that the block at `height` has the given state root hash.
+///
+/// Consecutive blocks can share a state root, in which case the highest of them is retained.
+fn insert_into_state_root_height_index(
+    index: &mut HashMap<Digest, u64>,
+    state_root_hash: Digest,
+    height: u64,
+) {
+    let entry = index.entry(state_root_hash).or_insert(height);
+    *entry = (*entry).max(height);
+}
+
+/// Appends a value to the end of the append-only list stored under `list_key` in `db`.
+///
+/// The length of the list is stored under `list_key` itself, while each value is stored under its
+/// entry key, see `list_entry_key()`.
+fn append_to_list<V: Serialize>(
+    txn: &mut RwTransaction,
+    db: Database,
+    list_key: &[u8],
+    value: &V,
+) -> Result<(), LmdbExtError> {
+    let len: u64 = txn.get_value(db, &list_key)?.unwrap_or_default();
+    txn.put_value(db, &list_entry_key(list_key, len), value, true)?;
+    txn.put_value(db, &list_key, &(len + 1), true)?;
+    Ok(())
+}
+
+/// Retrieves at most `count` values of the append-only list stored under `list_key` in `db`, newest
+/// first, skipping the `offset` newest ones.
+///
+/// Returns the length of the list along with the requested values.
+fn get_list_page<Tx: Transaction, V: DeserializeOwned>(
+    tx: &mut Tx,
+    db: Database,
+    list_key: &[u8],
+    offset: u64,
+    count: u64,
+) -> Result<(u64, Vec<V>), LmdbExtError> {
+    let len: u64 = tx.get_value(db, &list_key)?.unwrap_or_default();
+    let values = (0..len.saturating_sub(offset))
+        .rev()
+        .take(count as usize)
+        .filter_map(|index| {
+            tx.get_value(db, &list_entry_key(list_key, index))
+                .transpose()
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((len, values))
+}
+
+/// Retrieves the value at `index` of the append-only list stored under `list_key` in `db`, which
+/// must be within the bounds of the list.
+fn get_list_entry<Tx: Transaction, V: DeserializeOwned>(
+    tx: &mut Tx,
+    db: Database,
+    list_key: &[u8],
+    index: u64,
+) -> Result<V, LmdbExtError> {
+    tx.get_value(db, &list_entry_key(list_key, index))?
+        .ok_or_else(|| LmdbExtError::DataCorrupted(format!("missing list entry {}", index).into()))
+}
+
+/// Returns the key under which the entry at `index` of the list stored under `list_key` is stored.
+///
+/// The index is encoded big-endian so that the entries of a list are ordered by index in the db.
+fn list_entry_key(list_key: &[u8], index: u64) -> Vec<u8> {
+    let mut entry_key = Vec::with_capacity(list_key.len() + 1 + 8);
+    entry_key.extend_from_slice(list_key);
+    entry_key.push(LIST_ENTRY_SEPARATOR);
+    entry_key.extend_from_slice(&index.to_be_bytes());
+    entry_key
 }
 
 /// On-disk storage configuration.
@@ -484,6 +1243,38 @@ pub struct Config {
     ///
     /// The size should be a multiple of the OS page size.
     max_deploy_metadata_store_size: usize,
+    /// The maximum size of the database to use for the key history index.
+    ///
+    /// The size should be a multiple of the OS page size.
+    #[serde(default = "default_max_key_history_store_size")]
+    max_key_history_store_size: usize,
+    /// The maximum size of the database to use for the account activity index.
+    ///
+    /// The size should be a multiple of the OS page size.
+    #[serde(default = "default_max_account_deploys_store_size")]
+    max_account_deploys_store_size: usize,
+    /// The maximum size of the database to use for the index of the block holding each deploy.
+    ///
+    /// The size should be a multiple of the OS page size.
+    #[serde(default = "default_max_deploy_block_store_size")]
+    max_deploy_block_store_size: usize,
+    /// The maximum size of the database to use for the transfer index.
+    ///
+    /// The size should be a multiple of the OS page size.
+    #[serde(default = "default_max_transfer_store_size")]
+    max_transfer_store_size: usize,
+    /// The maximum size of the database to use for blocks conflicting with the linear chain.
+    ///
+    /// The size should be a multiple of the OS page size.
+    #[serde(default = "default_max_alternative_block_store_size")]
+    max_alternative_block_store_size: usize,
+    /// Whether the node is an archive node, guaranteeing that the global state of every stored
+    /// block remains queryable.
+    #[serde(default)]
+    archive: bool,
+    /// Periodic backup configuration.
+    #[serde(default)]
+    backup: backup::Config,
 }
 
 impl Default for Config {
@@ -494,11 +1285,51 @@ impl Default for Config {
             max_block_store_size: DEFAULT_MAX_BLOCK_STORE_SIZE,
             max_deploy_store_size: DEFAULT_MAX_DEPLOY_STORE_SIZE,
             max_deploy_metadata_store_size: DEFAULT_MAX_DEPLOY_METADATA_STORE_SIZE,
+            max_key_history_store_size: DEFAULT_MAX_KEY_HISTORY_STORE_SIZE,
+            max_account_deploys_store_size: DEFAULT_MAX_ACCOUNT_DEPLOYS_STORE_SIZE,
+            max_deploy_block_store_size: DEFAULT_MAX_DEPLOY_BLOCK_STORE_SIZE,
+            max_transfer_store_size: DEFAULT_MAX_TRANSFER_STORE_SIZE,
+            max_alternative_block_store_size: DEFAULT_MAX_ALTERNATIVE_BLOCK_STORE_SIZE,
+            archive: false,
+            backup: Default::default(),
         }
     }
 }
 
+fn default_max_key_history_store_size() -> usize {
+    DEFAULT_MAX_KEY_HISTORY_STORE_SIZE
+}
+
+fn default_max_account_deploys_store_size() -> usize {
+    DEFAULT_MAX_ACCOUNT_DEPLOYS_STORE_SIZE
+}
+
+fn default_max_deploy_block_store_size() -> usize {
+    DEFAULT_MAX_DEPLOY_BLOCK_STORE_SIZE
+}
+
+fn default_max_transfer_store_size() -> usize {
+    DEFAULT_MAX_TRANSFER_STORE_SIZE
+}
+
+fn default_max_alternative_block_store_size() -> usize {
+    DEFAULT_MAX_ALTERNATIVE_BLOCK_STORE_SIZE
+}
+
 impl Config {
+    /// Returns a default `Config` storing its databases under `path`.
+    pub(crate) fn with_path(path: PathBuf) -> Self {
+        Config {
+            path,
+            ..Default::default()
+        }
+    }
+
+    /// Returns whether the node is an archive node.
+    pub(crate) fn archive(&self) -> bool {
+        self.archive
+    }
+
     /// Returns a default `Config` suitable for tests, along with a `TempDir` which must be kept
     /// alive for the duration of the test since its destructor removes the dir from the filesystem.
     #[cfg(test)]
@@ -522,6 +1353,78 @@ impl Display for Event {
     }
 }
 
+/// Opens the storage at the configured location and rebuilds the given secondary indices from the
+/// blocks, deploys and execution results held in it, discarding their previous contents.
+///
+/// Blocks are processed in order of height, each in its own transaction, and `on_progress` is
+/// called with the number of blocks processed so far and the total number of blocks after each.
+/// If the rebuild is interrupted, it should be run again before starting the node, as the indices
+/// are then incomplete.  The node must not be running while rebuilding.
+pub fn rebuild_indexes<F: FnMut(u64, u64)>(
+    cfg: &WithDir<Config>,
+    indexes: &BTreeSet<Index>,
+    on_progress: F,
+) -> Result<(), Error> {
+    Storage::new(cfg, &Registry::new())?.rebuild_indexes(indexes, on_progress)
+}
+
+impl Storage {
+    /// Rebuilds the given secondary indices, see [`rebuild_indexes`].
+    fn rebuild_indexes<F: FnMut(u64, u64)>(
+        &self,
+        indexes: &BTreeSet<Index>,
+        mut on_progress: F,
+    ) -> Result<(), Error> {
+        let mut txn = self.env.begin_rw_txn()?;
+        for index in indexes {
+            let db = match index {
+                Index::AccountDeploys => self.account_deploys_db,
+                Index::DeployBlocks => self.deploy_block_db,
+                Index::KeyHistory => self.key_history_db,
+                Index::Transfers => self.transfer_db,
+            };
+            txn.clear_db(db)?;
+        }
+        txn.commit()?;
+
+        let total = self.block_height_index.len() as u64;
+        for (processed, block_hash) in self.block_height_index.values().enumerate() {
+            let mut txn = self.env.begin_rw_txn()?;
+            let block = self
+                .get_single_block(&mut txn, block_hash)?
+                .ok_or_else(|| {
+                    LmdbExtError::DataCorrupted(format!("missing block {}", block_hash).into())
+                })?;
+
+            if indexes.contains(&Index::AccountDeploys) {
+                self.index_account_deploys(&mut txn, &block)?;
+            }
+            if indexes.contains(&Index::DeployBlocks) {
+                self.index_deploy_blocks(&mut txn, &block)?;
+            }
+            for deploy_hash in block.deploy_hashes() {
+                let maybe_execution_result = self
+                    .get_deploy_metadata(&mut txn, deploy_hash)?
+                    .and_then(|mut metadata| metadata.execution_results.remove(block_hash));
+                let execution_result = match maybe_execution_result {
+                    Some(execution_result) => execution_result,
+                    None => continue,
+                };
+                if indexes.contains(&Index::KeyHistory) {
+                    self.index_key_history(&mut txn, *block_hash, *deploy_hash, &execution_result)?;
+                }
+                if indexes.contains(&Index::Transfers) && execution_result.is_success() {
+                    self.index_transfer(&mut txn, *block_hash, block.height(), *deploy_hash)?;
+                }
+            }
+
+            txn.commit()?;
+            on_progress(processed as u64 + 1, total);
+        }
+        Ok(())
+    }
+}
+
 // Legacy code follows.
 //
 // The functionality about for requests directly from the incoming network was previously present in
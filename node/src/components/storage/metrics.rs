@@ -0,0 +1,59 @@
+use prometheus::{IntCounter, IntGauge, Registry};
+
+/// Metrics of the storage component.
+///
+/// The rate of orphaned blocks is that of `storage_alternative_blocks_added` relative to that of
+/// `storage_linear_chain_blocks_added`.
+#[derive(Debug)]
+pub(super) struct Metrics {
+    /// Number of blocks added to the linear chain since startup.
+    pub(super) linear_chain_blocks_added: IntCounter,
+    /// Number of blocks retained as alternatives to the linear chain since startup.
+    pub(super) alternative_blocks_added: IntCounter,
+    /// Total number of alternative blocks held in storage.
+    pub(super) alternative_blocks: IntGauge,
+    /// Reference to the registry for unregistering.
+    registry: Registry,
+}
+
+impl Metrics {
+    pub(super) fn new(registry: &Registry) -> Result<Self, prometheus::Error> {
+        let linear_chain_blocks_added = IntCounter::new(
+            "storage_linear_chain_blocks_added",
+            "number of blocks added to the linear chain since startup",
+        )?;
+        let alternative_blocks_added = IntCounter::new(
+            "storage_alternative_blocks_added",
+            "number of blocks conflicting with the linear chain retained since startup",
+        )?;
+        let alternative_blocks = IntGauge::new(
+            "storage_alternative_blocks",
+            "total number of blocks conflicting with the linear chain held in storage",
+        )?;
+
+        registry.register(Box::new(linear_chain_blocks_added.clone()))?;
+        registry.register(Box::new(alternative_blocks_added.clone()))?;
+        registry.register(Box::new(alternative_blocks.clone()))?;
+
+        Ok(Metrics {
+            linear_chain_blocks_added,
+            alternative_blocks_added,
+            alternative_blocks,
+            registry: registry.clone(),
+        })
+    }
+}
+
+impl Drop for Metrics {
+    fn drop(&mut self) {
+        self.registry
+            .unregister(Box::new(self.linear_chain_blocks_added.clone()))
+            .expect("did not expect deregistering linear_chain_blocks_added to fail");
+        self.registry
+            .unregister(Box::new(self.alternative_blocks_added.clone()))
+            .expect("did not expect deregistering alternative_blocks_added to fail");
+        self.registry
+            .unregister(Box::new(self.alternative_blocks.clone()))
+            .expect("did not expect deregistering alternative_blocks to fail");
+    }
+}
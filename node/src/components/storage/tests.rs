@@ -1,17 +1,30 @@
 //! Unit tests for the storage component.
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
-use rand::prelude::SliceRandom;
+use prometheus::Registry;
+use rand::{prelude::SliceRandom, Rng};
 use semver::Version;
 use smallvec::smallvec;
 
-use super::{Config, Storage};
+use casper_types::{account::AccountHash, Key};
+
+use super::{backup, Config, Index, Storage};
 use crate::{
+    crypto::{
+        asymmetric_key::{self, PublicKey, SecretKey, Signature},
+        hash::Digest,
+    },
     effect::{requests::StorageRequest, Multiple},
     testing::{ComponentHarness, TestRng},
     types::{
         json_compatibility::ExecutionResult, Block, BlockHash, Deploy, DeployHash, DeployMetadata,
+        KeyHistoryEntry, TransferEntry,
     },
     utils::WithDir,
     Chainspec,
@@ -25,17 +38,33 @@ use crate::{
 ///
 /// Panics if setting up the storage fixture fails.
 fn storage_fixture(harness: &mut ComponentHarness<()>) -> Storage {
+    storage_fixture_at(harness.tmp.path(), harness.tmp.path().join("storage"))
+}
+
+/// Creates a storage component in the given directory.
+///
+/// # Panics
+///
+/// Panics if setting up the storage fails.
+fn storage_fixture_at(root: &Path, path: PathBuf) -> Storage {
     const MIB: usize = 1024 * 1024;
 
     // Restrict all stores to 50 mibibytes, to catch issues before filling up the entire disk.
     let cfg = Config {
-        path: harness.tmp.path().join("storage"),
+        path,
         max_block_store_size: 50 * MIB,
         max_deploy_store_size: 50 * MIB,
         max_deploy_metadata_store_size: 50 * MIB,
+        max_key_history_store_size: 50 * MIB,
+        max_account_deploys_store_size: 50 * MIB,
+        max_deploy_block_store_size: 50 * MIB,
+        max_transfer_store_size: 50 * MIB,
+        max_alternative_block_store_size: 50 * MIB,
+        archive: false,
+        backup: Default::default(),
     };
 
-    Storage::new(&WithDir::new(harness.tmp.path(), cfg)).expect(
+    Storage::new(&WithDir::new(root, cfg), &Registry::new()).expect(
         "could not create storage component
     fixture",
     )
@@ -125,6 +154,105 @@ fn get_deploy_and_metadata(
     response
 }
 
+/// Requests a page of the write history of a key from a storage component.
+fn get_key_history(
+    harness: &mut ComponentHarness<()>,
+    storage: &mut Storage,
+    key: Key,
+    offset: u64,
+    count: u64,
+) -> (u64, Vec<KeyHistoryEntry>) {
+    let response = harness.send_request(storage, move |responder| {
+        StorageRequest::GetKeyHistory {
+            key,
+            offset,
+            count,
+            responder,
+        }
+        .into()
+    });
+    assert!(harness.is_idle());
+    response
+}
+
+/// Requests a page of the deploys involving an account from a storage component.
+fn get_account_deploys(
+    harness: &mut ComponentHarness<()>,
+    storage: &mut Storage,
+    account_hash: AccountHash,
+    offset: u64,
+    count: u64,
+) -> (u64, Vec<DeployHash>) {
+    let response = harness.send_request(storage, move |responder| {
+        StorageRequest::GetAccountDeploys {
+            account_hash,
+            offset,
+            count,
+            responder,
+        }
+        .into()
+    });
+    assert!(harness.is_idle());
+    response
+}
+
+/// Requests the block including a deploy from a storage component.
+fn get_block_by_deploy_hash(
+    harness: &mut ComponentHarness<()>,
+    storage: &mut Storage,
+    deploy_hash: DeployHash,
+) -> Option<Block> {
+    let response = harness.send_request(storage, move |responder| {
+        StorageRequest::GetBlockByDeployHash {
+            deploy_hash,
+            responder,
+        }
+        .into()
+    });
+    assert!(harness.is_idle());
+    response
+}
+
+/// Requests the transfers to an account or purse from a storage component.
+fn get_transfers_to(
+    harness: &mut ComponentHarness<()>,
+    storage: &mut Storage,
+    target: Key,
+    since_block_height: u64,
+    max_count: u64,
+) -> (Vec<TransferEntry>, Option<u64>) {
+    let response = harness.send_request(storage, move |responder| {
+        StorageRequest::GetTransfersTo {
+            target,
+            since_block_height,
+            max_count,
+            responder,
+        }
+        .into()
+    });
+    assert!(harness.is_idle());
+    response
+}
+
+/// Requests a contiguous range of blocks from a storage component.
+fn get_blocks_in_height_range(
+    harness: &mut ComponentHarness<()>,
+    storage: &mut Storage,
+    start_height: u64,
+    max_count: u64,
+) -> Vec<Block> {
+    let response = harness.send_request(storage, move |responder| {
+        StorageRequest::GetBlocksInHeightRange {
+            start_height,
+            max_count,
+            responder,
+        }
+        .into()
+    });
+    assert!(harness.is_idle());
+    response
+}
+
 /// Requests the highest block from a storage component.
 fn get_highest_block(harness: &mut ComponentHarness<()>, storage: &mut Storage) -> Option<Block> {
     let response = harness.send_request(storage, |responder| {
@@ -134,6 +262,19 @@ fn get_highest_block(harness: &mut ComponentHarness<()>, storage: &mut Storage)
     response
 }
 
+/// Loads the blocks conflicting with the linear chain at the given height.
+fn get_alternative_blocks_at_height(
+    harness: &mut ComponentHarness<()>,
+    storage: &mut Storage,
+    height: u64,
+) -> Vec<Block> {
+    let response = harness.send_request(storage, |responder| {
+        StorageRequest::GetAlternativeBlocksAtHeight { height, responder }.into()
+    });
+    assert!(harness.is_idle());
+    response
+}
+
 /// Stores a block in a storage component.
 fn put_block(harness: &mut ComponentHarness<()>, storage: &mut Storage, block: Box<Block>) -> bool {
     let response = harness.send_request(storage, move |responder| {
@@ -143,6 +284,25 @@ fn put_block(harness: &mut ComponentHarness<()>, storage: &mut Storage, block: B
     response
 }
 
+/// Appends proofs to a block in a storage component.
+fn append_block_proofs(
+    harness: &mut ComponentHarness<()>,
+    storage: &mut Storage,
+    block_hash: BlockHash,
+    proofs: Vec<Signature>,
+) -> bool {
+    let response = harness.send_request(storage, move |responder| {
+        StorageRequest::AppendBlockProofs {
+            block_hash,
+            proofs,
+            responder,
+        }
+        .into()
+    });
+    assert!(harness.is_idle());
+    response
+}
+
 /// Stores the chainspec in a storage component.
 fn put_chainspec(harness: &mut ComponentHarness<()>, storage: &mut Storage, chainspec: Chainspec) {
     harness.send_request(storage, move |responder| {
@@ -187,6 +347,25 @@ fn put_execution_results(
     response
 }
 
+/// Stores a block along with its execution results in a storage component.
+fn put_block_and_execution_results(
+    harness: &mut ComponentHarness<()>,
+    storage: &mut Storage,
+    block: Box<Block>,
+    execution_results: HashMap<DeployHash, ExecutionResult>,
+) -> bool {
+    let response = harness.send_request(storage, move |responder| {
+        StorageRequest::PutBlockAndExecutionResults {
+            block,
+            execution_results,
+            responder,
+        }
+        .into()
+    });
+    assert!(harness.is_idle());
+    response
+}
+
 #[test]
 fn get_block_of_non_existing_block_returns_none() {
     let mut harness = ComponentHarness::default();
@@ -232,6 +411,50 @@ fn can_put_and_get_block() {
     assert_eq!(response.as_ref(), Some(block.header()));
 }
 
+#[test]
+fn can_append_block_proofs() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&mut harness);
+
+    let block = Box::new(Block::random(&mut harness.rng));
+    let secret_key = SecretKey::random(&mut harness.rng);
+    let public_key = PublicKey::from(&secret_key);
+    let proof = asymmetric_key::sign(
+        block.hash().inner(),
+        &secret_key,
+        &public_key,
+        &mut harness.rng,
+    );
+
+    // Appending to a block which isn't stored fails.
+    assert!(!append_block_proofs(
+        &mut harness,
+        &mut storage,
+        *block.hash(),
+        vec![proof]
+    ));
+
+    put_block(&mut harness, &mut storage, block.clone());
+    assert!(append_block_proofs(
+        &mut harness,
+        &mut storage,
+        *block.hash(),
+        vec![proof]
+    ));
+    // Appending the same proof again leaves the block unchanged.
+    assert!(append_block_proofs(
+        &mut harness,
+        &mut storage,
+        *block.hash(),
+        vec![proof]
+    ));
+
+    let stored_block = get_block(&mut harness, &mut storage, *block.hash()).unwrap();
+    let mut expected_proofs = block.proofs().to_vec();
+    expected_proofs.push(proof);
+    assert_eq!(stored_block.proofs(), expected_proofs.as_slice());
+}
+
 #[test]
 fn can_retrieve_block_by_height() {
     let mut harness = ComponentHarness::default();
@@ -308,8 +531,72 @@ fn can_retrieve_block_by_height() {
 }
 
 #[test]
-#[should_panic(expected = "duplicate entries")]
-fn different_block_at_height_is_fatal() {
+fn can_retrieve_block_height_by_state_root_hash() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&mut harness);
+
+    let block_14 = random_block_at_height(&mut harness.rng, 14);
+    let state_root_hash = *block_14.state_root_hash();
+    put_block(&mut harness, &mut storage, block_14);
+
+    let mut get_height = |state_root_hash| {
+        harness.send_request(&mut storage, |responder| {
+            StorageRequest::GetBlockHeightByStateRootHash {
+                state_root_hash,
+                responder,
+            }
+            .into()
+        })
+    };
+    assert_eq!(get_height(state_root_hash), Some(14));
+    assert_eq!(get_height(Digest::default()), None);
+}
+
+#[test]
+fn can_retrieve_blocks_in_height_range() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&mut harness);
+
+    // Store blocks 0 to 4, leave a gap at 5 and store 6.
+    let blocks: Vec<_> = (0..5)
+        .chain(6..7)
+        .map(|height| random_block_at_height(&mut harness.rng, height))
+        .collect();
+    for block in &blocks {
+        put_block(&mut harness, &mut storage, block.clone());
+    }
+
+    let heights = |blocks: Vec<Block>| blocks.iter().map(Block::height).collect::<Vec<_>>();
+
+    // The page size is respected.
+    assert_eq!(
+        heights(get_blocks_in_height_range(&mut harness, &mut storage, 1, 3)),
+        vec![1, 2, 3]
+    );
+
+    // The range stops at the first gap.
+    assert_eq!(
+        heights(get_blocks_in_height_range(
+            &mut harness,
+            &mut storage,
+            2,
+            10
+        )),
+        vec![2, 3, 4]
+    );
+
+    // Starting at a missing height yields nothing.
+    assert!(get_blocks_in_height_range(&mut harness, &mut storage, 5, 10).is_empty());
+    assert!(get_blocks_in_height_range(&mut harness, &mut storage, 0, 0).is_empty());
+
+    assert_eq!(
+        get_blocks_in_height_range(&mut harness, &mut storage, 6, 10),
+        vec![*blocks[5].clone()]
+    );
+}
+
+#[test]
+fn different_block_at_height_is_retained_as_alternative() {
     let mut harness = ComponentHarness::default();
     let mut storage = storage_fixture(&mut harness);
 
@@ -320,11 +607,37 @@ fn different_block_at_height_is_fatal() {
     let was_new = put_block(&mut harness, &mut storage, block_44_a.clone());
     assert!(was_new);
 
-    let was_new = put_block(&mut harness, &mut storage, block_44_a);
+    let was_new = put_block(&mut harness, &mut storage, block_44_a.clone());
     assert!(!was_new);
 
-    // Putting a different block with the same height should now crash.
-    put_block(&mut harness, &mut storage, block_44_b);
+    // Putting a different block with the same height leaves the linear chain unchanged.
+    let was_new = put_block(&mut harness, &mut storage, block_44_b.clone());
+    assert!(!was_new);
+    assert_eq!(
+        get_block_at_height(&mut harness, &mut storage, 44),
+        Some(*block_44_a.clone())
+    );
+    assert!(get_block(&mut harness, &mut storage, *block_44_b.hash()).is_none());
+    assert_eq!(
+        get_alternative_blocks_at_height(&mut harness, &mut storage, 44),
+        vec![*block_44_b.clone()]
+    );
+    assert!(get_alternative_blocks_at_height(&mut harness, &mut storage, 45).is_empty());
+    assert_eq!(storage.metrics.linear_chain_blocks_added.get(), 1);
+    assert_eq!(storage.metrics.alternative_blocks_added.get(), 1);
+
+    // The alternative block is retained across instantiations.
+    let (on_disk, rng) = harness.into_parts();
+    let mut harness = ComponentHarness::builder()
+        .on_disk(on_disk)
+        .rng(rng)
+        .build();
+    let mut storage = storage_fixture(&mut harness);
+    assert_eq!(
+        get_alternative_blocks_at_height(&mut harness, &mut storage, 44),
+        vec![*block_44_b]
+    );
+    assert_eq!(storage.metrics.alternative_blocks.get(), 1);
 }
 
 #[test]
@@ -610,6 +923,383 @@ fn store_identical_execution_results() {
     put_execution_results(&mut harness, &mut storage, block_hash, exec_result);
 }
 
+#[test]
+fn store_execution_results_indexes_written_keys() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&mut harness);
+
+    let key = Key::Hash(harness.rng.gen());
+    let block_hash_a = BlockHash::random(&mut harness.rng);
+    let block_hash_b = BlockHash::random(&mut harness.rng);
+    let deploy_hash_1 = DeployHash::random(&mut harness.rng);
+    let deploy_hash_2 = DeployHash::random(&mut harness.rng);
+    let deploy_hash_3 = DeployHash::random(&mut harness.rng);
+
+    // Only the first deploy of block A writes to the key.
+    let mut results_a = HashMap::new();
+    results_a.insert(
+        deploy_hash_1,
+        ExecutionResult::random_writing_to(&mut harness.rng, key),
+    );
+    results_a.insert(deploy_hash_2, ExecutionResult::random(&mut harness.rng));
+    put_execution_results(&mut harness, &mut storage, block_hash_a, results_a.clone());
+
+    // Storing the same results again must not duplicate the history entry.
+    put_execution_results(&mut harness, &mut storage, block_hash_a, results_a);
+
+    let mut results_b = HashMap::new();
+    results_b.insert(
+        deploy_hash_3,
+        ExecutionResult::random_writing_to(&mut harness.rng, key),
+    );
+    put_execution_results(&mut harness, &mut storage, block_hash_b, results_b);
+
+    let entry_a = KeyHistoryEntry {
+        block_hash: block_hash_a,
+        deploy_hash: deploy_hash_1,
+    };
+    let entry_b = KeyHistoryEntry {
+        block_hash: block_hash_b,
+        deploy_hash: deploy_hash_3,
+    };
+
+    // Entries are returned most recent first.
+    assert_eq!(
+        get_key_history(&mut harness, &mut storage, key, 0, 10),
+        (2, vec![entry_b, entry_a])
+    );
+    assert_eq!(
+        get_key_history(&mut harness, &mut storage, key, 0, 1),
+        (2, vec![entry_b])
+    );
+    assert_eq!(
+        get_key_history(&mut harness, &mut storage, key, 1, 10),
+        (2, vec![entry_a])
+    );
+    assert_eq!(
+        get_key_history(&mut harness, &mut storage, key, 2, 10),
+        (2, vec![])
+    );
+
+    // An untouched key has no history.
+    let other_key = Key::Hash(harness.rng.gen());
+    assert_eq!(
+        get_key_history(&mut harness, &mut storage, other_key, 0, 10),
+        (0, vec![])
+    );
+}
+
+#[test]
+fn store_block_indexes_account_deploys() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&mut harness);
+
+    // The first two deploys are sent by the same account.
+    let secret_key = SecretKey::random(&mut harness.rng);
+    let account_hash = PublicKey::from(&secret_key).to_account_hash();
+    let deploy_1 = Box::new(Deploy::random_from(&mut harness.rng, &secret_key));
+    let deploy_2 = Box::new(Deploy::random_from(&mut harness.rng, &secret_key));
+    let deploy_3 = Box::new(Deploy::random(&mut harness.rng));
+    let missing_deploy_hash = DeployHash::random(&mut harness.rng);
+    for deploy in &[&deploy_1, &deploy_2, &deploy_3] {
+        put_deploy(&mut harness, &mut storage, (*deploy).clone());
+    }
+
+    let block_a = Box::new(Block::random_with_deploys(
+        &mut harness.rng,
+        vec![*deploy_1.id(), *deploy_3.id()],
+    ));
+    let block_b = Box::new(Block::random_with_deploys(
+        &mut harness.rng,
+        vec![*deploy_2.id(), missing_deploy_hash],
+    ));
+    put_block(&mut harness, &mut storage, block_a.clone());
+    // Storing the same block again must not duplicate the index entries.
+    put_block(&mut harness, &mut storage, block_a);
+    put_block(&mut harness, &mut storage, block_b);
+
+    // Deploys are returned most recent first.
+    assert_eq!(
+        get_account_deploys(&mut harness, &mut storage, account_hash, 0, 10),
+        (2, vec![*deploy_2.id(), *deploy_1.id()])
+    );
+    assert_eq!(
+        get_account_deploys(&mut harness, &mut storage, account_hash, 1, 10),
+        (2, vec![*deploy_1.id()])
+    );
+
+    for other_account_hash in deploy_3.accounts() {
+        assert_eq!(
+            get_account_deploys(&mut harness, &mut storage, other_account_hash, 0, 10),
+            (1, vec![*deploy_3.id()])
+        );
+    }
+}
+
+#[test]
+fn store_block_indexes_deploy_blocks() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&mut harness);
+
+    let deploy_hash_1 = DeployHash::random(&mut harness.rng);
+    let deploy_hash_2 = DeployHash::random(&mut harness.rng);
+    let missing_deploy_hash = DeployHash::random(&mut harness.rng);
+    let block = Box::new(Block::random_with_deploys(
+        &mut harness.rng,
+        vec![deploy_hash_1, deploy_hash_2],
+    ));
+    put_block(&mut harness, &mut storage, block.clone());
+
+    assert_eq!(
+        get_block_by_deploy_hash(&mut harness, &mut storage, deploy_hash_1),
+        Some(*block.clone())
+    );
+    assert_eq!(
+        get_block_by_deploy_hash(&mut harness, &mut storage, deploy_hash_2),
+        Some(*block)
+    );
+    assert!(get_block_by_deploy_hash(&mut harness, &mut storage, missing_deploy_hash).is_none());
+}
+
+#[test]
+fn store_execution_results_indexes_transfers() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&mut harness);
+
+    let target = AccountHash::new([7; 32]);
+    let other_target = AccountHash::new([8; 32]);
+    let transfer_1 = Box::new(Deploy::random_transfer(&mut harness.rng, target, 1.into()));
+    let transfer_2 = Box::new(Deploy::random_transfer(&mut harness.rng, target, 2.into()));
+    let failed_transfer = Box::new(Deploy::random_transfer(&mut harness.rng, target, 3.into()));
+    let transfer_3 = Box::new(Deploy::random_transfer(&mut harness.rng, target, 4.into()));
+    let other_transfer = Box::new(Deploy::random_transfer(
+        &mut harness.rng,
+        other_target,
+        5.into(),
+    ));
+    for deploy in &[
+        &transfer_1,
+        &transfer_2,
+        &failed_transfer,
+        &transfer_3,
+        &other_transfer,
+    ] {
+        put_deploy(&mut harness, &mut storage, (*deploy).clone());
+    }
+
+    // Each block is stored before its execution results, as on the linear chain.
+    let mut failed_result = ExecutionResult::random(&mut harness.rng);
+    while failed_result.is_success() {
+        failed_result = ExecutionResult::random(&mut harness.rng);
+    }
+    let block_a = random_block_at_height(&mut harness.rng, 3);
+    put_block(&mut harness, &mut storage, block_a.clone());
+    let mut execution_results = HashMap::new();
+    execution_results.insert(
+        *transfer_1.id(),
+        ExecutionResult::random_success(&mut harness.rng),
+    );
+    execution_results.insert(*failed_transfer.id(), failed_result);
+    execution_results.insert(
+        *other_transfer.id(),
+        ExecutionResult::random_success(&mut harness.rng),
+    );
+    put_execution_results(
+        &mut harness,
+        &mut storage,
+        *block_a.hash(),
+        execution_results,
+    );
+
+    let block_b = random_block_at_height(&mut harness.rng, 5);
+    put_block(&mut harness, &mut storage, block_b.clone());
+    let mut execution_results = HashMap::new();
+    execution_results.insert(
+        *transfer_2.id(),
+        ExecutionResult::random_success(&mut harness.rng),
+    );
+    execution_results.insert(
+        *transfer_3.id(),
+        ExecutionResult::random_success(&mut harness.rng),
+    );
+    put_execution_results(
+        &mut harness,
+        &mut storage,
+        *block_b.hash(),
+        execution_results.clone(),
+    );
+    // Storing the same results again must not duplicate the index entries.
+    put_execution_results(
+        &mut harness,
+        &mut storage,
+        *block_b.hash(),
+        execution_results,
+    );
+
+    let (transfers, next) =
+        get_transfers_to(&mut harness, &mut storage, Key::Account(target), 0, 10);
+    assert_eq!(next, None);
+    assert_eq!(transfers.len(), 3);
+    assert_eq!(transfers[0].deploy_hash, *transfer_1.id());
+    assert_eq!(transfers[0].block_hash, *block_a.hash());
+    assert_eq!(transfers[0].block_height, 3);
+    assert_eq!(transfers[0].amount, 1.into());
+    assert_eq!(
+        transfers[0].from,
+        transfer_1.header().account().to_account_hash()
+    );
+    let later_deploys: Vec<_> = transfers[1..]
+        .iter()
+        .map(|transfer| transfer.deploy_hash)
+        .collect();
+    assert!(later_deploys.contains(transfer_2.id()));
+    assert!(later_deploys.contains(transfer_3.id()));
+
+    // Transfers in blocks below the given height are skipped.
+    let (transfers, next) =
+        get_transfers_to(&mut harness, &mut storage, Key::Account(target), 4, 10);
+    assert_eq!(next, None);
+    assert_eq!(transfers.len(), 2);
+    assert!(transfers.iter().all(|transfer| transfer.block_height == 5));
+
+    // Truncated results always complete the last block and indicate where to resume.
+    let (transfers, next) =
+        get_transfers_to(&mut harness, &mut storage, Key::Account(target), 0, 1);
+    assert_eq!(transfers.len(), 1);
+    assert_eq!(next, Some(5));
+    let (transfers, next) =
+        get_transfers_to(&mut harness, &mut storage, Key::Account(target), 5, 1);
+    assert_eq!(transfers.len(), 2);
+    assert_eq!(next, None);
+
+    let (transfers, _) = get_transfers_to(
+        &mut harness,
+        &mut storage,
+        Key::Account(other_target),
+        0,
+        10,
+    );
+    assert_eq!(transfers.len(), 1);
+    assert_eq!(transfers[0].deploy_hash, *other_transfer.id());
+}
+
+#[test]
+fn store_block_and_execution_results_together() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&mut harness);
+
+    let target = AccountHash::new([7; 32]);
+    let transfer = Box::new(Deploy::random_transfer(&mut harness.rng, target, 1.into()));
+    put_deploy(&mut harness, &mut storage, transfer.clone());
+
+    let block = random_block_at_height(&mut harness.rng, 7);
+    let execution_result = ExecutionResult::random_success(&mut harness.rng);
+    let mut execution_results = HashMap::new();
+    execution_results.insert(*transfer.id(), execution_result.clone());
+
+    let was_new = put_block_and_execution_results(
+        &mut harness,
+        &mut storage,
+        block.clone(),
+        execution_results.clone(),
+    );
+    assert!(was_new);
+
+    assert_eq!(
+        get_block_at_height(&mut harness, &mut storage, 7).expect("block not indexed properly"),
+        *block
+    );
+    let (_, deploy_metadata) = get_deploy_and_metadata(&mut harness, &mut storage, *transfer.id())
+        .expect("missing deploy we stored earlier");
+    assert_eq!(
+        deploy_metadata.execution_results[block.hash()],
+        execution_result
+    );
+
+    // The block's height is known to the transfer index, as both are written together.
+    let (transfers, _) = get_transfers_to(&mut harness, &mut storage, Key::Account(target), 0, 10);
+    assert_eq!(transfers.len(), 1);
+    assert_eq!(transfers[0].block_height, 7);
+
+    // Storing the same block and results again is a no-op.
+    let was_new =
+        put_block_and_execution_results(&mut harness, &mut storage, block, execution_results);
+    assert!(!was_new);
+    let (transfers, _) = get_transfers_to(&mut harness, &mut storage, Key::Account(target), 0, 10);
+    assert_eq!(transfers.len(), 1);
+}
+
+#[test]
+fn rebuild_indexes_covers_previously_unindexed_data() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&mut harness);
+
+    let target = AccountHash::new([7; 32]);
+    let transfer = Box::new(Deploy::random_transfer(&mut harness.rng, target, 1.into()));
+    let block = Box::new(Block::random_with_deploys(
+        &mut harness.rng,
+        vec![*transfer.id()],
+    ));
+
+    // The execution results are stored before the block, and the block before the deploy, so
+    // neither the transfer nor the accounts involved in it are indexed.
+    let mut execution_results = HashMap::new();
+    execution_results.insert(
+        *transfer.id(),
+        ExecutionResult::random_success(&mut harness.rng),
+    );
+    put_execution_results(&mut harness, &mut storage, *block.hash(), execution_results);
+    put_block(&mut harness, &mut storage, block.clone());
+    put_deploy(&mut harness, &mut storage, transfer.clone());
+    assert_eq!(
+        get_account_deploys(&mut harness, &mut storage, target, 0, 10),
+        (0, vec![])
+    );
+    assert_eq!(
+        get_transfers_to(&mut harness, &mut storage, Key::Account(target), 0, 10),
+        (vec![], None)
+    );
+
+    let mut progress = vec![];
+    let all_indexes = Index::ALL.iter().copied().collect();
+    storage
+        .rebuild_indexes(&all_indexes, |processed, total| {
+            progress.push((processed, total))
+        })
+        .expect("should rebuild indexes");
+    assert_eq!(progress, vec![(1, 1)]);
+
+    assert_eq!(
+        get_account_deploys(&mut harness, &mut storage, target, 0, 10),
+        (1, vec![*transfer.id()])
+    );
+    let (transfers, _) = get_transfers_to(&mut harness, &mut storage, Key::Account(target), 0, 10);
+    assert_eq!(transfers.len(), 1);
+    assert_eq!(transfers[0].block_height, block.height());
+    let key_history = get_key_history(&mut harness, &mut storage, Key::Account(target), 0, 10);
+
+    // Rebuilding again replaces rather than duplicates the entries.
+    storage
+        .rebuild_indexes(&all_indexes, |_, _| ())
+        .expect("should rebuild indexes");
+    assert_eq!(
+        get_account_deploys(&mut harness, &mut storage, target, 0, 10),
+        (1, vec![*transfer.id()])
+    );
+    assert_eq!(
+        get_key_history(&mut harness, &mut storage, Key::Account(target), 0, 10),
+        key_history
+    );
+}
+
+#[test]
+fn should_parse_index_names() {
+    for index in Index::ALL.iter() {
+        assert_eq!(index.to_string().parse::<Index>(), Ok(*index));
+    }
+    assert!("deploys".parse::<Index>().is_err());
+}
+
 #[test]
 fn store_and_load_chainspec() {
     let mut harness = ComponentHarness::default();
@@ -649,6 +1339,27 @@ fn test_legacy_interface() {
         .is_none())
 }
 
+#[test]
+fn backup_can_be_opened_as_storage() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&mut harness);
+
+    let block = random_block_at_height(&mut harness.rng, 7);
+    put_block(&mut harness, &mut storage, block.clone());
+
+    // A backup replaces the `storage.lmdb` file of a storage folder.
+    let backup_folder = harness.tmp.path().join("backup");
+    fs::create_dir_all(&backup_folder).unwrap();
+    backup::copy_environment(&storage.env, &backup_folder.join("storage.lmdb"))
+        .expect("should take backup");
+
+    let mut restored = storage_fixture_at(harness.tmp.path(), backup_folder);
+    assert_eq!(
+        get_block_at_height(&mut harness, &mut restored, 7).expect("missing backed up block"),
+        *block
+    );
+}
+
 #[test]
 fn persist_blocks_deploys_and_deploy_metadata_across_instantiations() {
     let mut harness = ComponentHarness::default();
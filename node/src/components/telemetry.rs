@@ -0,0 +1,241 @@
+//! Opt-in telemetry.
+//!
+//! When an aggregation endpoint is configured, the telemetry component periodically posts a report
+//! of the node's health to it, for network-wide observability dashboards.  Reports are anonymized:
+//! they hold no node ID, address or key.  Instead, each report carries an instance ID chosen at
+//! random when the node starts, so that successive reports from the same run of a node can be
+//! correlated without identifying the node.
+
+mod config;
+
+use std::{
+    convert::Infallible,
+    fmt::{self, Display, Formatter},
+};
+
+use datasize::DataSize;
+use futures::join;
+use rand::Rng;
+use reqwest::{header::CONTENT_TYPE, Client, Url};
+use serde::Serialize;
+use thiserror::Error;
+use tracing::{debug, info, warn};
+
+use crate::{
+    components::Component,
+    effect::{
+        requests::{ChainspecLoaderRequest, NetworkInfoRequest, StorageRequest},
+        EffectBuilder, EffectExt, Effects,
+    },
+    types::{Block, BlockHeader, NodeId, Timestamp},
+    NodeRng, NODE_START_TIME,
+};
+
+pub use config::Config;
+
+/// A helper trait whose bounds represent the requirements for a reactor event that the telemetry
+/// component can work with.
+pub(crate) trait ReactorEventT:
+    From<Event>
+    + From<NetworkInfoRequest<NodeId>>
+    + From<StorageRequest>
+    + From<ChainspecLoaderRequest>
+    + Send
+    + 'static
+{
+}
+
+impl<REv> ReactorEventT for REv where
+    REv: From<Event>
+        + From<NetworkInfoRequest<NodeId>>
+        + From<StorageRequest>
+        + From<ChainspecLoaderRequest>
+        + Send
+        + 'static
+{
+}
+
+/// An error constructing the telemetry component.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// The HTTP client could not be built.
+    #[error("failed to build telemetry HTTP client: {0}")]
+    Client(#[from] reqwest::Error),
+
+    /// The configured URL of the endpoint is invalid.
+    #[error("invalid telemetry endpoint {url}: {message}")]
+    InvalidUrl { url: String, message: String },
+}
+
+/// An event for the telemetry component.
+#[derive(Debug)]
+pub enum Event {
+    /// A report should be posted to the endpoint.
+    Report,
+}
+
+impl Display for Event {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Event::Report => write!(formatter, "report"),
+        }
+    }
+}
+
+/// An anonymized report of the node's health.
+#[derive(Debug, Serialize)]
+struct TelemetryReport {
+    /// Random ID chosen when the node started.
+    instance_id: String,
+    /// The name of the network, as given in the chainspec.
+    chainspec_name: String,
+    /// The compiled node version.
+    build_version: String,
+    /// The number of seconds since the node started.
+    uptime_secs: u64,
+    /// The number of connected peers.
+    peer_count: usize,
+    /// The height of the highest block in the linear chain.
+    block_height: Option<u64>,
+    /// The number of milliseconds elapsed since the timestamp of the highest block.
+    finalization_lag_millis: Option<u64>,
+}
+
+impl TelemetryReport {
+    fn new(
+        instance_id: u64,
+        chainspec_name: String,
+        peer_count: usize,
+        maybe_highest_block: Option<&BlockHeader>,
+        now: Timestamp,
+    ) -> Self {
+        TelemetryReport {
+            instance_id: format!("{:016x}", instance_id),
+            chainspec_name,
+            build_version: crate::VERSION_STRING.clone(),
+            uptime_secs: NODE_START_TIME.elapsed().as_secs(),
+            peer_count,
+            block_height: maybe_highest_block.map(BlockHeader::height),
+            finalization_lag_millis: maybe_highest_block
+                .map(|header| now.saturating_sub(header.timestamp()).millis()),
+        }
+    }
+}
+
+#[derive(DataSize, Debug)]
+pub(crate) struct Telemetry {
+    config: Config,
+    /// The parsed URL of the endpoint, if telemetry is enabled.
+    #[data_size(skip)]
+    endpoint: Option<Url>,
+    #[data_size(skip)]
+    client: Client,
+    instance_id: u64,
+}
+
+impl Telemetry {
+    pub(crate) fn new<REv>(
+        config: Config,
+        effect_builder: EffectBuilder<REv>,
+        rng: &mut NodeRng,
+    ) -> Result<(Self, Effects<Event>), Error>
+    where
+        REv: ReactorEventT,
+    {
+        let endpoint = config
+            .endpoint
+            .as_ref()
+            .map(|url| {
+                Url::parse(url).map_err(|error| Error::InvalidUrl {
+                    url: url.clone(),
+                    message: error.to_string(),
+                })
+            })
+            .transpose()?;
+        let client = Client::builder().timeout(config.request_timeout).build()?;
+
+        let effects = match &endpoint {
+            Some(url) => {
+                info!(endpoint = %url, "telemetry enabled");
+                effect_builder
+                    .set_timeout(config.interval)
+                    .event(|_| Event::Report)
+            }
+            None => Effects::new(),
+        };
+
+        let telemetry = Telemetry {
+            config,
+            endpoint,
+            client,
+            instance_id: rng.gen(),
+        };
+        Ok((telemetry, effects))
+    }
+}
+
+impl<REv> Component<REv> for Telemetry
+where
+    REv: ReactorEventT,
+{
+    type Event = Event;
+    type ConstructionError = Infallible;
+
+    fn handle_event(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        _rng: &mut NodeRng,
+        event: Self::Event,
+    ) -> Effects<Self::Event> {
+        match event {
+            Event::Report => {
+                let endpoint = match &self.endpoint {
+                    Some(url) => url.clone(),
+                    None => return Effects::new(),
+                };
+                let mut effects = effect_builder
+                    .set_timeout(self.config.interval)
+                    .event(|_| Event::Report);
+
+                let client = self.client.clone();
+                let instance_id = self.instance_id;
+                effects.extend(
+                    async move {
+                        let (peers, maybe_highest_block, chainspec_info) = join!(
+                            effect_builder.network_peers::<NodeId>(),
+                            effect_builder.get_highest_block(),
+                            effect_builder.get_chainspec_info()
+                        );
+                        let report = TelemetryReport::new(
+                            instance_id,
+                            chainspec_info.name(),
+                            peers.len(),
+                            maybe_highest_block.as_ref().map(Block::header),
+                            Timestamp::now(),
+                        );
+                        let body = match serde_json::to_vec(&report) {
+                            Ok(body) => body,
+                            Err(error) => {
+                                warn!(%error, "failed to encode telemetry report");
+                                return;
+                            }
+                        };
+                        let result = client
+                            .post(endpoint)
+                            .header(CONTENT_TYPE, "application/json")
+                            .body(body)
+                            .send()
+                            .await
+                            .and_then(|response| response.error_for_status());
+                        match result {
+                            Ok(_) => debug!("posted telemetry report"),
+                            Err(error) => warn!(%error, "failed to post telemetry report"),
+                        }
+                    }
+                    .ignore(),
+                );
+                effects
+            }
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+
+/// Default interval between reports.
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Default time allowed for the endpoint to respond to a report.
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Telemetry configuration.
+#[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// URL of the aggregation endpoint to which reports are posted.  Telemetry is disabled if
+    /// unset.
+    #[serde(default)]
+    pub endpoint: Option<String>,
+
+    /// Interval in milliseconds between reports.
+    #[serde(with = "crate::utils::milliseconds")]
+    pub interval: Duration,
+
+    /// Time in milliseconds allowed for the endpoint to respond to a report.
+    #[serde(with = "crate::utils::milliseconds")]
+    pub request_timeout: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            endpoint: None,
+            interval: DEFAULT_INTERVAL,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+        }
+    }
+}
@@ -0,0 +1,465 @@
+//! Webhooks.
+//!
+//! The webhooks component posts JSON notifications of selected events to the endpoints configured
+//! by the node operator, as an alternative to consuming the event stream.  Each endpoint chooses
+//! the kinds of event it is notified of, and may restrict notifications of processed deploys to
+//! those sent from a set of watched accounts.
+//!
+//! By default the notification itself is posted, as an object with an `event` field naming its
+//! kind and a `data` field holding its details.  An endpoint may instead configure a template of
+//! the payload, with placeholders referring to fields of the notification.
+//!
+//! If an endpoint has a secret configured, each request is signed with it, allowing the endpoint
+//! to authenticate the node.  A delivery which fails, either because the request could not be sent
+//! or because the endpoint responded with an error status, is retried with exponential backoff up
+//! to the configured number of attempts.
+
+mod config;
+mod event;
+
+use std::{convert::Infallible, time::Duration};
+
+use datasize::DataSize;
+use openssl::{error::ErrorStack, hash::MessageDigest, pkey::PKey, sign::Signer};
+use reqwest::{header::CONTENT_TYPE, Client, Url};
+use serde::Serialize;
+use serde_json::Value;
+use thiserror::Error;
+use tracing::{debug, warn};
+
+use crate::{
+    components::{consensus::EraId, Component},
+    crypto::asymmetric_key::PublicKey,
+    effect::{EffectBuilder, EffectExt, Effects},
+    types::{
+        json_compatibility::ExecutionResult, BlockHash, BlockHeader, DeployHash, TimeDiff,
+        Timestamp,
+    },
+    NodeRng,
+};
+
+pub use config::{Config, EndpointConfig, NotificationKind};
+use event::Delivery;
+pub(crate) use event::Event;
+
+/// The header holding the signature of a notification.
+const SIGNATURE_HEADER: &str = "X-Casper-Signature";
+
+/// The maximum number of times the retry interval is doubled.
+const MAX_BACKOFF_DOUBLINGS: u32 = 10;
+
+/// An error constructing the webhooks component.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// The HTTP client could not be built.
+    #[error("failed to build webhooks HTTP client: {0}")]
+    Client(#[from] reqwest::Error),
+
+    /// The configured URL of an endpoint is invalid.
+    #[error("invalid webhook URL {url}: {message}")]
+    InvalidUrl { url: String, message: String },
+}
+
+/// A notification of an event, as posted to endpoints.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+enum Notification {
+    /// A block has been added to the linear chain.
+    BlockAdded {
+        block_hash: BlockHash,
+        block_header: BlockHeader,
+    },
+    /// A deploy has been executed as part of the given block.
+    DeployProcessed {
+        deploy_hash: DeployHash,
+        account: PublicKey,
+        timestamp: Timestamp,
+        block_hash: BlockHash,
+        execution_result: ExecutionResult,
+    },
+    /// The given validator equivocated, and is excluded from the validator set of the given era
+    /// onwards.  The given block is the switch block of the preceding era.
+    ValidatorEjected {
+        validator: PublicKey,
+        era_id: EraId,
+        block_hash: BlockHash,
+    },
+    /// Finalization lags further behind the current time than the alert threshold.
+    FallingBehind {
+        block_height: u64,
+        block_timestamp: Timestamp,
+        lag: TimeDiff,
+    },
+}
+
+impl Notification {
+    fn kind(&self) -> NotificationKind {
+        match self {
+            Notification::BlockAdded { .. } => NotificationKind::BlockAdded,
+            Notification::DeployProcessed { .. } => NotificationKind::DeployProcessed,
+            Notification::ValidatorEjected { .. } => NotificationKind::ValidatorEjected,
+            Notification::FallingBehind { .. } => NotificationKind::FallingBehind,
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self.kind() {
+            NotificationKind::BlockAdded => "block added",
+            NotificationKind::DeployProcessed => "deploy processed",
+            NotificationKind::ValidatorEjected => "validator ejected",
+            NotificationKind::FallingBehind => "falling behind",
+        }
+    }
+
+    /// Returns whether `endpoint` should be sent this notification.
+    fn is_wanted_by(&self, endpoint: &EndpointConfig) -> bool {
+        if !endpoint.events.contains(&self.kind()) {
+            return false;
+        }
+        match self {
+            Notification::DeployProcessed { account, .. } => {
+                endpoint.watched_accounts.contains(account)
+            }
+            _ => true,
+        }
+    }
+}
+
+#[derive(DataSize, Debug)]
+pub(crate) struct Webhooks {
+    config: Config,
+    /// The parsed URLs of the configured endpoints, in the same order.
+    #[data_size(skip)]
+    urls: Vec<Url>,
+    #[data_size(skip)]
+    client: Client,
+}
+
+impl Webhooks {
+    pub(crate) fn new(config: Config) -> Result<Self, Error> {
+        let urls = config
+            .endpoints
+            .iter()
+            .map(|endpoint| {
+                Url::parse(&endpoint.url).map_err(|error| Error::InvalidUrl {
+                    url: endpoint.url.clone(),
+                    message: error.to_string(),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        let client = Client::builder().timeout(config.request_timeout).build()?;
+        Ok(Webhooks {
+            config,
+            urls,
+            client,
+        })
+    }
+
+    /// Posts `notification` to each endpoint which wants it.
+    fn notify(&self, notification: Notification) -> Effects<Event> {
+        let wanting_endpoints: Vec<_> = self
+            .config
+            .endpoints
+            .iter()
+            .enumerate()
+            .filter(|(_, endpoint)| notification.is_wanted_by(endpoint))
+            .collect();
+        if wanting_endpoints.is_empty() {
+            return Effects::new();
+        }
+
+        let value = match serde_json::to_value(&notification) {
+            Ok(value) => value,
+            Err(error) => {
+                warn!(%error, "failed to encode webhook notification");
+                return Effects::new();
+            }
+        };
+
+        let mut effects = Effects::new();
+        for (index, endpoint) in wanting_endpoints {
+            let payload = match &endpoint.payload_template {
+                Some(template) => render_payload(template, &value),
+                None => value.clone(),
+            };
+            let body = match serde_json::to_vec(&payload) {
+                Ok(body) => body,
+                Err(error) => {
+                    warn!(%error, "failed to encode webhook payload");
+                    continue;
+                }
+            };
+            let delivery = Delivery {
+                endpoint: index,
+                description: notification.description(),
+                body,
+                attempts: 0,
+            };
+            effects.extend(self.deliver(Box::new(delivery)));
+        }
+        effects
+    }
+
+    /// Makes an attempt to post the notification of `delivery` to its endpoint.
+    fn deliver(&self, mut delivery: Box<Delivery>) -> Effects<Event> {
+        delivery.attempts += 1;
+        let url = self.urls[delivery.endpoint].clone();
+        let mut request = self
+            .client
+            .post(url)
+            .header(CONTENT_TYPE, "application/json");
+        if let Some(secret) = &self.config.endpoints[delivery.endpoint].secret {
+            match sign(secret, &delivery.body) {
+                Ok(signature) => {
+                    request = request.header(SIGNATURE_HEADER, format!("sha256={}", signature))
+                }
+                Err(error) => {
+                    // The delivery can't succeed, so don't retry it.
+                    warn!(%error, "failed to sign webhook notification");
+                    return Effects::new();
+                }
+            }
+        }
+        let request = request.body(delivery.body.clone());
+
+        async move {
+            request
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .map(|_| ())
+                .map_err(|error| error.to_string())
+        }
+        .event(move |result| Event::DeliveryResult { delivery, result })
+    }
+
+    /// Returns the delay before the next attempt to deliver a notification which has already been
+    /// attempted `attempts` times.
+    fn retry_delay(&self, attempts: u32) -> Duration {
+        let doublings = attempts.saturating_sub(1).min(MAX_BACKOFF_DOUBLINGS);
+        self.config.retry_interval * 2u32.pow(doublings)
+    }
+}
+
+/// Returns the hex-encoded HMAC-SHA256 of `body` keyed by `secret`.
+fn sign(secret: &str, body: &[u8]) -> Result<String, ErrorStack> {
+    let key = PKey::hmac(secret.as_bytes())?;
+    let mut signer = Signer::new(MessageDigest::sha256(), &key)?;
+    signer.update(body)?;
+    Ok(hex::encode(signer.sign_to_vec()?))
+}
+
+/// Renders a payload template by replacing its placeholders with values from `notification`.
+///
+/// A string consisting of a single placeholder is replaced by the referenced JSON value, or `null`
+/// if there is none.  Placeholders within longer strings are replaced by the text of the
+/// referenced value, or removed if there is none.
+fn render_payload(template: &Value, notification: &Value) -> Value {
+    match template {
+        Value::String(string) => {
+            if let Some(path) = sole_placeholder(string) {
+                return lookup(notification, path).cloned().unwrap_or(Value::Null);
+            }
+            let mut rendered = String::new();
+            let mut remainder = string.as_str();
+            while let Some(start) = remainder.find("${") {
+                let end = match remainder[start..].find('}') {
+                    Some(end) => start + end,
+                    None => break,
+                };
+                rendered.push_str(&remainder[..start]);
+                match lookup(notification, &remainder[start + 2..end]) {
+                    Some(Value::String(value)) => rendered.push_str(value),
+                    Some(value) => rendered.push_str(&value.to_string()),
+                    None => (),
+                }
+                remainder = &remainder[end + 1..];
+            }
+            rendered.push_str(remainder);
+            Value::String(rendered)
+        }
+        Value::Array(values) => Value::Array(
+            values
+                .iter()
+                .map(|value| render_payload(value, notification))
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| (key.clone(), render_payload(value, notification)))
+                .collect(),
+        ),
+        _ => template.clone(),
+    }
+}
+
+/// Returns the path of the placeholder if `string` consists of nothing else.
+fn sole_placeholder(string: &str) -> Option<&str> {
+    let path = string.strip_prefix("${")?.strip_suffix('}')?;
+    if path.contains('}') {
+        return None;
+    }
+    Some(path)
+}
+
+/// Returns the value at a dot-separated path in `notification`, e.g. `data.block_header.height`.
+fn lookup<'a>(notification: &'a Value, path: &str) -> Option<&'a Value> {
+    notification.pointer(&format!("/{}", path.replace('.', "/")))
+}
+
+impl<REv> Component<REv> for Webhooks
+where
+    REv: From<Event> + Send + 'static,
+{
+    type Event = Event;
+    type ConstructionError = Infallible;
+
+    fn handle_event(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        _rng: &mut NodeRng,
+        event: Self::Event,
+    ) -> Effects<Self::Event> {
+        match event {
+            Event::BlockAdded {
+                block_hash,
+                block_header,
+            } => {
+                let mut effects = Effects::new();
+                if let Some(era_end) = block_header.era_end() {
+                    for validator in &era_end.equivocators {
+                        let notification = Notification::ValidatorEjected {
+                            validator: *validator,
+                            era_id: block_header.era_id().successor(),
+                            block_hash,
+                        };
+                        effects.extend(self.notify(notification));
+                    }
+                }
+                let notification = Notification::BlockAdded {
+                    block_hash,
+                    block_header: *block_header,
+                };
+                effects.extend(self.notify(notification));
+                effects
+            }
+            Event::DeployProcessed {
+                deploy_hash,
+                deploy_header,
+                block_hash,
+                execution_result,
+            } => {
+                let notification = Notification::DeployProcessed {
+                    deploy_hash,
+                    account: *deploy_header.account(),
+                    timestamp: deploy_header.timestamp(),
+                    block_hash,
+                    execution_result: *execution_result,
+                };
+                self.notify(notification)
+            }
+            Event::FallingBehind {
+                block_height,
+                block_timestamp,
+                lag,
+            } => {
+                let notification = Notification::FallingBehind {
+                    block_height,
+                    block_timestamp,
+                    lag,
+                };
+                self.notify(notification)
+            }
+            Event::Deliver(delivery) => self.deliver(delivery),
+            Event::DeliveryResult {
+                delivery,
+                result: Ok(()),
+            } => {
+                debug!(
+                    endpoint = %self.config.endpoints[delivery.endpoint].url,
+                    "delivered {} notification", delivery.description
+                );
+                Effects::new()
+            }
+            Event::DeliveryResult {
+                delivery,
+                result: Err(error),
+            } => {
+                let url = &self.config.endpoints[delivery.endpoint].url;
+                if delivery.attempts >= self.config.max_attempts {
+                    warn!(
+                        endpoint = %url,
+                        %error,
+                        attempts = delivery.attempts,
+                        "giving up delivering {} notification", delivery.description
+                    );
+                    return Effects::new();
+                }
+                let delay = self.retry_delay(delivery.attempts);
+                warn!(
+                    endpoint = %url,
+                    %error,
+                    ?delay,
+                    "failed to deliver {} notification, will retry", delivery.description
+                );
+                effect_builder
+                    .set_timeout(delay)
+                    .event(move |_| Event::Deliver(delivery))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn should_sign_with_hmac_sha256() {
+        // Test case 2 of RFC 4231.
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?").unwrap(),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn should_render_payload_template() {
+        let notification = json!({
+            "event": "falling_behind",
+            "data": {
+                "block_height": 10,
+                "lag": "2m 5s",
+            },
+        });
+        let template = json!({
+            "text": "Node is ${event}: block ${data.block_height} finalized ${data.lag} ago",
+            "height": "${data.block_height}",
+            "details": ["${data}", "${data.missing}", true],
+        });
+        let expected = json!({
+            "text": "Node is falling_behind: block 10 finalized 2m 5s ago",
+            "height": 10,
+            "details": [{"block_height": 10, "lag": "2m 5s"}, null, true],
+        });
+        assert_eq!(render_payload(&template, &notification), expected);
+    }
+
+    #[test]
+    fn should_back_off_exponentially() {
+        let config = Config {
+            retry_interval: Duration::from_secs(1),
+            ..Config::default()
+        };
+        let webhooks = Webhooks::new(config).unwrap();
+        assert_eq!(webhooks.retry_delay(1), Duration::from_secs(1));
+        assert_eq!(webhooks.retry_delay(2), Duration::from_secs(2));
+        assert_eq!(webhooks.retry_delay(4), Duration::from_secs(8));
+        assert_eq!(
+            webhooks.retry_delay(100),
+            Duration::from_secs(1 << MAX_BACKOFF_DOUBLINGS)
+        );
+    }
+}
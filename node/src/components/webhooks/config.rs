@@ -0,0 +1,109 @@
+use std::{
+    fmt::{self, Debug, Formatter},
+    time::Duration,
+};
+
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+
+use crate::crypto::asymmetric_key::PublicKey;
+
+/// Default time allowed for an endpoint to respond to a notification.
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default number of attempts made to deliver a notification.
+const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+
+/// Default delay before retrying a failed delivery.  It is doubled after each further failure.
+const DEFAULT_RETRY_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Webhooks configuration.
+#[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Time in milliseconds allowed for an endpoint to respond to a notification.
+    #[serde(with = "crate::utils::milliseconds")]
+    pub request_timeout: Duration,
+
+    /// Number of attempts made to deliver a notification before it is dropped.
+    pub max_attempts: u32,
+
+    /// Delay in milliseconds before retrying a failed delivery.  It is doubled after each further
+    /// failure.
+    #[serde(with = "crate::utils::milliseconds")]
+    pub retry_interval: Duration,
+
+    /// Endpoints to which notifications are posted.
+    #[serde(default)]
+    pub endpoints: Vec<EndpointConfig>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            retry_interval: DEFAULT_RETRY_INTERVAL,
+            endpoints: vec![],
+        }
+    }
+}
+
+/// The kinds of event for which a notification can be posted.
+#[derive(Clone, Copy, DataSize, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    /// A block has been added to the linear chain.
+    BlockAdded,
+    /// A deploy sent from one of the endpoint's watched accounts has been executed.
+    DeployProcessed,
+    /// A validator has been ejected from the validator set for equivocating.
+    ValidatorEjected,
+    /// Finalization lags further behind the current time than the finality monitor's alert
+    /// threshold.
+    FallingBehind,
+}
+
+/// An endpoint to which notifications are posted.
+#[derive(Clone, DataSize, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct EndpointConfig {
+    /// URL to which notifications are posted.
+    pub url: String,
+
+    /// The kinds of event for which notifications are posted.
+    pub events: Vec<NotificationKind>,
+
+    /// The accounts whose processed deploys are notified.  Only relevant if `events` includes
+    /// `deploy_processed`.
+    #[serde(default)]
+    pub watched_accounts: Vec<PublicKey>,
+
+    /// Secret used to sign each notification.  If set, the hex-encoded HMAC-SHA256 of the request
+    /// body is sent in the `X-Casper-Signature` header, prefixed with `sha256=`.
+    #[serde(default)]
+    pub secret: Option<String>,
+
+    /// Template of the JSON payload posted.  If unset, the notification itself is posted.
+    ///
+    /// Any string in the template consisting of a single placeholder such as `${data.block_hash}`
+    /// is replaced by the JSON value at that path in the notification, and placeholders within
+    /// longer strings are replaced by the value's text.
+    #[serde(default)]
+    #[data_size(skip)]
+    pub payload_template: Option<serde_json::Value>,
+}
+
+// Manually implemented to keep the secret out of the logs.
+impl Debug for EndpointConfig {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("EndpointConfig")
+            .field("url", &self.url)
+            .field("events", &self.events)
+            .field("watched_accounts", &self.watched_accounts)
+            .field("payload_template", &self.payload_template)
+            .finish()
+    }
+}
@@ -0,0 +1,70 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::types::{
+    json_compatibility::ExecutionResult, BlockHash, BlockHeader, DeployHash, DeployHeader,
+    TimeDiff, Timestamp,
+};
+
+/// A notification serialized and ready to be posted to an endpoint.
+#[derive(Debug)]
+pub struct Delivery {
+    /// The index of the endpoint in the configuration.
+    pub(super) endpoint: usize,
+    /// The kind of the notification, for logging.
+    pub(super) description: &'static str,
+    /// The request body.
+    pub(super) body: Vec<u8>,
+    /// The number of attempts made so far to deliver the notification.
+    pub(super) attempts: u32,
+}
+
+#[derive(Debug)]
+pub enum Event {
+    /// A block has been added to the linear chain.
+    BlockAdded {
+        block_hash: BlockHash,
+        block_header: Box<BlockHeader>,
+    },
+    /// A deploy has been executed as part of the given block.
+    DeployProcessed {
+        deploy_hash: DeployHash,
+        deploy_header: Box<DeployHeader>,
+        block_hash: BlockHash,
+        execution_result: Box<ExecutionResult>,
+    },
+    /// Finalization lags further behind the current time than the alert threshold.
+    FallingBehind {
+        block_height: u64,
+        block_timestamp: Timestamp,
+        lag: TimeDiff,
+    },
+    /// A notification should be posted to its endpoint.
+    Deliver(Box<Delivery>),
+    /// An attempt to post a notification to its endpoint has completed.
+    DeliveryResult {
+        delivery: Box<Delivery>,
+        result: Result<(), String>,
+    },
+}
+
+impl Display for Event {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Event::BlockAdded { block_hash, .. } => write!(formatter, "block added {}", block_hash),
+            Event::DeployProcessed { deploy_hash, .. } => {
+                write!(formatter, "deploy processed {}", deploy_hash)
+            }
+            Event::FallingBehind { lag, .. } => write!(formatter, "falling behind by {}", lag),
+            Event::Deliver(delivery) => write!(
+                formatter,
+                "deliver {} notification to endpoint {}",
+                delivery.description, delivery.endpoint
+            ),
+            Event::DeliveryResult { delivery, result } => write!(
+                formatter,
+                "{} notification to endpoint {} delivered: {:?}",
+                delivery.description, delivery.endpoint, result
+            ),
+        }
+    }
+}
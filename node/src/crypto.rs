@@ -3,5 +3,6 @@
 pub mod asymmetric_key;
 mod error;
 pub mod hash;
+pub mod signer;
 
 pub use error::{Error, Result};
@@ -2,6 +2,7 @@ use std::result;
 
 use base64::DecodeError;
 use hex::FromHexError;
+use openssl::error::ErrorStack;
 use pem::PemError;
 use thiserror::Error;
 
@@ -25,6 +26,14 @@ pub enum Error {
     #[error("secret key load failed: {0}")]
     SecretKeyLoad(ReadFileError),
 
+    /// Error trying to decode an encrypted secret key without a passphrase.
+    #[error("secret key is encrypted and requires a passphrase")]
+    SecretKeyEncrypted,
+
+    /// Error encrypting or decrypting a secret key, e.g. due to a wrong passphrase.
+    #[error("secret key encryption failed: {0}")]
+    SecretKeyEncryption(#[from] ErrorStack),
+
     /// Error trying to read a public key.
     #[error("public key load failed: {0}")]
     PublicKeyLoad(ReadFileError),
@@ -48,6 +57,10 @@ pub enum Error {
     /// Error trying to write a public key.
     #[error("public key save failed: {0}")]
     PublicKeySave(WriteFileError),
+
+    /// Error requesting a signature from a remote signer service.
+    #[error("remote signer error: {0}")]
+    RemoteSigner(String),
 }
 
 impl From<PemError> for Error {
@@ -15,6 +15,7 @@ use hex_buffer_serde::{Hex, HexForm};
 use hex_fmt::HexFmt;
 #[cfg(test)]
 use rand::Rng;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use casper_execution_engine::shared::newtypes::Blake2bHash;
@@ -26,9 +27,25 @@ use crate::testing::TestRng;
 
 /// The hash digest; a wrapped `u8` array.
 #[derive(
-    Copy, Clone, DataSize, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Default,
+    Copy,
+    Clone,
+    DataSize,
+    Ord,
+    PartialOrd,
+    Eq,
+    PartialEq,
+    Hash,
+    Serialize,
+    Deserialize,
+    Default,
+    JsonSchema,
 )]
-pub struct Digest(#[serde(with = "HexForm::<[u8; Digest::LENGTH]>")] [u8; Digest::LENGTH]);
+#[schemars(description = "Hex-encoded hash digest.")]
+pub struct Digest(
+    #[serde(with = "HexForm::<[u8; Digest::LENGTH]>")]
+    #[schemars(with = "String")]
+    [u8; Digest::LENGTH],
+);
 
 impl Digest {
     /// Length of `Digest` in bytes.
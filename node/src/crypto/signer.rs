@@ -0,0 +1,351 @@
+//! Signing of messages with a validator's key.
+//!
+//! The key is either held by the node itself (`LocalSigner`), or by an external signer service
+//! such as a KMS or HSM proxy (`RemoteSigner`), in which case it never resides on the node.
+
+use std::{
+    fmt::{self, Debug, Formatter},
+    net::IpAddr,
+    result,
+    sync::mpsc,
+    thread,
+    time::Duration,
+};
+
+use futures::{
+    channel::oneshot,
+    future::{self, BoxFuture},
+    FutureExt,
+};
+use reqwest::{
+    blocking::Client,
+    header::{AUTHORIZATION, CONTENT_TYPE},
+    Certificate, Identity, Url,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use super::{
+    asymmetric_key::{self, PublicKey, SecretKey, Signature},
+    Error, Result,
+};
+use crate::NodeRng;
+
+/// A provider of signatures made with a single key.
+pub trait Signer: Debug {
+    /// Returns the public key matching the key used to sign.
+    fn public_key(&self) -> &PublicKey;
+
+    /// Returns a future resolving to the signature of the given message.
+    ///
+    /// Never blocks the caller: the returned future has to be awaited, e.g. in an effect, to
+    /// obtain the signature.
+    fn sign(&self, message: Vec<u8>, rng: &mut NodeRng) -> BoxFuture<'static, Result<Signature>>;
+}
+
+/// A signer using a secret key held in memory.
+pub struct LocalSigner {
+    secret_key: SecretKey,
+    public_key: PublicKey,
+}
+
+impl LocalSigner {
+    /// Constructs a new `LocalSigner`.
+    pub fn new(secret_key: SecretKey) -> Self {
+        let public_key = PublicKey::from(&secret_key);
+        LocalSigner {
+            secret_key,
+            public_key,
+        }
+    }
+}
+
+impl Debug for LocalSigner {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "LocalSigner({})", self.public_key)
+    }
+}
+
+impl Signer for LocalSigner {
+    fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    fn sign(&self, message: Vec<u8>, rng: &mut NodeRng) -> BoxFuture<'static, Result<Signature>> {
+        let signature = asymmetric_key::sign(message, &self.secret_key, &self.public_key, rng);
+        future::ready(Ok(signature)).boxed()
+    }
+}
+
+/// The credentials with which a `RemoteSigner` authenticates to the remote signer service.
+#[derive(Clone)]
+pub struct RemoteSignerCredentials {
+    /// The token sent as `Authorization: Bearer <token>` with every request.
+    pub auth_token: String,
+    /// A PEM-encoded root certificate trusted for the service's TLS certificate, in addition to
+    /// the system's root certificates.
+    pub ca_certificate_pem: Option<Vec<u8>>,
+    /// A DER-encoded PKCS #12 archive without password, holding the client certificate and key
+    /// presented to the service for mutual TLS.
+    pub client_identity_pkcs12: Option<Vec<u8>>,
+}
+
+/// The body of a request to the remote signer service.
+#[derive(Serialize)]
+struct SignRequest {
+    /// The hex-encoded public key whose secret key should sign.
+    public_key: String,
+    /// The hex-encoded message to sign.
+    message: String,
+}
+
+/// The body of a response from the remote signer service.
+#[derive(Deserialize)]
+struct SignResponse {
+    /// The hex-encoded signature.
+    signature: String,
+}
+
+/// A request forwarded to the thread talking to the remote signer service.
+struct PendingRequest {
+    body: Vec<u8>,
+    responder: oneshot::Sender<result::Result<Vec<u8>, String>>,
+}
+
+/// A signer forwarding signing requests to an external signer service over HTTP.
+///
+/// Each request is a `POST` of a JSON object `{"public_key": <hex>, "message": <hex>}` to the
+/// configured address, authenticated with a bearer token and optionally a TLS client certificate,
+/// to which the service responds with `{"signature": <hex>}`.  The returned signature is verified
+/// before being used.  There is no fallback to a local key: if the service is unreachable or
+/// misbehaves, signing fails.
+///
+/// The HTTP client runs on a dedicated thread, so waiting for the service never blocks the
+/// reactor: `sign` only hands the request to that thread, and the returned future resolves once
+/// the service responded or the timeout elapsed.
+pub struct RemoteSigner {
+    public_key: PublicKey,
+    address: Url,
+    requests: mpsc::Sender<PendingRequest>,
+}
+
+impl RemoteSigner {
+    /// Constructs a new `RemoteSigner` signing with the key of the remote signer service at
+    /// `address` matching `public_key`.
+    ///
+    /// The address must use `https`, unless the service runs on a loopback address.
+    pub fn new(
+        address: Url,
+        credentials: RemoteSignerCredentials,
+        public_key: PublicKey,
+        timeout: Duration,
+    ) -> Result<Self> {
+        if credentials.auth_token.trim().is_empty() {
+            return Err(Error::RemoteSigner(
+                "the auth token must not be empty".to_string(),
+            ));
+        }
+        if address.scheme() != "https" && !is_loopback(&address) {
+            return Err(Error::RemoteSigner(format!(
+                "{} must use https, unless the service runs on a loopback address",
+                address
+            )));
+        }
+
+        let (requests, receiver) = mpsc::channel::<PendingRequest>();
+        let (init_sender, init_receiver) = mpsc::sync_channel(1);
+        let thread_address = address.clone();
+        thread::Builder::new()
+            .name("remote-signer".to_string())
+            .spawn(move || {
+                let client = match build_client(&credentials, timeout) {
+                    Ok(client) => {
+                        let _ = init_sender.send(Ok(()));
+                        client
+                    }
+                    Err(error) => {
+                        let _ = init_sender.send(Err(error));
+                        return;
+                    }
+                };
+                let authorization = format!("Bearer {}", credentials.auth_token.trim());
+                for request in receiver {
+                    let result = client
+                        .post(thread_address.clone())
+                        .header(CONTENT_TYPE, "application/json")
+                        .header(AUTHORIZATION, authorization.as_str())
+                        .body(request.body)
+                        .send()
+                        .and_then(|response| response.error_for_status())
+                        .and_then(|response| response.bytes())
+                        .map(|bytes| bytes.to_vec())
+                        .map_err(|error| error.to_string());
+                    let _ = request.responder.send(result);
+                }
+                debug!(address = %thread_address, "remote signer stopped");
+            })
+            .map_err(|error| Error::RemoteSigner(error.to_string()))?;
+        init_receiver
+            .recv()
+            .map_err(|error| Error::RemoteSigner(error.to_string()))?
+            .map_err(Error::RemoteSigner)?;
+
+        Ok(RemoteSigner {
+            public_key,
+            address,
+            requests,
+        })
+    }
+}
+
+/// Returns whether `address` points to the local host, where plain `http` can't be intercepted.
+fn is_loopback(address: &Url) -> bool {
+    match address.host_str() {
+        Some("localhost") => true,
+        Some(host) => host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+            .map_or(false, |ip| ip.is_loopback()),
+        None => false,
+    }
+}
+
+/// Builds the HTTP client presenting the given credentials' TLS certificates.
+fn build_client(
+    credentials: &RemoteSignerCredentials,
+    timeout: Duration,
+) -> result::Result<Client, String> {
+    let mut builder = Client::builder().timeout(timeout);
+    if let Some(pem) = &credentials.ca_certificate_pem {
+        let certificate = Certificate::from_pem(pem)
+            .map_err(|error| format!("invalid CA certificate: {}", error))?;
+        builder = builder.add_root_certificate(certificate);
+    }
+    if let Some(pkcs12) = &credentials.client_identity_pkcs12 {
+        let identity = Identity::from_pkcs12_der(pkcs12, "")
+            .map_err(|error| format!("invalid client identity: {}", error))?;
+        builder = builder.identity(identity);
+    }
+    builder.build().map_err(|error| error.to_string())
+}
+
+impl Debug for RemoteSigner {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "RemoteSigner({} at {})",
+            self.public_key, self.address
+        )
+    }
+}
+
+impl Signer for RemoteSigner {
+    fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    fn sign(&self, message: Vec<u8>, _rng: &mut NodeRng) -> BoxFuture<'static, Result<Signature>> {
+        let body = match serde_json::to_vec(&SignRequest {
+            public_key: self.public_key.to_hex(),
+            message: hex::encode(&message),
+        }) {
+            Ok(body) => body,
+            Err(error) => {
+                return future::ready(Err(Error::RemoteSigner(error.to_string()))).boxed()
+            }
+        };
+
+        let (responder, response) = oneshot::channel();
+        let sent = self.requests.send(PendingRequest { body, responder });
+        let public_key = self.public_key;
+        let address = self.address.clone();
+        async move {
+            sent.map_err(|_| Error::RemoteSigner("remote signer thread has stopped".to_string()))?;
+            let response = response
+                .await
+                .map_err(|_| Error::RemoteSigner("remote signer thread has stopped".to_string()))?
+                .map_err(|error| {
+                    warn!(%address, %error, "remote signer request failed");
+                    Error::RemoteSigner(error)
+                })?;
+
+            let response: SignResponse = serde_json::from_slice(&response)
+                .map_err(|error| Error::RemoteSigner(format!("invalid response: {}", error)))?;
+            let signature = Signature::from_hex(&response.signature)?;
+            asymmetric_key::verify(&message, &signature, &public_key).map_err(|error| {
+                Error::RemoteSigner(format!("invalid signature from remote signer: {}", error))
+            })?;
+            Ok(signature)
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+    use crate::testing::TestRng;
+
+    fn credentials() -> RemoteSignerCredentials {
+        RemoteSignerCredentials {
+            auth_token: "token".to_string(),
+            ca_certificate_pem: None,
+            client_identity_pkcs12: None,
+        }
+    }
+
+    #[test]
+    fn local_signer_should_sign() {
+        let mut rng = TestRng::new();
+        let signer = LocalSigner::new(SecretKey::random(&mut rng));
+        let message = b"message";
+        let signature = block_on(signer.sign(message.to_vec(), &mut rng)).unwrap();
+        asymmetric_key::verify(message, &signature, signer.public_key()).unwrap();
+    }
+
+    #[test]
+    fn remote_signer_should_fail_without_service() {
+        let mut rng = TestRng::new();
+        let public_key = PublicKey::random(&mut rng);
+        // Nothing listens on port 1 of the loopback address.
+        let address = Url::parse("http://127.0.0.1:1/sign").unwrap();
+        let signer =
+            RemoteSigner::new(address, credentials(), public_key, Duration::from_secs(1)).unwrap();
+        assert!(matches!(
+            block_on(signer.sign(b"message".to_vec(), &mut rng)),
+            Err(Error::RemoteSigner(_))
+        ));
+    }
+
+    #[test]
+    fn remote_signer_should_require_auth_token() {
+        let mut rng = TestRng::new();
+        let public_key = PublicKey::random(&mut rng);
+        let address = Url::parse("https://signer.example.com/sign").unwrap();
+        let credentials = RemoteSignerCredentials {
+            auth_token: " \n".to_string(),
+            ..credentials()
+        };
+        assert!(
+            RemoteSigner::new(address, credentials, public_key, Duration::from_secs(1)).is_err()
+        );
+    }
+
+    #[test]
+    fn remote_signer_should_require_https_unless_on_loopback() {
+        let mut rng = TestRng::new();
+        let public_key = PublicKey::random(&mut rng);
+        let timeout = Duration::from_secs(1);
+
+        let remote = Url::parse("http://signer.example.com/sign").unwrap();
+        assert!(RemoteSigner::new(remote, credentials(), public_key, timeout).is_err());
+
+        for address in &["http://localhost:7778/sign", "http://[::1]:7778/sign"] {
+            let address = Url::parse(address).unwrap();
+            assert!(RemoteSigner::new(address, credentials(), public_key, timeout).is_ok());
+        }
+    }
+}
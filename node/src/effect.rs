@@ -67,7 +67,7 @@ use std::{
     fmt::{self, Debug, Display, Formatter},
     future::Future,
     net::SocketAddr,
-    sync::Arc,
+    sync::{atomic::Ordering, Arc},
     time::{Duration, Instant},
 };
 
@@ -87,45 +87,60 @@ use casper_execution_engine::{
         execution_result::ExecutionResults,
         genesis::GenesisResult,
         step::{StepRequest, StepResult},
-        BalanceRequest, BalanceResult, QueryRequest, QueryResult,
+        BalanceRequest, BalanceResult, KeysWithPrefixRequest, KeysWithPrefixResult,
+        QueryBatchResult, QueryRequest, QueryRequestBatch, QueryResult,
+    },
+    shared::{additive_map::AdditiveMap, stored_value::StoredValue, transform::Transform},
+    storage::{
+        error::lmdb::Error as StorageLmdbError, global_state::CommitResult,
+        protocol_data::ProtocolData, trie::Trie,
     },
-    shared::{additive_map::AdditiveMap, transform::Transform},
-    storage::{global_state::CommitResult, protocol_data::ProtocolData},
 };
 use casper_types::{
-    auction::{EraValidators, ValidatorWeights},
+    account::AccountHash,
+    auction::{EraValidators, UnbondingPurses, ValidatorWeights, UNBONDING_PURSES_KEY},
     Key, ProtocolVersion,
 };
 
 use crate::{
     components::{
+        block_proposer::BufferFull,
         chainspec_loader::ChainspecInfo,
-        consensus::BlockContext,
+        consensus::{BlockContext, EraId},
         contract_runtime::{EraValidatorsRequest, ValidatorWeightsByEraIdRequest},
         fetcher::FetchResult,
         small_network::GossipedAddress,
     },
-    crypto::{asymmetric_key::Signature, hash::Digest},
+    crypto::{
+        asymmetric_key::{PublicKey, Signature},
+        hash::Digest,
+    },
     effect::requests::LinearChainRequest,
     reactor::{EventQueueHandle, QueueKind},
+    rpcs::admin::AdminCommand,
     types::{
-        json_compatibility::ExecutionResult, Block, BlockByHeight, BlockHash, BlockHeader,
-        BlockLike, Deploy, DeployHash, DeployHeader, DeployMetadata, FinalizedBlock, Item,
-        ProtoBlock, Timestamp,
+        json_compatibility::{ExecutionResult, GasProfile},
+        Block, BlockByHeight, BlockHash, BlockHeader, BlockLike, Deploy, DeployHash, DeployHeader,
+        DeployMetadata, DeployReceipt, FinalizedBlock, Item, KeyHistoryEntry, ProtoBlock,
+        SyncProgress, Timestamp, TransferEntry,
     },
     utils::Source,
     Chainspec,
 };
 use announcements::{
     BlockExecutorAnnouncement, ConsensusAnnouncement, DeployAcceptorAnnouncement,
-    GossiperAnnouncement, LinearChainAnnouncement, NetworkAnnouncement, RpcServerAnnouncement,
+    FinalityMonitorAnnouncement, GossiperAnnouncement, LinearChainAnnouncement,
+    NetworkAnnouncement, ResourceMonitorAnnouncement, RpcServerAnnouncement,
 };
 use requests::{
     BlockExecutorRequest, BlockProposerRequest, BlockValidationRequest, ChainspecLoaderRequest,
     ConsensusRequest, ContractRuntimeRequest, FetcherRequest, MetricsRequest, NetworkInfoRequest,
-    NetworkRequest, StorageRequest,
+    NetworkRequest, StorageRequest, SyncProgressRequest,
 };
 
+/// How often to check whether termination of the node has been requested.
+const TERMINATION_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 /// A pinned, boxed future that produces one or more events.
 pub type Effect<Ev> = BoxFuture<'static, Multiple<Ev>>;
 
@@ -407,6 +422,13 @@ impl<REv> EffectBuilder<REv> {
         Instant::now() - then
     }
 
+    /// Completes once termination of the node has been requested, e.g. by a SIGTERM.
+    pub(crate) async fn termination_requested(self) {
+        while !crate::TERMINATION_REQUESTED.load(Ordering::SeqCst) {
+            tokio::time::delay_for(TERMINATION_POLL_INTERVAL).await;
+        }
+    }
+
     /// Retrieve a snapshot of the nodes current metrics formatted as string.
     ///
     /// If an error occurred producing the metrics, `None` is returned.
@@ -547,6 +569,22 @@ impl<REv> EffectBuilder<REv> {
             .await;
     }
 
+    /// Announces that the drift of our clock from the network's has crossed the configured bound.
+    pub(crate) async fn announce_clock_drift<I, P>(self, median_offset: i64, exceeds_bound: bool)
+    where
+        REv: From<NetworkAnnouncement<I, P>>,
+    {
+        self.0
+            .schedule(
+                NetworkAnnouncement::ClockDrift {
+                    median_offset,
+                    exceeds_bound,
+                },
+                QueueKind::Regular,
+            )
+            .await;
+    }
+
     /// Announces that a gossiper has received a new item, where the item's ID is the complete item.
     pub(crate) async fn announce_complete_item_received_via_gossip<T: Item>(self, item: T::Id)
     where
@@ -578,6 +616,16 @@ impl<REv> EffectBuilder<REv> {
             .await;
     }
 
+    /// Announces that the HTTP API server has received a command from the node operator.
+    pub(crate) async fn announce_admin_command(self, command: AdminCommand)
+    where
+        REv: From<RpcServerAnnouncement>,
+    {
+        self.0
+            .schedule(RpcServerAnnouncement::AdminCommand(command), QueueKind::Api)
+            .await;
+    }
+
     /// Announces that a deploy not previously stored has now been accepted and stored.
     pub(crate) fn announce_new_deploy_accepted<I>(
         self,
@@ -608,6 +656,29 @@ impl<REv> EffectBuilder<REv> {
         )
     }
 
+    /// Announces that a deploy of the finalized block at the given height has been executed.
+    pub(crate) async fn announce_deploy_processed(
+        self,
+        deploy_hash: DeployHash,
+        deploy_header: DeployHeader,
+        block_height: u64,
+        execution_result: ExecutionResult,
+    ) where
+        REv: From<BlockExecutorAnnouncement>,
+    {
+        self.0
+            .schedule(
+                BlockExecutorAnnouncement::DeployProcessed {
+                    deploy_hash,
+                    deploy_header: Box::new(deploy_header),
+                    block_height,
+                    execution_result: Box::new(execution_result),
+                },
+                QueueKind::Regular,
+            )
+            .await
+    }
+
     /// Announce new block has been created.
     pub(crate) async fn announce_linear_chain_block(
         self,
@@ -627,13 +698,23 @@ impl<REv> EffectBuilder<REv> {
             .await
     }
 
-    /// Puts the given block into the linear block store.
-    pub(crate) async fn put_block_to_storage(self, block: Box<Block>) -> bool
+    /// Appends the given finality signatures to the proofs of a block in the linear block store.
+    ///
+    /// Returns false if the block is not stored.
+    pub(crate) async fn append_block_proofs_to_storage(
+        self,
+        block_hash: BlockHash,
+        proofs: Vec<Signature>,
+    ) -> bool
     where
         REv: From<StorageRequest>,
     {
         self.make_request(
-            |responder| StorageRequest::PutBlock { block, responder },
+            |responder| StorageRequest::AppendBlockProofs {
+                block_hash,
+                proofs,
+                responder,
+            },
             QueueKind::Regular,
         )
         .await
@@ -666,6 +747,81 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Requests the switch block of the given era.
+    pub(crate) async fn get_switch_block_at_era_id(self, era_id: EraId) -> Option<Block>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetSwitchBlockAtEraId { era_id, responder },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
+    /// Requests the switch block of the most recently ended era.
+    pub(crate) async fn get_highest_switch_block(self) -> Option<Block>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetHighestSwitchBlock { responder },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
+    /// Requests the height of the highest block on the linear chain with the given state root
+    /// hash.
+    pub(crate) async fn get_block_height_by_state_root_hash(
+        self,
+        state_root_hash: Digest,
+    ) -> Option<u64>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetBlockHeightByStateRootHash {
+                state_root_hash,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
+    /// Requests the contiguous run of blocks starting at `start_height`, limited to `max_count`.
+    pub(crate) async fn get_blocks_in_height_range(
+        self,
+        start_height: u64,
+        max_count: u64,
+    ) -> Vec<Block>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetBlocksInHeightRange {
+                start_height,
+                max_count,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
+    /// Requests the blocks at `height` which conflict with the linear chain.
+    pub(crate) async fn get_alternative_blocks_at_height(self, height: u64) -> Vec<Block>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetAlternativeBlocksAtHeight { height, responder },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     /// Requests the highest block.
     pub(crate) async fn get_highest_block(self) -> Option<Block>
     where
@@ -708,18 +864,19 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
-    /// Stores the given execution results for the deploys in the given block in the linear block
-    /// store.
-    pub(crate) async fn put_execution_results_to_storage(
+    /// Atomically stores the given block and the execution results for its deploys in the linear
+    /// block store.
+    pub(crate) async fn put_block_and_execution_results_to_storage(
         self,
-        block_hash: BlockHash,
+        block: Box<Block>,
         execution_results: HashMap<DeployHash, ExecutionResult>,
-    ) where
+    ) -> bool
+    where
         REv: From<StorageRequest>,
     {
         self.make_request(
-            |responder| StorageRequest::PutExecutionResults {
-                block_hash,
+            |responder| StorageRequest::PutBlockAndExecutionResults {
+                block,
                 execution_results,
                 responder,
             },
@@ -746,6 +903,98 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Gets at most `count` entries of the write history of `key` from storage, newest first,
+    /// skipping the `offset` newest ones, along with the total number of entries held for the key.
+    pub(crate) async fn get_key_history_from_storage(
+        self,
+        key: Key,
+        offset: u64,
+        count: u64,
+    ) -> (u64, Vec<KeyHistoryEntry>)
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetKeyHistory {
+                key,
+                offset,
+                count,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
+    /// Gets at most `count` hashes of the deploys involving `account_hash` from storage, newest
+    /// first, skipping the `offset` newest ones, along with the total number of deploys held for
+    /// the account.
+    pub(crate) async fn get_account_deploys_from_storage(
+        self,
+        account_hash: AccountHash,
+        offset: u64,
+        count: u64,
+    ) -> (u64, Vec<DeployHash>)
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetAccountDeploys {
+                account_hash,
+                offset,
+                count,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
+    /// Gets the block on the linear chain including the given deploy from storage.
+    pub(crate) async fn get_block_by_deploy_hash_from_storage(
+        self,
+        deploy_hash: DeployHash,
+    ) -> Option<Block>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetBlockByDeployHash {
+                deploy_hash,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
+    /// Gets the successful native transfers to `target` executed in blocks at or above
+    /// `since_block_height` from storage, oldest first, along with the height of the block from
+    /// which to resume if transfers remain.
+    ///
+    /// At most `max_count` transfers are returned, unless more are needed to complete the transfers
+    /// of the last block.
+    pub(crate) async fn get_transfers_to_from_storage(
+        self,
+        target: Key,
+        since_block_height: u64,
+        max_count: u64,
+    ) -> (Vec<TransferEntry>, Option<u64>)
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetTransfersTo {
+                target,
+                since_block_height,
+                max_count,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     /// Gets the requested deploy using the `DeployFetcher`.
     pub(crate) async fn fetch_deploy<I>(
         self,
@@ -809,6 +1058,19 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Checks whether the block proposer has room for more deploys, returning the interval after
+    /// which to retry if not.
+    pub(crate) async fn check_deploy_buffer_capacity(self) -> Result<(), BufferFull>
+    where
+        REv: From<BlockProposerRequest>,
+    {
+        self.make_request(
+            |responder| BlockProposerRequest::CheckCapacity { responder },
+            QueueKind::Api,
+        )
+        .await
+    }
+
     /// Passes the timestamp of a future block for which deploys are to be proposed.
     // TODO: The input `BlockContext` will probably be a different type than the context in the
     //       return value in the future.
@@ -913,6 +1175,26 @@ impl<REv> EffectBuilder<REv> {
             .await
     }
 
+    /// Announces an observation of the finality monitor.
+    pub(crate) async fn announce_finality_observation(
+        self,
+        announcement: FinalityMonitorAnnouncement,
+    ) where
+        REv: From<FinalityMonitorAnnouncement>,
+    {
+        self.0.schedule(announcement, QueueKind::Regular).await
+    }
+
+    /// Announces an observation of the resource monitor.
+    pub(crate) async fn announce_resource_observation(
+        self,
+        announcement: ResourceMonitorAnnouncement,
+    ) where
+        REv: From<ResourceMonitorAnnouncement>,
+    {
+        self.0.schedule(announcement, QueueKind::Regular).await
+    }
+
     /// The linear chain has stored a newly-created block.
     pub(crate) async fn announce_block_added(self, block_hash: BlockHash, block_header: BlockHeader)
     where
@@ -983,6 +1265,15 @@ impl<REv> EffectBuilder<REv> {
             .await
     }
 
+    /// Gets the node's progress in synchronizing the linear chain.
+    pub(crate) async fn get_sync_progress(self) -> SyncProgress
+    where
+        REv: From<SyncProgressRequest> + Send,
+    {
+        self.make_request(SyncProgressRequest::GetSyncProgress, QueueKind::Api)
+            .await
+    }
+
     /// Requests an execution of deploys using Contract Runtime.
     pub(crate) async fn request_execute(
         self,
@@ -1039,6 +1330,37 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Requests a batch of queries against the same state root be executed on the Contract Runtime
+    /// component.
+    pub(crate) async fn query_global_state_batch(
+        self,
+        batch: QueryRequestBatch,
+    ) -> Result<QueryBatchResult, engine_state::Error>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::QueryBatch { batch, responder },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
+    /// Requests a page of the keys matching a prefix from the Contract Runtime component.
+    pub(crate) async fn get_keys_with_prefix(
+        self,
+        request: KeysWithPrefixRequest,
+    ) -> Result<KeysWithPrefixResult, engine_state::Error>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::GetKeysWithPrefix { request, responder },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     /// Requests a query be executed on the Contract Runtime component.
     pub(crate) async fn get_balance(
         self,
@@ -1111,6 +1433,60 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Returns the auction contract's queue of unbonded amounts as known from `state_root_hash`, or
+    /// `None` if it could not be read.
+    ///
+    /// This operation is read only.
+    pub(crate) async fn get_unbonding_purses(
+        self,
+        state_root_hash: Digest,
+    ) -> Option<UnbondingPurses>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        let protocol_data = self
+            .get_protocol_data(ProtocolVersion::V1_0_0)
+            .await
+            .ok()
+            .flatten()?;
+        let query_request = QueryRequest::new(
+            state_root_hash.into(),
+            protocol_data.auction().into(),
+            vec![UNBONDING_PURSES_KEY.to_string()],
+        );
+        match self.query_global_state(query_request).await {
+            Ok(QueryResult::Success { value, .. }) => value.as_cl_value()?.clone().into_t().ok(),
+            _ => None,
+        }
+    }
+
+    /// Executes a single deploy against the given state root hash, returning the summary of the
+    /// execution result, and the breakdown of the gas consumed if `profile_gas` is set.
+    ///
+    /// This operation is read only: the effects of the execution are never committed.
+    pub(crate) async fn estimate_deploy_cost(
+        self,
+        state_root_hash: Digest,
+        block_time: Timestamp,
+        deploy: Box<Deploy>,
+        profile_gas: bool,
+    ) -> Result<(ExecutionResult, Option<GasProfile>), engine_state::RootNotFound>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::EstimateDeployCost {
+                state_root_hash,
+                block_time,
+                deploy,
+                profile_gas,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     /// Runs the end of era step using the system smart contract.
     pub(crate) async fn run_step(
         self,
@@ -1129,6 +1505,57 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Reads the serialized tries under the given hashes from global state.
+    pub(crate) async fn read_tries(
+        self,
+        trie_keys: Vec<Digest>,
+    ) -> Result<Vec<Option<Vec<u8>>>, StorageLmdbError>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::ReadTries {
+                trie_keys,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
+    /// Returns those of the given trie hashes which are absent from global state.
+    pub(crate) async fn find_missing_tries(
+        self,
+        trie_keys: Vec<Digest>,
+    ) -> Result<Vec<Digest>, StorageLmdbError>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::FindMissingTries {
+                trie_keys,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
+    /// Writes the given tries to global state, under the given hashes.
+    pub(crate) async fn write_tries(
+        self,
+        tries: Vec<(Digest, Trie<Key, StoredValue>)>,
+    ) -> Result<(), StorageLmdbError>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::WriteTries { tries, responder },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     /// Gets the set of validators, the booking block and the key block for a new era
     pub(crate) async fn create_new_era(
         self,
@@ -1150,7 +1577,13 @@ impl<REv> EffectBuilder<REv> {
     }
 
     /// Request consensus to sign a block from the linear chain and possibly start a new era.
-    pub(crate) async fn handle_linear_chain_block(self, block_header: BlockHeader) -> Signature
+    ///
+    /// Returns the signature along with the public key of the signer, or `None` if the block could
+    /// not be signed.
+    pub(crate) async fn handle_linear_chain_block(
+        self,
+        block_header: BlockHeader,
+    ) -> Option<(PublicKey, Signature)>
     where
         REv: From<ConsensusRequest>,
     {
@@ -1160,6 +1593,41 @@ impl<REv> EffectBuilder<REv> {
         )
         .await
     }
+
+    /// Request consensus to sign a serialized `SignedStatusPayload` with the node's current signing
+    /// key.
+    ///
+    /// Returns the signature along with the public key of the signer, or `None` if the payload
+    /// could not be signed.
+    pub(crate) async fn sign_status(self, payload: Vec<u8>) -> Option<(PublicKey, Signature)>
+    where
+        REv: From<ConsensusRequest>,
+    {
+        self.make_request(
+            |responder| ConsensusRequest::SignStatus(payload, responder),
+            QueueKind::Api,
+        )
+        .await
+    }
+
+    /// Request consensus to sign a receipt for a deploy accepted at `timestamp` with the node's
+    /// current signing key.
+    ///
+    /// Returns `None` if the receipt could not be signed.
+    pub(crate) async fn sign_deploy_receipt(
+        self,
+        deploy_hash: DeployHash,
+        timestamp: Timestamp,
+    ) -> Option<DeployReceipt>
+    where
+        REv: From<ConsensusRequest>,
+    {
+        self.make_request(
+            |responder| ConsensusRequest::SignDeployReceipt(deploy_hash, timestamp, responder),
+            QueueKind::Api,
+        )
+        .await
+    }
 }
 
 /// Construct a fatal error effect.
@@ -9,10 +9,11 @@ use std::{
 };
 
 use crate::{
-    components::small_network::GossipedAddress,
+    components::{consensus::EraId, resource_monitor::Resource, small_network::GossipedAddress},
+    rpcs::admin::AdminCommand,
     types::{
         json_compatibility::ExecutionResult, Block, BlockHash, BlockHeader, Deploy, DeployHash,
-        DeployHeader, FinalizedBlock, Item, ProtoBlock,
+        DeployHeader, FinalizedBlock, Item, ProtoBlock, ProtoBlockHash, TimeDiff, Timestamp,
     },
     utils::Source,
 };
@@ -36,6 +37,14 @@ pub enum NetworkAnnouncement<I, P> {
     ///                 not rely on or use this for anything without asking anyone that has written
     ///                 this section of the code first!
     NewPeer(I),
+    /// The drift of our clock from the median of peers' clocks has crossed the configured bound,
+    /// in either direction.
+    ClockDrift {
+        /// The median estimated offset in milliseconds of our clock from peers' clocks.
+        median_offset: i64,
+        /// Whether the drift now exceeds the configured bound.
+        exceeds_bound: bool,
+    },
 }
 
 impl<I, P> Display for NetworkAnnouncement<I, P>
@@ -52,6 +61,14 @@ where
             NetworkAnnouncement::NewPeer(id) => {
                 write!(formatter, "new peer connection established to {}", id)
             }
+            NetworkAnnouncement::ClockDrift {
+                median_offset,
+                exceeds_bound,
+            } => write!(
+                formatter,
+                "clock drift of {} ms, exceeds bound: {}",
+                median_offset, exceeds_bound
+            ),
         }
     }
 }
@@ -65,6 +82,8 @@ pub enum RpcServerAnnouncement {
         /// The received deploy.
         deploy: Box<Deploy>,
     },
+    /// A command from the node operator received.
+    AdminCommand(AdminCommand),
 }
 
 impl Display for RpcServerAnnouncement {
@@ -73,6 +92,9 @@ impl Display for RpcServerAnnouncement {
             RpcServerAnnouncement::DeployReceived { deploy } => {
                 write!(formatter, "api server received {}", deploy.id())
             }
+            RpcServerAnnouncement::AdminCommand(command) => {
+                write!(formatter, "api server received admin command: {}", command)
+            }
         }
     }
 }
@@ -151,6 +173,17 @@ impl Display for ConsensusAnnouncement {
 /// A BlockExecutor announcement.
 #[derive(Debug)]
 pub enum BlockExecutorAnnouncement {
+    /// A deploy of a finalized block has been executed, ahead of the block itself being created.
+    DeployProcessed {
+        /// The hash of the deploy.
+        deploy_hash: DeployHash,
+        /// The header of the deploy.
+        deploy_header: Box<DeployHeader>,
+        /// The height of the block containing the deploy.
+        block_height: u64,
+        /// The result of executing the deploy.
+        execution_result: Box<ExecutionResult>,
+    },
     /// A new block from the linear chain was produced.
     LinearChainBlock {
         /// The block.
@@ -163,6 +196,15 @@ pub enum BlockExecutorAnnouncement {
 impl Display for BlockExecutorAnnouncement {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
+            BlockExecutorAnnouncement::DeployProcessed {
+                deploy_hash,
+                block_height,
+                ..
+            } => write!(
+                f,
+                "processed deploy {} of block at height {}",
+                deploy_hash, block_height
+            ),
             BlockExecutorAnnouncement::LinearChainBlock { block, .. } => {
                 write!(f, "created linear chain block {}", block.hash())
             }
@@ -206,3 +248,96 @@ impl Display for LinearChainAnnouncement {
         }
     }
 }
+
+/// A finality monitor announcement.
+#[derive(Debug)]
+pub enum FinalityMonitorAnnouncement {
+    /// The time elapsed since the timestamp of the most recently finalized block exceeded the
+    /// alert threshold.
+    FinalizationLagExceeded {
+        /// The height of the most recently finalized block.
+        block_height: u64,
+        /// The timestamp of the most recently finalized block.
+        block_timestamp: Timestamp,
+        /// The time elapsed since the block's timestamp.
+        lag: TimeDiff,
+    },
+    /// The first block of a new era has been finalized.
+    EraTransition {
+        /// The new era.
+        era_id: EraId,
+        /// The time between the switch block of the previous era and the first block of the new
+        /// era.
+        duration: TimeDiff,
+    },
+    /// A proto block has been proposed again after having been proposed before.
+    BlockReproposed(ProtoBlockHash),
+}
+
+impl Display for FinalityMonitorAnnouncement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FinalityMonitorAnnouncement::FinalizationLagExceeded {
+                block_height, lag, ..
+            } => write!(
+                f,
+                "finalization lag of {} exceeded threshold at block height {}",
+                lag, block_height
+            ),
+            FinalityMonitorAnnouncement::EraTransition { era_id, duration } => {
+                write!(f, "transition to era {} took {}", era_id.0, duration)
+            }
+            FinalityMonitorAnnouncement::BlockReproposed(proto_block_hash) => {
+                write!(f, "proto block {} reproposed", proto_block_hash)
+            }
+        }
+    }
+}
+
+/// A resource monitor announcement.
+#[derive(Debug)]
+pub enum ResourceMonitorAnnouncement {
+    /// The usage of a resource crossed the alert threshold.
+    ThresholdCrossed {
+        /// The resource.
+        resource: Resource,
+        /// The usage of the resource in percent, or for disk space, the free space in percent.
+        percent: u8,
+        /// The threshold in percent.
+        threshold_percent: u8,
+    },
+    /// The free space of the disk holding the storage directory dropped below the pause threshold:
+    /// Block execution and deploy acceptance should be paused.
+    WritesPaused {
+        /// The free disk space in percent.
+        free_percent: u8,
+    },
+    /// The free space of the disk holding the storage directory reached the resume threshold:
+    /// Block execution and deploy acceptance should be resumed.
+    WritesResumed {
+        /// The free disk space in percent.
+        free_percent: u8,
+    },
+}
+
+impl Display for ResourceMonitorAnnouncement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ResourceMonitorAnnouncement::ThresholdCrossed {
+                resource,
+                percent,
+                threshold_percent,
+            } => write!(
+                f,
+                "{} at {}% crossed threshold of {}%",
+                resource, percent, threshold_percent
+            ),
+            ResourceMonitorAnnouncement::WritesPaused { free_percent } => {
+                write!(f, "writes paused at {}% free disk space", free_percent)
+            }
+            ResourceMonitorAnnouncement::WritesResumed { free_percent } => {
+                write!(f, "writes resumed at {}% free disk space", free_percent)
+            }
+        }
+    }
+}
@@ -22,14 +22,21 @@ use casper_execution_engine::{
         execute_request::ExecuteRequest,
         execution_result::ExecutionResults,
         genesis::GenesisResult,
-        query::{QueryRequest, QueryResult},
+        query::{
+            KeysWithPrefixRequest, KeysWithPrefixResult, QueryBatchResult, QueryRequest,
+            QueryRequestBatch, QueryResult,
+        },
         step::{StepRequest, StepResult},
         upgrade::{UpgradeConfig, UpgradeResult},
     },
-    shared::{additive_map::AdditiveMap, transform::Transform},
-    storage::{global_state::CommitResult, protocol_data::ProtocolData},
+    shared::{additive_map::AdditiveMap, stored_value::StoredValue, transform::Transform},
+    storage::{
+        error::lmdb::Error as StorageLmdbError, global_state::CommitResult,
+        protocol_data::ProtocolData, trie::Trie,
+    },
 };
 use casper_types::{
+    account::AccountHash,
     auction::{EraValidators, ValidatorWeights},
     Key, ProtocolVersion, URef,
 };
@@ -37,16 +44,23 @@ use casper_types::{
 use super::{Multiple, Responder};
 use crate::{
     components::{
+        block_proposer::BufferFull,
         chainspec_loader::ChainspecInfo,
+        consensus::EraId,
         contract_runtime::{EraValidatorsRequest, ValidatorWeightsByEraIdRequest},
+        deploy_acceptor::{DeployAcceptance, DeployViolation, SubmitDeployError},
         fetcher::FetchResult,
     },
-    crypto::{asymmetric_key::Signature, hash::Digest},
-    rpcs::chain::BlockIdentifier,
+    crypto::{
+        asymmetric_key::{PublicKey, Signature},
+        hash::Digest,
+    },
+    rpcs::{admin::AdminCommand, chain::BlockIdentifier},
     types::{
-        json_compatibility::ExecutionResult, Block as LinearBlock, Block, BlockHash, BlockHeader,
-        Deploy, DeployHash, DeployHeader, DeployMetadata, FinalizedBlock, Item, ProtoBlockHash,
-        StatusFeed, Timestamp,
+        json_compatibility::{ExecutionResult, GasProfile},
+        Block as LinearBlock, Block, BlockHash, BlockHeader, Deploy, DeployHash, DeployHeader,
+        DeployMetadata, DeployReceipt, FinalizedBlock, HistoricalStateWindow, Item,
+        KeyHistoryEntry, ProtoBlockHash, StatusFeed, SyncProgress, Timestamp, TransferEntry,
     },
     utils::DisplayIter,
     Chainspec,
@@ -194,6 +208,30 @@ pub enum StorageRequest {
         /// attempt or false if it was previously stored.
         responder: Responder<bool>,
     },
+    /// Store given block along with the execution results of its deploys.
+    ///
+    /// The block, the execution results and the resulting index updates are committed atomically.
+    PutBlockAndExecutionResults {
+        /// Block to be stored.
+        block: Box<Block>,
+        /// Mapping of deploys to execution results of the block.
+        execution_results: HashMap<DeployHash, ExecutionResult>,
+        /// Responder to call with the result.  Returns true if the block was stored on this
+        /// attempt or false if it was previously stored.
+        responder: Responder<bool>,
+    },
+    /// Append finality signatures to the proofs of a stored block.
+    ///
+    /// Signatures the block already holds are skipped, and all of them are written at once.
+    AppendBlockProofs {
+        /// Hash of the block the signatures are for.
+        block_hash: BlockHash,
+        /// Signatures to be appended.
+        proofs: Vec<Signature>,
+        /// Responder to call with the result.  Returns false if the block doesn't exist in local
+        /// storage.
+        responder: Responder<bool>,
+    },
     /// Retrieve block with given hash.
     GetBlock {
         /// Hash of block to be retrieved.
@@ -209,11 +247,52 @@ pub enum StorageRequest {
         /// Responder.
         responder: Responder<Option<Block>>,
     },
+    /// Retrieve the contiguous run of blocks starting at `start_height`, in ascending order.
+    ///
+    /// At most `max_count` blocks are returned. The run ends early at the first height for which
+    /// no block is stored locally.
+    GetBlocksInHeightRange {
+        /// Height of the first block.
+        start_height: BlockHeight,
+        /// Maximum number of blocks to retrieve.
+        max_count: u64,
+        /// Responder.
+        responder: Responder<Vec<Block>>,
+    },
+    /// Retrieve the blocks at the given height which conflict with the linear chain.
+    GetAlternativeBlocksAtHeight {
+        /// Height of the blocks.
+        height: BlockHeight,
+        /// Responder.
+        responder: Responder<Vec<Block>>,
+    },
     /// Retrieve highest block.
     GetHighestBlock {
         /// Responder.
         responder: Responder<Option<Block>>,
     },
+    /// Retrieve the switch block, i.e. the last block, of the given era.
+    GetSwitchBlockAtEraId {
+        /// The era.
+        era_id: EraId,
+        /// Responder to call with the result.  Returns `None` if the era has not ended yet or its
+        /// switch block doesn't exist in local storage.
+        responder: Responder<Option<Block>>,
+    },
+    /// Retrieve the switch block of the most recent era which has ended.
+    GetHighestSwitchBlock {
+        /// Responder.
+        responder: Responder<Option<Block>>,
+    },
+    /// Retrieve the height of the highest block on the linear chain with the given state root
+    /// hash.
+    GetBlockHeightByStateRootHash {
+        /// The state root hash.
+        state_root_hash: Digest,
+        /// Responder to call with the result.  Returns `None` if no stored block has the state
+        /// root hash.
+        responder: Responder<Option<u64>>,
+    },
     /// Retrieve block header with given hash.
     GetBlockHeader {
         /// Hash of block to get header of.
@@ -266,6 +345,54 @@ pub enum StorageRequest {
         /// Responder to call with the results.
         responder: Responder<Option<(Deploy, DeployMetadata)>>,
     },
+    /// Retrieve at most `count` entries of the write history of a global state key, newest first,
+    /// skipping the `offset` newest ones.
+    GetKeyHistory {
+        /// The key whose history is to be retrieved.
+        key: Key,
+        /// Number of newest entries to skip.
+        offset: u64,
+        /// Maximum number of entries to retrieve.
+        count: u64,
+        /// Responder to call with the total number of entries held for the key, along with the
+        /// requested entries.
+        responder: Responder<(u64, Vec<KeyHistoryEntry>)>,
+    },
+    /// Retrieve at most `count` hashes of the deploys involving an account, newest first, skipping
+    /// the `offset` newest ones.
+    GetAccountDeploys {
+        /// The account whose deploys are to be retrieved.
+        account_hash: AccountHash,
+        /// Number of newest deploys to skip.
+        offset: u64,
+        /// Maximum number of deploy hashes to retrieve.
+        count: u64,
+        /// Responder to call with the total number of deploys held for the account, along with the
+        /// requested deploy hashes.
+        responder: Responder<(u64, Vec<DeployHash>)>,
+    },
+    /// Retrieve the block on the linear chain including the given deploy.
+    GetBlockByDeployHash {
+        /// The hash of the deploy.
+        deploy_hash: DeployHash,
+        /// Responder to call with the result.  Returns `None` if no block including the deploy is
+        /// known.
+        responder: Responder<Option<Block>>,
+    },
+    /// Retrieve the successful native transfers to an account or purse executed in blocks at or
+    /// above `since_block_height`, oldest first.
+    GetTransfersTo {
+        /// The account or purse whose incoming transfers are to be retrieved.
+        target: Key,
+        /// Height of the earliest block whose transfers are to be retrieved.
+        since_block_height: u64,
+        /// Maximum number of transfers to retrieve, exceeded only to complete the transfers of the
+        /// last block.
+        max_count: u64,
+        /// Responder to call with the requested transfers, along with the height of the block from
+        /// which to resume if transfers remain.
+        responder: Responder<(Vec<TransferEntry>, Option<u64>)>,
+    },
     /// Store given chainspec.
     PutChainspec {
         /// Chainspec.
@@ -286,11 +413,47 @@ impl Display for StorageRequest {
     fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
         match self {
             StorageRequest::PutBlock { block, .. } => write!(formatter, "put {}", block),
+            StorageRequest::PutBlockAndExecutionResults { block, .. } => {
+                write!(formatter, "put {} and its execution results", block)
+            }
+            StorageRequest::AppendBlockProofs {
+                block_hash, proofs, ..
+            } => write!(
+                formatter,
+                "append {} proofs to block {}",
+                proofs.len(),
+                block_hash
+            ),
             StorageRequest::GetBlock { block_hash, .. } => write!(formatter, "get {}", block_hash),
             StorageRequest::GetBlockAtHeight { height, .. } => {
                 write!(formatter, "get block at height {}", height)
             }
+            StorageRequest::GetBlocksInHeightRange {
+                start_height,
+                max_count,
+                ..
+            } => write!(
+                formatter,
+                "get up to {} blocks from height {}",
+                max_count, start_height
+            ),
+            StorageRequest::GetAlternativeBlocksAtHeight { height, .. } => {
+                write!(formatter, "get alternative blocks at height {}", height)
+            }
             StorageRequest::GetHighestBlock { .. } => write!(formatter, "get highest block"),
+            StorageRequest::GetSwitchBlockAtEraId { era_id, .. } => {
+                write!(formatter, "get switch block for {}", era_id)
+            }
+            StorageRequest::GetHighestSwitchBlock { .. } => {
+                write!(formatter, "get highest switch block")
+            }
+            StorageRequest::GetBlockHeightByStateRootHash {
+                state_root_hash, ..
+            } => write!(
+                formatter,
+                "get height of block with state root hash {}",
+                state_root_hash
+            ),
             StorageRequest::GetBlockHeader { block_hash, .. } => {
                 write!(formatter, "get {}", block_hash)
             }
@@ -309,6 +472,40 @@ impl Display for StorageRequest {
             StorageRequest::GetDeployAndMetadata { deploy_hash, .. } => {
                 write!(formatter, "get deploy and metadata for {}", deploy_hash)
             }
+            StorageRequest::GetKeyHistory {
+                key, offset, count, ..
+            } => write!(
+                formatter,
+                "get up to {} history entries of {} from offset {}",
+                count,
+                key.to_formatted_string(),
+                offset
+            ),
+            StorageRequest::GetAccountDeploys {
+                account_hash,
+                offset,
+                count,
+                ..
+            } => write!(
+                formatter,
+                "get up to {} deploys of {} from offset {}",
+                count, account_hash, offset
+            ),
+            StorageRequest::GetBlockByDeployHash { deploy_hash, .. } => {
+                write!(formatter, "get block including {}", deploy_hash)
+            }
+            StorageRequest::GetTransfersTo {
+                target,
+                since_block_height,
+                max_count,
+                ..
+            } => write!(
+                formatter,
+                "get up to {} transfers to {} since height {}",
+                max_count,
+                target.to_formatted_string(),
+                since_block_height
+            ),
             StorageRequest::PutChainspec { chainspec, .. } => write!(
                 formatter,
                 "put chainspec {}",
@@ -334,6 +531,11 @@ pub enum BlockProposerRequest {
         /// Responder to call with the result.
         responder: Responder<HashSet<DeployHash>>,
     },
+    /// Check whether the buffer of pending deploys has room for more.
+    CheckCapacity {
+        /// Responder to call with the result.
+        responder: Responder<Result<(), BufferFull>>,
+    },
 }
 
 impl Display for BlockProposerRequest {
@@ -349,6 +551,7 @@ impl Display for BlockProposerRequest {
                 current_instant,
                 past_blocks.len()
             ),
+            BlockProposerRequest::CheckCapacity { .. } => write!(formatter, "check capacity"),
         }
     }
 }
@@ -360,12 +563,26 @@ impl Display for BlockProposerRequest {
 #[derive(Debug)]
 #[must_use]
 pub enum RpcRequest<I> {
-    /// Submit a deploy to be announced.
+    /// Submit a deploy to be announced, unless the block proposer is at capacity or the deploy is
+    /// unlikely to be executed as intended due to its timing relative to the current era.
+    ///
+    /// If `idempotency_key` is given and a deploy was already accepted with the same key, the
+    /// original acceptance is returned rather than the deploy being submitted again.
     SubmitDeploy {
         /// The deploy to be announced.
         deploy: Box<Deploy>,
-        /// Responder to call.
-        responder: Responder<()>,
+        /// The client-generated key identifying the submission, if any.
+        idempotency_key: Option<String>,
+        /// Responder to call with the result.
+        responder: Responder<Result<DeployAcceptance, SubmitDeployError>>,
+    },
+    /// Check a deploy against all the checks applied on submission, without submitting it.
+    CheckDeploy {
+        /// The deploy to be checked.
+        deploy: Box<Deploy>,
+        /// Responder to call with every violation found, or `None` if the checks could not be
+        /// performed.
+        responder: Responder<Option<Vec<DeployViolation>>>,
     },
     /// If `maybe_hash` is `Some`, return the specified block if it exists, else `None`.  If
     /// `maybe_hash` is `None`, return the latest block.
@@ -375,6 +592,37 @@ pub enum RpcRequest<I> {
         /// Responder to call with the result.
         responder: Responder<Option<LinearBlock>>,
     },
+    /// Return the contiguous run of at most `max_count` blocks starting at `start_height`.
+    GetBlocks {
+        /// The height of the first block to be retrieved.
+        start_height: u64,
+        /// The maximum number of blocks to be retrieved.
+        max_count: u64,
+        /// Responder to call with the result.
+        responder: Responder<Vec<LinearBlock>>,
+    },
+    /// Return the block on the linear chain including the deploy, if known.
+    GetBlockByDeploy {
+        /// The hash of the deploy.
+        deploy_hash: DeployHash,
+        /// Responder to call with the result.
+        responder: Responder<Option<LinearBlock>>,
+    },
+    /// Return the blocks at `height` which conflict with the linear chain.
+    GetAlternativeBlocks {
+        /// The height of the blocks to be retrieved.
+        height: u64,
+        /// Responder to call with the result.
+        responder: Responder<Vec<LinearBlock>>,
+    },
+    /// If `maybe_era_id` is `Some`, return the switch block of the specified era if it exists,
+    /// else `None`.  If `maybe_era_id` is `None`, return the most recent switch block.
+    GetSwitchBlock {
+        /// The era whose switch block is to be retrieved.
+        maybe_era_id: Option<EraId>,
+        /// Responder to call with the result.
+        responder: Responder<Option<LinearBlock>>,
+    },
     /// Query the global state at the given root hash.
     QueryGlobalState {
         /// The state root hash.
@@ -386,6 +634,38 @@ pub enum RpcRequest<I> {
         /// Responder to call with the result.
         responder: Responder<Result<QueryResult, engine_state::Error>>,
     },
+    /// Run several queries against the global state at the given root hash in one session.
+    QueryGlobalStateBatch {
+        /// The state root hash.
+        state_root_hash: Digest,
+        /// The base key and the path components starting from it of each query.
+        queries: Vec<(Key, Vec<String>)>,
+        /// Responder to call with the result.
+        responder: Responder<Result<QueryBatchResult, engine_state::Error>>,
+    },
+    /// Check whether the global state at the given root hash may be queried, i.e. whether it is
+    /// the state root hash of a block within the configured historical state window.  Responds
+    /// with the window if not.
+    CheckHistoricalState {
+        /// The state root hash.
+        state_root_hash: Digest,
+        /// Responder to call with the result.
+        responder: Responder<Result<(), HistoricalStateWindow>>,
+    },
+    /// Return a page of the keys in the global state at the given root hash whose serialized
+    /// representation starts with the given prefix, each with a proof of its inclusion.
+    GetKeysWithPrefix {
+        /// The state root hash.
+        state_root_hash: Digest,
+        /// The prefix of the serialized keys.
+        prefix: Vec<u8>,
+        /// The number of matching keys to skip.
+        offset: u64,
+        /// The maximum number of keys to return.
+        count: u64,
+        /// Responder to call with the result.
+        responder: Responder<Result<KeysWithPrefixResult, engine_state::Error>>,
+    },
     /// Query the global state at the given root hash.
     QueryEraValidators {
         /// The global state hash.
@@ -418,11 +698,74 @@ pub enum RpcRequest<I> {
         /// Responder to call with the result.
         responder: Responder<Option<(Deploy, DeployMetadata)>>,
     },
+    /// Return at most `count` entries of the write history of a global state key, newest first,
+    /// skipping the `offset` newest ones.
+    GetKeyHistory {
+        /// The key whose history is to be retrieved.
+        key: Key,
+        /// Number of newest entries to skip.
+        offset: u64,
+        /// Maximum number of entries to retrieve.
+        count: u64,
+        /// Responder to call with the total number of entries held for the key, along with the
+        /// requested entries.
+        responder: Responder<(u64, Vec<KeyHistoryEntry>)>,
+    },
+    /// Return at most `count` hashes of the deploys involving an account, newest first, skipping
+    /// the `offset` newest ones.
+    GetAccountDeploys {
+        /// The account whose deploys are to be retrieved.
+        account_hash: AccountHash,
+        /// Number of newest deploys to skip.
+        offset: u64,
+        /// Maximum number of deploy hashes to retrieve.
+        count: u64,
+        /// Responder to call with the total number of deploys held for the account, along with the
+        /// requested deploy hashes.
+        responder: Responder<(u64, Vec<DeployHash>)>,
+    },
+    /// Return the successful native transfers to an account or purse executed in blocks at or
+    /// above `since_block_height`, oldest first.
+    GetTransfersTo {
+        /// The account or purse whose incoming transfers are to be retrieved.
+        target: Key,
+        /// Height of the earliest block whose transfers are to be retrieved.
+        since_block_height: u64,
+        /// Maximum number of transfers to retrieve, exceeded only to complete the transfers of the
+        /// last block.
+        max_count: u64,
+        /// Responder to call with the requested transfers, along with the height of the block from
+        /// which to resume if transfers remain.
+        responder: Responder<(Vec<TransferEntry>, Option<u64>)>,
+    },
     /// Return the connected peers.
     GetPeers {
         /// Responder to call with the result.
         responder: Responder<HashMap<I, SocketAddr>>,
     },
+    /// Submit a command from the node operator to be announced.
+    AdminCommand {
+        /// The command to be announced.
+        command: AdminCommand,
+        /// Responder to call.
+        responder: Responder<()>,
+    },
+    /// Execute a deploy against the state of the latest block, without committing the effects, and
+    /// return the hash of that block along with the execution result, or `None` if there is no
+    /// block yet.
+    EstimateDeployCost {
+        /// The deploy to execute.
+        deploy: Box<Deploy>,
+        /// Whether to break the gas consumed down by host function and opcode category.
+        profile_gas: bool,
+        /// Responder to call with the result.
+        responder: Responder<
+            Option<(
+                BlockHash,
+                Result<(ExecutionResult, Option<GasProfile>), engine_state::RootNotFound>,
+            )>,
+        >,
+    },
     /// Return string formatted status or `None` if an error occurred.
     GetStatus {
         /// Responder to call with the result.
@@ -433,12 +776,44 @@ pub enum RpcRequest<I> {
         /// Responder to call with the result.
         responder: Responder<Option<String>>,
     },
+    /// Return the chainspec, or `None` if it is not known.
+    GetChainspec {
+        /// Responder to call with the result.
+        responder: Responder<Option<Arc<Chainspec>>>,
+    },
+    /// Return the accounts whose balances are excluded from the circulating supply.
+    GetCirculatingSupplyExcludedAccounts {
+        /// Responder to call with the result.
+        responder: Responder<Vec<PublicKey>>,
+    },
+    /// Sign a serialized status payload with the node's current signing key.
+    SignStatus {
+        /// The serialized `SignedStatusPayload`.
+        payload: Vec<u8>,
+        /// Responder to call with the signer's public key and the signature.
+        responder: Responder<Option<(PublicKey, Signature)>>,
+    },
+    /// Sign and retain a receipt for a deploy accepted from a client.
+    SignDeployReceipt {
+        /// The hash of the accepted deploy.
+        deploy_hash: DeployHash,
+        /// Responder to call with the receipt.
+        responder: Responder<Option<DeployReceipt>>,
+    },
+    /// Return a retained receipt for a deploy accepted from a client.
+    GetDeployReceipt {
+        /// The hash of the deploy.
+        deploy_hash: DeployHash,
+        /// Responder to call with the receipt.
+        responder: Responder<Option<DeployReceipt>>,
+    },
 }
 
 impl<I> Display for RpcRequest<I> {
     fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
         match self {
             RpcRequest::SubmitDeploy { deploy, .. } => write!(formatter, "submit {}", *deploy),
+            RpcRequest::CheckDeploy { deploy, .. } => write!(formatter, "check {}", *deploy),
             RpcRequest::GetBlock {
                 maybe_id: Some(BlockIdentifier::Hash(hash)),
                 ..
@@ -447,7 +822,33 @@ impl<I> Display for RpcRequest<I> {
                 maybe_id: Some(BlockIdentifier::Height(height)),
                 ..
             } => write!(formatter, "get {}", height),
+            RpcRequest::GetBlock {
+                maybe_id: Some(block_identifier),
+                ..
+            } => write!(formatter, "get {}", block_identifier),
             RpcRequest::GetBlock { maybe_id: None, .. } => write!(formatter, "get latest block"),
+            RpcRequest::GetBlocks {
+                start_height,
+                max_count,
+                ..
+            } => write!(
+                formatter,
+                "get up to {} blocks from height {}",
+                max_count, start_height
+            ),
+            RpcRequest::GetBlockByDeploy { deploy_hash, .. } => {
+                write!(formatter, "get block including {}", deploy_hash)
+            }
+            RpcRequest::GetAlternativeBlocks { height, .. } => {
+                write!(formatter, "get alternative blocks at height {}", height)
+            }
+            RpcRequest::GetSwitchBlock {
+                maybe_era_id: Some(era_id),
+                ..
+            } => write!(formatter, "get switch block for {}", era_id),
+            RpcRequest::GetSwitchBlock {
+                maybe_era_id: None, ..
+            } => write!(formatter, "get latest switch block"),
             RpcRequest::QueryProtocolData {
                 protocol_version, ..
             } => write!(formatter, "protocol_version {}", protocol_version),
@@ -461,6 +862,31 @@ impl<I> Display for RpcRequest<I> {
                 "query {}, base_key: {}, path: {:?}",
                 state_root_hash, base_key, path
             ),
+            RpcRequest::QueryGlobalStateBatch {
+                state_root_hash,
+                queries,
+                ..
+            } => write!(
+                formatter,
+                "query batch of {} against {}",
+                queries.len(),
+                state_root_hash
+            ),
+            RpcRequest::CheckHistoricalState {
+                state_root_hash, ..
+            } => write!(formatter, "check historical state {}", state_root_hash),
+            RpcRequest::GetKeysWithPrefix {
+                state_root_hash,
+                prefix,
+                offset,
+                ..
+            } => write!(
+                formatter,
+                "get keys with prefix {} at {} from offset {}",
+                hex::encode(prefix),
+                state_root_hash,
+                offset
+            ),
             RpcRequest::QueryEraValidators {
                 state_root_hash, ..
             } => write!(formatter, "auction {}", state_root_hash),
@@ -474,9 +900,51 @@ impl<I> Display for RpcRequest<I> {
                 state_root_hash, purse_uref
             ),
             RpcRequest::GetDeploy { hash, .. } => write!(formatter, "get {}", hash),
+            RpcRequest::GetKeyHistory { key, offset, .. } => write!(
+                formatter,
+                "get history of {} from offset {}",
+                key.to_formatted_string(),
+                offset
+            ),
+            RpcRequest::EstimateDeployCost { deploy, .. } => {
+                write!(formatter, "estimate cost of {}", deploy.id())
+            }
+            RpcRequest::GetAccountDeploys {
+                account_hash,
+                offset,
+                ..
+            } => write!(
+                formatter,
+                "get deploys of {} from offset {}",
+                account_hash, offset
+            ),
+            RpcRequest::GetTransfersTo {
+                target,
+                since_block_height,
+                ..
+            } => write!(
+                formatter,
+                "get transfers to {} since height {}",
+                target.to_formatted_string(),
+                since_block_height
+            ),
             RpcRequest::GetPeers { .. } => write!(formatter, "get peers"),
+            RpcRequest::AdminCommand { command, .. } => write!(formatter, "admin {}", command),
             RpcRequest::GetStatus { .. } => write!(formatter, "get status"),
             RpcRequest::GetMetrics { .. } => write!(formatter, "get metrics"),
+            RpcRequest::GetChainspec { .. } => write!(formatter, "get chainspec"),
+            RpcRequest::GetCirculatingSupplyExcludedAccounts { .. } => {
+                write!(formatter, "get circulating supply excluded accounts")
+            }
+            RpcRequest::SignStatus { payload, .. } => {
+                write!(formatter, "sign status payload of {} bytes", payload.len())
+            }
+            RpcRequest::SignDeployReceipt { deploy_hash, .. } => {
+                write!(formatter, "sign receipt for {}", deploy_hash)
+            }
+            RpcRequest::GetDeployReceipt { deploy_hash, .. } => {
+                write!(formatter, "get receipt for {}", deploy_hash)
+            }
         }
     }
 }
@@ -561,6 +1029,22 @@ pub enum ContractRuntimeRequest {
         /// Responder to call with the query result.
         responder: Responder<Result<QueryResult, engine_state::Error>>,
     },
+    /// A batch of queries against the same state root.
+    QueryBatch {
+        /// Query request batch.
+        #[serde(skip_serializing)]
+        batch: QueryRequestBatch,
+        /// Responder to call with the results.
+        responder: Responder<Result<QueryBatchResult, engine_state::Error>>,
+    },
+    /// A request for a page of the keys matching a prefix.
+    GetKeysWithPrefix {
+        /// Keys with prefix request.
+        #[serde(skip_serializing)]
+        request: KeysWithPrefixRequest,
+        /// Responder to call with the keys and their proofs.
+        responder: Responder<Result<KeysWithPrefixResult, engine_state::Error>>,
+    },
     /// A balance request.
     GetBalance {
         /// Balance request.
@@ -585,6 +1069,22 @@ pub enum ContractRuntimeRequest {
         /// Responder to call with the result.
         responder: Responder<Result<Option<ValidatorWeights>, GetEraValidatorsError>>,
     },
+    /// Executes a single deploy against the given state root hash in order to estimate its cost.
+    ///
+    /// The resulting effects are discarded: only their JSON-compatible summary is returned.
+    EstimateDeployCost {
+        /// The state root hash against which to execute the deploy.
+        state_root_hash: Digest,
+        /// The block time to use for the execution.
+        block_time: Timestamp,
+        /// The deploy to execute.
+        deploy: Box<Deploy>,
+        /// Whether to break the gas consumed down by host function and opcode category.
+        profile_gas: bool,
+        /// Responder to call with the execution result, and the gas profile if requested.
+        responder:
+            Responder<Result<(ExecutionResult, Option<GasProfile>), engine_state::RootNotFound>>,
+    },
     /// Performs a step consisting of calculating rewards, slashing and running the auction at the
     /// end of an era.
     Step {
@@ -594,6 +1094,28 @@ pub enum ContractRuntimeRequest {
         /// Responder to call with the result.
         responder: Responder<Result<StepResult, engine_state::Error>>,
     },
+    /// Reads the serialized tries under the given hashes from global state.
+    ReadTries {
+        /// The hashes of the tries to read.
+        trie_keys: Vec<Digest>,
+        /// Responder to call with the serialized tries, `None` for each trie not held.
+        responder: Responder<Result<Vec<Option<Vec<u8>>>, StorageLmdbError>>,
+    },
+    /// Returns those of the given trie hashes which are absent from global state.
+    FindMissingTries {
+        /// The hashes of the tries to look up.
+        trie_keys: Vec<Digest>,
+        /// Responder to call with the hashes of the missing tries.
+        responder: Responder<Result<Vec<Digest>, StorageLmdbError>>,
+    },
+    /// Writes the given tries to global state, under the given hashes.
+    WriteTries {
+        /// The tries to write, keyed by their hash.
+        #[serde(skip_serializing)]
+        tries: Vec<(Digest, Trie<Key, StoredValue>)>,
+        /// Responder to call with the result.
+        responder: Responder<Result<(), StorageLmdbError>>,
+    },
 }
 
 impl Display for ContractRuntimeRequest {
@@ -630,6 +1152,14 @@ impl Display for ContractRuntimeRequest {
                 write!(formatter, "query request: {:?}", query_request)
             }
 
+            ContractRuntimeRequest::QueryBatch { batch, .. } => {
+                write!(formatter, "query batch: {:?}", batch)
+            }
+
+            ContractRuntimeRequest::GetKeysWithPrefix { request, .. } => {
+                write!(formatter, "get keys with prefix request: {:?}", request)
+            }
+
             ContractRuntimeRequest::GetBalance {
                 balance_request, ..
             } => write!(formatter, "balance request: {:?}", balance_request),
@@ -642,6 +1172,17 @@ impl Display for ContractRuntimeRequest {
                 write!(formatter, "get validator weights: {:?}", request)
             }
 
+            ContractRuntimeRequest::EstimateDeployCost {
+                state_root_hash,
+                deploy,
+                ..
+            } => write!(
+                formatter,
+                "estimate cost of {} at {}",
+                deploy.id(),
+                state_root_hash
+            ),
+
             ContractRuntimeRequest::Step { step_request, .. } => {
                 write!(formatter, "step: {:?}", step_request)
             }
@@ -649,6 +1190,18 @@ impl Display for ContractRuntimeRequest {
             ContractRuntimeRequest::GetProtocolData {
                 protocol_version, ..
             } => write!(formatter, "protocol_version: {}", protocol_version),
+
+            ContractRuntimeRequest::ReadTries { trie_keys, .. } => {
+                write!(formatter, "read {} tries", trie_keys.len())
+            }
+
+            ContractRuntimeRequest::FindMissingTries { trie_keys, .. } => {
+                write!(formatter, "find missing among {} tries", trie_keys.len())
+            }
+
+            ContractRuntimeRequest::WriteTries { tries, .. } => {
+                write!(formatter, "write {} tries", tries.len())
+            }
         }
     }
 }
@@ -753,7 +1306,19 @@ impl<I: Display> Display for LinearChainRequest<I> {
 /// Consensus component requests.
 pub enum ConsensusRequest {
     /// Request for consensus to sign a new linear chain block and possibly start a new era.
-    HandleLinearBlock(Box<BlockHeader>, Responder<Signature>),
+    ///
+    /// Responds with `None` if the block could not be signed.
+    HandleLinearBlock(Box<BlockHeader>, Responder<Option<(PublicKey, Signature)>>),
+    /// Request for consensus to sign a serialized `SignedStatusPayload` with the node's current
+    /// signing key.  The payload is signed via `signed_status_digest`, never directly.
+    ///
+    /// Responds with `None` if the payload could not be signed.
+    SignStatus(Vec<u8>, Responder<Option<(PublicKey, Signature)>>),
+    /// Request for consensus to sign a receipt for a deploy accepted at the given time with the
+    /// node's current signing key.
+    ///
+    /// Responds with `None` if the receipt could not be signed.
+    SignDeployReceipt(DeployHash, Timestamp, Responder<Option<DeployReceipt>>),
 }
 
 /// ChainspecLoader componenent requests.
@@ -770,3 +1335,19 @@ impl Display for ChainspecLoaderRequest {
         }
     }
 }
+
+/// Linear chain synchronization progress requests.
+#[derive(Debug)]
+#[must_use]
+pub enum SyncProgressRequest {
+    /// Request for the node's progress in synchronizing the linear chain.
+    GetSyncProgress(Responder<SyncProgress>),
+}
+
+impl Display for SyncProgressRequest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SyncProgressRequest::GetSyncProgress(_) => write!(f, "get sync progress"),
+        }
+    }
+}
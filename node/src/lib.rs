@@ -30,13 +30,17 @@ pub mod effect;
 pub mod logging;
 pub mod protocol;
 pub mod reactor;
-#[cfg(test)]
+#[cfg(any(feature = "testing", test))]
+#[cfg_attr(not(test), allow(dead_code))]
 pub mod testing;
 pub mod tls;
 pub mod types;
 pub mod utils;
 
-use std::sync::{atomic::AtomicBool, Arc};
+use std::{
+    sync::{atomic::AtomicBool, Arc},
+    time::Instant,
+};
 
 use ansi_term::Color::Red;
 use lazy_static::lazy_static;
@@ -44,16 +48,30 @@ use lazy_static::lazy_static;
 use rand::SeedableRng;
 
 pub use components::{
+    block_executor::{replay_block, ExecutionResultMismatch, ReplayError, ReplayReport},
+    block_proposer::Config as BlockProposerConfig,
     chainspec_loader::{Chainspec, Error as ChainspecError},
     consensus::Config as ConsensusConfig,
-    contract_runtime::Config as ContractRuntimeConfig,
+    contract_runtime::{
+        simulate_deploy, Config as ContractRuntimeConfig, SimulationError, SimulationReport,
+        SimulationState,
+    },
     event_stream_server::Config as EventStreamServerConfig,
     fetcher::Config as FetcherConfig,
+    finality_monitor::Config as FinalityMonitorConfig,
     gossiper::{Config as GossipConfig, Error as GossipError},
+    linear_chain::Config as LinearChainConfig,
+    replication::Config as ReplicationConfig,
+    resource_monitor::Config as ResourceMonitorConfig,
     rest_server::Config as RestServerConfig,
     rpc_server::{rpcs, Config as RpcServerConfig},
     small_network::{Config as SmallNetworkConfig, Error as SmallNetworkError},
-    storage::{Config as StorageConfig, Error as StorageError},
+    storage::{
+        rebuild_indexes as rebuild_storage_indexes, Config as StorageConfig, Error as StorageError,
+        Index as StorageIndex,
+    },
+    telemetry::Config as TelemetryConfig,
+    webhooks::Config as WebhooksConfig,
 };
 pub use types::NodeRng;
 pub use utils::OS_PAGE_SIZE;
@@ -96,21 +114,38 @@ lazy_static! {
 
     /// Global flag that indicates the currently running reactor should dump its event queue.
     pub static ref QUEUE_DUMP_REQUESTED: Arc<AtomicBool> = Arc::new(AtomicBool::new(false));
+
+    /// Global flag that indicates the node has been asked to terminate.
+    pub static ref TERMINATION_REQUESTED: Arc<AtomicBool> = Arc::new(AtomicBool::new(false));
+
+    /// The time at which the node started, as recorded by `setup_signal_hooks`.
+    pub static ref NODE_START_TIME: Instant = Instant::now();
 }
 
 /// Setup UNIX signal hooks for current application.
+///
+/// A SIGTERM or SIGINT requests the node to terminate, allowing it to shut down gracefully.  A
+/// second one terminates the node immediately.
 pub fn setup_signal_hooks() {
+    lazy_static::initialize(&NODE_START_TIME);
     let _ = signal_hook::flag::register(libc::SIGUSR1, QUEUE_DUMP_REQUESTED.clone());
+    let termination_signals = [libc::SIGTERM, libc::SIGINT];
+    for signal in &termination_signals {
+        let _ = signal_hook::flag::register(*signal, TERMINATION_REQUESTED.clone());
+        // Restore the default handlers once the first one is received, so the next terminates the
+        // node.
+        let _ = signal_hook::cleanup::register(*signal, termination_signals.to_vec());
+    }
 }
 
 /// Constructs a new `NodeRng`.
-#[cfg(not(test))]
+#[cfg(not(any(feature = "testing", test)))]
 pub fn new_rng() -> NodeRng {
     NodeRng::from_entropy()
 }
 
 /// Constructs a new `NodeRng`.
-#[cfg(test)]
+#[cfg(any(feature = "testing", test))]
 pub fn new_rng() -> NodeRng {
     NodeRng::new()
 }
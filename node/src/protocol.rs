@@ -1,14 +1,25 @@
 //! A network message type used for communication between nodes
 
-use std::fmt::{self, Display, Formatter};
+use std::{
+    fmt::{self, Display, Formatter},
+    io,
+};
 
+use bytes::Bytes;
 use derive_more::From;
 use fmt::Debug;
 use hex_fmt::HexFmt;
-use serde::{Deserialize, Serialize};
+use serde::{
+    de::{SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
 
+pub use crate::components::small_network::{Payload, WireVersion};
 use crate::{
-    components::{consensus, gossiper, small_network::GossipedAddress},
+    components::{
+        consensus, gossiper,
+        small_network::{self, GossipedAddress},
+    },
     types::{Deploy, Item, Tag},
 };
 
@@ -29,14 +40,17 @@ pub enum Message {
         /// The type tag of the requested item.
         tag: Tag,
         /// The serialized ID of the requested item.
-        serialized_id: Vec<u8>,
+        serialized_id: Bytes,
     },
     /// Response to a `GetRequest`.
     GetResponse {
         /// The type tag of the contained item.
         tag: Tag,
-        /// The serialized item.
-        serialized_item: Vec<u8>,
+        /// The serialized item, deserialized only by the component handling it.
+        ///
+        /// When received from a peer speaking `WireVersion::V2`, this is a slice of the received
+        /// frame rather than a copy.
+        serialized_item: Bytes,
     },
 }
 
@@ -44,18 +58,170 @@ impl Message {
     pub(crate) fn new_get_request<T: Item>(id: &T::Id) -> Result<Self, bincode::Error> {
         Ok(Message::GetRequest {
             tag: T::TAG,
-            serialized_id: bincode::serialize(id)?,
+            serialized_id: bincode::serialize(id)?.into(),
         })
     }
 
     pub(crate) fn new_get_response<T: Item>(item: &T) -> Result<Self, bincode::Error> {
         Ok(Message::GetResponse {
             tag: T::TAG,
-            serialized_item: bincode::serialize(item)?,
+            serialized_item: bincode::serialize(item)?.into(),
         })
     }
 }
 
+/// Byte fields are encoded as binary blobs under `WireVersion::V2`, but as sequences of integers
+/// under `WireVersion::V1`.
+impl Payload for Message {
+    fn encode(&self, wire_version: WireVersion) -> Result<Option<Bytes>, rmp_serde::encode::Error> {
+        match (self, wire_version) {
+            // Busy responses were introduced with `WireVersion::V2`.
+            (Message::DeployGossiper(gossiper::Message::Busy(_)), WireVersion::V1)
+            | (Message::AddressGossiper(gossiper::Message::Busy(_)), WireVersion::V1) => Ok(None),
+            (_, WireVersion::V1) => {
+                small_network::encode_payload(&LegacyMessage::from(self)).map(Some)
+            }
+            (_, WireVersion::V2) => small_network::encode_payload(self).map(Some),
+        }
+    }
+
+    fn decode(frame: Bytes) -> io::Result<Self> {
+        let msg: ReceivedMessage = small_network::decode_payload(&frame)?;
+        Ok(msg.into_message(&frame))
+    }
+}
+
+/// `Message` as encoded under `WireVersion::V1`, with byte fields encoded as sequences of
+/// integers.
+#[derive(Serialize)]
+enum LegacyMessage<'a> {
+    Consensus(&'a consensus::ConsensusMessage),
+    DeployGossiper(&'a gossiper::Message<Deploy>),
+    AddressGossiper(&'a gossiper::Message<GossipedAddress>),
+    GetRequest { tag: Tag, serialized_id: &'a [u8] },
+    GetResponse { tag: Tag, serialized_item: &'a [u8] },
+}
+
+impl<'a> From<&'a Message> for LegacyMessage<'a> {
+    fn from(msg: &'a Message) -> Self {
+        match msg {
+            Message::Consensus(consensus) => LegacyMessage::Consensus(consensus),
+            Message::DeployGossiper(deploy) => LegacyMessage::DeployGossiper(deploy),
+            Message::AddressGossiper(gossiped_address) => {
+                LegacyMessage::AddressGossiper(gossiped_address)
+            }
+            Message::GetRequest { tag, serialized_id } => LegacyMessage::GetRequest {
+                tag: *tag,
+                serialized_id,
+            },
+            Message::GetResponse {
+                tag,
+                serialized_item,
+            } => LegacyMessage::GetResponse {
+                tag: *tag,
+                serialized_item,
+            },
+        }
+    }
+}
+
+/// `Message` as decoded from a received frame of either wire version, with byte fields borrowed
+/// from the frame where possible.
+#[derive(Deserialize)]
+enum ReceivedMessage<'a> {
+    Consensus(consensus::ConsensusMessage),
+    DeployGossiper(gossiper::Message<Deploy>),
+    AddressGossiper(gossiper::Message<GossipedAddress>),
+    GetRequest {
+        tag: Tag,
+        #[serde(borrow)]
+        serialized_id: FrameBytes<'a>,
+    },
+    GetResponse {
+        tag: Tag,
+        #[serde(borrow)]
+        serialized_item: FrameBytes<'a>,
+    },
+}
+
+impl ReceivedMessage<'_> {
+    /// Converts into a `Message`, slicing byte fields borrowed from `frame` rather than copying
+    /// them.
+    fn into_message(self, frame: &Bytes) -> Message {
+        match self {
+            ReceivedMessage::Consensus(consensus) => Message::Consensus(consensus),
+            ReceivedMessage::DeployGossiper(deploy) => Message::DeployGossiper(deploy),
+            ReceivedMessage::AddressGossiper(gossiped_address) => {
+                Message::AddressGossiper(gossiped_address)
+            }
+            ReceivedMessage::GetRequest { tag, serialized_id } => Message::GetRequest {
+                tag,
+                serialized_id: serialized_id.into_bytes(frame),
+            },
+            ReceivedMessage::GetResponse {
+                tag,
+                serialized_item,
+            } => Message::GetResponse {
+                tag,
+                serialized_item: serialized_item.into_bytes(frame),
+            },
+        }
+    }
+}
+
+/// A byte field of a received frame, borrowed from the frame if encoded as a binary blob, or
+/// collected if encoded as a sequence of integers.
+enum FrameBytes<'a> {
+    Borrowed(&'a [u8]),
+    Owned(Vec<u8>),
+}
+
+impl FrameBytes<'_> {
+    /// Returns the bytes as a slice of `frame` if borrowed from it.
+    fn into_bytes(self, frame: &Bytes) -> Bytes {
+        match self {
+            FrameBytes::Borrowed(bytes) => frame.slice_ref(bytes),
+            FrameBytes::Owned(bytes) => Bytes::from(bytes),
+        }
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for FrameBytes<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FrameBytesVisitor;
+
+        impl<'de> Visitor<'de> for FrameBytesVisitor {
+            type Value = FrameBytes<'de>;
+
+            fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                formatter.write_str("bytes or a sequence of bytes")
+            }
+
+            fn visit_borrowed_bytes<E>(self, bytes: &'de [u8]) -> Result<Self::Value, E> {
+                Ok(FrameBytes::Borrowed(bytes))
+            }
+
+            fn visit_bytes<E>(self, bytes: &[u8]) -> Result<Self::Value, E> {
+                Ok(FrameBytes::Owned(bytes.to_vec()))
+            }
+
+            fn visit_byte_buf<E>(self, bytes: Vec<u8>) -> Result<Self::Value, E> {
+                Ok(FrameBytes::Owned(bytes))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+                Ok(FrameBytes::Owned(bytes))
+            }
+        }
+
+        deserializer.deserialize_bytes(FrameBytesVisitor)
+    }
+}
+
 impl Debug for Message {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
@@ -97,3 +263,83 @@ impl Display for Message {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{crypto::hash::Digest, testing::TestRng, types::DeployHash};
+
+    /// `Message` as encoded by nodes predating wire version negotiation.
+    #[allow(dead_code)]
+    #[derive(Serialize)]
+    enum BaselineMessage {
+        Consensus(()),
+        DeployGossiper(()),
+        AddressGossiper(()),
+        GetRequest { tag: Tag, serialized_id: Vec<u8> },
+        GetResponse { tag: Tag, serialized_item: Vec<u8> },
+    }
+
+    fn serialized_item() -> Vec<u8> {
+        (0..1024).map(|index| index as u8).collect()
+    }
+
+    fn get_response() -> Message {
+        Message::GetResponse {
+            tag: Tag::Deploy,
+            serialized_item: Bytes::from(serialized_item()),
+        }
+    }
+
+    fn decoded_item(msg: Message) -> Bytes {
+        match msg {
+            Message::GetResponse {
+                tag: Tag::Deploy,
+                serialized_item,
+            } => serialized_item,
+            msg => panic!("unexpected message {}", msg),
+        }
+    }
+
+    #[test]
+    fn should_encode_and_decode_baseline_frames() {
+        let baseline_frame = small_network::encode_payload(&BaselineMessage::GetResponse {
+            tag: Tag::Deploy,
+            serialized_item: serialized_item(),
+        })
+        .unwrap();
+
+        let frame = get_response().encode(WireVersion::V1).unwrap().unwrap();
+        assert_eq!(frame, baseline_frame);
+
+        let item = decoded_item(Message::decode(baseline_frame).unwrap());
+        assert_eq!(item, serialized_item());
+    }
+
+    #[test]
+    fn should_decode_items_without_copying() {
+        let frame = get_response().encode(WireVersion::V2).unwrap().unwrap();
+        // The item is encoded as a single binary blob, rather than an integer per byte.
+        assert!(frame.len() < serialized_item().len() + 16);
+
+        let item = decoded_item(Message::decode(frame.clone()).unwrap());
+        assert_eq!(item, serialized_item());
+        let frame_range = frame.as_ptr() as usize..frame.as_ptr() as usize + frame.len();
+        assert!(frame_range.contains(&(item.as_ptr() as usize)));
+    }
+
+    #[test]
+    fn should_not_send_busy_responses_to_v1_peers() {
+        let mut rng = TestRng::new();
+        let msg = Message::DeployGossiper(gossiper::Message::Busy(DeployHash::new(
+            Digest::random(&mut rng),
+        )));
+        assert!(msg.encode(WireVersion::V1).unwrap().is_none());
+
+        let frame = msg.encode(WireVersion::V2).unwrap().unwrap();
+        assert!(matches!(
+            Message::decode(frame).unwrap(),
+            Message::DeployGossiper(gossiper::Message::Busy(_))
+        ));
+    }
+}
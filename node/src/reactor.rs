@@ -173,6 +173,15 @@ pub trait Reactor: Sized {
         false
     }
 
+    /// Indicates that the reactor shuts itself down gracefully once termination of the node has
+    /// been requested, and should be run until `is_stopped()` returns true.
+    ///
+    /// Reactors which don't are no longer run once termination has been requested.
+    #[inline]
+    fn shuts_down_on_termination(&self) -> bool {
+        false
+    }
+
     /// Instructs the reactor to update performance metrics, if any.
     fn update_metrics(&mut self, _event_queue_handle: EventQueueHandle<Self::Event>) {}
 }
@@ -352,7 +361,7 @@ where
 
     /// Inject (schedule then process) effects created via a call to `create_effects` which is
     /// itself passed an instance of an `EffectBuilder`.
-    #[cfg(test)]
+    #[cfg(any(feature = "testing", test))]
     pub(crate) async fn process_injected_effects<F>(&mut self, create_effects: F)
     where
         F: FnOnce(EffectBuilder<R::Event>) -> Effects<R::Event>,
@@ -462,10 +471,17 @@ where
         }
     }
 
-    /// Runs the reactor until `is_stopped()` returns true.
+    /// Runs the reactor until `is_stopped()` returns true, or until termination of the node has
+    /// been requested if the reactor does not shut itself down on termination.
     #[inline]
     pub async fn run(&mut self, rng: &mut NodeRng) {
         while !self.reactor.is_stopped() {
+            if crate::TERMINATION_REQUESTED.load(Ordering::SeqCst)
+                && !self.reactor.shuts_down_on_termination()
+            {
+                info!("termination requested, stopping reactor");
+                break;
+            }
             self.crank(rng).await;
         }
     }
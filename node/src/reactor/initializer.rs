@@ -7,6 +7,9 @@ use derive_more::From;
 use prometheus::Registry;
 use serde::Serialize;
 use thiserror::Error;
+use tracing::info;
+
+use casper_execution_engine::storage::error::lmdb::Error as StorageLmdbError;
 
 use crate::{
     components::{
@@ -15,13 +18,14 @@ use crate::{
         storage::{self, Storage},
         Component,
     },
+    crypto::hash::Digest,
     effect::{
         requests::{ContractRuntimeRequest, NetworkRequest, StorageRequest},
         EffectBuilder, Effects,
     },
     protocol::Message,
     reactor::{self, validator, EventQueueHandle},
-    types::NodeId,
+    types::{BlockHash, NodeId},
     utils::WithDir,
     NodeRng,
 };
@@ -93,6 +97,45 @@ pub enum Error {
     /// `ContractRuntime` component error.
     #[error("contract runtime config error: {0}")]
     ContractRuntime(#[from] contract_runtime::ConfigError),
+
+    /// Error reading global state while verifying it against the stored blocks.
+    #[error("failed to read global state: {0}")]
+    GlobalState(#[from] StorageLmdbError),
+
+    /// The global state of the highest stored block is missing, so global state and block storage
+    /// are out of sync.
+    #[error(
+        "global state under state root hash {state_root_hash} of highest stored block {block_hash} \
+        at height {height} is incomplete: trie {missing_trie} is missing; resync the node by \
+        clearing its storage directory before restarting"
+    )]
+    MissingGlobalState {
+        /// The height of the highest stored block.
+        height: u64,
+        /// The hash of the highest stored block.
+        block_hash: BlockHash,
+        /// The state root hash of the highest stored block.
+        state_root_hash: Digest,
+        /// The hash of the first trie found to be missing.
+        missing_trie: Digest,
+    },
+
+    /// The global state of a stored block is missing, so the node can't serve as an archive node.
+    #[error(
+        "archive node is missing global state under state root hash {state_root_hash} of stored \
+        block {block_hash} at height {height}: trie {missing_trie} is missing; resync the node by \
+        clearing its storage directory before restarting"
+    )]
+    MissingHistoricalState {
+        /// The height of the stored block.
+        height: u64,
+        /// The hash of the stored block.
+        block_hash: BlockHash,
+        /// The state root hash of the stored block.
+        state_root_hash: Digest,
+        /// The hash of the first trie found to be missing.
+        missing_trie: Digest,
+    },
 }
 
 /// Initializer node reactor.
@@ -111,6 +154,65 @@ impl Reactor {
     }
 }
 
+/// Verifies that the global state of the highest stored block, if any, is present in the trie
+/// store.
+///
+/// A node whose global state lags behind its linear chain would otherwise fail to execute the next
+/// block, so it refuses to start instead.
+fn verify_global_state(storage: &Storage, contract_runtime: &ContractRuntime) -> Result<(), Error> {
+    let block = match storage.read_highest_block()? {
+        Some(block) => block,
+        None => return Ok(()),
+    };
+    let state_root_hash = *block.state_root_hash();
+    if let Some(missing_trie) = contract_runtime.find_missing_trie(state_root_hash)? {
+        return Err(Error::MissingGlobalState {
+            height: block.height(),
+            block_hash: *block.hash(),
+            state_root_hash,
+            missing_trie,
+        });
+    }
+    info!(
+        height = block.height(),
+        %state_root_hash,
+        "verified global state of highest stored block"
+    );
+    Ok(())
+}
+
+/// Verifies that the global state of every stored block is present in the trie store, as an
+/// archive node guarantees that the global state of every block can be queried.
+fn verify_historical_state(
+    storage: &Storage,
+    contract_runtime: &ContractRuntime,
+) -> Result<(), Error> {
+    let highest_height = match storage.read_highest_block()? {
+        Some(block) => block.height(),
+        None => return Ok(()),
+    };
+    for height in 0..=highest_height {
+        let block = match storage.read_block_by_height(height)? {
+            Some(block) => block,
+            None => continue,
+        };
+        let state_root_hash = *block.state_root_hash();
+        if let Some(missing_trie) = contract_runtime.find_missing_trie(state_root_hash)? {
+            return Err(Error::MissingHistoricalState {
+                height,
+                block_hash: *block.hash(),
+                state_root_hash,
+                missing_trie,
+            });
+        }
+    }
+    info!(
+        highest_height,
+        "verified global state of every stored block"
+    );
+    Ok(())
+}
+
 impl reactor::Reactor for Reactor {
     type Event = Event;
     type Config = WithDir<validator::Config>;
@@ -134,11 +236,24 @@ impl reactor::Reactor for Reactor {
 
         let effect_builder = EffectBuilder::new(event_queue);
 
+        let archive = config.value().storage.archive();
+        if archive && config.value().rpc_server.historical_state_window.is_some() {
+            return Err(Error::ConfigError(
+                "an archive node can't restrict queries to a historical state window".to_string(),
+            ));
+        }
+
         let storage_config = config.map_ref(|cfg| cfg.storage.clone());
-        let storage = Storage::new(&storage_config)?;
+        let storage = Storage::new(&storage_config, registry)?;
+        storage.start_backups(registry)?;
 
         let contract_runtime =
             ContractRuntime::new(storage_config, &config.value().contract_runtime, registry)?;
+        if archive {
+            verify_historical_state(&storage, &contract_runtime)?;
+        } else {
+            verify_global_state(&storage, &contract_runtime)?;
+        }
         let (chainspec_loader, chainspec_effects) =
             ChainspecLoader::new(chainspec, effect_builder)?;
 
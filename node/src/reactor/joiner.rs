@@ -1,4 +1,9 @@
 //! Reactor used to join the network.
+//!
+//! In sidecar mode the node never moves on to the validator reactor: the joiner keeps following
+//! the linear chain and additionally serves the JSON-RPC API, with consensus replaced by a stub,
+//! so that no validator key is loaded.  If a replication primary is configured, the linear chain
+//! and global state are replicated from the primary rather than synchronized from peers.
 
 use std::fmt::{self, Display, Formatter};
 
@@ -9,7 +14,7 @@ use serde::Serialize;
 use tracing::{error, info, warn};
 
 use block_executor::BlockExecutor;
-use consensus::EraSupervisor;
+use consensus::{ConsensusStub, EraSupervisor};
 use deploy_acceptor::DeployAcceptor;
 use small_network::GossipedAddress;
 
@@ -27,23 +32,27 @@ use crate::{
         linear_chain,
         linear_chain_sync::{self, LinearChainSync},
         metrics::Metrics,
+        replication::{self, ReplicationClient, ReplicationServer},
         rest_server::{self, RestServer},
+        rpc_server::{self, rpcs::admin::AdminCommand, RpcServer},
         small_network::{self, SmallNetwork},
         storage::{self, Storage},
         Component,
     },
+    crypto::hash::Digest,
     effect::{
         announcements::{
             BlockExecutorAnnouncement, ConsensusAnnouncement, DeployAcceptorAnnouncement,
             GossiperAnnouncement, LinearChainAnnouncement, NetworkAnnouncement,
+            RpcServerAnnouncement,
         },
         requests::{
             BlockExecutorRequest, BlockProposerRequest, BlockValidationRequest,
             ChainspecLoaderRequest, ConsensusRequest, ContractRuntimeRequest, FetcherRequest,
             LinearChainRequest, MetricsRequest, NetworkInfoRequest, NetworkRequest, RestRequest,
-            StorageRequest,
+            RpcRequest, StorageRequest, SyncProgressRequest,
         },
-        EffectBuilder, Effects,
+        EffectBuilder, EffectExt, Effects,
     },
     protocol::Message,
     reactor::{
@@ -53,8 +62,11 @@ use crate::{
         validator::{self, Error, ValidatorInitConfig},
         EventQueueHandle, Finalize,
     },
-    types::{Block, BlockByHeight, BlockHeader, Deploy, NodeId, ProtoBlock, Tag, Timestamp},
-    utils::{Source, WithDir},
+    types::{
+        Block, BlockByHeight, BlockHash, BlockHeader, CheckpointError, Deploy, NodeConfig, NodeId,
+        ProtoBlock, Tag, Timestamp,
+    },
+    utils::{Source, VerificationPool, WithDir},
     NodeRng,
 };
 
@@ -78,6 +90,10 @@ pub enum Event {
     /// Event stream server event.
     EventStreamServer(#[serde(skip_serializing)] event_stream_server::Event),
 
+    /// RPC server event, only raised in sidecar mode.
+    #[from]
+    RpcServer(#[serde(skip_serializing)] rpc_server::Event),
+
     /// Metrics request.
     #[from]
     MetricsRequest(#[serde(skip_serializing)] MetricsRequest),
@@ -90,6 +106,10 @@ pub enum Event {
     #[from]
     ChainspecLoaderRequest(#[serde(skip_serializing)] ChainspecLoaderRequest),
 
+    /// Sync progress request.
+    #[from]
+    SyncProgressRequest(#[serde(skip_serializing)] SyncProgressRequest),
+
     /// Network info request.
     #[from]
     NetworkInfoRequest(#[serde(skip_serializing)] NetworkInfoRequest<NodeId>),
@@ -118,6 +138,10 @@ pub enum Event {
     #[from]
     LinearChainSync(#[serde(skip_serializing)] linear_chain_sync::Event<NodeId>),
 
+    /// Replication client event, only raised when replicating from a primary.
+    #[from]
+    ReplicationClient(#[serde(skip_serializing)] replication::Event),
+
     /// Block executor event.
     #[from]
     BlockExecutor(#[serde(skip_serializing)] block_executor::Event),
@@ -193,6 +217,10 @@ pub enum Event {
     /// Linear chain announcement.
     #[from]
     LinearChainAnnouncement(#[serde(skip_serializing)] LinearChainAnnouncement),
+
+    /// RPC server announcement.
+    #[from]
+    RpcServerAnnouncement(#[serde(skip_serializing)] RpcServerAnnouncement),
 }
 
 impl From<LinearChainRequest<NodeId>> for Event {
@@ -239,6 +267,12 @@ impl From<RestRequest<NodeId>> for Event {
     }
 }
 
+impl From<RpcRequest<NodeId>> for Event {
+    fn from(request: RpcRequest<NodeId>) -> Self {
+        Event::RpcServer(rpc_server::Event::RpcRequest(request))
+    }
+}
+
 impl Display for Event {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
@@ -247,9 +281,11 @@ impl Display for Event {
             Event::Storage(request) => write!(f, "storage: {}", request),
             Event::RestServer(event) => write!(f, "rest server: {}", event),
             Event::EventStreamServer(event) => write!(f, "event stream server: {}", event),
+            Event::RpcServer(event) => write!(f, "rpc server: {}", event),
             Event::MetricsRequest(req) => write!(f, "metrics request: {}", req),
             Event::ChainspecLoader(event) => write!(f, "chainspec loader: {}", event),
             Event::ChainspecLoaderRequest(req) => write!(f, "chainspec loader request: {}", req),
+            Event::SyncProgressRequest(req) => write!(f, "sync progress request: {}", req),
             Event::NetworkInfoRequest(req) => write!(f, "network info request: {}", req),
             Event::BlockFetcherRequest(request) => write!(f, "block fetcher request: {}", request),
             Event::BlockValidatorRequest(request) => {
@@ -259,6 +295,7 @@ impl Display for Event {
                 write!(f, "deploy fetcher request: {}", request)
             }
             Event::LinearChainSync(event) => write!(f, "linear chain: {}", event),
+            Event::ReplicationClient(event) => write!(f, "replication client: {}", event),
             Event::BlockFetcher(event) => write!(f, "block fetcher: {}", event),
             Event::BlockByHeightFetcherRequest(request) => {
                 write!(f, "block by height fetcher request: {}", request)
@@ -290,6 +327,7 @@ impl Display for Event {
             }
             Event::DeployAcceptor(event) => write!(f, "deploy acceptor: {}", event),
             Event::LinearChainAnnouncement(ann) => write!(f, "linear chain announcement: {}", ann),
+            Event::RpcServerAnnouncement(ann) => write!(f, "api server announcement: {}", ann),
         }
     }
 }
@@ -310,7 +348,10 @@ pub struct Reactor {
     pub(super) deploy_fetcher: Fetcher<Deploy>,
     pub(super) block_executor: BlockExecutor,
     pub(super) linear_chain: linear_chain::LinearChain<NodeId>,
-    pub(super) consensus: EraSupervisor<NodeId>,
+    /// The consensus component, or `None` in sidecar mode, in which `consensus_stub` handles the
+    /// consensus events instead.
+    pub(super) consensus: Option<EraSupervisor<NodeId>>,
+    consensus_stub: ConsensusStub<NodeId>,
     // Effects consensus component returned during creation.
     // In the `joining` phase we don't want to handle it,
     // so we carry them forward to the `validator` reactor.
@@ -321,12 +362,24 @@ pub struct Reactor {
     pub(super) block_by_height_fetcher: Fetcher<BlockByHeight>,
     #[data_size(skip)]
     pub(super) deploy_acceptor: DeployAcceptor,
+    /// The pool on which signatures are verified, carried forward to the `validator` reactor.
+    #[data_size(skip)]
+    verification_pool: VerificationPool,
     #[data_size(skip)]
     event_queue_metrics: EventQueueMetrics,
     #[data_size(skip)]
     pub(super) rest_server: RestServer,
     #[data_size(skip)]
     pub(super) event_stream_server: EventStreamServer,
+    /// The RPC server, only run in sidecar mode.
+    #[data_size(skip)]
+    rpc_server: Option<RpcServer>,
+    /// The replication client, only run in sidecar mode with a replication primary configured, in
+    /// which case the linear chain isn't synchronized from peers.
+    replication_client: Option<ReplicationClient>,
+    /// The replication server, only run in sidecar mode.
+    #[data_size(skip)]
+    replication_server: Option<ReplicationServer>,
 }
 
 impl reactor::Reactor for Reactor {
@@ -353,26 +406,64 @@ impl reactor::Reactor for Reactor {
         } = initializer;
 
         // TODO: Remove wrapper around Reactor::Config instead.
-        let (_, config) = config.into_parts();
+        let (_, mut config) = config.into_parts();
+        config.rpc_server.api_keys_file = config
+            .rpc_server
+            .api_keys_file
+            .map(|api_keys_file| root.join(api_keys_file));
+        config.rest_server.metrics_tls = config
+            .rest_server
+            .metrics_tls
+            .map(|metrics_tls| metrics_tls.with_root(&root));
 
         let event_queue_metrics = EventQueueMetrics::new(registry.clone(), event_queue)?;
 
         let metrics = Metrics::new(registry.clone());
 
-        let (net, net_effects) = SmallNetwork::new(event_queue, config.network.clone(), false)?;
+        let (net, net_effects) =
+            SmallNetwork::new(event_queue, config.network.clone(), registry, false)?;
 
         let linear_chain_fetcher = Fetcher::new(config.fetcher);
 
-        let effects = reactor::wrap_effects(Event::Network, net_effects);
+        let mut effects = reactor::wrap_effects(Event::Network, net_effects);
 
         let address_gossiper =
             Gossiper::new_for_complete_items("address_gossiper", config.gossip, registry)?;
 
         let effect_builder = EffectBuilder::new(event_queue);
 
-        let init_hash = config.node.trusted_hash;
+        let genesis_state_root_hash = chainspec_loader
+            .genesis_state_root_hash()
+            .expect("Should have Genesis state root hash");
+
+        let mut init_hash = trusted_hash(
+            &config.node,
+            &storage,
+            &contract_runtime,
+            genesis_state_root_hash,
+        )?;
+
+        // Executed blocks are persisted, so synchronization resumes after the highest stored one.
+        let highest_stored = storage.read_highest_block()?.map(Block::take_header);
+
+        let sidecar = config.node.sidecar;
+        let replica = sidecar && config.replication.primary_url.is_some();
+        if !sidecar && config.replication.primary_url.is_some() {
+            warn!("ignoring the replication primary outside sidecar mode");
+        }
+        if replica {
+            // A replica copies the primary's chain after its highest stored block instead.
+            init_hash = None;
+        } else if sidecar && init_hash.is_none() {
+            // Without a trust anchor a sidecar follows the chain from where it previously left off.
+            init_hash = highest_stored.as_ref().map(BlockHeader::hash);
+            if init_hash.is_none() {
+                return Err(Error::SidecarWithoutTrustedHash);
+            }
+        }
 
         match init_hash {
+            None if replica => info!("Replicating linear chain from the primary."),
             None => {
                 let highway_config = &chainspec_loader.chainspec().genesis.highway_config;
                 let genesis_timestamp = highway_config.genesis_era_start_timestamp;
@@ -389,7 +480,11 @@ impl reactor::Reactor for Reactor {
             Some(hash) => info!("Synchronizing linear chain from: {:?}", hash),
         }
 
-        let linear_chain_sync = LinearChainSync::new(init_hash);
+        let highest_stored_height = highest_stored.as_ref().map(BlockHeader::height);
+        let mut linear_chain_sync = LinearChainSync::new(init_hash, highest_stored);
+        if sidecar && !replica {
+            linear_chain_sync = linear_chain_sync.with_follow_mode();
+        }
 
         let rest_server = RestServer::new(config.rest_server.clone(), effect_builder);
 
@@ -402,37 +497,66 @@ impl reactor::Reactor for Reactor {
 
         let block_by_height_fetcher = Fetcher::new(config.fetcher);
 
-        let deploy_acceptor = DeployAcceptor::new();
+        let verification_pool =
+            VerificationPool::new(config.node.signature_verification_threads, registry)?;
 
-        let genesis_state_root_hash = chainspec_loader
-            .genesis_state_root_hash()
-            .expect("Should have Genesis state root hash");
+        let deploy_acceptor = DeployAcceptor::new(verification_pool.clone());
 
-        let block_executor = BlockExecutor::new(genesis_state_root_hash);
-
-        let linear_chain = linear_chain::LinearChain::new();
-
-        let validator_stakes = chainspec_loader
-            .chainspec()
-            .genesis
-            .genesis_validator_stakes();
-
-        // Used to decide whether era should be activated.
-        let timestamp = Timestamp::now();
-
-        let (consensus, init_consensus_effects) = EraSupervisor::new(
-            timestamp,
-            WithDir::new(root, config.consensus.clone()),
-            effect_builder,
-            validator_stakes,
-            chainspec_loader.chainspec(),
-            chainspec_loader
-                .genesis_state_root_hash()
-                .expect("should have genesis post state hash"),
-            registry,
-            Box::new(HighwayProtocol::new_boxed),
-            rng,
-        )?;
+        let block_executor = BlockExecutor::new(genesis_state_root_hash, registry)?;
+
+        let linear_chain =
+            linear_chain::LinearChain::new(config.linear_chain, verification_pool.clone());
+
+        // In sidecar mode the era supervisor, and with it the validator key, is never loaded.
+        let (consensus, init_consensus_effects, rpc_server) = if sidecar {
+            info!("running in sidecar mode, not participating in consensus");
+            let mut rpc_server =
+                RpcServer::new(config.rpc_server.clone(), effect_builder).with_sidecar_mode();
+            if config.storage.archive() {
+                rpc_server = rpc_server.with_archive_mode();
+            }
+            (None, Effects::new(), Some(rpc_server))
+        } else {
+            let validator_stakes = chainspec_loader
+                .chainspec()
+                .genesis
+                .genesis_validator_stakes();
+
+            // Used to decide whether era should be activated.
+            let timestamp = Timestamp::now();
+
+            let (consensus, init_consensus_effects) = EraSupervisor::new(
+                timestamp,
+                WithDir::new(root, config.consensus.clone()),
+                effect_builder,
+                validator_stakes,
+                chainspec_loader.chainspec(),
+                chainspec_loader
+                    .genesis_state_root_hash()
+                    .expect("should have genesis post state hash"),
+                registry,
+                Box::new(HighwayProtocol::new_boxed),
+                rng,
+            )?;
+            (Some(consensus), init_consensus_effects, None)
+        };
+
+        let replication_client = if replica {
+            let (replication_client, replication_effects) =
+                ReplicationClient::new(&config.replication, effect_builder, highest_stored_height)?;
+            effects.extend(reactor::wrap_effects(
+                Event::ReplicationClient,
+                replication_effects,
+            ));
+            Some(replication_client)
+        } else {
+            None
+        };
+        let replication_server = if sidecar {
+            ReplicationServer::new(config.replication.clone(), effect_builder)?
+        } else {
+            None
+        };
 
         Ok((
             Self {
@@ -450,12 +574,17 @@ impl reactor::Reactor for Reactor {
                 block_executor,
                 linear_chain,
                 consensus,
+                consensus_stub: ConsensusStub::new(),
                 init_consensus_effects,
                 block_by_height_fetcher,
                 deploy_acceptor,
+                verification_pool,
                 event_queue_metrics,
                 rest_server,
                 event_stream_server,
+                rpc_server,
+                replication_client,
+                replication_server,
             },
             effects,
         ))
@@ -480,6 +609,23 @@ impl reactor::Reactor for Reactor {
                     linear_chain_sync::Event::NewPeerConnected(id),
                 ),
             ),
+            Event::ReplicationClient(event) => match self.replication_client.as_mut() {
+                Some(replication_client) => reactor::wrap_effects(
+                    Event::ReplicationClient,
+                    replication_client.handle_event(effect_builder, rng, event),
+                ),
+                None => {
+                    error!(
+                        "Ignoring replication client event {} without a primary",
+                        event
+                    );
+                    Effects::new()
+                }
+            },
+            Event::NetworkAnnouncement(NetworkAnnouncement::ClockDrift { .. }) => {
+                // The network component has already warned; we are not validating while joining.
+                Effects::new()
+            }
             Event::NetworkAnnouncement(NetworkAnnouncement::GossipOurAddress(gossiped_address)) => {
                 let event = gossiper::Event::ItemReceived {
                     item_id: gossiped_address,
@@ -640,54 +786,68 @@ impl reactor::Reactor for Reactor {
                 self.contract_runtime
                     .handle_event(effect_builder, rng, event),
             ),
+            Event::BlockExecutorAnnouncement(BlockExecutorAnnouncement::DeployProcessed {
+                deploy_hash,
+                deploy_header,
+                block_height,
+                execution_result,
+            }) => {
+                let reactor_event =
+                    Event::EventStreamServer(event_stream_server::Event::DeployProcessed {
+                        deploy_hash,
+                        deploy_header,
+                        block_height,
+                        execution_result,
+                    });
+                self.dispatch_event(effect_builder, rng, reactor_event)
+            }
             Event::BlockExecutorAnnouncement(BlockExecutorAnnouncement::LinearChainBlock {
                 block,
                 execution_results,
             }) => {
-                let mut effects = Effects::new();
-                let block_hash = *block.hash();
-
-                // send to linear chain
+                // send to linear chain, the event stream having been sent each deploy as executed
                 let reactor_event = Event::LinearChain(linear_chain::Event::LinearChainBlock {
                     block: Box::new(block),
                     execution_results: execution_results
-                        .iter()
-                        .map(|(hash, (_header, results))| (*hash, results.clone()))
+                        .into_iter()
+                        .map(|(hash, (_header, results))| (hash, results))
                         .collect(),
                 });
-                effects.extend(self.dispatch_event(effect_builder, rng, reactor_event));
-
-                // send to event stream
-                for (deploy_hash, (deploy_header, execution_result)) in execution_results {
-                    let reactor_event =
-                        Event::EventStreamServer(event_stream_server::Event::DeployProcessed {
-                            deploy_hash,
-                            deploy_header: Box::new(deploy_header),
-                            block_hash,
-                            execution_result: Box::new(execution_result),
-                        });
-                    effects.extend(self.dispatch_event(effect_builder, rng, reactor_event));
-                }
-
-                effects
+                self.dispatch_event(effect_builder, rng, reactor_event)
             }
             Event::LinearChain(event) => reactor::wrap_effects(
                 Event::LinearChain,
                 self.linear_chain.handle_event(effect_builder, rng, event),
             ),
-            Event::Consensus(event) => reactor::wrap_effects(
-                Event::Consensus,
-                self.consensus.handle_event(effect_builder, rng, event),
-            ),
-            Event::ConsensusAnnouncement(announcement) => match announcement {
-                ConsensusAnnouncement::Handled(block_header) => reactor::wrap_effects(
-                    Event::LinearChainSync,
-                    self.linear_chain_sync.handle_event(
-                        effect_builder,
-                        rng,
-                        linear_chain_sync::Event::BlockHandled(block_header),
-                    ),
+            Event::Consensus(event) => match self.consensus.as_mut() {
+                Some(consensus) => reactor::wrap_effects(
+                    Event::Consensus,
+                    consensus.handle_event(effect_builder, rng, event),
+                ),
+                None => reactor::wrap_effects(
+                    Event::Consensus,
+                    self.consensus_stub.handle_event(effect_builder, rng, event),
                 ),
+            },
+            Event::ConsensusAnnouncement(announcement) => match announcement {
+                ConsensusAnnouncement::Handled(block_header) => {
+                    if self.replication_client.is_some() {
+                        let event = replication::Event::BlockHandled(block_header);
+                        return self.dispatch_event(
+                            effect_builder,
+                            rng,
+                            Event::ReplicationClient(event),
+                        );
+                    }
+                    reactor::wrap_effects(
+                        Event::LinearChainSync,
+                        self.linear_chain_sync.handle_event(
+                            effect_builder,
+                            rng,
+                            linear_chain_sync::Event::BlockHandled(block_header),
+                        ),
+                    )
+                }
                 ConsensusAnnouncement::Finalized(block) => reactor::wrap_effects(
                     Event::EventStreamServer,
                     self.event_stream_server.handle_event(
@@ -748,6 +908,81 @@ impl reactor::Reactor for Reactor {
                 self.event_stream_server
                     .handle_event(effect_builder, rng, event),
             ),
+            Event::RpcServer(event) => match self.rpc_server.as_mut() {
+                Some(rpc_server) => reactor::wrap_effects(
+                    Event::RpcServer,
+                    rpc_server.handle_event(effect_builder, rng, event),
+                ),
+                None => {
+                    error!("Ignoring rpc server event {} outside sidecar mode", event);
+                    Effects::new()
+                }
+            },
+            Event::RpcServerAnnouncement(RpcServerAnnouncement::DeployReceived { deploy }) => {
+                // The RPC server refuses deploys in sidecar mode.
+                warn!(deploy_hash = %deploy.id(), "Ignoring deploy received by rpc server");
+                Effects::new()
+            }
+            Event::RpcServerAnnouncement(RpcServerAnnouncement::AdminCommand(command)) => {
+                match command {
+                    AdminCommand::ConnectPeer(peer_address) => {
+                        let event = small_network::Event::ConnectPeer(peer_address);
+                        self.dispatch_event(effect_builder, rng, Event::Network(event))
+                    }
+                    AdminCommand::DisconnectPeer(peer_address) => {
+                        let event = small_network::Event::DisconnectPeer(peer_address);
+                        self.dispatch_event(effect_builder, rng, Event::Network(event))
+                    }
+                    AdminCommand::BanPeer(peer_address) => {
+                        let event = small_network::Event::BanPeer(peer_address);
+                        self.dispatch_event(effect_builder, rng, Event::Network(event))
+                    }
+                    AdminCommand::SetNetworkTimeouts(timeouts) => {
+                        let mut effects = Effects::new();
+                        if let Some(interval) = timeouts.address_gossip_interval {
+                            let event = small_network::Event::SetGossipInterval(interval);
+                            effects.extend(self.dispatch_event(
+                                effect_builder,
+                                rng,
+                                Event::Network(event),
+                            ));
+                        }
+                        if timeouts.gossip_request_timeout.is_some()
+                            || timeouts.get_remainder_timeout.is_some()
+                        {
+                            let event = gossiper::Event::SetTimeouts {
+                                gossip_timeout: timeouts.gossip_request_timeout,
+                                get_from_peer_timeout: timeouts.get_remainder_timeout,
+                            };
+                            effects.extend(self.dispatch_event(
+                                effect_builder,
+                                rng,
+                                Event::AddressGossiper(event),
+                            ));
+                        }
+                        if let Some(timeout) = timeouts.fetch_timeout {
+                            effects.extend(self.dispatch_event(
+                                effect_builder,
+                                rng,
+                                Event::BlockFetcher(fetcher::Event::SetPeerTimeout(timeout)),
+                            ));
+                            effects.extend(self.dispatch_event(
+                                effect_builder,
+                                rng,
+                                Event::BlockByHeightFetcher(fetcher::Event::SetPeerTimeout(
+                                    timeout,
+                                )),
+                            ));
+                            effects.extend(self.dispatch_event(
+                                effect_builder,
+                                rng,
+                                Event::DeployFetcher(fetcher::Event::SetPeerTimeout(timeout)),
+                            ));
+                        }
+                        effects
+                    }
+                }
+            }
             Event::MetricsRequest(req) => reactor::wrap_effects(
                 Event::MetricsRequest,
                 self.metrics.handle_event(effect_builder, rng, req),
@@ -760,6 +995,11 @@ impl reactor::Reactor for Reactor {
             Event::ChainspecLoaderRequest(req) => {
                 self.dispatch_event(effect_builder, rng, Event::ChainspecLoader(req.into()))
             }
+            Event::SyncProgressRequest(SyncProgressRequest::GetSyncProgress(responder)) => {
+                responder
+                    .respond(self.linear_chain_sync.progress())
+                    .ignore()
+            }
             Event::NetworkInfoRequest(req) => self.dispatch_event(
                 effect_builder,
                 rng,
@@ -769,7 +1009,8 @@ impl reactor::Reactor for Reactor {
     }
 
     fn is_stopped(&mut self) -> bool {
-        self.linear_chain_sync.is_synced()
+        // A replica keeps following its primary until the node is stopped.
+        self.replication_client.is_none() && self.linear_chain_sync.is_synced()
     }
 
     fn update_metrics(&mut self, event_queue_handle: EventQueueHandle<Self::Event>) {
@@ -793,15 +1034,87 @@ impl Reactor {
                 config: self.config,
                 contract_runtime: self.contract_runtime,
                 storage: self.storage,
-                consensus: self.consensus,
+                consensus: self
+                    .consensus
+                    .expect("should have consensus component outside sidecar mode"),
                 init_consensus_effects: self.init_consensus_effects,
                 linear_chain: self.linear_chain.linear_chain().clone(),
                 block_proposer_state,
                 event_stream_server: self.event_stream_server,
+                verification_pool: self.verification_pool,
             },
         );
         net.finalize().await;
         rest_server.finalize().await;
         config
     }
+
+    /// Shuts down the network and the API servers of a node in sidecar mode, which never becomes
+    /// a validator.
+    pub async fn shut_down_sidecar(mut self) {
+        if let Some(mut rpc_server) = self.rpc_server.take() {
+            rpc_server.shut_down().await;
+        }
+        if let Some(mut replication_server) = self.replication_server.take() {
+            replication_server.shut_down().await;
+        }
+        self.event_stream_server.shut_down().await;
+        self.net.finalize().await;
+        self.rest_server.finalize().await;
+    }
+}
+
+/// Returns the hash of the block up to which the linear chain is synchronized: that of the latest
+/// of the configured trusted checkpoints whose finality certificate can be verified, or the
+/// configured trusted hash if there are no checkpoints.
+///
+/// A certificate is verified against the weights of the validators of the checkpoint's era as
+/// recorded in the global state of the highest stored block or, failing that, of Genesis.
+/// Checkpoints of eras whose validators are recorded in neither are skipped.
+fn trusted_hash(
+    node_config: &NodeConfig,
+    storage: &Storage,
+    contract_runtime: &ContractRuntime,
+    genesis_state_root_hash: Digest,
+) -> Result<Option<BlockHash>, Error> {
+    if node_config.trusted_checkpoints.is_empty() {
+        return Ok(node_config.trusted_hash);
+    }
+    if node_config.trusted_hash.is_some() {
+        warn!("ignoring the trusted hash in favor of the trusted checkpoints");
+    }
+
+    let mut state_root_hashes = vec![];
+    if let Some(block) = storage.read_highest_block()? {
+        state_root_hashes.push(*block.state_root_hash());
+    }
+    state_root_hashes.push(genesis_state_root_hash);
+
+    for checkpoint in node_config.trusted_checkpoints.iter().rev() {
+        let maybe_validator_weights = state_root_hashes.iter().find_map(|state_root_hash| {
+            contract_runtime
+                .read_validator_weights(*state_root_hash, checkpoint.era_id)
+                .unwrap_or_else(|error| {
+                    warn!(%error, %state_root_hash, "failed to read validator weights");
+                    None
+                })
+        });
+        match maybe_validator_weights {
+            Some(validator_weights) => {
+                checkpoint.verify(&validator_weights)?;
+                info!(
+                    block_hash = %checkpoint.block_hash,
+                    era_id = ?checkpoint.era_id,
+                    "verified trusted checkpoint"
+                );
+                return Ok(Some(checkpoint.block_hash));
+            }
+            None => warn!(
+                block_hash = %checkpoint.block_hash,
+                era_id = ?checkpoint.era_id,
+                "validators of the trusted checkpoint's era unknown, skipping it"
+            ),
+        }
+    }
+    Err(CheckpointError::NoneVerifiable.into())
 }
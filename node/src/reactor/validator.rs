@@ -11,18 +11,22 @@ mod tests;
 use std::{
     cmp,
     fmt::{self, Debug, Display, Formatter},
+    path::Path,
     str::FromStr,
+    sync::Arc,
 };
 
 use datasize::DataSize;
 use derive_more::From;
 use prometheus::Registry;
 use serde::Serialize;
-use tracing::{debug, error, warn};
+use tracing::{debug, error, info, warn};
+
+use casper_types::ProtocolVersion;
 
 use block_proposer::BlockProposerState;
 
-#[cfg(test)]
+#[cfg(any(feature = "testing", test))]
 use crate::testing::network::NetworkedReactor;
 use crate::{
     components::{
@@ -31,43 +35,48 @@ use crate::{
         block_validator::{self, BlockValidator},
         chainspec_loader::{self, ChainspecLoader},
         consensus::{self, EraSupervisor},
-        contract_runtime::{self, ContractRuntime},
+        contract_runtime::{self, ContractRuntime, ValidatorWeightsByEraIdRequest},
         deploy_acceptor::{self, DeployAcceptor},
         event_stream_server::{self, EventStreamServer},
         fetcher::{self, Fetcher},
+        finality_monitor::{self, FinalityMonitor},
         gossiper::{self, Gossiper},
         linear_chain,
         metrics::Metrics,
+        replication::ReplicationServer,
+        resource_monitor::{self, MonitoredEnvironment, ResourceMonitor},
         rest_server::{self, RestServer},
-        rpc_server::{self, RpcServer},
+        rpc_server::{self, rpcs::admin::AdminCommand, RpcServer},
         small_network::{self, GossipedAddress, SmallNetwork},
         storage::{self, Storage},
+        telemetry::{self, Telemetry},
+        webhooks::{self, Webhooks},
         Component,
     },
     effect::{
         announcements::{
             BlockExecutorAnnouncement, ConsensusAnnouncement, DeployAcceptorAnnouncement,
-            GossiperAnnouncement, LinearChainAnnouncement, NetworkAnnouncement,
-            RpcServerAnnouncement,
+            FinalityMonitorAnnouncement, GossiperAnnouncement, LinearChainAnnouncement,
+            NetworkAnnouncement, ResourceMonitorAnnouncement, RpcServerAnnouncement,
         },
         requests::{
             BlockExecutorRequest, BlockProposerRequest, BlockValidationRequest,
             ChainspecLoaderRequest, ConsensusRequest, ContractRuntimeRequest, FetcherRequest,
             LinearChainRequest, MetricsRequest, NetworkInfoRequest, NetworkRequest, RestRequest,
-            RpcRequest, StorageRequest,
+            RpcRequest, StorageRequest, SyncProgressRequest,
         },
         EffectBuilder, EffectExt, Effects,
     },
     protocol::Message,
     reactor::{self, event_queue_metrics::EventQueueMetrics, EventQueueHandle},
-    types::{Block, Deploy, NodeId, ProtoBlock, Tag, TimeDiff, Timestamp},
-    utils::Source,
+    types::{Block, Deploy, NodeId, ProtoBlock, SyncProgress, SyncState, Tag, TimeDiff, Timestamp},
+    utils::{Source, VerificationPool},
     NodeRng,
 };
-pub use config::Config;
+pub use config::{Config, MemoryLimitsConfig};
 pub use error::Error;
 use linear_chain::LinearChain;
-use memory_metrics::MemoryMetrics;
+use memory_metrics::{MemoryMetrics, MemoryUsage};
 
 /// Top-level event for the reactor.
 #[derive(Debug, From, Serialize)]
@@ -121,6 +130,18 @@ pub enum Event {
     /// Linear chain event.
     #[from]
     LinearChain(#[serde(skip_serializing)] linear_chain::Event<NodeId>),
+    /// Finality monitor event.
+    #[from]
+    FinalityMonitor(#[serde(skip_serializing)] finality_monitor::Event),
+    /// Resource monitor event.
+    #[from]
+    ResourceMonitor(#[serde(skip_serializing)] resource_monitor::Event),
+    /// Webhooks event.
+    #[from]
+    Webhooks(#[serde(skip_serializing)] webhooks::Event),
+    /// Telemetry event.
+    #[from]
+    Telemetry(#[serde(skip_serializing)] telemetry::Event),
 
     // Requests
     /// Network request.
@@ -149,6 +170,9 @@ pub enum Event {
     /// Chainspec info request
     #[from]
     ChainspecLoaderRequest(#[serde(skip_serializing)] ChainspecLoaderRequest),
+    /// Sync progress request.
+    #[from]
+    SyncProgressRequest(#[serde(skip_serializing)] SyncProgressRequest),
 
     // Announcements
     /// Network announcement.
@@ -175,6 +199,17 @@ pub enum Event {
     /// Linear chain announcement.
     #[from]
     LinearChainAnnouncement(#[serde(skip_serializing)] LinearChainAnnouncement),
+    /// Finality monitor announcement.
+    #[from]
+    FinalityMonitorAnnouncement(#[serde(skip_serializing)] FinalityMonitorAnnouncement),
+    /// Resource monitor announcement.
+    #[from]
+    ResourceMonitorAnnouncement(#[serde(skip_serializing)] ResourceMonitorAnnouncement),
+
+    /// Termination of the node has been requested.
+    TerminationRequested,
+    /// One of the API servers has finished shutting down.
+    ApiServerShutDown,
 }
 
 impl From<StorageRequest> for Event {
@@ -250,6 +285,10 @@ impl Display for Event {
             Event::BlockExecutor(event) => write!(f, "block executor: {}", event),
             Event::LinearChain(event) => write!(f, "linear-chain event {}", event),
             Event::ProtoBlockValidator(event) => write!(f, "block validator: {}", event),
+            Event::FinalityMonitor(event) => write!(f, "finality monitor: {}", event),
+            Event::ResourceMonitor(event) => write!(f, "resource monitor: {}", event),
+            Event::Webhooks(event) => write!(f, "webhooks: {}", event),
+            Event::Telemetry(event) => write!(f, "telemetry: {}", event),
             Event::NetworkRequest(req) => write!(f, "network request: {}", req),
             Event::NetworkInfoRequest(req) => write!(f, "network info request: {}", req),
             Event::ChainspecLoaderRequest(req) => write!(f, "chainspec loader request: {}", req),
@@ -258,6 +297,7 @@ impl Display for Event {
             Event::BlockExecutorRequest(req) => write!(f, "block executor request: {}", req),
             Event::ProtoBlockValidatorRequest(req) => write!(f, "block validator request: {}", req),
             Event::MetricsRequest(req) => write!(f, "metrics request: {}", req),
+            Event::SyncProgressRequest(req) => write!(f, "sync progress request: {}", req),
             Event::NetworkAnnouncement(ann) => write!(f, "network announcement: {}", ann),
             Event::RpcServerAnnouncement(ann) => write!(f, "api server announcement: {}", ann),
             Event::DeployAcceptorAnnouncement(ann) => {
@@ -274,6 +314,14 @@ impl Display for Event {
                 write!(f, "address gossiper announcement: {}", ann)
             }
             Event::LinearChainAnnouncement(ann) => write!(f, "linear chain announcement: {}", ann),
+            Event::FinalityMonitorAnnouncement(ann) => {
+                write!(f, "finality monitor announcement: {}", ann)
+            }
+            Event::ResourceMonitorAnnouncement(ann) => {
+                write!(f, "resource monitor announcement: {}", ann)
+            }
+            Event::TerminationRequested => write!(f, "termination requested"),
+            Event::ApiServerShutDown => write!(f, "api server shut down"),
         }
     }
 }
@@ -289,6 +337,7 @@ pub struct ValidatorInitConfig {
     pub(super) linear_chain: Vec<Block>,
     pub(super) block_proposer_state: BlockProposerState,
     pub(super) event_stream_server: EventStreamServer,
+    pub(super) verification_pool: VerificationPool,
 }
 
 /// Validator node reactor.
@@ -312,15 +361,84 @@ pub struct Reactor {
     block_executor: BlockExecutor,
     proto_block_validator: BlockValidator<ProtoBlock, NodeId>,
     linear_chain: LinearChain<NodeId>,
+    finality_monitor: FinalityMonitor,
+    resource_monitor: ResourceMonitor,
+    webhooks: Webhooks,
+    telemetry: Telemetry,
 
     // Non-components.
+    #[data_size(skip)]
+    replication_server: Option<ReplicationServer>,
     #[data_size(skip)] // Never allocates heap data.
     memory_metrics: MemoryMetrics,
+    memory_limits: MemoryLimitsConfig,
+
+    /// The number of API servers yet to shut down, once termination has been requested.
+    api_servers_shutting_down: Option<usize>,
 
     #[data_size(skip)]
     event_queue_metrics: EventQueueMetrics,
 }
 
+impl Reactor {
+    /// Sheds data held by components whose estimated heap memory usage exceeds their soft limit.
+    fn shed_memory(&mut self, usage: &MemoryUsage) {
+        let limits = self.memory_limits;
+        let mut shed = 0;
+
+        if MemoryLimitsConfig::is_exceeded(limits.deploy_gossiper, usage.deploy_gossiper) {
+            let count = self
+                .deploy_gossiper
+                .shed_load(usage.deploy_gossiper, limits.deploy_gossiper);
+            warn!(
+                usage = usage.deploy_gossiper,
+                limit = limits.deploy_gossiper,
+                %count,
+                "deploy gossiper exceeds its memory limit, shed deploys and answering peers as busy"
+            );
+            shed += count;
+        } else {
+            self.deploy_gossiper.relieve_memory_pressure();
+        }
+
+        if MemoryLimitsConfig::is_exceeded(limits.address_gossiper, usage.address_gossiper) {
+            let count = self
+                .address_gossiper
+                .shed_load(usage.address_gossiper, limits.address_gossiper);
+            warn!(
+                usage = usage.address_gossiper,
+                limit = limits.address_gossiper,
+                %count,
+                "address gossiper exceeds its memory limit, shed addresses and answering peers as \
+                busy"
+            );
+            shed += count;
+        } else {
+            self.address_gossiper.relieve_memory_pressure();
+        }
+
+        if MemoryLimitsConfig::is_exceeded(limits.block_proposer, usage.block_proposer) {
+            // Assuming the usage is proportional to the number of pending deploys, keep as many as
+            // fit within the limit.
+            let pending = self.block_proposer.pending_deploys() as u128;
+            let max_pending =
+                pending * u128::from(limits.block_proposer) / u128::from(usage.block_proposer);
+            let count = self
+                .block_proposer
+                .shed_pending_deploys(max_pending as usize);
+            warn!(
+                usage = usage.block_proposer,
+                limit = limits.block_proposer,
+                %count,
+                "block proposer exceeds its memory limit, dropped pending deploys"
+            );
+            shed += count;
+        }
+
+        self.memory_metrics.record_shed(shed);
+    }
+}
+
 #[cfg(test)]
 impl Reactor {
     /// Inspect consensus.
@@ -341,9 +459,7 @@ impl reactor::Reactor for Reactor {
         config: Self::Config,
         registry: &Registry,
         event_queue: EventQueueHandle<Self::Event>,
-        // We don't need `rng` b/c consensus component was the only one using it,
-        // and now it's being passed on from the `joiner` reactor via `config`.
-        _rng: &mut NodeRng,
+        rng: &mut NodeRng,
     ) -> Result<(Self, Effects<Event>), Error> {
         let ValidatorInitConfig {
             config,
@@ -355,6 +471,7 @@ impl reactor::Reactor for Reactor {
             linear_chain,
             block_proposer_state,
             event_stream_server,
+            verification_pool,
         } = config;
 
         let memory_metrics = MemoryMetrics::new(registry.clone())?;
@@ -364,15 +481,19 @@ impl reactor::Reactor for Reactor {
         let metrics = Metrics::new(registry.clone());
 
         let effect_builder = EffectBuilder::new(event_queue);
-        let (net, net_effects) = SmallNetwork::new(event_queue, config.network, true)?;
+        let (net, net_effects) = SmallNetwork::new(event_queue, config.network, registry, true)?;
 
         let address_gossiper =
             Gossiper::new_for_complete_items("address_gossiper", config.gossip, registry)?;
 
-        let rpc_server = RpcServer::new(config.rpc_server.clone(), effect_builder);
+        let mut rpc_server = RpcServer::new(config.rpc_server.clone(), effect_builder);
+        if config.storage.archive() {
+            rpc_server = rpc_server.with_archive_mode();
+        }
         let rest_server = RestServer::new(config.rest_server.clone(), effect_builder);
+        let replication_server = ReplicationServer::new(config.replication, effect_builder)?;
 
-        let deploy_acceptor = DeployAcceptor::new();
+        let deploy_acceptor = DeployAcceptor::new(verification_pool.clone());
         let deploy_fetcher = Fetcher::new(config.fetcher);
         let deploy_gossiper = Gossiper::new_for_partial_items(
             "deploy_gossiper",
@@ -380,17 +501,57 @@ impl reactor::Reactor for Reactor {
             gossiper::get_deploy_from_storage::<Deploy, Event>,
             registry,
         )?;
-        let (block_proposer, block_proposer_effects) =
-            BlockProposer::new(registry.clone(), effect_builder, block_proposer_state)?;
+        let (block_proposer, block_proposer_effects) = BlockProposer::new(
+            registry.clone(),
+            effect_builder,
+            config.block_proposer,
+            block_proposer_state,
+        )?;
         let mut effects = reactor::wrap_effects(Event::BlockProposer, block_proposer_effects);
         // Post state hash is expected to be present.
         let genesis_state_root_hash = chainspec_loader
             .genesis_state_root_hash()
             .expect("should have state root hash");
-        let block_executor = BlockExecutor::new(genesis_state_root_hash)
+        let (finality_monitor, finality_monitor_effects) = FinalityMonitor::new(
+            config.finality_monitor,
+            registry,
+            effect_builder,
+            linear_chain.last().map(Block::header),
+        )?;
+        effects.extend(reactor::wrap_effects(
+            Event::FinalityMonitor,
+            finality_monitor_effects,
+        ));
+        let other_data_dirs = consensus
+            .unit_log_dir()
+            .map(Path::to_path_buf)
+            .into_iter()
+            .collect();
+        let storage_environment: Arc<dyn MonitoredEnvironment> = storage.lmdb_environment();
+        let global_state_environment: Arc<dyn MonitoredEnvironment> =
+            contract_runtime.lmdb_environment();
+        let (resource_monitor, resource_monitor_effects) = ResourceMonitor::new(
+            config.resource_monitor,
+            registry,
+            effect_builder,
+            storage.root_path().to_path_buf(),
+            other_data_dirs,
+            vec![
+                ("storage", storage_environment),
+                ("global state", global_state_environment),
+            ],
+        )?;
+        effects.extend(reactor::wrap_effects(
+            Event::ResourceMonitor,
+            resource_monitor_effects,
+        ));
+        let webhooks = Webhooks::new(config.webhooks)?;
+        let (telemetry, telemetry_effects) = Telemetry::new(config.telemetry, effect_builder, rng)?;
+        effects.extend(reactor::wrap_effects(Event::Telemetry, telemetry_effects));
+        let block_executor = BlockExecutor::new(genesis_state_root_hash, registry)?
             .with_parent_map(linear_chain.last().cloned());
         let proto_block_validator = BlockValidator::new();
-        let linear_chain = LinearChain::new();
+        let linear_chain = LinearChain::new(config.linear_chain, verification_pool);
 
         effects.extend(reactor::wrap_effects(Event::Network, net_effects));
         effects.extend(reactor::wrap_effects(
@@ -417,6 +578,12 @@ impl reactor::Reactor for Reactor {
                 .event(|_| consensus::Event::Shutdown),
         ));
 
+        effects.extend(
+            effect_builder
+                .termination_requested()
+                .event(|_| Event::TerminationRequested),
+        );
+
         Ok((
             Reactor {
                 metrics,
@@ -436,7 +603,14 @@ impl reactor::Reactor for Reactor {
                 block_executor,
                 proto_block_validator,
                 linear_chain,
+                finality_monitor,
+                resource_monitor,
+                webhooks,
+                telemetry,
+                replication_server,
                 memory_metrics,
+                memory_limits: config.memory_limits,
+                api_servers_shutting_down: None,
                 event_queue_metrics,
             },
             effects,
@@ -521,6 +695,24 @@ impl reactor::Reactor for Reactor {
                 Event::LinearChain,
                 self.linear_chain.handle_event(effect_builder, rng, event),
             ),
+            Event::FinalityMonitor(event) => reactor::wrap_effects(
+                Event::FinalityMonitor,
+                self.finality_monitor
+                    .handle_event(effect_builder, rng, event),
+            ),
+            Event::ResourceMonitor(event) => reactor::wrap_effects(
+                Event::ResourceMonitor,
+                self.resource_monitor
+                    .handle_event(effect_builder, rng, event),
+            ),
+            Event::Webhooks(event) => reactor::wrap_effects(
+                Event::Webhooks,
+                self.webhooks.handle_event(effect_builder, rng, event),
+            ),
+            Event::Telemetry(event) => reactor::wrap_effects(
+                Event::Telemetry,
+                self.telemetry.handle_event(effect_builder, rng, event),
+            ),
 
             // Requests:
             Event::NetworkRequest(req) => self.dispatch_event(
@@ -556,6 +748,12 @@ impl reactor::Reactor for Reactor {
             Event::ChainspecLoaderRequest(req) => {
                 self.dispatch_event(effect_builder, rng, Event::ChainspecLoader(req.into()))
             }
+            Event::SyncProgressRequest(SyncProgressRequest::GetSyncProgress(responder)) => {
+                // The linear chain has been synchronized by the time this reactor is running.
+                responder
+                    .respond(SyncProgress::new(SyncState::Validating))
+                    .ignore()
+            }
 
             // Announcements:
             Event::NetworkAnnouncement(NetworkAnnouncement::MessageReceived {
@@ -683,6 +881,16 @@ impl reactor::Reactor for Reactor {
                 debug!(%peer_id, "new peer announcement event ignored (validator reactor does not care)");
                 Effects::new()
             }
+            Event::NetworkAnnouncement(NetworkAnnouncement::ClockDrift {
+                median_offset,
+                exceeds_bound,
+            }) => {
+                let event = consensus::Event::ClockDrift {
+                    median_offset,
+                    exceeds_bound,
+                };
+                self.dispatch_event(effect_builder, rng, Event::Consensus(event))
+            }
             Event::RpcServerAnnouncement(RpcServerAnnouncement::DeployReceived { deploy }) => {
                 let event = deploy_acceptor::Event::Accept {
                     deploy,
@@ -690,6 +898,69 @@ impl reactor::Reactor for Reactor {
                 };
                 self.dispatch_event(effect_builder, rng, Event::DeployAcceptor(event))
             }
+            Event::RpcServerAnnouncement(RpcServerAnnouncement::AdminCommand(command)) => {
+                match command {
+                    AdminCommand::ConnectPeer(peer_address) => {
+                        let event = small_network::Event::ConnectPeer(peer_address);
+                        self.dispatch_event(effect_builder, rng, Event::Network(event))
+                    }
+                    AdminCommand::DisconnectPeer(peer_address) => {
+                        let event = small_network::Event::DisconnectPeer(peer_address);
+                        self.dispatch_event(effect_builder, rng, Event::Network(event))
+                    }
+                    AdminCommand::BanPeer(peer_address) => {
+                        let event = small_network::Event::BanPeer(peer_address);
+                        self.dispatch_event(effect_builder, rng, Event::Network(event))
+                    }
+                    AdminCommand::SetNetworkTimeouts(timeouts) => {
+                        let mut effects = Effects::new();
+
+                        if let Some(interval) = timeouts.address_gossip_interval {
+                            let event = small_network::Event::SetGossipInterval(interval);
+                            effects.extend(self.dispatch_event(
+                                effect_builder,
+                                rng,
+                                Event::Network(event),
+                            ));
+                        }
+
+                        if timeouts.gossip_request_timeout.is_some()
+                            || timeouts.get_remainder_timeout.is_some()
+                        {
+                            let event = gossiper::Event::SetTimeouts {
+                                gossip_timeout: timeouts.gossip_request_timeout,
+                                get_from_peer_timeout: timeouts.get_remainder_timeout,
+                            };
+                            effects.extend(self.dispatch_event(
+                                effect_builder,
+                                rng,
+                                Event::DeployGossiper(event),
+                            ));
+
+                            let event = gossiper::Event::SetTimeouts {
+                                gossip_timeout: timeouts.gossip_request_timeout,
+                                get_from_peer_timeout: timeouts.get_remainder_timeout,
+                            };
+                            effects.extend(self.dispatch_event(
+                                effect_builder,
+                                rng,
+                                Event::AddressGossiper(event),
+                            ));
+                        }
+
+                        if let Some(timeout) = timeouts.fetch_timeout {
+                            let event = fetcher::Event::SetPeerTimeout(timeout);
+                            effects.extend(self.dispatch_event(
+                                effect_builder,
+                                rng,
+                                Event::DeployFetcher(event),
+                            ));
+                        }
+
+                        effects
+                    }
+                }
+            }
             Event::DeployAcceptorAnnouncement(DeployAcceptorAnnouncement::AcceptedNewDeploy {
                 deploy,
                 source,
@@ -697,6 +968,7 @@ impl reactor::Reactor for Reactor {
                 let event = block_proposer::Event::Buffer {
                     hash: *deploy.id(),
                     header: Box::new(deploy.header().clone()),
+                    footprint: block_proposer::DeployFootprint::new(&deploy),
                 };
                 let mut effects =
                     self.dispatch_event(effect_builder, rng, Event::BlockProposer(event));
@@ -734,12 +1006,23 @@ impl reactor::Reactor for Reactor {
 
                 match consensus_announcement {
                     ConsensusAnnouncement::Proposed(block) => {
-                        reactor_event_dispatch(block_proposer::Event::ProposedProtoBlock(block))
+                        let mut effects = reactor_event_dispatch(
+                            block_proposer::Event::ProposedProtoBlock(block.clone()),
+                        );
+                        let reactor_event = Event::FinalityMonitor(
+                            finality_monitor::Event::ProtoBlockProposed(block),
+                        );
+                        effects.extend(self.dispatch_event(effect_builder, rng, reactor_event));
+                        effects
                     }
                     ConsensusAnnouncement::Finalized(block) => {
                         let mut effects = reactor_event_dispatch(
                             block_proposer::Event::FinalizedProtoBlock(block.proto_block().clone()),
                         );
+                        let reactor_event = Event::FinalityMonitor(
+                            finality_monitor::Event::BlockFinalized(block.clone()),
+                        );
+                        effects.extend(self.dispatch_event(effect_builder, rng, reactor_event));
                         let reactor_event = Event::EventStreamServer(
                             event_stream_server::Event::BlockFinalized(block),
                         );
@@ -755,6 +1038,21 @@ impl reactor::Reactor for Reactor {
                     }
                 }
             }
+            Event::BlockExecutorAnnouncement(BlockExecutorAnnouncement::DeployProcessed {
+                deploy_hash,
+                deploy_header,
+                block_height,
+                execution_result,
+            }) => {
+                let reactor_event =
+                    Event::EventStreamServer(event_stream_server::Event::DeployProcessed {
+                        deploy_hash,
+                        deploy_header,
+                        block_height,
+                        execution_result,
+                    });
+                self.dispatch_event(effect_builder, rng, reactor_event)
+            }
             Event::BlockExecutorAnnouncement(BlockExecutorAnnouncement::LinearChainBlock {
                 block,
                 execution_results,
@@ -772,15 +1070,14 @@ impl reactor::Reactor for Reactor {
                 });
                 effects.extend(self.dispatch_event(effect_builder, rng, reactor_event));
 
-                // send to event stream
+                // send to webhooks, the event stream having been sent each deploy as executed
                 for (deploy_hash, (deploy_header, execution_result)) in execution_results {
-                    let reactor_event =
-                        Event::EventStreamServer(event_stream_server::Event::DeployProcessed {
-                            deploy_hash,
-                            deploy_header: Box::new(deploy_header),
-                            block_hash,
-                            execution_result: Box::new(execution_result),
-                        });
+                    let reactor_event = Event::Webhooks(webhooks::Event::DeployProcessed {
+                        deploy_hash,
+                        deploy_header: Box::new(deploy_header),
+                        block_hash,
+                        execution_result: Box::new(execution_result),
+                    });
                     effects.extend(self.dispatch_event(effect_builder, rng, reactor_event));
                 }
 
@@ -799,24 +1096,213 @@ impl reactor::Reactor for Reactor {
                 block_hash,
                 block_header,
             }) => {
+                let reactor_event = Event::Webhooks(webhooks::Event::BlockAdded {
+                    block_hash,
+                    block_header: block_header.clone(),
+                });
+                let mut effects = self.dispatch_event(effect_builder, rng, reactor_event);
+
+                // The weights of the next era's validators are read from the switch block's global
+                // state before announcing the end of the era on the event stream.
+                if block_header.switch_block() {
+                    let request = ValidatorWeightsByEraIdRequest::new(
+                        (*block_header.state_root_hash()).into(),
+                        block_header.era_id().successor(),
+                        ProtocolVersion::V1_0_0,
+                    );
+                    let switch_block_header = block_header.clone();
+                    effects.extend(
+                        effect_builder
+                            .get_validator_weights_by_era_id(request)
+                            .event(move |result| {
+                                Event::EventStreamServer(event_stream_server::Event::EraEnd {
+                                    block_hash,
+                                    block_header: switch_block_header,
+                                    next_era_validator_weights: result.ok().flatten(),
+                                })
+                            }),
+                    );
+
+                    // Unbonded amounts are paid out when the switch block is executed, so the
+                    // unbonding queue is compared with the one as of the preceding block.
+                    let era_id = block_header.era_id();
+                    let height = block_header.height();
+                    let state_root_hash = *block_header.state_root_hash();
+                    effects.extend(
+                        async move {
+                            let unbonding_purses_before = match height.checked_sub(1) {
+                                Some(parent_height) => {
+                                    match effect_builder.get_block_at_height(parent_height).await {
+                                        Some(parent) => {
+                                            effect_builder
+                                                .get_unbonding_purses(
+                                                    *parent.header().state_root_hash(),
+                                                )
+                                                .await
+                                        }
+                                        None => None,
+                                    }
+                                }
+                                None => None,
+                            };
+                            let unbonding_purses_after =
+                                effect_builder.get_unbonding_purses(state_root_hash).await;
+                            (unbonding_purses_before, unbonding_purses_after)
+                        }
+                        .event(
+                            move |(unbonding_purses_before, unbonding_purses_after)| {
+                                Event::EventStreamServer(
+                                    event_stream_server::Event::UnbondsProcessed {
+                                        block_hash,
+                                        era_id,
+                                        unbonding_purses_before,
+                                        unbonding_purses_after,
+                                    },
+                                )
+                            },
+                        ),
+                    );
+                }
+
                 let reactor_event =
                     Event::EventStreamServer(event_stream_server::Event::BlockAdded {
                         block_hash,
                         block_header,
                     });
+                effects.extend(self.dispatch_event(effect_builder, rng, reactor_event));
+                effects
+            }
+            Event::FinalityMonitorAnnouncement(announcement) => {
+                let mut effects = Effects::new();
+                let event = match announcement {
+                    FinalityMonitorAnnouncement::FinalizationLagExceeded {
+                        block_height,
+                        block_timestamp,
+                        lag,
+                    } => {
+                        let reactor_event = Event::Webhooks(webhooks::Event::FallingBehind {
+                            block_height,
+                            block_timestamp,
+                            lag,
+                        });
+                        effects.extend(self.dispatch_event(effect_builder, rng, reactor_event));
+                        event_stream_server::Event::FinalizationLagExceeded {
+                            block_height,
+                            block_timestamp,
+                            lag,
+                        }
+                    }
+                    FinalityMonitorAnnouncement::EraTransition { era_id, duration } => {
+                        event_stream_server::Event::EraTransition { era_id, duration }
+                    }
+                    FinalityMonitorAnnouncement::BlockReproposed(proto_block_hash) => {
+                        event_stream_server::Event::BlockReproposed(proto_block_hash)
+                    }
+                };
+                effects.extend(self.dispatch_event(
+                    effect_builder,
+                    rng,
+                    Event::EventStreamServer(event),
+                ));
+                effects
+            }
+            Event::ResourceMonitorAnnouncement(ResourceMonitorAnnouncement::ThresholdCrossed {
+                resource,
+                percent,
+                threshold_percent,
+            }) => {
+                let reactor_event = Event::EventStreamServer(
+                    event_stream_server::Event::ResourceThresholdCrossed {
+                        resource: resource.to_string(),
+                        percent,
+                        threshold_percent,
+                    },
+                );
                 self.dispatch_event(effect_builder, rng, reactor_event)
             }
+            Event::ResourceMonitorAnnouncement(ResourceMonitorAnnouncement::WritesPaused {
+                ..
+            }) => {
+                // Consensus keeps running, so that consensus messages are still relayed.
+                let mut effects = self.dispatch_event(
+                    effect_builder,
+                    rng,
+                    Event::BlockExecutor(block_executor::Event::Pause),
+                );
+                effects.extend(self.dispatch_event(
+                    effect_builder,
+                    rng,
+                    Event::DeployAcceptor(deploy_acceptor::Event::Pause),
+                ));
+                effects.extend(self.dispatch_event(
+                    effect_builder,
+                    rng,
+                    Event::RpcServer(rpc_server::Event::PauseDeploys),
+                ));
+                effects
+            }
+            Event::ResourceMonitorAnnouncement(ResourceMonitorAnnouncement::WritesResumed {
+                ..
+            }) => {
+                let mut effects = self.dispatch_event(
+                    effect_builder,
+                    rng,
+                    Event::RpcServer(rpc_server::Event::ResumeDeploys),
+                );
+                effects.extend(self.dispatch_event(
+                    effect_builder,
+                    rng,
+                    Event::DeployAcceptor(deploy_acceptor::Event::Resume),
+                ));
+                effects.extend(self.dispatch_event(
+                    effect_builder,
+                    rng,
+                    Event::BlockExecutor(block_executor::Event::Resume),
+                ));
+                effects
+            }
+            Event::TerminationRequested => {
+                info!("termination requested, shutting down API servers");
+                let mut shutdowns = vec![
+                    self.rpc_server.shut_down(),
+                    self.rest_server.shut_down(),
+                    self.event_stream_server.shut_down(),
+                ];
+                if let Some(replication_server) = self.replication_server.as_mut() {
+                    shutdowns.push(replication_server.shut_down());
+                }
+                self.api_servers_shutting_down = Some(shutdowns.len());
+                shutdowns
+                    .into_iter()
+                    .flat_map(|shutdown| shutdown.event(|_| Event::ApiServerShutDown))
+                    .collect()
+            }
+            Event::ApiServerShutDown => {
+                if let Some(remaining) = self.api_servers_shutting_down.as_mut() {
+                    *remaining = remaining.saturating_sub(1);
+                }
+                Effects::new()
+            }
         }
     }
 
+    fn is_stopped(&mut self) -> bool {
+        self.api_servers_shutting_down == Some(0)
+    }
+
+    fn shuts_down_on_termination(&self) -> bool {
+        true
+    }
+
     fn update_metrics(&mut self, event_queue_handle: EventQueueHandle<Self::Event>) {
-        self.memory_metrics.estimate(&self);
+        let usage = self.memory_metrics.estimate(&self);
+        self.shed_memory(&usage);
         self.event_queue_metrics
             .record_event_queue_counts(&event_queue_handle)
     }
 }
 
-#[cfg(test)]
+#[cfg(any(feature = "testing", test))]
 impl NetworkedReactor for Reactor {
     type NodeId = NodeId;
     fn node_id(&self) -> Self::NodeId {
@@ -2,9 +2,10 @@ use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    logging::LoggingConfig, types::NodeConfig, ConsensusConfig, ContractRuntimeConfig,
-    EventStreamServerConfig, FetcherConfig, GossipConfig, RestServerConfig, RpcServerConfig,
-    SmallNetworkConfig, StorageConfig,
+    logging::LoggingConfig, types::NodeConfig, BlockProposerConfig, ConsensusConfig,
+    ContractRuntimeConfig, EventStreamServerConfig, FetcherConfig, FinalityMonitorConfig,
+    GossipConfig, LinearChainConfig, ReplicationConfig, ResourceMonitorConfig, RestServerConfig,
+    RpcServerConfig, SmallNetworkConfig, StorageConfig, TelemetryConfig, WebhooksConfig,
 };
 
 /// Root configuration.
@@ -34,4 +35,45 @@ pub struct Config {
     pub fetcher: FetcherConfig,
     /// Contract runtime configuration.
     pub contract_runtime: ContractRuntimeConfig,
+    /// Linear chain configuration.
+    pub linear_chain: LinearChainConfig,
+    /// Block proposer configuration.
+    pub block_proposer: BlockProposerConfig,
+    /// Finality monitor configuration.
+    pub finality_monitor: FinalityMonitorConfig,
+    /// Resource monitor configuration.
+    pub resource_monitor: ResourceMonitorConfig,
+    /// Webhooks configuration.
+    pub webhooks: WebhooksConfig,
+    /// Telemetry configuration.
+    pub telemetry: TelemetryConfig,
+    /// Read replica configuration.
+    pub replication: ReplicationConfig,
+    /// Soft limits on the memory usage of components.
+    pub memory_limits: MemoryLimitsConfig,
+}
+
+/// Soft limits on the estimated heap memory usage of individual components, in bytes.
+///
+/// A component exceeding its limit sheds the data it can most easily do without.  A limit of 0
+/// disables it.
+#[derive(Clone, Copy, DataSize, Debug, Default, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct MemoryLimitsConfig {
+    /// Limit for the deploy gossiper, which forgets the deploys which have finished gossiping,
+    /// stops gossiping the oldest ones and answers peers gossiping new ones as busy.
+    pub deploy_gossiper: u64,
+    /// Limit for the address gossiper, which forgets the addresses which have finished gossiping,
+    /// stops gossiping the oldest ones and answers peers gossiping new ones as busy.
+    pub address_gossiper: u64,
+    /// Limit for the block proposer, which drops the pending deploys closest to expiry.
+    pub block_proposer: u64,
+}
+
+impl MemoryLimitsConfig {
+    /// Returns whether `usage` exceeds `limit`, unless the limit is disabled.
+    pub(super) fn is_exceeded(limit: u64, usage: u64) -> bool {
+        limit > 0 && usage > limit
+    }
 }
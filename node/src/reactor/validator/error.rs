@@ -1,6 +1,10 @@
 use thiserror::Error;
 
-use crate::components::{contract_runtime, small_network, storage};
+use crate::{
+    components::{contract_runtime, replication, small_network, storage, telemetry, webhooks},
+    types::CheckpointError,
+    utils::VerificationPoolError,
+};
 
 /// Error type returned by the validator reactor.
 #[derive(Debug, Error)]
@@ -25,6 +29,30 @@ pub enum Error {
     #[error("contract runtime config error: {0}")]
     ContractRuntime(#[from] contract_runtime::ConfigError),
 
+    /// `Webhooks` component error.
+    #[error("webhooks error: {0}")]
+    Webhooks(#[from] webhooks::Error),
+
+    /// `Telemetry` component error.
+    #[error("telemetry error: {0}")]
+    Telemetry(#[from] telemetry::Error),
+
+    /// Signature verification pool error.
+    #[error("signature verification pool error: {0}")]
+    VerificationPool(#[from] VerificationPoolError),
+
+    /// Replication error.
+    #[error("replication error: {0}")]
+    Replication(#[from] replication::Error),
+
+    /// None of the trusted checkpoints was accepted.
+    #[error("trusted checkpoint error: {0}")]
+    TrustedCheckpoint(#[from] CheckpointError),
+
+    /// Sidecar mode requires a block to follow the linear chain from.
+    #[error("sidecar mode requires a trusted hash or a previously synchronized block")]
+    SidecarWithoutTrustedHash,
+
     /// Failed to serialize data.
     #[error("serialization: {0}")]
     Serialization(#[source] bincode::ErrorKind),
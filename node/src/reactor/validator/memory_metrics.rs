@@ -1,9 +1,17 @@
 use datasize::DataSize;
-use prometheus::{self, Histogram, HistogramOpts, IntGauge, Registry};
+use prometheus::{self, Histogram, HistogramOpts, IntCounter, IntGauge, Registry};
 use tracing::debug;
 
 use super::Reactor;
 
+/// The estimated heap memory usage of the components with soft memory limits, in bytes.
+#[derive(Debug)]
+pub(super) struct MemoryUsage {
+    pub(super) deploy_gossiper: u64,
+    pub(super) address_gossiper: u64,
+    pub(super) block_proposer: u64,
+}
+
 /// Metrics for memory usage.
 #[derive(Debug)]
 pub(super) struct MemoryMetrics {
@@ -46,6 +54,9 @@ pub(super) struct MemoryMetrics {
     /// Histogram detailing how long it took to measure memory usage.
     mem_estimator_runtime_s: Histogram,
 
+    /// Number of items dropped by components exceeding their soft memory limit.
+    mem_shed_items: IntCounter,
+
     /// Instance of registry to unregister from when being dropped.
     registry: Registry,
 }
@@ -120,7 +131,13 @@ impl MemoryMetrics {
         registry.register(Box::new(mem_block_executor.clone()))?;
         registry.register(Box::new(mem_proto_block_validator.clone()))?;
         registry.register(Box::new(mem_linear_chain.clone()))?;
+        let mem_shed_items = IntCounter::new(
+            "mem_shed_items",
+            "number of items dropped by components exceeding their memory limit",
+        )?;
+
         registry.register(Box::new(mem_estimator_runtime_s.clone()))?;
+        registry.register(Box::new(mem_shed_items.clone()))?;
 
         Ok(MemoryMetrics {
             mem_total,
@@ -141,12 +158,15 @@ impl MemoryMetrics {
             mem_proto_block_validator,
             mem_linear_chain,
             mem_estimator_runtime_s,
+            mem_shed_items,
             registry,
         })
     }
 
     /// Estimates memory usage and updates metrics.
-    pub(super) fn estimate(&self, reactor: &Reactor) {
+    ///
+    /// Returns the usage of the components with soft memory limits.
+    pub(super) fn estimate(&self, reactor: &Reactor) -> MemoryUsage {
         let timer = self.mem_estimator_runtime_s.start_timer();
 
         let metrics = reactor.metrics.estimate_heap_size() as i64;
@@ -224,6 +244,17 @@ impl MemoryMetrics {
                %proto_block_validator,
                %linear_chain,
                "Collected new set of memory metrics.");
+
+        MemoryUsage {
+            deploy_gossiper: deploy_gossiper as u64,
+            address_gossiper: address_gossiper as u64,
+            block_proposer: block_proposer as u64,
+        }
+    }
+
+    /// Records that `count` items were dropped by components exceeding their memory limit.
+    pub(super) fn record_shed(&self, count: usize) {
+        self.mem_shed_items.inc_by(count as i64);
     }
 }
 
@@ -280,5 +311,8 @@ impl Drop for MemoryMetrics {
         self.registry
             .unregister(Box::new(self.mem_linear_chain.clone()))
             .expect("did not expect deregistering mem_linear_chain, to fail");
+        self.registry
+            .unregister(Box::new(self.mem_shed_items.clone()))
+            .expect("did not expect deregistering mem_shed_items, to fail");
     }
 }
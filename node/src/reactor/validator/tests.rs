@@ -1,16 +1,23 @@
-use std::{collections::HashSet, time::Duration};
+use std::{collections::HashSet, io, net::SocketAddr, time::Duration};
 
 use anyhow::bail;
 use rand::Rng;
+use serde_json::json;
 use tempfile::TempDir;
+use tokio::{net::TcpStream, time};
 
 use casper_execution_engine::{core::engine_state::genesis::GenesisAccount, shared::motes::Motes};
 use casper_types::U512;
 
 use crate::{
-    components::{consensus::EraId, small_network, storage},
+    components::{
+        consensus::EraId,
+        rpc_server::{self, rpcs::RPC_API_PATH},
+        small_network, storage,
+    },
     crypto::asymmetric_key::{PublicKey, SecretKey},
-    reactor::{initializer, joiner, validator, Runner},
+    effect::EffectExt,
+    reactor::{initializer, joiner, validator, Reactor, Runner},
     testing::{self, network::Network, ConditionCheckReactor, TestRng},
     types::Timestamp,
     utils::{External, Loadable, WithDir, RESOURCES_PATH},
@@ -21,6 +28,10 @@ struct TestChain {
     keys: Vec<SecretKey>,
     storages: Vec<TempDir>,
     chainspec: Chainspec,
+    /// The address of each validator's JSON-RPC server.
+    rpc_addresses: Vec<String>,
+    /// The address of each validator's event stream server.
+    event_stream_addresses: Vec<String>,
 }
 
 type Nodes = crate::testing::network::Nodes<validator::Reactor>;
@@ -58,6 +69,8 @@ impl TestChain {
             keys,
             chainspec,
             storages: Vec::new(),
+            rpc_addresses: Vec::new(),
+            event_stream_addresses: Vec::new(),
         }
     }
 
@@ -84,6 +97,14 @@ impl TestChain {
         self.storages.push(temp_dir);
         cfg.storage = storage_cfg;
 
+        // Bind the JSON-RPC and event stream servers to known ports, so tests can reach them.
+        cfg.rpc_server.address = format!("127.0.0.1:{}", testing::unused_port_on_localhost());
+        self.rpc_addresses.push(cfg.rpc_server.address.clone());
+        cfg.event_stream_server.address =
+            format!("127.0.0.1:{}", testing::unused_port_on_localhost());
+        self.event_stream_addresses
+            .push(cfg.event_stream_server.address.clone());
+
         cfg
     }
 
@@ -181,3 +202,113 @@ async fn run_validator_network() {
     net.settle_on(&mut rng, is_in_era(2), Duration::from_secs(60))
         .await;
 }
+
+#[tokio::test]
+async fn should_drain_api_servers_on_termination() {
+    testing::init_logging();
+
+    let mut rng = crate::new_rng();
+
+    let mut chain = TestChain::new(&mut rng, 1);
+    let mut net = chain
+        .create_initialized_network(&mut rng)
+        .await
+        .expect("network initialization failed");
+    let node_id = net.nodes().keys().next().expect("need one node").clone();
+    let rpc_url = format!("http://{}/{}", chain.rpc_addresses[0], RPC_API_PATH);
+    let events_url = format!("http://{}/events", chain.event_stream_addresses[0]);
+
+    // Subscribe to the event stream, collecting it until the server closes it.
+    let mut events_response = reqwest::get(&events_url)
+        .await
+        .expect("should subscribe to events");
+    let events = tokio::spawn(async move {
+        let mut events = Vec::new();
+        while let Some(chunk) = events_response.chunk().await.expect("should read events") {
+            events.extend_from_slice(&chunk);
+        }
+        String::from_utf8(events).expect("events should be UTF-8")
+    });
+
+    // Send a request, and wait for it to reach the reactor, so it is in flight when termination is
+    // requested.
+    let rpc_request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "chain_get_state_root_hash"
+    })
+    .to_string();
+    let in_flight = tokio::spawn(
+        reqwest::Client::new()
+            .post(&rpc_url)
+            .header("content-type", "application/json")
+            .body(rpc_request)
+            .send(),
+    );
+    net.crank_until(
+        &node_id,
+        &mut rng,
+        |event| {
+            matches!(
+                event,
+                validator::Event::RpcServer(rpc_server::Event::RpcRequest(_))
+            )
+        },
+        Duration::from_secs(10),
+    )
+    .await;
+
+    net.process_injected_effect_on(&node_id, |effect_builder| {
+        effect_builder
+            .immediately()
+            .event(|_| validator::Event::TerminationRequested)
+    })
+    .await;
+    net.crank_until(
+        &node_id,
+        &mut rng,
+        |event| matches!(event, validator::Event::TerminationRequested),
+        Duration::from_secs(10),
+    )
+    .await;
+
+    // Give the servers a moment to stop listening, after which new connections are refused.
+    time::delay_for(Duration::from_millis(100)).await;
+    let error = TcpStream::connect(chain.rpc_addresses[0].parse::<SocketAddr>().unwrap())
+        .await
+        .expect_err("new connection should be refused");
+    assert_eq!(error.kind(), io::ErrorKind::ConnectionRefused);
+
+    // The node stops once all the API servers have reported that they shut down.
+    let mut runner = net.remove_node(&node_id).expect("should remove node");
+    time::timeout(Duration::from_secs(10), async {
+        while !runner.reactor_mut().inner_mut().is_stopped() {
+            if runner.try_crank(&mut rng).await.is_none() {
+                time::delay_for(Duration::from_millis(10)).await;
+            }
+        }
+    })
+    .await
+    .expect("API servers should shut down");
+
+    // The in-flight request completed...
+    let response = in_flight
+        .await
+        .unwrap()
+        .expect("in-flight request should complete");
+    assert!(response.status().is_success());
+    let response = response.text().await.expect("should read response");
+    assert!(
+        response.contains("\"result\""),
+        "unexpected response: {}",
+        response
+    );
+
+    // ...and the event stream was closed after delivering the final `Shutdown` event.
+    let events = events.await.unwrap();
+    let last_data = events
+        .lines()
+        .filter(|line| line.starts_with("data:"))
+        .last();
+    assert_eq!(last_data, Some("data:\"Shutdown\""), "events: {}", events);
+}
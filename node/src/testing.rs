@@ -2,6 +2,11 @@
 //!
 //! Contains various parts and components to aid writing tests and simulations using the
 //! `casper-node` library.
+//!
+//! Outside of the node's own tests, this module is only available with the `testing` feature
+//! enabled, allowing downstream crates to run networks of in-process nodes in their tests, e.g.
+//! using a `network::Network` connected through an in-memory network (see
+//! `components::in_memory_network`) and run in virtual time.
 
 mod condition_check_reactor;
 pub mod network;
@@ -27,8 +32,8 @@ use crate::{
     reactor::{EventQueueHandle, QueueKind, Scheduler},
 };
 use anyhow::Context;
-pub(crate) use condition_check_reactor::ConditionCheckReactor;
-pub(crate) use test_rng::TestRng;
+pub use condition_check_reactor::ConditionCheckReactor;
+pub use test_rng::TestRng;
 
 // Lower bound for the port, below there's a high chance of hitting a system service.
 const PORT_LOWER_BOUND: u16 = 10_000;
@@ -1,6 +1,7 @@
 //! A network of test reactors.
 
 use std::{
+    cmp,
     collections::{hash_map::Entry, HashMap},
     fmt::{Debug, Display},
     hash::Hash,
@@ -9,7 +10,7 @@ use std::{
 
 use futures::future::{BoxFuture, FutureExt};
 use serde::Serialize;
-use tokio::time;
+use tokio::{task, time};
 use tracing::{debug, error_span};
 use tracing_futures::Instrument;
 
@@ -38,15 +39,32 @@ pub trait NetworkedReactor: Sized {
 /// Time interval for which to poll an observed testing network when no events have occurred.
 const POLL_INTERVAL: Duration = Duration::from_millis(10);
 
+/// Interval by which virtual time is advanced at once.
+const VIRTUAL_TIME_STEP: Duration = Duration::from_millis(10);
+
+/// Number of times to yield to other tasks before considering an idle network to be settled.
+///
+/// Effects and the receiving ends of networking components run as separate tasks, which need to be
+/// given the opportunity to run to fill the event queues again.
+const IDLE_YIELD_COUNT: usize = 10;
+
 /// A network of multiple test reactors.
 ///
 /// Nodes themselves are not run in the background, rather manual cranking is required through
 /// `crank_all`. As an alternative, the `settle` and `settle_all` functions can be used to continue
 /// cranking until a condition has been reached.
+///
+/// The network can also be run in virtual time using `advance_time` and `run_until`, which pause
+/// tokio's clock and only ever advance it while all nodes are idle.  Together with the link
+/// conditions of an in-memory network (see `NetworkController`), this allows simulating latencies,
+/// partitions and timeouts without waiting for them in real time.  Virtual time requires the
+/// network to be run on a basic (single-threaded) runtime, as used by `#[tokio::test]`.
 #[derive(Debug, Default)]
 pub struct Network<R: Reactor + NetworkedReactor> {
     /// Current network.
     nodes: HashMap<<R as NetworkedReactor>::NodeId, Runner<ConditionCheckReactor<R>>>,
+    /// Whether tokio's clock has been paused to run the network in virtual time.
+    clock_paused: bool,
 }
 
 impl<R> Network<R>
@@ -91,6 +109,7 @@ where
     pub fn new() -> Self {
         Network {
             nodes: HashMap::new(),
+            clock_paused: false,
         }
     }
 
@@ -267,6 +286,82 @@ where
         }
     }
 
+    /// Advances virtual time by `duration`, processing events on all nodes along the way.
+    ///
+    /// Pauses tokio's clock if not already paused; it remains paused afterwards.  Time is advanced
+    /// in small steps, and before each step all nodes are cranked until idle, so that timers and
+    /// delayed messages fire in order.
+    pub async fn advance_time(&mut self, rng: &mut TestRng, duration: Duration) {
+        self.pause_clock();
+
+        let mut remaining = duration;
+        loop {
+            self.crank_all_until_idle(rng).await;
+            if remaining == Duration::from_secs(0) {
+                break;
+            }
+            let step = cmp::min(remaining, VIRTUAL_TIME_STEP);
+            time::advance(step).await;
+            remaining -= step;
+        }
+    }
+
+    /// Runs the network in virtual time until `condition` is true.
+    ///
+    /// Pauses tokio's clock if not already paused; it remains paused afterwards.  Virtual time is
+    /// only advanced while all nodes are idle.
+    ///
+    /// # Panics
+    ///
+    /// If the `condition` is not reached within `within` of virtual time, panics.
+    pub async fn run_until<F>(&mut self, rng: &mut TestRng, condition: F, within: Duration)
+    where
+        F: Fn(&Nodes<R>) -> bool,
+    {
+        self.pause_clock();
+
+        let mut elapsed = Duration::from_secs(0);
+        loop {
+            self.crank_all_until_idle(rng).await;
+            if condition(&self.nodes) {
+                debug!(?elapsed, "network met condition in virtual time");
+                return;
+            }
+            if elapsed >= within {
+                panic!(
+                    "network did not meet condition within {:?} of virtual time",
+                    within
+                );
+            }
+            time::advance(VIRTUAL_TIME_STEP).await;
+            elapsed += VIRTUAL_TIME_STEP;
+        }
+    }
+
+    /// Pauses tokio's clock, unless already paused.
+    fn pause_clock(&mut self) {
+        if !self.clock_paused {
+            time::pause();
+            self.clock_paused = true;
+        }
+    }
+
+    /// Cranks all runners until no events are left on any event queue, even after yielding to
+    /// pending tasks.
+    async fn crank_all_until_idle(&mut self, rng: &mut TestRng) {
+        loop {
+            while self.crank_all(rng).await != 0 {}
+
+            for _ in 0..IDLE_YIELD_COUNT {
+                task::yield_now().await;
+            }
+
+            if self.crank_all(rng).await == 0 {
+                break;
+            }
+        }
+    }
+
     /// Returns the internal map of nodes.
     pub fn nodes(&self) -> &HashMap<R::NodeId, Runner<ConditionCheckReactor<R>>> {
         &self.nodes
@@ -300,6 +395,10 @@ where
     fn finalize(self) -> BoxFuture<'static, ()> {
         // We support finalizing networks where the reactor itself can be finalized.
 
+        if self.clock_paused {
+            time::resume();
+        }
+
         async move {
             // Shutdown the sender of every reactor node to ensure the port is open again.
             for (_, node) in self.nodes.into_iter() {
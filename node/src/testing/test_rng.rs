@@ -1,4 +1,4 @@
-#![cfg(test)]
+#![cfg(any(feature = "testing", test))]
 
 use std::{
     cell::RefCell,
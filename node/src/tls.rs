@@ -44,10 +44,13 @@ use openssl::{
     nid,
     pkey::{PKey, PKeyRef, Private},
     sha,
-    ssl::{SslAcceptor, SslConnector, SslContextBuilder, SslMethod, SslVerifyMode, SslVersion},
+    ssl::{
+        self, AlpnError, SslAcceptor, SslConnector, SslContextBuilder, SslMethod, SslVerifyMode,
+        SslVersion,
+    },
     x509::{X509Builder, X509Name, X509NameBuilder, X509NameRef, X509Ref, X509},
 };
-#[cfg(test)]
+#[cfg(any(feature = "testing", test))]
 use rand::{
     distributions::{Distribution, Standard},
     Rng,
@@ -166,7 +169,7 @@ impl From<[u8; KeyFingerprint::LENGTH]> for KeyFingerprint {
     }
 }
 
-#[cfg(test)]
+#[cfg(any(feature = "testing", test))]
 impl Distribution<KeyFingerprint> for Standard {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> KeyFingerprint {
         let mut bytes = [0u8; Sha512::SIZE];
@@ -287,12 +290,20 @@ pub fn generate_node_cert() -> SslResult<(X509, PKey<Private>)> {
 /// compatible with connectors built with `create_tls_connector`.
 ///
 /// Incoming certificates must still be validated using `validate_cert`.
+///
+/// `alpn_protocols` lists the application protocols the server supports in ALPN wire format, i.e.
+/// each prefixed by its length, in order of preference.  The first of them offered by the client
+/// is selected; if none are offered, the handshake proceeds without selecting a protocol.
 pub(crate) fn create_tls_acceptor(
     cert: &X509Ref,
     private_key: &PKeyRef<Private>,
+    alpn_protocols: &'static [u8],
 ) -> SslResult<SslAcceptor> {
     let mut builder = SslAcceptor::mozilla_modern_v5(SslMethod::tls_server())?;
     set_context_options(&mut builder, cert, private_key)?;
+    builder.set_alpn_select_callback(move |_ssl, client_protocols| {
+        ssl::select_next_proto(alpn_protocols, client_protocols).ok_or(AlpnError::NOACK)
+    });
 
     Ok(builder.build())
 }
@@ -301,12 +312,17 @@ pub(crate) fn create_tls_acceptor(
 ///
 /// A connector compatible with the acceptor created using `create_tls_acceptor`. Server
 /// certificates must always be validated using `validate_cert` after connecting.
+///
+/// `alpn_protocols` lists the application protocols offered to the server, in the same format as
+/// for `create_tls_acceptor`.
 pub(crate) fn create_tls_connector(
     cert: &X509Ref,
     private_key: &PKeyRef<Private>,
+    alpn_protocols: &'static [u8],
 ) -> SslResult<SslConnector> {
     let mut builder = SslConnector::builder(SslMethod::tls_client())?;
     set_context_options(&mut builder, cert, private_key)?;
+    builder.set_alpn_protos(alpn_protocols)?;
 
     Ok(builder.build())
 }
@@ -2,6 +2,7 @@
 
 mod block;
 mod deploy;
+mod deploy_receipt;
 mod item;
 pub mod json_compatibility;
 mod node_config;
@@ -9,22 +10,31 @@ mod node_id;
 mod peers_map;
 mod status_feed;
 mod timestamp;
+mod trusted_checkpoint;
 
 use rand::{CryptoRng, RngCore};
 #[cfg(not(test))]
 use rand_chacha::ChaCha20Rng;
 
 pub use block::{Block, BlockHash, BlockHeader, BlockValidationError};
-pub(crate) use block::{BlockByHeight, BlockLike, FinalizedBlock, ProtoBlock, ProtoBlockHash};
+pub(crate) use block::{
+    BlockByHeight, BlockLike, EraEnd, FinalizedBlock, ProtoBlock, ProtoBlockHash,
+};
 pub use deploy::{
     Approval, Deploy, DeployHash, DeployHeader, DeployMetadata, Error as DeployError,
+    KeyHistoryEntry, TransferEntry,
 };
+pub use deploy_receipt::{deploy_receipt_digest, DeployReceipt, DeployReceiptPayload};
 pub use item::{Item, Tag};
 pub use node_config::NodeConfig;
 pub(crate) use node_id::NodeId;
 pub use peers_map::PeersMap;
-pub use status_feed::{GetStatusResult, StatusFeed};
+pub use status_feed::{
+    signed_status_digest, GetStatusResult, HistoricalStateWindow, SignedStatusPayload, StatusFeed,
+    SyncProgress, SyncState,
+};
 pub use timestamp::{TimeDiff, Timestamp};
+pub use trusted_checkpoint::{CheckpointError, CheckpointSignature, TrustedCheckpoint};
 
 /// An object-safe RNG trait that requires a cryptographically strong random number generator.
 pub trait CryptoRngCore: CryptoRng + RngCore {}
@@ -32,9 +42,9 @@ pub trait CryptoRngCore: CryptoRng + RngCore {}
 impl<T> CryptoRngCore for T where T: CryptoRng + RngCore + ?Sized {}
 
 /// The cryptographically secure RNG used throughout the node.
-#[cfg(not(test))]
+#[cfg(not(any(feature = "testing", test)))]
 pub type NodeRng = ChaCha20Rng;
 
 /// The RNG used throughout the node for testing.
-#[cfg(test)]
+#[cfg(any(feature = "testing", test))]
 pub type NodeRng = crate::testing::TestRng;
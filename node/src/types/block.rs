@@ -18,6 +18,7 @@ use hex::FromHexError;
 use hex_fmt::{HexFmt, HexList};
 #[cfg(test)]
 use rand::Rng;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -385,7 +386,18 @@ impl Display for FinalizedBlock {
 
 /// A cryptographic hash identifying a [`Block`](struct.Block.html).
 #[derive(
-    Copy, Clone, DataSize, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Debug,
+    Copy,
+    Clone,
+    DataSize,
+    Ord,
+    PartialOrd,
+    Eq,
+    PartialEq,
+    Hash,
+    Serialize,
+    Deserialize,
+    Debug,
+    JsonSchema,
 )]
 pub struct BlockHash(Digest);
 
@@ -445,7 +457,9 @@ impl FromBytes for BlockHash {
 }
 
 /// The header portion of a [`Block`](struct.Block.html).
-#[derive(Clone, DataSize, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Debug)]
+#[derive(
+    Clone, DataSize, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Debug, JsonSchema,
+)]
 pub struct BlockHeader {
     parent_hash: BlockHash,
     state_root_hash: Digest,
@@ -660,7 +674,9 @@ impl From<bytesrepr::Error> for BlockValidationError {
 
 /// A proto-block after execution, with the resulting post-state-hash.  This is the core component
 /// of the Casper linear blockchain.
-#[derive(DataSize, Clone, Debug, PartialOrd, Ord, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(
+    DataSize, Clone, Debug, PartialOrd, Ord, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema,
+)]
 pub struct Block {
     hash: BlockHash,
     header: BlockHeader,
@@ -716,7 +732,8 @@ impl Block {
         }
     }
 
-    pub(crate) fn header(&self) -> &BlockHeader {
+    /// The header of the block.
+    pub fn header(&self) -> &BlockHeader {
         &self.header
     }
 
@@ -743,6 +760,16 @@ impl Block {
         self.header.height()
     }
 
+    /// The timestamp from when the block was proposed.
+    pub fn timestamp(&self) -> Timestamp {
+        self.header.timestamp()
+    }
+
+    /// The finality signatures of this block known to this node.
+    pub fn proofs(&self) -> &[Signature] {
+        &self.proofs
+    }
+
     /// Appends the given signature to this block's proofs.  It should have been validated prior to
     /// this via `BlockHash::verify()`.
     pub(crate) fn append_proof(&mut self, proof: Signature) {
@@ -800,6 +827,24 @@ impl Block {
 
         block
     }
+
+    /// Generates a random instance using a `TestRng`, holding the given deploys.
+    #[cfg(test)]
+    pub fn random_with_deploys(rng: &mut TestRng, deploy_hashes: Vec<DeployHash>) -> Self {
+        let random_block = FinalizedBlock::random(rng);
+        let finalized_block = FinalizedBlock::new(
+            ProtoBlock::new(deploy_hashes, rng.gen()),
+            random_block.timestamp(),
+            None,
+            random_block.era_id(),
+            random_block.height(),
+            random_block.proposer(),
+        );
+        let parent_hash = BlockHash::new(Digest::random(rng));
+        let parent_seed = Digest::random(rng);
+        let state_root_hash = Digest::random(rng);
+        Block::new(parent_hash, parent_seed, state_root_hash, finalized_block)
+    }
 }
 
 impl Display for Block {
@@ -11,6 +11,7 @@ use hex::FromHexError;
 use itertools::Itertools;
 #[cfg(test)]
 use rand::{Rng, RngCore};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tracing::warn;
@@ -18,7 +19,13 @@ use tracing::warn;
 use casper_execution_engine::core::engine_state::{
     executable_deploy_item::ExecutableDeployItem, DeployItem,
 };
-use casper_types::bytesrepr::{self, FromBytes, ToBytes};
+#[cfg(test)]
+use casper_types::RuntimeArgs;
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes},
+    mint, standard_payment, CLType, CLTyped, CLValue, Key, URef, U512,
+};
 
 use super::{json_compatibility::ExecutionResult, BlockHash, Item, Tag, TimeDiff, Timestamp};
 #[cfg(test)]
@@ -48,6 +55,14 @@ pub enum Error {
     #[error("approval at index {0} does not exist")]
     NoSuchApproval(usize),
 
+    /// The body hash in the header is not the hash of the payment and session code.
+    #[error("invalid deploy body hash")]
+    InvalidBodyHash,
+
+    /// The deploy hash is not the hash of the header.
+    #[error("invalid deploy hash")]
+    InvalidDeployHash,
+
     /// Failed to verify an approval.
     #[error("failed to verify approval {index}: {error}")]
     FailedVerification {
@@ -84,6 +99,7 @@ impl From<TryFromSliceError> for Error {
     Deserialize,
     Debug,
     Default,
+    JsonSchema,
 )]
 pub struct DeployHash(Digest);
 
@@ -141,7 +157,9 @@ impl FromBytes for DeployHash {
 }
 
 /// The header portion of a [`Deploy`](struct.Deploy.html).
-#[derive(Clone, DataSize, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Debug)]
+#[derive(
+    Clone, DataSize, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Debug, JsonSchema,
+)]
 pub struct DeployHeader {
     account: PublicKey,
     timestamp: Timestamp,
@@ -265,7 +283,9 @@ impl Display for DeployHeader {
 }
 
 /// A struct containing a signature and the public key of the signer.
-#[derive(Clone, DataSize, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Debug)]
+#[derive(
+    Clone, DataSize, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Debug, JsonSchema,
+)]
 pub struct Approval {
     signer: PublicKey,
     signature: Signature,
@@ -312,11 +332,15 @@ impl FromBytes for Approval {
 }
 
 /// A deploy; an item containing a smart contract along with the requester's signature(s).
-#[derive(Clone, DataSize, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Debug)]
+#[derive(
+    Clone, DataSize, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Debug, JsonSchema,
+)]
 pub struct Deploy {
     hash: DeployHash,
     header: DeployHeader,
+    #[schemars(with = "serde_json::Value")]
     payment: ExecutableDeployItem,
+    #[schemars(with = "serde_json::Value")]
     session: ExecutableDeployItem,
     approvals: Vec<Approval>,
     #[serde(skip)]
@@ -401,6 +425,67 @@ impl Deploy {
         &self.session
     }
 
+    /// Returns the `Approval`s of this `Deploy`.
+    pub fn approvals(&self) -> &[Approval] {
+        &self.approvals
+    }
+
+    /// Returns the hashes of the accounts involved in this deploy: the account on whose behalf it
+    /// is executed and, if its session code is a native transfer to an account, the target
+    /// account.
+    pub fn accounts(&self) -> BTreeSet<AccountHash> {
+        let mut accounts = BTreeSet::new();
+        accounts.insert(self.header.account().to_account_hash());
+        accounts.extend(self.transfer_target().and_then(Key::into_account));
+        accounts
+    }
+
+    /// Returns the target of the transfer if the session code is a native transfer: either a
+    /// `Key::Account`, or a `Key::URef` without access rights if the target is a purse.
+    pub fn transfer_target(&self) -> Option<Key> {
+        let target = self.transfer_arg(mint::ARG_TARGET)?;
+        let cl_type = target.cl_type().clone();
+        if cl_type == AccountHash::cl_type() {
+            target.into_t().ok().map(Key::Account)
+        } else if cl_type == CLType::Key {
+            target
+                .into_t::<Key>()
+                .ok()
+                .filter(|key| key.into_account().is_some())
+        } else if cl_type == CLType::URef {
+            target
+                .into_t::<URef>()
+                .ok()
+                .map(|uref| Key::URef(uref).normalize())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the amount of the transfer if the session code is a native transfer.
+    pub fn transfer_amount(&self) -> Option<U512> {
+        self.transfer_arg(mint::ARG_AMOUNT)?.into_t().ok()
+    }
+
+    /// Returns the amount the payment code is given to pay for the deploy's execution, i.e. its
+    /// `"amount"` argument, if any.
+    pub fn payment_amount(&self) -> Option<U512> {
+        let args = self.payment.clone().into_runtime_args().ok()?;
+        args.get(standard_payment::ARG_AMOUNT)?
+            .clone()
+            .into_t()
+            .ok()
+    }
+
+    /// Returns the named argument of the session code if it is a native transfer.
+    fn transfer_arg(&self, name: &str) -> Option<CLValue> {
+        if !matches!(self.session, ExecutableDeployItem::Transfer { .. }) {
+            return None;
+        }
+        let args = self.session.clone().into_runtime_args().ok()?;
+        args.get(name).cloned()
+    }
+
     /// Returns true iff:
     ///   * the deploy hash is correct (should be the hash of the header), and
     ///   * the body hash is correct (should be the hash of the body), and
@@ -411,7 +496,13 @@ impl Deploy {
     pub fn is_valid(&mut self) -> bool {
         match self.is_valid {
             None => {
-                let validity = validate_deploy(self);
+                let validity = match validate_deploy(self) {
+                    Ok(()) => true,
+                    Err(error) => {
+                        warn!(deploy = ?self, %error, "invalid deploy");
+                        false
+                    }
+                };
                 self.is_valid = Some(validity);
                 validity
             }
@@ -419,9 +510,22 @@ impl Deploy {
         }
     }
 
+    /// Performs the same checks as `is_valid()`, returning the first one failed.  The result is
+    /// not cached.
+    pub fn validate(&self) -> Result<(), Error> {
+        validate_deploy(self)
+    }
+
     /// Generates a random instance using a `TestRng`.
     #[cfg(test)]
     pub fn random(rng: &mut TestRng) -> Self {
+        let secret_key = SecretKey::random(rng);
+        Deploy::random_from(rng, &secret_key)
+    }
+
+    /// Generates a random instance using a `TestRng`, signed by the given secret key.
+    #[cfg(test)]
+    pub fn random_from(rng: &mut TestRng, secret_key: &SecretKey) -> Self {
         let timestamp = Timestamp::random(rng);
         let ttl = TimeDiff::from(rng.gen_range(60_000, 3_600_000));
         let gas_price = rng.gen_range(1, 100);
@@ -436,8 +540,6 @@ impl Deploy {
         let payment = rng.gen();
         let session = rng.gen();
 
-        let secret_key = SecretKey::random(rng);
-
         Deploy::new(
             timestamp,
             ttl,
@@ -446,6 +548,30 @@ impl Deploy {
             chain_name,
             payment,
             session,
+            secret_key,
+            rng,
+        )
+    }
+
+    /// Generates a random native transfer of `amount` to the account `target`.
+    #[cfg(test)]
+    pub fn random_transfer(rng: &mut TestRng, target: AccountHash, amount: U512) -> Self {
+        let mut args = RuntimeArgs::new();
+        args.insert(mint::ARG_TARGET, target);
+        args.insert(mint::ARG_AMOUNT, amount);
+        let session = ExecutableDeployItem::Transfer {
+            args: args.to_bytes().expect("should serialize transfer args"),
+        };
+        let secret_key = SecretKey::random(rng);
+
+        Deploy::new(
+            Timestamp::random(rng),
+            TimeDiff::from(rng.gen_range(60_000, 3_600_000)),
+            rng.gen_range(1, 100),
+            vec![],
+            String::from("casper-example"),
+            rng.gen(),
+            session,
             &secret_key,
             rng,
         )
@@ -472,31 +598,26 @@ fn serialize_body(payment: &ExecutableDeployItem, session: &ExecutableDeployItem
 
 // Computationally expensive validity check for a given deploy instance, including
 // asymmetric_key signing verification.
-fn validate_deploy(deploy: &Deploy) -> bool {
+fn validate_deploy(deploy: &Deploy) -> Result<(), Error> {
     let serialized_body = serialize_body(&deploy.payment, &deploy.session);
     let body_hash = hash::hash(&serialized_body);
     if body_hash != deploy.header.body_hash {
-        warn!(?deploy, ?body_hash, "invalid deploy body hash");
-        return false;
+        return Err(Error::InvalidBodyHash);
     }
 
     let serialized_header = serialize_header(&deploy.header);
     let hash = DeployHash::new(hash::hash(&serialized_header));
     if hash != deploy.hash {
-        warn!(?deploy, ?hash, "invalid deploy hash");
-        return false;
+        return Err(Error::InvalidDeployHash);
     }
 
-    for (index, approval) in deploy.approvals.iter().enumerate() {
-        if let Err(error) =
-            asymmetric_key::verify(&deploy.hash, &approval.signature, &approval.signer)
-        {
-            warn!(?deploy, "failed to verify approval {}: {}", index, error);
-            return false;
-        }
-    }
-
-    true
+    let signed: Vec<_> = deploy
+        .approvals
+        .iter()
+        .map(|approval| (&deploy.hash, approval.signature, approval.signer))
+        .collect();
+    asymmetric_key::verify_batch(&signed)
+        .map_err(|(index, error)| Error::FailedVerification { index, error })
 }
 
 impl Item for Deploy {
@@ -549,6 +670,33 @@ pub struct DeployMetadata {
     pub execution_results: HashMap<BlockHash, ExecutionResult>,
 }
 
+/// An entry in the write history of a global state key: a deploy whose execution in the context
+/// of a block wrote to the key.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, JsonSchema)]
+pub struct KeyHistoryEntry {
+    /// The hash of the block in which the deploy was executed.
+    pub block_hash: BlockHash,
+    /// The hash of the deploy.
+    pub deploy_hash: DeployHash,
+}
+
+/// A successful native transfer, as held in the index of transfers by target.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, JsonSchema)]
+pub struct TransferEntry {
+    /// The hash of the block in which the transfer was executed.
+    pub block_hash: BlockHash,
+    /// The height of the block in which the transfer was executed.
+    pub block_height: u64,
+    /// The hash of the deploy making the transfer.
+    pub deploy_hash: DeployHash,
+    /// The account which sent the transfer.
+    #[schemars(with = "String")]
+    pub from: AccountHash,
+    /// The amount transferred.
+    #[schemars(with = "String")]
+    pub amount: U512,
+}
+
 impl ToBytes for Deploy {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         let mut buffer = bytesrepr::allocate_buffer(self)?;
@@ -592,6 +740,8 @@ impl FromBytes for Deploy {
 mod tests {
     use std::time::Duration;
 
+    use casper_types::{AccessRights, RuntimeArgs};
+
     use super::*;
 
     #[test]
@@ -653,4 +803,60 @@ mod tests {
         assert!(!deploy.is_valid(), "should not be valid");
         assert_eq!(deploy.is_valid, Some(false), "is valid should be false");
     }
+
+    #[test]
+    fn should_list_transfer_target_among_accounts() {
+        let mut rng = crate::new_rng();
+        let secret_key = SecretKey::random(&mut rng);
+        let payer = PublicKey::from(&secret_key).to_account_hash();
+        let target = AccountHash::new([7; 32]);
+
+        let mut args = RuntimeArgs::new();
+        args.insert(mint::ARG_TARGET, target);
+        let payment = ExecutableDeployItem::ModuleBytes {
+            module_bytes: vec![],
+            args: vec![],
+        };
+        let session = ExecutableDeployItem::Transfer {
+            args: args.to_bytes().unwrap(),
+        };
+        let deploy = Deploy::new(
+            Timestamp::zero(),
+            TimeDiff::from(Duration::default()),
+            1,
+            vec![],
+            String::default(),
+            payment.clone(),
+            session,
+            &secret_key,
+            &mut rng,
+        );
+        let expected: BTreeSet<_> = vec![payer, target].into_iter().collect();
+        assert_eq!(deploy.accounts(), expected);
+        assert_eq!(deploy.transfer_target(), Some(Key::Account(target)));
+
+        // A transfer to a purse only involves the payer.
+        let mut args = RuntimeArgs::new();
+        args.insert(mint::ARG_TARGET, URef::new([8; 32], AccessRights::ADD));
+        let session = ExecutableDeployItem::Transfer {
+            args: args.to_bytes().unwrap(),
+        };
+        let deploy = Deploy::new(
+            Timestamp::zero(),
+            TimeDiff::from(Duration::default()),
+            1,
+            vec![],
+            String::default(),
+            payment,
+            session,
+            &secret_key,
+            &mut rng,
+        );
+        let expected: BTreeSet<_> = vec![payer].into_iter().collect();
+        assert_eq!(deploy.accounts(), expected);
+        assert_eq!(
+            deploy.transfer_target(),
+            Some(Key::URef(URef::new([8; 32], AccessRights::NONE)))
+        );
+    }
 }
@@ -0,0 +1,131 @@
+//! Receipts signed by a node on accepting a deploy from a client, which the client can present as
+//! proof that the node received the deploy.
+
+use std::future::Future;
+
+use datasize::DataSize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    crypto::{
+        self,
+        asymmetric_key::{self, PublicKey, Signature},
+        hash::{self, Digest},
+        signer::Signer,
+    },
+    types::{DeployHash, Timestamp},
+    NodeRng,
+};
+
+/// The tag prepended to a deploy receipt payload before hashing, so that its signature can't be
+/// passed off as that of a block, a consensus message or a status.
+const DEPLOY_RECEIPT_TAG: &[u8] = b"casper-node deploy receipt:";
+
+/// The statement signed by a node in a deploy receipt.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq, JsonSchema)]
+pub struct DeployReceiptPayload {
+    /// The hash of the accepted deploy.
+    pub deploy_hash: DeployHash,
+    /// The time at which the node accepted the deploy.
+    pub timestamp: Timestamp,
+    /// The public key of the node's identity.
+    pub public_key: PublicKey,
+}
+
+/// A receipt signed by a node on accepting a deploy.
+#[derive(Clone, DataSize, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct DeployReceipt {
+    /// The JSON-encoded `DeployReceiptPayload`, exactly as signed.
+    pub payload: String,
+    /// The signature of the payload.
+    pub signature: Signature,
+}
+
+impl DeployReceipt {
+    /// Returns a future resolving to a receipt for `deploy_hash` accepted at `timestamp`, signed
+    /// by `signer`.
+    pub(crate) fn sign(
+        deploy_hash: DeployHash,
+        timestamp: Timestamp,
+        signer: &dyn Signer,
+        rng: &mut NodeRng,
+    ) -> impl Future<Output = crypto::Result<Self>> + Send + 'static {
+        let payload = DeployReceiptPayload {
+            deploy_hash,
+            timestamp,
+            public_key: *signer.public_key(),
+        };
+        // Serialization of the payload can't fail: it contains no maps with non-string keys.
+        let payload = serde_json::to_string(&payload).expect("should serialize receipt payload");
+        let digest = deploy_receipt_digest(payload.as_bytes());
+        let signing = signer.sign(digest.as_ref().to_vec(), rng);
+        async move {
+            let signature = signing.await?;
+            Ok(DeployReceipt { payload, signature })
+        }
+    }
+
+    /// Verifies the signature against the public key held in the payload, and returns the parsed
+    /// payload if it is valid.
+    ///
+    /// Callers should also check that the payload's public key is the one they expect the node to
+    /// use, and that its deploy hash is that of their deploy.
+    pub fn verify(&self) -> Result<DeployReceiptPayload, String> {
+        let payload: DeployReceiptPayload = serde_json::from_str(&self.payload)
+            .map_err(|error| format!("invalid payload: {}", error))?;
+        asymmetric_key::verify(
+            deploy_receipt_digest(self.payload.as_bytes()),
+            &self.signature,
+            &payload.public_key,
+        )
+        .map_err(|error| format!("invalid signature: {}", error))?;
+        Ok(payload)
+    }
+}
+
+/// Returns the digest signed by a node for the given serialized `DeployReceiptPayload`.
+pub fn deploy_receipt_digest(payload: &[u8]) -> Digest {
+    let mut bytes = DEPLOY_RECEIPT_TAG.to_vec();
+    bytes.extend_from_slice(payload);
+    hash::hash(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+    use crate::crypto::{asymmetric_key::SecretKey, signer::LocalSigner};
+
+    #[test]
+    fn should_verify_receipt() {
+        let mut rng = crate::new_rng();
+        let signer = LocalSigner::new(SecretKey::random(&mut rng));
+        let deploy_hash = DeployHash::random(&mut rng);
+        let timestamp = Timestamp::now();
+
+        let receipt = block_on(DeployReceipt::sign(
+            deploy_hash,
+            timestamp,
+            &signer,
+            &mut rng,
+        ))
+        .expect("should sign receipt");
+        let payload = receipt.verify().expect("should verify receipt");
+        assert_eq!(payload.deploy_hash, deploy_hash);
+        assert_eq!(payload.timestamp, timestamp);
+        assert_eq!(payload.public_key, *signer.public_key());
+
+        // A receipt for another deploy can't reuse the signature.
+        let other_payload = DeployReceiptPayload {
+            deploy_hash: DeployHash::random(&mut rng),
+            ..payload
+        };
+        let forged = DeployReceipt {
+            payload: serde_json::to_string(&other_payload).unwrap(),
+            signature: receipt.signature,
+        };
+        assert!(forged.verify().is_err());
+    }
+}
@@ -2,18 +2,45 @@
 
 use std::collections::BTreeMap;
 
-use casper_types::Key;
+use thiserror::Error;
+
+use casper_types::{bytesrepr, Key};
 
 mod auction_state;
+mod contract_abi;
 mod deploy_info;
 mod execution_result;
+mod execution_trace;
+mod gas_profile;
 mod stored_value;
 
 pub use auction_state::AuctionState;
+pub use contract_abi::{ArgAbi, ContractAbi, EntryPointAbi};
 pub use deploy_info::DeployInfo;
 pub use execution_result::ExecutionResult;
+pub use execution_trace::{ExecutionTrace, HostFunctionCall};
+pub use gas_profile::GasProfile;
 pub use stored_value::StoredValue;
 
+/// Error converting a type from this module back into the type which it represents.
+#[derive(Debug, Error)]
+pub enum ConversionError {
+    /// A field expected to be hex-encoded isn't valid hex.
+    #[error("invalid hex: {0}")]
+    Hex(#[from] hex::FromHexError),
+    /// A hex-encoded field doesn't hold a valid `bytesrepr` serialization.
+    #[error("invalid serialized value: {0}")]
+    BytesRepr(bytesrepr::Error),
+    /// A field doesn't hold a valid representation of its type.
+    #[error("invalid {field}: {value}")]
+    InvalidField {
+        /// The name of the field.
+        field: &'static str,
+        /// The invalid value.
+        value: String,
+    },
+}
+
 fn convert_named_keys(named_keys: &BTreeMap<String, Key>) -> BTreeMap<String, String> {
     named_keys
         .iter()
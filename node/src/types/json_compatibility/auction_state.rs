@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use casper_types::auction::{Bids, EraValidators};
@@ -5,15 +6,17 @@ use casper_types::auction::{Bids, EraValidators};
 use crate::crypto::hash::Digest;
 
 /// Data structure summarizing auction contract data.
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct AuctionState {
     /// Global state hash
     pub state_root_hash: Digest,
     /// Block height
     pub block_height: u64,
     /// Era validators
+    #[schemars(with = "Option<serde_json::Value>")]
     pub era_validators: Option<EraValidators>,
     /// All bids.
+    #[schemars(with = "Option<serde_json::Value>")]
     bids: Option<Bids>,
 }
 
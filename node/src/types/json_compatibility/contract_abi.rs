@@ -0,0 +1,199 @@
+//! This file provides types describing how to call a stored contract, combining the contract's
+//! entry points with the documentation in its optional `ContractMetadata`.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use casper_types::{
+    CLType, Contract, ContractMetadata, EntryPoint, EntryPointAccess, EntryPointType,
+};
+
+/// Description of an argument of an entry point.
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct ArgAbi {
+    /// The name of the argument.
+    pub name: String,
+    /// The type of the argument.
+    #[schemars(with = "serde_json::Value")]
+    pub cl_type: CLType,
+    /// A description of the argument, if given in the contract metadata.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub doc: Option<String>,
+}
+
+/// Description of an entry point of a contract.
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct EntryPointAbi {
+    /// The name of the entry point.
+    pub name: String,
+    /// A description of the entry point, if given in the contract metadata.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub doc: Option<String>,
+    /// The arguments of the entry point.
+    pub args: Vec<ArgAbi>,
+    /// The type of the return value.
+    #[schemars(with = "serde_json::Value")]
+    pub ret: CLType,
+    /// A description of the return value, if given in the contract metadata.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub ret_doc: Option<String>,
+    /// The groups allowed to call the entry point, or `None` if it is public.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub groups: Option<Vec<String>>,
+    /// Either "Session" or "Contract".
+    pub entry_point_type: String,
+}
+
+impl EntryPointAbi {
+    fn new(entry_point: &EntryPoint, maybe_metadata: Option<&ContractMetadata>) -> Self {
+        let maybe_entry_point_metadata =
+            maybe_metadata.and_then(|metadata| metadata.entry_point(entry_point.name()));
+        let args = entry_point
+            .args()
+            .iter()
+            .map(|parameter| {
+                let (name, cl_type): (String, CLType) = parameter.clone().into();
+                let doc = maybe_entry_point_metadata
+                    .and_then(|metadata| metadata.args.iter().find(|arg| arg.name == name))
+                    .and_then(|arg| arg.doc.clone());
+                ArgAbi { name, cl_type, doc }
+            })
+            .collect();
+        let groups = match entry_point.access() {
+            EntryPointAccess::Public => None,
+            EntryPointAccess::Groups(groups) => Some(
+                groups
+                    .iter()
+                    .map(|group| group.value().to_string())
+                    .collect(),
+            ),
+        };
+        let entry_point_type = match entry_point.entry_point_type() {
+            EntryPointType::Session => "Session",
+            EntryPointType::Contract => "Contract",
+        };
+        EntryPointAbi {
+            name: entry_point.name().to_string(),
+            doc: maybe_entry_point_metadata.and_then(|metadata| metadata.doc.clone()),
+            args,
+            ret: entry_point.ret().clone(),
+            ret_doc: maybe_entry_point_metadata.and_then(|metadata| metadata.ret_doc.clone()),
+            groups,
+            entry_point_type: entry_point_type.to_string(),
+        }
+    }
+}
+
+/// Description of the interface of a stored contract.
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct ContractAbi {
+    /// The human-readable name of the contract, if given in the contract metadata.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub name: Option<String>,
+    /// The version of the contract, if given in the contract metadata.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub version: Option<String>,
+    /// A description of the contract, if given in the contract metadata.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub description: Option<String>,
+    /// The hash of the contract package holding the contract.
+    pub contract_package_hash: String,
+    /// The hash of the contract's Wasm.
+    pub contract_wasm_hash: String,
+    /// The entry points of the contract, ordered by name.
+    pub entry_points: Vec<EntryPointAbi>,
+}
+
+impl ContractAbi {
+    /// Describes `contract`, documented by its metadata if available.
+    pub fn new(contract: &Contract, maybe_metadata: Option<&ContractMetadata>) -> Self {
+        let entry_points = contract
+            .entry_points()
+            .clone()
+            .take_entry_points()
+            .iter()
+            .map(|entry_point| EntryPointAbi::new(entry_point, maybe_metadata))
+            .collect();
+        ContractAbi {
+            name: maybe_metadata.map(|metadata| metadata.name.clone()),
+            version: maybe_metadata.and_then(|metadata| metadata.version.clone()),
+            description: maybe_metadata.and_then(|metadata| metadata.description.clone()),
+            contract_package_hash: hex::encode(contract.contract_package_hash()),
+            contract_wasm_hash: hex::encode(contract.contract_wasm_hash()),
+            entry_points,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{
+        contracts::NamedKeys, ArgMetadata, EntryPointMetadata, EntryPoints, Group, Parameter,
+        ProtocolVersion,
+    };
+
+    use super::*;
+
+    #[test]
+    fn should_merge_metadata_docs() {
+        let mut entry_points = EntryPoints::new();
+        entry_points.add_entry_point(EntryPoint::new(
+            "transfer",
+            vec![
+                Parameter::new("target", CLType::Key),
+                Parameter::new("amount", CLType::U512),
+            ],
+            CLType::Unit,
+            EntryPointAccess::Groups(vec![Group::new("admins")]),
+            EntryPointType::Contract,
+        ));
+        let contract = Contract::new(
+            [1; 32],
+            [2; 32],
+            NamedKeys::new(),
+            entry_points,
+            ProtocolVersion::V1_0_0,
+        );
+        let metadata = ContractMetadata {
+            name: "token".to_string(),
+            version: None,
+            description: None,
+            entry_points: vec![EntryPointMetadata {
+                name: "transfer".to_string(),
+                doc: Some("Moves tokens.".to_string()),
+                args: vec![ArgMetadata {
+                    name: "amount".to_string(),
+                    doc: Some("The amount to move.".to_string()),
+                }],
+                ret_doc: None,
+            }],
+        };
+
+        let abi = ContractAbi::new(&contract, Some(&metadata));
+        assert_eq!(abi.name.as_deref(), Some("token"));
+        let transfer = abi
+            .entry_points
+            .iter()
+            .find(|entry_point| entry_point.name == "transfer")
+            .unwrap();
+        assert_eq!(transfer.doc.as_deref(), Some("Moves tokens."));
+        assert_eq!(transfer.groups, Some(vec!["admins".to_string()]));
+        let amount = transfer
+            .args
+            .iter()
+            .find(|arg| arg.name == "amount")
+            .unwrap();
+        assert_eq!(amount.cl_type, CLType::U512);
+        assert_eq!(amount.doc.as_deref(), Some("The amount to move."));
+        let target = transfer
+            .args
+            .iter()
+            .find(|arg| arg.name == "target")
+            .unwrap();
+        assert!(target.doc.is_none());
+
+        let undocumented = ContractAbi::new(&contract, None);
+        assert!(undocumented.name.is_none());
+        assert_eq!(undocumented.entry_points.len(), abi.entry_points.len());
+    }
+}
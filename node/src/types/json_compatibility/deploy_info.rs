@@ -1,17 +1,23 @@
 //! This file provides types to allow conversion from an EE `DeployInfo` into a similar type
 //! which can be serialized to a valid JSON representation.
 
-use casper_types::{self, U512};
+use std::convert::{TryFrom, TryInto};
+
+use casper_types::{self, account::AccountHash, DeployHash, Key, URef, U512};
 use hex_fmt::HexFmt;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use super::ConversionError;
+
 /// Representation of deploy info
-#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug, JsonSchema)]
 pub struct DeployInfo {
     deploy_hash: String,
     transfers: Vec<String>,
     from: String,
     source: String,
+    #[schemars(with = "String")]
     gas: U512,
 }
 
@@ -35,3 +41,37 @@ impl From<&casper_types::DeployInfo> for DeployInfo {
         }
     }
 }
+
+impl TryFrom<&DeployInfo> for casper_types::DeployInfo {
+    type Error = ConversionError;
+
+    fn try_from(deploy_info: &DeployInfo) -> Result<Self, Self::Error> {
+        let invalid = |field, value: &str| ConversionError::InvalidField {
+            field,
+            value: value.to_string(),
+        };
+        let deploy_hash: DeployHash = hex::decode(&deploy_info.deploy_hash)?
+            .as_slice()
+            .try_into()
+            .map_err(|_| invalid("deploy_hash", &deploy_info.deploy_hash))?;
+        let transfers = deploy_info
+            .transfers
+            .iter()
+            .map(|transfer| match Key::from_formatted_str(transfer) {
+                Ok(Key::Transfer(transfer_addr)) => Ok(transfer_addr),
+                _ => Err(invalid("transfers", transfer)),
+            })
+            .collect::<Result<_, _>>()?;
+        let from = AccountHash::from_formatted_str(&deploy_info.from)
+            .map_err(|_| invalid("from", &deploy_info.from))?;
+        let source = URef::from_formatted_str(&deploy_info.source)
+            .map_err(|_| invalid("source", &deploy_info.source))?;
+        Ok(casper_types::DeployInfo {
+            deploy_hash,
+            transfers,
+            from,
+            source,
+            gas: deploy_info.gas,
+        })
+    }
+}
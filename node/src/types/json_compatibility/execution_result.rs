@@ -10,6 +10,7 @@ use datasize::DataSize;
 use log::info;
 #[cfg(test)]
 use rand::{seq::SliceRandom, Rng};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use casper_execution_engine::{
@@ -21,7 +22,7 @@ use casper_execution_engine::{
 };
 use casper_types::{
     bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
-    CLValue, U128, U256, U512,
+    CLValue, Key, U128, U256, U512,
 };
 
 #[cfg(test)]
@@ -51,14 +52,43 @@ const ADD_KEYS_TAG: u8 = 13;
 const FAILURE_TAG: u8 = 14;
 
 /// The result of executing a single deploy.
-#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug, DataSize)]
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug, DataSize, JsonSchema)]
 pub struct ExecutionResult {
     effect: ExecutionEffect,
+    #[schemars(with = "String")]
     cost: U512,
     error_message: Option<String>,
 }
 
 impl ExecutionResult {
+    /// Returns the gas consumed by the execution.
+    pub fn cost(&self) -> U512 {
+        self.cost
+    }
+
+    /// Returns `true` if the execution succeeded.
+    pub fn is_success(&self) -> bool {
+        self.error_message.is_none()
+    }
+
+    /// Returns the normalized, formatted keys which the execution wrote to.
+    ///
+    /// Keys which were only read or whose transform failed are omitted.  Keys which can't be
+    /// parsed are returned as they were formatted by the execution engine.
+    pub fn written_keys(&self) -> impl Iterator<Item = String> + '_ {
+        self.effect
+            .transforms
+            .iter()
+            .filter(
+                |(_, transform)| !matches!(transform, Transform::Identity | Transform::Failure(_)),
+            )
+            .map(|(key, _)| {
+                Key::from_formatted_str(key)
+                    .map(|key| key.normalize().to_formatted_string())
+                    .unwrap_or_else(|_| key.clone())
+            })
+    }
+
     /// Generates a random instance using a `TestRng`.
     #[cfg(test)]
     pub fn random(rng: &mut TestRng) -> Self {
@@ -95,6 +125,25 @@ impl ExecutionResult {
             error_message,
         }
     }
+
+    /// Generates a random successful instance using a `TestRng`.
+    #[cfg(test)]
+    pub fn random_success(rng: &mut TestRng) -> Self {
+        let mut execution_result = ExecutionResult::random(rng);
+        execution_result.error_message = None;
+        execution_result
+    }
+
+    /// Generates a random instance using a `TestRng`, which additionally writes to `key`.
+    #[cfg(test)]
+    pub fn random_writing_to(rng: &mut TestRng, key: Key) -> Self {
+        let mut execution_result = ExecutionResult::random(rng);
+        execution_result
+            .effect
+            .transforms
+            .insert(key.to_formatted_string(), Transform::WriteAccount);
+        execution_result
+    }
 }
 
 impl From<&EngineExecutionResult> for ExecutionResult {
@@ -150,7 +199,7 @@ impl FromBytes for ExecutionResult {
 }
 
 /// The effect of executing a single deploy.
-#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Default, Debug, DataSize)]
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Default, Debug, DataSize, JsonSchema)]
 struct ExecutionEffect {
     /// The resulting operations.  The map's key is the formatted string of the EE `Key`.
     operations: BTreeMap<String, Operation>,
@@ -200,7 +249,7 @@ impl FromBytes for ExecutionEffect {
     }
 }
 
-#[derive(Clone, Copy, Eq, PartialEq, Serialize, Deserialize, Debug, DataSize)]
+#[derive(Clone, Copy, Eq, PartialEq, Serialize, Deserialize, Debug, DataSize, JsonSchema)]
 enum Operation {
     Read,
     Write,
@@ -250,11 +299,11 @@ impl FromBytes for Operation {
     }
 }
 
-#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug, DataSize)]
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug, DataSize, JsonSchema)]
 enum Transform {
     Identity,
     #[data_size(skip)]
-    WriteCLValue(CLValue),
+    WriteCLValue(#[schemars(with = "serde_json::Value")] CLValue),
     WriteAccount,
     WriteContractWasm,
     WriteContract,
@@ -263,9 +312,9 @@ enum Transform {
     WriteTransfer,
     AddInt32(i32),
     AddUInt64(u64),
-    AddUInt128(U128),
-    AddUInt256(U256),
-    AddUInt512(U512),
+    AddUInt128(#[schemars(with = "String")] U128),
+    AddUInt256(#[schemars(with = "String")] U256),
+    AddUInt512(#[schemars(with = "String")] U512),
     AddKeys(BTreeMap<String, String>),
     Failure(String),
 }
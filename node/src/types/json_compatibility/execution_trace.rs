@@ -0,0 +1,55 @@
+//! This file provides types to allow conversion from an EE `ExecutionTrace` into a similar type
+//! which can be serialized to a valid JSON representation.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use casper_execution_engine::core::engine_state::{
+    ExecutionTrace as EngineExecutionTrace, HostFunctionCall as EngineHostFunctionCall,
+};
+use casper_types::U512;
+
+/// A call to a host function made during execution of a deploy.
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct HostFunctionCall {
+    name: String,
+    args: Vec<i64>,
+    phase: String,
+    depth: usize,
+    #[schemars(with = "String")]
+    gas_before: U512,
+    #[schemars(with = "String")]
+    gas_after: U512,
+    error_message: Option<String>,
+}
+
+impl From<&EngineHostFunctionCall> for HostFunctionCall {
+    fn from(call: &EngineHostFunctionCall) -> Self {
+        HostFunctionCall {
+            name: call.name.to_string(),
+            args: call.args.clone(),
+            phase: format!("{:?}", call.phase),
+            depth: call.depth,
+            gas_before: call.gas_before.value(),
+            gas_after: call.gas_after.value(),
+            error_message: call.error.clone(),
+        }
+    }
+}
+
+/// The host function calls made during execution of a deploy, in the order they were made.
+#[derive(Clone, Default, Eq, PartialEq, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct ExecutionTrace(Vec<HostFunctionCall>);
+
+impl ExecutionTrace {
+    /// Returns the recorded host function calls.
+    pub fn calls(&self) -> &[HostFunctionCall] {
+        &self.0
+    }
+}
+
+impl From<&EngineExecutionTrace> for ExecutionTrace {
+    fn from(trace: &EngineExecutionTrace) -> Self {
+        ExecutionTrace(trace.calls().iter().map(HostFunctionCall::from).collect())
+    }
+}
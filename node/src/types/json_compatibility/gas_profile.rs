@@ -0,0 +1,51 @@
+//! This file provides types to allow conversion from an EE `GasProfile` into a similar type which
+//! can be serialized to a valid JSON representation.
+
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use casper_execution_engine::{
+    core::engine_state::GasProfile as EngineGasProfile, shared::gas::Gas,
+};
+use casper_types::U512;
+
+/// The gas spent during execution of a deploy, broken down by where it was spent.
+#[derive(Clone, Default, Eq, PartialEq, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GasProfile {
+    /// The gas charged by each host function, excluding the gas spent by any contracts it called.
+    #[schemars(with = "BTreeMap<String, String>")]
+    host_functions: BTreeMap<String, U512>,
+    /// The gas charged for executing Wasm instructions, by opcode cost category.
+    #[schemars(with = "BTreeMap<String, String>")]
+    opcodes: BTreeMap<String, U512>,
+}
+
+impl GasProfile {
+    /// Returns the gas charged by each host function.
+    pub fn host_functions(&self) -> &BTreeMap<String, U512> {
+        &self.host_functions
+    }
+
+    /// Returns the gas charged for executing Wasm instructions, by opcode cost category.
+    pub fn opcodes(&self) -> &BTreeMap<String, U512> {
+        &self.opcodes
+    }
+}
+
+fn convert_gas_by_name(gas_by_name: &BTreeMap<&'static str, Gas>) -> BTreeMap<String, U512> {
+    gas_by_name
+        .iter()
+        .map(|(name, gas)| (name.to_string(), gas.value()))
+        .collect()
+}
+
+impl From<&EngineGasProfile> for GasProfile {
+    fn from(profile: &EngineGasProfile) -> Self {
+        GasProfile {
+            host_functions: convert_gas_by_name(&profile.host_functions),
+            opcodes: convert_gas_by_name(&profile.opcodes),
+        }
+    }
+}
@@ -1,18 +1,19 @@
 //! This file provides types to allow conversion from an EE `StoredValue` into a similar type
-//! which can be serialized to a valid JSON representation.
-use std::convert::TryFrom;
+//! which can be serialized to a valid JSON representation, and back again.
+use std::convert::{TryFrom, TryInto};
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use casper_execution_engine::shared::{
     account::Account, stored_value::StoredValue as ExecutionEngineStoredValue,
 };
 use casper_types::{
-    bytesrepr::{self, ToBytes},
+    bytesrepr::{self, FromBytes, ToBytes},
     CLValue, Transfer,
 };
 
-use super::DeployInfo;
+use super::{ConversionError, DeployInfo};
 
 /// Representation of a value stored in global state.
 ///
@@ -20,12 +21,12 @@ use super::DeployInfo;
 /// encoding the resulting byte string.
 ///
 /// `Account` has its own `json_compatibility` representation (see its docs for further info).
-#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug, JsonSchema)]
 pub enum StoredValue {
     /// A CasperLabs value.
-    CLValue(CLValue),
+    CLValue(#[schemars(with = "serde_json::Value")] CLValue),
     /// An account.
-    Account(Account),
+    Account(#[schemars(with = "serde_json::Value")] Account),
     /// A contract's Wasm
     ContractWasm(String),
     /// Methods and type signatures supported by a contract.
@@ -33,7 +34,7 @@ pub enum StoredValue {
     /// A contract definition, metadata, and security container.
     ContractPackage(String),
     /// A record of a transfer
-    Transfer(Transfer),
+    Transfer(#[schemars(with = "serde_json::Value")] Transfer),
     /// A record of a deploy
     DeployInfo(DeployInfo),
 }
@@ -63,3 +64,79 @@ impl TryFrom<&ExecutionEngineStoredValue> for StoredValue {
         Ok(stored_value)
     }
 }
+
+impl TryFrom<StoredValue> for ExecutionEngineStoredValue {
+    type Error = ConversionError;
+
+    fn try_from(stored_value: StoredValue) -> Result<Self, Self::Error> {
+        let ee_stored_value = match stored_value {
+            StoredValue::CLValue(cl_value) => ExecutionEngineStoredValue::CLValue(cl_value),
+            StoredValue::Account(account) => ExecutionEngineStoredValue::Account(account),
+            StoredValue::ContractWasm(contract_wasm) => {
+                ExecutionEngineStoredValue::ContractWasm(from_hex(&contract_wasm)?)
+            }
+            StoredValue::Contract(contract) => {
+                ExecutionEngineStoredValue::Contract(from_hex(&contract)?)
+            }
+            StoredValue::ContractPackage(contract_package) => {
+                ExecutionEngineStoredValue::ContractPackage(from_hex(&contract_package)?)
+            }
+            StoredValue::Transfer(transfer) => ExecutionEngineStoredValue::Transfer(transfer),
+            StoredValue::DeployInfo(deploy_info) => {
+                ExecutionEngineStoredValue::DeployInfo((&deploy_info).try_into()?)
+            }
+        };
+
+        Ok(ee_stored_value)
+    }
+}
+
+fn from_hex<T: FromBytes>(hex_encoded: &str) -> Result<T, ConversionError> {
+    let bytes = hex::decode(hex_encoded)?;
+    bytesrepr::deserialize(bytes).map_err(ConversionError::BytesRepr)
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{account::AccountHash, AccessRights, Contract, ContractWasm, URef, U512};
+
+    use super::*;
+
+    fn round_trip(ee_stored_value: ExecutionEngineStoredValue) {
+        let stored_value = StoredValue::try_from(&ee_stored_value).unwrap();
+        let json = serde_json::to_string(&stored_value).unwrap();
+        let decoded: StoredValue = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            ExecutionEngineStoredValue::try_from(decoded).unwrap(),
+            ee_stored_value
+        );
+    }
+
+    #[test]
+    fn should_convert_back_losslessly() {
+        round_trip(ExecutionEngineStoredValue::CLValue(
+            CLValue::from_t((1u8, "one".to_string())).unwrap(),
+        ));
+        round_trip(ExecutionEngineStoredValue::ContractWasm(ContractWasm::new(
+            vec![0, 97, 115, 109],
+        )));
+        round_trip(ExecutionEngineStoredValue::Contract(Contract::default()));
+        round_trip(ExecutionEngineStoredValue::DeployInfo(
+            casper_types::DeployInfo::new(
+                [1; 32],
+                &[[2; 32], [3; 32]],
+                AccountHash::new([4; 32]),
+                URef::new([5; 32], AccessRights::READ_ADD_WRITE),
+                U512::from(12_345),
+            ),
+        ));
+    }
+
+    #[test]
+    fn should_fail_to_convert_invalid_values() {
+        let invalid_hex = StoredValue::Contract("not hex".to_string());
+        assert!(ExecutionEngineStoredValue::try_from(invalid_hex).is_err());
+        let truncated = StoredValue::ContractPackage("0102".to_string());
+        assert!(ExecutionEngineStoredValue::try_from(truncated).is_err());
+    }
+}
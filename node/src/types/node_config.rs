@@ -1,9 +1,14 @@
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
-use crate::{types::BlockHash, utils::External, Chainspec};
+use crate::{
+    types::{BlockHash, TrustedCheckpoint},
+    utils::External,
+    Chainspec,
+};
 
 const DEFAULT_CHAINSPEC_CONFIG_PATH: &str = "chainspec.toml";
+const DEFAULT_SIGNATURE_VERIFICATION_THREADS: usize = 4;
 
 /// Node configuration.
 #[derive(DataSize, Debug, Deserialize, Serialize)]
@@ -12,8 +17,24 @@ const DEFAULT_CHAINSPEC_CONFIG_PATH: &str = "chainspec.toml";
 pub struct NodeConfig {
     /// Chainspec configuration.
     pub chainspec_config_path: External<Chainspec>,
-    /// Hash used as a trust anchor when joining, if any.
+    /// Hash used as a trust anchor when joining, if any.  Ignored if trusted checkpoints are
+    /// configured.
     pub trusted_hash: Option<BlockHash>,
+    /// Block hashes with finality certificates, oldest first, the latest verifiable one of which
+    /// is used as the trust anchor when joining.
+    #[serde(default)]
+    pub trusted_checkpoints: Vec<TrustedCheckpoint>,
+    /// Whether to run in sidecar mode: following the linear chain and serving the RPC, REST and
+    /// event stream APIs, without loading a validator key or participating in consensus.
+    #[serde(default)]
+    pub sidecar: bool,
+    /// Number of threads dedicated to verifying deploy approvals and finality signatures.
+    #[serde(default = "default_signature_verification_threads")]
+    pub signature_verification_threads: usize,
+}
+
+fn default_signature_verification_threads() -> usize {
+    DEFAULT_SIGNATURE_VERIFICATION_THREADS
 }
 
 impl Default for NodeConfig {
@@ -21,6 +42,9 @@ impl Default for NodeConfig {
         NodeConfig {
             chainspec_config_path: External::path(DEFAULT_CHAINSPEC_CONFIG_PATH),
             trusted_hash: None,
+            trusted_checkpoints: vec![],
+            sidecar: false,
+            signature_verification_threads: DEFAULT_SIGNATURE_VERIFICATION_THREADS,
         }
     }
 }
@@ -6,11 +6,11 @@ use std::{
 use datasize::DataSize;
 use hex_fmt::HexFmt;
 use libp2p::PeerId;
-#[cfg(test)]
+#[cfg(any(feature = "testing", test))]
 use rand::{Rng, RngCore};
 use serde::{de::Error as SerdeError, Deserialize, Deserializer, Serialize, Serializer};
 
-#[cfg(test)]
+#[cfg(any(feature = "testing", test))]
 use crate::testing::TestRng;
 use crate::tls::KeyFingerprint;
 
@@ -24,7 +24,7 @@ pub enum NodeId {
 
 impl NodeId {
     /// Generates a random instance using a `TestRng`.
-    #[cfg(test)]
+    #[cfg(any(feature = "testing", test))]
     pub(crate) fn random(rng: &mut TestRng) -> Self {
         if rng.gen() {
             Self::random_tls(rng)
@@ -34,13 +34,13 @@ impl NodeId {
     }
 
     /// Generates a random Tls instance using a `TestRng`.
-    #[cfg(test)]
+    #[cfg(any(feature = "testing", test))]
     pub(crate) fn random_tls(rng: &mut TestRng) -> Self {
         NodeId::Tls(rng.gen())
     }
 
     /// Generates a random P2p instance using a `TestRng`.
-    #[cfg(test)]
+    #[cfg(any(feature = "testing", test))]
     pub(crate) fn random_p2p(rng: &mut TestRng) -> Self {
         let mut bytes = [0u8; 32];
         rng.fill_bytes(&mut bytes[..]);
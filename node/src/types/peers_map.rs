@@ -3,13 +3,14 @@ use std::{
     net::SocketAddr,
 };
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::types::NodeId;
 
 /// Map of peers.
-#[derive(Serialize, Deserialize, Debug, PartialOrd, PartialEq)]
-pub struct PeersMap(BTreeMap<String, SocketAddr>);
+#[derive(Serialize, Deserialize, Debug, PartialOrd, PartialEq, JsonSchema)]
+pub struct PeersMap(#[schemars(with = "BTreeMap<String, String>")] BTreeMap<String, SocketAddr>);
 
 impl From<HashMap<NodeId, SocketAddr>> for PeersMap {
     fn from(input: HashMap<NodeId, SocketAddr>) -> Self {
@@ -1,13 +1,19 @@
 use std::{collections::HashMap, hash::Hash, net::SocketAddr};
 
+use schemars::JsonSchema;
 use semver::Version;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     components::{chainspec_loader::ChainspecInfo, consensus::EraId},
+    crypto::hash::{self, Digest},
     types::{Block, BlockHash, NodeId, PeersMap, Timestamp},
 };
 
+/// The tag prepended to a signed status payload before hashing, so that its signature can't be
+/// passed off as that of a block or of a consensus message.
+const SIGNED_STATUS_TAG: &[u8] = b"casper-node signed status:";
+
 /// Data feed for client "info_get_status" endpoint.
 #[derive(Debug, Serialize)]
 #[serde(bound = "I: Eq + Hash + Serialize")]
@@ -18,8 +24,15 @@ pub struct StatusFeed<I> {
     pub peers: HashMap<I, SocketAddr>,
     /// The chainspec info for this node.
     pub chainspec_info: ChainspecInfo,
+    /// The node's progress in synchronizing the linear chain.
+    pub sync_progress: SyncProgress,
     /// The compiled node version.
     pub version: &'static str,
+    /// The range of blocks whose global state can be queried, if restricted.
+    pub historical_state_window: Option<HistoricalStateWindow>,
+    /// Whether the node is an archive node, guaranteeing that the global state of every block can
+    /// be queried.
+    pub archive: bool,
 }
 
 impl<I> StatusFeed<I> {
@@ -27,18 +40,92 @@ impl<I> StatusFeed<I> {
         last_added_block: Option<Block>,
         peers: HashMap<I, SocketAddr>,
         chainspec_info: ChainspecInfo,
+        sync_progress: SyncProgress,
     ) -> Self {
         StatusFeed {
             last_added_block,
             peers,
             chainspec_info,
+            sync_progress,
             version: crate::VERSION_STRING.as_str(),
+            historical_state_window: None,
+            archive: false,
+        }
+    }
+}
+
+/// The range of most recent blocks whose global state can be queried.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct HistoricalStateWindow {
+    /// The number of most recent blocks whose global state can be queried.
+    pub size: u64,
+    /// The height of the earliest block whose global state can be queried.
+    pub earliest_block_height: u64,
+}
+
+impl HistoricalStateWindow {
+    /// Returns the window of `size` blocks ending at the block at `maybe_highest_block_height`.
+    pub(crate) fn new(size: u64, maybe_highest_block_height: Option<u64>) -> Self {
+        let earliest_block_height = maybe_highest_block_height
+            .map(|height| (height + 1).saturating_sub(size))
+            .unwrap_or_default();
+        HistoricalStateWindow {
+            size,
+            earliest_block_height,
+        }
+    }
+
+    /// Returns whether the global state of the block at `block_height` can be queried.
+    pub(crate) fn contains(&self, block_height: u64) -> bool {
+        self.size > 0 && block_height >= self.earliest_block_height
+    }
+}
+
+/// The stage a node has reached in joining the network.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncState {
+    /// The node is joining the network without synchronizing the linear chain, or has finished
+    /// synchronizing it and is about to start following the chain.
+    Joining,
+    /// The node is synchronizing the linear chain from its peers.
+    Syncing,
+    /// The node has joined the network and is following the chain, validating if it is bonded.
+    Validating,
+}
+
+/// A node's progress in synchronizing the linear chain.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SyncProgress {
+    /// The stage the node has reached in joining the network.
+    pub state: SyncState,
+    /// The height of the highest block received from peers while synchronizing.
+    pub highest_peer_block_height: Option<u64>,
+    /// The estimated number of blocks still to be synchronized, including those expected to have
+    /// been added to the chain since the highest block received from peers.
+    pub estimated_blocks_remaining: Option<u64>,
+    /// The average number of blocks received from peers per second since synchronizing started.
+    pub download_rate: Option<f64>,
+    /// The height of the highest block executed before the node was last restarted, if
+    /// synchronizing resumed from there rather than starting over.
+    pub resumed_from_height: Option<u64>,
+}
+
+impl SyncProgress {
+    /// Returns the progress of a node in the given state, with no synchronization statistics.
+    pub(crate) fn new(state: SyncState) -> Self {
+        SyncProgress {
+            state,
+            highest_peer_block_height: None,
+            estimated_blocks_remaining: None,
+            download_rate: None,
+            resumed_from_height: None,
         }
     }
 }
 
 /// Minimal info of a `Block`.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub struct MinimalBlockInfo {
     hash: BlockHash,
     timestamp: Timestamp,
@@ -58,9 +145,10 @@ impl From<Block> for MinimalBlockInfo {
 }
 
 /// Result for "info_get_status" RPC response.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub struct GetStatusResult {
     /// The RPC API version.
+    #[schemars(with = "Option<String>")]
     pub api_version: Option<Version>,
     /// The chainspec name.
     pub chainspec_name: String,
@@ -70,8 +158,28 @@ pub struct GetStatusResult {
     pub peers: PeersMap,
     /// The minimal info of the last block from the linear chain.
     pub last_added_block_info: Option<MinimalBlockInfo>,
+    /// The era of the last finalized block added to the linear chain.
+    pub last_finalized_era_id: Option<EraId>,
+    /// The stage the node has reached in joining the network.
+    pub sync_state: SyncState,
+    /// The height of the highest block received from peers while synchronizing.
+    pub highest_peer_block_height: Option<u64>,
+    /// The estimated number of blocks still to be synchronized.
+    pub estimated_blocks_remaining: Option<u64>,
+    /// The average number of blocks received from peers per second since synchronizing started.
+    pub download_rate: Option<f64>,
+    /// The height of the highest block executed before the node was last restarted, if
+    /// synchronizing resumed from there.
+    pub resumed_from_height: Option<u64>,
     /// The compiled node version.
     pub build_version: String,
+    /// The range of blocks whose global state can be queried, if restricted.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub historical_state_window: Option<HistoricalStateWindow>,
+    /// Whether the node is an archive node, guaranteeing that the global state of every block can
+    /// be queried.
+    #[serde(default)]
+    pub archive: bool,
 }
 
 impl GetStatusResult {
@@ -91,15 +199,54 @@ impl From<StatusFeed<NodeId>> for GetStatusResult {
             .to_string();
         let api_version = None;
         let peers = status_feed.peers.into();
+        let last_finalized_era_id = status_feed
+            .last_added_block
+            .as_ref()
+            .map(|block| block.header().era_id());
         let last_added_block_info = status_feed.last_added_block.map(Into::into);
+        let SyncProgress {
+            state: sync_state,
+            highest_peer_block_height,
+            estimated_blocks_remaining,
+            download_rate,
+            resumed_from_height,
+        } = status_feed.sync_progress;
         let build_version = crate::VERSION_STRING.clone();
+        let historical_state_window = status_feed.historical_state_window;
+        let archive = status_feed.archive;
         GetStatusResult {
             api_version,
             chainspec_name,
             genesis_root_hash,
             peers,
             last_added_block_info,
+            last_finalized_era_id,
+            sync_state,
+            highest_peer_block_height,
+            estimated_blocks_remaining,
+            download_rate,
+            resumed_from_height,
             build_version,
+            historical_state_window,
+            archive,
         }
     }
 }
+
+/// The status of a node as signed by it for "info_get_signed_status".
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct SignedStatusPayload {
+    /// The status of the node.
+    pub status: GetStatusResult,
+    /// The nonce given by the client, binding the signature to its request.
+    pub nonce: Option<String>,
+    /// The time at which the status was signed.
+    pub timestamp: Timestamp,
+}
+
+/// Returns the digest signed by a node for the given serialized `SignedStatusPayload`.
+pub fn signed_status_digest(payload: &[u8]) -> Digest {
+    let mut bytes = SIGNED_STATUS_TAG.to_vec();
+    bytes.extend_from_slice(payload);
+    hash::hash(bytes)
+}
@@ -10,6 +10,7 @@ use derive_more::{Add, AddAssign, From, Shl, Shr, Sub, SubAssign};
 use humantime::{DurationError, TimestampError};
 #[cfg(test)]
 use rand::Rng;
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
 use serde::{de::Error as SerdeError, Deserialize, Deserializer, Serialize, Serializer};
 
 use casper_types::bytesrepr::{self, FromBytes, ToBytes};
@@ -138,6 +139,19 @@ impl Serialize for Timestamp {
     }
 }
 
+impl JsonSchema for Timestamp {
+    fn schema_name() -> String {
+        String::from("Timestamp")
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let mut schema_object = gen.subschema_for::<String>().into_object();
+        schema_object.metadata().description =
+            Some(String::from("Timestamp formatted as per RFC 3339"));
+        schema_object.into()
+    }
+}
+
 impl<'de> Deserialize<'de> for Timestamp {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         if deserializer.is_human_readable() {
@@ -246,6 +260,19 @@ impl Serialize for TimeDiff {
     }
 }
 
+impl JsonSchema for TimeDiff {
+    fn schema_name() -> String {
+        String::from("TimeDiff")
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let mut schema_object = gen.subschema_for::<String>().into_object();
+        schema_object.metadata().description =
+            Some(String::from("Human-readable duration, e.g. \"1h 30m\""));
+        schema_object.into()
+    }
+}
+
 impl<'de> Deserialize<'de> for TimeDiff {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         if deserializer.is_human_readable() {
@@ -274,7 +301,6 @@ impl FromBytes for TimeDiff {
     }
 }
 
-#[cfg(test)]
 impl From<Duration> for TimeDiff {
     fn from(duration: Duration) -> TimeDiff {
         TimeDiff(duration.as_millis() as u64)
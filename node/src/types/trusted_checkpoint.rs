@@ -0,0 +1,255 @@
+//! Trusted checkpoints: hashes of blocks on the linear chain, distributed out-of-band together with
+//! a finality certificate, i.e. signatures of the block hash by validators of the block's era.
+//!
+//! A joining node only trusts a checkpoint once the signers are shown to hold more than two thirds
+//! of the total weight of the era's validators, as recorded in its own copy of global state.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use casper_types::U512;
+
+use crate::{
+    components::consensus::EraId,
+    crypto::asymmetric_key::{self, PublicKey, Signature},
+    types::BlockHash,
+};
+
+/// A validator's signature of a checkpoint's block hash.
+#[derive(Clone, DataSize, Debug, PartialEq, Eq, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct CheckpointSignature {
+    /// The validator's public key.
+    pub public_key: PublicKey,
+    /// The validator's signature of the block hash.
+    pub signature: Signature,
+}
+
+/// A block hash trusted as the starting point for joining the network, once its finality
+/// certificate has been verified.
+#[derive(Clone, DataSize, Debug, PartialEq, Eq, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct TrustedCheckpoint {
+    /// The hash of the block.
+    pub block_hash: BlockHash,
+    /// The era of the block, whose validators signed the certificate.
+    pub era_id: EraId,
+    /// The finality certificate.
+    pub signatures: Vec<CheckpointSignature>,
+}
+
+/// The reason a trusted checkpoint was not accepted.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CheckpointError {
+    /// A signature in the certificate is not valid for the block hash.
+    #[error("invalid signature by {public_key} in the certificate of checkpoint {block_hash}")]
+    InvalidSignature {
+        /// The checkpoint's block hash.
+        block_hash: BlockHash,
+        /// The signer.
+        public_key: PublicKey,
+    },
+    /// A signer is not a validator of the checkpoint's era.
+    #[error("{public_key} signed checkpoint {block_hash} but is not a validator of its era")]
+    NotAValidator {
+        /// The checkpoint's block hash.
+        block_hash: BlockHash,
+        /// The signer.
+        public_key: PublicKey,
+    },
+    /// A validator signed more than once.
+    #[error("{public_key} signed checkpoint {block_hash} more than once")]
+    DuplicateSigner {
+        /// The checkpoint's block hash.
+        block_hash: BlockHash,
+        /// The signer.
+        public_key: PublicKey,
+    },
+    /// The signers don't hold enough weight to finalize the block.
+    #[error(
+        "signers of checkpoint {block_hash} hold a weight of {signed_weight} out of \
+         {total_weight}, not more than two thirds"
+    )]
+    InsufficientWeight {
+        /// The checkpoint's block hash.
+        block_hash: BlockHash,
+        /// The total weight of the signers.
+        signed_weight: U512,
+        /// The total weight of the era's validators.
+        total_weight: U512,
+    },
+    /// None of the configured checkpoints could be verified, as the validator weights of their
+    /// eras are not recorded in the node's global state.
+    #[error("validator weights unknown for the eras of all trusted checkpoints")]
+    NoneVerifiable,
+}
+
+impl TrustedCheckpoint {
+    /// Verifies the finality certificate against the weights of the validators of the checkpoint's
+    /// era.
+    ///
+    /// The signers are checked first, and the signatures then verified together in a batch.
+    pub(crate) fn verify(
+        &self,
+        validator_weights: &BTreeMap<casper_types::PublicKey, U512>,
+    ) -> Result<(), CheckpointError> {
+        let block_hash = self.block_hash;
+        let mut signers = BTreeSet::new();
+        let mut signed_weight = U512::zero();
+        for CheckpointSignature { public_key, .. } in &self.signatures {
+            let public_key = *public_key;
+            if !signers.insert(public_key) {
+                return Err(CheckpointError::DuplicateSigner {
+                    block_hash,
+                    public_key,
+                });
+            }
+            let weight = validator_weights.get(&public_key.into()).ok_or(
+                CheckpointError::NotAValidator {
+                    block_hash,
+                    public_key,
+                },
+            )?;
+            signed_weight += *weight;
+        }
+
+        let signed: Vec<_> = self
+            .signatures
+            .iter()
+            .map(|checkpoint_signature| {
+                (
+                    block_hash.inner(),
+                    checkpoint_signature.signature,
+                    checkpoint_signature.public_key,
+                )
+            })
+            .collect();
+        if let Err((index, _)) = asymmetric_key::verify_batch(&signed) {
+            return Err(CheckpointError::InvalidSignature {
+                block_hash,
+                public_key: self.signatures[index].public_key,
+            });
+        }
+
+        let total_weight = validator_weights
+            .values()
+            .fold(U512::zero(), |sum, weight| sum + *weight);
+        if signed_weight * U512::from(3) <= total_weight * U512::from(2) {
+            return Err(CheckpointError::InsufficientWeight {
+                block_hash,
+                signed_weight,
+                total_weight,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{crypto::asymmetric_key::SecretKey, testing::TestRng};
+
+    type Validator = (SecretKey, PublicKey, U512);
+
+    fn validators(rng: &mut TestRng, weights: &[u64]) -> Vec<Validator> {
+        weights
+            .iter()
+            .map(|weight| {
+                let secret_key = SecretKey::random(rng);
+                let public_key = PublicKey::from(&secret_key);
+                (secret_key, public_key, U512::from(*weight))
+            })
+            .collect()
+    }
+
+    fn weights(validators: &[Validator]) -> BTreeMap<casper_types::PublicKey, U512> {
+        validators
+            .iter()
+            .map(|(_, public_key, weight)| ((*public_key).into(), *weight))
+            .collect()
+    }
+
+    fn checkpoint(
+        rng: &mut TestRng,
+        block_hash: BlockHash,
+        signers: &[&Validator],
+    ) -> TrustedCheckpoint {
+        let signatures = signers
+            .iter()
+            .map(|(secret_key, public_key, _)| CheckpointSignature {
+                public_key: *public_key,
+                signature: asymmetric_key::sign(block_hash.inner(), secret_key, public_key, rng),
+            })
+            .collect();
+        TrustedCheckpoint {
+            block_hash,
+            era_id: EraId(3),
+            signatures,
+        }
+    }
+
+    #[test]
+    fn should_require_more_than_two_thirds_of_the_weight() {
+        let mut rng = crate::new_rng();
+        let block_hash = BlockHash::random(&mut rng);
+
+        let quorum = validators(&mut rng, &[40, 30, 30]);
+        let certificate = checkpoint(&mut rng, block_hash, &[&quorum[0], &quorum[1]]);
+        assert!(certificate.verify(&weights(&quorum)).is_ok());
+
+        // exactly two thirds is not enough
+        let no_quorum = validators(&mut rng, &[40, 20, 30]);
+        let certificate = checkpoint(&mut rng, block_hash, &[&no_quorum[0], &no_quorum[1]]);
+        assert_eq!(
+            certificate.verify(&weights(&no_quorum)),
+            Err(CheckpointError::InsufficientWeight {
+                block_hash,
+                signed_weight: U512::from(60),
+                total_weight: U512::from(90),
+            })
+        );
+    }
+
+    #[test]
+    fn should_reject_invalid_certificates() {
+        let mut rng = crate::new_rng();
+        let validators = validators(&mut rng, &[1, 1, 1]);
+        // the third validator is not one of the era's validators
+        let weights = weights(&validators[..2]);
+        let block_hash = BlockHash::random(&mut rng);
+
+        let duplicate = checkpoint(&mut rng, block_hash, &[&validators[0], &validators[0]]);
+        assert_eq!(
+            duplicate.verify(&weights),
+            Err(CheckpointError::DuplicateSigner {
+                block_hash,
+                public_key: validators[0].1,
+            })
+        );
+
+        let outsider = checkpoint(&mut rng, block_hash, &[&validators[0], &validators[2]]);
+        assert_eq!(
+            outsider.verify(&weights),
+            Err(CheckpointError::NotAValidator {
+                block_hash,
+                public_key: validators[2].1,
+            })
+        );
+
+        let mut forged = checkpoint(&mut rng, block_hash, &[&validators[0], &validators[1]]);
+        forged.block_hash = BlockHash::random(&mut rng);
+        assert_eq!(
+            forged.verify(&weights),
+            Err(CheckpointError::InvalidSignature {
+                block_hash: forged.block_hash,
+                public_key: validators[0].1,
+            })
+        );
+    }
+}
@@ -4,27 +4,36 @@
 mod external;
 mod median;
 pub mod milliseconds;
+pub mod passphrase;
 mod round_robin;
+mod verification_pool;
 
 use std::{
     cell::RefCell,
     fmt::{self, Display, Formatter},
-    fs, io,
+    fs,
+    future::Future,
+    io,
     net::{SocketAddr, ToSocketAddrs},
     path::{Path, PathBuf},
+    time::Duration,
 };
 
 use datasize::DataSize;
+use futures::future;
 use lazy_static::lazy_static;
 use libc::{c_long, sysconf, _SC_PAGESIZE};
 use serde::Serialize;
 use thiserror::Error;
+use tokio::sync::oneshot;
 
 #[cfg(test)]
 pub use external::RESOURCES_PATH;
 pub use external::{External, LoadError, Loadable};
 pub(crate) use median::weighted_median;
 pub(crate) use round_robin::WeightedRoundRobin;
+pub use verification_pool::Error as VerificationPoolError;
+pub(crate) use verification_pool::VerificationPool;
 
 /// Sensible default for many if not all systems.
 const DEFAULT_PAGE_SIZE: usize = 4096;
@@ -52,6 +61,31 @@ pub(crate) fn resolve_address(addr: &str) -> io::Result<SocketAddr> {
     })
 }
 
+/// Creates the signal and the deadline for shutting down a server gracefully.
+///
+/// The signal completes once `shutdown_receiver` does, and should be passed to the server's
+/// `with_graceful_shutdown`, upon which it stops accepting new connections and finishes serving the
+/// in-flight requests.  The deadline completes `timeout` after the signal, after which the server
+/// should be dropped, closing any connections still open.
+pub(crate) fn graceful_shutdown_with_deadline(
+    shutdown_receiver: oneshot::Receiver<()>,
+    timeout: Duration,
+) -> (impl Future<Output = ()>, impl Future<Output = ()>) {
+    let (started_sender, started_receiver) = oneshot::channel::<()>();
+    let signal = async move {
+        let _ = shutdown_receiver.await;
+        let _ = started_sender.send(());
+    };
+    let deadline = async move {
+        match started_receiver.await {
+            Ok(()) => tokio::time::delay_for(timeout).await,
+            // The server stopped without being signalled, so there is nothing to wait for.
+            Err(_) => future::pending().await,
+        }
+    };
+    (signal, deadline)
+}
+
 /// Moves a value to the heap and then forgets about, leaving only a static reference behind.
 #[inline]
 pub(crate) fn leak<T>(value: T) -> &'static T {
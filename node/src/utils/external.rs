@@ -18,7 +18,10 @@ use thiserror::Error;
 
 use super::{read_file, ReadFileError};
 use crate::{
-    crypto::{self, asymmetric_key::SecretKey},
+    crypto::{
+        self,
+        asymmetric_key::{PublicKey, SecretKey},
+    },
     tls,
 };
 
@@ -176,6 +179,14 @@ impl Loadable for SecretKey {
     }
 }
 
+impl Loadable for PublicKey {
+    type Error = crypto::Error;
+
+    fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Self::Error> {
+        PublicKey::from_file(path)
+    }
+}
+
 impl Loadable for Vec<u8> {
     type Error = ReadFileError;
 
@@ -0,0 +1,157 @@
+//! Retrieval of the passphrase used to encrypt and decrypt secret key files.
+
+use std::{
+    env,
+    io::{self, BufRead, Write},
+    path::{Path, PathBuf},
+};
+
+use thiserror::Error;
+
+use super::{read_file, ReadFileError};
+
+/// Environment variable from which the passphrase is read if no passphrase file is given.
+pub const PASSPHRASE_ENV_VAR: &str = "CASPER_SECRET_KEY_PASSPHRASE";
+
+/// Error retrieving a passphrase.
+#[derive(Debug, Error)]
+pub enum PassphraseError {
+    /// Failed to read the passphrase file.
+    #[error("could not read passphrase file: {0}")]
+    File(#[from] ReadFileError),
+
+    /// Failed to prompt for the passphrase on the terminal.
+    #[error("could not prompt for passphrase at {path}: {error}")]
+    Prompt {
+        /// The path of the terminal.
+        path: PathBuf,
+        /// The underlying error.
+        error: io::Error,
+    },
+
+    /// The passphrase is empty.
+    #[error("passphrase is empty")]
+    Empty,
+}
+
+/// Returns the passphrase held in the file at `maybe_path` if given, otherwise the one held in the
+/// `CASPER_SECRET_KEY_PASSPHRASE` environment variable if set, otherwise prompts for it on the
+/// terminal.
+///
+/// A single trailing newline is stripped from passphrases read from a file.
+pub fn get_passphrase(maybe_path: Option<&Path>, prompt: &str) -> Result<Vec<u8>, PassphraseError> {
+    let passphrase = if let Some(path) = maybe_path {
+        let mut passphrase = read_file(path)?;
+        if passphrase.ends_with(b"\n") {
+            passphrase.pop();
+            if passphrase.ends_with(b"\r") {
+                passphrase.pop();
+            }
+        }
+        passphrase
+    } else if let Some(passphrase) = env::var_os(PASSPHRASE_ENV_VAR) {
+        passphrase.to_string_lossy().into_owned().into_bytes()
+    } else {
+        prompt_passphrase(prompt)?.into_bytes()
+    };
+
+    if passphrase.is_empty() {
+        return Err(PassphraseError::Empty);
+    }
+    Ok(passphrase)
+}
+
+/// Prompts for a passphrase on the controlling terminal, without echoing it.
+pub fn prompt_passphrase(prompt: &str) -> Result<String, PassphraseError> {
+    const TTY_PATH: &str = "/dev/tty";
+
+    let to_error = |error| PassphraseError::Prompt {
+        path: PathBuf::from(TTY_PATH),
+        error,
+    };
+
+    let tty = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(TTY_PATH)
+        .map_err(to_error)?;
+    let mut writer = &tty;
+    writer
+        .write_all(prompt.as_bytes())
+        .and_then(|_| writer.flush())
+        .map_err(to_error)?;
+
+    let line = {
+        let _echo_guard = EchoGuard::disable(&tty).map_err(to_error)?;
+        let mut line = String::new();
+        io::BufReader::new(&tty)
+            .read_line(&mut line)
+            .map_err(to_error)?;
+        line
+    };
+    // The newline typed by the user was not echoed.
+    writer.write_all(b"\n").map_err(to_error)?;
+
+    Ok(line
+        .trim_end_matches(|c| c == '\n' || c == '\r')
+        .to_string())
+}
+
+/// Disables echoing on a terminal until dropped.
+struct EchoGuard {
+    fd: libc::c_int,
+    original: libc::termios,
+}
+
+impl EchoGuard {
+    fn disable<T: std::os::unix::io::AsRawFd>(terminal: &T) -> io::Result<Self> {
+        let fd = terminal.as_raw_fd();
+        // Safe as `termios` is a plain C struct which is fully initialized by `tcgetattr`.
+        let mut original: libc::termios = unsafe { std::mem::zeroed() };
+        if unsafe { libc::tcgetattr(fd, &mut original) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let mut silent = original;
+        silent.c_lflag &= !libc::ECHO;
+        silent.c_lflag |= libc::ECHONL;
+        if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &silent) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(EchoGuard { fd, original })
+    }
+}
+
+impl Drop for EchoGuard {
+    fn drop(&mut self) {
+        unsafe {
+            libc::tcsetattr(self.fd, libc::TCSANOW, &self.original);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_read_passphrase_from_file() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("passphrase");
+        std::fs::write(&path, "secret\n").unwrap();
+
+        let passphrase = get_passphrase(Some(&path), "").unwrap();
+        assert_eq!(passphrase, b"secret");
+    }
+
+    #[test]
+    fn should_reject_empty_passphrase_file() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("passphrase");
+        std::fs::write(&path, "\n").unwrap();
+
+        assert!(matches!(
+            get_passphrase(Some(&path), ""),
+            Err(PassphraseError::Empty)
+        ));
+    }
+}
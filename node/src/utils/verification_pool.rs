@@ -0,0 +1,106 @@
+//! A thread pool dedicated to verifying signatures.
+
+use std::{future::Future, sync::Arc};
+
+use prometheus::{IntGauge, Registry};
+use rayon::{ThreadPool, ThreadPoolBuildError, ThreadPoolBuilder};
+use thiserror::Error;
+use tokio::sync::oneshot;
+
+/// Error creating a [`VerificationPool`].
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Failed to register the pool's metrics.
+    #[error("prometheus (metrics) error: {0}")]
+    Metrics(#[from] prometheus::Error),
+    /// Failed to spawn the pool's threads.
+    #[error("failed to build signature verification thread pool: {0}")]
+    ThreadPool(#[from] ThreadPoolBuildError),
+}
+
+/// A pool of threads on which signatures, e.g. of deploy approvals and finality signatures, are
+/// verified off the reactor's threads.
+///
+/// Verifications are queued and run at most as many at a time as the pool has threads, so that a
+/// burst of them neither stalls event processing nor competes with other blocking tasks.  Clones
+/// share the same pool.
+#[derive(Clone, Debug)]
+pub(crate) struct VerificationPool {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    thread_pool: ThreadPool,
+    /// The number of verifications queued but not yet started.
+    queue_depth: IntGauge,
+    /// Instance of registry to unregister from when being dropped.
+    registry: Registry,
+}
+
+impl VerificationPool {
+    /// Creates a pool of `threads` threads, registering its metrics with `registry`.
+    pub(crate) fn new(threads: usize, registry: &Registry) -> Result<Self, Error> {
+        let thread_pool = ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .thread_name(|index| format!("signature-verification-{}", index))
+            .build()?;
+        let queue_depth = IntGauge::new(
+            "signature_verification_queue_depth",
+            "number of signature verifications waiting for a thread",
+        )?;
+        registry.register(Box::new(queue_depth.clone()))?;
+        Ok(VerificationPool {
+            inner: Arc::new(Inner {
+                thread_pool,
+                queue_depth,
+                registry: registry.clone(),
+            }),
+        })
+    }
+
+    /// Queues `verification` to run on the pool, returning a future resolving to its result.
+    pub(crate) fn run<T, F>(&self, verification: F) -> impl Future<Output = T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let (sender, receiver) = oneshot::channel();
+        let queue_depth = self.inner.queue_depth.clone();
+        queue_depth.inc();
+        self.inner.thread_pool.spawn(move || {
+            queue_depth.dec();
+            let _ = sender.send(verification());
+        });
+        async move { receiver.await.expect("verification should not panic") }
+    }
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        self.registry
+            .unregister(Box::new(self.queue_depth.clone()))
+            .expect("did not expect deregistering signature_verification_queue_depth to fail");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_run_verifications_on_pool() {
+        let registry = Registry::new();
+        let pool = VerificationPool::new(2, &registry).unwrap();
+
+        let results = futures::future::join_all((0..10).map(|index| pool.run(move || index * 2)));
+        assert_eq!(
+            results.await,
+            (0..10).map(|index| index * 2).collect::<Vec<_>>()
+        );
+        assert_eq!(pool.inner.queue_depth.get(), 0);
+
+        drop(pool);
+        assert!(registry.gather().is_empty());
+    }
+}
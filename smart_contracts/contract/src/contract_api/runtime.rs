@@ -154,7 +154,13 @@ fn get_named_arg_size(name: &str) -> Option<usize> {
 /// Note that this is only relevant to contracts stored on-chain since a contract deployed directly
 /// is not invoked with any arguments.
 pub fn get_named_arg<T: FromBytes>(name: &str) -> T {
-    let arg_size = get_named_arg_size(name).unwrap_or_revert_with(ApiError::MissingArgument);
+    try_get_named_arg(name).unwrap_or_revert_with(ApiError::MissingArgument)
+}
+
+/// Returns given named argument passed to the host for the current module invocation, or `None` if
+/// no argument with the given name was passed.
+pub fn try_get_named_arg<T: FromBytes>(name: &str) -> Option<T> {
+    let arg_size = get_named_arg_size(name)?;
     let arg_bytes = if arg_size > 0 {
         let res = {
             let data_non_null_ptr = contract_api::alloc_bytes(arg_size);
@@ -176,7 +182,8 @@ pub fn get_named_arg<T: FromBytes>(name: &str) -> T {
         // Avoids allocation with 0 bytes and a call to get_named_arg
         Vec::new()
     };
-    bytesrepr::deserialize(arg_bytes).unwrap_or_revert_with(ApiError::InvalidArgument)
+    let value = bytesrepr::deserialize(arg_bytes).unwrap_or_revert_with(ApiError::InvalidArgument);
+    Some(value)
 }
 
 /// Returns the caller of the current context, i.e. the [`AccountHash`] of the account which made
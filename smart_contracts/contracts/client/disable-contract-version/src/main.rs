@@ -0,0 +1,25 @@
+#![no_std]
+#![no_main]
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{ContractHash, ContractPackageHash};
+
+const ARG_CONTRACT_PACKAGE_HASH: &str = "contract_package_hash";
+const ARG_CONTRACT_HASH: &str = "contract_hash";
+
+// Disable contract version contract.
+//
+// Accepts the hash of a contract package and the hash of one of its contract versions, and
+// disables that version so that it can no longer be called.  The calling account must hold the
+// package's access key in its named keys.
+#[no_mangle]
+pub extern "C" fn call() {
+    let contract_package_hash: ContractPackageHash =
+        runtime::get_named_arg(ARG_CONTRACT_PACKAGE_HASH);
+    let contract_hash: ContractHash = runtime::get_named_arg(ARG_CONTRACT_HASH);
+
+    storage::disable_contract_version(contract_package_hash, contract_hash).unwrap_or_revert();
+}
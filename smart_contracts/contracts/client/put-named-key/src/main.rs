@@ -0,0 +1,24 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use casper_contract::contract_api::runtime;
+use casper_types::Key;
+
+const ARG_NAME: &str = "name";
+const ARG_KEY: &str = "key";
+
+// Put named key contract.
+//
+// Accepts a name and a key, and adds the key to the calling account's named keys under that name,
+// replacing any key already held under it.
+#[no_mangle]
+pub extern "C" fn call() {
+    let name: String = runtime::get_named_arg(ARG_NAME);
+    let key: Key = runtime::get_named_arg(ARG_KEY);
+
+    runtime::put_key(&name, key);
+}
@@ -0,0 +1,20 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use casper_contract::contract_api::runtime;
+
+const ARG_NAME: &str = "name";
+
+// Remove named key contract.
+//
+// Accepts a name and removes the key held under it from the calling account's named keys, if any.
+#[no_mangle]
+pub extern "C" fn call() {
+    let name: String = runtime::get_named_arg(ARG_NAME);
+
+    runtime::remove_key(&name);
+}
@@ -0,0 +1,33 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use casper_contract::contract_api::{runtime, system};
+use casper_types::{auction, runtime_args, PublicKey, RuntimeArgs};
+
+fn rotate_signing_key(public_key: PublicKey, new_signing_key: PublicKey, signature: Vec<u8>) {
+    let contract_hash = system::get_auction();
+    let args = runtime_args! {
+        auction::ARG_PUBLIC_KEY => public_key,
+        auction::ARG_NEW_SIGNING_KEY => new_signing_key,
+        auction::ARG_SIGNATURE => signature,
+    };
+    runtime::call_contract(contract_hash, auction::METHOD_ROTATE_SIGNING_KEY, args)
+}
+
+// Rotate signing key contract.
+//
+// Accepts the public key of an existing bid, the new key its validator will sign with in
+// consensus and the new key's signature proving control of it.  Passing the bid's own public key as
+// the new signing key reverts to signing with it, in which case the signature is ignored.
+#[no_mangle]
+pub extern "C" fn call() {
+    let public_key = runtime::get_named_arg(auction::ARG_PUBLIC_KEY);
+    let new_signing_key = runtime::get_named_arg(auction::ARG_NEW_SIGNING_KEY);
+    let signature = runtime::get_named_arg(auction::ARG_SIGNATURE);
+
+    rotate_signing_key(public_key, new_signing_key, signature);
+}
@@ -10,16 +10,17 @@ use casper_contract::{
 };
 use casper_types::{
     auction::{
-        Bid, Bids, EraId, SeigniorageRecipient, SeigniorageRecipients,
+        Bid, Bids, Delegator, EraId, EraInfo, SeigniorageRecipient, SeigniorageRecipients,
         SeigniorageRecipientsSnapshot, UnbondingPurses, ValidatorWeights, ARG_AUCTION_DELAY,
-        ARG_GENESIS_VALIDATORS, ARG_LOCKED_FUNDS_PERIOD, ARG_MINT_CONTRACT_PACKAGE_HASH,
-        ARG_VALIDATOR_SLOTS, AUCTION_DELAY_KEY, BIDS_KEY, DELEGATOR_REWARD_PURSE_KEY, ERA_ID_KEY,
-        INITIAL_ERA_ID, LOCKED_FUNDS_PERIOD_KEY, SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY,
-        UNBONDING_PURSES_KEY, VALIDATOR_REWARD_PURSE_KEY, VALIDATOR_SLOTS_KEY,
+        ARG_GENESIS_DELEGATORS, ARG_GENESIS_VALIDATORS, ARG_LOCKED_FUNDS_PERIOD,
+        ARG_MINT_CONTRACT_PACKAGE_HASH, ARG_VALIDATOR_SLOTS, AUCTION_DELAY_KEY, BIDS_KEY,
+        DELEGATOR_REWARD_PURSE_KEY, ERA_ID_KEY, ERA_INFO_KEY, INITIAL_ERA_ID,
+        LOCKED_FUNDS_PERIOD_KEY, SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY, UNBONDING_PURSES_KEY,
+        VALIDATOR_REWARD_PURSE_KEY, VALIDATOR_SLOTS_KEY,
     },
     contracts::{NamedKeys, CONTRACT_INITIAL_VERSION},
     runtime_args,
-    system_contract_errors::mint,
+    system_contract_errors::{auction::Error, mint},
     CLValue, ContractPackageHash, PublicKey, RuntimeArgs, URef, U512,
 };
 
@@ -59,6 +60,26 @@ pub extern "C" fn install() {
             initial_validator_weights.insert(validator_public_key, amount);
         }
 
+        let genesis_delegators: BTreeMap<PublicKey, BTreeMap<PublicKey, U512>> =
+            runtime::get_named_arg(ARG_GENESIS_DELEGATORS);
+
+        for (validator_public_key, delegations) in genesis_delegators {
+            let founding_validator = validators
+                .get_mut(&validator_public_key)
+                .unwrap_or_revert_with(Error::ValidatorNotFound);
+            let validator_weight = initial_validator_weights
+                .get_mut(&validator_public_key)
+                .unwrap_or_revert_with(Error::ValidatorNotFound);
+            for (delegator_public_key, amount) in delegations {
+                let bonding_purse = create_purse(mint_package_hash, amount);
+                let delegator = Delegator::new(amount, bonding_purse, validator_public_key);
+                founding_validator
+                    .delegators_mut()
+                    .insert(delegator_public_key, delegator);
+                *validator_weight += amount;
+            }
+        }
+
         let auction_delay: u64 = runtime::get_named_arg(ARG_AUCTION_DELAY);
         let initial_snapshot_range = INITIAL_ERA_ID..=INITIAL_ERA_ID + auction_delay;
 
@@ -76,6 +97,10 @@ pub extern "C" fn install() {
             storage::new_uref(initial_seigniorage_recipients).into(),
         );
         named_keys.insert(BIDS_KEY.into(), storage::new_uref(validators).into());
+        named_keys.insert(
+            ERA_INFO_KEY.into(),
+            storage::new_uref(EraInfo::new()).into(),
+        );
         named_keys.insert(
             UNBONDING_PURSES_KEY.into(),
             storage::new_uref(UnbondingPurses::new()).into(),
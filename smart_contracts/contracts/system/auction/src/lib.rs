@@ -3,7 +3,7 @@
 #[macro_use]
 extern crate alloc;
 
-use alloc::{boxed::Box, collections::BTreeMap};
+use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
 use core::result::Result as StdResult;
 
 use casper_contract::{
@@ -15,12 +15,13 @@ use casper_types::{
     auction::{
         Auction, DelegationRate, MintProvider, RuntimeProvider, SeigniorageRecipients,
         StorageProvider, SystemProvider, ValidatorWeights, ARG_AMOUNT, ARG_DELEGATION_RATE,
-        ARG_DELEGATOR, ARG_DELEGATOR_PUBLIC_KEY, ARG_PUBLIC_KEY, ARG_REWARD_FACTORS,
-        ARG_SOURCE_PURSE, ARG_TARGET_PURSE, ARG_UNBOND_PURSE, ARG_VALIDATOR,
-        ARG_VALIDATOR_PUBLIC_KEY, ARG_VALIDATOR_PUBLIC_KEYS, METHOD_ADD_BID, METHOD_DELEGATE,
-        METHOD_DISTRIBUTE, METHOD_GET_ERA_VALIDATORS, METHOD_READ_ERA_ID,
-        METHOD_READ_SEIGNIORAGE_RECIPIENTS, METHOD_RUN_AUCTION, METHOD_SLASH, METHOD_UNDELEGATE,
-        METHOD_WITHDRAW_BID, METHOD_WITHDRAW_DELEGATOR_REWARD, METHOD_WITHDRAW_VALIDATOR_REWARD,
+        ARG_DELEGATOR, ARG_DELEGATOR_PUBLIC_KEY, ARG_NEW_SIGNING_KEY, ARG_PUBLIC_KEY,
+        ARG_REWARD_FACTORS, ARG_SIGNATURE, ARG_SOURCE_PURSE, ARG_TARGET_PURSE, ARG_UNBOND_PURSE,
+        ARG_VALIDATOR, ARG_VALIDATOR_PUBLIC_KEY, ARG_VALIDATOR_PUBLIC_KEYS, METHOD_ADD_BID,
+        METHOD_DELEGATE, METHOD_DISTRIBUTE, METHOD_GET_ERA_VALIDATORS, METHOD_READ_ERA_ID,
+        METHOD_READ_SEIGNIORAGE_RECIPIENTS, METHOD_ROTATE_SIGNING_KEY, METHOD_RUN_AUCTION,
+        METHOD_SLASH, METHOD_UNDELEGATE, METHOD_WITHDRAW_BID, METHOD_WITHDRAW_DELEGATOR_REWARD,
+        METHOD_WITHDRAW_VALIDATOR_REWARD,
     },
     bytesrepr::{FromBytes, ToBytes},
     mint::{METHOD_MINT, METHOD_READ_BASE_ROUND_REWARD},
@@ -78,6 +79,12 @@ impl RuntimeProvider for AuctionContract {
     fn blake2b<T: AsRef<[u8]>>(&self, data: T) -> [u8; BLAKE2B_DIGEST_LENGTH] {
         runtime::blake2b(data)
     }
+
+    // There is no host function verifying signatures; the execution engine runs the auction
+    // natively and verifies them itself.
+    fn verify_signature(&self, _message: &[u8], _signature: &[u8], _public_key: PublicKey) -> bool {
+        false
+    }
 }
 
 impl MintProvider for AuctionContract {
@@ -173,6 +180,17 @@ pub extern "C" fn withdraw_bid() {
     runtime::ret(cl_value)
 }
 
+#[no_mangle]
+pub extern "C" fn rotate_signing_key() {
+    let public_key = runtime::get_named_arg(ARG_PUBLIC_KEY);
+    let new_signing_key = runtime::get_named_arg(ARG_NEW_SIGNING_KEY);
+    let signature = runtime::get_named_arg(ARG_SIGNATURE);
+
+    AuctionContract
+        .rotate_signing_key(public_key, new_signing_key, signature)
+        .unwrap_or_revert();
+}
+
 #[no_mangle]
 pub extern "C" fn delegate() {
     let delegator = runtime::get_named_arg(ARG_DELEGATOR);
@@ -310,6 +328,19 @@ pub fn get_entry_points() -> EntryPoints {
     );
     entry_points.add_entry_point(entry_point);
 
+    let entry_point = EntryPoint::new(
+        METHOD_ROTATE_SIGNING_KEY,
+        vec![
+            Parameter::new(ARG_PUBLIC_KEY, PublicKey::cl_type()),
+            Parameter::new(ARG_NEW_SIGNING_KEY, PublicKey::cl_type()),
+            Parameter::new(ARG_SIGNATURE, Vec::<u8>::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
     let entry_point = EntryPoint::new(
         METHOD_DELEGATE,
         vec![
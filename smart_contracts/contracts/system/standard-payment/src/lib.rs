@@ -1,5 +1,9 @@
 #![no_std]
 
+extern crate alloc;
+
+use alloc::string::String;
+
 use casper_contract::{
     contract_api::{account, runtime, system},
     unwrap_or_revert::UnwrapOrRevert,
@@ -8,8 +12,9 @@ use casper_types::{
     proof_of_stake::METHOD_GET_PAYMENT_PURSE,
     standard_payment::{
         AccountProvider, MintProvider, ProofOfStakeProvider, StandardPayment, ARG_AMOUNT,
+        ARG_PURSE_NAME,
     },
-    ApiError, RuntimeArgs, URef, U512,
+    ApiError, Key, RuntimeArgs, URef, U512,
 };
 
 struct StandardPaymentContract;
@@ -18,6 +23,18 @@ impl AccountProvider for StandardPaymentContract {
     fn get_main_purse(&self) -> Result<URef, ApiError> {
         Ok(account::get_main_purse())
     }
+
+    fn get_named_purse(&self, name: &str) -> Result<URef, ApiError> {
+        let purse = match runtime::get_key(name) {
+            Some(Key::URef(uref)) => uref,
+            Some(_) => return Err(ApiError::InvalidPurse),
+            None => return Err(ApiError::InvalidPurseName),
+        };
+        if !purse.is_writeable() {
+            return Err(ApiError::InvalidPurse);
+        }
+        Ok(purse)
+    }
 }
 
 impl MintProvider for StandardPaymentContract {
@@ -49,6 +66,12 @@ pub fn delegate() {
     let mut standard_payment_contract = StandardPaymentContract;
 
     let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    let maybe_purse_name: Option<String> = runtime::try_get_named_arg(ARG_PURSE_NAME);
 
-    standard_payment_contract.pay(amount).unwrap_or_revert();
+    match maybe_purse_name {
+        Some(purse_name) => standard_payment_contract
+            .pay_from_named_purse(&purse_name, amount)
+            .unwrap_or_revert(),
+        None => standard_payment_contract.pay(amount).unwrap_or_revert(),
+    }
 }
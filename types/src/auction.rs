@@ -3,6 +3,7 @@ mod bid;
 mod constants;
 mod delegator;
 mod detail;
+mod era_info;
 mod providers;
 mod seigniorage_recipient;
 mod types;
@@ -14,6 +15,7 @@ use num_rational::Ratio;
 
 use crate::{
     account::AccountHash,
+    bytesrepr::ToBytes,
     system_contract_errors::auction::{Error, Result},
     PublicKey, URef, U512,
 };
@@ -21,11 +23,46 @@ use crate::{
 pub use bid::Bid;
 pub use constants::*;
 pub use delegator::Delegator;
+pub use era_info::{EraInfo, SeigniorageAllocation};
 pub use providers::{MintProvider, RuntimeProvider, StorageProvider, SystemProvider};
 pub use seigniorage_recipient::SeigniorageRecipient;
 pub use types::*;
 pub use unbonding_purse::UnbondingPurse;
 
+/// Domain separation tag prefixed to the message signed in [`Auction::rotate_signing_key`].
+const ROTATE_SIGNING_KEY_TAG: &[u8] = b"casper-rotate-signing-key";
+
+/// Returns the message the new signing key has to sign to be associated with the bid of
+/// `public_key` in era `era_id`: the tag `casper-rotate-signing-key`, followed by the
+/// serialized `public_key` and the little-endian `era_id`.
+///
+/// The era ID serves as a nonce, so a signature can't be replayed in a later era.
+pub fn rotate_signing_key_message(public_key: PublicKey, era_id: EraId) -> Vec<u8> {
+    let mut message = ROTATE_SIGNING_KEY_TAG.to_vec();
+    message.extend(public_key.to_bytes().expect("should serialize public key"));
+    message.extend(&era_id.to_le_bytes());
+    message
+}
+
+/// Computes the weights of each era's validators from a snapshot of seigniorage recipients.
+///
+/// Validators are identified by the keys they sign with in the era.
+pub fn era_validators_from_snapshot(snapshot: SeigniorageRecipientsSnapshot) -> EraValidators {
+    snapshot
+        .into_iter()
+        .map(|(era_id, recipients)| {
+            let validator_weights = recipients
+                .into_iter()
+                .map(|(public_key, recipient)| {
+                    let signing_key = *recipient.signing_key().unwrap_or(&public_key);
+                    (signing_key, recipient.total_stake())
+                })
+                .collect::<ValidatorWeights>();
+            (era_id, validator_weights)
+        })
+        .collect::<BTreeMap<EraId, ValidatorWeights>>()
+}
+
 /// Bonding auction contract interface
 pub trait Auction:
     StorageProvider + SystemProvider + RuntimeProvider + MintProvider + Sized
@@ -36,17 +73,7 @@ pub trait Auction:
     /// internal data structures recording current and past winners.
     fn get_era_validators(&mut self) -> Result<EraValidators> {
         let snapshot = detail::get_seigniorage_recipients_snapshot(self)?;
-        let era_validators = snapshot
-            .into_iter()
-            .map(|(era_id, recipients)| {
-                let validator_weights = recipients
-                    .into_iter()
-                    .map(|(public_key, bid)| (public_key, bid.total_stake()))
-                    .collect::<ValidatorWeights>();
-                (era_id, validator_weights)
-            })
-            .collect::<BTreeMap<EraId, ValidatorWeights>>();
-        Ok(era_validators)
+        Ok(era_validators_from_snapshot(snapshot))
     }
 
     /// Returns validators in era_validators, mapped to their bids or founding stakes, delegation
@@ -92,6 +119,12 @@ pub trait Auction:
                     .increase_stake(amount)?
             }
             None => {
+                let snapshot = detail::get_seigniorage_recipients_snapshot(self)?;
+                if detail::find_bid_key_by_signing_key(&validators, &snapshot, &public_key)
+                    .is_some()
+                {
+                    return Err(Error::SigningKeyInUse);
+                }
                 let bonding_purse = self.create_purse();
                 self.transfer_purse_to_purse(source, bonding_purse, amount)?;
                 let bid = Bid::unlocked(bonding_purse, amount, delegation_rate);
@@ -147,6 +180,59 @@ pub trait Auction:
         Ok(new_amount)
     }
 
+    /// Associates a new signing key with an existing bid, e.g. to replace a compromised key,
+    /// keeping the bid's stake, delegators and locked status.  Passing the bid's own public key
+    /// reverts to signing with it.
+    ///
+    /// The new key is used in consensus from the era selected by the next auction onwards, i.e.
+    /// `auction_delay + 1` eras after the current one.  Until then, the validator keeps signing
+    /// with its previous key.  A key can only ever be used by a single validator.
+    ///
+    /// Unless reverting to the bid's own key, `signature` must be a signature by
+    /// `new_signing_key` over [`rotate_signing_key_message`] for the current era, proving that
+    /// the bid's owner controls the new key.
+    fn rotate_signing_key(
+        &mut self,
+        public_key: PublicKey,
+        new_signing_key: PublicKey,
+        signature: Vec<u8>,
+    ) -> Result<()> {
+        let account_hash = AccountHash::from_public_key(public_key, |x| self.blake2b(x));
+        if self.get_caller() != account_hash {
+            return Err(Error::InvalidPublicKey);
+        }
+
+        if new_signing_key != public_key {
+            let era_id = detail::get_era_id(self)?;
+            let message = rotate_signing_key_message(public_key, era_id);
+            if !self.verify_signature(&message, &signature, new_signing_key) {
+                return Err(Error::InvalidSigningKeySignature);
+            }
+        }
+
+        let mut bids = detail::get_bids(self)?;
+        let snapshot = detail::get_seigniorage_recipients_snapshot(self)?;
+
+        let in_use_by_other = new_signing_key != public_key
+            && (bids.contains_key(&new_signing_key)
+                || detail::find_bid_key_by_signing_key(&bids, &snapshot, &new_signing_key)
+                    .map_or(false, |owner| owner != public_key));
+        if in_use_by_other {
+            return Err(Error::SigningKeyInUse);
+        }
+
+        let bid = bids.get_mut(&public_key).ok_or(Error::ValidatorNotFound)?;
+        if new_signing_key == public_key {
+            bid.set_signing_key(None);
+        } else {
+            bid.set_signing_key(Some(new_signing_key));
+        }
+
+        detail::set_bids(self, bids)?;
+
+        Ok(())
+    }
+
     /// Adds a new delegator to delegators, or tops off a current one. If the target validator is
     /// not in founders, the function call returns an error and does nothing.
     ///
@@ -251,7 +337,7 @@ pub trait Auction:
         Ok(new_amount)
     }
 
-    /// Slashes each validator.
+    /// Slashes each validator, identified by any key it signed with in a recent era.
     ///
     /// This can be only invoked through a system call.
     fn slash(&mut self, validator_public_keys: Vec<PublicKey>) -> Result<()> {
@@ -259,6 +345,18 @@ pub trait Auction:
             return Err(Error::InvalidCaller);
         }
 
+        let validator_public_keys: Vec<PublicKey> = {
+            let bids = detail::get_bids(self)?;
+            let snapshot = detail::get_seigniorage_recipients_snapshot(self)?;
+            validator_public_keys
+                .into_iter()
+                .map(|signing_key| {
+                    detail::find_bid_key_by_signing_key(&bids, &snapshot, &signing_key)
+                        .unwrap_or(signing_key)
+                })
+                .collect()
+        };
+
         detail::quash_bid(self, &validator_public_keys)?;
 
         let mut unbonding_purses: UnbondingPurses = detail::get_unbonding_purses(self)?;
@@ -379,7 +477,8 @@ pub trait Auction:
     }
 
     /// Mint and distribute seigniorage rewards to validators and their delegators,
-    /// according to `reward_factors` returned by the consensus component.
+    /// according to `reward_factors` returned by the consensus component, keyed by the keys the
+    /// validators signed with in the era.
     fn distribute(&mut self, reward_factors: BTreeMap<PublicKey, u64>) -> Result<()> {
         if self.get_caller() != SYSTEM_ACCOUNT {
             return Err(Error::InvalidCaller);
@@ -388,10 +487,20 @@ pub trait Auction:
         let seigniorage_recipients = self.read_seigniorage_recipients()?;
         let base_round_reward = self.read_base_round_reward()?;
 
+        let reward_factors: BTreeMap<PublicKey, u64> = reward_factors
+            .into_iter()
+            .map(|(signing_key, reward_factor)| {
+                let public_key = detail::recipient_bid_key(&seigniorage_recipients, signing_key);
+                (public_key, reward_factor)
+            })
+            .collect();
+
         if reward_factors.keys().ne(seigniorage_recipients.keys()) {
             return Err(Error::MismatchedEraValidators);
         }
 
+        let mut era_info = EraInfo::new();
+
         for (public_key, reward_factor) in reward_factors {
             let recipient = seigniorage_recipients
                 .get(&public_key)
@@ -431,13 +540,23 @@ pub trait Auction:
                         let reward = delegators_part * reward_multiplier;
                         (*delegator_key, reward)
                     });
-            let total_delegator_payout: U512 =
-                detail::update_delegator_rewards(self, public_key, delegator_rewards)?;
+            let total_delegator_payout: U512 = detail::update_delegator_rewards(
+                self,
+                public_key,
+                delegator_rewards,
+                &mut era_info,
+            )?;
 
             let validators_part: Ratio<U512> = total_reward - Ratio::from(total_delegator_payout);
             let validator_reward = validators_part.to_integer();
             let validator_payout =
                 detail::update_validator_reward(self, public_key, validator_reward)?;
+            era_info
+                .seigniorage_allocations_mut()
+                .push(SeigniorageAllocation::validator(
+                    public_key,
+                    validator_payout,
+                ));
 
             // TODO: add "mint into existing purse" facility
             let validator_reward_purse = self
@@ -470,7 +589,8 @@ pub trait Auction:
             )
             .map_err(|_| Error::Transfer)?;
         }
-        Ok(())
+
+        detail::record_era_info(self, era_info)
     }
 
     /// Allows delegators to withdraw the seigniorage rewards they have earned.
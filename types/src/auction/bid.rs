@@ -27,6 +27,8 @@ pub struct Bid {
     delegators: BTreeMap<PublicKey, Delegator>,
     /// This validator's seigniorage reward
     reward: U512,
+    /// The key this validator signs with in consensus, if it differs from the key of the bid.
+    signing_key: Option<PublicKey>,
 }
 
 impl Bid {
@@ -36,6 +38,7 @@ impl Bid {
         let release_era = Some(release_era);
         let delegators = BTreeMap::new();
         let reward = U512::zero();
+        let signing_key = None;
         Self {
             bonding_purse,
             staked_amount,
@@ -43,6 +46,7 @@ impl Bid {
             release_era,
             delegators,
             reward,
+            signing_key,
         }
     }
 
@@ -55,6 +59,7 @@ impl Bid {
         let release_era = None;
         let delegators = BTreeMap::new();
         let reward = U512::zero();
+        let signing_key = None;
         Self {
             bonding_purse,
             staked_amount,
@@ -62,6 +67,7 @@ impl Bid {
             release_era,
             delegators,
             reward,
+            signing_key,
         }
     }
 
@@ -105,6 +111,18 @@ impl Bid {
         &self.reward
     }
 
+    /// Returns the key the validator signs with in consensus, if it differs from the key of the
+    /// provided bid
+    pub fn signing_key(&self) -> Option<&PublicKey> {
+        self.signing_key.as_ref()
+    }
+
+    /// Sets the key the validator signs with in consensus, where `None` means the key of the
+    /// provided bid
+    pub fn set_signing_key(&mut self, signing_key: Option<PublicKey>) {
+        self.signing_key = signing_key;
+    }
+
     /// Decreases the stake of the provided bid
     pub fn decrease_stake(&mut self, amount: U512) -> Result<U512, Error> {
         if self.is_locked() {
@@ -188,15 +206,28 @@ impl CLTyped for Bid {
     }
 }
 
+/// Marks the encoding of a bid or seigniorage recipient with a signing key.
+///
+/// It takes the place of the length prefix of the stake, which is at most 64 in the original
+/// encoding, so values without a signing key keep their original encoding and values stored
+/// before signing keys were introduced still decode.
+pub(super) const SIGNING_KEY_TAG: u8 = u8::MAX;
+
 impl ToBytes for Bid {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         let mut result = bytesrepr::allocate_buffer(self)?;
         result.extend(self.bonding_purse.to_bytes()?);
+        if self.signing_key.is_some() {
+            result.push(SIGNING_KEY_TAG);
+        }
         result.extend(self.staked_amount.to_bytes()?);
         result.extend(self.delegation_rate.to_bytes()?);
         result.extend(self.release_era.to_bytes()?);
         result.extend(self.delegators.to_bytes()?);
         result.extend(self.reward.to_bytes()?);
+        if let Some(signing_key) = self.signing_key {
+            result.extend(signing_key.to_bytes()?);
+        }
         Ok(result)
     }
 
@@ -207,17 +238,30 @@ impl ToBytes for Bid {
             + self.release_era.serialized_length()
             + self.delegators.serialized_length()
             + self.reward.serialized_length()
+            + self
+                .signing_key
+                .map_or(0, |signing_key| 1 + signing_key.serialized_length())
     }
 }
 
 impl FromBytes for Bid {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
         let (bonding_purse, bytes) = FromBytes::from_bytes(bytes)?;
+        let (has_signing_key, bytes) = match bytes.split_first() {
+            Some((&SIGNING_KEY_TAG, remainder)) => (true, remainder),
+            _ => (false, bytes),
+        };
         let (staked_amount, bytes) = FromBytes::from_bytes(bytes)?;
         let (delegation_rate, bytes) = FromBytes::from_bytes(bytes)?;
         let (release_era, bytes) = FromBytes::from_bytes(bytes)?;
         let (delegators, bytes) = FromBytes::from_bytes(bytes)?;
         let (reward, bytes) = FromBytes::from_bytes(bytes)?;
+        let (signing_key, bytes) = if has_signing_key {
+            let (signing_key, bytes) = PublicKey::from_bytes(bytes)?;
+            (Some(signing_key), bytes)
+        } else {
+            (None, bytes)
+        };
         Ok((
             Bid {
                 bonding_purse,
@@ -226,6 +270,7 @@ impl FromBytes for Bid {
                 release_era,
                 delegators,
                 reward,
+                signing_key,
             },
             bytes,
         ))
@@ -234,11 +279,13 @@ impl FromBytes for Bid {
 
 #[cfg(test)]
 mod tests {
-    use alloc::collections::BTreeMap;
+    use alloc::{collections::BTreeMap, vec::Vec};
+    use core::iter::FromIterator;
 
     use crate::{
-        auction::{Bid, DelegationRate, EraId},
-        bytesrepr, AccessRights, URef, U512,
+        auction::{Bid, Bids, DelegationRate, EraId},
+        bytesrepr::{self, ToBytes},
+        AccessRights, PublicKey, URef, U512,
     };
 
     #[test]
@@ -250,7 +297,60 @@ mod tests {
             release_era: Some(EraId::max_value() - 1),
             delegators: BTreeMap::default(),
             reward: U512::one(),
+            signing_key: None,
         };
         bytesrepr::test_serialization_roundtrip(&founding_validator);
     }
+
+    #[test]
+    fn serialization_roundtrip_with_signing_key() {
+        let mut bid = Bid::unlocked(
+            URef::new([42; 32], AccessRights::READ_ADD_WRITE),
+            U512::one(),
+            DelegationRate::max_value(),
+        );
+        bid.set_signing_key(Some(PublicKey::Ed25519([43; 32])));
+        bytesrepr::test_serialization_roundtrip(&bid);
+    }
+
+    #[test]
+    fn should_keep_encoding_without_signing_key() {
+        let bid = Bid::locked(
+            URef::new([42; 32], AccessRights::READ_ADD_WRITE),
+            U512::from(u64::max_value()),
+            EraId::max_value(),
+        );
+        let mut legacy_bytes = Vec::new();
+        legacy_bytes.extend(bid.bonding_purse.to_bytes().unwrap());
+        legacy_bytes.extend(bid.staked_amount.to_bytes().unwrap());
+        legacy_bytes.extend(bid.delegation_rate.to_bytes().unwrap());
+        legacy_bytes.extend(bid.release_era.to_bytes().unwrap());
+        legacy_bytes.extend(bid.delegators.to_bytes().unwrap());
+        legacy_bytes.extend(bid.reward.to_bytes().unwrap());
+
+        assert_eq!(bid.to_bytes().unwrap(), legacy_bytes);
+        assert_eq!(bytesrepr::deserialize::<Bid>(legacy_bytes).unwrap(), bid);
+    }
+
+    #[test]
+    fn should_decode_bids_with_and_without_signing_keys() {
+        let mut rotated = Bid::unlocked(
+            URef::new([42; 32], AccessRights::READ_ADD_WRITE),
+            U512::one(),
+            DelegationRate::max_value(),
+        );
+        rotated.set_signing_key(Some(PublicKey::Ed25519([43; 32])));
+        let bids: Bids = BTreeMap::from_iter(vec![
+            (PublicKey::Ed25519([1; 32]), rotated),
+            (
+                PublicKey::Ed25519([2; 32]),
+                Bid::unlocked(
+                    URef::new([44; 32], AccessRights::READ_ADD_WRITE),
+                    U512::one(),
+                    DelegationRate::max_value(),
+                ),
+            ),
+        ]);
+        bytesrepr::test_serialization_roundtrip(&bids);
+    }
 }
@@ -41,6 +41,10 @@ pub const ARG_ERA_ID: &str = "era_id";
 pub const ARG_REWARD_FACTORS: &str = "reward_factors";
 /// Named constant for `validator_public_key`.
 pub const ARG_VALIDATOR_PUBLIC_KEY: &str = "validator_public_key";
+/// Named constant for `new_signing_key`.
+pub const ARG_NEW_SIGNING_KEY: &str = "new_signing_key";
+/// Named constant for `signature`.
+pub const ARG_SIGNATURE: &str = "signature";
 /// Named constant for `delegator_public_key`.
 pub const ARG_DELEGATOR_PUBLIC_KEY: &str = "delegator_public_key";
 /// Named constant for `target_purse`.
@@ -53,6 +57,8 @@ pub const ARG_VALIDATOR_SLOTS: &str = VALIDATOR_SLOTS_KEY;
 pub const ARG_MINT_CONTRACT_PACKAGE_HASH: &str = "mint_contract_package_hash";
 /// Named constant for `genesis_validators`
 pub const ARG_GENESIS_VALIDATORS: &str = "genesis_validators";
+/// Named constant for `genesis_delegators`
+pub const ARG_GENESIS_DELEGATORS: &str = "genesis_delegators";
 /// Named constant of `auction_delay`
 pub const ARG_AUCTION_DELAY: &str = "auction_delay";
 /// Named constant for `locked_funds_period`
@@ -83,6 +89,8 @@ pub const METHOD_WITHDRAW_DELEGATOR_REWARD: &str = "withdraw_delegator_reward";
 pub const METHOD_WITHDRAW_VALIDATOR_REWARD: &str = "withdraw_validator_reward";
 /// Named constant for method `read_era_id`.
 pub const METHOD_READ_ERA_ID: &str = "read_era_id";
+/// Named constant for method `rotate_signing_key`.
+pub const METHOD_ROTATE_SIGNING_KEY: &str = "rotate_signing_key";
 
 /// Storage for `UnbondingPurses`
 pub const UNBONDING_PURSES_KEY: &str = "unbonding_purses";
@@ -96,6 +104,8 @@ pub const SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY: &str = "seigniorage_recipients_sn
 pub const DELEGATOR_REWARD_PURSE_KEY: &str = "delegator_reward_purse";
 /// Storage for validator reward purse
 pub const VALIDATOR_REWARD_PURSE_KEY: &str = "validator_reward_purse";
+/// Storage for `EraInfo` of the most recently ended era.
+pub const ERA_INFO_KEY: &str = "era_info";
 /// Total validator slots allowed.
 pub const VALIDATOR_SLOTS_KEY: &str = "validator_slots";
 /// Amount of auction delay.
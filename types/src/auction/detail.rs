@@ -5,9 +5,9 @@ use num_rational::Ratio;
 
 use crate::{
     auction::{
-        constants::*, Auction, Bids, EraId, MintProvider, RuntimeProvider,
-        SeigniorageRecipientsSnapshot, StorageProvider, SystemProvider, UnbondingPurse,
-        UnbondingPurses,
+        constants::*, Auction, Bids, EraId, EraInfo, MintProvider, RuntimeProvider,
+        SeigniorageAllocation, SeigniorageRecipients, SeigniorageRecipientsSnapshot,
+        StorageProvider, SystemProvider, UnbondingPurse, UnbondingPurses,
     },
     bytesrepr::{FromBytes, ToBytes},
     system_contract_errors::auction::{Error, Result},
@@ -199,6 +199,7 @@ pub fn update_delegator_rewards<P>(
     provider: &mut P,
     validator_public_key: PublicKey,
     rewards: impl Iterator<Item = (PublicKey, Ratio<U512>)>,
+    era_info: &mut EraInfo,
 ) -> Result<U512>
 where
     P: MintProvider + RuntimeProvider + StorageProvider + SystemProvider + ?Sized,
@@ -226,6 +227,13 @@ where
         };
 
         delegator.increase_reward(delegator_reward_trunc)?;
+        era_info
+            .seigniorage_allocations_mut()
+            .push(SeigniorageAllocation::delegator(
+                delegator_key,
+                validator_public_key,
+                delegator_reward_trunc,
+            ));
 
         total_delegator_payout += delegator_reward_trunc;
     }
@@ -261,6 +269,20 @@ where
     Ok(amount)
 }
 
+/// Records the seigniorage allocations of the era which just ended.
+///
+/// Auction contracts installed before `EraInfo` was introduced don't have the named key, in which
+/// case nothing is recorded.
+pub fn record_era_info<P>(provider: &mut P, era_info: EraInfo) -> Result<()>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    if provider.get_key(ERA_INFO_KEY).is_none() {
+        return Ok(());
+    }
+    write_to(provider, ERA_INFO_KEY, era_info)
+}
+
 /// Removes validator entries from either founders or validators, wherever they
 /// might be found.
 ///
@@ -287,3 +309,41 @@ pub(crate) fn quash_bid<P: StorageProvider + RuntimeProvider + ?Sized>(
 
     Ok(())
 }
+
+/// Returns the key of the bid whose validator signs with `signing_key`, either as of the current
+/// bids or in any era of the seigniorage recipients snapshot.
+pub(crate) fn find_bid_key_by_signing_key(
+    bids: &Bids,
+    snapshot: &SeigniorageRecipientsSnapshot,
+    signing_key: &PublicKey,
+) -> Option<PublicKey> {
+    let current = bids
+        .iter()
+        .map(|(public_key, bid)| (public_key, bid.signing_key()));
+    let snapshotted = snapshot.values().flat_map(|recipients| {
+        recipients
+            .iter()
+            .map(|(public_key, recipient)| (public_key, recipient.signing_key()))
+    });
+    current
+        .chain(snapshotted)
+        .find(|(public_key, maybe_signing_key)| {
+            maybe_signing_key.unwrap_or(public_key) == signing_key
+        })
+        .map(|(public_key, _)| *public_key)
+}
+
+/// Returns the key of the bid of the recipient signing with `signing_key` in the era of the given
+/// recipients, or `signing_key` itself if there is no such recipient.
+pub(crate) fn recipient_bid_key(
+    recipients: &SeigniorageRecipients,
+    signing_key: PublicKey,
+) -> PublicKey {
+    recipients
+        .iter()
+        .find(|(public_key, recipient)| {
+            recipient.signing_key().unwrap_or(public_key) == &signing_key
+        })
+        .map(|(public_key, _)| *public_key)
+        .unwrap_or(signing_key)
+}
@@ -0,0 +1,223 @@
+use alloc::vec::Vec;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    CLType, CLTyped, PublicKey, U512,
+};
+
+const VALIDATOR_TAG: u8 = 0;
+const DELEGATOR_TAG: u8 = 1;
+
+/// The seigniorage credited to a single validator or delegator at the end of an era.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SeigniorageAllocation {
+    /// The reward credited to a validator.
+    Validator {
+        /// The public key of the validator's bid.
+        validator_public_key: PublicKey,
+        /// The amount credited.
+        amount: U512,
+    },
+    /// The reward credited to a delegator.
+    Delegator {
+        /// The public key of the delegator.
+        delegator_public_key: PublicKey,
+        /// The public key of the validator delegated to.
+        validator_public_key: PublicKey,
+        /// The amount credited.
+        amount: U512,
+    },
+}
+
+impl SeigniorageAllocation {
+    /// Constructs a validator's allocation.
+    pub fn validator(validator_public_key: PublicKey, amount: U512) -> Self {
+        SeigniorageAllocation::Validator {
+            validator_public_key,
+            amount,
+        }
+    }
+
+    /// Constructs a delegator's allocation.
+    pub fn delegator(
+        delegator_public_key: PublicKey,
+        validator_public_key: PublicKey,
+        amount: U512,
+    ) -> Self {
+        SeigniorageAllocation::Delegator {
+            delegator_public_key,
+            validator_public_key,
+            amount,
+        }
+    }
+
+    /// Returns the amount credited.
+    pub fn amount(&self) -> &U512 {
+        match self {
+            SeigniorageAllocation::Validator { amount, .. }
+            | SeigniorageAllocation::Delegator { amount, .. } => amount,
+        }
+    }
+}
+
+impl ToBytes for SeigniorageAllocation {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        match self {
+            SeigniorageAllocation::Validator {
+                validator_public_key,
+                amount,
+            } => {
+                result.push(VALIDATOR_TAG);
+                result.extend(validator_public_key.to_bytes()?);
+                result.extend(amount.to_bytes()?);
+            }
+            SeigniorageAllocation::Delegator {
+                delegator_public_key,
+                validator_public_key,
+                amount,
+            } => {
+                result.push(DELEGATOR_TAG);
+                result.extend(delegator_public_key.to_bytes()?);
+                result.extend(validator_public_key.to_bytes()?);
+                result.extend(amount.to_bytes()?);
+            }
+        }
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                SeigniorageAllocation::Validator {
+                    validator_public_key,
+                    amount,
+                } => validator_public_key.serialized_length() + amount.serialized_length(),
+                SeigniorageAllocation::Delegator {
+                    delegator_public_key,
+                    validator_public_key,
+                    amount,
+                } => {
+                    delegator_public_key.serialized_length()
+                        + validator_public_key.serialized_length()
+                        + amount.serialized_length()
+                }
+            }
+    }
+}
+
+impl FromBytes for SeigniorageAllocation {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, bytes): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
+        match tag {
+            VALIDATOR_TAG => {
+                let (validator_public_key, bytes) = FromBytes::from_bytes(bytes)?;
+                let (amount, bytes) = FromBytes::from_bytes(bytes)?;
+                Ok((
+                    SeigniorageAllocation::validator(validator_public_key, amount),
+                    bytes,
+                ))
+            }
+            DELEGATOR_TAG => {
+                let (delegator_public_key, bytes) = FromBytes::from_bytes(bytes)?;
+                let (validator_public_key, bytes) = FromBytes::from_bytes(bytes)?;
+                let (amount, bytes) = FromBytes::from_bytes(bytes)?;
+                Ok((
+                    SeigniorageAllocation::delegator(
+                        delegator_public_key,
+                        validator_public_key,
+                        amount,
+                    ),
+                    bytes,
+                ))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+impl CLTyped for SeigniorageAllocation {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+/// The seigniorage allocations made at the end of an era.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EraInfo {
+    seigniorage_allocations: Vec<SeigniorageAllocation>,
+}
+
+impl EraInfo {
+    /// Constructs an empty `EraInfo`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Returns the seigniorage allocations of the era.
+    pub fn seigniorage_allocations(&self) -> &Vec<SeigniorageAllocation> {
+        &self.seigniorage_allocations
+    }
+
+    /// Returns a mutable reference to the seigniorage allocations of the era.
+    pub fn seigniorage_allocations_mut(&mut self) -> &mut Vec<SeigniorageAllocation> {
+        &mut self.seigniorage_allocations
+    }
+}
+
+impl ToBytes for EraInfo {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.seigniorage_allocations.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.seigniorage_allocations.serialized_length()
+    }
+}
+
+impl FromBytes for EraInfo {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (seigniorage_allocations, bytes) = FromBytes::from_bytes(bytes)?;
+        Ok((
+            EraInfo {
+                seigniorage_allocations,
+            },
+            bytes,
+        ))
+    }
+}
+
+impl CLTyped for EraInfo {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        auction::{EraInfo, SeigniorageAllocation},
+        bytesrepr, PublicKey, U512,
+    };
+
+    #[test]
+    fn serialization_roundtrip() {
+        let mut era_info = EraInfo::new();
+        era_info
+            .seigniorage_allocations_mut()
+            .push(SeigniorageAllocation::validator(
+                PublicKey::Ed25519([42; 32]),
+                U512::max_value(),
+            ));
+        era_info
+            .seigniorage_allocations_mut()
+            .push(SeigniorageAllocation::delegator(
+                PublicKey::Ed25519([43; 32]),
+                PublicKey::Ed25519([42; 32]),
+                U512::zero(),
+            ));
+        bytesrepr::test_serialization_roundtrip(&era_info);
+    }
+}
@@ -2,7 +2,7 @@ use crate::{
     account::AccountHash,
     bytesrepr::{FromBytes, ToBytes},
     system_contract_errors::auction::Error,
-    CLTyped, Key, TransferResult, URef, BLAKE2B_DIGEST_LENGTH, U512,
+    CLTyped, Key, PublicKey, TransferResult, URef, BLAKE2B_DIGEST_LENGTH, U512,
 };
 
 /// Provider of runtime host functionality.
@@ -18,6 +18,9 @@ pub trait RuntimeProvider {
 
     /// Returns a 32-byte BLAKE2b digest
     fn blake2b<T: AsRef<[u8]>>(&self, data: T) -> [u8; BLAKE2B_DIGEST_LENGTH];
+
+    /// Returns `true` if `signature` is a valid signature of `message` by `public_key`.
+    fn verify_signature(&self, message: &[u8], signature: &[u8], public_key: PublicKey) -> bool;
 }
 
 /// Provides functionality of a contract storage.
@@ -1,7 +1,7 @@
 use alloc::{collections::BTreeMap, vec::Vec};
 
 use crate::{
-    auction::{Bid, DelegationRate, Delegator},
+    auction::{bid::SIGNING_KEY_TAG, Bid, DelegationRate, Delegator},
     bytesrepr::{self, FromBytes, ToBytes},
     CLType, CLTyped, PublicKey, U512,
 };
@@ -16,6 +16,8 @@ pub struct SeigniorageRecipient {
     delegation_rate: DelegationRate,
     /// List of delegators and their accumulated bids.
     delegators: BTreeMap<PublicKey, Delegator>,
+    /// The key the recipient signs with in consensus, if it differs from the key of its bid.
+    signing_key: Option<PublicKey>,
 }
 
 impl SeigniorageRecipient {
@@ -34,6 +36,12 @@ impl SeigniorageRecipient {
         &self.delegators
     }
 
+    /// Returns the key the provided recipient signs with in consensus, if it differs from the key
+    /// of its bid
+    pub fn signing_key(&self) -> Option<&PublicKey> {
+        self.signing_key.as_ref()
+    }
+
     /// Calculates total stake, including delegators' total stake
     pub fn total_stake(&self) -> U512 {
         self.stake + self.delegator_total_stake()
@@ -58,9 +66,15 @@ impl CLTyped for SeigniorageRecipient {
 impl ToBytes for SeigniorageRecipient {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         let mut result = bytesrepr::allocate_buffer(self)?;
+        if self.signing_key.is_some() {
+            result.push(SIGNING_KEY_TAG);
+        }
         result.extend(self.stake.to_bytes()?);
         result.extend(self.delegation_rate.to_bytes()?);
         result.extend(self.delegators.to_bytes()?);
+        if let Some(signing_key) = self.signing_key {
+            result.extend(signing_key.to_bytes()?);
+        }
         Ok(result)
     }
 
@@ -68,19 +82,33 @@ impl ToBytes for SeigniorageRecipient {
         self.stake.serialized_length()
             + self.delegation_rate.serialized_length()
             + self.delegators.serialized_length()
+            + self
+                .signing_key
+                .map_or(0, |signing_key| 1 + signing_key.serialized_length())
     }
 }
 
 impl FromBytes for SeigniorageRecipient {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (has_signing_key, bytes) = match bytes.split_first() {
+            Some((&SIGNING_KEY_TAG, remainder)) => (true, remainder),
+            _ => (false, bytes),
+        };
         let (stake, bytes) = FromBytes::from_bytes(bytes)?;
         let (delegation_rate, bytes) = FromBytes::from_bytes(bytes)?;
         let (delegators, bytes) = FromBytes::from_bytes(bytes)?;
+        let (signing_key, bytes) = if has_signing_key {
+            let (signing_key, bytes) = PublicKey::from_bytes(bytes)?;
+            (Some(signing_key), bytes)
+        } else {
+            (None, bytes)
+        };
         Ok((
             SeigniorageRecipient {
                 stake,
                 delegation_rate,
                 delegators,
+                signing_key,
             },
             bytes,
         ))
@@ -93,18 +121,20 @@ impl From<&Bid> for SeigniorageRecipient {
             stake: *bid.staked_amount(),
             delegation_rate: *bid.delegation_rate(),
             delegators: bid.delegators().clone(),
+            signing_key: bid.signing_key().cloned(),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use alloc::collections::BTreeMap;
+    use alloc::{collections::BTreeMap, vec::Vec};
     use core::iter::FromIterator;
 
     use crate::{
         auction::{DelegationRate, Delegator, SeigniorageRecipient},
-        bytesrepr, AccessRights, PublicKey, URef, U512,
+        bytesrepr::{self, ToBytes},
+        AccessRights, PublicKey, URef, U512,
     };
 
     #[test]
@@ -127,7 +157,32 @@ mod tests {
                     Delegator::new(U512::zero(), uref, PublicKey::Ed25519([44; 32])),
                 ),
             ]),
+            signing_key: Some(PublicKey::Ed25519([45; 32])),
         };
         bytesrepr::test_serialization_roundtrip(&seigniorage_recipient);
     }
+
+    #[test]
+    fn should_decode_legacy_encoding() {
+        let uref = URef::new([0; 32], AccessRights::READ_ADD_WRITE);
+        let seigniorage_recipient = SeigniorageRecipient {
+            stake: U512::max_value(),
+            delegation_rate: DelegationRate::max_value(),
+            delegators: BTreeMap::from_iter(vec![(
+                PublicKey::Ed25519([1; 32]),
+                Delegator::new(U512::one(), uref, PublicKey::Ed25519([42; 32])),
+            )]),
+            signing_key: None,
+        };
+        let mut legacy_bytes = Vec::new();
+        legacy_bytes.extend(seigniorage_recipient.stake.to_bytes().unwrap());
+        legacy_bytes.extend(seigniorage_recipient.delegation_rate.to_bytes().unwrap());
+        legacy_bytes.extend(seigniorage_recipient.delegators.to_bytes().unwrap());
+
+        assert_eq!(seigniorage_recipient.to_bytes().unwrap(), legacy_bytes);
+        assert_eq!(
+            bytesrepr::deserialize::<SeigniorageRecipient>(legacy_bytes).unwrap(),
+            seigniorage_recipient
+        );
+    }
 }
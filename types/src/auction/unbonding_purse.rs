@@ -6,8 +6,7 @@ use crate::{
 };
 
 /// Unbonding purse.
-#[cfg_attr(test, derive(Debug))]
-#[derive(Copy, Clone, PartialEq, Eq)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub struct UnbondingPurse {
     /// Bonding Purse
     pub bonding_purse: URef,
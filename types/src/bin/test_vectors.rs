@@ -0,0 +1,11 @@
+//! Prints the canonical `bytesrepr` test vectors for the core types as JSON to stdout.
+
+use casper_types::test_vectors;
+
+fn main() {
+    let vectors = test_vectors::test_vectors();
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&vectors).expect("should encode test vectors as JSON")
+    );
+}
@@ -0,0 +1,144 @@
+//! Optional metadata documenting the interface of a stored contract.
+//!
+//! A contract publishes its metadata by storing it as a [`CLValue`] (see
+//! [`ContractMetadata::to_cl_value`]) under a `URef` held in the contract's named keys as
+//! [`CONTRACT_METADATA_KEY`].  Tools can then combine it with the contract's entry points to
+//! describe how to call the contract.
+
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Display, Formatter};
+
+use failure::Fail;
+use serde::{Deserialize, Serialize};
+
+use crate::{CLValue, CLValueError};
+
+/// The name of the named key under which a contract stores the `URef` of its metadata.
+pub const CONTRACT_METADATA_KEY: &str = "contract_metadata";
+
+/// Error while converting [`ContractMetadata`] to or from a [`CLValue`].
+#[derive(Fail, PartialEq, Eq, Clone, Debug)]
+pub enum ContractMetadataError {
+    /// The `CLValue` is not a string.
+    #[fail(display = "{}", _0)]
+    CLValue(CLValueError),
+    /// The metadata could not be encoded to or decoded from JSON.
+    #[fail(display = "invalid contract metadata JSON: {}", _0)]
+    Json(String),
+}
+
+impl From<CLValueError> for ContractMetadataError {
+    fn from(error: CLValueError) -> Self {
+        ContractMetadataError::CLValue(error)
+    }
+}
+
+/// Documentation of an argument of an entry point.
+#[derive(Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ArgMetadata {
+    /// The name of the argument.
+    pub name: String,
+    /// A description of the argument.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+}
+
+/// Documentation of an entry point.
+#[derive(Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EntryPointMetadata {
+    /// The name of the entry point.
+    pub name: String,
+    /// A description of the entry point.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+    /// Documentation of the entry point's arguments.
+    #[serde(default)]
+    pub args: Vec<ArgMetadata>,
+    /// A description of the entry point's return value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ret_doc: Option<String>,
+}
+
+/// Metadata documenting the interface of a stored contract.
+#[derive(Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ContractMetadata {
+    /// The human-readable name of the contract.
+    pub name: String,
+    /// The version of the contract, in a scheme of the author's choosing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// A description of the contract.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Documentation of the contract's entry points.
+    #[serde(default)]
+    pub entry_points: Vec<EntryPointMetadata>,
+}
+
+impl ContractMetadata {
+    /// Returns the documentation of the named entry point, if any.
+    pub fn entry_point(&self, name: &str) -> Option<&EntryPointMetadata> {
+        self.entry_points
+            .iter()
+            .find(|entry_point| entry_point.name == name)
+    }
+
+    /// Encodes the metadata as a `CLValue` of type `String` holding its JSON representation.
+    pub fn to_cl_value(&self) -> Result<CLValue, ContractMetadataError> {
+        let json = serde_json::to_string(self)
+            .map_err(|error| ContractMetadataError::Json(error.to_string()))?;
+        Ok(CLValue::from_t(json)?)
+    }
+
+    /// Decodes the metadata from a `CLValue` of type `String` holding its JSON representation.
+    pub fn from_cl_value(cl_value: CLValue) -> Result<Self, ContractMetadataError> {
+        let json: String = cl_value.into_t()?;
+        serde_json::from_str(&json).map_err(|error| ContractMetadataError::Json(error.to_string()))
+    }
+}
+
+impl Display for ContractMetadata {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "{}", self.name)?;
+        if let Some(version) = &self.version {
+            write!(formatter, " {}", version)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cl_value_roundtrip() {
+        let metadata = ContractMetadata {
+            name: String::from("faucet"),
+            version: Some(String::from("1.0.0")),
+            description: None,
+            entry_points: vec![EntryPointMetadata {
+                name: String::from("call_faucet"),
+                doc: Some(String::from("Transfers tokens to the target account.")),
+                args: vec![ArgMetadata {
+                    name: String::from("target"),
+                    doc: Some(String::from("The account to fund.")),
+                }],
+                ret_doc: None,
+            }],
+        };
+        let cl_value = metadata.to_cl_value().unwrap();
+        assert_eq!(ContractMetadata::from_cl_value(cl_value).unwrap(), metadata);
+        assert!(metadata.entry_point("call_faucet").is_some());
+        assert!(metadata.entry_point("missing").is_none());
+    }
+
+    #[test]
+    fn should_reject_non_string_cl_value() {
+        let cl_value = CLValue::from_t(1u64).unwrap();
+        assert!(ContractMetadata::from_cl_value(cl_value).is_err());
+    }
+}
@@ -31,6 +31,7 @@ mod block_time;
 pub mod bytesrepr;
 mod cl_type;
 mod cl_value;
+mod contract_metadata;
 mod contract_wasm;
 pub mod contracts;
 mod deploy_info;
@@ -47,6 +48,8 @@ mod semver;
 pub mod standard_payment;
 pub mod system_contract_errors;
 pub mod system_contract_type;
+#[cfg(feature = "std")]
+pub mod test_vectors;
 mod transfer;
 mod transfer_result;
 mod uint;
@@ -59,6 +62,9 @@ pub use api_error::ApiError;
 pub use block_time::{BlockTime, BLOCKTIME_SERIALIZED_LENGTH};
 pub use cl_type::{named_key_type, CLType, CLTyped};
 pub use cl_value::{CLTypeMismatch, CLValue, CLValueError};
+pub use contract_metadata::{
+    ArgMetadata, ContractMetadata, ContractMetadataError, EntryPointMetadata, CONTRACT_METADATA_KEY,
+};
 pub use contracts::{
     Contract, ContractPackage, ContractVersion, ContractVersionKey, EntryPoint, EntryPointAccess,
     EntryPointType, EntryPoints, Group, Parameter,
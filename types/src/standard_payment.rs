@@ -22,4 +22,13 @@ pub trait StandardPayment: AccountProvider + MintProvider + ProofOfStakeProvider
         self.transfer_purse_to_purse(main_purse, payment_purse, amount)
             .map_err(|_| ApiError::Transfer)
     }
+
+    /// Pay `amount` to a payment purse from the purse stored under `purse_name` in the account's
+    /// named keys rather than from its main purse.
+    fn pay_from_named_purse(&mut self, purse_name: &str, amount: U512) -> Result<(), ApiError> {
+        let source_purse = self.get_named_purse(purse_name)?;
+        let payment_purse = self.get_payment_purse()?;
+        self.transfer_purse_to_purse(source_purse, payment_purse, amount)
+            .map_err(|_| ApiError::Transfer)
+    }
 }
@@ -4,4 +4,9 @@ use crate::{ApiError, URef};
 pub trait AccountProvider {
     /// Get currently executing account's purse.
     fn get_main_purse(&self) -> Result<URef, ApiError>;
+
+    /// Get the purse stored under `name` in the currently executing account's named keys.
+    ///
+    /// The purse must be writeable, as paying from it withdraws funds.
+    fn get_named_purse(&self, name: &str) -> Result<URef, ApiError>;
 }
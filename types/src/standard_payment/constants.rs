@@ -1,5 +1,7 @@
 /// Named constant for `amount`.
 pub const ARG_AMOUNT: &str = "amount";
+/// Named constant for `purse_name`.
+pub const ARG_PURSE_NAME: &str = "purse_name";
 
 /// Named constant for method `call`.
 pub const METHOD_CALL: &str = "call";
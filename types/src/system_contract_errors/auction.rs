@@ -97,11 +97,17 @@ pub enum Error {
     /// Invalid number of validator slots.
     #[fail(display = "Invalid number of validator slots")]
     InvalidValidatorSlotsValue = 25,
+    /// The signing key is in use by another validator.
+    #[fail(display = "Signing key in use by another validator")]
+    SigningKeyInUse = 26,
+    /// The new signing key did not sign the rotation request.
+    #[fail(display = "Invalid signature by the new signing key")]
+    InvalidSigningKeySignature = 27,
 }
 
 /// Used for testing; this should be set to the maximum value of the [`Error`] enum.
 #[cfg(test)]
-const MAX_ERROR_VALUE: u8 = 25;
+const MAX_ERROR_VALUE: u8 = 27;
 
 impl CLTyped for Error {
     fn cl_type() -> CLType {
@@ -148,6 +154,10 @@ impl TryFrom<u8> for Error {
             d if d == Error::InvalidValidatorSlotsValue as u8 => {
                 Ok(Error::InvalidValidatorSlotsValue)
             }
+            d if d == Error::SigningKeyInUse as u8 => Ok(Error::SigningKeyInUse),
+            d if d == Error::InvalidSigningKeySignature as u8 => {
+                Ok(Error::InvalidSigningKeySignature)
+            }
             _ => Err(TryFromU8ForError(())),
         }
     }
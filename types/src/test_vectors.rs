@@ -0,0 +1,225 @@
+//! Canonical `bytesrepr` test vectors for the core types.
+//!
+//! The vectors are committed to `types/resources/test_vectors.json`, so that third-party SDKs can
+//! check their serialization against them, and a test checks that they still match the output of
+//! [`test_vectors`].  After an intentional change of serialization format, regenerate them with:
+//!
+//! ```text
+//! cargo run -p casper-types --features=std --bin casper-types-test-vectors > \
+//!     types/resources/test_vectors.json
+//! ```
+
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt::Debug;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    account::AccountHash, bytesrepr::ToBytes, AccessRights, CLType, CLTyped, CLValue, Key,
+    NamedArg, ProtocolVersion, PublicKey, RuntimeArgs, Secp256k1Bytes, URef, U128, U256, U512,
+};
+
+/// A single test vector: a value and its canonical `bytesrepr` serialization.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct TestVector {
+    /// A unique name identifying the vector.
+    pub name: String,
+    /// The value's `CLType` in its `Debug` format if it has one, otherwise the Rust type name.
+    #[serde(rename = "type")]
+    pub type_description: String,
+    /// A human-readable JSON representation of the value.
+    pub value: Value,
+    /// The hex-encoded `bytesrepr` serialization of the value.
+    pub bytes: String,
+}
+
+impl TestVector {
+    fn new<T: ToBytes>(name: &str, type_description: String, value: Value, t: &T) -> Self {
+        let bytes = t
+            .to_bytes()
+            .unwrap_or_else(|error| panic!("should serialize {}: {:?}", name, error));
+        Self::with_bytes(name, type_description, value, &bytes)
+    }
+
+    fn with_bytes(name: &str, type_description: String, value: Value, bytes: &[u8]) -> Self {
+        TestVector {
+            name: name.to_string(),
+            type_description,
+            value,
+            bytes: base16::encode_lower(bytes),
+        }
+    }
+
+    /// Creates a vector for a CL-typed value whose `serde_json` representation is its
+    /// human-readable one.
+    fn cl_typed<T: CLTyped + ToBytes + Serialize>(name: &str, t: T) -> Self {
+        let value = serde_json::to_value(&t)
+            .unwrap_or_else(|error| panic!("should encode {} as JSON: {}", name, error));
+        Self::cl_typed_with_value(name, value, t)
+    }
+
+    fn cl_typed_with_value<T: CLTyped + ToBytes>(name: &str, value: Value, t: T) -> Self {
+        Self::new(name, debug_string(&T::cl_type()), value, &t)
+    }
+}
+
+fn debug_string<T: Debug>(t: &T) -> String {
+    format!("{:?}", t)
+}
+
+fn cl_value_json(cl_value: &CLValue) -> Value {
+    json!({
+        "cl_type": debug_string(cl_value.cl_type()),
+        "bytes": base16::encode_lower(cl_value.inner_bytes()),
+    })
+}
+
+fn cl_value(name: &str, cl_value: CLValue) -> TestVector {
+    TestVector::new(
+        name,
+        "CLValue".to_string(),
+        cl_value_json(&cl_value),
+        &cl_value,
+    )
+}
+
+fn cl_type(name: &str, cl_type: CLType) -> TestVector {
+    let mut bytes = vec![];
+    cl_type.append_bytes(&mut bytes);
+    TestVector::with_bytes(
+        name,
+        "CLType".to_string(),
+        Value::String(debug_string(&cl_type)),
+        &bytes,
+    )
+}
+
+fn key(name: &str, key: Key) -> TestVector {
+    TestVector::cl_typed_with_value(name, Value::String(key.to_formatted_string()), key)
+}
+
+/// Returns the test vectors for the core types, in a fixed order.
+pub fn test_vectors() -> Vec<TestVector> {
+    let mut map = BTreeMap::new();
+    let _ = map.insert("one".to_string(), 1u64);
+    let _ = map.insert("two".to_string(), 2u64);
+
+    let named_args = vec![
+        NamedArg::new(
+            "amount".to_string(),
+            CLValue::from_t(U512::from(1_000_000_000u64)).expect("should create CLValue"),
+        ),
+        NamedArg::new(
+            "id".to_string(),
+            CLValue::from_t(Option::<u64>::None).expect("should create CLValue"),
+        ),
+    ];
+    let runtime_args_json = Value::Array(
+        named_args
+            .iter()
+            .map(|named_arg| json!([named_arg.name(), cl_value_json(named_arg.cl_value())]))
+            .collect(),
+    );
+    let runtime_args = RuntimeArgs::from(named_args);
+
+    let account_hash = AccountHash::new([1; 32]);
+    let uref = URef::new([6; 32], AccessRights::READ);
+    let ed25519_key = PublicKey::Ed25519([7; 32]);
+    let secp256k1_key = PublicKey::Secp256k1(Secp256k1Bytes::from([8; 33]));
+    let protocol_version = ProtocolVersion::from_parts(1, 2, 3);
+
+    vec![
+        TestVector::cl_typed("bool_true", true),
+        TestVector::cl_typed("bool_false", false),
+        TestVector::cl_typed("i32_negative", -1i32),
+        TestVector::cl_typed("i32_max", i32::max_value()),
+        TestVector::cl_typed("i64_min", i64::min_value()),
+        TestVector::cl_typed("u8", 7u8),
+        TestVector::cl_typed("u32", 0x0102_0304u32),
+        TestVector::cl_typed("u64_max", u64::max_value()),
+        TestVector::cl_typed("u128_zero", U128::zero()),
+        TestVector::cl_typed("u256", U256::from(256)),
+        TestVector::cl_typed("u512", U512::from(1_000_000_000u64)),
+        TestVector::cl_typed("u512_max", U512::max_value()),
+        TestVector::cl_typed("unit", ()),
+        TestVector::cl_typed("string", "Hello, Casper!".to_string()),
+        TestVector::cl_typed("string_empty", String::new()),
+        TestVector::cl_typed("string_non_ascii", "ü".to_string()),
+        key("key_account", Key::Account(account_hash)),
+        key("key_hash", Key::Hash([2; 32])),
+        key(
+            "key_uref",
+            Key::URef(URef::new([3; 32], AccessRights::READ_ADD_WRITE)),
+        ),
+        key("key_transfer", Key::Transfer([4; 32])),
+        key("key_deploy_info", Key::DeployInfo([5; 32])),
+        TestVector::cl_typed_with_value("uref", Value::String(uref.to_formatted_string()), uref),
+        TestVector::cl_typed_with_value(
+            "account_hash",
+            Value::String(account_hash.to_formatted_string()),
+            account_hash,
+        ),
+        TestVector::cl_typed_with_value(
+            "public_key_ed25519",
+            Value::String(ed25519_key.to_hex()),
+            ed25519_key,
+        ),
+        TestVector::cl_typed_with_value(
+            "public_key_secp256k1",
+            Value::String(secp256k1_key.to_hex()),
+            secp256k1_key,
+        ),
+        TestVector::cl_typed("option_none", Option::<u64>::None),
+        TestVector::cl_typed("option_some", Some(42u64)),
+        TestVector::cl_typed("list_string", vec!["a".to_string(), "bc".to_string()]),
+        TestVector::cl_typed("list_u8", vec![1u8, 2, 3]),
+        TestVector::cl_typed("map", map),
+        TestVector::cl_typed("result_ok", Result::<String, u32>::Ok("ok".to_string())),
+        TestVector::cl_typed("result_err", Result::<String, u32>::Err(13)),
+        TestVector::cl_typed("tuple1", (true,)),
+        TestVector::cl_typed("tuple2", (1u8, "x".to_string())),
+        TestVector::cl_typed("tuple3", (false, -2i32, 3u64)),
+        TestVector::new(
+            "protocol_version",
+            "ProtocolVersion".to_string(),
+            Value::String(protocol_version.to_string()),
+            &protocol_version,
+        ),
+        cl_value(
+            "cl_value_u512",
+            CLValue::from_t(U512::from(1_000_000_000u64)).expect("should create CLValue"),
+        ),
+        cl_value(
+            "cl_value_option_string",
+            CLValue::from_t(Some("abc".to_string())).expect("should create CLValue"),
+        ),
+        cl_type(
+            "cl_type_map",
+            CLType::Map {
+                key: Box::new(CLType::String),
+                value: Box::new(CLType::List(Box::new(CLType::U8))),
+            },
+        ),
+        cl_type(
+            "cl_type_result",
+            CLType::Result {
+                ok: Box::new(CLType::Unit),
+                err: Box::new(CLType::String),
+            },
+        ),
+        TestVector::new(
+            "runtime_args",
+            "RuntimeArgs".to_string(),
+            runtime_args_json,
+            &runtime_args,
+        ),
+    ]
+}
@@ -0,0 +1,24 @@
+#![cfg(feature = "std")]
+
+use casper_types::test_vectors::{self, TestVector};
+
+const COMMITTED_TEST_VECTORS: &str = include_str!("../resources/test_vectors.json");
+
+#[test]
+fn should_match_committed_test_vectors() {
+    let committed: Vec<TestVector> =
+        serde_json::from_str(COMMITTED_TEST_VECTORS).expect("should parse committed test vectors");
+    let generated = test_vectors::test_vectors();
+    for (committed, generated) in committed.iter().zip(generated.iter()) {
+        assert_eq!(
+            committed, generated,
+            "test vector '{}' changed: if intentional, regenerate types/resources/test_vectors.json",
+            generated.name
+        );
+    }
+    assert_eq!(
+        committed.len(),
+        generated.len(),
+        "number of test vectors changed: regenerate types/resources/test_vectors.json"
+    );
+}